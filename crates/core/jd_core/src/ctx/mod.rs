@@ -6,24 +6,36 @@ pub use self::error::{Error, Result};
 
 // endregion: --- Modules
 
+/// Scope that grants every other scope.
+pub const SCOPE_ALL: &str = "*";
+
 #[derive(Clone, Debug)]
 pub struct Ctx {
   user_id: i64,
+  roles: Vec<String>,
+  scopes: Vec<String>,
 }
 
 // Constructor.
 impl Ctx {
   pub fn root_ctx() -> Self {
-    Ctx { user_id: 0 }
+    Ctx { user_id: 0, roles: Vec::new(), scopes: vec![SCOPE_ALL.to_string()] }
   }
 
   pub fn new(user_id: i64) -> Result<Self> {
     if user_id == 0 {
       Err(Error::CtxCannotNewRootCtx { message: user_id.to_string() })
     } else {
-      Ok(Self { user_id })
+      Ok(Self { user_id, roles: Vec::new(), scopes: Vec::new() })
     }
   }
+
+  /// Attach the resolved role set, for policy checks further down the stack.
+  pub fn with_roles(mut self, roles: Vec<String>, scopes: Vec<String>) -> Self {
+    self.roles = roles;
+    self.scopes = scopes;
+    self
+  }
 }
 
 // Property Accessors.
@@ -31,4 +43,59 @@ impl Ctx {
   pub fn user_id(&self) -> i64 {
     self.user_id
   }
+
+  pub fn roles(&self) -> &[String] {
+    &self.roles
+  }
+
+  pub fn scopes(&self) -> &[String] {
+    &self.scopes
+  }
+
+  pub fn has_role(&self, role: &str) -> bool {
+    self.roles.iter().any(|r| r == role)
+  }
+
+  pub fn has_scope(&self, required: &str) -> bool {
+    self.scopes.iter().any(|granted| scope_matches(granted, required))
+  }
+}
+
+/// Whether a granted scope covers a required one. Scopes are
+/// `resource:action`; `*` grants everything and `resource:*` every action on
+/// the resource.
+pub fn scope_matches(granted: &str, required: &str) -> bool {
+  if granted == SCOPE_ALL || granted == required {
+    return true;
+  }
+
+  match (granted.split_once(':'), required.split_once(':')) {
+    (Some((granted_resource, "*")), Some((required_resource, _))) => {
+      granted_resource == required_resource
+    }
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_scope_matching() {
+    assert!(scope_matches("*", "roles:admin"));
+    assert!(scope_matches("scores:read", "scores:read"));
+    assert!(scope_matches("moderation:*", "moderation:ban"));
+    assert!(!scope_matches("moderation:*", "roles:admin"));
+    assert!(!scope_matches("scores:read", "scores:write"));
+
+    let ctx = Ctx::new(7)
+      .unwrap()
+      .with_roles(vec!["member".to_string()], vec!["scores:read".to_string()]);
+    assert!(ctx.has_role("member"));
+    assert!(!ctx.has_role("admin"));
+    assert!(ctx.has_scope("scores:read"));
+    assert!(!ctx.has_scope("roles:admin"));
+    assert!(Ctx::root_ctx().has_scope("roles:admin"));
+  }
 }
@@ -60,6 +60,27 @@ pub enum Error {
     redis::RedisError,
   ),
 
+  #[error("Cache operation failed: {0}")]
+  Cache(
+    #[from]
+    #[serde_as(as = "DisplayFromStr")]
+    jd_cache::Error,
+  ),
+
+  #[error("Email error: {0}")]
+  Email(
+    #[from]
+    #[serde_as(as = "DisplayFromStr")]
+    jd_email::Error,
+  ),
+
+  #[error("Object store error: {0}")]
+  ObjectStore(
+    #[from]
+    #[serde_as(as = "DisplayFromStr")]
+    jd_object_store::Error,
+  ),
+
   #[error("Sui SDK error: {0}")]
   SuiSdk(
     #[from]
@@ -76,6 +97,9 @@ pub enum Error {
   #[error("Failed to create Sui client: {0}")]
   CantCreateSuiClient(String),
 
+  #[error("Sui network '{0}' is not supported by this deployment")]
+  UnsupportedSuiNetwork(String),
+
   #[error("RPC error: {0}")]
   RpcError(String),
 }
@@ -1,13 +1,23 @@
+use arc_swap::ArcSwap;
 use std::sync::Arc;
 use tracing::info;
 pub mod sui;
 
+use jd_cache::Cache;
+use jd_email::Mailer;
+use jd_feature_flags::FeatureFlags;
+use jd_messaging::EventBus;
+use jd_object_store::ObjectStore;
 use jd_storage::{dbx::Dbx, new_db_pool};
 use jd_utils::config::Config;
+use readiness::Readiness;
 use redis::Client as RedisClient;
+use shutdown::Shutdown;
 
 pub mod ctx;
 mod error;
+pub mod readiness;
+pub mod shutdown;
 pub use error::{Error, Result};
 pub mod base;
 pub use base::error as service_error;
@@ -40,16 +50,45 @@ impl ModelManager {
 pub struct AppState {
   pub mm: Arc<ModelManager>,
   pub redis: Arc<RedisClient>,
+  /// Namespaced, pooled access to the same Redis; prefer it to `redis`.
+  pub cache: Cache,
+  /// Subscribers to domain events, fed by the outbox relay.
+  pub events: EventBus,
+  /// Sends transactional email; queue emails with `jd_email::EmailQueue`
+  /// rather than sending them directly.
+  pub email: Mailer,
+  /// Object storage for artifacts too large for Postgres, when configured.
+  pub objects: Option<Arc<dyn ObjectStore>>,
+  /// Feature flags gating behavior that is still rolling out.
+  pub flags: FeatureFlags,
   pub sui_client: Arc<sui::sui_client::SuiClient>,
+  pub sui_networks: Arc<sui::network::SuiNetworks>,
+  /// The config as at startup.
   pub config: Arc<Config>,
+  /// The config with reloadable settings as last reloaded; see
+  /// `Config::with_reloadable`.
+  pub live_config: Arc<ArcSwap<Config>>,
+  pub readiness: Arc<Readiness>,
+  /// Triggered on SIGTERM or SIGINT; background loops stop when it is.
+  pub shutdown: Shutdown,
 }
 
 impl AppState {
   pub async fn new() -> Result<Self> {
     let mm = Arc::new(ModelManager::new().await?);
-    let config = Arc::new(Config::from_env()?);
+    let config = Arc::new(Config::load()?);
 
     let redis = Arc::new(RedisClient::open(config.redis.addr.clone())?);
+    let cache = Cache::from_config(&config.redis)?;
+    let email = Mailer::from_config(config.email.as_ref())?;
+    info!("Email provider: {}", email.provider());
+    let objects = jd_object_store::from_config(&config)?;
+    match &objects {
+      Some(objects) => info!("Object store backend: {}", objects.backend()),
+      None => info!("No object store is configured; large artifacts stay in Postgres"),
+    }
+
+    let flags = FeatureFlags::new(mm.dbx().db().clone(), cache.clone());
 
     info!("Initializing Sui client with environment: {}", config.sui.env);
     let sui_client = Arc::new(
@@ -57,8 +96,23 @@ impl AppState {
         .await
         .map_err(|ex| Error::CantCreateSuiClient(ex.to_string()))?,
     );
+    let sui_networks = Arc::new(sui::network::SuiNetworks::new(&config.sui, &sui_client)?);
 
-    Ok(AppState { mm, redis, sui_client, config })
+    Ok(AppState {
+      mm,
+      redis,
+      cache,
+      events: EventBus::new(),
+      email,
+      objects,
+      flags,
+      sui_client,
+      sui_networks,
+      live_config: Arc::new(ArcSwap::new(config.clone())),
+      config,
+      readiness: Arc::new(Readiness::default()),
+      shutdown: Shutdown::default(),
+    })
   }
 
   // Convenience methods
@@ -73,4 +127,17 @@ impl AppState {
   pub fn sui_client(&self) -> &sui::sui_client::SuiClient {
     &self.sui_client
   }
+
+  pub fn sui_networks(&self) -> &sui::network::SuiNetworks {
+    &self.sui_networks
+  }
+
+  /// The config with reloadable settings as last reloaded.
+  pub fn live_config(&self) -> Arc<Config> {
+    self.live_config.load_full()
+  }
+
+  pub fn readiness(&self) -> &Readiness {
+    &self.readiness
+  }
 }
@@ -0,0 +1,55 @@
+use serde::Serialize;
+use std::sync::{
+  RwLock,
+  atomic::{AtomicBool, Ordering},
+};
+
+/// Outcome of a single startup warm-up step.
+#[derive(Debug, Clone, Serialize)]
+pub struct WarmupCheck {
+  pub name: &'static str,
+  pub ok: bool,
+  pub required: bool,
+  pub duration_ms: u64,
+  pub error: Option<String>,
+}
+
+/// Process-wide readiness flag, flipped once startup warm-up completes and
+/// back when shutdown starts.
+///
+/// Liveness (`/health`) is independent of this; readiness only tells load
+/// balancers whether the instance should receive traffic yet.
+#[derive(Debug, Default)]
+pub struct Readiness {
+  ready: AtomicBool,
+  checks: RwLock<Vec<WarmupCheck>>,
+}
+
+impl Readiness {
+  pub fn is_ready(&self) -> bool {
+    self.ready.load(Ordering::Acquire)
+  }
+
+  pub fn mark_ready(&self) {
+    self.ready.store(true, Ordering::Release);
+  }
+
+  /// Stop taking traffic, so load balancers route around an instance that is
+  /// draining.
+  pub fn mark_not_ready(&self) {
+    self.ready.store(false, Ordering::Release);
+  }
+
+  /// Record (or replace) the latest result for a warm-up step.
+  pub fn record(&self, check: WarmupCheck) {
+    let mut checks = self.checks.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    match checks.iter_mut().find(|c| c.name == check.name) {
+      Some(existing) => *existing = check,
+      None => checks.push(check),
+    }
+  }
+
+  pub fn checks(&self) -> Vec<WarmupCheck> {
+    self.checks.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+  }
+}
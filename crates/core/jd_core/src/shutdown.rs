@@ -0,0 +1,114 @@
+use std::{
+  future::Future,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+use tokio::{
+  sync::watch,
+  task::JoinHandle,
+  time::{Instant, sleep, timeout_at},
+};
+
+/// Process-wide shutdown signal, and the background tasks to wait for once
+/// it is triggered.
+///
+/// Loops that watch it stop between iterations, so work under way finishes;
+/// tasks spawned with `spawn_until` are dropped at their next await instead.
+/// Either way, whatever is still running when `drain` gives up is aborted.
+#[derive(Clone)]
+pub struct Shutdown {
+  triggered: Arc<watch::Sender<bool>>,
+  tasks: Arc<Mutex<Vec<(&'static str, JoinHandle<()>)>>>,
+}
+
+impl Default for Shutdown {
+  fn default() -> Self {
+    Self { triggered: Arc::new(watch::Sender::new(false)), tasks: Arc::default() }
+  }
+}
+
+impl Shutdown {
+  pub fn trigger(&self) {
+    self.triggered.send_replace(true);
+  }
+
+  pub fn is_triggered(&self) -> bool {
+    *self.triggered.borrow()
+  }
+
+  /// Resolves once shutdown is triggered.
+  pub async fn triggered(&self) {
+    let mut triggered = self.triggered.subscribe();
+    // The sender lives as long as `self`, so this only returns once triggered
+    let _ = triggered.wait_for(|triggered| *triggered).await;
+  }
+
+  /// Sleep for `duration`, or until shutdown is triggered. False if it was.
+  pub async fn sleep(&self, duration: Duration) -> bool {
+    tokio::select! {
+      _ = sleep(duration) => true,
+      _ = self.triggered() => false,
+    }
+  }
+
+  /// Spawn a task that watches for shutdown itself; `drain` waits for it.
+  pub fn spawn<F>(&self, name: &'static str, task: F)
+  where
+    F: Future<Output = ()> + Send + 'static,
+  {
+    let handle = tokio::spawn(task);
+    self.tasks.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push((name, handle));
+  }
+
+  /// Spawn a task that runs until the process exits, dropping it at its next
+  /// await once shutdown is triggered. For queue workers whose claims lapse
+  /// and are retried, so dropped work is picked up again elsewhere.
+  pub fn spawn_until<F>(&self, name: &'static str, task: F)
+  where
+    F: Future<Output = ()> + Send + 'static,
+  {
+    let shutdown = self.clone();
+    self.spawn(name, async move {
+      tokio::select! {
+        _ = task => {}
+        _ = shutdown.triggered() => {}
+      }
+    });
+  }
+
+  /// Wait up to `timeout` for the spawned tasks to finish and abort the
+  /// rest. Returns the names of those aborted.
+  pub async fn drain(&self, timeout: Duration) -> Vec<&'static str> {
+    let deadline = Instant::now() + timeout;
+    let tasks =
+      std::mem::take(&mut *self.tasks.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    let mut aborted = Vec::new();
+    for (name, mut handle) in tasks {
+      if timeout_at(deadline, &mut handle).await.is_err() {
+        handle.abort();
+        aborted.push(name);
+      }
+    }
+    aborted
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn drain_waits_for_watching_tasks_and_aborts_the_rest() {
+    let shutdown = Shutdown::default();
+    let watcher = shutdown.clone();
+    shutdown.spawn("watching", async move {
+      while watcher.sleep(Duration::from_secs(60)).await {}
+    });
+    shutdown.spawn_until("until", std::future::pending());
+    shutdown.spawn("stuck", std::future::pending());
+
+    shutdown.trigger();
+    assert!(shutdown.is_triggered());
+    assert_eq!(shutdown.drain(Duration::from_millis(50)).await, vec!["stuck"]);
+  }
+}
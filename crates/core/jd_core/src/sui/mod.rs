@@ -1 +1,3 @@
+pub mod network;
+pub mod provider;
 pub mod sui_client;
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use jd_utils::config::SuiConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::sui::provider::{ProviderRole, RpcProviderPool};
+use crate::sui::sui_client::SuiClient;
+use crate::{Error, Result};
+
+/// A Sui network a deployment can serve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuiNetwork {
+  Mainnet,
+  Testnet,
+  Devnet,
+  Localnet,
+}
+
+impl SuiNetwork {
+  pub const ALL: [SuiNetwork; 4] =
+    [SuiNetwork::Mainnet, SuiNetwork::Testnet, SuiNetwork::Devnet, SuiNetwork::Localnet];
+
+  pub fn as_str(self) -> &'static str {
+    match self {
+      SuiNetwork::Mainnet => "mainnet",
+      SuiNetwork::Testnet => "testnet",
+      SuiNetwork::Devnet => "devnet",
+      SuiNetwork::Localnet => "localnet",
+    }
+  }
+
+  /// The network's public fullnode.
+  pub fn public_rpc_url(self) -> &'static str {
+    match self {
+      SuiNetwork::Mainnet => sui_sdk::SUI_MAINNET_URL,
+      SuiNetwork::Testnet => sui_sdk::SUI_TESTNET_URL,
+      SuiNetwork::Devnet => sui_sdk::SUI_DEVNET_URL,
+      SuiNetwork::Localnet => sui_sdk::SUI_LOCAL_NETWORK_URL,
+    }
+  }
+
+  /// The full nodes configured for the network, or its public fullnode.
+  pub fn rpc_urls(self, config: &SuiConfig) -> Vec<String> {
+    let urls = match self {
+      SuiNetwork::Mainnet => &config.mainnet_rpc_url,
+      SuiNetwork::Testnet => &config.testnet_rpc_url,
+      SuiNetwork::Devnet => &config.devnet_rpc_url,
+      SuiNetwork::Localnet => &config.localnet_rpc_url,
+    };
+    let urls = split_urls(urls);
+    if urls.is_empty() { vec![self.public_rpc_url().to_string()] } else { urls }
+  }
+
+  /// The endpoints configured to execute the network's transactions, if any.
+  pub fn write_rpc_urls(self, config: &SuiConfig) -> Vec<String> {
+    split_urls(match self {
+      SuiNetwork::Mainnet => &config.mainnet_write_rpc_url,
+      SuiNetwork::Testnet => &config.testnet_write_rpc_url,
+      SuiNetwork::Devnet => &config.devnet_write_rpc_url,
+      SuiNetwork::Localnet => &config.localnet_write_rpc_url,
+    })
+  }
+}
+
+fn split_urls(urls: &Option<String>) -> Vec<String> {
+  let mut split: Vec<String> = Vec::new();
+  for url in urls.iter().flat_map(|urls| urls.split(',')).map(str::trim) {
+    if !url.is_empty() && !split.iter().any(|known| known == url) {
+      split.push(url.to_string());
+    }
+  }
+  split
+}
+
+impl fmt::Display for SuiNetwork {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for SuiNetwork {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    match s.trim().to_lowercase().as_str() {
+      "mainnet" => Ok(SuiNetwork::Mainnet),
+      "testnet" => Ok(SuiNetwork::Testnet),
+      "devnet" => Ok(SuiNetwork::Devnet),
+      "localnet" | "local" => Ok(SuiNetwork::Localnet),
+      _ => Err(Error::UnsupportedSuiNetwork(s.to_string())),
+    }
+  }
+}
+
+/// The Sui networks a deployment serves, each with its own RPC endpoints.
+/// `SUI.ENV` is the default; `SUI.NETWORKS` lists any others. Endpoints are
+/// connected on first use.
+pub struct SuiNetworks {
+  default: SuiNetwork,
+  pools: HashMap<SuiNetwork, Arc<RpcProviderPool>>,
+}
+
+impl SuiNetworks {
+  /// The networks of `config`, with `default_client` already connected to
+  /// the default one.
+  pub fn new(config: &SuiConfig, default_client: &SuiClient) -> Result<Self> {
+    let default = SuiNetwork::from_str(&config.env)?;
+    let mut networks = vec![default];
+    for network in config.networks.iter().flat_map(|networks| networks.split(',')) {
+      if network.trim().is_empty() {
+        continue;
+      }
+      let network = SuiNetwork::from_str(network)?;
+      if !networks.contains(&network) {
+        networks.push(network);
+      }
+    }
+
+    let pools = networks
+      .into_iter()
+      .map(|network| {
+        let pool = RpcProviderPool::new(network, config);
+        let pool = if network == default { pool.with_connected(default_client) } else { pool };
+        (network, Arc::new(pool))
+      })
+      .collect();
+    Ok(Self { default, pools })
+  }
+
+  pub fn default_network(&self) -> SuiNetwork {
+    self.default
+  }
+
+  /// The served networks, the default first.
+  pub fn networks(&self) -> Vec<SuiNetwork> {
+    let mut networks: Vec<_> = self.pools.keys().copied().collect();
+    networks.sort_by_key(|network| (*network != self.default, network.as_str()));
+    networks
+  }
+
+  /// The network a request names, or the default one when it names none.
+  pub fn resolve(&self, requested: Option<&str>) -> Result<SuiNetwork> {
+    let Some(requested) = requested.filter(|requested| !requested.trim().is_empty()) else {
+      return Ok(self.default);
+    };
+    let network = SuiNetwork::from_str(requested)?;
+    if !self.pools.contains_key(&network) {
+      return Err(Error::UnsupportedSuiNetwork(network.to_string()));
+    }
+    Ok(network)
+  }
+
+  /// The RPC endpoints of `network`.
+  pub fn providers(&self, network: SuiNetwork) -> Result<Arc<RpcProviderPool>> {
+    self
+      .pools
+      .get(&network)
+      .cloned()
+      .ok_or_else(|| Error::UnsupportedSuiNetwork(network.to_string()))
+  }
+
+  /// A client of `network`'s healthiest full node.
+  pub async fn client(&self, network: SuiNetwork) -> Result<sui_sdk::SuiClient> {
+    self.providers(network)?.client(ProviderRole::Read).await
+  }
+}
@@ -0,0 +1,382 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use jd_utils::config::SuiConfig;
+use serde::Serialize;
+use tokio::sync::OnceCell;
+use tracing::warn;
+
+use crate::sui::network::SuiNetwork;
+use crate::sui::sui_client::SuiClient;
+use crate::{Error, Result};
+
+const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+const DEFAULT_CIRCUIT_OPEN: Duration = Duration::from_secs(30);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Weight of the newest sample in the latency and error rate averages.
+const SAMPLE_WEIGHT: f64 = 0.2;
+
+/// What a request does with its provider: reads go to full nodes, writes to
+/// the transaction endpoints when some are configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderRole {
+  Read,
+  Write,
+}
+
+/// When a provider's circuit opens and how long requests may take.
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitSettings {
+  pub failure_threshold: u32,
+  pub open_for: Duration,
+  pub request_timeout: Duration,
+}
+
+impl CircuitSettings {
+  pub fn from_config(config: &SuiConfig) -> Self {
+    Self {
+      failure_threshold: config.rpc_failure_threshold.unwrap_or(DEFAULT_FAILURE_THRESHOLD).max(1),
+      open_for: config
+        .rpc_circuit_open_secs
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_CIRCUIT_OPEN),
+      request_timeout: config
+        .rpc_request_timeout_secs
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_REQUEST_TIMEOUT),
+    }
+  }
+}
+
+/// Running latency and error averages of one provider, and its circuit.
+#[derive(Debug, Default, Clone)]
+pub struct ProviderHealth {
+  latency_ms: Option<f64>,
+  error_rate: f64,
+  consecutive_failures: u32,
+  requests: u64,
+  failures: u64,
+  open_until: Option<Instant>,
+}
+
+impl ProviderHealth {
+  pub fn record_success(&mut self, latency: Duration) {
+    let latency_ms = latency.as_secs_f64() * 1000.0;
+    self.latency_ms = Some(match self.latency_ms {
+      Some(average) => average + SAMPLE_WEIGHT * (latency_ms - average),
+      None => latency_ms,
+    });
+    self.error_rate -= SAMPLE_WEIGHT * self.error_rate;
+    self.consecutive_failures = 0;
+    self.requests += 1;
+    self.open_until = None;
+  }
+
+  /// Count a failure, opening the circuit once `settings.failure_threshold`
+  /// happen in a row. A failed trial after the circuit reopens it at once.
+  pub fn record_failure(&mut self, settings: &CircuitSettings, now: Instant) {
+    self.error_rate += SAMPLE_WEIGHT * (1.0 - self.error_rate);
+    self.consecutive_failures += 1;
+    self.requests += 1;
+    self.failures += 1;
+    if self.consecutive_failures >= settings.failure_threshold {
+      self.open_until = Some(now + settings.open_for);
+    }
+  }
+
+  /// Whether requests may go to the provider: its circuit is closed, or has
+  /// been open long enough to let a trial request through.
+  pub fn is_available(&self, now: Instant) -> bool {
+    self.open_until.is_none_or(|until| now >= until)
+  }
+
+  /// Lower is better. Untried providers score best so they get sampled.
+  pub fn score(&self) -> f64 {
+    self.latency_ms.unwrap_or(0.0) * (1.0 + 4.0 * self.error_rate)
+  }
+}
+
+/// A provider's health as reported to operators.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderStatus {
+  pub url: String,
+  pub writes: bool,
+  pub reads: bool,
+  pub connected: bool,
+  pub available: bool,
+  pub latency_ms: Option<f64>,
+  pub error_rate: f64,
+  pub consecutive_failures: u32,
+  pub requests: u64,
+  pub failures: u64,
+  pub score: f64,
+}
+
+/// One RPC endpoint, connected on first use.
+struct RpcProvider {
+  url: String,
+  reads: bool,
+  writes: bool,
+  client: OnceCell<sui_sdk::SuiClient>,
+  health: Mutex<ProviderHealth>,
+}
+
+impl RpcProvider {
+  fn new(url: String, reads: bool, writes: bool) -> Self {
+    Self { url, reads, writes, client: OnceCell::new(), health: Mutex::default() }
+  }
+
+  fn health(&self) -> ProviderHealth {
+    self.health.lock().map(|health| health.clone()).unwrap_or_default()
+  }
+
+  fn record(&self, outcome: std::result::Result<Duration, ()>, settings: &CircuitSettings) {
+    let Ok(mut health) = self.health.lock() else {
+      return;
+    };
+    match outcome {
+      Ok(latency) => health.record_success(latency),
+      Err(()) => health.record_failure(settings, Instant::now()),
+    }
+  }
+}
+
+/// The RPC endpoints of one network, ranked by health. Requests go to the
+/// best available endpoint of their role and fail over to the next one on
+/// an error or timeout, so a dead endpoint only costs its first few callers.
+pub struct RpcProviderPool {
+  network: SuiNetwork,
+  providers: Vec<RpcProvider>,
+  settings: CircuitSettings,
+}
+
+impl RpcProviderPool {
+  /// The endpoints `config` sets for `network`: its full nodes, or its public
+  /// fullnode when none are set, and its transaction endpoints, if any.
+  pub fn new(network: SuiNetwork, config: &SuiConfig) -> Self {
+    Self::from_urls(
+      network,
+      network.rpc_urls(config),
+      network.write_rpc_urls(config),
+      CircuitSettings::from_config(config),
+    )
+  }
+
+  /// Full nodes at `read_urls`, which also take writes unless `write_urls`
+  /// names endpoints of their own.
+  pub fn from_urls(
+    network: SuiNetwork,
+    read_urls: Vec<String>,
+    write_urls: Vec<String>,
+    settings: CircuitSettings,
+  ) -> Self {
+    let split = !write_urls.is_empty();
+    let mut providers: Vec<RpcProvider> =
+      read_urls.into_iter().map(|url| RpcProvider::new(url, true, !split)).collect();
+    for url in write_urls {
+      match providers.iter_mut().find(|provider| provider.url == url) {
+        Some(provider) => provider.writes = true,
+        None => providers.push(RpcProvider::new(url, false, true)),
+      }
+    }
+    Self { network, providers, settings }
+  }
+
+  /// Reuse `client`, already connected to one of the endpoints, instead of
+  /// connecting again.
+  pub fn with_connected(self, client: &SuiClient) -> Self {
+    if let Some(provider) = self.providers.iter().find(|provider| provider.url == client.url) {
+      let _ = provider.client.set(client.client.clone());
+    }
+    self
+  }
+
+  pub fn network(&self) -> SuiNetwork {
+    self.network
+  }
+
+  /// Providers of `role` that may take a request now, best first.
+  fn ranked(&self, role: ProviderRole) -> Vec<&RpcProvider> {
+    let now = Instant::now();
+    let mut ranked: Vec<(&RpcProvider, f64)> = self
+      .providers
+      .iter()
+      .filter(|provider| match role {
+        ProviderRole::Read => provider.reads,
+        ProviderRole::Write => provider.writes,
+      })
+      .map(|provider| (provider, provider.health()))
+      .filter(|(_, health)| health.is_available(now))
+      .map(|(provider, health)| (provider, health.score()))
+      .collect();
+    ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+    ranked.into_iter().map(|(provider, _)| provider).collect()
+  }
+
+  async fn connect<'a>(&self, provider: &'a RpcProvider) -> Result<&'a sui_sdk::SuiClient> {
+    provider
+      .client
+      .get_or_try_init(|| async {
+        let client = tokio::time::timeout(
+          self.settings.request_timeout,
+          SuiClient::connect(&provider.url, self.settings.request_timeout),
+        )
+        .await
+        .map_err(|_| Error::RpcError(format!("{} timed out connecting", provider.url)))
+        .and_then(|client| client);
+        if client.is_err() {
+          provider.record(Err(()), &self.settings);
+        }
+        client.map(|client| client.client)
+      })
+      .await
+  }
+
+  /// A client of the best available provider of `role`, for callers that
+  /// make several requests in a row. Failures are not recorded against it;
+  /// prefer [`Self::call`] where a request can be retried.
+  pub async fn client(&self, role: ProviderRole) -> Result<sui_sdk::SuiClient> {
+    let mut last_error = None;
+    for provider in self.ranked(role) {
+      match self.connect(provider).await {
+        Ok(client) => return Ok(client.clone()),
+        Err(e) => last_error = Some(e),
+      }
+    }
+    Err(self.unavailable(role, last_error))
+  }
+
+  /// Run `request` against the best available provider of `role`, failing
+  /// over to the next one on an error or timeout. Requests must be safe to
+  /// repeat; executing the same signed transaction twice is.
+  pub async fn call<T, F, Fut>(&self, role: ProviderRole, request: F) -> Result<T>
+  where
+    F: Fn(sui_sdk::SuiClient) -> Fut,
+    Fut: Future<Output = sui_sdk::error::SuiRpcResult<T>>,
+  {
+    let mut last_error = None;
+    for provider in self.ranked(role) {
+      let client = match self.connect(provider).await {
+        Ok(client) => client.clone(),
+        Err(e) => {
+          last_error = Some(e);
+          continue;
+        }
+      };
+      let started = Instant::now();
+      match tokio::time::timeout(self.settings.request_timeout, request(client)).await {
+        Ok(Ok(value)) => {
+          provider.record(Ok(started.elapsed()), &self.settings);
+          return Ok(value);
+        }
+        Ok(Err(e)) => {
+          warn!(network = %self.network, url = %provider.url, error = %e, "Sui RPC failed");
+          provider.record(Err(()), &self.settings);
+          last_error = Some(Error::SuiSdk(e));
+        }
+        Err(_) => {
+          warn!(network = %self.network, url = %provider.url, "Sui RPC timed out");
+          provider.record(Err(()), &self.settings);
+          last_error = Some(Error::RpcError(format!("{} timed out", provider.url)));
+        }
+      }
+    }
+    Err(self.unavailable(role, last_error))
+  }
+
+  /// Send a cheap request to every provider whose circuit allows one, so
+  /// scores stay fresh and dead providers come back once they recover.
+  pub async fn probe(&self) -> Vec<ProviderStatus> {
+    let now = Instant::now();
+    for provider in &self.providers {
+      if !provider.health().is_available(now) {
+        continue;
+      }
+      let Ok(client) = self.connect(provider).await else {
+        continue;
+      };
+      let started = Instant::now();
+      let probe = tokio::time::timeout(
+        self.settings.request_timeout,
+        client.read_api().get_latest_checkpoint_sequence_number(),
+      )
+      .await;
+      match probe {
+        Ok(Ok(_)) => provider.record(Ok(started.elapsed()), &self.settings),
+        _ => provider.record(Err(()), &self.settings),
+      }
+    }
+    self.statuses()
+  }
+
+  pub fn statuses(&self) -> Vec<ProviderStatus> {
+    let now = Instant::now();
+    self
+      .providers
+      .iter()
+      .map(|provider| {
+        let health = provider.health();
+        ProviderStatus {
+          url: provider.url.clone(),
+          reads: provider.reads,
+          writes: provider.writes,
+          connected: provider.client.initialized(),
+          available: health.is_available(now),
+          latency_ms: health.latency_ms,
+          error_rate: health.error_rate,
+          consecutive_failures: health.consecutive_failures,
+          requests: health.requests,
+          failures: health.failures,
+          score: health.score(),
+        }
+      })
+      .collect()
+  }
+
+  fn unavailable(&self, role: ProviderRole, last_error: Option<Error>) -> Error {
+    let reason = last_error.map(|e| e.to_string()).unwrap_or_else(|| "all circuits open".into());
+    Error::RpcError(format!("No {:?} RPC provider of {} available: {}", role, self.network, reason))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn settings() -> CircuitSettings {
+    CircuitSettings {
+      failure_threshold: 2,
+      open_for: Duration::from_secs(30),
+      request_timeout: Duration::from_secs(5),
+    }
+  }
+
+  #[test]
+  fn circuit_opens_after_consecutive_failures_and_allows_a_trial_later() {
+    let now = Instant::now();
+    let mut health = ProviderHealth::default();
+    health.record_failure(&settings(), now);
+    assert!(health.is_available(now));
+    health.record_failure(&settings(), now);
+    assert!(!health.is_available(now));
+    assert!(health.is_available(now + Duration::from_secs(30)));
+
+    health.record_success(Duration::from_millis(40));
+    assert!(health.is_available(now));
+    assert_eq!(health.consecutive_failures, 0);
+  }
+
+  #[test]
+  fn errors_rank_a_fast_provider_below_a_slower_healthy_one() {
+    let mut flaky = ProviderHealth::default();
+    let mut steady = ProviderHealth::default();
+    flaky.record_success(Duration::from_millis(50));
+    steady.record_success(Duration::from_millis(120));
+    for _ in 0..3 {
+      flaky.record_failure(&settings(), Instant::now());
+    }
+    flaky.record_success(Duration::from_millis(50));
+    assert!(steady.score() < flaky.score());
+  }
+}
@@ -1,25 +1,38 @@
-use crate::error::Error;
 use crate::Result;
+use crate::sui::network::SuiNetwork;
+use crate::sui::provider::CircuitSettings;
 use jd_utils::config::SuiConfig;
+use std::str::FromStr;
+use std::time::Duration;
 use sui_sdk::SuiClientBuilder;
 
 pub struct SuiClient {
   pub client: sui_sdk::SuiClient,
+  pub url: String,
 }
 
 impl SuiClient {
+  /// A client of the default network's first full node that answers.
   pub async fn new(config: &SuiConfig) -> Result<Self> {
-    let client = match config.env.to_lowercase().as_str() {
-      "mainnet" => SuiClientBuilder::default().build_mainnet().await?,
-      "testnet" => SuiClientBuilder::default().build_testnet().await?,
-      "devnet" => SuiClientBuilder::default().build_devnet().await?,
-      "local" => SuiClientBuilder::default().build_localnet().await?,
-      _ => {
-        return Err(Error::CantCreateSuiClient(format!("Invalid Sui environment: {}", config.env)));
+    let network = SuiNetwork::from_str(&config.env)?;
+    let request_timeout = CircuitSettings::from_config(config).request_timeout;
+    let mut last_error = None;
+    for url in network.rpc_urls(config) {
+      match Self::connect(&url, request_timeout).await {
+        Ok(client) => return Ok(client),
+        Err(e) => {
+          tracing::warn!("Sui RPC {} unreachable: {}", url, e);
+          last_error = Some(e);
+        }
       }
-    };
+    }
+    Err(last_error.unwrap_or_else(|| crate::Error::UnsupportedSuiNetwork(network.to_string())))
+  }
 
-    Ok(Self { client })
+  /// A client of the RPC endpoint at `url`.
+  pub async fn connect(url: &str, request_timeout: Duration) -> Result<Self> {
+    let client = SuiClientBuilder::default().request_timeout(request_timeout).build(url).await?;
+    Ok(Self { client, url: url.to_string() })
   }
 
   pub async fn get_api_version(&self) -> Result<String> {
@@ -0,0 +1,23 @@
+use axum::{
+  Router,
+  routing::{get, post},
+};
+use jd_core::AppState;
+
+use auth_service::application::handlers::AccountHandler;
+
+/// The caller's account: linked wallets, GitHub identities, aggregated
+/// reputation and email notification preferences. Mounted behind bearer auth
+/// in `v1_routes`.
+pub fn account_router() -> Router<AppState> {
+  Router::new()
+    .route("/me", get(AccountHandler::get_account))
+    .route("/me/wallets", post(AccountHandler::link_wallet))
+    .route("/me/wallets/unlink", post(AccountHandler::unlink_wallet))
+    .route("/me/merge", post(AccountHandler::merge_account))
+    .route(
+      "/me/notification-preferences",
+      get(AccountHandler::get_notification_preferences)
+        .put(AccountHandler::update_notification_preferences),
+    )
+}
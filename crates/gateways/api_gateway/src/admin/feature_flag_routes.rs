@@ -0,0 +1,67 @@
+use auth_service::domain::Claims;
+use axum::{
+  extract::{Path, State},
+  http::StatusCode,
+  response::Json,
+  routing::get,
+  Extension, Router,
+};
+use jd_core::AppState;
+use jd_feature_flags::{FeatureFlag, FlagSettings};
+use tracing::warn;
+
+use crate::{Result, error::Error};
+
+/// Flags change behavior for every caller, so `v1_routes` mounts this
+/// behind bearer auth and the `features:admin` scope policy.
+pub fn feature_flag_admin_router() -> Router<AppState> {
+  Router::new()
+    .route("/feature-flags", get(list_flags))
+    .route("/feature-flags/{key}", get(get_flag).put(save_flag).delete(delete_flag))
+}
+
+/// GET /admin/feature-flags
+async fn list_flags(State(app_state): State<AppState>) -> Result<Json<Vec<FeatureFlag>>> {
+  Ok(Json(app_state.flags.list().await?))
+}
+
+/// GET /admin/feature-flags/{key}
+async fn get_flag(
+  State(app_state): State<AppState>,
+  Path(key): Path<String>,
+) -> Result<Json<FeatureFlag>> {
+  let flag = app_state.flags.get(&key).await?;
+  flag.map(Json).ok_or_else(|| Error::route_not_found(format!("feature-flags/{key}"), "GET"))
+}
+
+/// PUT /admin/feature-flags/{key}
+/// Create the flag or replace its settings.
+async fn save_flag(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(key): Path<String>,
+  Json(settings): Json<FlagSettings>,
+) -> Result<Json<FeatureFlag>> {
+  let flag = app_state.flags.save(&key, &settings, &caller.address).await?;
+  warn!(
+    changed_by = %caller.address,
+    flag = %flag.key,
+    enabled = flag.enabled,
+    rollout_percentage = flag.rollout_percentage,
+    "Feature flag saved"
+  );
+  Ok(Json(flag))
+}
+
+/// DELETE /admin/feature-flags/{key}
+async fn delete_flag(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(key): Path<String>,
+) -> Result<StatusCode> {
+  if !app_state.flags.delete(&key).await? {
+    return Err(Error::route_not_found(format!("feature-flags/{key}"), "DELETE"));
+  }
+  warn!(changed_by = %caller.address, flag = %key, "Feature flag deleted");
+  Ok(StatusCode::NO_CONTENT)
+}
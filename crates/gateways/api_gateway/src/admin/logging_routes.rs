@@ -0,0 +1,78 @@
+use auth_service::domain::Claims;
+use axum::{Extension, Router, response::Json, routing::get};
+use jd_core::AppState;
+use jd_tracing::{LogFilterError, LogFilterHandle, LogFilterStatus};
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::{Result, error::Error};
+
+/// Overrides revert after ten minutes unless asked otherwise, and after a
+/// day at most, so a forgotten `trace` does not flood production logs.
+const DEFAULT_TTL_SECS: u64 = 10 * 60;
+const MAX_TTL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Deserialize)]
+pub struct LogFilterRequest {
+  /// `EnvFilter` directives layered over the baseline, e.g.
+  /// `sui_service=trace,sqlx=debug`.
+  pub directives: String,
+  pub ttl_secs: Option<u64>,
+}
+
+fn log_filter() -> Result<LogFilterHandle> {
+  jd_tracing::log_filter().ok_or_else(|| Error::service_unavailable("log filter"))
+}
+
+fn filter_error(e: LogFilterError) -> Error {
+  match e {
+    LogFilterError::InvalidDirectives(_) => Error::invalid_request(e.to_string()),
+    LogFilterError::Reload(_) => Error::service_unavailable("log filter"),
+  }
+}
+
+/// Changing log filters affects the whole process, so `v1_routes` mounts
+/// this behind bearer auth and the `logging:admin` scope policy.
+pub fn logging_admin_router() -> Router<AppState> {
+  Router::new()
+    .route("/logging", get(get_log_filter).put(set_log_filter).delete(reset_log_filter))
+}
+
+/// GET /admin/logging
+async fn get_log_filter() -> Result<Json<LogFilterStatus>> {
+  Ok(Json(log_filter()?.status()))
+}
+
+/// PUT /admin/logging
+/// Layer directives over the baseline filter until `ttl_secs` pass.
+async fn set_log_filter(
+  Extension(caller): Extension<Claims>,
+  Json(request): Json<LogFilterRequest>,
+) -> Result<Json<LogFilterStatus>> {
+  let ttl_secs = request.ttl_secs.unwrap_or(DEFAULT_TTL_SECS);
+  if !(1..=MAX_TTL_SECS).contains(&ttl_secs) {
+    return Err(Error::invalid_request(format!(
+      "ttl_secs must be between 1 and {}",
+      MAX_TTL_SECS
+    )));
+  }
+  let status = log_filter()?
+    .apply(&request.directives, Some(Duration::from_secs(ttl_secs)))
+    .map_err(filter_error)?;
+  warn!(
+    changed_by = %caller.address,
+    directives = %status.directives,
+    ttl_secs,
+    "Log filter override applied"
+  );
+  Ok(Json(status))
+}
+
+/// DELETE /admin/logging
+/// Restore the baseline filter now.
+async fn reset_log_filter(Extension(caller): Extension<Claims>) -> Result<Json<LogFilterStatus>> {
+  let status = log_filter()?.reset().map_err(filter_error)?;
+  warn!(changed_by = %caller.address, "Log filter override removed");
+  Ok(Json(status))
+}
@@ -0,0 +1,5 @@
+mod feature_flag_routes;
+mod logging_routes;
+
+pub use feature_flag_routes::feature_flag_admin_router;
+pub use logging_routes::logging_admin_router;
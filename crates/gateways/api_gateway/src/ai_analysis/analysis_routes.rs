@@ -39,6 +39,7 @@ pub mod integration {
             analysis_repository_impl::AnalysisRepositoryImpl,
             github_integration::GitHubIntegrationService,
             llm_client::LLMClient,
+            llm_response_cache::LlmResponseCache,
         },
     };
     use jd_core::AppState;
@@ -46,8 +47,8 @@ pub mod integration {
 
     pub struct AiAnalysisServiceConfig {
         pub app_state: AppState,
-        pub openai_api_key: Option<String>,
-        pub anthropic_api_key: Option<String>,
+        /// The provider chain, usually `LLMClient::from_env()`.
+        pub llm_client: Option<LLMClient>,
         pub enable_llm_analysis: bool,
     }
 
@@ -55,22 +56,17 @@ pub mod integration {
         Arc<super::AnalysisHandler>,
         Arc<GitHubIntegrationService>,
     ) {
+        // Setup LLM provider if configured, answering unchanged files from the cache
+        let response_cache = LlmResponseCache::new(config.app_state.mm().dbx().db().clone());
+        let llm_provider = config
+            .llm_client
+            .filter(|_| config.enable_llm_analysis)
+            .map(|client| client.with_response_cache(response_cache))
+            .map(|client| Arc::new(client) as Arc<dyn ai_analysis_service::domain::llm_provider_trait::LLMProvider>);
+
         // Setup repository
         let analysis_repository = Arc::new(AnalysisRepositoryImpl::new(config.app_state));
 
-        // Setup LLM provider if configured
-        let llm_provider = if config.enable_llm_analysis {
-            if let Some(api_key) = config.openai_api_key {
-                Some(Arc::new(LLMClient::new_openai(api_key)) as Arc<dyn ai_analysis_service::domain::llm_provider_trait::LLMProvider>)
-            } else if let Some(api_key) = config.anthropic_api_key {
-                Some(Arc::new(LLMClient::new_anthropic(api_key)) as Arc<dyn ai_analysis_service::domain::llm_provider_trait::LLMProvider>)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
         // Setup use cases
         let analysis_use_cases = Arc::new(AnalysisUseCases::new(
             analysis_repository,
@@ -0,0 +1,42 @@
+use analytics_service::{
+  application::use_cases::AnomalyUseCases,
+  domain::{AlertStatus, AnalyticsAlert},
+  infrastructure::AnomalyRepositoryImpl,
+};
+use axum::{
+  Router,
+  extract::{Query, State},
+  response::Json,
+  routing::get,
+};
+use jd_core::AppState;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::Result;
+
+const DEFAULT_ALERTS_PAGE: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct AlertsQuery {
+  pub status: Option<AlertStatus>,
+  pub limit: Option<i64>,
+}
+
+/// Alerts name repositories whose findings suddenly spiked, so `v1_routes`
+/// mounts this behind bearer auth and the `analytics:alerts` scope policy.
+pub fn analytics_alert_router() -> Router<AppState> {
+  Router::new().route("/alerts", get(list_alerts))
+}
+
+/// GET /analytics/alerts?status=&limit=
+async fn list_alerts(
+  State(app_state): State<AppState>,
+  Query(query): Query<AlertsQuery>,
+) -> Result<Json<Vec<AnalyticsAlert>>> {
+  let repository = AnomalyRepositoryImpl::new(app_state.mm().dbx().db().clone());
+  let alerts = AnomalyUseCases::new(Arc::new(repository))
+    .alerts(query.status, query.limit.unwrap_or(DEFAULT_ALERTS_PAGE))
+    .await?;
+  Ok(Json(alerts))
+}
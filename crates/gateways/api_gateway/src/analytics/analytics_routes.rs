@@ -1,11 +1,27 @@
+use ai_analysis_service::domain::ai_budget::{
+    month_start, AiUsageFilter, ApprovalStatus, BudgetPolicy, BudgetScope, BudgetStatus,
+};
+use ai_analysis_service::domain::analysis_repository_trait::AnalysisRepository;
+use ai_analysis_service::infrastructure::analysis_repository_impl::AnalysisRepositoryImpl;
+use ai_analysis_service::Error as AnalysisError;
+use analytics_service::application::use_cases::{RollupUseCases, ScorecardUseCases};
+use analytics_service::domain::{scorecard_badge_svg, RollupGranularity};
+use analytics_service::infrastructure::{RollupRepositoryImpl, ScorecardRepositoryImpl};
+use analytics_service::models::ScorecardResponse;
+use auth_service::domain::Claims;
 use axum::{
-    extract::{Path, Query, State},
-    response::Json,
-    routing::{get, post},
+    extract::{Extension, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{get, post, put},
     Router,
 };
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 use jd_core::AppState;
+use serde::Deserialize;
 use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::error;
 use uuid::Uuid;
 
 pub fn analytics_router() -> Router<AppState> {
@@ -18,6 +34,25 @@ pub fn analytics_router() -> Router<AppState> {
         .route("/trends/activity", get(get_activity_trends))
         // Vulnerability trends
         .route("/trends/vulnerabilities", get(get_vulnerability_trends))
+        // Security posture
+        .route("/repositories/{id}/scorecard", get(get_repository_scorecard))
+        .route("/repositories/{id}/scorecard/badge.svg", get(get_repository_scorecard_badge))
+        // Triage
+        .route("/suppressions", get(get_suppression_stats))
+        // LLM usage
+        .route("/usage/ai", get(get_ai_usage))
+        // Analysis pipeline, LLM and patch generation metrics of this instance
+        .route("/system", get(get_system_metrics))
+}
+
+/// AI budget routes; changes and approvals record who made them. `v1_routes`
+/// mounts these behind bearer auth and the `analytics:budgets` scope policy.
+pub fn ai_budget_router() -> Router<AppState> {
+    Router::new()
+        .route("/usage/ai/budgets", put(put_ai_budget).delete(delete_ai_budget))
+        .route("/usage/ai/approvals", get(list_budget_approvals))
+        .route("/usage/ai/approvals/{id}/approve", post(approve_budget_approval))
+        .route("/usage/ai/approvals/{id}/deny", post(deny_budget_approval))
 }
 
 // Handler functions with placeholder implementations
@@ -63,53 +98,373 @@ async fn get_top_repositories(
     }))
 }
 
-async fn get_activity_trends(
-    State(_app_state): State<AppState>,
-    Query(params): Query<Value>,
-) -> Json<Value> {
-    Json(json!({
-        "period": params.get("period").and_then(|v| v.as_str()).unwrap_or("daily"),
-        "data": [
-            {
-                "timestamp": "2024-01-08T00:00:00Z",
-                "repositories_analyzed": 45,
-                "vulnerabilities_found": 123,
-                "patches_generated": 34,
-                "developers_active": 67
-            },
-            {
-                "timestamp": "2024-01-09T00:00:00Z",
-                "repositories_analyzed": 52,
-                "vulnerabilities_found": 145,
-                "patches_generated": 41,
-                "developers_active": 73
+#[derive(Debug, Deserialize)]
+struct TrendsQuery {
+    /// `daily` or `weekly`.
+    period: Option<String>,
+    /// First day, inclusive.
+    from: Option<NaiveDate>,
+    /// Last day, inclusive; today by default.
+    to: Option<NaiveDate>,
+    repository_id: Option<Uuid>,
+}
+
+impl TrendsQuery {
+    /// The granularity and days asked for, defaulting to `periods` periods
+    /// of `default` up to today.
+    fn range(
+        &self,
+        default: RollupGranularity,
+        periods: i64,
+    ) -> crate::Result<(RollupGranularity, NaiveDate, NaiveDate)> {
+        let granularity = match self.period.as_deref() {
+            None => default,
+            Some("daily") => RollupGranularity::Day,
+            Some("weekly") => RollupGranularity::Week,
+            Some(other) => {
+                return Err(crate::error::Error::InvalidRequestFormat {
+                    message: format!("period must be daily or weekly, got {}", other),
+                })
             }
-        ]
-    }))
+        };
+        let to = self.to.unwrap_or_else(|| Utc::now().date_naive());
+        let days = match granularity {
+            RollupGranularity::Day => periods,
+            RollupGranularity::Week => periods * 7,
+        };
+        let from = self.from.unwrap_or(to - Duration::days(days - 1));
+        Ok((granularity, from, to))
+    }
 }
 
+fn period_label(granularity: RollupGranularity) -> &'static str {
+    match granularity {
+        RollupGranularity::Day => "daily",
+        RollupGranularity::Week => "weekly",
+    }
+}
+
+fn rollups(app_state: AppState) -> RollupUseCases {
+    RollupUseCases::new(Arc::new(RollupRepositoryImpl::new(app_state.mm().dbx().db().clone())))
+}
+
+/// GET /analytics/trends/activity
+/// Analyses run, vulnerabilities found, patches merged and scores issued per
+/// day (last 30 by default) or week, from the rollups when they cover the
+/// range.
+async fn get_activity_trends(
+    State(app_state): State<AppState>,
+    Query(query): Query<TrendsQuery>,
+) -> crate::Result<Json<Value>> {
+    let (granularity, from, to) = query.range(RollupGranularity::Day, 30)?;
+    let series = rollups(app_state).series(granularity, from, to, query.repository_id).await?;
+    let data: Vec<Value> = series
+        .points
+        .iter()
+        .map(|point| {
+            json!({
+                "period_start": point.period_start,
+                "analyses_run": point.analyses_run,
+                "vulnerabilities_found": point.vulnerabilities_found,
+                "patches_merged": point.patches_merged,
+                "scores_issued": point.scores_issued,
+            })
+        })
+        .collect();
+    Ok(Json(json!({
+        "period": period_label(granularity),
+        "repository_id": series.repository_id,
+        "source": series.source,
+        "data": data,
+    })))
+}
+
+/// GET /analytics/trends/vulnerabilities
+/// Vulnerabilities found by severity per week (last 12 by default) or day.
 async fn get_vulnerability_trends(
-    State(_app_state): State<AppState>,
-    Query(params): Query<Value>,
-) -> Json<Value> {
+    State(app_state): State<AppState>,
+    Query(query): Query<TrendsQuery>,
+) -> crate::Result<Json<Value>> {
+    let (granularity, from, to) = query.range(RollupGranularity::Week, 12)?;
+    let series = rollups(app_state).series(granularity, from, to, query.repository_id).await?;
+    let data: Vec<Value> = series
+        .points
+        .iter()
+        .map(|point| {
+            json!({
+                "period_start": point.period_start,
+                "total": point.vulnerabilities_found,
+                "by_severity": point.vulnerabilities_by_severity,
+            })
+        })
+        .collect();
+    Ok(Json(json!({
+        "period": period_label(granularity),
+        "repository_id": series.repository_id,
+        "source": series.source,
+        "data": data,
+    })))
+}
+
+fn scorecards(app_state: AppState) -> ScorecardUseCases {
+    let repository = ScorecardRepositoryImpl::new(app_state.mm().dbx().db().clone());
+    ScorecardUseCases::new(Arc::new(repository))
+}
+
+/// GET /analytics/repositories/{id}/scorecard
+/// Graded security posture of a public repository, with its trend over the
+/// last week and the scores of recent days.
+async fn get_repository_scorecard(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> crate::Result<Json<ScorecardResponse>> {
+    Ok(Json(scorecards(app_state).scorecard(id).await?))
+}
+
+/// GET /analytics/repositories/{id}/scorecard/badge.svg
+/// The grade and trend arrow as an SVG badge for READMEs.
+async fn get_repository_scorecard_badge(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> crate::Result<Response> {
+    let (scorecard, trend) = scorecards(app_state).badge(id).await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml"),
+            (header::CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        scorecard_badge_svg(scorecard.grade, trend),
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize)]
+struct SuppressionStatsQuery {
+    repository_id: Option<Uuid>,
+}
+
+/// How many findings teams have suppressed, for one repository or all.
+async fn get_suppression_stats(
+    State(app_state): State<AppState>,
+    Query(query): Query<SuppressionStatsQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    let stats = AnalysisRepositoryImpl::new(app_state)
+        .get_suppression_statistics(query.repository_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to load suppression statistics: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(json!({
+        "repository_id": query.repository_id,
+        "suppressions": stats
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+struct AiUsageQuery {
+    repository_id: Option<Uuid>,
+    organization_id: Option<Uuid>,
+    /// `YYYY-MM`; the current month by default.
+    month: Option<String>,
+}
+
+/// GET /analytics/usage/ai
+/// Counters and latency histograms of the analysis queue, LLM calls and
+/// patch generation since this instance started.
+async fn get_system_metrics() -> Json<Value> {
     Json(json!({
-        "period": params.get("period").and_then(|v| v.as_str()).unwrap_or("weekly"),
-        "data": [
-            {
-                "week_start": "2024-01-01T00:00:00Z",
-                "by_severity": {
-                    "critical": 12,
-                    "high": 45,
-                    "medium": 89,
-                    "low": 234
-                },
-                "by_type": {
-                    "sql_injection": 23,
-                    "xss": 45,
-                    "buffer_overflow": 12,
-                    "other": 300
-                }
-            }
-        ]
+        "generated_at": Utc::now(),
+        "metrics": jd_utils::metrics::summary(),
     }))
-}
\ No newline at end of file
+}
+
+/// LLM requests, tokens and estimated cost over a month, for one repository,
+/// an organization's repositories or all of them, with the budgets covering
+/// them and what is left of each this month.
+async fn get_ai_usage(
+    State(app_state): State<AppState>,
+    Query(query): Query<AiUsageQuery>,
+) -> crate::Result<Json<Value>> {
+    let (from, to) = month_bounds(query.month.as_deref())?;
+    let repository = AnalysisRepositoryImpl::new(app_state);
+    let filter = AiUsageFilter {
+        repository_id: query.repository_id,
+        organization_id: query.organization_id,
+        from,
+        to,
+    };
+    let summary = repository.summarize_ai_usage(&filter).await?;
+
+    // Budgets reset monthly, so they are always reported for this month
+    let this_month = month_start(Utc::now());
+    let budgets = match (query.repository_id, query.organization_id) {
+        (Some(repository_id), _) => {
+            repository.get_budget_statuses(BudgetScope::Repository, repository_id, this_month).await?
+        }
+        (None, Some(organization_id)) => {
+            repository.get_budget_statuses(BudgetScope::Organization, organization_id, this_month).await?
+        }
+        (None, None) => Vec::new(),
+    };
+
+    Ok(Json(json!({
+        "repository_id": query.repository_id,
+        "organization_id": query.organization_id,
+        "period": { "from": from, "to": to },
+        "usage": summary,
+        "budgets": budgets.iter().map(budget_response).collect::<Vec<_>>(),
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+struct AiBudgetBody {
+    scope: BudgetScope,
+    scope_id: Uuid,
+    monthly_limit_usd: f64,
+    policy: BudgetPolicy,
+}
+
+/// PUT /analytics/usage/ai/budgets
+/// Set the monthly LLM budget of a repository or organization and what
+/// happens to LLM analyses once it is spent.
+async fn put_ai_budget(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Json(body): Json<AiBudgetBody>,
+) -> crate::Result<Json<Value>> {
+    if !body.monthly_limit_usd.is_finite() || body.monthly_limit_usd < 0.0 {
+        return Err(AnalysisError::InvalidAiBudget {
+            message: "monthly_limit_usd must be a non-negative amount".to_string(),
+        }
+        .into());
+    }
+
+    let repository = AnalysisRepositoryImpl::new(app_state);
+    repository
+        .save_ai_budget(body.scope, body.scope_id, body.monthly_limit_usd, body.policy, &caller.address)
+        .await?
+        .ok_or(AnalysisError::AiBudgetNotFound {
+            scope: body.scope.as_str().to_string(),
+            scope_id: body.scope_id,
+        })?;
+    let statuses = repository
+        .get_budget_statuses(body.scope, body.scope_id, month_start(Utc::now()))
+        .await?;
+    let status = statuses
+        .iter()
+        .find(|status| status.budget.scope == body.scope && status.budget.scope_id == body.scope_id);
+
+    Ok(Json(status.map(budget_response).unwrap_or(Value::Null)))
+}
+
+#[derive(Debug, Deserialize)]
+struct AiBudgetTarget {
+    scope: BudgetScope,
+    scope_id: Uuid,
+}
+
+/// DELETE /analytics/usage/ai/budgets?scope=&scope_id=
+/// Remove a budget; its repositories' LLM analyses are no longer capped by it.
+async fn delete_ai_budget(
+    State(app_state): State<AppState>,
+    Query(target): Query<AiBudgetTarget>,
+) -> crate::Result<Json<Value>> {
+    let deleted = AnalysisRepositoryImpl::new(app_state)
+        .delete_ai_budget(target.scope, target.scope_id)
+        .await?;
+
+    Ok(Json(json!({
+        "scope": target.scope,
+        "scope_id": target.scope_id,
+        "deleted": deleted,
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+struct BudgetApprovalQuery {
+    repository_id: Option<Uuid>,
+    status: Option<ApprovalStatus>,
+}
+
+/// GET /analytics/usage/ai/approvals
+/// Requests to run LLM analyses over a spent budget, newest first.
+async fn list_budget_approvals(
+    State(app_state): State<AppState>,
+    Query(query): Query<BudgetApprovalQuery>,
+) -> crate::Result<Json<Value>> {
+    let approvals = AnalysisRepositoryImpl::new(app_state)
+        .list_budget_approvals(query.repository_id, query.status)
+        .await?;
+
+    Ok(Json(json!({ "approvals": approvals })))
+}
+
+/// POST /analytics/usage/ai/approvals/{id}/approve
+/// Let the repository's next LLM analysis run over its budget.
+async fn approve_budget_approval(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(approval_id): Path<Uuid>,
+) -> crate::Result<Json<Value>> {
+    decide_budget_approval(app_state, approval_id, true, &caller.address).await
+}
+
+/// POST /analytics/usage/ai/approvals/{id}/deny
+async fn deny_budget_approval(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(approval_id): Path<Uuid>,
+) -> crate::Result<Json<Value>> {
+    decide_budget_approval(app_state, approval_id, false, &caller.address).await
+}
+
+async fn decide_budget_approval(
+    app_state: AppState,
+    approval_id: Uuid,
+    approve: bool,
+    decided_by: &str,
+) -> crate::Result<Json<Value>> {
+    let approval = AnalysisRepositoryImpl::new(app_state)
+        .decide_budget_approval(approval_id, approve, decided_by)
+        .await?
+        .ok_or(AnalysisError::BudgetApprovalNotFound { approval_id })?;
+
+    Ok(Json(json!(approval)))
+}
+
+fn budget_response(status: &BudgetStatus) -> Value {
+    json!({
+        "scope": status.budget.scope,
+        "scope_id": status.budget.scope_id,
+        "monthly_limit_usd": status.budget.monthly_limit_usd,
+        "policy": status.budget.policy,
+        "spent_usd": status.spent_usd,
+        "remaining_usd": status.remaining_usd(),
+        "exceeded": status.is_exceeded(),
+        "updated_by": status.budget.updated_by,
+        "updated_at": status.budget.updated_at,
+    })
+}
+
+/// Start of the month `YYYY-MM`, or of the current month, and of the next.
+fn month_bounds(month: Option<&str>) -> crate::Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let first = match month {
+        Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .map_err(|_| crate::error::Error::InvalidRequestFormat {
+                message: format!("month must be YYYY-MM, got {}", month),
+            })?,
+        None => month_start(Utc::now()).date_naive(),
+    };
+    let next = if first.month() == 12 {
+        NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+    };
+    let start_of = |date: NaiveDate| date.and_hms_opt(0, 0, 0).map(|at| at.and_utc());
+    match (start_of(first), next.and_then(start_of)) {
+        (Some(from), Some(to)) => Ok((from, to)),
+        _ => Err(crate::error::Error::InvalidRequestFormat { message: "month is out of range".to_string() }),
+    }
+}
@@ -0,0 +1,141 @@
+use ai_analysis_service::domain::disclosure::SCOPE_VULNERABILITIES_DISCLOSURE;
+use analytics_service::{
+  application::use_cases::ExportUseCases,
+  domain::{AnalyticsExport, ExportDataset, ExportDownload, ExportFormat, ExportQuery, ExportStatus},
+  infrastructure::ExportRepositoryImpl,
+};
+use auth_service::domain::Claims;
+use axum::{
+  Extension, Router,
+  body::Body,
+  extract::{Path, Query, State},
+  http::{StatusCode, header},
+  response::{IntoResponse, Json, Redirect, Response},
+  routing::{get, post},
+};
+use chrono::{DateTime, Utc};
+use jd_core::{AppState, ctx::scope_matches};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::Result;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportRequest {
+  pub dataset: ExportDataset,
+  pub format: ExportFormat,
+  /// Records created at or after this, if set.
+  pub from: Option<DateTime<Utc>>,
+  /// Records created before this, if set.
+  pub to: Option<DateTime<Utc>>,
+}
+
+impl ExportRequest {
+  /// Findings still under coordinated disclosure are only exported for
+  /// callers who may see them.
+  fn query(self, caller: &Claims) -> ExportQuery {
+    ExportQuery {
+      dataset: self.dataset,
+      format: self.format,
+      from: self.from,
+      to: self.to,
+      include_undisclosed: caller
+        .scopes
+        .iter()
+        .any(|granted| scope_matches(granted, SCOPE_VULNERABILITIES_DISCLOSURE)),
+    }
+  }
+}
+
+fn exports(app_state: AppState) -> ExportUseCases {
+  ExportUseCases::new(Arc::new(ExportRepositoryImpl::new(app_state.mm().dbx().clone())))
+    .with_objects(app_state.objects.clone())
+}
+
+fn attachment(query: &ExportQuery) -> [(header::HeaderName, String); 2] {
+  [
+    (header::CONTENT_TYPE, query.format.content_type().to_string()),
+    (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", query.file_name())),
+  ]
+}
+
+fn export_response(export: &AnalyticsExport) -> Value {
+  let download_url = (export.status == ExportStatus::Ready)
+    .then(|| format!("/api/v1/analytics/exports/{}/download", export.id));
+  json!({
+    "id": export.id,
+    "dataset": export.dataset,
+    "format": export.format,
+    "from": export.range_from,
+    "to": export.range_to,
+    "status": export.status,
+    "row_count": export.row_count,
+    "byte_size": export.byte_size,
+    "error": export.error,
+    "created_at": export.created_at,
+    "completed_at": export.completed_at,
+    "expires_at": export.expires_at,
+    "download_url": download_url,
+  })
+}
+
+/// Exports are attributed to, and only downloadable by, the token subject,
+/// so `v1_routes` mounts this behind bearer auth.
+pub fn analytics_export_router() -> Router<AppState> {
+  Router::new()
+    .route("/export", get(stream_export))
+    .route("/exports", post(prepare_export))
+    .route("/exports/{id}", get(get_export))
+    .route("/exports/{id}/download", get(download_export))
+}
+
+/// GET /analytics/export?dataset=&format=&from=&to=
+/// Stream a dataset as CSV or NDJSON while the rows are read.
+async fn stream_export(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Query(request): Query<ExportRequest>,
+) -> Result<Response> {
+  let query = request.query(&caller);
+  let lines = exports(app_state).stream(&query)?;
+  Ok((attachment(&query), Body::from_stream(lines)).into_response())
+}
+
+/// POST /analytics/exports
+/// Queue a dataset export to be prepared in the background, for ranges too
+/// large to stream in one request.
+async fn prepare_export(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Json(request): Json<ExportRequest>,
+) -> Result<(StatusCode, Json<Value>)> {
+  let query = request.query(&caller);
+  let export = exports(app_state).prepare(&caller.address, &query).await?;
+  Ok((StatusCode::ACCEPTED, Json(export_response(&export))))
+}
+
+/// GET /analytics/exports/{id}
+async fn get_export(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<Value>> {
+  let export = exports(app_state).status(id, &caller.address).await?;
+  Ok(Json(export_response(&export)))
+}
+
+/// GET /analytics/exports/{id}/download
+/// The file, or a redirect to a short-lived object storage link to it.
+async fn download_export(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+) -> Result<Response> {
+  let (export, download) = exports(app_state).download(id, &caller.address).await?;
+  Ok(match download {
+    ExportDownload::Content(content) => (attachment(&export.query()), content).into_response(),
+    ExportDownload::Redirect(url) => Redirect::temporary(&url).into_response(),
+  })
+}
@@ -1,3 +1,11 @@
+mod alert_routes;
 mod analytics_routes;
+mod export_routes;
+mod report_routes;
+mod score_recompute_routes;
 
-pub use analytics_routes::analytics_router;
\ No newline at end of file
+pub use alert_routes::analytics_alert_router;
+pub use analytics_routes::{ai_budget_router, analytics_router};
+pub use export_routes::analytics_export_router;
+pub use report_routes::analytics_report_router;
+pub use score_recompute_routes::score_recompute_router;
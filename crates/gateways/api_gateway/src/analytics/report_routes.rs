@@ -0,0 +1,140 @@
+use ai_analysis_service::domain::disclosure::SCOPE_VULNERABILITIES_DISCLOSURE;
+use analytics_service::{
+  application::use_cases::ReportUseCases,
+  domain::{ReportRun, SavedQuery},
+  infrastructure::{HttpReportDelivery, ReportRepositoryImpl},
+  models::{SavedQueryRequest, SavedQueryResponse},
+};
+use auth_service::domain::Claims;
+use axum::{
+  Extension, Router,
+  extract::{Path, Query, State},
+  http::StatusCode,
+  response::Json,
+  routing::{get, post},
+};
+use jd_core::{AppState, ctx::scope_matches};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::Result;
+
+const DEFAULT_RUNS_PAGE: i64 = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct RunsQuery {
+  pub limit: Option<i64>,
+}
+
+fn reports(app_state: &AppState) -> ReportUseCases {
+  ReportUseCases::new(
+    Arc::new(ReportRepositoryImpl::new(app_state.mm().dbx().db().clone())),
+    Arc::new(HttpReportDelivery::from_env()),
+  )
+}
+
+/// Findings still under coordinated disclosure are only reported to owners
+/// who could see them when the query was saved.
+fn may_see_undisclosed(caller: &Claims) -> bool {
+  caller.scopes.iter().any(|granted| scope_matches(granted, SCOPE_VULNERABILITIES_DISCLOSURE))
+}
+
+/// Saved queries and their runs belong to, and are only visible to, the
+/// token subject, so `v1_routes` mounts this behind bearer auth.
+pub fn analytics_report_router() -> Router<AppState> {
+  Router::new()
+    .route("/saved-queries", post(create_saved_query).get(list_saved_queries))
+    .route(
+      "/saved-queries/{id}",
+      get(get_saved_query).put(update_saved_query).delete(delete_saved_query),
+    )
+    .route("/saved-queries/{id}/run", post(run_saved_query))
+    .route("/saved-queries/{id}/runs", get(list_report_runs))
+    .route("/report-runs/{id}", get(get_report_run))
+}
+
+/// POST /analytics/saved-queries
+/// Save a query, optionally on a schedule. A webhook secret, if one is
+/// generated, is only returned here.
+async fn create_saved_query(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Json(request): Json<SavedQueryRequest>,
+) -> Result<(StatusCode, Json<SavedQueryResponse>)> {
+  let query = reports(&app_state)
+    .create(&caller.address, request, may_see_undisclosed(&caller))
+    .await?;
+  Ok((StatusCode::CREATED, Json(query)))
+}
+
+/// GET /analytics/saved-queries
+async fn list_saved_queries(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+) -> Result<Json<Vec<SavedQuery>>> {
+  Ok(Json(reports(&app_state).list(&caller.address).await?))
+}
+
+/// GET /analytics/saved-queries/{id}
+async fn get_saved_query(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<SavedQuery>> {
+  Ok(Json(reports(&app_state).get(id, &caller.address).await?))
+}
+
+/// PUT /analytics/saved-queries/{id}
+async fn update_saved_query(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+  Json(request): Json<SavedQueryRequest>,
+) -> Result<Json<SavedQueryResponse>> {
+  let query = reports(&app_state)
+    .update(id, &caller.address, request, may_see_undisclosed(&caller))
+    .await?;
+  Ok(Json(query))
+}
+
+/// DELETE /analytics/saved-queries/{id}
+async fn delete_saved_query(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+) -> Result<StatusCode> {
+  reports(&app_state).delete(id, &caller.address).await?;
+  Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /analytics/saved-queries/{id}/run
+/// Run a saved query now and deliver the report as its schedule would.
+async fn run_saved_query(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<ReportRun>> {
+  Ok(Json(reports(&app_state).run_now(id, &caller.address).await?))
+}
+
+/// GET /analytics/saved-queries/{id}/runs?limit=
+async fn list_report_runs(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+  Query(query): Query<RunsQuery>,
+) -> Result<Json<Vec<ReportRun>>> {
+  let limit = query.limit.unwrap_or(DEFAULT_RUNS_PAGE);
+  Ok(Json(reports(&app_state).runs(id, &caller.address, limit).await?))
+}
+
+/// GET /analytics/report-runs/{id}
+/// A past run with the rows it returned.
+async fn get_report_run(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<ReportRun>> {
+  Ok(Json(reports(&app_state).run_output(id, &caller.address).await?))
+}
@@ -0,0 +1,76 @@
+use auth_service::domain::Claims;
+use axum::{
+  Extension, Router,
+  extract::{Path, State},
+  http::StatusCode,
+  response::Json,
+  routing::{get, post},
+};
+use jd_core::AppState;
+use scoring_service::{
+  application::use_cases::{
+    feature_use_cases::FeatureUseCases, recompute_use_cases::RecomputeUseCases,
+  },
+  domain::{feature_extraction::FeatureSet, recompute_job::RecomputeFilter},
+  infrastructure::{
+    feature_repository_impl::FeatureRepositoryImpl,
+    model_registry_repository_impl::ModelRegistryRepositoryImpl,
+    recompute_job_repository_impl::RecomputeJobRepositoryImpl,
+  },
+  models::responses::RecomputeJobResponse,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::Result;
+
+#[derive(Debug, Deserialize)]
+pub struct RecomputeScoresRequest {
+  #[serde(flatten)]
+  pub filter: RecomputeFilter,
+  /// Registered model to rescore with; the active one if left out.
+  pub target_model_version: Option<String>,
+}
+
+type Recompute =
+  RecomputeUseCases<RecomputeJobRepositoryImpl, ModelRegistryRepositoryImpl, FeatureRepositoryImpl>;
+
+fn recompute(app_state: AppState) -> Recompute {
+  let features = FeatureSet::from_config(app_state.config.scoring.as_ref());
+  RecomputeUseCases::new(
+    RecomputeJobRepositoryImpl::new(app_state.clone()),
+    ModelRegistryRepositoryImpl::new(app_state.clone()),
+    FeatureUseCases::new(FeatureRepositoryImpl::new(app_state), features),
+  )
+}
+
+/// Score recomputation rewrites users' current scores, so `v1_routes`
+/// mounts this behind bearer auth and the `scoring:models_admin` scope
+/// policy.
+pub fn score_recompute_router() -> Router<AppState> {
+  Router::new()
+    .route("/scores/recompute", post(recompute_scores))
+    .route("/scores/recompute/{id}", get(get_recompute_job))
+}
+
+/// POST /analytics/scores/recompute
+/// Queue recomputation of the scores the filter selects. New results are
+/// written alongside the old ones.
+async fn recompute_scores(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Json(request): Json<RecomputeScoresRequest>,
+) -> Result<(StatusCode, Json<RecomputeJobResponse>)> {
+  let job = recompute(app_state)
+    .enqueue(Some(&caller.address), request.filter, request.target_model_version.as_deref())
+    .await?;
+  Ok((StatusCode::ACCEPTED, Json(job.into())))
+}
+
+/// GET /analytics/scores/recompute/{id}
+async fn get_recompute_job(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<RecomputeJobResponse>> {
+  Ok(Json(recompute(app_state).get_job(id).await?.into()))
+}
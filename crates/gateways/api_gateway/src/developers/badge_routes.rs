@@ -0,0 +1,86 @@
+use auth_service::domain::Claims;
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    routing::{get, post},
+    Extension, Router,
+};
+use developer_service::{
+    application::use_cases::BadgeUseCases,
+    domain::{BadgeAward, BadgeDefinition, BadgeKind},
+    infrastructure::{BadgeRepositoryImpl, ReputationRepositoryImpl},
+};
+use jd_core::AppState;
+use serde::Deserialize;
+use std::sync::Arc;
+use sui_service::infrastructure::badge_minter::BadgeMinter;
+use uuid::Uuid;
+
+use super::sui_badge_minter::SuiBadgeMinter;
+use crate::Result;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ClaimBadgeRequest {
+    /// Sui wallet linked to the developer to mint the badge to. The badge
+    /// is only marked claimed if unset.
+    pub mint_to: Option<String>,
+}
+
+fn badges(app_state: &AppState) -> BadgeUseCases {
+    let db = app_state.mm().dbx().db().clone();
+    BadgeUseCases::new(
+        Arc::new(BadgeRepositoryImpl::new(db.clone())),
+        Arc::new(ReputationRepositoryImpl::new(db)),
+    )
+}
+
+/// Badge use cases that mint claims when a badge package is configured.
+fn minting_badges(app_state: &AppState) -> Result<BadgeUseCases> {
+    let minter = BadgeMinter::from_state(app_state)
+        .map_err(|e| developer_service::Error::ServiceError(e.to_string()))?;
+    Ok(match minter {
+        Some(minter) => badges(app_state).with_minter(Arc::new(SuiBadgeMinter::new(minter))),
+        None => badges(app_state),
+    })
+}
+
+/// Badge definitions and awards, readable by anyone.
+pub fn developer_badge_router() -> Router<AppState> {
+    Router::new()
+        .route("/badges", get(list_badge_definitions))
+        .route("/{id}/badges", get(list_developer_badges))
+}
+
+/// Claims act for the developer the token subject belongs to, so
+/// `v1_routes` mounts this behind bearer auth.
+pub fn badge_claim_router() -> Router<AppState> {
+    Router::new().route("/badges/{badge}/claim", post(claim_badge))
+}
+
+/// GET /developers/badges
+async fn list_badge_definitions() -> Json<Vec<BadgeDefinition>> {
+    Json(BadgeUseCases::definitions())
+}
+
+/// GET /developers/{id}/badges
+async fn list_developer_badges(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<BadgeAward>>> {
+    Ok(Json(badges(&app_state).awards(id).await?))
+}
+
+/// POST /developers/badges/{badge}/claim
+/// Claim an awarded badge, optionally minting it as a Sui object.
+async fn claim_badge(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(badge): Path<BadgeKind>,
+    request: Option<Json<ClaimBadgeRequest>>,
+) -> Result<Json<BadgeAward>> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let award = minting_badges(&app_state)?
+        .claim(&caller.address, badge, request.mint_to.as_deref())
+        .await?;
+    Ok(Json(award))
+}
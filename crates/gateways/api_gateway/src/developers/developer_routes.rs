@@ -5,8 +5,14 @@ use axum::{
     routing::{get, post, put},
     Json, Router,
 };
+use developer_service::{
+    application::use_cases::ReputationUseCases, domain::ReputationWindow,
+    infrastructure::ReputationRepositoryImpl, models::ReputationLeaderboardResponse,
+};
 use jd_core::AppState;
+use serde::Deserialize;
 use serde_json::{json, Value};
+use std::sync::Arc;
 use uuid::Uuid;
 
 // Placeholder handlers for developer management
@@ -86,16 +92,30 @@ pub async fn search_developers(
     Ok(ResponseJson(response))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQuery {
+    /// `all_time` (default), `90d` or `30d`.
+    pub window: Option<ReputationWindow>,
+    /// Primary language of the repositories counted; all if unset.
+    pub language: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// GET /developers/leaderboard?window=&language=&limit=
+/// Developers ranked by reputation, as last recomputed by the scheduler.
 pub async fn get_leaderboard(
-    State(_app_state): State<AppState>,
-    Query(_params): Query<Value>,
-) -> Result<ResponseJson<Value>, StatusCode> {
-    let response = json!({
-        "leaderboard": [],
-        "updated_at": chrono::Utc::now(),
-        "period": "all_time"
-    });
-    Ok(ResponseJson(response))
+    State(app_state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> crate::Result<ResponseJson<ReputationLeaderboardResponse>> {
+    let repository = ReputationRepositoryImpl::new(app_state.mm().dbx().db().clone());
+    let leaderboard = ReputationUseCases::new(Arc::new(repository))
+        .leaderboard(
+            query.window.unwrap_or(ReputationWindow::AllTime),
+            query.language.as_deref(),
+            query.limit.unwrap_or(50),
+        )
+        .await?;
+    Ok(ResponseJson(leaderboard))
 }
 
 pub async fn get_developers_by_skill(
@@ -1,3 +1,6 @@
+pub mod badge_routes;
 pub mod developer_routes;
+pub mod sui_badge_minter;
 
+pub use badge_routes::*;
 pub use developer_routes::*;
\ No newline at end of file
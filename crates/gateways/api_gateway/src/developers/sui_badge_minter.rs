@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use developer_service::domain::{BadgeKind, BadgeMinter, MintOutcome, PreparedMint};
+use sui_service::infrastructure::badge_minter::BadgeMinter as SuiMinter;
+
+/// Mints claimed badges as Sui objects through the sponsor's gas pool.
+pub struct SuiBadgeMinter {
+    minter: SuiMinter,
+}
+
+impl SuiBadgeMinter {
+    pub fn new(minter: SuiMinter) -> Self {
+        Self { minter }
+    }
+}
+
+#[async_trait]
+impl BadgeMinter for SuiBadgeMinter {
+    async fn prepare(
+        &self,
+        badge: BadgeKind,
+        recipient: &str,
+    ) -> developer_service::Result<PreparedMint> {
+        let signed = self
+            .minter
+            .prepare(recipient, badge.as_str(), badge.definition().name)
+            .await
+            .map_err(|e| developer_service::Error::NetworkError(e.to_string()))?;
+        Ok(PreparedMint { digest: signed.digest, signed_tx: signed.signed_tx })
+    }
+
+    async fn submit(&self, prepared: &PreparedMint) -> developer_service::Result<MintOutcome> {
+        let executed = self
+            .minter
+            .submit(&prepared.signed_tx)
+            .await
+            .map_err(|e| developer_service::Error::NetworkError(e.to_string()))?;
+        Ok(match executed.error {
+            None => MintOutcome::Minted { digest: executed.digest },
+            Some(reason) => MintOutcome::Failed { digest: executed.digest, reason },
+        })
+    }
+}
@@ -5,6 +5,8 @@ use serde::Serialize;
 use serde_with::serde_as;
 use tracing::{error, info, warn};
 
+use jd_error::ErrorKind;
+
 use crate::middleware::{self};
 
 // ============================================================================
@@ -132,6 +134,10 @@ pub enum Error {
 
   #[error("Request blocked by security policy: {policy}")]
   SecurityPolicyViolation { policy: String },
+
+  // -- Downstream Service Errors
+  #[error("{0}")]
+  Service(#[from] jd_error::AppError),
 }
 
 impl Clone for Error {
@@ -206,6 +212,7 @@ impl Clone for Error {
       Self::SecurityPolicyViolation { policy } => {
         Self::SecurityPolicyViolation { policy: policy.clone() }
       }
+      Self::Service(e) => Self::Service(e.clone()),
     }
   }
 }
@@ -364,6 +371,13 @@ impl Error {
       Self::CorsViolation { .. } | Self::CspViolation { .. } => ErrorSeverity::High,
 
       Self::MetricsCollectionFailed { .. } => ErrorSeverity::Low,
+
+      // Service errors - severity follows the taxonomy kind
+      Self::Service(e) => match e.kind() {
+        ErrorKind::Internal => ErrorSeverity::High,
+        kind if kind.is_server_error() => ErrorSeverity::Medium,
+        _ => ErrorSeverity::Low,
+      },
     }
   }
 
@@ -411,10 +425,22 @@ impl Error {
       | Self::ReqStampNotInReqExt => ErrorCategory::Monitoring,
 
       Self::GatewayConfig { .. } => ErrorCategory::Infrastructure,
+
+      Self::Service(e) => match e.kind() {
+        ErrorKind::Unauthenticated => ErrorCategory::Authentication,
+        ErrorKind::PermissionDenied | ErrorKind::RateLimited => ErrorCategory::Authorization,
+        ErrorKind::Validation | ErrorKind::PayloadTooLarge => ErrorCategory::Validation,
+        ErrorKind::NotFound => ErrorCategory::Routing,
+        _ => ErrorCategory::ServiceCommunication,
+      },
     }
   }
 
   pub fn is_retryable(&self) -> bool {
+    if let Self::Service(e) = self {
+      return e.is_retryable();
+    }
+
     matches!(
       self,
       Self::ServiceTimeout { .. }
@@ -430,12 +456,35 @@ impl Error {
     !matches!(self.severity(), ErrorSeverity::Low)
   }
 
+  /// Send a high or critical error to the error aggregator, tagged with its
+  /// request so it can be matched to the logs
+  fn report(&self, request_context: &RequestContext, status_code: StatusCode) {
+    let level = match self.severity() {
+      ErrorSeverity::Critical => jd_tracing::ReportLevel::Fatal,
+      ErrorSeverity::High => jd_tracing::ReportLevel::Error,
+      _ => return,
+    };
+    jd_tracing::report_error(jd_tracing::ErrorReport {
+      message: self.to_string(),
+      level,
+      request_id: request_context.request_id.clone(),
+      trace_id: request_context.trace_id.clone(),
+      user_id: request_context.user_id.clone(),
+      tags: vec![
+        ("error", self.as_ref().to_string()),
+        ("category", format!("{:?}", self.category())),
+        ("status_code", status_code.as_u16().to_string()),
+      ],
+    });
+  }
+
   pub fn retry_after_seconds(&self) -> Option<u32> {
     match self {
       Self::RateLimitExceeded { .. } => Some(60),
       Self::ServiceUnavailable { .. } => Some(30),
       Self::CircuitBreakerOpen { .. } => Some(120),
       Self::DatabasePoolExhausted => Some(5),
+      Self::Service(e) => e.retry_after_secs().map(|secs| secs as u32),
       _ => None,
     }
   }
@@ -601,6 +650,14 @@ impl Error {
         None, // Don't expose policy details
       ),
 
+      // Service errors carry their own status, code and user-safe message
+      Self::Service(e) => (
+        e.status_code(),
+        e.code(),
+        e.user_message().to_string(),
+        e.details().cloned(),
+      ),
+
       // Other errors default to 500
       _ => (
         StatusCode::INTERNAL_SERVER_ERROR,
@@ -673,6 +730,7 @@ impl IntoResponse for Error {
             trace_id = ?request_context.trace_id,
             "Critical gateway error"
         );
+        self.report(&request_context, status_code);
       }
       ErrorSeverity::High => {
         error!(
@@ -682,6 +740,7 @@ impl IntoResponse for Error {
             request_id = ?request_context.request_id,
             "High severity gateway error"
         );
+        self.report(&request_context, status_code);
       }
       ErrorSeverity::Medium => {
         warn!(
@@ -796,12 +855,18 @@ impl RequestContext {
     }
   }
 
-  /// Set the current request context in task-local storage
+  /// Set the current request context in task-local storage, and its ids as
+  /// the correlation services forward on outbound calls
   pub async fn run_with_context<F, R>(self, future: F) -> R
   where
     F: std::future::Future<Output = R>,
   {
-    CURRENT_REQUEST_CONTEXT.scope(Some(self), future).await
+    let correlation = jd_utils::correlation::Correlation {
+      request_id: self.request_id.clone(),
+      trace_id: self.trace_id.clone(),
+      user_id: self.user_id.clone(),
+    };
+    CURRENT_REQUEST_CONTEXT.scope(Some(self), correlation.scope(future)).await
   }
 
   /// Update user information (called by auth middleware)
@@ -839,51 +904,57 @@ impl From<redis::RedisError> for Error {
   }
 }
 
-impl From<github_service::Error> for Error {
-  fn from(err: github_service::Error) -> Self {
+impl From<jd_object_store::Error> for Error {
+  fn from(err: jd_object_store::Error) -> Self {
     match err {
-      github_service::Error::RepositoryNotFound { owner, repo } => Self::RouteNotFound {
-        path: format!("/repositories/{}/{}", owner, repo),
-        method: "GET".to_string(),
-      },
-      github_service::Error::NoSmartContractsFound => Self::InvalidRequestFormat {
-        message: "Repository does not contain any smart contracts".to_string(),
-      },
-      github_service::Error::GitHubApi(msg) => Self::service_error("github", 502, Some(msg)),
-      github_service::Error::InvalidWebhookSignature => {
-        Self::ApiKeyAuthFailed { reason: "Invalid webhook signature".to_string() }
-      }
-      github_service::Error::WebhookPayloadError(msg) => {
-        Self::InvalidRequestFormat { message: format!("Invalid webhook payload: {}", msg) }
-      }
-      github_service::Error::RateLimitExceeded { retry_after_seconds: _ } => {
-        Self::RateLimitExceeded {
-          client_id: "github_api".to_string(),
-          limit: 5000,
-          window: "hour".to_string(),
-        }
-      }
-      github_service::Error::JobNotFound(id) => {
-        Self::RouteNotFound { path: format!("/jobs/{}", id), method: "GET".to_string() }
-      }
-      github_service::Error::QueueFull => Self::service_unavailable("github_queue"),
-      github_service::Error::AuthenticationError(msg) => Self::ApiKeyAuthFailed { reason: msg },
-      github_service::Error::ConfigurationError(msg) => {
-        Self::GatewayConfig { config_key: format!("github_service: {}", msg) }
-      }
-      github_service::Error::Database(err) => {
-        Self::service_error("database", 500, Some(err.to_string()))
-      }
-      github_service::Error::Serialization(err) => {
-        Self::InvalidRequestFormat { message: format!("Serialization error: {}", err) }
-      }
-      github_service::Error::HttpRequest(err) => {
-        Self::service_error("github_api", 502, Some(err.to_string()))
-      }
-      github_service::Error::Octocrab(err) => {
-        Self::service_error("github_api", 502, Some(err.to_string()))
+      jd_object_store::Error::NotFound { key } => Self::route_not_found(key, "GET"),
+      err => Self::service_error("object_store", 502, Some(err.to_string())),
+    }
+  }
+}
+
+impl From<jd_feature_flags::Error> for Error {
+  fn from(err: jd_feature_flags::Error) -> Self {
+    match err {
+      jd_feature_flags::Error::InvalidFlag(message) => Self::invalid_request(message),
+      err => Self::service_error("feature_flags", 500, Some(err.to_string())),
+    }
+  }
+}
+
+impl From<jd_cache::Error> for Error {
+  fn from(err: jd_cache::Error) -> Self {
+    match err {
+      jd_cache::Error::Redis(err) => err.into(),
+      jd_cache::Error::Serde { key, .. } => Self::CacheOperationFailed { key },
+      jd_cache::Error::LockAcquisitionFailed { resource } => {
+        Self::LockAcquisitionFailed { resource }
       }
-      github_service::Error::Internal(msg) => Self::service_error("github", 500, Some(msg)),
     }
   }
 }
+
+/// Service errors convert through the shared `jd_error` taxonomy. The per-variant
+/// mapping lives next to each service's `Error` enum (`#[derive(ErrorTaxonomy)]`).
+macro_rules! impl_from_service_error {
+  ($($service_error:ty),* $(,)?) => {
+    $(
+      impl From<$service_error> for Error {
+        fn from(err: $service_error) -> Self {
+          Self::Service(err.into())
+        }
+      }
+    )*
+  };
+}
+
+impl_from_service_error!(
+  github_service::Error,
+  auth_service::Error,
+  ai_analysis_service::Error,
+  analytics_service::Error,
+  behavior_service::Error,
+  scoring_service::Error,
+  zkproof_service::Error,
+  developer_service::Error,
+);
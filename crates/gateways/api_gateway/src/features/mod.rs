@@ -0,0 +1,22 @@
+use axum::{response::Json, routing::get, Router};
+use jd_core::AppState;
+use serde::Serialize;
+
+use crate::middleware::feature_flags::Features;
+use crate::Result;
+
+#[derive(Debug, Serialize)]
+pub struct EnabledFeatures {
+  pub flags: Vec<String>,
+}
+
+/// GET /features
+/// The flags on for the caller, so clients can show the same features the
+/// API serves them. Anonymous without a bearer token.
+async fn get_enabled_features(features: Features) -> Result<Json<EnabledFeatures>> {
+  Ok(Json(EnabledFeatures { flags: features.enabled().await? }))
+}
+
+pub fn feature_router() -> Router<AppState> {
+  Router::new().route("/", get(get_enabled_features))
+}
@@ -1,9 +1,11 @@
+use auth_service::domain::Claims;
 use axum::{
-  extract::{Json, Path, Query, State},
+  extract::{Extension, Json, Path, Query, State},
   http::{HeaderMap, StatusCode},
   response::Json as ResponseJson,
 };
-use jd_core::AppState;
+use jd_core::{ctx::Ctx, AppState};
+use jd_utils::correlation::Correlate;
 use rust_decimal::prelude::ToPrimitive;
 use serde_json::{json, Value};
 use sqlx::Row;
@@ -123,8 +125,7 @@ pub async fn analyze_repository(
 
   let ai_config = AiAnalysisServiceConfig {
     app_state: app_state.clone(),
-    openai_api_key: std::env::var("OPENAI_API_KEY").ok(),
-    anthropic_api_key: std::env::var("ANTHROPIC_API_KEY").ok(),
+    llm_client: ai_analysis_service::LLMClient::from_env(),
     enable_llm_analysis: std::env::var("ENABLE_LLM_ANALYSIS").unwrap_or("false".to_string())
       == "true",
   };
@@ -154,7 +155,7 @@ pub async fn analyze_repository(
   match fetch_github_files(owner, repo).await {
     Ok(files) => {
       if files.is_empty() {
-        error!("No Move files found in repository {}/{}", owner, repo);
+        error!("No smart contract files found in repository {}/{}", owner, repo);
         // Fallback to sample code for testing
         file_contents
           .insert("sources/sample.move".to_string(), "module sample::empty { }".to_string());
@@ -205,11 +206,20 @@ pub async fn analyze_repository(
 }
 
 // Keep existing handlers below
+use ai_analysis_service::MoveBytecodeAnalyzer;
 use github_service::{
-  AddRepositoryRequest, GitHubWebhookPayload, RepositoryDetailResponse, RepositoryHandler,
-  RepositoryListParams, RepositoryListResponse, RepositoryResponse,
-  UpdateRepositorySettingsRequest, WebhookHandler, WebhookResponse,
+  AddRepositoryRequest, AnalysisJobDetail, AnalysisJobListParams, AnalysisJobListResponse,
+  CommitListParams, ContributorSummary, DeployedModule, DeploymentHandler, DeploymentVerification,
+  GitHubWebhookPayload, ModuleBytecode, MovePackageBuilder, OnchainPackageReader,
+  OrganizationImportRequest, OrganizationImportResponse, PackageDeployment, RateLimitResponse,
+  RegisterDeploymentRequest, RepositoryAccessStore, RepositoryCommit, RepositoryDetailResponse,
+  RepositoryHandler, RepositoryListParams, RepositoryListResponse, RepositoryPackage,
+  RepositoryResponse, UpdateJobPriorityRequest, UpdateRepositorySettingsRequest,
+  VerifyDeploymentRequest, WebhookDeliveryDetailResponse, WebhookDeliveryListParams,
+  WebhookDeliveryListResponse, WebhookDeliverySummary, WebhookHandler, WebhookResponse,
+  WebhookSecretRotation, SCOPE_REPOSITORIES_ADMIN,
 };
+use sui_service::infrastructure::enhanced_sui_repository::EnhancedSuiRepository;
 
 use crate::error::Error as ApiError;
 type Result<T> = std::result::Result<T, ApiError>;
@@ -225,14 +235,7 @@ pub async fn list_repositories(
     ApiError::service_error("github", 500, Some(e.to_string()))
   })?;
 
-  repository_handler
-    .list_repositories(params)
-    .await
-    .map(ResponseJson)
-    .map_err(|e| {
-      error!("Failed to list repositories: {}", e);
-      map_github_error(e)
-    })
+  Ok(ResponseJson(repository_handler.list_repositories(params).await?))
 }
 
 /// Add a new repository for monitoring
@@ -245,14 +248,237 @@ pub async fn add_repository(
     ApiError::service_error("github", 500, Some(e.to_string()))
   })?;
 
-  repository_handler
-    .add_repository(request)
-    .await
-    .map(ResponseJson)
-    .map_err(|e| {
-      error!("Failed to add repository: {}", e);
-      map_github_error(e)
-    })
+  Ok(ResponseJson(repository_handler.add_repository(request).await?))
+}
+
+/// Import the matching repositories of a GitHub organization
+pub async fn import_organization(
+  State(app_state): State<AppState>,
+  Path(org): Path<String>,
+  Json(request): Json<OrganizationImportRequest>,
+) -> Result<ResponseJson<OrganizationImportResponse>> {
+  let repository_handler = create_repository_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub repository handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(repository_handler.import_organization(&org, request).await?))
+}
+
+/// List the smart contract packages detected in a repository
+pub async fn list_repository_packages(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<ResponseJson<Vec<RepositoryPackage>>> {
+  let repository_handler = create_repository_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub repository handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(repository_handler.list_packages(id).await?))
+}
+
+/// Re-detect a repository's packages from its default branch. Owners and
+/// admins of an organization monitoring the repository may, as may tokens
+/// granting `repositories:admin`.
+pub async fn detect_repository_packages(
+  State(app_state): State<AppState>,
+  Extension(ctx): Extension<Ctx>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+) -> Result<ResponseJson<Vec<RepositoryPackage>>> {
+  require_repository_manager(&app_state, &ctx, &caller, id, "Package detection").await?;
+  let repository_handler = create_repository_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub repository handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(repository_handler.detect_packages(id).await?))
+}
+
+/// List the packages a repository has published on-chain
+pub async fn list_package_deployments(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<ResponseJson<Vec<PackageDeployment>>> {
+  let deployment_handler = create_deployment_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub deployment handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(deployment_handler.list_deployments(id).await?))
+}
+
+/// Register a package the repository published on-chain, as a repository
+/// owner or a `repositories:admin` token
+pub async fn register_package_deployment(
+  State(app_state): State<AppState>,
+  Extension(ctx): Extension<Ctx>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+  Json(request): Json<RegisterDeploymentRequest>,
+) -> Result<ResponseJson<PackageDeployment>> {
+  require_repository_manager(&app_state, &ctx, &caller, id, "Deployment registration").await?;
+  let deployment_handler = create_deployment_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub deployment handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(deployment_handler.register_deployment(id, request).await?))
+}
+
+/// Fetch the on-chain modules of a deployed package
+pub async fn get_deployed_modules(
+  State(app_state): State<AppState>,
+  Path((id, deployment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<Vec<DeployedModule>>> {
+  let deployment_handler = create_deployment_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub deployment handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(deployment_handler.deployed_modules(id, deployment_id).await?))
+}
+
+/// Check a deployed package's bytecode against the source at a commit, as
+/// a repository owner or a `repositories:admin` token
+pub async fn verify_package_deployment(
+  State(app_state): State<AppState>,
+  Extension(ctx): Extension<Ctx>,
+  Extension(caller): Extension<Claims>,
+  Path((id, deployment_id)): Path<(Uuid, Uuid)>,
+  Json(request): Json<VerifyDeploymentRequest>,
+) -> Result<ResponseJson<DeploymentVerification>> {
+  require_repository_manager(&app_state, &ctx, &caller, id, "Deployment verification").await?;
+  let deployment_handler = create_deployment_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub deployment handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(deployment_handler.verify_deployment(id, deployment_id, request).await?))
+}
+
+/// Rotate the secret of a repository's webhook
+pub async fn rotate_repository_webhook_secret(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<ResponseJson<WebhookSecretRotation>> {
+  let repository_handler = create_repository_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub repository handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(repository_handler.rotate_webhook_secret(id).await?))
+}
+
+/// Rotate the secret of the GitHub App's webhook
+pub async fn rotate_app_webhook_secret(
+  State(app_state): State<AppState>,
+) -> Result<ResponseJson<WebhookSecretRotation>> {
+  let repository_handler = create_repository_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub repository handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(repository_handler.rotate_app_webhook_secret().await?))
+}
+
+/// List a repository's ingested commits, newest first
+pub async fn list_repository_commits(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+  Query(params): Query<CommitListParams>,
+) -> Result<ResponseJson<Vec<RepositoryCommit>>> {
+  let repository_handler = create_repository_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub repository handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(repository_handler.list_commits(id, params).await?))
+}
+
+/// List a repository's commit authors with their contribution totals
+pub async fn list_repository_contributors(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<ResponseJson<Vec<ContributorSummary>>> {
+  let repository_handler = create_repository_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub repository handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(repository_handler.list_contributors(id).await?))
+}
+
+/// Current GitHub API budgets, as last reported by GitHub
+pub async fn get_rate_limit(
+  State(app_state): State<AppState>,
+) -> Result<ResponseJson<RateLimitResponse>> {
+  use github_service::{GitHubServiceConfig, GitHubServiceFactory};
+
+  let github_config = GitHubServiceConfig::from_config(&app_state.config).map_err(|e| {
+    error!("Failed to load GitHub configuration: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+  let rate_limiter = GitHubServiceFactory::shared_rate_limiter(&github_config);
+
+  Ok(ResponseJson(RateLimitResponse {
+    budgets: rate_limiter.snapshot().await,
+    low_priority_deferred: rate_limiter.is_constrained().await,
+  }))
+}
+
+/// List analysis jobs, newest first, optionally filtered by status
+pub async fn list_analysis_jobs(
+  State(app_state): State<AppState>,
+  Query(params): Query<AnalysisJobListParams>,
+) -> Result<ResponseJson<AnalysisJobListResponse>> {
+  let limit = params.limit.unwrap_or(50).clamp(1, 200);
+  let offset = params.offset.unwrap_or(0).max(0);
+
+  let (jobs, total_count) =
+    analysis_queue(&app_state)?.list_jobs(params.status, limit, offset).await?;
+
+  Ok(ResponseJson(AnalysisJobListResponse { jobs, total_count, limit, offset }))
+}
+
+/// Get an analysis job with its queue position and estimated start
+pub async fn get_analysis_job(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<ResponseJson<AnalysisJobDetail>> {
+  Ok(ResponseJson(analysis_queue(&app_state)?.get_job_detail(id).await?))
+}
+
+/// Cancel an analysis job that has not finished
+pub async fn cancel_analysis_job(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<ResponseJson<AnalysisJobDetail>> {
+  let queue = analysis_queue(&app_state)?;
+  queue.cancel_job(id).await?;
+  Ok(ResponseJson(queue.get_job_detail(id).await?))
+}
+
+/// Change the priority of a queued analysis job
+pub async fn update_analysis_job_priority(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+  Json(request): Json<UpdateJobPriorityRequest>,
+) -> Result<ResponseJson<AnalysisJobDetail>> {
+  let queue = analysis_queue(&app_state)?;
+  queue.set_priority(id, request.priority).await?;
+  Ok(ResponseJson(queue.get_job_detail(id).await?))
+}
+
+fn analysis_queue(app_state: &AppState) -> Result<github_service::AnalysisQueueImpl> {
+  use github_service::{GitHubServiceConfig, GitHubServiceFactory};
+
+  let github_config = GitHubServiceConfig::from_config(&app_state.config).map_err(|e| {
+    error!("Failed to load GitHub configuration: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+  Ok(GitHubServiceFactory::create_analysis_queue(&github_config, app_state.mm().dbx().db().clone()))
 }
 
 /// Get detailed information about a repository
@@ -265,14 +491,7 @@ pub async fn get_repository(
     ApiError::service_error("github", 500, Some(e.to_string()))
   })?;
 
-  repository_handler
-    .get_repository(id)
-    .await
-    .map(ResponseJson)
-    .map_err(|e| {
-      error!("Failed to get repository {}: {}", id, e);
-      map_github_error(e)
-    })
+  Ok(ResponseJson(repository_handler.get_repository(id).await?))
 }
 
 /// Update repository settings
@@ -286,14 +505,7 @@ pub async fn update_repository_settings(
     ApiError::service_error("github", 500, Some(e.to_string()))
   })?;
 
-  repository_handler
-    .update_repository_settings(id, request)
-    .await
-    .map(ResponseJson)
-    .map_err(|e| {
-      error!("Failed to update repository {} settings: {}", id, e);
-      map_github_error(e)
-    })
+  Ok(ResponseJson(repository_handler.update_repository_settings(id, request).await?))
 }
 
 /// Get comprehensive analysis data for a repository
@@ -423,6 +635,9 @@ pub async fn get_repository_analysis(
       (0.0, 0.0, None)
     };
 
+  let deployments = github_service::PackageDeploymentStore::new(app_state.mm().dbx().db().clone());
+  let verified_build = deployments.verified_build(id).await?;
+
   // Build response
   let response = json!({
     "repository": {
@@ -431,7 +646,8 @@ pub async fn get_repository_analysis(
       "name": repo,
       "full_name": full_name,
       "security_score": security_score.map(|d| d.to_f64().unwrap_or(0.0)),
-      "last_analyzed_at": last_analyzed_at
+      "last_analyzed_at": last_analyzed_at,
+      "verified_build": verified_build
     },
     "analysis_summary": {
       "total_analyses": total_analyses,
@@ -548,113 +764,260 @@ pub async fn handle_github_webhook(
     ApiError::service_error("github", 500, Some(e.to_string()))
   })?;
 
-  webhook_handler
-    .handle_github_webhook(headers, payload)
-    .await
-    .map(ResponseJson)
-    .map_err(|e| {
-      error!("Webhook processing error: {}", e);
-      map_github_error(e)
-    })
+  Ok(ResponseJson(webhook_handler.handle_github_webhook(headers, payload).await?))
+}
+
+/// List logged webhook deliveries, most recent first
+pub async fn list_webhook_deliveries(
+  State(app_state): State<AppState>,
+  Query(params): Query<WebhookDeliveryListParams>,
+) -> Result<ResponseJson<WebhookDeliveryListResponse>> {
+  let webhook_handler = create_webhook_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub webhook handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(webhook_handler.list_deliveries(params).await?))
+}
+
+/// Get a logged webhook delivery with its headers and payload
+pub async fn get_webhook_delivery(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<ResponseJson<WebhookDeliveryDetailResponse>> {
+  let webhook_handler = create_webhook_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub webhook handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(webhook_handler.get_delivery(id).await?))
+}
+
+/// Re-run a logged webhook delivery; responds with the replay's log entry
+pub async fn replay_webhook_delivery(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<ResponseJson<WebhookDeliverySummary>> {
+  let webhook_handler = create_webhook_handler(&app_state).map_err(|e| {
+    error!("Failed to create GitHub webhook handler: {}", e);
+    ApiError::service_error("github", 500, Some(e.to_string()))
+  })?;
+
+  Ok(ResponseJson(webhook_handler.replay_delivery(id).await?))
+}
+
+/// Fail unless the caller owns the repository through an organization
+/// monitoring it or holds `repositories:admin`; `change` names the denied
+/// change in the audit log.
+async fn require_repository_manager(
+  app_state: &AppState,
+  ctx: &Ctx,
+  caller: &Claims,
+  repository_id: Uuid,
+  change: &str,
+) -> Result<()> {
+  if ctx.has_scope(SCOPE_REPOSITORIES_ADMIN)
+    || RepositoryAccessStore::new(app_state.mm().dbx().db().clone())
+      .can_manage(repository_id, &caller.address)
+      .await?
+  {
+    return Ok(());
+  }
+
+  warn!(
+      target: "security_audit",
+      action = "denied",
+      repository_id = %repository_id,
+      caller = %caller.address,
+      "{} denied",
+      change
+  );
+  Err(ApiError::insufficient_permissions(SCOPE_REPOSITORIES_ADMIN))
 }
 
 // Helper functions to create GitHub service handlers
 fn create_repository_handler(
   app_state: &AppState,
 ) -> std::result::Result<RepositoryHandler, Box<dyn std::error::Error + Send + Sync>> {
-  use github_service::{GitHubServiceConfig, GitHubServiceFactory};
+  use github_service::{
+    CommitStore, GitHubServiceConfig, GitHubServiceFactory, PackageDeploymentStore,
+    ReanalysisScheduleStore, RepositoryPackageStore,
+  };
 
   let github_config = GitHubServiceConfig::from_config(&app_state.config)?;
 
-  let github_client = Arc::new(GitHubServiceFactory::create_client(&github_config)?);
-  let analysis_queue = Arc::new(GitHubServiceFactory::create_analysis_queue(&github_config));
+  let github_client = Arc::new(GitHubServiceFactory::create_cached_client(
+    &github_config,
+    app_state.mm().dbx().db().clone(),
+  )?);
+  let analysis_queue = Arc::new(GitHubServiceFactory::create_analysis_queue(
+    &github_config,
+    app_state.mm().dbx().db().clone(),
+  ));
 
   let repository_repo =
     Arc::new(jd_storage::repository::developer_repositories::GitHubRepositoryRepository::new(
       app_state.mm().dbx().clone(),
     ));
 
-  Ok(RepositoryHandler::new(
+  let schedules = ReanalysisScheduleStore::new(app_state.mm().dbx().db().clone());
+  let packages = RepositoryPackageStore::new(app_state.mm().dbx().db().clone());
+  let commits = CommitStore::new(app_state.mm().dbx().db().clone());
+  let deployments = PackageDeploymentStore::new(app_state.mm().dbx().db().clone());
+  let secrets = GitHubServiceFactory::create_webhook_secret_store(
+    &github_config,
+    app_state.mm().dbx().db().clone(),
+  )?;
+
+  let handler = RepositoryHandler::new(
     github_client,
     analysis_queue,
     repository_repo,
     github_config.webhook_base_url,
-  ))
+  )
+  .with_reanalysis_schedules(schedules, github_config.reanalysis.default_schedule)
+  .with_package_store(packages)
+  .with_commit_store(commits)
+  .with_deployment_store(deployments);
+
+  Ok(match secrets {
+    Some(secrets) => handler.with_webhook_secrets(secrets, github_config.webhook_secrets),
+    None => handler,
+  })
 }
 
-fn create_webhook_handler(
+type PackageDeploymentHandler = DeploymentHandler<SuiPackages, MoveToolchain>;
+
+fn create_deployment_handler(
   app_state: &AppState,
-) -> std::result::Result<WebhookHandler, Box<dyn std::error::Error + Send + Sync>> {
-  use github_service::{GitHubServiceConfig, GitHubServiceFactory};
+) -> std::result::Result<PackageDeploymentHandler, Box<dyn std::error::Error + Send + Sync>> {
+  use github_service::{
+    GitHubServiceConfig, GitHubServiceFactory, PackageDeploymentStore, RepositoryPackageStore,
+  };
 
   let github_config = GitHubServiceConfig::from_config(&app_state.config)?;
+  let github_client = Arc::new(GitHubServiceFactory::create_cached_client(
+    &github_config,
+    app_state.mm().dbx().db().clone(),
+  )?);
+  let repository_repo =
+    Arc::new(jd_storage::repository::developer_repositories::GitHubRepositoryRepository::new(
+      app_state.mm().dbx().clone(),
+    ));
+  let deployments = PackageDeploymentStore::new(app_state.mm().dbx().db().clone());
+  let packages = RepositoryPackageStore::new(app_state.mm().dbx().db().clone());
 
-  let github_client = Arc::new(GitHubServiceFactory::create_client(&github_config)?);
-  let analysis_queue = Arc::new(GitHubServiceFactory::create_analysis_queue(&github_config));
-
-  Ok(WebhookHandler::new(github_client, analysis_queue))
+  let handler = DeploymentHandler::new(
+    github_client,
+    repository_repo,
+    deployments,
+    packages,
+    SuiPackages(app_state.clone()),
+  );
+  Ok(match MoveBytecodeAnalyzer::from_env() {
+    Some(analyzer) => handler.with_builder(MoveToolchain(analyzer)),
+    None => handler,
+  })
 }
 
-// Helper function to map GitHub service errors to API gateway errors
-fn map_github_error(error: github_service::Error) -> ApiError {
-  match error {
-    github_service::Error::RepositoryNotFound { owner, repo } => ApiError::RouteNotFound {
-      path: format!("/repositories/{}/{}", owner, repo),
-      method: "GET".to_string(),
-    },
-    github_service::Error::NoSmartContractsFound => ApiError::InvalidRequestFormat {
-      message: "Repository does not contain any smart contracts".to_string(),
-    },
-    github_service::Error::GitHubApi(msg) => ApiError::service_error("github", 502, Some(msg)),
-    github_service::Error::InvalidWebhookSignature => {
-      ApiError::ApiKeyAuthFailed { reason: "Invalid webhook signature".to_string() }
-    }
-    github_service::Error::WebhookPayloadError(msg) => {
-      ApiError::InvalidRequestFormat { message: format!("Invalid webhook payload: {}", msg) }
-    }
-    github_service::Error::RateLimitExceeded { retry_after_seconds: _ } => {
-      ApiError::RateLimitExceeded {
-        client_id: "github_api".to_string(),
-        limit: 5000,
-        window: "hour".to_string(),
+/// Reads published packages through the Sui service.
+struct SuiPackages(AppState);
+
+impl OnchainPackageReader for SuiPackages {
+  async fn package_modules(
+    &self,
+    network: &str,
+    package_id: &str,
+  ) -> github_service::Result<Vec<ModuleBytecode>> {
+    let read = async {
+      let repository = EnhancedSuiRepository::for_network(self.0.clone(), Some(network)).await?;
+      repository.package_modules(package_id).await
+    };
+    match read.await {
+      Ok(modules) => Ok(
+        modules.into_iter().map(|(name, bytecode)| ModuleBytecode { name, bytecode }).collect(),
+      ),
+      Err(sui_service::Error::InvalidRequest(message)) => {
+        Err(github_service::Error::InvalidDeployment(message))
       }
+      Err(e) => Err(github_service::Error::Internal(e.to_string())),
     }
-    github_service::Error::JobNotFound(id) => {
-      ApiError::RouteNotFound { path: format!("/jobs/{}", id), method: "GET".to_string() }
-    }
-    github_service::Error::QueueFull => ApiError::service_unavailable("github_queue"),
-    github_service::Error::AuthenticationError(msg) => ApiError::ApiKeyAuthFailed { reason: msg },
-    github_service::Error::ConfigurationError(msg) => {
-      ApiError::GatewayConfig { config_key: format!("github_service: {}", msg) }
-    }
-    github_service::Error::Database(err) => {
-      ApiError::service_error("database", 500, Some(err.to_string()))
-    }
-    github_service::Error::Serialization(err) => {
-      ApiError::InvalidRequestFormat { message: format!("Serialization error: {}", err) }
-    }
-    github_service::Error::HttpRequest(err) => {
-      ApiError::service_error("github_api", 502, Some(err.to_string()))
-    }
-    github_service::Error::Octocrab(err) => {
-      ApiError::service_error("github_api", 502, Some(err.to_string()))
-    }
-    github_service::Error::Internal(msg) => ApiError::service_error("github", 500, Some(msg)),
   }
 }
 
+/// Builds packages with the Move toolchain the bytecode analyzer runs.
+struct MoveToolchain(MoveBytecodeAnalyzer);
+
+impl MovePackageBuilder for MoveToolchain {
+  async fn build(
+    &self,
+    package_path: &str,
+    sources: &HashMap<String, String>,
+  ) -> github_service::Result<Vec<ModuleBytecode>> {
+    let sources: HashMap<&str, &str> =
+      sources.iter().map(|(path, content)| (path.as_str(), content.as_str())).collect();
+    let modules = self
+      .0
+      .compile_package(package_path, &sources)
+      .await
+      .map_err(|e| github_service::Error::Internal(e.to_string()))?;
+    Ok(modules.into_iter().map(|(name, bytecode)| ModuleBytecode { name, bytecode }).collect())
+  }
+}
+
+fn create_webhook_handler(
+  app_state: &AppState,
+) -> std::result::Result<WebhookHandler, Box<dyn std::error::Error + Send + Sync>> {
+  use github_service::{
+    GitHubServiceConfig, GitHubServiceFactory, PatchPullRequestStore, RepositoryPackageStore,
+    WebhookDeliveryStore,
+  };
+
+  let github_config = GitHubServiceConfig::from_config(&app_state.config)?;
+
+  let github_client = Arc::new(GitHubServiceFactory::create_cached_client(
+    &github_config,
+    app_state.mm().dbx().db().clone(),
+  )?);
+  let analysis_queue = Arc::new(GitHubServiceFactory::create_analysis_queue(
+    &github_config,
+    app_state.mm().dbx().db().clone(),
+  ));
+  let deliveries = WebhookDeliveryStore::new(app_state.mm().dbx().db().clone());
+  let packages = RepositoryPackageStore::new(app_state.mm().dbx().db().clone());
+  let patch_pull_requests = PatchPullRequestStore::new(app_state.mm().dbx().db().clone());
+  let secrets = GitHubServiceFactory::create_webhook_secret_store(
+    &github_config,
+    app_state.mm().dbx().db().clone(),
+  )?;
+
+  let handler = WebhookHandler::new(github_client, analysis_queue)
+    .with_delivery_log(deliveries)
+    .with_package_scopes(packages)
+    .with_patch_pull_requests(patch_pull_requests);
+
+  Ok(match secrets {
+    Some(secrets) => handler.with_secret_store(secrets),
+    None => handler,
+  })
+}
+
+/// Files the static analyzers check.
+const CONTRACT_EXTENSIONS: [&str; 3] = [".move", ".sol", ".rs"];
+
 // Function to fetch real files from GitHub repository
 async fn fetch_github_files(
   owner: &str,
   repo: &str,
 ) -> std::result::Result<HashMap<String, String>, Box<dyn std::error::Error + Send + Sync>> {
   let mut file_contents = HashMap::new();
+  let client = reqwest::Client::new();
 
   // GitHub API endpoints for repository contents
   let base_url = format!("https://api.github.com/repos/{}/{}/contents", owner, repo);
 
-  // List of directories to check for Move files
-  let directories = vec!["sources", "tests", "scripts", ""];
+  // Directories to check for Move, Solidity and Solana program files
+  let directories = vec!["sources", "tests", "scripts", "contracts", "src", ""];
 
   for dir in directories {
     let url = if dir.is_empty() { base_url.clone() } else { format!("{}/{}", base_url, dir) };
@@ -662,7 +1025,7 @@ async fn fetch_github_files(
     info!("Fetching directory contents from: {}", url);
 
     // Make request to GitHub API
-    let response = match reqwest::get(&url).await {
+    let response = match client.get(&url).correlated().send().await {
       Ok(resp) => resp,
       Err(e) => {
         warn!("Failed to fetch directory {}: {}", dir, e);
@@ -690,12 +1053,12 @@ async fn fetch_github_files(
         item.get("type").and_then(|v| v.as_str()),
         item.get("download_url").and_then(|v| v.as_str()),
       ) {
-        // Only process Move files
-        if file_type == "file" && name.ends_with(".move") {
-          info!("Fetching Move file: {}", name);
+        // Only process smart contract files
+        if file_type == "file" && CONTRACT_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) {
+          info!("Fetching smart contract file: {}", name);
 
           // Download the file content
-          match reqwest::get(download_url).await {
+          match client.get(download_url).correlated().send().await {
             Ok(file_response) => {
               if file_response.status().is_success() {
                 match file_response.text().await {
@@ -719,7 +1082,7 @@ async fn fetch_github_files(
   }
 
   if file_contents.is_empty() {
-    Err("No Move files found in repository".to_string().into())
+    Err("No smart contract files found in repository".to_string().into())
   } else {
     Ok(file_contents)
   }
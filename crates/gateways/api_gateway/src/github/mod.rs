@@ -21,9 +21,67 @@ pub fn github_router() -> Router<AppState> {
     // .route("/repositories/{id}", get(get_repository))
     .route("/repositories/{id}", get(get_repository_analysis))
     .route("/repositories/{id}/settings", put(update_repository_settings))
+    .route("/repositories/{id}/packages", get(list_repository_packages))
+    .route("/repositories/{id}/deployments", get(list_package_deployments))
+    .route("/repositories/{id}/deployments/{deployment_id}/modules", get(get_deployed_modules))
+    .route("/repositories/{id}/commits", get(list_repository_commits))
+    .route("/repositories/{id}/contributors", get(list_repository_contributors))
+    .route("/rate-limit", get(get_rate_limit))
+    .route("/jobs", get(list_analysis_jobs))
+    .route("/jobs/{id}", get(get_analysis_job))
+    // Legacy webhook receiver, deprecated in favour of /webhook
+    // (see metering::deprecation)
     .route("/webhooks/github", post(handle_github_webhook))
 }
 
+/// Webhook delivery log. Deliveries carry repository payloads, so
+/// `v1_routes` mounts this behind user auth.
+pub fn webhook_delivery_router() -> Router<AppState> {
+  Router::new()
+    .route("/webhooks/deliveries", get(list_webhook_deliveries))
+    .route("/webhooks/deliveries/{id}", get(get_webhook_delivery))
+    .route("/webhooks/deliveries/{id}/replay", post(replay_webhook_delivery))
+}
+
+/// Package re-detection, which fetches the repository's tree with the
+/// server's GitHub credentials. `v1_routes` mounts this behind bearer auth;
+/// the handler admits repository owners and `repositories:admin` tokens.
+pub fn repository_package_admin_router() -> Router<AppState> {
+  Router::new().route("/repositories/{id}/packages/detect", post(detect_repository_packages))
+}
+
+/// Registering and verifying deployments, which query the chain and build
+/// the repository's source. `v1_routes` mounts this behind bearer auth and a
+/// per-caller rate limit; the handlers admit repository owners and
+/// `repositories:admin` tokens.
+pub fn package_deployment_admin_router() -> Router<AppState> {
+  Router::new()
+    .route("/repositories/{id}/deployments", post(register_package_deployment))
+    .route("/repositories/{id}/deployments/{deployment_id}/verify", post(verify_package_deployment))
+}
+
+/// Webhook secret rotation. `v1_routes` mounts this behind bearer auth and
+/// the `webhooks:admin` scope policy.
+pub fn webhook_admin_router() -> Router<AppState> {
+  Router::new()
+    .route("/repositories/{id}/webhook/rotate-secret", post(rotate_repository_webhook_secret))
+    .route("/webhook/rotate-secret", post(rotate_app_webhook_secret))
+}
+
+/// Cancelling and reprioritising analysis jobs. `v1_routes` mounts this
+/// behind bearer auth and the `analysis_jobs:admin` scope policy.
+pub fn analysis_job_admin_router() -> Router<AppState> {
+  Router::new()
+    .route("/jobs/{id}/cancel", post(cancel_analysis_job))
+    .route("/jobs/{id}/priority", post(update_analysis_job_priority))
+}
+
+/// Organization-wide import creates many repositories and jobs at once, so
+/// `v1_routes` mounts this behind user auth.
+pub fn repository_import_router() -> Router<AppState> {
+  Router::new().route("/organizations/{org}/import", post(import_organization))
+}
+
 async fn health_check() -> axum::response::Json<serde_json::Value> {
   axum::response::Json(serde_json::json!({
       "status": "healthy",
@@ -0,0 +1,110 @@
+use axum::{
+  Router,
+  extract::{ConnectInfo, Request, State},
+  http::{HeaderValue, header},
+  middleware::{self as axum_middleware, Next},
+  response::{IntoResponse, Response},
+  routing::get,
+};
+use jd_core::AppState;
+use sha2::{Digest, Sha256};
+use std::{
+  net::{IpAddr, SocketAddr},
+  sync::Arc,
+};
+use tracing::warn;
+
+use crate::Result;
+use crate::error::Error;
+use crate::middleware::mw_security::{IpNet, parse_ip_list};
+
+/// Who may use the internal listener, independently of the public one's
+/// `[security]` settings.
+struct InternalGuard {
+  allowlist: Vec<IpNet>,
+  metrics_token: Option<String>,
+}
+
+impl InternalGuard {
+  fn allows(&self, ip: IpAddr) -> bool {
+    self.allowlist.is_empty() || self.allowlist.iter().any(|net| net.contains(ip))
+  }
+}
+
+/// Routes of the internal listener: Prometheus `/metrics`, the `/health` and
+/// `/ready` probes, and the admin routes under `/admin`, which take the same
+/// scoped bearer tokens as on the public listener.
+pub fn internal_routes(app_state: AppState) -> Router {
+  let config = app_state.config.internal.as_ref();
+  let guard = Arc::new(InternalGuard {
+    allowlist: parse_ip_list(config.and_then(|c| c.ip_allowlist.as_deref()), "ip_allowlist"),
+    metrics_token: config.and_then(|c| c.metrics_token.clone()),
+  });
+
+  Router::new()
+    .route("/metrics", get(metrics))
+    .route_layer(axum_middleware::from_fn_with_state(guard.clone(), mw_metrics_token))
+    .route("/health", get(crate::health_check))
+    .route("/ready", get(crate::readiness_check))
+    .merge(crate::admin_routes(&app_state))
+    .layer(axum_middleware::from_fn_with_state(guard, mw_internal_allowlist))
+    .with_state(app_state)
+}
+
+async fn metrics() -> Response {
+  let (body, content_type) = jd_utils::metrics::render();
+  ([(header::CONTENT_TYPE, HeaderValue::from_static(content_type))], body).into_response()
+}
+
+/// Only peers on `INTERNAL.IP_ALLOWLIST` may connect over TCP. Unix socket
+/// connections carry no address; the socket's permissions decide who may
+/// connect.
+async fn mw_internal_allowlist(
+  State(guard): State<Arc<InternalGuard>>,
+  req: Request,
+  next: Next,
+) -> Result<Response> {
+  let peer = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(addr)| addr.ip());
+  if let Some(ip) = peer.filter(|ip| !guard.allows(*ip)) {
+    warn!(
+        target: "security_audit",
+        action = "blocked",
+        rule = "internal_ip_allowlist",
+        client_ip = %ip,
+        method = %req.method(),
+        path = %req.uri().path(),
+        "Internal listener request blocked"
+    );
+    return Err(Error::SecurityPolicyViolation { policy: "ip_allowlist".to_string() });
+  }
+
+  Ok(next.run(req).await)
+}
+
+/// Require `Authorization: Bearer {INTERNAL.METRICS_TOKEN}` when it is set.
+async fn mw_metrics_token(
+  State(guard): State<Arc<InternalGuard>>,
+  req: Request,
+  next: Next,
+) -> Result<Response> {
+  let Some(expected) = &guard.metrics_token else {
+    return Ok(next.run(req).await);
+  };
+  let given = req
+    .headers()
+    .get(header::AUTHORIZATION)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.strip_prefix("Bearer "))
+    .unwrap_or_default();
+  if !tokens_match(expected, given) {
+    return Err(Error::api_key_failed("missing or invalid metrics token"));
+  }
+
+  Ok(next.run(req).await)
+}
+
+/// Compare digests, so the time taken says nothing about the token.
+fn tokens_match(expected: &str, given: &str) -> bool {
+  let (expected, given) = (Sha256::digest(expected), Sha256::digest(given));
+  expected.iter().zip(given.iter()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
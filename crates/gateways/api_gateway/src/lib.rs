@@ -1,23 +1,104 @@
-use axum::{middleware as axum_middleware, Router, response::Json};
+use axum::{
+  extract::State, http::StatusCode, middleware as axum_middleware, response::Json, Router,
+};
 use jd_core::AppState;
 use serde_json::json;
 use std::sync::Arc;
 
+mod accounts;
+mod admin;
 mod ai_analysis;
 mod analytics;
 mod developers;
 mod error;
+mod features;
 mod github;
+mod internal;
 mod log;
+pub mod metering;
 pub mod middleware;
+mod notifications;
+mod objects;
+mod organizations;
 mod patches;
+mod repositories;
 mod routes_rpc;
+mod scoring;
 mod sui;
 mod vulnerabilities;
 mod zkpersona;
 
 pub type Result<T> = std::result::Result<T, error::Error>;
 
+pub use internal::internal_routes;
+
+const ROLES_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[auth_service::domain::SCOPE_ROLES_ADMIN]);
+const WEBHOOKS_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[github_service::SCOPE_WEBHOOKS_ADMIN]);
+const ANALYSIS_JOBS_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[github_service::SCOPE_ANALYSIS_JOBS_ADMIN]);
+const VULNERABILITIES_DISCLOSURE_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    ai_analysis_service::domain::disclosure::SCOPE_VULNERABILITIES_DISCLOSURE,
+  ]);
+const PROOF_KEYS_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    zkproof_service::domain::verification_key::SCOPE_PROOF_KEYS_ADMIN,
+  ]);
+const PROOF_REQUESTS_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    zkproof_service::domain::proof_request::SCOPE_PROOF_REQUESTS,
+  ]);
+const SCORING_MODELS_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    scoring_service::domain::model_registry::SCOPE_SCORING_MODELS_ADMIN,
+  ]);
+const SCORING_LEDGER_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    scoring_service::domain::score_ledger::SCOPE_SCORING_LEDGER_ADMIN,
+  ]);
+const SCORING_SYBIL_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    scoring_service::domain::sybil::SCOPE_SCORING_SYBIL,
+  ]);
+const BEHAVIOR_SCHEMAS_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    behavior_service::domain::event_schema::SCOPE_BEHAVIOR_SCHEMAS_ADMIN,
+  ]);
+const ANALYTICS_ALERTS_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[analytics_service::domain::SCOPE_ANALYTICS_ALERTS]);
+const ANALYTICS_BUDGETS_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    ai_analysis_service::domain::ai_budget::SCOPE_ANALYTICS_BUDGETS,
+  ]);
+const METERING_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[metering::SCOPE_METERING_ADMIN]);
+const LOGGING_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[jd_tracing::SCOPE_LOGGING_ADMIN]);
+const FEATURES_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[jd_feature_flags::SCOPE_FEATURES_ADMIN]);
+const PATCHES_REVIEW_ADMIN_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    patch_service::domain::SCOPE_PATCHES_REVIEW_ADMIN,
+  ]);
+const PATCHES_BULK_GENERATE_POLICY: middleware::mw_policy::ScopePolicy =
+  middleware::mw_policy::ScopePolicy::require(&[
+    patch_service::domain::SCOPE_PATCHES_BULK_GENERATE,
+  ]);
+const PATCH_GENERATION_RATE_LIMIT: middleware::mw_rate_limit::RateLimit =
+  middleware::mw_rate_limit::RateLimit::per_window(
+    "patch_generation",
+    20,
+    std::time::Duration::from_secs(60 * 60),
+  );
+const PACKAGE_DEPLOYMENTS_RATE_LIMIT: middleware::mw_rate_limit::RateLimit =
+  middleware::mw_rate_limit::RateLimit::per_window(
+    "package_deployments",
+    20,
+    std::time::Duration::from_secs(60 * 60),
+  );
+
 async fn health_check() -> Json<serde_json::Value> {
     Json(json!({
         "status": "healthy",
@@ -26,6 +107,19 @@ async fn health_check() -> Json<serde_json::Value> {
     }))
 }
 
+/// Readiness probe: 503 until startup warm-up has completed.
+async fn readiness_check(
+  State(app_state): State<AppState>,
+) -> (StatusCode, Json<serde_json::Value>) {
+  let readiness = app_state.readiness();
+  let (status, label) = if readiness.is_ready() {
+    (StatusCode::OK, "ready")
+  } else {
+    (StatusCode::SERVICE_UNAVAILABLE, "warming_up")
+  };
+
+  (status, Json(json!({ "status": label, "checks": readiness.checks() })))
+}
 
 pub fn v1_routes(app_state: AppState) -> Router {
   let mm = app_state.mm.as_ref().clone();
@@ -37,29 +131,497 @@ pub fn v1_routes(app_state: AppState) -> Router {
       middleware::mw_user_auth::mw_ctx_require_user_auth,
     ));
 
+  // Deprecation usage exposes every caller's identity: metering
+  // administrators only
+  let metering_routes = metering::metering_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      METERING_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Role administration: bearer token whose role set grants `roles:admin`
+  let role_admin_routes = zkpersona::auth_endpoints::role_admin_routes()
+    .route_layer(axum_middleware::from_fn_with_state(
+      ROLES_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Publishing verification keys decides which proofs verify: tokens
+  // granting `proofs:keys_admin` only
+  let proof_key_admin_routes = zkpersona::proof_key_endpoints::proof_key_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      PROOF_KEYS_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Activating a model changes every new score: tokens granting
+  // `scoring:models_admin` only
+  let scoring_model_admin_routes = scoring::scoring_model_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      SCORING_MODELS_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Verifying the ledger replays the whole chain: tokens granting
+  // `scoring:ledger_admin` only
+  let scoring_ledger_admin_routes = scoring::scoring_ledger_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      SCORING_LEDGER_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Sybil scores flag users to partners: tokens granting `scoring:sybil`
+  // only; thresholds are set per token subject
+  let scoring_sybil_routes = scoring::scoring_sybil_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      SCORING_SYBIL_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Credentials are issued to and revoked by the token subject
+  let credential_holder_routes = zkpersona::credential_endpoints::credential_holder_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Proof requests are created and read by partners: tokens granting
+  // `proofs:partner` only
+  let proof_request_partner_routes =
+    zkpersona::proof_request_endpoints::proof_request_partner_router()
+      .route_layer(axum_middleware::from_fn_with_state(
+        PROOF_REQUESTS_POLICY,
+        middleware::mw_policy::mw_require_scopes,
+      ))
+      .route_layer(axum_middleware::from_fn_with_state(
+        app_state.clone(),
+        middleware::mw_policy::mw_ctx_require_bearer,
+      ));
+
+  // Proof requests are fulfilled by the user they name, as the token subject
+  let proof_request_routes = zkpersona::proof_request_endpoints::proof_request_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Subjects read their own score history
+  let score_history_routes = zkpersona::score_history_endpoints::score_history_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Batch ingestion serves authenticated high-volume clients
+  let behavior_routes = zkpersona::behavior_endpoints::behavior_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Event schemas decide which events are accepted: tokens granting
+  // `behavior:schemas_admin` only
+  let behavior_schema_admin_routes = zkpersona::behavior_endpoints::behavior_schema_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      BEHAVIOR_SCHEMAS_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Self-serve onboarding acts on behalf of the token subject
+  let organization_routes = organizations::organization_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Webhook delivery log exposes raw payloads; keep it behind user auth
+  let webhook_delivery_routes = github::webhook_delivery_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_user_auth::mw_ctx_require_user_auth,
+    ),
+  );
+
+  // Bulk organization import fans out into many GitHub calls and analyses
+  let repository_import_routes = github::repository_import_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_user_auth::mw_ctx_require_user_auth,
+    ),
+  );
+
+  // Package detection spends the server's GitHub quota and rewrites the
+  // stored package list: repository owners and admin tokens only
+  let repository_package_admin_routes = github::repository_package_admin_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Verifying a deployment builds the repository's source, and registering
+  // one queries the chain: repository owners and admin tokens only, within
+  // an hourly budget
+  let package_deployment_admin_routes = github::package_deployment_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      (app_state.clone(), PACKAGE_DEPLOYMENTS_RATE_LIMIT),
+      middleware::mw_rate_limit::mw_rate_limit,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Rotating webhook secrets re-registers hooks: admin tokens only
+  let webhook_admin_routes = github::webhook_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      WEBHOOKS_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Cancelling or reprioritising a job affects every repository in the
+  // queue: admin tokens only
+  let analysis_job_admin_routes = github::analysis_job_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      ANALYSIS_JOBS_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Wallet linking and merges act on the token subject's account
+  let account_routes = accounts::account_router().route_layer(axum_middleware::from_fn_with_state(
+    app_state.clone(),
+    middleware::mw_policy::mw_ctx_require_bearer,
+  ));
+
+  // Notifications belong to the token subject's user
+  let notification_routes = notifications::notification_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Suppressions are attributed to the token subject
+  let vulnerability_triage_routes = vulnerabilities::vulnerability_triage_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Disclosure moves findings out of private triage: tokens granting
+  // `vulnerabilities:disclosure` only
+  let vulnerability_disclosure_routes = vulnerabilities::vulnerability_disclosure_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      VULNERABILITIES_DISCLOSURE_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Findings not yet public are listed only for tokens that may see them
+  let vulnerability_routes = vulnerabilities::vulnerability_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_optional_bearer,
+    ),
+  );
+
+  // Each candidate set costs several LLM calls and a sandbox build per
+  // candidate: signed-in callers only, within an hourly budget
+  let patch_generation_routes = patches::patch_generation_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      (app_state.clone(), PATCH_GENERATION_RATE_LIMIT),
+      middleware::mw_rate_limit::mw_rate_limit,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Bulk generation patches a whole class of a repository's findings in one
+  // go: tokens granting `patches:bulk_generate` only
+  let patch_bulk_generation_routes = patches::patch_bulk_generation_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      PATCHES_BULK_GENERATE_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Verification builds and runs the patched repository's code
+  let patch_verification_routes = patches::patch_verification_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Reviews and pull request submissions are attributed to the token subject
+  let patch_review_routes = patches::patch_review_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Assigning reviewers decides who approves a patch: tokens granting
+  // `patches:review_admin` only
+  let patch_review_admin_routes = patches::patch_review_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      PATCHES_REVIEW_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Archived analysis reports include findings still under disclosure:
+  // tokens granting `vulnerabilities:disclosure` only
+  let analysis_report_routes = repositories::analysis_report_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      VULNERABILITIES_DISCLOSURE_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Ruleset changes are attributed to the token subject
+  let repository_ruleset_routes = repositories::repository_ruleset_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Budgets cap what any repository may spend: budget administrators only,
+  // and changes and approvals are attributed to the token subject
+  let ai_budget_routes = analytics::ai_budget_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      ANALYTICS_BUDGETS_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Badges are claimed for the developer the token subject belongs to
+  let badge_claim_routes = developers::badge_claim_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Exports are attributed to, and only downloadable by, the token subject
+  let analytics_export_routes = analytics::analytics_export_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Saved queries and their reports belong to the token subject
+  let analytics_report_routes = analytics::analytics_report_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ),
+  );
+
+  // Alerts name repositories whose findings spiked: operators only
+  let analytics_alert_routes = analytics::analytics_alert_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      ANALYTICS_ALERTS_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // With an internal listener, the admin routes are served there instead
+  let public_admin_routes = match &app_state.config.internal {
+    Some(internal) if internal.public_admin != Some(true) => Router::new(),
+    _ => admin_routes(&app_state),
+  };
+
+  // Signed-in callers see the flags targeted at them, others only those on
+  // for everyone
+  let feature_routes = features::feature_router().route_layer(
+    axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_optional_bearer,
+    ),
+  );
+
+  // Recomputing rewrites current scores: model administrators only
+  let score_recompute_routes = analytics::score_recompute_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      SCORING_MODELS_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
   // Create public routes
   let public_zkpersona_routes = Router::new()
     .route("/verify", axum::routing::post(zkpersona::unified_endpoints::verify_proof))
-    .nest("/auth", zkpersona::auth_endpoints::auth_routes());
+    .merge(zkpersona::credential_endpoints::credential_router())
+    .nest("/auth", zkpersona::auth_endpoints::auth_routes().nest("/admin", role_admin_routes));
 
   Router::new()
     .nest(
       "/api/v1",
       Router::<AppState>::new()
         .route("/health", axum::routing::get(health_check))
-        .nest("/analytics", analytics::analytics_router())
-        .nest("/vulnerabilities", vulnerabilities::vulnerability_router())
-        .nest("/patches", patches::patch_router())
-        .nest("/developers", developers::developer_router())
+        .route("/ready", axum::routing::get(readiness_check))
+        .nest(
+          "/analytics",
+          analytics::analytics_router()
+            .merge(ai_budget_routes)
+            .merge(analytics_export_routes)
+            .merge(analytics_report_routes)
+            .merge(analytics_alert_routes)
+            .merge(score_recompute_routes),
+        )
+        .nest(
+          "/vulnerabilities",
+          vulnerability_routes
+            .merge(vulnerability_triage_routes)
+            .merge(vulnerability_disclosure_routes),
+        )
+        .nest(
+          "/patches",
+          patches::patch_router()
+            .merge(patch_generation_routes)
+            .merge(patch_bulk_generation_routes)
+            .merge(patch_verification_routes)
+            .merge(patch_review_routes)
+            .merge(patch_review_admin_routes),
+        )
+        .nest("/repositories", repository_ruleset_routes.merge(analysis_report_routes))
+        .nest(
+          "/developers",
+          developers::developer_router()
+            .merge(developers::developer_badge_router())
+            .merge(badge_claim_routes),
+        )
+        .nest(
+          "/scoring",
+          scoring::scoring_router()
+            .merge(scoring_ledger_admin_routes)
+            .merge(scoring_model_admin_routes)
+            .merge(scoring_sybil_routes),
+        )
+        .nest("/metering", metering_routes)
+        .nest("/organizations", organization_routes)
+        .nest("/accounts", account_routes)
+        .nest("/notifications", notification_routes)
+        .merge(public_admin_routes)
+        .nest("/features", feature_routes)
         .nest(
           "/zkpersona",
           Router::new()
             .merge(protected_zkpersona_routes)
+            .merge(proof_key_admin_routes)
+            .merge(credential_holder_routes)
+            .merge(proof_request_partner_routes)
+            .merge(proof_request_routes)
+            .merge(score_history_routes)
+            .merge(behavior_routes)
+            .merge(behavior_schema_admin_routes)
             .merge(public_zkpersona_routes),
         )
         .nest("/sui", sui::sui_router())
-        .nest("/github", github::github_router()),
+        .nest("/objects", objects::object_router())
+        .nest(
+          "/github",
+          github::github_router()
+            .merge(webhook_delivery_routes)
+            .merge(repository_import_routes)
+            .merge(repository_package_admin_routes)
+            .merge(package_deployment_admin_routes)
+            .merge(webhook_admin_routes)
+            .merge(analysis_job_admin_routes),
+        ),
     )
     .nest("/api", routes_rpc::routes(mm))
     .with_state(app_state)
 }
+
+/// Process administration under `/admin`, on the public listener unless an
+/// internal one serves it.
+fn admin_routes(app_state: &AppState) -> Router<AppState> {
+  // Log filters apply to the whole process: `logging:admin` only
+  let logging_admin_routes = admin::logging_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      LOGGING_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  // Flags change behavior for every caller: `features:admin` only
+  let feature_flag_admin_routes = admin::feature_flag_admin_router()
+    .route_layer(axum_middleware::from_fn_with_state(
+      FEATURES_ADMIN_POLICY,
+      middleware::mw_policy::mw_require_scopes,
+    ))
+    .route_layer(axum_middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::mw_policy::mw_ctx_require_bearer,
+    ));
+
+  Router::new().nest("/admin", logging_admin_routes.merge(feature_flag_admin_routes))
+}
@@ -1,4 +1,5 @@
 use crate::error::{ClientError, Error};
+use crate::middleware::mw_res_map::RequestLogPolicy;
 use crate::{middleware::mw_res_timestamp::ReqStamp, Result};
 use axum::http::{Method, Uri};
 use jd_core::ctx::Ctx;
@@ -126,8 +127,9 @@ fn is_error_response(response: &LogResponse) -> bool {
   false
 }
 
-pub async fn log_request(log_entry: LogEntry) -> Result<()> {
+pub async fn log_request(log_entry: LogEntry, policy: &RequestLogPolicy) -> Result<()> {
   let LogEntry { request, response } = log_entry;
+  let log_bodies = policy.logs_bodies(request.uri.path());
 
   let error_type = response.error.as_ref().map(|e| e.as_ref().to_string());
   let error_data = response
@@ -147,11 +149,11 @@ pub async fn log_request(log_entry: LogEntry) -> Result<()> {
   // Extract and sanitize query parameters
   let query_params = extract_query_params(&request.uri);
 
-  // Sanitize request body if present
-  let mut sanitized_request_body = request.body;
-  if let Some(ref mut body) = sanitized_request_body {
-    sanitize_value(body);
-  }
+  // Sanitize request body if present and the route's bodies may be logged
+  let sanitized_request_body = request.body.filter(|_| log_bodies).map(|mut body| {
+    sanitize_value(&mut body);
+    policy.cap_body(body)
+  });
 
   // Determine if this is an error response before moving response parts
   let is_error = is_error_response(&response);
@@ -175,7 +177,7 @@ pub async fn log_request(log_entry: LogEntry) -> Result<()> {
     // Response context
     response: ResponseContext {
       status: if is_error { "❌ error".to_string() } else { "✅ success".to_string() },
-      body: response.body,
+      body: response.body.filter(|_| log_bodies).map(|body| policy.cap_body(body)),
       size: response_size,
     },
 
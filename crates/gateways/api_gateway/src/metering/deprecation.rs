@@ -0,0 +1,170 @@
+use axum::http::{HeaderMap, HeaderValue, Method, header};
+use chrono::{NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+
+/// Path of the legacy GitHub webhook receiver, superseded by `/github/webhook`.
+pub const LEGACY_GITHUB_WEBHOOK_PATH: &str = "/api/v1/github/webhooks/github";
+
+/// An endpoint scheduled for removal.
+///
+/// Calls are answered as usual but carry `Deprecation`, `Sunset` and
+/// `Link: rel="successor-version"` headers, and are metered per caller.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecatedEndpoint {
+  /// Stable identifier used as the metering key.
+  pub id: &'static str,
+  pub method: &'static str,
+  pub path: &'static str,
+  pub deprecated_since: NaiveDate,
+  pub sunset: Option<NaiveDate>,
+  pub replacement: Option<&'static str>,
+}
+
+impl DeprecatedEndpoint {
+  pub fn new(
+    id: &'static str,
+    method: Method,
+    path: &'static str,
+    deprecated_since: NaiveDate,
+  ) -> Self {
+    Self {
+      id,
+      method: method_str(&method),
+      path,
+      deprecated_since,
+      sunset: None,
+      replacement: None,
+    }
+  }
+
+  pub fn sunset(mut self, sunset: NaiveDate) -> Self {
+    self.sunset = Some(sunset);
+    self
+  }
+
+  pub fn replacement(mut self, replacement: &'static str) -> Self {
+    self.replacement = Some(replacement);
+    self
+  }
+
+  pub fn matches(&self, method: &Method, path: &str) -> bool {
+    let path = path.strip_suffix('/').filter(|p| !p.is_empty()).unwrap_or(path);
+    self.method == method.as_str() && self.path == path
+  }
+
+  /// Days left until the sunset date; negative once it has passed.
+  pub fn days_until_sunset(&self, today: NaiveDate) -> Option<i64> {
+    self.sunset.map(|sunset| (sunset - today).num_days())
+  }
+
+  /// Add the deprecation headers: `Deprecation` (RFC 9745), `Sunset`
+  /// (RFC 8594) and a `Link` to the replacement.
+  pub fn apply_headers(&self, headers: &mut HeaderMap) {
+    let since = self.deprecated_since.and_time(NaiveTime::MIN).and_utc().timestamp();
+    if let Ok(value) = HeaderValue::from_str(&format!("@{}", since)) {
+      headers.insert("deprecation", value);
+    }
+
+    if let Some(sunset) = self.sunset {
+      let http_date = sunset.and_time(NaiveTime::MIN).and_utc().format("%a, %d %b %Y %H:%M:%S GMT");
+      if let Ok(value) = HeaderValue::from_str(&http_date.to_string()) {
+        headers.insert("sunset", value);
+      }
+    }
+
+    if let Some(replacement) = self.replacement {
+      let link = format!("<{}>; rel=\"successor-version\"", replacement);
+      if let Ok(value) = HeaderValue::from_str(&link) {
+        headers.append(header::LINK, value);
+      }
+    }
+  }
+}
+
+/// Registered deprecations. The `Default` registry is the one the gateway
+/// serves; add an entry here when announcing a deprecation and remove it
+/// together with the route.
+#[derive(Debug, Clone)]
+pub struct DeprecationRegistry {
+  endpoints: Vec<DeprecatedEndpoint>,
+}
+
+impl DeprecationRegistry {
+  pub fn empty() -> Self {
+    Self { endpoints: Vec::new() }
+  }
+
+  pub fn register(mut self, endpoint: DeprecatedEndpoint) -> Self {
+    self.endpoints.push(endpoint);
+    self
+  }
+
+  pub fn find(&self, method: &Method, path: &str) -> Option<&DeprecatedEndpoint> {
+    self.endpoints.iter().find(|endpoint| endpoint.matches(method, path))
+  }
+
+  pub fn endpoints(&self) -> &[DeprecatedEndpoint] {
+    &self.endpoints
+  }
+}
+
+impl Default for DeprecationRegistry {
+  fn default() -> Self {
+    Self::empty().register(
+      DeprecatedEndpoint::new(
+        "github_legacy_webhook",
+        Method::POST,
+        LEGACY_GITHUB_WEBHOOK_PATH,
+        date(2026, 10, 16),
+      )
+      .sunset(date(2027, 1, 15))
+      .replacement("/api/v1/github/webhook"),
+    )
+  }
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+  NaiveDate::from_ymd_opt(year, month, day).expect("valid deprecation date")
+}
+
+fn method_str(method: &Method) -> &'static str {
+  match *method {
+    Method::GET => "GET",
+    Method::POST => "POST",
+    Method::PUT => "PUT",
+    Method::PATCH => "PATCH",
+    Method::DELETE => "DELETE",
+    Method::HEAD => "HEAD",
+    Method::OPTIONS => "OPTIONS",
+    _ => "OTHER",
+  }
+}
+
+/// Today's date in UTC, for sunset arithmetic.
+pub fn today() -> NaiveDate {
+  Utc::now().date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_registry_matches_and_headers() {
+    let registry = DeprecationRegistry::default();
+
+    assert!(registry.find(&Method::POST, LEGACY_GITHUB_WEBHOOK_PATH).is_some());
+    assert!(registry.find(&Method::POST, "/api/v1/github/webhooks/github/").is_some());
+    assert!(registry.find(&Method::GET, LEGACY_GITHUB_WEBHOOK_PATH).is_none());
+    assert!(registry.find(&Method::POST, "/api/v1/github/webhook").is_none());
+
+    let endpoint = registry.find(&Method::POST, LEGACY_GITHUB_WEBHOOK_PATH).unwrap();
+    let mut headers = HeaderMap::new();
+    endpoint.apply_headers(&mut headers);
+
+    assert_eq!(headers["deprecation"], "@1792108800");
+    assert_eq!(headers["sunset"], "Fri, 15 Jan 2027 00:00:00 GMT");
+    assert_eq!(headers[header::LINK], "</api/v1/github/webhook>; rel=\"successor-version\"");
+    assert_eq!(endpoint.days_until_sunset(date(2027, 1, 5)), Some(10));
+  }
+}
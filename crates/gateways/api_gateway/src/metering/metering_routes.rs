@@ -0,0 +1,17 @@
+use axum::{extract::State, response::Json};
+use jd_core::AppState;
+
+use super::{DeprecationRegistry, DeprecationReport, UsageMeter};
+use crate::Result;
+
+/// GET /metering/deprecations
+/// Deprecated endpoints with their sunset dates and the callers (API keys,
+/// webhooks, users or IPs) still calling them.
+pub async fn deprecation_report(
+  State(app_state): State<AppState>,
+) -> Result<Json<DeprecationReport>> {
+  let meter = UsageMeter::new(app_state.redis.clone());
+  let report = meter.deprecation_report(&DeprecationRegistry::default()).await?;
+
+  Ok(Json(report))
+}
@@ -0,0 +1,19 @@
+use axum::{Router, routing::get};
+use jd_core::AppState;
+
+pub mod deprecation;
+mod metering_routes;
+pub mod usage_meter;
+
+pub use deprecation::{DeprecatedEndpoint, DeprecationRegistry};
+pub use metering_routes::*;
+pub use usage_meter::{Caller, DeprecationReport, UsageMeter};
+
+/// Grants reading who calls which endpoints.
+pub const SCOPE_METERING_ADMIN: &str = "metering:admin";
+
+/// Usage reports across every caller. `v1_routes` mounts this behind bearer
+/// auth and the `metering:admin` scope policy.
+pub fn metering_router() -> Router<AppState> {
+  Router::new().route("/deprecations", get(deprecation_report))
+}
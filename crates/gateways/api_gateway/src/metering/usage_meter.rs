@@ -0,0 +1,211 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use redis::{AsyncCommands, Client as RedisClient};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::deprecation::{DeprecatedEndpoint, DeprecationRegistry, today};
+use crate::Result;
+use crate::error::{Error, RequestContext};
+
+const API_KEY_HEADER: &str = "x-api-key";
+const GITHUB_HOOK_ID_HEADER: &str = "x-github-hook-id";
+
+/// Who made a metered call, from the most to the least specific identity.
+/// API keys are stored as a short SHA-256 fingerprint, never in clear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Caller {
+  ApiKey(String),
+  GithubHook(String),
+  User(String),
+  Ip(String),
+  Anonymous,
+}
+
+impl Caller {
+  pub fn resolve(headers: &HeaderMap, context: &RequestContext) -> Self {
+    let header = |name: &str| {
+      headers.get(name).and_then(|v| v.to_str().ok()).map(str::trim).filter(|v| !v.is_empty())
+    };
+
+    if let Some(api_key) = header(API_KEY_HEADER) {
+      return Self::ApiKey(fingerprint(api_key));
+    }
+    if let Some(hook_id) = header(GITHUB_HOOK_ID_HEADER) {
+      return Self::GithubHook(hook_id.to_string());
+    }
+    if let Some(user_id) = &context.user_id {
+      return Self::User(user_id.clone());
+    }
+    match &context.client_ip {
+      Some(ip) => Self::Ip(ip.clone()),
+      None => Self::Anonymous,
+    }
+  }
+}
+
+impl fmt::Display for Caller {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::ApiKey(fp) => write!(f, "api_key:{}", fp),
+      Self::GithubHook(id) => write!(f, "github_hook:{}", id),
+      Self::User(id) => write!(f, "user:{}", id),
+      Self::Ip(ip) => write!(f, "ip:{}", ip),
+      Self::Anonymous => write!(f, "anonymous"),
+    }
+  }
+}
+
+fn fingerprint(api_key: &str) -> String {
+  hex::encode(&Sha256::digest(api_key.as_bytes())[..8])
+}
+
+// region:    --- Report
+
+#[derive(Debug, Serialize)]
+pub struct CallerUsage {
+  pub caller: String,
+  pub calls: u64,
+  pub last_seen: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeprecatedEndpointUsage {
+  #[serde(flatten)]
+  pub endpoint: DeprecatedEndpoint,
+  pub days_until_sunset: Option<i64>,
+  pub total_calls: u64,
+  /// Callers still using the endpoint, most active first.
+  pub callers: Vec<CallerUsage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeprecationReport {
+  pub generated_at: DateTime<Utc>,
+  pub endpoints: Vec<DeprecatedEndpointUsage>,
+}
+
+// endregion: --- Report
+
+/// Per-caller call counters, kept in Redis so they survive restarts and are
+/// shared by every gateway instance.
+#[derive(Clone)]
+pub struct UsageMeter {
+  redis: Arc<RedisClient>,
+}
+
+impl UsageMeter {
+  pub fn new(redis: Arc<RedisClient>) -> Self {
+    Self { redis }
+  }
+
+  fn calls_key(endpoint_id: &str) -> String {
+    format!("metering:deprecated:{}:calls", endpoint_id)
+  }
+
+  fn last_seen_key(endpoint_id: &str) -> String {
+    format!("metering:deprecated:{}:last_seen", endpoint_id)
+  }
+
+  async fn connection(&self) -> Result<redis::aio::MultiplexedConnection> {
+    self
+      .redis
+      .get_multiplexed_async_connection()
+      .await
+      .map_err(|e| Error::RedisConnectionFailed { source: Box::new(e) })
+  }
+
+  pub async fn record_call(&self, endpoint_id: &str, caller: &Caller) -> Result<()> {
+    let mut conn = self.connection().await?;
+    let caller = caller.to_string();
+    let calls_key = Self::calls_key(endpoint_id);
+
+    redis::pipe()
+      .atomic()
+      .hincr(&calls_key, &caller, 1u64)
+      .ignore()
+      .hset(Self::last_seen_key(endpoint_id), &caller, Utc::now().timestamp())
+      .ignore()
+      .query_async::<()>(&mut conn)
+      .await
+      .map_err(|_| Error::CacheOperationFailed { key: calls_key })
+  }
+
+  pub async fn endpoint_usage(&self, endpoint_id: &str) -> Result<Vec<CallerUsage>> {
+    let mut conn = self.connection().await?;
+    let calls_key = Self::calls_key(endpoint_id);
+
+    let calls: HashMap<String, u64> = conn
+      .hgetall(&calls_key)
+      .await
+      .map_err(|_| Error::CacheOperationFailed { key: calls_key.clone() })?;
+    let last_seen: HashMap<String, i64> = conn
+      .hgetall(Self::last_seen_key(endpoint_id))
+      .await
+      .map_err(|_| Error::CacheOperationFailed { key: calls_key })?;
+
+    let mut callers: Vec<CallerUsage> = calls
+      .into_iter()
+      .map(|(caller, calls)| CallerUsage {
+        last_seen: last_seen.get(&caller).and_then(|ts| DateTime::from_timestamp(*ts, 0)),
+        caller,
+        calls,
+      })
+      .collect();
+    callers.sort_by(|a, b| b.calls.cmp(&a.calls).then_with(|| a.caller.cmp(&b.caller)));
+
+    Ok(callers)
+  }
+
+  /// Which callers still hit each registered deprecated endpoint.
+  pub async fn deprecation_report(
+    &self,
+    registry: &DeprecationRegistry,
+  ) -> Result<DeprecationReport> {
+    let today = today();
+    let mut endpoints = Vec::with_capacity(registry.endpoints().len());
+
+    for endpoint in registry.endpoints() {
+      let callers = self.endpoint_usage(endpoint.id).await?;
+      endpoints.push(DeprecatedEndpointUsage {
+        days_until_sunset: endpoint.days_until_sunset(today),
+        total_calls: callers.iter().map(|c| c.calls).sum(),
+        endpoint: endpoint.clone(),
+        callers,
+      });
+    }
+
+    Ok(DeprecationReport { generated_at: Utc::now(), endpoints })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_caller_resolution_order() {
+    let context = RequestContext {
+      user_id: Some("42".to_string()),
+      client_ip: Some("203.0.113.9".to_string()),
+      ..Default::default()
+    };
+
+    let mut headers = HeaderMap::new();
+    assert_eq!(Caller::resolve(&headers, &context).to_string(), "user:42");
+
+    headers.insert(GITHUB_HOOK_ID_HEADER, "1234".parse().unwrap());
+    assert_eq!(Caller::resolve(&headers, &context).to_string(), "github_hook:1234");
+
+    headers.insert(API_KEY_HEADER, "sk_live_secret".parse().unwrap());
+    let caller = Caller::resolve(&headers, &context).to_string();
+    assert!(caller.starts_with("api_key:"));
+    assert!(!caller.contains("sk_live_secret"));
+    assert_eq!(caller.len(), "api_key:".len() + 16);
+
+    let anonymous = RequestContext::default();
+    assert_eq!(Caller::resolve(&HeaderMap::new(), &anonymous), Caller::Anonymous);
+  }
+}
@@ -0,0 +1,68 @@
+use auth_service::application::use_cases::FlagSubjectUseCase;
+use auth_service::domain::Claims;
+use auth_service::infrastructure::AccountRepositoryImpl;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use jd_core::AppState;
+use jd_feature_flags::{FeatureFlags, FlagSubject};
+use tracing::warn;
+
+use crate::error::Error;
+use crate::Result;
+
+/// Feature flags as they apply to the caller, for handlers gating new
+/// behavior:
+///
+/// ```ignore
+/// async fn handler(features: Features) -> Result<Json<Value>> {
+///   if features.is_enabled("llm-v2-prompts").await { ... }
+/// }
+/// ```
+///
+/// Behind bearer auth the caller is targeted by their user id, or their
+/// token subject when it has no user, and by their organizations; without
+/// it they are anonymous and only see flags rolled out to everyone.
+#[derive(Clone)]
+pub struct Features {
+  flags: FeatureFlags,
+  subject: FlagSubject,
+}
+
+impl Features {
+  pub async fn is_enabled(&self, key: &str) -> bool {
+    self.flags.is_enabled(key, &self.subject).await
+  }
+
+  /// The keys of every flag on for the caller.
+  pub async fn enabled(&self) -> Result<Vec<String>> {
+    Ok(self.flags.enabled_for(&self.subject).await?)
+  }
+
+  pub fn subject(&self) -> &FlagSubject {
+    &self.subject
+  }
+}
+
+impl FromRequestParts<AppState> for Features {
+  type Rejection = Error;
+
+  async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self> {
+    let subject = match parts.extensions.get::<Claims>() {
+      Some(claims) => subject_for(state, claims).await,
+      None => FlagSubject::anonymous(),
+    };
+    Ok(Self { flags: state.flags.clone(), subject })
+  }
+}
+
+/// The caller's user and organizations. A failed lookup targets the caller
+/// by token subject alone rather than failing the request.
+async fn subject_for(state: &AppState, claims: &Claims) -> FlagSubject {
+  FlagSubjectUseCase::new(AccountRepositoryImpl::new(state.clone()), state.flags.clone())
+    .subject(claims)
+    .await
+    .unwrap_or_else(|e| {
+      warn!(subject = %claims.address, "Feature flag subject lookup failed: {e}");
+      FlagSubject::user(claims.address.clone())
+    })
+}
@@ -1,6 +1,12 @@
+pub mod feature_flags;
 pub mod mw_auth;
+pub mod mw_deprecation;
+pub mod mw_policy;
+pub mod mw_rate_limit;
+pub mod mw_readiness;
 pub mod mw_request_context;
 pub mod mw_res_map;
 pub mod mw_res_timestamp;
+pub mod mw_security;
 pub mod mw_user_auth;
 pub mod pagination;
@@ -0,0 +1,59 @@
+use axum::{
+  extract::{Request, State},
+  middleware::Next,
+  response::Response,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::error::RequestContext;
+use crate::metering::{Caller, DeprecationRegistry, UsageMeter};
+
+pub struct DeprecationTracker {
+  registry: DeprecationRegistry,
+  meter: UsageMeter,
+}
+
+impl DeprecationTracker {
+  pub fn new(registry: DeprecationRegistry, meter: UsageMeter) -> Self {
+    Self { registry, meter }
+  }
+}
+
+/// Annotate responses of deprecated endpoints with `Deprecation`, `Sunset`
+/// and `Link` headers and meter the call against its caller.
+///
+/// Metering runs off the request path; a Redis failure never fails the call.
+pub async fn mw_deprecation(
+  State(tracker): State<Arc<DeprecationTracker>>,
+  req: Request,
+  next: Next,
+) -> Response {
+  let Some(endpoint) = tracker.registry.find(req.method(), req.uri().path()).cloned() else {
+    return next.run(req).await;
+  };
+
+  let context = req.extensions().get::<RequestContext>().cloned().unwrap_or_default();
+  let caller = Caller::resolve(req.headers(), &context);
+
+  warn!(
+      target: "deprecation",
+      endpoint = endpoint.id,
+      caller = %caller,
+      sunset = ?endpoint.sunset,
+      request_id = %context.request_id.as_deref().unwrap_or("unknown"),
+      "Deprecated endpoint called"
+  );
+
+  let meter = tracker.meter.clone();
+  let endpoint_id = endpoint.id;
+  tokio::spawn(async move {
+    if let Err(e) = meter.record_call(endpoint_id, &caller).await {
+      warn!("Failed to meter deprecated call to {}: {}", endpoint_id, e);
+    }
+  });
+
+  let mut response = next.run(req).await;
+  endpoint.apply_headers(response.headers_mut());
+  response
+}
@@ -0,0 +1,111 @@
+use auth_service::domain::JwtManager;
+use axum::{
+  body::Body,
+  extract::State,
+  http::{Request, header::AUTHORIZATION},
+  middleware::Next,
+  response::Response,
+};
+use jd_core::{AppState, ctx::Ctx};
+use tracing::warn;
+
+use crate::Result;
+use crate::error::{Error, RequestContext};
+use crate::middleware::mw_auth::CtxExtError;
+use crate::middleware::mw_user_auth::ctx_user_id;
+
+/// Authenticate a `Bearer` access token and put a `Ctx` carrying the token's
+/// roles and scopes, plus the decoded claims, into the request extensions.
+pub async fn mw_ctx_require_bearer(
+  State(app_state): State<AppState>,
+  mut req: Request<Body>,
+  next: Next,
+) -> Result<Response> {
+  let auth_header = req
+    .headers()
+    .get(AUTHORIZATION)
+    .and_then(|h| h.to_str().ok())
+    .ok_or_else(|| Error::jwt_failed("missing bearer token"))?;
+  let token = JwtManager::extract_token_from_header(auth_header)
+    .map_err(|e| Error::jwt_failed(e.error))?;
+
+  let claims = JwtManager::new(app_state.config.auth_jwt_secret.clone())
+    .validate_token(token)
+    .map_err(|e| Error::jwt_failed(e.error))?;
+  if claims.token_type != "access" {
+    return Err(Error::jwt_failed("not an access token"));
+  }
+
+  let ctx = Ctx::new(ctx_user_id(&claims.address))
+    .map_err(|e| Error::CtxExt(CtxExtError::CtxCreateFail(e.to_string())))?
+    .with_roles(claims.roles.clone(), claims.scopes.clone());
+
+  tracing::Span::current().record("user_id", claims.address.as_str());
+  let context = req.extensions_mut().get_mut::<RequestContext>().map(|context| {
+    context.user_id = Some(claims.address.clone());
+    context.clone()
+  });
+  req.extensions_mut().insert(ctx);
+  req.extensions_mut().insert(claims);
+
+  // Errors raised further in read the task-local context, so they carry the
+  // caller too
+  match context {
+    Some(context) => Ok(context.run_with_context(next.run(req)).await),
+    None => Ok(next.run(req).await),
+  }
+}
+
+/// Authenticate like [`mw_ctx_require_bearer`] when the request carries an
+/// `Authorization` header; requests without one pass through anonymously, for
+/// routes that show more to some callers.
+pub async fn mw_ctx_optional_bearer(
+  State(app_state): State<AppState>,
+  req: Request<Body>,
+  next: Next,
+) -> Result<Response> {
+  if req.headers().contains_key(AUTHORIZATION) {
+    return mw_ctx_require_bearer(State(app_state), req, next).await;
+  }
+  Ok(next.run(req).await)
+}
+
+/// Scopes a route group requires; every listed scope must be granted.
+#[derive(Debug, Clone, Copy)]
+pub struct ScopePolicy {
+  required: &'static [&'static str],
+}
+
+impl ScopePolicy {
+  pub const fn require(required: &'static [&'static str]) -> Self {
+    Self { required }
+  }
+}
+
+/// Evaluate a [`ScopePolicy`] against the `Ctx` role set. Must run after an
+/// authenticating middleware such as [`mw_ctx_require_bearer`].
+pub async fn mw_require_scopes(
+  State(policy): State<ScopePolicy>,
+  req: Request<Body>,
+  next: Next,
+) -> Result<Response> {
+  let ctx = req
+    .extensions()
+    .get::<Ctx>()
+    .ok_or(Error::CtxExt(CtxExtError::CtxNotInRequestExt))?;
+
+  if let Some(missing) = policy.required.iter().find(|scope| !ctx.has_scope(scope)) {
+    warn!(
+        target: "security_audit",
+        action = "denied",
+        scope = %missing,
+        roles = ?ctx.roles(),
+        method = %req.method(),
+        path = %req.uri().path(),
+        "Request denied by scope policy"
+    );
+    return Err(Error::insufficient_permissions(*missing));
+  }
+
+  Ok(next.run(req).await)
+}
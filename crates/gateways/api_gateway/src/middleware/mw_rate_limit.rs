@@ -0,0 +1,65 @@
+use auth_service::domain::Claims;
+use axum::{body::Body, extract::State, http::Request, middleware::Next, response::Response};
+use jd_core::AppState;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::Result;
+use crate::error::Error;
+use crate::middleware::mw_auth::CtxExtError;
+
+/// Requests each caller may make to a route group per window, for routes
+/// that spend LLM calls or sandbox builds.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+  /// Names the route group in counter keys and the audit log.
+  name: &'static str,
+  limit: u32,
+  window: Duration,
+}
+
+impl RateLimit {
+  pub const fn per_window(name: &'static str, limit: u32, window: Duration) -> Self {
+    Self { name, limit, window }
+  }
+}
+
+/// Count the request against the token subject's [`RateLimit`] in a fixed
+/// window kept in Redis. Must run after [`mw_ctx_require_bearer`]. Requests
+/// are refused while Redis is unavailable, since nothing else bounds what
+/// these routes spend.
+///
+/// [`mw_ctx_require_bearer`]: crate::middleware::mw_policy::mw_ctx_require_bearer
+pub async fn mw_rate_limit(
+  State((app_state, limit)): State<(AppState, RateLimit)>,
+  req: Request<Body>,
+  next: Next,
+) -> Result<Response> {
+  let caller = req
+    .extensions()
+    .get::<Claims>()
+    .map(|claims| claims.address.clone())
+    .ok_or(Error::CtxExt(CtxExtError::CtxNotInRequestExt))?;
+
+  let key = format!("rate_limit:{}:{}", limit.name, caller);
+  let count = app_state
+    .cache
+    .increment(&key, 1, Some(limit.window))
+    .await
+    .map_err(|e| {
+      warn!(route_group = limit.name, "Rate limit counter unavailable: {e}");
+      Error::service_unavailable("rate_limiter")
+    })?;
+  if count > i64::from(limit.limit) {
+    warn!(
+        target: "security_audit",
+        action = "rate_limited",
+        route_group = limit.name,
+        caller = %caller,
+        "Request denied by rate limit"
+    );
+    return Err(Error::rate_limited(caller, limit.limit, format!("{}s", limit.window.as_secs())));
+  }
+
+  Ok(next.run(req).await)
+}
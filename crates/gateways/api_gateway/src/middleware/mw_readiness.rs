@@ -0,0 +1,25 @@
+use crate::Result;
+use crate::error::Error;
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use jd_core::AppState;
+
+/// Paths that must answer while the instance is still warming up.
+const PROBE_PATHS: &[&str] = &["/api/v1/health", "/api/v1/ready"];
+
+/// Reject traffic with 503 + `Retry-After` until startup warm-up has finished,
+/// so cold connections and caches never surface as 500s on the first requests.
+pub async fn mw_readiness_gate(
+  State(app_state): State<AppState>,
+  req: Request<Body>,
+  next: Next,
+) -> Result<Response> {
+  if !app_state.readiness().is_ready() && !PROBE_PATHS.contains(&req.uri().path()) {
+    return Err(Error::service_unavailable("api_gateway"));
+  }
+
+  Ok(next.run(req).await)
+}
@@ -1,82 +1,102 @@
+use arc_swap::ArcSwap;
 use axum::{
-  extract::{ConnectInfo, Request},
+  extract::{ConnectInfo, Request, State},
   http::HeaderMap,
   middleware::Next,
   response::Response,
 };
-use std::net::SocketAddr;
-use tracing::{info, instrument};
+use std::{
+  net::{IpAddr, SocketAddr},
+  sync::Arc,
+};
+use tracing::{Instrument, field, info, info_span};
 
 use crate::error::RequestContext;
+use crate::middleware::mw_security::{IpNet, SecurityGuard};
 
-/// Middleware to extract and setup RequestContext for the entire request lifecycle
-#[instrument(skip(req, next), fields(request_id, trace_id))]
+/// Middleware to extract and setup RequestContext for the entire request lifecycle.
+/// The client IP is resolved against the guard's trusted proxies.
 pub async fn mw_request_context(
+  State(guard): State<Arc<ArcSwap<SecurityGuard>>>,
   ConnectInfo(addr): ConnectInfo<SocketAddr>,
   mut req: Request,
   next: Next,
 ) -> Response {
   // Extract client IP
-  let client_ip = extract_client_ip(req.headers(), addr);
+  let client_ip = extract_client_ip(req.headers(), addr, guard.load().trusted_proxies());
 
   // Create RequestContext from headers and client info
   let mut context = RequestContext::from_headers(req.headers());
-  context = context.with_client_ip(client_ip);
+  context = context.with_client_ip(client_ip.to_string());
 
-  // Set tracing fields
   let request_id = context.request_id.clone().unwrap_or_default();
   let trace_id = context.trace_id.clone().unwrap_or_default();
 
-  tracing::Span::current()
-    .record("request_id", &request_id)
-    .record("trace_id", &trace_id);
-
-  info!(
-      request_id = %request_id,
-      trace_id = %trace_id,
-      client_ip = %context.client_ip.as_deref().unwrap_or("unknown"),
-      user_agent = %context.user_agent.as_deref().unwrap_or("unknown"),
-      method = %req.method(),
-      path = %req.uri().path(),
-      "Request started"
+  // Everything logged while serving the request, services included, carries
+  // its ids. Targeted apart from this module so the span survives the
+  // module's quieter filter; `user_id` is filled in once the caller is known.
+  let span = info_span!(
+    target: "api_gateway::request",
+    "request",
+    request_id = %request_id,
+    trace_id = %trace_id,
+    user_id = field::Empty,
+    method = %req.method(),
+    path = %req.uri().path(),
   );
 
+  span.in_scope(|| {
+    info!(
+        request_id = %request_id,
+        trace_id = %trace_id,
+        client_ip = %context.client_ip.as_deref().unwrap_or("unknown"),
+        user_agent = %context.user_agent.as_deref().unwrap_or("unknown"),
+        method = %req.method(),
+        path = %req.uri().path(),
+        "Request started"
+    )
+  });
+
   // Store context in request extensions for other middleware to access
   req.extensions_mut().insert(context.clone());
 
   // Run the rest of the request with the context in task-local storage
-  context.run_with_context(next.run(req)).await
+  context.run_with_context(next.run(req)).instrument(span).await
 }
 
-/// Extract client IP from various headers with fallback to socket address
-fn extract_client_ip(headers: &HeaderMap, socket_addr: SocketAddr) -> String {
-  // Try various headers in order of preference
-  let possible_headers = [
-    "x-forwarded-for",     // Most common proxy header
-    "x-real-ip",           // Nginx
-    "cf-connecting-ip",    // Cloudflare
-    "x-cluster-client-ip", // GCP Load Balancer
-    "x-forwarded",         // General forwarded header
-    "forwarded-for",       // Alternative
-    "forwarded",           // RFC 7239
-  ];
-
-  for header_name in possible_headers {
-    if let Some(header_value) = headers.get(header_name) {
-      if let Ok(header_str) = header_value.to_str() {
-        // Handle comma-separated IPs (take the first one)
-        if let Some(first_ip) = header_str.split(',').next() {
-          let trimmed_ip = first_ip.trim();
-          if !trimmed_ip.is_empty() && trimmed_ip != "unknown" {
-            return trimmed_ip.to_string();
-          }
-        }
-      }
-    }
+/// The client's address. It is the peer's, unless the peer is a trusted
+/// proxy: then `X-Forwarded-For` is read right to left, since each proxy
+/// appends the address it got the request from, and the first hop that is not
+/// a trusted proxy is the client. Whatever the client wrote into the header
+/// itself sits left of that and is never reached. Headers such as `X-Real-IP`
+/// are not read, as no proxy is known to overwrite them.
+fn extract_client_ip(headers: &HeaderMap, peer: SocketAddr, trusted_proxies: &[IpNet]) -> IpAddr {
+  let is_trusted = |ip: IpAddr| trusted_proxies.iter().any(|net| net.contains(ip));
+
+  let mut client = peer.ip();
+  if !is_trusted(client) {
+    return client;
   }
 
-  // Fallback to socket address
-  socket_addr.ip().to_string()
+  // Repeated headers count as one list, in the order received
+  let hops: Vec<&str> = headers
+    .get_all("x-forwarded-for")
+    .iter()
+    .filter_map(|value| value.to_str().ok())
+    .flat_map(|value| value.split(','))
+    .map(str::trim)
+    .collect();
+  for hop in hops.into_iter().rev() {
+    // A hop that is not an address ends the chain at the last proxy
+    let Ok(ip) = hop.parse::<IpAddr>() else {
+      break;
+    };
+    client = ip;
+    if !is_trusted(ip) {
+      break;
+    }
+  }
+  client
 }
 
 /// Helper to get RequestContext from request extensions
@@ -93,22 +113,25 @@ mod tests {
   #[test]
   fn test_extract_client_ip_from_headers() {
     let mut headers = HeaderMap::new();
-    let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)), 8080);
+    let proxy = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), 8080);
+    let trusted = [IpNet::parse("10.0.0.0/8").unwrap()];
 
-    // Test x-forwarded-for header
+    // Without trusted proxies the header is ignored
     headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.195"));
-    assert_eq!(extract_client_ip(&headers, socket_addr), "203.0.113.195");
+    assert_eq!(extract_client_ip(&headers, proxy, &[]).to_string(), "10.0.0.2");
+    assert_eq!(extract_client_ip(&headers, proxy, &trusted).to_string(), "203.0.113.195");
+
+    // A spoofed entry on the left loses to the right-most untrusted hop
+    headers.insert("x-forwarded-for", HeaderValue::from_static("1.2.3.4, 198.51.100.7, 10.0.0.7"));
+    assert_eq!(extract_client_ip(&headers, proxy, &trusted).to_string(), "198.51.100.7");
 
-    // Test comma-separated IPs (should take first)
-    headers.insert(
-      "x-forwarded-for",
-      HeaderValue::from_static("203.0.113.195, 70.41.3.18, 150.172.238.178"),
-    );
-    assert_eq!(extract_client_ip(&headers, socket_addr), "203.0.113.195");
+    // An untrusted peer is the client, whatever it sends
+    let direct = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(203, 0, 113, 9)), 8080);
+    assert_eq!(extract_client_ip(&headers, direct, &trusted).to_string(), "203.0.113.9");
 
-    // Test fallback to socket address
+    // Fallback to socket address
     headers.clear();
-    assert_eq!(extract_client_ip(&headers, socket_addr), "192.168.1.1");
+    assert_eq!(extract_client_ip(&headers, proxy, &trusted).to_string(), "10.0.0.2");
   }
 
   #[test]
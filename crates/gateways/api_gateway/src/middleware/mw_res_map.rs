@@ -5,18 +5,116 @@ use crate::{
 };
 use axum::body::to_bytes;
 use axum::{
+  extract::State,
   http::{Method, StatusCode, Uri},
   response::{IntoResponse, Response},
   Json,
 };
+use jd_utils::config::RequestLogConfig;
 use jd_utils::time::{format_time, now_utc};
+use rand::Rng;
 use serde_json::{json, Value};
-use tracing::{error, info};
+use std::sync::Arc;
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use super::{mw_auth::CtxW, mw_res_timestamp::ReqStamp};
 use crate::error::RequestContext;
 
+const DEFAULT_MAX_BODY_BYTES: usize = 4096;
+
+/// Which requests the request log records and how much of their bodies.
+/// Failed requests are always recorded, so sampling only thins out the
+/// successful ones.
+#[derive(Debug, Clone)]
+pub struct RequestLogPolicy {
+  sample_rate: f64,
+  /// Longest prefix first.
+  route_sample_rates: Vec<(String, f64)>,
+  max_body_bytes: usize,
+  /// None when every route's bodies may be logged.
+  body_routes: Option<Vec<String>>,
+}
+
+impl Default for RequestLogPolicy {
+  fn default() -> Self {
+    Self::from_config(None)
+  }
+}
+
+impl RequestLogPolicy {
+  pub fn from_config(config: Option<&RequestLogConfig>) -> Self {
+    let sample_rate = config.and_then(|c| c.sample_rate).unwrap_or(1.0).clamp(0.0, 1.0);
+    let mut route_sample_rates: Vec<(String, f64)> = config
+      .and_then(|c| c.route_sample_rates.as_deref())
+      .map(|list| split_list(list).filter_map(parse_route_rate).collect())
+      .unwrap_or_default();
+    route_sample_rates.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+
+    let body_routes = match config.and_then(|c| c.body_routes.as_deref()) {
+      None => None,
+      Some(list) if split_list(list).any(|route| route == "*") => None,
+      Some(list) => Some(split_list(list).map(str::to_string).collect()),
+    };
+
+    Self {
+      sample_rate,
+      route_sample_rates,
+      max_body_bytes: config.and_then(|c| c.max_body_bytes).unwrap_or(DEFAULT_MAX_BODY_BYTES),
+      body_routes,
+    }
+  }
+
+  fn sample_rate(&self, path: &str) -> f64 {
+    self
+      .route_sample_rates
+      .iter()
+      .find(|(prefix, _)| path.starts_with(prefix.as_str()))
+      .map_or(self.sample_rate, |(_, rate)| *rate)
+  }
+
+  /// Whether a request to `path` is recorded this time.
+  fn samples(&self, path: &str, failed: bool) -> bool {
+    let rate = self.sample_rate(path);
+    failed || rate >= 1.0 || (rate > 0.0 && rand::thread_rng().gen_bool(rate))
+  }
+
+  pub(crate) fn logs_bodies(&self, path: &str) -> bool {
+    self
+      .body_routes
+      .as_ref()
+      .is_none_or(|routes| routes.iter().any(|route| path.starts_with(route.as_str())))
+  }
+
+  /// `body` as is when it fits, otherwise a preview of its start.
+  pub(crate) fn cap_body(&self, body: Value) -> Value {
+    let serialized = body.to_string();
+    if serialized.len() <= self.max_body_bytes {
+      return body;
+    }
+    let mut end = self.max_body_bytes;
+    while !serialized.is_char_boundary(end) {
+      end -= 1;
+    }
+    json!({ "truncated": true, "size": serialized.len(), "preview": &serialized[..end] })
+  }
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+  list.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn parse_route_rate(entry: &str) -> Option<(String, f64)> {
+  let parsed = entry.split_once('=').and_then(|(prefix, rate)| {
+    let rate = rate.trim().parse::<f64>().ok().filter(|rate| (0.0..=1.0).contains(rate))?;
+    Some((prefix.trim().to_string(), rate))
+  });
+  if parsed.is_none() {
+    warn!("Ignoring invalid route_sample_rates entry: '{}'", entry);
+  }
+  parsed
+}
+
 /// Standard response structure for all API responses
 #[derive(Debug)]
 struct ApiResponse {
@@ -132,7 +230,9 @@ fn process_unknown_error_response(uuid: Uuid, status_code: StatusCode) -> Proces
 }
 
 /// Log the request based on success/error status
+#[allow(clippy::too_many_arguments)]
 async fn log_request_response(
+  policy: &RequestLogPolicy,
   uri: Uri,
   req_method: Method,
   req_stamp: ReqStamp,
@@ -152,7 +252,7 @@ async fn log_request_response(
 
   let log_entry = LogEntry::new(request_log, log_response);
 
-  if let Err(e) = log_request(log_entry).await {
+  if let Err(e) = log_request(log_entry, policy).await {
     error!("Failed to log request: {}", e);
   }
 }
@@ -177,6 +277,7 @@ fn log_response_message(req_method: &Method, uri: &Uri, processed: &ProcessedRes
 }
 
 pub async fn mw_map_response(
+  State(policy): State<Arc<RequestLogPolicy>>,
   ctx: Result<CtxW>,
   uri: Uri,
   req_method: Method,
@@ -208,11 +309,26 @@ pub async fn mw_map_response(
     }
   };
 
-  // Log the response message
-  log_response_message(&req_method, &uri, &processed);
+  let failed = processed.client_error.is_some()
+    || processed.status_code.is_client_error()
+    || processed.status_code.is_server_error();
+  if policy.samples(uri.path(), failed) {
+    // Log the response message
+    log_response_message(&req_method, &uri, &processed);
 
-  // Log the request details
-  log_request_response(uri, req_method, req_stamp, ctx, request_body, &processed, web_error).await;
+    // Log the request details
+    log_request_response(
+      &policy,
+      uri,
+      req_method,
+      req_stamp,
+      ctx,
+      request_body,
+      &processed,
+      web_error,
+    )
+    .await;
+  }
 
   // Return the processed response
   (processed.status_code, Json(processed.body)).into_response()
@@ -246,4 +362,26 @@ mod tests {
     assert!(response.data.is_none());
     assert_eq!(response.error, Some(error_data));
   }
+
+  #[test]
+  fn request_log_policy_samples_routes_and_caps_bodies() {
+    let policy = RequestLogPolicy::from_config(Some(&RequestLogConfig {
+      sample_rate: Some(1.0),
+      route_sample_rates: Some("/api/v1/health=0, /api/v1/health/deep=1,bad".to_string()),
+      max_body_bytes: Some(16),
+      body_routes: Some("/api/v1/developers".to_string()),
+    }));
+
+    assert!(!policy.samples("/api/v1/health", false));
+    assert!(policy.samples("/api/v1/health", true));
+    assert!(policy.samples("/api/v1/health/deep", false));
+    assert!(policy.logs_bodies("/api/v1/developers/42"));
+    assert!(!policy.logs_bodies("/api/v1/analytics"));
+
+    let small = json!({"a": 1});
+    assert_eq!(policy.cap_body(small.clone()), small);
+    let capped = policy.cap_body(json!({"message": "a body well past the limit"}));
+    assert_eq!(capped["truncated"], json!(true));
+    assert_eq!(capped["preview"].as_str().unwrap().len(), 16);
+  }
 }
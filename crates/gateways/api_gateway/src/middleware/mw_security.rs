@@ -0,0 +1,410 @@
+use arc_swap::ArcSwap;
+use axum::{
+  extract::{ConnectInfo, Request, State},
+  middleware::Next,
+  response::Response,
+};
+use jd_utils::config::SecurityConfig;
+use std::{
+  collections::HashMap,
+  net::{IpAddr, SocketAddr},
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+use tracing::warn;
+
+use crate::Result;
+use crate::error::{Error, RequestContext};
+
+const DEFAULT_MAX_REQUESTS_PER_MINUTE: u32 = 600;
+const DEFAULT_BLOCK_DURATION_SECS: u64 = 300;
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+/// Prune idle per-IP windows once the tracker grows past this many entries.
+const MAX_TRACKED_IPS: usize = 10_000;
+
+const DEFAULT_BLOCKED_USER_AGENTS: &[&str] = &[
+  "sqlmap", "nikto", "nmap", "masscan", "zgrab", "nuclei", "gobuster", "dirbuster", "wpscan",
+  "acunetix",
+];
+
+/// Gateway-level request screening: IP allow/deny lists, user-agent heuristics
+/// and per-IP request-rate anomaly detection.
+///
+/// Every rejection emits a structured event on the `security_audit` target.
+/// The guard is swapped for a new one when the config is reloaded.
+pub async fn mw_security(
+  State(guard): State<Arc<ArcSwap<SecurityGuard>>>,
+  ConnectInfo(addr): ConnectInfo<SocketAddr>,
+  req: Request,
+  next: Next,
+) -> Result<Response> {
+  let guard = guard.load();
+  if !guard.enabled {
+    return Ok(next.run(req).await);
+  }
+
+  let context = req.extensions().get::<RequestContext>().cloned().unwrap_or_default();
+  let client_ip = context
+    .client_ip
+    .as_deref()
+    .and_then(|ip| ip.parse::<IpAddr>().ok())
+    .unwrap_or_else(|| addr.ip());
+
+  if let Err(rule) = guard.check(client_ip, context.user_agent.as_deref()) {
+    audit_blocked(&rule, client_ip, &context, &req);
+    return Err(rule.into_error(client_ip));
+  }
+
+  Ok(next.run(req).await)
+}
+
+fn audit_blocked(rule: &SecurityRule, client_ip: IpAddr, context: &RequestContext, req: &Request) {
+  warn!(
+      target: "security_audit",
+      action = "blocked",
+      rule = rule.as_str(),
+      detail = %rule.detail(),
+      client_ip = %client_ip,
+      user_agent = %context.user_agent.as_deref().unwrap_or("unknown"),
+      method = %req.method(),
+      path = %req.uri().path(),
+      request_id = %context.request_id.as_deref().unwrap_or("unknown"),
+      "Request blocked by security policy"
+  );
+}
+
+// region:    --- Security Guard
+
+/// Rule that caused a request to be rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityRule {
+  IpDenied,
+  IpNotAllowed,
+  BlockedUserAgent(String),
+  MissingUserAgent,
+  RateAnomaly { limit: u32, blocked_for_secs: u64 },
+}
+
+impl SecurityRule {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::IpDenied => "ip_denylist",
+      Self::IpNotAllowed => "ip_allowlist",
+      Self::BlockedUserAgent(_) => "blocked_user_agent",
+      Self::MissingUserAgent => "missing_user_agent",
+      Self::RateAnomaly { .. } => "rate_anomaly",
+    }
+  }
+
+  fn detail(&self) -> String {
+    match self {
+      Self::BlockedUserAgent(pattern) => format!("user agent matched '{pattern}'"),
+      Self::RateAnomaly { limit, blocked_for_secs } => {
+        format!("exceeded {limit} requests/min, blocked for {blocked_for_secs}s")
+      }
+      _ => String::new(),
+    }
+  }
+
+  fn into_error(self, client_ip: IpAddr) -> Error {
+    match self {
+      Self::IpDenied | Self::IpNotAllowed => {
+        Error::SecurityPolicyViolation { policy: self.as_str().to_string() }
+      }
+      Self::BlockedUserAgent(_) | Self::MissingUserAgent => {
+        Error::suspicious_request(self.as_str())
+      }
+      Self::RateAnomaly { limit, .. } => Error::rate_limited(client_ip.to_string(), limit, "1m"),
+    }
+  }
+}
+
+#[derive(Debug)]
+struct RateWindow {
+  started_at: Instant,
+  count: u32,
+  blocked_until: Option<Instant>,
+}
+
+pub struct SecurityGuard {
+  enabled: bool,
+  allowlist: Vec<IpNet>,
+  denylist: Vec<IpNet>,
+  blocked_user_agents: Vec<String>,
+  block_empty_user_agent: bool,
+  max_requests_per_minute: u32,
+  block_duration: Duration,
+  trusted_proxies: Vec<IpNet>,
+  windows: Mutex<HashMap<IpAddr, RateWindow>>,
+}
+
+impl SecurityGuard {
+  pub fn from_config(config: Option<&SecurityConfig>) -> Self {
+    let Some(config) = config else {
+      return Self::disabled();
+    };
+
+    let blocked_user_agents = match config.blocked_user_agents.as_deref() {
+      Some(list) => split_list(list).map(|ua| ua.to_ascii_lowercase()).collect(),
+      None => DEFAULT_BLOCKED_USER_AGENTS.iter().map(|ua| ua.to_string()).collect(),
+    };
+
+    Self {
+      enabled: config.enable.unwrap_or(true),
+      allowlist: parse_ip_list(config.ip_allowlist.as_deref(), "ip_allowlist"),
+      denylist: parse_ip_list(config.ip_denylist.as_deref(), "ip_denylist"),
+      blocked_user_agents,
+      block_empty_user_agent: config.block_empty_user_agent.unwrap_or(false),
+      max_requests_per_minute: config
+        .max_requests_per_minute
+        .unwrap_or(DEFAULT_MAX_REQUESTS_PER_MINUTE),
+      block_duration: Duration::from_secs(
+        config.block_duration_secs.unwrap_or(DEFAULT_BLOCK_DURATION_SECS),
+      ),
+      trusted_proxies: parse_ip_list(config.trusted_proxies.as_deref(), "trusted_proxies"),
+      windows: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// A guard with the rules of `config` that carries on the rate windows of
+  /// this one, so a reload neither resets counts nor lifts blocks.
+  pub fn reloaded(&self, config: Option<&SecurityConfig>) -> Self {
+    let mut guard = Self::from_config(config);
+    let mut windows = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard.windows = Mutex::new(std::mem::take(&mut *windows));
+    guard
+  }
+
+  pub fn disabled() -> Self {
+    Self {
+      enabled: false,
+      allowlist: Vec::new(),
+      denylist: Vec::new(),
+      blocked_user_agents: Vec::new(),
+      block_empty_user_agent: false,
+      max_requests_per_minute: DEFAULT_MAX_REQUESTS_PER_MINUTE,
+      block_duration: Duration::from_secs(DEFAULT_BLOCK_DURATION_SECS),
+      trusted_proxies: Vec::new(),
+      windows: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Proxies whose `X-Forwarded-For` is believed. Applies whether or not
+  /// the checks are enabled.
+  pub fn trusted_proxies(&self) -> &[IpNet] {
+    &self.trusted_proxies
+  }
+
+  /// Run every check in order of cost; the rate tracker is only touched for
+  /// requests that passed the static rules.
+  pub fn check(
+    &self,
+    ip: IpAddr,
+    user_agent: Option<&str>,
+  ) -> std::result::Result<(), SecurityRule> {
+    if self.denylist.iter().any(|net| net.contains(ip)) {
+      return Err(SecurityRule::IpDenied);
+    }
+    if !self.allowlist.is_empty() && !self.allowlist.iter().any(|net| net.contains(ip)) {
+      return Err(SecurityRule::IpNotAllowed);
+    }
+    self.check_user_agent(user_agent)?;
+    self.check_rate(ip, Instant::now())
+  }
+
+  fn check_user_agent(&self, user_agent: Option<&str>) -> std::result::Result<(), SecurityRule> {
+    let user_agent = user_agent.map(str::trim).unwrap_or_default();
+    if user_agent.is_empty() {
+      return if self.block_empty_user_agent { Err(SecurityRule::MissingUserAgent) } else { Ok(()) };
+    }
+
+    let lowered = user_agent.to_ascii_lowercase();
+    match self.blocked_user_agents.iter().find(|pattern| lowered.contains(pattern.as_str())) {
+      Some(pattern) => Err(SecurityRule::BlockedUserAgent(pattern.clone())),
+      None => Ok(()),
+    }
+  }
+
+  fn check_rate(&self, ip: IpAddr, now: Instant) -> std::result::Result<(), SecurityRule> {
+    let rate_anomaly = SecurityRule::RateAnomaly {
+      limit: self.max_requests_per_minute,
+      blocked_for_secs: self.block_duration.as_secs(),
+    };
+
+    let mut windows = self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if windows.len() > MAX_TRACKED_IPS {
+      windows.retain(|_, w| {
+        now.duration_since(w.started_at) < RATE_WINDOW || w.blocked_until.is_some_and(|t| t > now)
+      });
+    }
+
+    let window =
+      windows.entry(ip).or_insert(RateWindow { started_at: now, count: 0, blocked_until: None });
+
+    if let Some(blocked_until) = window.blocked_until {
+      if blocked_until > now {
+        return Err(rate_anomaly);
+      }
+      window.blocked_until = None;
+      window.started_at = now;
+      window.count = 0;
+    }
+
+    if now.duration_since(window.started_at) >= RATE_WINDOW {
+      window.started_at = now;
+      window.count = 0;
+    }
+
+    window.count += 1;
+    if window.count > self.max_requests_per_minute {
+      window.blocked_until = Some(now + self.block_duration);
+      return Err(rate_anomaly);
+    }
+
+    Ok(())
+  }
+}
+
+// endregion: --- Security Guard
+
+// region:    --- IP Networks
+
+/// IPv4/IPv6 network in CIDR notation. A bare address is treated as a /32 or /128.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNet {
+  addr: IpAddr,
+  prefix: u8,
+}
+
+impl IpNet {
+  pub fn parse(s: &str) -> Option<Self> {
+    let (addr, prefix) = match s.split_once('/') {
+      Some((addr, prefix)) => (addr.parse::<IpAddr>().ok()?, Some(prefix.parse::<u8>().ok()?)),
+      None => (s.parse::<IpAddr>().ok()?, None),
+    };
+    let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max_prefix);
+    (prefix <= max_prefix).then_some(Self { addr, prefix })
+  }
+
+  pub fn contains(&self, ip: IpAddr) -> bool {
+    match (self.addr, ip) {
+      (IpAddr::V4(net), IpAddr::V4(ip)) => {
+        prefix_matches(u32::from(net) as u128, u32::from(ip) as u128, self.prefix, 32)
+      }
+      (IpAddr::V6(net), IpAddr::V6(ip)) => {
+        prefix_matches(u128::from(net), u128::from(ip), self.prefix, 128)
+      }
+      _ => false,
+    }
+  }
+}
+
+fn prefix_matches(net: u128, ip: u128, prefix: u8, bits: u8) -> bool {
+  if prefix == 0 {
+    return true;
+  }
+  let shift = bits - prefix;
+  (net >> shift) == (ip >> shift)
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+  list.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+pub(crate) fn parse_ip_list(list: Option<&str>, name: &str) -> Vec<IpNet> {
+  split_list(list.unwrap_or_default())
+    .filter_map(|entry| {
+      let net = IpNet::parse(entry);
+      if net.is_none() {
+        warn!("Ignoring invalid {} entry: '{}'", name, entry);
+      }
+      net
+    })
+    .collect()
+}
+
+// endregion: --- IP Networks
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn guard(config: SecurityConfig) -> SecurityGuard {
+    SecurityGuard::from_config(Some(&config))
+  }
+
+  fn config() -> SecurityConfig {
+    SecurityConfig {
+      enable: Some(true),
+      ip_allowlist: None,
+      ip_denylist: None,
+      blocked_user_agents: None,
+      block_empty_user_agent: None,
+      max_requests_per_minute: None,
+      block_duration_secs: None,
+      trusted_proxies: None,
+    }
+  }
+
+  #[test]
+  fn test_ip_net_contains() {
+    let net = IpNet::parse("10.1.0.0/16").unwrap();
+    assert!(net.contains("10.1.200.3".parse().unwrap()));
+    assert!(!net.contains("10.2.0.1".parse().unwrap()));
+    assert!(!net.contains("::1".parse().unwrap()));
+
+    let single = IpNet::parse("2001:db8::1").unwrap();
+    assert!(single.contains("2001:db8::1".parse().unwrap()));
+    assert!(!single.contains("2001:db8::2".parse().unwrap()));
+
+    assert!(IpNet::parse("10.0.0.0/33").is_none());
+    assert!(IpNet::parse("not-an-ip").is_none());
+  }
+
+  #[test]
+  fn test_ip_lists() {
+    let guard = guard(SecurityConfig {
+      ip_allowlist: Some("192.168.0.0/24, 10.0.0.1".to_string()),
+      ip_denylist: Some("192.168.0.66".to_string()),
+      ..config()
+    });
+
+    let ua = Some("app/1.0");
+    assert_eq!(guard.check("192.168.0.10".parse().unwrap(), ua), Ok(()));
+    assert_eq!(guard.check("192.168.0.66".parse().unwrap(), ua), Err(SecurityRule::IpDenied));
+    assert_eq!(guard.check("172.16.0.1".parse().unwrap(), ua), Err(SecurityRule::IpNotAllowed));
+  }
+
+  #[test]
+  fn test_user_agent_heuristics() {
+    let guard = guard(SecurityConfig { block_empty_user_agent: Some(true), ..config() });
+    let ip = "203.0.113.7".parse().unwrap();
+
+    assert_eq!(
+      guard.check(ip, Some("sqlmap/1.7.2#stable (https://sqlmap.org)")),
+      Err(SecurityRule::BlockedUserAgent("sqlmap".to_string()))
+    );
+    assert_eq!(guard.check(ip, Some("  ")), Err(SecurityRule::MissingUserAgent));
+    assert_eq!(guard.check(ip, Some("Mozilla/5.0")), Ok(()));
+  }
+
+  #[test]
+  fn test_rate_anomaly_blocks_ip() {
+    let guard = guard(SecurityConfig {
+      max_requests_per_minute: Some(3),
+      block_duration_secs: Some(120),
+      ..config()
+    });
+    let ip = "198.51.100.4".parse().unwrap();
+    let start = Instant::now();
+
+    for _ in 0..3 {
+      assert!(guard.check_rate(ip, start).is_ok());
+    }
+    assert!(guard.check_rate(ip, start).is_err());
+    // Still blocked after the rate window rolls over.
+    assert!(guard.check_rate(ip, start + Duration::from_secs(90)).is_err());
+    assert!(guard.check_rate(ip, start + Duration::from_secs(121)).is_ok());
+  }
+}
@@ -29,14 +29,7 @@ pub async fn mw_ctx_require_user_auth(
   // Get user ID from token
   let user_id = get_user_id_from_token(&cookies, &app_state).await?;
 
-  // For now, use a simple hash of the UUID as i64
-  // In production, you might want to store a mapping
-  let user_id_i64 = user_id
-    .to_string()
-    .chars()
-    .take(15)
-    .fold(1i64, |acc, c| acc.wrapping_add(c as i64).wrapping_mul(31))
-    .abs();
+  let user_id_i64 = ctx_user_id(&user_id.to_string());
 
   // Create context with user ID
   let ctx = Ctx::new(user_id_i64).map_err(|e| {
@@ -62,13 +55,7 @@ pub async fn mw_ctx_optional_user_auth(
 
   // Try to get user ID from token, but don't fail if not present
   if let Ok(user_id) = get_user_id_from_token(&cookies, &app_state).await {
-    // For now, use a simple hash of the UUID as i64
-    let user_id_i64 = user_id
-      .to_string()
-      .chars()
-      .take(15)
-      .fold(1i64, |acc, c| acc.wrapping_add(c as i64).wrapping_mul(31))
-      .abs();
+    let user_id_i64 = ctx_user_id(&user_id.to_string());
 
     // Create context with user ID
     if let Ok(ctx) = Ctx::new(user_id_i64) {
@@ -79,6 +66,18 @@ pub async fn mw_ctx_optional_user_auth(
   Ok(next.run(req).await)
 }
 
+/// Map a user identifier onto the numeric `Ctx` user id.
+///
+/// For now, use a simple hash as i64. In production, you might want to store
+/// a mapping.
+pub(crate) fn ctx_user_id(subject: &str) -> i64 {
+  subject
+    .chars()
+    .take(15)
+    .fold(1i64, |acc, c| acc.wrapping_add(c as i64).wrapping_mul(31))
+    .abs()
+}
+
 /// Extract user ID from authentication token
 async fn get_user_id_from_token(cookies: &Cookies, app_state: &AppState) -> Result<Id, StatusCode> {
   // Get auth token from cookies
@@ -93,7 +92,7 @@ async fn get_user_id_from_token(cookies: &Cookies, app_state: &AppState) -> Resu
   // Parse token to get user ID
   // For now, we'll use a simple implementation where the token IS the user ID
   // In production, this should be a JWT or similar secure token
-  let user_id = Id::from_str(&auth_token).map_err(|e| {
+  let user_id = auth_token.parse::<Id>().map_err(|e| {
     error!("Invalid auth token format: {}", e);
     StatusCode::UNAUTHORIZED
   })?;
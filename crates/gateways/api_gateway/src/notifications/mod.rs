@@ -0,0 +1,20 @@
+use axum::{
+  Router,
+  routing::{get, post},
+};
+use jd_core::AppState;
+
+use auth_service::application::handlers::NotificationHandler;
+
+/// The caller's notification center: notifications, read state and which
+/// notifications they receive. Mounted behind bearer auth in `v1_routes`.
+pub fn notification_router() -> Router<AppState> {
+  Router::new()
+    .route("/", get(NotificationHandler::list_notifications))
+    .route("/read-all", post(NotificationHandler::mark_all_read))
+    .route("/{id}/read", post(NotificationHandler::mark_read))
+    .route(
+      "/preferences",
+      get(NotificationHandler::get_preferences).put(NotificationHandler::update_preferences),
+    )
+}
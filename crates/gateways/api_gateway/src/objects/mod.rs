@@ -0,0 +1,79 @@
+use axum::{
+  Router,
+  body::Body,
+  extract::{Path, Query, State},
+  http::{StatusCode, header},
+  response::{IntoResponse, Response},
+  routing::get,
+};
+use futures::TryStreamExt;
+use jd_core::AppState;
+use jd_object_store::ObjectStore;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::{Result, error::Error};
+
+/// The query string of a presigned local object URL.
+#[derive(Debug, Deserialize)]
+pub struct PresignedQuery {
+  /// Unix time the URL stops working.
+  pub expires: i64,
+  pub signature: String,
+}
+
+/// Presigned URLs to objects of the local-disk store, which has no server of
+/// its own. The signature is the only credential, so these are public.
+pub fn object_router() -> Router<AppState> {
+  Router::new().route("/{*key}", get(get_object).put(put_object))
+}
+
+/// The store, if it issued `query`'s signature for `method` on `key`.
+fn authorized(
+  app_state: &AppState,
+  method: &str,
+  key: &str,
+  query: &PresignedQuery,
+) -> Result<Arc<dyn ObjectStore>> {
+  app_state
+    .objects
+    .clone()
+    .filter(|objects| objects.verify_presigned(method, key, query.expires, &query.signature))
+    .ok_or_else(|| Error::insufficient_permissions(key))
+}
+
+/// GET /objects/{key}?expires=&signature=
+async fn get_object(
+  State(app_state): State<AppState>,
+  Path(key): Path<String>,
+  Query(query): Query<PresignedQuery>,
+) -> Result<Response> {
+  let objects = authorized(&app_state, "GET", &key, &query)?;
+  let content = objects.get_stream(&key).await?;
+  Ok(([(header::CONTENT_TYPE, content_type(&key))], Body::from_stream(content)).into_response())
+}
+
+/// PUT /objects/{key}?expires=&signature=
+/// Store the request body as the object.
+async fn put_object(
+  State(app_state): State<AppState>,
+  Path(key): Path<String>,
+  Query(query): Query<PresignedQuery>,
+  body: Body,
+) -> Result<StatusCode> {
+  let objects = authorized(&app_state, "PUT", &key, &query)?;
+  let content =
+    body.into_data_stream().map_err(|e| jd_object_store::Error::Io(std::io::Error::other(e)));
+  objects.put_stream(&key, Box::pin(content), content_type(&key)).await?;
+  Ok(StatusCode::OK)
+}
+
+/// The local store keeps no content types, so guess from the extension.
+fn content_type(key: &str) -> &'static str {
+  match key.rsplit_once('.').map(|(_, extension)| extension) {
+    Some("json") => "application/json",
+    Some("ndjson") => "application/x-ndjson",
+    Some("csv") => "text/csv; charset=utf-8",
+    _ => "application/octet-stream",
+  }
+}
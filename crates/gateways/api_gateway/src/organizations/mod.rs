@@ -0,0 +1,30 @@
+use axum::{
+  Router,
+  routing::{get, post, put},
+};
+use jd_core::AppState;
+
+use auth_service::application::handlers::{AlertChannelHandler, OrganizationHandler};
+
+/// Self-serve organization onboarding, and the organization's chat alert
+/// channels. Mounted behind bearer auth in `v1_routes`; the onboarding
+/// handlers answer 404 while the feature flag is off.
+pub fn organization_router() -> Router<AppState> {
+  Router::new()
+    .route("/", post(OrganizationHandler::create_organization))
+    .route("/{organization_id}", get(OrganizationHandler::get_organization))
+    .route("/{organization_id}/invitations", post(OrganizationHandler::invite_member))
+    .route("/invitations/{token}/accept", post(OrganizationHandler::accept_invitation))
+    .route(
+      "/{organization_id}/alert-channels",
+      get(AlertChannelHandler::list_channels).post(AlertChannelHandler::create_channel),
+    )
+    .route(
+      "/{organization_id}/alert-channels/{channel_id}",
+      put(AlertChannelHandler::update_channel).delete(AlertChannelHandler::delete_channel),
+    )
+    .route(
+      "/{organization_id}/alert-channels/{channel_id}/test",
+      post(AlertChannelHandler::test_channel),
+    )
+}
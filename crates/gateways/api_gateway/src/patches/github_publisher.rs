@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use github_service::{GitHubClient, PatchPullRequest};
+use patch_service::domain::{
+    PullRequestDraft, PullRequestPublisher, RepositoryCheckout, SubmittedPullRequest,
+};
+
+/// Opens pull requests for patches with the configured GitHub credentials:
+/// the app installation's when running as a GitHub App, the personal token
+/// otherwise.
+pub struct GitHubPullRequestPublisher {
+    github_client: GitHubClient,
+}
+
+impl GitHubPullRequestPublisher {
+    pub fn new(github_client: GitHubClient) -> Self {
+        Self { github_client }
+    }
+}
+
+#[async_trait]
+impl PullRequestPublisher for GitHubPullRequestPublisher {
+    async fn open_pull_request(
+        &self,
+        checkout: &RepositoryCheckout,
+        draft: &PullRequestDraft,
+    ) -> patch_service::Result<SubmittedPullRequest> {
+        let (owner, repo) = checkout.full_name.split_once('/').ok_or_else(|| {
+            patch_service::Error::GithubIntegrationError(format!(
+                "invalid repository name {}",
+                checkout.full_name
+            ))
+        })?;
+        let patch = PatchPullRequest {
+            branch: draft.branch.clone(),
+            base_commit: draft.base_commit.clone(),
+            title: draft.title.clone(),
+            body: draft.body.clone(),
+            commit_message: draft.commit_message.clone(),
+            diff: draft.diff.clone(),
+        };
+
+        let opened = self
+            .github_client
+            .open_patch_pull_request(owner, repo, &patch)
+            .await
+            .map_err(|e| patch_service::Error::GithubIntegrationError(e.to_string()))?;
+
+        Ok(SubmittedPullRequest {
+            number: opened.number as i32,
+            url: opened.html_url,
+            state: opened.state.as_str().to_string(),
+            branch: opened.branch,
+        })
+    }
+}
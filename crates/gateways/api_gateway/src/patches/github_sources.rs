@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use github_service::GitHubClient;
+use patch_service::domain::{RepositoryCheckout, SourceFileProvider};
+use std::collections::HashMap;
+
+/// Reads patched files from GitHub with the configured credentials.
+pub struct GitHubSourceFiles {
+    github_client: GitHubClient,
+}
+
+impl GitHubSourceFiles {
+    pub fn new(github_client: GitHubClient) -> Self {
+        Self { github_client }
+    }
+}
+
+#[async_trait]
+impl SourceFileProvider for GitHubSourceFiles {
+    async fn files_at(
+        &self,
+        checkout: &RepositoryCheckout,
+        paths: &[String],
+    ) -> patch_service::Result<HashMap<String, String>> {
+        let github_error = |e: github_service::Error| {
+            patch_service::Error::GithubIntegrationError(e.to_string())
+        };
+        let (owner, repo) = checkout.full_name.split_once('/').ok_or_else(|| {
+            patch_service::Error::GithubIntegrationError(format!(
+                "invalid repository name {}",
+                checkout.full_name
+            ))
+        })?;
+        let commit_sha = match &checkout.commit_sha {
+            Some(commit_sha) => commit_sha.clone(),
+            None => {
+                self.github_client.get_head_commit_sha(owner, repo).await.map_err(github_error)?
+            }
+        };
+
+        let files = self
+            .github_client
+            .get_repository_files_at_ref(owner, repo, paths, &commit_sha)
+            .await
+            .map_err(github_error)?;
+        Ok(files.into_iter().map(|file| (file.path, file.content)).collect())
+    }
+}
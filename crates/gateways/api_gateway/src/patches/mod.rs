@@ -1,3 +1,5 @@
+pub mod github_publisher;
+pub mod github_sources;
 pub mod patch_routes;
 
 pub use patch_routes::*;
\ No newline at end of file
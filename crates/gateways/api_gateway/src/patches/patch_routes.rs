@@ -1,14 +1,35 @@
+use auth_service::domain::Claims;
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::Json as ResponseJson,
     routing::{delete, get, post, put},
     Json, Router,
 };
+use github_service::{GitHubServiceConfig, GitHubServiceFactory};
 use jd_core::AppState;
+use patch_service::{
+    application::use_cases::{
+        PatchCandidateUseCases, PatchDiffUseCases, PatchGenerationUseCases, PatchReviewUseCases,
+        PatchSubmissionUseCases, PatchUseCases, PatchVerificationUseCases,
+    },
+    domain::{GenerationStrategy, PatchCandidate, PatchGroup, PatchRepository, PatchReviewSummary},
+    infrastructure::{
+        AIPatchGenerator, IndexedExposureSource, PatchRepositoryImpl, SandboxPatchVerifier,
+    },
+    models::{
+        AssignReviewersRequest, GenerateBulkPatchesRequest, GeneratePatchCandidatesRequest,
+        GeneratePatchResponse, PatchCandidatesResponse, PatchDiffResponse,
+        SubmitPullRequestResponse, SubmitReviewRequest, VerifyPatchResponse,
+    },
+};
 use serde_json::{json, Value};
+use std::sync::Arc;
 use uuid::Uuid;
 
+use super::github_publisher::GitHubPullRequestPublisher;
+use super::github_sources::GitHubSourceFiles;
+
 // Placeholder handlers for patch management
 pub async fn list_patches(
     State(_app_state): State<AppState>,
@@ -114,10 +135,15 @@ pub async fn get_voting_status(
 }
 
 pub async fn apply_patch(
-    State(_app_state): State<AppState>,
+    State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
     Json(_payload): Json<Value>,
-) -> Result<ResponseJson<Value>, StatusCode> {
+) -> Result<ResponseJson<Value>, patch_service::Error> {
+    // Only patches verified to build may be submitted
+    PatchUseCases::new(Arc::new(PatchRepositoryImpl::new(app_state)))
+        .submittable_patch(id)
+        .await?;
+
     let response = json!({
         "patch_id": id,
         "status": "applied",
@@ -128,37 +154,208 @@ pub async fn apply_patch(
 }
 
 pub async fn generate_patch(
-    State(_app_state): State<AppState>,
+    State(app_state): State<AppState>,
     Path(vulnerability_id): Path<Uuid>,
-    Json(_payload): Json<Value>,
-) -> Result<ResponseJson<Value>, StatusCode> {
-    let response = json!({
-        "generated_patch": {
-            "title": "AI Generated Patch",
-            "description": "Automatically generated security fix",
-            "confidence_score": 0.85
-        },
-        "vulnerability_id": vulnerability_id,
-        "generation_time_ms": 1500
-    });
-    Ok(ResponseJson(response))
+    Json(payload): Json<Value>,
+) -> Result<ResponseJson<GeneratePatchResponse>, patch_service::Error> {
+    let strategy = payload
+        .get("strategy")
+        .cloned()
+        .and_then(|strategy| serde_json::from_value(strategy).ok())
+        .unwrap_or(GenerationStrategy::Balanced);
+
+    let use_cases = PatchGenerationUseCases::new(
+        Arc::new(PatchRepositoryImpl::new(app_state.clone())),
+        Arc::new(AIPatchGenerator::new()),
+        Arc::new(IndexedExposureSource::new(app_state)),
+    );
+    let generated_patch = use_cases.generate_patch(vulnerability_id, strategy).await?;
+
+    Ok(ResponseJson(GeneratePatchResponse {
+        generation_time_ms: generated_patch.generation_metadata.generation_time_ms,
+        generated_patch,
+        vulnerability_id,
+    }))
 }
 
+/// Generate alternative patches for a finding with different strategies,
+/// ranked by a validation pass. Candidates are built and tested when a
+/// sandbox toolchain is configured.
+pub async fn generate_patch_candidates(
+    State(app_state): State<AppState>,
+    Path(vulnerability_id): Path<Uuid>,
+    Json(request): Json<GeneratePatchCandidatesRequest>,
+) -> Result<ResponseJson<PatchCandidatesResponse>, patch_service::Error> {
+    let mut use_cases = PatchCandidateUseCases::new(
+        Arc::new(PatchRepositoryImpl::new(app_state)),
+        Arc::new(AIPatchGenerator::new()),
+    );
+    if let Some(verifier) = SandboxPatchVerifier::from_env() {
+        use_cases = use_cases.with_verifier(Arc::new(verifier));
+    }
+    let candidates = use_cases
+        .generate_candidates(
+            vulnerability_id,
+            request.strategy.unwrap_or(GenerationStrategy::Conservative),
+            request.count,
+        )
+        .await?;
+
+    Ok(ResponseJson(PatchCandidatesResponse { vulnerability_id, candidates }))
+}
+
+pub async fn get_patch_candidates(
+    State(app_state): State<AppState>,
+    Path(vulnerability_id): Path<Uuid>,
+) -> Result<ResponseJson<PatchCandidatesResponse>, patch_service::Error> {
+    let use_cases = PatchCandidateUseCases::new(
+        Arc::new(PatchRepositoryImpl::new(app_state)),
+        Arc::new(AIPatchGenerator::new()),
+    );
+    let candidates = use_cases.candidates(vulnerability_id).await?;
+
+    Ok(ResponseJson(PatchCandidatesResponse { vulnerability_id, candidates }))
+}
+
+/// Pick a candidate for its finding as the token subject.
+pub async fn select_patch_candidate(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<PatchCandidate>, patch_service::Error> {
+    let use_cases = PatchCandidateUseCases::new(
+        Arc::new(PatchRepositoryImpl::new(app_state)),
+        Arc::new(AIPatchGenerator::new()),
+    );
+    Ok(ResponseJson(use_cases.select_candidate(id, &caller.address).await?))
+}
+
+/// Generate one coordinated patch for a class of a repository's findings,
+/// recorded as a patch group with a sub-patch per file.
+pub async fn generate_bulk_patches(
+    State(app_state): State<AppState>,
+    Json(request): Json<GenerateBulkPatchesRequest>,
+) -> Result<ResponseJson<PatchGroup>, patch_service::Error> {
+    let use_cases = PatchGenerationUseCases::new(
+        Arc::new(PatchRepositoryImpl::new(app_state.clone())),
+        Arc::new(AIPatchGenerator::new()),
+        Arc::new(IndexedExposureSource::new(app_state)),
+    );
+    let group = use_cases
+        .generate_bulk(
+            request.repository_id,
+            request.filter,
+            request.strategy.unwrap_or(GenerationStrategy::Balanced),
+            request.limit,
+        )
+        .await?;
+
+    Ok(ResponseJson(group))
+}
+
+pub async fn get_patch_group(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<PatchGroup>, patch_service::Error> {
+    Ok(ResponseJson(PatchRepositoryImpl::new(app_state).get_patch_group(id).await?))
+}
+
+/// Apply the patch to a checkout of its repository, build and test it in
+/// the sandbox, and record the results on the patch.
 pub async fn validate_patch(
-    State(_app_state): State<AppState>,
+    State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(_payload): Json<Value>,
-) -> Result<ResponseJson<Value>, StatusCode> {
-    let response = json!({
-        "validation_result": {
-            "is_valid": true,
-            "can_apply_cleanly": true,
-            "conflicts": [],
-            "syntax_errors": []
-        },
-        "validated_at": chrono::Utc::now()
-    });
-    Ok(ResponseJson(response))
+) -> Result<ResponseJson<VerifyPatchResponse>, patch_service::Error> {
+    let verifier =
+        SandboxPatchVerifier::from_env().ok_or(patch_service::Error::VerifierNotConfigured)?;
+    let use_cases = PatchVerificationUseCases::new(
+        Arc::new(PatchRepositoryImpl::new(app_state)),
+        Arc::new(verifier),
+    );
+    let validation = use_cases.verify_patch(id).await?;
+
+    Ok(ResponseJson(VerifyPatchResponse { patch_id: id, validation }))
+}
+
+/// Push the verified patch to a new branch of its repository and open a
+/// pull request for it with the configured GitHub credentials, as the token
+/// subject.
+pub async fn submit_patch_pr(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<SubmitPullRequestResponse>, patch_service::Error> {
+    let github_client = GitHubServiceConfig::from_config(&app_state.config)
+        .and_then(|config| GitHubServiceFactory::create_client(&config))
+        .map_err(|e| patch_service::Error::GithubIntegrationError(e.to_string()))?;
+    let use_cases = PatchSubmissionUseCases::new(
+        Arc::new(PatchRepositoryImpl::new(app_state)),
+        Arc::new(GitHubPullRequestPublisher::new(github_client)),
+    );
+    let pull_request = use_cases.submit_pull_request(id, &caller.address).await?;
+
+    Ok(ResponseJson(SubmitPullRequestResponse { patch_id: id, pull_request }))
+}
+
+pub async fn get_patch_reviews(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<PatchReviewSummary>, patch_service::Error> {
+    let use_cases = PatchReviewUseCases::new(Arc::new(PatchRepositoryImpl::new(app_state)));
+    Ok(ResponseJson(use_cases.review_summary(id).await?))
+}
+
+/// Approve, reject or comment on a patch as the token subject.
+pub async fn submit_patch_review(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SubmitReviewRequest>,
+) -> Result<ResponseJson<PatchReviewSummary>, patch_service::Error> {
+    let use_cases = PatchReviewUseCases::new(Arc::new(PatchRepositoryImpl::new(app_state)));
+    let summary = use_cases
+        .submit_review(id, &caller.address, request.decision, request.comment.as_deref())
+        .await?;
+    Ok(ResponseJson(summary))
+}
+
+pub async fn assign_patch_reviewers(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<AssignReviewersRequest>,
+) -> Result<ResponseJson<PatchReviewSummary>, patch_service::Error> {
+    let use_cases = PatchReviewUseCases::new(Arc::new(PatchRepositoryImpl::new(app_state)));
+    let summary = use_cases
+        .assign_reviewers(id, &request.reviewers, request.required_approvals, &caller.address)
+        .await?;
+    Ok(ResponseJson(summary))
+}
+
+pub async fn remove_patch_reviewer(
+    State(app_state): State<AppState>,
+    Path((id, reviewer)): Path<(Uuid, String)>,
+) -> Result<ResponseJson<PatchReviewSummary>, patch_service::Error> {
+    let use_cases = PatchReviewUseCases::new(Arc::new(PatchRepositoryImpl::new(app_state)));
+    Ok(ResponseJson(use_cases.remove_reviewer(id, &reviewer).await?))
+}
+
+/// The patch's diff as files, hunks and numbered lines for side-by-side
+/// rendering, checked against the commit its finding was reported on.
+pub async fn get_patch_diff(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<PatchDiffResponse>, patch_service::Error> {
+    let github_client = GitHubServiceConfig::from_config(&app_state.config)
+        .and_then(|config| GitHubServiceFactory::create_client(&config))
+        .map_err(|e| patch_service::Error::GithubIntegrationError(e.to_string()))?;
+    let use_cases = PatchDiffUseCases::new(
+        Arc::new(PatchRepositoryImpl::new(app_state)),
+        Arc::new(GitHubSourceFiles::new(github_client)),
+    );
+    let diff = use_cases.patch_diff(id).await?;
+
+    Ok(ResponseJson(PatchDiffResponse { patch_id: id, diff }))
 }
 
 pub async fn preview_patch(
@@ -260,13 +457,52 @@ pub fn patch_router() -> Router<AppState> {
         .route("/{id}/apply", post(apply_patch))
         // Vulnerability and Repository specific
         .route("/vulnerability/{vulnerability_id}", get(get_patches_by_vulnerability))
+        .route("/vulnerability/{vulnerability_id}/candidates", get(get_patch_candidates))
         .route("/repository/{repository_id}", get(get_patches_by_repository))
         // AI Generation and Validation
         .route("/ai-suggestions", post(ai_patch_suggestions))
         .route("/generate/{vulnerability_id}", post(generate_patch))
-        .route("/{id}/validate", post(validate_patch))
+        .route("/groups/{id}", get(get_patch_group))
         .route("/{id}/preview", get(preview_patch))
+        .route("/{id}/diff", get(get_patch_diff))
         // Statistics
         .route("/statistics", get(get_patch_statistics))
         .route("/leaderboard", get(get_patch_leaderboard))
-}
\ No newline at end of file
+}
+
+/// Candidate generation, which spends LLM calls and sandbox builds.
+/// `v1_routes` mounts this behind bearer auth and a per-caller rate limit.
+pub fn patch_generation_router() -> Router<AppState> {
+    Router::new()
+        .route("/generate/{vulnerability_id}/candidates", post(generate_patch_candidates))
+}
+
+/// Bulk generation, an LLM call for each of up to `MAX_BULK_FINDINGS`
+/// findings.
+/// `v1_routes` mounts this behind bearer auth and the
+/// `patches:bulk_generate` scope policy.
+pub fn patch_bulk_generation_router() -> Router<AppState> {
+    Router::new().route("/generate-bulk", post(generate_bulk_patches))
+}
+
+/// Patch verification, which builds and tests the patched repository in the
+/// sandbox. `v1_routes` mounts this behind bearer auth.
+pub fn patch_verification_router() -> Router<AppState> {
+    Router::new().route("/{id}/validate", post(validate_patch))
+}
+
+/// Review routes; reviews, candidate picks and pull request submissions are
+/// attributed to the token subject.
+pub fn patch_review_router() -> Router<AppState> {
+    Router::new()
+        .route("/{id}/reviews", get(get_patch_reviews).post(submit_patch_review))
+        .route("/{id}/submit-pr", post(submit_patch_pr))
+        .route("/candidates/{id}/select", post(select_patch_candidate))
+}
+
+/// Reviewer assignment, for tokens granting `patches:review_admin`.
+pub fn patch_review_admin_router() -> Router<AppState> {
+    Router::new()
+        .route("/{id}/reviews/reviewers", post(assign_patch_reviewers))
+        .route("/{id}/reviews/reviewers/{reviewer}", delete(remove_patch_reviewer))
+}
@@ -0,0 +1,5 @@
+pub mod report_routes;
+pub mod ruleset_routes;
+
+pub use report_routes::*;
+pub use ruleset_routes::*;
@@ -0,0 +1,39 @@
+use ai_analysis_service::infrastructure::report_archive::AnalysisReportArchive;
+use axum::{
+  extract::{Path, State},
+  response::Redirect,
+  routing::get,
+  Router,
+};
+use jd_core::AppState;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::Result;
+
+/// How long a link to an archived report works.
+const REPORT_LINK_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// GET /repositories/{id}/analyses/{analysis_id}/report
+/// Redirect to a short-lived link to the archived JSON report of an
+/// analysis: its results and its findings as they stood when it finished.
+pub async fn get_analysis_report(
+  State(app_state): State<AppState>,
+  Path((repository_id, analysis_id)): Path<(Uuid, Uuid)>,
+) -> Result<Redirect> {
+  let not_archived = ai_analysis_service::Error::AnalysisReportNotFound { analysis_id };
+  let Some(objects) = app_state.objects.clone() else {
+    return Err(not_archived.into());
+  };
+  let url = AnalysisReportArchive::new(app_state.mm().dbx().db().clone(), objects)
+    .report_url(repository_id, analysis_id, REPORT_LINK_TTL)
+    .await?
+    .ok_or(not_archived)?;
+  Ok(Redirect::temporary(&url))
+}
+
+/// Archived reports include findings still under coordinated disclosure,
+/// so `v1_routes` mounts this for `vulnerabilities:disclosure` tokens only.
+pub fn analysis_report_router() -> Router<AppState> {
+  Router::new().route("/{id}/analyses/{analysis_id}/report", get(get_analysis_report))
+}
@@ -0,0 +1,110 @@
+use ai_analysis_service::domain::analysis_repository_trait::AnalysisRepository;
+use ai_analysis_service::domain::analysis_ruleset::{Ruleset, SCOPE_RULESETS_ADMIN};
+use ai_analysis_service::domain::vulnerability_patterns::VulnerabilityPatterns;
+use ai_analysis_service::infrastructure::analysis_repository_impl::AnalysisRepositoryImpl;
+use auth_service::domain::Claims;
+use axum::{
+  extract::{Extension, Path, State},
+  response::Json,
+  routing::get,
+  Router,
+};
+use jd_core::{ctx::Ctx, AppState};
+use serde_json::{json, Value};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{error::Error, Result};
+
+/// GET /repositories/{id}/ruleset
+/// The repository's ruleset, with every built-in pattern and whether it is
+/// enabled. Repositories that never configured one run the built-in
+/// patterns alone.
+pub async fn get_ruleset(
+  State(app_state): State<AppState>,
+  Path(repository_id): Path<Uuid>,
+) -> Result<Json<Value>> {
+  let ruleset = AnalysisRepositoryImpl::new(app_state).get_ruleset(repository_id).await?;
+  let (rules, updated_by, updated_at) = match ruleset {
+    Some(ruleset) => (ruleset.rules, Some(ruleset.updated_by), Some(ruleset.updated_at)),
+    None => (Ruleset::default(), None, None),
+  };
+
+  Ok(Json(ruleset_response(repository_id, &rules, updated_by, updated_at)))
+}
+
+/// PUT /repositories/{id}/ruleset
+/// Replace the repository's ruleset. Later analyses of the repository run
+/// under it; the next incremental analysis covers the whole repository.
+/// Owners and admins of an organization monitoring the repository may
+/// change it, as may tokens granting `rulesets:admin`.
+pub async fn put_ruleset(
+  State(app_state): State<AppState>,
+  Extension(ctx): Extension<Ctx>,
+  Extension(caller): Extension<Claims>,
+  Path(repository_id): Path<Uuid>,
+  Json(rules): Json<Ruleset>,
+) -> Result<Json<Value>> {
+  let repository = AnalysisRepositoryImpl::new(app_state);
+  if !ctx.has_scope(SCOPE_RULESETS_ADMIN)
+    && !repository.can_manage_ruleset(repository_id, &caller.address).await?
+  {
+    warn!(
+        target: "security_audit",
+        action = "denied",
+        repository_id = %repository_id,
+        caller = %caller.address,
+        "Ruleset change denied"
+    );
+    return Err(Error::insufficient_permissions(SCOPE_RULESETS_ADMIN));
+  }
+
+  rules.validate()?;
+
+  let ruleset = repository
+    .save_ruleset(repository_id, &rules, &caller.address)
+    .await?
+    .ok_or(ai_analysis_service::Error::RepositoryNotFound { repository_id })?;
+
+  Ok(Json(ruleset_response(
+    repository_id,
+    &ruleset.rules,
+    Some(ruleset.updated_by),
+    Some(ruleset.updated_at),
+  )))
+}
+
+fn ruleset_response(
+  repository_id: Uuid,
+  rules: &Ruleset,
+  updated_by: Option<String>,
+  updated_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Value {
+  let builtin_patterns: Vec<Value> = VulnerabilityPatterns::new()
+    .get_all_patterns()
+    .iter()
+    .map(|pattern| {
+      json!({
+        "id": pattern.id,
+        "name": pattern.name,
+        "description": pattern.description,
+        "severity": pattern.severity,
+        "enabled": !rules.is_disabled(&pattern.id),
+      })
+    })
+    .collect();
+
+  json!({
+    "repository_id": repository_id,
+    "disabled_patterns": rules.disabled_patterns,
+    "custom_patterns": rules.custom_patterns,
+    "builtin_patterns": builtin_patterns,
+    "updated_by": updated_by,
+    "updated_at": updated_at,
+  })
+}
+
+/// Ruleset routes; changes record who made them, so these need a caller.
+pub fn repository_ruleset_router() -> Router<AppState> {
+  Router::new().route("/{id}/ruleset", get(get_ruleset).put(put_ruleset))
+}
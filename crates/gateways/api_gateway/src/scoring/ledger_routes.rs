@@ -0,0 +1,31 @@
+use axum::{extract::State, response::Json};
+use jd_core::AppState;
+use scoring_service::{
+  application::use_cases::score_ledger_use_cases::ScoreLedgerUseCases,
+  infrastructure::score_ledger_repository_impl::ScoreLedgerRepositoryImpl,
+  models::responses::LedgerVerificationResponse,
+};
+use tracing::warn;
+
+use crate::Result;
+
+/// GET /scoring/ledger/verify
+/// Recompute the score ledger hash chain from genesis and report the first
+/// tampered, missing or reordered row.
+pub async fn verify_score_ledger(
+  State(app_state): State<AppState>,
+) -> Result<Json<LedgerVerificationResponse>> {
+  let use_cases = ScoreLedgerUseCases::new(ScoreLedgerRepositoryImpl::new(app_state));
+  let report = use_cases.verify_chain().await?;
+
+  if let Some(first_break) = &report.first_break {
+    warn!(
+      target: "security_audit",
+      seq = first_break.seq,
+      reason = %first_break.reason,
+      "Score ledger integrity check failed"
+    );
+  }
+
+  Ok(Json(report))
+}
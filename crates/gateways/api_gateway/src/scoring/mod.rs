@@ -0,0 +1,41 @@
+use axum::{
+  Router,
+  routing::{get, post},
+};
+use jd_core::AppState;
+
+mod ledger_routes;
+mod model_routes;
+mod sybil_routes;
+
+pub use ledger_routes::*;
+pub use model_routes::*;
+pub use sybil_routes::*;
+
+pub fn scoring_router() -> Router<AppState> {
+  Router::new()
+    .route("/models", get(list_scoring_models))
+    .route("/models/{id}", get(get_scoring_model))
+}
+
+/// Ledger integrity checks. `v1_routes` mounts this behind bearer auth and
+/// the `scoring:ledger_admin` scope policy.
+pub fn scoring_ledger_admin_router() -> Router<AppState> {
+  Router::new().route("/ledger/verify", get(verify_score_ledger))
+}
+
+/// Model registration and activation. `v1_routes` mounts this behind bearer
+/// auth and the `scoring:models_admin` scope policy.
+pub fn scoring_model_admin_router() -> Router<AppState> {
+  Router::new()
+    .route("/models", post(register_scoring_model))
+    .route("/models/{id}/activate", post(activate_scoring_model))
+}
+
+/// Sybil scores and per-partner thresholds. `v1_routes` mounts this behind
+/// bearer auth and the `scoring:sybil` scope policy.
+pub fn scoring_sybil_router() -> Router<AppState> {
+  Router::new()
+    .route("/sybil/thresholds", get(get_sybil_thresholds).put(put_sybil_thresholds))
+    .route("/sybil/{subject}", get(get_sybil_report))
+}
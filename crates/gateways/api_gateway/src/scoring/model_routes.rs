@@ -0,0 +1,76 @@
+use auth_service::domain::Claims;
+use axum::{
+  Extension,
+  extract::{Path, State},
+  http::StatusCode,
+  response::Json,
+};
+use jd_core::AppState;
+use scoring_service::{
+  application::use_cases::model_registry_use_cases::ModelRegistryUseCases,
+  domain::model_registry::{ModelInputSchema, ModelKind, NewScoringModel, RegisteredModel},
+  infrastructure::model_registry_repository_impl::ModelRegistryRepositoryImpl,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::Result;
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterModelRequest {
+  pub name: String,
+  pub version: String,
+  pub kind: ModelKind,
+  /// `file://` URI of the ONNX model.
+  pub artifact_uri: Option<String>,
+  #[serde(default)]
+  pub input_schema: ModelInputSchema,
+}
+
+fn model_registry(app_state: AppState) -> ModelRegistryUseCases<ModelRegistryRepositoryImpl> {
+  ModelRegistryUseCases::new(ModelRegistryRepositoryImpl::new(app_state))
+}
+
+/// GET /scoring/models
+/// Every registered model, newest first; `active` marks the one scoring.
+pub async fn list_scoring_models(
+  State(app_state): State<AppState>,
+) -> Result<Json<Vec<RegisteredModel>>> {
+  Ok(Json(model_registry(app_state).list().await?))
+}
+
+/// GET /scoring/models/{id}
+pub async fn get_scoring_model(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<RegisteredModel>> {
+  Ok(Json(model_registry(app_state).get(id).await?))
+}
+
+/// POST /scoring/models
+/// Register a model. It scores nothing until activated.
+pub async fn register_scoring_model(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Json(request): Json<RegisterModelRequest>,
+) -> Result<(StatusCode, Json<RegisteredModel>)> {
+  let model = NewScoringModel {
+    name: request.name,
+    version: request.version,
+    kind: request.kind,
+    artifact_uri: request.artifact_uri,
+    input_schema: request.input_schema,
+    registered_by: Some(caller.address),
+  };
+  let registered = model_registry(app_state).register(model).await?;
+  Ok((StatusCode::CREATED, Json(registered)))
+}
+
+/// POST /scoring/models/{id}/activate
+/// Score new behavior inputs with this model instead of the active one.
+pub async fn activate_scoring_model(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<RegisteredModel>> {
+  Ok(Json(model_registry(app_state).activate(id).await?))
+}
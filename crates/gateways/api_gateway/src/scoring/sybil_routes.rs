@@ -0,0 +1,50 @@
+use auth_service::domain::Claims;
+use axum::{
+  Extension,
+  extract::{Path, State},
+  response::Json,
+};
+use jd_core::AppState;
+use scoring_service::{
+  application::use_cases::sybil_use_cases::SybilUseCases,
+  domain::sybil::SybilThresholds,
+  infrastructure::sybil_repository_impl::SybilRepositoryImpl,
+  models::responses::SybilReportResponse,
+};
+
+use crate::Result;
+
+fn sybil(app_state: AppState) -> SybilUseCases<SybilRepositoryImpl> {
+  let defaults = SybilThresholds::from_config(app_state.config.scoring.as_ref());
+  SybilUseCases::new(SybilRepositoryImpl::new(app_state), defaults)
+}
+
+/// GET /scoring/sybil/{subject}
+/// The sybil score of the user with wallet `subject` next to their persona
+/// score, with the verdict of the caller's thresholds.
+pub async fn get_sybil_report(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(subject): Path<String>,
+) -> Result<Json<SybilReportResponse>> {
+  Ok(Json(sybil(app_state).report(&caller.address, &subject).await?))
+}
+
+/// GET /scoring/sybil/thresholds
+/// The caller's thresholds, or the defaults if it set none.
+pub async fn get_sybil_thresholds(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+) -> Result<Json<SybilThresholds>> {
+  Ok(Json(sybil(app_state).thresholds(&caller.address).await?))
+}
+
+/// PUT /scoring/sybil/thresholds
+/// Set the sybil scores at which the caller reviews and blocks users.
+pub async fn put_sybil_thresholds(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Json(thresholds): Json<SybilThresholds>,
+) -> Result<Json<SybilThresholds>> {
+  Ok(Json(sybil(app_state).set_thresholds(&caller.address, thresholds).await?))
+}
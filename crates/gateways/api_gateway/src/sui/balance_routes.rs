@@ -0,0 +1,27 @@
+use axum::{
+  Router,
+  extract::{Path, Query, State},
+  response::Json,
+  routing::get,
+};
+use jd_core::AppState;
+use sui_service::{
+  Error,
+  application::use_cases::{BalanceInfo, CoinUseCases},
+  infrastructure::enhanced_sui_repository::EnhancedSuiRepository,
+  models::requests::NetworkQuery,
+};
+
+// Wallet balances, served from the object cache when fresh
+pub fn balance_router() -> Router<AppState> {
+  Router::new().route("/balances/{address}", get(get_balances))
+}
+
+async fn get_balances(
+  State(app_state): State<AppState>,
+  Path(address): Path<String>,
+  Query(query): Query<NetworkQuery>,
+) -> Result<Json<BalanceInfo>, Error> {
+  let repository = EnhancedSuiRepository::for_network(app_state, query.network.as_deref()).await?;
+  Ok(Json(CoinUseCases::new(repository).get_balance_info(&address).await?))
+}
@@ -0,0 +1,37 @@
+use axum::{
+  Router,
+  extract::{Query, State},
+  response::Json,
+  routing::get,
+};
+use jd_core::AppState;
+use sui_service::{
+  Error,
+  application::use_cases::OnchainEventUseCases,
+  infrastructure::onchain_event_store::OnchainEventStore,
+  models::{EventIndexerCursor, OnchainEventPage, requests::ListOnchainEventsRequest},
+};
+
+// Events indexed from the configured packages
+pub fn event_router() -> Router<AppState> {
+  Router::new()
+    .route("/events", get(list_events))
+    .route("/events/cursors", get(indexer_cursors))
+}
+
+fn use_cases(app_state: &AppState) -> OnchainEventUseCases {
+  OnchainEventUseCases::new(OnchainEventStore::new(app_state.mm().dbx().db().clone()))
+}
+
+async fn list_events(
+  State(app_state): State<AppState>,
+  Query(request): Query<ListOnchainEventsRequest>,
+) -> Result<Json<OnchainEventPage>, Error> {
+  Ok(Json(use_cases(&app_state).list_events(request).await?))
+}
+
+async fn indexer_cursors(
+  State(app_state): State<AppState>,
+) -> Result<Json<Vec<EventIndexerCursor>>, Error> {
+  Ok(Json(use_cases(&app_state).indexer_cursors().await?))
+}
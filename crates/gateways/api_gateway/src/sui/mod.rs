@@ -1,7 +1,12 @@
 use axum::{routing::post, Router};
+mod balance_routes;
+mod event_routes;
 mod sponsor_routes;
 use jd_core::AppState;
 
 pub fn sui_router() -> Router<AppState> {
-  Router::new().merge(sponsor_routes::sponsor_router())
+  Router::new()
+    .merge(sponsor_routes::sponsor_router())
+    .merge(balance_routes::balance_router())
+    .merge(event_routes::event_router())
 }
@@ -1,11 +1,23 @@
-use axum::{extract::State, response::Json, routing::{get, post}, Router};
+use axum::{
+  extract::{Query, State},
+  response::Json,
+  routing::{get, post},
+  Router,
+};
 use jd_core::AppState;
 use serde_json::{json, Value};
+use sui_service::{
+  infrastructure::enhanced_sui_repository::EnhancedSuiRepository,
+  models::{requests::NetworkQuery, NetworkInfo},
+  Error, SuiRepository,
+};
 
 // Sponsor operations - simplified to working methods only
 pub fn sponsor_router() -> Router<AppState> {
   Router::new()
     .route("/health", get(health_check))
+    .route("/networks", get(list_networks))
+    .route("/rpc-providers", get(rpc_providers))
     .route("/test-connection", get(test_connection))
     .route("/network-info", get(get_network_info))
     .route("/sponsor-transaction", post(sponsor_transaction))
@@ -15,17 +27,51 @@ async fn health_check() -> Json<Value> {
   Json(json!({"status": "ok"}))
 }
 
-async fn test_connection() -> Json<Value> {
-  Json(json!({"connection": "ok"}))
+async fn list_networks(State(app_state): State<AppState>) -> Json<Value> {
+  let networks = app_state.sui_networks();
+  Json(json!({
+    "default": networks.default_network(),
+    "networks": networks.networks(),
+  }))
 }
 
-async fn get_network_info(State(_app_state): State<AppState>) -> Json<Value> {
-  Json(json!({
-    "network": "devnet",
-    "chain_id": "35834a8a",
-    "epoch": 123,
-    "checkpoint": 456789,
-    "gas_price": 1000
+async fn rpc_providers(
+  State(app_state): State<AppState>,
+  Query(query): Query<NetworkQuery>,
+) -> Result<Json<Value>, Error> {
+  let networks = app_state.sui_networks();
+  let network =
+    networks.resolve(query.network.as_deref()).map_err(|e| Error::InvalidRequest(e.to_string()))?;
+  let providers = networks.providers(network).map_err(|e| Error::Internal(e.to_string()))?;
+  Ok(Json(json!({
+    "network": network,
+    "providers": providers.statuses(),
+  })))
+}
+
+async fn test_connection(
+  State(app_state): State<AppState>,
+  Query(query): Query<NetworkQuery>,
+) -> Result<Json<Value>, Error> {
+  let repository = EnhancedSuiRepository::for_network(app_state, query.network.as_deref()).await?;
+  let chain_id = repository.get_chain_identifier().await?;
+  Ok(Json(json!({
+    "connection": "ok",
+    "network": repository.network(),
+    "chain_id": chain_id,
+  })))
+}
+
+async fn get_network_info(
+  State(app_state): State<AppState>,
+  Query(query): Query<NetworkQuery>,
+) -> Result<Json<NetworkInfo>, Error> {
+  let repository = EnhancedSuiRepository::for_network(app_state, query.network.as_deref()).await?;
+  Ok(Json(NetworkInfo {
+    network: repository.network().to_string(),
+    chain_id: repository.get_chain_identifier().await?,
+    checkpoint: repository.get_latest_checkpoint_sequence_number().await?,
+    gas_price: repository.get_reference_gas_price().await?,
   }))
 }
 
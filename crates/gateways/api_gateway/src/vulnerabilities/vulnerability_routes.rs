@@ -1,29 +1,131 @@
+use ai_analysis_service::domain::analysis_models::{FindingKind, Severity};
+use ai_analysis_service::domain::analysis_repository_trait::{
+    AnalysisRepository, SuppressionKind, VulnerabilityFilter, VulnerabilitySort,
+    VulnerabilityStatus,
+};
+use ai_analysis_service::domain::disclosure::{DisclosureState, SCOPE_VULNERABILITIES_DISCLOSURE};
+use ai_analysis_service::infrastructure::analysis_repository_impl::AnalysisRepositoryImpl;
+use ai_analysis_service::models::sarif::{SarifLog, SARIF_CONTENT_TYPE};
+use auth_service::domain::Claims;
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json as ResponseJson,
+    extract::{Extension, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{delete, get, post, put},
     Json, Router,
 };
+use chrono::{DateTime, Utc};
+use jd_core::ctx::scope_matches;
 use jd_core::AppState;
+use serde::Deserialize;
 use serde_json::{json, Value};
+use tracing::error;
+use std::collections::HashSet;
 use uuid::Uuid;
 
-// Placeholder handlers that return mock data for now
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListVulnerabilitiesQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    pub severity: Option<String>,
+    pub status: Option<VulnerabilityStatus>,
+    pub repository_id: Option<Uuid>,
+    /// `CWE-284` or `284`.
+    pub cwe: Option<String>,
+    pub min_cvss: Option<f64>,
+    pub max_cvss: Option<f64>,
+    /// `code` or `dependency`.
+    pub kind: Option<FindingKind>,
+    /// Only callers who may see undisclosed findings can ask for states other
+    /// than `public`.
+    pub disclosure_state: Option<DisclosureState>,
+    pub sort: Option<VulnerabilitySort>,
+    pub order: Option<SortOrder>,
+}
+
+impl ListVulnerabilitiesQuery {
+    fn into_filter(self) -> Result<VulnerabilityFilter, StatusCode> {
+        let severity = match self.severity.as_deref().map(str::to_lowercase).as_deref() {
+            None => None,
+            Some("critical") => Some(Severity::Critical),
+            Some("high") => Some(Severity::High),
+            Some("medium") => Some(Severity::Medium),
+            Some("low") => Some(Severity::Low),
+            Some(_) => return Err(StatusCode::BAD_REQUEST),
+        };
+        let valid_score = |score: &f64| (0.0..=10.0).contains(score);
+        if !self.min_cvss.iter().chain(self.max_cvss.iter()).all(valid_score) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        let cwe_id = self.cwe.map(|cwe| {
+            let cwe = cwe.trim();
+            let number = cwe.trim_start_matches("CWE-").trim_start_matches("cwe-");
+            format!("CWE-{}", number)
+        });
+
+        Ok(VulnerabilityFilter {
+            repository_id: self.repository_id,
+            severity,
+            status: self.status,
+            cwe_id,
+            min_cvss_score: self.min_cvss,
+            max_cvss_score: self.max_cvss,
+            kind: self.kind,
+            disclosure_state: self.disclosure_state,
+            sort: self.sort.unwrap_or_default(),
+            descending: !matches!(self.order, Some(SortOrder::Asc)),
+            page: self.page.unwrap_or(1).max(1),
+            limit: self.limit.unwrap_or(20).clamp(1, 100),
+        })
+    }
+}
+
+/// Whether the caller's token lets them see findings that are not public
+/// yet. Anonymous callers only see public findings.
+fn sees_undisclosed(caller: &Option<Extension<Claims>>) -> bool {
+    caller.as_ref().is_some_and(|Extension(claims)| {
+        claims.scopes.iter().any(|granted| scope_matches(granted, SCOPE_VULNERABILITIES_DISCLOSURE))
+    })
+}
+
 pub async fn list_vulnerabilities(
-    State(_app_state): State<AppState>,
-    Query(_params): Query<Value>,
+    State(app_state): State<AppState>,
+    caller: Option<Extension<Claims>>,
+    Query(query): Query<ListVulnerabilitiesQuery>,
 ) -> Result<ResponseJson<Value>, StatusCode> {
+    let mut filter = query.into_filter()?;
+    if !sees_undisclosed(&caller) {
+        if filter.disclosure_state.is_some_and(|state| state != DisclosureState::Public) {
+            return Err(StatusCode::FORBIDDEN);
+        }
+        filter.disclosure_state = Some(DisclosureState::Public);
+    }
+    let (vulnerabilities, total_count) = AnalysisRepositoryImpl::new(app_state)
+        .list_vulnerabilities(&filter)
+        .await
+        .map_err(|e| {
+            error!("Failed to list vulnerabilities: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
     let response = json!({
-        "vulnerabilities": [],
-        "total_count": 0,
-        "page": 1,
-        "limit": 20,
-        "has_more": false
+        "vulnerabilities": vulnerabilities,
+        "total_count": total_count,
+        "page": filter.page,
+        "limit": filter.limit,
+        "has_more": (filter.page as i64) * (filter.limit as i64) < total_count
     });
     Ok(ResponseJson(response))
 }
 
+// Placeholder handlers that return mock data for now
 pub async fn get_vulnerability(
     State(_app_state): State<AppState>,
     Path(id): Path<Uuid>,
@@ -44,6 +146,43 @@ pub async fn get_vulnerability(
     Ok(ResponseJson(response))
 }
 
+/// The evidence an LLM-assisted finding was reported on, by every analysis
+/// that reported it, newest first: the code snippet, the model's reasoning,
+/// the pattern it matched, the model and prompt version, and its confidence.
+pub async fn get_vulnerability_evidence(
+    State(app_state): State<AppState>,
+    caller: Option<Extension<Claims>>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<Value>, StatusCode> {
+    let repository = AnalysisRepositoryImpl::new(app_state);
+    let record = repository
+        .get_vulnerability_record(id)
+        .await
+        .map_err(|e| {
+            error!("Failed to load vulnerability {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .filter(|record| {
+            record.disclosure_state == DisclosureState::Public || sees_undisclosed(&caller)
+        })
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let evidence = repository.list_finding_evidence(id).await.map_err(|e| {
+        error!("Failed to load evidence of vulnerability {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(ResponseJson(json!({
+        "vulnerability_id": id,
+        "repository_id": record.repository_id,
+        "vulnerability_type": record.finding.vulnerability_type,
+        "severity": record.finding.severity,
+        "cwe_id": record.finding.cwe_id,
+        "status": record.status,
+        "llm_assisted": record.finding.prompt_version.is_some(),
+        "evidence": evidence
+    })))
+}
+
 pub async fn update_vulnerability_status(
     State(_app_state): State<AppState>,
     Path(id): Path<Uuid>,
@@ -160,6 +299,202 @@ pub async fn export_vulnerabilities(
     Ok(ResponseJson(response))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub format: Option<String>,
+    /// Repository id, or `owner/name`.
+    pub repo: String,
+}
+
+/// Findings of the repository's latest analysis as a SARIF 2.1.0 log, ready
+/// for GitHub Code Scanning or an IDE SARIF viewer.
+pub async fn export_sarif(
+    State(app_state): State<AppState>,
+    caller: Option<Extension<Claims>>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, StatusCode> {
+    let format = query.format.as_deref().unwrap_or("sarif");
+    if !format.eq_ignore_ascii_case("sarif") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let repository = match Uuid::parse_str(&query.repo) {
+        Ok(id) => sqlx::query_as::<_, (Uuid, String)>(
+            "SELECT id, full_name FROM github_repositories WHERE id = $1",
+        )
+        .bind(id),
+        Err(_) => sqlx::query_as::<_, (Uuid, String)>(
+            "SELECT id, full_name FROM github_repositories WHERE full_name = $1",
+        )
+        .bind(query.repo.trim_matches('/')),
+    }
+    .fetch_optional(app_state.mm().dbx().db())
+    .await
+    .map_err(|e| {
+        error!("Database error looking up repository {}: {}", query.repo, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let (repository_id, full_name) = repository.ok_or(StatusCode::NOT_FOUND)?;
+
+    let analysis_repository = AnalysisRepositoryImpl::new(app_state);
+    let mut analysis = analysis_repository
+        .get_latest_analysis_for_repository(repository_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to load latest analysis for {}: {}", full_name, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if !sees_undisclosed(&caller) {
+        let undisclosed: HashSet<Uuid> = analysis_repository
+            .list_undisclosed_vulnerability_ids(repository_id)
+            .await
+            .map_err(|e| {
+                error!("Failed to load undisclosed findings of {}: {}", full_name, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .into_iter()
+            .collect();
+        analysis.vulnerabilities.retain(|finding| !undisclosed.contains(&finding.id));
+    }
+
+    let repository_uri = format!("https://github.com/{}", full_name);
+    let log = SarifLog::from_analysis(&analysis, Some(&repository_uri));
+    let body = serde_json::to_vec_pretty(&log).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let disposition = format!(
+        "attachment; filename=\"{}-{}.sarif\"",
+        full_name.replace('/', "-"),
+        &analysis.commit_sha[..analysis.commit_sha.len().min(7)]
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, SARIF_CONTENT_TYPE.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuppressVulnerabilityRequest {
+    pub kind: SuppressionKind,
+    pub reason: String,
+    /// Unset for suppressions that never expire.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Suppress a finding as a false positive or accepted risk. Later analyses
+/// that report it again store it suppressed until the suppression expires or
+/// is revoked.
+pub async fn suppress_vulnerability(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SuppressVulnerabilityRequest>,
+) -> Result<(StatusCode, ResponseJson<Value>), StatusCode> {
+    let reason = request.reason.trim();
+    if reason.is_empty() || request.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let suppression = AnalysisRepositoryImpl::new(app_state)
+        .suppress_vulnerability(id, request.kind, reason, request.expires_at, &caller.address)
+        .await
+        .map_err(|e| {
+            error!("Failed to suppress vulnerability {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok((StatusCode::CREATED, ResponseJson(json!({ "suppression": suppression }))))
+}
+
+/// Revoke a finding's suppression, reopening it.
+pub async fn unsuppress_vulnerability(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    let revoked = AnalysisRepositoryImpl::new(app_state)
+        .revoke_suppression(id)
+        .await
+        .map_err(|e| {
+            error!("Failed to revoke suppression of vulnerability {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if revoked {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListSuppressionsQuery {
+    /// Include revoked and expired suppressions.
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+pub async fn list_repository_suppressions(
+    State(app_state): State<AppState>,
+    Path(repository_id): Path<Uuid>,
+    Query(query): Query<ListSuppressionsQuery>,
+) -> Result<ResponseJson<Value>, StatusCode> {
+    let suppressions = AnalysisRepositoryImpl::new(app_state)
+        .list_suppressions(repository_id, query.include_inactive)
+        .await
+        .map_err(|e| {
+            error!("Failed to list suppressions for repository {}: {}", repository_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(ResponseJson(json!({
+        "repository_id": repository_id,
+        "total_count": suppressions.len(),
+        "suppressions": suppressions
+    })))
+}
+
+/// GET /vulnerabilities/{id}/disclosure
+pub async fn get_vulnerability_disclosure(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> crate::Result<ResponseJson<Value>> {
+    let disclosure = AnalysisRepositoryImpl::new(app_state)
+        .get_disclosure(id)
+        .await?
+        .ok_or(ai_analysis_service::Error::VulnerabilityNotFound { vulnerability_id: id })?;
+
+    Ok(ResponseJson(json!({ "disclosure": disclosure })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateDisclosureRequest {
+    pub state: DisclosureState,
+    /// Required when embargoing, and only then.
+    pub embargo_until: Option<DateTime<Utc>>,
+}
+
+/// PUT /vulnerabilities/{id}/disclosure
+/// Move a finding forward through disclosure. Embargoed findings are
+/// published when their embargo lapses.
+pub async fn update_vulnerability_disclosure(
+    State(app_state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateDisclosureRequest>,
+) -> crate::Result<ResponseJson<Value>> {
+    let disclosure = AnalysisRepositoryImpl::new(app_state)
+        .update_disclosure(id, request.state, request.embargo_until, &caller.address)
+        .await?
+        .ok_or(ai_analysis_service::Error::VulnerabilityNotFound { vulnerability_id: id })?;
+
+    Ok(ResponseJson(json!({ "disclosure": disclosure })))
+}
+
 pub fn vulnerability_router() -> Router<AppState> {
     Router::new()
         // List and Filter
@@ -170,6 +505,7 @@ pub fn vulnerability_router() -> Router<AppState> {
         // Individual Vulnerability
         .route("/{id}", get(get_vulnerability))
         .route("/{id}/status", put(update_vulnerability_status))
+        .route("/{id}/evidence", get(get_vulnerability_evidence))
         .route("/{id}", delete(delete_vulnerability))
         // Repository Specific
         .route("/repository/{repository_id}", get(get_repository_vulnerabilities))
@@ -177,4 +513,24 @@ pub fn vulnerability_router() -> Router<AppState> {
         // Bulk Operations
         .route("/bulk/update", post(bulk_update_vulnerabilities))
         .route("/bulk/export", post(export_vulnerabilities))
-}
\ No newline at end of file
+        .route("/export", get(export_sarif))
+}
+
+/// Triage routes; suppressions record who made them, so these need a caller.
+pub fn vulnerability_triage_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{id}/suppress",
+            post(suppress_vulnerability).delete(unsuppress_vulnerability),
+        )
+        .route("/repository/{repository_id}/suppressions", get(list_repository_suppressions))
+}
+
+/// Disclosure routes; moves record who made them and need the
+/// `vulnerabilities:disclosure` scope.
+pub fn vulnerability_disclosure_router() -> Router<AppState> {
+    Router::new().route(
+        "/{id}/disclosure",
+        get(get_vulnerability_disclosure).put(update_vulnerability_disclosure),
+    )
+}
@@ -1,18 +1,19 @@
 use axum::{
-  routing::{get, post},
+  routing::{delete, get, post},
   Router,
 };
 use jd_core::AppState;
 
 // Import the static handler functions directly
 use auth_service::application::handlers::auth_handler::AuthHandler;
+use auth_service::application::handlers::github_oauth_handler::GithubOAuthHandler;
+use auth_service::application::handlers::role_admin_handler::RoleAdminHandler;
 use auth_service::infrastructure::database::{
-  NonceRepositoryImpl, SignatureVerifierImpl, UserRepositoryImpl,
+  NonceRepositoryImpl, RoleRepositoryImpl, UserRepositoryImpl,
 };
 
 // Type alias for our concrete AuthHandler
-type ConcreteAuthHandler =
-  AuthHandler<NonceRepositoryImpl, UserRepositoryImpl, SignatureVerifierImpl>;
+type ConcreteAuthHandler = AuthHandler<NonceRepositoryImpl, UserRepositoryImpl, RoleRepositoryImpl>;
 
 /// Creates authentication routes using auth_service handlers
 pub fn auth_routes() -> Router<AppState> {
@@ -21,4 +22,18 @@ pub fn auth_routes() -> Router<AppState> {
     .route("/login", post(ConcreteAuthHandler::verify_signature))
     .route("/refresh", post(ConcreteAuthHandler::refresh_token))
     .route("/me", get(ConcreteAuthHandler::get_current_user))
+    .route("/github/login", get(GithubOAuthHandler::github_login))
+    .route("/github/callback", get(GithubOAuthHandler::github_callback))
+}
+
+/// Role administration routes. Mounted behind bearer auth and the
+/// `roles:admin` scope policy in `v1_routes`.
+pub fn role_admin_routes() -> Router<AppState> {
+  Router::new()
+    .route("/roles", get(RoleAdminHandler::list_roles))
+    .route(
+      "/users/{user_id}/roles",
+      get(RoleAdminHandler::list_user_roles).post(RoleAdminHandler::grant_role),
+    )
+    .route("/users/{user_id}/roles/{role}", delete(RoleAdminHandler::revoke_role))
 }
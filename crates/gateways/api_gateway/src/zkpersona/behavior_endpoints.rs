@@ -0,0 +1,74 @@
+use auth_service::domain::Claims;
+use axum::{
+  Extension, Router,
+  extract::{DefaultBodyLimit, Path, State},
+  response::Json,
+  routing::{get, post, put},
+};
+use behavior_service::{
+  application::use_cases::behavior_use_cases::BehaviorUseCases,
+  domain::{event_schema::EventSchema, ingestion_tier::IngestionTiers},
+  infrastructure::behavior_repository_impl::BehaviorRepositoryImpl,
+  models::{
+    requests::{BehaviorBatchRequest, EventSchemaRequest},
+    responses::BatchIngestionResponse,
+  },
+};
+use jd_core::AppState;
+
+use crate::Result;
+
+/// Room for a full batch of 1,000 events; axum's default is 2 MB.
+const BATCH_BODY_LIMIT: usize = 16 * 1024 * 1024;
+
+fn behavior(app_state: AppState) -> BehaviorUseCases<BehaviorRepositoryImpl> {
+  let tiers = IngestionTiers::from_config(app_state.config.behavior_analysis.as_ref());
+  BehaviorUseCases::new(BehaviorRepositoryImpl::new(app_state), tiers)
+}
+
+/// Batch ingestion and the schemas it validates against, mounted by
+/// `v1_routes` behind bearer auth.
+pub fn behavior_router() -> Router<AppState> {
+  Router::new()
+    .route(
+      "/behavior/batch",
+      post(ingest_behavior_batch).layer(DefaultBodyLimit::max(BATCH_BODY_LIMIT)),
+    )
+    .route("/behavior/schemas", get(list_event_schemas))
+}
+
+/// Registering schemas decides which events are accepted, so `v1_routes`
+/// mounts this behind `behavior:schemas_admin`.
+pub fn behavior_schema_admin_router() -> Router<AppState> {
+  Router::new().route("/behavior/schemas/{input_type}", put(register_event_schema))
+}
+
+/// POST /behavior/batch
+/// Ingest up to 1,000 events, reporting for each whether it was accepted,
+/// a duplicate of an already ingested client event id, or rejected.
+pub async fn ingest_behavior_batch(
+  State(app_state): State<AppState>,
+  Json(request): Json<BehaviorBatchRequest>,
+) -> Result<Json<BatchIngestionResponse>> {
+  Ok(Json(behavior(app_state).ingest_batch(request).await?))
+}
+
+/// GET /behavior/schemas
+pub async fn list_event_schemas(
+  State(app_state): State<AppState>,
+) -> Result<Json<Vec<EventSchema>>> {
+  Ok(Json(behavior(app_state).list_event_schemas().await?))
+}
+
+/// PUT /behavior/schemas/{input_type}
+pub async fn register_event_schema(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(input_type): Path<String>,
+  Json(request): Json<EventSchemaRequest>,
+) -> Result<Json<EventSchema>> {
+  let schema = behavior(app_state)
+    .register_event_schema(&input_type, request.schema, &caller.address)
+    .await?;
+  Ok(Json(schema))
+}
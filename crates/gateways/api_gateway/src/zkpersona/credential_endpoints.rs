@@ -0,0 +1,116 @@
+use auth_service::domain::Claims;
+use axum::{
+  Extension, Router,
+  extract::{Path, State},
+  http::StatusCode,
+  response::Json,
+  routing::{get, post},
+};
+use jd_core::AppState;
+use serde::Deserialize;
+use uuid::Uuid;
+use zkproof_service::{
+  Error,
+  application::use_cases::credential_use_cases::CredentialUseCases,
+  domain::credential::{
+    CredentialIssuer, CredentialRecord, CredentialVerification, IssuedCredential,
+  },
+  infrastructure::{credential_signer::CredentialSigner, credential_store::CredentialStore},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct IssueCredentialRequest {
+  /// Score the credential asserts the holder has at least, 1 to 100.
+  pub threshold: i16,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevokeCredentialRequest {
+  pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyCredentialRequest {
+  /// The VC-JWT presented by the holder.
+  pub credential: String,
+  pub minimum_score: Option<i16>,
+}
+
+fn credentials(app_state: &AppState) -> Result<CredentialUseCases, Error> {
+  let signer = CredentialSigner::from_env().ok_or_else(|| {
+    Error::CredentialIssuerUnavailable(
+      "Credentials need CREDENTIALS_ISSUER and its keys to be set".to_string(),
+    )
+  })?;
+  let store = CredentialStore::new(app_state.mm().dbx().db().clone());
+  Ok(CredentialUseCases::new(store, signer))
+}
+
+/// Verification and status lookups, for partners checking a credential.
+pub fn credential_router() -> Router<AppState> {
+  Router::new()
+    .route("/credentials/issuer", get(get_issuer))
+    .route("/credentials/verify", post(verify_credential))
+    .route("/credentials/{id}/status", get(get_credential_status))
+}
+
+/// Issuance and revocation act on the token subject's credentials, so
+/// `v1_routes` mounts this behind bearer auth.
+pub fn credential_holder_router() -> Router<AppState> {
+  Router::new()
+    .route("/credentials", post(issue_credential))
+    .route("/credentials/{id}/revoke", post(revoke_credential))
+}
+
+/// POST /credentials
+/// Issue a credential that the caller's latest score is at least
+/// `threshold`.
+pub async fn issue_credential(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Json(request): Json<IssueCredentialRequest>,
+) -> Result<(StatusCode, Json<IssuedCredential>), Error> {
+  let issued = credentials(&app_state)?.issue(&caller.address, request.threshold).await?;
+  Ok((StatusCode::CREATED, Json(issued)))
+}
+
+/// POST /credentials/{id}/revoke
+pub async fn revoke_credential(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+  Json(request): Json<RevokeCredentialRequest>,
+) -> Result<Json<CredentialRecord>, Error> {
+  let record = credentials(&app_state)?
+    .revoke(id, &caller.address, request.reason.as_deref())
+    .await?;
+  Ok(Json(record))
+}
+
+/// GET /credentials/{id}/status
+/// The credential's revocation registry entry, linked from its
+/// `credentialStatus`.
+pub async fn get_credential_status(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<CredentialRecord>, Error> {
+  Ok(Json(credentials(&app_state)?.status(id).await?))
+}
+
+/// POST /credentials/verify
+pub async fn verify_credential(
+  State(app_state): State<AppState>,
+  Json(request): Json<VerifyCredentialRequest>,
+) -> Result<Json<CredentialVerification>, Error> {
+  let verification =
+    credentials(&app_state)?.verify(&request.credential, request.minimum_score).await?;
+  Ok(Json(verification))
+}
+
+/// GET /credentials/issuer
+/// The issuer's public key, for verifying credentials offline.
+pub async fn get_issuer(
+  State(app_state): State<AppState>,
+) -> Result<Json<CredentialIssuer>, Error> {
+  Ok(Json(credentials(&app_state)?.issuer()))
+}
@@ -1,7 +1,17 @@
-use axum::{routing::post, Router};
+use axum::{
+  routing::{get, post},
+  Router,
+};
 use jd_core::AppState;
 
 pub mod auth_endpoints;
+pub mod behavior_endpoints;
+pub mod credential_endpoints;
+pub mod proof_job_endpoints;
+pub mod proof_key_endpoints;
+pub mod proof_request_endpoints;
+pub mod proof_verification_endpoints;
+pub mod score_history_endpoints;
 pub mod unified_endpoints;
 
 pub fn zkpersona_router() -> Router<AppState> {
@@ -12,5 +22,11 @@ pub fn zkpersona_router() -> Router<AppState> {
 }
 
 pub fn zkpersona_protected_router() -> Router<AppState> {
-  Router::new().route("/generate-proof", post(unified_endpoints::generate_proof))
+  Router::new()
+    .route("/generate-proof", post(unified_endpoints::generate_proof))
+    .route("/proofs", post(proof_job_endpoints::request_proof))
+    .route("/proofs/jobs/{id}", get(proof_job_endpoints::get_proof_job))
+    .route("/proofs/{id}/verify", post(proof_verification_endpoints::verify_stored_proof))
+    .route("/proofs/{id}/download", get(proof_verification_endpoints::download_proof))
+    .merge(proof_key_endpoints::proof_key_router())
 }
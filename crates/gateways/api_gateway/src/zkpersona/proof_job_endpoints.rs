@@ -0,0 +1,55 @@
+use axum::{
+  extract::{Path, State},
+  http::StatusCode,
+  response::Json,
+};
+use jd_core::AppState;
+use serde::Deserialize;
+use uuid::Uuid;
+use zkproof_service::{
+  Error,
+  application::use_cases::proving_use_cases::ProvingUseCases,
+  domain::proof_job::ProofJob,
+  infrastructure::{
+    ezkl_prover::EzklProver, object_artifact_store::ObjectArtifactStore,
+    proof_job_store::ProofJobStore, verification_key_store::VerificationKeyStore,
+  },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct RequestProofRequest {
+  pub scoring_result_id: Uuid,
+  /// Hex challenge the proof is to commit to, such as a proof request's
+  /// nonce.
+  pub challenge: Option<String>,
+}
+
+fn proving(
+  app_state: &AppState,
+) -> Result<ProvingUseCases<EzklProver, ObjectArtifactStore>, Error> {
+  let prover = EzklProver::from_env().ok_or_else(|| {
+    Error::ProverUnavailable("Proving needs EZKL_ARTIFACTS_DIR to be set".to_string())
+  })?;
+  let db = app_state.mm().dbx().db().clone();
+  Ok(ProvingUseCases::new(ProofJobStore::new(db.clone()), VerificationKeyStore::new(db), prover))
+}
+
+/// POST /proofs
+/// Queue a proof of a scoring result; proving runs in the background.
+pub async fn request_proof(
+  State(app_state): State<AppState>,
+  Json(request): Json<RequestProofRequest>,
+) -> Result<(StatusCode, Json<ProofJob>), Error> {
+  let job = proving(&app_state)?
+    .request_proof(request.scoring_result_id, request.challenge.as_deref())
+    .await?;
+  Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// GET /proofs/jobs/{id}
+pub async fn get_proof_job(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<ProofJob>, Error> {
+  Ok(Json(proving(&app_state)?.get_job(id).await?))
+}
@@ -0,0 +1,94 @@
+use auth_service::domain::Claims;
+use axum::{
+  Extension, Router,
+  extract::{Path, Query, State},
+  http::StatusCode,
+  response::Json,
+  routing::{get, post},
+};
+use base64::{Engine as _, engine::general_purpose};
+use jd_core::AppState;
+use serde::Deserialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+use zkproof_service::{
+  Error,
+  application::use_cases::verification_key_use_cases::VerificationKeyUseCases,
+  domain::verification_key::{NewVerificationKey, VerificationKey, VerificationKeyDetail},
+  infrastructure::verification_key_store::VerificationKeyStore,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct PublishKeyRequest {
+  pub model_version: String,
+  pub circuit_version: String,
+  pub prover_backend: String,
+  /// Base64 of the key file.
+  pub verification_key: String,
+  #[serde(default, with = "time::serde::rfc3339::option")]
+  pub active_from: Option<OffsetDateTime>,
+  #[serde(default, with = "time::serde::rfc3339::option")]
+  pub active_until: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KeyListParams {
+  pub model_version: Option<String>,
+}
+
+fn key_registry(app_state: &AppState) -> VerificationKeyUseCases {
+  VerificationKeyUseCases::new(VerificationKeyStore::new(app_state.mm().dbx().db().clone()))
+}
+
+/// Verification key lookups, for users verifying proofs themselves.
+pub fn proof_key_router() -> Router<AppState> {
+  Router::new()
+    .route("/proofs/keys", get(list_keys))
+    .route("/proofs/keys/{id}", get(get_key))
+}
+
+/// Key publishing. `v1_routes` mounts this behind bearer auth and the
+/// `proofs:keys_admin` scope policy.
+pub fn proof_key_admin_router() -> Router<AppState> {
+  Router::new().route("/proofs/keys", post(publish_key))
+}
+
+/// POST /proofs/keys
+/// Publish a verification key; the model's current key is used for new
+/// proofs until the new one activates.
+pub async fn publish_key(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Json(request): Json<PublishKeyRequest>,
+) -> Result<(StatusCode, Json<VerificationKey>), Error> {
+  let verification_key = general_purpose::STANDARD
+    .decode(&request.verification_key)
+    .map_err(|_| Error::InvalidInput("verification_key must be base64".to_string()))?;
+  let key = NewVerificationKey {
+    model_version: request.model_version,
+    circuit_version: request.circuit_version,
+    prover_backend: request.prover_backend,
+    verification_key,
+    active_from: request.active_from,
+    active_until: request.active_until,
+    published_by: Some(caller.address),
+  };
+  let published = key_registry(&app_state).publish(key).await?;
+  Ok((StatusCode::CREATED, Json(published)))
+}
+
+/// GET /proofs/keys
+pub async fn list_keys(
+  State(app_state): State<AppState>,
+  Query(params): Query<KeyListParams>,
+) -> Result<Json<Vec<VerificationKey>>, Error> {
+  Ok(Json(key_registry(&app_state).list(params.model_version.as_deref()).await?))
+}
+
+/// GET /proofs/keys/{id}
+pub async fn get_key(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<VerificationKeyDetail>, Error> {
+  Ok(Json(key_registry(&app_state).get(id).await?))
+}
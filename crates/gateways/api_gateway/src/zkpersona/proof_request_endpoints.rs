@@ -0,0 +1,96 @@
+use auth_service::domain::Claims;
+use axum::{
+  Extension, Router,
+  extract::{Path, State},
+  http::StatusCode,
+  response::Json,
+  routing::{get, post},
+};
+use jd_core::AppState;
+use serde::Deserialize;
+use std::time::Duration;
+use uuid::Uuid;
+use zkproof_service::{
+  Error,
+  application::use_cases::proof_request_use_cases::ProofRequestUseCases,
+  domain::proof_request::{CreatedProofRequest, ProofRequest, ProofStatement},
+  infrastructure::proof_request_store::ProofRequestStore,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateProofRequestRequest {
+  /// Wallet address of the user asked; no one else may fulfil the request.
+  pub subject: String,
+  pub statement: ProofStatement,
+  /// Where the signed answer is posted once the request is fulfilled.
+  pub callback_url: String,
+  /// Defaults to 15 minutes, at most 24 hours.
+  pub expires_in_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FulfilProofRequestRequest {
+  pub proof_id: Uuid,
+}
+
+fn proof_requests(app_state: &AppState) -> ProofRequestUseCases {
+  ProofRequestUseCases::new(ProofRequestStore::new(app_state.mm().dbx().db().clone()))
+}
+
+/// Partners create and read their own requests, so `v1_routes` mounts this
+/// behind bearer auth and the `proofs:partner` scope policy.
+pub fn proof_request_partner_router() -> Router<AppState> {
+  Router::new()
+    .route("/proof-requests", post(create_proof_request))
+    .route("/proof-requests/{id}", get(get_proof_request))
+}
+
+/// Users fulfil requests addressed to them as the token subject, so
+/// `v1_routes` mounts this behind bearer auth.
+pub fn proof_request_router() -> Router<AppState> {
+  Router::new().route("/proof-requests/{id}/fulfil", post(fulfil_proof_request))
+}
+
+/// POST /proof-requests
+/// Ask a user to prove `statement`. The response carries the callback
+/// secret, which is not shown again.
+pub async fn create_proof_request(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Json(request): Json<CreateProofRequestRequest>,
+) -> Result<(StatusCode, Json<CreatedProofRequest>), Error> {
+  let created = proof_requests(&app_state)
+    .create(
+      &caller.address,
+      &request.subject,
+      request.statement,
+      &request.callback_url,
+      request.expires_in_secs.map(Duration::from_secs),
+    )
+    .await?;
+  Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// GET /proof-requests/{id}
+/// One of the caller's own requests.
+pub async fn get_proof_request(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<ProofRequest>, Error> {
+  Ok(Json(proof_requests(&app_state).get(id, &caller.address).await?))
+}
+
+/// POST /proof-requests/{id}/fulfil
+/// Fulfil a request addressed to the caller with one of their verified
+/// proofs, generated for the request's nonce.
+pub async fn fulfil_proof_request(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(id): Path<Uuid>,
+  Json(request): Json<FulfilProofRequestRequest>,
+) -> Result<Json<ProofRequest>, Error> {
+  let fulfilled =
+    proof_requests(&app_state).fulfil(id, &caller.address, request.proof_id).await?;
+  Ok(Json(fulfilled))
+}
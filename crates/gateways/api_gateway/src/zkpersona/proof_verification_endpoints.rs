@@ -0,0 +1,54 @@
+use axum::{
+  extract::{Path, State},
+  response::Json,
+};
+use jd_core::AppState;
+use uuid::Uuid;
+use zkproof_service::{
+  Error,
+  application::use_cases::{
+    artifact_use_cases::ProofArtifactUseCases, verification_use_cases::ProofVerificationUseCases,
+  },
+  domain::{artifact_store_trait::ProofDownload, proof_verification::ProofVerification},
+  infrastructure::{
+    ezkl_prover::EzklProver, object_artifact_store::ObjectArtifactStore, proof_store::ProofStore,
+    verification_key_store::VerificationKeyStore,
+  },
+};
+
+/// POST /proofs/{id}/verify
+/// Verify a stored proof with the configured backend; `verified` follows
+/// the result.
+pub async fn verify_stored_proof(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<ProofVerification>, Error> {
+  let prover = EzklProver::from_env().ok_or_else(|| {
+    Error::ProverUnavailable("Verification needs EZKL_ARTIFACTS_DIR to be set".to_string())
+  })?;
+  let db = app_state.mm().dbx().db().clone();
+  let mut verification = ProofVerificationUseCases::new(
+    ProofStore::new(db.clone()),
+    VerificationKeyStore::new(db),
+    prover,
+  );
+  if let Some(objects) = app_state.objects.clone() {
+    verification = verification.with_artifacts(ObjectArtifactStore::new(objects));
+  }
+  Ok(Json(verification.verify_proof(id).await?))
+}
+
+/// GET /proofs/{id}/download
+/// Presigned object storage links to the proof, and to its verification key
+/// if it predates the key registry.
+pub async fn download_proof(
+  State(app_state): State<AppState>,
+  Path(id): Path<Uuid>,
+) -> Result<Json<ProofDownload>, Error> {
+  let objects = app_state.objects.clone().ok_or_else(|| {
+    Error::ProverUnavailable("Downloads need an object store to be configured".to_string())
+  })?;
+  let artifacts = ObjectArtifactStore::new(objects);
+  let store = ProofStore::new(app_state.mm().dbx().db().clone());
+  Ok(Json(ProofArtifactUseCases::new(store, artifacts).download(id).await?))
+}
@@ -0,0 +1,39 @@
+use auth_service::domain::Claims;
+use axum::{
+  Extension, Router,
+  extract::{Path, Query, State},
+  response::Json,
+  routing::get,
+};
+use jd_core::AppState;
+use scoring_service::{
+  application::use_cases::score_history_use_cases::ScoreHistoryUseCases,
+  infrastructure::scoring_repository_impl::ScoringRepositoryImpl,
+  models::{requests::ScoreHistoryQuery, responses::ScoreHistoryResponse},
+};
+
+use crate::{Result, error::Error};
+
+/// Score histories show the behavior inputs behind each score, so
+/// `v1_routes` mounts this behind bearer auth.
+pub fn score_history_router() -> Router<AppState> {
+  Router::new().route("/scores/{subject}/history", get(get_score_history))
+}
+
+/// GET /scores/{subject}/history
+/// The subject's scores over time with their deltas, the model behind each
+/// and the behavior inputs that changed. Subjects can only see their own.
+pub async fn get_score_history(
+  State(app_state): State<AppState>,
+  Extension(caller): Extension<Claims>,
+  Path(subject): Path<String>,
+  Query(query): Query<ScoreHistoryQuery>,
+) -> Result<Json<ScoreHistoryResponse>> {
+  if !subject.eq_ignore_ascii_case(&caller.address) {
+    return Err(Error::insufficient_permissions(format!("score history of {}", subject)));
+  }
+  let history = ScoreHistoryUseCases::new(ScoringRepositoryImpl::new(app_state))
+    .history(&caller.address, query)
+    .await?;
+  Ok(Json(history))
+}
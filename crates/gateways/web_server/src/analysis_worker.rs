@@ -0,0 +1,332 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use ai_analysis_service::{
+  AnalysisUseCases, MoveBytecodeAnalyzer, WebhookLeakNotifier, WebhookRegressionNotifier,
+  domain::{
+    analysis_models::{AnalysisResult, AnalysisType as AiAnalysisType, Severity},
+    change_set::{ChangeSet, ChangeSetProvider},
+  },
+  infrastructure::analysis_repository_impl::AnalysisRepositoryImpl,
+  models::requests::AnalyzeRepositoryRequest,
+};
+use async_trait::async_trait;
+use github_service::{
+  AnalysisJob, AnalysisJobProcessor, AnalysisWorker, Error, GitHubClient, GitHubServiceConfig,
+  GitHubServiceFactory, RepositoryPackageStore, is_in_scope,
+};
+use jd_core::AppState;
+use sqlx::{FromRow, types::Uuid};
+use tracing::{info, warn};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Move packages are fetched with their manifest so they can be compiled, and
+/// manifests with their lock files so dependencies can be checked.
+const SMART_CONTRACT_EXTENSIONS: [&str; 8] = [
+  ".sol",
+  ".rs",
+  ".move",
+  ".vy",
+  "Move.toml",
+  "Move.lock",
+  "Cargo.toml",
+  "Cargo.lock",
+];
+/// Findings listed in a pull request review; the rest are summarised.
+const MAX_REVIEW_FINDINGS: usize = 20;
+
+/// Start draining the durable analysis queue, unless GitHub is not configured
+/// or `GITHUB.ANALYSIS_WORKER_ENABLED=false` leaves it to another process.
+pub fn start(app_state: AppState) {
+  let Some(github) = app_state.config.github.as_ref() else {
+    info!("GitHub is not configured; analysis worker not started");
+    return;
+  };
+  if !github.analysis_worker_enabled.unwrap_or(true) {
+    info!("Analysis worker disabled by GITHUB.ANALYSIS_WORKER_ENABLED");
+    return;
+  }
+
+  let worker = match build_worker(&app_state) {
+    Ok(worker) => worker,
+    Err(e) => {
+      warn!(error = %e, "Analysis worker not started");
+      return;
+    }
+  };
+  let shutdown = app_state.shutdown.clone();
+  shutdown.spawn("analysis_worker", run(worker, app_state));
+}
+
+fn build_worker(
+  app_state: &AppState,
+) -> github_service::Result<AnalysisWorker<RepositoryAnalysis>> {
+  let config = GitHubServiceConfig::from_config(&app_state.config)?;
+  let github_client = Arc::new(GitHubServiceFactory::create_cached_client(
+    &config,
+    app_state.mm().dbx().db().clone(),
+  )?);
+  let queue = Arc::new(GitHubServiceFactory::create_analysis_queue(
+    &config,
+    app_state.mm().dbx().db().clone(),
+  ));
+
+  let worker_id = format!(
+    "{}-{}",
+    std::env::var("HOSTNAME").unwrap_or_else(|_| "web_server".to_string()),
+    std::process::id()
+  );
+  let processor = RepositoryAnalysis::new(app_state.clone(), github_client);
+  Ok(
+    AnalysisWorker::new(queue, processor, worker_id)
+      .with_rate_limiter(GitHubServiceFactory::shared_rate_limiter(&config)),
+  )
+}
+
+/// Process jobs until shutdown, finishing the one under way.
+async fn run(worker: AnalysisWorker<RepositoryAnalysis>, app_state: AppState) {
+  let shutdown = app_state.shutdown.clone();
+  while !shutdown.is_triggered() {
+    if !app_state.readiness().is_ready() {
+      shutdown.sleep(POLL_INTERVAL).await;
+      continue;
+    }
+
+    match worker.run_once().await {
+      Ok(true) => {}
+      Ok(false) => {
+        shutdown.sleep(POLL_INTERVAL).await;
+      }
+      Err(e) => {
+        warn!(error = %e, "Analysis worker iteration failed");
+        shutdown.sleep(POLL_INTERVAL).await;
+      }
+    }
+  }
+}
+
+#[derive(FromRow)]
+struct RegisteredRepository {
+  id: Uuid,
+  owner_username: String,
+  repo_name: String,
+}
+
+/// Fetches a job's smart contract files from GitHub and runs the static and
+/// vulnerability analyses on them. LLM review is left to on-demand requests.
+/// Pull request jobs fetch only the changed files and post a review; other
+/// jobs analyse what changed since the repository's last analysis.
+struct RepositoryAnalysis {
+  app_state: AppState,
+  github_client: Arc<GitHubClient>,
+  analysis: AnalysisUseCases,
+}
+
+impl RepositoryAnalysis {
+  fn new(app_state: AppState, github_client: Arc<GitHubClient>) -> Self {
+    let repository = Arc::new(AnalysisRepositoryImpl::new(app_state.clone()));
+    let mut analysis = AnalysisUseCases::new(repository, None);
+    // Bytecode analysis needs the Move toolchain; see `MOVE_COMPILER_COMMAND`
+    if let Some(analyzer) = MoveBytecodeAnalyzer::from_env() {
+      info!("Move bytecode analysis enabled");
+      analysis = analysis.with_bytecode_analyzer(Arc::new(analyzer));
+    }
+    if let Some(notifier) = WebhookLeakNotifier::from_env(app_state.clone()) {
+      info!("Secret leak notifications enabled");
+      analysis = analysis.with_leak_notifier(Arc::new(notifier));
+    }
+    if let Some(notifier) = WebhookRegressionNotifier::from_env(app_state.clone()) {
+      info!("Patch regression notifications enabled");
+      analysis = analysis.with_regression_notifier(Arc::new(notifier));
+    }
+    Self { app_state, github_client, analysis }
+  }
+}
+
+impl AnalysisJobProcessor for RepositoryAnalysis {
+  async fn process(&self, job: &AnalysisJob) -> github_service::Result<()> {
+    let repository = sqlx::query_as::<_, RegisteredRepository>(
+      "SELECT id, owner_username, repo_name FROM github_repositories WHERE github_repo_id = $1",
+    )
+    .bind(job.repository_id as i64)
+    .fetch_optional(self.app_state.mm().dbx().db())
+    .await?
+    .ok_or_else(|| Error::Internal(format!("repository {} is not registered", job.repository_id)))?;
+
+    // Scheduled work yields to webhook-triggered jobs when the budget is low.
+    let github_client = self.github_client.as_ref().clone().with_priority(job.priority.into());
+    let (owner, repo) = (&repository.owner_username, &repository.repo_name);
+    let installation_id = match job.installation_id {
+      Some(installation_id) => installation_id,
+      None => github_client.get_installation_id_for_repo(owner, repo).await?,
+    };
+
+    let files = match job.pull_request_number {
+      Some(_) => {
+        github_client
+          .get_files_at_ref(installation_id, owner, repo, &job.files_to_analyze, &job.commit_sha)
+          .await?
+      }
+      None => {
+        github_client
+          .get_repository_files(
+            installation_id,
+            owner,
+            repo,
+            Some(&job.commit_sha),
+            &SMART_CONTRACT_EXTENSIONS,
+          )
+          .await?
+      }
+    };
+    let packages = RepositoryPackageStore::new(self.app_state.mm().dbx().db().clone())
+      .list(repository.id)
+      .await?;
+    let file_contents: HashMap<String, String> = files
+      .into_iter()
+      .filter(|file| job.files_to_analyze.is_empty() || job.files_to_analyze.contains(&file.path))
+      .filter(|file| is_in_scope(&file.path, &packages))
+      .map(|file| (file.path, file.content))
+      .collect();
+    if file_contents.is_empty() {
+      info!("Analysis job {} has no smart contract files to analyze", job.id);
+      return Ok(());
+    }
+
+    let request = AnalyzeRepositoryRequest {
+      repository_id: repository.id,
+      commit_sha: job.commit_sha.clone(),
+      files_to_analyze: (!job.files_to_analyze.is_empty()).then(|| job.files_to_analyze.clone()),
+      analysis_types: vec![AiAnalysisType::StaticAnalysis, AiAnalysisType::VulnerabilityDetection],
+      enable_llm_analysis: Some(false),
+    };
+    let result = match job.pull_request_number {
+      Some(_) => self.analysis.analyze_repository(request, file_contents).await,
+      None => {
+        let changes =
+          GitHubChangeSet { github_client: &github_client, installation_id, owner, repo };
+        self.analysis.analyze_repository_incremental(request, file_contents, &changes).await
+      }
+    }
+    .map_err(|e| Error::Internal(format!("analysis failed: {}", e)))?;
+
+    info!(
+      job_id = %job.id,
+      analysis_id = %result.analysis_id,
+      vulnerabilities_found = result.vulnerabilities_found,
+      "Analysis job processed"
+    );
+
+    if let Some(number) = job.pull_request_number {
+      let detail = self
+        .analysis
+        .get_detailed_analysis(result.analysis_id)
+        .await
+        .map_err(|e| Error::Internal(format!("loading analysis failed: {}", e)))?;
+      let body = review_body(&detail.analysis_result);
+      github_client
+        .create_pull_request_review(installation_id, owner, repo, number, &job.commit_sha, &body)
+        .await?;
+    }
+    Ok(())
+  }
+}
+
+/// Changes between two commits of a repository, from the GitHub compare API.
+struct GitHubChangeSet<'a> {
+  github_client: &'a GitHubClient,
+  installation_id: u64,
+  owner: &'a str,
+  repo: &'a str,
+}
+
+#[async_trait]
+impl ChangeSetProvider for GitHubChangeSet<'_> {
+  async fn changed_files(
+    &self,
+    base_sha: &str,
+    head_sha: &str,
+  ) -> ai_analysis_service::Result<Option<ChangeSet>> {
+    let comparison = match self
+      .github_client
+      .compare_commits(self.installation_id, self.owner, self.repo, base_sha, head_sha)
+      .await
+    {
+      Ok(comparison) => comparison,
+      Err(e) => {
+        warn!(error = %e, base_sha, head_sha, "Comparing commits failed");
+        return Ok(None);
+      }
+    };
+    if !comparison.is_complete() {
+      return Ok(None);
+    }
+
+    let mut change_set = ChangeSet::default();
+    for file in comparison.files {
+      match file.status.as_str() {
+        "removed" => change_set.removed.push(file.filename),
+        _ => {
+          change_set.removed.extend(file.previous_filename);
+          change_set.changed.push(file.filename);
+        }
+      }
+    }
+    Ok(Some(change_set))
+  }
+}
+
+/// Markdown summary of an analysis for a pull request review: scores, then
+/// findings from most to least severe.
+fn review_body(analysis: &AnalysisResult) -> String {
+  let mut findings: Vec<_> =
+    analysis.vulnerabilities.iter().filter(|finding| !finding.is_false_positive).collect();
+  findings.sort_by_key(|finding| severity_rank(&finding.severity));
+
+  let mut body = format!(
+    "### Security analysis for `{}`\n\nSecurity score: **{:.1}** · Quality score: **{:.1}**\n\n",
+    short_sha(&analysis.commit_sha),
+    analysis.security_score,
+    analysis.quality_score
+  );
+
+  if findings.is_empty() {
+    body.push_str("No vulnerabilities found in the changed files.\n");
+    return body;
+  }
+
+  body.push_str("| Severity | Location | Finding |\n|---|---|---|\n");
+  for finding in findings.iter().take(MAX_REVIEW_FINDINGS) {
+    let location = match finding.line_number {
+      Some(line) => format!("`{}:{}`", finding.file_path, line),
+      None => format!("`{}`", finding.file_path),
+    };
+    body.push_str(&format!(
+      "| {} | {} | {} |\n",
+      finding.severity,
+      location,
+      table_cell(&finding.description)
+    ));
+  }
+  if findings.len() > MAX_REVIEW_FINDINGS {
+    body.push_str(&format!("\n…and {} more finding(s).\n", findings.len() - MAX_REVIEW_FINDINGS));
+  }
+  body
+}
+
+fn severity_rank(severity: &Severity) -> u8 {
+  match severity {
+    Severity::Critical => 0,
+    Severity::High => 1,
+    Severity::Medium => 2,
+    Severity::Low => 3,
+  }
+}
+
+fn short_sha(sha: &str) -> &str {
+  sha.get(..7).unwrap_or(sha)
+}
+
+/// Keep a description on one table row.
+fn table_cell(text: &str) -> String {
+  text.replace('|', "\\|").replace('\n', " ")
+}
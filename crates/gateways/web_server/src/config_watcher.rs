@@ -0,0 +1,105 @@
+use std::{sync::Arc, time::Duration, time::SystemTime};
+
+use api_gateway::middleware::mw_security::SecurityGuard;
+use arc_swap::ArcSwap;
+use jd_core::AppState;
+use jd_utils::config::Config;
+use tokio::{
+  signal::unix::{Signal, SignalKind, signal},
+  time::{MissedTickBehavior, interval},
+};
+use tracing::{info, warn};
+
+/// How often the config files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Reload the settings `Config::with_reloadable` names on SIGHUP or when a
+/// config file changes. Environment variables are fixed for the life of the
+/// process, so only changes made in the files are picked up.
+pub fn start(app_state: AppState, security_guard: Arc<ArcSwap<SecurityGuard>>) {
+  // The filter tracing started with, for when the config stops setting one
+  let startup_filter = jd_tracing::log_filter().map(|handle| handle.status().baseline);
+  apply_log_filter(&app_state.live_config(), startup_filter.as_deref());
+
+  tokio::spawn(async move {
+    let mut hangup = signal(SignalKind::hangup())
+      .map_err(|e| warn!(error = %e, "SIGHUP not handled; config reloads on file changes only"))
+      .ok();
+    let mut poll = interval(POLL_INTERVAL);
+    poll.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut modified = modified_times();
+    loop {
+      tokio::select! {
+        _ = received(&mut hangup) => info!("SIGHUP received, reloading config"),
+        _ = poll.tick() => {
+          let current = modified_times();
+          if current == modified {
+            continue;
+          }
+          modified = current;
+          info!("Config files changed, reloading config");
+        }
+      }
+      reload(&app_state, &security_guard, startup_filter.as_deref());
+    }
+  });
+}
+
+fn reload(
+  app_state: &AppState,
+  security_guard: &ArcSwap<SecurityGuard>,
+  startup_filter: Option<&str>,
+) {
+  // An invalid config is reported and the current one kept
+  let fresh = match Config::load() {
+    Ok(fresh) => fresh,
+    Err(e) => {
+      warn!("Config not reloaded: {}", e);
+      return;
+    }
+  };
+  let previous = app_state.live_config();
+  let config = previous.with_reloadable(&fresh);
+  let changed = config.changed_settings(&previous);
+  if changed.is_empty() {
+    info!("Config reloaded, no reloadable setting changed");
+    return;
+  }
+
+  if changed.iter().any(|key| key.starts_with("security")) {
+    security_guard.store(Arc::new(security_guard.load().reloaded(config.security.as_ref())));
+  }
+  if changed.iter().any(|key| key.starts_with("log")) {
+    apply_log_filter(&config, startup_filter);
+  }
+  app_state.live_config.store(Arc::new(config));
+  info!(changed = %changed.join(", "), "Config reloaded");
+}
+
+fn apply_log_filter(config: &Config, startup_filter: Option<&str>) {
+  let Some(handle) = jd_tracing::log_filter() else {
+    return;
+  };
+  let configured = config.log.as_ref().and_then(|log| log.filter.as_deref());
+  if let Some(filter) = configured.or(startup_filter) {
+    if let Err(e) = handle.set_baseline(filter) {
+      warn!(error = %e, "Log filter from config not applied");
+    }
+  }
+}
+
+async fn received(hangup: &mut Option<Signal>) {
+  match hangup {
+    Some(hangup) => {
+      hangup.recv().await;
+    }
+    None => std::future::pending().await,
+  }
+}
+
+fn modified_times() -> Vec<Option<SystemTime>> {
+  Config::files()
+    .iter()
+    .map(|file| std::fs::metadata(file).and_then(|metadata| metadata.modified()).ok())
+    .collect()
+}
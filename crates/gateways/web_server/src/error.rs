@@ -1,4 +1,25 @@
 pub type Result<T> = core::result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
-pub enum Error {}
+pub enum Error {
+  #[error(transparent)]
+  Database(#[from] jd_storage::config::DatabaseConfigError),
+
+  #[error("Database error: {0}")]
+  Sqlx(#[from] sqlx::Error),
+
+  #[error("Migration failed: {0}")]
+  Migration(#[from] sqlx::migrate::MigrateError),
+
+  #[error("Database migrations do not match this binary: {0}")]
+  MigrationMismatch(String),
+
+  #[error("TLS certificate could not be loaded: {0}")]
+  Tls(std::io::Error),
+
+  #[error("Internal listener could not be started: {0}")]
+  InternalListener(std::io::Error),
+
+  #[error("Unknown command '{0}'; expected `migrate [run|status|check]`")]
+  UnknownCommand(String),
+}
@@ -0,0 +1,386 @@
+use ai_analysis_service::infrastructure::report_archive::AnalysisReportArchive;
+use jd_core::AppState;
+use jd_alerts::{Alert, AlertChannels};
+use jd_email::{EmailQueue, EmailTemplate};
+use jd_messaging::{
+  EventEnvelope,
+  events::{
+    AnalysisCompleted, AnalysisFailed, PatchApproved, PatchMerged, PatchReviewRequested,
+    ProofVerified, ScoreUpdated, VulnerabilityCreated,
+  },
+};
+use jd_notifications::{NotificationStore, NotificationTemplate};
+use serde_json::json;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::scheduler;
+
+/// Subscribe this server's reactions to domain events. Call before the
+/// outbox relay starts.
+pub fn register(app_state: &AppState) {
+  let bus = &app_state.events;
+
+  let state = app_state.clone();
+  bus.subscribe_all("realtime", move |event| {
+    let state = state.clone();
+    async move { publish_realtime(&state, &event).await.map_err(|e| e.to_string()) }
+  });
+
+  // Attest verified proofs now rather than at the next scheduled run.
+  let state = app_state.clone();
+  bus.subscribe("proof_attestation", move |event: ProofVerified| {
+    let state = state.clone();
+    async move {
+      let detail = scheduler::publish_proof_attestations(state).await?;
+      info!(proof_id = %event.proof_id, detail = %detail, "Proof attestation run");
+      Ok(())
+    }
+  });
+
+  // Email the members of organizations monitoring the repository; the
+  // scheduler's `deliver_emails` job sends them.
+  let state = app_state.clone();
+  bus.subscribe("email_critical_vulnerability", move |event: VulnerabilityCreated| {
+    let state = state.clone();
+    async move {
+      if event.severity != "critical" {
+        return Ok(());
+      }
+      let Some(repository) = repository_name(&state, event.repository_id).await? else {
+        return Ok(());
+      };
+      let template = EmailTemplate::CriticalVulnerability {
+        vulnerability_id: event.vulnerability_id,
+        repository,
+        vulnerability_type: event.vulnerability_type,
+        file_path: event.file_path,
+      };
+      enqueue_for_repository(&state, event.repository_id, &template).await
+    }
+  });
+
+  let state = app_state.clone();
+  bus.subscribe("email_patch_approved", move |event: PatchApproved| {
+    let state = state.clone();
+    async move {
+      let Some(repository) = repository_name(&state, event.repository_id).await? else {
+        return Ok(());
+      };
+      let template =
+        EmailTemplate::PatchApproved { patch_id: event.patch_id, repository, title: event.title };
+      enqueue_for_repository(&state, event.repository_id, &template).await
+    }
+  });
+
+  let state = app_state.clone();
+  bus.subscribe("email_analysis_complete", move |event: AnalysisCompleted| {
+    let state = state.clone();
+    async move {
+      let Some(analysis) = analysis_summary(&state, &event).await? else {
+        return Ok(());
+      };
+      let template = EmailTemplate::AnalysisComplete {
+        analysis_job_id: event.analysis_job_id,
+        repository: analysis.repository,
+        commit_sha: event.commit_sha,
+        issues_found: analysis.issues_found,
+        critical_issues: analysis.critical_issues,
+      };
+      enqueue_for_repository(&state, analysis.repository_id, &template).await
+    }
+  });
+
+  // In-app notifications, with the recipients' unread counts pushed to
+  // their real-time connections.
+  let state = app_state.clone();
+  bus.subscribe("notify_analysis_complete", move |event: AnalysisCompleted| {
+    let state = state.clone();
+    async move {
+      let Some(analysis) = analysis_summary(&state, &event).await? else {
+        return Ok(());
+      };
+      let template = NotificationTemplate::AnalysisComplete {
+        analysis_job_id: event.analysis_job_id,
+        repository_id: analysis.repository_id,
+        repository: analysis.repository,
+        commit_sha: event.commit_sha,
+        issues_found: analysis.issues_found,
+        critical_issues: analysis.critical_issues,
+      };
+      let store = notification_store(&state);
+      let notified = store.notify_repository(analysis.repository_id, &template).await;
+      publish_notified(&state, &store, &template, notified).await
+    }
+  });
+
+  let state = app_state.clone();
+  bus.subscribe("notify_critical_vulnerability", move |event: VulnerabilityCreated| {
+    let state = state.clone();
+    async move {
+      if event.severity != "critical" {
+        return Ok(());
+      }
+      let Some(repository) = repository_name(&state, event.repository_id).await? else {
+        return Ok(());
+      };
+      let template = NotificationTemplate::CriticalVulnerability {
+        vulnerability_id: event.vulnerability_id,
+        repository,
+        vulnerability_type: event.vulnerability_type,
+        file_path: event.file_path,
+      };
+      let store = notification_store(&state);
+      let notified = store.notify_repository(event.repository_id, &template).await;
+      publish_notified(&state, &store, &template, notified).await
+    }
+  });
+
+  let state = app_state.clone();
+  bus.subscribe("notify_patch_review_requested", move |event: PatchReviewRequested| {
+    let state = state.clone();
+    async move {
+      let Some(repository) = repository_name(&state, event.repository_id).await? else {
+        return Ok(());
+      };
+      let template = NotificationTemplate::PatchReviewRequested {
+        patch_id: event.patch_id,
+        repository,
+        title: event.title,
+      };
+      let store = notification_store(&state);
+      let notified = store.notify_subjects(&event.reviewers, &template).await;
+      publish_notified(&state, &store, &template, notified).await
+    }
+  });
+
+  let state = app_state.clone();
+  bus.subscribe("notify_score_changed", move |event: ScoreUpdated| {
+    let state = state.clone();
+    async move {
+      if event.previous_score == Some(event.score) {
+        return Ok(());
+      }
+      // Scores are of behavior inputs; only those tied to a user notify.
+      let user_id: Option<Option<Uuid>> =
+        sqlx::query_scalar("SELECT user_id FROM behavior_inputs WHERE id = $1")
+          .bind(event.subject_id)
+          .fetch_optional(state.mm().dbx().db())
+          .await
+          .map_err(|e| e.to_string())?;
+      let Some(user_id) = user_id.flatten() else {
+        return Ok(());
+      };
+      let template = NotificationTemplate::ScoreChanged {
+        subject_id: event.subject_id,
+        score: event.score,
+        previous_score: event.previous_score,
+      };
+      let store = notification_store(&state);
+      let notified = store.notify_user(user_id, &template).await;
+      publish_notified(&state, &store, &template, notified).await
+    }
+  });
+
+  // Chat alerts, to the Slack and Discord channels covering the repository.
+  let state = app_state.clone();
+  bus.subscribe("alert_critical_finding", move |event: VulnerabilityCreated| {
+    let state = state.clone();
+    async move {
+      if event.severity != "critical" {
+        return Ok(());
+      }
+      let Some(repository) = repository_name(&state, event.repository_id).await? else {
+        return Ok(());
+      };
+      let alert = Alert::CriticalFinding {
+        vulnerability_id: event.vulnerability_id,
+        repository,
+        vulnerability_type: event.vulnerability_type,
+        file_path: event.file_path,
+      };
+      dispatch_alert(&state, event.repository_id, &alert).await
+    }
+  });
+
+  let state = app_state.clone();
+  bus.subscribe("alert_analysis_failed", move |event: AnalysisFailed| {
+    let state = state.clone();
+    async move {
+      let repository: Option<(Uuid, String)> =
+        sqlx::query_as("SELECT id, full_name FROM github_repositories WHERE github_repo_id = $1")
+          .bind(event.github_repo_id)
+          .fetch_optional(state.mm().dbx().db())
+          .await
+          .map_err(|e| e.to_string())?;
+      let Some((repository_id, repository)) = repository else {
+        return Ok(());
+      };
+      let alert = Alert::AnalysisFailed {
+        analysis_job_id: event.analysis_job_id,
+        repository,
+        commit_sha: event.commit_sha,
+        attempts: event.attempts,
+        error: event.error,
+      };
+      dispatch_alert(&state, repository_id, &alert).await
+    }
+  });
+
+  let state = app_state.clone();
+  bus.subscribe("alert_patch_merged", move |event: PatchMerged| {
+    let state = state.clone();
+    async move {
+      let Some(repository) = repository_name(&state, event.repository_id).await? else {
+        return Ok(());
+      };
+      let alert = Alert::PatchMerged {
+        patch_id: event.patch_id,
+        repository,
+        pull_request_number: event.pull_request_number,
+      };
+      dispatch_alert(&state, event.repository_id, &alert).await
+    }
+  });
+
+  // Archive the commit's report now rather than at the next scheduled run.
+  let state = app_state.clone();
+  bus.subscribe("analysis_report_archive", move |event: AnalysisCompleted| {
+    let state = state.clone();
+    async move {
+      let Some(objects) = state.objects.clone() else {
+        return Ok(());
+      };
+      let analyses: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT a.id FROM code_analysis_results a \
+         JOIN github_repositories r ON r.id = a.repository_id \
+         WHERE r.github_repo_id = $1 AND a.commit_sha = $2 AND a.report_uri IS NULL",
+      )
+      .bind(event.github_repo_id)
+      .bind(&event.commit_sha)
+      .fetch_all(state.mm().dbx().db())
+      .await
+      .map_err(|e| e.to_string())?;
+      let archive = AnalysisReportArchive::new(state.mm().dbx().db().clone(), objects);
+      for analysis_id in analyses {
+        let uri = archive.archive(analysis_id).await.map_err(|e| e.to_string())?;
+        info!(%analysis_id, uri = uri.as_deref().unwrap_or_default(), "Analysis report archived");
+      }
+      Ok(())
+    }
+  });
+}
+
+/// The repository and newest results of an analysed commit.
+struct AnalysisSummary {
+  repository_id: Uuid,
+  repository: String,
+  issues_found: i32,
+  critical_issues: i32,
+}
+
+async fn analysis_summary(
+  app_state: &AppState,
+  event: &AnalysisCompleted,
+) -> Result<Option<AnalysisSummary>, String> {
+  // The newest results for the commit, if the analysis saved any.
+  let analysis: Option<(Uuid, String, Option<i32>, Option<i32>)> = sqlx::query_as(
+    "SELECT r.id, r.full_name, a.issues_found, a.critical_issues \
+     FROM github_repositories r \
+     LEFT JOIN LATERAL ( \
+       SELECT issues_found, critical_issues FROM code_analysis_results \
+       WHERE repository_id = r.id AND commit_sha = $2 ORDER BY ctime DESC LIMIT 1 \
+     ) a ON TRUE \
+     WHERE r.github_repo_id = $1",
+  )
+  .bind(event.github_repo_id)
+  .bind(&event.commit_sha)
+  .fetch_optional(app_state.mm().dbx().db())
+  .await
+  .map_err(|e| e.to_string())?;
+  Ok(analysis.map(|(repository_id, repository, issues_found, critical_issues)| AnalysisSummary {
+    repository_id,
+    repository,
+    issues_found: issues_found.unwrap_or_default(),
+    critical_issues: critical_issues.unwrap_or_default(),
+  }))
+}
+
+async fn repository_name(
+  app_state: &AppState,
+  repository_id: Uuid,
+) -> Result<Option<String>, String> {
+  sqlx::query_scalar("SELECT full_name FROM github_repositories WHERE id = $1")
+    .bind(repository_id)
+    .fetch_optional(app_state.mm().dbx().db())
+    .await
+    .map_err(|e| e.to_string())
+}
+
+async fn enqueue_for_repository(
+  app_state: &AppState,
+  repository_id: Uuid,
+  template: &EmailTemplate,
+) -> Result<(), String> {
+  let queued = EmailQueue::new(app_state.mm().dbx().db().clone())
+    .enqueue_for_repository(repository_id, template)
+    .await
+    .map_err(|e| e.to_string())?;
+  info!(template = template.name(), %repository_id, queued, "Emails queued");
+  Ok(())
+}
+
+/// Channels that fail are logged by `dispatch` and not retried, so only a
+/// failed channel lookup fails the subscriber.
+async fn dispatch_alert(
+  app_state: &AppState,
+  repository_id: Uuid,
+  alert: &Alert,
+) -> Result<(), String> {
+  let channels = AlertChannels::new(app_state.mm().dbx().db().clone(), app_state.cache.clone());
+  let run = channels.dispatch(repository_id, alert).await.map_err(|e| e.to_string())?;
+  info!(
+    event = alert.event(),
+    delivered = run.delivered,
+    failed = run.failed,
+    rate_limited = run.rate_limited,
+    "Chat alerts dispatched"
+  );
+  Ok(())
+}
+
+fn notification_store(app_state: &AppState) -> NotificationStore {
+  NotificationStore::new(app_state.mm().dbx().db().clone())
+}
+
+/// Log who was notified and push their unread counts. Notifications are
+/// saved by then, so a failed push is logged rather than retried.
+async fn publish_notified(
+  app_state: &AppState,
+  store: &NotificationStore,
+  template: &NotificationTemplate,
+  notified: jd_notifications::Result<Vec<Uuid>>,
+) -> Result<(), String> {
+  let notified = notified.map_err(|e| e.to_string())?;
+  info!(template = template.name(), notified = notified.len(), "Notifications created");
+  if let Err(e) = store.publish_unread(app_state.redis(), &notified).await {
+    warn!(template = template.name(), "Unread notification counts not pushed: {e}");
+  }
+  Ok(())
+}
+
+/// Forward an event to real-time clients as `{"type": "score_updated",
+/// "data": {...}}`.
+async fn publish_realtime(app_state: &AppState, event: &EventEnvelope) -> redis::RedisResult<()> {
+  let message = json!({
+    "type": event.event_type.replace('.', "_"),
+    "event_id": event.event_id,
+    "data": event.payload,
+  });
+  let mut conn = app_state.redis.get_multiplexed_async_connection().await?;
+  redis::cmd("PUBLISH")
+    .arg(jd_messaging::REALTIME_CHANNEL)
+    .arg(message.to_string())
+    .query_async::<()>(&mut conn)
+    .await
+}
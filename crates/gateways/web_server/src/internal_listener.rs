@@ -0,0 +1,65 @@
+use std::{
+  fs::{self, Permissions},
+  net::SocketAddr,
+  os::unix::fs::{FileTypeExt, PermissionsExt},
+  path::Path,
+};
+
+use api_gateway::internal_routes;
+use jd_core::AppState;
+use tokio::net::{TcpListener, UnixListener};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+
+/// Serve `/metrics`, `/health`, `/ready` and the admin routes on
+/// `INTERNAL.ADDR`, a TCP address or Unix socket, when it is set. The
+/// listener is bound before returning, so a bad address stops startup; it
+/// stops with the public one at shutdown.
+pub async fn start(app_state: &AppState) -> Result<()> {
+  let Some(internal) = app_state.config.internal.clone() else {
+    return Ok(());
+  };
+  let app = internal_routes(app_state.clone());
+  let stop = app_state.shutdown.clone();
+  let stopped = async move { stop.triggered().await };
+
+  match internal.socket_path().map(str::to_string) {
+    Some(path) => {
+      remove_stale_socket(Path::new(&path))?;
+      let listener = UnixListener::bind(&path).map_err(Error::InternalListener)?;
+      // Validated with the config
+      let mode = internal.socket_mode().unwrap_or(0o660);
+      fs::set_permissions(&path, Permissions::from_mode(mode)).map_err(Error::InternalListener)?;
+      info!(socket = %path, mode = %format_args!("{mode:o}"), "Internal listener started");
+      app_state.shutdown.spawn("internal_listener", async move {
+        if let Err(e) = axum::serve(listener, app).with_graceful_shutdown(stopped).await {
+          warn!(error = %e, "Internal listener stopped with an error");
+        }
+        let _ = fs::remove_file(&path);
+      });
+    }
+    None => {
+      let listener = TcpListener::bind(&internal.addr).await.map_err(Error::InternalListener)?;
+      info!(addr = %internal.addr, "Internal listener started");
+      let service = app.into_make_service_with_connect_info::<SocketAddr>();
+      app_state.shutdown.spawn("internal_listener", async move {
+        if let Err(e) = axum::serve(listener, service).with_graceful_shutdown(stopped).await {
+          warn!(error = %e, "Internal listener stopped with an error");
+        }
+      });
+    }
+  }
+  Ok(())
+}
+
+/// A socket left behind by a previous run would fail the bind. Anything else
+/// at the path is left alone.
+fn remove_stale_socket(path: &Path) -> Result<()> {
+  match fs::symlink_metadata(path) {
+    Ok(metadata) if metadata.file_type().is_socket() => {
+      fs::remove_file(path).map_err(Error::InternalListener)
+    }
+    _ => Ok(()),
+  }
+}
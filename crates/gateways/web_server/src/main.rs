@@ -1,48 +1,123 @@
 use api_gateway::{
   middleware::{
-    mw_auth::mw_ctx_resolve, mw_request_context::mw_request_context, mw_res_map, mw_res_timestamp,
+    mw_auth::mw_ctx_resolve,
+    mw_deprecation::{mw_deprecation, DeprecationTracker},
+    mw_readiness::mw_readiness_gate,
+    mw_request_context::mw_request_context,
+    mw_res_map::{self, RequestLogPolicy},
+    mw_res_timestamp,
+    mw_security::{mw_security, SecurityGuard},
   },
+  metering::{DeprecationRegistry, UsageMeter},
   v1_routes,
 };
 
+use analytics_service::{
+  application::use_cases::ExportUseCases, infrastructure::ExportRepositoryImpl,
+};
+use arc_swap::ArcSwap;
 use axum::{http::StatusCode, middleware, response::IntoResponse, Json, Router};
+use behavior_service::{
+  application::use_cases::{
+    behavior_use_cases::BehaviorUseCases, stream_ingestion_use_cases::StreamIngestionUseCases,
+  },
+  domain::ingestion_tier::IngestionTiers,
+  infrastructure::{
+    behavior_repository_impl::BehaviorRepositoryImpl, redis_stream_source::RedisStreamSource,
+  },
+};
 use dotenv::dotenv;
 use jd_core::AppState;
+use jd_messaging::OutboxRelay;
+use scoring_service::{
+  application::use_cases::{
+    feature_use_cases::FeatureUseCases, recompute_use_cases::RecomputeUseCases,
+  },
+  domain::feature_extraction::FeatureSet,
+  infrastructure::{
+    feature_repository_impl::FeatureRepositoryImpl,
+    model_registry_repository_impl::ModelRegistryRepositoryImpl,
+    recompute_job_repository_impl::RecomputeJobRepositoryImpl,
+  },
+};
 use serde_json::json;
+use sqlx::postgres::PgConnectOptions;
 use tower_cookies::CookieManagerLayer;
-use tower_http::cors::CorsLayer;
-use tracing::info;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use std::{sync::Arc, time::Duration};
+use sui_service::infrastructure::event_indexer::EventIndexer;
+use tracing::{debug, info, warn};
+use zkproof_service::{
+  application::use_cases::proving_use_cases::ProvingUseCases,
+  infrastructure::{
+    ezkl_prover::EzklProver, object_artifact_store::ObjectArtifactStore,
+    proof_job_store::ProofJobStore, verification_key_store::VerificationKeyStore,
+  },
+};
 
 use jd_tracing::tracing_init;
 use jd_utils::{
-  config,
+  config, secrets,
   time::{format_time, now_utc},
 };
 
 use axum::http::{HeaderName, HeaderValue, Method};
 
+mod analysis_worker;
+mod config_watcher;
 mod error;
+mod event_subscribers;
+mod internal_listener;
+mod migrate;
+mod scheduler;
+mod scoring_pipeline;
+mod shutdown;
+mod tls;
+mod warmup;
 
 #[tokio::main]
 async fn main() -> error::Result<()> {
   dotenv().ok();
 
-  let _ = tracing_init();
+  // Held so file sinks keep writing until shutdown
+  let _tracing = tracing_init();
+
+  // Loaded first so every missing or invalid setting is listed before anything connects
+  config::Config::fetch_secrets().await.unwrap_or_else(|e| panic!("{e}"));
+  let cfg = config::Config::load().unwrap_or_else(|e| panic!("{e}"));
+  debug!("Configuration: {}", cfg.redacted_debug());
+
+  // `web_server migrate ...` runs a migration command instead of the server
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  if args.first().map(String::as_str) == Some("migrate") {
+    return migrate::run(args.get(1).map(String::as_str)).await;
+  }
 
   let app_state = AppState::new().await.expect("Failed to create app state");
 
-  let cfg = config::Config::from_env().expect("Loading env failed");
+  watch_secrets(&app_state);
+
+  let security_guard =
+    Arc::new(ArcSwap::from_pointee(SecurityGuard::from_config(cfg.security.as_ref())));
+  let request_log_policy = Arc::new(RequestLogPolicy::from_config(cfg.request_log.as_ref()));
+  let deprecation_tracker = Arc::new(DeprecationTracker::new(
+    DeprecationRegistry::default(),
+    UsageMeter::new(app_state.redis.clone()),
+  ));
 
   let app = Router::new()
     .merge(v1_routes(app_state.clone()))
-    .layer(middleware::map_response(mw_res_map::mw_map_response))
+    .layer(middleware::map_response_with_state(request_log_policy, mw_res_map::mw_map_response))
+    .layer(middleware::from_fn_with_state(deprecation_tracker, mw_deprecation))
     .layer(middleware::from_fn_with_state(app_state.clone(), mw_ctx_resolve))
     .layer(CookieManagerLayer::new())
     .layer(middleware::from_fn(mw_res_timestamp::mw_req_stamp_resolver))
-    .layer(middleware::from_fn(mw_request_context))
+    .layer(middleware::from_fn_with_state(app_state.clone(), mw_readiness_gate))
+    .layer(middleware::from_fn_with_state(security_guard.clone(), mw_security))
+    .layer(middleware::from_fn_with_state(security_guard.clone(), mw_request_context))
     .layer(
       CorsLayer::new()
-        .allow_origin("http://localhost:3000".parse::<HeaderValue>().unwrap())
+        .allow_origin(allowed_origins(&app_state))
         .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS])
         .allow_headers([
           HeaderName::from_static("content-type"),
@@ -59,12 +134,111 @@ async fn main() -> error::Result<()> {
   info!("Server is running on port: {}", cfg.web.addr);
 
   let listener = tokio::net::TcpListener::bind(cfg.web.addr).await.unwrap();
-  axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>())
-    .await
-    .unwrap();
+  // A certificate that does not load stops startup before anything runs
+  let server = tls::server(&app_state, listener, app).await?;
+  internal_listener::start(&app_state).await?;
+
+  shutdown::listen(app_state.clone());
+  // Accept connections immediately but report not-ready until warm-up is done.
+  app_state.shutdown.spawn_until("warmup", warmup::run(app_state.clone()));
+  scheduler::start(app_state.clone());
+  config_watcher::start(app_state.clone(), security_guard);
+  analysis_worker::start(app_state.clone());
+  scoring_pipeline::start(app_state.clone());
+  event_subscribers::register(&app_state);
+  let (db, bus) = (app_state.mm().dbx().db().clone(), app_state.events.clone());
+  match OutboxRelay::from_config(db, bus, app_state.config.events.as_ref()).await {
+    Ok(Some(relay)) => {
+      app_state.shutdown.spawn_until("outbox_relay", relay.run());
+    }
+    Ok(None) => info!("Outbox relay disabled by EVENTS.RELAY_ENABLED"),
+    Err(e) => warn!(error = %e, "Outbox relay not started"),
+  }
+  match EventIndexer::from_state(&app_state) {
+    Ok(Some(indexer)) => {
+      app_state.shutdown.spawn("event_indexer", indexer.run(app_state.shutdown.clone()));
+    }
+    Ok(None) => info!("No Sui packages to index; event indexer not started"),
+    Err(e) => warn!(error = %e, "Event indexer not started"),
+  }
+  match RedisStreamSource::from_state(&app_state).await {
+    Ok(Some(source)) => {
+      let tiers = IngestionTiers::from_config(app_state.config.behavior_analysis.as_ref());
+      let behavior = BehaviorUseCases::new(BehaviorRepositoryImpl::new(app_state.clone()), tiers);
+      let ingestion = StreamIngestionUseCases::new(source, behavior);
+      app_state.shutdown.spawn_until("stream_ingestion", ingestion.run());
+    }
+    Ok(None) => info!("No behavior stream configured; stream ingestion not started"),
+    Err(e) => warn!(error = %e, "Behavior stream ingestion not started"),
+  }
+  app_state.shutdown.spawn_until(
+    "score_recompute",
+    RecomputeUseCases::new(
+      RecomputeJobRepositoryImpl::new(app_state.clone()),
+      ModelRegistryRepositoryImpl::new(app_state.clone()),
+      FeatureUseCases::new(
+        FeatureRepositoryImpl::new(app_state.clone()),
+        FeatureSet::from_config(app_state.config.scoring.as_ref()),
+      ),
+    )
+    .run(),
+  );
+  app_state.shutdown.spawn_until(
+    "analytics_export",
+    ExportUseCases::new(Arc::new(ExportRepositoryImpl::new(app_state.mm().dbx().clone())))
+      .with_objects(app_state.objects.clone())
+      .run(),
+  );
+  match EzklProver::from_env() {
+    Some(prover) => {
+      let db = app_state.mm().dbx().db().clone();
+      let mut proving =
+        ProvingUseCases::new(ProofJobStore::new(db.clone()), VerificationKeyStore::new(db), prover);
+      if let Some(objects) = app_state.objects.clone() {
+        proving = proving.with_artifacts(ObjectArtifactStore::new(objects));
+      }
+      app_state.shutdown.spawn_until("proof_worker", proving.run());
+    }
+    None => info!("EZKL_ARTIFACTS_DIR is not set; proof worker not started"),
+  }
+
+  shutdown::serve(&app_state, server).await;
   Ok(())
 }
 
+/// Origins from `WEB.CORS_ORIGINS`, as last reloaded.
+fn allowed_origins(app_state: &AppState) -> AllowOrigin {
+  let live_config = app_state.live_config.clone();
+  AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+    origin.to_str().is_ok_and(|origin| live_config.load().web.allows_origin(origin))
+  })
+}
+
+/// Refetch secrets as their TTL passes. A rotated database password is used
+/// for new connections; other secrets take effect on restart.
+fn watch_secrets(app_state: &AppState) {
+  let store = secrets::global();
+  if let Some(reference) = config::Config::secret_reference("postgres.dsn") {
+    let db = app_state.mm().dbx().db().clone();
+    store.on_rotation(&reference, move |dsn| match dsn.parse::<PgConnectOptions>() {
+      Ok(options) => {
+        db.set_connect_options(options);
+        info!("Database credentials rotated");
+      }
+      Err(e) => warn!(error = %e, "Ignoring rotated database DSN"),
+    });
+  }
+  tokio::spawn(async move {
+    let mut interval = tokio::time::interval(store.ttl().max(Duration::from_secs(1)));
+    loop {
+      interval.tick().await;
+      for (reference, e) in store.refresh().await {
+        warn!(error = %e, "Failed to refresh secret {}; keeping the previous value", reference);
+      }
+    }
+  });
+}
+
 // Professional fallback handler for unmatched routes
 async fn fallback_handler() -> impl IntoResponse {
   let response = json!({
@@ -0,0 +1,61 @@
+use jd_storage::{
+  DatabaseConfig, new_db_pool_with_config,
+  migrations::{self, MigrationCheck, MigrationInfo},
+};
+
+use crate::error::{Error, Result};
+
+/// `web_server migrate [run|status|check]`, for CI/CD pipelines:
+/// - `run` (the default) applies pending migrations
+/// - `status` lists applied, pending and mismatched migrations
+/// - `check` fails unless the database matches this binary
+pub async fn run(command: Option<&str>) -> Result<()> {
+  let config = DatabaseConfig::from_env()?;
+  let check = config.migration_check;
+  // Connect without the startup check; reporting on it is the point here
+  let db = new_db_pool_with_config(DatabaseConfig {
+    auto_migrate: false,
+    migration_check: MigrationCheck::Off,
+    ..config
+  })
+  .await?;
+
+  match command.unwrap_or("run") {
+    "run" => {
+      let pending = migrations::status(&db).await?.pending;
+      migrations::run(&db, check).await?;
+      println!("Applied {} migration(s)", pending.len());
+      print_migrations("Applied", &pending);
+    }
+    "status" => {
+      let status = migrations::status(&db).await?;
+      println!("{} migration(s) applied", status.applied.len());
+      print_migrations("Pending", &status.pending);
+      print_migrations("Failed", &status.failed);
+      print_migrations("Changed since applied", &status.modified);
+      if !status.unknown.is_empty() {
+        println!("Unknown to this binary: {:?}", status.unknown);
+      }
+    }
+    "check" => {
+      let problems = migrations::status(&db).await?.problems(check, false);
+      if !problems.is_empty() {
+        return Err(Error::MigrationMismatch(problems.join("; ")));
+      }
+      println!("Database migrations match this binary");
+    }
+    other => return Err(Error::UnknownCommand(other.to_string())),
+  }
+  db.close().await;
+  Ok(())
+}
+
+fn print_migrations(heading: &str, migrations: &[MigrationInfo]) {
+  if migrations.is_empty() {
+    return;
+  }
+  println!("{heading}:");
+  for migration in migrations {
+    println!("  {:04} {}", migration.version, migration.description);
+  }
+}
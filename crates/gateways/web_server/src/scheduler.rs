@@ -0,0 +1,775 @@
+use std::{
+  collections::HashMap,
+  future::Future,
+  pin::Pin,
+  sync::Arc,
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ai_analysis_service::{
+  AdvisoryFeed, LlmResponseCache, WebhookEmbargoNotifier,
+  domain::{analysis_repository_trait::AnalysisRepository, embargo_notifier_trait::EmbargoNotifier},
+  infrastructure::{
+    analysis_repository_impl::AnalysisRepositoryImpl, report_archive::AnalysisReportArchive,
+  },
+};
+use analytics_service::{
+  application::use_cases::{
+    AnomalyUseCases, ExportUseCases, ReportUseCases, RollupUseCases, ScorecardUseCases,
+  },
+  infrastructure::{
+    AnomalyRepositoryImpl, ExportRepositoryImpl, HttpReportDelivery, ReportRepositoryImpl,
+    RollupRepositoryImpl, ScorecardRepositoryImpl, WebhookAlertNotifier,
+  },
+};
+use auth_service::{
+  application::use_cases::{OnboardingUseCase, PurgeNoncesUseCase},
+  domain::OnboardingSettings,
+  infrastructure::{NonceRepositoryImpl, OrganizationRepositoryImpl},
+};
+use github_service::{
+  AnalysisQueueImpl, AnalysisQueueSettings, ContentCache, GitHubServiceConfig, GitHubServiceFactory,
+};
+use async_trait::async_trait;
+use developer_service::{
+  application::use_cases::{BadgeUseCases, ReputationUseCases},
+  infrastructure::{BadgeRepositoryImpl, ReputationRepositoryImpl},
+};
+use chrono::{Datelike, Utc};
+use jd_core::AppState;
+use jd_email::EmailQueue;
+use jd_messaging::outbox::OutboxStore;
+use jd_notifications::NotificationStore;
+use jd_object_store::{LifecycleRule, lifecycle};
+use sui_service::infrastructure::{
+  attestation_publisher::AttestationPublisher as SuiAttestationPublisher, gas_station::GasStation,
+};
+use tokio::time::{Instant, MissedTickBehavior, interval, timeout};
+use tracing::{debug, info, warn};
+use zkproof_service::{
+  application::use_cases::{
+    artifact_use_cases::ProofArtifactUseCases, attestation_use_cases::AttestationUseCases,
+    proof_request_use_cases::ProofRequestUseCases,
+  },
+  domain::{
+    attestation::{AttestationOutcome, PreparedAttestation, ProofAttestation},
+    attestation_publisher_trait::AttestationPublisher,
+  },
+  infrastructure::{
+    attestation_store::AttestationStore, credential_store::CredentialStore,
+    object_artifact_store::ObjectArtifactStore, proof_request_store::ProofRequestStore,
+    proof_store::ProofStore,
+  },
+};
+
+const JOB_TIMEOUT: Duration = Duration::from_secs(60);
+const COMPLETED_ANALYSIS_JOB_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const GITHUB_CONTENT_CACHE_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+const LLM_RESPONSE_CACHE_RETENTION: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+const PUBLISHED_OUTBOX_EVENT_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Longer than the retries of any email, and than a digest week.
+const EMAIL_JOB_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+const READ_NOTIFICATION_RETENTION: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+/// How long before an embargo lapses its repository's maintainers are warned.
+const EMBARGO_NOTICE: Duration = Duration::from_secs(3 * 24 * 60 * 60);
+/// Attestations submitted per run; each waits for its transaction to execute.
+const ATTESTATION_BATCH: i64 = 5;
+/// Inline proofs moved to object storage per run; each can be megabytes.
+const PROOF_OFFLOAD_BATCH: i64 = 5;
+/// Reports archived per run, oldest first, so a backlog drains gradually.
+const REPORT_ARCHIVE_BATCH: i64 = 50;
+/// Proof request callbacks posted per run; each can wait 10s on the partner.
+const PROOF_REQUEST_CALLBACK_BATCH: i64 = 5;
+/// Saved reports run per run; each can wait 10s on its delivery target.
+const SAVED_REPORT_BATCH: i64 = 5;
+
+type JobFuture = Pin<Box<dyn Future<Output = Result<String, String>> + Send>>;
+
+/// A periodic background job. Jobs are skipped while the instance is not
+/// ready. Each run is claimed in Redis, so across every instance sharing it
+/// a job runs once per period; see `claim`.
+struct ScheduledJob {
+  name: &'static str,
+  every: Duration,
+  run: fn(AppState) -> JobFuture,
+}
+
+const JOBS: &[ScheduledJob] = &[
+  ScheduledJob { name: "expire_trials", every: Duration::from_secs(15 * 60), run: expire_trials },
+  ScheduledJob { name: "purge_nonces", every: Duration::from_secs(60 * 60), run: purge_nonces },
+  ScheduledJob {
+    name: "purge_analysis_jobs",
+    every: Duration::from_secs(24 * 60 * 60),
+    run: purge_analysis_jobs,
+  },
+  ScheduledJob {
+    name: "purge_github_content_cache",
+    every: Duration::from_secs(24 * 60 * 60),
+    run: purge_github_content_cache,
+  },
+  ScheduledJob {
+    name: "purge_llm_response_cache",
+    every: Duration::from_secs(24 * 60 * 60),
+    run: purge_llm_response_cache,
+  },
+  ScheduledJob {
+    name: "purge_outbox_events",
+    every: Duration::from_secs(24 * 60 * 60),
+    run: purge_outbox_events,
+  },
+  ScheduledJob {
+    name: "sync_dependency_advisories",
+    every: Duration::from_secs(24 * 60 * 60),
+    run: sync_dependency_advisories,
+  },
+  ScheduledJob {
+    name: "advance_embargoes",
+    every: Duration::from_secs(15 * 60),
+    run: advance_embargoes,
+  },
+  ScheduledJob {
+    name: "reanalyze_repositories",
+    every: Duration::from_secs(5 * 60),
+    run: reanalyze_repositories,
+  },
+  ScheduledJob { name: "ingest_commits", every: Duration::from_secs(5 * 60), run: ingest_commits },
+  ScheduledJob {
+    name: "probe_sui_rpc_providers",
+    every: Duration::from_secs(60),
+    run: probe_sui_rpc_providers,
+  },
+  ScheduledJob {
+    name: "rebalance_gas_pool",
+    every: Duration::from_secs(10 * 60),
+    run: rebalance_gas_pool,
+  },
+  ScheduledJob {
+    name: "publish_proof_attestations",
+    every: Duration::from_secs(60),
+    run: publish_proof_attestations,
+  },
+  ScheduledJob {
+    name: "offload_proof_artifacts",
+    every: Duration::from_secs(5 * 60),
+    run: offload_proof_artifacts,
+  },
+  ScheduledJob {
+    name: "revoke_lapsed_credentials",
+    every: Duration::from_secs(15 * 60),
+    run: revoke_lapsed_credentials,
+  },
+  ScheduledJob {
+    name: "deliver_proof_request_callbacks",
+    every: Duration::from_secs(60),
+    run: deliver_proof_request_callbacks,
+  },
+  ScheduledJob {
+    name: "refresh_analytics_rollups",
+    every: Duration::from_secs(15 * 60),
+    run: refresh_analytics_rollups,
+  },
+  ScheduledJob {
+    name: "purge_analytics_exports",
+    every: Duration::from_secs(60 * 60),
+    run: purge_analytics_exports,
+  },
+  ScheduledJob {
+    name: "record_repository_scorecards",
+    every: Duration::from_secs(24 * 60 * 60),
+    run: record_repository_scorecards,
+  },
+  ScheduledJob {
+    name: "recompute_developer_reputation",
+    every: Duration::from_secs(60 * 60),
+    run: recompute_developer_reputation,
+  },
+  ScheduledJob { name: "run_saved_reports", every: Duration::from_secs(60), run: run_saved_reports },
+  ScheduledJob {
+    name: "detect_analytics_anomalies",
+    every: Duration::from_secs(15 * 60),
+    run: detect_analytics_anomalies,
+  },
+  ScheduledJob { name: "deliver_emails", every: Duration::from_secs(30), run: deliver_emails },
+  ScheduledJob {
+    name: "queue_weekly_digests",
+    every: Duration::from_secs(60 * 60),
+    run: queue_weekly_digests,
+  },
+  ScheduledJob {
+    name: "purge_email_jobs",
+    every: Duration::from_secs(24 * 60 * 60),
+    run: purge_email_jobs,
+  },
+  ScheduledJob {
+    name: "archive_analysis_reports",
+    every: Duration::from_secs(15 * 60),
+    run: archive_analysis_reports,
+  },
+  ScheduledJob {
+    name: "apply_object_lifecycle",
+    every: Duration::from_secs(6 * 60 * 60),
+    run: apply_object_lifecycle,
+  },
+  ScheduledJob {
+    name: "purge_notifications",
+    every: Duration::from_secs(24 * 60 * 60),
+    run: purge_notifications,
+  },
+];
+
+/// Spawn one loop per job. Intended to be called once at startup. Loops stop
+/// at shutdown, once any run under way has finished.
+pub fn start(app_state: AppState) {
+  for job in JOBS {
+    app_state.shutdown.spawn(job.name, run_job(job, app_state.clone()));
+  }
+}
+
+async fn run_job(job: &'static ScheduledJob, app_state: AppState) {
+  let mut ticker = interval(job.every);
+  ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+  loop {
+    tokio::select! {
+      _ = ticker.tick() => {}
+      _ = app_state.shutdown.triggered() => break,
+    }
+    if !app_state.readiness().is_ready() {
+      continue;
+    }
+
+    let started = Instant::now();
+    match claim(job, &app_state).await {
+      Ok(true) => {}
+      Ok(false) => {
+        debug!(job = job.name, "Scheduled job claimed by another instance");
+        continue;
+      }
+      // Runs must not stop with Redis, so they may overlap while it is down
+      Err(e) => warn!(job = job.name, "Scheduled job could not be claimed; running anyway: {e}"),
+    }
+
+    let result = match timeout(JOB_TIMEOUT, (job.run)(app_state.clone())).await {
+      Ok(result) => result,
+      Err(_) => Err(format!("timed out after {}s", JOB_TIMEOUT.as_secs())),
+    };
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+      Ok(detail) => info!(job = job.name, duration_ms, detail = %detail, "Scheduled job done"),
+      Err(error) => warn!(job = job.name, duration_ms, error = %error, "Scheduled job failed"),
+    }
+  }
+}
+
+/// Claim this period's run of `job` for this instance; false if another
+/// instance has. Periods are counted from the Unix epoch, so every instance
+/// names the same period alike whenever it ticks. The claim is never
+/// released and lives as long as a period, so it cannot lapse before the
+/// period it stands for ends. If the claiming instance dies mid-run, the
+/// job waits for the next period.
+async fn claim(job: &ScheduledJob, app_state: &AppState) -> jd_cache::Result<bool> {
+  app_state.cache.set_if_absent(&claim_key(job, SystemTime::now()), &true, job.every).await
+}
+
+/// `scheduler:{name}:{period}`, `period` being the index of the period
+/// `now` falls in.
+fn claim_key(job: &ScheduledJob, now: SystemTime) -> String {
+  let elapsed = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+  let period = elapsed.as_secs() / job.every.as_secs().max(1);
+  format!("scheduler:{}:{}", job.name, period)
+}
+
+// region:    --- Jobs
+
+/// Flip lapsed self-serve trials to `expired`. Runs regardless of the
+/// onboarding feature flag so trials created before it was turned off still end.
+fn expire_trials(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let settings = OnboardingSettings::from_config(app_state.config.onboarding.as_ref());
+    let expired = OnboardingUseCase::new(OrganizationRepositoryImpl::new(app_state), settings)
+      .expire_trials()
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} trial(s) expired", expired.len()))
+  })
+}
+
+/// Drop expired login nonces and old replay markers. A no-op on Redis, where
+/// both expire on their own.
+fn purge_nonces(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let purged = PurgeNoncesUseCase::new(NonceRepositoryImpl::new(app_state))
+      .execute()
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} nonce record(s) purged", purged))
+  })
+}
+
+/// Delete analysis jobs that completed or were cancelled more than a week
+/// ago. Failed and dead-lettered jobs are kept for inspection.
+fn purge_analysis_jobs(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let queue =
+      AnalysisQueueImpl::new(app_state.mm().dbx().db().clone(), AnalysisQueueSettings::default());
+    let cleared = queue
+      .clear_completed_jobs(COMPLETED_ANALYSIS_JOB_RETENTION)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} finished analysis job(s) cleared", cleared))
+  })
+}
+
+/// Drop cached GitHub listings and blobs no analysis has read for 30 days.
+fn purge_github_content_cache(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let purged = ContentCache::new(app_state.mm().dbx().db().clone())
+      .purge_unused(GITHUB_CONTENT_CACHE_RETENTION)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} cached GitHub content row(s) purged", purged))
+  })
+}
+
+/// Drop cached LLM responses no analysis has reused for 90 days.
+fn purge_llm_response_cache(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let purged = LlmResponseCache::new(app_state.mm().dbx().db().clone())
+      .purge_unused(LLM_RESPONSE_CACHE_RETENTION)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} cached LLM response(s) purged", purged))
+  })
+}
+
+/// Delete outbox events published more than a week ago. Unpublished ones are
+/// kept, including those the relay gave up on.
+fn purge_outbox_events(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let purged = OutboxStore::new(app_state.mm().dbx().db().clone())
+      .purge_published(PUBLISHED_OUTBOX_EVENT_RETENTION)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} published outbox event(s) purged", purged))
+  })
+}
+
+/// Refresh the local copy of dependency advisories from the feed at
+/// `DEPENDENCY_ADVISORY_FEED_URL`.
+fn sync_dependency_advisories(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let Some(feed) = AdvisoryFeed::from_env() else {
+      return Ok("No advisory feed is configured".to_string());
+    };
+    let advisories = feed.fetch().await.map_err(|e| e.to_string())?;
+    let saved = AnalysisRepositoryImpl::new(app_state)
+      .save_dependency_advisories(&advisories)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} advisory record(s) synced", saved))
+  })
+}
+
+/// Publish findings whose embargo has lapsed, and warn the maintainers of
+/// repositories whose embargoes lapse within `EMBARGO_NOTICE` through the
+/// webhook at `EMBARGO_WEBHOOK_URL`.
+fn advance_embargoes(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let notifier = WebhookEmbargoNotifier::from_env(app_state.clone());
+    let repository = AnalysisRepositoryImpl::new(app_state);
+    let published = repository.publish_lapsed_embargoes().await.map_err(|e| e.to_string())?;
+
+    let Some(notifier) = notifier else {
+      return Ok(format!("{} embargoed finding(s) published", published.len()));
+    };
+    let notice = chrono::Duration::from_std(EMBARGO_NOTICE).map_err(|e| e.to_string())?;
+    let lapsing = repository
+      .claim_lapsing_embargoes(chrono::Utc::now() + notice)
+      .await
+      .map_err(|e| e.to_string())?;
+    let mut by_repository: HashMap<_, Vec<_>> = HashMap::new();
+    for disclosure in lapsing {
+      by_repository.entry(disclosure.repository_id).or_default().push(disclosure);
+    }
+
+    let mut warned = 0;
+    for (repository_id, disclosures) in by_repository {
+      match notifier.notify_lapsing(repository_id, &disclosures).await {
+        Ok(()) => warned += disclosures.len(),
+        Err(e) => {
+          warn!("Embargo warning for repository {} failed: {}", repository_id, e);
+          let ids: Vec<_> =
+            disclosures.iter().map(|disclosure| disclosure.vulnerability_id).collect();
+          repository.release_lapsing_embargoes(&ids).await.map_err(|e| e.to_string())?;
+        }
+      }
+    }
+    Ok(format!(
+      "{} embargoed finding(s) published, {} lapsing embargo warning(s) sent",
+      published.len(),
+      warned
+    ))
+  })
+}
+
+/// Queue re-analyses of tracked repositories whose schedules are due and
+/// whose HEAD has moved.
+fn reanalyze_repositories(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    if app_state.config.github.is_none() {
+      return Ok("GitHub is not configured".to_string());
+    }
+    // Re-analysis budgets can be reloaded
+    let config =
+      GitHubServiceConfig::from_config(&app_state.live_config()).map_err(|e| e.to_string())?;
+    let run = GitHubServiceFactory::create_reanalysis_scheduler(
+      &config,
+      app_state.mm().dbx().db().clone(),
+    )
+    .map_err(|e| e.to_string())?
+    .run_once()
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(format!(
+      "{} re-analysis job(s) queued, {} unchanged, {} failed",
+      run.enqueued, run.unchanged, run.failed
+    ))
+  })
+}
+
+/// Ingest new commits of tracked repositories whose history sync is due.
+/// Each run takes a bounded slice; repositories with a backlog stay due.
+fn ingest_commits(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    if app_state.config.github.is_none() {
+      return Ok("GitHub is not configured".to_string());
+    }
+    let config = GitHubServiceConfig::from_config(&app_state.config).map_err(|e| e.to_string())?;
+    let run =
+      GitHubServiceFactory::create_commit_ingestor(&config, app_state.mm().dbx().db().clone())
+        .map_err(|e| e.to_string())?
+        .run_once()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(format!(
+      "{} commit(s) ingested from {} repositories, {} failed",
+      run.commits, run.repositories, run.failed
+    ))
+  })
+}
+
+/// Send each Sui RPC endpoint whose circuit allows it a cheap request, so
+/// endpoint rankings stay fresh without live traffic and endpoints that
+/// recovered are used again.
+fn probe_sui_rpc_providers(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let networks = app_state.sui_networks();
+    let mut available = 0;
+    let mut total = 0;
+    for network in networks.networks() {
+      let providers = networks.providers(network).map_err(|e| e.to_string())?;
+      let statuses = providers.probe().await;
+      available += statuses.iter().filter(|status| status.available).count();
+      total += statuses.len();
+    }
+    Ok(format!("{} of {} RPC endpoint(s) available", available, total))
+  })
+}
+
+/// Merge the sponsor's dust gas coins and split new ones off its largest
+/// until each served network's pool is back at its target size. Coins held
+/// by in-flight sponsorships or another instance's rebalance are left for
+/// the next run.
+fn rebalance_gas_pool(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let networks = app_state.sui_networks();
+    let mut summary = Vec::new();
+    for network in networks.networks() {
+      let gas_station = GasStation::from_state(&app_state, network).map_err(|e| e.to_string())?;
+      let Some(gas_station) = gas_station else {
+        return Ok("Gas station is not configured".to_string());
+      };
+      let plan = gas_station.rebalance().await.map_err(|e| format!("{}: {}", network, e))?;
+      summary.push(format!(
+        "{}: {} gas coin(s) merged, {} split",
+        network,
+        plan.merge.len(),
+        plan.split.len()
+      ));
+    }
+    Ok(summary.join("; "))
+  })
+}
+
+/// Attest verified zk proofs on-chain and record the digests in
+/// `zkml_proofs.blockchain_tx_hash`.
+pub(crate) fn publish_proof_attestations(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let publisher = SuiAttestationPublisher::from_state(&app_state).map_err(|e| e.to_string())?;
+    let Some(publisher) = publisher else {
+      return Ok("Proof attestation is not configured".to_string());
+    };
+    let store = AttestationStore::new(app_state.mm().dbx().db().clone());
+    let run = AttestationUseCases::new(store, SuiAttestations(publisher))
+      .publish_due(ATTESTATION_BATCH)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} attestation(s) published, {} failed", run.published, run.failed))
+  })
+}
+
+/// Move proofs still kept in `zkml_proofs` to object storage, leaving their
+/// URIs and content hashes.
+fn offload_proof_artifacts(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let Some(objects) = app_state.objects.clone() else {
+      return Ok("Object storage is not configured".to_string());
+    };
+    let store = ProofStore::new(app_state.mm().dbx().db().clone());
+    let run = ProofArtifactUseCases::new(store, ObjectArtifactStore::new(objects))
+      .offload_inline(PROOF_OFFLOAD_BATCH)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} proof(s) offloaded, {} failed", run.offloaded, run.failed))
+  })
+}
+
+/// Score credentials stop verifying once their holder's score drops below
+/// the threshold they assert.
+fn revoke_lapsed_credentials(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let store = CredentialStore::new(app_state.mm().dbx().db().clone());
+    let revoked = store.revoke_below_threshold().await.map_err(|e| e.to_string())?;
+    Ok(format!("{} credential(s) revoked", revoked))
+  })
+}
+
+fn deliver_proof_request_callbacks(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let proof_requests =
+      ProofRequestUseCases::new(ProofRequestStore::new(app_state.mm().dbx().db().clone()));
+    let run = proof_requests
+      .deliver_callbacks(PROOF_REQUEST_CALLBACK_BATCH)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} callback(s) delivered, {} failed", run.delivered, run.failed))
+  })
+}
+
+/// Roll up the current and previous day and week of analytics activity, and
+/// backfill periods never rolled up, so trend reads avoid the source tables.
+fn refresh_analytics_rollups(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let db = app_state.mm().dbx().db().clone();
+    let run = RollupUseCases::new(Arc::new(RollupRepositoryImpl::new(db)))
+      .refresh()
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} period(s) rolled up, {} row(s) stored", run.periods, run.rows))
+  })
+}
+
+/// Delete prepared analytics exports past their download window.
+fn purge_analytics_exports(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let repository = ExportRepositoryImpl::new(app_state.mm().dbx().clone());
+    let purged = ExportUseCases::new(Arc::new(repository))
+      .purge_expired()
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} analytics export(s) purged", purged))
+  })
+}
+
+/// Record today's security scorecard of every analyzed public repository,
+/// so scorecard trends cover repositories nobody looked at that day.
+fn record_repository_scorecards(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let repository = ScorecardRepositoryImpl::new(app_state.mm().dbx().db().clone());
+    let recorded = ScorecardUseCases::new(Arc::new(repository))
+      .record_all()
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} scorecard(s) recorded", recorded))
+  })
+}
+
+/// Re-rank developers by reputation for every leaderboard window and
+/// language, refresh their stored coding reputation, and award the badges
+/// their contributions have earned since.
+fn recompute_developer_reputation(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let db = app_state.mm().dbx().db().clone();
+    let reputation = Arc::new(ReputationRepositoryImpl::new(db.clone()));
+    let run = ReputationUseCases::new(reputation.clone())
+      .recompute()
+      .await
+      .map_err(|e| e.to_string())?;
+    let awarded = BadgeUseCases::new(Arc::new(BadgeRepositoryImpl::new(db)), reputation)
+      .award_earned()
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!(
+      "{} leaderboard(s) ranked, {} developer(s) updated, {} badge(s) awarded",
+      run.leaderboards, run.developers_updated, awarded
+    ))
+  })
+}
+
+/// Run saved analytics queries whose schedule is due and deliver their
+/// reports.
+fn run_saved_reports(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let repository = ReportRepositoryImpl::new(app_state.mm().dbx().db().clone());
+    let ran = ReportUseCases::new(Arc::new(repository), Arc::new(HttpReportDelivery::from_env()))
+      .run_due(SAVED_REPORT_BATCH)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} saved report(s) run", ran))
+  })
+}
+
+/// Check key analytics metrics against their baselines, raising alerts and
+/// notifying `ANALYTICS_ALERT_WEBHOOK_URL` when set.
+fn detect_analytics_anomalies(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let repository = AnomalyRepositoryImpl::new(app_state.mm().dbx().db().clone());
+    let mut anomalies = AnomalyUseCases::new(Arc::new(repository));
+    if let Some(notifier) = WebhookAlertNotifier::from_env() {
+      anomalies = anomalies.with_notifier(Arc::new(notifier));
+    }
+    let run = anomalies.detect().await.map_err(|e| e.to_string())?;
+    Ok(format!(
+      "{} anomalies found, {} alert(s) notified, {} resolved",
+      run.anomalies, run.notified, run.resolved
+    ))
+  })
+}
+
+/// Send queued emails that are due, retrying failed ones later.
+fn deliver_emails(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let queue = EmailQueue::new(app_state.mm().dbx().db().clone());
+    let run = app_state.email.deliver_due(&queue).await.map_err(|e| e.to_string())?;
+    Ok(format!("{} email(s) sent, {} failed", run.sent, run.failed))
+  })
+}
+
+/// Queue this ISO week's digest for members whose repositories saw activity
+/// in the past seven days. Each user is queued one digest per week however
+/// often this runs, so digests go out early in the week.
+fn queue_weekly_digests(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let now = Utc::now();
+    let week = now.iso_week();
+    let queued = EmailQueue::new(app_state.mm().dbx().db().clone())
+      .enqueue_weekly_digests(
+        &format!("{}-W{:02}", week.year(), week.week()),
+        now - chrono::Duration::days(7),
+      )
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} weekly digest(s) queued", queued))
+  })
+}
+
+/// Delete emails queued more than 30 days ago.
+fn purge_email_jobs(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let purged = EmailQueue::new(app_state.mm().dbx().db().clone())
+      .purge(EMAIL_JOB_RETENTION)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} email job(s) purged", purged))
+  })
+}
+
+/// Archive the reports of analyses the `AnalysisCompleted` subscriber
+/// missed, such as those from before object storage was configured.
+fn archive_analysis_reports(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let Some(objects) = app_state.objects.clone() else {
+      return Ok("Object storage is not configured".to_string());
+    };
+    let archived = AnalysisReportArchive::new(app_state.mm().dbx().db().clone(), objects)
+      .archive_pending(REPORT_ARCHIVE_BATCH)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} analysis report(s) archived", archived))
+  })
+}
+
+/// Delete objects past the age their key prefix's lifecycle rule allows.
+fn apply_object_lifecycle(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let Some(objects) = app_state.objects.clone() else {
+      return Ok("Object storage is not configured".to_string());
+    };
+    let rules = LifecycleRule::from_config(&app_state.config).map_err(|e| e.to_string())?;
+    let run = lifecycle::apply(objects.as_ref(), &rules).await.map_err(|e| e.to_string())?;
+    Ok(format!("{} expired object(s) deleted, {} failed", run.deleted, run.failed))
+  })
+}
+
+/// Delete read notifications sent more than 90 days ago.
+fn purge_notifications(app_state: AppState) -> JobFuture {
+  Box::pin(async move {
+    let purged = NotificationStore::new(app_state.mm().dbx().db().clone())
+      .purge_read(READ_NOTIFICATION_RETENTION)
+      .await
+      .map_err(|e| e.to_string())?;
+    Ok(format!("{} read notification(s) purged", purged))
+  })
+}
+
+/// Proof attestations executed on Sui by the sponsor.
+struct SuiAttestations(SuiAttestationPublisher);
+
+#[async_trait]
+impl AttestationPublisher for SuiAttestations {
+  async fn prepare(
+    &self,
+    attestation: &ProofAttestation,
+  ) -> zkproof_service::Result<PreparedAttestation> {
+    let signed = self
+      .0
+      .prepare(&attestation.proof_hash, &attestation.score_commitment, &attestation.subject_address)
+      .await
+      .map_err(|e| zkproof_service::Error::Internal(e.to_string()))?;
+    Ok(PreparedAttestation { digest: signed.digest, signed_tx: signed.signed_tx })
+  }
+
+  async fn submit(
+    &self,
+    prepared: &PreparedAttestation,
+  ) -> zkproof_service::Result<AttestationOutcome> {
+    let executed = self
+      .0
+      .submit(&prepared.signed_tx)
+      .await
+      .map_err(|e| zkproof_service::Error::Internal(e.to_string()))?;
+    Ok(match executed.error {
+      None => AttestationOutcome::Published { digest: executed.digest },
+      Some(reason) => AttestationOutcome::Failed { digest: executed.digest, reason },
+    })
+  }
+}
+
+// endregion: --- Jobs
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn claim_key_names_the_period() {
+    let job = &JOBS[0];
+    let every = job.every.as_secs();
+    let at = |secs: u64| claim_key(job, UNIX_EPOCH + Duration::from_secs(secs));
+
+    assert_eq!(at(3 * every), format!("scheduler:{}:3", job.name));
+    assert_eq!(at(3 * every), at(4 * every - 1));
+    assert_ne!(at(4 * every - 1), at(4 * every));
+  }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use behavior_service::{
+  application::use_cases::behavior_use_cases::BehaviorUseCases,
+  domain::ingestion_tier::IngestionTiers,
+  infrastructure::behavior_repository_impl::BehaviorRepositoryImpl,
+  models::responses::UnscoredSubject,
+};
+use jd_core::{AppState, shutdown::Shutdown};
+use scoring_service::{
+  application::use_cases::{
+    feature_use_cases::FeatureUseCases, scoring_use_cases::ScoringUseCases,
+    sybil_use_cases::SybilUseCases,
+  },
+  domain::{feature_extraction::FeatureSet, sybil::SybilThresholds},
+  infrastructure::{
+    feature_repository_impl::FeatureRepositoryImpl,
+    model_registry_repository_impl::ModelRegistryRepositoryImpl,
+    scoring_repository_impl::ScoringRepositoryImpl, sybil_repository_impl::SybilRepositoryImpl,
+  },
+  models::requests::ScoringRequest,
+};
+use tracing::{info, warn};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_DEBOUNCE: Duration = Duration::from_secs(5);
+/// A subject that keeps sending inputs is still scored this often.
+const MAX_WAIT: Duration = Duration::from_secs(60);
+/// How long claimed inputs stay leased; a failed subject is retried after.
+const LEASE: Duration = Duration::from_secs(120);
+const SUBJECTS_PER_RUN: i64 = 50;
+
+type Behavior = BehaviorUseCases<BehaviorRepositoryImpl>;
+type Scoring =
+  ScoringUseCases<ScoringRepositoryImpl, ModelRegistryRepositoryImpl, FeatureRepositoryImpl>;
+type Sybil = SybilUseCases<SybilRepositoryImpl>;
+
+/// What the pipeline scores with.
+struct Pipeline {
+  behavior: Behavior,
+  scoring: Scoring,
+  sybil: Sybil,
+}
+
+/// Start scoring new behavior inputs as they arrive, unless
+/// `SCORING.PIPELINE_ENABLED=false` leaves scoring to explicit requests.
+pub fn start(app_state: AppState) {
+  let scoring_config = app_state.config.scoring.as_ref();
+  if scoring_config.and_then(|c| c.pipeline_enabled) == Some(false) {
+    info!("Scoring pipeline disabled by SCORING.PIPELINE_ENABLED");
+    return;
+  }
+  let debounce = scoring_config
+    .and_then(|c| c.pipeline_debounce_secs)
+    .map(Duration::from_secs)
+    .unwrap_or(DEFAULT_DEBOUNCE);
+
+  let tiers = IngestionTiers::from_config(app_state.config.behavior_analysis.as_ref());
+  let behavior = BehaviorUseCases::new(BehaviorRepositoryImpl::new(app_state.clone()), tiers);
+  let scoring = ScoringUseCases::new(
+    ScoringRepositoryImpl::new(app_state.clone()),
+    ModelRegistryRepositoryImpl::new(app_state.clone()),
+    FeatureUseCases::new(
+      FeatureRepositoryImpl::new(app_state.clone()),
+      FeatureSet::from_config(scoring_config),
+    ),
+  );
+  let sybil = SybilUseCases::new(
+    SybilRepositoryImpl::new(app_state.clone()),
+    SybilThresholds::from_config(scoring_config),
+  );
+  let shutdown = app_state.shutdown.clone();
+  let pipeline = Pipeline { behavior, scoring, sybil };
+  app_state.shutdown.spawn("scoring_pipeline", run(pipeline, debounce, shutdown));
+}
+
+/// Score until shutdown. Subjects claimed but not yet scored when it comes
+/// are scored by the next instance to claim them once their lease lapses.
+async fn run(pipeline: Pipeline, debounce: Duration, shutdown: Shutdown) {
+  info!(debounce_secs = debounce.as_secs(), "Scoring pipeline started");
+  loop {
+    match pipeline.behavior.claim_unscored(debounce, MAX_WAIT, LEASE, SUBJECTS_PER_RUN).await {
+      Ok(subjects) => {
+        for subject in subjects {
+          if shutdown.is_triggered() {
+            break;
+          }
+          if let Err(e) = score_subject(&pipeline, &subject).await {
+            warn!(subject = %subject.subject, error = %e, "Scoring subject failed");
+          }
+        }
+      }
+      Err(e) => warn!(error = %e, "Claiming unscored behavior inputs failed"),
+    }
+    if !shutdown.sleep(POLL_INTERVAL).await {
+      break;
+    }
+  }
+}
+
+/// Score the subject's latest input and mark all its claimed inputs
+/// processed. An input that was already scored, say by an explicit request,
+/// is not scored again. A user's sybil score is reassessed along with it.
+/// The score ledger records a `score.updated` event for new scores.
+async fn score_subject(pipeline: &Pipeline, subject: &UnscoredSubject) -> Result<(), String> {
+  let Pipeline { behavior, scoring, sybil } = pipeline;
+  let latest = &subject.latest;
+  let existing =
+    scoring.get_scoring_by_behavior_id(latest.id.clone()).await.map_err(|e| e.to_string())?;
+  if existing.is_none() {
+    scoring
+      .calculate_score(
+        ScoringRequest { behavior_input_id: latest.id.clone(), model_version: None },
+        latest.to_behavior_data(),
+      )
+      .await
+      .map_err(|e| e.to_string())?;
+  }
+  behavior.mark_inputs_processed(&subject.input_ids).await.map_err(|e| e.to_string())?;
+
+  // A stale sybil score is recomputed when read, so a failure here is not fatal
+  if let Err(e) = sybil.assess_input_user(latest.id.to_uuid()).await {
+    warn!(subject = %subject.subject, error = %e, "Sybil assessment failed");
+  }
+  Ok(())
+}
@@ -0,0 +1,116 @@
+use std::{future::Future, io, time::Duration};
+
+use jd_core::AppState;
+use tokio::{
+  task::JoinError,
+  time::{Instant, timeout, timeout_at},
+};
+use tracing::{info, warn};
+
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long the database pool may take to close once draining is done.
+const POOL_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Trigger `app_state.shutdown` on SIGTERM or SIGINT. The instance reports
+/// not ready first, so load balancers stop routing to it while it drains.
+pub fn listen(app_state: AppState) {
+  tokio::spawn(async move {
+    let signal = wait_for_signal().await;
+    info!(signal, "Shutting down; draining requests and background work");
+    app_state.readiness().mark_not_ready();
+    app_state.shutdown.trigger();
+  });
+}
+
+/// Run the server until shutdown is triggered. Then wait for it to finish
+/// the requests under way and for background tasks to stop, within
+/// `WEB.SHUTDOWN_TIMEOUT_SECS` in all, and abort what is left. Last, roll
+/// back any transaction the aborted work left open and close the database
+/// pool, so Postgres sees every connection end cleanly.
+///
+/// `server` must stop accepting connections once shutdown is triggered.
+pub async fn serve<F>(app_state: &AppState, server: F)
+where
+  F: Future<Output = io::Result<()>> + Send + 'static,
+{
+  let drain_timeout = app_state
+    .config
+    .web
+    .shutdown_timeout_secs
+    .map(Duration::from_secs)
+    .unwrap_or(DEFAULT_DRAIN_TIMEOUT);
+
+  let mut server = tokio::spawn(server);
+  let stopped = tokio::select! {
+    served = &mut server => {
+      log_served(served);
+      true
+    }
+    _ = app_state.shutdown.triggered() => false,
+  };
+  // A server that stopped on its own takes the background work down with it
+  app_state.shutdown.trigger();
+  let deadline = Instant::now() + drain_timeout;
+
+  if !stopped {
+    match timeout_at(deadline, &mut server).await {
+      Ok(served) => log_served(served),
+      Err(_) => {
+        server.abort();
+        warn!(timeout_secs = drain_timeout.as_secs(), "Requests still open at the drain timeout");
+      }
+    }
+  }
+
+  let aborted = app_state.shutdown.drain(deadline.saturating_duration_since(Instant::now())).await;
+  if aborted.is_empty() {
+    info!("Background tasks stopped");
+  } else {
+    warn!(?aborted, "Background tasks aborted at the drain timeout");
+  }
+
+  let dbx = app_state.mm().dbx();
+  if let Err(e) = dbx.abandon_txn().await {
+    warn!(error = %e, "Rolling back the open transaction failed");
+  }
+  // Postgres rolls back whatever is still open on a connection as it closes
+  if timeout(POOL_CLOSE_TIMEOUT, dbx.db().close()).await.is_err() {
+    warn!("Database connections still in use at exit");
+  }
+  info!("Shutdown complete");
+}
+
+fn log_served(served: Result<io::Result<()>, JoinError>) {
+  match served {
+    Ok(Ok(())) => info!("Server stopped; all connections closed"),
+    Ok(Err(e)) => warn!(error = %e, "Server stopped with an error"),
+    Err(e) => warn!(error = %e, "Server task failed"),
+  }
+}
+
+async fn wait_for_signal() -> &'static str {
+  tokio::select! {
+    Ok(()) = tokio::signal::ctrl_c() => "SIGINT",
+    _ = terminate() => "SIGTERM",
+  }
+}
+
+#[cfg(unix)]
+async fn terminate() {
+  use tokio::signal::unix::{SignalKind, signal};
+
+  match signal(SignalKind::terminate()) {
+    Ok(mut terminate) => {
+      terminate.recv().await;
+    }
+    Err(e) => {
+      warn!(error = %e, "SIGTERM handler not installed; only SIGINT shuts down gracefully");
+      std::future::pending::<()>().await;
+    }
+  }
+}
+
+#[cfg(not(unix))]
+async fn terminate() {
+  std::future::pending::<()>().await;
+}
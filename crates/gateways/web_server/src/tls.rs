@@ -0,0 +1,149 @@
+use std::{
+  future::{Future, IntoFuture},
+  io,
+  net::SocketAddr,
+  path::{Path, PathBuf},
+  pin::Pin,
+  sync::Arc,
+  time::{Duration, SystemTime},
+};
+
+use axum::Router;
+use axum_server::{Handle, tls_rustls::RustlsConfig};
+use futures::StreamExt;
+use jd_core::AppState;
+use jd_utils::config::WebConfig;
+use rustls::ServerConfig;
+use rustls_acme::{AcmeConfig, acme::ACME_TLS_ALPN_NAME, axum::AxumAcceptor, caches::DirCache};
+use tokio::{
+  net::TcpListener,
+  time::{MissedTickBehavior, interval},
+};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+
+/// How often the certificate files are checked for changes.
+const CERT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_ACME_CACHE_DIR: &str = "data/acme";
+
+pub type ServerFuture = Pin<Box<dyn Future<Output = io::Result<()>> + Send>>;
+
+/// Where certificates come from.
+enum Certificates {
+  Files { cert: PathBuf, key: PathBuf },
+  Acme,
+}
+
+impl Certificates {
+  fn from_config(web: &WebConfig) -> Option<Self> {
+    if web.tls_acme == Some(true) {
+      return Some(Self::Acme);
+    }
+    let (cert, key) = web.tls_cert_path.as_ref().zip(web.tls_key_path.as_ref())?;
+    Some(Self::Files { cert: cert.into(), key: key.into() })
+  }
+}
+
+/// The server of `app` on `listener`: HTTPS with the certificate files of
+/// `WEB.TLS_CERT_PATH` and `WEB.TLS_KEY_PATH` or from ACME, or else plain
+/// HTTP. HTTP/2 is served alongside HTTP/1.1, negotiated through ALPN over
+/// TLS and by prior knowledge without. The server stops accepting
+/// connections once shutdown is triggered.
+pub async fn server(
+  app_state: &AppState,
+  listener: TcpListener,
+  app: Router,
+) -> Result<ServerFuture> {
+  let web = &app_state.config.web;
+  let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+  let Some(certificates) = Certificates::from_config(web) else {
+    info!("Serving HTTP; TLS is left to a proxy in front");
+    let stop = app_state.shutdown.clone();
+    let server = axum::serve(listener, make_service)
+      .with_graceful_shutdown(async move { stop.triggered().await });
+    return Ok(Box::pin(server.into_future()));
+  };
+
+  // ring is the provider linked in already; rustls needs one chosen
+  let _ = rustls::crypto::ring::default_provider().install_default();
+  let handle = Handle::new();
+  let (stop, stopping) = (app_state.shutdown.clone(), handle.clone());
+  tokio::spawn(async move {
+    stop.triggered().await;
+    // Open connections are left to finish within the drain timeout
+    stopping.graceful_shutdown(None);
+  });
+  let listener = listener.into_std().map_err(Error::Tls)?;
+
+  match certificates {
+    Certificates::Acme => {
+      info!(domains = ?web.acme_domains(), "Serving HTTPS with ACME certificates");
+      let server = axum_server::from_tcp(listener)
+        .acceptor(acme_acceptor(web))
+        .handle(handle)
+        .serve(make_service);
+      Ok(Box::pin(server))
+    }
+    Certificates::Files { cert, key } => {
+      let config = RustlsConfig::from_pem_file(&cert, &key).await.map_err(Error::Tls)?;
+      info!(cert = %cert.display(), "Serving HTTPS");
+      app_state.shutdown.spawn_until("tls_reload", reload_on_change(config.clone(), cert, key));
+      let server = axum_server::from_tcp_rustls(listener, config).handle(handle);
+      Ok(Box::pin(server.serve(make_service)))
+    }
+  }
+}
+
+/// Accepts TLS with certificates from Let's Encrypt, ordered and renewed in
+/// the background and cached in `WEB.TLS_ACME_CACHE_DIR`.
+fn acme_acceptor(web: &WebConfig) -> AxumAcceptor {
+  let cache_dir = web.tls_acme_cache_dir.clone().unwrap_or_else(|| DEFAULT_ACME_CACHE_DIR.into());
+  let mut state = AcmeConfig::new(web.acme_domains())
+    .contact(web.tls_acme_email.iter().map(|email| format!("mailto:{email}")))
+    .cache(DirCache::new(cache_dir))
+    .directory_lets_encrypt(web.tls_acme_staging != Some(true))
+    .state();
+
+  let mut config =
+    ServerConfig::builder().with_no_client_auth().with_cert_resolver(state.resolver());
+  config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec(), ACME_TLS_ALPN_NAME.to_vec()];
+  let acceptor = state.axum_acceptor(Arc::new(config));
+
+  tokio::spawn(async move {
+    while let Some(event) = state.next().await {
+      match event {
+        Ok(event) => info!(?event, "ACME certificate event"),
+        Err(e) => warn!(error = %e, "ACME certificate order failed"),
+      }
+    }
+  });
+  acceptor
+}
+
+/// Serve the certificate files anew once they change, as when they are
+/// renewed. A pair that does not load, say while only one of them has been
+/// replaced, is retried at the next check.
+async fn reload_on_change(config: RustlsConfig, cert: PathBuf, key: PathBuf) {
+  let mut modified = modified_times(&cert, &key);
+  let mut poll = interval(CERT_POLL_INTERVAL);
+  poll.set_missed_tick_behavior(MissedTickBehavior::Skip);
+  loop {
+    poll.tick().await;
+    let current = modified_times(&cert, &key);
+    if current == modified {
+      continue;
+    }
+    match config.reload_from_pem_file(&cert, &key).await {
+      Ok(()) => {
+        modified = current;
+        info!(cert = %cert.display(), "TLS certificate reloaded");
+      }
+      Err(e) => warn!(error = %e, "TLS certificate not reloaded; serving the previous one"),
+    }
+  }
+}
+
+fn modified_times(cert: &Path, key: &Path) -> [Option<SystemTime>; 2] {
+  [cert, key].map(|path| path.metadata().and_then(|m| m.modified()).ok())
+}
@@ -0,0 +1,149 @@
+use std::{future::Future, pin::Pin, time::Duration};
+
+use ai_analysis_service::VulnerabilityPatterns;
+use jd_core::{AppState, readiness::WarmupCheck};
+use tokio::time::{Instant, sleep, timeout};
+use tracing::{info, warn};
+
+const STEP_TIMEOUT: Duration = Duration::from_secs(10);
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+type StepFuture = Pin<Box<dyn Future<Output = Result<String, String>> + Send>>;
+
+/// A single warm-up step. Required steps gate readiness and are retried until
+/// they succeed; optional steps are attempted once and only logged on failure.
+struct WarmupStep {
+  name: &'static str,
+  required: bool,
+  run: fn(AppState) -> StepFuture,
+}
+
+// Verification keys are read from Postgres per proof and LLM prompts are
+// formatted per request, so neither has a cache of its own to prime.
+const STEPS: &[WarmupStep] = &[
+  WarmupStep { name: "postgres", required: true, run: warm_postgres },
+  WarmupStep { name: "redis", required: true, run: warm_redis },
+  WarmupStep { name: "feature_flags", required: false, run: warm_feature_flags },
+  WarmupStep {
+    name: "vulnerability_patterns",
+    required: false,
+    run: warm_vulnerability_patterns,
+  },
+];
+
+/// Run every warm-up step and flip the readiness flag once all required steps
+/// have succeeded. Intended to be spawned right after the listener is bound.
+pub async fn run(app_state: AppState) {
+  let started = Instant::now();
+
+  for step in STEPS.iter().filter(|s| !s.required) {
+    run_step(step, &app_state).await;
+  }
+
+  let mut pending: Vec<&WarmupStep> = STEPS.iter().filter(|s| s.required).collect();
+  let mut backoff = INITIAL_BACKOFF;
+  loop {
+    let mut failed = Vec::new();
+    for step in pending {
+      if !run_step(step, &app_state).await {
+        failed.push(step);
+      }
+    }
+
+    if failed.is_empty() {
+      break;
+    }
+
+    warn!(
+      failed = ?failed.iter().map(|s| s.name).collect::<Vec<_>>(),
+      retry_in_secs = backoff.as_secs(),
+      "Warm-up incomplete, instance stays not-ready"
+    );
+    sleep(backoff).await;
+    backoff = (backoff * 2).min(MAX_BACKOFF);
+    pending = failed;
+  }
+
+  // A draining instance must not report ready again
+  if app_state.shutdown.is_triggered() {
+    return;
+  }
+  app_state.readiness().mark_ready();
+  info!(elapsed_ms = started.elapsed().as_millis() as u64, "Warm-up complete, instance is ready");
+}
+
+async fn run_step(step: &WarmupStep, app_state: &AppState) -> bool {
+  let started = Instant::now();
+  let result = match timeout(STEP_TIMEOUT, (step.run)(app_state.clone())).await {
+    Ok(result) => result,
+    Err(_) => Err(format!("timed out after {}s", STEP_TIMEOUT.as_secs())),
+  };
+  let duration_ms = started.elapsed().as_millis() as u64;
+
+  match &result {
+    Ok(detail) => info!(step = step.name, duration_ms, detail = %detail, "Warm-up step done"),
+    Err(error) => warn!(step = step.name, duration_ms, error = %error, "Warm-up step failed"),
+  }
+
+  let ok = result.is_ok();
+  app_state.readiness().record(WarmupCheck {
+    name: step.name,
+    ok,
+    required: step.required,
+    duration_ms,
+    error: result.err(),
+  });
+  ok
+}
+
+// region:    --- Steps
+
+/// Open the pool's minimum number of connections up front so the first
+/// requests do not pay TCP/TLS/auth handshakes.
+fn warm_postgres(app_state: AppState) -> StepFuture {
+  Box::pin(async move {
+    let pool = app_state.mm().dbx().db().clone();
+    let postgres = &app_state.config.postgres;
+    let target = postgres.min_conns.unwrap_or(1).clamp(1, postgres.max_conns.max(1));
+
+    let mut conns = Vec::with_capacity(target as usize);
+    for _ in 0..target {
+      let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+      sqlx::query("SELECT 1").execute(&mut *conn).await.map_err(|e| e.to_string())?;
+      // Hold on to each connection so the next acquire opens a new one.
+      conns.push(conn);
+    }
+
+    Ok(format!("{} connection(s) established", conns.len()))
+  })
+}
+
+fn warm_redis(app_state: AppState) -> StepFuture {
+  Box::pin(async move {
+    let mut conn = app_state
+      .redis()
+      .get_multiplexed_async_connection()
+      .await
+      .map_err(|e| e.to_string())?;
+    let pong: String =
+      redis::cmd("PING").query_async(&mut conn).await.map_err(|e| e.to_string())?;
+    Ok(pong)
+  })
+}
+
+fn warm_feature_flags(app_state: AppState) -> StepFuture {
+  Box::pin(async move {
+    let flags = app_state.flags.warm_up().await.map_err(|e| e.to_string())?;
+    Ok(format!("{flags} flag definition(s) cached"))
+  })
+}
+
+fn warm_vulnerability_patterns(_app_state: AppState) -> StepFuture {
+  Box::pin(async move {
+    let compiled = VulnerabilityPatterns::new().warm_up().map_err(|e| e.to_string())?;
+    Ok(format!("{compiled} pattern regex(es) compiled"))
+  })
+}
+
+// endregion: --- Steps
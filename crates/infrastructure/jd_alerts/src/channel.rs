@@ -0,0 +1,153 @@
+//! Slack and Discord channels organizations send alerts to.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::templates::{self, EVENTS};
+use crate::{Error, Result};
+
+const DEFAULT_MAX_PER_HOUR: i32 = 30;
+const MAX_PER_HOUR_LIMIT: i32 = 1000;
+const MAX_NAME_LEN: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelKind {
+  Slack,
+  Discord,
+}
+
+impl ChannelKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Slack => "slack",
+      Self::Discord => "discord",
+    }
+  }
+
+  /// Webhook URLs must be the provider's own, so channels cannot be used to
+  /// make requests elsewhere.
+  fn accepts_webhook(&self, url: &str) -> bool {
+    let prefixes: &[&str] = match self {
+      Self::Slack => &["https://hooks.slack.com/services/"],
+      Self::Discord => {
+        &["https://discord.com/api/webhooks/", "https://discordapp.com/api/webhooks/"]
+      }
+    };
+    prefixes.iter().any(|prefix| url.len() > prefix.len() && url.starts_with(prefix))
+  }
+}
+
+/// A channel as stored. The webhook URL embeds the provider's credential,
+/// so it is never serialized.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AlertChannel {
+  pub id: Uuid,
+  pub organization_id: Uuid,
+  /// The one repository the channel covers, or every repository the
+  /// organization monitors when unset.
+  pub repository_id: Option<Uuid>,
+  pub name: String,
+  pub kind: ChannelKind,
+  #[serde(skip_serializing)]
+  pub webhook_url: String,
+  pub events: Vec<String>,
+  /// Message templates by event; events without one use the default.
+  #[sqlx(json)]
+  pub templates: BTreeMap<String, String>,
+  pub max_per_hour: i32,
+  pub enabled: bool,
+  pub created_by: String,
+  pub last_delivered_at: Option<DateTime<Utc>>,
+  pub last_error: Option<String>,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl AlertChannel {
+  pub fn subscribes_to(&self, event: &str) -> bool {
+    self.enabled && self.events.iter().any(|subscribed| subscribed == event)
+  }
+}
+
+/// A channel as created or replaced through the API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChannelSettings {
+  pub name: String,
+  pub kind: ChannelKind,
+  /// Required when creating a channel; a replaced channel keeps its webhook
+  /// when left out.
+  pub webhook_url: Option<String>,
+  #[serde(default)]
+  pub repository_id: Option<Uuid>,
+  pub events: Vec<String>,
+  #[serde(default)]
+  pub templates: BTreeMap<String, String>,
+  #[serde(default = "default_max_per_hour")]
+  pub max_per_hour: i32,
+  #[serde(default = "default_enabled")]
+  pub enabled: bool,
+}
+
+impl ChannelSettings {
+  pub fn validate(&self) -> Result<()> {
+    let name = self.name.trim();
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+      return Err(Error::InvalidChannel(format!("name: must be 1 to {MAX_NAME_LEN} characters")));
+    }
+    if self.webhook_url.as_deref().is_some_and(|url| !self.kind.accepts_webhook(url)) {
+      return Err(Error::InvalidChannel(format!(
+        "webhook_url: not a {} incoming webhook URL",
+        self.kind.as_str()
+      )));
+    }
+    if self.events.is_empty() {
+      return Err(Error::InvalidChannel("events: at least one is required".to_string()));
+    }
+    if let Some(event) = self.events.iter().find(|event| !EVENTS.contains(&event.as_str())) {
+      return Err(Error::InvalidChannel(format!("events: unknown event '{event}'")));
+    }
+    if !(1..=MAX_PER_HOUR_LIMIT).contains(&self.max_per_hour) {
+      return Err(Error::InvalidChannel(format!(
+        "max_per_hour: must be between 1 and {MAX_PER_HOUR_LIMIT}"
+      )));
+    }
+    templates::validate_templates(&self.templates)
+  }
+}
+
+fn default_max_per_hour() -> i32 {
+  DEFAULT_MAX_PER_HOUR
+}
+
+fn default_enabled() -> bool {
+  true
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn webhook_urls_must_belong_to_the_provider() {
+    let mut settings: ChannelSettings = serde_json::from_value(serde_json::json!({
+      "name": "#security",
+      "kind": "slack",
+      "webhook_url": "https://hooks.slack.com/services/T000/B000/XXXX",
+      "events": ["critical_finding"],
+    }))
+    .unwrap();
+    settings.validate().unwrap();
+    assert_eq!(settings.max_per_hour, DEFAULT_MAX_PER_HOUR);
+
+    settings.kind = ChannelKind::Discord;
+    assert!(settings.validate().is_err());
+    settings.webhook_url = Some("https://discord.com/api/webhooks/1/abc".to_string());
+    settings.validate().unwrap();
+    settings.webhook_url = Some("http://169.254.169.254/latest".to_string());
+    assert!(settings.validate().is_err());
+  }
+}
@@ -0,0 +1,16 @@
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  #[error("Alert channel database error: {0}")]
+  Database(#[from] sqlx::Error),
+
+  #[error("Invalid alert channel: {0}")]
+  InvalidChannel(String),
+
+  #[error("Alert delivery failed: {0}")]
+  Delivery(String),
+
+  #[error("Alert channel has sent its {0} alerts for this hour")]
+  RateLimited(i32),
+}
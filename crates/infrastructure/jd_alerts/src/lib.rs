@@ -0,0 +1,261 @@
+//! Chat alerts. Organizations point Slack and Discord incoming webhooks at
+//! their repositories; event subscribers post critical findings, failed
+//! analyses and merged patches to the channels subscribed to them, rendered
+//! from each channel's templates and capped at its hourly limit.
+
+pub mod channel;
+mod error;
+mod metrics;
+pub mod templates;
+
+use jd_cache::Cache;
+use jd_utils::correlation::Correlate;
+use sqlx::{Pool, Postgres, types::Json};
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+pub use channel::{AlertChannel, ChannelKind, ChannelSettings};
+pub use error::{Error, Result};
+pub use templates::Alert;
+
+use metrics::ALERTS;
+
+const COLUMNS: &str = "id, organization_id, repository_id, name, kind, webhook_url, events, \
+                       templates, max_per_hour, enabled, created_by, last_delivered_at, \
+                       last_error, created_at, updated_at";
+const RATE_WINDOW: Duration = Duration::from_secs(60 * 60);
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What an `AlertChannels::dispatch` did.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DispatchRun {
+  pub delivered: usize,
+  pub failed: usize,
+  pub rate_limited: usize,
+}
+
+#[derive(Clone)]
+pub struct AlertChannels {
+  db: Pool<Postgres>,
+  cache: Cache,
+  http: reqwest::Client,
+}
+
+impl AlertChannels {
+  pub fn new(db: Pool<Postgres>, cache: Cache) -> Self {
+    let http = reqwest::Client::builder().timeout(WEBHOOK_TIMEOUT).build().unwrap_or_default();
+    Self { db, cache, http }
+  }
+
+  pub async fn list(&self, organization_id: Uuid) -> Result<Vec<AlertChannel>> {
+    let channels = sqlx::query_as::<_, AlertChannel>(&format!(
+      "SELECT {COLUMNS} FROM alert_channels WHERE organization_id = $1 ORDER BY created_at"
+    ))
+    .bind(organization_id)
+    .fetch_all(&self.db)
+    .await?;
+    Ok(channels)
+  }
+
+  pub async fn get(&self, organization_id: Uuid, id: Uuid) -> Result<Option<AlertChannel>> {
+    let channel = sqlx::query_as::<_, AlertChannel>(&format!(
+      "SELECT {COLUMNS} FROM alert_channels WHERE id = $1 AND organization_id = $2"
+    ))
+    .bind(id)
+    .bind(organization_id)
+    .fetch_optional(&self.db)
+    .await?;
+    Ok(channel)
+  }
+
+  pub async fn create(
+    &self,
+    organization_id: Uuid,
+    settings: &ChannelSettings,
+    created_by: &str,
+  ) -> Result<AlertChannel> {
+    settings.validate()?;
+    let Some(webhook_url) = &settings.webhook_url else {
+      return Err(Error::InvalidChannel("webhook_url: required".to_string()));
+    };
+    self.check_repository(organization_id, settings.repository_id).await?;
+    let channel = sqlx::query_as::<_, AlertChannel>(&format!(
+      "INSERT INTO alert_channels \
+         (organization_id, repository_id, name, kind, webhook_url, events, templates, \
+          max_per_hour, enabled, created_by) \
+       VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+       RETURNING {COLUMNS}"
+    ))
+    .bind(organization_id)
+    .bind(settings.repository_id)
+    .bind(settings.name.trim())
+    .bind(settings.kind)
+    .bind(webhook_url)
+    .bind(&settings.events)
+    .bind(Json(&settings.templates))
+    .bind(settings.max_per_hour)
+    .bind(settings.enabled)
+    .bind(created_by)
+    .fetch_one(&self.db)
+    .await?;
+    Ok(channel)
+  }
+
+  /// Replace the channel's settings. None if the organization has no such
+  /// channel.
+  pub async fn update(
+    &self,
+    organization_id: Uuid,
+    id: Uuid,
+    settings: &ChannelSettings,
+  ) -> Result<Option<AlertChannel>> {
+    settings.validate()?;
+    self.check_repository(organization_id, settings.repository_id).await?;
+    // A webhook left out is kept, unless the kind changed under it
+    let channel = sqlx::query_as::<_, AlertChannel>(&format!(
+      "UPDATE alert_channels SET \
+         repository_id = $3, name = $4, kind = $5, webhook_url = COALESCE($6, webhook_url), \
+         events = $7, templates = $8, max_per_hour = $9, enabled = $10, updated_at = NOW() \
+       WHERE id = $1 AND organization_id = $2 AND ($6 IS NOT NULL OR kind = $5) \
+       RETURNING {COLUMNS}"
+    ))
+    .bind(id)
+    .bind(organization_id)
+    .bind(settings.repository_id)
+    .bind(settings.name.trim())
+    .bind(settings.kind)
+    .bind(&settings.webhook_url)
+    .bind(&settings.events)
+    .bind(Json(&settings.templates))
+    .bind(settings.max_per_hour)
+    .bind(settings.enabled)
+    .fetch_optional(&self.db)
+    .await?;
+    // Tell a changed kind apart from a missing channel
+    if channel.is_none()
+      && settings.webhook_url.is_none()
+      && self.get(organization_id, id).await?.is_some()
+    {
+      return Err(Error::InvalidChannel("webhook_url: required when changing the kind".to_string()));
+    }
+    Ok(channel)
+  }
+
+  pub async fn delete(&self, organization_id: Uuid, id: Uuid) -> Result<bool> {
+    let deleted = sqlx::query("DELETE FROM alert_channels WHERE id = $1 AND organization_id = $2")
+      .bind(id)
+      .bind(organization_id)
+      .execute(&self.db)
+      .await?;
+    Ok(deleted.rows_affected() > 0)
+  }
+
+  /// Post the alert to every enabled channel subscribed to its event that
+  /// covers the repository. A channel that fails is not retried, so the
+  /// others are not alerted twice; its `last_error` says why.
+  pub async fn dispatch(&self, repository_id: Uuid, alert: &Alert) -> Result<DispatchRun> {
+    let channels = sqlx::query_as::<_, AlertChannel>(&format!(
+      "SELECT {COLUMNS} FROM alert_channels \
+       WHERE enabled AND $2 = ANY(events) \
+         AND (repository_id = $1 \
+           OR (repository_id IS NULL AND organization_id IN ( \
+             SELECT organization_id FROM organization_repositories \
+             WHERE github_repository_id = $1)))"
+    ))
+    .bind(repository_id)
+    .bind(alert.event())
+    .fetch_all(&self.db)
+    .await?;
+
+    let mut run = DispatchRun::default();
+    for channel in &channels {
+      match self.send(channel, alert).await {
+        Ok(()) => run.delivered += 1,
+        Err(Error::RateLimited(_)) => run.rate_limited += 1,
+        Err(e) => {
+          warn!(channel = %channel.id, event = alert.event(), "Alert not delivered: {e}");
+          run.failed += 1;
+        }
+      }
+    }
+    Ok(run)
+  }
+
+  /// Post the alert to one channel, within its hourly limit, and record the
+  /// outcome on the channel.
+  pub async fn send(&self, channel: &AlertChannel, alert: &Alert) -> Result<()> {
+    let (kind, event) = (channel.kind.as_str(), alert.event());
+    if let Err(e) = self.take_slot(channel).await {
+      ALERTS.with_label_values(&[kind, event, "rate_limited"]).inc();
+      return Err(e);
+    }
+
+    let body = templates::payload(channel.kind, &alert.render(&channel.templates));
+    let response = self.http.post(&channel.webhook_url).json(&body).correlated().send().await;
+    let posted = match response {
+      Ok(response) if response.status().is_success() => Ok(()),
+      Ok(response) => {
+        Err(Error::Delivery(format!("{kind} webhook returned {}", response.status())))
+      }
+      // The URL holds the webhook's credential; keep it out of errors
+      Err(e) => Err(Error::Delivery(e.without_url().to_string())),
+    };
+
+    let outcome = if posted.is_ok() { "delivered" } else { "failed" };
+    ALERTS.with_label_values(&[kind, event, outcome]).inc();
+    let last_error = posted.as_ref().err().map(ToString::to_string);
+    sqlx::query(
+      "UPDATE alert_channels SET \
+         last_delivered_at = CASE WHEN $2::TEXT IS NULL THEN NOW() ELSE last_delivered_at END, \
+         last_error = $2 \
+       WHERE id = $1",
+    )
+    .bind(channel.id)
+    .bind(&last_error)
+    .execute(&self.db)
+    .await?;
+    posted
+  }
+
+  /// Count a message against the channel's hourly limit. Alerts are sent
+  /// anyway while Redis is unavailable.
+  async fn take_slot(&self, channel: &AlertChannel) -> Result<()> {
+    let key = format!("alerts:rate:{}", channel.id);
+    match self.cache.increment(&key, 1, Some(RATE_WINDOW)).await {
+      Ok(sent) if sent > i64::from(channel.max_per_hour) => {
+        Err(Error::RateLimited(channel.max_per_hour))
+      }
+      Ok(_) => Ok(()),
+      Err(e) => {
+        warn!(channel = %channel.id, "Alert rate limit unavailable; sending anyway: {e}");
+        Ok(())
+      }
+    }
+  }
+
+  /// A channel for one repository must be for one the organization monitors.
+  async fn check_repository(
+    &self,
+    organization_id: Uuid,
+    repository_id: Option<Uuid>,
+  ) -> Result<()> {
+    let Some(repository_id) = repository_id else {
+      return Ok(());
+    };
+    let monitored: bool = sqlx::query_scalar(
+      "SELECT EXISTS (SELECT 1 FROM organization_repositories \
+       WHERE organization_id = $1 AND github_repository_id = $2)",
+    )
+    .bind(organization_id)
+    .bind(repository_id)
+    .fetch_one(&self.db)
+    .await?;
+    if !monitored {
+      return Err(Error::InvalidChannel(
+        "repository_id: not a repository the organization monitors".to_string(),
+      ));
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,12 @@
+use jd_utils::metrics::{self, IntCounterVec};
+use std::sync::LazyLock;
+
+/// Alerts posted to chat channels, by channel kind, event and outcome
+/// (`delivered`, `failed` or `rate_limited`).
+pub static ALERTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+  metrics::counter(
+    "chat_alerts_total",
+    "Alerts posted to Slack and Discord channels",
+    &["kind", "event", "outcome"],
+  )
+});
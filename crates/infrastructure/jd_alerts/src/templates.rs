@@ -0,0 +1,222 @@
+//! The alerts channels can receive, and the message templates they are
+//! rendered with. Templates name variables in braces, e.g.
+//! `Critical {vulnerability_type} in {repository}`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::channel::ChannelKind;
+use crate::{Error, Result};
+
+/// Events a channel can subscribe to.
+pub const EVENTS: [&str; 3] = ["critical_finding", "analysis_failed", "patch_merged"];
+
+const MAX_TEMPLATE_LEN: usize = 1000;
+/// Discord rejects longer messages.
+const DISCORD_MAX_LEN: usize = 2000;
+const SLACK_MAX_LEN: usize = 4000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Alert {
+  CriticalFinding {
+    vulnerability_id: Uuid,
+    repository: String,
+    vulnerability_type: String,
+    file_path: String,
+  },
+  AnalysisFailed {
+    analysis_job_id: Uuid,
+    repository: String,
+    commit_sha: String,
+    attempts: i32,
+    error: String,
+  },
+  PatchMerged {
+    patch_id: Uuid,
+    repository: String,
+    pull_request_number: i32,
+  },
+}
+
+impl Alert {
+  /// Name of the event, as channels subscribe to it.
+  pub fn event(&self) -> &'static str {
+    match self {
+      Self::CriticalFinding { .. } => "critical_finding",
+      Self::AnalysisFailed { .. } => "analysis_failed",
+      Self::PatchMerged { .. } => "patch_merged",
+    }
+  }
+
+  /// An alert of `event` with made-up values, for test deliveries.
+  pub fn sample(event: &str) -> Option<Self> {
+    let repository = "acme/payments".to_string();
+    match event {
+      "critical_finding" => Some(Self::CriticalFinding {
+        vulnerability_id: Uuid::nil(),
+        repository,
+        vulnerability_type: "sql_injection".to_string(),
+        file_path: "src/db/query.rs".to_string(),
+      }),
+      "analysis_failed" => Some(Self::AnalysisFailed {
+        analysis_job_id: Uuid::nil(),
+        repository,
+        commit_sha: "4f1c2a9e8d3b4c7a9f2e1a6b5c3d7e80a1b2c3d4".to_string(),
+        attempts: 5,
+        error: "repository could not be cloned".to_string(),
+      }),
+      "patch_merged" => Some(Self::PatchMerged {
+        patch_id: Uuid::nil(),
+        repository,
+        pull_request_number: 42,
+      }),
+      _ => None,
+    }
+  }
+
+  /// The variables templates of this alert's event may use.
+  pub fn variables(&self) -> Vec<(&'static str, String)> {
+    match self {
+      Self::CriticalFinding { vulnerability_id, repository, vulnerability_type, file_path } => {
+        vec![
+          ("vulnerability_id", vulnerability_id.to_string()),
+          ("repository", repository.clone()),
+          ("vulnerability_type", vulnerability_type.replace('_', " ")),
+          ("file_path", file_path.clone()),
+        ]
+      }
+      Self::AnalysisFailed { analysis_job_id, repository, commit_sha, attempts, error } => vec![
+        ("analysis_job_id", analysis_job_id.to_string()),
+        ("repository", repository.clone()),
+        ("commit", commit_sha[..commit_sha.len().min(7)].to_string()),
+        ("attempts", attempts.to_string()),
+        ("error", error.clone()),
+      ],
+      Self::PatchMerged { patch_id, repository, pull_request_number } => vec![
+        ("patch_id", patch_id.to_string()),
+        ("repository", repository.clone()),
+        ("pull_request_number", pull_request_number.to_string()),
+      ],
+    }
+  }
+
+  /// The message for a channel, from its template for this event or the
+  /// default one.
+  pub fn render(&self, templates: &BTreeMap<String, String>) -> String {
+    let template = templates
+      .get(self.event())
+      .map(String::as_str)
+      .unwrap_or_else(|| default_template(self.event()));
+    let mut message = template.to_string();
+    for (name, value) in self.variables() {
+      message = message.replace(&format!("{{{name}}}"), &value);
+    }
+    message
+  }
+}
+
+pub fn default_template(event: &str) -> &'static str {
+  match event {
+    "critical_finding" => {
+      ":rotating_light: Critical {vulnerability_type} found in {repository} at {file_path}"
+    }
+    "analysis_failed" => {
+      ":x: Analysis of {repository} at {commit} failed after {attempts} attempt(s): {error}"
+    }
+    "patch_merged" => ":white_check_mark: Patch merged into {repository} in #{pull_request_number}",
+    _ => "",
+  }
+}
+
+/// Reject templates for unknown events, overly long ones, and ones naming
+/// variables their event does not have.
+pub fn validate_templates(templates: &BTreeMap<String, String>) -> Result<()> {
+  for (event, template) in templates {
+    let Some(sample) = Alert::sample(event) else {
+      return Err(Error::InvalidChannel(format!("templates: unknown event '{event}'")));
+    };
+    if template.trim().is_empty() || template.len() > MAX_TEMPLATE_LEN {
+      return Err(Error::InvalidChannel(format!(
+        "templates.{event}: must be 1 to {MAX_TEMPLATE_LEN} characters"
+      )));
+    }
+    let variables = sample.variables();
+    for name in placeholders(template) {
+      if !variables.iter().any(|(variable, _)| *variable == name) {
+        return Err(Error::InvalidChannel(format!(
+          "templates.{event}: unknown variable '{{{name}}}'"
+        )));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// The webhook body posting `message` to a channel of `kind`.
+pub fn payload(kind: ChannelKind, message: &str) -> serde_json::Value {
+  match kind {
+    // Slack treats &, < and > as markup
+    ChannelKind::Slack => serde_json::json!({
+      "text": truncate(
+        &message.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;"),
+        SLACK_MAX_LEN,
+      ),
+    }),
+    // Repository and error text must not ping @everyone
+    ChannelKind::Discord => serde_json::json!({
+      "content": truncate(message, DISCORD_MAX_LEN),
+      "allowed_mentions": { "parse": [] },
+    }),
+  }
+}
+
+/// The names in `{name}` placeholders, e.g. `repository`.
+fn placeholders(template: &str) -> Vec<&str> {
+  let mut names = Vec::new();
+  let mut rest = template;
+  while let Some(start) = rest.find('{') {
+    rest = &rest[start + 1..];
+    let Some(end) = rest.find('}') else { break };
+    let name = &rest[..end];
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+      names.push(name);
+    }
+    rest = &rest[end + 1..];
+  }
+  names
+}
+
+fn truncate(message: &str, max_chars: usize) -> String {
+  if message.chars().count() <= max_chars {
+    return message.to_string();
+  }
+  let mut truncated: String = message.chars().take(max_chars - 1).collect();
+  truncated.push('…');
+  truncated
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_custom_templates_and_rejects_unknown_variables() {
+    let alert = Alert::sample("analysis_failed").unwrap();
+    let mut templates = BTreeMap::new();
+    assert_eq!(
+      alert.render(&templates),
+      ":x: Analysis of acme/payments at 4f1c2a9 failed after 5 attempt(s): \
+       repository could not be cloned"
+    );
+
+    templates.insert("analysis_failed".to_string(), "{repository} broke <@here>".to_string());
+    validate_templates(&templates).unwrap();
+    let message = alert.render(&templates);
+    assert_eq!(payload(ChannelKind::Slack, &message)["text"], "acme/payments broke &lt;@here&gt;");
+
+    templates.insert("patch_merged".to_string(), "{repository} merged {commit}".to_string());
+    assert!(validate_templates(&templates).is_err());
+  }
+}
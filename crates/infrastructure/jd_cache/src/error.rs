@@ -0,0 +1,13 @@
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  #[error("Redis error: {0}")]
+  Redis(#[from] redis::RedisError),
+
+  #[error("Cached value for '{key}' could not be (de)serialized: {source}")]
+  Serde { key: String, source: serde_json::Error },
+
+  #[error("Lock on '{resource}' is held elsewhere")]
+  LockAcquisitionFailed { resource: String },
+}
@@ -0,0 +1,290 @@
+//! Redis behind a small pool of reconnecting connections, with keys
+//! namespaced per environment, serde-typed values, TTL helpers and
+//! distributed locks. Every command is timed and counted in
+//! `jd_utils::metrics`.
+
+mod error;
+mod lock;
+pub mod metrics;
+
+pub use error::{Error, Result};
+pub use lock::CacheLock;
+
+use redis::{AsyncCommands, Client, RedisResult, aio::ConnectionManager};
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+  future::Future,
+  sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+  },
+  time::{Duration, Instant},
+};
+use tokio::sync::OnceCell;
+
+use crate::metrics::{CACHE_DURATION, CACHE_OPERATIONS};
+
+const DEFAULT_POOL_SIZE: usize = 4;
+
+/// A namespaced view of Redis. Cheap to clone; clones share connections.
+#[derive(Clone)]
+pub struct Cache {
+  inner: Arc<Inner>,
+}
+
+struct Inner {
+  client: Client,
+  namespace: String,
+  pool_size: usize,
+  /// Opened on first use, so a Redis that is down does not stop startup.
+  connections: OnceCell<Vec<ConnectionManager>>,
+  next: AtomicUsize,
+}
+
+impl Cache {
+  pub fn new(client: Client, namespace: impl Into<String>, pool_size: usize) -> Self {
+    Self {
+      inner: Arc::new(Inner {
+        client,
+        namespace: namespace.into(),
+        pool_size: pool_size.max(1),
+        connections: OnceCell::new(),
+        next: AtomicUsize::new(0),
+      }),
+    }
+  }
+
+  /// From `redis.addr`, `redis.namespace` (the environment's name by default)
+  /// and `redis.pool_size` (default 4).
+  pub fn from_config(config: &jd_utils::config::Redis) -> Result<Self> {
+    let client = Client::open(config.addr.as_str())?;
+    let namespace = config.namespace.clone().unwrap_or_else(jd_utils::config::environment_name);
+    Ok(Self::new(client, namespace, config.pool_size.unwrap_or(DEFAULT_POOL_SIZE)))
+  }
+
+  pub fn namespace(&self) -> &str {
+    &self.inner.namespace
+  }
+
+  /// `key` within this cache's namespace, e.g. `production:auth:nonce:...`.
+  pub fn key(&self, key: &str) -> String {
+    format!("{}:{}", self.inner.namespace, key)
+  }
+
+  /// A connection from the pool, for commands the helpers do not cover.
+  /// Keys passed to it must go through [`Cache::key`]. Connections reconnect
+  /// on their own after Redis restarts.
+  pub async fn connection(&self) -> Result<ConnectionManager> {
+    let connections = self
+      .inner
+      .connections
+      .get_or_try_init(|| async {
+        let mut connections = Vec::with_capacity(self.inner.pool_size);
+        for _ in 0..self.inner.pool_size {
+          connections.push(ConnectionManager::new(self.inner.client.clone()).await?);
+        }
+        Ok::<_, Error>(connections)
+      })
+      .await?;
+    let index = self.inner.next.fetch_add(1, Ordering::Relaxed) % connections.len();
+    Ok(connections[index].clone())
+  }
+
+  pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+    let (name, key) = (key, self.key(key));
+    let value: Option<String> =
+      self.run("get", |mut conn| async move { conn.get(key).await }).await?;
+    record("get", if value.is_some() { "hit" } else { "miss" });
+    value.map(|json| decode(name, &json)).transpose()
+  }
+
+  /// Store `value`, expiring after `ttl` when given.
+  pub async fn set<T: Serialize>(
+    &self,
+    key: &str,
+    value: &T,
+    ttl: Option<Duration>,
+  ) -> Result<()> {
+    let json = serde_json::to_string(value).map_err(|source| serde_error(key, source))?;
+    let key = self.key(key);
+    self
+      .run("set", |mut conn| async move {
+        match ttl {
+          Some(ttl) => conn.set_ex(key, json, ttl_secs(ttl)).await,
+          None => conn.set(key, json).await,
+        }
+      })
+      .await?;
+    record("set", "ok");
+    Ok(())
+  }
+
+  /// Store `value` unless the key exists. True if it was stored.
+  pub async fn set_if_absent<T: Serialize>(
+    &self,
+    key: &str,
+    value: &T,
+    ttl: Duration,
+  ) -> Result<bool> {
+    let json = serde_json::to_string(value).map_err(|source| serde_error(key, source))?;
+    let key = self.key(key);
+    let stored: Option<String> = self
+      .run("set_if_absent", |mut conn| async move {
+        redis::cmd("SET")
+          .arg(key)
+          .arg(json)
+          .arg("NX")
+          .arg("PX")
+          .arg(ttl_millis(ttl))
+          .query_async(&mut conn)
+          .await
+      })
+      .await?;
+    record("set_if_absent", "ok");
+    Ok(stored.is_some())
+  }
+
+  /// Get and delete in one step, so two callers cannot both take a value.
+  pub async fn take<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+    let (name, key) = (key, self.key(key));
+    let value: Option<String> =
+      self.run("take", |mut conn| async move { conn.get_del(key).await }).await?;
+    record("take", if value.is_some() { "hit" } else { "miss" });
+    value.map(|json| decode(name, &json)).transpose()
+  }
+
+  /// The cached value, or `compute`'s, stored for `ttl`.
+  pub async fn get_or_set<T, F, Fut, E>(
+    &self,
+    key: &str,
+    ttl: Duration,
+    compute: F,
+  ) -> std::result::Result<T, E>
+  where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = std::result::Result<T, E>>,
+    E: From<Error>,
+  {
+    if let Some(value) = self.get(key).await? {
+      return Ok(value);
+    }
+    let value = compute().await?;
+    self.set(key, &value, Some(ttl)).await?;
+    Ok(value)
+  }
+
+  /// True if the key existed.
+  pub async fn delete(&self, key: &str) -> Result<bool> {
+    let key = self.key(key);
+    let deleted: u64 = self.run("delete", |mut conn| async move { conn.del(key).await }).await?;
+    record("delete", "ok");
+    Ok(deleted > 0)
+  }
+
+  pub async fn exists(&self, key: &str) -> Result<bool> {
+    let key = self.key(key);
+    let exists: bool =
+      self.run("exists", |mut conn| async move { conn.exists(key).await }).await?;
+    record("exists", "ok");
+    Ok(exists)
+  }
+
+  /// Expire the key after `ttl`. False if it does not exist.
+  pub async fn expire(&self, key: &str, ttl: Duration) -> Result<bool> {
+    let key = self.key(key);
+    let millis = ttl_millis(ttl) as i64;
+    let set: bool =
+      self.run("expire", |mut conn| async move { conn.pexpire(key, millis).await }).await?;
+    record("expire", "ok");
+    Ok(set)
+  }
+
+  /// Time left before the key expires; None if it does not exist or never
+  /// expires.
+  pub async fn ttl(&self, key: &str) -> Result<Option<Duration>> {
+    let key = self.key(key);
+    let millis: i64 = self.run("ttl", |mut conn| async move { conn.pttl(key).await }).await?;
+    record("ttl", "ok");
+    Ok(u64::try_from(millis).ok().map(Duration::from_millis))
+  }
+
+  /// Add `by` to a counter. A counter created by this call expires after
+  /// `ttl`, e.g. for fixed-window rate limits.
+  pub async fn increment(&self, key: &str, by: i64, ttl: Option<Duration>) -> Result<i64> {
+    let key = self.key(key);
+    let count: i64 = self
+      .run("increment", |mut conn| async move {
+        let count: i64 = conn.incr(&key, by).await?;
+        if let Some(ttl) = ttl.filter(|_| count == by) {
+          let _: bool = conn.pexpire(&key, ttl_millis(ttl) as i64).await?;
+        }
+        Ok(count)
+      })
+      .await?;
+    record("increment", "ok");
+    Ok(count)
+  }
+
+  /// Take the lock on `resource` for `ttl`, or None if it is held.
+  pub async fn try_lock(&self, resource: &str, ttl: Duration) -> Result<Option<CacheLock>> {
+    CacheLock::try_acquire(self, resource, ttl).await
+  }
+
+  /// Take the lock on `resource` for `ttl`, waiting up to `wait` for its
+  /// holder to release it. Fails with `LockAcquisitionFailed`.
+  pub async fn lock(&self, resource: &str, ttl: Duration, wait: Duration) -> Result<CacheLock> {
+    CacheLock::acquire(self, resource, ttl, wait).await
+  }
+
+  /// Run `command` on a pooled connection, timing it and counting failures.
+  pub(crate) async fn run<T, F, Fut>(&self, operation: &'static str, command: F) -> Result<T>
+  where
+    F: FnOnce(ConnectionManager) -> Fut,
+    Fut: Future<Output = RedisResult<T>>,
+  {
+    let started = Instant::now();
+    let result = match self.connection().await {
+      Ok(conn) => command(conn).await.map_err(Error::from),
+      Err(e) => Err(e),
+    };
+    CACHE_DURATION.with_label_values(&[operation]).observe(started.elapsed().as_secs_f64());
+    if result.is_err() {
+      record(operation, "error");
+    }
+    result
+  }
+}
+
+fn decode<T: DeserializeOwned>(key: &str, json: &str) -> Result<T> {
+  serde_json::from_str(json).map_err(|source| serde_error(key, source))
+}
+
+fn serde_error(key: &str, source: serde_json::Error) -> Error {
+  Error::Serde { key: key.to_string(), source }
+}
+
+pub(crate) fn record(operation: &str, outcome: &str) {
+  CACHE_OPERATIONS.with_label_values(&[operation, outcome]).inc();
+}
+
+/// Redis rejects a TTL of zero, so the shortest is one second.
+fn ttl_secs(ttl: Duration) -> u64 {
+  ttl.as_secs().max(1)
+}
+
+pub(crate) fn ttl_millis(ttl: Duration) -> u64 {
+  (ttl.as_millis() as u64).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn keys_are_namespaced() {
+    let cache = Cache::new(Client::open("redis://localhost:6379").unwrap(), "staging", 2);
+    assert_eq!(cache.key("auth:nonce:sui:0x1"), "staging:auth:nonce:sui:0x1");
+    assert_eq!(ttl_secs(Duration::from_millis(10)), 1);
+  }
+}
@@ -0,0 +1,114 @@
+use redis::Script;
+use std::time::Duration;
+use tokio::time::{Instant, sleep};
+use uuid::Uuid;
+
+use crate::{Cache, Error, Result, record, ttl_millis};
+
+/// How often a waiting `Cache::lock` retries.
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Deletes the lock only while it still holds our token.
+const RELEASE_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+  return redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+/// Extends the lock only while it still holds our token.
+const EXTEND_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+  return redis.call("PEXPIRE", KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+/// A lock on a resource shared by every process using the same Redis
+/// namespace. It lapses after its TTL, so a crashed holder cannot block
+/// others forever; holders of long tasks should `extend` it. Dropping it
+/// does not release it.
+pub struct CacheLock {
+  cache: Cache,
+  resource: String,
+  key: String,
+  token: String,
+}
+
+impl CacheLock {
+  pub(crate) async fn try_acquire(
+    cache: &Cache,
+    resource: &str,
+    ttl: Duration,
+  ) -> Result<Option<Self>> {
+    let key = cache.key(&format!("lock:{resource}"));
+    let token = Uuid::new_v4().to_string();
+    let (lock_key, lock_token) = (key.clone(), token.clone());
+    let acquired: Option<String> = cache
+      .run("lock", |mut conn| async move {
+        redis::cmd("SET")
+          .arg(lock_key)
+          .arg(lock_token)
+          .arg("NX")
+          .arg("PX")
+          .arg(ttl_millis(ttl))
+          .query_async(&mut conn)
+          .await
+      })
+      .await?;
+    record("lock", if acquired.is_some() { "acquired" } else { "held" });
+    Ok(acquired.map(|_| Self { cache: cache.clone(), resource: resource.to_string(), key, token }))
+  }
+
+  pub(crate) async fn acquire(
+    cache: &Cache,
+    resource: &str,
+    ttl: Duration,
+    wait: Duration,
+  ) -> Result<Self> {
+    let deadline = Instant::now() + wait;
+    loop {
+      if let Some(lock) = Self::try_acquire(cache, resource, ttl).await? {
+        return Ok(lock);
+      }
+      if Instant::now() + RETRY_INTERVAL > deadline {
+        return Err(Error::LockAcquisitionFailed { resource: resource.to_string() });
+      }
+      sleep(RETRY_INTERVAL).await;
+    }
+  }
+
+  pub fn resource(&self) -> &str {
+    &self.resource
+  }
+
+  /// Hold the lock for `ttl` from now. False if it had lapsed and may be
+  /// held by someone else.
+  pub async fn extend(&self, ttl: Duration) -> Result<bool> {
+    let (key, token) = (self.key.clone(), self.token.clone());
+    let extended: i64 = self
+      .cache
+      .run("lock_extend", |mut conn| async move {
+        Script::new(EXTEND_SCRIPT)
+          .key(key)
+          .arg(token)
+          .arg(ttl_millis(ttl))
+          .invoke_async(&mut conn)
+          .await
+      })
+      .await?;
+    Ok(extended == 1)
+  }
+
+  /// Release the lock. False if it had already lapsed.
+  pub async fn release(self) -> Result<bool> {
+    let (key, token) = (self.key, self.token);
+    let released: i64 = self
+      .cache
+      .run("lock_release", |mut conn| async move {
+        Script::new(RELEASE_SCRIPT).key(key).arg(token).invoke_async(&mut conn).await
+      })
+      .await?;
+    Ok(released == 1)
+  }
+}
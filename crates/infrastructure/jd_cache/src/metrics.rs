@@ -0,0 +1,21 @@
+use jd_utils::metrics::{self, HistogramVec, IntCounterVec};
+use std::sync::LazyLock;
+
+/// Buckets, in seconds, for Redis round trips.
+const CACHE_BUCKETS: &[f64] = &[0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0];
+
+/// Time of each cache command, by operation.
+pub static CACHE_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+  metrics::histogram(
+    "cache_operation_duration_seconds",
+    "Time of Redis cache commands",
+    &["operation"],
+    CACHE_BUCKETS,
+  )
+});
+
+/// Cache commands by operation and outcome: `hit`, `miss`, `ok`, `error`,
+/// or for locks `acquired` or `held`.
+pub static CACHE_OPERATIONS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+  metrics::counter("cache_operations_total", "Redis cache commands", &["operation", "outcome"])
+});
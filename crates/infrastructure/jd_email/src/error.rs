@@ -0,0 +1,19 @@
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  #[error("Email database error: {0}")]
+  Database(#[from] sqlx::Error),
+
+  #[error("Email payload could not be (de)serialized: {0}")]
+  Payload(#[from] serde_json::Error),
+
+  #[error("Invalid email configuration: {0}")]
+  Config(String),
+
+  #[error("'{address}' is not a valid email address")]
+  InvalidAddress { address: String },
+
+  #[error("Sending through {provider} failed: {reason}")]
+  Send { provider: &'static str, reason: String },
+}
@@ -0,0 +1,163 @@
+//! Transactional email. Emails are queued in `email_jobs`, once per user and
+//! subject, after checking the user's `NotificationPreferences`; a scheduled
+//! job then renders and delivers them through the configured
+//! `EmailSender`, retrying failures with a growing delay.
+
+mod error;
+mod metrics;
+pub mod preferences;
+pub mod queue;
+mod sender;
+mod sendgrid;
+mod ses;
+mod smtp;
+pub mod templates;
+
+use jd_utils::config::EmailConfig;
+use std::{sync::Arc, time::Duration};
+use tracing::warn;
+
+pub use error::{Error, Result};
+pub use preferences::{NotificationPreferences, PreferenceStore};
+pub use queue::{EmailJob, EmailQueue};
+pub use sender::{Email, EmailSender, LogSender};
+pub use sendgrid::SendgridSender;
+pub use ses::SesSender;
+pub use smtp::SmtpSender;
+pub use templates::{EmailTemplate, RenderedEmail};
+
+use metrics::EMAILS;
+
+const DEFAULT_FROM_ADDRESS: &str = "notifications@localhost";
+const DEFAULT_APP_URL: &str = "http://localhost:3000";
+const DEFAULT_SMTP_PORT: u16 = 587;
+const DEFAULT_BATCH_SIZE: i64 = 50;
+const DEFAULT_MAX_ATTEMPTS: i32 = 8;
+/// How long a claimed email is hidden from other instances while it is sent.
+const LEASE: Duration = Duration::from_secs(60);
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Renders templates and sends them from the configured address.
+#[derive(Clone)]
+pub struct Mailer {
+  sender: Arc<dyn EmailSender>,
+  from_address: String,
+  from_name: Option<String>,
+  app_url: String,
+  batch_size: i64,
+  max_attempts: i32,
+}
+
+/// What a `Mailer::deliver_due` run did.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DeliveryRun {
+  pub sent: usize,
+  pub failed: usize,
+}
+
+impl Mailer {
+  pub fn new(sender: Arc<dyn EmailSender>, from_address: impl Into<String>) -> Self {
+    Self {
+      sender,
+      from_address: from_address.into(),
+      from_name: None,
+      app_url: DEFAULT_APP_URL.to_string(),
+      batch_size: DEFAULT_BATCH_SIZE,
+      max_attempts: DEFAULT_MAX_ATTEMPTS,
+    }
+  }
+
+  /// The provider `config.provider` names, or a `LogSender` without one.
+  pub fn from_config(config: Option<&EmailConfig>) -> Result<Self> {
+    let Some(config) = config else {
+      return Ok(Self::new(Arc::new(LogSender), DEFAULT_FROM_ADDRESS));
+    };
+    let required = |value: &Option<String>, key: &str| {
+      value.clone().ok_or_else(|| Error::Config(format!("email.{key} is not set")))
+    };
+    let sender: Arc<dyn EmailSender> = match config.provider.as_deref().unwrap_or("log") {
+      "log" => Arc::new(LogSender),
+      "smtp" => {
+        let credentials = config.smtp_username.clone().zip(config.smtp_password.clone());
+        Arc::new(SmtpSender::new(
+          &required(&config.smtp_host, "smtp_host")?,
+          config.smtp_port.unwrap_or(DEFAULT_SMTP_PORT),
+          credentials,
+        )?)
+      }
+      "sendgrid" => {
+        Arc::new(SendgridSender::new(required(&config.sendgrid_api_key, "sendgrid_api_key")?))
+      }
+      "ses" => Arc::new(SesSender::from_env(&required(&config.ses_region, "ses_region")?)?),
+      other => return Err(Error::Config(format!("unknown email provider '{other}'"))),
+    };
+
+    let mut mailer = Self::new(
+      sender,
+      config.from_address.clone().unwrap_or_else(|| DEFAULT_FROM_ADDRESS.to_string()),
+    );
+    mailer.from_name = config.from_name.clone();
+    if let Some(app_url) = &config.app_url {
+      mailer.app_url = app_url.clone();
+    }
+    mailer.batch_size = config.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+    mailer.max_attempts = config.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+    Ok(mailer)
+  }
+
+  pub fn provider(&self) -> &'static str {
+    self.sender.provider()
+  }
+
+  /// Render `template` and send it to `to` now. Prefer queueing it with
+  /// `EmailQueue`, which retries and respects the user's preferences.
+  pub async fn send(&self, to: &str, template: &EmailTemplate) -> Result<()> {
+    let rendered = template.render(&self.app_url);
+    let email = Email {
+      from_address: self.from_address.clone(),
+      from_name: self.from_name.clone(),
+      to: to.to_string(),
+      subject: rendered.subject,
+      text: rendered.text,
+      html: rendered.html,
+    };
+    self.sender.send(&email).await
+  }
+
+  /// Send a batch of queued emails that are due.
+  pub async fn deliver_due(&self, queue: &EmailQueue) -> Result<DeliveryRun> {
+    let mut run = DeliveryRun::default();
+    for job in queue.claim_due(self.batch_size, self.max_attempts, LEASE).await? {
+      let sent = match serde_json::from_value::<EmailTemplate>(job.payload.clone()) {
+        Ok(template) => self.send(&job.recipient, &template).await,
+        Err(e) => Err(e.into()),
+      };
+      match sent {
+        Ok(()) => {
+          queue.mark_sent(job.id).await?;
+          EMAILS.with_label_values(&[job.template.as_str(), self.provider(), "sent"]).inc();
+          run.sent += 1;
+        }
+        Err(e) => {
+          warn!(
+            email_job_id = job.id,
+            template = %job.template,
+            attempt = job.attempts + 1,
+            "Email delivery failed: {e}"
+          );
+          queue.mark_failed(job.id, &e.to_string(), retry_delay(job.attempts)).await?;
+          EMAILS.with_label_values(&[job.template.as_str(), self.provider(), "failed"]).inc();
+          run.failed += 1;
+        }
+      }
+    }
+    Ok(run)
+  }
+}
+
+/// Delay before retrying an email that has failed `attempts` times before:
+/// doubling from 30 seconds up to six hours.
+fn retry_delay(attempts: i32) -> Duration {
+  FIRST_RETRY_DELAY.saturating_mul(1 << attempts.clamp(0, 16)).min(MAX_RETRY_DELAY)
+}
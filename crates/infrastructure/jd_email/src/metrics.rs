@@ -0,0 +1,11 @@
+use jd_utils::metrics::{self, IntCounterVec};
+use std::sync::LazyLock;
+
+/// Delivery attempts by template, provider and outcome: `sent` or `failed`.
+pub static EMAILS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+  metrics::counter(
+    "emails_total",
+    "Transactional email deliveries",
+    &["template", "provider", "outcome"],
+  )
+});
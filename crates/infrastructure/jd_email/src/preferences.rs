@@ -0,0 +1,86 @@
+//! Which transactional emails each user receives.
+
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::Result;
+
+/// One switch per `EmailTemplate`. Analysis results are off by default as
+/// scheduled reanalyses would otherwise email every member on each run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct NotificationPreferences {
+  pub analysis_complete: bool,
+  pub critical_vulnerability: bool,
+  pub patch_approved: bool,
+  pub weekly_digest: bool,
+}
+
+impl Default for NotificationPreferences {
+  fn default() -> Self {
+    Self {
+      analysis_complete: false,
+      critical_vulnerability: true,
+      patch_approved: true,
+      weekly_digest: true,
+    }
+  }
+}
+
+impl NotificationPreferences {
+  /// Whether emails from the template named `template` are wanted.
+  pub fn allows(&self, template: &str) -> bool {
+    match template {
+      "analysis_complete" => self.analysis_complete,
+      "critical_vulnerability" => self.critical_vulnerability,
+      "patch_approved" => self.patch_approved,
+      "weekly_digest" => self.weekly_digest,
+      _ => false,
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct PreferenceStore {
+  db: Pool<Postgres>,
+}
+
+impl PreferenceStore {
+  pub fn new(db: Pool<Postgres>) -> Self {
+    Self { db }
+  }
+
+  /// The user's preferences, or the defaults if they have not set any.
+  pub async fn get(&self, user_id: Uuid) -> Result<NotificationPreferences> {
+    let preferences = sqlx::query_as::<_, NotificationPreferences>(
+      "SELECT analysis_complete, critical_vulnerability, patch_approved, weekly_digest \
+       FROM email_notification_preferences WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(&self.db)
+    .await?;
+    Ok(preferences.unwrap_or_default())
+  }
+
+  pub async fn save(&self, user_id: Uuid, preferences: &NotificationPreferences) -> Result<()> {
+    sqlx::query(
+      "INSERT INTO email_notification_preferences \
+         (user_id, analysis_complete, critical_vulnerability, patch_approved, weekly_digest) \
+       VALUES ($1, $2, $3, $4, $5) \
+       ON CONFLICT (user_id) DO UPDATE SET \
+         analysis_complete = EXCLUDED.analysis_complete, \
+         critical_vulnerability = EXCLUDED.critical_vulnerability, \
+         patch_approved = EXCLUDED.patch_approved, \
+         weekly_digest = EXCLUDED.weekly_digest, \
+         updated_at = NOW()",
+    )
+    .bind(user_id)
+    .bind(preferences.analysis_complete)
+    .bind(preferences.critical_vulnerability)
+    .bind(preferences.patch_approved)
+    .bind(preferences.weekly_digest)
+    .execute(&self.db)
+    .await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,215 @@
+//! Writes to and reads from `email_jobs`.
+
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{EmailTemplate, NotificationPreferences, Result};
+
+/// A queued email as the delivery job sends it.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct EmailJob {
+  pub id: i64,
+  pub user_id: Uuid,
+  pub recipient: String,
+  pub template: String,
+  pub payload: serde_json::Value,
+  /// Earlier failed attempts to send it.
+  pub attempts: i32,
+}
+
+/// One user's activity over a digest period.
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct DigestStats {
+  user_id: Uuid,
+  repositories: i64,
+  new_vulnerabilities: i64,
+  critical_vulnerabilities: i64,
+  patches_approved: i64,
+  analyses_completed: i64,
+}
+
+#[derive(Clone)]
+pub struct EmailQueue {
+  db: Pool<Postgres>,
+}
+
+impl EmailQueue {
+  pub fn new(db: Pool<Postgres>) -> Self {
+    Self { db }
+  }
+
+  /// Queue `template` for a user, unless they have no email address, have
+  /// turned the template off or were already sent it. Returns whether it
+  /// was queued.
+  pub async fn enqueue_for_user(&self, user_id: Uuid, template: &EmailTemplate) -> Result<bool> {
+    let queued = sqlx::query(&format!(
+      "INSERT INTO email_jobs (user_id, recipient, template, payload, dedup_key) \
+       SELECT u.id, u.email, $2, $3, $4 \
+       FROM users u \
+       LEFT JOIN email_notification_preferences p ON p.user_id = u.id \
+       WHERE u.id = $1 AND {} \
+       ON CONFLICT (user_id, dedup_key) DO NOTHING",
+      recipient_filter(template)
+    ))
+    .bind(user_id)
+    .bind(template.name())
+    .bind(serde_json::to_value(template)?)
+    .bind(template.dedup_key())
+    .execute(&self.db)
+    .await?;
+    Ok(queued.rows_affected() > 0)
+  }
+
+  /// Queue `template` for the members of every organization monitoring the
+  /// repository, on the same terms as `enqueue_for_user`. Returns how many
+  /// were queued.
+  pub async fn enqueue_for_repository(
+    &self,
+    repository_id: Uuid,
+    template: &EmailTemplate,
+  ) -> Result<u64> {
+    let queued = sqlx::query(&format!(
+      "INSERT INTO email_jobs (user_id, recipient, template, payload, dedup_key) \
+       SELECT DISTINCT u.id, u.email, $2, $3, $4 \
+       FROM organization_repositories r \
+       JOIN organization_members m ON m.organization_id = r.organization_id \
+       JOIN users u ON u.id = m.user_id \
+       LEFT JOIN email_notification_preferences p ON p.user_id = u.id \
+       WHERE r.github_repository_id = $1 AND {} \
+       ON CONFLICT (user_id, dedup_key) DO NOTHING",
+      recipient_filter(template)
+    ))
+    .bind(repository_id)
+    .bind(template.name())
+    .bind(serde_json::to_value(template)?)
+    .bind(template.dedup_key())
+    .execute(&self.db)
+    .await?;
+    Ok(queued.rows_affected())
+  }
+
+  /// Queue the digest for `week` for every organization member who wants
+  /// one and had activity on their repositories since `since`. Returns how
+  /// many were queued; rerunning within the week queues none.
+  pub async fn enqueue_weekly_digests(&self, week: &str, since: DateTime<Utc>) -> Result<u64> {
+    let stats = sqlx::query_as::<_, DigestStats>(
+      "WITH watched AS ( \
+         SELECT m.user_id, ARRAY_AGG(DISTINCT r.github_repository_id) AS repository_ids \
+         FROM organization_members m \
+         JOIN organization_repositories r ON r.organization_id = m.organization_id \
+         WHERE r.github_repository_id IS NOT NULL \
+         GROUP BY m.user_id \
+       ) \
+       SELECT w.user_id, CARDINALITY(w.repository_ids)::BIGINT AS repositories, \
+         (SELECT COUNT(*) FROM security_vulnerabilities v \
+          WHERE v.repository_id = ANY(w.repository_ids) AND v.ctime >= $1 \
+            AND NOT v.is_false_positive) AS new_vulnerabilities, \
+         (SELECT COUNT(*) FROM security_vulnerabilities v \
+          WHERE v.repository_id = ANY(w.repository_ids) AND v.ctime >= $1 \
+            AND NOT v.is_false_positive AND v.severity = 'critical') AS critical_vulnerabilities, \
+         (SELECT COUNT(*) FROM patch_proposals pp \
+          WHERE pp.repository_id = ANY(w.repository_ids) AND pp.mtime >= $1 \
+            AND pp.status::TEXT IN ('approved', 'submitted', 'merged', 'applied')) \
+           AS patches_approved, \
+         (SELECT COUNT(*) FROM code_analysis_results a \
+          WHERE a.repository_id = ANY(w.repository_ids) AND a.ctime >= $1) AS analyses_completed \
+       FROM watched w \
+       JOIN users u ON u.id = w.user_id \
+       LEFT JOIN email_notification_preferences p ON p.user_id = w.user_id \
+       WHERE u.email IS NOT NULL AND u.merged_into IS NULL \
+         AND COALESCE(p.weekly_digest, TRUE)",
+    )
+    .bind(since)
+    .fetch_all(&self.db)
+    .await?;
+
+    let mut queued = 0;
+    for stats in stats {
+      if stats.new_vulnerabilities + stats.patches_approved + stats.analyses_completed == 0 {
+        continue;
+      }
+      let digest = EmailTemplate::WeeklyDigest {
+        week: week.to_string(),
+        repositories: stats.repositories,
+        new_vulnerabilities: stats.new_vulnerabilities,
+        critical_vulnerabilities: stats.critical_vulnerabilities,
+        patches_approved: stats.patches_approved,
+        analyses_completed: stats.analyses_completed,
+      };
+      if self.enqueue_for_user(stats.user_id, &digest).await? {
+        queued += 1;
+      }
+    }
+    Ok(queued)
+  }
+
+  /// Lease up to `limit` due emails, oldest first, that have failed fewer
+  /// than `max_attempts` times. Leased emails are hidden from other
+  /// instances for `lease`, after which an email whose sender died is
+  /// retried.
+  pub async fn claim_due(
+    &self,
+    limit: i64,
+    max_attempts: i32,
+    lease: Duration,
+  ) -> Result<Vec<EmailJob>> {
+    let mut jobs = sqlx::query_as::<_, EmailJob>(
+      "UPDATE email_jobs SET available_at = NOW() + make_interval(secs => $3) \
+       WHERE id IN ( \
+         SELECT id FROM email_jobs \
+         WHERE sent_at IS NULL AND available_at <= NOW() AND attempts < $2 \
+         ORDER BY id LIMIT $1 FOR UPDATE SKIP LOCKED \
+       ) RETURNING id, user_id, recipient, template, payload, attempts",
+    )
+    .bind(limit)
+    .bind(max_attempts)
+    .bind(lease.as_secs_f64())
+    .fetch_all(&self.db)
+    .await?;
+    jobs.sort_by_key(|job| job.id);
+    Ok(jobs)
+  }
+
+  pub async fn mark_sent(&self, id: i64) -> Result<()> {
+    sqlx::query("UPDATE email_jobs SET sent_at = NOW(), last_error = NULL WHERE id = $1")
+      .bind(id)
+      .execute(&self.db)
+      .await?;
+    Ok(())
+  }
+
+  /// Count a failed attempt and retry after `retry_in`.
+  pub async fn mark_failed(&self, id: i64, error: &str, retry_in: Duration) -> Result<()> {
+    sqlx::query(
+      "UPDATE email_jobs SET attempts = attempts + 1, last_error = $2, \
+       available_at = NOW() + make_interval(secs => $3) WHERE id = $1",
+    )
+    .bind(id)
+    .bind(error)
+    .bind(retry_in.as_secs_f64())
+    .execute(&self.db)
+    .await?;
+    Ok(())
+  }
+
+  /// Delete emails queued more than `age` ago, whether or not they were
+  /// sent. Returns how many.
+  pub async fn purge(&self, age: Duration) -> Result<u64> {
+    let purged =
+      sqlx::query("DELETE FROM email_jobs WHERE created_at < NOW() - make_interval(secs => $1)")
+        .bind(age.as_secs_f64())
+        .execute(&self.db)
+        .await?;
+    Ok(purged.rows_affected())
+  }
+}
+
+/// Conditions on `u`, the user, and `p`, their preferences, for being sent
+/// `template`.
+fn recipient_filter(template: &EmailTemplate) -> String {
+  let name = template.name();
+  let default = NotificationPreferences::default().allows(name);
+  format!("u.email IS NOT NULL AND u.merged_into IS NULL AND COALESCE(p.{name}, {default})")
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::Result;
+
+/// An addressed email.
+#[derive(Debug, Clone)]
+pub struct Email {
+  pub from_address: String,
+  pub from_name: Option<String>,
+  pub to: String,
+  pub subject: String,
+  pub text: String,
+  pub html: String,
+}
+
+/// Hands emails to a mail provider.
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+  /// e.g. `smtp`, for logs and metrics.
+  fn provider(&self) -> &'static str;
+
+  async fn send(&self, email: &Email) -> Result<()>;
+}
+
+/// Logs emails instead of sending them, for development.
+pub struct LogSender;
+
+#[async_trait]
+impl EmailSender for LogSender {
+  fn provider(&self) -> &'static str {
+    "log"
+  }
+
+  async fn send(&self, email: &Email) -> Result<()> {
+    info!(
+      to = %email.to,
+      subject = %email.subject,
+      "Email not sent as no provider is configured:\n{}",
+      email.text
+    );
+    Ok(())
+  }
+}
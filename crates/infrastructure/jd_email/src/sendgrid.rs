@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use jd_utils::correlation::Correlate;
+use serde_json::json;
+use std::time::Duration;
+
+use crate::{Email, EmailSender, Error, Result};
+
+const ENDPOINT: &str = "https://api.sendgrid.com/v3/mail/send";
+
+/// Sends through the SendGrid v3 Mail Send API.
+pub struct SendgridSender {
+  api_key: String,
+  http: reqwest::Client,
+}
+
+impl SendgridSender {
+  pub fn new(api_key: String) -> Self {
+    let http =
+      reqwest::Client::builder().timeout(Duration::from_secs(15)).build().unwrap_or_default();
+    Self { api_key, http }
+  }
+}
+
+#[async_trait]
+impl EmailSender for SendgridSender {
+  fn provider(&self) -> &'static str {
+    "sendgrid"
+  }
+
+  async fn send(&self, email: &Email) -> Result<()> {
+    let body = json!({
+      "personalizations": [{ "to": [{ "email": email.to }] }],
+      "from": { "email": email.from_address, "name": email.from_name },
+      "subject": email.subject,
+      "content": [
+        { "type": "text/plain", "value": email.text },
+        { "type": "text/html", "value": email.html },
+      ],
+    });
+    let response = self
+      .http
+      .post(ENDPOINT)
+      .bearer_auth(&self.api_key)
+      .json(&body)
+      .correlated()
+      .send()
+      .await
+      .map_err(|e| Error::Send { provider: "sendgrid", reason: e.to_string() })?;
+    if !response.status().is_success() {
+      let status = response.status();
+      let detail = response.text().await.unwrap_or_default();
+      return Err(Error::Send { provider: "sendgrid", reason: format!("{status}: {detail}") });
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use jd_utils::correlation::Correlate;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{env, time::Duration};
+
+use crate::{Email, EmailSender, Error, Result};
+
+const SERVICE: &str = "ses";
+const PATH: &str = "/v2/email/outbound-emails";
+const CONTENT_TYPE: &str = "application/json";
+
+/// Sends through the Amazon SES v2 `SendEmail` API, signed with AWS SigV4.
+pub struct SesSender {
+  endpoint: String,
+  region: String,
+  access_key_id: String,
+  secret_access_key: String,
+  session_token: Option<String>,
+  http: reqwest::Client,
+}
+
+impl SesSender {
+  /// For `region`, with credentials from `AWS_ACCESS_KEY_ID`,
+  /// `AWS_SECRET_ACCESS_KEY` and optionally `AWS_SESSION_TOKEN`.
+  /// `AWS_SES_ENDPOINT` overrides the regional endpoint, e.g. for LocalStack.
+  pub fn from_env(region: &str) -> Result<Self> {
+    let env = |name: &str| env::var(name).ok().filter(|value| !value.trim().is_empty());
+    let credential = |name: &str| {
+      env(name).ok_or_else(|| Error::Config(format!("{name} is required to send through SES")))
+    };
+    let endpoint =
+      env("AWS_SES_ENDPOINT").unwrap_or_else(|| format!("https://email.{region}.amazonaws.com"));
+    let http =
+      reqwest::Client::builder().timeout(Duration::from_secs(15)).build().unwrap_or_default();
+    Ok(Self {
+      endpoint: endpoint.trim_end_matches('/').to_string(),
+      region: region.to_string(),
+      access_key_id: credential("AWS_ACCESS_KEY_ID")?,
+      secret_access_key: credential("AWS_SECRET_ACCESS_KEY")?,
+      session_token: env("AWS_SESSION_TOKEN"),
+      http,
+    })
+  }
+
+  fn host(&self) -> &str {
+    let without_scheme =
+      self.endpoint.split_once("://").map_or(self.endpoint.as_str(), |(_, rest)| rest);
+    without_scheme.split('/').next().unwrap_or(without_scheme)
+  }
+
+  fn scope(&self, amz_date: &str) -> String {
+    format!("{}/{}/{SERVICE}/aws4_request", &amz_date[..8], self.region)
+  }
+
+  /// The `Authorization` header of a `POST` to `PATH` with these `x-amz-*`
+  /// headers.
+  fn authorization(&self, amz_headers: &[(&str, &str)], amz_date: &str, body: &str) -> String {
+    let mut headers = vec![("content-type", CONTENT_TYPE), ("host", self.host())];
+    headers.extend_from_slice(amz_headers);
+    headers.sort_by_key(|(name, _)| *name);
+    let canonical_headers: String =
+      headers.iter().map(|(name, value)| format!("{name}:{value}\n")).collect();
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    let canonical_request = format!(
+      "POST\n{PATH}\n\n{canonical_headers}\n{signed_headers}\n{}",
+      hex::encode(Sha256::digest(body.as_bytes()))
+    );
+
+    let string_to_sign = format!(
+      "AWS4-HMAC-SHA256\n{amz_date}\n{}\n{}",
+      self.scope(amz_date),
+      hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let mut key = format!("AWS4{}", self.secret_access_key).into_bytes();
+    for part in [&amz_date[..8], self.region.as_str(), SERVICE, "aws4_request"] {
+      key = hmac_sha256(&key, part);
+    }
+    format!(
+      "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={signed_headers}, Signature={}",
+      self.access_key_id,
+      self.scope(amz_date),
+      hex::encode(hmac_sha256(&key, &string_to_sign))
+    )
+  }
+}
+
+#[async_trait]
+impl EmailSender for SesSender {
+  fn provider(&self) -> &'static str {
+    "ses"
+  }
+
+  async fn send(&self, email: &Email) -> Result<()> {
+    let from = match &email.from_name {
+      Some(name) => format!("{} <{}>", name.replace(['"', '<', '>'], ""), email.from_address),
+      None => email.from_address.clone(),
+    };
+    let body = json!({
+      "FromEmailAddress": from,
+      "Destination": { "ToAddresses": [email.to] },
+      "Content": {
+        "Simple": {
+          "Subject": { "Data": email.subject, "Charset": "UTF-8" },
+          "Body": {
+            "Text": { "Data": email.text, "Charset": "UTF-8" },
+            "Html": { "Data": email.html, "Charset": "UTF-8" },
+          },
+        },
+      },
+    })
+    .to_string();
+    let amz_date = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut amz_headers = vec![("x-amz-date", amz_date.as_str())];
+    if let Some(token) = &self.session_token {
+      amz_headers.push(("x-amz-security-token", token));
+    }
+
+    let mut request = self
+      .http
+      .post(format!("{}{PATH}", self.endpoint))
+      .header("authorization", self.authorization(&amz_headers, &amz_date, &body))
+      .header("content-type", CONTENT_TYPE);
+    for (name, value) in &amz_headers {
+      request = request.header(*name, *value);
+    }
+    let response = request
+      .body(body)
+      .correlated()
+      .send()
+      .await
+      .map_err(|e| Error::Send { provider: "ses", reason: e.to_string() })?;
+    if !response.status().is_success() {
+      let status = response.status();
+      let detail = response.text().await.unwrap_or_default();
+      return Err(Error::Send { provider: "ses", reason: format!("{status}: {detail}") });
+    }
+    Ok(())
+  }
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+  mac.update(data.as_bytes());
+  mac.finalize().into_bytes().to_vec()
+}
@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use lettre::{
+  AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+  message::{Mailbox, MultiPart},
+  transport::smtp::authentication::Credentials,
+};
+use std::time::Duration;
+
+use crate::{Email, EmailSender, Error, Result};
+
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Sends through an SMTP relay: with implicit TLS on port 465, STARTTLS on
+/// 587 and in plain text on other ports, for local mail catchers.
+pub struct SmtpSender {
+  transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpSender {
+  pub fn new(host: &str, port: u16, credentials: Option<(String, String)>) -> Result<Self> {
+    let config_error =
+      |e: lettre::transport::smtp::Error| Error::Config(format!("SMTP relay {host}: {e}"));
+    let mut builder = match port {
+      465 => AsyncSmtpTransport::<Tokio1Executor>::relay(host).map_err(config_error)?,
+      587 => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host).map_err(config_error)?,
+      _ => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+    }
+    .port(port)
+    .timeout(Some(TIMEOUT));
+    if let Some((username, password)) = credentials {
+      builder = builder.credentials(Credentials::new(username, password));
+    }
+    Ok(Self { transport: builder.build() })
+  }
+}
+
+#[async_trait]
+impl EmailSender for SmtpSender {
+  fn provider(&self) -> &'static str {
+    "smtp"
+  }
+
+  async fn send(&self, email: &Email) -> Result<()> {
+    let address = |address: &str| {
+      address.parse().map_err(|_| Error::InvalidAddress { address: address.to_string() })
+    };
+    let message = Message::builder()
+      .from(Mailbox::new(email.from_name.clone(), address(&email.from_address)?))
+      .to(Mailbox::new(None, address(&email.to)?))
+      .subject(&email.subject)
+      .multipart(MultiPart::alternative_plain_html(email.text.clone(), email.html.clone()))
+      .map_err(|e| Error::Send { provider: "smtp", reason: e.to_string() })?;
+    self
+      .transport
+      .send(message)
+      .await
+      .map_err(|e| Error::Send { provider: "smtp", reason: e.to_string() })?;
+    Ok(())
+  }
+}
@@ -0,0 +1,191 @@
+//! The transactional emails users can receive. Each template is also the
+//! preference that turns it off; see `NotificationPreferences`.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An email and the data it is rendered from, stored with its job as JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "template", rename_all = "snake_case")]
+pub enum EmailTemplate {
+  AnalysisComplete {
+    analysis_job_id: Uuid,
+    repository: String,
+    commit_sha: String,
+    issues_found: i32,
+    critical_issues: i32,
+  },
+  CriticalVulnerability {
+    vulnerability_id: Uuid,
+    repository: String,
+    vulnerability_type: String,
+    file_path: String,
+  },
+  PatchApproved {
+    patch_id: Uuid,
+    repository: String,
+    title: String,
+  },
+  WeeklyDigest {
+    /// ISO week, e.g. `2026-W42`.
+    week: String,
+    repositories: i64,
+    new_vulnerabilities: i64,
+    critical_vulnerabilities: i64,
+    patches_approved: i64,
+    analyses_completed: i64,
+  },
+}
+
+/// A rendered email, ready to address and send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedEmail {
+  pub subject: String,
+  pub text: String,
+  pub html: String,
+}
+
+impl EmailTemplate {
+  /// Name of the template, and of the preference column that controls it.
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::AnalysisComplete { .. } => "analysis_complete",
+      Self::CriticalVulnerability { .. } => "critical_vulnerability",
+      Self::PatchApproved { .. } => "patch_approved",
+      Self::WeeklyDigest { .. } => "weekly_digest",
+    }
+  }
+
+  /// Identifies what the email is about, so a user is sent it once.
+  pub fn dedup_key(&self) -> String {
+    let subject = match self {
+      Self::AnalysisComplete { analysis_job_id, .. } => analysis_job_id.to_string(),
+      Self::CriticalVulnerability { vulnerability_id, .. } => vulnerability_id.to_string(),
+      Self::PatchApproved { patch_id, .. } => patch_id.to_string(),
+      Self::WeeklyDigest { week, .. } => week.clone(),
+    };
+    format!("{}:{subject}", self.name())
+  }
+
+  /// Render with links into the web app at `app_url`.
+  pub fn render(&self, app_url: &str) -> RenderedEmail {
+    let app_url = app_url.trim_end_matches('/');
+    match self {
+      Self::AnalysisComplete { repository, commit_sha, issues_found, critical_issues, .. } => {
+        let commit = &commit_sha[..commit_sha.len().min(7)];
+        let summary = match (*issues_found, *critical_issues) {
+          (0, _) => "No issues were found.".to_string(),
+          (found, 0) => format!("{found} issue(s) were found, none of them critical."),
+          (found, critical) => format!("{found} issue(s) were found, {critical} of them critical."),
+        };
+        layout(
+          format!("Analysis of {repository} at {commit} is complete"),
+          vec![
+            format!("The security analysis of {repository} at commit {commit} has finished."),
+            summary,
+          ],
+          ("View the results", format!("{app_url}/repositories")),
+        )
+      }
+      Self::CriticalVulnerability {
+        vulnerability_id,
+        repository,
+        vulnerability_type,
+        file_path,
+      } => {
+        layout(
+          format!("Critical vulnerability found in {repository}"),
+          vec![
+            format!(
+              "A critical {} was found in {file_path} of {repository}.",
+              vulnerability_type.replace('_', " ")
+            ),
+            "Review it and apply or request a patch as soon as you can.".to_string(),
+          ],
+          ("Review the vulnerability", format!("{app_url}/vulnerabilities/{vulnerability_id}")),
+        )
+      }
+      Self::PatchApproved { patch_id, repository, title } => layout(
+        format!("Patch approved for {repository}"),
+        vec![
+          format!("The patch \"{title}\" for {repository} has been approved by its reviewers."),
+          "It can now be submitted as a pull request.".to_string(),
+        ],
+        ("View the patch", format!("{app_url}/patches/{patch_id}")),
+      ),
+      Self::WeeklyDigest {
+        week,
+        repositories,
+        new_vulnerabilities,
+        critical_vulnerabilities,
+        patches_approved,
+        analyses_completed,
+      } => layout(
+        format!("Your security digest for {week}"),
+        vec![
+          format!("Across your {repositories} monitored repositories this week:"),
+          format!("{analyses_completed} analyses completed"),
+          format!("{new_vulnerabilities} new vulnerabilities, {critical_vulnerabilities} critical"),
+          format!("{patches_approved} patches approved"),
+        ],
+        ("Open your dashboard", format!("{app_url}/dashboard")),
+      ),
+    }
+  }
+}
+
+fn layout(
+  subject: String,
+  paragraphs: Vec<String>,
+  (action, url): (&str, String),
+) -> RenderedEmail {
+  let footer = "You are receiving this because of your notification preferences.";
+  let text = format!("{}\n\n{action}: {url}\n\n--\n{footer}\n", paragraphs.join("\n\n"));
+  let body: String =
+    paragraphs.iter().map(|paragraph| format!("<p>{}</p>\n", escape(paragraph))).collect();
+  let html = format!(
+    "<!DOCTYPE html>\n<html><body style=\"font-family: sans-serif; line-height: 1.5\">\n\
+     <h2>{}</h2>\n{body}<p><a href=\"{}\">{}</a></p>\n\
+     <p style=\"color: #888; font-size: 12px\">{footer}</p>\n</body></html>\n",
+    escape(&subject),
+    escape(&url),
+    escape(action),
+  );
+  RenderedEmail { subject, text, html }
+}
+
+fn escape(value: &str) -> String {
+  let mut escaped = String::with_capacity(value.len());
+  for c in value.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&#39;"),
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_escaped_html_and_dedups_by_subject() {
+    let template = EmailTemplate::PatchApproved {
+      patch_id: Uuid::nil(),
+      repository: "acme/vault".to_string(),
+      title: "Check <owner> before withdraw".to_string(),
+    };
+    let email = template.render("https://app.example.com/");
+
+    assert_eq!(email.subject, "Patch approved for acme/vault");
+    assert!(email.text.contains("\"Check <owner> before withdraw\""));
+    assert!(email.html.contains("&quot;Check &lt;owner&gt; before withdraw&quot;"));
+    assert!(email.html.contains(&format!("https://app.example.com/patches/{}", Uuid::nil())));
+    assert_eq!(template.dedup_key(), format!("patch_approved:{}", Uuid::nil()));
+  }
+}
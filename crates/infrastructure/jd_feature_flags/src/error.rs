@@ -0,0 +1,10 @@
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  #[error("Feature flag database error: {0}")]
+  Database(#[from] sqlx::Error),
+
+  #[error("Invalid feature flag: {0}")]
+  InvalidFlag(String),
+}
@@ -0,0 +1,180 @@
+//! Flag definitions and how they are evaluated.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{Error, Result};
+
+const MAX_KEY_LEN: usize = 64;
+
+/// A flag as stored in `feature_flags`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FeatureFlag {
+  /// e.g. `llm-v2-prompts`.
+  pub key: String,
+  pub description: Option<String>,
+  /// Off turns the flag off for everyone, whatever its other settings.
+  pub enabled: bool,
+  /// Environments the flag is on in; empty for all of them.
+  pub environments: Vec<String>,
+  /// Users the flag is on for: their user id, or the token subject of
+  /// callers without one.
+  pub user_ids: Vec<String>,
+  /// Organizations whose members the flag is on for.
+  pub organization_ids: Vec<Uuid>,
+  /// Share of other users, from 0 to 100, the flag is on for.
+  pub rollout_percentage: i16,
+  pub updated_by: Option<String>,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+/// The settings of a flag an administrator saves.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FlagSettings {
+  pub description: Option<String>,
+  pub enabled: bool,
+  pub environments: Vec<String>,
+  pub user_ids: Vec<String>,
+  pub organization_ids: Vec<Uuid>,
+  pub rollout_percentage: i16,
+}
+
+/// Who a flag is checked for. Anonymous callers only see flags rolled out
+/// to everyone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlagSubject {
+  pub user_id: Option<String>,
+  pub organization_ids: Vec<Uuid>,
+}
+
+impl FlagSubject {
+  pub fn anonymous() -> Self {
+    Self::default()
+  }
+
+  pub fn user(user_id: impl Into<String>) -> Self {
+    Self { user_id: Some(user_id.into()), organization_ids: Vec::new() }
+  }
+
+  pub fn with_organizations(mut self, organization_ids: Vec<Uuid>) -> Self {
+    self.organization_ids = organization_ids;
+    self
+  }
+}
+
+impl FeatureFlag {
+  /// Whether the flag is on for `subject` in `environment`. Listed users
+  /// and organizations come first; everyone else falls into a percentile
+  /// by user id, so a user stays in or out of a rollout as it grows.
+  pub fn evaluate(&self, subject: &FlagSubject, environment: &str) -> bool {
+    if !self.enabled {
+      return false;
+    }
+    if !self.environments.is_empty() && !self.environments.iter().any(|env| env == environment) {
+      return false;
+    }
+    if subject.user_id.as_ref().is_some_and(|user_id| self.user_ids.contains(user_id))
+      || subject.organization_ids.iter().any(|id| self.organization_ids.contains(id))
+    {
+      return true;
+    }
+    match &subject.user_id {
+      Some(user_id) => i16::from(percentile(&self.key, user_id)) < self.rollout_percentage,
+      None => self.rollout_percentage >= 100,
+    }
+  }
+}
+
+impl FlagSettings {
+  pub fn validate(&self) -> Result<()> {
+    if !(0..=100).contains(&self.rollout_percentage) {
+      return Err(Error::InvalidFlag("rollout_percentage must be between 0 and 100".to_string()));
+    }
+    if self.environments.iter().any(|env| env.trim().is_empty())
+      || self.user_ids.iter().any(|user_id| user_id.trim().is_empty())
+    {
+      return Err(Error::InvalidFlag("environments and user_ids cannot be blank".to_string()));
+    }
+    Ok(())
+  }
+}
+
+/// Keys are up to 64 lowercase letters, digits, `-`, `_` and `.`.
+pub fn validate_key(key: &str) -> Result<()> {
+  let valid = !key.is_empty()
+    && key.len() <= MAX_KEY_LEN
+    && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "-_.".contains(c));
+  if valid {
+    Ok(())
+  } else {
+    Err(Error::InvalidFlag(format!(
+      "'{key}' must be up to {MAX_KEY_LEN} lowercase letters, digits, '-', '_' or '.'"
+    )))
+  }
+}
+
+/// The user's percentile, 0 to 99, for `flag`. Hashing the flag in too
+/// puts different users first in each rollout.
+fn percentile(flag: &str, user_id: &str) -> u8 {
+  let digest = Sha256::digest(format!("{flag}:{user_id}").as_bytes());
+  let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+  (value % 100) as u8
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn flag(rollout_percentage: i16) -> FeatureFlag {
+    FeatureFlag {
+      key: "llm-v2-prompts".to_string(),
+      description: None,
+      enabled: true,
+      environments: Vec::new(),
+      user_ids: vec!["listed".to_string()],
+      organization_ids: Vec::new(),
+      rollout_percentage,
+      updated_by: None,
+      created_at: Utc::now(),
+      updated_at: Utc::now(),
+    }
+  }
+
+  #[test]
+  fn rollouts_grow_without_dropping_users() {
+    let users: Vec<String> = (0..1000).map(|i| format!("user-{i}")).collect();
+    let on_at = |percentage| {
+      let flag = flag(percentage);
+      users.iter().filter(|user| flag.evaluate(&FlagSubject::user(*user), "production")).count()
+    };
+    assert_eq!(on_at(0), 0);
+    assert!((150..250).contains(&on_at(20)));
+    assert_eq!(on_at(100), 1000);
+    let (small, large) = (flag(10), flag(50));
+    for user in &users {
+      let subject = FlagSubject::user(user);
+      assert!(!small.evaluate(&subject, "production") || large.evaluate(&subject, "production"));
+    }
+  }
+
+  #[test]
+  fn targets_and_environments() {
+    let mut flag = flag(0);
+    assert!(flag.evaluate(&FlagSubject::user("listed"), "staging"));
+    assert!(!flag.evaluate(&FlagSubject::anonymous(), "staging"));
+    let organization = Uuid::new_v4();
+    flag.organization_ids.push(organization);
+    let member = FlagSubject::user("member").with_organizations(vec![organization]);
+    assert!(flag.evaluate(&member, "staging"));
+    flag.environments = vec!["production".to_string()];
+    assert!(!flag.evaluate(&member, "staging"));
+    flag.enabled = false;
+    assert!(!flag.evaluate(&FlagSubject::user("listed"), "production"));
+    assert!(validate_key("gitlab-support").is_ok());
+    assert!(validate_key("GitLab Support").is_err());
+  }
+}
@@ -0,0 +1,187 @@
+//! Feature flags: definitions kept in Postgres and cached in Redis, turned
+//! on per environment, for listed users and organizations, and for a
+//! percentage of everyone else, so new behavior ships dark and rolls out
+//! without a deploy.
+
+mod error;
+pub mod flag;
+mod metrics;
+
+use jd_cache::Cache;
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+pub use error::{Error, Result};
+pub use flag::{FeatureFlag, FlagSettings, FlagSubject};
+
+use metrics::EVALUATIONS;
+
+/// Scope for creating, changing and deleting flags.
+pub const SCOPE_FEATURES_ADMIN: &str = "features:admin";
+
+const CACHE_KEY: &str = "feature_flags:definitions";
+/// How long instances may act on a definition after another changed it.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+const COLUMNS: &str = "key, description, enabled, environments, user_ids, organization_ids, \
+                       rollout_percentage, updated_by, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct FeatureFlags {
+  db: Pool<Postgres>,
+  cache: Cache,
+  environment: String,
+}
+
+impl FeatureFlags {
+  /// Flags as they apply to this process's environment.
+  pub fn new(db: Pool<Postgres>, cache: Cache) -> Self {
+    Self { db, cache, environment: jd_utils::config::environment_name() }
+  }
+
+  pub fn environment(&self) -> &str {
+    &self.environment
+  }
+
+  /// Whether `key` is on for `subject`. Unknown flags are off, as are all
+  /// flags while their definitions cannot be read.
+  pub async fn is_enabled(&self, key: &str, subject: &FlagSubject) -> bool {
+    let enabled = match self.definitions().await {
+      Ok(flags) => flags
+        .iter()
+        .find(|flag| flag.key == key)
+        .is_some_and(|flag| flag.evaluate(subject, &self.environment)),
+      Err(e) => {
+        warn!(flag = key, "Feature flags could not be read; treating the flag as off: {e}");
+        false
+      }
+    };
+    EVALUATIONS.with_label_values(&[key, if enabled { "on" } else { "off" }]).inc();
+    enabled
+  }
+
+  /// The keys of every flag on for `subject`.
+  pub async fn enabled_for(&self, subject: &FlagSubject) -> Result<Vec<String>> {
+    let flags = self.definitions().await?;
+    Ok(
+      flags
+        .into_iter()
+        .filter(|flag| flag.evaluate(subject, &self.environment))
+        .map(|flag| flag.key)
+        .collect(),
+    )
+  }
+
+  /// The organizations `user_id` belongs to, for targeting by organization.
+  pub async fn organizations_of(&self, user_id: Uuid) -> Result<Vec<Uuid>> {
+    let organizations = sqlx::query_scalar::<_, Uuid>(
+      "SELECT organization_id FROM organization_members WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(&self.db)
+    .await?;
+    Ok(organizations)
+  }
+
+  /// Cache the definitions, so the first evaluations do not all miss.
+  /// Returns how many flags there are.
+  pub async fn warm_up(&self) -> Result<usize> {
+    Ok(self.definitions().await?.len())
+  }
+
+  /// Every flag, straight from Postgres.
+  pub async fn list(&self) -> Result<Vec<FeatureFlag>> {
+    let flags = sqlx::query_as::<_, FeatureFlag>(&format!(
+      "SELECT {COLUMNS} FROM feature_flags ORDER BY key"
+    ))
+    .fetch_all(&self.db)
+    .await?;
+    Ok(flags)
+  }
+
+  pub async fn get(&self, key: &str) -> Result<Option<FeatureFlag>> {
+    let flag = sqlx::query_as::<_, FeatureFlag>(&format!(
+      "SELECT {COLUMNS} FROM feature_flags WHERE key = $1"
+    ))
+    .bind(key)
+    .fetch_optional(&self.db)
+    .await?;
+    Ok(flag)
+  }
+
+  /// Create or replace the flag `key`. Other instances see the change within
+  /// `CACHE_TTL`; this one straight away.
+  pub async fn save(
+    &self,
+    key: &str,
+    settings: &FlagSettings,
+    updated_by: &str,
+  ) -> Result<FeatureFlag> {
+    flag::validate_key(key)?;
+    settings.validate()?;
+    let flag = sqlx::query_as::<_, FeatureFlag>(&format!(
+      "INSERT INTO feature_flags \
+         (key, description, enabled, environments, user_ids, organization_ids, \
+          rollout_percentage, updated_by) \
+       VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+       ON CONFLICT (key) DO UPDATE SET \
+         description = EXCLUDED.description, \
+         enabled = EXCLUDED.enabled, \
+         environments = EXCLUDED.environments, \
+         user_ids = EXCLUDED.user_ids, \
+         organization_ids = EXCLUDED.organization_ids, \
+         rollout_percentage = EXCLUDED.rollout_percentage, \
+         updated_by = EXCLUDED.updated_by, \
+         updated_at = NOW() \
+       RETURNING {COLUMNS}"
+    ))
+    .bind(key)
+    .bind(&settings.description)
+    .bind(settings.enabled)
+    .bind(&settings.environments)
+    .bind(&settings.user_ids)
+    .bind(&settings.organization_ids)
+    .bind(settings.rollout_percentage)
+    .bind(updated_by)
+    .fetch_one(&self.db)
+    .await?;
+    self.invalidate().await;
+    Ok(flag)
+  }
+
+  /// True if the flag existed. Code checking it sees it as off.
+  pub async fn delete(&self, key: &str) -> Result<bool> {
+    let deleted = sqlx::query("DELETE FROM feature_flags WHERE key = $1")
+      .bind(key)
+      .execute(&self.db)
+      .await?;
+    self.invalidate().await;
+    Ok(deleted.rows_affected() > 0)
+  }
+
+  /// Every definition, from Redis when cached there. Postgres is read
+  /// directly while Redis is unavailable.
+  async fn definitions(&self) -> Result<Vec<FeatureFlag>> {
+    match self.cache.get::<Vec<FeatureFlag>>(CACHE_KEY).await {
+      Ok(Some(flags)) => return Ok(flags),
+      Ok(None) => {}
+      Err(e) => {
+        warn!("Feature flag cache unavailable: {e}");
+        return self.list().await;
+      }
+    }
+    let flags = self.list().await?;
+    if let Err(e) = self.cache.set(CACHE_KEY, &flags, Some(CACHE_TTL)).await {
+      warn!("Feature flags could not be cached: {e}");
+    }
+    Ok(flags)
+  }
+
+  async fn invalidate(&self) {
+    if let Err(e) = self.cache.delete(CACHE_KEY).await {
+      warn!("Feature flag cache could not be cleared: {e}");
+    }
+  }
+}
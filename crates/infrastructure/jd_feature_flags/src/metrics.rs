@@ -0,0 +1,7 @@
+use jd_utils::metrics::{self, IntCounterVec};
+use std::sync::LazyLock;
+
+/// Flag checks by flag and result: `on` or `off`.
+pub static EVALUATIONS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+  metrics::counter("feature_flag_evaluations_total", "Feature flag checks", &["flag", "result"])
+});
@@ -0,0 +1,161 @@
+use futures::future::BoxFuture;
+use std::{
+  collections::HashMap,
+  future::Future,
+  sync::{Arc, RwLock},
+};
+
+use crate::{Event, EventEnvelope};
+
+type Handler = Arc<dyn Fn(EventEnvelope) -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+struct Subscriber {
+  name: &'static str,
+  handler: Handler,
+}
+
+#[derive(Default)]
+struct Subscribers {
+  by_type: HashMap<&'static str, Vec<Subscriber>>,
+  all: Vec<Subscriber>,
+}
+
+/// In-process subscribers to domain events, called by the outbox relay.
+/// Cheap to clone; clones share subscribers.
+#[derive(Clone, Default)]
+pub struct EventBus {
+  subscribers: Arc<RwLock<Subscribers>>,
+}
+
+impl EventBus {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Call `handler` with every `E`. `name` identifies the subscriber in
+  /// logs and in the relay's record of where an event was delivered, so it
+  /// must be unique. An error fails the delivery to this subscriber, which
+  /// is then retried, so handlers may see an event again.
+  pub fn subscribe<E, F, Fut>(&self, name: &'static str, handler: F)
+  where
+    E: Event,
+    F: Fn(E) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+  {
+    let handler = Arc::new(handler);
+    self.add(
+      Some(E::TYPE),
+      name,
+      Arc::new(move |envelope: EventEnvelope| {
+        let handler = handler.clone();
+        Box::pin(async move {
+          match envelope.decode::<E>() {
+            Some(Ok(event)) => handler(event).await,
+            Some(Err(e)) => Err(format!("undecodable {} payload: {e}", E::TYPE)),
+            None => Ok(()),
+          }
+        })
+      }),
+    );
+  }
+
+  /// Call `handler` with every event, e.g. to forward them elsewhere.
+  pub fn subscribe_all<F, Fut>(&self, name: &'static str, handler: F)
+  where
+    F: Fn(EventEnvelope) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+  {
+    self.add(None, name, Arc::new(move |envelope| Box::pin(handler(envelope))));
+  }
+
+  /// Deliver `envelope` to its subscribers, one after another. Fails with
+  /// every failing subscriber's error once all have been called.
+  pub async fn dispatch(&self, envelope: &EventEnvelope) -> Result<(), String> {
+    let errors: Vec<String> = self
+      .dispatch_skipping(envelope, &[])
+      .await
+      .into_iter()
+      .filter_map(|(name, dispatched)| dispatched.err().map(|e| format!("{name}: {e}")))
+      .collect();
+    if errors.is_empty() { Ok(()) } else { Err(errors.join("; ")) }
+  }
+
+  /// Deliver `envelope` to its subscribers but those named in `skip`, one
+  /// after another. Returns the outcome for each subscriber called.
+  pub async fn dispatch_skipping(
+    &self,
+    envelope: &EventEnvelope,
+    skip: &[&str],
+  ) -> Vec<(&'static str, Result<(), String>)> {
+    let handlers: Vec<(&'static str, Handler)> = {
+      let subscribers = self.subscribers.read().unwrap_or_else(|e| e.into_inner());
+      let typed = subscribers.by_type.get(envelope.event_type.as_str());
+      typed
+        .into_iter()
+        .flatten()
+        .chain(&subscribers.all)
+        .filter(|subscriber| !skip.contains(&subscriber.name))
+        .map(|subscriber| (subscriber.name, subscriber.handler.clone()))
+        .collect()
+    };
+
+    let mut outcomes = Vec::with_capacity(handlers.len());
+    for (name, handler) in handlers {
+      outcomes.push((name, handler(envelope.clone()).await));
+    }
+    outcomes
+  }
+
+  fn add(&self, event_type: Option<&'static str>, name: &'static str, handler: Handler) {
+    let mut subscribers = self.subscribers.write().unwrap_or_else(|e| e.into_inner());
+    let subscriber = Subscriber { name, handler };
+    match event_type {
+      Some(event_type) => subscribers.by_type.entry(event_type).or_default().push(subscriber),
+      None => subscribers.all.push(subscriber),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::events::ProofVerified;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use uuid::Uuid;
+
+  #[tokio::test]
+  async fn typed_subscribers_only_see_their_events() {
+    let bus = EventBus::new();
+    let (typed, all) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let counter = typed.clone();
+    bus.subscribe("typed", move |_: ProofVerified| {
+      counter.fetch_add(1, Ordering::SeqCst);
+      async { Ok(()) }
+    });
+    let counter = all.clone();
+    bus.subscribe_all("all", move |_| {
+      counter.fetch_add(1, Ordering::SeqCst);
+      async { Err("unavailable".to_string()) }
+    });
+
+    let event = ProofVerified { proof_id: Uuid::new_v4(), verifier_version: "v1".to_string() };
+    let mut envelope = EventEnvelope {
+      id: 1,
+      event_id: Uuid::new_v4(),
+      event_type: "proof.verified".to_string(),
+      aggregate_id: event.proof_id.to_string(),
+      payload: serde_json::to_value(&event).unwrap(),
+      occurred_at: chrono::Utc::now(),
+      attempts: 0,
+      delivered_to: Vec::new(),
+    };
+    assert_eq!(bus.dispatch(&envelope).await, Err("all: unavailable".to_string()));
+    let retried = bus.dispatch_skipping(&envelope, &["typed"]).await;
+    assert_eq!(retried, vec![("all", Err("unavailable".to_string()))]);
+    envelope.event_type = "patch.merged".to_string();
+    let _ = bus.dispatch(&envelope).await;
+
+    assert_eq!(typed.load(Ordering::SeqCst), 1);
+    assert_eq!(all.load(Ordering::SeqCst), 3);
+  }
+}
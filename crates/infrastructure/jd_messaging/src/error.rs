@@ -0,0 +1,16 @@
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  #[error("Outbox database error: {0}")]
+  Database(#[from] sqlx::Error),
+
+  #[error("Event payload could not be (de)serialized: {0}")]
+  Payload(#[from] serde_json::Error),
+
+  #[error("Cannot connect to {sink}: {reason}")]
+  Connect { sink: &'static str, reason: String },
+
+  #[error("Publishing to {sink} failed: {reason}")]
+  Publish { sink: &'static str, reason: String },
+}
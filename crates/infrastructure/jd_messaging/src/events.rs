@@ -0,0 +1,186 @@
+//! The events services publish. Their `TYPE`s name them in `outbox_events`,
+//! NATS subjects and the real-time channel, so they must not change.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use uuid::Uuid;
+
+/// A domain event, as stored in the outbox as JSON.
+pub trait Event: Serialize + DeserializeOwned + Send + Sync + 'static {
+  /// e.g. `vulnerability.created`.
+  const TYPE: &'static str;
+
+  /// Id of the record the event is about; Kafka partitions by it.
+  fn aggregate_id(&self) -> String;
+}
+
+/// A recorded event as the relay delivers it.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct EventEnvelope {
+  #[serde(skip)]
+  pub id: i64,
+  pub event_id: Uuid,
+  pub event_type: String,
+  pub aggregate_id: String,
+  pub payload: serde_json::Value,
+  pub occurred_at: DateTime<Utc>,
+  /// Earlier failed attempts to publish the event.
+  #[serde(skip)]
+  pub attempts: i32,
+  /// Sinks earlier attempts delivered the event to.
+  #[serde(skip)]
+  pub delivered_to: Vec<String>,
+}
+
+impl EventEnvelope {
+  /// The payload as `E`, or None if the event is of another type.
+  pub fn decode<E: Event>(&self) -> Option<serde_json::Result<E>> {
+    (self.event_type == E::TYPE).then(|| serde_json::from_value(self.payload.clone()))
+  }
+}
+
+/// An analysis found a vulnerability not seen in the repository before.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VulnerabilityCreated {
+  pub vulnerability_id: Uuid,
+  pub repository_id: Uuid,
+  pub analysis_id: Uuid,
+  pub vulnerability_type: String,
+  pub severity: String,
+  pub file_path: String,
+}
+
+impl Event for VulnerabilityCreated {
+  const TYPE: &'static str = "vulnerability.created";
+
+  fn aggregate_id(&self) -> String {
+    self.vulnerability_id.to_string()
+  }
+}
+
+/// A queued repository analysis finished.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisCompleted {
+  pub analysis_job_id: Uuid,
+  /// GitHub's id of the repository, as analysis jobs hold it.
+  pub github_repo_id: i64,
+  pub commit_sha: String,
+}
+
+impl Event for AnalysisCompleted {
+  const TYPE: &'static str = "analysis.completed";
+
+  fn aggregate_id(&self) -> String {
+    self.analysis_job_id.to_string()
+  }
+}
+
+/// A queued repository analysis used its last attempt without finishing
+/// and was dead-lettered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisFailed {
+  pub analysis_job_id: Uuid,
+  /// GitHub's id of the repository, as analysis jobs hold it.
+  pub github_repo_id: i64,
+  pub commit_sha: String,
+  pub attempts: i32,
+  /// The last attempt's error.
+  pub error: String,
+}
+
+impl Event for AnalysisFailed {
+  const TYPE: &'static str = "analysis.failed";
+
+  fn aggregate_id(&self) -> String {
+    self.analysis_job_id.to_string()
+  }
+}
+
+/// A patch proposal passed review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchApproved {
+  pub patch_id: Uuid,
+  pub vulnerability_id: Uuid,
+  pub repository_id: Uuid,
+  pub title: String,
+}
+
+impl Event for PatchApproved {
+  const TYPE: &'static str = "patch.approved";
+
+  fn aggregate_id(&self) -> String {
+    self.patch_id.to_string()
+  }
+}
+
+/// Reviewers were newly assigned to a patch proposal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchReviewRequested {
+  pub patch_id: Uuid,
+  pub repository_id: Uuid,
+  pub title: String,
+  /// Token subjects of the reviewers assigned by this request only.
+  pub reviewers: Vec<String>,
+  pub assigned_by: String,
+}
+
+impl Event for PatchReviewRequested {
+  const TYPE: &'static str = "patch.review_requested";
+
+  fn aggregate_id(&self) -> String {
+    self.patch_id.to_string()
+  }
+}
+
+/// The pull request opened from a patch proposal was merged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchMerged {
+  pub patch_id: Uuid,
+  pub vulnerability_id: Uuid,
+  pub repository_id: Uuid,
+  pub pull_request_number: i32,
+}
+
+impl Event for PatchMerged {
+  const TYPE: &'static str = "patch.merged";
+
+  fn aggregate_id(&self) -> String {
+    self.patch_id.to_string()
+  }
+}
+
+/// A score was recorded in the score ledger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreUpdated {
+  /// The scored behavior input.
+  pub subject_id: Uuid,
+  pub scoring_result_id: Option<Uuid>,
+  pub score: f64,
+  pub previous_score: Option<f64>,
+  pub model_version: String,
+  /// Why the score changed, e.g. `score_calculated`.
+  pub reason: String,
+}
+
+impl Event for ScoreUpdated {
+  const TYPE: &'static str = "score.updated";
+
+  fn aggregate_id(&self) -> String {
+    self.subject_id.to_string()
+  }
+}
+
+/// A stored proof passed server-side verification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofVerified {
+  pub proof_id: Uuid,
+  pub verifier_version: String,
+}
+
+impl Event for ProofVerified {
+  const TYPE: &'static str = "proof.verified";
+
+  fn aggregate_id(&self) -> String {
+    self.proof_id.to_string()
+  }
+}
@@ -1,14 +1,22 @@
-pub fn add(left: u64, right: u64) -> u64 {
-  left + right
-}
+//! Domain events shared between services. A service records an event in
+//! `outbox_events` within the transaction that makes the change it
+//! describes; the `OutboxRelay` then hands it to subscribers on the
+//! in-process `EventBus` and to NATS or Kafka when configured. Delivery is
+//! at least once, so subscribers must tolerate an event arriving twice.
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+mod bus;
+mod error;
+pub mod events;
+mod metrics;
+pub mod outbox;
+mod publisher;
+mod relay;
 
-  #[test]
-  fn it_works() {
-    let result = add(2, 2);
-    assert_eq!(result, 4);
-  }
-}
+pub use bus::EventBus;
+pub use error::{Error, Result};
+pub use events::{Event, EventEnvelope};
+pub use publisher::{ExternalPublisher, KafkaRestPublisher, NatsPublisher};
+pub use relay::{OutboxRelay, RelaySettings};
+
+/// Redis channel events for real-time clients are published on.
+pub const REALTIME_CHANNEL: &str = "zkpersona:events";
@@ -0,0 +1,7 @@
+use jd_utils::metrics::{self, IntCounterVec};
+use std::sync::LazyLock;
+
+/// Relay attempts by event type and outcome: `published` or `failed`.
+pub static OUTBOX_EVENTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+  metrics::counter("outbox_events_total", "Outbox events relayed", &["event_type", "outcome"])
+});
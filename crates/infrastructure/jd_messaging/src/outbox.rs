@@ -0,0 +1,106 @@
+//! Writes to and reads from `outbox_events`.
+
+use sqlx::{PgExecutor, Pool, Postgres};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{Event, EventEnvelope, Result};
+
+const ENVELOPE_COLUMNS: &str =
+  "id, event_id, event_type, aggregate_id, payload, occurred_at, attempts, delivered_to";
+
+/// Record `event` for publication. Pass the transaction making the change
+/// the event describes, so the event is kept exactly when the change
+/// commits. Returns the event's id.
+pub async fn record<'e, E: Event>(executor: impl PgExecutor<'e>, event: &E) -> sqlx::Result<Uuid> {
+  let payload = serde_json::to_value(event).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+  sqlx::query_scalar(
+    "INSERT INTO outbox_events (event_type, aggregate_id, payload) VALUES ($1, $2, $3) \
+     RETURNING event_id",
+  )
+  .bind(E::TYPE)
+  .bind(event.aggregate_id())
+  .bind(payload)
+  .fetch_one(executor)
+  .await
+}
+
+/// The relay's view of the outbox.
+#[derive(Clone)]
+pub struct OutboxStore {
+  db: Pool<Postgres>,
+}
+
+impl OutboxStore {
+  pub fn new(db: Pool<Postgres>) -> Self {
+    Self { db }
+  }
+
+  /// Lease up to `limit` due events, oldest first, that have failed fewer
+  /// than `max_attempts` times. Leased events are hidden from other relays
+  /// for `lease`, after which an event whose relay died is retried.
+  pub async fn claim_due(
+    &self,
+    limit: i64,
+    max_attempts: i32,
+    lease: Duration,
+  ) -> Result<Vec<EventEnvelope>> {
+    let mut events = sqlx::query_as::<_, EventEnvelope>(&format!(
+      "UPDATE outbox_events SET available_at = NOW() + make_interval(secs => $3) \
+       WHERE id IN ( \
+         SELECT id FROM outbox_events \
+         WHERE published_at IS NULL AND available_at <= NOW() AND attempts < $2 \
+         ORDER BY id LIMIT $1 FOR UPDATE SKIP LOCKED \
+       ) RETURNING {ENVELOPE_COLUMNS}"
+    ))
+    .bind(limit)
+    .bind(max_attempts)
+    .bind(lease.as_secs_f64())
+    .fetch_all(&self.db)
+    .await?;
+    events.sort_by_key(|event| event.id);
+    Ok(events)
+  }
+
+  pub async fn mark_published(&self, id: i64) -> Result<()> {
+    sqlx::query("UPDATE outbox_events SET published_at = NOW(), last_error = NULL WHERE id = $1")
+      .bind(id)
+      .execute(&self.db)
+      .await?;
+    Ok(())
+  }
+
+  /// Count a failed attempt and retry after `retry_in`, skipping the sinks
+  /// in `delivered_to` then.
+  pub async fn mark_failed(
+    &self,
+    id: i64,
+    error: &str,
+    delivered_to: &[String],
+    retry_in: Duration,
+  ) -> Result<()> {
+    sqlx::query(
+      "UPDATE outbox_events SET attempts = attempts + 1, last_error = $2, delivered_to = $3, \
+       available_at = NOW() + make_interval(secs => $4) WHERE id = $1",
+    )
+    .bind(id)
+    .bind(error)
+    .bind(delivered_to)
+    .bind(retry_in.as_secs_f64())
+    .execute(&self.db)
+    .await?;
+    Ok(())
+  }
+
+  /// Delete events published more than `age` ago. Returns how many.
+  pub async fn purge_published(&self, age: Duration) -> Result<u64> {
+    let purged = sqlx::query(
+      "DELETE FROM outbox_events \
+       WHERE published_at < NOW() - make_interval(secs => $1)",
+    )
+    .bind(age.as_secs_f64())
+    .execute(&self.db)
+    .await?;
+    Ok(purged.rows_affected())
+  }
+}
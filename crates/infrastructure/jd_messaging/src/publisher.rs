@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use serde_json::json;
+use std::time::Duration;
+
+use crate::{Error, EventEnvelope, Result};
+
+/// A broker events are published to besides the in-process bus.
+#[async_trait]
+pub trait ExternalPublisher: Send + Sync {
+  fn name(&self) -> &'static str;
+
+  async fn publish(&self, event: &EventEnvelope) -> Result<()>;
+}
+
+/// Publishes to NATS subjects `{prefix}.{event_type}`. The event id goes in
+/// the `Nats-Msg-Id` header, which JetStream deduplicates redeliveries by.
+pub struct NatsPublisher {
+  client: async_nats::Client,
+  prefix: String,
+}
+
+impl NatsPublisher {
+  pub async fn connect(url: &str, prefix: impl Into<String>) -> Result<Self> {
+    let client = async_nats::connect(url)
+      .await
+      .map_err(|e| Error::Connect { sink: "nats", reason: e.to_string() })?;
+    Ok(Self { client, prefix: prefix.into() })
+  }
+}
+
+#[async_trait]
+impl ExternalPublisher for NatsPublisher {
+  fn name(&self) -> &'static str {
+    "nats"
+  }
+
+  async fn publish(&self, event: &EventEnvelope) -> Result<()> {
+    let failed = |e: &dyn std::fmt::Display| Error::Publish { sink: "nats", reason: e.to_string() };
+    let mut headers = async_nats::HeaderMap::new();
+    headers.insert("Nats-Msg-Id", event.event_id.to_string().as_str());
+    let subject = format!("{}.{}", self.prefix, event.event_type);
+    let payload = serde_json::to_vec(event)?;
+    self
+      .client
+      .publish_with_headers(subject, headers, payload.into())
+      .await
+      .map_err(|e| failed(&e))?;
+    // Publishing only buffers; an event counts as sent once flushed.
+    self.client.flush().await.map_err(|e| failed(&e))
+  }
+}
+
+/// Publishes to a Kafka topic through a Confluent REST Proxy (API v2),
+/// keyed by the event's aggregate id so one record's events stay in order.
+pub struct KafkaRestPublisher {
+  http: reqwest::Client,
+  url: String,
+}
+
+impl KafkaRestPublisher {
+  pub fn new(rest_url: &str, topic: &str) -> Result<Self> {
+    let http = reqwest::Client::builder()
+      .timeout(Duration::from_secs(10))
+      .build()
+      .map_err(|e| Error::Connect { sink: "kafka", reason: e.to_string() })?;
+    Ok(Self { http, url: format!("{}/topics/{}", rest_url.trim_end_matches('/'), topic) })
+  }
+}
+
+#[async_trait]
+impl ExternalPublisher for KafkaRestPublisher {
+  fn name(&self) -> &'static str {
+    "kafka"
+  }
+
+  async fn publish(&self, event: &EventEnvelope) -> Result<()> {
+    let failed = |reason: String| Error::Publish { sink: "kafka", reason };
+    let body = json!({ "records": [{ "key": event.aggregate_id, "value": event }] });
+    let response = self
+      .http
+      .post(&self.url)
+      .header("Content-Type", "application/vnd.kafka.json.v2+json")
+      .body(serde_json::to_vec(&body)?)
+      .send()
+      .await
+      .map_err(|e| failed(e.to_string()))?;
+    let status = response.status();
+    let reply: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+      return Err(failed(format!("HTTP {status}: {reply}")));
+    }
+    // The proxy answers 200 even when the broker rejected a record.
+    match reply["offsets"][0]["error"].as_str() {
+      Some(error) => Err(failed(error.to_string())),
+      None => Ok(()),
+    }
+  }
+}
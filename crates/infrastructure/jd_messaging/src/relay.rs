@@ -0,0 +1,168 @@
+use jd_utils::config::EventsConfig;
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use crate::{
+  EventBus, EventEnvelope, ExternalPublisher, KafkaRestPublisher, NatsPublisher, Result,
+  metrics::OUTBOX_EVENTS, outbox::OutboxStore,
+};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_BATCH_SIZE: i64 = 100;
+/// About a day of retries with the delays of `retry_delay`.
+const DEFAULT_MAX_ATTEMPTS: i32 = 30;
+const DEFAULT_NATS_SUBJECT_PREFIX: &str = "events";
+/// How long a relay has to publish the events it claimed.
+const LEASE: Duration = Duration::from_secs(60);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(3600);
+/// Prefix of the sinks in `delivered_to` that are bus subscribers.
+const BUS_SINK_PREFIX: &str = "bus:";
+
+#[derive(Debug, Clone)]
+pub struct RelaySettings {
+  pub poll_interval: Duration,
+  pub batch_size: i64,
+  pub max_attempts: i32,
+}
+
+impl RelaySettings {
+  pub fn from_config(config: Option<&EventsConfig>) -> Self {
+    Self {
+      poll_interval: config
+        .and_then(|c| c.poll_interval_ms)
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_POLL_INTERVAL),
+      batch_size: config.and_then(|c| c.batch_size).unwrap_or(DEFAULT_BATCH_SIZE).max(1),
+      max_attempts: config.and_then(|c| c.max_attempts).unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1),
+    }
+  }
+}
+
+/// Publishes recorded events to each bus subscriber, then to each external
+/// broker. An event that fails anywhere is retried after a growing delay, up
+/// to `max_attempts` times, but only to the subscribers and brokers it has
+/// not reached yet. Several servers may run relays side by side; each event
+/// is leased to one of them at a time.
+pub struct OutboxRelay {
+  store: OutboxStore,
+  bus: EventBus,
+  publishers: Vec<Box<dyn ExternalPublisher>>,
+  settings: RelaySettings,
+}
+
+impl OutboxRelay {
+  pub fn new(db: Pool<Postgres>, bus: EventBus, settings: RelaySettings) -> Self {
+    Self { store: OutboxStore::new(db), bus, publishers: Vec::new(), settings }
+  }
+
+  pub fn with_publisher(mut self, publisher: impl ExternalPublisher + 'static) -> Self {
+    self.publishers.push(Box::new(publisher));
+    self
+  }
+
+  /// A relay as `EVENTS.*` configures it, or None with
+  /// `EVENTS.RELAY_ENABLED=false`. Fails if a configured broker is
+  /// unreachable.
+  pub async fn from_config(
+    db: Pool<Postgres>,
+    bus: EventBus,
+    config: Option<&EventsConfig>,
+  ) -> Result<Option<Self>> {
+    if config.and_then(|c| c.relay_enabled) == Some(false) {
+      return Ok(None);
+    }
+    let mut relay = Self::new(db, bus, RelaySettings::from_config(config));
+    let Some(config) = config else {
+      return Ok(Some(relay));
+    };
+    if let Some(url) = &config.nats_url {
+      let prefix = config.nats_subject_prefix.as_deref().unwrap_or(DEFAULT_NATS_SUBJECT_PREFIX);
+      relay = relay.with_publisher(NatsPublisher::connect(url, prefix).await?);
+    }
+    if let (Some(url), Some(topic)) = (&config.kafka_rest_url, &config.kafka_topic) {
+      relay = relay.with_publisher(KafkaRestPublisher::new(url, topic)?);
+    }
+    Ok(Some(relay))
+  }
+
+  pub async fn run(self) {
+    let sinks: Vec<&str> = self.publishers.iter().map(|p| p.name()).collect();
+    info!(brokers = ?sinks, "Outbox relay started");
+    loop {
+      match self.publish_due().await {
+        // A full batch suggests a backlog; carry on without waiting.
+        Ok(claimed) if claimed as i64 == self.settings.batch_size => continue,
+        Ok(_) => {}
+        Err(e) => warn!(error = %e, "Outbox relay failed to read due events"),
+      }
+      sleep(self.settings.poll_interval).await;
+    }
+  }
+
+  /// Publish one batch of due events. Returns how many were claimed.
+  pub async fn publish_due(&self) -> Result<usize> {
+    let events = self
+      .store
+      .claim_due(self.settings.batch_size, self.settings.max_attempts, LEASE)
+      .await?;
+    for event in &events {
+      let (delivered_to, errors) = self.deliver(event).await;
+      if errors.is_empty() {
+        self.store.mark_published(event.id).await?;
+        OUTBOX_EVENTS.with_label_values(&[&event.event_type, "published"]).inc();
+      } else {
+        let error = errors.join("; ");
+        let attempts = event.attempts + 1;
+        let retrying = attempts < self.settings.max_attempts;
+        warn!(
+          event_id = %event.event_id,
+          event_type = %event.event_type,
+          attempts,
+          retrying,
+          error = %error,
+          "Publishing outbox event failed"
+        );
+        self.store.mark_failed(event.id, &error, &delivered_to, retry_delay(attempts)).await?;
+        OUTBOX_EVENTS.with_label_values(&[&event.event_type, "failed"]).inc();
+      }
+    }
+    Ok(events.len())
+  }
+
+  /// Deliver `event` to the sinks earlier attempts did not reach. Returns
+  /// every sink it has now reached, and an error for each it has not.
+  async fn deliver(&self, event: &EventEnvelope) -> (Vec<String>, Vec<String>) {
+    let mut delivered_to = event.delivered_to.clone();
+    let mut errors = Vec::new();
+
+    let skip: Vec<&str> =
+      event.delivered_to.iter().filter_map(|sink| sink.strip_prefix(BUS_SINK_PREFIX)).collect();
+    for (subscriber, dispatched) in self.bus.dispatch_skipping(event, &skip).await {
+      let sink = format!("{BUS_SINK_PREFIX}{subscriber}");
+      match dispatched {
+        Ok(()) => delivered_to.push(sink),
+        Err(e) => errors.push(format!("{sink}: {e}")),
+      }
+    }
+
+    for publisher in &self.publishers {
+      let sink = publisher.name();
+      if event.delivered_to.iter().any(|delivered| delivered == sink) {
+        continue;
+      }
+      match publisher.publish(event).await {
+        Ok(()) => delivered_to.push(sink.to_string()),
+        Err(e) => errors.push(format!("{sink}: {e}")),
+      }
+    }
+    (delivered_to, errors)
+  }
+}
+
+/// 2, 4, 8... seconds after the first, second, third failure, at most an hour.
+fn retry_delay(attempts: i32) -> Duration {
+  let secs = 2u64.saturating_pow(attempts.clamp(1, 32) as u32);
+  Duration::from_secs(secs).min(MAX_RETRY_DELAY)
+}
@@ -0,0 +1,13 @@
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  #[error("Notification database error: {0}")]
+  Database(#[from] sqlx::Error),
+
+  #[error("Notification payload could not be (de)serialized: {0}")]
+  Payload(#[from] serde_json::Error),
+
+  #[error("Unread count could not be published: {0}")]
+  Publish(#[from] redis::RedisError),
+}
@@ -0,0 +1,15 @@
+//! In-app notifications. Event subscribers create them for the users an
+//! event concerns, once per user and subject, after checking the user's
+//! `InAppPreferences`; users list them and mark them read through the API,
+//! and their unread counts are pushed to their real-time connections.
+
+mod error;
+mod metrics;
+pub mod preferences;
+pub mod store;
+pub mod templates;
+
+pub use error::{Error, Result};
+pub use preferences::{InAppPreferenceStore, InAppPreferences};
+pub use store::{Notification, NotificationStore};
+pub use templates::{NotificationTemplate, RenderedNotification};
@@ -0,0 +1,7 @@
+use jd_utils::metrics::{self, IntCounterVec};
+use std::sync::LazyLock;
+
+/// Notifications created, by template.
+pub static NOTIFICATIONS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+  metrics::counter("notifications_total", "In-app notifications created", &["template"])
+});
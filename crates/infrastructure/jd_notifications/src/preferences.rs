@@ -0,0 +1,86 @@
+//! Which in-app notifications each user receives.
+
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::Result;
+
+/// One switch per `NotificationTemplate`, all on by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct InAppPreferences {
+  pub analysis_complete: bool,
+  pub critical_vulnerability: bool,
+  pub patch_review_requested: bool,
+  pub score_changed: bool,
+}
+
+impl Default for InAppPreferences {
+  fn default() -> Self {
+    Self {
+      analysis_complete: true,
+      critical_vulnerability: true,
+      patch_review_requested: true,
+      score_changed: true,
+    }
+  }
+}
+
+impl InAppPreferences {
+  /// Whether notifications from the template named `template` are wanted.
+  pub fn allows(&self, template: &str) -> bool {
+    match template {
+      "analysis_complete" => self.analysis_complete,
+      "critical_vulnerability" => self.critical_vulnerability,
+      "patch_review_requested" => self.patch_review_requested,
+      "score_changed" => self.score_changed,
+      _ => false,
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct InAppPreferenceStore {
+  db: Pool<Postgres>,
+}
+
+impl InAppPreferenceStore {
+  pub fn new(db: Pool<Postgres>) -> Self {
+    Self { db }
+  }
+
+  /// The user's preferences, or the defaults if they have not set any.
+  pub async fn get(&self, user_id: Uuid) -> Result<InAppPreferences> {
+    let preferences = sqlx::query_as::<_, InAppPreferences>(
+      "SELECT analysis_complete, critical_vulnerability, patch_review_requested, score_changed \
+       FROM in_app_notification_preferences WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(&self.db)
+    .await?;
+    Ok(preferences.unwrap_or_default())
+  }
+
+  pub async fn save(&self, user_id: Uuid, preferences: &InAppPreferences) -> Result<()> {
+    sqlx::query(
+      "INSERT INTO in_app_notification_preferences \
+         (user_id, analysis_complete, critical_vulnerability, patch_review_requested, \
+          score_changed) \
+       VALUES ($1, $2, $3, $4, $5) \
+       ON CONFLICT (user_id) DO UPDATE SET \
+         analysis_complete = EXCLUDED.analysis_complete, \
+         critical_vulnerability = EXCLUDED.critical_vulnerability, \
+         patch_review_requested = EXCLUDED.patch_review_requested, \
+         score_changed = EXCLUDED.score_changed, \
+         updated_at = NOW()",
+    )
+    .bind(user_id)
+    .bind(preferences.analysis_complete)
+    .bind(preferences.critical_vulnerability)
+    .bind(preferences.patch_review_requested)
+    .bind(preferences.score_changed)
+    .execute(&self.db)
+    .await?;
+    Ok(())
+  }
+}
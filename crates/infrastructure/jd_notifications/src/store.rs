@@ -0,0 +1,221 @@
+//! Writes to and reads from `notifications`.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::metrics::NOTIFICATIONS;
+use crate::{InAppPreferences, NotificationTemplate, Result};
+
+/// A notification as the notification center lists it.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Notification {
+  pub id: i64,
+  pub template: String,
+  pub title: String,
+  pub body: String,
+  pub link: String,
+  /// The template's data, as `NotificationTemplate` serializes it.
+  pub data: serde_json::Value,
+  pub read_at: Option<DateTime<Utc>>,
+  pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct NotificationStore {
+  db: Pool<Postgres>,
+}
+
+impl NotificationStore {
+  pub fn new(db: Pool<Postgres>) -> Self {
+    Self { db }
+  }
+
+  /// Notify a user, unless they have turned the template off or already
+  /// got it. Returns the users notified.
+  pub async fn notify_user(
+    &self,
+    user_id: Uuid,
+    template: &NotificationTemplate,
+  ) -> Result<Vec<Uuid>> {
+    self.insert("SELECT $1::UUID", user_id, template).await
+  }
+
+  /// Notify the members of every organization monitoring the repository, on
+  /// the same terms as `notify_user`.
+  pub async fn notify_repository(
+    &self,
+    repository_id: Uuid,
+    template: &NotificationTemplate,
+  ) -> Result<Vec<Uuid>> {
+    self
+      .insert(
+        "SELECT DISTINCT m.user_id FROM organization_repositories r \
+         JOIN organization_members m ON m.organization_id = r.organization_id \
+         WHERE r.github_repository_id = $1",
+        repository_id,
+        template,
+      )
+      .await
+  }
+
+  /// Notify the users behind token subjects, such as patch reviewers, on
+  /// the same terms as `notify_user`. Subjects without a user are skipped.
+  pub async fn notify_subjects(
+    &self,
+    subjects: &[String],
+    template: &NotificationTemplate,
+  ) -> Result<Vec<Uuid>> {
+    self
+      .insert(
+        "SELECT user_id FROM user_wallets WHERE address = ANY($1) \
+         UNION SELECT user_id FROM github_identities WHERE github_id::TEXT = ANY($1)",
+        subjects.to_vec(),
+        template,
+      )
+      .await
+  }
+
+  /// Up to `limit` of the user's notifications, newest first, before the
+  /// notification with id `before` when given.
+  pub async fn list(
+    &self,
+    user_id: Uuid,
+    unread_only: bool,
+    before: Option<i64>,
+    limit: i64,
+  ) -> Result<Vec<Notification>> {
+    let notifications = sqlx::query_as::<_, Notification>(
+      "SELECT id, template, title, body, link, data, read_at, created_at \
+       FROM notifications \
+       WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL) AND ($3::BIGINT IS NULL OR id < $3) \
+       ORDER BY id DESC LIMIT $4",
+    )
+    .bind(user_id)
+    .bind(unread_only)
+    .bind(before)
+    .bind(limit)
+    .fetch_all(&self.db)
+    .await?;
+    Ok(notifications)
+  }
+
+  pub async fn unread_count(&self, user_id: Uuid) -> Result<i64> {
+    let count = sqlx::query_scalar::<_, i64>(
+      "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND read_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_one(&self.db)
+    .await?;
+    Ok(count)
+  }
+
+  /// Mark one of the user's notifications read. False if they have none
+  /// with that id.
+  pub async fn mark_read(&self, user_id: Uuid, id: i64) -> Result<bool> {
+    let marked = sqlx::query(
+      "UPDATE notifications SET read_at = COALESCE(read_at, NOW()) \
+       WHERE id = $1 AND user_id = $2",
+    )
+    .bind(id)
+    .bind(user_id)
+    .execute(&self.db)
+    .await?;
+    Ok(marked.rows_affected() > 0)
+  }
+
+  /// Returns how many were unread.
+  pub async fn mark_all_read(&self, user_id: Uuid) -> Result<u64> {
+    let marked = sqlx::query(
+      "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL",
+    )
+    .bind(user_id)
+    .execute(&self.db)
+    .await?;
+    Ok(marked.rows_affected())
+  }
+
+  /// Push each user's unread count to their real-time connections, as
+  /// `{"type": "notifications_unread", "user_id": ..., "data":
+  /// {"unread_count": 3}}` on the channel domain events are forwarded on.
+  pub async fn publish_unread(&self, redis: &redis::Client, user_ids: &[Uuid]) -> Result<()> {
+    if user_ids.is_empty() {
+      return Ok(());
+    }
+    let counts = sqlx::query_as::<_, (Uuid, i64)>(
+      "SELECT u.id, COUNT(n.id) FROM UNNEST($1::UUID[]) AS u(id) \
+       LEFT JOIN notifications n ON n.user_id = u.id AND n.read_at IS NULL \
+       GROUP BY u.id",
+    )
+    .bind(user_ids)
+    .fetch_all(&self.db)
+    .await?;
+    let mut conn = redis.get_multiplexed_async_connection().await?;
+    for (user_id, unread_count) in counts {
+      let message = json!({
+        "type": "notifications_unread",
+        "user_id": user_id,
+        "data": { "unread_count": unread_count },
+      });
+      redis::cmd("PUBLISH")
+        .arg(jd_messaging::REALTIME_CHANNEL)
+        .arg(message.to_string())
+        .query_async::<()>(&mut conn)
+        .await?;
+    }
+    Ok(())
+  }
+
+  /// Delete read notifications created more than `age` ago. Returns how
+  /// many.
+  pub async fn purge_read(&self, age: Duration) -> Result<u64> {
+    let purged = sqlx::query(
+      "DELETE FROM notifications \
+       WHERE read_at IS NOT NULL AND created_at < NOW() - make_interval(secs => $1)",
+    )
+    .bind(age.as_secs_f64())
+    .execute(&self.db)
+    .await?;
+    Ok(purged.rows_affected())
+  }
+
+  /// Notify the users `recipients` selects, given `$1`, who want the
+  /// template and have not been sent it.
+  async fn insert<T>(
+    &self,
+    recipients: &str,
+    recipients_arg: T,
+    template: &NotificationTemplate,
+  ) -> Result<Vec<Uuid>>
+  where
+    T: for<'q> sqlx::Encode<'q, Postgres> + sqlx::Type<Postgres> + Send + 'static,
+  {
+    let name = template.name();
+    let default = InAppPreferences::default().allows(name);
+    let rendered = template.render();
+    let notified = sqlx::query_scalar::<_, Uuid>(&format!(
+      "INSERT INTO notifications (user_id, template, title, body, link, data, dedup_key) \
+       SELECT u.id, $2, $3, $4, $5, $6, $7 \
+       FROM ({recipients}) AS r(user_id) \
+       JOIN users u ON u.id = r.user_id \
+       LEFT JOIN in_app_notification_preferences p ON p.user_id = u.id \
+       WHERE u.merged_into IS NULL AND COALESCE(p.{name}, {default}) \
+       ON CONFLICT (user_id, dedup_key) DO NOTHING \
+       RETURNING user_id"
+    ))
+    .bind(recipients_arg)
+    .bind(name)
+    .bind(&rendered.title)
+    .bind(&rendered.body)
+    .bind(&rendered.link)
+    .bind(serde_json::to_value(template)?)
+    .bind(template.dedup_key())
+    .fetch_all(&self.db)
+    .await?;
+    NOTIFICATIONS.with_label_values(&[name]).inc_by(notified.len() as u64);
+    Ok(notified)
+  }
+}
@@ -0,0 +1,138 @@
+//! The in-app notifications users can receive. Each template is also the
+//! preference that turns it off; see `InAppPreferences`.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A notification and the data it is rendered from, stored with it as JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "template", rename_all = "snake_case")]
+pub enum NotificationTemplate {
+  AnalysisComplete {
+    analysis_job_id: Uuid,
+    repository_id: Uuid,
+    repository: String,
+    commit_sha: String,
+    issues_found: i32,
+    critical_issues: i32,
+  },
+  CriticalVulnerability {
+    vulnerability_id: Uuid,
+    repository: String,
+    vulnerability_type: String,
+    file_path: String,
+  },
+  PatchReviewRequested {
+    patch_id: Uuid,
+    repository: String,
+    title: String,
+  },
+  ScoreChanged {
+    /// The scored behavior input.
+    subject_id: Uuid,
+    score: f64,
+    previous_score: Option<f64>,
+  },
+}
+
+/// What the notification center shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedNotification {
+  pub title: String,
+  pub body: String,
+  /// Path in the web app the notification opens.
+  pub link: String,
+}
+
+impl NotificationTemplate {
+  /// Name of the template, and of the preference column that controls it.
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::AnalysisComplete { .. } => "analysis_complete",
+      Self::CriticalVulnerability { .. } => "critical_vulnerability",
+      Self::PatchReviewRequested { .. } => "patch_review_requested",
+      Self::ScoreChanged { .. } => "score_changed",
+    }
+  }
+
+  /// Identifies what the notification is about, so a user gets it once.
+  pub fn dedup_key(&self) -> String {
+    let subject = match self {
+      Self::AnalysisComplete { analysis_job_id, .. } => analysis_job_id.to_string(),
+      Self::CriticalVulnerability { vulnerability_id, .. } => vulnerability_id.to_string(),
+      Self::PatchReviewRequested { patch_id, .. } => patch_id.to_string(),
+      Self::ScoreChanged { subject_id, score, .. } => format!("{subject_id}:{score}"),
+    };
+    format!("{}:{subject}", self.name())
+  }
+
+  pub fn render(&self) -> RenderedNotification {
+    match self {
+      Self::AnalysisComplete {
+        repository_id,
+        repository,
+        commit_sha,
+        issues_found,
+        critical_issues,
+        ..
+      } => {
+        let commit = &commit_sha[..commit_sha.len().min(7)];
+        let body = match (*issues_found, *critical_issues) {
+          (0, _) => "No issues were found.".to_string(),
+          (found, 0) => format!("{found} issue(s) found, none of them critical."),
+          (found, critical) => format!("{found} issue(s) found, {critical} of them critical."),
+        };
+        RenderedNotification {
+          title: format!("Analysis of {repository} at {commit} is complete"),
+          body,
+          link: format!("/repositories/{repository_id}"),
+        }
+      }
+      Self::CriticalVulnerability {
+        vulnerability_id,
+        repository,
+        vulnerability_type,
+        file_path,
+      } => RenderedNotification {
+        title: format!("Critical vulnerability found in {repository}"),
+        body: format!("A critical {} in {file_path}.", vulnerability_type.replace('_', " ")),
+        link: format!("/vulnerabilities/{vulnerability_id}"),
+      },
+      Self::PatchReviewRequested { patch_id, repository, title } => RenderedNotification {
+        title: format!("Your review is requested on a patch for {repository}"),
+        body: format!("\"{title}\" is waiting for your verdict."),
+        link: format!("/patches/{patch_id}"),
+      },
+      Self::ScoreChanged { score, previous_score, .. } => RenderedNotification {
+        title: "Your score changed".to_string(),
+        body: match previous_score {
+          Some(previous) => format!("Your score went from {previous:.1} to {score:.1}."),
+          None => format!("Your first score is {score:.1}."),
+        },
+        link: "/scores".to_string(),
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_and_dedups_per_subject() {
+    let template = NotificationTemplate::ScoreChanged {
+      subject_id: Uuid::nil(),
+      score: 72.5,
+      previous_score: Some(70.0),
+    };
+    assert_eq!(template.render().body, "Your score went from 70.0 to 72.5.");
+    assert_eq!(
+      template.dedup_key(),
+      "score_changed:00000000-0000-0000-0000-000000000000:72.5"
+    );
+    let json = serde_json::to_value(&template).unwrap();
+    assert_eq!(json["template"], "score_changed");
+    assert_eq!(serde_json::from_value::<NotificationTemplate>(json).unwrap(), template);
+  }
+}
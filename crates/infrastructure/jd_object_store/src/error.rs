@@ -0,0 +1,22 @@
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  #[error("Invalid object store configuration: {0}")]
+  Config(String),
+
+  #[error("Object {key} not found")]
+  NotFound { key: String },
+
+  #[error("'{key}' is not a valid object key")]
+  InvalidKey { key: String },
+
+  #[error("{uri} is not an object of this store")]
+  ForeignUri { uri: String },
+
+  #[error("Failed to {action} {key}: {reason}")]
+  Request { action: &'static str, key: String, reason: String },
+
+  #[error("Object store I/O error: {0}")]
+  Io(#[from] std::io::Error),
+}
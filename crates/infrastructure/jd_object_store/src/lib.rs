@@ -0,0 +1,129 @@
+//! Object storage for artifacts too large for Postgres: zk proof artifacts,
+//! archived analysis reports and prepared analytics exports. `ObjectStore`
+//! is implemented for S3-compatible buckets (AWS S3, GCS through its XML
+//! API, MinIO) and for a local directory, whose presigned URLs the API
+//! serves. Objects expire by key prefix through `lifecycle` rules.
+
+mod error;
+pub mod lifecycle;
+mod local;
+mod metrics;
+mod s3;
+mod sigv4;
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use jd_utils::config::Config;
+use serde::Serialize;
+use std::{sync::Arc, time::Duration};
+
+pub use error::{Error, Result};
+pub use lifecycle::{LifecycleRule, LifecycleRun};
+pub use local::{LocalDiskStore, OBJECTS_PATH};
+pub use s3::{S3Settings, S3Store};
+
+/// Object content, read or written a chunk at a time.
+pub type ByteStream = BoxStream<'static, Result<Bytes>>;
+
+/// Longest a presigned URL may live, the SigV4 limit.
+pub const MAX_PRESIGN_EXPIRY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// An object as listed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectInfo {
+  pub key: String,
+  pub size: u64,
+  pub last_modified: Option<DateTime<Utc>>,
+  pub content_type: Option<String>,
+}
+
+/// A bucket or directory of objects addressed by `/`-separated keys.
+#[async_trait]
+pub trait ObjectStore: Send + Sync {
+  /// e.g. `s3`, for logs and metrics.
+  fn backend(&self) -> &'static str;
+
+  /// The URI to record for the object at `key`, e.g. `s3://bucket/key`.
+  fn uri(&self, key: &str) -> String;
+
+  /// The key of the object at `uri`, which must be one of this store's.
+  fn key(&self, uri: &str) -> Result<String>;
+
+  async fn put(&self, key: &str, bytes: Bytes, content_type: &str) -> Result<()>;
+
+  /// Store what `stream` yields under `key` without holding all of it in
+  /// memory. Returns the bytes stored.
+  async fn put_stream(&self, key: &str, stream: ByteStream, content_type: &str) -> Result<u64>;
+
+  /// The object's content as it downloads. `Error::NotFound` if there is
+  /// none.
+  async fn get_stream(&self, key: &str) -> Result<ByteStream>;
+
+  async fn get(&self, key: &str) -> Result<Bytes> {
+    let mut stream = self.get_stream(key).await?;
+    let mut bytes = BytesMut::new();
+    while let Some(chunk) = stream.next().await {
+      bytes.extend_from_slice(&chunk?);
+    }
+    Ok(bytes.freeze())
+  }
+
+  /// The object at `key`, if there is one.
+  async fn head(&self, key: &str) -> Result<Option<ObjectInfo>>;
+
+  /// Delete the object at `key`, if there is one.
+  async fn delete(&self, key: &str) -> Result<()>;
+
+  /// Every object whose key starts with `prefix`.
+  async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>>;
+
+  /// A URL that downloads the object without credentials until
+  /// `expires_in`, at most `MAX_PRESIGN_EXPIRY`, has passed.
+  fn presigned_get_url(&self, key: &str, expires_in: Duration) -> Result<String>;
+
+  /// A URL that uploads the object with a `PUT` of its content, likewise.
+  fn presigned_put_url(&self, key: &str, expires_in: Duration) -> Result<String>;
+
+  /// Whether `signature` is a live signature this store issued for `method`
+  /// on `key` in a presigned URL the API serves. Only the local store
+  /// issues those; buckets serve their own.
+  fn verify_presigned(&self, _method: &str, _key: &str, _expires: i64, _signature: &str) -> bool {
+    false
+  }
+}
+
+/// The store `config.object_store` describes or, without that section, the
+/// S3-compatible bucket the legacy `OBJECT_STORE_*` variables describe.
+/// `None` if neither is set.
+pub fn from_config(config: &Config) -> Result<Option<Arc<dyn ObjectStore>>> {
+  let Some(settings) = &config.object_store else {
+    let legacy = S3Settings::from_legacy_env().map(|settings| S3Store::new("s3", settings));
+    return Ok(legacy.map(|store| Arc::new(store) as Arc<dyn ObjectStore>));
+  };
+  let store: Arc<dyn ObjectStore> = match settings.backend.as_deref().unwrap_or("s3") {
+    "local" => Arc::new(LocalDiskStore::from_config(config)),
+    backend @ ("s3" | "gcs" | "minio") => {
+      Arc::new(S3Store::new(backend, S3Settings::from_config(backend, settings)?))
+    }
+    other => return Err(Error::Config(format!("unknown object store backend '{other}'"))),
+  };
+  Ok(Some(store))
+}
+
+/// Reject keys that are empty, absolute or climb out of their prefix, so a
+/// key means the same object on every backend.
+pub(crate) fn validate_key(key: &str) -> Result<()> {
+  let valid = !key.is_empty()
+    && !key.starts_with('/')
+    && !key.contains('\\')
+    && key.split('/').all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+  if valid { Ok(()) } else { Err(Error::InvalidKey { key: key.to_string() }) }
+}
+
+/// `expires_in` as whole seconds within what presigned URLs allow.
+pub(crate) fn presign_secs(expires_in: Duration) -> u64 {
+  expires_in.as_secs().clamp(1, MAX_PRESIGN_EXPIRY.as_secs())
+}
+
@@ -0,0 +1,70 @@
+//! Expiry of objects by key prefix. Rules are enforced by a scheduled job
+//! rather than bucket policies, so they work alike on every backend.
+
+use chrono::Utc;
+use jd_utils::config::Config;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::{Error, ObjectStore, Result};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+/// Rules applied unless `object_store.lifecycle` replaces them: prepared
+/// analytics exports outlive their day-long download window by a day.
+const DEFAULT_RULES: &[(&str, u32)] = &[("analytics-exports/", 2)];
+
+/// Objects whose key starts with `prefix` are deleted once older than
+/// `expire_after`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LifecycleRule {
+  pub prefix: String,
+  pub expire_after: Duration,
+}
+
+impl LifecycleRule {
+  pub fn days(prefix: impl Into<String>, days: u32) -> Self {
+    Self { prefix: prefix.into(), expire_after: DAY * days }
+  }
+
+  /// The rules `object_store.lifecycle` sets, or the defaults.
+  pub fn from_config(config: &Config) -> Result<Vec<Self>> {
+    let configured = match &config.object_store {
+      Some(object_store) => object_store.lifecycle_rules().map_err(Error::Config)?,
+      None => None,
+    };
+    Ok(match configured {
+      Some(rules) => rules.into_iter().map(|(prefix, days)| Self::days(prefix, days)).collect(),
+      None => DEFAULT_RULES.iter().map(|(prefix, days)| Self::days(*prefix, *days)).collect(),
+    })
+  }
+}
+
+/// What an `apply` run did.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LifecycleRun {
+  pub deleted: usize,
+  pub failed: usize,
+}
+
+/// Delete the objects `rules` have expired. An empty prefix would expire
+/// everything, so such rules are ignored.
+pub async fn apply(store: &dyn ObjectStore, rules: &[LifecycleRule]) -> Result<LifecycleRun> {
+  let mut run = LifecycleRun::default();
+  for rule in rules.iter().filter(|rule| !rule.prefix.is_empty()) {
+    let Ok(expire_after) = chrono::Duration::from_std(rule.expire_after) else { continue };
+    let cutoff = Utc::now() - expire_after;
+    for object in store.list(&rule.prefix).await? {
+      if object.last_modified.is_none_or(|modified| modified >= cutoff) {
+        continue;
+      }
+      match store.delete(&object.key).await {
+        Ok(()) => run.deleted += 1,
+        Err(e) => {
+          warn!(key = %object.key, "Failed to delete expired object: {e}");
+          run.failed += 1;
+        }
+      }
+    }
+  }
+  Ok(run)
+}
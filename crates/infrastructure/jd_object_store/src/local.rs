@@ -0,0 +1,290 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use hkdf::Hkdf;
+use jd_utils::config::Config;
+use sha2::Sha256;
+use std::{
+  io::ErrorKind,
+  path::{Path, PathBuf},
+  time::Duration,
+};
+use tokio::{fs, io::AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+
+use crate::{
+  ByteStream, Error, ObjectInfo, ObjectStore, Result, metrics::record, presign_secs,
+  sigv4::hmac_sha256, validate_key,
+};
+
+const BACKEND: &str = "local";
+const DEFAULT_ROOT: &str = "data/objects";
+/// Where the API serves presigned requests for local objects.
+pub const OBJECTS_PATH: &str = "/api/v1/objects";
+/// Suffix of files being written, which are not objects yet.
+const PARTIAL_SUFFIX: &str = ".partial";
+/// HKDF info for the signing key derived from `auth_jwt_secret`, so links to
+/// objects are never signed with the key that signs access tokens.
+const SIGNING_KEY_INFO: &[u8] = b"jd_object_store/local/presign-v1";
+
+/// Objects as files under a directory, for development and single-node
+/// deployments. Presigned URLs point at the API, which checks their HMAC
+/// signature before serving the file.
+pub struct LocalDiskStore {
+  root: PathBuf,
+  public_url: String,
+  signing_key: Vec<u8>,
+}
+
+impl LocalDiskStore {
+  pub fn new(root: impl Into<PathBuf>, public_url: &str, signing_key: &[u8]) -> Self {
+    Self {
+      root: root.into(),
+      public_url: public_url.trim_end_matches('/').to_string(),
+      signing_key: signing_key.to_vec(),
+    }
+  }
+
+  /// From `object_store`, linking to this API on its configured port unless
+  /// `object_store.public_url` says otherwise. Without
+  /// `object_store.signing_key`, links are signed with a key derived from
+  /// `auth_jwt_secret`.
+  pub fn from_config(config: &Config) -> Self {
+    let settings = config.object_store.as_ref();
+    let root = settings.and_then(|s| s.root.clone()).unwrap_or_else(|| DEFAULT_ROOT.to_string());
+    let public_url = settings.and_then(|s| s.public_url.clone()).unwrap_or_else(|| {
+      let port = config.web.addr.rsplit(':').next().unwrap_or("8080");
+      format!("http://localhost:{port}")
+    });
+    let signing_key = match settings.and_then(|s| s.signing_key.as_deref()) {
+      Some(signing_key) => signing_key.as_bytes().to_vec(),
+      None => derive_signing_key(&config.auth_jwt_secret),
+    };
+    Self::new(root, &public_url, &signing_key)
+  }
+
+  fn path(&self, key: &str) -> Result<PathBuf> {
+    validate_key(key)?;
+    Ok(self.root.join(key))
+  }
+
+  fn signature(&self, method: &str, key: &str, expires: i64) -> String {
+    hex::encode(hmac_sha256(&self.signing_key, &format!("{method}\n{key}\n{expires}")))
+  }
+
+  fn presigned_url(&self, method: &str, key: &str, expires_in: Duration) -> Result<String> {
+    validate_key(key)?;
+    let expires = Utc::now().timestamp() + presign_secs(expires_in) as i64;
+    let signature = self.signature(method, key, expires);
+    Ok(format!("{}{OBJECTS_PATH}/{key}?expires={expires}&signature={signature}", self.public_url))
+  }
+
+  /// Write to a partial file renamed into place once complete, so readers
+  /// never see half an object.
+  async fn write(&self, key: &str, mut stream: ByteStream) -> Result<u64> {
+    let path = self.path(key)?;
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).await?;
+    }
+    let partial = partial_path(&path);
+    let mut file = fs::File::create(&partial).await?;
+    let mut size = 0;
+    let written: Result<()> = async {
+      while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await?;
+        size += chunk.len() as u64;
+      }
+      file.sync_all().await?;
+      Ok(())
+    }
+    .await;
+    if let Err(e) = written {
+      let _ = fs::remove_file(&partial).await;
+      return Err(e);
+    }
+    fs::rename(&partial, &path).await?;
+    Ok(size)
+  }
+
+  /// Every object under `dir`, with keys relative to the root.
+  async fn walk(&self, dir: &Path) -> Result<Vec<ObjectInfo>> {
+    let mut objects = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+      let mut entries = match fs::read_dir(&dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => continue,
+        Err(e) => return Err(e.into()),
+      };
+      while let Some(entry) = entries.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        let path = entry.path();
+        if metadata.is_dir() {
+          pending.push(path);
+          continue;
+        }
+        let Some(key) = self.key_of_path(&path) else { continue };
+        if !key.ends_with(PARTIAL_SUFFIX) {
+          objects.push(object_info(key, &metadata));
+        }
+      }
+    }
+    Ok(objects)
+  }
+
+  fn key_of_path(&self, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(&self.root).ok()?;
+    let segments: Option<Vec<&str>> =
+      relative.components().map(|component| component.as_os_str().to_str()).collect();
+    Some(segments?.join("/"))
+  }
+}
+
+#[async_trait]
+impl ObjectStore for LocalDiskStore {
+  fn backend(&self) -> &'static str {
+    BACKEND
+  }
+
+  fn uri(&self, key: &str) -> String {
+    format!("local://{key}")
+  }
+
+  fn key(&self, uri: &str) -> Result<String> {
+    uri
+      .strip_prefix("local://")
+      .filter(|key| validate_key(key).is_ok())
+      .map(str::to_string)
+      .ok_or_else(|| Error::ForeignUri { uri: uri.to_string() })
+  }
+
+  async fn put(&self, key: &str, bytes: Bytes, _content_type: &str) -> Result<()> {
+    let stream: ByteStream = Box::pin(futures::stream::iter([Ok(bytes)]));
+    record(BACKEND, "put", self.write(key, stream).await.map(|_| ()))
+  }
+
+  async fn put_stream(&self, key: &str, stream: ByteStream, _content_type: &str) -> Result<u64> {
+    record(BACKEND, "put", self.write(key, stream).await)
+  }
+
+  async fn get_stream(&self, key: &str) -> Result<ByteStream> {
+    let opened = match fs::File::open(self.path(key)?).await {
+      Ok(file) => Ok(file),
+      Err(e) if e.kind() == ErrorKind::NotFound => Err(Error::NotFound { key: key.to_string() }),
+      Err(e) => Err(e.into()),
+    };
+    let file = record(BACKEND, "get", opened)?;
+    Ok(Box::pin(ReaderStream::new(file).map_err(Error::from)))
+  }
+
+  async fn head(&self, key: &str) -> Result<Option<ObjectInfo>> {
+    let found = match fs::metadata(self.path(key)?).await {
+      Ok(metadata) if metadata.is_file() => Ok(Some(object_info(key.to_string(), &metadata))),
+      Ok(_) => Ok(None),
+      Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+      Err(e) => Err(e.into()),
+    };
+    record(BACKEND, "head", found)
+  }
+
+  async fn delete(&self, key: &str) -> Result<()> {
+    let deleted = match fs::remove_file(self.path(key)?).await {
+      Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+      _ => Ok(()),
+    };
+    record(BACKEND, "delete", deleted)
+  }
+
+  async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+    // Walk only the deepest directory the prefix names
+    let dir = match prefix.rsplit_once('/') {
+      Some((dir, _)) if validate_key(dir).is_ok() => self.root.join(dir),
+      _ => self.root.clone(),
+    };
+    let listed = self.walk(&dir).await.map(|objects| {
+      objects.into_iter().filter(|object| object.key.starts_with(prefix)).collect()
+    });
+    record(BACKEND, "list", listed)
+  }
+
+  fn presigned_get_url(&self, key: &str, expires_in: Duration) -> Result<String> {
+    self.presigned_url("GET", key, expires_in)
+  }
+
+  fn presigned_put_url(&self, key: &str, expires_in: Duration) -> Result<String> {
+    self.presigned_url("PUT", key, expires_in)
+  }
+
+  fn verify_presigned(&self, method: &str, key: &str, expires: i64, signature: &str) -> bool {
+    let expected = self.signature(method, key, expires);
+    expires >= Utc::now().timestamp()
+      && expected.len() == signature.len()
+      && expected.bytes().zip(signature.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+  }
+}
+
+fn derive_signing_key(secret: &str) -> Vec<u8> {
+  let mut key = vec![0; 32];
+  Hkdf::<Sha256>::new(None, secret.as_bytes())
+    .expand(SIGNING_KEY_INFO, &mut key)
+    .expect("32 bytes is a valid HKDF-SHA256 output length");
+  key
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+  let mut partial = path.as_os_str().to_owned();
+  partial.push(format!(".{}{PARTIAL_SUFFIX}", Utc::now().timestamp_nanos_opt().unwrap_or(0)));
+  PathBuf::from(partial)
+}
+
+fn object_info(key: String, metadata: &std::fs::Metadata) -> ObjectInfo {
+  ObjectInfo {
+    key,
+    size: metadata.len(),
+    last_modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+    content_type: None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn stores_lists_and_signs_objects() {
+    let root = std::env::temp_dir().join(format!("jd_object_store_{}", std::process::id()));
+    let store = LocalDiskStore::new(&root, "http://localhost:8080/", b"key");
+
+    store.put("reports/a/1.json", Bytes::from_static(b"{}"), "application/json").await.unwrap();
+    store.put("reports/b.json", Bytes::from_static(b"[]"), "application/json").await.unwrap();
+    assert_eq!(store.get("reports/a/1.json").await.unwrap(), Bytes::from_static(b"{}"));
+    let mut keys: Vec<_> =
+      store.list("reports/").await.unwrap().into_iter().map(|object| object.key).collect();
+    keys.sort();
+    assert_eq!(keys, ["reports/a/1.json", "reports/b.json"]);
+    assert_eq!(store.key(&store.uri("reports/b.json")).unwrap(), "reports/b.json");
+
+    let url = store.presigned_get_url("reports/b.json", Duration::from_secs(60)).unwrap();
+    let (_, query) = url.split_once('?').unwrap();
+    let expires: i64 = query[8..query.find('&').unwrap()].parse().unwrap();
+    let signature = query.rsplit_once("signature=").unwrap().1;
+    assert!(url.starts_with("http://localhost:8080/api/v1/objects/reports/b.json?"));
+    assert!(store.verify_presigned("GET", "reports/b.json", expires, signature));
+    assert!(!store.verify_presigned("PUT", "reports/b.json", expires, signature));
+    assert!(!store.verify_presigned("GET", "reports/a/1.json", expires, signature));
+
+    store.delete("reports/b.json").await.unwrap();
+    assert!(store.head("reports/b.json").await.unwrap().is_none());
+    let _ = std::fs::remove_dir_all(root);
+  }
+
+  #[test]
+  fn derived_signing_key_differs_from_the_jwt_secret() {
+    let key = derive_signing_key("jwt-secret");
+    assert_eq!(key.len(), 32);
+    assert_ne!(key, b"jwt-secret");
+    assert_eq!(key, derive_signing_key("jwt-secret"));
+  }
+}
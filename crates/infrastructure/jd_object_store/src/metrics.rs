@@ -0,0 +1,18 @@
+use jd_utils::metrics::{self, IntCounterVec};
+use std::sync::LazyLock;
+
+/// Operations by backend, operation and outcome: `ok` or `error`.
+pub static OPERATIONS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+  metrics::counter(
+    "object_store_operations_total",
+    "Object store operations",
+    &["backend", "operation", "outcome"],
+  )
+});
+
+/// Count the outcome of `operation` on `backend` and pass it on.
+pub(crate) fn record<T, E>(backend: &str, operation: &str, result: Result<T, E>) -> Result<T, E> {
+  let outcome = if result.is_ok() { "ok" } else { "error" };
+  OPERATIONS.with_label_values(&[backend, operation, outcome]).inc();
+  result
+}
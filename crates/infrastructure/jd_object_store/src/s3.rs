@@ -0,0 +1,490 @@
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use jd_utils::{config::ObjectStoreConfig, correlation::Correlate};
+use reqwest::{Method, Response, StatusCode};
+use sha2::{Digest, Sha256};
+use std::{env, time::Duration};
+use tracing::warn;
+
+use crate::{
+  ByteStream, Error, ObjectInfo, ObjectStore, Result,
+  metrics::record,
+  presign_secs,
+  sigv4::{Signer, amz_timestamp, uri_encode},
+  validate_key,
+};
+
+/// Streams are uploaded in parts of this size, above the 5 MiB minimum S3
+/// allows for every part but the last.
+const PART_SIZE: usize = 8 * 1024 * 1024;
+const TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// Longest error body kept in an error message.
+const MAX_ERROR_DETAIL: usize = 512;
+
+/// An S3-compatible bucket, addressed path-style as
+/// `{endpoint}/{bucket}/{key}`.
+#[derive(Debug, Clone)]
+pub struct S3Settings {
+  /// e.g. `https://s3.eu-west-1.amazonaws.com`, `http://minio:9000` or
+  /// `https://storage.googleapis.com`.
+  pub endpoint: String,
+  pub bucket: String,
+  /// `auto` for GCS.
+  pub region: String,
+  pub access_key_id: String,
+  pub secret_access_key: String,
+}
+
+impl S3Settings {
+  /// Settings for `backend` from `object_store`, with the endpoint and
+  /// region defaulting to the provider's own.
+  pub fn from_config(backend: &str, config: &ObjectStoreConfig) -> Result<Self> {
+    let required = |value: &Option<String>, key: &str| {
+      value
+        .clone()
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| Error::Config(format!("object_store.{key} is not set")))
+    };
+    let (default_region, default_endpoint) = match backend {
+      "gcs" => ("auto", Some("https://storage.googleapis.com".to_string())),
+      "minio" => ("us-east-1", None),
+      _ => {
+        let region = config.region.as_deref().unwrap_or("us-east-1");
+        ("us-east-1", Some(format!("https://s3.{region}.amazonaws.com")))
+      }
+    };
+    let endpoint = match (&config.endpoint, default_endpoint) {
+      (Some(endpoint), _) => endpoint.clone(),
+      (None, Some(endpoint)) => endpoint,
+      (None, None) => required(&config.endpoint, "endpoint")?,
+    };
+    Ok(Self {
+      endpoint: endpoint.trim_end_matches('/').to_string(),
+      bucket: required(&config.bucket, "bucket")?,
+      region: config.region.clone().unwrap_or_else(|| default_region.to_string()),
+      access_key_id: required(&config.access_key_id, "access_key_id")?,
+      secret_access_key: required(&config.secret_access_key, "secret_access_key")?,
+    })
+  }
+
+  /// Settings from the `OBJECT_STORE_*` variables read before the
+  /// `object_store` config section, or `None` unless a bucket and its
+  /// credentials are set.
+  pub fn from_legacy_env() -> Option<Self> {
+    let env = |name: &str| env::var(name).ok().filter(|value| !value.trim().is_empty());
+    Some(Self {
+      endpoint: env("OBJECT_STORE_ENDPOINT")
+        .unwrap_or_else(|| "https://s3.amazonaws.com".to_string())
+        .trim_end_matches('/')
+        .to_string(),
+      bucket: env("OBJECT_STORE_BUCKET")?,
+      region: env("OBJECT_STORE_REGION").unwrap_or_else(|| "us-east-1".to_string()),
+      access_key_id: env("OBJECT_STORE_ACCESS_KEY_ID")?,
+      secret_access_key: env("OBJECT_STORE_SECRET_ACCESS_KEY")?,
+    })
+  }
+}
+
+/// Objects in an S3-compatible bucket, signed with AWS SigV4.
+pub struct S3Store {
+  backend: &'static str,
+  endpoint: String,
+  bucket: String,
+  signer: Signer,
+  http: reqwest::Client,
+}
+
+impl S3Store {
+  /// `backend` is `s3`, `gcs` or `minio`, for logs and metrics.
+  pub fn new(backend: &'static str, settings: S3Settings) -> Self {
+    let http = reqwest::Client::builder().timeout(TIMEOUT).build().unwrap_or_default();
+    Self {
+      backend,
+      endpoint: settings.endpoint,
+      bucket: settings.bucket,
+      signer: Signer {
+        access_key_id: settings.access_key_id,
+        secret_access_key: settings.secret_access_key,
+        region: settings.region,
+      },
+      http,
+    }
+  }
+
+  fn host(&self) -> &str {
+    let without_scheme =
+      self.endpoint.split_once("://").map_or(self.endpoint.as_str(), |(_, rest)| rest);
+    without_scheme.split('/').next().unwrap_or(without_scheme)
+  }
+
+  /// The bucket itself for an empty key.
+  fn path(&self, key: &str) -> String {
+    let bucket = uri_encode(&self.bucket, false);
+    match key {
+      "" => format!("/{bucket}"),
+      key => format!("/{bucket}/{}", uri_encode(key, true)),
+    }
+  }
+
+  async fn send(
+    &self,
+    action: &'static str,
+    method: Method,
+    key: &str,
+    query: &[(&str, &str)],
+    body: Option<(Bytes, &str)>,
+  ) -> Result<Response> {
+    let path = self.path(key);
+    let amz_date = amz_timestamp(Utc::now());
+    let payload_hash = match &body {
+      Some((bytes, _)) => hex::encode(Sha256::digest(bytes)),
+      None => hex::encode(Sha256::digest(b"")),
+    };
+    let authorization = self.signer.authorization(
+      method.as_str(),
+      self.host(),
+      &path,
+      query,
+      &amz_date,
+      &payload_hash,
+    );
+
+    let mut request = self
+      .http
+      .request(method, format!("{}{path}", self.endpoint))
+      .query(query)
+      .header("authorization", authorization)
+      .header("x-amz-content-sha256", payload_hash)
+      .header("x-amz-date", amz_date);
+    if let Some((bytes, content_type)) = body {
+      request = request.header("content-type", content_type).body(bytes);
+    }
+    request.correlated().send().await.map_err(|e| request_error(action, key, e))
+  }
+
+  /// `response`, or an error with the status and what the backend said.
+  async fn expect_success(action: &'static str, key: &str, response: Response) -> Result<Response> {
+    if response.status() == StatusCode::NOT_FOUND && !key.is_empty() {
+      return Err(Error::NotFound { key: key.to_string() });
+    }
+    if response.status().is_success() {
+      return Ok(response);
+    }
+    let status = response.status();
+    let mut detail = response.text().await.unwrap_or_default();
+    detail.truncate(MAX_ERROR_DETAIL);
+    Err(Error::Request { action, key: key.to_string(), reason: format!("{status}: {detail}") })
+  }
+
+  async fn put_object(&self, key: &str, bytes: Bytes, content_type: &str) -> Result<()> {
+    let response =
+      self.send("upload", Method::PUT, key, &[], Some((bytes, content_type))).await?;
+    Self::expect_success("upload", key, response).await?;
+    Ok(())
+  }
+
+  /// Upload `first`, then the rest of `stream`, as the parts of a multipart
+  /// upload. Returns the bytes stored.
+  async fn put_multipart(
+    &self,
+    key: &str,
+    first: Bytes,
+    stream: &mut ByteStream,
+    content_type: &str,
+  ) -> Result<u64> {
+    let response = self
+      .send("upload", Method::POST, key, &[("uploads", "")], Some((Bytes::new(), content_type)))
+      .await?;
+    let body = Self::expect_success("upload", key, response).await?.text().await;
+    let body = body.map_err(|e| request_error("upload", key, e))?;
+    let upload_id = xml_text(&body, "UploadId")
+      .ok_or_else(|| Error::Request {
+        action: "upload",
+        key: key.to_string(),
+        reason: "no UploadId in the response".to_string(),
+      })?
+      .to_string();
+
+    let uploaded = self.upload_parts(key, &upload_id, first, stream).await;
+    let (etags, size) = match uploaded {
+      Ok(uploaded) => uploaded,
+      Err(e) => {
+        let query = [("uploadId", upload_id.as_str())];
+        if let Err(abort) = self.send("upload", Method::DELETE, key, &query, None).await {
+          warn!(key, upload_id, "Failed to abort multipart upload: {abort}");
+        }
+        return Err(e);
+      }
+    };
+
+    let parts: String = etags
+      .iter()
+      .enumerate()
+      .map(|(i, etag)| {
+        format!("<Part><PartNumber>{}</PartNumber><ETag>{etag}</ETag></Part>", i + 1)
+      })
+      .collect();
+    let complete = format!("<CompleteMultipartUpload>{parts}</CompleteMultipartUpload>");
+    let query = [("uploadId", upload_id.as_str())];
+    let response = self
+      .send("upload", Method::POST, key, &query, Some((complete.into(), "application/xml")))
+      .await?;
+    // Completion can fail after a 200 with an error document in the body
+    let body = Self::expect_success("upload", key, response).await?.text().await;
+    let body = body.map_err(|e| request_error("upload", key, e))?;
+    if body.contains("<Error>") {
+      return Err(Error::Request {
+        action: "upload",
+        key: key.to_string(),
+        reason: xml_text(&body, "Message").unwrap_or("completion failed").to_string(),
+      });
+    }
+    Ok(size)
+  }
+
+  /// Upload parts of `PART_SIZE`, the last smaller, returning their ETags
+  /// in order and their total size.
+  async fn upload_parts(
+    &self,
+    key: &str,
+    upload_id: &str,
+    first: Bytes,
+    stream: &mut ByteStream,
+  ) -> Result<(Vec<String>, u64)> {
+    let mut etags = Vec::new();
+    let mut size = 0;
+    let mut part = Some(first);
+    let mut buffer = BytesMut::new();
+    while let Some(bytes) = part.take() {
+      size += bytes.len() as u64;
+      let part_number = (etags.len() + 1).to_string();
+      let query = [("partNumber", part_number.as_str()), ("uploadId", upload_id)];
+      let response = self
+        .send("upload", Method::PUT, key, &query, Some((bytes, "application/octet-stream")))
+        .await?;
+      let response = Self::expect_success("upload", key, response).await?;
+      let etag = response.headers().get("etag").and_then(|etag| etag.to_str().ok());
+      etags.push(etag.unwrap_or_default().to_string());
+
+      while buffer.len() < PART_SIZE {
+        match stream.next().await {
+          Some(chunk) => buffer.extend_from_slice(&chunk?),
+          None => break,
+        }
+      }
+      if !buffer.is_empty() {
+        part = Some(buffer.split_to(buffer.len().min(PART_SIZE)).freeze());
+      }
+    }
+    Ok((etags, size))
+  }
+
+  async fn list_objects(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+    let mut objects = Vec::new();
+    let mut continuation_token: Option<String> = None;
+    loop {
+      let mut query = vec![("list-type", "2"), ("prefix", prefix)];
+      if let Some(token) = &continuation_token {
+        query.push(("continuation-token", token));
+      }
+      let response = self.send("list", Method::GET, "", &query, None).await?;
+      let body = Self::expect_success("list", prefix, response).await?.text().await;
+      let body = body.map_err(|e| request_error("list", prefix, e))?;
+      objects.extend(parse_list(&body));
+      continuation_token = match xml_text(&body, "IsTruncated") {
+        Some("true") => xml_text(&body, "NextContinuationToken").map(xml_unescape),
+        _ => None,
+      };
+      if continuation_token.is_none() {
+        return Ok(objects);
+      }
+    }
+  }
+
+  fn presigned_url(&self, method: &str, key: &str, expires_in: Duration) -> Result<String> {
+    validate_key(key)?;
+    let path = self.path(key);
+    let amz_date = amz_timestamp(Utc::now());
+    let query =
+      self.signer.presigned_query(method, self.host(), &path, &amz_date, presign_secs(expires_in));
+    Ok(format!("{}{path}?{query}", self.endpoint))
+  }
+}
+
+#[async_trait]
+impl ObjectStore for S3Store {
+  fn backend(&self) -> &'static str {
+    self.backend
+  }
+
+  fn uri(&self, key: &str) -> String {
+    format!("s3://{}/{key}", self.bucket)
+  }
+
+  fn key(&self, uri: &str) -> Result<String> {
+    uri
+      .strip_prefix("s3://")
+      .and_then(|rest| rest.strip_prefix(self.bucket.as_str()))
+      .and_then(|rest| rest.strip_prefix('/'))
+      .filter(|key| validate_key(key).is_ok())
+      .map(str::to_string)
+      .ok_or_else(|| Error::ForeignUri { uri: uri.to_string() })
+  }
+
+  async fn put(&self, key: &str, bytes: Bytes, content_type: &str) -> Result<()> {
+    validate_key(key)?;
+    record(self.backend, "put", self.put_object(key, bytes, content_type).await)
+  }
+
+  async fn put_stream(&self, key: &str, mut stream: ByteStream, content_type: &str) -> Result<u64> {
+    validate_key(key)?;
+    let mut buffer = BytesMut::new();
+    while buffer.len() < PART_SIZE {
+      match stream.next().await {
+        Some(chunk) => buffer.extend_from_slice(&chunk?),
+        None => {
+          // Small enough for a single request
+          let size = buffer.len() as u64;
+          let stored = self.put_object(key, buffer.freeze(), content_type).await;
+          return record(self.backend, "put", stored.map(|()| size));
+        }
+      }
+    }
+    let first = buffer.split_to(PART_SIZE).freeze();
+    let mut rest: ByteStream =
+      Box::pin(futures::stream::iter([Ok(buffer.freeze())]).chain(stream));
+    let stored = self.put_multipart(key, first, &mut rest, content_type).await;
+    record(self.backend, "put", stored)
+  }
+
+  async fn get_stream(&self, key: &str) -> Result<ByteStream> {
+    validate_key(key)?;
+    let fetched = async {
+      let response = self.send("download", Method::GET, key, &[], None).await?;
+      Self::expect_success("download", key, response).await
+    };
+    let response = record(self.backend, "get", fetched.await)?;
+    let key = key.to_string();
+    Ok(Box::pin(response.bytes_stream().map_err(move |e| request_error("download", &key, e))))
+  }
+
+  async fn head(&self, key: &str) -> Result<Option<ObjectInfo>> {
+    validate_key(key)?;
+    let fetched = async {
+      let response = self.send("inspect", Method::HEAD, key, &[], None).await?;
+      match Self::expect_success("inspect", key, response).await {
+        Ok(response) => Ok(Some(response)),
+        Err(Error::NotFound { .. }) => Ok(None),
+        Err(e) => Err(e),
+      }
+    };
+    let Some(response) = record(self.backend, "head", fetched.await)? else {
+      return Ok(None);
+    };
+    let header = |name: &str| response.headers().get(name).and_then(|value| value.to_str().ok());
+    Ok(Some(ObjectInfo {
+      key: key.to_string(),
+      size: header("content-length").and_then(|size| size.parse().ok()).unwrap_or_default(),
+      last_modified: header("last-modified")
+        .and_then(|at| DateTime::parse_from_rfc2822(at).ok())
+        .map(|at| at.with_timezone(&Utc)),
+      content_type: header("content-type").map(str::to_string),
+    }))
+  }
+
+  async fn delete(&self, key: &str) -> Result<()> {
+    validate_key(key)?;
+    let deleted = async {
+      let response = self.send("delete", Method::DELETE, key, &[], None).await?;
+      match Self::expect_success("delete", key, response).await {
+        Ok(_) | Err(Error::NotFound { .. }) => Ok(()),
+        Err(e) => Err(e),
+      }
+    };
+    record(self.backend, "delete", deleted.await)
+  }
+
+  async fn list(&self, prefix: &str) -> Result<Vec<ObjectInfo>> {
+    record(self.backend, "list", self.list_objects(prefix).await)
+  }
+
+  fn presigned_get_url(&self, key: &str, expires_in: Duration) -> Result<String> {
+    self.presigned_url("GET", key, expires_in)
+  }
+
+  fn presigned_put_url(&self, key: &str, expires_in: Duration) -> Result<String> {
+    self.presigned_url("PUT", key, expires_in)
+  }
+}
+
+fn request_error(action: &'static str, key: &str, e: reqwest::Error) -> Error {
+  Error::Request { action, key: key.to_string(), reason: e.to_string() }
+}
+
+/// The objects in a `ListObjectsV2` response.
+fn parse_list(body: &str) -> Vec<ObjectInfo> {
+  xml_elements(body, "Contents")
+    .into_iter()
+    .filter_map(|contents| {
+      Some(ObjectInfo {
+        key: xml_unescape(xml_text(contents, "Key")?),
+        size: xml_text(contents, "Size").and_then(|size| size.parse().ok()).unwrap_or_default(),
+        last_modified: xml_text(contents, "LastModified")
+          .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+          .map(|at| at.with_timezone(&Utc)),
+        content_type: None,
+      })
+    })
+    .collect()
+}
+
+/// The content of every `<tag>` element, without nesting of the same tag.
+fn xml_elements<'a>(body: &'a str, tag: &str) -> Vec<&'a str> {
+  let (open, close) = (format!("<{tag}>"), format!("</{tag}>"));
+  let mut elements = Vec::new();
+  let mut rest = body;
+  while let Some(start) = rest.find(&open) {
+    let content = &rest[start + open.len()..];
+    let Some(end) = content.find(&close) else { break };
+    elements.push(&content[..end]);
+    rest = &content[end + close.len()..];
+  }
+  elements
+}
+
+/// The content of the first `<tag>` element.
+fn xml_text<'a>(body: &'a str, tag: &str) -> Option<&'a str> {
+  xml_elements(body, tag).into_iter().next()
+}
+
+fn xml_unescape(text: &str) -> String {
+  text
+    .replace("&lt;", "<")
+    .replace("&gt;", ">")
+    .replace("&quot;", "\"")
+    .replace("&apos;", "'")
+    .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_a_list_objects_page() {
+    let body = "<ListBucketResult><IsTruncated>true</IsTruncated>\
+      <Contents><Key>reports/a&amp;b.json</Key>\
+      <LastModified>2026-01-02T03:04:05.000Z</LastModified><Size>42</Size></Contents>\
+      <Contents><Key>reports/c.json</Key><Size>7</Size></Contents>\
+      <NextContinuationToken>1x/y==</NextContinuationToken></ListBucketResult>";
+    let objects = parse_list(body);
+    assert_eq!(objects.len(), 2);
+    assert_eq!(objects[0].key, "reports/a&b.json");
+    assert_eq!(objects[0].size, 42);
+    assert_eq!(objects[0].last_modified.map(|at| at.timestamp()), Some(1767323045));
+    assert!(objects[1].last_modified.is_none());
+    assert_eq!(xml_text(body, "NextContinuationToken"), Some("1x/y=="));
+  }
+}
@@ -4,6 +4,7 @@ use std::time::Duration;
 use tracing::{info, warn};
 
 use crate::dbx::{Dbx, Result as DbxResult};
+use crate::migrations::{self, MigrationCheck};
 
 pub type Db = Pool<Postgres>;
 
@@ -33,6 +34,9 @@ pub struct DatabaseConfig {
     
     /// Run migrations on startup
     pub auto_migrate: bool,
+
+    /// What the startup migration check refuses to boot on
+    pub migration_check: MigrationCheck,
     
     /// Enable transaction support
     pub enable_transactions: bool,
@@ -58,6 +62,7 @@ impl Default for DatabaseConfig {
             max_lifetime_secs: Some(3600), // 1 hour
             ssl_mode: "prefer".to_string(),
             auto_migrate: true,
+            migration_check: MigrationCheck::Strict,
             enable_transactions: true,
             test_connection: true,
             retry_attempts: 3,
@@ -69,7 +74,7 @@ impl Default for DatabaseConfig {
 impl DatabaseConfig {
     /// Load configuration from environment variables using centralized config
     pub fn from_env() -> Result<Self, DatabaseConfigError> {
-        let config = jd_utils::config::Config::from_env()
+        let config = jd_utils::config::Config::load()
             .map_err(|e| DatabaseConfigError::InvalidConfig {
                 field: "config".to_string(),
                 error: format!("Failed to load configuration: {}", e),
@@ -84,6 +89,7 @@ impl DatabaseConfig {
             max_lifetime_secs: config.postgres.max_lifetime_secs,
             ssl_mode: config.postgres.ssl_mode.unwrap_or_else(|| "prefer".to_string()),
             auto_migrate: config.postgres.auto_migrate.unwrap_or(true),
+            migration_check: migration_check(config.postgres.migration_check.as_deref()),
             enable_transactions: config.postgres.enable_transactions.unwrap_or(true),
             test_connection: config.postgres.test_connection.unwrap_or(true),
             retry_attempts: config.postgres.retry_attempts.unwrap_or(3),
@@ -102,6 +108,7 @@ impl DatabaseConfig {
             max_lifetime_secs: postgres.max_lifetime_secs,
             ssl_mode: postgres.ssl_mode.clone().unwrap_or_else(|| "prefer".to_string()),
             auto_migrate: postgres.auto_migrate.unwrap_or(true),
+            migration_check: migration_check(postgres.migration_check.as_deref()),
             enable_transactions: postgres.enable_transactions.unwrap_or(true),
             test_connection: postgres.test_connection.unwrap_or(true),
             retry_attempts: postgres.retry_attempts.unwrap_or(3),
@@ -175,9 +182,7 @@ impl DatabaseManager {
                         self.test_connection().await?;
                     }
                     
-                    if self.config.auto_migrate {
-                        self.run_migrations().await?;
-                    }
+                    self.apply_migrations().await?;
                     
                     return Ok(());
                 }
@@ -232,17 +237,30 @@ impl DatabaseManager {
         Ok(())
     }
 
-    /// Run database migrations
-    async fn run_migrations(&self) -> Result<(), DatabaseConfigError> {
+    /// Check the database's migrations against the binary's, applying
+    /// pending ones when `auto_migrate` is set. A mismatch stops startup
+    /// unless `migration_check` is off.
+    async fn apply_migrations(&self) -> Result<(), DatabaseConfigError> {
         let pool = self.pool.as_ref().ok_or(DatabaseConfigError::PoolNotInitialized)?;
-        
-        info!("Running database migrations...");
-        sqlx::migrate!("../../../sql")
-            .run(pool)
-            .await
-            .map_err(DatabaseConfigError::Migration)?;
-            
-        info!("Database migrations completed successfully");
+
+        let status = migrations::status(pool).await?;
+        let problems = status.problems(self.config.migration_check, self.config.auto_migrate);
+        if !problems.is_empty() {
+            if self.config.migration_check != MigrationCheck::Off {
+                return Err(DatabaseConfigError::IncompatibleMigrations(problems.join("; ")));
+            }
+            for problem in &problems {
+                warn!("Database migrations {}", problem);
+            }
+        }
+
+        if self.config.auto_migrate && !status.pending.is_empty() {
+            info!("Running {} database migration(s)...", status.pending.len());
+            migrations::run(pool, self.config.migration_check)
+                .await
+                .map_err(DatabaseConfigError::Migration)?;
+            info!("Database migrations completed successfully");
+        }
         Ok(())
     }
 
@@ -329,10 +347,18 @@ pub enum DatabaseConfigError {
     #[error("Migration failed: {0}")]
     Migration(sqlx::migrate::MigrateError),
 
+    #[error("Database migrations do not match this binary ({0}); see `web_server migrate status`")]
+    IncompatibleMigrations(String),
+
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 }
 
+/// `POSTGRES.MIGRATION_CHECK`, validated when the config was loaded.
+fn migration_check(value: Option<&str>) -> MigrationCheck {
+    value.and_then(|value| value.parse().ok()).unwrap_or_default()
+}
+
 /// Helper function to create a new database pool with default configuration
 pub async fn new_db_pool() -> Result<Db, DatabaseConfigError> {
     let config = DatabaseConfig::from_env()?;
@@ -1,3 +1,4 @@
+use futures::{SinkExt, StreamExt, channel::mpsc};
 use std::{
   ops::{Deref, DerefMut},
   sync::Arc,
@@ -17,6 +18,13 @@ mod error;
 
 pub use error::{Error, Result};
 
+/// Rows a stream reads ahead of its consumer.
+const STREAM_BUFFER: usize = 256;
+
+/// Rows of a query read by `Dbx::fetch_stream`, in order. Ends after the
+/// first error.
+pub type RowStream<O> = mpsc::Receiver<Result<O>>;
+
 /// A database transaction wrapper that supports nested transactions
 /// through a counter mechanism.
 #[derive(Debug, Clone)]
@@ -136,6 +144,25 @@ impl Dbx {
     }
   }
 
+  /// Rolls back the open transaction whatever its nesting, as when the work
+  /// that began it was aborted.
+  ///
+  /// # Returns
+  ///
+  /// * `Ok(true)` if a transaction was rolled back
+  /// * `Ok(false)` if none was open
+  pub async fn abandon_txn(&self) -> Result<bool> {
+    let mut txh_g = self.txn_holder.lock().await;
+    match txh_g.take() {
+      Some(txn_holder) if txn_holder.is_active() => {
+        warn!("Rolling back a transaction left open at nesting {}", txn_holder.counter);
+        txn_holder.txn.rollback().await?;
+        Ok(true)
+      }
+      _ => Ok(false),
+    }
+  }
+
   /// Commits the current transaction if it's the last one in the stack.
   ///
   /// # Returns
@@ -243,6 +270,30 @@ impl Dbx {
     Ok(data)
   }
 
+  /// Streams the rows of `query` instead of collecting them, for result sets
+  /// too large to hold in memory. Rows are read on a spawned task from a
+  /// pooled connection, outside any open transaction, and at most
+  /// `STREAM_BUFFER` rows ahead of the consumer. Dropping the stream cancels
+  /// the query.
+  pub fn fetch_stream<O, A>(&self, query: QueryAs<'static, Postgres, O, A>) -> RowStream<O>
+  where
+    O: for<'r> FromRow<'r, <Postgres as sqlx::Database>::Row> + Send + Unpin + 'static,
+    A: IntoArguments<'static, Postgres> + Send + 'static,
+  {
+    let (mut sender, receiver) = mpsc::channel(STREAM_BUFFER);
+    let db_pool = self.db_pool.clone();
+    tokio::spawn(async move {
+      let mut rows = query.fetch(&db_pool);
+      while let Some(row) = rows.next().await {
+        let failed = row.is_err();
+        if sender.send(row.map_err(Error::from)).await.is_err() || failed {
+          break;
+        }
+      }
+    });
+    receiver
+  }
+
   pub async fn execute<'q, A>(&self, query: Query<'q, Postgres, A>) -> Result<u64>
   where
     A: IntoArguments<'q, Postgres> + 'q,
@@ -1,5 +1,6 @@
 pub mod config;
 pub mod dbx;
+pub mod migrations;
 pub mod repository;
 pub mod utils;
 
@@ -8,7 +9,7 @@ pub use config::{
     new_db_pool, new_db_pool_with_config, 
     DatabaseConfig, DatabaseManager, DatabaseStats, HealthStatus
 };
-pub use dbx::{Dbx, Error as DbxError, Result as DbxResult};
+pub use dbx::{Dbx, Error as DbxError, Result as DbxResult, RowStream};
 
 use sqlx::{Pool, Postgres};
 
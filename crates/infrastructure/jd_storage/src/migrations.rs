@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{
+  Row,
+  migrate::{MigrateError, Migrator},
+};
+use std::{collections::HashMap, str::FromStr};
+
+use crate::Db;
+
+/// The migrations in `sql/`, compiled into the binary.
+pub static MIGRATOR: Migrator = sqlx::migrate!("../../../sql");
+
+/// What the startup check refuses to boot on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationCheck {
+  /// Pending migrations that are not applied at startup, and applied ones
+  /// that failed, were edited since, or are unknown to this binary.
+  #[default]
+  Strict,
+  /// As `Strict`, but migrations newer than this binary are accepted, e.g.
+  /// while an older release is still rolling out.
+  AllowNewer,
+  /// Problems are only logged.
+  Off,
+}
+
+impl FromStr for MigrationCheck {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.trim().to_lowercase().as_str() {
+      "strict" => Ok(Self::Strict),
+      "allow_newer" => Ok(Self::AllowNewer),
+      "off" => Ok(Self::Off),
+      other => Err(format!("unknown migration check '{other}'; use strict, allow_newer or off")),
+    }
+  }
+}
+
+/// A migration by version and description.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationInfo {
+  pub version: i64,
+  pub description: String,
+}
+
+/// How the database's migrations compare with the binary's.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationStatus {
+  pub applied: Vec<MigrationInfo>,
+  pub pending: Vec<MigrationInfo>,
+  /// Applied, but their file changed since.
+  pub modified: Vec<MigrationInfo>,
+  /// Started but did not complete.
+  pub failed: Vec<MigrationInfo>,
+  /// Applied by a newer binary.
+  pub unknown: Vec<i64>,
+}
+
+impl MigrationStatus {
+  /// Why the database does not match this binary under `check`. Pending
+  /// migrations are fine when `applying_pending`.
+  pub fn problems(&self, check: MigrationCheck, applying_pending: bool) -> Vec<String> {
+    let describe = |migrations: &[MigrationInfo]| {
+      migrations.iter().map(|m| format!("{} ({})", m.version, m.description)).collect::<Vec<_>>()
+    };
+    let mut problems = Vec::new();
+    if !applying_pending && !self.pending.is_empty() {
+      problems.push(format!("pending: {}", describe(&self.pending).join(", ")));
+    }
+    if !self.failed.is_empty() {
+      problems.push(format!("failed: {}", describe(&self.failed).join(", ")));
+    }
+    if !self.modified.is_empty() {
+      problems.push(format!("changed since applied: {}", describe(&self.modified).join(", ")));
+    }
+    if check == MigrationCheck::Strict && !self.unknown.is_empty() {
+      let versions: Vec<String> = self.unknown.iter().map(i64::to_string).collect();
+      problems.push(format!("unknown to this binary: {}", versions.join(", ")));
+    }
+    problems
+  }
+}
+
+/// Compare the migrations applied to the database with `MIGRATOR`'s.
+pub async fn status(db: &Db) -> Result<MigrationStatus, sqlx::Error> {
+  let table_exists: bool =
+    sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL").fetch_one(db).await?;
+  let mut applied_rows: HashMap<i64, (bool, Vec<u8>)> = HashMap::new();
+  if table_exists {
+    let rows = sqlx::query("SELECT version, success, checksum FROM _sqlx_migrations")
+      .fetch_all(db)
+      .await?;
+    for row in rows {
+      applied_rows.insert(row.get("version"), (row.get("success"), row.get("checksum")));
+    }
+  }
+
+  let mut status = MigrationStatus::default();
+  for migration in MIGRATOR.iter().filter(|m| !m.migration_type.is_down_migration()) {
+    let info =
+      MigrationInfo { version: migration.version, description: migration.description.to_string() };
+    match applied_rows.remove(&migration.version) {
+      None => status.pending.push(info),
+      Some((false, _)) => status.failed.push(info),
+      Some((true, checksum)) if checksum != *migration.checksum => status.modified.push(info),
+      Some(_) => status.applied.push(info),
+    }
+  }
+  status.unknown = applied_rows.into_keys().collect();
+  status.unknown.sort_unstable();
+  Ok(status)
+}
+
+/// Apply pending migrations. Unless `check` is `Strict`, migrations unknown
+/// to this binary are left alone rather than refused.
+pub async fn run(db: &Db, check: MigrationCheck) -> Result<(), MigrateError> {
+  let mut migrator = sqlx::migrate!("../../../sql");
+  migrator.set_ignore_missing(check != MigrationCheck::Strict);
+  migrator.run(db).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn info(version: i64) -> MigrationInfo {
+    MigrationInfo { version, description: format!("migration {version}") }
+  }
+
+  #[test]
+  fn newer_migrations_are_only_refused_when_strict() {
+    let status = MigrationStatus {
+      pending: vec![info(67)],
+      unknown: vec![68],
+      ..Default::default()
+    };
+    assert_eq!(status.problems(MigrationCheck::Strict, true), ["unknown to this binary: 68"]);
+    assert!(status.problems(MigrationCheck::AllowNewer, true).is_empty());
+    assert_eq!(
+      status.problems(MigrationCheck::AllowNewer, false),
+      ["pending: 67 (migration 67)"]
+    );
+  }
+}
@@ -59,11 +59,9 @@ impl BehaviorInputRepository {
     /// Convert database row to BehaviorInput model
     fn row_to_model(row: &sqlx::postgres::PgRow) -> sqlx::Result<BehaviorInput> {
         Ok(BehaviorInput {
-            id: Id::new(row.try_get::<uuid::Uuid, _>("id")?.to_string()),
-            user_id: row.try_get::<Option<uuid::Uuid>, _>("user_id")?
-                .map(|u| Id::new(u.to_string())),
-            behavior_session_id: row.try_get::<Option<uuid::Uuid>, _>("behavior_session_id")?
-                .map(|u| Id::new(u.to_string())),
+            id: row.try_get("id")?,
+            user_id: row.try_get::<Option<Id>, _>("user_id")?,
+            behavior_session_id: row.try_get::<Option<Id>, _>("behavior_session_id")?,
             session_id: row.try_get("session_id")?,
             input_data: row.try_get("input_data")?,
             input_type: row.try_get::<String, _>("input_type")?
@@ -74,11 +72,9 @@ impl BehaviorInputRepository {
                 .map_err(|e| sqlx::Error::Decode(Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e))))?,
             timestamp: row.try_get("timestamp")?,
             processed: row.try_get("processed")?,
-            cid: row.try_get::<Option<uuid::Uuid>, _>("cid")?
-                .map(|u| Id::new(u.to_string())),
+            cid: row.try_get::<Option<Id>, _>("cid")?,
             ctime: row.try_get("ctime")?,
-            mid: row.try_get::<Option<uuid::Uuid>, _>("mid")?
-                .map(|u| Id::new(u.to_string())),
+            mid: row.try_get::<Option<Id>, _>("mid")?,
             mtime: row.try_get("mtime")?,
         })
     }
@@ -0,0 +1,118 @@
+use crate::Environment;
+use color_eyre::eyre::{Result, eyre};
+use sentry::{
+  ClientInitGuard, ClientOptions, Level,
+  integrations::tracing::{EventFilter, SentryLayer},
+  protocol::User,
+};
+use std::env;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Release reported when `SENTRY_RELEASE` is not set.
+const DEFAULT_RELEASE: &str = concat!("hkt-server@", env!("CARGO_PKG_VERSION"));
+
+/// Where high-severity errors and panics are reported, through any
+/// Sentry-compatible endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorReportingConfig {
+  pub dsn: String,
+  pub release: String,
+  /// Share of errors sent, from 0.0 to 1.0.
+  pub sample_rate: f32,
+}
+
+impl ErrorReportingConfig {
+  /// From `SENTRY_DSN`, `SENTRY_RELEASE` and `SENTRY_SAMPLE_RATE` (default
+  /// 1.0). None when no DSN is set.
+  pub fn from_env() -> Result<Option<Self>> {
+    let Some(dsn) = env::var("SENTRY_DSN").ok().filter(|dsn| !dsn.trim().is_empty()) else {
+      return Ok(None);
+    };
+    let release = env::var("SENTRY_RELEASE").unwrap_or_else(|_| DEFAULT_RELEASE.to_string());
+    let sample_rate = match env::var("SENTRY_SAMPLE_RATE") {
+      Ok(rate) => rate
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|rate| (0.0..=1.0).contains(rate))
+        .ok_or_else(|| eyre!("SENTRY_SAMPLE_RATE must be between 0.0 and 1.0"))?,
+      Err(_) => 1.0,
+    };
+    Ok(Some(Self { dsn, release, sample_rate }))
+  }
+
+  /// Start the client; panics are reported from then on. Pending reports are
+  /// sent when the guard drops.
+  pub(crate) fn init(&self, environment: Environment) -> Result<ClientInitGuard> {
+    let guard = sentry::init(ClientOptions {
+      dsn: Some(self.dsn.parse().map_err(|e| eyre!("Invalid SENTRY_DSN: {e}"))?),
+      release: Some(self.release.clone().into()),
+      environment: Some(environment.as_str().into()),
+      sample_rate: self.sample_rate,
+      attach_stacktrace: true,
+      ..Default::default()
+    });
+    Ok(guard)
+  }
+}
+
+/// Logs become breadcrumbs of the next report; only [`report_error`] sends
+/// events, so routine `error!` lines do not page anyone.
+pub(crate) fn breadcrumb_layer<S>() -> SentryLayer<S>
+where
+  S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+  sentry::integrations::tracing::layer().event_filter(|metadata| match *metadata.level() {
+    tracing::Level::ERROR | tracing::Level::WARN | tracing::Level::INFO => {
+      EventFilter::Breadcrumb
+    }
+    _ => EventFilter::Ignore,
+  })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportLevel {
+  Error,
+  /// The service cannot do its job until someone intervenes.
+  Fatal,
+}
+
+/// An error worth a person's attention, with what identifies its request.
+#[derive(Debug, Clone)]
+pub struct ErrorReport {
+  pub message: String,
+  pub level: ReportLevel,
+  pub request_id: Option<String>,
+  pub trace_id: Option<String>,
+  pub user_id: Option<String>,
+  pub tags: Vec<(&'static str, String)>,
+}
+
+/// Send `report` to the error aggregator; a no-op unless reporting was
+/// configured at init.
+pub fn report_error(report: ErrorReport) {
+  if !sentry::Hub::current().client().is_some_and(|client| client.is_enabled()) {
+    return;
+  }
+  let level = match report.level {
+    ReportLevel::Error => Level::Error,
+    ReportLevel::Fatal => Level::Fatal,
+  };
+  sentry::with_scope(
+    |scope| {
+      if let Some(request_id) = &report.request_id {
+        scope.set_tag("request_id", request_id);
+      }
+      if let Some(trace_id) = &report.trace_id {
+        scope.set_tag("trace_id", trace_id);
+      }
+      if let Some(user_id) = &report.user_id {
+        scope.set_user(Some(User { id: Some(user_id.clone()), ..Default::default() }));
+      }
+      for (key, value) in &report.tags {
+        scope.set_tag(key, value);
+      }
+    },
+    || sentry::capture_message(&report.message, level),
+  );
+}
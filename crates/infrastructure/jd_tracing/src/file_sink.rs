@@ -0,0 +1,207 @@
+use color_eyre::eyre::{Result, eyre};
+use std::{
+  env,
+  fs::{self, File, OpenOptions},
+  io::{self, Write},
+  path::{Path, PathBuf},
+  str::FromStr,
+};
+use tracing_appender::{
+  non_blocking::{NonBlocking, WorkerGuard},
+  rolling::{self, Rotation},
+};
+
+const DEFAULT_RETENTION: usize = 7;
+
+/// When a log file is closed and a new one started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogRotation {
+  Never,
+  Hourly,
+  Daily,
+  /// Once the file would grow past this many bytes.
+  Size(u64),
+}
+
+impl FromStr for LogRotation {
+  type Err = color_eyre::eyre::Report;
+
+  /// `never`, `hourly`, `daily`, or a size such as `100mb`, `512kb`, `1gb`
+  fn from_str(s: &str) -> Result<Self> {
+    let s = s.trim().to_lowercase();
+    match s.as_str() {
+      "never" => return Ok(Self::Never),
+      "hourly" => return Ok(Self::Hourly),
+      "daily" => return Ok(Self::Daily),
+      _ => {}
+    }
+    let (digits, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+    let multiplier = match unit.trim() {
+      "" | "b" => 1,
+      "kb" => 1024,
+      "mb" => 1024 * 1024,
+      "gb" => 1024 * 1024 * 1024,
+      _ => return Err(eyre!("Unknown log rotation '{}'", s)),
+    };
+    match digits.parse::<u64>() {
+      Ok(size) if size > 0 => Ok(Self::Size(size * multiplier)),
+      _ => Err(eyre!("Unknown log rotation '{}'", s)),
+    }
+  }
+}
+
+/// A log file written alongside stdout, rotated and pruned as it grows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSinkConfig {
+  /// The active file; rotated files are kept next to it.
+  pub path: PathBuf,
+  pub rotation: LogRotation,
+  /// Rotated files kept, besides the active one.
+  pub retention: usize,
+}
+
+impl FileSinkConfig {
+  /// From `{prefix}_PATH`, `{prefix}_ROTATION` (default `daily`) and
+  /// `{prefix}_RETENTION` (default 7). None when no path is set.
+  pub fn from_env(prefix: &str) -> Result<Option<Self>> {
+    let Some(path) = env::var(format!("{prefix}_PATH")).ok().filter(|path| !path.trim().is_empty())
+    else {
+      return Ok(None);
+    };
+    let rotation = match env::var(format!("{prefix}_ROTATION")) {
+      Ok(rotation) => rotation.parse()?,
+      Err(_) => LogRotation::Daily,
+    };
+    let retention = match env::var(format!("{prefix}_RETENTION")) {
+      Ok(retention) => retention
+        .trim()
+        .parse()
+        .map_err(|_| eyre!("{prefix}_RETENTION must be a number of files"))?,
+      Err(_) => DEFAULT_RETENTION,
+    };
+    Ok(Some(Self { path: PathBuf::from(path), rotation, retention }))
+  }
+
+  /// A writer that hands lines to a background thread. Buffered lines are
+  /// flushed when the guard drops.
+  pub(crate) fn writer(&self) -> Result<(NonBlocking, WorkerGuard)> {
+    let directory =
+      self.path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    fs::create_dir_all(directory)?;
+    let file_name = self
+      .path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .ok_or_else(|| eyre!("Log file path '{}' has no file name", self.path.display()))?;
+
+    let rotation = match self.rotation {
+      LogRotation::Size(max_bytes) => {
+        let file = SizeRollingFile::open(self.path.clone(), max_bytes, self.retention)?;
+        return Ok(tracing_appender::non_blocking(file));
+      }
+      LogRotation::Never => Rotation::NEVER,
+      LogRotation::Hourly => Rotation::HOURLY,
+      LogRotation::Daily => Rotation::DAILY,
+    };
+    // Time-rotated files are named `{file_name}.{date}`; the active one counts
+    // towards the files kept
+    let appender = rolling::Builder::new()
+      .rotation(rotation)
+      .filename_prefix(file_name)
+      .max_log_files(self.retention + 1)
+      .build(directory)?;
+    Ok(tracing_appender::non_blocking(appender))
+  }
+}
+
+/// A file rotated once it would grow past `max_bytes`: `app.log` becomes
+/// `app.log.1`, `app.log.1` becomes `app.log.2`, and so on up to
+/// `retention`.
+struct SizeRollingFile {
+  path: PathBuf,
+  max_bytes: u64,
+  retention: usize,
+  file: File,
+  written: u64,
+}
+
+impl SizeRollingFile {
+  fn open(path: PathBuf, max_bytes: u64, retention: usize) -> io::Result<Self> {
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    let written = file.metadata()?.len();
+    Ok(Self { path, max_bytes, retention, file, written })
+  }
+
+  fn rotated(&self, index: usize) -> PathBuf {
+    let mut name = self.path.clone().into_os_string();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+  }
+
+  fn rotate(&mut self) -> io::Result<()> {
+    self.file.flush()?;
+    if self.retention == 0 {
+      fs::remove_file(&self.path)?;
+    } else {
+      let _ = fs::remove_file(self.rotated(self.retention));
+      for index in (1..self.retention).rev() {
+        let from = self.rotated(index);
+        if from.exists() {
+          fs::rename(from, self.rotated(index + 1))?;
+        }
+      }
+      fs::rename(&self.path, self.rotated(1))?;
+    }
+    self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+    self.written = 0;
+    Ok(())
+  }
+}
+
+impl Write for SizeRollingFile {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    // A line larger than the limit still gets a file of its own
+    if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+      self.rotate()?;
+    }
+    let written = self.file.write(buf)?;
+    self.written += written as u64;
+    Ok(written)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.file.flush()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rotations_parse_from_names_and_sizes() {
+    assert_eq!("Daily".parse::<LogRotation>().unwrap(), LogRotation::Daily);
+    assert_eq!("100MB".parse::<LogRotation>().unwrap(), LogRotation::Size(100 * 1024 * 1024));
+    assert_eq!("4096".parse::<LogRotation>().unwrap(), LogRotation::Size(4096));
+    assert!("0mb".parse::<LogRotation>().is_err());
+    assert!("weekly".parse::<LogRotation>().is_err());
+  }
+
+  #[test]
+  fn size_rotation_keeps_the_newest_files() {
+    let dir = env::temp_dir().join(format!("jd_tracing-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("app.log");
+    let mut file = SizeRollingFile::open(path.clone(), 10, 2).unwrap();
+    for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+      file.write_all(line.as_bytes()).unwrap();
+    }
+    file.flush().unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "fourth\n");
+    assert_eq!(fs::read_to_string(dir.join("app.log.1")).unwrap(), "third\n");
+    assert_eq!(fs::read_to_string(dir.join("app.log.2")).unwrap(), "second\n");
+    assert!(!dir.join("app.log.3").exists());
+    fs::remove_dir_all(dir).unwrap();
+  }
+}
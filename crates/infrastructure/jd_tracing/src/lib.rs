@@ -4,12 +4,26 @@ use serde::{Deserialize, Serialize};
 use std::env;
 use tracing::Level;
 use tracing_error::ErrorLayer;
+use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{
   EnvFilter,
+  filter::{LevelFilter, Targets},
   fmt::{self, format::FmtSpan, time::SystemTime},
   layer::{Layer, SubscriberExt},
+  reload,
   util::SubscriberInitExt,
 };
+
+mod error_reporting;
+mod file_sink;
+mod log_filter;
+
+pub use error_reporting::{ErrorReport, ErrorReportingConfig, ReportLevel, report_error};
+pub use file_sink::{FileSinkConfig, LogRotation};
+pub use log_filter::{
+  LogFilterError, LogFilterHandle, LogFilterStatus, SCOPE_LOGGING_ADMIN, log_filter,
+};
+
 /// Environment types for different deployment stages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -36,6 +50,16 @@ impl Environment {
     }
   }
 
+  /// Lowercase name, as reported to log and error aggregators
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Development => "development",
+      Self::Staging => "staging",
+      Self::Production => "production",
+      Self::Testing => "testing",
+    }
+  }
+
   /// Check if running in production
   pub fn is_production(&self) -> bool {
     matches!(self, Self::Production)
@@ -58,6 +82,12 @@ pub struct TracingConfig {
   pub enable_thread_names: bool,
   pub enable_span_events: bool,
   pub custom_filter: Option<String>,
+  /// Also write logs as JSON to a file, kept across restarts
+  pub file: Option<FileSinkConfig>,
+  /// Write security audit events to a file of their own
+  pub audit_file: Option<FileSinkConfig>,
+  /// Report high-severity errors and panics to an error aggregator
+  pub error_reporting: Option<ErrorReportingConfig>,
 }
 
 impl TracingConfig {
@@ -82,6 +112,9 @@ impl TracingConfig {
       enable_thread_names: false,
       enable_span_events: false,
       custom_filter: None,
+      file: None,
+      audit_file: None,
+      error_reporting: None,
     }
   }
 
@@ -96,6 +129,9 @@ impl TracingConfig {
       enable_thread_names: true,
       enable_span_events: true,
       custom_filter: None,
+      file: None,
+      audit_file: None,
+      error_reporting: None,
     }
   }
 
@@ -110,6 +146,9 @@ impl TracingConfig {
       enable_thread_names: false,
       enable_span_events: false,
       custom_filter: None,
+      file: None,
+      audit_file: None,
+      error_reporting: None,
     }
   }
 
@@ -124,12 +163,25 @@ impl TracingConfig {
       enable_thread_names: false,
       enable_span_events: false,
       custom_filter: Some("warn".to_string()),
+      file: None,
+      audit_file: None,
+      error_reporting: None,
     }
   }
 
   /// Create environment filter based on config
   pub fn create_env_filter(&self) -> Result<EnvFilter> {
-    let base_filter = if let Some(custom) = &self.custom_filter {
+    Ok(
+      EnvFilter::builder()
+        .with_default_directive(self.default_level.into())
+        .parse_lossy(self.filter_directives()),
+    )
+  }
+
+  /// Filter directives of this config: the custom filter if set, otherwise
+  /// the environment's defaults
+  pub fn filter_directives(&self) -> String {
+    if let Some(custom) = &self.custom_filter {
       custom.clone()
     } else {
       match self.environment {
@@ -163,26 +215,77 @@ impl TracingConfig {
             .to_string()
         }
       }
-    };
-
-    Ok(
-      EnvFilter::builder()
-        .with_default_directive(self.default_level.into())
-        .parse_lossy(&base_filter),
-    )
+    }
   }
 }
 
+/// Target of security audit events, routed to the audit file when set
+const AUDIT_TARGET: &str = "security_audit";
+
+/// Keeps file sinks writing; buffered lines are flushed when dropped, so hold
+/// it until the process exits
+#[must_use = "dropping the guard stops file logging"]
+pub struct TracingGuard {
+  _guards: Vec<WorkerGuard>,
+  _error_reporting: Option<sentry::ClientInitGuard>,
+}
+
 /// Initialize tracing with improved configuration
-pub fn tracing_init() -> Result<()> {
-  tracing_init_with_config(TracingConfig::from_environment(Environment::from_env()))
+pub fn tracing_init() -> Result<TracingGuard> {
+  let environment = Environment::from_env();
+  let mut config = TracingConfig::from_environment(environment);
+  if environment != Environment::Testing {
+    config.file = FileSinkConfig::from_env("LOG_FILE")?;
+    config.audit_file = FileSinkConfig::from_env("AUDIT_LOG_FILE")?;
+    config.error_reporting = ErrorReportingConfig::from_env()?;
+  }
+  tracing_init_with_config(config)
 }
 
 /// Initialize tracing with custom configuration
-pub fn tracing_init_with_config(config: TracingConfig) -> Result<()> {
+pub fn tracing_init_with_config(config: TracingConfig) -> Result<TracingGuard> {
   color_eyre::install()?;
 
-  let env_filter = config.create_env_filter()?;
+  // Never reported from tests, whatever the config says
+  let reporter = match &config.error_reporting {
+    Some(reporting) if config.environment != Environment::Testing => {
+      Some(reporting.init(config.environment)?)
+    }
+    _ => None,
+  };
+  let sentry_layer = reporter.is_some().then(error_reporting::breadcrumb_layer);
+
+  let mut guards = Vec::new();
+  let file_layer = match &config.file {
+    Some(file) => {
+      let (writer, guard) = file.writer()?;
+      guards.push(guard);
+      Some(fmt::layer().json().with_ansi(false).with_writer(writer))
+    }
+    None => None,
+  };
+  let audit_layer = match &config.audit_file {
+    Some(file) => {
+      let (writer, guard) = file.writer()?;
+      guards.push(guard);
+      Some(
+        fmt::layer()
+          .json()
+          .with_ansi(false)
+          .with_writer(writer)
+          .with_filter(Targets::new().with_target(AUDIT_TARGET, LevelFilter::TRACE)),
+      )
+    }
+    None => None,
+  };
+
+  // Reloadable so directives can be changed without a restart
+  let (env_filter, reload_handle) = reload::Layer::new(config.create_env_filter()?);
+  log_filter::install(LogFilterHandle::new(
+    reload_handle,
+    config.default_level.into(),
+    config.filter_directives(),
+  ));
 
   let fmt_layer = fmt::layer()
     .with_target(false)
@@ -206,6 +309,9 @@ pub fn tracing_init_with_config(config: TracingConfig) -> Result<()> {
     .with(env_filter)
     .with(ErrorLayer::default())
     .with(fmt_layer)
+    .with(file_layer)
+    .with(audit_layer)
+    .with(sentry_layer)
     .init();
 
   // Log initialization info with our custom time format
@@ -213,14 +319,17 @@ pub fn tracing_init_with_config(config: TracingConfig) -> Result<()> {
       environment = ?config.environment,
       level = ?config.default_level,
       json_format = config.use_json_format,
+      log_file = ?config.file.as_ref().map(|file| &file.path),
+      audit_log_file = ?config.audit_file.as_ref().map(|file| &file.path),
+      error_reporting = reporter.is_some(),
       timestamp = time::format_time(time::now_utc()),
       "Tracing initialized"
   );
 
-  Ok(())
+  Ok(TracingGuard { _guards: guards, _error_reporting: reporter })
 }
 
 /// Initialize tracing specifically for tests
-pub fn tracing_init_test() -> Result<()> {
+pub fn tracing_init_test() -> Result<TracingGuard> {
   tracing_init_with_config(TracingConfig::testing())
 }
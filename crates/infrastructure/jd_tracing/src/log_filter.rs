@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{
+  sync::{Arc, Mutex, OnceLock},
+  time::Duration,
+};
+use tracing_subscriber::{EnvFilter, Registry, filter::LevelFilter, reload};
+
+/// Grants changing log filters at runtime.
+pub const SCOPE_LOGGING_ADMIN: &str = "logging:admin";
+
+static LOG_FILTER: OnceLock<LogFilterHandle> = OnceLock::new();
+
+/// The live log filter, once tracing is initialized.
+pub fn log_filter() -> Option<LogFilterHandle> {
+  LOG_FILTER.get().cloned()
+}
+
+pub(crate) fn install(handle: LogFilterHandle) {
+  let _ = LOG_FILTER.set(handle);
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LogFilterError {
+  #[error("Invalid log directives: {0}")]
+  InvalidDirectives(String),
+  #[error("Log filter could not be reloaded: {0}")]
+  Reload(String),
+}
+
+/// The filter in effect and, for a temporary change, when it reverts.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogFilterStatus {
+  /// Directives the process started with, or was reloaded with.
+  pub baseline: String,
+  pub directives: String,
+  pub expires_at: Option<DateTime<Utc>>,
+}
+
+struct FilterState {
+  baseline: String,
+  directives: String,
+  expires_at: Option<DateTime<Utc>>,
+  /// Bumped on every change, so a pending revert only undoes its own.
+  generation: u64,
+}
+
+struct Inner {
+  reload: reload::Handle<EnvFilter, Registry>,
+  default_level: LevelFilter,
+  state: Mutex<FilterState>,
+}
+
+/// Changes the directives of the running subscriber's `EnvFilter`.
+#[derive(Clone)]
+pub struct LogFilterHandle {
+  inner: Arc<Inner>,
+}
+
+impl LogFilterHandle {
+  pub(crate) fn new(
+    reload: reload::Handle<EnvFilter, Registry>,
+    default_level: LevelFilter,
+    baseline: String,
+  ) -> Self {
+    let state =
+      FilterState { directives: baseline.clone(), baseline, expires_at: None, generation: 0 };
+    Self { inner: Arc::new(Inner { reload, default_level, state: Mutex::new(state) }) }
+  }
+
+  pub fn status(&self) -> LogFilterStatus {
+    let state = self.inner.state.lock().unwrap_or_else(|e| e.into_inner());
+    self.status_of(&state)
+  }
+
+  /// Layer `overrides` over the baseline: a directive for a target replaces
+  /// the baseline's for that target, e.g. `sui_service=trace`. With `ttl`,
+  /// the baseline comes back once it passes unless the filter changed again
+  /// in between. Must be called within a Tokio runtime.
+  pub fn apply(
+    &self,
+    overrides: &str,
+    ttl: Option<Duration>,
+  ) -> Result<LogFilterStatus, LogFilterError> {
+    let mut state = self.inner.state.lock().unwrap_or_else(|e| e.into_inner());
+    let directives = merge_directives(&state.baseline, overrides);
+    let filter = self.parse(&directives)?;
+    self.inner.reload.reload(filter).map_err(|e| LogFilterError::Reload(e.to_string()))?;
+    state.generation += 1;
+    state.directives = directives;
+    state.expires_at =
+      ttl.and_then(|ttl| chrono::Duration::from_std(ttl).ok()).map(|ttl| Utc::now() + ttl);
+    tracing::warn!(
+      directives = %state.directives,
+      expires_at = ?state.expires_at,
+      "Log filter changed"
+    );
+
+    if let Some(ttl) = ttl {
+      let handle = self.clone();
+      let generation = state.generation;
+      tokio::spawn(async move {
+        tokio::time::sleep(ttl).await;
+        let _ = handle.revert(Some(generation));
+      });
+    }
+    Ok(self.status_of(&state))
+  }
+
+  /// Replace the baseline, e.g. when the config is reloaded. A temporary
+  /// change in effect is left alone and reverts to the new baseline.
+  pub fn set_baseline(&self, baseline: &str) -> Result<LogFilterStatus, LogFilterError> {
+    let filter = self.parse(baseline)?;
+    let mut state = self.inner.state.lock().unwrap_or_else(|e| e.into_inner());
+    if state.baseline == baseline {
+      return Ok(self.status_of(&state));
+    }
+    if state.expires_at.is_none() && state.directives == state.baseline {
+      self.inner.reload.reload(filter).map_err(|e| LogFilterError::Reload(e.to_string()))?;
+      state.generation += 1;
+      state.directives = baseline.to_string();
+    }
+    state.baseline = baseline.to_string();
+    tracing::warn!(baseline = %state.baseline, "Log filter baseline changed");
+    Ok(self.status_of(&state))
+  }
+
+  /// Go back to the baseline now.
+  pub fn reset(&self) -> Result<LogFilterStatus, LogFilterError> {
+    // Without a generation the baseline is always restored
+    Ok(self.revert(None)?.unwrap_or_else(|| self.status()))
+  }
+
+  /// Restore the baseline, only if the filter is still at `generation`
+  /// when one is given. None if it moved on.
+  fn revert(&self, generation: Option<u64>) -> Result<Option<LogFilterStatus>, LogFilterError> {
+    let mut state = self.inner.state.lock().unwrap_or_else(|e| e.into_inner());
+    if generation.is_some_and(|generation| generation != state.generation) {
+      return Ok(None);
+    }
+    let filter = EnvFilter::builder()
+      .with_default_directive(self.inner.default_level.into())
+      .parse_lossy(&state.baseline);
+    if let Err(e) = self.inner.reload.reload(filter) {
+      tracing::error!(error = %e, "Log filter could not be reverted");
+      return Err(LogFilterError::Reload(e.to_string()));
+    }
+    state.generation += 1;
+    state.directives = state.baseline.clone();
+    state.expires_at = None;
+    tracing::warn!(directives = %state.directives, "Log filter reverted to baseline");
+    Ok(Some(self.status_of(&state)))
+  }
+
+  fn parse(&self, directives: &str) -> Result<EnvFilter, LogFilterError> {
+    EnvFilter::builder()
+      .with_default_directive(self.inner.default_level.into())
+      .parse(directives)
+      .map_err(|e| LogFilterError::InvalidDirectives(e.to_string()))
+  }
+
+  fn status_of(&self, state: &FilterState) -> LogFilterStatus {
+    LogFilterStatus {
+      baseline: state.baseline.clone(),
+      directives: state.directives.clone(),
+      expires_at: state.expires_at,
+    }
+  }
+}
+
+/// Baseline directives not overridden, followed by `overrides`. A directive
+/// is keyed by what precedes `=`; a bare level sets the default.
+fn merge_directives(baseline: &str, overrides: &str) -> String {
+  let key =
+    |directive: &str| directive.split_once('=').map_or("", |(target, _)| target).to_string();
+  let split = |directives: &str| -> Vec<String> {
+    directives.split(',').map(str::trim).filter(|d| !d.is_empty()).map(str::to_string).collect()
+  };
+  let overrides = split(overrides);
+  let overridden: Vec<String> = overrides.iter().map(|d| key(d)).collect();
+  split(baseline)
+    .into_iter()
+    .filter(|directive| !overridden.contains(&key(directive)))
+    .chain(overrides)
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn overrides_replace_the_baseline_for_their_targets() {
+    let baseline = "info,sqlx=warn, sui_service=info";
+    assert_eq!(merge_directives(baseline, "sui_service=trace"), "info,sqlx=warn,sui_service=trace");
+    assert_eq!(merge_directives(baseline, "debug"), "sqlx=warn,sui_service=info,debug");
+    assert_eq!(merge_directives(baseline, ""), "info,sqlx=warn,sui_service=info");
+  }
+}
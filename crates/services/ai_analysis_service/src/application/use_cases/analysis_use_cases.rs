@@ -1,18 +1,34 @@
+use crate::domain::ai_budget::{binding_budget, month_start, BudgetPolicy, BudgetScope};
 use crate::domain::analysis_engine::AnalysisEngine;
-use crate::domain::analysis_models::{AnalysisRequest, AnalysisResult, AnalysisType};
-use crate::domain::analysis_repository_trait::AnalysisRepository;
-use crate::domain::llm_provider_trait::LLMProvider;
+use crate::domain::analysis_models::{
+    AnalysisRequest, AnalysisResult, AnalysisType, CompilationStatus, FindingKind, VulnerabilityFinding, VulnerabilityType,
+};
+use crate::domain::analysis_repository_trait::{AnalysisRepository, VulnerabilityFilter, VulnerabilityStatus};
+use crate::domain::analysis_ruleset::Ruleset;
+use crate::domain::bytecode_analyzer_trait::{is_within, move_packages, BytecodeAnalyzer, MOVE_MANIFEST};
+use crate::domain::change_set::{direct_dependencies, ChangeSetProvider};
+use crate::domain::dependency_advisory::version_key;
+use crate::domain::dependency_manifest::{self, is_dependency_file, Ecosystem};
+use crate::domain::finding_fingerprint::fingerprints;
+use crate::domain::leak_notifier_trait::LeakNotifier;
+use crate::domain::patch_regression::PatchRegressionCheck;
+use crate::domain::regression_notifier_trait::RegressionNotifier;
+use crate::domain::llm_provider_trait::{usage_since, LLMProvider, ProviderUsage};
 use crate::error::{Error, Result};
 use crate::models::requests::{AnalyzeRepositoryRequest, AnalyzeCodeRequest, MarkVulnerabilityRequest, VulnerabilityAction};
 use crate::models::responses::{AnalysisResponse, DetailedAnalysisResponse, CodeAnalysisResponse, AnalysisStatusResponse};
 use crate::domain::analysis_repository_trait::VulnerabilityStatistics;
-use std::collections::HashMap;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::{info, warn, error};
 
 pub struct AnalysisUseCases {
     analysis_engine: AnalysisEngine,
     analysis_repository: Arc<dyn AnalysisRepository>,
+    bytecode_analyzer: Option<Arc<dyn BytecodeAnalyzer>>,
+    leak_notifier: Option<Arc<dyn LeakNotifier>>,
+    regression_notifier: Option<Arc<dyn RegressionNotifier>>,
 }
 
 impl AnalysisUseCases {
@@ -28,9 +44,39 @@ impl AnalysisUseCases {
         Self {
             analysis_engine,
             analysis_repository,
+            bytecode_analyzer: None,
+            leak_notifier: None,
+            regression_notifier: None,
         }
     }
 
+    /// Compile Move packages whose manifest is among the analysed files and
+    /// add the findings in their bytecode to static analyses.
+    pub fn with_bytecode_analyzer(mut self, analyzer: Arc<dyn BytecodeAnalyzer>) -> Self {
+        self.bytecode_analyzer = Some(analyzer);
+        self
+    }
+
+    /// Alert the repository's owners when an analysis finds secrets that no
+    /// earlier analysis reported.
+    pub fn with_leak_notifier(mut self, notifier: Arc<dyn LeakNotifier>) -> Self {
+        self.leak_notifier = Some(notifier);
+        self
+    }
+
+    /// Tell the people following a repository's patches when an analysis
+    /// finds a merged patch reverted or its finding back.
+    pub fn with_regression_notifier(mut self, notifier: Arc<dyn RegressionNotifier>) -> Self {
+        self.regression_notifier = Some(notifier);
+        self
+    }
+
+    /// Whether a file at this path may be analyzed, before its content is
+    /// fetched.
+    pub fn supports_path(&self, file_path: &str) -> bool {
+        self.analysis_engine.supports_path(file_path)
+    }
+
     pub async fn analyze_repository(
         &self,
         request: AnalyzeRepositoryRequest,
@@ -38,33 +84,447 @@ impl AnalysisUseCases {
     ) -> Result<AnalysisResponse> {
         info!("Starting repository analysis for repository: {}", request.repository_id);
 
-        // Create internal analysis request
-        let analysis_request = AnalysisRequest {
+        let scope = if request.files_to_analyze.is_some() { "partial" } else { "full" };
+        let ruleset = self.ruleset(request.repository_id).await?;
+        let ruleset_fingerprint = ruleset.fingerprint();
+        let mut analysis_request = Self::analysis_request(request, ruleset);
+        let budget = self.enforce_budget(&mut analysis_request).await?;
+        let bytecode_files = self.bytecode_files(&analysis_request.analysis_types, &file_contents);
+        let dependency_files = dependency_files(&analysis_request.analysis_types, &file_contents);
+        let contract_files = self.contract_files(file_contents)?;
+        let regression_check = self.regression_check(analysis_request.repository_id, &contract_files).await;
+        let mut analyzed_files: Vec<String> = contract_files.keys().cloned().collect();
+        let score_files = (bytecode_files.is_some() || dependency_files.is_some()).then(|| contract_files.clone());
+
+        let usage_before = self.analysis_engine.llm_usage();
+        let mut final_result = self.run_analysis(analysis_request, contract_files).await?;
+        let llm_usage = usage_since(&usage_before, &self.analysis_engine.llm_usage());
+        let mut added = false;
+        if let Some(bytecode_files) = bytecode_files {
+            added |= self.add_bytecode_findings(&mut final_result, &bytecode_files, None).await;
+        }
+        if let Some(dependency_files) = dependency_files {
+            let checked = self.add_dependency_findings(&mut final_result, &dependency_files).await;
+            // Dependency findings are only closed when the advisories were checked
+            if let Some(dependency_findings) = checked {
+                added |= dependency_findings > 0;
+                analyzed_files.extend(dependency_files.into_keys());
+            }
+        }
+        if let (true, Some(score_files)) = (added, score_files) {
+            let (security_score, quality_score) = self.analysis_engine
+                .calculate_scores(&final_result.vulnerabilities, &score_files);
+            final_result.security_score = security_score;
+            final_result.quality_score = quality_score;
+        }
+        tag_raw_results(&mut final_result.raw_results, json!({
+            "scope": scope,
+            "ruleset": ruleset_fingerprint
+        }));
+        if let Some(budget) = budget {
+            tag_raw_results(&mut final_result.raw_results, json!({ "budget": budget }));
+        }
+
+        self.finish_analysis(final_result, &analyzed_files, &llm_usage, regression_check).await
+    }
+
+    /// Analyze only the files changed since the repository's last full or
+    /// incremental analysis, with the files that import them or that they
+    /// import. Findings in the other files carry over from that analysis.
+    /// Falls back to a full analysis when there is no earlier analysis, the
+    /// changes cannot be listed or the repository's ruleset has changed since.
+    pub async fn analyze_repository_incremental(
+        &self,
+        request: AnalyzeRepositoryRequest,
+        file_contents: HashMap<String, String>,
+        changes: &dyn ChangeSetProvider,
+    ) -> Result<AnalysisResponse> {
+        if request.files_to_analyze.is_some() {
+            return self.analyze_repository(request, file_contents).await;
+        }
+
+        let baseline = self.analysis_repository
+            .get_latest_baseline_analysis(request.repository_id, &stored_analysis_type(&request.analysis_types))
+            .await?;
+        let Some(baseline) = baseline else {
+            info!("Repository {} has no earlier analysis; analysing it in full", request.repository_id);
+            return self.analyze_repository(request, file_contents).await;
+        };
+        let ruleset = self.ruleset(request.repository_id).await?;
+        let ruleset_fingerprint = ruleset.fingerprint();
+        // Analyses from before rulesets ran the built-in patterns alone
+        let baseline_fingerprint = baseline.raw_results.get("ruleset")
+            .and_then(|fingerprint| fingerprint.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| Ruleset::default().fingerprint());
+        if baseline_fingerprint != ruleset_fingerprint {
+            info!("Ruleset of repository {} changed since {}; analysing it in full", request.repository_id, baseline.commit_sha);
+            return self.analyze_repository(request, file_contents).await;
+        }
+        let Some(change_set) = changes.changed_files(&baseline.commit_sha, &request.commit_sha).await? else {
+            info!("Changes since {} cannot be listed; analysing repository {} in full", baseline.commit_sha, request.repository_id);
+            return self.analyze_repository(request, file_contents).await;
+        };
+
+        let (repository_id, commit_sha) = (request.repository_id, request.commit_sha.clone());
+        let mut analysis_request = Self::analysis_request(request, ruleset);
+        let budget = self.enforce_budget(&mut analysis_request).await?;
+        let bytecode_files = self.bytecode_files(&analysis_request.analysis_types, &file_contents);
+        let dependency_files = dependency_files(&analysis_request.analysis_types, &file_contents);
+        let contract_files = self.contract_files(file_contents)?;
+        let regression_check = self.regression_check(repository_id, &contract_files).await;
+
+        let changed: HashSet<String> = change_set.changed
+            .into_iter()
+            .filter(|path| contract_files.contains_key(path))
+            .collect();
+        let mut scope = direct_dependencies(&changed, &contract_files);
+        scope.extend(changed.iter().cloned());
+        info!(
+            "{} files of repository {} changed since {}; analysing {} with their dependencies",
+            changed.len(), repository_id, baseline.commit_sha, scope.len()
+        );
+
+        // Findings outside the scope carry over, except in files that are gone
+        let mut resolved_files = scope.clone();
+        resolved_files.extend(change_set.removed);
+        let mut carried = Vec::new();
+        let mut carried_dependencies = Vec::new();
+        for finding in baseline.vulnerabilities {
+            // Dependencies are checked again on every run, advisories change
+            if finding.kind == FindingKind::Dependency {
+                carried_dependencies.push(finding);
+                continue;
+            }
+            if scope.contains(&finding.file_path) {
+                continue;
+            }
+            if contract_files.contains_key(&finding.file_path) {
+                carried.push(finding);
+            } else {
+                resolved_files.insert(finding.file_path.clone());
+            }
+        }
+
+        let scope_files: HashMap<String, String> = contract_files
+            .iter()
+            .filter(|(path, _)| scope.contains(*path))
+            .map(|(path, content)| (path.clone(), content.clone()))
+            .collect();
+        let usage_before = self.analysis_engine.llm_usage();
+        let mut final_result = if scope_files.is_empty() {
+            AnalysisResult {
+                id: uuid::Uuid::new_v4(),
+                repository_id,
+                commit_sha,
+                analysis_type: baseline.analysis_type,
+                security_score: 0.0,
+                quality_score: 0.0,
+                vulnerabilities: Vec::new(),
+                recommendations: Vec::new(),
+                analysis_duration_ms: 0,
+                analyzer_version: baseline.analyzer_version,
+                raw_results: json!({}),
+                created_at: chrono::Utc::now(),
+            }
+        } else {
+            let mut result = self.run_analysis(analysis_request, scope_files).await?;
+            // Only packages with files in scope are compiled again
+            if let Some(mut bytecode_files) = bytecode_files {
+                let packages = move_packages(bytecode_files.keys());
+                let touched: Vec<&String> = packages
+                    .iter()
+                    .filter(|package| scope.iter().any(|path| is_within(path, package)))
+                    .collect();
+                bytecode_files.retain(|path, _| touched.iter().any(|package| is_within(path, package)));
+                if !touched.is_empty() {
+                    self.add_bytecode_findings(&mut result, &bytecode_files, Some(&scope)).await;
+                }
+            }
+            result
+        };
+        let llm_usage = usage_since(&usage_before, &self.analysis_engine.llm_usage());
+        let checked = match &dependency_files {
+            Some(dependency_files) => self.add_dependency_findings(&mut final_result, dependency_files).await,
+            None => None,
+        };
+        match (checked, dependency_files) {
+            (Some(_), Some(dependency_files)) => resolved_files.extend(dependency_files.into_keys()),
+            (None, Some(_)) => carried.extend(carried_dependencies),
+            // No manifest is left to pull the dependencies in
+            (_, None) => resolved_files.extend(carried_dependencies.into_iter().map(|finding| finding.file_path)),
+        }
+        let carried_count = carried.len();
+
+        // Scores cover the whole repository, not only the files analysed
+        final_result.vulnerabilities.extend(carried);
+        let (security_score, quality_score) = self.analysis_engine
+            .calculate_scores(&final_result.vulnerabilities, &contract_files);
+        final_result.security_score = security_score;
+        final_result.quality_score = quality_score;
+        tag_raw_results(&mut final_result.raw_results, json!({
+            "scope": "incremental",
+            "ruleset": ruleset_fingerprint,
+            "base_commit_sha": baseline.commit_sha,
+            "changed_files": changed.len(),
+            "files_analyzed": scope.len(),
+            "findings_carried_over": carried_count
+        }));
+
+        if let Some(budget) = budget {
+            tag_raw_results(&mut final_result.raw_results, json!({ "budget": budget }));
+        }
+
+        let resolved_files: Vec<String> = resolved_files.into_iter().collect();
+        self.finish_analysis(final_result, &resolved_files, &llm_usage, regression_check).await
+    }
+
+    /// The Move sources and package manifests among `file_contents`, when a
+    /// bytecode analyzer is configured, a static analysis was asked for and
+    /// there is a package to compile.
+    fn bytecode_files(
+        &self,
+        analysis_types: &[AnalysisType],
+        file_contents: &HashMap<String, String>,
+    ) -> Option<HashMap<String, String>> {
+        self.bytecode_analyzer.as_ref()?;
+        if !analysis_types.iter().any(|analysis_type| {
+            matches!(analysis_type, AnalysisType::StaticAnalysis | AnalysisType::VulnerabilityDetection)
+        }) {
+            return None;
+        }
+        let files: HashMap<String, String> = file_contents
+            .iter()
+            .filter(|(path, _)| path.ends_with(".move") || path.rsplit('/').next() == Some(MOVE_MANIFEST))
+            .map(|(path, content)| (path.clone(), content.clone()))
+            .collect();
+        (!move_packages(files.keys()).is_empty()).then_some(files)
+    }
+
+    /// Compile the Move packages among `files` and add their bytecode
+    /// findings, only those in `scope` when given. The result's Move findings
+    /// record how their package compiled. Returns whether findings were added.
+    async fn add_bytecode_findings(
+        &self,
+        result: &mut AnalysisResult,
+        files: &HashMap<String, String>,
+        scope: Option<&HashSet<String>>,
+    ) -> bool {
+        let Some(analyzer) = &self.bytecode_analyzer else {
+            return false;
+        };
+        let analysis = match analyzer.analyze_packages(files).await {
+            Ok(analysis) => analysis,
+            Err(e) => {
+                warn!("Bytecode analysis of repository {} failed: {}", result.repository_id, e);
+                return false;
+            }
+        };
+
+        for finding in &mut result.vulnerabilities {
+            if finding.file_path.ends_with(".move") {
+                finding.compilation_status = analysis.status_of(&finding.file_path);
+            }
+        }
+        let findings: Vec<_> = analysis.findings
+            .into_iter()
+            .filter(|finding| scope.map_or(true, |scope| scope.contains(&finding.file_path)))
+            .map(|mut finding| {
+                finding.compilation_status = Some(CompilationStatus::Compiled);
+                finding
+            })
+            .collect();
+        let added = !findings.is_empty();
+        result.vulnerabilities.extend(findings);
+        tag_raw_results(&mut result.raw_results, json!({
+            "bytecode": { "analyzer": analyzer.name(), "packages": analysis.packages }
+        }));
+        added
+    }
+
+    /// Check the dependencies pinned by the manifests and lock files among
+    /// `files` against the synced advisories and add a finding for each one
+    /// an advisory affects. Returns how many were added, or `None` when the
+    /// advisories could not be read.
+    async fn add_dependency_findings(
+        &self,
+        result: &mut AnalysisResult,
+        files: &HashMap<String, String>,
+    ) -> Option<usize> {
+        let dependencies = dependency_manifest::dependencies(files);
+        let mut packages: HashMap<Ecosystem, Vec<String>> = HashMap::new();
+        for dependency in &dependencies {
+            packages.entry(dependency.ecosystem).or_default().push(dependency.name.clone());
+        }
+        let mut advisories = Vec::new();
+        for (ecosystem, names) in packages {
+            match self.analysis_repository.find_dependency_advisories(ecosystem, &names).await {
+                Ok(found) => advisories.extend(found),
+                Err(e) => {
+                    warn!("Dependency advisories for repository {} could not be read: {}", result.repository_id, e);
+                    return None;
+                }
+            }
+        }
+
+        let mut findings = Vec::new();
+        let mut unversioned = 0;
+        for dependency in &dependencies {
+            let Some(version) = &dependency.version else {
+                unversioned += 1;
+                continue;
+            };
+            // Branches such as `framework/mainnet` only match advisories listing them
+            if version_key(version).is_none() {
+                unversioned += 1;
+            }
+            findings.extend(
+                advisories
+                    .iter()
+                    .filter(|advisory| advisory.ecosystem == dependency.ecosystem)
+                    .filter(|advisory| advisory.package.eq_ignore_ascii_case(&dependency.name))
+                    .filter(|advisory| advisory.affects(version))
+                    .map(|advisory| advisory.finding(dependency)),
+            );
+        }
+        let added = findings.len();
+        result.vulnerabilities.extend(findings);
+        tag_raw_results(&mut result.raw_results, json!({
+            "dependencies": {
+                "checked": dependencies.len(),
+                "unversioned": unversioned,
+                "vulnerable": added
+            }
+        }));
+        Some(added)
+    }
+
+    /// The result's unsuppressed secret findings that are not already open in
+    /// the repository. None when the open findings cannot be read, so an
+    /// outage does not re-send every alert.
+    async fn new_secrets(&self, result: &AnalysisResult) -> Vec<VulnerabilityFinding> {
+        let secrets: Vec<(&VulnerabilityFinding, String)> = result.vulnerabilities
+            .iter()
+            .zip(fingerprints(&result.vulnerabilities))
+            .filter(|(finding, _)| finding.vulnerability_type == VulnerabilityType::SecretExposure)
+            .filter(|(finding, _)| !finding.is_false_positive)
+            .collect();
+        if secrets.is_empty() {
+            return Vec::new();
+        }
+
+        let filter = VulnerabilityFilter {
+            repository_id: Some(result.repository_id),
+            status: Some(VulnerabilityStatus::Open),
+            cwe_id: VulnerabilityType::SecretExposure.cwe_id().map(str::to_string),
+            limit: 1000,
+            ..Default::default()
+        };
+        let open: HashSet<String> = match self.analysis_repository.list_vulnerabilities(&filter).await {
+            Ok((records, _)) => records.into_iter().filter_map(|record| record.fingerprint).collect(),
+            Err(e) => {
+                warn!("Open secret findings of repository {} could not be read: {}", result.repository_id, e);
+                return Vec::new();
+            }
+        };
+        secrets
+            .into_iter()
+            .filter(|(_, fingerprint)| !open.contains(fingerprint))
+            .map(|(finding, _)| finding.clone())
+            .collect()
+    }
+
+    /// Apply the spent budget covering the repository, if any, to a request
+    /// for LLM analyses: drop them, refuse the request, or let it through on
+    /// an approval. Returns what was applied, for the raw results.
+    async fn enforce_budget(&self, request: &mut AnalysisRequest) -> Result<Option<serde_json::Value>> {
+        let uses_llm = |analysis_type: &AnalysisType| {
+            matches!(analysis_type, AnalysisType::LLMReview | AnalysisType::CodeQualityAssessment)
+        };
+        if !self.analysis_engine.has_llm_provider() || !request.analysis_types.iter().any(uses_llm) {
+            return Ok(None);
+        }
+        let statuses = self.analysis_repository
+            .get_budget_statuses(BudgetScope::Repository, request.repository_id, month_start(chrono::Utc::now()))
+            .await?;
+        let Some(binding) = binding_budget(&statuses) else {
+            return Ok(None);
+        };
+        let scope = binding.budget.scope.as_str().to_string();
+        let applied = json!({
+            "scope": scope,
+            "scope_id": binding.budget.scope_id,
+            "policy": binding.budget.policy.as_str(),
+            "monthly_limit_usd": binding.budget.monthly_limit_usd,
+            "spent_usd": binding.spent_usd
+        });
+
+        match binding.budget.policy {
+            BudgetPolicy::Block => Err(Error::AiBudgetExceeded { scope, limit_usd: binding.budget.monthly_limit_usd }),
+            BudgetPolicy::RequireApproval => {
+                if self.analysis_repository.take_budget_approval(request.repository_id).await? {
+                    info!("Repository {} is over its {} AI budget; running on approval", request.repository_id, scope);
+                    return Ok(Some(applied));
+                }
+                let approval = self.analysis_repository
+                    .request_budget_approval(request.repository_id, &request.commit_sha)
+                    .await?;
+                Err(Error::AiBudgetApprovalRequired { scope, approval_id: approval.id })
+            }
+            BudgetPolicy::StaticOnly => {
+                info!("Repository {} is over its {} AI budget; analysing statically", request.repository_id, scope);
+                request.analysis_types.retain(|analysis_type| !uses_llm(analysis_type));
+                if request.analysis_types.is_empty() {
+                    request.analysis_types.push(AnalysisType::StaticAnalysis);
+                }
+                Ok(Some(applied))
+            }
+        }
+    }
+
+    /// The repository's configured ruleset, or the default one.
+    async fn ruleset(&self, repository_id: uuid::Uuid) -> Result<Ruleset> {
+        let ruleset = self.analysis_repository.get_ruleset(repository_id).await?;
+        Ok(ruleset.map(|ruleset| ruleset.rules).unwrap_or_default())
+    }
+
+    fn analysis_request(request: AnalyzeRepositoryRequest, ruleset: Ruleset) -> AnalysisRequest {
+        AnalysisRequest {
             repository_id: request.repository_id,
-            commit_sha: request.commit_sha.clone(),
+            commit_sha: request.commit_sha,
             files_to_analyze: request.files_to_analyze.unwrap_or_default(),
             analysis_types: if request.analysis_types.is_empty() {
                 vec![AnalysisType::StaticAnalysis] // Default to static analysis
             } else {
                 request.analysis_types
             },
-        };
+            ruleset,
+        }
+    }
 
-        // Filter to only Move files if no specific files requested
-        let move_files: HashMap<String, String> = file_contents
+    /// Keep the files a static analyzer checks: Move, Solidity and Solana programs
+    fn contract_files(&self, file_contents: HashMap<String, String>) -> Result<HashMap<String, String>> {
+        let contract_files: HashMap<String, String> = file_contents
             .into_iter()
-            .filter(|(path, _)| path.ends_with(".move"))
+            .filter(|(path, content)| self.analysis_engine.supports_file(path, content))
             .collect();
 
-        if move_files.is_empty() {
+        if contract_files.is_empty() {
             return Err(Error::AnalysisFailed {
-                message: "No Move files found in repository".to_string(),
+                message: "No supported smart contract files found in repository".to_string(),
             });
         }
+        Ok(contract_files)
+    }
 
-        // Run analysis
+    /// Run the requested analyses, merged into one result.
+    async fn run_analysis(
+        &self,
+        request: AnalysisRequest,
+        files: HashMap<String, String>,
+    ) -> Result<AnalysisResult> {
         let analysis_results = self.analysis_engine
-            .analyze_repository(analysis_request, move_files)
+            .analyze_repository(request, files)
             .await?;
 
         if analysis_results.is_empty() {
@@ -74,10 +534,91 @@ impl AnalysisUseCases {
         }
 
         // Merge results if multiple analysis types were run
-        let final_result = if analysis_results.len() == 1 {
-            analysis_results.into_iter().next().unwrap()
+        if analysis_results.len() == 1 {
+            Ok(analysis_results.into_iter().next().unwrap())
         } else {
-            self.analysis_engine.merge_analysis_results(analysis_results)?
+            self.analysis_engine.merge_analysis_results(analysis_results)
+        }
+    }
+
+    /// The repository's merged patches, to check the analysis of `files`
+    /// against once it is done. Empty when they cannot be read, so an outage
+    /// does not fail the analysis.
+    async fn regression_check(&self, repository_id: uuid::Uuid, files: &HashMap<String, String>) -> PatchRegressionCheck {
+        match self.analysis_repository.list_merged_patches(repository_id).await {
+            Ok(patches) => PatchRegressionCheck::new(patches, files),
+            Err(e) => {
+                warn!("Merged patches of repository {} could not be read: {}", repository_id, e);
+                PatchRegressionCheck::default()
+            }
+        }
+    }
+
+    /// Flag the merged patches the stored analysis shows regressed and tell
+    /// the people following them.
+    async fn flag_regressions(&self, check: &PatchRegressionCheck, result: &AnalysisResult, analysis_id: uuid::Uuid) {
+        let regressions = check.regressions(&result.vulnerabilities);
+        if regressions.is_empty() {
+            return;
+        }
+        let flagged = match self.analysis_repository.flag_patch_regressions(analysis_id, &regressions).await {
+            Ok(flagged) => flagged,
+            Err(e) => {
+                warn!("Regressed patches of analysis {} could not be flagged: {}", analysis_id, e);
+                return;
+            }
+        };
+        if flagged.is_empty() {
+            return;
+        }
+        warn!("Analysis {} found {} merged patches regressed in repository {}", analysis_id, flagged.len(), result.repository_id);
+        if let Some(notifier) = &self.regression_notifier {
+            if let Err(e) = notifier.notify_regressed(result.repository_id, &result.commit_sha, &flagged).await {
+                warn!("Patch regression notification for repository {} failed: {}", result.repository_id, e);
+            }
+        }
+    }
+
+    /// Apply suppressions, store the result with the LLM usage it took, close
+    /// the findings in `analyzed_files` it no longer reports and flag the
+    /// merged patches it shows regressed.
+    async fn finish_analysis(
+        &self,
+        mut final_result: AnalysisResult,
+        analyzed_files: &[String],
+        llm_usage: &[ProviderUsage],
+        regression_check: PatchRegressionCheck,
+    ) -> Result<AnalysisResponse> {
+        let repository_id = final_result.repository_id;
+        if !llm_usage.is_empty() {
+            let cost_usd: f64 = llm_usage.iter().map(|usage| usage.cost_usd).sum();
+            tag_raw_results(&mut final_result.raw_results, json!({
+                "llm_usage": llm_usage,
+                "llm_cost_usd": cost_usd
+            }));
+        }
+
+        // Findings triaged on an earlier run stay suppressed
+        let suppressed: HashSet<String> = self.analysis_repository
+            .list_suppressions(repository_id, false)
+            .await?
+            .into_iter()
+            .map(|suppression| suppression.fingerprint)
+            .collect();
+        if !suppressed.is_empty() {
+            let fingerprints = fingerprints(&final_result.vulnerabilities);
+            for (vulnerability, fingerprint) in final_result.vulnerabilities.iter_mut().zip(fingerprints) {
+                if suppressed.contains(&fingerprint) {
+                    vulnerability.is_false_positive = true;
+                }
+            }
+        }
+
+        // Read what was already reported before this analysis is stored over it
+        let new_secrets = if self.leak_notifier.is_some() {
+            self.new_secrets(&final_result).await
+        } else {
+            Vec::new()
         };
 
         // Save to database
@@ -85,17 +626,47 @@ impl AnalysisUseCases {
             .save_analysis_result(&final_result)
             .await?;
 
-        info!("Analysis completed for repository: {} with ID: {}", request.repository_id, analysis_id);
+        if !llm_usage.is_empty() {
+            let recorded = self.analysis_repository.record_ai_usage(repository_id, Some(analysis_id), llm_usage).await;
+            if let Err(e) = recorded {
+                warn!("LLM usage of analysis {} could not be recorded: {}", analysis_id, e);
+            }
+        }
+
+        if let (Some(notifier), false) = (&self.leak_notifier, new_secrets.is_empty()) {
+            match notifier.notify(repository_id, &final_result.commit_sha, &new_secrets).await {
+                Ok(()) => info!("Notified owners of repository {} of {} leaked secrets", repository_id, new_secrets.len()),
+                Err(e) => warn!("Secret leak notification for repository {} failed: {}", repository_id, e),
+            }
+        }
+
+        // Close findings that no longer show up in the files just analysed
+        let resolved = self.analysis_repository
+            .resolve_missing_vulnerabilities(repository_id, analysis_id, analyzed_files)
+            .await?;
+        if resolved > 0 {
+            info!("Resolved {} vulnerabilities no longer reported in repository {}", resolved, repository_id);
+        }
+
+        if !regression_check.is_empty() {
+            self.flag_regressions(&regression_check, &final_result, analysis_id).await;
+        }
+
+        info!("Analysis completed for repository: {} with ID: {}", repository_id, analysis_id);
 
         Ok(AnalysisResponse {
             analysis_id,
-            repository_id: final_result.repository_id,
+            repository_id,
             commit_sha: final_result.commit_sha,
             security_score: final_result.security_score,
             quality_score: final_result.quality_score,
-            vulnerabilities_found: final_result.vulnerabilities.len() as u32,
+            vulnerabilities_found: final_result.vulnerabilities
+                .iter()
+                .filter(|v| !v.is_false_positive)
+                .count() as u32,
             critical_vulnerabilities: final_result.vulnerabilities
                 .iter()
+                .filter(|v| !v.is_false_positive)
                 .filter(|v| matches!(v.severity, crate::domain::analysis_models::Severity::Critical))
                 .count() as u32,
             analysis_duration_ms: final_result.analysis_duration_ms,
@@ -119,18 +690,35 @@ impl AnalysisUseCases {
             } else {
                 request.analysis_types
             },
+            ruleset: Ruleset::default(),
         };
 
         let analysis_results = self.analysis_engine
             .analyze_repository(analysis_request, file_contents)
             .await?;
 
-        let final_result = if analysis_results.len() == 1 {
+        let mut final_result = if analysis_results.len() == 1 {
             analysis_results.into_iter().next().unwrap()
         } else {
             self.analysis_engine.merge_analysis_results(analysis_results)?
         };
 
+        // Findings triaged on an earlier run stay suppressed
+        let suppressed: HashSet<String> = self.analysis_repository
+            .list_suppressions(final_result.repository_id, false)
+            .await?
+            .into_iter()
+            .map(|suppression| suppression.fingerprint)
+            .collect();
+        if !suppressed.is_empty() {
+            let fingerprints = fingerprints(&final_result.vulnerabilities);
+            for (vulnerability, fingerprint) in final_result.vulnerabilities.iter_mut().zip(fingerprints) {
+                if suppressed.contains(&fingerprint) {
+                    vulnerability.is_false_positive = true;
+                }
+            }
+        }
+
         let analysis_duration = start_time.elapsed();
 
         Ok(CodeAnalysisResponse {
@@ -257,4 +845,39 @@ impl AnalysisUseCases {
             total_count: analyses.len(),
         })
     }
-}
\ No newline at end of file
+}
+
+/// The type an analysis of `analysis_types` is stored as; several analyses
+/// are merged into a static one.
+fn stored_analysis_type(analysis_types: &[AnalysisType]) -> AnalysisType {
+    match analysis_types {
+        [analysis_type] => analysis_type.clone(),
+        _ => AnalysisType::StaticAnalysis,
+    }
+}
+
+/// The manifests and lock files among `file_contents`, when a static
+/// analysis was asked for and there are any.
+fn dependency_files(
+    analysis_types: &[AnalysisType],
+    file_contents: &HashMap<String, String>,
+) -> Option<HashMap<String, String>> {
+    if !analysis_types.iter().any(|analysis_type| {
+        matches!(analysis_type, AnalysisType::StaticAnalysis | AnalysisType::VulnerabilityDetection)
+    }) {
+        return None;
+    }
+    let files: HashMap<String, String> = file_contents
+        .iter()
+        .filter(|(path, _)| is_dependency_file(path))
+        .map(|(path, content)| (path.clone(), content.clone()))
+        .collect();
+    (!files.is_empty()).then_some(files)
+}
+
+/// Add `fields` to an analysis's raw results.
+fn tag_raw_results(raw_results: &mut serde_json::Value, fields: serde_json::Value) {
+    if let (Some(raw_results), serde_json::Value::Object(fields)) = (raw_results.as_object_mut(), fields) {
+        raw_results.extend(fields);
+    }
+}
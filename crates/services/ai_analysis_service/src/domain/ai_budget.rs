@@ -0,0 +1,223 @@
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Grants setting AI budgets and deciding their approval requests.
+pub const SCOPE_ANALYTICS_BUDGETS: &str = "analytics:budgets";
+
+/// What a budget caps the LLM spend of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetScope {
+    Repository,
+    /// Every repository the organization has added.
+    Organization,
+}
+
+impl BudgetScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetScope::Repository => "repository",
+            BudgetScope::Organization => "organization",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "organization" => BudgetScope::Organization,
+            _ => BudgetScope::Repository,
+        }
+    }
+}
+
+/// What happens to analyses asking for an LLM once a budget is spent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPolicy {
+    /// Run the static analyses alone.
+    StaticOnly,
+    /// Refuse the analysis until someone approves it.
+    RequireApproval,
+    /// Refuse the analysis until the next month.
+    Block,
+}
+
+impl BudgetPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetPolicy::StaticOnly => "static_only",
+            BudgetPolicy::RequireApproval => "require_approval",
+            BudgetPolicy::Block => "block",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "static_only" => BudgetPolicy::StaticOnly,
+            "require_approval" => BudgetPolicy::RequireApproval,
+            _ => BudgetPolicy::Block,
+        }
+    }
+}
+
+/// A monthly cap on the LLM spend of a repository or organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiBudget {
+    pub scope: BudgetScope,
+    pub scope_id: Uuid,
+    pub monthly_limit_usd: f64,
+    pub policy: BudgetPolicy,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A budget and what has been spent against it this month.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetStatus {
+    #[serde(flatten)]
+    pub budget: AiBudget,
+    pub spent_usd: f64,
+}
+
+impl BudgetStatus {
+    pub fn remaining_usd(&self) -> f64 {
+        (self.budget.monthly_limit_usd - self.spent_usd).max(0.0)
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.spent_usd >= self.budget.monthly_limit_usd
+    }
+}
+
+/// The spent budget with the strictest policy among `statuses`, which
+/// decides what happens to an analysis they all cover. `None` while every
+/// budget has money left.
+pub fn binding_budget(statuses: &[BudgetStatus]) -> Option<&BudgetStatus> {
+    statuses
+        .iter()
+        .filter(|status| status.is_exceeded())
+        .max_by_key(|status| status.budget.policy)
+}
+
+/// Start of the calendar month, in UTC, that `at` falls in. Budgets reset
+/// then.
+pub fn month_start(at: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(at.year(), at.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(at)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Denied,
+    /// Approved, and spent on the analysis it let through.
+    Used,
+}
+
+impl ApprovalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalStatus::Pending => "pending",
+            ApprovalStatus::Approved => "approved",
+            ApprovalStatus::Denied => "denied",
+            ApprovalStatus::Used => "used",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "approved" => ApprovalStatus::Approved,
+            "denied" => ApprovalStatus::Denied,
+            "used" => ApprovalStatus::Used,
+            _ => ApprovalStatus::Pending,
+        }
+    }
+}
+
+/// A request to run one LLM analysis of a repository over its budget. An
+/// approved request lets the next such analysis through.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetApproval {
+    pub id: Uuid,
+    pub repository_id: Uuid,
+    /// The commit whose analysis asked for it.
+    pub commit_sha: String,
+    pub status: ApprovalStatus,
+    pub requested_at: DateTime<Utc>,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+/// LLM usage summed over some analyses, under a label such as a model or a
+/// repository.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AiUsageTotals {
+    /// The model, repository id or `total` the usage is summed under.
+    pub key: String,
+    pub analyses: i64,
+    pub requests: i64,
+    pub cache_hits: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub cost_usd: f64,
+}
+
+/// Which analyses a usage report covers: those recorded in `[from, to)`, of
+/// one repository or of an organization's repositories when given.
+#[derive(Debug, Clone)]
+pub struct AiUsageFilter {
+    pub repository_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+/// LLM usage over a period, in total and broken down.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AiUsageSummary {
+    pub total: AiUsageTotals,
+    pub by_model: Vec<AiUsageTotals>,
+    pub by_repository: Vec<AiUsageTotals>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(policy: BudgetPolicy, monthly_limit_usd: f64, spent_usd: f64) -> BudgetStatus {
+        BudgetStatus {
+            budget: AiBudget {
+                scope: BudgetScope::Repository,
+                scope_id: Uuid::new_v4(),
+                monthly_limit_usd,
+                policy,
+                updated_by: "0xadmin".to_string(),
+                updated_at: Utc::now(),
+            },
+            spent_usd,
+        }
+    }
+
+    #[test]
+    fn strictest_spent_budget_binds() {
+        let statuses = [
+            status(BudgetPolicy::Block, 50.0, 10.0),
+            status(BudgetPolicy::StaticOnly, 5.0, 5.0),
+            status(BudgetPolicy::RequireApproval, 20.0, 21.5),
+        ];
+
+        let binding = binding_budget(&statuses).unwrap();
+        assert_eq!(binding.budget.policy, BudgetPolicy::RequireApproval);
+        assert_eq!(binding.remaining_usd(), 0.0);
+        assert!(binding_budget(&statuses[..1]).is_none());
+    }
+
+    #[test]
+    fn months_start_on_the_first() {
+        let at = Utc.with_ymd_and_hms(2024, 3, 17, 13, 45, 0).unwrap();
+        assert_eq!(month_start(at), Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap());
+    }
+}
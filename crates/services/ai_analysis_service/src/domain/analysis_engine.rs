@@ -1,6 +1,7 @@
 use crate::domain::analysis_models::{AnalysisRequest, AnalysisResult, AnalysisType, VulnerabilityFinding, SecurityRecommendation};
-use crate::domain::llm_provider_trait::LLMProvider;
-use crate::infrastructure::static_analyzer::SuiMoveStaticAnalyzer;
+use crate::domain::llm_provider_trait::{usage_since, LLMProvider, ProviderUsage};
+use crate::domain::static_analyzer_trait::StaticAnalyzer;
+use crate::infrastructure::static_analyzer::MultiLanguageStaticAnalyzer;
 use crate::error::{Error, Result};
 use chrono::Utc;
 use serde_json::json;
@@ -9,14 +10,14 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct AnalysisEngine {
-    static_analyzer: SuiMoveStaticAnalyzer,
+    static_analyzer: MultiLanguageStaticAnalyzer,
     llm_provider: Option<Arc<dyn LLMProvider>>,
 }
 
 impl AnalysisEngine {
     pub fn new() -> Self {
         Self {
-            static_analyzer: SuiMoveStaticAnalyzer::new(),
+            static_analyzer: MultiLanguageStaticAnalyzer::new(),
             llm_provider: None,
         }
     }
@@ -26,6 +27,39 @@ impl AnalysisEngine {
         self
     }
 
+    /// Add a static analyzer for another language.
+    pub fn with_static_analyzer(mut self, analyzer: Box<dyn StaticAnalyzer>) -> Self {
+        self.static_analyzer = self.static_analyzer.with_analyzer(analyzer);
+        self
+    }
+
+    /// Whether LLM analyses can run.
+    pub fn has_llm_provider(&self) -> bool {
+        self.llm_provider.is_some()
+    }
+
+    /// Requests, tokens and cost of the LLM provider so far; empty without
+    /// one.
+    pub fn llm_usage(&self) -> Vec<ProviderUsage> {
+        self.llm_provider.as_ref().map(|provider| provider.usage()).unwrap_or_default()
+    }
+
+    /// Whether a static analyzer checks this file.
+    pub fn supports_file(&self, file_path: &str, content: &str) -> bool {
+        self.static_analyzer.supports(file_path, content)
+    }
+
+    /// Whether a static analyzer may check a file at this path.
+    pub fn supports_path(&self, file_path: &str) -> bool {
+        self.static_analyzer.supports_path(file_path)
+    }
+
+    /// Security and quality scores of `files` given their findings, as the
+    /// static analysis scores them.
+    pub fn calculate_scores(&self, vulnerabilities: &[VulnerabilityFinding], files: &HashMap<String, String>) -> (f64, f64) {
+        self.static_analyzer.calculate_scores(vulnerabilities, files)
+    }
+
     pub async fn analyze_repository(&self, request: AnalysisRequest, file_contents: HashMap<String, String>) -> Result<Vec<AnalysisResult>> {
         let mut results = Vec::new();
 
@@ -69,6 +103,7 @@ impl AnalysisEngine {
         llm_provider: Arc<dyn LLMProvider>,
     ) -> Result<AnalysisResult> {
         let start_time = std::time::Instant::now();
+        let usage_before = llm_provider.usage();
         let mut all_vulnerabilities = Vec::new();
         let mut all_recommendations = Vec::new();
 
@@ -126,7 +161,8 @@ impl AnalysisEngine {
                 "llm_provider": llm_provider.get_provider_name(),
                 "llm_model": llm_provider.get_model_name(),
                 "total_vulnerabilities": all_vulnerabilities.len(),
-                "vulnerability_breakdown": self.get_vulnerability_breakdown(&all_vulnerabilities)
+                "vulnerability_breakdown": self.get_vulnerability_breakdown(&all_vulnerabilities),
+                "llm_usage": usage_since(&usage_before, &llm_provider.usage())
             }),
             created_at: Utc::now(),
         })
@@ -139,6 +175,7 @@ impl AnalysisEngine {
         llm_provider: Arc<dyn LLMProvider>,
     ) -> Result<AnalysisResult> {
         let start_time = std::time::Instant::now();
+        let usage_before = llm_provider.usage();
 
         let move_files: HashMap<String, String> = file_contents
             .iter()
@@ -200,7 +237,8 @@ impl AnalysisEngine {
                 "files_analyzed": analyzed_files,
                 "average_quality_score": average_quality,
                 "llm_provider": llm_provider.get_provider_name(),
-                "llm_model": llm_provider.get_model_name()
+                "llm_model": llm_provider.get_model_name(),
+                "llm_usage": usage_since(&usage_before, &llm_provider.usage())
             }),
             created_at: Utc::now(),
         })
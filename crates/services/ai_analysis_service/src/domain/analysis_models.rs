@@ -1,3 +1,5 @@
+use crate::domain::analysis_ruleset::Ruleset;
+use crate::domain::cvss::CvssVector;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -8,6 +10,9 @@ pub struct AnalysisRequest {
     pub commit_sha: String,
     pub files_to_analyze: Vec<String>,
     pub analysis_types: Vec<AnalysisType>,
+    /// The repository's ruleset; the built-in patterns alone by default.
+    #[serde(default)]
+    pub ruleset: Ruleset,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -46,7 +51,128 @@ pub struct VulnerabilityFinding {
     pub description: String,
     pub recommendation: String,
     pub cve_id: Option<String>,
+    /// CWE weakness identifier, e.g. `CWE-284`.
+    #[serde(default)]
+    pub cwe_id: Option<String>,
+    /// CVSS v3.1 base vector, e.g. `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:N`.
+    #[serde(default)]
+    pub cvss_vector: Option<String>,
+    /// Base score computed from `cvss_vector`.
+    #[serde(default)]
+    pub cvss_score: Option<f64>,
     pub is_false_positive: bool,
+    /// Version of the prompt template an LLM finding was reported with;
+    /// `None` for static analysis findings.
+    #[serde(default)]
+    pub prompt_version: Option<String>,
+    /// The LLM model that reported the finding.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Whether the finding's Move package compiled, when its bytecode was
+    /// analysed; `None` for files that were not compiled.
+    #[serde(default)]
+    pub compilation_status: Option<CompilationStatus>,
+    /// Whether the finding is in the repository's own code or in a package
+    /// it depends on.
+    #[serde(default)]
+    pub kind: FindingKind,
+    /// Why an LLM reported the finding; `None` for static analysis findings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<FindingEvidence>,
+}
+
+/// The justification an LLM gave for a finding, kept so it can be audited.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FindingEvidence {
+    /// The model's summary of why the code is vulnerable.
+    pub reasoning: Option<String>,
+    /// The known vulnerability pattern the model matched the code against.
+    pub referenced_pattern: Option<String>,
+}
+
+impl FindingEvidence {
+    /// Evidence from what a model answered, `None` when it gave neither.
+    pub fn new(reasoning: Option<String>, referenced_pattern: Option<String>) -> Option<Self> {
+        let present = |value: Option<String>| {
+            value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+        };
+        let (reasoning, referenced_pattern) = (present(reasoning), present(referenced_pattern));
+        (reasoning.is_some() || referenced_pattern.is_some()).then_some(Self { reasoning, referenced_pattern })
+    }
+}
+
+/// Where a finding was made.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    /// In the analysed source files.
+    #[default]
+    Code,
+    /// In a dependency with a published advisory; the finding points at the
+    /// manifest or lock file that pulls it in.
+    Dependency,
+}
+
+impl FindingKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FindingKind::Code => "code",
+            FindingKind::Dependency => "dependency",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "dependency" => FindingKind::Dependency,
+            _ => FindingKind::Code,
+        }
+    }
+}
+
+/// Outcome of compiling a Move package for bytecode analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompilationStatus {
+    Compiled,
+    Failed,
+    TimedOut,
+}
+
+impl CompilationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompilationStatus::Compiled => "compiled",
+            CompilationStatus::Failed => "failed",
+            CompilationStatus::TimedOut => "timed_out",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "compiled" => CompilationStatus::Compiled,
+            "timed_out" => CompilationStatus::TimedOut,
+            _ => CompilationStatus::Failed,
+        }
+    }
+}
+
+impl VulnerabilityFinding {
+    /// Fill in the CWE and CVSS vector from the vulnerability type where the
+    /// finding's source gave none, or gave an invalid vector, and compute the
+    /// CVSS score from the vector.
+    pub fn classify(mut self) -> Self {
+        if self.cwe_id.is_none() {
+            self.cwe_id = self.vulnerability_type.cwe_id().map(str::to_string);
+        }
+        let vector = self
+            .cvss_vector
+            .as_deref()
+            .and_then(|vector| vector.parse::<CvssVector>().ok())
+            .or_else(|| self.vulnerability_type.default_cvss_vector());
+        self.cvss_vector = vector.map(|vector| vector.to_string());
+        self.cvss_score = vector.map(|vector| vector.base_score());
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -62,6 +188,8 @@ pub enum VulnerabilityType {
     
     // General smart contract vulnerabilities
     AccessControl,
+    /// A private key, mnemonic or API token committed to the repository.
+    SecretExposure,
     Other(String),
 }
 
@@ -76,11 +204,66 @@ impl std::fmt::Display for VulnerabilityType {
             VulnerabilityType::ReentrancyLike => write!(f, "Reentrancy-like"),
             VulnerabilityType::InsufficientValidation => write!(f, "Insufficient Validation"),
             VulnerabilityType::AccessControl => write!(f, "Access Control"),
+            VulnerabilityType::SecretExposure => write!(f, "Secret Exposure"),
             VulnerabilityType::Other(s) => write!(f, "{}", s),
         }
     }
 }
 
+impl VulnerabilityType {
+    /// Identifier of the rule findings of this type report under:
+    /// `Reentrancy-like` becomes `reentrancy-like`, `Integer Overflow`
+    /// becomes `integer-overflow`.
+    pub fn rule_id(&self) -> String {
+        self.to_string()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_ascii_lowercase())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// The CWE weakness findings of this type usually are.
+    pub fn cwe_id(&self) -> Option<&'static str> {
+        match self {
+            VulnerabilityType::UnauthorizedAccess => Some("CWE-862"),
+            VulnerabilityType::ResourceExhaustion => Some("CWE-400"),
+            VulnerabilityType::IntegerOverflow => Some("CWE-190"),
+            VulnerabilityType::LogicError => Some("CWE-840"),
+            VulnerabilityType::TimestampDependence => Some("CWE-829"),
+            VulnerabilityType::ReentrancyLike => Some("CWE-841"),
+            VulnerabilityType::InsufficientValidation => Some("CWE-20"),
+            VulnerabilityType::AccessControl => Some("CWE-284"),
+            VulnerabilityType::SecretExposure => Some("CWE-798"),
+            VulnerabilityType::Other(name) if name == "Unchecked External Call" => Some("CWE-252"),
+            VulnerabilityType::Other(_) => None,
+        }
+    }
+
+    /// A typical CVSS v3.1 base vector for the type, for findings whose
+    /// source does not rate them. Contract code is reachable by anyone over
+    /// the network, so only complexity and impact vary.
+    pub fn default_cvss_vector(&self) -> Option<CvssVector> {
+        let metrics = match self {
+            VulnerabilityType::UnauthorizedAccess => "AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:N",
+            VulnerabilityType::ResourceExhaustion => "AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:H",
+            VulnerabilityType::IntegerOverflow | VulnerabilityType::LogicError => {
+                "AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:H/A:N"
+            }
+            VulnerabilityType::TimestampDependence => "AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:L/A:N",
+            VulnerabilityType::ReentrancyLike => "AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:H/A:H",
+            VulnerabilityType::InsufficientValidation => "AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:L/A:N",
+            VulnerabilityType::AccessControl => "AV:N/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:N",
+            VulnerabilityType::SecretExposure => "AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:N",
+            VulnerabilityType::Other(name) if name == "Unchecked External Call" => {
+                "AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:H/A:N"
+            }
+            VulnerabilityType::Other(_) => return None,
+        };
+        format!("CVSS:3.1/{}", metrics).parse().ok()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Severity {
     Critical,
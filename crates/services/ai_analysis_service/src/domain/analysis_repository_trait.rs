@@ -1,6 +1,16 @@
-use crate::domain::analysis_models::{AnalysisResult, VulnerabilityFinding};
+use crate::domain::ai_budget::{
+    AiBudget, AiUsageFilter, AiUsageSummary, ApprovalStatus, BudgetApproval, BudgetPolicy, BudgetScope, BudgetStatus,
+};
+use crate::domain::analysis_models::{AnalysisResult, AnalysisType, FindingKind, Severity, VulnerabilityFinding};
+use crate::domain::analysis_ruleset::{AnalysisRuleset, Ruleset};
+use crate::domain::dependency_advisory::DependencyAdvisory;
+use crate::domain::dependency_manifest::Ecosystem;
+use crate::domain::disclosure::{DisclosureState, VulnerabilityDisclosure};
+use crate::domain::llm_provider_trait::ProviderUsage;
+use crate::domain::patch_regression::{MergedPatch, PatchRegression, RegressionReason};
 use crate::error::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -14,6 +24,148 @@ pub struct VulnerabilityStatistics {
     pub fixed_count: i64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VulnerabilityStatus {
+    Open,
+    Fixed,
+    FalsePositive,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VulnerabilitySort {
+    #[default]
+    Severity,
+    CvssScore,
+    DetectedAt,
+}
+
+/// Filters, order and page of a vulnerability listing. Unset filters match
+/// everything.
+#[derive(Debug, Clone)]
+pub struct VulnerabilityFilter {
+    pub repository_id: Option<Uuid>,
+    pub severity: Option<Severity>,
+    pub status: Option<VulnerabilityStatus>,
+    /// e.g. `CWE-284`.
+    pub cwe_id: Option<String>,
+    pub min_cvss_score: Option<f64>,
+    pub max_cvss_score: Option<f64>,
+    pub kind: Option<FindingKind>,
+    pub disclosure_state: Option<DisclosureState>,
+    pub sort: VulnerabilitySort,
+    pub descending: bool,
+    pub page: u32,
+    pub limit: u32,
+}
+
+impl Default for VulnerabilityFilter {
+    fn default() -> Self {
+        Self {
+            repository_id: None,
+            severity: None,
+            status: None,
+            cwe_id: None,
+            min_cvss_score: None,
+            max_cvss_score: None,
+            kind: None,
+            disclosure_state: None,
+            sort: VulnerabilitySort::default(),
+            descending: true,
+            page: 1,
+            limit: 20,
+        }
+    }
+}
+
+/// A stored finding with the repository it was found in and its history
+/// across analyses.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VulnerabilityRecord {
+    #[serde(flatten)]
+    pub finding: VulnerabilityFinding,
+    pub repository_id: Uuid,
+    /// `None` for findings stored before fingerprinting.
+    pub fingerprint: Option<String>,
+    pub status: VulnerabilityStatus,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    /// When the finding was marked fixed.
+    pub fixed_at: Option<DateTime<Utc>>,
+    /// When a newer analysis of its file stopped reporting the finding.
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub disclosure_state: DisclosureState,
+    pub embargo_until: Option<DateTime<Utc>>,
+}
+
+/// What an analysis reported an LLM-assisted finding on: the code the model
+/// saw, its reasoning, the pattern it matched and how sure it was.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FindingEvidenceRecord {
+    pub id: Uuid,
+    pub vulnerability_id: Uuid,
+    /// `None` once the analysis has been deleted.
+    pub analysis_id: Option<Uuid>,
+    pub code_snippet: Option<String>,
+    pub reasoning: Option<String>,
+    pub referenced_pattern: Option<String>,
+    pub model: Option<String>,
+    pub prompt_version: Option<String>,
+    pub confidence_score: f64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuppressionKind {
+    FalsePositive,
+    AcceptedRisk,
+}
+
+impl SuppressionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SuppressionKind::FalsePositive => "false_positive",
+            SuppressionKind::AcceptedRisk => "accepted_risk",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "accepted_risk" => SuppressionKind::AcceptedRisk,
+            _ => SuppressionKind::FalsePositive,
+        }
+    }
+}
+
+/// A triage decision on a finding. It applies to the finding's fingerprint,
+/// so later analyses reporting the finding again keep it suppressed.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VulnerabilitySuppression {
+    pub id: Uuid,
+    pub repository_id: Uuid,
+    pub fingerprint: String,
+    pub kind: SuppressionKind,
+    pub reason: String,
+    /// `None` for suppressions that never expire.
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SuppressionStatistics {
+    pub active_count: i64,
+    pub false_positive_count: i64,
+    pub accepted_risk_count: i64,
+    /// Active suppressions expiring within the next seven days.
+    pub expiring_soon_count: i64,
+    pub expired_count: i64,
+    pub revoked_count: i64,
+}
+
 #[async_trait]
 pub trait AnalysisRepository: Send + Sync {
     async fn save_analysis_result(&self, result: &AnalysisResult) -> Result<Uuid>;
@@ -23,7 +175,17 @@ pub trait AnalysisRepository: Send + Sync {
     async fn get_latest_analysis_for_repository(&self, repository_id: Uuid) -> Result<Option<AnalysisResult>>;
     
     async fn get_analysis_history(&self, repository_id: Uuid, limit: Option<u32>) -> Result<Vec<AnalysisResult>>;
+
+    /// The latest analysis of this type that covered the whole repository,
+    /// in full or incrementally, which a later incremental analysis builds on.
+    async fn get_latest_baseline_analysis(
+        &self,
+        repository_id: Uuid,
+        analysis_type: &AnalysisType,
+    ) -> Result<Option<AnalysisResult>>;
     
+    /// Store a finding, updating the existing record if an earlier analysis
+    /// reported the same finding.
     async fn save_vulnerability(&self, vulnerability: &VulnerabilityFinding, analysis_id: Uuid) -> Result<Uuid>;
     
     async fn get_vulnerabilities_for_analysis(&self, analysis_id: Uuid) -> Result<Vec<VulnerabilityFinding>>;
@@ -35,4 +197,183 @@ pub trait AnalysisRepository: Send + Sync {
     async fn mark_vulnerability_as_fixed(&self, vulnerability_id: Uuid) -> Result<()>;
     
     async fn get_vulnerability_statistics(&self, repository_id: Uuid) -> Result<VulnerabilityStatistics>;
-}
\ No newline at end of file
+
+    /// One page of the findings matching `filter`, and the total number of
+    /// matches.
+    async fn list_vulnerabilities(
+        &self,
+        filter: &VulnerabilityFilter,
+    ) -> Result<(Vec<VulnerabilityRecord>, i64)>;
+
+    async fn get_vulnerability_record(&self, vulnerability_id: Uuid) -> Result<Option<VulnerabilityRecord>>;
+
+    /// What each analysis that reported an LLM-assisted finding based it on,
+    /// newest first. Empty for static analysis findings.
+    async fn list_finding_evidence(&self, vulnerability_id: Uuid) -> Result<Vec<FindingEvidenceRecord>>;
+
+    /// Close the open findings in `analyzed_files` that the analysis no
+    /// longer reports, returning how many were closed. Only findings last
+    /// reported by the same kind of analysis are considered, so a static run
+    /// does not close what an LLM review found.
+    async fn resolve_missing_vulnerabilities(
+        &self,
+        repository_id: Uuid,
+        analysis_id: Uuid,
+        analyzed_files: &[String],
+    ) -> Result<u64>;
+
+    /// The repository's merged or applied patches not flagged as regressed,
+    /// with the state of the findings they fixed.
+    async fn list_merged_patches(&self, repository_id: Uuid) -> Result<Vec<MergedPatch>>;
+
+    /// Flag the patches as regressed by the analysis, returning those not
+    /// flagged before.
+    async fn flag_patch_regressions(
+        &self,
+        analysis_id: Uuid,
+        regressions: &[(Uuid, RegressionReason)],
+    ) -> Result<Vec<PatchRegression>>;
+
+    /// Suppress the finding's fingerprint in its repository, replacing any
+    /// earlier suppression of it. `None` if there is no such finding.
+    async fn suppress_vulnerability(
+        &self,
+        vulnerability_id: Uuid,
+        kind: SuppressionKind,
+        reason: &str,
+        expires_at: Option<DateTime<Utc>>,
+        created_by: &str,
+    ) -> Result<Option<VulnerabilitySuppression>>;
+
+    /// Revoke the finding's suppression and reopen it, returning whether it
+    /// had one.
+    async fn revoke_suppression(&self, vulnerability_id: Uuid) -> Result<bool>;
+
+    /// The repository's suppressions, newest first. Revoked and expired ones
+    /// are only included with `include_inactive`.
+    async fn list_suppressions(
+        &self,
+        repository_id: Uuid,
+        include_inactive: bool,
+    ) -> Result<Vec<VulnerabilitySuppression>>;
+
+    /// Suppression counts for one repository, or across all of them.
+    async fn get_suppression_statistics(
+        &self,
+        repository_id: Option<Uuid>,
+    ) -> Result<SuppressionStatistics>;
+
+    /// The repository's ruleset, `None` if it never configured one.
+    async fn get_ruleset(&self, repository_id: Uuid) -> Result<Option<AnalysisRuleset>>;
+
+    /// Replace the repository's ruleset. `None` if there is no such
+    /// repository.
+    async fn save_ruleset(
+        &self,
+        repository_id: Uuid,
+        rules: &Ruleset,
+        updated_by: &str,
+    ) -> Result<Option<AnalysisRuleset>>;
+
+    /// Whether the wallet `address` belongs to an owner or admin of an
+    /// organization monitoring the repository.
+    async fn can_manage_ruleset(&self, repository_id: Uuid, address: &str) -> Result<bool>;
+
+    /// Store advisories from the feed, replacing earlier copies of them.
+    /// Returns how many were stored.
+    async fn save_dependency_advisories(&self, advisories: &[DependencyAdvisory]) -> Result<u64>;
+
+    /// The stored advisories against any of `packages`, matched by name
+    /// regardless of case.
+    async fn find_dependency_advisories(
+        &self,
+        ecosystem: Ecosystem,
+        packages: &[String],
+    ) -> Result<Vec<DependencyAdvisory>>;
+
+    /// Store what an analysis's LLM calls used, one row per backend called
+    /// or served from the cache.
+    async fn record_ai_usage(
+        &self,
+        repository_id: Uuid,
+        analysis_id: Option<Uuid>,
+        usage: &[ProviderUsage],
+    ) -> Result<()>;
+
+    /// LLM usage matching `filter`.
+    async fn summarize_ai_usage(&self, filter: &AiUsageFilter) -> Result<AiUsageSummary>;
+
+    /// The budgets covering a repository, its own and those of the
+    /// organizations it belongs to, or an organization's own budget, with
+    /// what each has spent since `since`.
+    async fn get_budget_statuses(
+        &self,
+        scope: BudgetScope,
+        scope_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<BudgetStatus>>;
+
+    /// Set the monthly budget of a repository or organization. `None` if
+    /// there is no such repository or organization.
+    async fn save_ai_budget(
+        &self,
+        scope: BudgetScope,
+        scope_id: Uuid,
+        monthly_limit_usd: f64,
+        policy: BudgetPolicy,
+        updated_by: &str,
+    ) -> Result<Option<AiBudget>>;
+
+    /// Remove a budget, returning whether there was one.
+    async fn delete_ai_budget(&self, scope: BudgetScope, scope_id: Uuid) -> Result<bool>;
+
+    /// Ask to run an analysis of the repository over its budget. Returns the
+    /// repository's pending request, opening one if there is none.
+    async fn request_budget_approval(&self, repository_id: Uuid, commit_sha: &str) -> Result<BudgetApproval>;
+
+    /// Mark one approved request of the repository used, returning whether
+    /// there was one to use.
+    async fn take_budget_approval(&self, repository_id: Uuid) -> Result<bool>;
+
+    /// Approve or deny a pending request. `None` if there is no pending
+    /// request with this id.
+    async fn decide_budget_approval(
+        &self,
+        approval_id: Uuid,
+        approve: bool,
+        decided_by: &str,
+    ) -> Result<Option<BudgetApproval>>;
+
+    /// Approval requests, newest first, of one repository or all of them.
+    async fn list_budget_approvals(
+        &self,
+        repository_id: Option<Uuid>,
+        status: Option<ApprovalStatus>,
+    ) -> Result<Vec<BudgetApproval>>;
+
+    async fn get_disclosure(&self, vulnerability_id: Uuid) -> Result<Option<VulnerabilityDisclosure>>;
+
+    /// Move a finding to `state`, checking the move is allowed, and record
+    /// who made it. `None` if there is no such finding.
+    async fn update_disclosure(
+        &self,
+        vulnerability_id: Uuid,
+        state: DisclosureState,
+        embargo_until: Option<DateTime<Utc>>,
+        updated_by: &str,
+    ) -> Result<Option<VulnerabilityDisclosure>>;
+
+    /// Publish the embargoed findings whose embargo has ended.
+    async fn publish_lapsed_embargoes(&self) -> Result<Vec<VulnerabilityDisclosure>>;
+
+    /// Embargoed findings whose embargo ends before `before` and whose
+    /// maintainers have not been warned yet, marked warned so no other
+    /// instance warns them too.
+    async fn claim_lapsing_embargoes(&self, before: DateTime<Utc>) -> Result<Vec<VulnerabilityDisclosure>>;
+
+    /// Undo a claim whose warning could not be sent, so it is tried again.
+    async fn release_lapsing_embargoes(&self, vulnerability_ids: &[Uuid]) -> Result<()>;
+
+    /// The repository's findings that are not public yet.
+    async fn list_undisclosed_vulnerability_ids(&self, repository_id: Uuid) -> Result<Vec<Uuid>>;
+}
@@ -0,0 +1,261 @@
+use crate::domain::analysis_models::VulnerabilityFinding;
+use crate::domain::cvss::CvssVector;
+use crate::domain::static_analyzer_trait::has_extension;
+use crate::domain::vulnerability_patterns::{
+    ConditionOperator, PatternRule, VulnerabilityPattern, VulnerabilityPatterns, AST_NODE_FIELDS,
+};
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Grants changing the ruleset of any repository, not only those of the
+/// caller's organizations.
+pub const SCOPE_RULESETS_ADMIN: &str = "rulesets:admin";
+
+/// Most custom patterns one repository may define.
+pub const MAX_CUSTOM_PATTERNS: usize = 100;
+
+/// A pattern a repository defines for its own code, checked alongside the
+/// built-in analyzers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomPattern {
+    #[serde(flatten)]
+    pub pattern: VulnerabilityPattern,
+    /// Extensions of the files the pattern applies to, e.g. `.sol`; every
+    /// analysed file when empty.
+    #[serde(default)]
+    pub file_extensions: Vec<String>,
+}
+
+impl CustomPattern {
+    pub fn applies_to(&self, file_path: &str) -> bool {
+        let extensions: Vec<&str> = self.file_extensions.iter().map(String::as_str).collect();
+        extensions.is_empty() || has_extension(file_path, &extensions)
+    }
+}
+
+/// The built-in patterns a repository's analyses skip and the patterns of
+/// its own they add. The default runs the built-in patterns alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ruleset {
+    /// Ids of built-in `VulnerabilityPatterns` not to check.
+    #[serde(default)]
+    pub disabled_patterns: Vec<String>,
+    #[serde(default)]
+    pub custom_patterns: Vec<CustomPattern>,
+}
+
+impl Ruleset {
+    pub fn is_disabled(&self, pattern_id: &str) -> bool {
+        self.disabled_patterns.iter().any(|id| id == pattern_id)
+    }
+
+    /// Check that the disabled ids name built-in patterns, and that custom
+    /// patterns are well formed and have ids of their own.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(Error::InvalidRuleset { message });
+        let builtin = VulnerabilityPatterns::new();
+        let builtin_ids: HashSet<&str> =
+            builtin.get_all_patterns().iter().map(|pattern| pattern.id.as_str()).collect();
+
+        if let Some(id) = self.disabled_patterns.iter().find(|id| !builtin_ids.contains(id.as_str())) {
+            return invalid(format!("unknown built-in pattern {:?}", id));
+        }
+        if self.custom_patterns.len() > MAX_CUSTOM_PATTERNS {
+            return invalid(format!("at most {} custom patterns are allowed", MAX_CUSTOM_PATTERNS));
+        }
+
+        let mut ids = HashSet::new();
+        for custom in &self.custom_patterns {
+            let pattern = &custom.pattern;
+            let id = pattern.id.as_str();
+            if id.is_empty()
+                || id.len() > 100
+                || !id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+            {
+                return invalid(format!(
+                    "pattern id {:?} must be 1 to 100 letters, digits, '_', '-' or '.'",
+                    id
+                ));
+            }
+            if builtin_ids.contains(id) || !ids.insert(id) {
+                return invalid(format!("pattern id {:?} is already taken", id));
+            }
+            if pattern.name.trim().is_empty() {
+                return invalid(format!("pattern {:?} has no name", id));
+            }
+            if !(0.0..=100.0).contains(&pattern.confidence_base) {
+                return invalid(format!("pattern {:?} confidence must be between 0 and 100", id));
+            }
+            if let Some(cwe_id) = &pattern.cwe_id {
+                let number = cwe_id.strip_prefix("CWE-").unwrap_or_default();
+                if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                    return invalid(format!("pattern {:?} CWE id {:?} is not CWE-<number>", id, cwe_id));
+                }
+            }
+            if let Some(vector) = &pattern.cvss_vector {
+                vector.parse::<CvssVector>()?;
+            }
+            if let Some(extension) = custom.file_extensions.iter().find(|ext| !ext.starts_with('.')) {
+                return invalid(format!("pattern {:?} extension {:?} must start with '.'", id, extension));
+            }
+            validate_rule(id, &pattern.pattern)?;
+        }
+        Ok(())
+    }
+
+    /// Findings of the custom patterns that apply to `file_path`.
+    pub fn scan_custom_patterns(&self, file_path: &str, code: &str) -> Result<Vec<VulnerabilityFinding>> {
+        let patterns: Vec<VulnerabilityPattern> = self
+            .custom_patterns
+            .iter()
+            .filter(|custom| custom.applies_to(file_path))
+            .map(|custom| custom.pattern.clone())
+            .collect();
+        if patterns.is_empty() {
+            return Ok(Vec::new());
+        }
+        VulnerabilityPatterns::with_patterns(patterns).scan_code(file_path, code)
+    }
+
+    /// Hex SHA-256 of the rules, recorded with analyses so an incremental
+    /// analysis can tell whether its baseline ran under the same rules.
+    pub fn fingerprint(&self) -> String {
+        let rules = serde_json::to_vec(self).unwrap_or_default();
+        hex::encode(Sha256::digest(rules))
+    }
+}
+
+fn validate_rule(id: &str, rule: &PatternRule) -> Result<()> {
+    let invalid = |message: String| Err(Error::InvalidRuleset { message });
+    match rule {
+        PatternRule::Regex(regex_str) => {
+            if let Err(e) = regex::Regex::new(regex_str) {
+                return invalid(format!("pattern {:?} regex is invalid: {}", id, e));
+            }
+        }
+        PatternRule::ASTPattern(ast_rule) => {
+            let Some((_, fields)) =
+                AST_NODE_FIELDS.iter().find(|(node_type, _)| *node_type == ast_rule.node_type)
+            else {
+                return invalid(format!(
+                    "pattern {:?} node type {:?} is not supported",
+                    id, ast_rule.node_type
+                ));
+            };
+            for condition in &ast_rule.conditions {
+                if !fields.contains(&condition.field.as_str()) {
+                    return invalid(format!(
+                        "pattern {:?} {} nodes have no field {:?}",
+                        id, ast_rule.node_type, condition.field
+                    ));
+                }
+                if matches!(condition.operator, ConditionOperator::Regex) {
+                    validate_rule(id, &PatternRule::Regex(condition.value.clone()))?;
+                }
+            }
+        }
+        PatternRule::Combined(rules) => {
+            // Combined rules report every line one of their expressions matches
+            if rules.is_empty() || !rules.iter().all(|rule| matches!(rule, PatternRule::Regex(_))) {
+                return invalid(format!("pattern {:?} must combine one or more regex rules", id));
+            }
+            for rule in rules {
+                validate_rule(id, rule)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A repository's stored ruleset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisRuleset {
+    pub repository_id: Uuid,
+    #[serde(flatten)]
+    pub rules: Ruleset,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ruleset(custom_patterns: serde_json::Value) -> Ruleset {
+        serde_json::from_value(serde_json::json!({
+            "disabled_patterns": ["sui_integer_overflow"],
+            "custom_patterns": custom_patterns,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn validates_patterns() {
+        let pattern = |id: &str, rule: serde_json::Value| {
+            serde_json::json!({
+                "id": id,
+                "name": "Admin entry point",
+                "description": "Entry function takes no AdminCap",
+                "vulnerability_type": "AccessControl",
+                "severity": "High",
+                "confidence_base": 70.0,
+                "pattern": rule,
+                "recommendation": "Require an AdminCap",
+                "file_extensions": [".move"],
+            })
+        };
+
+        let regex = |regex: &str| serde_json::json!({ "Regex": regex });
+        let valid = ruleset(serde_json::json!([pattern("admin_entry", regex(r"entry\s+fun\s+admin_"))]));
+        assert!(valid.validate().is_ok());
+        assert!(valid.is_disabled("sui_integer_overflow"));
+
+        let unknown = Ruleset { disabled_patterns: vec!["nope".to_string()], ..Default::default() };
+        assert!(unknown.validate().is_err());
+
+        let bad_regex = ruleset(serde_json::json!([pattern("admin_entry", regex("("))]));
+        assert!(bad_regex.validate().is_err());
+
+        let taken = ruleset(serde_json::json!([pattern("sui_unbounded_vector", regex("x"))]));
+        assert!(taken.validate().is_err());
+    }
+
+    #[test]
+    fn matches_functions_with_ast_patterns() {
+        let rules = ruleset(serde_json::json!([{
+            "id": "admin_body_transfer",
+            "name": "Admin transfer",
+            "description": "Admin function moves coins",
+            "vulnerability_type": "AccessControl",
+            "severity": "High",
+            "confidence_base": 80.0,
+            "pattern": {"ASTPattern": {"node_type": "function", "conditions": [
+                {"field": "name", "operator": "StartsWith", "value": "admin_"},
+                {"field": "body", "operator": "Contains", "value": "transfer::"},
+            ]}},
+            "recommendation": "Require an AdminCap",
+        }]));
+        assert!(rules.validate().is_ok());
+
+        let code = "module vault::vault {
+    public entry fun admin_drain(v: &mut Vault) {
+        transfer::public_transfer(coin, @0x1);
+    }
+
+    public fun admin_pause(v: &mut Vault) {
+        v.paused = true;
+    }
+}";
+        let findings = rules.scan_custom_patterns("sources/vault.move", code).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line_number, Some(2));
+        assert_eq!(
+            findings[0].code_snippet.as_deref(),
+            Some("public entry fun admin_drain(v: &mut Vault)")
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use crate::domain::analysis_models::{CompilationStatus, VulnerabilityFinding};
+use crate::error::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Name of the manifest that makes a directory a Move package.
+pub const MOVE_MANIFEST: &str = "Move.toml";
+
+/// How compiling one package went.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageCompilation {
+    /// Directory holding the manifest; empty for the repository root.
+    pub package_path: String,
+    pub status: CompilationStatus,
+    /// Compiler output explaining a failure.
+    pub message: Option<String>,
+    pub modules_analyzed: usize,
+}
+
+/// Findings of a bytecode analysis and the compilation of each package it
+/// covered.
+#[derive(Debug, Clone, Default)]
+pub struct BytecodeAnalysis {
+    pub packages: Vec<PackageCompilation>,
+    pub findings: Vec<VulnerabilityFinding>,
+}
+
+impl BytecodeAnalysis {
+    /// How the package containing `file_path` compiled, if it was compiled.
+    pub fn status_of(&self, file_path: &str) -> Option<CompilationStatus> {
+        self.packages
+            .iter()
+            .filter(|package| is_within(file_path, &package.package_path))
+            .max_by_key(|package| package.package_path.len())
+            .map(|package| package.status)
+    }
+}
+
+/// An analyzer that compiles packages and inspects what the compiler emits,
+/// confirming what source patterns can only suspect.
+#[async_trait]
+pub trait BytecodeAnalyzer: Send + Sync {
+    /// Short name recorded with the results, e.g. `move-bytecode`.
+    fn name(&self) -> &str;
+
+    /// Compile the packages whose manifests are among `files` and analyse
+    /// their bytecode. Packages that fail to compile are reported, not
+    /// returned as errors.
+    async fn analyze_packages(&self, files: &HashMap<String, String>) -> Result<BytecodeAnalysis>;
+}
+
+/// Directories of the Move packages among `files`, innermost last.
+pub fn move_packages<'a>(files: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+    let mut packages: Vec<String> = files
+        .into_iter()
+        .filter_map(|path| match path.rsplit_once('/') {
+            Some((dir, MOVE_MANIFEST)) => Some(dir.to_string()),
+            None if path == MOVE_MANIFEST => Some(String::new()),
+            _ => None,
+        })
+        .collect();
+    packages.sort_by(|a, b| (a.len(), a).cmp(&(b.len(), b)));
+    packages
+}
+
+/// Whether `path` is `dir` or lies beneath it. Every path lies beneath the
+/// repository root.
+pub fn is_within(path: &str, dir: &str) -> bool {
+    dir.is_empty() || path.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/'))
+}
@@ -0,0 +1,235 @@
+use crate::error::Result;
+use async_trait::async_trait;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// Files that changed between an analysed commit and a newer one.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeSet {
+    /// Added, modified and renamed files, by their new path.
+    pub changed: Vec<String>,
+    /// Deleted files, and the old paths of renamed ones.
+    pub removed: Vec<String>,
+}
+
+/// Where a repository's changes come from, e.g. the GitHub compare API.
+#[async_trait]
+pub trait ChangeSetProvider: Send + Sync {
+    /// The files changed from `base_sha` to `head_sha`, or `None` when they
+    /// cannot be listed in full, such as after a force push.
+    async fn changed_files(&self, base_sha: &str, head_sha: &str) -> Result<Option<ChangeSet>>;
+}
+
+/// `module pkg::name` in Move.
+fn move_module() -> &'static Regex {
+    static MOVE_MODULE: OnceLock<Regex> = OnceLock::new();
+    MOVE_MODULE.get_or_init(|| Regex::new(r"\bmodule\s+(\w+)::(\w+)").expect("valid regex"))
+}
+
+/// `pkg::name::` or `use pkg::name` in Move.
+fn move_reference() -> &'static Regex {
+    static MOVE_REFERENCE: OnceLock<Regex> = OnceLock::new();
+    MOVE_REFERENCE.get_or_init(|| Regex::new(r"\b(\w+)::(\w+)\b").expect("valid regex"))
+}
+
+/// `import "path";` and `import {A} from "path";` in Solidity.
+fn solidity_import() -> &'static Regex {
+    static SOLIDITY_IMPORT: OnceLock<Regex> = OnceLock::new();
+    SOLIDITY_IMPORT.get_or_init(|| {
+        Regex::new(r#"\bimport\s+(?:[^"';]*\s+from\s+)?["']([^"']+)["']"#).expect("valid regex")
+    })
+}
+
+/// `mod name;` in Rust.
+fn rust_module() -> &'static Regex {
+    static RUST_MODULE: OnceLock<Regex> = OnceLock::new();
+    RUST_MODULE.get_or_init(|| {
+        Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*;").expect("valid regex")
+    })
+}
+
+/// `crate::name` in Rust.
+fn rust_crate_path() -> &'static Regex {
+    static RUST_CRATE_PATH: OnceLock<Regex> = OnceLock::new();
+    RUST_CRATE_PATH.get_or_init(|| Regex::new(r"\bcrate::(\w+)").expect("valid regex"))
+}
+
+/// The files of `files` that import one of `changed` or are imported by
+/// one, excluding `changed` themselves.
+pub fn direct_dependencies(
+    changed: &HashSet<String>,
+    files: &HashMap<String, String>,
+) -> HashSet<String> {
+    let imports = imports(files);
+    let mut dependencies = HashSet::new();
+    for (file_path, imported) in &imports {
+        if changed.contains(file_path) {
+            dependencies.extend(imported.iter().cloned());
+        } else if imported.iter().any(|path| changed.contains(path)) {
+            dependencies.insert(file_path.clone());
+        }
+    }
+    dependencies.retain(|path| !changed.contains(path));
+    dependencies
+}
+
+/// The files each file imports, among `files`.
+fn imports(files: &HashMap<String, String>) -> HashMap<String, HashSet<String>> {
+    let mut move_modules: HashMap<(String, String), Vec<&String>> = HashMap::new();
+    for (file_path, content) in files.iter().filter(|(path, _)| path.ends_with(".move")) {
+        for captures in move_module().captures_iter(content) {
+            move_modules
+                .entry((captures[1].to_string(), captures[2].to_string()))
+                .or_default()
+                .push(file_path);
+        }
+    }
+
+    files
+        .iter()
+        .map(|(file_path, content)| {
+            let imported: HashSet<String> = if file_path.ends_with(".move") {
+                move_reference()
+                    .captures_iter(content)
+                    .filter_map(|captures| {
+                        move_modules.get(&(captures[1].to_string(), captures[2].to_string()))
+                    })
+                    .flatten()
+                    .map(|path| path.to_string())
+                    .collect()
+            } else if file_path.ends_with(".sol") {
+                solidity_import()
+                    .captures_iter(content)
+                    .map(|captures| resolve_solidity_import(file_path, &captures[1]))
+                    .collect()
+            } else if file_path.ends_with(".rs") {
+                rust_imports(file_path, content)
+            } else {
+                HashSet::new()
+            };
+            let imported = imported
+                .into_iter()
+                .filter(|path| path != file_path && files.contains_key(path))
+                .collect();
+            (file_path.clone(), imported)
+        })
+        .collect()
+}
+
+/// Relative imports resolve against the importing file's directory; others
+/// are taken as repository paths.
+fn resolve_solidity_import(file_path: &str, import: &str) -> String {
+    if import.starts_with("./") || import.starts_with("../") {
+        join(parent(file_path), import)
+    } else {
+        join("", import)
+    }
+}
+
+/// Candidate files of a Rust file's `mod` declarations and `crate::` paths.
+fn rust_imports(file_path: &str, content: &str) -> HashSet<String> {
+    let directory = parent(file_path);
+    let file_name = file_path.rsplit('/').next().unwrap_or(file_path);
+    // Modules declared in lib.rs, main.rs and mod.rs live beside them; in
+    // any other file, in a directory named after it.
+    let module_directory = match file_name {
+        "lib.rs" | "main.rs" | "mod.rs" => directory.to_string(),
+        _ => join(directory, file_name.trim_end_matches(".rs")),
+    };
+    let crate_root = match file_path.rfind("src/") {
+        Some(index) => &file_path[..index + "src".len()],
+        None => directory,
+    };
+
+    let declared = rust_module()
+        .captures_iter(content)
+        .map(|captures| (module_directory.as_str(), captures[1].to_string()));
+    let used = rust_crate_path()
+        .captures_iter(content)
+        .map(|captures| (crate_root, captures[1].to_string()));
+    declared
+        .chain(used)
+        .flat_map(|(directory, module)| {
+            [
+                join(directory, &format!("{}.rs", module)),
+                join(directory, &format!("{}/mod.rs", module)),
+            ]
+        })
+        .collect()
+}
+
+fn parent(file_path: &str) -> &str {
+    file_path.rsplit_once('/').map_or("", |(directory, _)| directory)
+}
+
+/// `relative` appended to `directory`, with `.` and `..` segments resolved.
+fn join(directory: &str, relative: &str) -> String {
+    let mut segments: Vec<&str> = directory.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in relative.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect()
+    }
+
+    fn changed(paths: &[&str]) -> HashSet<String> {
+        paths.iter().map(|path| path.to_string()).collect()
+    }
+
+    fn sorted(paths: HashSet<String>) -> Vec<String> {
+        let mut paths: Vec<_> = paths.into_iter().collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn finds_move_importers_and_imports() {
+        let files = files(&[
+            ("sources/pool.move", "module amm::pool {\n    use amm::math;\n}"),
+            ("sources/math.move", "module amm::math {\n}"),
+            ("sources/router.move", "module amm::router {\n    fun swap() { amm::pool::swap(); }\n}"),
+            ("sources/oracle.move", "module amm::oracle {\n    use sui::clock;\n}"),
+        ]);
+
+        let dependencies = direct_dependencies(&changed(&["sources/pool.move"]), &files);
+        assert_eq!(sorted(dependencies), ["sources/math.move", "sources/router.move"]);
+    }
+
+    #[test]
+    fn resolves_solidity_and_rust_paths() {
+        let files = files(&[
+            ("src/Vault.sol", "import {Token} from \"./tokens/Token.sol\";"),
+            ("src/tokens/Token.sol", "import \"../access/Owned.sol\";"),
+            ("src/access/Owned.sol", "contract Owned {}"),
+            ("programs/vault/src/lib.rs", "pub mod state;\nuse crate::errors::VaultError;"),
+            ("programs/vault/src/state.rs", "pub struct Vault {}"),
+            ("programs/vault/src/errors/mod.rs", "pub enum VaultError {}"),
+        ]);
+
+        let dependencies = direct_dependencies(&changed(&["src/tokens/Token.sol"]), &files);
+        assert_eq!(sorted(dependencies), ["src/Vault.sol", "src/access/Owned.sol"]);
+
+        let dependencies = direct_dependencies(&changed(&["programs/vault/src/lib.rs"]), &files);
+        assert_eq!(
+            sorted(dependencies),
+            ["programs/vault/src/errors/mod.rs", "programs/vault/src/state.rs"]
+        );
+    }
+}
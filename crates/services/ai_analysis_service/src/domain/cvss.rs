@@ -0,0 +1,284 @@
+use crate::domain::analysis_models::Severity;
+use crate::error::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+const PREFIX: &str = "CVSS:3.1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackVector {
+    Network,
+    Adjacent,
+    Local,
+    Physical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackComplexity {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivilegesRequired {
+    None,
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserInteraction {
+    None,
+    Required,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Unchanged,
+    Changed,
+}
+
+/// Impact on confidentiality, integrity or availability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Impact {
+    None,
+    Low,
+    High,
+}
+
+/// The base metrics of a CVSS v3.1 vector, e.g.
+/// `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:N`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CvssVector {
+    pub attack_vector: AttackVector,
+    pub attack_complexity: AttackComplexity,
+    pub privileges_required: PrivilegesRequired,
+    pub user_interaction: UserInteraction,
+    pub scope: Scope,
+    pub confidentiality: Impact,
+    pub integrity: Impact,
+    pub availability: Impact,
+}
+
+impl CvssVector {
+    /// The base score, from 0.0 to 10.0, as computed by the v3.1
+    /// specification.
+    pub fn base_score(&self) -> f64 {
+        let changed = self.scope == Scope::Changed;
+        let impact_subscore = 1.0
+            - (1.0 - self.confidentiality.weight())
+                * (1.0 - self.integrity.weight())
+                * (1.0 - self.availability.weight());
+        let impact = if changed {
+            7.52 * (impact_subscore - 0.029) - 3.25 * (impact_subscore - 0.02).powi(15)
+        } else {
+            6.42 * impact_subscore
+        };
+        if impact <= 0.0 {
+            return 0.0;
+        }
+
+        let privileges = match (self.privileges_required, changed) {
+            (PrivilegesRequired::None, _) => 0.85,
+            (PrivilegesRequired::Low, false) => 0.62,
+            (PrivilegesRequired::Low, true) => 0.68,
+            (PrivilegesRequired::High, false) => 0.27,
+            (PrivilegesRequired::High, true) => 0.5,
+        };
+        let attack_vector = match self.attack_vector {
+            AttackVector::Network => 0.85,
+            AttackVector::Adjacent => 0.62,
+            AttackVector::Local => 0.55,
+            AttackVector::Physical => 0.2,
+        };
+        let attack_complexity = match self.attack_complexity {
+            AttackComplexity::Low => 0.77,
+            AttackComplexity::High => 0.44,
+        };
+        let user_interaction = match self.user_interaction {
+            UserInteraction::None => 0.85,
+            UserInteraction::Required => 0.62,
+        };
+        let exploitability =
+            8.22 * attack_vector * attack_complexity * privileges * user_interaction;
+
+        if changed {
+            round_up((1.08 * (impact + exploitability)).min(10.0))
+        } else {
+            round_up((impact + exploitability).min(10.0))
+        }
+    }
+
+    /// The qualitative rating of the base score. Scores of 0.0, rated None by
+    /// the specification, are reported as Low.
+    pub fn severity(&self) -> Severity {
+        severity_for_score(self.base_score())
+    }
+}
+
+pub fn severity_for_score(score: f64) -> Severity {
+    match score {
+        s if s >= 9.0 => Severity::Critical,
+        s if s >= 7.0 => Severity::High,
+        s if s >= 4.0 => Severity::Medium,
+        _ => Severity::Low,
+    }
+}
+
+/// The specification's Roundup: the smallest one-decimal number not below
+/// `value`, computed on integers to avoid floating point artifacts.
+fn round_up(value: f64) -> f64 {
+    let scaled = (value * 100_000.0).round() as i64;
+    if scaled % 10_000 == 0 {
+        scaled as f64 / 100_000.0
+    } else {
+        (scaled / 10_000 + 1) as f64 / 10.0
+    }
+}
+
+impl Impact {
+    fn weight(self) -> f64 {
+        match self {
+            Impact::High => 0.56,
+            Impact::Low => 0.22,
+            Impact::None => 0.0,
+        }
+    }
+
+    fn code(self) -> char {
+        match self {
+            Impact::High => 'H',
+            Impact::Low => 'L',
+            Impact::None => 'N',
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "H" => Some(Impact::High),
+            "L" => Some(Impact::Low),
+            "N" => Some(Impact::None),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for CvssVector {
+    type Err = Error;
+
+    /// Parses a v3.1 vector with all eight base metrics, in any order.
+    /// Temporal and environmental metrics are ignored.
+    fn from_str(vector: &str) -> Result<Self> {
+        let invalid = || Error::InvalidCvssVector { vector: vector.to_string() };
+        let mut parts = vector.trim().split('/');
+        if parts.next() != Some(PREFIX) {
+            return Err(invalid());
+        }
+
+        let (mut av, mut ac, mut pr, mut ui, mut s, mut c, mut i, mut a) =
+            (None, None, None, None, None, None, None, None);
+        for part in parts {
+            let (metric, value) = part.split_once(':').ok_or_else(invalid)?;
+            match (metric, value) {
+                ("AV", "N") => av = Some(AttackVector::Network),
+                ("AV", "A") => av = Some(AttackVector::Adjacent),
+                ("AV", "L") => av = Some(AttackVector::Local),
+                ("AV", "P") => av = Some(AttackVector::Physical),
+                ("AC", "L") => ac = Some(AttackComplexity::Low),
+                ("AC", "H") => ac = Some(AttackComplexity::High),
+                ("PR", "N") => pr = Some(PrivilegesRequired::None),
+                ("PR", "L") => pr = Some(PrivilegesRequired::Low),
+                ("PR", "H") => pr = Some(PrivilegesRequired::High),
+                ("UI", "N") => ui = Some(UserInteraction::None),
+                ("UI", "R") => ui = Some(UserInteraction::Required),
+                ("S", "U") => s = Some(Scope::Unchanged),
+                ("S", "C") => s = Some(Scope::Changed),
+                ("C", value) => c = Some(Impact::parse(value).ok_or_else(invalid)?),
+                ("I", value) => i = Some(Impact::parse(value).ok_or_else(invalid)?),
+                ("A", value) => a = Some(Impact::parse(value).ok_or_else(invalid)?),
+                ("AV" | "AC" | "PR" | "UI" | "S", _) => return Err(invalid()),
+                _ => {}
+            }
+        }
+
+        Ok(CvssVector {
+            attack_vector: av.ok_or_else(invalid)?,
+            attack_complexity: ac.ok_or_else(invalid)?,
+            privileges_required: pr.ok_or_else(invalid)?,
+            user_interaction: ui.ok_or_else(invalid)?,
+            scope: s.ok_or_else(invalid)?,
+            confidentiality: c.ok_or_else(invalid)?,
+            integrity: i.ok_or_else(invalid)?,
+            availability: a.ok_or_else(invalid)?,
+        })
+    }
+}
+
+impl fmt::Display for CvssVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let av = match self.attack_vector {
+            AttackVector::Network => 'N',
+            AttackVector::Adjacent => 'A',
+            AttackVector::Local => 'L',
+            AttackVector::Physical => 'P',
+        };
+        let ac = match self.attack_complexity {
+            AttackComplexity::Low => 'L',
+            AttackComplexity::High => 'H',
+        };
+        let pr = match self.privileges_required {
+            PrivilegesRequired::None => 'N',
+            PrivilegesRequired::Low => 'L',
+            PrivilegesRequired::High => 'H',
+        };
+        let ui = match self.user_interaction {
+            UserInteraction::None => 'N',
+            UserInteraction::Required => 'R',
+        };
+        let s = match self.scope {
+            Scope::Unchanged => 'U',
+            Scope::Changed => 'C',
+        };
+        write!(
+            f,
+            "{}/AV:{}/AC:{}/PR:{}/UI:{}/S:{}/C:{}/I:{}/A:{}",
+            PREFIX,
+            av,
+            ac,
+            pr,
+            ui,
+            s,
+            self.confidentiality.code(),
+            self.integrity.code(),
+            self.availability.code()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(vector: &str) -> f64 {
+        vector.parse::<CvssVector>().unwrap().base_score()
+    }
+
+    #[test]
+    fn computes_base_scores() {
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), 9.8);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H"), 10.0);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:N"), 8.1);
+        assert_eq!(score("CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:C/C:L/I:N/A:N"), 2.3);
+        assert_eq!(score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N"), 0.0);
+    }
+
+    #[test]
+    fn round_trips_and_rejects_incomplete_vectors() {
+        let vector = "CVSS:3.1/AV:A/AC:H/PR:L/UI:R/S:C/C:L/I:H/A:N";
+        assert_eq!(vector.parse::<CvssVector>().unwrap().to_string(), vector);
+
+        assert!("CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H".parse::<CvssVector>().is_err());
+        assert!("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H".parse::<CvssVector>().is_err());
+        assert!("CVSS:3.1/AV:X/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H".parse::<CvssVector>().is_err());
+    }
+}
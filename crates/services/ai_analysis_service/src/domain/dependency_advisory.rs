@@ -0,0 +1,189 @@
+use crate::domain::analysis_models::{FindingKind, Severity, VulnerabilityFinding, VulnerabilityType};
+use crate::domain::dependency_manifest::{Dependency, Ecosystem};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Versions an advisory covers: from `introduced` (the first release when
+/// absent) up to but excluding `fixed`, or up to and including
+/// `last_affected`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AffectedRange {
+    #[serde(default)]
+    pub introduced: Option<String>,
+    #[serde(default)]
+    pub fixed: Option<String>,
+    #[serde(default)]
+    pub last_affected: Option<String>,
+}
+
+/// A published advisory against one package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyAdvisory {
+    /// Id in the feed, e.g. `RUSTSEC-2024-0001`.
+    pub id: String,
+    pub ecosystem: Ecosystem,
+    pub package: String,
+    pub summary: String,
+    pub severity: Severity,
+    pub cve_id: Option<String>,
+    pub cwe_id: Option<String>,
+    pub cvss_vector: Option<String>,
+    pub ranges: Vec<AffectedRange>,
+    /// Versions or git revisions affected outside any range.
+    pub versions: Vec<String>,
+    pub url: Option<String>,
+    pub modified_at: DateTime<Utc>,
+}
+
+impl DependencyAdvisory {
+    /// Whether the advisory covers `version`. Versions are compared by their
+    /// numbers, so `mainnet-v1.20.0` is `1.20.0`; branch names and commits
+    /// only match when listed.
+    pub fn affects(&self, version: &str) -> bool {
+        if self.versions.iter().any(|listed| listed == version) {
+            return true;
+        }
+        let Some(version) = version_key(version) else {
+            return false;
+        };
+        let bound = |bound: &Option<String>| bound.as_deref().map(version_key);
+        self.ranges.iter().any(|range| {
+            let introduced = match bound(&range.introduced) {
+                None => true,
+                Some(Some(introduced)) => introduced <= version,
+                // "0" and other unnumbered starts cover every version
+                Some(None) => true,
+            };
+            let before_fix = match bound(&range.fixed) {
+                None => true,
+                Some(Some(fixed)) => version < fixed,
+                Some(None) => false,
+            };
+            let up_to_last = match bound(&range.last_affected) {
+                None => true,
+                Some(Some(last_affected)) => version <= last_affected,
+                Some(None) => false,
+            };
+            introduced && before_fix && up_to_last
+        })
+    }
+
+    /// The lowest version fixing the advisory, if one is published.
+    pub fn fixed_version(&self) -> Option<&str> {
+        self.ranges
+            .iter()
+            .filter_map(|range| range.fixed.as_deref())
+            .filter_map(|fixed| Some((version_key(fixed)?, fixed)))
+            .min()
+            .map(|(_, fixed)| fixed)
+    }
+
+    /// A finding for `dependency`, which the advisory affects, at the line
+    /// pulling it in.
+    pub fn finding(&self, dependency: &Dependency) -> VulnerabilityFinding {
+        let version = dependency.version.as_deref().unwrap_or("unknown");
+        let via = if dependency.direct { "" } else { " (pulled in by another dependency)" };
+        let recommendation = match self.fixed_version() {
+            Some(fixed) => format!("Upgrade {} to {} or later and update the lock file", self.package, fixed),
+            None => format!(
+                "No fixed release of {} is published; replace it or confirm the affected code is unreachable",
+                self.package
+            ),
+        };
+        VulnerabilityFinding {
+            id: Uuid::new_v4(),
+            vulnerability_type: VulnerabilityType::Other("Vulnerable Dependency".to_string()),
+            severity: self.severity.clone(),
+            confidence_score: 90.0,
+            file_path: dependency.file_path.clone(),
+            line_number: dependency.line_number,
+            code_snippet: dependency.code_snippet.clone(),
+            description: format!(
+                "{} {}{} is affected by {}: {}",
+                dependency.name, version, via, self.id, self.summary
+            ),
+            recommendation,
+            cve_id: Some(self.cve_id.clone().unwrap_or_else(|| self.id.clone())),
+            cwe_id: self.cwe_id.clone(),
+            cvss_vector: self.cvss_vector.clone(),
+            cvss_score: None,
+            is_false_positive: false,
+            prompt_version: None,
+            model: None,
+            compilation_status: None,
+            kind: FindingKind::Dependency,
+            evidence: None,
+        }
+        .classify()
+    }
+}
+
+/// Numeric key of a version or tag, e.g. `(1, 20, 0)` for `mainnet-v1.20.0`
+/// or `(0, 29, 0)` for `0.29`. `None` without a `major.minor` number, and
+/// for commit hashes.
+pub fn version_key(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim();
+    let is_commit = version.len() >= 7 && version.chars().all(|c| c.is_ascii_hexdigit());
+    if is_commit {
+        return None;
+    }
+    // The first run of digits and dots at the start or after a separator
+    let bytes = version.as_bytes();
+    let start = (0..bytes.len()).find(|&i| {
+        bytes[i].is_ascii_digit() && (i == 0 || matches!(bytes[i - 1], b'v' | b'V' | b'-' | b'/' | b'_' | b'@'))
+    })?;
+    let numbers: Vec<u64> = version[start..]
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .next()?
+        .split('.')
+        .take(3)
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    match numbers[..] {
+        [major, minor] => Some((major, minor, 0)),
+        [major, minor, patch] => Some((major, minor, patch)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advisory(ranges: Vec<AffectedRange>, versions: Vec<&str>) -> DependencyAdvisory {
+        DependencyAdvisory {
+            id: "GHSA-test".to_string(),
+            ecosystem: Ecosystem::Move,
+            package: "Sui".to_string(),
+            summary: "Coin split can mint value".to_string(),
+            severity: Severity::High,
+            cve_id: None,
+            cwe_id: Some("CWE-682".to_string()),
+            cvss_vector: None,
+            ranges,
+            versions: versions.into_iter().map(str::to_string).collect(),
+            url: None,
+            modified_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn matches_versions_against_ranges() {
+        let range = |introduced: &str, fixed: &str| AffectedRange {
+            introduced: Some(introduced.to_string()),
+            fixed: Some(fixed.to_string()),
+            last_affected: None,
+        };
+        let advisory = advisory(vec![range("0", "1.18.1"), range("1.19.0", "1.20.2")], vec!["deadbeefcafe"]);
+
+        assert!(advisory.affects("mainnet-v1.17.3"));
+        assert!(!advisory.affects("mainnet-v1.18.1"));
+        assert!(advisory.affects("v1.20"));
+        assert!(!advisory.affects("testnet-v1.21.0"));
+        assert!(!advisory.affects("framework/mainnet"));
+        assert!(advisory.affects("deadbeefcafe"));
+        assert!(!advisory.affects("0123456789abcdef"));
+        assert_eq!(advisory.fixed_version(), Some("1.18.1"));
+    }
+}
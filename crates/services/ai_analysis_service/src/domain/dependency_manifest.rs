@@ -0,0 +1,540 @@
+use crate::domain::bytecode_analyzer_trait::MOVE_MANIFEST;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub const MOVE_LOCK: &str = "Move.lock";
+pub const CARGO_MANIFEST: &str = "Cargo.toml";
+pub const CARGO_LOCK: &str = "Cargo.lock";
+
+/// Manifests and lock files dependencies are read from.
+pub const DEPENDENCY_FILES: [&str; 4] = [MOVE_MANIFEST, MOVE_LOCK, CARGO_MANIFEST, CARGO_LOCK];
+
+/// Where a dependency is published, named as advisories name it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Ecosystem {
+    #[serde(rename = "move")]
+    Move,
+    #[serde(rename = "crates.io")]
+    CratesIo,
+}
+
+impl Ecosystem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Ecosystem::Move => "move",
+            Ecosystem::CratesIo => "crates.io",
+        }
+    }
+
+    /// The ecosystem of an OSV `affected.package.ecosystem`, if one we check.
+    pub fn from_osv(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "move" | "sui" => Some(Ecosystem::Move),
+            "crates.io" => Some(Ecosystem::CratesIo),
+            _ => None,
+        }
+    }
+}
+
+/// A package the repository's code depends on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    /// The locked version or git revision, or the required one when there is
+    /// no lock file; `None` when neither names a version.
+    pub version: Option<String>,
+    /// The manifest declaring the dependency, or the lock file for ones
+    /// pulled in by other dependencies.
+    pub file_path: String,
+    pub line_number: Option<u32>,
+    pub code_snippet: Option<String>,
+    /// Declared in a manifest rather than pulled in by another dependency.
+    pub direct: bool,
+}
+
+/// Whether `path` is a manifest or lock file dependencies are read from.
+pub fn is_dependency_file(path: &str) -> bool {
+    DEPENDENCY_FILES.contains(&file_name(path))
+}
+
+/// The dependencies declared in the manifests among `files`, at the versions
+/// their lock files pin, followed by the ones only the lock files name.
+/// Dependencies on code in the repository itself are left out; it is
+/// analysed with the rest.
+pub fn dependencies(files: &HashMap<String, String>) -> Vec<Dependency> {
+    let mut found = Vec::new();
+    // Lock files with the names the manifests using them declare
+    let mut locks: BTreeMap<&str, (Ecosystem, HashSet<String>)> = BTreeMap::new();
+
+    let mut manifests: Vec<(&String, &String)> = files
+        .iter()
+        .filter(|(path, _)| matches!(file_name(path), MOVE_MANIFEST | CARGO_MANIFEST))
+        .collect();
+    manifests.sort();
+    for (path, content) in manifests {
+        let (ecosystem, lock_path) = match file_name(path) {
+            MOVE_MANIFEST => (Ecosystem::Move, sibling_lock(files, path, MOVE_LOCK)),
+            _ => (Ecosystem::CratesIo, ancestor_lock(files, path)),
+        };
+        let locked: HashMap<String, Option<String>> = lock_path
+            .map(|lock_path| {
+                locked_packages(ecosystem, &files[lock_path])
+                    .into_iter()
+                    .map(|package| (package.name, package.version))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let declared = match ecosystem {
+            Ecosystem::Move => move_manifest_dependencies(content),
+            Ecosystem::CratesIo => cargo_manifest_dependencies(content),
+        };
+        let mut names = HashSet::new();
+        for declaration in declared {
+            let version = locked.get(&declaration.name).cloned().flatten().or(declaration.version);
+            names.insert(declaration.name.clone());
+            found.push(Dependency {
+                ecosystem,
+                name: declaration.name,
+                version,
+                file_path: path.clone(),
+                line_number: Some(declaration.line_number),
+                code_snippet: Some(declaration.line),
+                direct: true,
+            });
+        }
+        if let Some(lock_path) = lock_path {
+            locks.entry(lock_path).or_insert_with(|| (ecosystem, HashSet::new())).1.extend(names);
+        }
+    }
+
+    for (lock_path, (ecosystem, declared)) in locks {
+        for package in locked_packages(ecosystem, &files[lock_path]) {
+            if declared.contains(&package.name) {
+                continue;
+            }
+            found.push(Dependency {
+                ecosystem,
+                name: package.name,
+                version: package.version,
+                file_path: lock_path.to_string(),
+                line_number: Some(package.line_number),
+                code_snippet: Some(package.line),
+                direct: false,
+            });
+        }
+    }
+
+    let mut seen = HashSet::new();
+    found.retain(|dependency| {
+        seen.insert((dependency.file_path.clone(), dependency.name.clone(), dependency.version.clone()))
+    });
+    found
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn sibling_lock<'a>(files: &'a HashMap<String, String>, manifest: &str, lock: &str) -> Option<&'a str> {
+    let path = match manifest.rsplit_once('/') {
+        Some((dir, _)) => format!("{}/{}", dir, lock),
+        None => lock.to_string(),
+    };
+    files.get_key_value(&path).map(|(path, _)| path.as_str())
+}
+
+/// Cargo writes one lock file at the root of a workspace.
+fn ancestor_lock<'a>(files: &'a HashMap<String, String>, manifest: &str) -> Option<&'a str> {
+    let mut dir = manifest;
+    loop {
+        match dir.rsplit_once('/') {
+            Some((parent, _)) => {
+                if let Some(lock) = sibling_lock(files, &format!("{}/{}", parent, CARGO_MANIFEST), CARGO_LOCK) {
+                    return Some(lock);
+                }
+                dir = parent;
+            }
+            None => return sibling_lock(files, CARGO_MANIFEST, CARGO_LOCK),
+        }
+    }
+}
+
+struct Declaration {
+    name: String,
+    version: Option<String>,
+    line_number: u32,
+    line: String,
+}
+
+/// `[dependencies]` of a Move manifest: git dependencies at their `rev`.
+/// Local dependencies are in the repository and left out.
+fn move_manifest_dependencies(content: &str) -> Vec<Declaration> {
+    dependency_tables(content, |table| table == "dependencies")
+        .into_iter()
+        .filter(|(_, fields, _)| !fields.contains_key("local"))
+        .map(|(name, fields, entry)| Declaration {
+            version: fields.get("rev").or_else(|| fields.get("version")).cloned(),
+            name,
+            line_number: entry.line_number,
+            line: entry.line,
+        })
+        .collect()
+}
+
+/// `[dependencies]` of a Cargo manifest, including workspace and
+/// target-specific ones. Path dependencies are in the repository and left out.
+fn cargo_manifest_dependencies(content: &str) -> Vec<Declaration> {
+    let is_dependencies = |table: &str| {
+        table == "dependencies"
+            || table == "workspace.dependencies"
+            || (table.starts_with("target.") && table.ends_with(".dependencies"))
+    };
+    dependency_tables(content, is_dependencies)
+        .into_iter()
+        .filter(|(_, fields, _)| !fields.contains_key("path"))
+        .map(|(name, mut fields, entry)| Declaration {
+            // `package` renames the crate in code; advisories use its real name
+            name: fields.remove("package").unwrap_or(name),
+            version: fields.get("version").and_then(|requirement| required_version(requirement)),
+            line_number: entry.line_number,
+            line: entry.line,
+        })
+        .collect()
+}
+
+/// Dependencies declared in the tables `is_dependencies` accepts, either as
+/// `name = "1.0"`, `name = { ... }` or under a `[dependencies.name]` header,
+/// with their fields and the line declaring them. A bare string is the
+/// `version` field.
+fn dependency_tables(
+    content: &str,
+    is_dependencies: impl Fn(&str) -> bool,
+) -> Vec<(String, HashMap<String, String>, Entry)> {
+    let mut dependencies: Vec<(String, HashMap<String, String>, Entry)> = Vec::new();
+    for entry in toml_entries(content) {
+        if is_dependencies(&entry.table) {
+            let fields = match &entry.value {
+                Value::String(version) => HashMap::from([("version".to_string(), version.clone())]),
+                Value::Table(fields) => fields.clone(),
+                Value::Other => continue,
+            };
+            dependencies.push((entry.key.clone(), fields, entry));
+            continue;
+        }
+        // `[dependencies.name]` tables, declared at their header
+        let Some((table, name)) = entry.table.rsplit_once('.') else {
+            continue;
+        };
+        if !is_dependencies(table) {
+            continue;
+        }
+        let Value::String(value) = &entry.value else {
+            continue;
+        };
+        let name = unquote(name);
+        match dependencies.iter_mut().find(|(declared, _, first)| *declared == name && first.table == entry.table) {
+            Some((_, fields, _)) => {
+                fields.insert(entry.key.clone(), value.clone());
+            }
+            None => {
+                let fields = HashMap::from([(entry.key.clone(), value.clone())]);
+                let header = Entry {
+                    line_number: entry.header_line_number,
+                    line: entry.header_line.clone(),
+                    ..entry
+                };
+                dependencies.push((name, fields, header));
+            }
+        }
+    }
+    dependencies
+}
+
+/// The version a simple requirement such as `1.2`, `^1.2.3` or `=0.29.0`
+/// starts from; `None` for ranges, which only a lock file resolves.
+fn required_version(requirement: &str) -> Option<String> {
+    let version = requirement.trim().trim_start_matches(['^', '~', '=']).trim();
+    let simple = !version.is_empty() && version.chars().all(|c| c.is_ascii_digit() || c == '.');
+    simple.then(|| version.to_string())
+}
+
+struct LockedPackage {
+    name: String,
+    version: Option<String>,
+    line_number: u32,
+    line: String,
+}
+
+/// The external packages a lock file pins: git revisions in `Move.lock`,
+/// registry versions in `Cargo.lock`.
+fn locked_packages(ecosystem: Ecosystem, content: &str) -> Vec<LockedPackage> {
+    // Fields of each `[[package]]`-style element, by the element's header
+    let mut elements: BTreeMap<usize, (String, HashMap<String, Value>, Vec<Entry>)> = BTreeMap::new();
+    for entry in toml_entries(content) {
+        let element = elements
+            .entry(entry.element)
+            .or_insert_with(|| (entry.table.clone(), HashMap::new(), Vec::new()));
+        element.1.insert(entry.key.clone(), entry.value.clone());
+        element.2.push(entry);
+    }
+
+    let mut packages = Vec::new();
+    for (table, fields, entries) in elements.into_values() {
+        let string = |key: &str| match fields.get(key) {
+            Some(Value::String(value)) => Some(value.clone()),
+            _ => None,
+        };
+        let package = match ecosystem {
+            // Older lock files list `[[move.package]]`s, newer ones
+            // `[pinned.<environment>.<name>]` tables
+            Ecosystem::Move => {
+                let name = match table.strip_prefix("pinned.") {
+                    Some(pinned) => pinned.rsplit_once('.').map(|(_, name)| unquote(name)),
+                    None if table == "move.package" => string("name"),
+                    None => None,
+                };
+                let Some(Value::Table(source)) = fields.get("source") else {
+                    continue;
+                };
+                if !source.contains_key("git") {
+                    continue;
+                }
+                name.map(|name| (name, source.get("rev").cloned(), "source"))
+            }
+            // Workspace members and path dependencies have no source
+            Ecosystem::CratesIo if table == "package" && fields.contains_key("source") => {
+                string("name").map(|name| (name, string("version"), "name"))
+            }
+            Ecosystem::CratesIo => None,
+        };
+        let Some((name, version, key)) = package else {
+            continue;
+        };
+        let Some(entry) = entries.into_iter().find(|entry| entry.key == key) else {
+            continue;
+        };
+        packages.push(LockedPackage { name, version, line_number: entry.line_number, line: entry.line });
+    }
+    packages
+}
+
+/// One `key = value` line of a TOML document.
+#[derive(Debug, Clone)]
+struct Entry {
+    /// Dotted name of the table the key is in, empty at the top.
+    table: String,
+    /// Counts table headers, telling the elements of `[[array]]` tables apart.
+    element: usize,
+    key: String,
+    value: Value,
+    line_number: u32,
+    line: String,
+    header_line_number: u32,
+    header_line: String,
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    String(String),
+    /// An inline table's string fields.
+    Table(HashMap<String, String>),
+    Other,
+}
+
+/// The `key = value` lines of a TOML document, enough of TOML for manifests
+/// and lock files: strings and inline tables of strings are read, other
+/// values are kept as `Other` and lines continuing a multi-line value are
+/// skipped.
+fn toml_entries(content: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let (mut table, mut element) = (String::new(), 0);
+    let (mut header_line_number, mut header_line) = (0, String::new());
+    for (index, raw) in content.lines().enumerate() {
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            let name = line.trim_start_matches('[').trim_end_matches(']');
+            table = name.split('.').map(|part| unquote(part.trim())).collect::<Vec<_>>().join(".");
+            element += 1;
+            header_line_number = index as u32 + 1;
+            header_line = line.to_string();
+            continue;
+        }
+        let Some(equals) = find_outside_quotes(line, '=') else {
+            continue;
+        };
+        let key = line[..equals].trim();
+        if !is_key(key) {
+            continue;
+        }
+        entries.push(Entry {
+            table: table.clone(),
+            element,
+            key: unquote(key),
+            value: parse_value(line[equals + 1..].trim()),
+            line_number: index as u32 + 1,
+            line: line.to_string(),
+            header_line_number,
+            header_line: header_line.clone(),
+        });
+    }
+    entries
+}
+
+fn parse_value(value: &str) -> Value {
+    if let Some(string) = parse_string(value) {
+        return Value::String(string);
+    }
+    let Some(inner) = value.strip_prefix('{').and_then(|value| value.strip_suffix('}')) else {
+        return Value::Other;
+    };
+    let mut fields = HashMap::new();
+    let mut rest = inner;
+    while !rest.trim().is_empty() {
+        let end = find_outside_quotes(rest, ',').unwrap_or(rest.len());
+        let field = &rest[..end];
+        if let Some(equals) = find_outside_quotes(field, '=') {
+            let key = field[..equals].trim();
+            if let (true, Some(value)) = (is_key(key), parse_string(field[equals + 1..].trim())) {
+                fields.insert(unquote(key), value);
+            }
+        }
+        rest = rest.get(end + 1..).unwrap_or_default();
+    }
+    Value::Table(fields)
+}
+
+/// A basic `"..."` or literal `'...'` string.
+fn parse_string(value: &str) -> Option<String> {
+    let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let inner = value[1..].strip_suffix(quote)?;
+    Some(if quote == '"' { inner.replace("\\\"", "\"").replace("\\\\", "\\") } else { inner.to_string() })
+}
+
+fn is_key(key: &str) -> bool {
+    parse_string(key).is_some()
+        || (!key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+}
+
+fn unquote(key: &str) -> String {
+    parse_string(key).unwrap_or_else(|| key.to_string())
+}
+
+fn strip_comment(line: &str) -> &str {
+    match find_outside_quotes(line, '#') {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+/// Byte index of the first `target` outside a quoted string.
+fn find_outside_quotes(line: &str, target: char) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == target => return Some(index),
+            None => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries.iter().map(|(path, content)| (path.to_string(), content.to_string())).collect()
+    }
+
+    #[test]
+    fn resolves_move_dependencies_from_the_lock_file() {
+        let manifest = r#"[package]
+name = "vault"
+
+[dependencies]
+Sui = { git = "https://github.com/MystenLabs/sui.git", subdir = "crates/sui-framework/packages/sui-framework", rev = "framework/mainnet" }
+Utils = { local = "../utils" }
+
+[dependencies.DeepBook]
+git = "https://github.com/MystenLabs/deepbookv3.git"
+rev = "v1.2.0" # pinned
+"#;
+        let lock = r#"[move]
+version = 0
+dependencies = [
+  { name = "Sui" },
+]
+
+[[move.package]]
+name = "MoveStdlib"
+source = { git = "https://github.com/MystenLabs/sui.git", rev = "mainnet-v1.20.0", subdir = "crates/sui-framework/packages/move-stdlib" }
+
+[[move.package]]
+name = "Sui"
+source = { git = "https://github.com/MystenLabs/sui.git", rev = "mainnet-v1.20.0", subdir = "crates/sui-framework/packages/sui-framework" }
+"#;
+        let found = dependencies(&files(&[("vault/Move.toml", manifest), ("vault/Move.lock", lock)]));
+        let summary: Vec<_> = found
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_deref(), d.file_path.as_str(), d.line_number, d.direct))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Sui", Some("mainnet-v1.20.0"), "vault/Move.toml", Some(5), true),
+                ("DeepBook", Some("v1.2.0"), "vault/Move.toml", Some(8), true),
+                ("MoveStdlib", Some("mainnet-v1.20.0"), "vault/Move.lock", Some(9), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_cargo_dependencies_from_the_workspace_lock_file() {
+        let manifest = r#"[package]
+name = "vault"
+
+[dependencies]
+anchor-lang = "0.29"
+spl = { package = "spl-token", version = "4.0.0", features = ["no-entrypoint"] }
+helpers = { path = "../helpers" }
+"#;
+        let lock = r#"[[package]]
+name = "anchor-lang"
+version = "0.29.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "vault"
+version = "0.1.0"
+
+[[package]]
+name = "curve25519-dalek"
+version = "3.2.1"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        let found = dependencies(&files(&[("programs/vault/Cargo.toml", manifest), ("Cargo.lock", lock)]));
+        let summary: Vec<_> = found
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_deref(), d.file_path.as_str(), d.direct))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("anchor-lang", Some("0.29.0"), "programs/vault/Cargo.toml", true),
+                ("spl-token", Some("4.0.0"), "programs/vault/Cargo.toml", true),
+                ("curve25519-dalek", Some("3.2.1"), "Cargo.lock", false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Scope a token needs to see findings that are not yet public and to move
+/// findings through disclosure.
+pub const SCOPE_VULNERABILITIES_DISCLOSURE: &str = "vulnerabilities:disclosure";
+
+/// Where a finding is in coordinated disclosure. Findings only move forward,
+/// and only public ones are shown to everyone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisclosureState {
+    /// Reported by an analysis and not yet shared with anyone.
+    Draft,
+    /// Shared with the repository's maintainers.
+    PrivatelyDisclosed,
+    /// Kept private until `embargo_until`, when it is published.
+    Embargoed,
+    Public,
+}
+
+impl DisclosureState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisclosureState::Draft => "draft",
+            DisclosureState::PrivatelyDisclosed => "privately_disclosed",
+            DisclosureState::Embargoed => "embargoed",
+            DisclosureState::Public => "public",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "draft" => DisclosureState::Draft,
+            "privately_disclosed" => DisclosureState::PrivatelyDisclosed,
+            "embargoed" => DisclosureState::Embargoed,
+            _ => DisclosureState::Public,
+        }
+    }
+}
+
+/// A finding's disclosure state and when it moved through it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VulnerabilityDisclosure {
+    pub vulnerability_id: Uuid,
+    pub repository_id: Uuid,
+    pub state: DisclosureState,
+    /// When an embargoed finding is published.
+    pub embargo_until: Option<DateTime<Utc>>,
+    /// When the finding was shared with the maintainers.
+    pub disclosed_at: Option<DateTime<Utc>>,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Who last moved the finding; `system` for automatic publication.
+    pub updated_by: Option<String>,
+}
+
+/// Check that a finding in `from` may move to `to`, embargoed until
+/// `embargo_until`. Findings move forward only, may skip states, and an
+/// embargo may be moved while it runs. An embargo needs an end in the future.
+pub fn check_transition(
+    from: DisclosureState,
+    to: DisclosureState,
+    embargo_until: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let invalid = |message: String| Err(Error::InvalidDisclosureTransition { message });
+    if to < from || (to == from && to != DisclosureState::Embargoed) {
+        return invalid(format!("a {} finding cannot become {}", from.as_str(), to.as_str()));
+    }
+    match (to, embargo_until) {
+        (DisclosureState::Embargoed, None) => invalid("an embargo needs embargo_until".to_string()),
+        (DisclosureState::Embargoed, Some(until)) if until <= now => {
+            invalid("embargo_until must be in the future".to_string())
+        }
+        (DisclosureState::Embargoed, Some(_)) => Ok(()),
+        (_, Some(_)) => invalid("only embargoed findings take embargo_until".to_string()),
+        (_, None) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn findings_move_forward_only() {
+        let now = Utc::now();
+        let later = Some(now + Duration::days(30));
+
+        assert!(check_transition(DisclosureState::Draft, DisclosureState::PrivatelyDisclosed, None, now).is_ok());
+        assert!(check_transition(DisclosureState::PrivatelyDisclosed, DisclosureState::Embargoed, later, now).is_ok());
+        assert!(check_transition(DisclosureState::Embargoed, DisclosureState::Embargoed, later, now).is_ok());
+        assert!(check_transition(DisclosureState::Draft, DisclosureState::Public, None, now).is_ok());
+        assert!(check_transition(DisclosureState::Public, DisclosureState::Draft, None, now).is_err());
+        assert!(check_transition(DisclosureState::Embargoed, DisclosureState::PrivatelyDisclosed, None, now).is_err());
+        assert!(check_transition(DisclosureState::Draft, DisclosureState::Draft, None, now).is_err());
+    }
+
+    #[test]
+    fn embargoes_need_a_future_end() {
+        let now = Utc::now();
+
+        assert!(check_transition(DisclosureState::Draft, DisclosureState::Embargoed, None, now).is_err());
+        assert!(check_transition(
+            DisclosureState::Draft,
+            DisclosureState::Embargoed,
+            Some(now - Duration::hours(1)),
+            now
+        )
+        .is_err());
+        assert!(check_transition(DisclosureState::Draft, DisclosureState::Public, Some(now), now).is_err());
+    }
+}
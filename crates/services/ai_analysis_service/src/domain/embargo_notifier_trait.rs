@@ -0,0 +1,13 @@
+use crate::domain::disclosure::VulnerabilityDisclosure;
+use crate::error::Result;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Warns a repository's maintainers that embargoes on its findings are about
+/// to lapse, after which the findings are published.
+#[async_trait]
+pub trait EmbargoNotifier: Send + Sync {
+    /// Report `disclosures`, embargoed findings of the repository whose
+    /// embargo ends soon.
+    async fn notify_lapsing(&self, repository_id: Uuid, disclosures: &[VulnerabilityDisclosure]) -> Result<()>;
+}
@@ -0,0 +1,112 @@
+use crate::domain::analysis_models::{FindingKind, VulnerabilityFinding};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Stable identity of a finding across analyses, from its rule, normalized
+/// file path and flagged code. Line numbers are left out so a finding keeps
+/// its identity when code above it moves. Dependency findings are told apart
+/// by their advisory instead, so one stays the same finding while the
+/// dependency is bumped to versions the advisory still covers.
+pub fn fingerprint(finding: &VulnerabilityFinding) -> String {
+    let code = match (&finding.kind, &finding.cve_id, &finding.code_snippet) {
+        (FindingKind::Dependency, Some(advisory), _) => format!("advisory:{}", advisory),
+        (_, _, Some(snippet)) => normalize_code(snippet),
+        // Without the code, the line is all that tells findings apart.
+        (_, _, None) => format!("line:{}", finding.line_number.unwrap_or(0)),
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(finding.vulnerability_type.rule_id());
+    hasher.update([0u8]);
+    hasher.update(normalize_path(&finding.file_path));
+    hasher.update([0u8]);
+    hasher.update(code);
+    hex::encode(hasher.finalize())
+}
+
+/// Fingerprints of one analysis's findings, in order. The same rule can flag
+/// identical code more than once in a file; later occurrences get their
+/// ordinal mixed in so each stays distinct.
+pub fn fingerprints(findings: &[VulnerabilityFinding]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    findings
+        .iter()
+        .map(|finding| {
+            let base = fingerprint(finding);
+            let occurrence = seen.entry(base.clone()).or_insert(0);
+            *occurrence += 1;
+            if *occurrence == 1 {
+                base
+            } else {
+                hex::encode(Sha256::digest(format!("{}#{}", base, occurrence)))
+            }
+        })
+        .collect()
+}
+
+/// Repository-relative path with forward slashes and no leading `./`.
+pub fn normalize_path(file_path: &str) -> String {
+    let path = file_path.trim().replace('\\', "/");
+    let mut path = path.as_str();
+    while let Some(rest) = path.strip_prefix("./").or_else(|| path.strip_prefix('/')) {
+        path = rest;
+    }
+    path.to_string()
+}
+
+/// Code with whitespace runs collapsed, so reformatting does not change it.
+fn normalize_code(code: &str) -> String {
+    code.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::analysis_models::{Severity, VulnerabilityType};
+    use uuid::Uuid;
+
+    fn finding(file_path: &str, line_number: u32, code: &str) -> VulnerabilityFinding {
+        VulnerabilityFinding {
+            id: Uuid::new_v4(),
+            vulnerability_type: VulnerabilityType::IntegerOverflow,
+            severity: Severity::Medium,
+            confidence_score: 60.0,
+            file_path: file_path.to_string(),
+            line_number: Some(line_number),
+            code_snippet: Some(code.to_string()),
+            description: "Arithmetic operations without overflow protection".to_string(),
+            recommendation: "Use safe arithmetic".to_string(),
+            cve_id: None,
+            cwe_id: None,
+            cvss_vector: None,
+            cvss_score: None,
+            is_false_positive: false,
+            prompt_version: None,
+            model: None,
+            compilation_status: None,
+            kind: FindingKind::Code,
+            evidence: None,
+        }
+    }
+
+    #[test]
+    fn survives_moves_and_reformatting() {
+        let before = finding("./sources/pool.move", 10, "let total = a + b;");
+        let after = finding("sources/pool.move", 42, "let total  =  a + b;");
+        assert_eq!(fingerprint(&before), fingerprint(&after));
+
+        let changed = finding("sources/pool.move", 42, "let total = a + c;");
+        assert_ne!(fingerprint(&before), fingerprint(&changed));
+    }
+
+    #[test]
+    fn keeps_repeated_code_distinct() {
+        let findings = [
+            finding("sources/pool.move", 10, "x = x + 1;"),
+            finding("sources/pool.move", 20, "x = x + 1;"),
+        ];
+        let fingerprints = fingerprints(&findings);
+        assert_eq!(fingerprints[0], fingerprint(&findings[0]));
+        assert_ne!(fingerprints[0], fingerprints[1]);
+    }
+}
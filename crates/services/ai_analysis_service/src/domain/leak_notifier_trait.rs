@@ -0,0 +1,13 @@
+use crate::domain::analysis_models::VulnerabilityFinding;
+use crate::error::Result;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Tells a repository's owners that an analysis found secrets committed to
+/// it, as soon as the analysis is stored.
+#[async_trait]
+pub trait LeakNotifier: Send + Sync {
+    /// Report `findings`, secrets not reported by an earlier analysis of the
+    /// repository. Their snippets are redacted.
+    async fn notify(&self, repository_id: Uuid, commit_sha: &str, findings: &[VulnerabilityFinding]) -> Result<()>;
+}
@@ -2,6 +2,7 @@ use crate::domain::analysis_models::{VulnerabilityFinding, SecurityRecommendatio
 use crate::error::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LLMRequest {
@@ -46,4 +47,144 @@ pub trait LLMProvider: Send + Sync {
     fn get_provider_name(&self) -> &str;
     
     fn get_model_name(&self) -> &str;
-}
\ No newline at end of file
+
+    /// Requests, tokens and cost so far, per backend.
+    fn usage(&self) -> Vec<ProviderUsage> {
+        Vec::new()
+    }
+}
+
+/// A backend that completes prompts: one hosted API or local endpoint.
+/// `LLMClient` runs analyses on a fallback chain of these.
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    async fn complete(&self, request: &LLMRequest) -> Result<LLMResponse>;
+
+    fn name(&self) -> &str;
+
+    fn model(&self) -> &str;
+
+    fn pricing(&self) -> TokenPricing;
+}
+
+/// Price of a backend's tokens, in USD per million.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenPricing {
+    pub prompt_per_million: f64,
+    pub completion_per_million: f64,
+}
+
+impl TokenPricing {
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        (usage.prompt_tokens as f64 * self.prompt_per_million
+            + usage.completion_tokens as f64 * self.completion_per_million)
+            / 1_000_000.0
+    }
+}
+
+/// What one backend of a chain has been used for.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProviderUsage {
+    pub provider: String,
+    pub model: String,
+    /// Calls made, retries included.
+    pub requests: u64,
+    /// Requests that failed after their retries, and were passed down the chain.
+    pub failures: u64,
+    /// Responses rejected with 429, retried or not.
+    pub rate_limited: u64,
+    /// Requests answered from the response cache, without a call.
+    pub cache_hits: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// The usage between two snapshots of the same chain, leaving out backends
+/// that were neither called nor served from the cache in between.
+pub fn usage_since(before: &[ProviderUsage], after: &[ProviderUsage]) -> Vec<ProviderUsage> {
+    after
+        .iter()
+        .filter_map(|now| {
+            let then = before.iter().find(|then| then.provider == now.provider);
+            let then = then.cloned().unwrap_or_default();
+            let requests = now.requests.saturating_sub(then.requests);
+            let cache_hits = now.cache_hits.saturating_sub(then.cache_hits);
+            (requests > 0 || cache_hits > 0).then(|| ProviderUsage {
+                provider: now.provider.clone(),
+                model: now.model.clone(),
+                requests,
+                failures: now.failures.saturating_sub(then.failures),
+                rate_limited: now.rate_limited.saturating_sub(then.rate_limited),
+                cache_hits,
+                prompt_tokens: now.prompt_tokens.saturating_sub(then.prompt_tokens),
+                completion_tokens: now.completion_tokens.saturating_sub(then.completion_tokens),
+                cost_usd: now.cost_usd - then.cost_usd,
+            })
+        })
+        .collect()
+}
+
+/// How a backend's 429 responses are retried before the chain moves on.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The wait before retry `attempt` (from 0): the provider's `Retry-After`
+    /// when it gave one, otherwise exponential backoff. `jitter`, between 0
+    /// and 1, spreads retries of concurrent analyses over up to half the wait
+    /// again so they do not hit the provider together.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>, jitter: f64) -> Duration {
+        let backoff = retry_after
+            .unwrap_or_else(|| self.base_delay.saturating_mul(2u32.saturating_pow(attempt)))
+            .min(self.max_delay);
+        backoff + backoff.mul_f64(jitter.clamp(0.0, 1.0) / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_back_off_within_bounds() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(policy.delay(0, None, 0.0), Duration::from_millis(500));
+        assert_eq!(policy.delay(2, None, 0.0), Duration::from_secs(2));
+        assert_eq!(policy.delay(2, None, 1.0), Duration::from_secs(3));
+        assert_eq!(policy.delay(20, None, 0.0), Duration::from_secs(30));
+        assert_eq!(policy.delay(0, Some(Duration::from_secs(7)), 0.0), Duration::from_secs(7));
+    }
+
+    #[test]
+    fn usage_since_covers_only_backends_called() {
+        let usage = |provider: &str, requests, prompt_tokens| ProviderUsage {
+            provider: provider.to_string(),
+            requests,
+            prompt_tokens,
+            ..Default::default()
+        };
+        let before = [usage("OpenAI", 2, 100), usage("Anthropic", 1, 50)];
+        let after = [usage("OpenAI", 2, 100), usage("Anthropic", 3, 150), usage("Ollama", 1, 10)];
+
+        assert_eq!(
+            usage_since(&before, &after),
+            [usage("Anthropic", 2, 100), usage("Ollama", 1, 10)]
+        );
+    }
+}
@@ -1,5 +1,21 @@
+pub mod ai_budget;
 pub mod analysis_engine;
 pub mod analysis_models;
 pub mod analysis_repository_trait;
+pub mod analysis_ruleset;
+pub mod bytecode_analyzer_trait;
+pub mod change_set;
+pub mod cvss;
+pub mod dependency_advisory;
+pub mod dependency_manifest;
+pub mod disclosure;
+pub mod embargo_notifier_trait;
+pub mod finding_fingerprint;
+pub mod leak_notifier_trait;
+pub mod move_bytecode;
+pub mod patch_regression;
+pub mod regression_notifier_trait;
+pub mod secret_scanner;
 pub mod vulnerability_patterns;
-pub mod llm_provider_trait;
\ No newline at end of file
+pub mod llm_provider_trait;
+pub mod static_analyzer_trait;
\ No newline at end of file
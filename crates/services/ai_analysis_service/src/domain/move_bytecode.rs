@@ -0,0 +1,585 @@
+use crate::domain::analysis_models::{FindingKind, Severity, VulnerabilityFinding, VulnerabilityType};
+use regex::Regex;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// A module as the Move disassembler prints it: its structs with their
+/// abilities and its functions with their signatures and instructions.
+#[derive(Debug, Clone, Default)]
+pub struct DisassembledModule {
+    pub name: String,
+    pub structs: Vec<StructDefinition>,
+    pub functions: Vec<FunctionDefinition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StructDefinition {
+    pub name: String,
+    pub abilities: Vec<String>,
+    /// Field types, e.g. `Balance<T>`.
+    pub fields: Vec<String>,
+}
+
+impl StructDefinition {
+    pub fn has(&self, ability: &str) -> bool {
+        self.abilities.iter().any(|a| a == ability)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Private,
+    /// `public(friend)` or `public(package)`.
+    Package,
+    Public,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionDefinition {
+    pub name: String,
+    pub visibility: Visibility,
+    pub is_entry: bool,
+    /// Parameter types, e.g. `&mut Vault`.
+    pub parameters: Vec<String>,
+    pub return_type: Option<String>,
+    pub instructions: Vec<String>,
+}
+
+impl FunctionDefinition {
+    /// Whether anyone may call the function in a transaction.
+    pub fn is_callable(&self) -> bool {
+        self.visibility == Visibility::Public || self.is_entry
+    }
+
+    /// Whether the function is handed a capability, by reference or value.
+    pub fn takes_capability(&self) -> bool {
+        self.parameters.iter().any(|ty| is_capability_name(base_type(ty)))
+    }
+
+    fn calls(&self, function: &str) -> bool {
+        self.instructions.iter().any(|instruction| {
+            instruction
+                .strip_prefix("Call ")
+                .is_some_and(|call| call.starts_with(function))
+        })
+    }
+
+    /// Type arguments of calls to `function`, e.g. `AdminCap` for
+    /// `Call transfer::public_transfer<AdminCap>(AdminCap, address)`.
+    fn call_type_arguments<'a>(&'a self, function: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.instructions.iter().filter_map(move |instruction| {
+            let call = instruction.strip_prefix("Call ")?.strip_prefix(function)?;
+            let arguments = call.strip_prefix('<')?;
+            Some(base_type(&arguments[..arguments.find('>')?]))
+        })
+    }
+
+    fn packs(&self, struct_name: &str) -> bool {
+        self.instructions.iter().any(|instruction| {
+            instruction.starts_with("Pack")
+                && instruction
+                    .split_once('(')
+                    .is_some_and(|(_, rest)| base_type(rest.trim_end_matches(')')) == struct_name)
+        })
+    }
+}
+
+fn module_header() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"^module\s+(?:\w+(?:\.|::))?(\w+)\s*\{").unwrap())
+}
+
+fn struct_header() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"^(?:public\s+)?struct\s+(\w+)(?:<[^>]*>)?(?:\s+has\s+([\w,\s]+?))?\s*\{").unwrap()
+    })
+}
+
+fn function_header() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"^(entry\s+)?(public(?:\((?:friend|package)\))?\s+)?(entry\s+)?(?:native\s+)?(?:fun\s+)?(\w+)(?:<[^>]*>)?\((.*?)\)(?:\s*:\s*([^{]+?))?\s*\{?\s*$",
+        )
+        .unwrap()
+    })
+}
+
+impl DisassembledModule {
+    /// Parse the disassembler's output. `None` if it holds no module.
+    pub fn parse(text: &str) -> Option<Self> {
+        enum Block {
+            None,
+            Struct(StructDefinition),
+            Function(FunctionDefinition),
+        }
+
+        let mut module: Option<DisassembledModule> = None;
+        let mut block = Block::None;
+        for line in text.lines() {
+            let top_level = !line.starts_with(char::is_whitespace);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            if let Some(captures) = module_header().captures(line).filter(|_| top_level) {
+                module = Some(DisassembledModule { name: captures[1].to_string(), ..Default::default() });
+                continue;
+            }
+            let Some(module) = module.as_mut() else {
+                continue;
+            };
+
+            if top_level && line.starts_with('}') {
+                match std::mem::replace(&mut block, Block::None) {
+                    Block::Struct(definition) => module.structs.push(definition),
+                    Block::Function(definition) => module.functions.push(definition),
+                    Block::None => {}
+                }
+                continue;
+            }
+            match &mut block {
+                Block::Struct(definition) => {
+                    if let Some((_, ty)) = line.split_once(':') {
+                        definition.fields.push(ty.trim().trim_end_matches(',').to_string());
+                    }
+                    continue;
+                }
+                Block::Function(definition) => {
+                    // Skip basic block labels such as `B0:`
+                    if !(line.ends_with(':') && line.starts_with('B')) {
+                        // Drop the instruction's offset, e.g. `0: `
+                        let instruction = line
+                            .split_once(": ")
+                            .filter(|(offset, _)| offset.chars().all(|c| c.is_ascii_digit()))
+                            .map_or(line, |(_, rest)| rest);
+                        definition.instructions.push(instruction.to_string());
+                    }
+                    continue;
+                }
+                Block::None => {}
+            }
+            if !top_level {
+                continue;
+            }
+
+            if let Some(captures) = struct_header().captures(line) {
+                let definition = StructDefinition {
+                    name: captures[1].to_string(),
+                    abilities: captures
+                        .get(2)
+                        .map(|abilities| abilities.as_str().split(',').map(|a| a.trim().to_string()).collect())
+                        .unwrap_or_default(),
+                    fields: Vec::new(),
+                };
+                if line.ends_with('}') {
+                    module.structs.push(definition);
+                } else {
+                    block = Block::Struct(definition);
+                }
+            } else if let Some(captures) = function_header().captures(line) {
+                if matches!(&captures[4], "use" | "friend" | "const") {
+                    continue;
+                }
+                let visibility = match captures.get(2).map(|m| m.as_str().trim()) {
+                    Some("public") => Visibility::Public,
+                    Some(_) => Visibility::Package,
+                    None => Visibility::Private,
+                };
+                let definition = FunctionDefinition {
+                    name: captures[4].to_string(),
+                    visibility,
+                    is_entry: captures.get(1).is_some() || captures.get(3).is_some(),
+                    parameters: split_top_level(&captures[5])
+                        .into_iter()
+                        .map(|parameter| parameter.split_once(':').map_or(parameter, |(_, ty)| ty).trim().to_string())
+                        .filter(|parameter| !parameter.is_empty())
+                        .collect(),
+                    return_type: captures.get(6).map(|ty| ty.as_str().trim().to_string()),
+                    instructions: Vec::new(),
+                };
+                // Native functions have no body
+                if line.ends_with('{') {
+                    block = Block::Function(definition);
+                } else {
+                    module.functions.push(definition);
+                }
+            }
+        }
+        module
+    }
+
+    fn local_struct(&self, name: &str) -> Option<&StructDefinition> {
+        self.structs.iter().find(|definition| definition.name == name)
+    }
+
+    fn is_local_capability(&self, name: &str) -> bool {
+        is_capability_name(name) && self.local_struct(name).is_some_and(|definition| definition.has("key"))
+    }
+
+    /// Whether another struct of the module holds `name` in a field.
+    fn is_wrapped(&self, name: &str) -> bool {
+        self.structs.iter().any(|definition| {
+            definition.name != name && definition.fields.iter().any(|ty| mentions_type(ty, name))
+        })
+    }
+
+    fn instructions(&self) -> impl Iterator<Item = &str> {
+        self.functions
+            .iter()
+            .flat_map(|function| function.instructions.iter().map(String::as_str))
+    }
+
+    /// Capability leaks, entry functions anyone may call to change shared
+    /// objects, and abilities the module never needs.
+    pub fn check(&self) -> Vec<BytecodeIssue> {
+        let mut issues = Vec::new();
+        for function in &self.functions {
+            issues.extend(self.check_capability_leak(function));
+            issues.extend(self.check_unrestricted_entry(function));
+        }
+        for definition in &self.structs {
+            issues.extend(self.check_unused_abilities(definition));
+        }
+        issues
+    }
+
+    fn check_capability_leak(&self, function: &FunctionDefinition) -> Option<BytecodeIssue> {
+        let shared = ["transfer::share_object", "transfer::public_share_object"]
+            .iter()
+            .flat_map(|call| function.call_type_arguments(call))
+            .find(|name| self.is_local_capability(name));
+        if let Some(capability) = shared {
+            return Some(self.issue(
+                BytecodeCheck::CapabilityLeak,
+                &function.name,
+                format!("`{}` shares capability `{}`, so any transaction can use it", function.name, capability),
+            ));
+        }
+
+        // `init` runs once, at publication, and is where capabilities belong
+        if function.name == "init" || !function.is_callable() || function.takes_capability() {
+            return None;
+        }
+        let returned = function
+            .return_type
+            .as_deref()
+            .map(split_tuple)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|ty| !ty.starts_with('&') && self.is_local_capability(base_type(ty)));
+        let minted = self
+            .structs
+            .iter()
+            .find(|definition| self.is_local_capability(&definition.name) && function.packs(&definition.name));
+        let capability = returned.map(base_type).or(minted.map(|definition| definition.name.as_str()))?;
+        Some(self.issue(
+            BytecodeCheck::CapabilityLeak,
+            &function.name,
+            format!(
+                "{} function `{}` hands out capability `{}` without requiring one",
+                if function.is_entry { "Entry" } else { "Public" },
+                function.name,
+                capability
+            ),
+        ))
+    }
+
+    fn check_unrestricted_entry(&self, function: &FunctionDefinition) -> Option<BytecodeIssue> {
+        if !function.is_entry || function.takes_capability() || function.calls("tx_context::sender") {
+            return None;
+        }
+        let object = function.parameters.iter().find_map(|ty| {
+            let object = base_type(ty.strip_prefix("&mut ")?);
+            self.local_struct(object).filter(|definition| definition.has("key"))
+        })?;
+        Some(self.issue(
+            BytecodeCheck::UnrestrictedEntry,
+            &function.name,
+            format!(
+                "Entry function `{}` changes `{}` without a capability or a sender check",
+                function.name, object.name
+            ),
+        ))
+    }
+
+    fn check_unused_abilities(&self, definition: &StructDefinition) -> Vec<BytecodeIssue> {
+        let name = definition.name.as_str();
+        let mut issues = Vec::new();
+
+        let copied = self.instructions().any(|instruction| {
+            (instruction.starts_with("CopyLoc") || instruction.starts_with("ReadRef"))
+                && mentions_type(instruction, name)
+        });
+        if definition.has("copy") && !copied && !self.is_wrapped(name) {
+            issues.push(self.issue(
+                BytecodeCheck::UnusedAbility,
+                name,
+                format!("`{}` has `copy` but the module never copies it; values can be duplicated", name),
+            ));
+        }
+
+        let stored = self.functions.iter().any(|function| {
+            [
+                "transfer::public_transfer",
+                "transfer::public_share_object",
+                "transfer::public_freeze_object",
+                "dynamic_field::add",
+                "dynamic_object_field::add",
+            ]
+            .iter()
+            .any(|call| function.call_type_arguments(call).any(|ty| ty == name))
+        });
+        if definition.has("key") && definition.has("store") && !stored && !self.is_wrapped(name) {
+            let mut issue = self.issue(
+                BytecodeCheck::UnusedAbility,
+                name,
+                format!(
+                    "`{}` has `store` but the module never needs it; holders can transfer or wrap it outside the module's rules",
+                    name
+                ),
+            );
+            if is_capability_name(name) {
+                issue.severity = Severity::Medium;
+            }
+            issues.push(issue);
+        }
+        issues
+    }
+
+    fn issue(&self, check: BytecodeCheck, item: &str, description: String) -> BytecodeIssue {
+        BytecodeIssue {
+            check,
+            module: self.name.clone(),
+            item: item.to_string(),
+            severity: check.severity(),
+            description,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BytecodeCheck {
+    CapabilityLeak,
+    UnrestrictedEntry,
+    UnusedAbility,
+}
+
+impl BytecodeCheck {
+    fn severity(&self) -> Severity {
+        match self {
+            BytecodeCheck::CapabilityLeak => Severity::Critical,
+            BytecodeCheck::UnrestrictedEntry => Severity::High,
+            BytecodeCheck::UnusedAbility => Severity::Low,
+        }
+    }
+}
+
+/// Something a check found in a module, on one of its functions or structs.
+#[derive(Debug, Clone)]
+pub struct BytecodeIssue {
+    pub check: BytecodeCheck,
+    pub module: String,
+    /// The function or struct.
+    pub item: String,
+    pub severity: Severity,
+    pub description: String,
+}
+
+impl BytecodeIssue {
+    /// Line of the item's declaration in the module's source, with the line.
+    pub fn locate<'a>(&self, source: &'a str) -> Option<(usize, &'a str)> {
+        let keyword = match self.check {
+            BytecodeCheck::UnusedAbility => "struct",
+            _ => "fun",
+        };
+        let declaration = Regex::new(&format!(r"\b{}\s+{}\b", keyword, regex::escape(&self.item))).ok()?;
+        source
+            .lines()
+            .enumerate()
+            .find(|(_, line)| declaration.is_match(line))
+            .map(|(index, line)| (index + 1, line))
+    }
+
+    pub fn finding(&self, file_path: &str, location: Option<(usize, &str)>) -> VulnerabilityFinding {
+        let (vulnerability_type, confidence, cwe_id, recommendation) = match self.check {
+            BytecodeCheck::CapabilityLeak => (
+                VulnerabilityType::UnauthorizedAccess,
+                85.0,
+                "CWE-269",
+                "Create capabilities only in `init` or behind an existing capability, and transfer them to a known owner",
+            ),
+            BytecodeCheck::UnrestrictedEntry => (
+                VulnerabilityType::AccessControl,
+                55.0,
+                "CWE-284",
+                "Require a capability argument or check `tx_context::sender` before changing shared state",
+            ),
+            BytecodeCheck::UnusedAbility => (
+                VulnerabilityType::Other("Unused Ability".to_string()),
+                50.0,
+                "CWE-732",
+                "Remove abilities the module does not use so the type cannot be copied or transferred freely",
+            ),
+        };
+        VulnerabilityFinding {
+            id: Uuid::new_v4(),
+            vulnerability_type,
+            severity: self.severity.clone(),
+            confidence_score: confidence,
+            file_path: file_path.to_string(),
+            line_number: location.map(|(line_number, _)| line_number as u32),
+            code_snippet: location.map(|(_, line)| line.trim().to_string()),
+            description: format!("{} (confirmed in the bytecode of module `{}`)", self.description, self.module),
+            recommendation: recommendation.to_string(),
+            cve_id: None,
+            cwe_id: Some(cwe_id.to_string()),
+            cvss_vector: None,
+            cvss_score: None,
+            is_false_positive: false,
+            prompt_version: None,
+            model: None,
+            compilation_status: None,
+            kind: FindingKind::Code,
+            evidence: None,
+        }
+        .classify()
+    }
+}
+
+/// Name of the module a Move source file declares.
+pub fn source_module_name(source: &str) -> Option<&str> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| Regex::new(r"(?m)^\s*module\s+(?:\w+::)?(\w+)").unwrap());
+    regex.captures(source).and_then(|captures| captures.get(1)).map(|name| name.as_str())
+}
+
+/// `AdminCap`, `TreasuryCap`, `MintCapability` and the like.
+fn is_capability_name(name: &str) -> bool {
+    name.ends_with("Cap") || name.ends_with("Capability")
+}
+
+/// The struct a type names, without references, module path or type
+/// arguments: `&mut coin::TreasuryCap<T>` becomes `TreasuryCap`.
+fn base_type(ty: &str) -> &str {
+    let ty = ty.trim().trim_start_matches('&').trim_start_matches("mut ").trim();
+    let ty = ty.split('<').next().unwrap_or(ty);
+    ty.rsplit(|c| c == ':' || c == '.').next().unwrap_or(ty).trim()
+}
+
+fn mentions_type(text: &str, name: &str) -> bool {
+    text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).any(|word| word == name)
+}
+
+/// Split on commas outside `<>` and `()`.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (index, c) in text.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(text[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+/// The types of a return type, which may be a tuple.
+fn split_tuple(ty: &str) -> Vec<&str> {
+    let ty = ty.trim();
+    match ty.strip_prefix('(').and_then(|inner| inner.strip_suffix(')')) {
+        Some(inner) => split_top_level(inner),
+        None => vec![ty],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VAULT: &str = "// Move bytecode v6
+module 0.vault {
+use 0000000000000000000000000000000000000000000000000000000000000002::transfer;
+use 0000000000000000000000000000000000000000000000000000000000000002::tx_context;
+
+struct AdminCap has store, key {
+	id: UID
+}
+struct Vault has key {
+	id: UID,
+	balance: u64
+}
+struct Receipt has copy, drop {
+	amount: u64
+}
+
+init(Arg0: &mut TxContext) {
+B0:
+	0: MoveLoc[0](Arg0: &mut TxContext)
+	1: Call object::new(&mut TxContext): UID
+	2: Pack[0](AdminCap)
+	3: Ret
+}
+public new_admin(Arg0: &mut TxContext): AdminCap {
+B0:
+	0: MoveLoc[0](Arg0: &mut TxContext)
+	1: Call object::new(&mut TxContext): UID
+	2: Pack[0](AdminCap)
+	3: Ret
+}
+entry public withdraw(Arg0: &mut Vault, Arg1: u64) {
+B0:
+	0: MoveLoc[0](Arg0: &mut Vault)
+	1: Ret
+}
+entry public pause(Arg0: &AdminCap, Arg1: &mut Vault) {
+B0:
+	0: Ret
+}
+}";
+
+    #[test]
+    fn parses_disassembly() {
+        let module = DisassembledModule::parse(VAULT).unwrap();
+        assert_eq!(module.name, "vault");
+        assert_eq!(module.structs.len(), 3);
+        assert_eq!(module.structs[0].abilities, vec!["store", "key"]);
+        assert_eq!(module.structs[1].fields, vec!["UID", "u64"]);
+
+        let withdraw = module.functions.iter().find(|f| f.name == "withdraw").unwrap();
+        assert!(withdraw.is_entry);
+        assert_eq!(withdraw.visibility, Visibility::Public);
+        assert_eq!(withdraw.parameters, vec!["&mut Vault", "u64"]);
+        assert_eq!(withdraw.instructions[0], "MoveLoc[0](Arg0: &mut Vault)");
+        assert_eq!(module.functions[1].return_type.as_deref(), Some("AdminCap"));
+    }
+
+    #[test]
+    fn finds_leaks_unrestricted_entries_and_unused_abilities() {
+        let issues = DisassembledModule::parse(VAULT).unwrap().check();
+        let found: Vec<(BytecodeCheck, &str)> =
+            issues.iter().map(|issue| (issue.check, issue.item.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (BytecodeCheck::CapabilityLeak, "new_admin"),
+                (BytecodeCheck::UnrestrictedEntry, "withdraw"),
+                (BytecodeCheck::UnusedAbility, "AdminCap"),
+                (BytecodeCheck::UnusedAbility, "Receipt"),
+            ]
+        );
+
+        let source = "module vault::vault {\n    public fun new_admin(ctx: &mut TxContext): AdminCap {\n";
+        let finding = issues[0].finding("sources/vault.move", issues[0].locate(source));
+        assert_eq!(finding.line_number, Some(2));
+        assert_eq!(finding.cwe_id.as_deref(), Some("CWE-269"));
+        assert_eq!(source_module_name(source), Some("vault"));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::domain::analysis_models::VulnerabilityFinding;
+use crate::domain::finding_fingerprint::{fingerprints, normalize_path};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// How an analysis found a merged patch's fix undone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegressionReason {
+    /// None of the lines the patch added are left in the patched files.
+    Reverted,
+    /// The finding the patch fixed had been closed and is reported again at
+    /// the same fingerprint.
+    Reappeared,
+}
+
+impl RegressionReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RegressionReason::Reverted => "reverted",
+            RegressionReason::Reappeared => "reappeared",
+        }
+    }
+}
+
+impl From<String> for RegressionReason {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "reverted" => RegressionReason::Reverted,
+            _ => RegressionReason::Reappeared,
+        }
+    }
+}
+
+/// A merged patch of the repository that has not regressed yet, with the
+/// finding it fixed.
+#[derive(Debug, Clone)]
+pub struct MergedPatch {
+    pub patch_id: Uuid,
+    pub vulnerability_id: Uuid,
+    pub fingerprint: Option<String>,
+    /// Whether the finding was resolved or marked fixed before the analysis.
+    pub finding_closed: bool,
+    pub diff: String,
+    pub merged_at: DateTime<Utc>,
+}
+
+/// A patch flagged as regressed by an analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchRegression {
+    pub patch_id: Uuid,
+    pub vulnerability_id: Uuid,
+    pub repository_id: Uuid,
+    pub title: String,
+    pub reason: RegressionReason,
+    pub analysis_id: Uuid,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub regressed_at: DateTime<Utc>,
+}
+
+/// The repository's merged patches and the analysed content of the files
+/// they touched, read before an analysis is stored over their findings.
+#[derive(Debug, Clone, Default)]
+pub struct PatchRegressionCheck {
+    patches: Vec<MergedPatch>,
+    contents: HashMap<String, String>,
+}
+
+impl PatchRegressionCheck {
+    pub fn new(patches: Vec<MergedPatch>, files: &HashMap<String, String>) -> Self {
+        let patched: HashSet<String> = patches
+            .iter()
+            .flat_map(|patch| added_lines(&patch.diff).into_keys())
+            .collect();
+        let contents = files
+            .iter()
+            .map(|(path, content)| (normalize_path(path), content))
+            .filter(|(path, _)| patched.contains(path))
+            .map(|(path, content)| (path, content.clone()))
+            .collect();
+        Self { patches, contents }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patches.is_empty()
+    }
+
+    /// The patches `findings` show regressed. A patch counts as reverted
+    /// before its finding is checked, since reverting it usually brings the
+    /// finding back too.
+    pub fn regressions(&self, findings: &[VulnerabilityFinding]) -> Vec<(Uuid, RegressionReason)> {
+        let reported: HashSet<String> = findings
+            .iter()
+            .zip(fingerprints(findings))
+            .filter(|(finding, _)| !finding.is_false_positive)
+            .map(|(_, fingerprint)| fingerprint)
+            .collect();
+
+        self.patches
+            .iter()
+            .filter_map(|patch| {
+                if is_reverted(&patch.diff, &self.contents) {
+                    return Some((patch.patch_id, RegressionReason::Reverted));
+                }
+                let reappeared = patch.finding_closed
+                    && patch.fingerprint.as_ref().is_some_and(|fingerprint| reported.contains(fingerprint));
+                reappeared.then_some((patch.patch_id, RegressionReason::Reappeared))
+            })
+            .collect()
+    }
+}
+
+/// Lines a unified diff adds, whitespace-collapsed, by the normalized path
+/// of the file they are added to. Lines of punctuation alone, such as a
+/// closing brace, say nothing about whether the patch is still there and
+/// are left out.
+fn added_lines(diff: &str) -> HashMap<String, HashSet<String>> {
+    let mut added: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut file: Option<String> = None;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            let path = path.split('\t').next().unwrap_or(path).trim();
+            file = (path != "/dev/null")
+                .then(|| normalize_path(path.strip_prefix("b/").unwrap_or(path)));
+            continue;
+        }
+        if line.starts_with("--- ") || line.starts_with("diff ") {
+            continue;
+        }
+        let (Some(path), Some(code)) = (&file, line.strip_prefix('+')) else {
+            continue;
+        };
+        let code = normalize_line(code);
+        if code.chars().any(char::is_alphanumeric) {
+            added.entry(path.clone()).or_default().insert(code);
+        }
+    }
+    added
+}
+
+/// Whether none of the lines the diff adds are in the patched files that
+/// were analysed. A patch none of whose files were analysed is not judged.
+fn is_reverted(diff: &str, contents: &HashMap<String, String>) -> bool {
+    let mut checked = false;
+    for (path, lines) in added_lines(diff) {
+        let Some(content) = contents.get(&path) else {
+            continue;
+        };
+        checked = true;
+        if content.lines().map(normalize_line).any(|line| lines.contains(&line)) {
+            return false;
+        }
+    }
+    checked
+}
+
+fn normalize_line(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+--- a/sources/vault.move
++++ b/sources/vault.move
+@@ -10,3 +10,4 @@
+ public fun withdraw(vault: &mut Vault, amount: u64, ctx: &mut TxContext) {
++    assert!(tx_context::sender(ctx) == vault.owner, ENotOwner);
+     coin::take(&mut vault.balance, amount, ctx)
++}
+";
+
+    fn merged_patch(finding_closed: bool) -> MergedPatch {
+        MergedPatch {
+            patch_id: Uuid::new_v4(),
+            vulnerability_id: Uuid::new_v4(),
+            fingerprint: None,
+            finding_closed,
+            diff: DIFF.to_string(),
+            merged_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn patch_is_reverted_when_its_added_lines_are_gone() {
+        let patched = "public fun withdraw(vault: &mut Vault, amount: u64, ctx: &mut TxContext) {\n\
+            \x20   assert!(tx_context::sender(ctx)  ==  vault.owner, ENotOwner);\n}\n";
+        let reverted = "public fun withdraw(vault: &mut Vault, amount: u64, ctx: &mut TxContext) {\n}\n";
+        let patch = merged_patch(false);
+
+        let files = HashMap::from([("./sources/vault.move".to_string(), patched.to_string())]);
+        let check = PatchRegressionCheck::new(vec![patch.clone()], &files);
+        assert!(check.regressions(&[]).is_empty());
+
+        let files = HashMap::from([("sources/vault.move".to_string(), reverted.to_string())]);
+        let check = PatchRegressionCheck::new(vec![patch.clone()], &files);
+        assert_eq!(check.regressions(&[]), vec![(patch.patch_id, RegressionReason::Reverted)]);
+
+        // Files the analysis did not read tell nothing either way
+        let check = PatchRegressionCheck::new(vec![patch], &HashMap::new());
+        assert!(check.regressions(&[]).is_empty());
+    }
+}
@@ -0,0 +1,13 @@
+use crate::domain::patch_regression::PatchRegression;
+use crate::error::Result;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Tells the people following a repository's patches that an analysis found
+/// merged fixes undone.
+#[async_trait]
+pub trait RegressionNotifier: Send + Sync {
+    /// Report `regressions`, patches just flagged as regressed by the
+    /// analysis of `commit_sha`.
+    async fn notify_regressed(&self, repository_id: Uuid, commit_sha: &str, regressions: &[PatchRegression]) -> Result<()>;
+}
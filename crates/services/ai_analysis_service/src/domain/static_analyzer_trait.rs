@@ -0,0 +1,93 @@
+use crate::domain::analysis_models::{FindingKind, Severity, VulnerabilityFinding, VulnerabilityType};
+use crate::domain::analysis_ruleset::Ruleset;
+use crate::error::Result;
+use uuid::Uuid;
+
+/// A static analyzer for one smart contract language. Analyzers are picked
+/// per file, so a repository mixing Move, Solidity and Anchor programs gets
+/// each file checked by the analyzer for its language.
+pub trait StaticAnalyzer: Send + Sync {
+    /// Short name recorded with the findings, e.g. `solidity`.
+    fn name(&self) -> &str;
+
+    fn version(&self) -> &str;
+
+    /// File extensions the analyzer reads, with their leading dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Whether the analyzer should check this file. By default, any file with
+    /// one of its extensions.
+    fn handles(&self, file_path: &str, _content: &str) -> bool {
+        has_extension(file_path, self.extensions())
+    }
+
+    fn analyze_file(&self, file_path: &str, content: &str) -> Result<Vec<VulnerabilityFinding>>;
+
+    /// `analyze_file` under a repository's ruleset. Analyzers built on
+    /// `VulnerabilityPatterns` override it to skip the disabled patterns;
+    /// custom patterns are checked by the caller.
+    fn analyze_file_with_ruleset(
+        &self,
+        file_path: &str,
+        content: &str,
+        _ruleset: &Ruleset,
+    ) -> Result<Vec<VulnerabilityFinding>> {
+        self.analyze_file(file_path, content)
+    }
+}
+
+pub fn has_extension(file_path: &str, extensions: &[&str]) -> bool {
+    let file_path = file_path.to_lowercase();
+    extensions.iter().any(|extension| file_path.ends_with(extension))
+}
+
+/// The code of a line with any trailing `//` comment removed, or `None` for
+/// lines that are only a comment. Block comments are recognised by their
+/// usual leading `/*` or `*`.
+pub fn code_of_line(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    if trimmed.is_empty()
+        || trimmed.starts_with("//")
+        || trimmed.starts_with("/*")
+        || trimmed.starts_with('*')
+    {
+        return None;
+    }
+    Some(trimmed.split("//").next().unwrap_or(trimmed).trim_end())
+}
+
+/// What a line-level check reports; `finding` ties it to the line it matched.
+pub struct LineRule {
+    pub vulnerability_type: VulnerabilityType,
+    pub severity: Severity,
+    pub confidence: f64,
+    pub description: &'static str,
+    pub recommendation: &'static str,
+}
+
+impl LineRule {
+    pub fn finding(&self, file_path: &str, line_number: usize, code: &str) -> VulnerabilityFinding {
+        VulnerabilityFinding {
+            id: Uuid::new_v4(),
+            vulnerability_type: self.vulnerability_type.clone(),
+            severity: self.severity.clone(),
+            confidence_score: self.confidence,
+            file_path: file_path.to_string(),
+            line_number: Some(line_number as u32),
+            code_snippet: Some(code.trim().to_string()),
+            description: self.description.to_string(),
+            recommendation: self.recommendation.to_string(),
+            cve_id: None,
+            cwe_id: None,
+            cvss_vector: None,
+            cvss_score: None,
+            is_false_positive: false,
+            prompt_version: None,
+            model: None,
+            compilation_status: None,
+            kind: FindingKind::Code,
+            evidence: None,
+        }
+        .classify()
+    }
+}
@@ -1,9 +1,36 @@
-use crate::domain::analysis_models::{VulnerabilityFinding, VulnerabilityType, Severity};
+use crate::domain::analysis_models::{FindingKind, VulnerabilityFinding, VulnerabilityType, Severity};
 use crate::error::{Error, Result};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
 use uuid::Uuid;
 
+/// Compiled pattern regexes shared by every `VulnerabilityPatterns` instance,
+/// so each expression is compiled once per process rather than once per scan.
+static REGEX_CACHE: OnceLock<RwLock<HashMap<String, Regex>>> = OnceLock::new();
+
+fn compiled_regex(regex_str: &str) -> Result<Regex> {
+    let cache = REGEX_CACHE.get_or_init(Default::default);
+    if let Some(regex) = cache.read().unwrap_or_else(|e| e.into_inner()).get(regex_str) {
+        return Ok(regex.clone());
+    }
+
+    let regex = Regex::new(regex_str)?;
+    cache
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(regex_str.to_string(), regex.clone());
+    Ok(regex)
+}
+
+/// A function declaration: Move `fun`, Solidity `function` or Rust `fn`.
+fn function_declaration() -> &'static Regex {
+    static FUNCTION_DECLARATION: OnceLock<Regex> = OnceLock::new();
+    FUNCTION_DECLARATION
+        .get_or_init(|| Regex::new(r"\b(?:fun|function|fn)\s+(\w+)").expect("valid regex"))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VulnerabilityPattern {
     pub id: String,
@@ -14,6 +41,12 @@ pub struct VulnerabilityPattern {
     pub confidence_base: f64,
     pub pattern: PatternRule,
     pub recommendation: String,
+    /// CWE identifier reported with matches; defaults to the type's.
+    #[serde(default)]
+    pub cwe_id: Option<String>,
+    /// CVSS v3.1 vector reported with matches; defaults to the type's.
+    #[serde(default)]
+    pub cvss_vector: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +78,84 @@ pub enum ConditionOperator {
     Regex,
 }
 
+impl ConditionOperator {
+    pub fn matches(&self, text: &str, value: &str) -> Result<bool> {
+        Ok(match self {
+            ConditionOperator::Equals => text == value,
+            ConditionOperator::Contains => text.contains(value),
+            ConditionOperator::StartsWith => text.starts_with(value),
+            ConditionOperator::EndsWith => text.ends_with(value),
+            ConditionOperator::Regex => compiled_regex(value)?.is_match(text),
+        })
+    }
+}
+
+/// The AST node types `ASTPatternRule`s can match, with their fields.
+pub const AST_NODE_FIELDS: &[(&str, &[&str])] = &[("function", &["name", "signature", "body"])];
+
+/// A function found in source code, for `ASTPatternRule`s on `function`
+/// nodes.
+struct FunctionNode<'a> {
+    /// 1-based line of the declaration.
+    line_number: usize,
+    name: &'a str,
+    /// From the start of the declaration's line up to the body.
+    signature: &'a str,
+    /// The body with its braces; empty for declarations without one.
+    body: &'a str,
+}
+
+impl<'a> FunctionNode<'a> {
+    fn field(&self, field: &str) -> Option<&'a str> {
+        match field {
+            "name" => Some(self.name),
+            "signature" => Some(self.signature),
+            "body" => Some(self.body),
+            _ => None,
+        }
+    }
+}
+
+/// The functions declared in `code`. Bodies are delimited by counting
+/// braces, so braces inside strings or comments can cut them short.
+fn function_nodes(code: &str) -> Vec<FunctionNode<'_>> {
+    function_declaration()
+        .captures_iter(code)
+        .filter_map(|captures| {
+            let declaration = captures.get(0)?;
+            let line_start = code[..declaration.start()].rfind('\n').map_or(0, |index| index + 1);
+            let rest = &code[declaration.start()..];
+            let end = rest.find(['{', ';'])?;
+            let body = if rest[end..].starts_with('{') { block(&rest[end..]) } else { "" };
+            Some(FunctionNode {
+                line_number: code[..declaration.start()].matches('\n').count() + 1,
+                name: captures.get(1)?.as_str(),
+                signature: code[line_start..declaration.start() + end].trim(),
+                body,
+            })
+        })
+        .collect()
+}
+
+/// The block `text` opens, up to its matching brace, or all of `text` if
+/// the braces do not balance.
+fn block(text: &str) -> &str {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &text[..=index];
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
 pub struct VulnerabilityPatterns {
     patterns: Vec<VulnerabilityPattern>,
 }
@@ -56,10 +167,47 @@ impl VulnerabilityPatterns {
         }
     }
 
+    /// `patterns` alone, without the built-in Sui Move ones.
+    pub fn with_patterns(patterns: Vec<VulnerabilityPattern>) -> Self {
+        Self { patterns }
+    }
+
+    /// Compile every regex rule up front. Called during startup warm-up so the
+    /// first analysis request does not pay the compilation cost; returns the
+    /// number of compiled expressions.
+    pub fn warm_up(&self) -> Result<usize> {
+        fn collect<'a>(rule: &'a PatternRule, out: &mut Vec<&'a str>) {
+            match rule {
+                PatternRule::Regex(regex_str) => out.push(regex_str),
+                PatternRule::Combined(rules) => rules.iter().for_each(|r| collect(r, out)),
+                PatternRule::ASTPattern(_) => {}
+            }
+        }
+
+        let mut regexes = Vec::new();
+        for pattern in &self.patterns {
+            collect(&pattern.pattern, &mut regexes);
+        }
+        for regex_str in &regexes {
+            compiled_regex(regex_str)?;
+        }
+        Ok(regexes.len())
+    }
+
     pub fn scan_code(&self, file_path: &str, code: &str) -> Result<Vec<VulnerabilityFinding>> {
+        self.scan_code_matching(file_path, code, |_| true)
+    }
+
+    /// `scan_code` with only the patterns `enabled` accepts.
+    pub fn scan_code_matching(
+        &self,
+        file_path: &str,
+        code: &str,
+        enabled: impl Fn(&VulnerabilityPattern) -> bool,
+    ) -> Result<Vec<VulnerabilityFinding>> {
         let mut findings = Vec::new();
 
-        for pattern in &self.patterns {
+        for pattern in self.patterns.iter().filter(|pattern| enabled(pattern)) {
             if let Some(matches) = self.apply_pattern(pattern, file_path, code)? {
                 findings.extend(matches);
             }
@@ -78,10 +226,7 @@ impl VulnerabilityPatterns {
             PatternRule::Regex(regex_str) => {
                 self.apply_regex_pattern(pattern, file_path, code, regex_str)
             }
-            PatternRule::ASTPattern(_) => {
-                // TODO: Implement AST pattern matching
-                Ok(None)
-            }
+            PatternRule::ASTPattern(rule) => self.apply_ast_pattern(pattern, file_path, code, rule),
             PatternRule::Combined(rules) => {
                 let mut all_findings = Vec::new();
                 for rule in rules {
@@ -103,31 +248,84 @@ impl VulnerabilityPatterns {
         code: &str,
         regex_str: &str,
     ) -> Result<Option<Vec<VulnerabilityFinding>>> {
-        let regex = Regex::new(regex_str)?;
+        let regex = compiled_regex(regex_str)?;
         let mut findings = Vec::new();
 
         for (line_number, line) in code.lines().enumerate() {
             if let Some(_captures) = regex.captures(line) {
-                let finding = VulnerabilityFinding {
-                    id: Uuid::new_v4(),
-                    vulnerability_type: pattern.vulnerability_type.clone(),
-                    severity: pattern.severity.clone(),
-                    confidence_score: pattern.confidence_base,
-                    file_path: file_path.to_string(),
-                    line_number: Some((line_number + 1) as u32),
-                    code_snippet: Some(line.trim().to_string()),
-                    description: pattern.description.clone(),
-                    recommendation: pattern.recommendation.clone(),
-                    cve_id: None,
-                    is_false_positive: false,
+                findings.push(self.finding(pattern, file_path, line_number + 1, line));
+            }
+        }
+
+        Ok(if findings.is_empty() { None } else { Some(findings) })
+    }
+
+    /// Match `rule` against the functions declared in `code`, reporting each
+    /// function every condition holds for at its declaration.
+    fn apply_ast_pattern(
+        &self,
+        pattern: &VulnerabilityPattern,
+        file_path: &str,
+        code: &str,
+        rule: &ASTPatternRule,
+    ) -> Result<Option<Vec<VulnerabilityFinding>>> {
+        if rule.node_type != "function" {
+            return Ok(None);
+        }
+
+        let mut findings = Vec::new();
+        for function in function_nodes(code) {
+            let mut matched = true;
+            for condition in &rule.conditions {
+                let Some(text) = function.field(&condition.field) else {
+                    matched = false;
+                    break;
                 };
-                findings.push(finding);
+                if !condition.operator.matches(text, &condition.value)? {
+                    matched = false;
+                    break;
+                }
+            }
+            if matched {
+                let signature = function.signature.lines().next().unwrap_or_default();
+                findings.push(self.finding(pattern, file_path, function.line_number, signature));
             }
         }
 
         Ok(if findings.is_empty() { None } else { Some(findings) })
     }
 
+    fn finding(
+        &self,
+        pattern: &VulnerabilityPattern,
+        file_path: &str,
+        line_number: usize,
+        line: &str,
+    ) -> VulnerabilityFinding {
+        VulnerabilityFinding {
+            id: Uuid::new_v4(),
+            vulnerability_type: pattern.vulnerability_type.clone(),
+            severity: pattern.severity.clone(),
+            confidence_score: pattern.confidence_base,
+            file_path: file_path.to_string(),
+            line_number: Some(line_number as u32),
+            code_snippet: Some(line.trim().to_string()),
+            description: pattern.description.clone(),
+            recommendation: pattern.recommendation.clone(),
+            cve_id: None,
+            cwe_id: pattern.cwe_id.clone(),
+            cvss_vector: pattern.cvss_vector.clone(),
+            cvss_score: None,
+            is_false_positive: false,
+            prompt_version: None,
+            model: None,
+            compilation_status: None,
+            kind: FindingKind::Code,
+            evidence: None,
+        }
+        .classify()
+    }
+
     fn load_sui_move_patterns() -> Vec<VulnerabilityPattern> {
         vec![
             // Unauthorized access patterns
@@ -140,6 +338,8 @@ impl VulnerabilityPatterns {
                 confidence_base: 80.0,
                 pattern: PatternRule::Regex(r"coin::transfer".to_string()),
                 recommendation: "Add proper authorization checks before transfers".to_string(),
+                cwe_id: Some("CWE-862".to_string()),
+                cvss_vector: Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:H/A:N".to_string()),
             },
 
             // Access control patterns
@@ -152,6 +352,8 @@ impl VulnerabilityPatterns {
                 confidence_base: 70.0,
                 pattern: PatternRule::Regex(r"public\s+fun\s+\w+.*\{".to_string()),
                 recommendation: "Add capability parameter and verification".to_string(),
+                cwe_id: Some("CWE-284".to_string()),
+                cvss_vector: Some("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:L/I:H/A:N".to_string()),
             },
 
             // Integer overflow patterns
@@ -164,6 +366,8 @@ impl VulnerabilityPatterns {
                 confidence_base: 60.0,
                 pattern: PatternRule::Regex(r"[+\-*/]\s*\w+".to_string()),
                 recommendation: "Use safe arithmetic operations or add overflow checks".to_string(),
+                cwe_id: Some("CWE-190".to_string()),
+                cvss_vector: Some("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:H/A:N".to_string()),
             },
 
             // Logic error patterns
@@ -176,6 +380,8 @@ impl VulnerabilityPatterns {
                 confidence_base: 75.0,
                 pattern: PatternRule::Regex(r"balance::(value|split|join)".to_string()),
                 recommendation: "Add balance validation and error handling".to_string(),
+                cwe_id: Some("CWE-682".to_string()),
+                cvss_vector: Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:H/A:L".to_string()),
             },
 
             // Timestamp dependence
@@ -188,6 +394,8 @@ impl VulnerabilityPatterns {
                 confidence_base: 65.0,
                 pattern: PatternRule::Regex(r"timestamp::now_seconds".to_string()),
                 recommendation: "Avoid timestamp-dependent critical logic or add proper validation".to_string(),
+                cwe_id: Some("CWE-829".to_string()),
+                cvss_vector: Some("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:L/A:N".to_string()),
             },
 
             // Resource exhaustion
@@ -200,6 +408,8 @@ impl VulnerabilityPatterns {
                 confidence_base: 55.0,
                 pattern: PatternRule::Regex(r"vector::(push_back|append)".to_string()),
                 recommendation: "Add vector size limits and bounds checking".to_string(),
+                cwe_id: Some("CWE-770".to_string()),
+                cvss_vector: Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:H".to_string()),
             },
 
             // Insufficient validation
@@ -212,6 +422,8 @@ impl VulnerabilityPatterns {
                 confidence_base: 50.0,
                 pattern: PatternRule::Regex(r"public\s+fun\s+\w+\([^)]+\)\s*\{".to_string()),
                 recommendation: "Add comprehensive input validation using assert! statements".to_string(),
+                cwe_id: Some("CWE-20".to_string()),
+                cvss_vector: Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:L/A:N".to_string()),
             },
         ]
     }
@@ -2,33 +2,95 @@ use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "ai_analysis")]
 pub enum Error {
     #[error("Analysis failed: {message}")]
+    #[taxonomy(kind = Internal)]
     AnalysisFailed { message: String },
 
     #[error("LLM API error: {message}")]
+    #[taxonomy(kind = Upstream, code = "LLM_API_ERROR")]
     LLMApiError { message: String },
 
+    #[error("{provider} rate limited the request")]
+    #[taxonomy(kind = RateLimited, code = "LLM_RATE_LIMITED", retry_after = retry_after_seconds)]
+    LLMRateLimited { provider: String, retry_after_seconds: Option<u64> },
+
+    #[error("No LLM provider could serve the request: {message}")]
+    #[taxonomy(kind = Unavailable, code = "LLM_UNAVAILABLE")]
+    LLMUnavailable { message: String },
+
     #[error("Pattern matching error: {message}")]
+    #[taxonomy(kind = Internal)]
     PatternMatchingError { message: String },
 
     #[error("File parsing error: {file_path} - {message}")]
+    #[taxonomy(kind = Validation, expose)]
     FileParsingError { file_path: String, message: String },
 
+    #[error("Invalid CVSS v3.1 vector: {vector}")]
+    #[taxonomy(kind = Validation, code = "INVALID_CVSS_VECTOR", expose)]
+    InvalidCvssVector { vector: String },
+
+    #[error("Invalid ruleset: {message}")]
+    #[taxonomy(kind = Validation, code = "INVALID_RULESET", expose)]
+    InvalidRuleset { message: String },
+
+    #[error("Repository {repository_id} not found")]
+    #[taxonomy(kind = NotFound, code = "REPOSITORY_NOT_FOUND", expose)]
+    RepositoryNotFound { repository_id: uuid::Uuid },
+
+    #[error("Vulnerability {vulnerability_id} not found")]
+    #[taxonomy(kind = NotFound, code = "VULNERABILITY_NOT_FOUND", expose)]
+    VulnerabilityNotFound { vulnerability_id: uuid::Uuid },
+
+    #[error("No archived report of analysis {analysis_id}")]
+    #[taxonomy(kind = NotFound, code = "ANALYSIS_REPORT_NOT_FOUND", expose)]
+    AnalysisReportNotFound { analysis_id: uuid::Uuid },
+
+    #[error("The {scope} AI budget of ${limit_usd:.2} is spent for this month")]
+    #[taxonomy(kind = RateLimited, code = "AI_BUDGET_EXCEEDED", expose)]
+    AiBudgetExceeded { scope: String, limit_usd: f64 },
+
+    #[error("The {scope} AI budget is spent for this month; LLM analyses need approval {approval_id}")]
+    #[taxonomy(kind = PermissionDenied, code = "AI_BUDGET_APPROVAL_REQUIRED", expose)]
+    AiBudgetApprovalRequired { scope: String, approval_id: uuid::Uuid },
+
+    #[error("Invalid AI budget: {message}")]
+    #[taxonomy(kind = Validation, code = "INVALID_AI_BUDGET", expose)]
+    InvalidAiBudget { message: String },
+
+    #[error("No {scope} {scope_id} to budget")]
+    #[taxonomy(kind = NotFound, code = "AI_BUDGET_NOT_FOUND", expose)]
+    AiBudgetNotFound { scope: String, scope_id: uuid::Uuid },
+
+    #[error("Invalid disclosure transition: {message}")]
+    #[taxonomy(kind = Conflict, code = "INVALID_DISCLOSURE_TRANSITION", expose)]
+    InvalidDisclosureTransition { message: String },
+
+    #[error("No pending AI budget approval {approval_id}")]
+    #[taxonomy(kind = NotFound, code = "AI_BUDGET_APPROVAL_NOT_FOUND", expose)]
+    BudgetApprovalNotFound { approval_id: uuid::Uuid },
+
     #[error("Vulnerability scoring error: {message}")]
+    #[taxonomy(kind = Internal)]
     VulnerabilityScoringError { message: String },
 
     #[error("Database error: {message}")]
+    #[taxonomy(kind = Internal)]
     DatabaseError { message: String },
 
     #[error("Configuration error: {message}")]
+    #[taxonomy(kind = Internal)]
     ConfigurationError { message: String },
 
     #[error("External service error: {service} - {message}")]
+    #[taxonomy(kind = Upstream)]
     ExternalServiceError { service: String, message: String },
 
     #[error("Internal error: {0}")]
+    #[taxonomy(kind = Internal)]
     Internal(String),
 }
 
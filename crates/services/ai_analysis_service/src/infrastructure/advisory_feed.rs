@@ -0,0 +1,215 @@
+use crate::domain::analysis_models::Severity;
+use crate::domain::cvss::CvssVector;
+use crate::domain::dependency_advisory::{AffectedRange, DependencyAdvisory};
+use crate::domain::dependency_manifest::Ecosystem;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Fetches advisories from an OSV-format feed: a JSON array of OSV records,
+/// or an object with them under `vulns`. Only advisories against Move and
+/// crates.io packages are kept.
+pub struct AdvisoryFeed {
+    url: String,
+    client: Client,
+}
+
+impl AdvisoryFeed {
+    pub fn new(url: String) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(45))
+            .build()
+            .unwrap_or_default();
+        Self { url, client }
+    }
+
+    /// The feed at `DEPENDENCY_ADVISORY_FEED_URL`, if set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("DEPENDENCY_ADVISORY_FEED_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .map(Self::new)
+    }
+
+    pub async fn fetch(&self) -> Result<Vec<DependencyAdvisory>> {
+        let response = self.client.get(&self.url).send().await?;
+        if !response.status().is_success() {
+            return Err(Error::ExternalServiceError {
+                service: "advisory feed".to_string(),
+                message: format!("{} returned {}", self.url, response.status()),
+            });
+        }
+        let feed: OsvFeed = response.json().await.map_err(|e| Error::ExternalServiceError {
+            service: "advisory feed".to_string(),
+            message: format!("Invalid OSV feed: {}", e),
+        })?;
+        let records = match feed {
+            OsvFeed::Records(records) | OsvFeed::Wrapped { vulns: records } => records,
+        };
+        Ok(records.iter().flat_map(advisories).collect())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OsvFeed {
+    Records(Vec<OsvRecord>),
+    Wrapped { vulns: Vec<OsvRecord> },
+}
+
+#[derive(Deserialize)]
+struct OsvRecord {
+    id: String,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    details: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    modified: DateTime<Utc>,
+    #[serde(default)]
+    withdrawn: Option<DateTime<Utc>>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+    #[serde(default)]
+    severity: Vec<OsvSeverity>,
+    #[serde(default)]
+    database_specific: Option<serde_json::Value>,
+    #[serde(default)]
+    references: Vec<OsvReference>,
+}
+
+#[derive(Deserialize)]
+struct OsvAffected {
+    package: OsvPackage,
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct OsvPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct OsvRange {
+    #[serde(rename = "type")]
+    range_type: String,
+    #[serde(default)]
+    events: Vec<OsvEvent>,
+}
+
+#[derive(Deserialize)]
+struct OsvEvent {
+    #[serde(default)]
+    introduced: Option<String>,
+    #[serde(default)]
+    fixed: Option<String>,
+    #[serde(default)]
+    last_affected: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OsvSeverity {
+    #[serde(rename = "type")]
+    severity_type: String,
+    score: String,
+}
+
+#[derive(Deserialize)]
+struct OsvReference {
+    #[serde(rename = "type")]
+    reference_type: String,
+    url: String,
+}
+
+/// One advisory per package of the record we check. Withdrawn records have
+/// none.
+fn advisories(record: &OsvRecord) -> Vec<DependencyAdvisory> {
+    if record.withdrawn.is_some() {
+        return Vec::new();
+    }
+    let database_specific = record.database_specific.as_ref();
+    let cvss_vector = record
+        .severity
+        .iter()
+        .filter(|severity| severity.severity_type == "CVSS_V3")
+        .find_map(|severity| severity.score.parse::<CvssVector>().ok());
+    let severity = match database_specific
+        .and_then(|specific| specific.get("severity"))
+        .and_then(|severity| severity.as_str())
+        .map(str::to_ascii_uppercase)
+        .as_deref()
+    {
+        Some("CRITICAL") => Severity::Critical,
+        Some("HIGH") => Severity::High,
+        Some("MODERATE") | Some("MEDIUM") => Severity::Medium,
+        Some("LOW") => Severity::Low,
+        _ => cvss_vector.map(|vector| vector.severity()).unwrap_or(Severity::Medium),
+    };
+    let cwe_id = database_specific
+        .and_then(|specific| specific.get("cwe_ids"))
+        .and_then(|cwe_ids| cwe_ids.as_array()?.first()?.as_str())
+        .map(str::to_string);
+    let summary = record
+        .summary
+        .clone()
+        .or_else(|| record.details.as_ref().and_then(|details| details.lines().next().map(str::to_string)))
+        .unwrap_or_else(|| record.id.clone());
+    let url = record
+        .references
+        .iter()
+        .find(|reference| reference.reference_type == "ADVISORY")
+        .or_else(|| record.references.first())
+        .map(|reference| reference.url.clone());
+
+    record
+        .affected
+        .iter()
+        .filter_map(|affected| {
+            Some(DependencyAdvisory {
+                id: record.id.clone(),
+                ecosystem: Ecosystem::from_osv(&affected.package.ecosystem)?,
+                package: affected.package.name.clone(),
+                summary: summary.clone(),
+                severity: severity.clone(),
+                cve_id: record.aliases.iter().find(|alias| alias.starts_with("CVE-")).cloned(),
+                cwe_id: cwe_id.clone(),
+                cvss_vector: cvss_vector.map(|vector| vector.to_string()),
+                ranges: affected_ranges(&affected.ranges),
+                versions: affected.versions.clone(),
+                url: url.clone(),
+                modified_at: record.modified,
+            })
+        })
+        .collect()
+}
+
+/// Ranges from the events of version ranges. Git ranges name commits, which
+/// cannot be ordered without the history, and are left out; their commits
+/// are usually listed in `versions` too.
+fn affected_ranges(ranges: &[OsvRange]) -> Vec<AffectedRange> {
+    let mut affected = Vec::new();
+    for range in ranges.iter().filter(|range| range.range_type != "GIT") {
+        let mut current: Option<AffectedRange> = None;
+        for event in &range.events {
+            if let Some(introduced) = &event.introduced {
+                affected.extend(current.take());
+                current = Some(AffectedRange { introduced: Some(introduced.clone()), ..Default::default() });
+            }
+            if event.fixed.is_some() || event.last_affected.is_some() {
+                let mut closed = current.take().unwrap_or_default();
+                closed.fixed = event.fixed.clone();
+                closed.last_affected = event.last_affected.clone();
+                affected.push(closed);
+            }
+        }
+        affected.extend(current);
+    }
+    affected
+}
@@ -1,10 +1,27 @@
-use crate::domain::analysis_models::{AnalysisResult, VulnerabilityFinding};
-use crate::domain::analysis_repository_trait::{AnalysisRepository, VulnerabilityStatistics};
+use crate::domain::ai_budget::{
+    AiBudget, AiUsageFilter, AiUsageSummary, AiUsageTotals, ApprovalStatus, BudgetApproval, BudgetPolicy, BudgetScope,
+    BudgetStatus,
+};
+use crate::domain::analysis_models::{AnalysisResult, CompilationStatus, FindingKind, VulnerabilityFinding};
+use crate::domain::analysis_ruleset::{AnalysisRuleset, Ruleset};
+use crate::domain::dependency_advisory::DependencyAdvisory;
+use crate::domain::dependency_manifest::Ecosystem;
+use crate::domain::disclosure::{check_transition, DisclosureState, VulnerabilityDisclosure};
+use crate::domain::finding_fingerprint::{fingerprint, fingerprints};
+use crate::domain::llm_provider_trait::ProviderUsage;
+use crate::domain::patch_regression::{MergedPatch, PatchRegression, RegressionReason};
+use crate::domain::analysis_repository_trait::{
+    AnalysisRepository, FindingEvidenceRecord, SuppressionKind, SuppressionStatistics, VulnerabilityFilter,
+    VulnerabilityRecord, VulnerabilitySort, VulnerabilityStatistics, VulnerabilityStatus,
+    VulnerabilitySuppression,
+};
 use crate::error::{Error, Result};
 use async_trait::async_trait;
 use jd_core::AppState;
+use jd_messaging::{events::VulnerabilityCreated, outbox};
 use rust_decimal::Decimal;
-use sqlx::{PgPool, Row};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use time;
@@ -54,6 +71,7 @@ impl AnalysisRepositoryImpl {
             crate::domain::analysis_models::VulnerabilityType::AccessControl => "access_control",
             crate::domain::analysis_models::VulnerabilityType::IntegerOverflow => "overflow",
             crate::domain::analysis_models::VulnerabilityType::ReentrancyLike => "reentrancy",
+            crate::domain::analysis_models::VulnerabilityType::SecretExposure => "secret_exposure",
             _ => "other",
         }
     }
@@ -63,6 +81,7 @@ impl AnalysisRepositoryImpl {
             "access_control" => crate::domain::analysis_models::VulnerabilityType::AccessControl,
             "overflow" => crate::domain::analysis_models::VulnerabilityType::IntegerOverflow,
             "reentrancy" => crate::domain::analysis_models::VulnerabilityType::ReentrancyLike,
+            "secret_exposure" => crate::domain::analysis_models::VulnerabilityType::SecretExposure,
             "other" => crate::domain::analysis_models::VulnerabilityType::Other("Unknown".to_string()),
             _ => crate::domain::analysis_models::VulnerabilityType::Other(db_type.to_string()),
         }
@@ -86,6 +105,404 @@ impl AnalysisRepositoryImpl {
             _ => crate::domain::analysis_models::Severity::Medium,
         }
     }
+
+    /// Insert a finding, or if one with the same fingerprint was stored by an
+    /// earlier analysis, move that record to this analysis and reopen it.
+    /// Findings with an active suppression are stored as false positives;
+    /// ones whose suppression expired or was revoked come back open, while
+    /// false-positive marks made without a suppression are kept. A newly
+    /// inserted finding that is not suppressed records a
+    /// `vulnerability.created` event.
+    async fn upsert_vulnerability(
+        &self,
+        vulnerability: &VulnerabilityFinding,
+        repository_id: Uuid,
+        analysis_id: Uuid,
+        fingerprint: &str,
+    ) -> Result<Uuid> {
+        let vulnerability_type_db = self.map_vulnerability_type_to_db(&vulnerability.vulnerability_type);
+        let severity_db = self.map_severity_to_db(&vulnerability.severity);
+        let database_error = |e: sqlx::Error| Error::DatabaseError { message: e.to_string() };
+
+        let mut tx = self.db().begin().await.map_err(database_error)?;
+        let row = sqlx::query(
+            r#"
+            INSERT INTO security_vulnerabilities (
+                id, repository_id, analysis_result_id, vulnerability_type, severity,
+                confidence_score, file_path, line_number, code_snippet,
+                description, recommendation, cve_id, cwe_id, cvss_vector, cvss_score,
+                is_false_positive, fingerprint, prompt_version, model, compilation_status, kind
+            ) VALUES (
+                $1, $2, $3, $4::vulnerability_type_enum, $5::severity_enum, $6, $7, $8, $9,
+                $10, $11, $12, $13, $14, $15,
+                $16 OR EXISTS (
+                    SELECT 1 FROM vulnerability_suppressions s
+                    WHERE s.repository_id = $2 AND s.fingerprint = $17
+                      AND s.revoked_at IS NULL
+                      AND (s.expires_at IS NULL OR s.expires_at > NOW())
+                ),
+                $17, $18, $19, $20, $21
+            )
+            ON CONFLICT (repository_id, fingerprint) WHERE fingerprint IS NOT NULL DO UPDATE SET
+                analysis_result_id = EXCLUDED.analysis_result_id,
+                vulnerability_type = EXCLUDED.vulnerability_type,
+                severity = EXCLUDED.severity,
+                confidence_score = EXCLUDED.confidence_score,
+                file_path = EXCLUDED.file_path,
+                line_number = EXCLUDED.line_number,
+                code_snippet = EXCLUDED.code_snippet,
+                description = EXCLUDED.description,
+                recommendation = EXCLUDED.recommendation,
+                cve_id = COALESCE(EXCLUDED.cve_id, security_vulnerabilities.cve_id),
+                cwe_id = COALESCE(EXCLUDED.cwe_id, security_vulnerabilities.cwe_id),
+                cvss_vector = COALESCE(EXCLUDED.cvss_vector, security_vulnerabilities.cvss_vector),
+                cvss_score = COALESCE(EXCLUDED.cvss_score, security_vulnerabilities.cvss_score),
+                prompt_version = EXCLUDED.prompt_version,
+                model = EXCLUDED.model,
+                compilation_status = EXCLUDED.compilation_status,
+                kind = EXCLUDED.kind,
+                is_false_positive = EXCLUDED.is_false_positive OR (
+                    security_vulnerabilities.is_false_positive AND NOT EXISTS (
+                        SELECT 1 FROM vulnerability_suppressions s
+                        WHERE s.repository_id = EXCLUDED.repository_id
+                          AND s.fingerprint = EXCLUDED.fingerprint
+                    )
+                ),
+                last_seen_at = NOW(),
+                resolved_at = NULL,
+                fixed_at = NULL
+            RETURNING id, is_false_positive, (xmax = 0) AS inserted
+            "#,
+        )
+        .bind(vulnerability.id)
+        .bind(repository_id)
+        .bind(analysis_id)
+        .bind(vulnerability_type_db)
+        .bind(severity_db)
+        .bind(vulnerability.confidence_score)
+        .bind(&vulnerability.file_path)
+        .bind(vulnerability.line_number.map(|n| n as i32))
+        .bind(&vulnerability.code_snippet)
+        .bind(&vulnerability.description)
+        .bind(&vulnerability.recommendation)
+        .bind(&vulnerability.cve_id)
+        .bind(&vulnerability.cwe_id)
+        .bind(&vulnerability.cvss_vector)
+        .bind(vulnerability.cvss_score)
+        .bind(vulnerability.is_false_positive)
+        .bind(fingerprint)
+        .bind(&vulnerability.prompt_version)
+        .bind(&vulnerability.model)
+        .bind(vulnerability.compilation_status.map(|status| status.as_str()))
+        .bind(vulnerability.kind.as_str())
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(database_error)?;
+
+        let id: Uuid = row.get("id");
+        if row.get::<bool, _>("inserted") && !row.get::<bool, _>("is_false_positive") {
+            let event = VulnerabilityCreated {
+                vulnerability_id: id,
+                repository_id,
+                analysis_id,
+                vulnerability_type: vulnerability_type_db.to_string(),
+                severity: severity_db.to_string(),
+                file_path: vulnerability.file_path.clone(),
+            };
+            outbox::record(&mut *tx, &event).await.map_err(database_error)?;
+        }
+        tx.commit().await.map_err(database_error)?;
+        Ok(id)
+    }
+
+    /// Map a row selected with `VULNERABILITY_COLUMNS`.
+    fn map_row_to_vulnerability(&self, row: &PgRow) -> VulnerabilityFinding {
+        VulnerabilityFinding {
+            id: row.get("id"),
+            vulnerability_type: self.map_db_to_vulnerability_type(&row.get::<Option<String>, _>("vulnerability_type").unwrap()),
+            severity: self.map_db_to_severity(&row.get::<Option<String>, _>("severity").unwrap()),
+            confidence_score: self.decimal_to_f64(row.get("confidence_score")),
+            file_path: row.get("file_path"),
+            line_number: row.get::<Option<i32>, _>("line_number").map(|n| n as u32),
+            code_snippet: row.get("code_snippet"),
+            description: row.get("description"),
+            recommendation: row.get("recommendation"),
+            cve_id: row.get("cve_id"),
+            cwe_id: row.get("cwe_id"),
+            cvss_vector: row.get("cvss_vector"),
+            cvss_score: row
+                .get::<Option<Decimal>, _>("cvss_score")
+                .map(|score| self.decimal_to_f64(score)),
+            is_false_positive: row.get("is_false_positive"),
+            prompt_version: row.get("prompt_version"),
+            model: row.get("model"),
+            compilation_status: row
+                .get::<Option<String>, _>("compilation_status")
+                .map(|status| CompilationStatus::from_db(&status)),
+            kind: FindingKind::from_db(&row.get::<String, _>("kind")),
+            evidence: None,
+        }
+    }
+
+    /// Map a row selected with `VULNERABILITY_COLUMNS` and `RECORD_COLUMNS`.
+    fn map_row_to_record(&self, row: &PgRow) -> VulnerabilityRecord {
+        let finding = self.map_row_to_vulnerability(row);
+        let fixed_at: Option<time::OffsetDateTime> = row.get("fixed_at");
+        let resolved_at: Option<time::OffsetDateTime> = row.get("resolved_at");
+        let status = if finding.is_false_positive {
+            VulnerabilityStatus::FalsePositive
+        } else if fixed_at.is_some() || resolved_at.is_some() {
+            VulnerabilityStatus::Fixed
+        } else {
+            VulnerabilityStatus::Open
+        };
+        VulnerabilityRecord {
+            finding,
+            repository_id: row.get("repository_id"),
+            fingerprint: row.get("fingerprint"),
+            status,
+            first_seen_at: self.offsetdatetime_to_utc(row.get("first_seen_at")),
+            last_seen_at: self.offsetdatetime_to_utc(row.get("last_seen_at")),
+            fixed_at: fixed_at.map(|dt| self.offsetdatetime_to_utc(dt)),
+            resolved_at: resolved_at.map(|dt| self.offsetdatetime_to_utc(dt)),
+            disclosure_state: DisclosureState::from_db(&row.get::<String, _>("disclosure_state")),
+            embargo_until: row
+                .get::<Option<time::OffsetDateTime>, _>("embargo_until")
+                .map(|dt| self.offsetdatetime_to_utc(dt)),
+        }
+    }
+
+    /// Map a row selected with `DISCLOSURE_COLUMNS`.
+    fn map_row_to_disclosure(&self, row: &PgRow) -> VulnerabilityDisclosure {
+        let optional_time = |column: &str| {
+            row.get::<Option<time::OffsetDateTime>, _>(column)
+                .map(|dt| self.offsetdatetime_to_utc(dt))
+        };
+        VulnerabilityDisclosure {
+            vulnerability_id: row.get("id"),
+            repository_id: row.get("repository_id"),
+            state: DisclosureState::from_db(&row.get::<String, _>("disclosure_state")),
+            embargo_until: optional_time("embargo_until"),
+            disclosed_at: optional_time("disclosed_at"),
+            published_at: optional_time("published_at"),
+            updated_by: row.get("disclosure_updated_by"),
+        }
+    }
+
+    /// Keep what an LLM-assisted finding was reported on, for the analysis
+    /// that reported it.
+    async fn save_finding_evidence(
+        &self,
+        vulnerability: &VulnerabilityFinding,
+        vulnerability_id: Uuid,
+        analysis_id: Uuid,
+    ) -> Result<()> {
+        let evidence = vulnerability.evidence.clone().unwrap_or_default();
+        sqlx::query(
+            r#"
+            INSERT INTO finding_evidence (
+                vulnerability_id, analysis_result_id, code_snippet, reasoning,
+                referenced_pattern, model, prompt_version, confidence_score
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(vulnerability_id)
+        .bind(analysis_id)
+        .bind(&vulnerability.code_snippet)
+        .bind(&evidence.reasoning)
+        .bind(&evidence.referenced_pattern)
+        .bind(&vulnerability.model)
+        .bind(&vulnerability.prompt_version)
+        .bind(vulnerability.confidence_score)
+        .execute(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+        Ok(())
+    }
+
+    /// Map a row selected with `SUPPRESSION_COLUMNS`.
+    fn map_row_to_suppression(&self, row: &PgRow) -> VulnerabilitySuppression {
+        VulnerabilitySuppression {
+            id: row.get("id"),
+            repository_id: row.get("repository_id"),
+            fingerprint: row.get("fingerprint"),
+            kind: SuppressionKind::from_db(&row.get::<String, _>("kind")),
+            reason: row.get("reason"),
+            expires_at: row
+                .get::<Option<time::OffsetDateTime>, _>("expires_at")
+                .map(|dt| self.offsetdatetime_to_utc(dt)),
+            created_by: row.get("created_by"),
+            created_at: self.offsetdatetime_to_utc(row.get("created_at")),
+            revoked_at: row
+                .get::<Option<time::OffsetDateTime>, _>("revoked_at")
+                .map(|dt| self.offsetdatetime_to_utc(dt)),
+        }
+    }
+
+    fn map_row_to_advisory(&self, row: &PgRow) -> Result<DependencyAdvisory> {
+        let ranges = serde_json::from_value(row.get("affected_ranges"))
+            .map_err(|e| Error::DatabaseError { message: format!("Invalid affected ranges: {}", e) })?;
+        Ok(DependencyAdvisory {
+            id: row.get("id"),
+            ecosystem: match row.get::<String, _>("ecosystem").as_str() {
+                "crates.io" => Ecosystem::CratesIo,
+                _ => Ecosystem::Move,
+            },
+            package: row.get("package"),
+            summary: row.get("summary"),
+            severity: self.map_db_to_severity(&row.get::<String, _>("severity")),
+            cve_id: row.get("cve_id"),
+            cwe_id: row.get("cwe_id"),
+            cvss_vector: row.get("cvss_vector"),
+            ranges,
+            versions: row.get("affected_versions"),
+            url: row.get("url"),
+            modified_at: self.offsetdatetime_to_utc(row.get("modified_at")),
+        })
+    }
+
+    fn map_row_to_ruleset(&self, row: &PgRow) -> Result<AnalysisRuleset> {
+        let custom_patterns = serde_json::from_value(row.get("custom_patterns"))
+            .map_err(|e| Error::DatabaseError { message: format!("Invalid custom patterns: {}", e) })?;
+        Ok(AnalysisRuleset {
+            repository_id: row.get("repository_id"),
+            rules: Ruleset {
+                disabled_patterns: row.get("disabled_patterns"),
+                custom_patterns,
+            },
+            updated_by: row.get("updated_by"),
+            updated_at: self.offsetdatetime_to_utc(row.get("updated_at")),
+        })
+    }
+
+    fn map_row_to_budget(&self, row: &PgRow) -> AiBudget {
+        AiBudget {
+            scope: BudgetScope::from_db(&row.get::<String, _>("scope")),
+            scope_id: row.get("scope_id"),
+            monthly_limit_usd: row.get("monthly_limit_usd"),
+            policy: BudgetPolicy::from_db(&row.get::<String, _>("policy")),
+            updated_by: row.get("updated_by"),
+            updated_at: self.offsetdatetime_to_utc(row.get("updated_at")),
+        }
+    }
+
+    fn map_row_to_approval(&self, row: &PgRow) -> BudgetApproval {
+        BudgetApproval {
+            id: row.get("id"),
+            repository_id: row.get("repository_id"),
+            commit_sha: row.get("commit_sha"),
+            status: ApprovalStatus::from_db(&row.get::<String, _>("status")),
+            requested_at: self.offsetdatetime_to_utc(row.get("requested_at")),
+            decided_by: row.get("decided_by"),
+            decided_at: row
+                .get::<Option<time::OffsetDateTime>, _>("decided_at")
+                .map(|dt| self.offsetdatetime_to_utc(dt)),
+        }
+    }
+
+    /// Usage matching `filter`, summed per `key`, an SQL expression over
+    /// `ai_usage`; most expensive first.
+    async fn ai_usage_totals(&self, filter: &AiUsageFilter, key: &str) -> Result<Vec<AiUsageTotals>> {
+        let mut query = QueryBuilder::<Postgres>::new(format!(
+            "SELECT {} AS key, COUNT(DISTINCT analysis_result_id) AS analyses, \
+             COALESCE(SUM(requests), 0)::int8 AS requests, COALESCE(SUM(cache_hits), 0)::int8 AS cache_hits, \
+             COALESCE(SUM(prompt_tokens), 0)::int8 AS prompt_tokens, \
+             COALESCE(SUM(completion_tokens), 0)::int8 AS completion_tokens, \
+             COALESCE(SUM(cost_usd), 0)::float8 AS cost_usd \
+             FROM ai_usage WHERE recorded_at >= ",
+            key
+        ));
+        query.push_bind(filter.from).push(" AND recorded_at < ").push_bind(filter.to);
+        if let Some(repository_id) = filter.repository_id {
+            query.push(" AND repository_id = ").push_bind(repository_id);
+        }
+        if let Some(organization_id) = filter.organization_id {
+            query
+                .push(" AND repository_id IN (SELECT github_repository_id FROM organization_repositories WHERE organization_id = ")
+                .push_bind(organization_id)
+                .push(")");
+        }
+        query.push(" GROUP BY 1 ORDER BY cost_usd DESC, key");
+
+        let rows = query
+            .build()
+            .fetch_all(self.db())
+            .await
+            .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+        Ok(rows
+            .iter()
+            .map(|row| AiUsageTotals {
+                key: row.get("key"),
+                analyses: row.get("analyses"),
+                requests: row.get("requests"),
+                cache_hits: row.get("cache_hits"),
+                prompt_tokens: row.get("prompt_tokens"),
+                completion_tokens: row.get("completion_tokens"),
+                cost_usd: row.get("cost_usd"),
+            })
+            .collect())
+    }
+}
+
+const VULNERABILITY_COLUMNS: &str = "id, vulnerability_type::text, severity::text, confidence_score, \
+    file_path, line_number, code_snippet, description, recommendation, cve_id, \
+    cwe_id, cvss_vector, cvss_score, is_false_positive, prompt_version, model, compilation_status, \
+    kind";
+
+/// Columns of a `VulnerabilityRecord` besides `VULNERABILITY_COLUMNS`.
+const RECORD_COLUMNS: &str = "repository_id, fingerprint, first_seen_at, last_seen_at, fixed_at, resolved_at, \
+    disclosure_state, embargo_until";
+
+const DISCLOSURE_COLUMNS: &str = "id, repository_id, disclosure_state, embargo_until, disclosed_at, published_at, \
+    disclosure_updated_by";
+
+const SUPPRESSION_COLUMNS: &str = "id, repository_id, fingerprint, kind, reason, expires_at, \
+    created_by, created_at, revoked_at";
+
+const BUDGET_COLUMNS: &str = "scope, scope_id, monthly_limit_usd::float8 AS monthly_limit_usd, policy, \
+    updated_by, updated_at";
+
+const APPROVAL_COLUMNS: &str = "id, repository_id, commit_sha, status, requested_at, decided_by, decided_at";
+
+/// Appends the `WHERE` conditions of `filter`.
+fn push_vulnerability_filter(query: &mut QueryBuilder<'_, Postgres>, filter: &VulnerabilityFilter) {
+    query.push(" WHERE 1=1");
+    if let Some(repository_id) = filter.repository_id {
+        query.push(" AND repository_id = ").push_bind(repository_id);
+    }
+    if let Some(severity) = &filter.severity {
+        query
+            .push(" AND severity = ")
+            .push_bind(severity.to_string().to_lowercase())
+            .push("::severity_enum");
+    }
+    match filter.status {
+        Some(VulnerabilityStatus::Open) => {
+            query.push(" AND fixed_at IS NULL AND resolved_at IS NULL AND is_false_positive = false");
+        }
+        Some(VulnerabilityStatus::Fixed) => {
+            query.push(" AND (fixed_at IS NOT NULL OR resolved_at IS NOT NULL)");
+        }
+        Some(VulnerabilityStatus::FalsePositive) => {
+            query.push(" AND is_false_positive = true");
+        }
+        None => {}
+    }
+    if let Some(cwe_id) = &filter.cwe_id {
+        query.push(" AND cwe_id = ").push_bind(cwe_id.to_uppercase());
+    }
+    if let Some(min_cvss_score) = filter.min_cvss_score {
+        query.push(" AND cvss_score >= ").push_bind(min_cvss_score);
+    }
+    if let Some(max_cvss_score) = filter.max_cvss_score {
+        query.push(" AND cvss_score <= ").push_bind(max_cvss_score);
+    }
+    if let Some(kind) = filter.kind {
+        query.push(" AND kind = ").push_bind(kind.as_str());
+    }
+    if let Some(disclosure_state) = filter.disclosure_state {
+        query.push(" AND disclosure_state = ").push_bind(disclosure_state.as_str());
+    }
 }
 
 #[async_trait]
@@ -119,9 +536,15 @@ impl AnalysisRepository for AnalysisRepositoryImpl {
         
         let analysis_id: Uuid = row.get("id");
 
-        // Save vulnerabilities
-        for vulnerability in &result.vulnerabilities {
-            self.save_vulnerability(vulnerability, analysis_id).await?;
+        // Save vulnerabilities, updating the records of ones seen before
+        let fingerprints = fingerprints(&result.vulnerabilities);
+        for (vulnerability, fingerprint) in result.vulnerabilities.iter().zip(&fingerprints) {
+            let vulnerability_id = self
+                .upsert_vulnerability(vulnerability, result.repository_id, analysis_id, fingerprint)
+                .await?;
+            if vulnerability.prompt_version.is_some() {
+                self.save_finding_evidence(vulnerability, vulnerability_id, analysis_id).await?;
+            }
         }
 
         Ok(analysis_id)
@@ -246,115 +669,80 @@ impl AnalysisRepository for AnalysisRepositoryImpl {
         Ok(results)
     }
 
-    async fn save_vulnerability(&self, vulnerability: &VulnerabilityFinding, analysis_id: Uuid) -> Result<Uuid> {
-        let vulnerability_type_db = self.map_vulnerability_type_to_db(&vulnerability.vulnerability_type);
-        let severity_db = self.map_severity_to_db(&vulnerability.severity);
-
-        // First, get the repository_id from the analysis
-        let repository_id: Uuid = sqlx::query_scalar(
-            "SELECT repository_id FROM code_analysis_results WHERE id = $1"
+    async fn get_latest_baseline_analysis(
+        &self,
+        repository_id: Uuid,
+        analysis_type: &crate::domain::analysis_models::AnalysisType,
+    ) -> Result<Option<AnalysisResult>> {
+        let id: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM code_analysis_results
+            WHERE repository_id = $1
+              AND analysis_type = $2::analysis_type_enum
+              AND raw_results->>'scope' IN ('full', 'incremental')
+            ORDER BY ctime DESC
+            LIMIT 1
+            "#,
         )
-        .bind(analysis_id)
-        .fetch_one(self.db())
+        .bind(repository_id)
+        .bind(self.map_analysis_type_to_db(analysis_type))
+        .fetch_optional(self.db())
         .await
         .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
 
-        let row = sqlx::query(
-            r#"
-            INSERT INTO security_vulnerabilities (
-                id, repository_id, analysis_result_id, vulnerability_type, severity,
-                confidence_score, file_path, line_number, code_snippet,
-                description, recommendation, cve_id, is_false_positive
-            ) VALUES ($1, $2, $3, $4::vulnerability_type_enum, $5::severity_enum, $6, $7, $8, $9, $10, $11, $12, $13)
-            RETURNING id
-            "#,
+        match id {
+            Some(id) => self.get_analysis_result(id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn save_vulnerability(&self, vulnerability: &VulnerabilityFinding, analysis_id: Uuid) -> Result<Uuid> {
+        // First, get the repository_id from the analysis
+        let repository_id: Uuid = sqlx::query_scalar(
+            "SELECT repository_id FROM code_analysis_results WHERE id = $1"
         )
-        .bind(vulnerability.id)
-        .bind(repository_id)
         .bind(analysis_id)
-        .bind(vulnerability_type_db)
-        .bind(severity_db)
-        .bind(vulnerability.confidence_score)
-        .bind(&vulnerability.file_path)
-        .bind(vulnerability.line_number.map(|n| n as i32))
-        .bind(&vulnerability.code_snippet)
-        .bind(&vulnerability.description)
-        .bind(&vulnerability.recommendation)
-        .bind(&vulnerability.cve_id)
-        .bind(vulnerability.is_false_positive)
         .fetch_one(self.db())
         .await
         .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
 
-        let vulnerability_id: Uuid = row.get("id");
-
-        Ok(vulnerability_id)
+        self.upsert_vulnerability(vulnerability, repository_id, analysis_id, &fingerprint(vulnerability))
+            .await
     }
 
     async fn get_vulnerabilities_for_analysis(&self, analysis_id: Uuid) -> Result<Vec<VulnerabilityFinding>> {
-        let rows = sqlx::query(
-            r#"
-            SELECT id, vulnerability_type::text, severity::text, confidence_score, 
-                   file_path, line_number, code_snippet, description, recommendation, cve_id, is_false_positive
-            FROM security_vulnerabilities
-            WHERE analysis_result_id = $1
-            "#,
-        )
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM security_vulnerabilities WHERE analysis_result_id = $1",
+            VULNERABILITY_COLUMNS
+        ))
         .bind(analysis_id)
         .fetch_all(self.db())
         .await
         .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
 
         let vulnerabilities = rows
-            .into_iter()
-            .map(|row| VulnerabilityFinding {
-                id: row.get("id"),
-                vulnerability_type: self.map_db_to_vulnerability_type(&row.get::<Option<String>, _>("vulnerability_type").unwrap()),
-                severity: self.map_db_to_severity(&row.get::<Option<String>, _>("severity").unwrap()),
-                confidence_score: self.decimal_to_f64(row.get("confidence_score")),
-                file_path: row.get("file_path"),
-                line_number: row.get::<Option<i32>, _>("line_number").map(|n| n as u32),
-                code_snippet: row.get("code_snippet"),
-                description: row.get("description"),
-                recommendation: row.get("recommendation"),
-                cve_id: row.get("cve_id"),
-                is_false_positive: row.get("is_false_positive"),
-            })
+            .iter()
+            .map(|row| self.map_row_to_vulnerability(row))
             .collect();
 
         Ok(vulnerabilities)
     }
 
     async fn get_vulnerabilities_for_repository(&self, repository_id: Uuid) -> Result<Vec<VulnerabilityFinding>> {
-        let rows = sqlx::query(
-            r#"
-            SELECT id, vulnerability_type::text, severity::text, confidence_score,
-                   file_path, line_number, code_snippet, description, recommendation, cve_id, is_false_positive
-            FROM security_vulnerabilities
-            WHERE repository_id = $1 AND fixed_at IS NULL
-            ORDER BY severity DESC, confidence_score DESC
-            "#,
-        )
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM security_vulnerabilities \
+             WHERE repository_id = $1 AND fixed_at IS NULL AND resolved_at IS NULL \
+             ORDER BY severity DESC, confidence_score DESC",
+            VULNERABILITY_COLUMNS
+        ))
         .bind(repository_id)
         .fetch_all(self.db())
         .await
         .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
 
         let vulnerabilities = rows
-            .into_iter()
-            .map(|row| VulnerabilityFinding {
-                id: row.get("id"),
-                vulnerability_type: self.map_db_to_vulnerability_type(&row.get::<Option<String>, _>("vulnerability_type").unwrap()),
-                severity: self.map_db_to_severity(&row.get::<Option<String>, _>("severity").unwrap()),
-                confidence_score: self.decimal_to_f64(row.get("confidence_score")),
-                file_path: row.get("file_path"),
-                line_number: row.get::<Option<i32>, _>("line_number").map(|n| n as u32),
-                code_snippet: row.get("code_snippet"),
-                description: row.get("description"),
-                recommendation: row.get("recommendation"),
-                cve_id: row.get("cve_id"),
-                is_false_positive: row.get("is_false_positive"),
-            })
+            .iter()
+            .map(|row| self.map_row_to_vulnerability(row))
             .collect();
 
         Ok(vulnerabilities)
@@ -394,7 +782,7 @@ impl AnalysisRepository for AnalysisRepositoryImpl {
                 COUNT(*) FILTER (WHERE severity = 'medium') as medium_count,
                 COUNT(*) FILTER (WHERE severity = 'low') as low_count,
                 COUNT(*) FILTER (WHERE is_false_positive = true) as false_positive_count,
-                COUNT(*) FILTER (WHERE fixed_at IS NOT NULL) as fixed_count
+                COUNT(*) FILTER (WHERE fixed_at IS NOT NULL OR resolved_at IS NOT NULL) as fixed_count
             FROM security_vulnerabilities
             WHERE repository_id = $1
             "#,
@@ -414,4 +802,897 @@ impl AnalysisRepository for AnalysisRepositoryImpl {
             fixed_count: stats.get::<Option<i64>, _>("fixed_count").unwrap_or(0),
         })
     }
-}
\ No newline at end of file
+
+    async fn list_vulnerabilities(
+        &self,
+        filter: &VulnerabilityFilter,
+    ) -> Result<(Vec<VulnerabilityRecord>, i64)> {
+        let mut count_query =
+            QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM security_vulnerabilities");
+        push_vulnerability_filter(&mut count_query, filter);
+        let total_count: i64 = count_query
+            .build_query_scalar()
+            .fetch_one(self.db())
+            .await
+            .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        let mut query = QueryBuilder::<Postgres>::new(format!(
+            "SELECT {}, {} FROM security_vulnerabilities",
+            VULNERABILITY_COLUMNS, RECORD_COLUMNS
+        ));
+        push_vulnerability_filter(&mut query, filter);
+        // severity_enum runs from critical to low, so ascending is most severe first.
+        let order = match (filter.sort, filter.descending) {
+            (VulnerabilitySort::Severity, true) => "severity ASC, cvss_score DESC NULLS LAST",
+            (VulnerabilitySort::Severity, false) => "severity DESC, cvss_score ASC NULLS LAST",
+            (VulnerabilitySort::CvssScore, true) => "cvss_score DESC NULLS LAST",
+            (VulnerabilitySort::CvssScore, false) => "cvss_score ASC NULLS LAST",
+            (VulnerabilitySort::DetectedAt, true) => "first_seen_at DESC",
+            (VulnerabilitySort::DetectedAt, false) => "first_seen_at ASC",
+        };
+        query
+            .push(" ORDER BY ")
+            .push(order)
+            .push(", id LIMIT ")
+            .push_bind(filter.limit as i64)
+            .push(" OFFSET ")
+            .push_bind(filter.page.saturating_sub(1) as i64 * filter.limit as i64);
+
+        let rows = query
+            .build()
+            .fetch_all(self.db())
+            .await
+            .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        let records = rows.iter().map(|row| self.map_row_to_record(row)).collect();
+
+        Ok((records, total_count))
+    }
+
+    async fn get_vulnerability_record(&self, vulnerability_id: Uuid) -> Result<Option<VulnerabilityRecord>> {
+        let row = sqlx::query(&format!(
+            "SELECT {}, {} FROM security_vulnerabilities WHERE id = $1",
+            VULNERABILITY_COLUMNS, RECORD_COLUMNS
+        ))
+        .bind(vulnerability_id)
+        .fetch_optional(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(row.map(|row| self.map_row_to_record(&row)))
+    }
+
+    async fn list_finding_evidence(&self, vulnerability_id: Uuid) -> Result<Vec<FindingEvidenceRecord>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, vulnerability_id, analysis_result_id, code_snippet, reasoning,
+                   referenced_pattern, model, prompt_version,
+                   confidence_score::float8 AS confidence_score, recorded_at
+            FROM finding_evidence
+            WHERE vulnerability_id = $1
+            ORDER BY recorded_at DESC, id
+            "#,
+        )
+        .bind(vulnerability_id)
+        .fetch_all(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| FindingEvidenceRecord {
+                id: row.get("id"),
+                vulnerability_id: row.get("vulnerability_id"),
+                analysis_id: row.get("analysis_result_id"),
+                code_snippet: row.get("code_snippet"),
+                reasoning: row.get("reasoning"),
+                referenced_pattern: row.get("referenced_pattern"),
+                model: row.get("model"),
+                prompt_version: row.get("prompt_version"),
+                confidence_score: row.get("confidence_score"),
+                recorded_at: self.offsetdatetime_to_utc(row.get("recorded_at")),
+            })
+            .collect())
+    }
+
+    async fn resolve_missing_vulnerabilities(
+        &self,
+        repository_id: Uuid,
+        analysis_id: Uuid,
+        analyzed_files: &[String],
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE security_vulnerabilities
+            SET resolved_at = NOW()
+            WHERE repository_id = $1
+              AND analysis_result_id <> $2
+              AND file_path = ANY($3)
+              AND fingerprint IS NOT NULL
+              AND resolved_at IS NULL
+              AND fixed_at IS NULL
+              AND analysis_result_id IN (
+                  SELECT id FROM code_analysis_results
+                  WHERE analysis_type = (SELECT analysis_type FROM code_analysis_results WHERE id = $2)
+              )
+            "#,
+        )
+        .bind(repository_id)
+        .bind(analysis_id)
+        .bind(analyzed_files)
+        .execute(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn list_merged_patches(&self, repository_id: Uuid) -> Result<Vec<MergedPatch>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT p.id, p.vulnerability_id, p.diff_content, p.applied_at, v.fingerprint,
+                   (v.resolved_at IS NOT NULL OR v.fixed_at IS NOT NULL) AS finding_closed
+            FROM patch_proposals p
+            JOIN security_vulnerabilities v ON v.id = p.vulnerability_id
+            WHERE p.repository_id = $1
+              AND p.status::TEXT IN ('merged', 'applied')
+              AND p.regressed_at IS NULL
+            "#,
+        )
+        .bind(repository_id)
+        .fetch_all(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| MergedPatch {
+                patch_id: row.get("id"),
+                vulnerability_id: row.get("vulnerability_id"),
+                fingerprint: row.get("fingerprint"),
+                finding_closed: row.get("finding_closed"),
+                diff: row.get("diff_content"),
+                merged_at: self.offsetdatetime_to_utc(row.get("applied_at")),
+            })
+            .collect())
+    }
+
+    async fn flag_patch_regressions(
+        &self,
+        analysis_id: Uuid,
+        regressions: &[(Uuid, RegressionReason)],
+    ) -> Result<Vec<PatchRegression>> {
+        let (patch_ids, reasons): (Vec<Uuid>, Vec<&str>) = regressions
+            .iter()
+            .map(|(patch_id, reason)| (*patch_id, reason.as_str()))
+            .unzip();
+        let rows = sqlx::query(
+            r#"
+            UPDATE patch_proposals p
+            SET regressed_at = NOW(), regression_reason = r.reason, regression_analysis_id = $3
+            FROM UNNEST($1::UUID[], $2::TEXT[]) AS r(patch_id, reason)
+            WHERE p.id = r.patch_id AND p.regressed_at IS NULL
+            RETURNING p.id, p.vulnerability_id, p.repository_id, p.title, p.regression_reason,
+                      p.applied_at, p.regressed_at
+            "#,
+        )
+        .bind(&patch_ids)
+        .bind(&reasons)
+        .bind(analysis_id)
+        .fetch_all(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let applied_at: Option<time::OffsetDateTime> = row.get("applied_at");
+                PatchRegression {
+                    patch_id: row.get("id"),
+                    vulnerability_id: row.get("vulnerability_id"),
+                    repository_id: row.get("repository_id"),
+                    title: row.get("title"),
+                    reason: RegressionReason::from(row.get::<String, _>("regression_reason")),
+                    analysis_id,
+                    merged_at: applied_at.map(|dt| self.offsetdatetime_to_utc(dt)),
+                    regressed_at: self.offsetdatetime_to_utc(row.get("regressed_at")),
+                }
+            })
+            .collect())
+    }
+
+    async fn suppress_vulnerability(
+        &self,
+        vulnerability_id: Uuid,
+        kind: SuppressionKind,
+        reason: &str,
+        expires_at: Option<DateTime<Utc>>,
+        created_by: &str,
+    ) -> Result<Option<VulnerabilitySuppression>> {
+        let db_error = |e: sqlx::Error| Error::DatabaseError { message: e.to_string() };
+        let mut tx = self.db().begin().await.map_err(db_error)?;
+
+        let finding: Option<(Uuid, Option<String>)> = sqlx::query_as(
+            "SELECT repository_id, fingerprint FROM security_vulnerabilities WHERE id = $1 FOR UPDATE",
+        )
+        .bind(vulnerability_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let Some((repository_id, fingerprint)) = finding else {
+            return Ok(None);
+        };
+        // Findings stored before fingerprinting are suppressed by their id,
+        // which no later analysis will report again.
+        let fingerprint = fingerprint.unwrap_or_else(|| vulnerability_id.simple().to_string());
+
+        sqlx::query(
+            r#"
+            UPDATE vulnerability_suppressions SET revoked_at = NOW()
+            WHERE repository_id = $1 AND fingerprint = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(repository_id)
+        .bind(&fingerprint)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        let row = sqlx::query(&format!(
+            "INSERT INTO vulnerability_suppressions \
+                 (repository_id, fingerprint, kind, reason, expires_at, created_by) \
+             VALUES ($1, $2, $3, $4, $5, $6) \
+             RETURNING {}",
+            SUPPRESSION_COLUMNS
+        ))
+        .bind(repository_id)
+        .bind(&fingerprint)
+        .bind(kind.as_str())
+        .bind(reason)
+        .bind(expires_at)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        sqlx::query(
+            r#"
+            UPDATE security_vulnerabilities SET is_false_positive = true
+            WHERE id = $1 OR (repository_id = $2 AND fingerprint = $3)
+            "#,
+        )
+        .bind(vulnerability_id)
+        .bind(repository_id)
+        .bind(&fingerprint)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+        Ok(Some(self.map_row_to_suppression(&row)))
+    }
+
+    async fn revoke_suppression(&self, vulnerability_id: Uuid) -> Result<bool> {
+        let db_error = |e: sqlx::Error| Error::DatabaseError { message: e.to_string() };
+        let mut tx = self.db().begin().await.map_err(db_error)?;
+
+        let revoked: Option<(Uuid, String)> = sqlx::query_as(
+            r#"
+            UPDATE vulnerability_suppressions s SET revoked_at = NOW()
+            FROM security_vulnerabilities v
+            WHERE v.id = $1
+              AND s.repository_id = v.repository_id
+              AND s.fingerprint = COALESCE(v.fingerprint, REPLACE(v.id::text, '-', ''))
+              AND s.revoked_at IS NULL
+            RETURNING s.repository_id, s.fingerprint
+            "#,
+        )
+        .bind(vulnerability_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let Some((repository_id, fingerprint)) = revoked else {
+            return Ok(false);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE security_vulnerabilities SET is_false_positive = false
+            WHERE id = $1 OR (repository_id = $2 AND fingerprint = $3)
+            "#,
+        )
+        .bind(vulnerability_id)
+        .bind(repository_id)
+        .bind(&fingerprint)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+        Ok(true)
+    }
+
+    async fn list_suppressions(
+        &self,
+        repository_id: Uuid,
+        include_inactive: bool,
+    ) -> Result<Vec<VulnerabilitySuppression>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM vulnerability_suppressions \
+             WHERE repository_id = $1 \
+               AND ($2 OR (revoked_at IS NULL AND (expires_at IS NULL OR expires_at > NOW()))) \
+             ORDER BY created_at DESC",
+            SUPPRESSION_COLUMNS
+        ))
+        .bind(repository_id)
+        .bind(include_inactive)
+        .fetch_all(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(rows.iter().map(|row| self.map_row_to_suppression(row)).collect())
+    }
+
+    async fn get_suppression_statistics(
+        &self,
+        repository_id: Option<Uuid>,
+    ) -> Result<SuppressionStatistics> {
+        let stats = sqlx::query(
+            r#"
+            WITH suppressions AS (
+                SELECT kind, expires_at, revoked_at,
+                       revoked_at IS NULL AND (expires_at IS NULL OR expires_at > NOW()) AS active
+                FROM vulnerability_suppressions
+                WHERE $1::uuid IS NULL OR repository_id = $1
+            )
+            SELECT
+                COUNT(*) FILTER (WHERE active) as active_count,
+                COUNT(*) FILTER (WHERE active AND kind = 'false_positive') as false_positive_count,
+                COUNT(*) FILTER (WHERE active AND kind = 'accepted_risk') as accepted_risk_count,
+                COUNT(*) FILTER (WHERE active AND expires_at <= NOW() + INTERVAL '7 days') as expiring_soon_count,
+                COUNT(*) FILTER (WHERE revoked_at IS NULL AND expires_at <= NOW()) as expired_count,
+                COUNT(*) FILTER (WHERE revoked_at IS NOT NULL) as revoked_count
+            FROM suppressions
+            "#,
+        )
+        .bind(repository_id)
+        .fetch_one(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(SuppressionStatistics {
+            active_count: stats.get::<Option<i64>, _>("active_count").unwrap_or(0),
+            false_positive_count: stats.get::<Option<i64>, _>("false_positive_count").unwrap_or(0),
+            accepted_risk_count: stats.get::<Option<i64>, _>("accepted_risk_count").unwrap_or(0),
+            expiring_soon_count: stats.get::<Option<i64>, _>("expiring_soon_count").unwrap_or(0),
+            expired_count: stats.get::<Option<i64>, _>("expired_count").unwrap_or(0),
+            revoked_count: stats.get::<Option<i64>, _>("revoked_count").unwrap_or(0),
+        })
+    }
+
+    async fn get_ruleset(&self, repository_id: Uuid) -> Result<Option<AnalysisRuleset>> {
+        let row = sqlx::query(
+            r#"
+            SELECT repository_id, disabled_patterns, custom_patterns, updated_by, updated_at
+            FROM analysis_rulesets
+            WHERE repository_id = $1
+            "#,
+        )
+        .bind(repository_id)
+        .fetch_optional(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        row.map(|row| self.map_row_to_ruleset(&row)).transpose()
+    }
+
+    async fn save_ruleset(
+        &self,
+        repository_id: Uuid,
+        rules: &Ruleset,
+        updated_by: &str,
+    ) -> Result<Option<AnalysisRuleset>> {
+        let custom_patterns = serde_json::to_value(&rules.custom_patterns)
+            .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO analysis_rulesets (repository_id, disabled_patterns, custom_patterns, updated_by)
+            SELECT id, $2, $3, $4 FROM github_repositories WHERE id = $1
+            ON CONFLICT (repository_id) DO UPDATE
+            SET disabled_patterns = EXCLUDED.disabled_patterns,
+                custom_patterns = EXCLUDED.custom_patterns,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = NOW()
+            RETURNING repository_id, disabled_patterns, custom_patterns, updated_by, updated_at
+            "#,
+        )
+        .bind(repository_id)
+        .bind(&rules.disabled_patterns)
+        .bind(custom_patterns)
+        .bind(updated_by)
+        .fetch_optional(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        row.map(|row| self.map_row_to_ruleset(&row)).transpose()
+    }
+
+    async fn can_manage_ruleset(&self, repository_id: Uuid, address: &str) -> Result<bool> {
+        sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM organization_repositories r
+                JOIN organization_members m ON m.organization_id = r.organization_id
+                JOIN user_wallets w ON w.user_id = m.user_id
+                WHERE r.github_repository_id = $1
+                  AND w.address IN ($2, LOWER($2))
+                  AND m.role IN ('owner', 'admin')
+            )
+            "#,
+        )
+        .bind(repository_id)
+        .bind(address)
+        .fetch_one(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })
+    }
+
+    async fn save_dependency_advisories(&self, advisories: &[DependencyAdvisory]) -> Result<u64> {
+        let db_error = |e: sqlx::Error| Error::DatabaseError { message: e.to_string() };
+        let mut tx = self.db().begin().await.map_err(db_error)?;
+        let mut saved = 0;
+        for advisory in advisories {
+            let ranges = serde_json::to_value(&advisory.ranges)
+                .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+            let result = sqlx::query(
+                r#"
+                INSERT INTO dependency_advisories (
+                    id, ecosystem, package, summary, severity, cve_id, cwe_id, cvss_vector,
+                    affected_ranges, affected_versions, url, modified_at
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                ON CONFLICT (id, ecosystem, package) DO UPDATE SET
+                    summary = EXCLUDED.summary,
+                    severity = EXCLUDED.severity,
+                    cve_id = EXCLUDED.cve_id,
+                    cwe_id = EXCLUDED.cwe_id,
+                    cvss_vector = EXCLUDED.cvss_vector,
+                    affected_ranges = EXCLUDED.affected_ranges,
+                    affected_versions = EXCLUDED.affected_versions,
+                    url = EXCLUDED.url,
+                    modified_at = EXCLUDED.modified_at,
+                    synced_at = NOW()
+                "#,
+            )
+            .bind(&advisory.id)
+            .bind(advisory.ecosystem.as_str())
+            .bind(&advisory.package)
+            .bind(&advisory.summary)
+            .bind(self.map_severity_to_db(&advisory.severity))
+            .bind(&advisory.cve_id)
+            .bind(&advisory.cwe_id)
+            .bind(&advisory.cvss_vector)
+            .bind(ranges)
+            .bind(&advisory.versions)
+            .bind(&advisory.url)
+            .bind(advisory.modified_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+            saved += result.rows_affected();
+        }
+        tx.commit().await.map_err(db_error)?;
+        Ok(saved)
+    }
+
+    async fn find_dependency_advisories(
+        &self,
+        ecosystem: Ecosystem,
+        packages: &[String],
+    ) -> Result<Vec<DependencyAdvisory>> {
+        let packages: Vec<String> = packages.iter().map(|package| package.to_lowercase()).collect();
+        let rows = sqlx::query(
+            r#"
+            SELECT id, ecosystem, package, summary, severity, cve_id, cwe_id, cvss_vector,
+                   affected_ranges, affected_versions, url, modified_at
+            FROM dependency_advisories
+            WHERE ecosystem = $1 AND LOWER(package) = ANY($2)
+            ORDER BY id
+            "#,
+        )
+        .bind(ecosystem.as_str())
+        .bind(&packages)
+        .fetch_all(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        rows.iter().map(|row| self.map_row_to_advisory(row)).collect()
+    }
+
+    async fn record_ai_usage(
+        &self,
+        repository_id: Uuid,
+        analysis_id: Option<Uuid>,
+        usage: &[ProviderUsage],
+    ) -> Result<()> {
+        let db_error = |e: sqlx::Error| Error::DatabaseError { message: e.to_string() };
+        let mut tx = self.db().begin().await.map_err(db_error)?;
+        for backend in usage {
+            sqlx::query(
+                r#"
+                INSERT INTO ai_usage (
+                    analysis_result_id, repository_id, provider, model, requests, cache_hits,
+                    prompt_tokens, completion_tokens, cost_usd
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+            )
+            .bind(analysis_id)
+            .bind(repository_id)
+            .bind(&backend.provider)
+            .bind(&backend.model)
+            .bind(backend.requests as i32)
+            .bind(backend.cache_hits as i32)
+            .bind(backend.prompt_tokens as i64)
+            .bind(backend.completion_tokens as i64)
+            .bind(backend.cost_usd.max(0.0))
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+        tx.commit().await.map_err(db_error)?;
+        Ok(())
+    }
+
+    async fn summarize_ai_usage(&self, filter: &AiUsageFilter) -> Result<AiUsageSummary> {
+        let total = self
+            .ai_usage_totals(filter, "'total'")
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| AiUsageTotals { key: "total".to_string(), ..Default::default() });
+        Ok(AiUsageSummary {
+            total,
+            by_model: self.ai_usage_totals(filter, "provider || '/' || model").await?,
+            by_repository: self.ai_usage_totals(filter, "repository_id::text").await?,
+        })
+    }
+
+    async fn get_budget_statuses(
+        &self,
+        scope: BudgetScope,
+        scope_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<BudgetStatus>> {
+        // A repository is covered by its own budget and its organizations'
+        let covering = match scope {
+            BudgetScope::Repository => {
+                "(b.scope = 'repository' AND b.scope_id = $1) \
+                 OR (b.scope = 'organization' AND b.scope_id IN ( \
+                     SELECT organization_id FROM organization_repositories WHERE github_repository_id = $1))"
+            }
+            BudgetScope::Organization => "b.scope = 'organization' AND b.scope_id = $1",
+        };
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, COALESCE((
+                SELECT SUM(u.cost_usd) FROM ai_usage u
+                WHERE u.recorded_at >= $2
+                  AND CASE b.scope
+                      WHEN 'repository' THEN u.repository_id = b.scope_id
+                      ELSE u.repository_id IN (
+                          SELECT github_repository_id FROM organization_repositories
+                          WHERE organization_id = b.scope_id)
+                  END
+            ), 0)::float8 AS spent_usd
+            FROM ai_budgets b
+            WHERE {}
+            ORDER BY b.scope DESC, b.scope_id
+            "#,
+            BUDGET_COLUMNS, covering
+        ))
+        .bind(scope_id)
+        .bind(since)
+        .fetch_all(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| BudgetStatus {
+                budget: self.map_row_to_budget(row),
+                spent_usd: row.get("spent_usd"),
+            })
+            .collect())
+    }
+
+    async fn save_ai_budget(
+        &self,
+        scope: BudgetScope,
+        scope_id: Uuid,
+        monthly_limit_usd: f64,
+        policy: BudgetPolicy,
+        updated_by: &str,
+    ) -> Result<Option<AiBudget>> {
+        let target = match scope {
+            BudgetScope::Repository => "github_repositories",
+            BudgetScope::Organization => "organizations",
+        };
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO ai_budgets (scope, scope_id, monthly_limit_usd, policy, updated_by)
+            SELECT $1, id, $3, $4, $5 FROM {} WHERE id = $2
+            ON CONFLICT (scope, scope_id) DO UPDATE
+            SET monthly_limit_usd = EXCLUDED.monthly_limit_usd,
+                policy = EXCLUDED.policy,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = NOW()
+            RETURNING {}
+            "#,
+            target, BUDGET_COLUMNS
+        ))
+        .bind(scope.as_str())
+        .bind(scope_id)
+        .bind(monthly_limit_usd)
+        .bind(policy.as_str())
+        .bind(updated_by)
+        .fetch_optional(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(row.map(|row| self.map_row_to_budget(&row)))
+    }
+
+    async fn delete_ai_budget(&self, scope: BudgetScope, scope_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM ai_budgets WHERE scope = $1 AND scope_id = $2")
+            .bind(scope.as_str())
+            .bind(scope_id)
+            .execute(self.db())
+            .await
+            .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn request_budget_approval(&self, repository_id: Uuid, commit_sha: &str) -> Result<BudgetApproval> {
+        // The pending request follows the latest commit refused
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO ai_budget_approvals (repository_id, commit_sha)
+            VALUES ($1, $2)
+            ON CONFLICT (repository_id) WHERE status = 'pending' DO UPDATE
+            SET commit_sha = EXCLUDED.commit_sha
+            RETURNING {}
+            "#,
+            APPROVAL_COLUMNS
+        ))
+        .bind(repository_id)
+        .bind(commit_sha)
+        .fetch_one(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(self.map_row_to_approval(&row))
+    }
+
+    async fn take_budget_approval(&self, repository_id: Uuid) -> Result<bool> {
+        let used = sqlx::query(
+            r#"
+            UPDATE ai_budget_approvals SET status = 'used'
+            WHERE id = (
+                SELECT id FROM ai_budget_approvals
+                WHERE repository_id = $1 AND status = 'approved'
+                ORDER BY decided_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id
+            "#,
+        )
+        .bind(repository_id)
+        .fetch_optional(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(used.is_some())
+    }
+
+    async fn decide_budget_approval(
+        &self,
+        approval_id: Uuid,
+        approve: bool,
+        decided_by: &str,
+    ) -> Result<Option<BudgetApproval>> {
+        let status = if approve { ApprovalStatus::Approved } else { ApprovalStatus::Denied };
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE ai_budget_approvals
+            SET status = $2, decided_by = $3, decided_at = NOW()
+            WHERE id = $1 AND status = 'pending'
+            RETURNING {}
+            "#,
+            APPROVAL_COLUMNS
+        ))
+        .bind(approval_id)
+        .bind(status.as_str())
+        .bind(decided_by)
+        .fetch_optional(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(row.map(|row| self.map_row_to_approval(&row)))
+    }
+
+    async fn list_budget_approvals(
+        &self,
+        repository_id: Option<Uuid>,
+        status: Option<ApprovalStatus>,
+    ) -> Result<Vec<BudgetApproval>> {
+        let mut query = QueryBuilder::<Postgres>::new(format!(
+            "SELECT {} FROM ai_budget_approvals WHERE 1=1",
+            APPROVAL_COLUMNS
+        ));
+        if let Some(repository_id) = repository_id {
+            query.push(" AND repository_id = ").push_bind(repository_id);
+        }
+        if let Some(status) = status {
+            query.push(" AND status = ").push_bind(status.as_str());
+        }
+        query.push(" ORDER BY requested_at DESC LIMIT 100");
+
+        let rows = query
+            .build()
+            .fetch_all(self.db())
+            .await
+            .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(rows.iter().map(|row| self.map_row_to_approval(row)).collect())
+    }
+
+    async fn get_disclosure(&self, vulnerability_id: Uuid) -> Result<Option<VulnerabilityDisclosure>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM security_vulnerabilities WHERE id = $1",
+            DISCLOSURE_COLUMNS
+        ))
+        .bind(vulnerability_id)
+        .fetch_optional(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(row.map(|row| self.map_row_to_disclosure(&row)))
+    }
+
+    async fn update_disclosure(
+        &self,
+        vulnerability_id: Uuid,
+        state: DisclosureState,
+        embargo_until: Option<DateTime<Utc>>,
+        updated_by: &str,
+    ) -> Result<Option<VulnerabilityDisclosure>> {
+        let db_error = |e: sqlx::Error| Error::DatabaseError { message: e.to_string() };
+        let mut tx = self.db().begin().await.map_err(db_error)?;
+
+        let current: Option<(String,)> = sqlx::query_as(
+            "SELECT disclosure_state FROM security_vulnerabilities WHERE id = $1 FOR UPDATE",
+        )
+        .bind(vulnerability_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let Some((current,)) = current else {
+            return Ok(None);
+        };
+        let from = DisclosureState::from_db(&current);
+        check_transition(from, state, embargo_until, Utc::now())?;
+
+        // A moved embargo gets a fresh warning before it lapses
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE security_vulnerabilities SET
+                disclosure_state = $2,
+                embargo_until = $3,
+                embargo_notified_at = NULL,
+                disclosed_at = COALESCE(disclosed_at, NOW()),
+                published_at = CASE WHEN $2 = 'public' THEN NOW() ELSE published_at END,
+                disclosure_updated_by = $4
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            DISCLOSURE_COLUMNS
+        ))
+        .bind(vulnerability_id)
+        .bind(state.as_str())
+        .bind(embargo_until)
+        .bind(updated_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO vulnerability_disclosure_events (vulnerability_id, from_state, to_state, embargo_until, actor)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(vulnerability_id)
+        .bind(from.as_str())
+        .bind(state.as_str())
+        .bind(embargo_until)
+        .bind(updated_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+        Ok(Some(self.map_row_to_disclosure(&row)))
+    }
+
+    async fn publish_lapsed_embargoes(&self) -> Result<Vec<VulnerabilityDisclosure>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            WITH published AS (
+                UPDATE security_vulnerabilities SET
+                    disclosure_state = 'public',
+                    published_at = NOW(),
+                    disclosure_updated_by = 'system'
+                WHERE disclosure_state = 'embargoed' AND embargo_until <= NOW()
+                RETURNING {}
+            ), events AS (
+                INSERT INTO vulnerability_disclosure_events (vulnerability_id, from_state, to_state, embargo_until, actor)
+                SELECT id, 'embargoed', 'public', embargo_until, 'system' FROM published
+            )
+            SELECT * FROM published
+            "#,
+            DISCLOSURE_COLUMNS
+        ))
+        .fetch_all(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(rows.iter().map(|row| self.map_row_to_disclosure(row)).collect())
+    }
+
+    async fn claim_lapsing_embargoes(&self, before: DateTime<Utc>) -> Result<Vec<VulnerabilityDisclosure>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            UPDATE security_vulnerabilities SET embargo_notified_at = NOW()
+            WHERE id IN (
+                SELECT id FROM security_vulnerabilities
+                WHERE disclosure_state = 'embargoed'
+                  AND embargo_notified_at IS NULL
+                  AND embargo_until <= $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            DISCLOSURE_COLUMNS
+        ))
+        .bind(before)
+        .fetch_all(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(rows.iter().map(|row| self.map_row_to_disclosure(row)).collect())
+    }
+
+    async fn release_lapsing_embargoes(&self, vulnerability_ids: &[Uuid]) -> Result<()> {
+        sqlx::query(
+            "UPDATE security_vulnerabilities SET embargo_notified_at = NULL \
+             WHERE id = ANY($1) AND disclosure_state = 'embargoed'",
+        )
+        .bind(vulnerability_ids)
+        .execute(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+        Ok(())
+    }
+
+    async fn list_undisclosed_vulnerability_ids(&self, repository_id: Uuid) -> Result<Vec<Uuid>> {
+        sqlx::query_scalar(
+            "SELECT id FROM security_vulnerabilities WHERE repository_id = $1 AND disclosure_state <> 'public'",
+        )
+        .bind(repository_id)
+        .fetch_all(self.db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })
+    }
+}
@@ -0,0 +1,207 @@
+use crate::domain::analysis_models::{Severity, VulnerabilityFinding, VulnerabilityType};
+use crate::domain::static_analyzer_trait::{code_of_line, has_extension, LineRule, StaticAnalyzer};
+use crate::error::Result;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Account fields that authorize an instruction and so must sign it.
+const AUTHORITY_FIELDS: [&str; 3] = ["authority", "admin", "owner"];
+
+/// A field of an accounts struct: `pub name: Type<'info>,`.
+fn account_field() -> &'static Regex {
+    static ACCOUNT_FIELD: OnceLock<Regex> = OnceLock::new();
+    ACCOUNT_FIELD.get_or_init(|| Regex::new(r"^pub\s+(\w+)\s*:\s*(.+?),?$").expect("valid regex"))
+}
+
+/// Solana program checks, for Anchor and native programs: unvalidated and
+/// unsigned accounts, unchecked arithmetic and discarded cross-program
+/// invocation results.
+pub struct AnchorStaticAnalyzer {
+    version: String,
+}
+
+impl AnchorStaticAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            version: "1.0.0".to_string(),
+        }
+    }
+
+    fn missing_signer() -> LineRule {
+        LineRule {
+            vulnerability_type: VulnerabilityType::UnauthorizedAccess,
+            severity: Severity::High,
+            confidence: 80.0,
+            description: "Authority account is not required to sign, so anyone can pass it",
+            recommendation: "Declare the account as Signer<'info> or add a signer constraint",
+        }
+    }
+
+    fn unchecked_account() -> LineRule {
+        LineRule {
+            vulnerability_type: VulnerabilityType::AccessControl,
+            severity: Severity::Medium,
+            confidence: 65.0,
+            description: "Account is not validated, so any account can be passed in its place",
+            recommendation: "Use a typed Account<'info, T>, or constrain the account and explain the checks in a /// CHECK: comment",
+        }
+    }
+
+    fn unchecked_arithmetic() -> LineRule {
+        LineRule {
+            vulnerability_type: VulnerabilityType::IntegerOverflow,
+            severity: Severity::Medium,
+            confidence: 50.0,
+            description: "Arithmetic wraps silently in release builds unless overflow-checks is enabled",
+            recommendation: "Use checked_add, checked_sub or checked_mul and return an error on overflow",
+        }
+    }
+
+    fn discarded_cpi() -> LineRule {
+        LineRule {
+            vulnerability_type: VulnerabilityType::Other("Unchecked External Call".to_string()),
+            severity: Severity::Medium,
+            confidence: 85.0,
+            description: "Result of a cross-program invocation is discarded, so a failed call goes unnoticed",
+            recommendation: "Propagate the result with `?`",
+        }
+    }
+
+    /// The doc comments and attributes above the field on `lines[index]`.
+    fn field_annotations(lines: &[&str], index: usize) -> String {
+        lines[..index]
+            .iter()
+            .rev()
+            .map(|line| line.trim())
+            .take_while(|line| !line.ends_with('{') && !account_field().is_match(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn check_account_field(
+        lines: &[&str],
+        index: usize,
+        file_path: &str,
+        code: &str,
+    ) -> Option<VulnerabilityFinding> {
+        let captures = account_field().captures(code)?;
+        let (name, field_type) = (&captures[1], &captures[2]);
+        if !field_type.starts_with("AccountInfo<") && !field_type.starts_with("UncheckedAccount<") {
+            return None;
+        }
+
+        let annotations = Self::field_annotations(lines, index);
+        let rule = if AUTHORITY_FIELDS.contains(&name) && !annotations.contains("signer") {
+            Self::missing_signer()
+        } else if !annotations.contains("CHECK") {
+            Self::unchecked_account()
+        } else {
+            return None;
+        };
+        Some(rule.finding(file_path, index + 1, code))
+    }
+
+    fn is_unchecked_arithmetic(code: &str) -> bool {
+        let compound = ["+= ", "-= ", "*= "].iter().any(|op| code.contains(op));
+        let guarded = ["checked_", "saturating_", "wrapping_"].iter().any(|f| code.contains(f));
+        // Counters rarely hold amounts.
+        compound && !guarded && !code.ends_with("+= 1;")
+    }
+
+    fn discards_cpi_result(code: &str) -> bool {
+        let discarded = code.starts_with("let _ =") || code.starts_with("_ =");
+        let cpi = ["invoke(", "invoke_signed(", "cpi::"].iter().any(|call| code.contains(call));
+        discarded && cpi
+    }
+}
+
+impl StaticAnalyzer for AnchorStaticAnalyzer {
+    fn name(&self) -> &str {
+        "anchor"
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[".rs"]
+    }
+
+    /// Rust files that are Solana programs; other Rust code is left alone.
+    fn handles(&self, file_path: &str, content: &str) -> bool {
+        has_extension(file_path, self.extensions())
+            && (content.contains("anchor_lang") || content.contains("solana_program"))
+    }
+
+    fn analyze_file(&self, file_path: &str, content: &str) -> Result<Vec<VulnerabilityFinding>> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut findings = Vec::new();
+
+        for (index, line) in lines.iter().enumerate() {
+            let Some(code) = code_of_line(line) else {
+                continue;
+            };
+
+            if let Some(finding) = Self::check_account_field(&lines, index, file_path, code) {
+                findings.push(finding);
+            }
+            if Self::is_unchecked_arithmetic(code) {
+                findings.push(Self::unchecked_arithmetic().finding(file_path, index + 1, code));
+            }
+            if Self::discards_cpi_result(code) {
+                findings.push(Self::discarded_cpi().finding(file_path, index + 1, code));
+            }
+        }
+
+        Ok(findings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = r#"use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: only receives lamports
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub oracle: UncheckedAccount<'info>,
+}
+
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    ctx.accounts.vault.total -= amount;
+    let _ = invoke(&transfer_ix, &accounts);
+    Ok(())
+}
+"#;
+
+    #[test]
+    fn finds_unsigned_and_unchecked_accounts() {
+        let analyzer = AnchorStaticAnalyzer::new();
+        assert!(analyzer.handles("programs/vault/src/lib.rs", PROGRAM));
+        assert!(!analyzer.handles("src/main.rs", "fn main() {}"));
+
+        let findings = analyzer.analyze_file("programs/vault/src/lib.rs", PROGRAM).unwrap();
+        let found: Vec<_> = findings
+            .iter()
+            .map(|finding| (finding.line_number.unwrap(), finding.description.as_str()))
+            .collect();
+
+        assert_eq!(
+            found,
+            [
+                (10, AnchorStaticAnalyzer::missing_signer().description),
+                (11, AnchorStaticAnalyzer::unchecked_account().description),
+                (15, AnchorStaticAnalyzer::unchecked_arithmetic().description),
+                (16, AnchorStaticAnalyzer::discarded_cpi().description),
+            ]
+        );
+    }
+}
@@ -50,25 +50,25 @@ impl GitHubIntegrationService {
             HashMap::new()
         };
 
-        // Filter to only Move files for initial analysis
-        let move_files: HashMap<String, String> = file_contents
+        // Filter to smart contract files for initial analysis
+        let contract_files: HashMap<String, String> = file_contents
             .into_iter()
-            .filter(|(path, _)| path.ends_with(".move"))
+            .filter(|(path, _)| self.analysis_use_cases.supports_path(path))
             .collect();
 
-        if move_files.is_empty() {
+        if contract_files.is_empty() {
             return Err(Error::AnalysisFailed {
-                message: "No Move files found in repository for analysis".to_string(),
+                message: "No smart contract files found in repository for analysis".to_string(),
             });
         }
 
-        info!("Found {} Move files for analysis", move_files.len());
+        info!("Found {} smart contract files for analysis", contract_files.len());
 
         // Create analysis request with default analysis types
         let analysis_request = AnalyzeRepositoryRequest {
             repository_id,
             commit_sha: commit_sha.unwrap_or("HEAD").to_string(),
-            files_to_analyze: None, // Analyze all Move files
+            files_to_analyze: None, // Analyze all smart contract files
             analysis_types: vec![
                 AnalysisType::StaticAnalysis,
                 AnalysisType::VulnerabilityDetection,
@@ -78,7 +78,7 @@ impl GitHubIntegrationService {
 
         // Run analysis
         let analysis_result = self.analysis_use_cases
-            .analyze_repository(analysis_request, move_files)
+            .analyze_repository(analysis_request, contract_files)
             .await?;
 
         info!(
@@ -100,21 +100,21 @@ impl GitHubIntegrationService {
     ) -> Result<Option<crate::models::responses::AnalysisResponse>> {
         info!("Webhook triggered analysis for repository: {}/{} at commit: {}", owner, repo_name, commit_sha);
 
-        // Filter to only Move files that were changed
-        let move_files_changed: Vec<String> = changed_files
+        // Filter to the smart contract files that were changed
+        let contract_files_changed: Vec<String> = changed_files
             .into_iter()
-            .filter(|path| path.ends_with(".move"))
+            .filter(|path| self.analysis_use_cases.supports_path(path))
             .collect();
 
-        if move_files_changed.is_empty() {
-            info!("No Move files changed, skipping analysis");
+        if contract_files_changed.is_empty() {
+            info!("No smart contract files changed, skipping analysis");
             return Ok(None);
         }
 
-        // Get content for changed Move files
+        // Get content for changed smart contract files
         let mut file_contents = HashMap::new();
         if let Some(github_client) = &self.github_client {
-            for file_path in &move_files_changed {
+            for file_path in &contract_files_changed {
                 match github_client.get_file_content(owner, repo_name, file_path, Some(commit_sha)).await {
                     Ok(content) => {
                         file_contents.insert(file_path.clone(), content);
@@ -129,7 +129,7 @@ impl GitHubIntegrationService {
 
         if file_contents.is_empty() {
             return Err(Error::AnalysisFailed {
-                message: "Failed to retrieve content for changed Move files".to_string(),
+                message: "Failed to retrieve content for changed smart contract files".to_string(),
             });
         }
 
@@ -137,7 +137,7 @@ impl GitHubIntegrationService {
         let analysis_request = AnalyzeRepositoryRequest {
             repository_id,
             commit_sha: commit_sha.to_string(),
-            files_to_analyze: Some(move_files_changed),
+            files_to_analyze: Some(contract_files_changed),
             analysis_types: vec![
                 AnalysisType::StaticAnalysis,
                 AnalysisType::VulnerabilityDetection,
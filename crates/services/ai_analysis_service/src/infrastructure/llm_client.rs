@@ -1,81 +1,294 @@
-use crate::domain::analysis_models::{VulnerabilityFinding, SecurityRecommendation, VulnerabilityType, Severity, RecommendationCategory, Priority, CodeExample};
-use crate::domain::llm_provider_trait::{LLMProvider, LLMRequest, LLMResponse, TokenUsage, CodeAnalysisResponse};
+use crate::domain::analysis_models::{FindingEvidence, FindingKind, VulnerabilityFinding, SecurityRecommendation, VulnerabilityType, Severity, RecommendationCategory, Priority, CodeExample};
+use crate::domain::llm_provider_trait::{
+    CodeAnalysisResponse, LLMProvider, LLMRequest, LLMResponse, LlmProvider, ProviderUsage,
+    RetryPolicy, TokenPricing,
+};
 use crate::error::{Error, Result};
+use crate::infrastructure::llm_providers::{
+    AnthropicProvider, GoogleProvider, OllamaProvider, OpenAiProvider,
+};
+use crate::infrastructure::llm_metrics::{LLM_FAILURES, LLM_REQUEST_DURATION, LLM_TOKENS};
+use crate::infrastructure::llm_response_cache::{LlmCacheKey, LlmResponseCache};
 use async_trait::async_trait;
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use serde::Deserialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tracing::warn;
 use uuid::Uuid;
 
+/// Runs analyses on a chain of LLM backends in priority order. A backend
+/// that rate limits is retried with backoff; one that fails, or keeps rate
+/// limiting, is passed over for the next, so one provider's outage does not
+/// halt analyses.
 pub struct LLMClient {
-    client: Client,
-    provider: LLMProviderType,
-    api_key: String,
-    base_url: String,
-    model: String,
+    providers: Vec<Arc<dyn LlmProvider>>,
+    retry_policy: RetryPolicy,
+    usage: Mutex<Vec<ProviderUsage>>,
+    /// The backend that served the last request.
+    last_served: AtomicUsize,
+    response_cache: Option<LlmResponseCache>,
 }
 
-#[derive(Debug, Clone)]
-pub enum LLMProviderType {
-    OpenAI,
-    Anthropic,
-    Local,
-}
+/// The providers `LLMClient::from_env` tries, in order, when `LLM_PROVIDERS`
+/// is not set.
+const DEFAULT_PROVIDER_ORDER: &str = "openai,anthropic,google,ollama";
 
-#[derive(Debug, Deserialize)]
-struct OpenAIResponse {
-    choices: Vec<OpenAIChoice>,
-    usage: OpenAIUsage,
-    model: String,
-}
+/// Versions of the prompt templates, recorded on findings and part of the
+/// response cache key. Bump one whenever its template, or how its response
+/// is parsed, changes, so responses to the old prompt are not reused.
+pub const VULNERABILITY_PROMPT_VERSION: &str = "vulnerability-detection/v2";
+pub const RECOMMENDATIONS_PROMPT_VERSION: &str = "security-recommendations/v1";
+pub const CODE_QUALITY_PROMPT_VERSION: &str = "code-quality/v1";
 
-#[derive(Debug, Deserialize)]
-struct OpenAIChoice {
-    message: OpenAIMessage,
-}
+impl LLMClient {
+    /// A client over `providers`, tried first to last.
+    pub fn new(providers: Vec<Arc<dyn LlmProvider>>) -> Self {
+        let usage = providers
+            .iter()
+            .map(|provider| ProviderUsage {
+                provider: provider.name().to_string(),
+                model: provider.model().to_string(),
+                ..Default::default()
+            })
+            .collect();
+        Self {
+            providers,
+            retry_policy: RetryPolicy::default(),
+            usage: Mutex::new(usage),
+            last_served: AtomicUsize::new(0),
+            response_cache: None,
+        }
+    }
 
-#[derive(Debug, Deserialize)]
-struct OpenAIMessage {
-    content: String,
-}
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
 
-#[derive(Debug, Deserialize)]
-struct OpenAIUsage {
-    prompt_tokens: u32,
-    completion_tokens: u32,
-    total_tokens: u32,
-}
+    /// Answer prompts the chain's models already answered from `cache`.
+    pub fn with_response_cache(mut self, cache: LlmResponseCache) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
 
-impl LLMClient {
     pub fn new_openai(api_key: String) -> Self {
-        Self {
-            client: Client::new(),
-            provider: LLMProviderType::OpenAI,
-            api_key,
-            base_url: "https://api.openai.com/v1".to_string(),
-            model: "gpt-4".to_string(),
-        }
+        Self::new(vec![Arc::new(OpenAiProvider::new(api_key))])
     }
 
     pub fn new_anthropic(api_key: String) -> Self {
-        Self {
-            client: Client::new(),
-            provider: LLMProviderType::Anthropic,
-            api_key,
-            base_url: "https://api.anthropic.com/v1".to_string(),
-            model: "claude-3-sonnet-20240229".to_string(),
-        }
+        Self::new(vec![Arc::new(AnthropicProvider::new(api_key))])
     }
 
     pub fn new_local(base_url: String, model: String) -> Self {
-        Self {
-            client: Client::new(),
-            provider: LLMProviderType::Local,
-            api_key: String::new(),
-            base_url,
-            model,
+        Self::new(vec![Arc::new(OllamaProvider::new(base_url, model))])
+    }
+
+    /// The chain configured by the environment, or `None` when no provider
+    /// is. `LLM_PROVIDERS` lists providers in priority order; each is used
+    /// when its credentials are set:
+    ///
+    /// - `openai`: `OPENAI_API_KEY`, optionally `OPENAI_MODEL`
+    /// - `anthropic`: `ANTHROPIC_API_KEY`, optionally `ANTHROPIC_MODEL`
+    /// - `google`: `GOOGLE_API_KEY`, optionally `GOOGLE_MODEL`
+    /// - `ollama`: `OLLAMA_BASE_URL` and `OLLAMA_MODEL`
+    ///
+    /// Hosted providers' prices can be overridden with `<PROVIDER>_PRICING`,
+    /// as USD per million prompt and completion tokens: `3.0,15.0`.
+    /// `LLM_MAX_RETRIES` sets how often a rate-limited request is retried.
+    pub fn from_env() -> Option<Self> {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+        // API keys may refer to a secret fetched at startup
+        let api_key = |name: &str| {
+            let value = env(name)?;
+            jd_utils::secrets::resolve(&value)
+                .map_err(|e| warn!("Ignoring {}: {}", name, e))
+                .ok()
+        };
+        let pricing = |prefix: &str| {
+            let value = env(&format!("{}_PRICING", prefix))?;
+            let (prompt, completion) = value.split_once(',')?;
+            Some(TokenPricing {
+                prompt_per_million: prompt.trim().parse().ok()?,
+                completion_per_million: completion.trim().parse().ok()?,
+            })
+        };
+
+        let order = env("LLM_PROVIDERS").unwrap_or_else(|| DEFAULT_PROVIDER_ORDER.to_string());
+        let mut providers: Vec<Arc<dyn LlmProvider>> = Vec::new();
+        for name in order.split(',').map(|name| name.trim().to_lowercase()) {
+            match name.as_str() {
+                "openai" => {
+                    if let Some(api_key) = api_key("OPENAI_API_KEY") {
+                        let mut provider = OpenAiProvider::new(api_key);
+                        if let Some(model) = env("OPENAI_MODEL") {
+                            provider = provider.with_model(model);
+                        }
+                        if let Some(pricing) = pricing("OPENAI") {
+                            provider = provider.with_pricing(pricing);
+                        }
+                        providers.push(Arc::new(provider));
+                    }
+                }
+                "anthropic" => {
+                    if let Some(api_key) = api_key("ANTHROPIC_API_KEY") {
+                        let mut provider = AnthropicProvider::new(api_key);
+                        if let Some(model) = env("ANTHROPIC_MODEL") {
+                            provider = provider.with_model(model);
+                        }
+                        if let Some(pricing) = pricing("ANTHROPIC") {
+                            provider = provider.with_pricing(pricing);
+                        }
+                        providers.push(Arc::new(provider));
+                    }
+                }
+                "google" => {
+                    if let Some(api_key) = api_key("GOOGLE_API_KEY") {
+                        let mut provider = GoogleProvider::new(api_key);
+                        if let Some(model) = env("GOOGLE_MODEL") {
+                            provider = provider.with_model(model);
+                        }
+                        if let Some(pricing) = pricing("GOOGLE") {
+                            provider = provider.with_pricing(pricing);
+                        }
+                        providers.push(Arc::new(provider));
+                    }
+                }
+                "ollama" => {
+                    let (base_url, model) = (env("OLLAMA_BASE_URL"), env("OLLAMA_MODEL"));
+                    if let (Some(base_url), Some(model)) = (base_url, model) {
+                        providers.push(Arc::new(OllamaProvider::new(base_url, model)));
+                    }
+                }
+                "" => {}
+                other => warn!("Ignoring unknown LLM provider {:?} in LLM_PROVIDERS", other),
+            }
+        }
+
+        if providers.is_empty() {
+            return None;
+        }
+        let mut client = Self::new(providers);
+        if let Some(max_retries) = env("LLM_MAX_RETRIES").and_then(|value| value.parse().ok()) {
+            client.retry_policy.max_retries = max_retries;
+        }
+        Some(client)
+    }
+
+    /// Complete `request` on one backend, retrying while it rate limits.
+    async fn complete_with_retries(
+        &self,
+        index: usize,
+        request: &LLMRequest,
+    ) -> Result<LLMResponse> {
+        let provider = &self.providers[index];
+        let mut attempt = 0;
+        loop {
+            let started = Instant::now();
+            let result = provider.complete(request).await;
+            self.record(index, provider.pricing(), &result, started.elapsed());
+            match result {
+                Err(Error::LLMRateLimited { retry_after_seconds, .. })
+                    if attempt < self.retry_policy.max_retries =>
+                {
+                    let retry_after = retry_after_seconds.map(Duration::from_secs);
+                    let delay = self.retry_policy.delay(attempt, retry_after, jitter());
+                    warn!("{} rate limited the request; retrying in {:?}", provider.name(), delay);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Complete `request` on the chain and parse the response, unless one of
+    /// the chain's models already answered the prompt `key` identifies. The
+    /// earliest backend with a cached response wins. Only responses that
+    /// parse are cached.
+    async fn complete_cached<T>(
+        &self,
+        request: LLMRequest,
+        key: &LlmCacheKey,
+        parse: impl Fn(&LLMResponse) -> Result<T> + Send,
+    ) -> Result<T> {
+        let Some(cache) = &self.response_cache else {
+            return parse(&self.analyze_code(request).await?);
+        };
+
+        let models: Vec<String> =
+            self.providers.iter().map(|provider| provider.model().to_string()).collect();
+        match cache.response(key, &models).await {
+            Ok(Some((model, response))) => match parse(&response) {
+                Ok(parsed) => {
+                    if let Some(index) = models.iter().position(|candidate| *candidate == model) {
+                        self.last_served.store(index, Ordering::Relaxed);
+                        self.lock_usage()[index].cache_hits += 1;
+                    }
+                    return Ok(parsed);
+                }
+                Err(e) => warn!("Ignoring cached LLM response that no longer parses: {}", e),
+            },
+            Ok(None) => {}
+            Err(e) => warn!("Failed to read cached LLM response: {}", e),
+        }
+
+        let response = self.analyze_code(request).await?;
+        let parsed = parse(&response)?;
+        if let Some(provider) = self.served() {
+            if let Err(e) = cache
+                .store_response(key, provider.name(), provider.model(), &response)
+                .await
+            {
+                warn!("Failed to cache LLM response: {}", e);
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn record(
+        &self,
+        index: usize,
+        pricing: TokenPricing,
+        result: &Result<LLMResponse>,
+        elapsed: Duration,
+    ) {
+        let mut usage = self.lock_usage();
+        let usage = &mut usage[index];
+        usage.requests += 1;
+        let provider = usage.provider.as_str();
+        let outcome = match result {
+            Ok(response) => {
+                usage.prompt_tokens += u64::from(response.usage.prompt_tokens);
+                usage.completion_tokens += u64::from(response.usage.completion_tokens);
+                usage.cost_usd += pricing.cost(&response.usage);
+                LLM_TOKENS
+                    .with_label_values(&[provider, "prompt"])
+                    .inc_by(u64::from(response.usage.prompt_tokens));
+                LLM_TOKENS
+                    .with_label_values(&[provider, "completion"])
+                    .inc_by(u64::from(response.usage.completion_tokens));
+                "success"
+            }
+            Err(Error::LLMRateLimited { .. }) => {
+                usage.rate_limited += 1;
+                "rate_limited"
+            }
+            Err(Error::LLMApiError { .. }) => "api_error",
+            Err(_) => "error",
+        };
+        if outcome != "success" {
+            LLM_FAILURES.with_label_values(&[provider, outcome]).inc();
         }
+        LLM_REQUEST_DURATION.with_label_values(&[provider, outcome]).observe(elapsed.as_secs_f64());
+    }
+
+    fn lock_usage(&self) -> MutexGuard<'_, Vec<ProviderUsage>> {
+        self.usage.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn served(&self) -> Option<&Arc<dyn LlmProvider>> {
+        self.providers.get(self.last_served.load(Ordering::Relaxed))
     }
 
     fn create_vulnerability_detection_prompt(&self, code: &str, file_path: &str) -> String {
@@ -106,6 +319,10 @@ For each vulnerability found, provide:
 - Detailed explanation of the risk
 - Specific remediation recommendations
 - Confidence level (0-100)
+- A short summary of the reasoning that shows the code is vulnerable
+- The known vulnerability pattern it matches (e.g. "missing capability check")
+- CWE identifier of the weakness (e.g. CWE-284)
+- CVSS v3.1 base vector (e.g. CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:N)
 
 Respond in JSON format:
 {{
@@ -117,7 +334,11 @@ Respond in JSON format:
       "code_snippet": "code",
       "description": "detailed_description",
       "recommendation": "specific_fix",
-      "confidence": number
+      "confidence": number,
+      "reasoning": "why_the_code_is_vulnerable",
+      "pattern": "known_vulnerability_pattern",
+      "cwe_id": "CWE-number",
+      "cvss_vector": "CVSS:3.1/AV:_/AC:_/PR:_/UI:_/S:_/C:_/I:_/A:_"
     }}
   ],
   "summary": "overall_assessment",
@@ -127,13 +348,7 @@ Respond in JSON format:
         )
     }
 
-    fn create_security_recommendations_prompt(&self, code: &str, vulnerabilities: &[VulnerabilityFinding]) -> String {
-        let vuln_summary = vulnerabilities
-            .iter()
-            .map(|v| format!("- {} ({}): {}", v.vulnerability_type, v.severity, v.description))
-            .collect::<Vec<_>>()
-            .join("\n");
-
+    fn create_security_recommendations_prompt(&self, code: &str, vuln_summary: &str) -> String {
         format!(
             r#"Based on the following Sui Move code and identified vulnerabilities, provide comprehensive security recommendations:
 
@@ -202,58 +417,7 @@ Respond in JSON format:
         )
     }
 
-    async fn call_openai_api(&self, prompt: &str, max_tokens: u32, temperature: f64) -> Result<LLMResponse> {
-        let request_body = json!({
-            "model": self.model,
-            "messages": [
-                {
-                    "role": "system",
-                    "content": "You are a security expert specializing in Sui Move smart contract analysis."
-                },
-                {
-                    "role": "user",
-                    "content": prompt
-                }
-            ],
-            "max_tokens": max_tokens,
-            "temperature": temperature
-        });
-
-        let response = self
-            .client
-            .post(&format!("{}/chat/completions", self.base_url))
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(Error::LLMApiError {
-                message: format!("OpenAI API error: {}", error_text),
-            });
-        }
-
-        let openai_response: OpenAIResponse = response.json().await?;
-        
-        let first_choice = openai_response.choices.into_iter().next()
-            .ok_or_else(|| Error::LLMApiError {
-                message: "No choices in OpenAI response".to_string(),
-            })?;
-
-        Ok(LLMResponse {
-            content: first_choice.message.content,
-            usage: TokenUsage {
-                prompt_tokens: openai_response.usage.prompt_tokens,
-                completion_tokens: openai_response.usage.completion_tokens,
-                total_tokens: openai_response.usage.total_tokens,
-            },
-            model: openai_response.model,
-        })
-    }
-
-    fn parse_vulnerability_response(&self, content: &str, file_path: &str) -> Result<CodeAnalysisResponse> {
+    fn parse_vulnerability_response(&self, response: &LLMResponse, file_path: &str) -> Result<CodeAnalysisResponse> {
         #[derive(Deserialize)]
         struct VulnResponse {
             vulnerabilities: Vec<RawVulnerability>,
@@ -271,9 +435,17 @@ Respond in JSON format:
             description: String,
             recommendation: String,
             confidence: f64,
+            #[serde(default)]
+            cwe_id: Option<String>,
+            #[serde(default)]
+            cvss_vector: Option<String>,
+            #[serde(default)]
+            reasoning: Option<String>,
+            #[serde(default)]
+            pattern: Option<String>,
         }
 
-        let parsed: VulnResponse = serde_json::from_str(content)
+        let parsed: VulnResponse = serde_json::from_str(&response.content)
             .map_err(|e| Error::LLMApiError {
                 message: format!("Failed to parse vulnerability response: {}", e),
             })?;
@@ -292,8 +464,17 @@ Respond in JSON format:
                 description: raw.description,
                 recommendation: raw.recommendation,
                 cve_id: None,
+                cwe_id: raw.cwe_id,
+                cvss_vector: raw.cvss_vector,
+                cvss_score: None,
                 is_false_positive: false,
-            })
+                prompt_version: Some(VULNERABILITY_PROMPT_VERSION.to_string()),
+                model: Some(response.model.clone()),
+                compilation_status: None,
+                kind: FindingKind::Code,
+                evidence: FindingEvidence::new(raw.reasoning, raw.pattern),
+            }
+            .classify())
             .collect();
 
         Ok(CodeAnalysisResponse {
@@ -403,23 +584,28 @@ Respond in JSON format:
 #[async_trait]
 impl LLMProvider for LLMClient {
     async fn analyze_code(&self, request: LLMRequest) -> Result<LLMResponse> {
-        match self.provider {
-            LLMProviderType::OpenAI => {
-                self.call_openai_api(&request.prompt, request.max_tokens, request.temperature).await
-            }
-            LLMProviderType::Anthropic => {
-                // TODO: Implement Anthropic API
-                Err(Error::LLMApiError {
-                    message: "Anthropic provider not implemented yet".to_string(),
-                })
-            }
-            LLMProviderType::Local => {
-                // TODO: Implement local model API
-                Err(Error::LLMApiError {
-                    message: "Local provider not implemented yet".to_string(),
-                })
+        let mut failures = Vec::new();
+        for (index, provider) in self.providers.iter().enumerate() {
+            match self.complete_with_retries(index, &request).await {
+                Ok(response) => {
+                    self.last_served.store(index, Ordering::Relaxed);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    warn!("LLM provider {} failed, falling back: {}", provider.name(), e);
+                    self.lock_usage()[index].failures += 1;
+                    failures.push(format!("{}: {}", provider.name(), e));
+                }
             }
         }
+
+        Err(Error::LLMUnavailable {
+            message: if failures.is_empty() {
+                "no providers configured".to_string()
+            } else {
+                failures.join("; ")
+            },
+        })
     }
 
     async fn detect_vulnerabilities(&self, code: &str, file_path: &str) -> Result<CodeAnalysisResponse> {
@@ -431,12 +617,22 @@ impl LLMProvider for LLMClient {
             temperature: 0.1,
         };
 
-        let response = self.analyze_code(request).await?;
-        self.parse_vulnerability_response(&response.content, file_path)
+        // Keyed by content alone: the path only names the file in the prompt,
+        // and findings take the path they are asked for.
+        let key = LlmCacheKey::new(VULNERABILITY_PROMPT_VERSION, &[code]);
+        self.complete_cached(request, &key, |response| {
+            self.parse_vulnerability_response(response, file_path)
+        })
+        .await
     }
 
     async fn generate_security_recommendations(&self, code: &str, vulnerabilities: &[VulnerabilityFinding]) -> Result<Vec<SecurityRecommendation>> {
-        let prompt = self.create_security_recommendations_prompt(code, vulnerabilities);
+        let vuln_summary = vulnerabilities
+            .iter()
+            .map(|v| format!("- {} ({}): {}", v.vulnerability_type, v.severity, v.description))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let prompt = self.create_security_recommendations_prompt(code, &vuln_summary);
         let request = LLMRequest {
             prompt,
             code_context: code.to_string(),
@@ -444,8 +640,11 @@ impl LLMProvider for LLMClient {
             temperature: 0.1,
         };
 
-        let response = self.analyze_code(request).await?;
-        self.parse_recommendations_response(&response.content)
+        let key = LlmCacheKey::new(RECOMMENDATIONS_PROMPT_VERSION, &[code, &vuln_summary]);
+        self.complete_cached(request, &key, |response| {
+            self.parse_recommendations_response(&response.content)
+        })
+        .await
     }
 
     async fn assess_code_quality(&self, code: &str) -> Result<f64> {
@@ -457,30 +656,37 @@ impl LLMProvider for LLMClient {
             temperature: 0.1,
         };
 
-        let response = self.analyze_code(request).await?;
-        
         #[derive(Deserialize)]
         struct QualityResponse {
             quality_score: f64,
         }
 
-        let parsed: QualityResponse = serde_json::from_str(&response.content)
-            .map_err(|e| Error::LLMApiError {
-                message: format!("Failed to parse quality response: {}", e),
-            })?;
+        let key = LlmCacheKey::new(CODE_QUALITY_PROMPT_VERSION, &[code]);
+        let parsed: QualityResponse = self
+            .complete_cached(request, &key, |response| {
+                serde_json::from_str(&response.content).map_err(|e| Error::LLMApiError {
+                    message: format!("Failed to parse quality response: {}", e),
+                })
+            })
+            .await?;
 
         Ok(parsed.quality_score.max(0.0).min(100.0))
     }
 
     fn get_provider_name(&self) -> &str {
-        match self.provider {
-            LLMProviderType::OpenAI => "OpenAI",
-            LLMProviderType::Anthropic => "Anthropic",
-            LLMProviderType::Local => "Local",
-        }
+        self.served().map_or("None", |provider| provider.name())
     }
 
     fn get_model_name(&self) -> &str {
-        &self.model
+        self.served().map_or("", |provider| provider.model())
+    }
+
+    fn usage(&self) -> Vec<ProviderUsage> {
+        self.lock_usage().clone()
     }
-}
\ No newline at end of file
+}
+
+/// A random fraction in [0, 1) to spread retries with.
+fn jitter() -> f64 {
+    (Uuid::new_v4().as_u128() % 1_000_000) as f64 / 1_000_000.0
+}
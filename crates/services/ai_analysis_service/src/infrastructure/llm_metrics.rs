@@ -0,0 +1,23 @@
+use jd_utils::metrics::{self, HistogramVec, IntCounterVec, CALL_BUCKETS};
+use std::sync::LazyLock;
+
+/// Time of each provider call, retries included separately, by provider and
+/// how it ended.
+pub static LLM_REQUEST_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    metrics::histogram(
+        "llm_request_duration_seconds",
+        "Time of LLM provider calls",
+        &["provider", "outcome"],
+        CALL_BUCKETS,
+    )
+});
+
+/// Tokens billed, by provider and `prompt` or `completion`.
+pub static LLM_TOKENS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    metrics::counter("llm_tokens_total", "Tokens used by LLM provider calls", &["provider", "kind"])
+});
+
+/// Failed provider calls, by provider and reason.
+pub static LLM_FAILURES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    metrics::counter("llm_failures_total", "Failed LLM provider calls", &["provider", "reason"])
+});
@@ -0,0 +1,462 @@
+use crate::domain::llm_provider_trait::{
+    LLMRequest, LLMResponse, LlmProvider, TokenPricing, TokenUsage,
+};
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use jd_utils::correlation::Correlate;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+
+const SYSTEM_PROMPT: &str =
+    "You are a security expert specializing in Sui Move smart contract analysis.";
+
+/// Send a provider request, turning 429s into `LLMRateLimited` so the chain
+/// can retry them, and other failures into `LLMApiError`.
+async fn send(provider: &str, request: RequestBuilder) -> Result<Response> {
+    let response = request.correlated().send().await?;
+    let status = response.status();
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        let retry_after_seconds = response
+            .headers()
+            .get("retry-after")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok());
+        return Err(Error::LLMRateLimited {
+            provider: provider.to_string(),
+            retry_after_seconds,
+        });
+    }
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(Error::LLMApiError {
+            message: format!("{} API error ({}): {}", provider, status, error_text),
+        });
+    }
+    Ok(response)
+}
+
+fn empty_response(provider: &str) -> Error {
+    Error::LLMApiError {
+        message: format!("No content in {} response", provider),
+    }
+}
+
+/// OpenAI's chat completions API.
+pub struct OpenAiProvider {
+    client: Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+    pricing: TokenPricing,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIResponse {
+    choices: Vec<OpenAIChoice>,
+    usage: OpenAIUsage,
+    model: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIChoice {
+    message: OpenAIMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIMessage {
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAIUsage {
+    prompt_tokens: u32,
+    completion_tokens: u32,
+    total_tokens: u32,
+}
+
+impl OpenAiProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            base_url: "https://api.openai.com/v1".to_string(),
+            model: "gpt-4".to_string(),
+            pricing: TokenPricing { prompt_per_million: 30.0, completion_per_million: 60.0 },
+        }
+    }
+
+    pub fn with_model(mut self, model: String) -> Self {
+        self.model = model;
+        self
+    }
+
+    pub fn with_pricing(mut self, pricing: TokenPricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiProvider {
+    async fn complete(&self, request: &LLMRequest) -> Result<LLMResponse> {
+        let request_body = json!({
+            "model": self.model,
+            "messages": [
+                { "role": "system", "content": SYSTEM_PROMPT },
+                { "role": "user", "content": request.prompt }
+            ],
+            "max_tokens": request.max_tokens,
+            "temperature": request.temperature
+        });
+
+        let response = send(
+            self.name(),
+            self.client
+                .post(format!("{}/chat/completions", self.base_url))
+                .bearer_auth(&self.api_key)
+                .json(&request_body),
+        )
+        .await?;
+        let openai_response: OpenAIResponse = response.json().await?;
+
+        let first_choice = openai_response
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| empty_response(self.name()))?;
+
+        Ok(LLMResponse {
+            content: first_choice.message.content,
+            usage: TokenUsage {
+                prompt_tokens: openai_response.usage.prompt_tokens,
+                completion_tokens: openai_response.usage.completion_tokens,
+                total_tokens: openai_response.usage.total_tokens,
+            },
+            model: openai_response.model,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "OpenAI"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn pricing(&self) -> TokenPricing {
+        self.pricing
+    }
+}
+
+/// Anthropic's messages API.
+pub struct AnthropicProvider {
+    client: Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+    pricing: TokenPricing,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicResponse {
+    content: Vec<AnthropicContent>,
+    usage: AnthropicUsage,
+    model: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContent {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicUsage {
+    input_tokens: u32,
+    output_tokens: u32,
+}
+
+impl AnthropicProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            base_url: "https://api.anthropic.com/v1".to_string(),
+            model: "claude-3-sonnet-20240229".to_string(),
+            pricing: TokenPricing { prompt_per_million: 3.0, completion_per_million: 15.0 },
+        }
+    }
+
+    pub fn with_model(mut self, model: String) -> Self {
+        self.model = model;
+        self
+    }
+
+    pub fn with_pricing(mut self, pricing: TokenPricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+}
+
+#[async_trait]
+impl LlmProvider for AnthropicProvider {
+    async fn complete(&self, request: &LLMRequest) -> Result<LLMResponse> {
+        let request_body = json!({
+            "model": self.model,
+            "system": SYSTEM_PROMPT,
+            "messages": [{ "role": "user", "content": request.prompt }],
+            "max_tokens": request.max_tokens,
+            "temperature": request.temperature
+        });
+
+        let response = send(
+            self.name(),
+            self.client
+                .post(format!("{}/messages", self.base_url))
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", "2023-06-01")
+                .json(&request_body),
+        )
+        .await?;
+        let anthropic_response: AnthropicResponse = response.json().await?;
+
+        let content: String =
+            anthropic_response.content.into_iter().filter_map(|block| block.text).collect();
+        if content.is_empty() {
+            return Err(empty_response(self.name()));
+        }
+
+        let usage = anthropic_response.usage;
+        Ok(LLMResponse {
+            content,
+            usage: TokenUsage {
+                prompt_tokens: usage.input_tokens,
+                completion_tokens: usage.output_tokens,
+                total_tokens: usage.input_tokens + usage.output_tokens,
+            },
+            model: anthropic_response.model,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "Anthropic"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn pricing(&self) -> TokenPricing {
+        self.pricing
+    }
+}
+
+/// Google's Gemini `generateContent` API.
+pub struct GoogleProvider {
+    client: Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+    pricing: TokenPricing,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    #[serde(default)]
+    candidates: Vec<GeminiCandidate>,
+    #[serde(default)]
+    usage_metadata: Option<GeminiUsage>,
+    #[serde(default)]
+    model_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiCandidate {
+    content: Option<GeminiContent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiContent {
+    #[serde(default)]
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiPart {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiUsage {
+    #[serde(default)]
+    prompt_token_count: u32,
+    #[serde(default)]
+    candidates_token_count: u32,
+    #[serde(default)]
+    total_token_count: u32,
+}
+
+impl GoogleProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            base_url: "https://generativelanguage.googleapis.com/v1beta".to_string(),
+            model: "gemini-1.5-pro".to_string(),
+            pricing: TokenPricing { prompt_per_million: 1.25, completion_per_million: 5.0 },
+        }
+    }
+
+    pub fn with_model(mut self, model: String) -> Self {
+        self.model = model;
+        self
+    }
+
+    pub fn with_pricing(mut self, pricing: TokenPricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+}
+
+#[async_trait]
+impl LlmProvider for GoogleProvider {
+    async fn complete(&self, request: &LLMRequest) -> Result<LLMResponse> {
+        let request_body = json!({
+            "systemInstruction": { "parts": [{ "text": SYSTEM_PROMPT }] },
+            "contents": [{ "role": "user", "parts": [{ "text": request.prompt }] }],
+            "generationConfig": {
+                "maxOutputTokens": request.max_tokens,
+                "temperature": request.temperature
+            }
+        });
+
+        let response = send(
+            self.name(),
+            self.client
+                .post(format!("{}/models/{}:generateContent", self.base_url, self.model))
+                .header("x-goog-api-key", &self.api_key)
+                .json(&request_body),
+        )
+        .await?;
+        let gemini_response: GeminiResponse = response.json().await?;
+
+        let content: String = gemini_response
+            .candidates
+            .into_iter()
+            .next()
+            .and_then(|candidate| candidate.content)
+            .map(|content| content.parts.into_iter().filter_map(|part| part.text).collect())
+            .unwrap_or_default();
+        if content.is_empty() {
+            return Err(empty_response(self.name()));
+        }
+
+        let usage = gemini_response.usage_metadata.unwrap_or_default();
+        Ok(LLMResponse {
+            content,
+            usage: TokenUsage {
+                prompt_tokens: usage.prompt_token_count,
+                completion_tokens: usage.candidates_token_count,
+                total_tokens: usage.total_token_count,
+            },
+            model: gemini_response.model_version.unwrap_or_else(|| self.model.clone()),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "Google"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn pricing(&self) -> TokenPricing {
+        self.pricing
+    }
+}
+
+/// A local model served by Ollama. Free to call, so it is usually last in
+/// the chain.
+pub struct OllamaProvider {
+    client: Client,
+    base_url: String,
+    model: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaResponse {
+    message: OllamaMessage,
+    #[serde(default)]
+    prompt_eval_count: u32,
+    #[serde(default)]
+    eval_count: u32,
+    model: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaMessage {
+    content: String,
+}
+
+impl OllamaProvider {
+    pub fn new(base_url: String, model: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OllamaProvider {
+    async fn complete(&self, request: &LLMRequest) -> Result<LLMResponse> {
+        let request_body = json!({
+            "model": self.model,
+            "messages": [
+                { "role": "system", "content": SYSTEM_PROMPT },
+                { "role": "user", "content": request.prompt }
+            ],
+            "stream": false,
+            "options": {
+                "num_predict": request.max_tokens,
+                "temperature": request.temperature
+            }
+        });
+
+        let response = send(
+            self.name(),
+            self.client.post(format!("{}/api/chat", self.base_url)).json(&request_body),
+        )
+        .await?;
+        let ollama_response: OllamaResponse = response.json().await?;
+
+        Ok(LLMResponse {
+            content: ollama_response.message.content,
+            usage: TokenUsage {
+                prompt_tokens: ollama_response.prompt_eval_count,
+                completion_tokens: ollama_response.eval_count,
+                total_tokens: ollama_response.prompt_eval_count + ollama_response.eval_count,
+            },
+            model: ollama_response.model,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "Ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn pricing(&self) -> TokenPricing {
+        TokenPricing::default()
+    }
+}
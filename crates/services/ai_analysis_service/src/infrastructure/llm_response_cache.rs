@@ -0,0 +1,154 @@
+use crate::domain::llm_provider_trait::{LLMResponse, TokenUsage};
+use crate::error::{Error, Result};
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Row};
+use std::time::Duration;
+
+/// What a cached response answered: the code a prompt was filled with and
+/// the version of the prompt template. Responses are cached per model too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LlmCacheKey {
+    /// SHA-256 of the prompt's inputs, see `content_hash`.
+    pub content_hash: String,
+    pub prompt_version: &'static str,
+}
+
+impl LlmCacheKey {
+    pub fn new(prompt_version: &'static str, inputs: &[&str]) -> Self {
+        Self { content_hash: content_hash(inputs), prompt_version }
+    }
+}
+
+/// Hex SHA-256 of `inputs`, each prefixed with its length so that
+/// `["ab", "c"]` and `["a", "bc"]` differ.
+pub fn content_hash(inputs: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for input in inputs {
+        hasher.update((input.len() as u64).to_be_bytes());
+        hasher.update(input.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Postgres cache of LLM responses in `llm_response_cache`, so analysing a
+/// file that has not changed since does not call the provider again.
+#[derive(Clone)]
+pub struct LlmResponseCache {
+    db: PgPool,
+}
+
+impl LlmResponseCache {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// The cached response to `key` from the first of `models` that has one,
+    /// with the model it is cached under.
+    pub async fn response(
+        &self,
+        key: &LlmCacheKey,
+        models: &[String],
+    ) -> Result<Option<(String, LLMResponse)>> {
+        let row = sqlx::query(
+            r#"
+            UPDATE llm_response_cache
+            SET last_used_at = NOW()
+            WHERE (content_hash, prompt_version, model) = (
+                SELECT content_hash, prompt_version, model FROM llm_response_cache
+                WHERE content_hash = $1 AND prompt_version = $2 AND model = ANY($3)
+                ORDER BY array_position($3::text[], model::text)
+                LIMIT 1
+            )
+            RETURNING model, response_model, content, prompt_tokens, completion_tokens
+            "#,
+        )
+        .bind(&key.content_hash)
+        .bind(key.prompt_version)
+        .bind(models)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(row.map(|row| {
+            let prompt_tokens = row.get::<i32, _>("prompt_tokens") as u32;
+            let completion_tokens = row.get::<i32, _>("completion_tokens") as u32;
+            let response = LLMResponse {
+                content: row.get("content"),
+                usage: TokenUsage {
+                    prompt_tokens,
+                    completion_tokens,
+                    total_tokens: prompt_tokens + completion_tokens,
+                },
+                model: row.get("response_model"),
+            };
+            (row.get("model"), response)
+        }))
+    }
+
+    /// Cache `response`, served by `provider`, under `key` and the model
+    /// that was asked. Providers may answer with a dated variant of the
+    /// model, which is kept as the response's model but not used as the key.
+    pub async fn store_response(
+        &self,
+        key: &LlmCacheKey,
+        provider: &str,
+        model: &str,
+        response: &LLMResponse,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO llm_response_cache (
+                content_hash, prompt_version, model, provider, response_model, content,
+                prompt_tokens, completion_tokens
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (content_hash, prompt_version, model) DO UPDATE
+            SET provider = EXCLUDED.provider, response_model = EXCLUDED.response_model,
+                content = EXCLUDED.content,
+                prompt_tokens = EXCLUDED.prompt_tokens,
+                completion_tokens = EXCLUDED.completion_tokens,
+                created_at = NOW(), last_used_at = NOW()
+            "#,
+        )
+        .bind(&key.content_hash)
+        .bind(key.prompt_version)
+        .bind(model)
+        .bind(provider)
+        .bind(&response.model)
+        .bind(&response.content)
+        .bind(response.usage.prompt_tokens as i32)
+        .bind(response.usage.completion_tokens as i32)
+        .execute(&self.db)
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+
+        Ok(())
+    }
+
+    /// Drop responses unused for longer than `retention`. Returns the number
+    /// of rows removed.
+    pub async fn purge_unused(&self, retention: Duration) -> Result<u64> {
+        let purged = sqlx::query(
+            "DELETE FROM llm_response_cache WHERE last_used_at < NOW() - make_interval(secs => $1)",
+        )
+        .bind(retention.as_secs_f64())
+        .execute(&self.db)
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?
+        .rows_affected();
+
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_separates_inputs() {
+        assert_eq!(content_hash(&["module a {}"]), content_hash(&["module a {}"]));
+        assert_ne!(content_hash(&["ab", "c"]), content_hash(&["a", "bc"]));
+        assert_eq!(content_hash(&[]).len(), 64);
+    }
+}
@@ -1,4 +1,15 @@
+pub mod advisory_feed;
 pub mod analysis_repository_impl;
+pub mod anchor_analyzer;
 pub mod github_integration;
 pub mod llm_client;
-pub mod static_analyzer;
\ No newline at end of file
+pub mod llm_metrics;
+pub mod llm_providers;
+pub mod llm_response_cache;
+pub mod move_bytecode_analyzer;
+pub mod report_archive;
+pub mod solidity_analyzer;
+pub mod static_analyzer;
+pub mod webhook_embargo_notifier;
+pub mod webhook_leak_notifier;
+pub mod webhook_regression_notifier;
\ No newline at end of file
@@ -0,0 +1,340 @@
+use crate::domain::analysis_models::{CompilationStatus, VulnerabilityFinding};
+use crate::domain::bytecode_analyzer_trait::{
+    is_within, move_packages, BytecodeAnalysis, BytecodeAnalyzer, PackageCompilation, MOVE_MANIFEST,
+};
+use crate::domain::move_bytecode::{source_module_name, DisassembledModule};
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+/// Compiler output kept in a failed package's message.
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+/// How the Move toolchain is run.
+#[derive(Debug, Clone)]
+pub struct MoveCompilerSettings {
+    /// The `sui` binary, or another CLI with `move build` and
+    /// `move disassemble` subcommands.
+    pub command: String,
+    /// Command the compiler runs under, e.g. `bwrap --unshare-all ...` or
+    /// `firejail --net=none`; the compiler runs directly when empty.
+    pub sandbox: Vec<String>,
+    /// Where the toolchain caches fetched dependencies between runs.
+    pub move_home: Option<PathBuf>,
+    /// Budget for compiling and disassembling one package.
+    pub timeout: Duration,
+    /// Most packages compiled per analysis; the rest are skipped.
+    pub max_packages: usize,
+}
+
+impl Default for MoveCompilerSettings {
+    fn default() -> Self {
+        Self {
+            command: "sui".to_string(),
+            sandbox: Vec::new(),
+            move_home: None,
+            timeout: Duration::from_secs(120),
+            max_packages: 10,
+        }
+    }
+}
+
+impl MoveCompilerSettings {
+    /// Settings from `MOVE_COMPILER_*`, or `None` unless
+    /// `MOVE_COMPILER_COMMAND` names the toolchain to run.
+    pub fn from_env() -> Option<Self> {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+        let defaults = Self::default();
+        Some(Self {
+            command: env("MOVE_COMPILER_COMMAND")?,
+            sandbox: env("MOVE_COMPILER_SANDBOX")
+                .map(|sandbox| sandbox.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            move_home: env("MOVE_HOME").map(PathBuf::from),
+            timeout: env("MOVE_COMPILER_TIMEOUT_SECS")
+                .and_then(|secs| secs.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.timeout),
+            max_packages: env("MOVE_COMPILER_MAX_PACKAGES")
+                .and_then(|max| max.parse().ok())
+                .unwrap_or(defaults.max_packages),
+        })
+    }
+}
+
+/// Compiles Move packages in a scratch directory and checks the bytecode of
+/// their modules: capabilities handed out to anyone, entry functions that
+/// change shared objects unchecked and abilities the modules never use.
+/// Findings point at the declarations in the package's sources.
+pub struct MoveBytecodeAnalyzer {
+    settings: MoveCompilerSettings,
+}
+
+impl MoveBytecodeAnalyzer {
+    pub fn new(settings: MoveCompilerSettings) -> Self {
+        Self { settings }
+    }
+
+    /// The analyzer configured by `MoveCompilerSettings::from_env`.
+    pub fn from_env() -> Option<Self> {
+        MoveCompilerSettings::from_env().map(Self::new)
+    }
+
+    async fn analyze_package(
+        &self,
+        package_path: &str,
+        sources: &HashMap<&str, &str>,
+    ) -> Result<(PackageCompilation, Vec<VulnerabilityFinding>)> {
+        let scratch = scratch_dir()?;
+        let dir = scratch.path();
+        write_sources(dir, package_path, sources).await?;
+
+        let compilation = |status, message: Option<String>, modules_analyzed| PackageCompilation {
+            package_path: package_path.to_string(),
+            status,
+            message,
+            modules_analyzed,
+        };
+        let deadline = Instant::now() + self.settings.timeout;
+        let dir_arg = dir.to_string_lossy().into_owned();
+        match self.run(dir, &["move", "build", "--path", &dir_arg], deadline).await {
+            Ok(_) => {}
+            Err(CommandFailure::TimedOut) => {
+                return Ok((compilation(CompilationStatus::TimedOut, None, 0), Vec::new()));
+            }
+            Err(CommandFailure::Failed(message)) => {
+                return Ok((compilation(CompilationStatus::Failed, Some(message), 0), Vec::new()));
+            }
+        }
+
+        // Source files by the module they declare, to point findings at
+        let files_by_module: HashMap<&str, (&str, &str)> = sources
+            .iter()
+            .filter(|(path, _)| path.ends_with(".move"))
+            .filter_map(|(path, content)| Some((source_module_name(content)?, (*path, *content))))
+            .collect();
+        let manifest_path = match package_path {
+            "" => MOVE_MANIFEST.to_string(),
+            _ => format!("{}/{}", package_path, MOVE_MANIFEST),
+        };
+
+        let mut findings = Vec::new();
+        let mut modules_analyzed = 0;
+        for module_path in compiled_modules(dir).await? {
+            let module_arg = module_path.to_string_lossy().into_owned();
+            let disassembly = match self.run(dir, &["move", "disassemble", &module_arg], deadline).await {
+                Ok(disassembly) => disassembly,
+                Err(CommandFailure::TimedOut) => {
+                    return Ok((compilation(CompilationStatus::TimedOut, None, modules_analyzed), findings));
+                }
+                Err(CommandFailure::Failed(message)) => {
+                    warn!("Failed to disassemble {}: {}", module_path.display(), message);
+                    continue;
+                }
+            };
+            let Some(module) = DisassembledModule::parse(&disassembly) else {
+                debug!("No module in disassembly of {}", module_path.display());
+                continue;
+            };
+            modules_analyzed += 1;
+            for issue in module.check() {
+                let finding = match files_by_module.get(issue.module.as_str()) {
+                    Some((path, content)) => issue.finding(path, issue.locate(content)),
+                    None => issue.finding(&manifest_path, None),
+                };
+                findings.push(finding);
+            }
+        }
+
+        Ok((compilation(CompilationStatus::Compiled, None, modules_analyzed), findings))
+    }
+
+    /// Compile the package at `package_path` and read back its modules'
+    /// bytecode by module name, e.g. to check a published package against
+    /// its source. A build that fails or times out is an error.
+    pub async fn compile_package(
+        &self,
+        package_path: &str,
+        sources: &HashMap<&str, &str>,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let scratch = scratch_dir()?;
+        let dir = scratch.path();
+        write_sources(dir, package_path, sources).await?;
+
+        let deadline = Instant::now() + self.settings.timeout;
+        let dir_arg = dir.to_string_lossy().into_owned();
+        match self.run(dir, &["move", "build", "--path", &dir_arg], deadline).await {
+            Ok(_) => {}
+            Err(CommandFailure::TimedOut) => {
+                return Err(Error::AnalysisFailed {
+                    message: format!("Building Move package '{}' timed out", package_path),
+                });
+            }
+            Err(CommandFailure::Failed(message)) => return Err(Error::AnalysisFailed { message }),
+        }
+
+        let mut modules = Vec::new();
+        for module_path in compiled_modules(dir).await? {
+            let Some(name) = module_path.file_stem().map(|stem| stem.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            let bytecode = tokio::fs::read(&module_path).await.map_err(|e| Error::AnalysisFailed {
+                message: format!("Failed to read {}: {}", module_path.display(), e),
+            })?;
+            modules.push((name, bytecode));
+        }
+        Ok(modules)
+    }
+
+    /// Run a toolchain subcommand in `dir` under the sandbox, returning its
+    /// standard output. The environment is cleared but for `PATH`, with
+    /// `HOME` pointing at the scratch directory.
+    async fn run(
+        &self,
+        dir: &Path,
+        args: &[&str],
+        deadline: Instant,
+    ) -> std::result::Result<String, CommandFailure> {
+        let (program, prefix) = match self.settings.sandbox.split_first() {
+            Some((program, sandbox_args)) => {
+                let mut prefix = sandbox_args.to_vec();
+                prefix.push(self.settings.command.clone());
+                (program.as_str(), prefix)
+            }
+            None => (self.settings.command.as_str(), Vec::new()),
+        };
+        let mut command = Command::new(program);
+        command
+            .args(&prefix)
+            .args(args)
+            .current_dir(dir)
+            .env_clear()
+            .env("PATH", std::env::var_os("PATH").unwrap_or_default())
+            .env("HOME", dir)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        if let Some(move_home) = &self.settings.move_home {
+            command.env("MOVE_HOME", move_home);
+        }
+
+        let output = tokio::time::timeout_at(deadline, command.output())
+            .await
+            .map_err(|_| CommandFailure::TimedOut)?
+            .map_err(|e| CommandFailure::Failed(format!("Failed to run {}: {}", program, e)))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stderr = stderr.trim();
+            // The end of the output says why the build failed
+            let start = stderr.char_indices().rev().nth(MAX_MESSAGE_CHARS - 1).map_or(0, |(i, _)| i);
+            return Err(CommandFailure::Failed(stderr[start..].to_string()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+enum CommandFailure {
+    TimedOut,
+    Failed(String),
+}
+
+fn scratch_dir() -> Result<tempfile::TempDir> {
+    tempfile::Builder::new()
+        .prefix("move-build-")
+        .tempdir()
+        .map_err(|e| Error::AnalysisFailed { message: format!("Failed to create build directory: {}", e) })
+}
+
+/// Write the package's sources into `dir`, relative to the package root.
+async fn write_sources(dir: &Path, package_path: &str, sources: &HashMap<&str, &str>) -> Result<()> {
+    for (path, content) in sources {
+        let relative = path.strip_prefix(package_path).unwrap_or(*path).trim_start_matches('/');
+        let target = dir.join(relative);
+        // Paths come from the repository; never write outside the scratch directory
+        if !Path::new(relative).components().all(|part| matches!(part, Component::Normal(_))) {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+        }
+        tokio::fs::write(&target, content).await.map_err(io_error)?;
+    }
+    Ok(())
+}
+
+fn io_error(e: std::io::Error) -> Error {
+    Error::AnalysisFailed { message: format!("Failed to write package sources: {}", e) }
+}
+
+/// The package's own compiled modules, `build/<package>/bytecode_modules/*.mv`;
+/// dependencies are compiled into a subdirectory and left out.
+async fn compiled_modules(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut modules = Vec::new();
+    let Ok(mut builds) = tokio::fs::read_dir(dir.join("build")).await else {
+        return Ok(modules);
+    };
+    while let Some(build) = builds.next_entry().await.map_err(io_error)? {
+        let Ok(mut entries) = tokio::fs::read_dir(build.path().join("bytecode_modules")).await else {
+            continue;
+        };
+        while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+            let path = entry.path();
+            if path.extension().is_some_and(|extension| extension == "mv") {
+                modules.push(path);
+            }
+        }
+    }
+    modules.sort();
+    Ok(modules)
+}
+
+#[async_trait]
+impl BytecodeAnalyzer for MoveBytecodeAnalyzer {
+    fn name(&self) -> &str {
+        "move-bytecode"
+    }
+
+    async fn analyze_packages(&self, files: &HashMap<String, String>) -> Result<BytecodeAnalysis> {
+        let packages = move_packages(files.keys());
+        let mut analysis = BytecodeAnalysis::default();
+        if packages.len() > self.settings.max_packages {
+            warn!(
+                "Compiling {} of {} Move packages; raise MOVE_COMPILER_MAX_PACKAGES to compile the rest",
+                self.settings.max_packages,
+                packages.len()
+            );
+        }
+
+        for package_path in packages.iter().take(self.settings.max_packages) {
+            // A file belongs to the innermost package containing it
+            let sources: HashMap<&str, &str> = files
+                .iter()
+                .filter(|(path, _)| {
+                    packages
+                        .iter()
+                        .filter(|package| is_within(path, package))
+                        .max_by_key(|package| package.len())
+                        == Some(package_path)
+                })
+                .map(|(path, content)| (path.as_str(), content.as_str()))
+                .collect();
+
+            let (compilation, findings) = self.analyze_package(package_path, &sources).await?;
+            info!(
+                "Move package '{}' {}: {} modules analysed, {} bytecode findings",
+                package_path,
+                compilation.status.as_str(),
+                compilation.modules_analyzed,
+                findings.len()
+            );
+            analysis.packages.push(compilation);
+            analysis.findings.extend(findings);
+        }
+        Ok(analysis)
+    }
+}
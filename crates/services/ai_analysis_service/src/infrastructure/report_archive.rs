@@ -0,0 +1,120 @@
+use jd_object_store::ObjectStore;
+use sqlx::{Pool, Postgres};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{Error, Result};
+
+/// Archived reports are kept under this prefix, as
+/// `{prefix}{owner}/{name}/{commit_sha}/{analysis_id}.json`.
+pub const REPORT_OBJECT_PREFIX: &str = "analysis-reports/";
+
+/// The analysis, its raw results and its findings as they stood when
+/// archived.
+const REPORT_SQL: &str = r#"
+    SELECT r.full_name, a.commit_sha, jsonb_build_object(
+        'repository', r.full_name,
+        'analysis', to_jsonb(a) - 'report_uri',
+        'vulnerabilities', COALESCE(
+            (SELECT jsonb_agg(to_jsonb(v) ORDER BY v.ctime, v.id)
+             FROM security_vulnerabilities v
+             WHERE v.analysis_result_id = a.id),
+            '[]'::jsonb
+        ),
+        'archived_at', NOW()
+    )
+    FROM code_analysis_results a
+    JOIN github_repositories r ON r.id = a.repository_id
+    WHERE a.id = $1
+"#;
+
+/// JSON reports of finished analyses in object storage, kept for audits
+/// after findings are triaged, fixed or purged.
+pub struct AnalysisReportArchive {
+    db: Pool<Postgres>,
+    objects: Arc<dyn ObjectStore>,
+}
+
+impl AnalysisReportArchive {
+    pub fn new(db: Pool<Postgres>, objects: Arc<dyn ObjectStore>) -> Self {
+        Self { db, objects }
+    }
+
+    /// Archive the report of `analysis_id` and record its URI. Returns the
+    /// URI, or `None` if there is no such analysis.
+    pub async fn archive(&self, analysis_id: Uuid) -> Result<Option<String>> {
+        let report: Option<(String, String, serde_json::Value)> = sqlx::query_as(REPORT_SQL)
+            .bind(analysis_id)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(database_error)?;
+        let Some((repository, commit_sha, report)) = report else {
+            return Ok(None);
+        };
+
+        let key = format!("{}{}/{}/{}.json", REPORT_OBJECT_PREFIX, repository, commit_sha, analysis_id);
+        self.objects
+            .put(&key, serde_json::to_vec(&report)?.into(), "application/json")
+            .await
+            .map_err(storage_error)?;
+        let uri = self.objects.uri(&key);
+        sqlx::query("UPDATE code_analysis_results SET report_uri = $2 WHERE id = $1")
+            .bind(analysis_id)
+            .bind(&uri)
+            .execute(&self.db)
+            .await
+            .map_err(database_error)?;
+        Ok(Some(uri))
+    }
+
+    /// Archive up to `limit` analyses not archived yet, oldest first.
+    /// Returns how many were archived.
+    pub async fn archive_pending(&self, limit: i64) -> Result<usize> {
+        let pending: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM code_analysis_results WHERE report_uri IS NULL ORDER BY ctime LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await
+        .map_err(database_error)?;
+        let mut archived = 0;
+        for analysis_id in pending {
+            if self.archive(analysis_id).await?.is_some() {
+                archived += 1;
+            }
+        }
+        Ok(archived)
+    }
+
+    /// A link to download the archived report of `analysis_id`, an analysis
+    /// of `repository_id`, until `expires_in` has passed. `None` if it has
+    /// not been archived.
+    pub async fn report_url(
+        &self,
+        repository_id: Uuid,
+        analysis_id: Uuid,
+        expires_in: Duration,
+    ) -> Result<Option<String>> {
+        let uri: Option<Option<String>> =
+            sqlx::query_scalar("SELECT report_uri FROM code_analysis_results WHERE id = $1 AND repository_id = $2")
+                .bind(analysis_id)
+                .bind(repository_id)
+                .fetch_optional(&self.db)
+                .await
+                .map_err(database_error)?;
+        let Some(uri) = uri.flatten() else {
+            return Ok(None);
+        };
+        let key = self.objects.key(&uri).map_err(storage_error)?;
+        Ok(Some(self.objects.presigned_get_url(&key, expires_in).map_err(storage_error)?))
+    }
+}
+
+fn storage_error(e: jd_object_store::Error) -> Error {
+    Error::ExternalServiceError { service: "object_store".to_string(), message: e.to_string() }
+}
+
+fn database_error(e: sqlx::Error) -> Error {
+    Error::DatabaseError { message: e.to_string() }
+}
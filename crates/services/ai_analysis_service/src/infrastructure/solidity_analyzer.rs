@@ -0,0 +1,221 @@
+use crate::domain::analysis_models::{Severity, VulnerabilityFinding, VulnerabilityType};
+use crate::domain::static_analyzer_trait::{code_of_line, LineRule, StaticAnalyzer};
+use crate::error::Result;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Low-level calls, which hand control to the callee and report failure
+/// through their return value instead of reverting.
+const LOW_LEVEL_CALLS: [&str; 6] =
+    [".call{", ".call(", ".call.value(", ".delegatecall(", ".staticcall(", ".send("];
+
+/// A statement writing contract state: assignments to a name, mapping entry or
+/// field at the start of the line, and `delete`. Declarations such as
+/// `uint256 amount = ...` start with a type and do not match.
+fn state_write() -> &'static Regex {
+    static STATE_WRITE: OnceLock<Regex> = OnceLock::new();
+    STATE_WRITE.get_or_init(|| {
+        Regex::new(r"^(delete\s|\w+(\[[^\]]*\])*(\.\w+)*\s*([-+*/]?=)[^=])").expect("valid regex")
+    })
+}
+
+/// The function whose body is being scanned.
+struct FunctionScope {
+    /// Brace depth of the `function` line.
+    depth: usize,
+    body_opened: bool,
+    /// `nonReentrant` or a similar guard modifier.
+    guarded: bool,
+    /// First external call made so far, and its line number.
+    external_call: Option<(usize, String)>,
+    reentrancy_reported: bool,
+}
+
+/// Solidity checks: reentrancy, unchecked low-level calls and `tx.origin`
+/// authorization.
+pub struct SolidityStaticAnalyzer {
+    version: String,
+}
+
+impl SolidityStaticAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            version: "1.0.0".to_string(),
+        }
+    }
+
+    fn reentrancy() -> LineRule {
+        LineRule {
+            vulnerability_type: VulnerabilityType::ReentrancyLike,
+            severity: Severity::High,
+            confidence: 75.0,
+            description: "External call is made before contract state is updated, so a reentrant call sees stale state",
+            recommendation: "Update state before the call (checks-effects-interactions) or add a nonReentrant guard",
+        }
+    }
+
+    fn unchecked_call() -> LineRule {
+        LineRule {
+            vulnerability_type: VulnerabilityType::Other("Unchecked External Call".to_string()),
+            severity: Severity::Medium,
+            confidence: 80.0,
+            description: "Return value of a low-level call is ignored, so a failed call goes unnoticed",
+            recommendation: "Check the returned success flag, e.g. `(bool ok, ) = target.call(...); require(ok);`",
+        }
+    }
+
+    fn tx_origin() -> LineRule {
+        LineRule {
+            vulnerability_type: VulnerabilityType::AccessControl,
+            severity: Severity::High,
+            confidence: 85.0,
+            description: "Authorization uses tx.origin, which a malicious contract called by the owner can pass",
+            recommendation: "Authorize with msg.sender instead of tx.origin",
+        }
+    }
+
+    fn is_external_call(code: &str) -> Option<usize> {
+        LOW_LEVEL_CALLS.iter().filter_map(|call| code.find(call)).min()
+    }
+
+    /// Whether the statement making the call at `position` uses its result.
+    fn call_result_checked(code: &str, position: usize) -> bool {
+        let before = code[..position].trim_start();
+        let checking = ["require(", "assert(", "if", "return", "("];
+        before.contains('=')
+            || before.contains("bool")
+            || checking.iter().any(|start| before.starts_with(start))
+    }
+
+    fn uses_tx_origin_for_auth(code: &str) -> bool {
+        // `tx.origin == msg.sender` only rejects contract callers.
+        code.contains("tx.origin")
+            && (code.contains("==") || code.contains("!="))
+            && !code.contains("msg.sender")
+    }
+}
+
+impl StaticAnalyzer for SolidityStaticAnalyzer {
+    fn name(&self) -> &str {
+        "solidity"
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[".sol"]
+    }
+
+    fn analyze_file(&self, file_path: &str, content: &str) -> Result<Vec<VulnerabilityFinding>> {
+        let mut findings = Vec::new();
+        let mut depth = 0usize;
+        let mut function: Option<FunctionScope> = None;
+
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let Some(code) = code_of_line(line) else {
+                continue;
+            };
+
+            let starts_function = ["function ", "constructor(", "fallback(", "receive("]
+                .iter()
+                .any(|start| code.starts_with(start));
+            if function.is_none() && starts_function {
+                function = Some(FunctionScope {
+                    depth,
+                    body_opened: false,
+                    guarded: false,
+                    external_call: None,
+                    reentrancy_reported: false,
+                });
+            }
+
+            if let Some(scope) = function.as_mut() {
+                if !scope.body_opened && code.contains("nonReentrant") {
+                    scope.guarded = true;
+                }
+                if scope.body_opened {
+                    if let Some(position) = Self::is_external_call(code) {
+                        if scope.external_call.is_none() {
+                            scope.external_call = Some((line_number, code.to_string()));
+                        }
+                        if !Self::call_result_checked(code, position) {
+                            findings.push(Self::unchecked_call().finding(file_path, line_number, code));
+                        }
+                    } else if !scope.guarded && !scope.reentrancy_reported && state_write().is_match(code) {
+                        if let Some((call_line, call_code)) = &scope.external_call {
+                            findings.push(Self::reentrancy().finding(file_path, *call_line, call_code));
+                            scope.reentrancy_reported = true;
+                        }
+                    }
+                }
+            }
+
+            if Self::uses_tx_origin_for_auth(code) {
+                findings.push(Self::tx_origin().finding(file_path, line_number, code));
+            }
+
+            depth += code.matches('{').count();
+            depth = depth.saturating_sub(code.matches('}').count());
+            if let Some(scope) = function.as_mut() {
+                if depth > scope.depth {
+                    scope.body_opened = true;
+                } else if scope.body_opened || code.ends_with(';') || code.ends_with('}') {
+                    // The body closed, this was a declaration without one, or
+                    // the whole function was on one line.
+                    function = None;
+                }
+            }
+        }
+
+        Ok(findings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VAULT: &str = r#"
+contract Vault {
+    mapping(address => uint256) public balances;
+
+    function withdraw() external {
+        uint256 amount = balances[msg.sender];
+        (bool ok, ) = msg.sender.call{value: amount}("");
+        require(ok);
+        balances[msg.sender] = 0;
+    }
+
+    function safeWithdraw() external nonReentrant {
+        uint256 amount = balances[msg.sender];
+        (bool ok, ) = msg.sender.call{value: amount}("");
+        require(ok);
+        balances[msg.sender] = 0;
+    }
+
+    function sweep(address payable to) external {
+        require(tx.origin == owner);
+        to.send(address(this).balance);
+    }
+}
+"#;
+
+    #[test]
+    fn finds_reentrancy_unchecked_calls_and_tx_origin() {
+        let findings = SolidityStaticAnalyzer::new().analyze_file("contracts/Vault.sol", VAULT).unwrap();
+        let lines = |description: &str| {
+            findings
+                .iter()
+                .filter(|finding| finding.description == description)
+                .map(|finding| finding.line_number.unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(lines(SolidityStaticAnalyzer::reentrancy().description), [7]);
+        assert_eq!(lines(SolidityStaticAnalyzer::tx_origin().description), [20]);
+        assert_eq!(lines(SolidityStaticAnalyzer::unchecked_call().description), [21]);
+    }
+}
@@ -1,50 +1,92 @@
-use crate::domain::analysis_models::{AnalysisResult, AnalysisRequest, AnalysisType, VulnerabilityFinding};
+use crate::domain::analysis_models::{AnalysisResult, AnalysisRequest, AnalysisType, FindingKind, VulnerabilityFinding};
+use crate::domain::analysis_ruleset::Ruleset;
+use crate::domain::secret_scanner::scan_secrets;
+use crate::domain::static_analyzer_trait::{has_extension, StaticAnalyzer};
 use crate::domain::vulnerability_patterns::VulnerabilityPatterns;
 use crate::error::{Error, Result};
+use crate::infrastructure::anchor_analyzer::AnchorStaticAnalyzer;
+use crate::infrastructure::solidity_analyzer::SolidityStaticAnalyzer;
 use chrono::Utc;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
 
-pub struct SuiMoveStaticAnalyzer {
-    patterns: VulnerabilityPatterns,
-    version: String,
+/// Runs the analyzer for each file's language and scores the findings of
+/// all of them together. Files no analyzer handles are skipped.
+pub struct MultiLanguageStaticAnalyzer {
+    analyzers: Vec<Box<dyn StaticAnalyzer>>,
 }
 
-impl SuiMoveStaticAnalyzer {
+impl MultiLanguageStaticAnalyzer {
+    /// The built-in analyzers: Sui Move, Solidity and Anchor programs.
     pub fn new() -> Self {
         Self {
-            patterns: VulnerabilityPatterns::new(),
-            version: "1.0.0".to_string(),
+            analyzers: vec![
+                Box::new(SuiMoveStaticAnalyzer::new()),
+                Box::new(SolidityStaticAnalyzer::new()),
+                Box::new(AnchorStaticAnalyzer::new()),
+            ],
         }
     }
 
+    /// Add an analyzer. It is tried after the ones already registered.
+    pub fn with_analyzer(mut self, analyzer: Box<dyn StaticAnalyzer>) -> Self {
+        self.analyzers.push(analyzer);
+        self
+    }
+
+    /// Whether any analyzer may check a file at this path. Analyzers can
+    /// still pass over it once they see its content.
+    pub fn supports_path(&self, file_path: &str) -> bool {
+        self.analyzers.iter().any(|analyzer| has_extension(file_path, analyzer.extensions()))
+    }
+
+    /// Whether any analyzer checks this file.
+    pub fn supports(&self, file_path: &str, content: &str) -> bool {
+        self.analyzer_for(file_path, content).is_some()
+    }
+
+    fn analyzer_for(&self, file_path: &str, content: &str) -> Option<&dyn StaticAnalyzer> {
+        self.analyzers
+            .iter()
+            .find(|analyzer| analyzer.handles(file_path, content))
+            .map(|analyzer| analyzer.as_ref())
+    }
+
+    /// Findings of each file's analyzer, the ruleset's custom patterns and
+    /// the secrets scan.
     pub async fn analyze(&self, request: AnalysisRequest, file_contents: HashMap<String, String>) -> Result<AnalysisResult> {
         let start_time = std::time::Instant::now();
         let mut all_vulnerabilities = Vec::new();
+        let mut files_by_analyzer: BTreeMap<String, usize> = BTreeMap::new();
+        let mut analyzed_files = HashMap::new();
+        let mut secrets_found = 0;
 
-        // Filter only Move files
-        let move_files: HashMap<String, String> = file_contents
-            .into_iter()
-            .filter(|(path, _)| path.ends_with(".move"))
-            .collect();
+        for (file_path, content) in file_contents {
+            let Some(analyzer) = self.analyzer_for(&file_path, &content) else {
+                continue;
+            };
+            all_vulnerabilities.extend(analyzer.analyze_file_with_ruleset(&file_path, &content, &request.ruleset)?);
+            all_vulnerabilities.extend(request.ruleset.scan_custom_patterns(&file_path, &content)?);
+            let secrets = scan_secrets(&file_path, &content);
+            secrets_found += secrets.len();
+            all_vulnerabilities.extend(secrets);
+            *files_by_analyzer
+                .entry(format!("{}-{}", analyzer.name(), analyzer.version()))
+                .or_default() += 1;
+            analyzed_files.insert(file_path, content);
+        }
 
-        if move_files.is_empty() {
+        if analyzed_files.is_empty() {
             return Err(Error::AnalysisFailed {
-                message: "No Move files found for analysis".to_string(),
+                message: "No supported smart contract files found for analysis".to_string(),
             });
         }
 
-        // Analyze each Move file
-        for (file_path, content) in &move_files {
-            let vulnerabilities = self.analyze_file(file_path, content).await?;
-            all_vulnerabilities.extend(vulnerabilities);
-        }
-
         let analysis_duration = start_time.elapsed();
         
         // Calculate scores based on findings
-        let (security_score, quality_score) = self.calculate_scores(&all_vulnerabilities, &move_files);
+        let (security_score, quality_score) = self.calculate_scores(&all_vulnerabilities, &analyzed_files);
 
         Ok(AnalysisResult {
             id: Uuid::new_v4(),
@@ -56,9 +98,13 @@ impl SuiMoveStaticAnalyzer {
             vulnerabilities: all_vulnerabilities.clone(),
             recommendations: self.generate_recommendations(&all_vulnerabilities),
             analysis_duration_ms: analysis_duration.as_millis() as u64,
-            analyzer_version: self.version.clone(),
+            analyzer_version: files_by_analyzer.keys().cloned().collect::<Vec<_>>().join("+"),
             raw_results: json!({
-                "files_analyzed": move_files.len(),
+                "files_analyzed": analyzed_files.len(),
+                "files_by_analyzer": files_by_analyzer,
+                "disabled_patterns": request.ruleset.disabled_patterns,
+                "custom_patterns": request.ruleset.custom_patterns.len(),
+                "secrets_found": secrets_found,
                 "total_vulnerabilities": all_vulnerabilities.len(),
                 "vulnerability_breakdown": self.get_vulnerability_breakdown(&all_vulnerabilities)
             }),
@@ -66,119 +112,8 @@ impl SuiMoveStaticAnalyzer {
         })
     }
 
-    async fn analyze_file(&self, file_path: &str, content: &str) -> Result<Vec<VulnerabilityFinding>> {
-        // Basic file validation
-        if content.trim().is_empty() {
-            return Ok(Vec::new());
-        }
-
-        // Check if it's a valid Move file
-        if !self.is_valid_move_file(content) {
-            return Err(Error::FileParsingError {
-                file_path: file_path.to_string(),
-                message: "Invalid Move file format".to_string(),
-            });
-        }
-
-        // Apply vulnerability patterns
-        let mut findings = self.patterns.scan_code(file_path, content)?;
-
-        // Apply additional Move-specific checks
-        findings.extend(self.check_move_specific_patterns(file_path, content)?);
-
-        // Apply confidence scoring based on context
-        self.adjust_confidence_scores(&mut findings, content);
-
-        Ok(findings)
-    }
-
-    fn is_valid_move_file(&self, content: &str) -> bool {
-        // Basic Move file validation
-        content.contains("module") || content.contains("script") || content.contains("use ")
-    }
-
-    fn check_move_specific_patterns(&self, file_path: &str, content: &str) -> Result<Vec<VulnerabilityFinding>> {
-        let mut findings = Vec::new();
-
-        // Check for missing module documentation
-        if !content.contains("///") && content.contains("module") {
-            findings.push(VulnerabilityFinding {
-                id: Uuid::new_v4(),
-                vulnerability_type: crate::domain::analysis_models::VulnerabilityType::Other("Documentation".to_string()),
-                severity: crate::domain::analysis_models::Severity::Low,
-                confidence_score: 90.0,
-                file_path: file_path.to_string(),
-                line_number: Some(1),
-                code_snippet: None,
-                description: "Module lacks proper documentation".to_string(),
-                recommendation: "Add module documentation using /// comments".to_string(),
-                cve_id: None,
-                is_false_positive: false,
-            });
-        }
-
-        // Check for friend declarations (potential access control issues)
-        if content.contains("friend") {
-            for (line_number, line) in content.lines().enumerate() {
-                if line.trim().starts_with("friend") {
-                    findings.push(VulnerabilityFinding {
-                        id: Uuid::new_v4(),
-                        vulnerability_type: crate::domain::analysis_models::VulnerabilityType::AccessControl,
-                        severity: crate::domain::analysis_models::Severity::Medium,
-                        confidence_score: 70.0,
-                        file_path: file_path.to_string(),
-                        line_number: Some((line_number + 1) as u32),
-                        code_snippet: Some(line.trim().to_string()),
-                        description: "Friend declaration may introduce unexpected access".to_string(),
-                        recommendation: "Review friend module access and ensure it's necessary".to_string(),
-                        cve_id: None,
-                        is_false_positive: false,
-                    });
-                }
-            }
-        }
-
-        // Check for test functions in non-test modules
-        if !file_path.contains("test") && content.contains("#[test]") {
-            findings.push(VulnerabilityFinding {
-                id: Uuid::new_v4(),
-                vulnerability_type: crate::domain::analysis_models::VulnerabilityType::Other("Test in Production".to_string()),
-                severity: crate::domain::analysis_models::Severity::Medium,
-                confidence_score: 85.0,
-                file_path: file_path.to_string(),
-                line_number: None,
-                code_snippet: None,
-                description: "Test functions found in production module".to_string(),
-                recommendation: "Move test functions to separate test modules".to_string(),
-                cve_id: None,
-                is_false_positive: false,
-            });
-        }
-
-        Ok(findings)
-    }
-
-    fn adjust_confidence_scores(&self, findings: &mut Vec<VulnerabilityFinding>, content: &str) {
-        for finding in findings {
-            // Increase confidence if similar patterns are found multiple times
-            let pattern_count = content.matches(&finding.description).count();
-            if pattern_count > 1 {
-                finding.confidence_score = (finding.confidence_score * 1.2).min(95.0);
-            }
-
-            // Adjust based on file type and context
-            if content.contains("entry fun") && finding.vulnerability_type == crate::domain::analysis_models::VulnerabilityType::AccessControl {
-                finding.confidence_score = (finding.confidence_score * 1.3).min(95.0);
-            }
-
-            // Lower confidence for files with extensive validation
-            if content.matches("assert!").count() > 5 {
-                finding.confidence_score *= 0.9;
-            }
-        }
-    }
-
-    fn calculate_scores(&self, vulnerabilities: &[VulnerabilityFinding], files: &HashMap<String, String>) -> (f64, f64) {
+    /// Security and quality scores of `files` given their findings.
+    pub fn calculate_scores(&self, vulnerabilities: &[VulnerabilityFinding], files: &HashMap<String, String>) -> (f64, f64) {
         if vulnerabilities.is_empty() {
             return (95.0, 90.0); // High scores for clean code
         }
@@ -217,8 +152,13 @@ impl SuiMoveStaticAnalyzer {
         // Penalty for vulnerability density
         quality_score -= vulnerability_density * 50.0;
         
-        // Bonus for assertion usage (defensive programming)
-        let assertion_ratio = total_content.matches("assert!").count() as f64 / total_lines.max(1) as f64;
+        // Bonus for assertion usage (defensive programming): Move and Rust
+        // `assert!`, Solidity `require(` and Anchor `require!(`
+        let assertions = ["assert!", "require(", "require!("]
+            .iter()
+            .map(|assertion| total_content.matches(assertion).count())
+            .sum::<usize>();
+        let assertion_ratio = assertions as f64 / total_lines.max(1) as f64;
         quality_score += assertion_ratio * 20.0;
 
         (security_score.max(0.0).min(100.0), quality_score.max(0.0).min(100.0))
@@ -275,6 +215,40 @@ impl SuiMoveStaticAnalyzer {
                             ],
                         }
                     },
+                    crate::domain::analysis_models::VulnerabilityType::ReentrancyLike => {
+                        SecurityRecommendation {
+                            id: Uuid::new_v4(),
+                            category: RecommendationCategory::CodeStructure,
+                            title: "Update State Before External Calls".to_string(),
+                            description: "Follow checks-effects-interactions: finish every state change before calling another contract, or guard the function against reentry".to_string(),
+                            priority: Priority::Critical,
+                            code_examples: vec![
+                                CodeExample {
+                                    title: "Zero the Balance First".to_string(),
+                                    before: Some("(bool ok, ) = msg.sender.call{value: amount}(\"\");\nbalances[msg.sender] = 0;".to_string()),
+                                    after: "balances[msg.sender] = 0;\n(bool ok, ) = msg.sender.call{value: amount}(\"\");\nrequire(ok, \"transfer failed\");".to_string(),
+                                    explanation: "A reentrant call sees the balance already spent".to_string(),
+                                }
+                            ],
+                        }
+                    },
+                    crate::domain::analysis_models::VulnerabilityType::SecretExposure => {
+                        SecurityRecommendation {
+                            id: Uuid::new_v4(),
+                            category: RecommendationCategory::AccessControl,
+                            title: "Rotate Committed Secrets".to_string(),
+                            description: "Treat every committed key and token as compromised: rotate it, purge it from the git history and load secrets at runtime".to_string(),
+                            priority: Priority::Critical,
+                            code_examples: vec![
+                                CodeExample {
+                                    title: "Read the Key from the Environment".to_string(),
+                                    before: Some("const keypair = Ed25519Keypair.fromSecretKey(\"suiprivkey1...\");".to_string()),
+                                    after: "const keypair = Ed25519Keypair.fromSecretKey(process.env.SUI_PRIVATE_KEY!);".to_string(),
+                                    explanation: "The key stays out of the repository and can be rotated without a commit".to_string(),
+                                }
+                            ],
+                        }
+                    },
                     _ => continue,
                 };
                 recommendations.push(rec);
@@ -308,4 +282,179 @@ impl SuiMoveStaticAnalyzer {
 
         json!(breakdown)
     }
-}
\ No newline at end of file
+}
+
+pub struct SuiMoveStaticAnalyzer {
+    patterns: VulnerabilityPatterns,
+    version: String,
+}
+
+impl SuiMoveStaticAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            patterns: VulnerabilityPatterns::new(),
+            version: "1.0.0".to_string(),
+        }
+    }
+
+    fn is_valid_move_file(&self, content: &str) -> bool {
+        // Basic Move file validation
+        content.contains("module") || content.contains("script") || content.contains("use ")
+    }
+
+    fn check_move_specific_patterns(&self, file_path: &str, content: &str) -> Result<Vec<VulnerabilityFinding>> {
+        let mut findings = Vec::new();
+
+        // Check for missing module documentation
+        if !content.contains("///") && content.contains("module") {
+            findings.push(VulnerabilityFinding {
+                id: Uuid::new_v4(),
+                vulnerability_type: crate::domain::analysis_models::VulnerabilityType::Other("Documentation".to_string()),
+                severity: crate::domain::analysis_models::Severity::Low,
+                confidence_score: 90.0,
+                file_path: file_path.to_string(),
+                line_number: Some(1),
+                code_snippet: None,
+                description: "Module lacks proper documentation".to_string(),
+                recommendation: "Add module documentation using /// comments".to_string(),
+                cve_id: None,
+                cwe_id: Some("CWE-1059".to_string()),
+                cvss_vector: None,
+                cvss_score: None,
+                is_false_positive: false,
+                prompt_version: None,
+                model: None,
+                compilation_status: None,
+                kind: FindingKind::Code,
+                evidence: None,
+            });
+        }
+
+        // Check for friend declarations (potential access control issues)
+        if content.contains("friend") {
+            for (line_number, line) in content.lines().enumerate() {
+                if line.trim().starts_with("friend") {
+                    findings.push(VulnerabilityFinding {
+                        id: Uuid::new_v4(),
+                        vulnerability_type: crate::domain::analysis_models::VulnerabilityType::AccessControl,
+                        severity: crate::domain::analysis_models::Severity::Medium,
+                        confidence_score: 70.0,
+                        file_path: file_path.to_string(),
+                        line_number: Some((line_number + 1) as u32),
+                        code_snippet: Some(line.trim().to_string()),
+                        description: "Friend declaration may introduce unexpected access".to_string(),
+                        recommendation: "Review friend module access and ensure it's necessary".to_string(),
+                        cve_id: None,
+                        cwe_id: None,
+                        cvss_vector: None,
+                        cvss_score: None,
+                        is_false_positive: false,
+                        prompt_version: None,
+                        model: None,
+                        compilation_status: None,
+                        kind: FindingKind::Code,
+                        evidence: None,
+                    }
+                    .classify());
+                }
+            }
+        }
+
+        // Check for test functions in non-test modules
+        if !file_path.contains("test") && content.contains("#[test]") {
+            findings.push(VulnerabilityFinding {
+                id: Uuid::new_v4(),
+                vulnerability_type: crate::domain::analysis_models::VulnerabilityType::Other("Test in Production".to_string()),
+                severity: crate::domain::analysis_models::Severity::Medium,
+                confidence_score: 85.0,
+                file_path: file_path.to_string(),
+                line_number: None,
+                code_snippet: None,
+                description: "Test functions found in production module".to_string(),
+                recommendation: "Move test functions to separate test modules".to_string(),
+                cve_id: None,
+                cwe_id: Some("CWE-489".to_string()),
+                cvss_vector: None,
+                cvss_score: None,
+                is_false_positive: false,
+                prompt_version: None,
+                model: None,
+                compilation_status: None,
+                kind: FindingKind::Code,
+                evidence: None,
+            });
+        }
+
+        Ok(findings)
+    }
+
+    fn adjust_confidence_scores(&self, findings: &mut Vec<VulnerabilityFinding>, content: &str) {
+        for finding in findings {
+            // Increase confidence if similar patterns are found multiple times
+            let pattern_count = content.matches(&finding.description).count();
+            if pattern_count > 1 {
+                finding.confidence_score = (finding.confidence_score * 1.2).min(95.0);
+            }
+
+            // Adjust based on file type and context
+            if content.contains("entry fun") && finding.vulnerability_type == crate::domain::analysis_models::VulnerabilityType::AccessControl {
+                finding.confidence_score = (finding.confidence_score * 1.3).min(95.0);
+            }
+
+            // Lower confidence for files with extensive validation
+            if content.matches("assert!").count() > 5 {
+                finding.confidence_score *= 0.9;
+            }
+        }
+    }
+}
+
+impl StaticAnalyzer for SuiMoveStaticAnalyzer {
+    fn name(&self) -> &str {
+        "sui-move"
+    }
+
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[".move"]
+    }
+
+    fn analyze_file(&self, file_path: &str, content: &str) -> Result<Vec<VulnerabilityFinding>> {
+        self.analyze_file_with_ruleset(file_path, content, &Ruleset::default())
+    }
+
+    fn analyze_file_with_ruleset(
+        &self,
+        file_path: &str,
+        content: &str,
+        ruleset: &Ruleset,
+    ) -> Result<Vec<VulnerabilityFinding>> {
+        // Basic file validation
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Check if it's a valid Move file
+        if !self.is_valid_move_file(content) {
+            return Err(Error::FileParsingError {
+                file_path: file_path.to_string(),
+                message: "Invalid Move file format".to_string(),
+            });
+        }
+
+        // Apply the vulnerability patterns the repository has not disabled
+        let mut findings = self.patterns
+            .scan_code_matching(file_path, content, |pattern| !ruleset.is_disabled(&pattern.id))?;
+
+        // Apply additional Move-specific checks
+        findings.extend(self.check_move_specific_patterns(file_path, content)?);
+
+        // Apply confidence scoring based on context
+        self.adjust_confidence_scores(&mut findings, content);
+
+        Ok(findings)
+    }
+}
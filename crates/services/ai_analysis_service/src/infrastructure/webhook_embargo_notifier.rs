@@ -0,0 +1,69 @@
+use crate::domain::disclosure::VulnerabilityDisclosure;
+use crate::domain::embargo_notifier_trait::EmbargoNotifier;
+use crate::error::{Error, Result};
+use crate::infrastructure::webhook_leak_notifier::repository_owner;
+use async_trait::async_trait;
+use jd_core::AppState;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Posts warnings of lapsing embargoes to a webhook, e.g. a relay that
+/// emails the repository's maintainers before their findings go public.
+pub struct WebhookEmbargoNotifier {
+    url: String,
+    client: Client,
+    state: AppState,
+}
+
+impl WebhookEmbargoNotifier {
+    pub fn new(url: String, state: AppState) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { url, client, state }
+    }
+
+    /// The webhook at `EMBARGO_WEBHOOK_URL`, if set.
+    pub fn from_env(state: AppState) -> Option<Self> {
+        std::env::var("EMBARGO_WEBHOOK_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .map(|url| Self::new(url, state))
+    }
+}
+
+#[async_trait]
+impl EmbargoNotifier for WebhookEmbargoNotifier {
+    async fn notify_lapsing(&self, repository_id: Uuid, disclosures: &[VulnerabilityDisclosure]) -> Result<()> {
+        let (repository, owner, owner_email) = repository_owner(&self.state, repository_id).await?;
+        let vulnerabilities: Vec<_> = disclosures
+            .iter()
+            .map(|disclosure| {
+                json!({
+                    "vulnerability_id": disclosure.vulnerability_id,
+                    "embargo_until": disclosure.embargo_until,
+                })
+            })
+            .collect();
+        let payload = json!({
+            "event": "embargo_lapsing",
+            "repository_id": repository_id,
+            "repository": repository,
+            "owner": owner,
+            "owner_email": owner_email,
+            "vulnerabilities": vulnerabilities,
+        });
+
+        let response = self.client.post(&self.url).json(&payload).send().await?;
+        if !response.status().is_success() {
+            return Err(Error::ExternalServiceError {
+                service: "embargo webhook".to_string(),
+                message: format!("{} returned {}", self.url, response.status()),
+            });
+        }
+        Ok(())
+    }
+}
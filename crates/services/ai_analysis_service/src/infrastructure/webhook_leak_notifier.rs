@@ -0,0 +1,93 @@
+use crate::domain::analysis_models::VulnerabilityFinding;
+use crate::domain::leak_notifier_trait::LeakNotifier;
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use jd_core::AppState;
+use reqwest::Client;
+use serde_json::json;
+use sqlx::Row;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Posts leaked-secret alerts to a webhook, e.g. a relay that emails or
+/// messages the repository's owner. The payload names the repository, its
+/// owner and where each secret is; the secrets themselves are never sent.
+pub struct WebhookLeakNotifier {
+    url: String,
+    client: Client,
+    state: AppState,
+}
+
+impl WebhookLeakNotifier {
+    pub fn new(url: String, state: AppState) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { url, client, state }
+    }
+
+    /// The webhook at `SECRET_LEAK_WEBHOOK_URL`, if set.
+    pub fn from_env(state: AppState) -> Option<Self> {
+        std::env::var("SECRET_LEAK_WEBHOOK_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .map(|url| Self::new(url, state))
+    }
+}
+
+/// Full name of the repository, its owner's GitHub login and the owner's
+/// email if they are a registered developer.
+pub(crate) async fn repository_owner(state: &AppState, repository_id: Uuid) -> Result<(String, String, Option<String>)> {
+    let row = sqlx::query(
+        r#"
+        SELECT r.full_name, r.owner_username, d.email
+        FROM github_repositories r
+        LEFT JOIN developers d ON LOWER(d.github_username) = LOWER(r.owner_username)
+        WHERE r.id = $1
+        "#,
+    )
+    .bind(repository_id)
+    .fetch_optional(state.mm().dbx().db())
+    .await
+    .map_err(|e| Error::DatabaseError { message: e.to_string() })?
+    .ok_or(Error::RepositoryNotFound { repository_id })?;
+    Ok((row.get("full_name"), row.get("owner_username"), row.get("email")))
+}
+
+#[async_trait]
+impl LeakNotifier for WebhookLeakNotifier {
+    async fn notify(&self, repository_id: Uuid, commit_sha: &str, findings: &[VulnerabilityFinding]) -> Result<()> {
+        let (repository, owner, owner_email) = repository_owner(&self.state, repository_id).await?;
+        let secrets: Vec<_> = findings
+            .iter()
+            .map(|finding| {
+                json!({
+                    "file_path": finding.file_path,
+                    "line_number": finding.line_number,
+                    "severity": finding.severity.to_string(),
+                    "description": finding.description,
+                    "code_snippet": finding.code_snippet,
+                })
+            })
+            .collect();
+        let payload = json!({
+            "event": "secret_leak",
+            "repository_id": repository_id,
+            "repository": repository,
+            "owner": owner,
+            "owner_email": owner_email,
+            "commit_sha": commit_sha,
+            "secrets": secrets,
+        });
+
+        let response = self.client.post(&self.url).json(&payload).send().await?;
+        if !response.status().is_success() {
+            return Err(Error::ExternalServiceError {
+                service: "secret leak webhook".to_string(),
+                message: format!("{} returned {}", self.url, response.status()),
+            });
+        }
+        Ok(())
+    }
+}
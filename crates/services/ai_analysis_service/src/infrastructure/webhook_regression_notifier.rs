@@ -0,0 +1,103 @@
+use crate::domain::patch_regression::PatchRegression;
+use crate::domain::regression_notifier_trait::RegressionNotifier;
+use crate::error::{Error, Result};
+use crate::infrastructure::webhook_leak_notifier::repository_owner;
+use async_trait::async_trait;
+use jd_core::AppState;
+use reqwest::Client;
+use serde_json::json;
+use sqlx::Row;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Posts regressed patches to a webhook, e.g. a relay that emails the
+/// repository's owner and each patch's proposer and reviewers.
+pub struct WebhookRegressionNotifier {
+    url: String,
+    client: Client,
+    state: AppState,
+}
+
+impl WebhookRegressionNotifier {
+    pub fn new(url: String, state: AppState) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { url, client, state }
+    }
+
+    /// The webhook at `PATCH_REGRESSION_WEBHOOK_URL`, if set.
+    pub fn from_env(state: AppState) -> Option<Self> {
+        std::env::var("PATCH_REGRESSION_WEBHOOK_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .map(|url| Self::new(url, state))
+    }
+
+    /// Each patch's proposer's email, if known, and its assigned reviewers.
+    async fn subscribers(&self, patch_ids: &[Uuid]) -> Result<HashMap<Uuid, (Option<String>, Vec<String>)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT p.id, d.email,
+                   ARRAY(SELECT r.reviewer FROM patch_reviewers r WHERE r.patch_id = p.id ORDER BY r.reviewer)
+                       AS reviewers
+            FROM patch_proposals p
+            LEFT JOIN developers d ON d.id = p.proposed_by_developer_id
+            WHERE p.id = ANY($1)
+            "#,
+        )
+        .bind(patch_ids)
+        .fetch_all(self.state.mm().dbx().db())
+        .await
+        .map_err(|e| Error::DatabaseError { message: e.to_string() })?;
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("id"), (row.get("email"), row.get("reviewers"))))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl RegressionNotifier for WebhookRegressionNotifier {
+    async fn notify_regressed(&self, repository_id: Uuid, commit_sha: &str, regressions: &[PatchRegression]) -> Result<()> {
+        let (repository, owner, owner_email) = repository_owner(&self.state, repository_id).await?;
+        let patch_ids: Vec<Uuid> = regressions.iter().map(|regression| regression.patch_id).collect();
+        let mut subscribers = self.subscribers(&patch_ids).await?;
+        let patches: Vec<_> = regressions
+            .iter()
+            .map(|regression| {
+                let (proposer_email, reviewers) = subscribers.remove(&regression.patch_id).unwrap_or_default();
+                json!({
+                    "patch_id": regression.patch_id,
+                    "vulnerability_id": regression.vulnerability_id,
+                    "title": regression.title,
+                    "reason": regression.reason.as_str(),
+                    "merged_at": regression.merged_at,
+                    "proposer_email": proposer_email,
+                    "reviewers": reviewers,
+                })
+            })
+            .collect();
+        let payload = json!({
+            "event": "patch_regression",
+            "repository_id": repository_id,
+            "repository": repository,
+            "owner": owner,
+            "owner_email": owner_email,
+            "commit_sha": commit_sha,
+            "analysis_id": regressions.first().map(|regression| regression.analysis_id),
+            "patches": patches,
+        });
+
+        let response = self.client.post(&self.url).json(&payload).send().await?;
+        if !response.status().is_success() {
+            return Err(Error::ExternalServiceError {
+                service: "patch regression webhook".to_string(),
+                message: format!("{} returned {}", self.url, response.status()),
+            });
+        }
+        Ok(())
+    }
+}
@@ -10,6 +10,18 @@ pub use error::{Error, Result};
 pub use application::handlers::analysis_handler::AnalysisHandler;
 pub use application::use_cases::analysis_use_cases::AnalysisUseCases;
 pub use domain::analysis_engine::AnalysisEngine;
+pub use domain::cvss::CvssVector;
 pub use domain::vulnerability_patterns::VulnerabilityPatterns;
-pub use infrastructure::static_analyzer::SuiMoveStaticAnalyzer;
-pub use infrastructure::llm_client::LLMClient;
\ No newline at end of file
+pub use domain::static_analyzer_trait::StaticAnalyzer;
+pub use infrastructure::advisory_feed::AdvisoryFeed;
+pub use infrastructure::anchor_analyzer::AnchorStaticAnalyzer;
+pub use infrastructure::solidity_analyzer::SolidityStaticAnalyzer;
+pub use infrastructure::static_analyzer::{MultiLanguageStaticAnalyzer, SuiMoveStaticAnalyzer};
+pub use infrastructure::llm_client::LLMClient;
+pub use infrastructure::llm_response_cache::LlmResponseCache;
+pub use infrastructure::move_bytecode_analyzer::{MoveBytecodeAnalyzer, MoveCompilerSettings};
+pub use infrastructure::llm_providers::{AnthropicProvider, GoogleProvider, OllamaProvider, OpenAiProvider};
+pub use infrastructure::webhook_embargo_notifier::WebhookEmbargoNotifier;
+pub use infrastructure::webhook_leak_notifier::WebhookLeakNotifier;
+pub use infrastructure::webhook_regression_notifier::WebhookRegressionNotifier;
+pub use models::sarif::SarifLog;
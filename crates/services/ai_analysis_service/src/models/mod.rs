@@ -1,2 +1,3 @@
 pub mod requests;
-pub mod responses;
\ No newline at end of file
+pub mod responses;
+pub mod sarif;
\ No newline at end of file
@@ -0,0 +1,355 @@
+use crate::domain::analysis_models::{AnalysisResult, Severity, VulnerabilityFinding};
+use crate::domain::finding_fingerprint::fingerprints;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const SARIF_VERSION: &str = "2.1.0";
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+pub const SARIF_CONTENT_TYPE: &str = "application/sarif+json";
+
+const TOOL_NAME: &str = "commandoss-analyzer";
+/// Key of our fingerprint in `partialFingerprints`, so Code Scanning tracks
+/// alerts across runs the same way the findings database does.
+const FINGERPRINT_KEY: &str = "commandossFingerprint/v1";
+
+/// A SARIF 2.1.0 log, the format GitHub Code Scanning and IDE SARIF viewers
+/// read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub version_control_provenance: Vec<SarifVersionControl>,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: String,
+    pub version: String,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub name: String,
+    pub short_description: SarifMessage,
+    pub help: SarifMessage,
+    pub default_configuration: SarifConfiguration,
+    pub properties: SarifRuleProperties,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifConfiguration {
+    pub level: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifRuleProperties {
+    pub tags: Vec<String>,
+    /// GitHub ranks alerts by this CVSS-like score, sent as a string.
+    #[serde(rename = "security-severity")]
+    pub security_severity: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifVersionControl {
+    pub repository_uri: String,
+    pub revision_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: String,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+    pub partial_fingerprints: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<SarifSuppression>,
+    pub properties: SarifResultProperties,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+    pub uri_base_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<SarifMessage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifSuppression {
+    pub kind: String,
+    pub justification: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResultProperties {
+    pub finding_id: String,
+    pub severity: String,
+    pub confidence_score: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cve_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvss_vector: Option<String>,
+}
+
+impl SarifLog {
+    /// One run holding the findings of `analysis`. `repository_uri` is the
+    /// repository's clone URL, tying the results to the analysed commit.
+    pub fn from_analysis(analysis: &AnalysisResult, repository_uri: Option<&str>) -> Self {
+        let mut run = SarifRun::new(&analysis.analyzer_version, &analysis.vulnerabilities);
+        if let Some(repository_uri) = repository_uri {
+            run.version_control_provenance.push(SarifVersionControl {
+                repository_uri: repository_uri.to_string(),
+                revision_id: analysis.commit_sha.clone(),
+            });
+        }
+        Self::with_runs(vec![run])
+    }
+
+    pub fn from_findings(tool_version: &str, findings: &[VulnerabilityFinding]) -> Self {
+        Self::with_runs(vec![SarifRun::new(tool_version, findings)])
+    }
+
+    fn with_runs(runs: Vec<SarifRun>) -> Self {
+        Self {
+            schema: SARIF_SCHEMA.to_string(),
+            version: SARIF_VERSION.to_string(),
+            runs,
+        }
+    }
+}
+
+impl SarifRun {
+    fn new(tool_version: &str, findings: &[VulnerabilityFinding]) -> Self {
+        let mut rules: Vec<SarifRule> = Vec::new();
+        let mut rule_indexes: HashMap<String, usize> = HashMap::new();
+        let mut results = Vec::with_capacity(findings.len());
+
+        for (finding, fingerprint) in findings.iter().zip(fingerprints(findings)) {
+            let rule_id = finding.vulnerability_type.rule_id();
+            let rule_index = *rule_indexes.entry(rule_id.clone()).or_insert_with(|| {
+                rules.push(SarifRule::for_finding(&rule_id, finding));
+                rules.len() - 1
+            });
+            // Rules take the most severe level among their findings.
+            let rule = &mut rules[rule_index];
+            let score = security_severity(finding);
+            let current: f64 = rule.properties.security_severity.parse().unwrap_or_default();
+            if score > current {
+                rule.default_configuration.level = level(&finding.severity).to_string();
+                rule.properties.security_severity = format!("{:.1}", score);
+            }
+            results.push(SarifResult::for_finding(rule_id, rule_index, fingerprint, finding));
+        }
+
+        Self {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: TOOL_NAME.to_string(),
+                    version: tool_version.to_string(),
+                    rules,
+                },
+            },
+            version_control_provenance: Vec::new(),
+            results,
+        }
+    }
+}
+
+impl SarifRule {
+    fn for_finding(id: &str, finding: &VulnerabilityFinding) -> Self {
+        let name = finding.vulnerability_type.to_string();
+        let mut tags = vec!["security".to_string()];
+        if let Some(cwe_id) = &finding.cwe_id {
+            tags.push(format!("external/cwe/{}", cwe_id.to_lowercase()));
+        }
+        Self {
+            id: id.to_string(),
+            short_description: SarifMessage { text: name.clone() },
+            help: SarifMessage { text: finding.recommendation.clone() },
+            default_configuration: SarifConfiguration {
+                level: level(&finding.severity).to_string(),
+            },
+            properties: SarifRuleProperties {
+                tags,
+                security_severity: format!("{:.1}", security_severity(finding)),
+            },
+            name,
+        }
+    }
+}
+
+impl SarifResult {
+    fn for_finding(
+        rule_id: String,
+        rule_index: usize,
+        fingerprint: String,
+        finding: &VulnerabilityFinding,
+    ) -> Self {
+        let suppressions = if finding.is_false_positive {
+            vec![SarifSuppression {
+                kind: "external".to_string(),
+                justification: "Marked as a false positive".to_string(),
+            }]
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            rule_id,
+            rule_index,
+            level: level(&finding.severity).to_string(),
+            message: SarifMessage { text: finding.description.clone() },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: finding.file_path.trim_start_matches("./").to_string(),
+                        uri_base_id: "%SRCROOT%".to_string(),
+                    },
+                    // Code Scanning requires a region; file-level findings
+                    // point at the first line.
+                    region: SarifRegion {
+                        start_line: finding.line_number.unwrap_or(1).max(1),
+                        snippet: finding
+                            .code_snippet
+                            .as_ref()
+                            .map(|snippet| SarifMessage { text: snippet.clone() }),
+                    },
+                },
+            }],
+            partial_fingerprints: BTreeMap::from([(FINGERPRINT_KEY.to_string(), fingerprint)]),
+            suppressions,
+            properties: SarifResultProperties {
+                finding_id: finding.id.to_string(),
+                severity: finding.severity.to_string(),
+                confidence_score: finding.confidence_score,
+                cve_id: finding.cve_id.clone(),
+                cvss_vector: finding.cvss_vector.clone(),
+            },
+        }
+    }
+}
+
+fn level(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+/// The finding's CVSS score, or for unscored findings a score inside the band
+/// GitHub maps to the finding's severity.
+fn security_severity(finding: &VulnerabilityFinding) -> f64 {
+    finding.cvss_score.unwrap_or(match finding.severity {
+        Severity::Critical => 9.5,
+        Severity::High => 8.0,
+        Severity::Medium => 5.5,
+        Severity::Low => 2.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::analysis_models::{FindingKind, VulnerabilityType};
+    use uuid::Uuid;
+
+    fn finding(vulnerability_type: VulnerabilityType, severity: Severity) -> VulnerabilityFinding {
+        VulnerabilityFinding {
+            id: Uuid::new_v4(),
+            vulnerability_type,
+            severity,
+            confidence_score: 80.0,
+            file_path: "./sources/vault.move".to_string(),
+            line_number: None,
+            code_snippet: None,
+            description: "Vault can be drained".to_string(),
+            recommendation: "Check the capability".to_string(),
+            cve_id: None,
+            cwe_id: None,
+            cvss_vector: None,
+            cvss_score: None,
+            is_false_positive: false,
+            prompt_version: None,
+            model: None,
+            compilation_status: None,
+            kind: FindingKind::Code,
+            evidence: None,
+        }
+    }
+
+    #[test]
+    fn groups_findings_into_rules() {
+        let mut false_positive = finding(VulnerabilityType::ReentrancyLike, Severity::Low);
+        false_positive.is_false_positive = true;
+        let findings = [
+            finding(VulnerabilityType::UnauthorizedAccess, Severity::Medium),
+            false_positive,
+            finding(VulnerabilityType::UnauthorizedAccess, Severity::Critical),
+        ];
+
+        let log = serde_json::to_value(SarifLog::from_findings("1.0.0", &findings)).unwrap();
+        let run = &log["runs"][0];
+
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "unauthorized-access");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["defaultConfiguration"]["level"], "error");
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "reentrancy-like");
+        assert_eq!(run["results"][2]["ruleIndex"], 0);
+        assert_eq!(run["results"][1]["suppressions"][0]["kind"], "external");
+
+        let location = &run["results"][0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "sources/vault.move");
+        assert_eq!(location["region"]["startLine"], 1);
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::domain::{
+    analysis_failure_spike, critical_findings_spike, scoring_throughput_drop, AlertNotifier, AlertStatus,
+    AnalyticsAlert, Anomaly, AnomalyMetric, AnomalyRepository, ANALYSIS_BASELINE, ANALYSIS_WINDOW,
+    CRITICAL_FINDINGS_BASELINE, CRITICAL_FINDINGS_WINDOW, SCORING_BASELINE, SCORING_STRIDE, SCORING_WINDOW,
+};
+use crate::{Error, Result};
+
+/// An open alert is notified again at most this often, and an alert
+/// reopened this soon after one was notified is not notified at all.
+const NOTIFY_COOLDOWN: Duration = Duration::from_secs(6 * 60 * 60);
+const MAX_ALERTS_PAGE: i64 = 200;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnomalyDetectionRun {
+    pub anomalies: usize,
+    pub notified: usize,
+    pub resolved: u64,
+}
+
+/// Watches key metrics against baselines of their recent past: new critical
+/// findings per repository, the analysis failure rate and scoring
+/// throughput. Anomalies are kept as alerts, one open per metric and
+/// subject, and pushed to the notifier outside their cooldown.
+pub struct AnomalyUseCases {
+    repository: Arc<dyn AnomalyRepository>,
+    notifier: Option<Arc<dyn AlertNotifier>>,
+}
+
+impl AnomalyUseCases {
+    pub fn new(repository: Arc<dyn AnomalyRepository>) -> Self {
+        Self { repository, notifier: None }
+    }
+
+    pub fn with_notifier(mut self, notifier: Arc<dyn AlertNotifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Check every metric, raise or update alerts for the anomalies found
+    /// and resolve the alerts whose anomaly has cleared.
+    pub async fn detect(&self) -> Result<AnomalyDetectionRun> {
+        let now = Utc::now();
+        let anomalies = self.anomalies(now).await?;
+        let mut run = AnomalyDetectionRun { anomalies: anomalies.len(), ..AnomalyDetectionRun::default() };
+
+        for metric in AnomalyMetric::ALL {
+            let still_anomalous: Vec<String> = anomalies
+                .iter()
+                .filter(|anomaly| anomaly.metric == metric)
+                .map(|anomaly| anomaly.subject.clone())
+                .collect();
+            run.resolved += self.repository.resolve_cleared(metric, &still_anomalous, now).await?;
+        }
+        for anomaly in &anomalies {
+            let alert = self.repository.raise(anomaly, now).await?;
+            if self.notify(&alert, now).await? {
+                run.notified += 1;
+            }
+        }
+        if run.anomalies > 0 || run.resolved > 0 {
+            info!(
+                anomalies = run.anomalies,
+                notified = run.notified,
+                resolved = run.resolved,
+                "Analytics anomalies checked"
+            );
+        }
+        Ok(run)
+    }
+
+    /// The latest alerts, newest first.
+    pub async fn alerts(&self, status: Option<AlertStatus>, limit: i64) -> Result<Vec<AnalyticsAlert>> {
+        if !(1..=MAX_ALERTS_PAGE).contains(&limit) {
+            return Err(Error::InvalidFilter(format!("limit must be between 1 and {}", MAX_ALERTS_PAGE)));
+        }
+        self.repository.list(status, limit).await
+    }
+
+    async fn anomalies(&self, now: DateTime<Utc>) -> Result<Vec<Anomaly>> {
+        let findings = self
+            .repository
+            .critical_findings(now, CRITICAL_FINDINGS_WINDOW, CRITICAL_FINDINGS_BASELINE)
+            .await?;
+        let mut anomalies: Vec<Anomaly> = findings
+            .iter()
+            .filter_map(|(repository_id, counts)| critical_findings_spike(*repository_id, counts))
+            .collect();
+
+        let analyses = self
+            .repository
+            .analysis_windows(now, ANALYSIS_WINDOW, ANALYSIS_WINDOW, ANALYSIS_BASELINE)
+            .await?;
+        anomalies.extend(analysis_failure_spike(&analyses));
+
+        let scores = self
+            .repository
+            .scoring_counts(now, SCORING_WINDOW, SCORING_STRIDE, SCORING_BASELINE)
+            .await?;
+        anomalies.extend(scoring_throughput_drop(&scores));
+        Ok(anomalies)
+    }
+
+    /// Notify `alert` unless its metric and subject were notified within
+    /// the cooldown. Returns whether it was sent. A failure is recorded on
+    /// the alert and retried on the next run.
+    async fn notify(&self, alert: &AnalyticsAlert, now: DateTime<Utc>) -> Result<bool> {
+        let Some(notifier) = &self.notifier else {
+            return Ok(false);
+        };
+        let last_notified_at = self.repository.last_notified_at(alert.metric, &alert.subject).await?;
+        let cooling_down = last_notified_at.is_some_and(|at| (now - at).to_std().unwrap_or_default() < NOTIFY_COOLDOWN);
+        if cooling_down {
+            return Ok(false);
+        }
+        match notifier.notify(alert).await {
+            Ok(()) => {
+                self.repository.record_notification(alert.id, Some(now), None).await?;
+                Ok(true)
+            }
+            Err(e) => {
+                warn!(alert_id = %alert.id, metric = alert.metric.as_str(), error = %e, "Analytics alert not notified");
+                self.repository.record_notification(alert.id, None, Some(&e.to_string())).await?;
+                Ok(false)
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use jd_object_store::ObjectStore;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::{AnalyticsExport, ExportDownload, ExportFile, ExportQuery, ExportRepository, ExportStatus};
+use crate::{Error, Result};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Time a worker has to prepare an export before another may take it over.
+const LEASE: Duration = Duration::from_secs(30 * 60);
+const MAX_ATTEMPTS: i32 = 3;
+/// How long a prepared file can be downloaded.
+const DOWNLOAD_TTL_HOURS: i64 = 24;
+/// Largest file prepared in the background without object storage; larger
+/// ranges must be split.
+const MAX_PREPARED_BYTES: usize = 256 * 1024 * 1024;
+/// Prepared files are kept under this prefix in object storage, where a
+/// lifecycle rule deletes them after their download window.
+pub const EXPORT_OBJECT_PREFIX: &str = "analytics-exports/";
+/// How long a presigned download link works.
+const DOWNLOAD_LINK_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Exports of analytics datasets as CSV or NDJSON, either streamed as the
+/// rows are read or prepared in the background and downloaded later.
+pub struct ExportUseCases {
+    repository: Arc<dyn ExportRepository>,
+    objects: Option<Arc<dyn ObjectStore>>,
+}
+
+impl ExportUseCases {
+    pub fn new(repository: Arc<dyn ExportRepository>) -> Self {
+        Self { repository, objects: None }
+    }
+
+    /// Stream prepared files to `objects` rather than keep them in Postgres,
+    /// which also lifts the size limit.
+    pub fn with_objects(mut self, objects: Option<Arc<dyn ObjectStore>>) -> Self {
+        self.objects = objects;
+        self
+    }
+
+    /// The export `query` selects, encoded line by line as the records are
+    /// read. A database error ends the stream early.
+    pub fn stream(&self, query: &ExportQuery) -> Result<impl Stream<Item = Result<String>> + Send + 'static> {
+        validate(query)?;
+        let (dataset, format) = (query.dataset, query.format);
+        let header = stream::iter(format.header(dataset).map(Ok));
+        let records = self
+            .repository
+            .records(query)
+            .map_ok(move |record| format.encode(dataset, &record));
+        Ok(header.chain(records))
+    }
+
+    /// Queue `query` to be prepared in the background for `requested_by`.
+    pub async fn prepare(&self, requested_by: &str, query: &ExportQuery) -> Result<AnalyticsExport> {
+        validate(query)?;
+        let export = self.repository.create(requested_by, query).await?;
+        info!(export_id = %export.id, dataset = query.dataset.as_str(), "Analytics export queued");
+        Ok(export)
+    }
+
+    pub async fn status(&self, id: Uuid, requested_by: &str) -> Result<AnalyticsExport> {
+        self.repository
+            .find(id, requested_by)
+            .await?
+            .ok_or_else(|| Error::ExportNotFound(id.to_string()))
+    }
+
+    /// A prepared export and its file, or a link to download the file from
+    /// object storage.
+    pub async fn download(&self, id: Uuid, requested_by: &str) -> Result<(AnalyticsExport, ExportDownload)> {
+        let export = self.status(id, requested_by).await?;
+        if export.status != ExportStatus::Ready {
+            return Err(Error::ExportNotReady(id.to_string()));
+        }
+        // Purged between the two reads
+        let file = self
+            .repository
+            .file(id, requested_by)
+            .await?
+            .ok_or_else(|| Error::ExportNotFound(id.to_string()))?;
+        let download = match file {
+            ExportFile::Inline(content) => ExportDownload::Content(content),
+            ExportFile::Stored { uri, .. } => {
+                let objects = self
+                    .objects
+                    .as_ref()
+                    .ok_or_else(|| Error::StorageError("Object storage is not configured".to_string()))?;
+                ExportDownload::Redirect(objects.presigned_get_url(&objects.key(&uri)?, DOWNLOAD_LINK_TTL)?)
+            }
+        };
+        Ok((export, download))
+    }
+
+    /// Prepare the next queued export, if any. Returns whether one was
+    /// claimed.
+    pub async fn run_next(&self) -> Result<bool> {
+        let Some(export) = self.repository.claim_next(LEASE, MAX_ATTEMPTS).await? else {
+            return Ok(false);
+        };
+        let built = match &self.objects {
+            Some(objects) => self.build_stored(&export, objects.as_ref()).await,
+            None => self.build(&export).await,
+        };
+        match built {
+            Ok((row_count, file)) => {
+                let bytes = match &file {
+                    ExportFile::Inline(content) => content.len() as i64,
+                    ExportFile::Stored { byte_size, .. } => *byte_size,
+                };
+                let expires_at = Utc::now() + chrono::Duration::hours(DOWNLOAD_TTL_HOURS);
+                self.repository.complete(export.id, row_count, file, expires_at).await?;
+                info!(export_id = %export.id, rows = row_count, bytes, "Analytics export prepared");
+            }
+            // Retrying will not make it smaller
+            Err(e @ Error::InvalidFilter(_)) => {
+                warn!(export_id = %export.id, error = %e, "Analytics export failed");
+                self.repository.fail(export.id, &e.to_string()).await?;
+            }
+            Err(e) if export.attempts >= MAX_ATTEMPTS => {
+                warn!(export_id = %export.id, error = %e, "Analytics export failed after its last attempt");
+                self.repository.fail(export.id, &e.to_string()).await?;
+            }
+            // Picked up again once the lease runs out
+            Err(e) => {
+                warn!(export_id = %export.id, attempt = export.attempts, error = %e, "Analytics export interrupted");
+            }
+        }
+        Ok(true)
+    }
+
+    /// Prepare queued exports until the process exits, polling while idle.
+    pub async fn run(self) {
+        info!("Analytics export worker started");
+        loop {
+            match self.run_next().await {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => warn!(error = %e, "Analytics export worker run failed"),
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Delete expired exports and old failed ones. Files in object storage
+    /// are left to its lifecycle rule for `EXPORT_OBJECT_PREFIX`.
+    pub async fn purge_expired(&self) -> Result<u64> {
+        self.repository.purge_expired().await
+    }
+
+    async fn build(&self, export: &AnalyticsExport) -> Result<(i64, ExportFile)> {
+        let query = export.query();
+        let mut content = query.format.header(query.dataset).unwrap_or_default().into_bytes();
+        let mut row_count = 0;
+        let mut records = self.repository.records(&query);
+        while let Some(record) = records.next().await {
+            content.extend_from_slice(query.format.encode(query.dataset, &record?).as_bytes());
+            row_count += 1;
+            if content.len() > MAX_PREPARED_BYTES {
+                return Err(Error::InvalidFilter(format!(
+                    "Export exceeds {} MiB; split the range",
+                    MAX_PREPARED_BYTES / (1024 * 1024)
+                )));
+            }
+        }
+        Ok((row_count, ExportFile::Inline(content)))
+    }
+
+    /// Stream the file to object storage as the records are read.
+    async fn build_stored(&self, export: &AnalyticsExport, objects: &dyn ObjectStore) -> Result<(i64, ExportFile)> {
+        let query = export.query();
+        let (dataset, format) = (query.dataset, query.format);
+        let row_count = Arc::new(AtomicI64::new(0));
+        let counted = row_count.clone();
+        let header = stream::iter(format.header(dataset).map(|header| Ok(Bytes::from(header))));
+        let records = self.repository.records(&query).map(move |record| match record {
+            Ok(record) => {
+                counted.fetch_add(1, Ordering::Relaxed);
+                Ok(Bytes::from(format.encode(dataset, &record)))
+            }
+            Err(e) => Err(jd_object_store::Error::Io(std::io::Error::other(e.to_string()))),
+        });
+
+        let key = format!("{}{}.{}", EXPORT_OBJECT_PREFIX, export.id, format.extension());
+        let byte_size = objects
+            .put_stream(&key, Box::pin(header.chain(records)), format.content_type())
+            .await?;
+        let file = ExportFile::Stored { uri: objects.uri(&key), byte_size: byte_size as i64 };
+        Ok((row_count.load(Ordering::Relaxed), file))
+    }
+}
+
+fn validate(query: &ExportQuery) -> Result<()> {
+    match (query.from, query.to) {
+        (Some(from), Some(to)) if from >= to => Err(Error::InvalidTimeRange),
+        _ => Ok(()),
+    }
+}
@@ -1,3 +1,13 @@
 pub mod analytics_use_cases;
+pub mod anomaly_use_cases;
+pub mod export_use_cases;
+pub mod report_use_cases;
+pub mod rollup_use_cases;
+pub mod scorecard_use_cases;
 
-pub use analytics_use_cases::AnalyticsUseCases;
\ No newline at end of file
+pub use analytics_use_cases::AnalyticsUseCases;
+pub use anomaly_use_cases::{AnomalyDetectionRun, AnomalyUseCases};
+pub use export_use_cases::ExportUseCases;
+pub use report_use_cases::ReportUseCases;
+pub use rollup_use_cases::RollupUseCases;
+pub use scorecard_use_cases::ScorecardUseCases;
\ No newline at end of file
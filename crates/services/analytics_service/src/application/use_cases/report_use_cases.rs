@@ -0,0 +1,207 @@
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::{
+    generate_webhook_secret, next_run_after, parse_schedule, DeliveryChannel, DeliveryStatus, ReportDelivery,
+    ReportRepository, ReportRun, ReportTrigger, SavedQuery, SavedQueryDefinition,
+};
+use crate::models::{SavedQueryRequest, SavedQueryResponse};
+use crate::{Error, Result};
+
+/// Time a worker has to run a due query before another may take it over.
+const LEASE: Duration = Duration::from_secs(10 * 60);
+const MAX_NAME_LENGTH: usize = 200;
+pub const MAX_RUNS_PAGE: i64 = 100;
+
+/// Saved analytics queries, run on demand or on a cron schedule. Every run
+/// is kept with the rows it returned and, if the query has a delivery
+/// channel, sent by email or signed webhook.
+pub struct ReportUseCases {
+    repository: Arc<dyn ReportRepository>,
+    delivery: Arc<dyn ReportDelivery>,
+}
+
+impl ReportUseCases {
+    pub fn new(repository: Arc<dyn ReportRepository>, delivery: Arc<dyn ReportDelivery>) -> Self {
+        Self { repository, delivery }
+    }
+
+    /// Save a query for `owner`. Vulnerabilities not yet disclosed are only
+    /// reported if `include_undisclosed`, however the query is run later.
+    pub async fn create(
+        &self,
+        owner: &str,
+        request: SavedQueryRequest,
+        include_undisclosed: bool,
+    ) -> Result<SavedQueryResponse> {
+        let definition = definition(request, include_undisclosed)?;
+        let next_run_at = match &definition.schedule {
+            Some(schedule) => Some(next_run_after(schedule, Utc::now())?),
+            None => None,
+        };
+        let webhook_secret = (definition.delivery_channel == DeliveryChannel::Webhook).then(generate_webhook_secret);
+        let query = self
+            .repository
+            .create(owner, &definition, next_run_at, webhook_secret.as_deref())
+            .await?;
+        info!(query_id = %query.id, dataset = query.dataset.as_str(), "Analytics query saved");
+        Ok(SavedQueryResponse { query, webhook_secret })
+    }
+
+    /// Replace the definition of a saved query. A secret is generated the
+    /// first time it delivers by webhook; an existing one is kept.
+    pub async fn update(
+        &self,
+        id: Uuid,
+        owner: &str,
+        request: SavedQueryRequest,
+        include_undisclosed: bool,
+    ) -> Result<SavedQueryResponse> {
+        let existing = self.get(id, owner).await?;
+        let definition = definition(request, include_undisclosed)?;
+        let next_run_at = match &definition.schedule {
+            Some(schedule) if existing.schedule.as_ref() == Some(schedule) => existing.next_run_at,
+            Some(schedule) => Some(next_run_after(schedule, Utc::now())?),
+            None => None,
+        };
+        let webhook_secret = (definition.delivery_channel == DeliveryChannel::Webhook
+            && existing.webhook_secret.is_none())
+        .then(generate_webhook_secret);
+        let query = self
+            .repository
+            .update(id, owner, &definition, next_run_at, webhook_secret.as_deref())
+            .await?
+            .ok_or_else(|| Error::SavedQueryNotFound(id.to_string()))?;
+        Ok(SavedQueryResponse { query, webhook_secret })
+    }
+
+    pub async fn delete(&self, id: Uuid, owner: &str) -> Result<()> {
+        if !self.repository.delete(id, owner).await? {
+            return Err(Error::SavedQueryNotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    pub async fn list(&self, owner: &str) -> Result<Vec<SavedQuery>> {
+        self.repository.list(owner).await
+    }
+
+    pub async fn get(&self, id: Uuid, owner: &str) -> Result<SavedQuery> {
+        self.repository
+            .find(id, owner)
+            .await?
+            .ok_or_else(|| Error::SavedQueryNotFound(id.to_string()))
+    }
+
+    /// Run a saved query now, outside its schedule.
+    pub async fn run_now(&self, id: Uuid, owner: &str) -> Result<ReportRun> {
+        let query = self.get(id, owner).await?;
+        let run = self.execute(&query, ReportTrigger::Manual).await?;
+        self.repository.mark_ran(query.id, run.started_at, None).await?;
+        Ok(run)
+    }
+
+    /// The latest runs of a saved query, newest first and without their
+    /// output.
+    pub async fn runs(&self, id: Uuid, owner: &str, limit: i64) -> Result<Vec<ReportRun>> {
+        if !(1..=MAX_RUNS_PAGE).contains(&limit) {
+            return Err(Error::InvalidFilter(format!("limit must be between 1 and {}", MAX_RUNS_PAGE)));
+        }
+        self.get(id, owner).await?;
+        self.repository.runs(id, owner, limit).await
+    }
+
+    /// A past run with the rows it returned.
+    pub async fn run_output(&self, run_id: Uuid, owner: &str) -> Result<ReportRun> {
+        self.repository
+            .run(run_id, owner)
+            .await?
+            .ok_or_else(|| Error::ReportRunNotFound(run_id.to_string()))
+    }
+
+    /// Run up to `limit` queries whose schedule is due and move each to its
+    /// next run. Returns how many ran.
+    pub async fn run_due(&self, limit: i64) -> Result<usize> {
+        let due = self.repository.claim_due(limit, LEASE).await?;
+        for query in &due {
+            let ran_at = Utc::now();
+            if let Err(e) = self.execute(query, ReportTrigger::Schedule).await {
+                warn!(query_id = %query.id, error = %e, "Scheduled report could not be run");
+            }
+            let next_run_at = match query.schedule.as_deref().map(|schedule| next_run_after(schedule, ran_at)) {
+                Some(Ok(next_run_at)) => Some(next_run_at),
+                // Left leased, to be retried once the lease runs out
+                Some(Err(e)) => {
+                    warn!(query_id = %query.id, error = %e, "Scheduled report has no next run");
+                    None
+                }
+                None => None,
+            };
+            self.repository.mark_ran(query.id, ran_at, next_run_at).await?;
+        }
+        Ok(due.len())
+    }
+
+    /// Run `query`, record the outcome and deliver the rows. A failed
+    /// delivery is recorded on the run rather than returned.
+    async fn execute(&self, query: &SavedQuery, trigger: ReportTrigger) -> Result<ReportRun> {
+        let (from, to) = query.filters.range(Utc::now());
+        let run = self.repository.start_run(query.id, trigger, from, to).await?;
+        let fetched = self
+            .repository
+            .fetch_rows(query.dataset, &query.filters, query.include_undisclosed, from, to)
+            .await;
+        match fetched {
+            Ok(rows) => {
+                self.repository.complete_run(run.id, &rows).await?;
+                let (status, error) = match query.delivery_channel {
+                    DeliveryChannel::None => (DeliveryStatus::Skipped, None),
+                    _ => match self.delivery.deliver(query, &run, &rows).await {
+                        Ok(()) => (DeliveryStatus::Delivered, None),
+                        Err(e) => {
+                            warn!(query_id = %query.id, run_id = %run.id, error = %e, "Report delivery failed");
+                            (DeliveryStatus::Failed, Some(e.to_string()))
+                        }
+                    },
+                };
+                self.repository.record_delivery(run.id, status, error.as_deref()).await?;
+                info!(query_id = %query.id, run_id = %run.id, rows = rows.len(), "Report run finished");
+            }
+            Err(e) => {
+                warn!(query_id = %query.id, run_id = %run.id, error = %e, "Report run failed");
+                self.repository.fail_run(run.id, &e.to_string()).await?;
+            }
+        }
+        self.run_output(run.id, &query.owner).await
+    }
+}
+
+fn definition(request: SavedQueryRequest, include_undisclosed: bool) -> Result<SavedQueryDefinition> {
+    let name = request.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(Error::InvalidFilter(format!("name must be 1 to {} characters", MAX_NAME_LENGTH)));
+    }
+    request.filters.validate(request.dataset)?;
+    let schedule = request
+        .schedule
+        .map(|schedule| schedule.trim().to_string())
+        .filter(|schedule| !schedule.is_empty());
+    if let Some(schedule) = &schedule {
+        parse_schedule(schedule)?;
+    }
+    let delivery_channel = request.delivery_channel.unwrap_or(DeliveryChannel::None);
+    let delivery_target = delivery_channel.validate_target(request.delivery_target.as_deref())?;
+    Ok(SavedQueryDefinition {
+        name: name.to_string(),
+        dataset: request.dataset,
+        filters: request.filters,
+        include_undisclosed,
+        schedule,
+        delivery_channel,
+        delivery_target,
+        enabled: request.enabled.unwrap_or(true),
+    })
+}
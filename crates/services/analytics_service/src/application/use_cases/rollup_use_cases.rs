@@ -0,0 +1,99 @@
+use chrono::{Duration, NaiveDate, Utc};
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{rollup_points, RollupGranularity, RollupRepository};
+use crate::models::{RollupSeriesResponse, RollupSource};
+use crate::{Error, Result};
+
+/// Periods a single series may span.
+const MAX_PERIODS: usize = 400;
+/// How far back the refresh job backfills periods never rolled up.
+const BACKFILL_DAYS: i64 = 90;
+
+/// Outcome of one refresh.
+#[derive(Debug, Default)]
+pub struct RollupRefreshRun {
+    pub periods: usize,
+    pub rows: u64,
+}
+
+/// Daily and weekly activity aggregates. A scheduled refresh keeps recent
+/// periods rolled up; reads use the rollups when every period asked for has
+/// been rolled up and aggregate the source tables otherwise.
+pub struct RollupUseCases {
+    repository: Arc<dyn RollupRepository>,
+}
+
+impl RollupUseCases {
+    pub fn new(repository: Arc<dyn RollupRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Roll up the current and previous period of each granularity, which
+    /// may still be changing, and any period of the backfill window not
+    /// rolled up yet.
+    pub async fn refresh(&self) -> Result<RollupRefreshRun> {
+        let today = Utc::now().date_naive();
+        let mut run = RollupRefreshRun::default();
+        for granularity in [RollupGranularity::Day, RollupGranularity::Week] {
+            let current = granularity.period_start(today);
+            let end = granularity.next(current);
+            let recent = granularity.period_start(current - Duration::days(1));
+
+            let window_start = granularity.period_start(today - Duration::days(BACKFILL_DAYS));
+            let rolled_up: HashSet<NaiveDate> = self
+                .repository
+                .rolled_up_periods(granularity, window_start, recent)
+                .await?
+                .into_iter()
+                .collect();
+            let from = granularity
+                .periods(window_start, recent - Duration::days(1))
+                .into_iter()
+                .find(|period| !rolled_up.contains(period))
+                .unwrap_or(recent);
+
+            run.rows += self.repository.refresh(granularity, from, end).await?;
+            run.periods += granularity.periods(from, current).len();
+        }
+        Ok(run)
+    }
+
+    /// Activity per period from `from` to `to`, both inclusive, for one
+    /// repository or all of them.
+    pub async fn series(
+        &self,
+        granularity: RollupGranularity,
+        from: NaiveDate,
+        to: NaiveDate,
+        repository_id: Option<Uuid>,
+    ) -> Result<RollupSeriesResponse> {
+        if from > to {
+            return Err(Error::InvalidTimeRange);
+        }
+        let periods = granularity.periods(from, to);
+        if periods.len() > MAX_PERIODS {
+            return Err(Error::InvalidFilter(format!("A series spans at most {} periods", MAX_PERIODS)));
+        }
+        let (first, end) = match (periods.first(), periods.last()) {
+            (Some(&first), Some(&last)) => (first, granularity.next(last)),
+            _ => return Err(Error::InvalidTimeRange),
+        };
+
+        let rolled_up = self.repository.rolled_up_periods(granularity, first, end).await?;
+        let (source, rows) = if rolled_up.len() == periods.len() {
+            (RollupSource::Rollup, self.repository.load(granularity, first, end, repository_id).await?)
+        } else {
+            (RollupSource::Live, self.repository.compute(granularity, first, end, repository_id).await?)
+        };
+
+        Ok(RollupSeriesResponse {
+            granularity,
+            repository_id,
+            source,
+            points: rollup_points(&periods, &rows),
+        })
+    }
+}
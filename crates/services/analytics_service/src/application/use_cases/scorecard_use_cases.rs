@@ -0,0 +1,70 @@
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::domain::{Scorecard, ScorecardRepository, ScorecardTrend};
+use crate::models::ScorecardResponse;
+use crate::{Error, Result};
+
+/// Trends compare against the score recorded this many days earlier.
+const TREND_DAYS: i64 = 7;
+const HISTORY_DAYS: i64 = 30;
+
+/// Security posture scorecards of public repositories. Every computation is
+/// recorded as the scorecard of its day, and a daily job records one for
+/// each analyzed repository so trends do not depend on anyone looking.
+pub struct ScorecardUseCases {
+    repository: Arc<dyn ScorecardRepository>,
+}
+
+impl ScorecardUseCases {
+    pub fn new(repository: Arc<dyn ScorecardRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Compute and record the scorecard of `repository_id`.
+    pub async fn compute(&self, repository_id: Uuid) -> Result<Scorecard> {
+        if !self.repository.is_public_repository(repository_id).await? {
+            return Err(Error::RepositoryNotFound(repository_id.to_string()));
+        }
+        let inputs = self.repository.load_inputs(repository_id).await?;
+        let scorecard = Scorecard::compute(repository_id, inputs, Utc::now());
+        self.repository.record(&scorecard).await?;
+        Ok(scorecard)
+    }
+
+    /// The current scorecard with its trend and recent history.
+    pub async fn scorecard(&self, repository_id: Uuid) -> Result<ScorecardResponse> {
+        let scorecard = self.compute(repository_id).await?;
+        let trend = self.trend(&scorecard).await?;
+        let history = self.repository.history(repository_id, HISTORY_DAYS).await?;
+        Ok(ScorecardResponse { scorecard, trend, history })
+    }
+
+    /// The current scorecard and its trend, for badges.
+    pub async fn badge(&self, repository_id: Uuid) -> Result<(Scorecard, ScorecardTrend)> {
+        let scorecard = self.compute(repository_id).await?;
+        let trend = self.trend(&scorecard).await?;
+        Ok((scorecard, trend))
+    }
+
+    /// Record today's scorecard of every analyzed public repository.
+    /// Returns how many were recorded.
+    pub async fn record_all(&self) -> Result<usize> {
+        let mut recorded = 0;
+        for repository_id in self.repository.list_scored_repositories().await? {
+            match self.compute(repository_id).await {
+                Ok(_) => recorded += 1,
+                Err(e) => warn!(repository_id = %repository_id, error = %e, "Scorecard not recorded"),
+            }
+        }
+        Ok(recorded)
+    }
+
+    async fn trend(&self, scorecard: &Scorecard) -> Result<ScorecardTrend> {
+        let week_ago = (scorecard.computed_at - Duration::days(TREND_DAYS)).date_naive();
+        let previous = self.repository.score_on(scorecard.repository_id, week_ago).await?;
+        Ok(ScorecardTrend::between(previous, scorecard.score))
+    }
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use super::anomaly::AnalyticsAlert;
+use crate::Result;
+
+/// Tells operators about anomalies found in analytics metrics.
+#[async_trait]
+pub trait AlertNotifier: Send + Sync {
+    async fn notify(&self, alert: &AnalyticsAlert) -> Result<()>;
+}
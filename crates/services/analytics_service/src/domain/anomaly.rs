@@ -0,0 +1,241 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::types::Json;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Grants reading analytics alerts.
+pub const SCOPE_ANALYTICS_ALERTS: &str = "analytics:alerts";
+
+/// Standard deviations from the baseline mean that make a value anomalous.
+pub const Z_THRESHOLD: f64 = 3.0;
+/// Baselines with fewer windows than this are not trusted.
+const MIN_BASELINE_WINDOWS: usize = 7;
+
+/// Critical findings per repository per day, against the previous 14 days.
+pub const CRITICAL_FINDINGS_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+pub const CRITICAL_FINDINGS_BASELINE: usize = 14;
+/// Fewer new critical findings in a day are never a spike.
+const MIN_CRITICAL_SPIKE: f64 = 3.0;
+
+/// Analysis failure rate over six hours, against the previous week.
+pub const ANALYSIS_WINDOW: Duration = Duration::from_secs(6 * 60 * 60);
+pub const ANALYSIS_BASELINE: usize = 28;
+/// Windows with fewer finished analyses carry no rate.
+const MIN_FINISHED_ANALYSES: i64 = 10;
+/// Lower failure rates are never alerted on.
+const MIN_FAILURE_RATE: f64 = 0.2;
+
+/// Scores issued in the last hour, against the same hour of the previous
+/// 14 days so daily cycles are not mistaken for drops.
+pub const SCORING_WINDOW: Duration = Duration::from_secs(60 * 60);
+pub const SCORING_STRIDE: Duration = Duration::from_secs(24 * 60 * 60);
+pub const SCORING_BASELINE: usize = 14;
+/// Baselines issuing fewer scores an hour are too quiet to drop.
+const MIN_SCORING_THROUGHPUT: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum AnomalyMetric {
+    /// New critical findings in a repository.
+    CriticalFindingsSpike,
+    /// Share of finished analysis jobs that failed.
+    AnalysisFailureRate,
+    /// Scores issued platform-wide.
+    ScoringThroughputDrop,
+}
+
+impl AnomalyMetric {
+    pub const ALL: [AnomalyMetric; 3] =
+        [Self::CriticalFindingsSpike, Self::AnalysisFailureRate, Self::ScoringThroughputDrop];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CriticalFindingsSpike => "critical_findings_spike",
+            Self::AnalysisFailureRate => "analysis_failure_rate",
+            Self::ScoringThroughputDrop => "scoring_throughput_drop",
+        }
+    }
+}
+
+/// Mean and standard deviation of a metric over past windows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Baseline {
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Baseline {
+    /// None when there are too few samples to trust.
+    pub fn of(samples: &[f64]) -> Option<Self> {
+        if samples.len() < MIN_BASELINE_WINDOWS {
+            return None;
+        }
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|sample| (sample - mean).powi(2)).sum::<f64>() / n;
+        Some(Self { mean, stddev: variance.sqrt() })
+    }
+
+    /// Values above this are spikes. `min_spread` stands in for a deviation
+    /// too small to mean anything, e.g. of a metric that was always zero.
+    pub fn upper(&self, min_spread: f64) -> f64 {
+        self.mean + Z_THRESHOLD * self.stddev.max(min_spread)
+    }
+
+    /// Values below this are drops.
+    pub fn lower(&self, min_spread: f64) -> f64 {
+        self.mean - Z_THRESHOLD * self.stddev.max(min_spread)
+    }
+}
+
+/// A metric outside its baseline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Anomaly {
+    pub metric: AnomalyMetric,
+    /// Repository the anomaly is about; empty for platform-wide metrics.
+    pub subject: String,
+    pub observed: f64,
+    pub expected: f64,
+    pub threshold: f64,
+    pub details: Value,
+}
+
+/// Analysis jobs that finished in one window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::FromRow)]
+pub struct AnalysisWindow {
+    pub finished: i64,
+    pub failed: i64,
+}
+
+impl AnalysisWindow {
+    fn failure_rate(&self) -> Option<f64> {
+        (self.finished >= MIN_FINISHED_ANALYSES).then(|| self.failed as f64 / self.finished as f64)
+    }
+}
+
+/// `counts` are a repository's critical findings per day, newest first; the
+/// first is the day being checked.
+pub fn critical_findings_spike(repository_id: Uuid, counts: &[i64]) -> Option<Anomaly> {
+    let (&observed, history) = counts.split_first()?;
+    let samples: Vec<f64> = history.iter().map(|&count| count as f64).collect();
+    let baseline = Baseline::of(&samples)?;
+    let threshold = baseline.upper(1.0).max(MIN_CRITICAL_SPIKE);
+    (observed as f64 >= threshold).then(|| Anomaly {
+        metric: AnomalyMetric::CriticalFindingsSpike,
+        subject: repository_id.to_string(),
+        observed: observed as f64,
+        expected: baseline.mean,
+        threshold,
+        details: json!({ "repository_id": repository_id, "window_hours": 24, "baseline_days": history.len() }),
+    })
+}
+
+/// `windows` are analysis outcomes per window, newest first; the first is
+/// the window being checked. Windows with too few analyses are skipped.
+pub fn analysis_failure_spike(windows: &[AnalysisWindow]) -> Option<Anomaly> {
+    let (current, history) = windows.split_first()?;
+    let observed = current.failure_rate()?;
+    let samples: Vec<f64> = history.iter().filter_map(AnalysisWindow::failure_rate).collect();
+    let baseline = Baseline::of(&samples)?;
+    let threshold = baseline.upper(0.05).max(MIN_FAILURE_RATE);
+    (observed > threshold).then(|| Anomaly {
+        metric: AnomalyMetric::AnalysisFailureRate,
+        subject: String::new(),
+        observed,
+        expected: baseline.mean,
+        threshold,
+        details: json!({
+            "finished": current.finished,
+            "failed": current.failed,
+            "window_hours": ANALYSIS_WINDOW.as_secs() / 3600,
+        }),
+    })
+}
+
+/// `counts` are scores issued per window, newest first; the first is the
+/// window being checked.
+pub fn scoring_throughput_drop(counts: &[i64]) -> Option<Anomaly> {
+    let (&observed, history) = counts.split_first()?;
+    let samples: Vec<f64> = history.iter().map(|&count| count as f64).collect();
+    let baseline = Baseline::of(&samples)?;
+    if baseline.mean < MIN_SCORING_THROUGHPUT {
+        return None;
+    }
+    let threshold = baseline.lower(1.0);
+    ((observed as f64) < threshold).then(|| Anomaly {
+        metric: AnomalyMetric::ScoringThroughputDrop,
+        subject: String::new(),
+        observed: observed as f64,
+        expected: baseline.mean,
+        threshold,
+        details: json!({ "window_hours": SCORING_WINDOW.as_secs() / 3600, "baseline_days": history.len() }),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum AlertStatus {
+    Open,
+    Resolved,
+}
+
+/// A stored anomaly. Detecting it again while open updates it in place.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AnalyticsAlert {
+    pub id: Uuid,
+    pub metric: AnomalyMetric,
+    pub subject: String,
+    pub status: AlertStatus,
+    pub observed: f64,
+    pub expected: f64,
+    pub threshold: f64,
+    pub details: Json<Value>,
+    pub occurrences: i32,
+    pub first_detected_at: DateTime<Utc>,
+    pub last_detected_at: DateTime<Utc>,
+    pub notified_at: Option<DateTime<Utc>>,
+    pub notify_error: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spikes_stand_out_from_a_quiet_baseline() {
+        let repository_id = Uuid::new_v4();
+        let mut counts = vec![0; CRITICAL_FINDINGS_BASELINE + 1];
+        counts[3] = 1;
+        assert!(critical_findings_spike(repository_id, &counts).is_none());
+        counts[0] = 2;
+        assert!(critical_findings_spike(repository_id, &counts).is_none());
+        counts[0] = 6;
+        let anomaly = critical_findings_spike(repository_id, &counts).unwrap();
+        assert_eq!(anomaly.subject, repository_id.to_string());
+        assert!(anomaly.threshold >= MIN_CRITICAL_SPIKE);
+    }
+
+    #[test]
+    fn quiet_windows_carry_no_failure_rate() {
+        let busy = AnalysisWindow { finished: 40, failed: 2 };
+        let mut windows = vec![busy; ANALYSIS_BASELINE + 1];
+        windows[0] = AnalysisWindow { finished: 4, failed: 4 };
+        assert!(analysis_failure_spike(&windows).is_none());
+        windows[0] = AnalysisWindow { finished: 20, failed: 12 };
+        assert_eq!(analysis_failure_spike(&windows).unwrap().observed, 0.6);
+    }
+
+    #[test]
+    fn throughput_drops_need_a_busy_baseline() {
+        let mut counts = vec![50; SCORING_BASELINE + 1];
+        counts[0] = 10;
+        assert!(scoring_throughput_drop(&counts).is_some());
+        let quiet = vec![2, 5, 5, 5, 5, 5, 5, 5, 5];
+        assert!(scoring_throughput_drop(&quiet).is_none());
+    }
+}
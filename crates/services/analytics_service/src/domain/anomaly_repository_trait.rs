@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::anomaly::{AlertStatus, AnalysisWindow, AnalyticsAlert, Anomaly, AnomalyMetric};
+use crate::Result;
+
+#[async_trait]
+pub trait AnomalyRepository: Send + Sync {
+    /// Critical findings per `window` of every repository with one in the
+    /// last `count + 1` windows before `now`, newest window first.
+    /// Repositories added since the first window are left out.
+    async fn critical_findings(
+        &self,
+        now: DateTime<Utc>,
+        window: Duration,
+        count: usize,
+    ) -> Result<Vec<(Uuid, Vec<i64>)>>;
+
+    /// Analysis jobs finished in `count + 1` windows of length `window`,
+    /// ending `stride` apart from `now` back, newest first.
+    async fn analysis_windows(
+        &self,
+        now: DateTime<Utc>,
+        window: Duration,
+        stride: Duration,
+        count: usize,
+    ) -> Result<Vec<AnalysisWindow>>;
+
+    /// Scores issued in windows laid out as in `analysis_windows`.
+    async fn scoring_counts(
+        &self,
+        now: DateTime<Utc>,
+        window: Duration,
+        stride: Duration,
+        count: usize,
+    ) -> Result<Vec<i64>>;
+
+    /// Open an alert for `anomaly`, or update the one already open for its
+    /// metric and subject.
+    async fn raise(&self, anomaly: &Anomaly, now: DateTime<Utc>) -> Result<AnalyticsAlert>;
+
+    /// Resolve open alerts of `metric` whose subject is not in
+    /// `still_anomalous`. Returns how many were resolved.
+    async fn resolve_cleared(
+        &self,
+        metric: AnomalyMetric,
+        still_anomalous: &[String],
+        now: DateTime<Utc>,
+    ) -> Result<u64>;
+
+    /// When any alert of `metric` and `subject` was last notified.
+    async fn last_notified_at(&self, metric: AnomalyMetric, subject: &str) -> Result<Option<DateTime<Utc>>>;
+
+    /// Record a notification attempt: when it was sent, or why it failed.
+    async fn record_notification(
+        &self,
+        id: Uuid,
+        notified_at: Option<DateTime<Utc>>,
+        error: Option<&str>,
+    ) -> Result<()>;
+
+    /// The latest `limit` alerts, newest first, optionally of one status.
+    async fn list(&self, status: Option<AlertStatus>, limit: i64) -> Result<Vec<AnalyticsAlert>>;
+}
@@ -0,0 +1,238 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// A table that can be exported, one record per row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ExportDataset {
+    /// Security vulnerabilities found by code analysis.
+    Vulnerabilities,
+    /// Persona scores issued for behavior inputs.
+    Scores,
+    /// Code analysis runs.
+    Analyses,
+}
+
+impl ExportDataset {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Vulnerabilities => "vulnerabilities",
+            Self::Scores => "scores",
+            Self::Analyses => "analyses",
+        }
+    }
+
+    /// Fields of each record, in CSV column order.
+    pub fn columns(&self) -> &'static [&'static str] {
+        match self {
+            Self::Vulnerabilities => &[
+                "id",
+                "repository_id",
+                "analysis_result_id",
+                "vulnerability_type",
+                "severity",
+                "confidence_score",
+                "file_path",
+                "line_number",
+                "cve_id",
+                "disclosure_state",
+                "is_false_positive",
+                "fixed_at",
+                "created_at",
+            ],
+            Self::Scores => &["id", "behavior_input_id", "score", "model_version", "scored_at"],
+            Self::Analyses => &[
+                "id",
+                "repository_id",
+                "commit_sha",
+                "analysis_type",
+                "security_score",
+                "quality_score",
+                "issues_found",
+                "critical_issues",
+                "analysis_duration_ms",
+                "analyzer_version",
+                "created_at",
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    /// One JSON object per line.
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+        }
+    }
+
+    /// The line written before the records, if any.
+    pub fn header(&self, dataset: ExportDataset) -> Option<String> {
+        match self {
+            Self::Csv => Some(format!("{}\n", dataset.columns().join(","))),
+            Self::Ndjson => None,
+        }
+    }
+
+    /// `record` as one line, newline included. CSV fields follow the
+    /// dataset's columns; missing fields and nulls are empty.
+    pub fn encode(&self, dataset: ExportDataset, record: &Value) -> String {
+        match self {
+            Self::Csv => {
+                let fields: Vec<String> = dataset
+                    .columns()
+                    .iter()
+                    .map(|column| csv_field(record.get(column).unwrap_or(&Value::Null)))
+                    .collect();
+                format!("{}\n", fields.join(","))
+            }
+            Self::Ndjson => format!("{}\n", record),
+        }
+    }
+}
+
+fn csv_field(value: &Value) -> String {
+    let raw = match value {
+        Value::Null => return String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if raw.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", raw.replace('"', "\"\""))
+    } else {
+        raw
+    }
+}
+
+/// What to export: records of `dataset` created from `from` (inclusive) to
+/// `to` (exclusive), oldest first. Either bound may be left open.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportQuery {
+    pub dataset: ExportDataset,
+    pub format: ExportFormat,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Whether vulnerabilities not yet publicly disclosed are exported.
+    pub include_undisclosed: bool,
+}
+
+impl ExportQuery {
+    /// File name offered to the client.
+    pub fn file_name(&self) -> String {
+        let bound = |at: Option<DateTime<Utc>>| at.map_or("all".to_string(), |at| at.format("%Y%m%d").to_string());
+        format!(
+            "{}-{}-{}.{}",
+            self.dataset.as_str(),
+            bound(self.from),
+            bound(self.to),
+            self.format.extension()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ExportStatus {
+    Queued,
+    Running,
+    Ready,
+    Failed,
+}
+
+/// An export prepared in the background, for ranges too large to stream in
+/// one request. The file itself is loaded separately.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AnalyticsExport {
+    pub id: Uuid,
+    pub requested_by: String,
+    pub dataset: ExportDataset,
+    pub format: ExportFormat,
+    pub range_from: Option<DateTime<Utc>>,
+    pub range_to: Option<DateTime<Utc>>,
+    pub include_undisclosed: bool,
+    pub status: ExportStatus,
+    pub attempts: i32,
+    pub row_count: Option<i64>,
+    pub byte_size: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When a ready file is deleted.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AnalyticsExport {
+    pub fn query(&self) -> ExportQuery {
+        ExportQuery {
+            dataset: self.dataset,
+            format: self.format,
+            from: self.range_from,
+            to: self.range_to,
+            include_undisclosed: self.include_undisclosed,
+        }
+    }
+}
+
+/// Where a prepared export's file is kept.
+#[derive(Debug, Clone)]
+pub enum ExportFile {
+    /// In `analytics_exports.content`, when no object store is configured.
+    Inline(Vec<u8>),
+    /// In object storage, at `uri`.
+    Stored { uri: String, byte_size: i64 },
+}
+
+/// How a prepared export is handed to the client.
+#[derive(Debug, Clone)]
+pub enum ExportDownload {
+    Content(Vec<u8>),
+    /// A presigned object storage URL to fetch it from.
+    Redirect(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn csv_records_follow_the_dataset_columns() {
+        let record = json!({
+            "score": 71.5,
+            "id": "a",
+            "model_version": "v1, \"beta\"",
+            "behavior_input_id": null,
+        });
+
+        assert_eq!(
+            ExportFormat::Csv.header(ExportDataset::Scores).unwrap(),
+            "id,behavior_input_id,score,model_version,scored_at\n"
+        );
+        assert_eq!(
+            ExportFormat::Csv.encode(ExportDataset::Scores, &record),
+            "a,,71.5,\"v1, \"\"beta\"\"\",\n"
+        );
+        assert!(ExportFormat::Ndjson.header(ExportDataset::Scores).is_none());
+        assert_eq!(ExportFormat::Ndjson.encode(ExportDataset::Scores, &json!({"id": "a"})), "{\"id\":\"a\"}\n");
+    }
+}
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use serde_json::Value;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::export::{AnalyticsExport, ExportFile, ExportQuery};
+use crate::Result;
+
+/// Records of an export, one JSON object per row, oldest first.
+pub type RecordStream = BoxStream<'static, Result<Value>>;
+
+#[async_trait]
+pub trait ExportRepository: Send + Sync {
+    /// Stream the records `query` selects without loading them all.
+    fn records(&self, query: &ExportQuery) -> RecordStream;
+
+    /// Queue an export for `requested_by` to be prepared in the background.
+    async fn create(&self, requested_by: &str, query: &ExportQuery) -> Result<AnalyticsExport>;
+
+    /// An export `requested_by` asked for.
+    async fn find(&self, id: Uuid, requested_by: &str) -> Result<Option<AnalyticsExport>>;
+
+    /// Claim the oldest queued export, or one whose lease ran out, for
+    /// `lease`. Exports are attempted at most `max_attempts` times.
+    async fn claim_next(&self, lease: Duration, max_attempts: i32) -> Result<Option<AnalyticsExport>>;
+
+    /// Record the prepared file and mark the export ready until `expires_at`.
+    async fn complete(&self, id: Uuid, row_count: i64, file: ExportFile, expires_at: DateTime<Utc>) -> Result<()>;
+
+    async fn fail(&self, id: Uuid, error: &str) -> Result<()>;
+
+    /// The prepared file of a ready export `requested_by` asked for.
+    async fn file(&self, id: Uuid, requested_by: &str) -> Result<Option<ExportFile>>;
+
+    /// Delete exports past their expiry. Returns how many were deleted.
+    async fn purge_expired(&self) -> Result<u64>;
+}
@@ -1,5 +1,29 @@
+pub mod alert_notifier_trait;
 pub mod analytics_models;
 pub mod analytics_repository_trait;
+pub mod anomaly;
+pub mod anomaly_repository_trait;
+pub mod export;
+pub mod export_repository_trait;
+pub mod report;
+pub mod report_delivery_trait;
+pub mod report_repository_trait;
+pub mod rollup;
+pub mod rollup_repository_trait;
+pub mod scorecard;
+pub mod scorecard_repository_trait;
 
+pub use alert_notifier_trait::*;
 pub use analytics_models::*;
-pub use analytics_repository_trait::*;
\ No newline at end of file
+pub use analytics_repository_trait::*;
+pub use anomaly::*;
+pub use anomaly_repository_trait::*;
+pub use export::*;
+pub use export_repository_trait::*;
+pub use report::*;
+pub use report_delivery_trait::*;
+pub use report_repository_trait::*;
+pub use rollup::*;
+pub use rollup_repository_trait::*;
+pub use scorecard::*;
+pub use scorecard_repository_trait::*;
\ No newline at end of file
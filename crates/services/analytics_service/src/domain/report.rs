@@ -0,0 +1,336 @@
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use serde_with::skip_serializing_none;
+use sha2::Sha256;
+use sqlx::types::Json;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::{Error, Result};
+
+/// Rows a report returns at most, and by default.
+pub const MAX_REPORT_ROWS: i64 = 1000;
+const DEFAULT_REPORT_ROWS: i64 = 100;
+const MAX_LOOKBACK_DAYS: i64 = 366;
+const SEVERITIES: [&str; 4] = ["critical", "high", "medium", "low"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ReportDataset {
+    Vulnerabilities,
+    /// Persona scores issued for behavior inputs.
+    Scores,
+    /// Public repositories and their security scores.
+    Repositories,
+}
+
+impl ReportDataset {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Vulnerabilities => "vulnerabilities",
+            Self::Scores => "scores",
+            Self::Repositories => "repositories",
+        }
+    }
+}
+
+/// Parameters of a saved query. Unset filters match everything; filters
+/// that do not apply to the query's dataset are rejected.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportFilters {
+    /// Only records from this many days before each run; all time if unset.
+    pub lookback_days: Option<i64>,
+    /// Vulnerabilities and repositories.
+    pub repository_id: Option<Uuid>,
+    /// Vulnerabilities: `critical`, `high`, `medium` or `low`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub severities: Vec<String>,
+    /// Vulnerabilities.
+    pub vulnerability_type: Option<String>,
+    /// Vulnerabilities not fixed, resolved or marked false positive.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub open_only: bool,
+    /// Scores by score, repositories by security score, from 0 to 100.
+    pub min_score: Option<f64>,
+    pub max_score: Option<f64>,
+    /// Scores.
+    pub model_version: Option<String>,
+    /// Repositories by primary language, case-insensitive.
+    pub language: Option<String>,
+    /// Rows returned; 100 by default.
+    pub limit: Option<i64>,
+}
+
+impl ReportFilters {
+    pub fn validate(&self, dataset: ReportDataset) -> Result<()> {
+        use ReportDataset::*;
+        let applies: [(&str, bool, &[ReportDataset]); 8] = [
+            ("repository_id", self.repository_id.is_some(), &[Vulnerabilities, Repositories]),
+            ("severities", !self.severities.is_empty(), &[Vulnerabilities]),
+            ("vulnerability_type", self.vulnerability_type.is_some(), &[Vulnerabilities]),
+            ("open_only", self.open_only, &[Vulnerabilities]),
+            ("min_score", self.min_score.is_some(), &[Scores, Repositories]),
+            ("max_score", self.max_score.is_some(), &[Scores, Repositories]),
+            ("model_version", self.model_version.is_some(), &[Scores]),
+            ("language", self.language.is_some(), &[Repositories]),
+        ];
+        if let Some((filter, ..)) = applies.iter().find(|(_, set, datasets)| *set && !datasets.contains(&dataset)) {
+            return Err(Error::InvalidFilter(format!("{} does not apply to {}", filter, dataset.as_str())));
+        }
+
+        if let Some(severity) = self.severities.iter().find(|s| !SEVERITIES.contains(&s.as_str())) {
+            return Err(Error::InvalidFilter(format!("Unknown severity '{}'", severity)));
+        }
+        if matches!(self.lookback_days, Some(days) if !(1..=MAX_LOOKBACK_DAYS).contains(&days)) {
+            return Err(Error::InvalidFilter(format!(
+                "lookback_days must be between 1 and {}",
+                MAX_LOOKBACK_DAYS
+            )));
+        }
+        if matches!(self.limit, Some(limit) if !(1..=MAX_REPORT_ROWS).contains(&limit)) {
+            return Err(Error::InvalidFilter(format!("limit must be between 1 and {}", MAX_REPORT_ROWS)));
+        }
+        let in_range = |score: Option<f64>| score.is_none_or(|score| (0.0..=100.0).contains(&score));
+        if !in_range(self.min_score) || !in_range(self.max_score) {
+            return Err(Error::InvalidFilter("Scores range from 0 to 100".to_string()));
+        }
+        if let (Some(min), Some(max)) = (self.min_score, self.max_score) {
+            if min > max {
+                return Err(Error::InvalidFilter("min_score is above max_score".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_REPORT_ROWS)
+    }
+
+    /// Time range of a run at `now`: from `lookback_days` before, or open,
+    /// to `now`.
+    pub fn range(&self, now: DateTime<Utc>) -> (Option<DateTime<Utc>>, DateTime<Utc>) {
+        (self.lookback_days.map(|days| now - Duration::days(days)), now)
+    }
+}
+
+/// Where a report's results are sent after each run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum DeliveryChannel {
+    /// Results are only kept in the run history.
+    None,
+    Email,
+    /// Signed POST to an https URL.
+    Webhook,
+}
+
+impl DeliveryChannel {
+    /// The normalized target of this channel.
+    pub fn validate_target(&self, target: Option<&str>) -> Result<Option<String>> {
+        let target = target.map(str::trim).filter(|target| !target.is_empty());
+        match (self, target) {
+            (Self::None, None) => Ok(None),
+            (Self::None, Some(_)) => Err(Error::InvalidFilter("delivery_target needs a delivery_channel".to_string())),
+            (_, None) => Err(Error::InvalidFilter("delivery_target is required".to_string())),
+            (Self::Email, Some(address)) => {
+                let valid = address
+                    .split_once('@')
+                    .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'));
+                if !valid || address.contains(char::is_whitespace) {
+                    return Err(Error::InvalidFilter(format!("Invalid email address '{}'", address)));
+                }
+                Ok(Some(address.to_string()))
+            }
+            (Self::Webhook, Some(url)) => {
+                let parsed =
+                    Url::parse(url).map_err(|e| Error::InvalidFilter(format!("Invalid webhook URL: {}", e)))?;
+                match (parsed.scheme(), parsed.host_str()) {
+                    ("https", Some(_)) => Ok(Some(url.to_string())),
+                    ("http", Some("localhost" | "127.0.0.1")) => Ok(Some(url.to_string())),
+                    _ => Err(Error::InvalidFilter("Webhook URLs must use https".to_string())),
+                }
+            }
+        }
+    }
+}
+
+/// What the owner saves: the query and how its reports are delivered.
+#[derive(Debug, Clone)]
+pub struct SavedQueryDefinition {
+    pub name: String,
+    pub dataset: ReportDataset,
+    pub filters: ReportFilters,
+    pub include_undisclosed: bool,
+    pub schedule: Option<String>,
+    pub delivery_channel: DeliveryChannel,
+    pub delivery_target: Option<String>,
+    pub enabled: bool,
+}
+
+/// A saved query and its report schedule.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SavedQuery {
+    pub id: Uuid,
+    pub owner: String,
+    pub name: String,
+    pub dataset: ReportDataset,
+    pub filters: Json<ReportFilters>,
+    /// Whether vulnerabilities not yet publicly disclosed are reported.
+    pub include_undisclosed: bool,
+    pub schedule: Option<String>,
+    pub delivery_channel: DeliveryChannel,
+    pub delivery_target: Option<String>,
+    #[serde(skip_serializing)]
+    pub webhook_secret: Option<String>,
+    pub enabled: bool,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ReportTrigger {
+    Schedule,
+    Manual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ReportRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// The query has no delivery channel.
+    Skipped,
+    Delivered,
+    Failed,
+}
+
+/// One run of a saved query. `output` holds the rows returned, and is only
+/// loaded when a single run is fetched.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReportRun {
+    pub id: Uuid,
+    pub query_id: Uuid,
+    pub trigger: ReportTrigger,
+    pub status: ReportRunStatus,
+    pub range_from: Option<DateTime<Utc>>,
+    pub range_to: DateTime<Utc>,
+    pub row_count: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Json<Vec<Value>>>,
+    pub error: Option<String>,
+    pub delivery_status: Option<DeliveryStatus>,
+    pub delivery_error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// The body delivered for a finished run.
+pub fn report_payload(query: &SavedQuery, run: &ReportRun, rows: &[Value]) -> Value {
+    json!({
+        "event": "report_run",
+        "query_id": query.id,
+        "query_name": query.name,
+        "run_id": run.id,
+        "dataset": query.dataset,
+        "filters": query.filters,
+        "range_from": run.range_from,
+        "range_to": run.range_to,
+        "row_count": rows.len(),
+        "rows": rows,
+    })
+}
+
+/// Parse a cron expression. The usual five fields (minute to day of week)
+/// are accepted as well as six or seven with seconds and year.
+pub fn parse_schedule(expression: &str) -> Result<cron::Schedule> {
+    let expression = expression.trim();
+    let normalized = match expression.split_whitespace().count() {
+        5 => format!("0 {}", expression),
+        _ => expression.to_string(),
+    };
+    cron::Schedule::from_str(&normalized).map_err(|e| Error::InvalidSchedule(format!("{}: {}", expression, e)))
+}
+
+/// The first run of `expression` strictly after `after`.
+pub fn next_run_after(expression: &str, after: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    parse_schedule(expression)?
+        .after(&after)
+        .next()
+        .ok_or_else(|| Error::InvalidSchedule(format!("{} never runs again", expression)))
+}
+
+/// A new random webhook secret: 64 hex characters.
+pub fn generate_webhook_secret() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// `X-Report-Signature` of a webhook delivery: `sha256=` and the HMAC of
+/// `{timestamp}.{body}` under the query's webhook secret.
+pub fn sign_report(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn filters_must_apply_to_the_dataset() {
+        let filters = ReportFilters {
+            severities: vec!["critical".to_string()],
+            open_only: true,
+            ..ReportFilters::default()
+        };
+        assert!(filters.validate(ReportDataset::Vulnerabilities).is_ok());
+        assert!(filters.validate(ReportDataset::Scores).is_err());
+
+        let filters = ReportFilters { min_score: Some(80.0), max_score: Some(60.0), ..ReportFilters::default() };
+        assert!(filters.validate(ReportDataset::Scores).is_err());
+        let filters = ReportFilters { severities: vec!["urgent".to_string()], ..ReportFilters::default() };
+        assert!(filters.validate(ReportDataset::Vulnerabilities).is_err());
+    }
+
+    #[test]
+    fn five_field_schedules_run_on_the_minute() {
+        // A Wednesday
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 12, 30, 15).unwrap();
+        assert_eq!(
+            next_run_after("0 9 * * Mon", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 19, 9, 0, 0).unwrap()
+        );
+        assert!(parse_schedule("every monday").is_err());
+    }
+
+    #[test]
+    fn delivery_targets_match_their_channel() {
+        assert_eq!(DeliveryChannel::None.validate_target(Some(" ")).unwrap(), None);
+        assert!(DeliveryChannel::Email.validate_target(Some("ops@localhost")).is_err());
+        assert!(DeliveryChannel::Email.validate_target(Some("ops@example.org")).is_ok());
+        assert!(DeliveryChannel::Webhook.validate_target(Some("http://example.org/hook")).is_err());
+        assert!(DeliveryChannel::Webhook.validate_target(None).is_err());
+    }
+}
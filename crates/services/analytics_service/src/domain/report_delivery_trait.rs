@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::report::{ReportRun, SavedQuery};
+use crate::Result;
+
+/// Sends the results of a finished run to the query's delivery target.
+#[async_trait]
+pub trait ReportDelivery: Send + Sync {
+    async fn deliver(&self, query: &SavedQuery, run: &ReportRun, rows: &[Value]) -> Result<()>;
+}
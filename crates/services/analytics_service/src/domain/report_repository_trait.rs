@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::report::{
+    DeliveryStatus, ReportDataset, ReportFilters, ReportRun, ReportTrigger, SavedQuery, SavedQueryDefinition,
+};
+use crate::Result;
+
+#[async_trait]
+pub trait ReportRepository: Send + Sync {
+    async fn create(
+        &self,
+        owner: &str,
+        definition: &SavedQueryDefinition,
+        next_run_at: Option<DateTime<Utc>>,
+        webhook_secret: Option<&str>,
+    ) -> Result<SavedQuery>;
+
+    /// Replace the definition of a query `owner` saved. The webhook secret
+    /// is only replaced if `webhook_secret` is set.
+    async fn update(
+        &self,
+        id: Uuid,
+        owner: &str,
+        definition: &SavedQueryDefinition,
+        next_run_at: Option<DateTime<Utc>>,
+        webhook_secret: Option<&str>,
+    ) -> Result<Option<SavedQuery>>;
+
+    /// Delete a query `owner` saved, with its runs. Returns whether it existed.
+    async fn delete(&self, id: Uuid, owner: &str) -> Result<bool>;
+
+    async fn find(&self, id: Uuid, owner: &str) -> Result<Option<SavedQuery>>;
+
+    async fn list(&self, owner: &str) -> Result<Vec<SavedQuery>>;
+
+    /// Claim up to `limit` enabled queries whose next run is due for
+    /// `lease`, skipping ones another worker holds.
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<SavedQuery>>;
+
+    /// Record that a query ran at `ran_at`. A scheduled run also moves the
+    /// next run to `next_run_at` and releases the lease.
+    async fn mark_ran(&self, id: Uuid, ran_at: DateTime<Utc>, next_run_at: Option<DateTime<Utc>>) -> Result<()>;
+
+    /// Rows of `dataset` matching `filters` recorded in `[from, to)`, at
+    /// most `filters.limit()`. `from` is open if unset.
+    async fn fetch_rows(
+        &self,
+        dataset: ReportDataset,
+        filters: &ReportFilters,
+        include_undisclosed: bool,
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Value>>;
+
+    async fn start_run(
+        &self,
+        query_id: Uuid,
+        trigger: ReportTrigger,
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    ) -> Result<ReportRun>;
+
+    /// Store the rows a run returned and mark it succeeded.
+    async fn complete_run(&self, run_id: Uuid, rows: &[Value]) -> Result<()>;
+
+    async fn fail_run(&self, run_id: Uuid, error: &str) -> Result<()>;
+
+    async fn record_delivery(&self, run_id: Uuid, status: DeliveryStatus, error: Option<&str>) -> Result<()>;
+
+    /// The latest `limit` runs of a query `owner` saved, newest first and
+    /// without their output.
+    async fn runs(&self, query_id: Uuid, owner: &str, limit: i64) -> Result<Vec<ReportRun>>;
+
+    /// A run of a query `owner` saved, with its output.
+    async fn run(&self, run_id: Uuid, owner: &str) -> Result<Option<ReportRun>>;
+}
@@ -0,0 +1,153 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+pub const METRIC_VULNERABILITIES_FOUND: &str = "vulnerabilities_found";
+pub const METRIC_ANALYSES_RUN: &str = "analyses_run";
+pub const METRIC_PATCHES_MERGED: &str = "patches_merged";
+pub const METRIC_SCORES_ISSUED: &str = "scores_issued";
+
+/// Length of a rollup period. Days and ISO weeks start at UTC midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupGranularity {
+    Day,
+    Week,
+}
+
+impl RollupGranularity {
+    /// Name stored in `analytics_rollups.granularity`, which is also the
+    /// PostgreSQL `date_trunc` unit.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+
+    /// Start of the period `date` falls in.
+    pub fn period_start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        }
+    }
+
+    pub fn next(&self, period_start: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => period_start + Duration::days(1),
+            Self::Week => period_start + Duration::days(7),
+        }
+    }
+
+    /// Starts of the periods covering `from` to `to`, both inclusive.
+    pub fn periods(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let mut periods = Vec::new();
+        let mut period = self.period_start(from);
+        while period <= to {
+            periods.push(period);
+            period = self.next(period);
+        }
+        periods
+    }
+}
+
+/// One aggregate: a metric, optionally split by a dimension such as
+/// severity, for one repository or, without one, platform-wide.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RollupRow {
+    pub period_start: NaiveDate,
+    pub metric: String,
+    /// Empty when the metric is not split.
+    pub dimension: String,
+    pub repository_id: Option<Uuid>,
+    pub value: i64,
+}
+
+/// Activity over one period.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RollupPoint {
+    pub period_start: NaiveDate,
+    pub vulnerabilities_by_severity: BTreeMap<String, i64>,
+    pub vulnerabilities_found: i64,
+    pub analyses_run: i64,
+    pub patches_merged: i64,
+    /// Scores are not tied to a repository, so this is platform-wide.
+    pub scores_issued: i64,
+}
+
+/// One point per period in `periods`, summing `rows` over repositories.
+/// Periods without rows are all zeros.
+pub fn rollup_points(periods: &[NaiveDate], rows: &[RollupRow]) -> Vec<RollupPoint> {
+    let mut points: BTreeMap<NaiveDate, RollupPoint> = periods
+        .iter()
+        .map(|&period_start| (period_start, RollupPoint { period_start, ..RollupPoint::default() }))
+        .collect();
+    for row in rows {
+        let Some(point) = points.get_mut(&row.period_start) else {
+            continue;
+        };
+        match row.metric.as_str() {
+            METRIC_VULNERABILITIES_FOUND => {
+                point.vulnerabilities_found += row.value;
+                *point.vulnerabilities_by_severity.entry(row.dimension.clone()).or_default() += row.value;
+            }
+            METRIC_ANALYSES_RUN => point.analyses_run += row.value,
+            METRIC_PATCHES_MERGED => point.patches_merged += row.value,
+            METRIC_SCORES_ISSUED => point.scores_issued += row.value,
+            _ => {}
+        }
+    }
+    points.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn row(period: &str, metric: &str, dimension: &str, value: i64) -> RollupRow {
+        RollupRow {
+            period_start: date(period),
+            metric: metric.to_string(),
+            dimension: dimension.to_string(),
+            repository_id: Some(Uuid::new_v4()),
+            value,
+        }
+    }
+
+    #[test]
+    fn weeks_start_on_monday() {
+        // 2026-10-16 is a Friday
+        assert_eq!(RollupGranularity::Week.period_start(date("2026-10-16")), date("2026-10-12"));
+        assert_eq!(
+            RollupGranularity::Week.periods(date("2026-10-16"), date("2026-10-26")),
+            vec![date("2026-10-12"), date("2026-10-19"), date("2026-10-26")]
+        );
+        assert_eq!(RollupGranularity::Day.periods(date("2026-10-16"), date("2026-10-17")).len(), 2);
+    }
+
+    #[test]
+    fn rows_are_summed_over_repositories() {
+        let periods = RollupGranularity::Day.periods(date("2026-10-15"), date("2026-10-16"));
+        let points = rollup_points(
+            &periods,
+            &[
+                row("2026-10-16", METRIC_VULNERABILITIES_FOUND, "high", 2),
+                row("2026-10-16", METRIC_VULNERABILITIES_FOUND, "high", 1),
+                row("2026-10-16", METRIC_VULNERABILITIES_FOUND, "low", 4),
+                row("2026-10-16", METRIC_ANALYSES_RUN, "", 3),
+            ],
+        );
+
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0], RollupPoint { period_start: date("2026-10-15"), ..RollupPoint::default() });
+        assert_eq!(points[1].vulnerabilities_found, 7);
+        assert_eq!(points[1].vulnerabilities_by_severity["high"], 3);
+        assert_eq!(points[1].analyses_run, 3);
+    }
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::rollup::{RollupGranularity, RollupRow};
+use crate::Result;
+
+#[async_trait]
+pub trait RollupRepository: Send + Sync {
+    /// Aggregate the source tables over the periods starting from `from`
+    /// up to, not including, `to`.
+    async fn compute(
+        &self,
+        granularity: RollupGranularity,
+        from: NaiveDate,
+        to: NaiveDate,
+        repository_id: Option<Uuid>,
+    ) -> Result<Vec<RollupRow>>;
+
+    /// Recompute and store the rollups of the periods from `from` up to
+    /// `to`, replacing any stored before. Returns how many rows were stored.
+    async fn refresh(&self, granularity: RollupGranularity, from: NaiveDate, to: NaiveDate) -> Result<u64>;
+
+    /// Starts of the periods from `from` up to `to` that have been rolled up.
+    async fn rolled_up_periods(
+        &self,
+        granularity: RollupGranularity,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<NaiveDate>>;
+
+    /// Stored rollups of the periods from `from` up to `to`.
+    async fn load(
+        &self,
+        granularity: RollupGranularity,
+        from: NaiveDate,
+        to: NaiveDate,
+        repository_id: Option<Uuid>,
+    ) -> Result<Vec<RollupRow>>;
+}
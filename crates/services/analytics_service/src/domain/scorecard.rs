@@ -0,0 +1,297 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How much each component counts towards the overall score. Components
+/// that cannot be scored yet are left out and the rest reweighted.
+const VULNERABILITIES_WEIGHT: f64 = 0.4;
+const DEPENDENCIES_WEIGHT: f64 = 0.2;
+const PATCH_LATENCY_WEIGHT: f64 = 0.2;
+const FRESHNESS_WEIGHT: f64 = 0.2;
+
+/// Patches merged within this many hours of the finding score full marks,
+/// and ones taking `PATCH_LATENCY_ZERO_HOURS` or longer none.
+const PATCH_LATENCY_FULL_HOURS: f64 = 24.0;
+const PATCH_LATENCY_ZERO_HOURS: f64 = 30.0 * 24.0;
+/// Analyses this many days old score full marks, and ones
+/// `FRESHNESS_ZERO_DAYS` old or never run none.
+const FRESHNESS_FULL_DAYS: f64 = 7.0;
+const FRESHNESS_ZERO_DAYS: f64 = 90.0;
+
+/// Overall score change, against the score a week earlier, that turns the
+/// trend arrow up or down.
+const TREND_THRESHOLD: f64 = 2.0;
+
+/// What a repository's scorecard is computed from. Open findings are those
+/// not fixed, resolved or marked false positive.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ScorecardInputs {
+    pub open_critical: i64,
+    pub open_high: i64,
+    pub open_medium: i64,
+    pub open_low: i64,
+    pub dependency_critical: i64,
+    pub dependency_high: i64,
+    pub dependency_medium: i64,
+    pub dependency_low: i64,
+    /// Median hours from a finding to its merged patch, over the patches
+    /// merged in the last 90 days. `None` without any.
+    pub median_patch_latency_hours: Option<f64>,
+    pub last_analyzed_at: Option<DateTime<Utc>>,
+}
+
+/// Component scores from 0 to 100.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScorecardComponents {
+    /// Open findings in the repository's own code, weighted by severity.
+    pub vulnerabilities: f64,
+    /// Open findings in its dependencies, weighted by severity.
+    pub dependencies: f64,
+    /// `None` until a patch has been merged.
+    pub patch_latency: Option<f64>,
+    pub freshness: f64,
+}
+
+impl ScorecardComponents {
+    pub fn from_inputs(inputs: &ScorecardInputs, now: DateTime<Utc>) -> Self {
+        let penalty = |critical: i64, high: i64, medium: i64, low: i64, weights: [f64; 4]| {
+            let deducted = critical as f64 * weights[0]
+                + high as f64 * weights[1]
+                + medium as f64 * weights[2]
+                + low as f64 * weights[3];
+            (100.0 - deducted).max(0.0)
+        };
+        let freshness = inputs.last_analyzed_at.map_or(0.0, |at| {
+            let days = (now - at).num_seconds().max(0) as f64 / 86_400.0;
+            linear_decay(days, FRESHNESS_FULL_DAYS, FRESHNESS_ZERO_DAYS)
+        });
+        Self {
+            vulnerabilities: penalty(
+                inputs.open_critical,
+                inputs.open_high,
+                inputs.open_medium,
+                inputs.open_low,
+                [25.0, 10.0, 3.0, 1.0],
+            ),
+            dependencies: penalty(
+                inputs.dependency_critical,
+                inputs.dependency_high,
+                inputs.dependency_medium,
+                inputs.dependency_low,
+                [20.0, 8.0, 3.0, 1.0],
+            ),
+            patch_latency: inputs
+                .median_patch_latency_hours
+                .map(|hours| linear_decay(hours, PATCH_LATENCY_FULL_HOURS, PATCH_LATENCY_ZERO_HOURS)),
+            freshness,
+        }
+    }
+
+    /// Weighted mean of the components that could be scored.
+    pub fn overall(&self) -> f64 {
+        let scored = [
+            (Some(self.vulnerabilities), VULNERABILITIES_WEIGHT),
+            (Some(self.dependencies), DEPENDENCIES_WEIGHT),
+            (self.patch_latency, PATCH_LATENCY_WEIGHT),
+            (Some(self.freshness), FRESHNESS_WEIGHT),
+        ];
+        let (sum, weights) = scored
+            .iter()
+            .filter_map(|(score, weight)| score.map(|score| (score * weight, *weight)))
+            .fold((0.0, 0.0), |(sum, weights), (score, weight)| (sum + score, weights + weight));
+        (sum / weights * 10.0).round() / 10.0
+    }
+}
+
+/// 100 up to `full`, 0 from `zero` on, linear in between.
+fn linear_decay(value: f64, full: f64, zero: f64) -> f64 {
+    if value <= full {
+        100.0
+    } else if value >= zero {
+        0.0
+    } else {
+        100.0 * (zero - value) / (zero - full)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar")]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl Grade {
+    pub fn from_score(score: f64) -> Self {
+        match score {
+            s if s >= 90.0 => Self::A,
+            s if s >= 80.0 => Self::B,
+            s if s >= 70.0 => Self::C,
+            s if s >= 60.0 => Self::D,
+            _ => Self::F,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+            Self::D => "D",
+            Self::F => "F",
+        }
+    }
+
+    /// Badge color, green to red.
+    fn color(&self) -> &'static str {
+        match self {
+            Self::A => "#4c1",
+            Self::B => "#97ca00",
+            Self::C => "#dfb317",
+            Self::D => "#fe7d37",
+            Self::F => "#e05d44",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScorecardTrend {
+    Up,
+    Down,
+    Flat,
+}
+
+impl ScorecardTrend {
+    /// Trend from `previous` to `current`; flat without a previous score.
+    pub fn between(previous: Option<f64>, current: f64) -> Self {
+        match previous {
+            Some(previous) if current - previous >= TREND_THRESHOLD => Self::Up,
+            Some(previous) if previous - current >= TREND_THRESHOLD => Self::Down,
+            _ => Self::Flat,
+        }
+    }
+
+    fn arrow(&self) -> &'static str {
+        match self {
+            Self::Up => " \u{2191}",
+            Self::Down => " \u{2193}",
+            Self::Flat => "",
+        }
+    }
+}
+
+/// A repository's security posture on one day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scorecard {
+    pub repository_id: Uuid,
+    pub score: f64,
+    pub grade: Grade,
+    pub components: ScorecardComponents,
+    pub inputs: ScorecardInputs,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl Scorecard {
+    pub fn compute(repository_id: Uuid, inputs: ScorecardInputs, now: DateTime<Utc>) -> Self {
+        let components = ScorecardComponents::from_inputs(&inputs, now);
+        let score = components.overall();
+        Self {
+            repository_id,
+            score,
+            grade: Grade::from_score(score),
+            components,
+            inputs,
+            computed_at: now,
+        }
+    }
+}
+
+/// The last scorecard recorded on a day.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ScorecardHistoryEntry {
+    pub recorded_on: NaiveDate,
+    pub score: f64,
+    pub grade: Grade,
+}
+
+/// A shields-style SVG badge reading `security | B ↑`.
+pub fn scorecard_badge_svg(grade: Grade, trend: ScorecardTrend) -> String {
+    const LABEL: &str = "security";
+    // Verdana 11px averages about 7px per character
+    let value = format!("{}{}", grade.as_str(), trend.arrow());
+    let label_width = 10 + 7 * LABEL.len();
+    let value_width = 10 + 7 * value.chars().count();
+    let width = label_width + value_width;
+    format!(
+        concat!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" "##,
+            r##"aria-label="{label}: {value}"><title>{label}: {value}</title>"##,
+            r##"<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/>"##,
+            r##"<stop offset="1" stop-opacity=".1"/></linearGradient>"##,
+            r##"<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>"##,
+            r##"<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/>"##,
+            r##"<rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/>"##,
+            r##"<rect width="{width}" height="20" fill="url(#s)"/></g>"##,
+            r##"<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" "##,
+            r##"font-size="11"><text x="{label_x}" y="14">{label}</text>"##,
+            r##"<text x="{value_x}" y="14">{value}</text></g></svg>"##,
+        ),
+        width = width,
+        label = LABEL,
+        value = value,
+        label_width = label_width,
+        value_width = value_width,
+        color = grade.color(),
+        label_x = label_width / 2,
+        value_x = label_width + value_width / 2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn components_without_data_are_left_out() {
+        let now = Utc::now();
+        let inputs = ScorecardInputs {
+            open_high: 1,
+            open_low: 5,
+            last_analyzed_at: Some(now - Duration::days(2)),
+            ..ScorecardInputs::default()
+        };
+        let scorecard = Scorecard::compute(Uuid::new_v4(), inputs, now);
+
+        assert_eq!(scorecard.components.vulnerabilities, 85.0);
+        assert_eq!(scorecard.components.patch_latency, None);
+        // (85 * 0.4 + 100 * 0.2 + 100 * 0.2) / 0.8
+        assert_eq!(scorecard.score, 92.5);
+        assert_eq!(scorecard.grade, Grade::A);
+    }
+
+    #[test]
+    fn stale_unpatched_repositories_fail() {
+        let inputs = ScorecardInputs {
+            open_critical: 3,
+            median_patch_latency_hours: Some(PATCH_LATENCY_ZERO_HOURS),
+            ..ScorecardInputs::default()
+        };
+        let scorecard = Scorecard::compute(Uuid::new_v4(), inputs, Utc::now());
+
+        assert_eq!(scorecard.components.freshness, 0.0);
+        assert_eq!(scorecard.grade, Grade::F);
+    }
+
+    #[test]
+    fn trend_needs_a_meaningful_change() {
+        assert_eq!(ScorecardTrend::between(None, 80.0), ScorecardTrend::Flat);
+        assert_eq!(ScorecardTrend::between(Some(79.0), 80.0), ScorecardTrend::Flat);
+        assert_eq!(ScorecardTrend::between(Some(75.0), 80.0), ScorecardTrend::Up);
+        assert!(scorecard_badge_svg(Grade::B, ScorecardTrend::Down).contains(">B \u{2193}</text>"));
+    }
+}
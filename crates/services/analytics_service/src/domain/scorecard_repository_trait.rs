@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::scorecard::{Scorecard, ScorecardHistoryEntry, ScorecardInputs};
+use crate::Result;
+
+#[async_trait]
+pub trait ScorecardRepository: Send + Sync {
+    /// Whether `repository_id` is a public repository.
+    async fn is_public_repository(&self, repository_id: Uuid) -> Result<bool>;
+
+    /// Public repositories that have been analyzed at least once.
+    async fn list_scored_repositories(&self) -> Result<Vec<Uuid>>;
+
+    async fn load_inputs(&self, repository_id: Uuid) -> Result<ScorecardInputs>;
+
+    /// Record `scorecard` as the repository's scorecard of its day,
+    /// replacing one recorded earlier that day.
+    async fn record(&self, scorecard: &Scorecard) -> Result<()>;
+
+    /// The score last recorded on or before `on`.
+    async fn score_on(&self, repository_id: Uuid, on: NaiveDate) -> Result<Option<f64>>;
+
+    /// The latest `limit` days recorded, newest first.
+    async fn history(&self, repository_id: Uuid, limit: i64) -> Result<Vec<ScorecardHistoryEntry>>;
+}
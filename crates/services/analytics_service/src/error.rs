@@ -5,16 +5,40 @@ use std::fmt;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "analytics")]
 pub enum Error {
+    #[taxonomy(kind = NotFound, expose)]
     DeveloperNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
     RepositoryNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
     TeamNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
+    ExportNotFound(String),
+    #[taxonomy(kind = Conflict, expose)]
+    ExportNotReady(String),
+    #[taxonomy(kind = NotFound, expose)]
+    SavedQueryNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
+    ReportRunNotFound(String),
+    #[taxonomy(kind = Validation, expose)]
     InvalidTimeRange,
+    #[taxonomy(kind = Validation, expose)]
     InvalidFilter(String),
+    #[taxonomy(kind = Validation, expose)]
+    InvalidSchedule(String),
+    #[taxonomy(kind = Upstream)]
+    DeliveryError(String),
+    #[taxonomy(kind = Upstream)]
+    StorageError(String),
+    #[taxonomy(kind = Internal)]
     CalculationError(String),
+    #[taxonomy(kind = Internal)]
     DatabaseError(String),
+    #[taxonomy(kind = Internal)]
     ServiceError(String),
+    #[taxonomy(kind = Unauthenticated)]
     CtxError(String),
 }
 
@@ -30,14 +54,27 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<jd_object_store::Error> for Error {
+    fn from(e: jd_object_store::Error) -> Self {
+        Error::StorageError(e.to_string())
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::DeveloperNotFound(id) => write!(f, "Developer not found: {}", id),
             Error::RepositoryNotFound(id) => write!(f, "Repository not found: {}", id),
             Error::TeamNotFound(id) => write!(f, "Team not found: {}", id),
+            Error::ExportNotFound(id) => write!(f, "Export not found: {}", id),
+            Error::ExportNotReady(id) => write!(f, "Export is not ready: {}", id),
+            Error::SavedQueryNotFound(id) => write!(f, "Saved query not found: {}", id),
+            Error::ReportRunNotFound(id) => write!(f, "Report run not found: {}", id),
             Error::InvalidTimeRange => write!(f, "Invalid time range specified"),
             Error::InvalidFilter(msg) => write!(f, "Invalid filter: {}", msg),
+            Error::InvalidSchedule(msg) => write!(f, "Invalid schedule: {}", msg),
+            Error::DeliveryError(msg) => write!(f, "Delivery failed: {}", msg),
+            Error::StorageError(msg) => write!(f, "Object storage error: {}", msg),
             Error::CalculationError(msg) => write!(f, "Calculation error: {}", msg),
             Error::DatabaseError(msg) => write!(f, "Database error: {}", msg),
             Error::ServiceError(msg) => write!(f, "Service error: {}", msg),
@@ -51,10 +88,15 @@ impl std::error::Error for Error {}
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
         let status_code = match &self {
-            Error::DeveloperNotFound(_) | Error::RepositoryNotFound(_) | Error::TeamNotFound(_) => {
-                StatusCode::NOT_FOUND
-            }
-            Error::InvalidTimeRange | Error::InvalidFilter(_) => StatusCode::BAD_REQUEST,
+            Error::DeveloperNotFound(_)
+            | Error::RepositoryNotFound(_)
+            | Error::TeamNotFound(_)
+            | Error::ExportNotFound(_)
+            | Error::SavedQueryNotFound(_)
+            | Error::ReportRunNotFound(_) => StatusCode::NOT_FOUND,
+            Error::ExportNotReady(_) => StatusCode::CONFLICT,
+            Error::InvalidTimeRange | Error::InvalidFilter(_) | Error::InvalidSchedule(_) => StatusCode::BAD_REQUEST,
+            Error::DeliveryError(_) | Error::StorageError(_) => StatusCode::BAD_GATEWAY,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::{AlertStatus, AnalysisWindow, AnalyticsAlert, Anomaly, AnomalyMetric, AnomalyRepository};
+use crate::Result;
+
+const ALERT_COLUMNS: &str = "id, metric, subject, status, observed, expected, threshold, details, occurrences, \
+                             first_detected_at, last_detected_at, notified_at, notify_error, resolved_at";
+
+pub struct AnomalyRepositoryImpl {
+    db_pool: Pool<Postgres>,
+}
+
+impl AnomalyRepositoryImpl {
+    pub fn new(db_pool: Pool<Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl AnomalyRepository for AnomalyRepositoryImpl {
+    async fn critical_findings(
+        &self,
+        now: DateTime<Utc>,
+        window: Duration,
+        count: usize,
+    ) -> Result<Vec<(Uuid, Vec<i64>)>> {
+        // Window k covers (now - (k + 1) * window, now - k * window]
+        let rows = sqlx::query_as::<_, (Uuid, Vec<i64>)>(
+            r#"
+            WITH counts AS (
+                SELECT v.repository_id,
+                       FLOOR(EXTRACT(EPOCH FROM $1 - v.ctime) / $2)::INT AS k,
+                       COUNT(*) AS findings
+                FROM security_vulnerabilities v
+                JOIN github_repositories r ON r.id = v.repository_id
+                WHERE v.severity::TEXT = 'critical'
+                  AND v.ctime <= $1
+                  AND v.ctime > $1 - make_interval(secs => $2 * ($3 + 1))
+                  AND r.ctime <= $1 - make_interval(secs => $2 * ($3 + 1))
+                GROUP BY 1, 2
+            )
+            SELECT repository.repository_id,
+                   ARRAY(
+                       SELECT COALESCE(c.findings, 0)
+                       FROM generate_series(0, $3) AS w(k)
+                       LEFT JOIN counts c ON c.repository_id = repository.repository_id AND c.k = w.k
+                       ORDER BY w.k
+                   ) AS findings
+            FROM (SELECT DISTINCT repository_id FROM counts) repository
+            "#,
+        )
+        .bind(now)
+        .bind(window.as_secs_f64())
+        .bind(count as i32)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(rows)
+    }
+
+    async fn analysis_windows(
+        &self,
+        now: DateTime<Utc>,
+        window: Duration,
+        stride: Duration,
+        count: usize,
+    ) -> Result<Vec<AnalysisWindow>> {
+        let windows = sqlx::query_as::<_, AnalysisWindow>(
+            r#"
+            SELECT COUNT(j.id) AS finished,
+                   COUNT(j.id) FILTER (WHERE j.status IN ('failed', 'dead_lettered')) AS failed
+            FROM generate_series(0, $4) AS w(k)
+            LEFT JOIN analysis_jobs j
+                ON j.status IN ('completed', 'failed', 'dead_lettered')
+               AND j.updated_at <= $1 - make_interval(secs => w.k * $3)
+               AND j.updated_at > $1 - make_interval(secs => w.k * $3 + $2)
+            GROUP BY w.k
+            ORDER BY w.k
+            "#,
+        )
+        .bind(now)
+        .bind(window.as_secs_f64())
+        .bind(stride.as_secs_f64())
+        .bind(count as i32)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(windows)
+    }
+
+    async fn scoring_counts(
+        &self,
+        now: DateTime<Utc>,
+        window: Duration,
+        stride: Duration,
+        count: usize,
+    ) -> Result<Vec<i64>> {
+        let counts = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(s.id)
+            FROM generate_series(0, $4) AS w(k)
+            LEFT JOIN scoring_results s
+                ON s.timestamp <= $1 - make_interval(secs => w.k * $3)
+               AND s.timestamp > $1 - make_interval(secs => w.k * $3 + $2)
+            GROUP BY w.k
+            ORDER BY w.k
+            "#,
+        )
+        .bind(now)
+        .bind(window.as_secs_f64())
+        .bind(stride.as_secs_f64())
+        .bind(count as i32)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(counts)
+    }
+
+    async fn raise(&self, anomaly: &Anomaly, now: DateTime<Utc>) -> Result<AnalyticsAlert> {
+        let alert = sqlx::query_as::<_, AnalyticsAlert>(&format!(
+            r#"
+            INSERT INTO analytics_alerts (
+                metric, subject, observed, expected, threshold, details, first_detected_at, last_detected_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $7)
+            ON CONFLICT (metric, subject) WHERE status = 'open' DO UPDATE
+            SET observed = EXCLUDED.observed,
+                expected = EXCLUDED.expected,
+                threshold = EXCLUDED.threshold,
+                details = EXCLUDED.details,
+                occurrences = analytics_alerts.occurrences + 1,
+                last_detected_at = EXCLUDED.last_detected_at
+            RETURNING {}
+            "#,
+            ALERT_COLUMNS
+        ))
+        .bind(anomaly.metric)
+        .bind(&anomaly.subject)
+        .bind(anomaly.observed)
+        .bind(anomaly.expected)
+        .bind(anomaly.threshold)
+        .bind(Json(&anomaly.details))
+        .bind(now)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(alert)
+    }
+
+    async fn resolve_cleared(
+        &self,
+        metric: AnomalyMetric,
+        still_anomalous: &[String],
+        now: DateTime<Utc>,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE analytics_alerts
+            SET status = 'resolved', resolved_at = $3
+            WHERE metric = $1 AND status = 'open' AND subject <> ALL($2)
+            "#,
+        )
+        .bind(metric)
+        .bind(still_anomalous)
+        .bind(now)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn last_notified_at(&self, metric: AnomalyMetric, subject: &str) -> Result<Option<DateTime<Utc>>> {
+        let notified_at = sqlx::query_scalar(
+            "SELECT MAX(notified_at) FROM analytics_alerts WHERE metric = $1 AND subject = $2",
+        )
+        .bind(metric)
+        .bind(subject)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(notified_at)
+    }
+
+    async fn record_notification(
+        &self,
+        id: Uuid,
+        notified_at: Option<DateTime<Utc>>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE analytics_alerts
+            SET notified_at = COALESCE($2, notified_at), notify_error = $3
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(notified_at)
+        .bind(error)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn list(&self, status: Option<AlertStatus>, limit: i64) -> Result<Vec<AnalyticsAlert>> {
+        let alerts = sqlx::query_as::<_, AnalyticsAlert>(&format!(
+            r#"
+            SELECT {}
+            FROM analytics_alerts
+            WHERE ($1::VARCHAR IS NULL OR status = $1)
+            ORDER BY last_detected_at DESC
+            LIMIT $2
+            "#,
+            ALERT_COLUMNS
+        ))
+        .bind(status)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(alerts)
+    }
+}
@@ -0,0 +1,245 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use jd_storage::Dbx;
+use serde_json::Value;
+use sqlx::types::Json;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::{AnalyticsExport, ExportDataset, ExportFile, ExportQuery, ExportRepository, RecordStream};
+use crate::{Error, Result};
+
+const EXPORT_COLUMNS: &str = "id, requested_by, dataset, format, range_from, range_to, include_undisclosed, \
+                              status, attempts, row_count, byte_size, error, created_at, completed_at, expires_at";
+
+/// Bounds are `$1` (inclusive) and `$2` (exclusive), either NULL for open.
+/// Findings not yet public are left out unless `$3`.
+const VULNERABILITY_RECORDS_SQL: &str = r#"
+    SELECT json_build_object(
+        'id', v.id,
+        'repository_id', v.repository_id,
+        'analysis_result_id', v.analysis_result_id,
+        'vulnerability_type', v.vulnerability_type,
+        'severity', v.severity,
+        'confidence_score', v.confidence_score,
+        'file_path', v.file_path,
+        'line_number', v.line_number,
+        'cve_id', v.cve_id,
+        'disclosure_state', v.disclosure_state,
+        'is_false_positive', v.is_false_positive,
+        'fixed_at', v.fixed_at,
+        'created_at', v.ctime
+    )
+    FROM security_vulnerabilities v
+    WHERE ($1::TIMESTAMPTZ IS NULL OR v.ctime >= $1)
+      AND ($2::TIMESTAMPTZ IS NULL OR v.ctime < $2)
+      AND ($3 OR v.disclosure_state = 'public')
+    ORDER BY v.ctime, v.id
+"#;
+
+const SCORE_RECORDS_SQL: &str = r#"
+    SELECT json_build_object(
+        'id', s.id,
+        'behavior_input_id', s.behavior_input_id,
+        'score', s.score,
+        'model_version', s.model_version,
+        'scored_at', s.timestamp
+    )
+    FROM scoring_results s
+    WHERE ($1::TIMESTAMPTZ IS NULL OR s.timestamp >= $1)
+      AND ($2::TIMESTAMPTZ IS NULL OR s.timestamp < $2)
+    ORDER BY s.timestamp, s.id
+"#;
+
+const ANALYSIS_RECORDS_SQL: &str = r#"
+    SELECT json_build_object(
+        'id', a.id,
+        'repository_id', a.repository_id,
+        'commit_sha', a.commit_sha,
+        'analysis_type', a.analysis_type,
+        'security_score', a.security_score,
+        'quality_score', a.quality_score,
+        'issues_found', a.issues_found,
+        'critical_issues', a.critical_issues,
+        'analysis_duration_ms', a.analysis_duration_ms,
+        'analyzer_version', a.analyzer_version,
+        'created_at', a.ctime
+    )
+    FROM code_analysis_results a
+    WHERE ($1::TIMESTAMPTZ IS NULL OR a.ctime >= $1)
+      AND ($2::TIMESTAMPTZ IS NULL OR a.ctime < $2)
+    ORDER BY a.ctime, a.id
+"#;
+
+pub struct ExportRepositoryImpl {
+    dbx: Dbx,
+}
+
+impl ExportRepositoryImpl {
+    pub fn new(dbx: Dbx) -> Self {
+        Self { dbx }
+    }
+}
+
+#[async_trait]
+impl ExportRepository for ExportRepositoryImpl {
+    fn records(&self, query: &ExportQuery) -> RecordStream {
+        let sql = match query.dataset {
+            ExportDataset::Vulnerabilities => VULNERABILITY_RECORDS_SQL,
+            ExportDataset::Scores => SCORE_RECORDS_SQL,
+            ExportDataset::Analyses => ANALYSIS_RECORDS_SQL,
+        };
+        let mut records = sqlx::query_as::<_, (Json<Value>,)>(sql).bind(query.from).bind(query.to);
+        if query.dataset == ExportDataset::Vulnerabilities {
+            records = records.bind(query.include_undisclosed);
+        }
+        self.dbx
+            .fetch_stream(records)
+            .map(|row| {
+                row.map(|(Json(record),)| record)
+                    .map_err(|e| Error::DatabaseError(e.to_string()))
+            })
+            .boxed()
+    }
+
+    async fn create(&self, requested_by: &str, query: &ExportQuery) -> Result<AnalyticsExport> {
+        let export = sqlx::query_as::<_, AnalyticsExport>(&format!(
+            r#"
+            INSERT INTO analytics_exports (requested_by, dataset, format, range_from, range_to, include_undisclosed)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
+            "#,
+            EXPORT_COLUMNS
+        ))
+        .bind(requested_by)
+        .bind(query.dataset)
+        .bind(query.format)
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.include_undisclosed)
+        .fetch_one(self.dbx.db())
+        .await?;
+        Ok(export)
+    }
+
+    async fn find(&self, id: Uuid, requested_by: &str) -> Result<Option<AnalyticsExport>> {
+        let export = sqlx::query_as::<_, AnalyticsExport>(&format!(
+            "SELECT {} FROM analytics_exports WHERE id = $1 AND requested_by = $2",
+            EXPORT_COLUMNS
+        ))
+        .bind(id)
+        .bind(requested_by)
+        .fetch_optional(self.dbx.db())
+        .await?;
+        Ok(export)
+    }
+
+    async fn claim_next(&self, lease: Duration, max_attempts: i32) -> Result<Option<AnalyticsExport>> {
+        let export = sqlx::query_as::<_, AnalyticsExport>(&format!(
+            r#"
+            UPDATE analytics_exports e
+            SET status = 'running',
+                attempts = e.attempts + 1,
+                lease_until = NOW() + make_interval(secs => $1)
+            FROM (
+                SELECT id AS next_id
+                FROM analytics_exports
+                WHERE attempts < $2
+                  AND (status = 'queued' OR (status = 'running' AND lease_until < NOW()))
+                ORDER BY created_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            ) next
+            WHERE e.id = next.next_id
+            RETURNING {}
+            "#,
+            EXPORT_COLUMNS
+        ))
+        .bind(lease.as_secs_f64())
+        .bind(max_attempts)
+        .fetch_optional(self.dbx.db())
+        .await?;
+        Ok(export)
+    }
+
+    async fn complete(&self, id: Uuid, row_count: i64, file: ExportFile, expires_at: DateTime<Utc>) -> Result<()> {
+        let (byte_size, content, content_uri) = match file {
+            ExportFile::Inline(content) => (content.len() as i64, Some(content), None),
+            ExportFile::Stored { uri, byte_size } => (byte_size, None, Some(uri)),
+        };
+        sqlx::query(
+            r#"
+            UPDATE analytics_exports
+            SET status = 'ready',
+                row_count = $2,
+                byte_size = $3,
+                content = $4,
+                content_uri = $5,
+                error = NULL,
+                lease_until = NULL,
+                completed_at = NOW(),
+                expires_at = $6
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(row_count)
+        .bind(byte_size)
+        .bind(content)
+        .bind(content_uri)
+        .bind(expires_at)
+        .execute(self.dbx.db())
+        .await?;
+        Ok(())
+    }
+
+    async fn fail(&self, id: Uuid, error: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE analytics_exports
+            SET status = 'failed', error = $2, lease_until = NULL, completed_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .execute(self.dbx.db())
+        .await?;
+        Ok(())
+    }
+
+    async fn file(&self, id: Uuid, requested_by: &str) -> Result<Option<ExportFile>> {
+        let row: Option<(Option<Vec<u8>>, Option<String>, Option<i64>)> = sqlx::query_as(
+            r#"
+            SELECT content, content_uri, byte_size
+            FROM analytics_exports
+            WHERE id = $1 AND requested_by = $2 AND status = 'ready'
+            "#,
+        )
+        .bind(id)
+        .bind(requested_by)
+        .fetch_optional(self.dbx.db())
+        .await?;
+        Ok(row.and_then(|row| match row {
+            (_, Some(uri), byte_size) => Some(ExportFile::Stored { uri, byte_size: byte_size.unwrap_or_default() }),
+            (Some(content), None, _) => Some(ExportFile::Inline(content)),
+            (None, None, _) => None,
+        }))
+    }
+
+    async fn purge_expired(&self) -> Result<u64> {
+        // Exports that never became ready are kept a week for inspection
+        let purged = sqlx::query(
+            r#"
+            DELETE FROM analytics_exports
+            WHERE expires_at < NOW()
+               OR (status = 'failed' AND completed_at < NOW() - INTERVAL '7 days')
+            "#,
+        )
+        .execute(self.dbx.db())
+        .await?
+        .rows_affected();
+        Ok(purged)
+    }
+}
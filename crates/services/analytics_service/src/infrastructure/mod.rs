@@ -1,3 +1,17 @@
 pub mod analytics_repository_impl;
+pub mod anomaly_repository_impl;
+pub mod export_repository_impl;
+pub mod report_delivery_impl;
+pub mod report_repository_impl;
+pub mod rollup_repository_impl;
+pub mod scorecard_repository_impl;
+pub mod webhook_alert_notifier;
 
-pub use analytics_repository_impl::AnalyticsRepositoryImpl;
\ No newline at end of file
+pub use analytics_repository_impl::AnalyticsRepositoryImpl;
+pub use anomaly_repository_impl::AnomalyRepositoryImpl;
+pub use export_repository_impl::ExportRepositoryImpl;
+pub use report_delivery_impl::HttpReportDelivery;
+pub use report_repository_impl::ReportRepositoryImpl;
+pub use rollup_repository_impl::RollupRepositoryImpl;
+pub use scorecard_repository_impl::ScorecardRepositoryImpl;
+pub use webhook_alert_notifier::WebhookAlertNotifier;
\ No newline at end of file
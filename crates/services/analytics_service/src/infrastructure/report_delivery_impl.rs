@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::domain::{report_payload, sign_report, DeliveryChannel, ReportDelivery, ReportRun, SavedQuery};
+use crate::{Error, Result};
+
+/// Delivers reports over HTTP: signed POSTs to the query's webhook, and
+/// emails through a relay that mails the payload to the recipient.
+pub struct HttpReportDelivery {
+    client: Client,
+    email_relay_url: Option<String>,
+}
+
+impl HttpReportDelivery {
+    pub fn new(email_relay_url: Option<String>) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { client, email_relay_url }
+    }
+
+    /// Emails go through the relay at `REPORT_EMAIL_RELAY_URL`, if set.
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("REPORT_EMAIL_RELAY_URL")
+                .ok()
+                .filter(|url| !url.trim().is_empty()),
+        )
+    }
+
+    async fn post(&self, url: &str, body: Vec<u8>, signature: Option<(i64, String)>) -> Result<()> {
+        let mut request = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        if let Some((timestamp, signature)) = signature {
+            request = request
+                .header("X-Report-Timestamp", timestamp.to_string())
+                .header("X-Report-Signature", signature);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::DeliveryError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(Error::DeliveryError(format!("{} returned {}", url, response.status())));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReportDelivery for HttpReportDelivery {
+    async fn deliver(&self, query: &SavedQuery, run: &ReportRun, rows: &[Value]) -> Result<()> {
+        let Some(target) = query.delivery_target.as_deref() else {
+            return Ok(());
+        };
+        let payload = report_payload(query, run, rows);
+        match query.delivery_channel {
+            DeliveryChannel::None => Ok(()),
+            DeliveryChannel::Webhook => {
+                let secret = query
+                    .webhook_secret
+                    .as_deref()
+                    .ok_or_else(|| Error::DeliveryError("Query has no webhook secret".to_string()))?;
+                let body = serde_json::to_vec(&payload)?;
+                let timestamp = Utc::now().timestamp();
+                let signature = sign_report(secret, timestamp, &body);
+                self.post(target, body, Some((timestamp, signature))).await
+            }
+            DeliveryChannel::Email => {
+                let relay = self
+                    .email_relay_url
+                    .as_deref()
+                    .ok_or_else(|| Error::DeliveryError("REPORT_EMAIL_RELAY_URL is not set".to_string()))?;
+                let email = json!({
+                    "to": target,
+                    "subject": format!("Analytics report: {}", query.name),
+                    "report": payload,
+                });
+                self.post(relay, serde_json::to_vec(&email)?, None).await
+            }
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::types::Json;
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::{
+    DeliveryStatus, ReportDataset, ReportFilters, ReportRepository, ReportRun, ReportTrigger, SavedQuery,
+    SavedQueryDefinition,
+};
+use crate::Result;
+
+const QUERY_COLUMNS: &str = "id, owner, name, dataset, filters, include_undisclosed, schedule, delivery_channel, \
+                             delivery_target, webhook_secret, enabled, next_run_at, last_run_at, created_at, \
+                             updated_at";
+
+const RUN_COLUMNS: &str = "r.id, r.query_id, r.trigger, r.status, r.range_from, r.range_to, r.row_count, \
+                           r.error, r.delivery_status, r.delivery_error, r.started_at, r.finished_at";
+
+/// Bounds are `$1` (inclusive, NULL for open) and `$2` (exclusive). Newest
+/// first; findings not yet public are left out unless `$3`.
+const VULNERABILITY_ROWS_SQL: &str = r#"
+    SELECT json_build_object(
+        'id', v.id,
+        'repository_id', v.repository_id,
+        'vulnerability_type', v.vulnerability_type,
+        'severity', v.severity,
+        'confidence_score', v.confidence_score,
+        'file_path', v.file_path,
+        'line_number', v.line_number,
+        'cve_id', v.cve_id,
+        'disclosure_state', v.disclosure_state,
+        'is_false_positive', v.is_false_positive,
+        'fixed_at', v.fixed_at,
+        'resolved_at', v.resolved_at,
+        'created_at', v.ctime
+    )
+    FROM security_vulnerabilities v
+    WHERE ($1::TIMESTAMPTZ IS NULL OR v.ctime >= $1)
+      AND v.ctime < $2
+      AND ($3 OR v.disclosure_state = 'public')
+      AND ($4::UUID IS NULL OR v.repository_id = $4)
+      AND (CARDINALITY($5::TEXT[]) = 0 OR v.severity::TEXT = ANY($5))
+      AND ($6::TEXT IS NULL OR v.vulnerability_type::TEXT = $6)
+      AND (NOT $7 OR (v.fixed_at IS NULL AND v.resolved_at IS NULL AND NOT v.is_false_positive))
+    ORDER BY v.ctime DESC, v.id
+    LIMIT $8
+"#;
+
+/// Newest first.
+const SCORE_ROWS_SQL: &str = r#"
+    SELECT json_build_object(
+        'id', s.id,
+        'behavior_input_id', s.behavior_input_id,
+        'score', s.score,
+        'model_version', s.model_version,
+        'scored_at', s.timestamp
+    )
+    FROM scoring_results s
+    WHERE ($1::TIMESTAMPTZ IS NULL OR s.timestamp >= $1)
+      AND s.timestamp < $2
+      AND ($3::FLOAT8 IS NULL OR s.score >= $3)
+      AND ($4::FLOAT8 IS NULL OR s.score <= $4)
+      AND ($5::TEXT IS NULL OR s.model_version = $5)
+    ORDER BY s.timestamp DESC, s.id
+    LIMIT $6
+"#;
+
+/// Public repositories analyzed in the range, lowest security score first.
+/// Open findings not yet public are only counted if `$3`.
+const REPOSITORY_ROWS_SQL: &str = r#"
+    SELECT json_build_object(
+        'id', r.id,
+        'full_name', r.full_name,
+        'primary_language', r.primary_language,
+        'star_count', r.star_count,
+        'security_score', r.security_score,
+        'open_vulnerabilities', (
+            SELECT COUNT(*)
+            FROM security_vulnerabilities v
+            WHERE v.repository_id = r.id
+              AND v.fixed_at IS NULL
+              AND v.resolved_at IS NULL
+              AND NOT v.is_false_positive
+              AND ($3 OR v.disclosure_state = 'public')
+        ),
+        'last_analyzed_at', r.last_analyzed_at
+    )
+    FROM github_repositories r
+    WHERE NOT r.is_private
+      AND ($1::TIMESTAMPTZ IS NULL OR (r.last_analyzed_at >= $1 AND r.last_analyzed_at < $2))
+      AND ($4::UUID IS NULL OR r.id = $4)
+      AND ($5::FLOAT8 IS NULL OR r.security_score >= $5)
+      AND ($6::FLOAT8 IS NULL OR r.security_score <= $6)
+      AND ($7::TEXT IS NULL OR LOWER(r.primary_language) = LOWER($7))
+    ORDER BY r.security_score NULLS LAST, r.full_name
+    LIMIT $8
+"#;
+
+pub struct ReportRepositoryImpl {
+    db_pool: Pool<Postgres>,
+}
+
+impl ReportRepositoryImpl {
+    pub fn new(db_pool: Pool<Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ReportRepository for ReportRepositoryImpl {
+    async fn create(
+        &self,
+        owner: &str,
+        definition: &SavedQueryDefinition,
+        next_run_at: Option<DateTime<Utc>>,
+        webhook_secret: Option<&str>,
+    ) -> Result<SavedQuery> {
+        let query = sqlx::query_as::<_, SavedQuery>(&format!(
+            r#"
+            INSERT INTO saved_analytics_queries (
+                owner, name, dataset, filters, include_undisclosed, schedule, delivery_channel,
+                delivery_target, webhook_secret, enabled, next_run_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING {}
+            "#,
+            QUERY_COLUMNS
+        ))
+        .bind(owner)
+        .bind(&definition.name)
+        .bind(definition.dataset)
+        .bind(Json(&definition.filters))
+        .bind(definition.include_undisclosed)
+        .bind(&definition.schedule)
+        .bind(definition.delivery_channel)
+        .bind(&definition.delivery_target)
+        .bind(webhook_secret)
+        .bind(definition.enabled)
+        .bind(next_run_at)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(query)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        owner: &str,
+        definition: &SavedQueryDefinition,
+        next_run_at: Option<DateTime<Utc>>,
+        webhook_secret: Option<&str>,
+    ) -> Result<Option<SavedQuery>> {
+        let query = sqlx::query_as::<_, SavedQuery>(&format!(
+            r#"
+            UPDATE saved_analytics_queries
+            SET name = $3,
+                dataset = $4,
+                filters = $5,
+                include_undisclosed = $6,
+                schedule = $7,
+                delivery_channel = $8,
+                delivery_target = $9,
+                webhook_secret = COALESCE($10, webhook_secret),
+                enabled = $11,
+                next_run_at = $12,
+                updated_at = NOW()
+            WHERE id = $1 AND owner = $2
+            RETURNING {}
+            "#,
+            QUERY_COLUMNS
+        ))
+        .bind(id)
+        .bind(owner)
+        .bind(&definition.name)
+        .bind(definition.dataset)
+        .bind(Json(&definition.filters))
+        .bind(definition.include_undisclosed)
+        .bind(&definition.schedule)
+        .bind(definition.delivery_channel)
+        .bind(&definition.delivery_target)
+        .bind(webhook_secret)
+        .bind(definition.enabled)
+        .bind(next_run_at)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(query)
+    }
+
+    async fn delete(&self, id: Uuid, owner: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM saved_analytics_queries WHERE id = $1 AND owner = $2")
+            .bind(id)
+            .bind(owner)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find(&self, id: Uuid, owner: &str) -> Result<Option<SavedQuery>> {
+        let query = sqlx::query_as::<_, SavedQuery>(&format!(
+            "SELECT {} FROM saved_analytics_queries WHERE id = $1 AND owner = $2",
+            QUERY_COLUMNS
+        ))
+        .bind(id)
+        .bind(owner)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(query)
+    }
+
+    async fn list(&self, owner: &str) -> Result<Vec<SavedQuery>> {
+        let queries = sqlx::query_as::<_, SavedQuery>(&format!(
+            "SELECT {} FROM saved_analytics_queries WHERE owner = $1 ORDER BY created_at",
+            QUERY_COLUMNS
+        ))
+        .bind(owner)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(queries)
+    }
+
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<SavedQuery>> {
+        let queries = sqlx::query_as::<_, SavedQuery>(&format!(
+            r#"
+            UPDATE saved_analytics_queries
+            SET leased_until = NOW() + make_interval(secs => $2)
+            WHERE id IN (
+                SELECT id
+                FROM saved_analytics_queries
+                WHERE enabled
+                  AND next_run_at <= NOW()
+                  AND (leased_until IS NULL OR leased_until < NOW())
+                ORDER BY next_run_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            QUERY_COLUMNS
+        ))
+        .bind(limit)
+        .bind(lease.as_secs_f64())
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(queries)
+    }
+
+    async fn mark_ran(&self, id: Uuid, ran_at: DateTime<Utc>, next_run_at: Option<DateTime<Utc>>) -> Result<()> {
+        // The schedule may have been removed while the query ran
+        sqlx::query(
+            r#"
+            UPDATE saved_analytics_queries
+            SET last_run_at = $2,
+                next_run_at = CASE WHEN schedule IS NULL THEN NULL ELSE COALESCE($3, next_run_at) END,
+                leased_until = CASE WHEN $3::TIMESTAMPTZ IS NULL THEN leased_until END
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(ran_at)
+        .bind(next_run_at)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn fetch_rows(
+        &self,
+        dataset: ReportDataset,
+        filters: &ReportFilters,
+        include_undisclosed: bool,
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Value>> {
+        let rows = match dataset {
+            ReportDataset::Vulnerabilities => {
+                sqlx::query_as::<_, (Json<Value>,)>(VULNERABILITY_ROWS_SQL)
+                    .bind(from)
+                    .bind(to)
+                    .bind(include_undisclosed)
+                    .bind(filters.repository_id)
+                    .bind(&filters.severities)
+                    .bind(&filters.vulnerability_type)
+                    .bind(filters.open_only)
+                    .bind(filters.limit())
+                    .fetch_all(&self.db_pool)
+                    .await?
+            }
+            ReportDataset::Scores => {
+                sqlx::query_as::<_, (Json<Value>,)>(SCORE_ROWS_SQL)
+                    .bind(from)
+                    .bind(to)
+                    .bind(filters.min_score)
+                    .bind(filters.max_score)
+                    .bind(&filters.model_version)
+                    .bind(filters.limit())
+                    .fetch_all(&self.db_pool)
+                    .await?
+            }
+            ReportDataset::Repositories => {
+                sqlx::query_as::<_, (Json<Value>,)>(REPOSITORY_ROWS_SQL)
+                    .bind(from)
+                    .bind(to)
+                    .bind(include_undisclosed)
+                    .bind(filters.repository_id)
+                    .bind(filters.min_score)
+                    .bind(filters.max_score)
+                    .bind(&filters.language)
+                    .bind(filters.limit())
+                    .fetch_all(&self.db_pool)
+                    .await?
+            }
+        };
+        Ok(rows.into_iter().map(|(Json(row),)| row).collect())
+    }
+
+    async fn start_run(
+        &self,
+        query_id: Uuid,
+        trigger: ReportTrigger,
+        from: Option<DateTime<Utc>>,
+        to: DateTime<Utc>,
+    ) -> Result<ReportRun> {
+        let run = sqlx::query_as::<_, ReportRun>(&format!(
+            r#"
+            INSERT INTO report_runs AS r (query_id, trigger, range_from, range_to)
+            VALUES ($1, $2, $3, $4)
+            RETURNING {}, NULL::JSONB AS output
+            "#,
+            RUN_COLUMNS
+        ))
+        .bind(query_id)
+        .bind(trigger)
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(run)
+    }
+
+    async fn complete_run(&self, run_id: Uuid, rows: &[Value]) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE report_runs
+            SET status = 'succeeded', row_count = $2, output = $3, finished_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(run_id)
+        .bind(rows.len() as i32)
+        .bind(Json(rows))
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn fail_run(&self, run_id: Uuid, error: &str) -> Result<()> {
+        sqlx::query("UPDATE report_runs SET status = 'failed', error = $2, finished_at = NOW() WHERE id = $1")
+            .bind(run_id)
+            .bind(error)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn record_delivery(&self, run_id: Uuid, status: DeliveryStatus, error: Option<&str>) -> Result<()> {
+        sqlx::query("UPDATE report_runs SET delivery_status = $2, delivery_error = $3 WHERE id = $1")
+            .bind(run_id)
+            .bind(status)
+            .bind(error)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn runs(&self, query_id: Uuid, owner: &str, limit: i64) -> Result<Vec<ReportRun>> {
+        let runs = sqlx::query_as::<_, ReportRun>(&format!(
+            r#"
+            SELECT {}, NULL::JSONB AS output
+            FROM report_runs r
+            JOIN saved_analytics_queries q ON q.id = r.query_id
+            WHERE r.query_id = $1 AND q.owner = $2
+            ORDER BY r.started_at DESC
+            LIMIT $3
+            "#,
+            RUN_COLUMNS
+        ))
+        .bind(query_id)
+        .bind(owner)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(runs)
+    }
+
+    async fn run(&self, run_id: Uuid, owner: &str) -> Result<Option<ReportRun>> {
+        let run = sqlx::query_as::<_, ReportRun>(&format!(
+            r#"
+            SELECT {}, r.output
+            FROM report_runs r
+            JOIN saved_analytics_queries q ON q.id = r.query_id
+            WHERE r.id = $1 AND q.owner = $2
+            "#,
+            RUN_COLUMNS
+        ))
+        .bind(run_id)
+        .bind(owner)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(run)
+    }
+}
@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::domain::{RollupGranularity, RollupRepository, RollupRow};
+use crate::Result;
+
+/// Advisory lock key serializing refreshes, so two instances rolling up the
+/// same periods cannot interleave their deletes and inserts.
+const ANALYTICS_ROLLUP_LOCK_KEY: i64 = 0xA11_2011;
+
+/// Aggregates per period over `$2` (inclusive) to `$3` (exclusive), both
+/// dates at UTC midnight, in `date_trunc` unit `$1`, for repository `$4` or
+/// all of them. Scores are not tied to a repository and are left out when
+/// one is asked for.
+const ROLLUP_ROWS_SQL: &str = r#"
+    SELECT date_trunc($1, v.ctime AT TIME ZONE 'UTC')::DATE AS period_start,
+           'vulnerabilities_found' AS metric,
+           v.severity::TEXT AS dimension,
+           v.repository_id,
+           COUNT(*) AS value
+    FROM security_vulnerabilities v
+    WHERE NOT v.is_false_positive
+      AND v.ctime >= $2::TIMESTAMP AT TIME ZONE 'UTC'
+      AND v.ctime < $3::TIMESTAMP AT TIME ZONE 'UTC'
+      AND ($4::UUID IS NULL OR v.repository_id = $4)
+    GROUP BY 1, 3, 4
+    UNION ALL
+    SELECT date_trunc($1, a.ctime AT TIME ZONE 'UTC')::DATE, 'analyses_run', '', a.repository_id, COUNT(*)
+    FROM code_analysis_results a
+    WHERE a.ctime >= $2::TIMESTAMP AT TIME ZONE 'UTC'
+      AND a.ctime < $3::TIMESTAMP AT TIME ZONE 'UTC'
+      AND ($4::UUID IS NULL OR a.repository_id = $4)
+    GROUP BY 1, 4
+    UNION ALL
+    SELECT date_trunc($1, p.applied_at AT TIME ZONE 'UTC')::DATE, 'patches_merged', '', p.repository_id, COUNT(*)
+    FROM patch_proposals p
+    WHERE p.status::TEXT IN ('applied', 'merged')
+      AND p.applied_at >= $2::TIMESTAMP AT TIME ZONE 'UTC'
+      AND p.applied_at < $3::TIMESTAMP AT TIME ZONE 'UTC'
+      AND ($4::UUID IS NULL OR p.repository_id = $4)
+    GROUP BY 1, 4
+    UNION ALL
+    SELECT date_trunc($1, s.timestamp AT TIME ZONE 'UTC')::DATE, 'scores_issued', '', NULL::UUID, COUNT(*)
+    FROM scoring_results s
+    WHERE s.timestamp >= $2::TIMESTAMP AT TIME ZONE 'UTC'
+      AND s.timestamp < $3::TIMESTAMP AT TIME ZONE 'UTC'
+      AND $4::UUID IS NULL
+    GROUP BY 1
+"#;
+
+pub struct RollupRepositoryImpl {
+    db_pool: Pool<Postgres>,
+}
+
+impl RollupRepositoryImpl {
+    pub fn new(db_pool: Pool<Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl RollupRepository for RollupRepositoryImpl {
+    async fn compute(
+        &self,
+        granularity: RollupGranularity,
+        from: NaiveDate,
+        to: NaiveDate,
+        repository_id: Option<Uuid>,
+    ) -> Result<Vec<RollupRow>> {
+        let rows = sqlx::query_as::<_, RollupRow>(ROLLUP_ROWS_SQL)
+            .bind(granularity.as_str())
+            .bind(from)
+            .bind(to)
+            .bind(repository_id)
+            .fetch_all(&self.db_pool)
+            .await?;
+        Ok(rows)
+    }
+
+    async fn refresh(&self, granularity: RollupGranularity, from: NaiveDate, to: NaiveDate) -> Result<u64> {
+        let mut tx = self.db_pool.begin().await?;
+
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(ANALYTICS_ROLLUP_LOCK_KEY)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "DELETE FROM analytics_rollups WHERE granularity = $1 AND period_start >= $2 AND period_start < $3",
+        )
+        .bind(granularity.as_str())
+        .bind(from)
+        .bind(to)
+        .execute(&mut *tx)
+        .await?;
+
+        let stored = sqlx::query(&format!(
+            r#"
+            INSERT INTO analytics_rollups (granularity, period_start, metric, dimension, repository_id, value)
+            SELECT $1, r.period_start, r.metric, r.dimension, r.repository_id, r.value
+            FROM ({}) r
+            "#,
+            ROLLUP_ROWS_SQL
+        ))
+        .bind(granularity.as_str())
+        .bind(from)
+        .bind(to)
+        .bind(None::<Uuid>)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let step = match granularity {
+            RollupGranularity::Day => "1 day",
+            RollupGranularity::Week => "1 week",
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO analytics_rollup_periods (granularity, period_start)
+            SELECT $1, period::DATE
+            FROM generate_series($2::TIMESTAMP, $3::TIMESTAMP - $4::INTERVAL, $4::INTERVAL) AS period
+            ON CONFLICT (granularity, period_start) DO UPDATE SET refreshed_at = NOW()
+            "#,
+        )
+        .bind(granularity.as_str())
+        .bind(from)
+        .bind(to)
+        .bind(step)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(stored)
+    }
+
+    async fn rolled_up_periods(
+        &self,
+        granularity: RollupGranularity,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<NaiveDate>> {
+        let periods = sqlx::query_scalar(
+            r#"
+            SELECT period_start
+            FROM analytics_rollup_periods
+            WHERE granularity = $1 AND period_start >= $2 AND period_start < $3
+            ORDER BY period_start
+            "#,
+        )
+        .bind(granularity.as_str())
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(periods)
+    }
+
+    async fn load(
+        &self,
+        granularity: RollupGranularity,
+        from: NaiveDate,
+        to: NaiveDate,
+        repository_id: Option<Uuid>,
+    ) -> Result<Vec<RollupRow>> {
+        let rows = sqlx::query_as::<_, RollupRow>(
+            r#"
+            SELECT period_start, metric, dimension, repository_id, value
+            FROM analytics_rollups
+            WHERE granularity = $1
+              AND period_start >= $2 AND period_start < $3
+              AND ($4::UUID IS NULL OR repository_id = $4)
+            "#,
+        )
+        .bind(granularity.as_str())
+        .bind(from)
+        .bind(to)
+        .bind(repository_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(rows)
+    }
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::types::Json;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::domain::{Scorecard, ScorecardHistoryEntry, ScorecardInputs, ScorecardRepository};
+use crate::Result;
+
+pub struct ScorecardRepositoryImpl {
+    db_pool: Pool<Postgres>,
+}
+
+impl ScorecardRepositoryImpl {
+    pub fn new(db_pool: Pool<Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ScorecardRepository for ScorecardRepositoryImpl {
+    async fn is_public_repository(&self, repository_id: Uuid) -> Result<bool> {
+        let public = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM github_repositories WHERE id = $1 AND NOT is_private)",
+        )
+        .bind(repository_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(public)
+    }
+
+    async fn list_scored_repositories(&self) -> Result<Vec<Uuid>> {
+        let repository_ids = sqlx::query_scalar(
+            r#"
+            SELECT r.id
+            FROM github_repositories r
+            WHERE NOT r.is_private
+              AND EXISTS (SELECT 1 FROM code_analysis_results a WHERE a.repository_id = r.id)
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(repository_ids)
+    }
+
+    async fn load_inputs(&self, repository_id: Uuid) -> Result<ScorecardInputs> {
+        let inputs = sqlx::query_as::<_, ScorecardInputs>(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE v.kind = 'code' AND v.severity = 'critical') AS open_critical,
+                   COUNT(*) FILTER (WHERE v.kind = 'code' AND v.severity = 'high') AS open_high,
+                   COUNT(*) FILTER (WHERE v.kind = 'code' AND v.severity = 'medium') AS open_medium,
+                   COUNT(*) FILTER (WHERE v.kind = 'code' AND v.severity = 'low') AS open_low,
+                   COUNT(*) FILTER (WHERE v.kind = 'dependency' AND v.severity = 'critical') AS dependency_critical,
+                   COUNT(*) FILTER (WHERE v.kind = 'dependency' AND v.severity = 'high') AS dependency_high,
+                   COUNT(*) FILTER (WHERE v.kind = 'dependency' AND v.severity = 'medium') AS dependency_medium,
+                   COUNT(*) FILTER (WHERE v.kind = 'dependency' AND v.severity = 'low') AS dependency_low,
+                   (SELECT (PERCENTILE_CONT(0.5) WITHIN GROUP (
+                               ORDER BY EXTRACT(EPOCH FROM p.applied_at - f.ctime) / 3600
+                           ))::FLOAT8
+                    FROM patch_proposals p
+                    JOIN security_vulnerabilities f ON f.id = p.vulnerability_id
+                    WHERE p.repository_id = $1
+                      AND p.status::TEXT IN ('applied', 'merged')
+                      AND p.applied_at > NOW() - INTERVAL '90 days') AS median_patch_latency_hours,
+                   (SELECT MAX(a.ctime) FROM code_analysis_results a WHERE a.repository_id = $1) AS last_analyzed_at
+            FROM security_vulnerabilities v
+            WHERE v.repository_id = $1
+              AND NOT v.is_false_positive
+              AND v.fixed_at IS NULL
+              AND v.resolved_at IS NULL
+            "#,
+        )
+        .bind(repository_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(inputs)
+    }
+
+    async fn record(&self, scorecard: &Scorecard) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO repository_scorecards (
+                repository_id, recorded_on, score, grade, components, inputs, computed_at
+            )
+            VALUES ($1, ($2 AT TIME ZONE 'UTC')::DATE, $3, $4, $5, $6, $2)
+            ON CONFLICT (repository_id, recorded_on) DO UPDATE SET
+                score = EXCLUDED.score,
+                grade = EXCLUDED.grade,
+                components = EXCLUDED.components,
+                inputs = EXCLUDED.inputs,
+                computed_at = EXCLUDED.computed_at
+            "#,
+        )
+        .bind(scorecard.repository_id)
+        .bind(scorecard.computed_at)
+        .bind(scorecard.score)
+        .bind(scorecard.grade)
+        .bind(Json(&scorecard.components))
+        .bind(Json(&scorecard.inputs))
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn score_on(&self, repository_id: Uuid, on: NaiveDate) -> Result<Option<f64>> {
+        let score = sqlx::query_scalar(
+            r#"
+            SELECT score
+            FROM repository_scorecards
+            WHERE repository_id = $1 AND recorded_on <= $2
+            ORDER BY recorded_on DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(repository_id)
+        .bind(on)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(score)
+    }
+
+    async fn history(&self, repository_id: Uuid, limit: i64) -> Result<Vec<ScorecardHistoryEntry>> {
+        let history = sqlx::query_as::<_, ScorecardHistoryEntry>(
+            r#"
+            SELECT recorded_on, score, grade
+            FROM repository_scorecards
+            WHERE repository_id = $1
+            ORDER BY recorded_on DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(repository_id)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(history)
+    }
+}
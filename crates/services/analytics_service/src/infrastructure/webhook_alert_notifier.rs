@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+
+use crate::domain::{AlertNotifier, AnalyticsAlert};
+use crate::{Error, Result};
+
+/// Posts analytics alerts to a webhook, e.g. an on-call relay or a chat
+/// channel's incoming webhook.
+pub struct WebhookAlertNotifier {
+    url: String,
+    client: Client,
+}
+
+impl WebhookAlertNotifier {
+    pub fn new(url: String) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { url, client }
+    }
+
+    /// The webhook at `ANALYTICS_ALERT_WEBHOOK_URL`, if set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("ANALYTICS_ALERT_WEBHOOK_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .map(Self::new)
+    }
+}
+
+#[async_trait]
+impl AlertNotifier for WebhookAlertNotifier {
+    async fn notify(&self, alert: &AnalyticsAlert) -> Result<()> {
+        let payload = json!({
+            "event": "analytics_alert",
+            "alert_id": alert.id,
+            "metric": alert.metric,
+            "subject": alert.subject,
+            "observed": alert.observed,
+            "expected": alert.expected,
+            "threshold": alert.threshold,
+            "details": alert.details,
+            "occurrences": alert.occurrences,
+            "first_detected_at": alert.first_detected_at,
+            "last_detected_at": alert.last_detected_at,
+        });
+
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| Error::DeliveryError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(Error::DeliveryError(format!("{} returned {}", self.url, response.status())));
+        }
+        Ok(())
+    }
+}
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::{DeliveryChannel, ReportDataset, ReportFilters};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeRangeRequest {
     pub start_date: DateTime<Utc>,
@@ -26,4 +28,19 @@ impl Default for PaginationRequest {
             limit: Some(20),
         }
     }
+}
+
+/// A query to save, or the new definition of a saved one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQueryRequest {
+    pub name: String,
+    pub dataset: ReportDataset,
+    #[serde(default)]
+    pub filters: ReportFilters,
+    /// Cron expression in UTC; the query only runs on demand if unset.
+    pub schedule: Option<String>,
+    pub delivery_channel: Option<DeliveryChannel>,
+    /// Email address or webhook URL, depending on the channel.
+    pub delivery_target: Option<String>,
+    pub enabled: Option<bool>,
 }
\ No newline at end of file
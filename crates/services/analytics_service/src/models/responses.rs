@@ -5,6 +5,7 @@ use uuid::Uuid;
 
 use crate::domain::{
     CollaborationMetrics, DeveloperAnalytics, PlatformOverview, RepositoryAnalytics,
+    RollupGranularity, RollupPoint, SavedQuery, Scorecard, ScorecardHistoryEntry, ScorecardTrend,
     SecurityTrend, SkillDistribution, TeamAnalytics,
 };
 
@@ -115,4 +116,41 @@ pub struct PlatformTrendsResponse {
     pub trends: Vec<SecurityTrend>,
     pub period_start: DateTime<Utc>,
     pub period_end: DateTime<Utc>,
+}
+
+/// Where a series was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupSource {
+    /// Stored rollups
+    Rollup,
+    /// The source tables, because some period was not rolled up yet
+    Live,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollupSeriesResponse {
+    pub granularity: RollupGranularity,
+    pub repository_id: Option<Uuid>,
+    pub source: RollupSource,
+    pub points: Vec<RollupPoint>,
+}
+
+/// A repository's current scorecard, which way it moved over the last week,
+/// and the scores of recent days, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScorecardResponse {
+    #[serde(flatten)]
+    pub scorecard: Scorecard,
+    pub trend: ScorecardTrend,
+    pub history: Vec<ScorecardHistoryEntry>,
+}
+
+/// A saved query. The webhook secret is only returned when it is generated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQueryResponse {
+    #[serde(flatten)]
+    pub query: SavedQuery,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_secret: Option<String>,
 }
\ No newline at end of file
@@ -0,0 +1,117 @@
+use axum::{
+  extract::{Extension, Json, State},
+  http::StatusCode,
+  response::Json as ResponseJson,
+};
+use jd_core::AppState;
+use jd_email::{NotificationPreferences, PreferenceStore};
+use validator::Validate;
+
+use crate::application::use_cases::{
+  LinkedAccountsUseCase, NotificationPreferencesUseCase, WalletProof, WalletProofVerifier,
+};
+use crate::domain::{AccountMerge, Claims, LinkedWallet, LoginMessageSettings};
+use crate::error::{Error, Result};
+use crate::infrastructure::{AccountRepositoryImpl, NonceRepositoryImpl, SignatureVerifierRegistry};
+use crate::models::{AccountOverview, UpdateNotificationPreferencesRequest, VerifyRequest};
+
+/// The caller's linked wallets and identities. Wallet changes take the same
+/// signed-nonce body as `/auth/login`, signed by the wallet concerned over a
+/// structured message whose statement names the change.
+/// Callers are expected to sit behind bearer authentication.
+pub struct AccountHandler;
+
+impl AccountHandler {
+  fn use_case(
+    state: &AppState,
+  ) -> LinkedAccountsUseCase<AccountRepositoryImpl, NonceRepositoryImpl> {
+    LinkedAccountsUseCase::new(
+      AccountRepositoryImpl::new(state.clone()),
+      WalletProofVerifier::new(
+        NonceRepositoryImpl::new(state.clone()),
+        SignatureVerifierRegistry::default(),
+        LoginMessageSettings::from_config(state.config.auth.as_ref()),
+      ),
+    )
+  }
+
+  pub async fn get_account(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+  ) -> Result<ResponseJson<AccountOverview>> {
+    let overview = Self::use_case(&state).overview(&caller).await?;
+    Ok(ResponseJson(overview))
+  }
+
+  pub async fn link_wallet(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Json(request): Json<VerifyRequest>,
+  ) -> Result<(StatusCode, ResponseJson<LinkedWallet>)> {
+    validate(&request)?;
+    let wallet = Self::use_case(&state).link_wallet(&caller, proof(&request)).await?;
+    Ok((StatusCode::CREATED, ResponseJson(wallet)))
+  }
+
+  pub async fn unlink_wallet(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Json(request): Json<VerifyRequest>,
+  ) -> Result<StatusCode> {
+    validate(&request)?;
+    Self::use_case(&state).unlink_wallet(&caller, proof(&request)).await?;
+    Ok(StatusCode::NO_CONTENT)
+  }
+
+  pub async fn merge_account(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Json(request): Json<VerifyRequest>,
+  ) -> Result<ResponseJson<AccountMerge>> {
+    validate(&request)?;
+    let merge = Self::use_case(&state).merge(&caller, proof(&request)).await?;
+    Ok(ResponseJson(merge))
+  }
+
+  pub async fn get_notification_preferences(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+  ) -> Result<ResponseJson<NotificationPreferences>> {
+    let preferences = Self::preferences_use_case(&state).get(&caller).await?;
+    Ok(ResponseJson(preferences))
+  }
+
+  pub async fn update_notification_preferences(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Json(request): Json<UpdateNotificationPreferencesRequest>,
+  ) -> Result<ResponseJson<NotificationPreferences>> {
+    let preferences = Self::preferences_use_case(&state).update(&caller, &request).await?;
+    Ok(ResponseJson(preferences))
+  }
+
+  fn preferences_use_case(
+    state: &AppState,
+  ) -> NotificationPreferencesUseCase<AccountRepositoryImpl> {
+    NotificationPreferencesUseCase::new(
+      AccountRepositoryImpl::new(state.clone()),
+      PreferenceStore::new(state.mm().dbx().db().clone()),
+    )
+  }
+}
+
+fn validate(request: &VerifyRequest) -> Result<()> {
+  request
+    .validate()
+    .map_err(|e| Error::invalid_request_data(&format!("Validation failed: {}", e)))
+}
+
+fn proof(request: &VerifyRequest) -> WalletProof<'_> {
+  WalletProof {
+    chain: request.chain,
+    address: &request.address,
+    signature: &request.signature,
+    public_key: &request.public_key,
+    message: request.message.as_deref(),
+  }
+}
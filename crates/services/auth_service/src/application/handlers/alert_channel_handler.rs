@@ -0,0 +1,77 @@
+use axum::{
+  extract::{Extension, Json, Path, Query, State},
+  http::StatusCode,
+  response::Json as ResponseJson,
+};
+use jd_alerts::{AlertChannel, AlertChannels, ChannelSettings};
+use jd_core::AppState;
+use uuid::Uuid;
+
+use crate::application::use_cases::AlertChannelsUseCase;
+use crate::domain::Claims;
+use crate::error::Result;
+use crate::infrastructure::OrganizationRepositoryImpl;
+use crate::models::{AlertTestResponse, TestAlertChannelQuery};
+
+/// An organization's Slack and Discord alert channels. Callers are expected
+/// to sit behind bearer authentication.
+pub struct AlertChannelHandler;
+
+impl AlertChannelHandler {
+  fn use_case(state: &AppState) -> AlertChannelsUseCase<OrganizationRepositoryImpl> {
+    AlertChannelsUseCase::new(
+      OrganizationRepositoryImpl::new(state.clone()),
+      AlertChannels::new(state.mm().dbx().db().clone(), state.cache.clone()),
+    )
+  }
+
+  pub async fn list_channels(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(organization_id): Path<Uuid>,
+  ) -> Result<ResponseJson<Vec<AlertChannel>>> {
+    Ok(ResponseJson(Self::use_case(&state).list(&caller, organization_id).await?))
+  }
+
+  pub async fn create_channel(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(organization_id): Path<Uuid>,
+    Json(settings): Json<ChannelSettings>,
+  ) -> Result<(StatusCode, ResponseJson<AlertChannel>)> {
+    let channel = Self::use_case(&state).create(&caller, organization_id, &settings).await?;
+    Ok((StatusCode::CREATED, ResponseJson(channel)))
+  }
+
+  pub async fn update_channel(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path((organization_id, channel_id)): Path<(Uuid, Uuid)>,
+    Json(settings): Json<ChannelSettings>,
+  ) -> Result<ResponseJson<AlertChannel>> {
+    let channel =
+      Self::use_case(&state).update(&caller, organization_id, channel_id, &settings).await?;
+    Ok(ResponseJson(channel))
+  }
+
+  pub async fn delete_channel(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path((organization_id, channel_id)): Path<(Uuid, Uuid)>,
+  ) -> Result<StatusCode> {
+    Self::use_case(&state).delete(&caller, organization_id, channel_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+  }
+
+  pub async fn test_channel(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path((organization_id, channel_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<TestAlertChannelQuery>,
+  ) -> Result<ResponseJson<AlertTestResponse>> {
+    let sent = Self::use_case(&state)
+      .test(&caller, organization_id, channel_id, query.event.as_deref())
+      .await?;
+    Ok(ResponseJson(sent))
+  }
+}
@@ -8,27 +8,31 @@ use validator::Validate;
 use crate::application::use_cases::{
   GenerateNonceUseCase, RefreshTokenUseCase, ValidateTokenUseCase, VerifySignatureUseCase,
 };
-use crate::domain::{AuthUser, NonceRepository, SignatureVerifier, UserRepository};
+use crate::domain::{
+  AuthUser, LoginMessageSettings, NonceRepository, RoleRepository, UserRepository, nonce_ttl,
+};
 use crate::error::{Error, Result};
-use crate::infrastructure::{NonceRepositoryImpl, SignatureVerifierImpl, ZkPersonaUserRepositoryImpl};
+use crate::infrastructure::{
+  NonceRepositoryImpl, RoleRepositoryImpl, SignatureVerifierRegistry, ZkPersonaUserRepositoryImpl,
+};
 use crate::models::{
   NonceRequest, NonceResponse, RefreshRequest, RefreshResponse, UserInfo, VerifyRequest,
   VerifyResponse,
 };
 use jd_core::AppState;
 
-pub struct AuthHandler<N: NonceRepository, U: UserRepository, S: SignatureVerifier> {
+pub struct AuthHandler<N: NonceRepository, U: UserRepository, R: RoleRepository> {
   pub generate_nonce: GenerateNonceUseCase<N>,
-  pub verify_signature: VerifySignatureUseCase<N, U, S>,
-  pub refresh_token: RefreshTokenUseCase,
+  pub verify_signature: VerifySignatureUseCase<N, U, R>,
+  pub refresh_token: RefreshTokenUseCase<R>,
   pub validate_token: ValidateTokenUseCase<U>,
 }
 
-impl<N: NonceRepository, U: UserRepository, S: SignatureVerifier> AuthHandler<N, U, S> {
+impl<N: NonceRepository, U: UserRepository, R: RoleRepository> AuthHandler<N, U, R> {
   pub fn new(
     generate_nonce: GenerateNonceUseCase<N>,
-    verify_signature: VerifySignatureUseCase<N, U, S>,
-    refresh_token: RefreshTokenUseCase,
+    verify_signature: VerifySignatureUseCase<N, U, R>,
+    refresh_token: RefreshTokenUseCase<R>,
     validate_token: ValidateTokenUseCase<U>,
   ) -> Self {
     Self { generate_nonce, verify_signature, refresh_token, validate_token }
@@ -42,12 +46,19 @@ impl<N: NonceRepository, U: UserRepository, S: SignatureVerifier> AuthHandler<N,
       .validate()
       .map_err(|e| Error::invalid_request_data(&format!("Validation failed: {}", e)))?;
 
+    let ttl = nonce_ttl(state.config.auth.as_ref());
+    let settings = LoginMessageSettings::from_config(state.config.auth.as_ref());
     let nonce_repo = NonceRepositoryImpl::new(state);
-    let use_case = GenerateNonceUseCase::new(nonce_repo);
-    let nonce = use_case.execute(&request.address).await?;
+    let use_case = GenerateNonceUseCase::new(nonce_repo, ttl, settings);
+    let (nonce, message) =
+      use_case.execute(request.chain, &request.address, request.message_version).await?;
 
-    let response =
-      NonceResponse { nonce: nonce.nonce.clone(), message: nonce.get_signing_message() };
+    let response = NonceResponse {
+      chain: nonce.chain,
+      nonce: nonce.nonce,
+      message,
+      message_version: nonce.message_version,
+    };
 
     Ok(ResponseJson(response))
   }
@@ -62,14 +73,28 @@ impl<N: NonceRepository, U: UserRepository, S: SignatureVerifier> AuthHandler<N,
 
     let nonce_repo = NonceRepositoryImpl::new(state.clone());
     let user_repo = ZkPersonaUserRepositoryImpl::new(state.clone());
-    let signature_verifier = SignatureVerifierImpl::new();
+    let role_repo = RoleRepositoryImpl::new(state.clone());
+    let verifiers = SignatureVerifierRegistry::default();
     let jwt_secret = state.config.auth_jwt_secret.clone();
+    let settings = LoginMessageSettings::from_config(state.config.auth.as_ref());
 
-    let use_case =
-      VerifySignatureUseCase::new(nonce_repo, user_repo, signature_verifier, jwt_secret);
+    let use_case = VerifySignatureUseCase::new(
+      nonce_repo,
+      user_repo,
+      role_repo,
+      verifiers,
+      jwt_secret,
+      settings,
+    );
 
     let (user, tokens) = use_case
-      .execute(&request.address, &request.signature, &request.public_key)
+      .execute(
+        request.chain,
+        &request.address,
+        &request.signature,
+        &request.public_key,
+        request.message.as_deref(),
+      )
       .await?;
 
     let response = VerifyResponse { success: true, user: UserInfo::from(user), tokens };
@@ -85,8 +110,9 @@ impl<N: NonceRepository, U: UserRepository, S: SignatureVerifier> AuthHandler<N,
       .validate()
       .map_err(|e| Error::invalid_request_data(&format!("Validation failed: {}", e)))?;
 
+    let role_repo = RoleRepositoryImpl::new(state.clone());
     let jwt_secret = state.config.auth_jwt_secret.clone();
-    let use_case = RefreshTokenUseCase::new(jwt_secret);
+    let use_case = RefreshTokenUseCase::new(role_repo, jwt_secret);
     let access_token = use_case.execute(&request.refresh_token).await?;
 
     let response = RefreshResponse { access_token };
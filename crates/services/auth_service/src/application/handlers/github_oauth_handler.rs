@@ -0,0 +1,130 @@
+use axum::{
+  extract::{Query, State},
+  http::HeaderMap,
+  response::{Json as ResponseJson, Redirect},
+};
+use jd_core::AppState;
+use jd_utils::crypto::SecretCipher;
+use tower_cookies::{
+  Cookie, Cookies,
+  cookie::{SameSite, time::Duration},
+};
+use tracing::error;
+use validator::Validate;
+
+use crate::application::use_cases::{GithubOAuthUseCase, ValidateTokenUseCase};
+use crate::domain::JwtManager;
+use crate::error::{Error, Result};
+use crate::infrastructure::{
+  GithubIdentityRepositoryImpl, GithubOAuthClientImpl, OAuthStateRepositoryImpl,
+  RoleRepositoryImpl, ZkPersonaUserRepositoryImpl,
+};
+use crate::models::{GithubCallbackQuery, GithubLoginResponse, UserInfo};
+
+type ConcreteGithubOAuthUseCase = GithubOAuthUseCase<
+  OAuthStateRepositoryImpl,
+  GithubIdentityRepositoryImpl,
+  GithubOAuthClientImpl,
+  RoleRepositoryImpl,
+>;
+
+/// Holds the OAuth state in the browser that started the flow.
+const OAUTH_STATE_COOKIE: &str = "github_oauth_state";
+const OAUTH_STATE_COOKIE_PATH: &str = "/api/v1/zkpersona/auth/github";
+/// As long as the stored state lives.
+const OAUTH_STATE_COOKIE_MAX_AGE: Duration = Duration::minutes(10);
+
+pub struct GithubOAuthHandler;
+
+impl GithubOAuthHandler {
+  fn use_case(state: &AppState) -> Result<ConcreteGithubOAuthUseCase> {
+    let github = state.config.github.as_ref().ok_or_else(Error::oauth_not_configured)?;
+    let (Some(client_id), Some(client_secret), Some(redirect_url), Some(encryption_key)) = (
+      github.client_id.clone(),
+      github.client_secret.clone(),
+      github.oauth_redirect_url.clone(),
+      github.token_encryption_key.as_deref(),
+    ) else {
+      return Err(Error::oauth_not_configured());
+    };
+
+    let cipher = SecretCipher::from_base64_key(encryption_key).map_err(|e| {
+      error!("❌ Invalid GITHUB.TOKEN_ENCRYPTION_KEY: {}", e);
+      Error::oauth_not_configured()
+    })?;
+
+    Ok(GithubOAuthUseCase::new(
+      OAuthStateRepositoryImpl::new(state.clone()),
+      GithubIdentityRepositoryImpl::new(state.clone()),
+      GithubOAuthClientImpl::new(client_id, client_secret, redirect_url),
+      RoleRepositoryImpl::new(state.clone()),
+      cipher,
+      state.config.auth_jwt_secret.clone(),
+    ))
+  }
+
+  /// Redirect to GitHub's consent screen. When called with a valid wallet
+  /// access token, the GitHub account is linked to that user on callback.
+  /// The state is also set in a short-lived cookie, which the callback
+  /// must bring back.
+  pub async fn github_login(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    headers: HeaderMap,
+  ) -> Result<Redirect> {
+    let use_case = Self::use_case(&state)?;
+
+    let link_user_id = match headers.get("authorization").and_then(|h| h.to_str().ok()) {
+      Some(auth_header) => {
+        let token = JwtManager::extract_token_from_header(auth_header)?;
+        let validate_token = ValidateTokenUseCase::new(
+          ZkPersonaUserRepositoryImpl::new(state.clone()),
+          state.config.auth_jwt_secret.clone(),
+        );
+        let user = validate_token.execute(token).await?;
+        Some(use_case.wallet_user_id(&user).await?)
+      }
+      None => None,
+    };
+
+    let (oauth_state, authorize_url) = use_case.start(link_user_id).await?;
+    cookies.add(
+      Cookie::build((OAUTH_STATE_COOKIE, oauth_state))
+        .path(OAUTH_STATE_COOKIE_PATH)
+        .max_age(OAUTH_STATE_COOKIE_MAX_AGE)
+        .http_only(true)
+        .secure(true)
+        // GitHub redirects back cross-site, which `Strict` would not send on
+        .same_site(SameSite::Lax)
+        .build(),
+    );
+    Ok(Redirect::to(&authorize_url))
+  }
+
+  pub async fn github_callback(
+    State(state): State<AppState>,
+    cookies: Cookies,
+    Query(query): Query<GithubCallbackQuery>,
+  ) -> Result<ResponseJson<GithubLoginResponse>> {
+    query
+      .validate()
+      .map_err(|e| Error::invalid_request_data(&format!("Validation failed: {}", e)))?;
+
+    let browser_state = cookies.get(OAUTH_STATE_COOKIE).map(|cookie| cookie.value().to_string());
+    cookies.remove(Cookie::build(OAUTH_STATE_COOKIE).path(OAUTH_STATE_COOKIE_PATH).build());
+
+    let use_case = Self::use_case(&state)?;
+    let result = use_case.complete(&query.code, &query.state, browser_state.as_deref()).await?;
+
+    let response = GithubLoginResponse {
+      success: true,
+      user: UserInfo::from(result.user),
+      github: result.identity.into(),
+      tokens: result.tokens,
+      is_new_user: result.is_new_user,
+      linked: result.linked,
+    };
+
+    Ok(ResponseJson(response))
+  }
+}
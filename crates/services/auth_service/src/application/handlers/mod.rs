@@ -1,3 +1,15 @@
+pub mod account_handler;
+pub mod alert_channel_handler;
 pub mod auth_handler;
+pub mod github_oauth_handler;
+pub mod notification_handler;
+pub mod organization_handler;
+pub mod role_admin_handler;
 
+pub use account_handler::AccountHandler;
+pub use alert_channel_handler::AlertChannelHandler;
 pub use auth_handler::AuthHandler;
+pub use github_oauth_handler::GithubOAuthHandler;
+pub use notification_handler::NotificationHandler;
+pub use organization_handler::OrganizationHandler;
+pub use role_admin_handler::RoleAdminHandler;
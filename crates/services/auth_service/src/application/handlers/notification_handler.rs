@@ -0,0 +1,84 @@
+use axum::{
+  extract::{Extension, Json, Path, Query, State},
+  response::Json as ResponseJson,
+};
+use jd_core::AppState;
+use jd_notifications::{InAppPreferenceStore, InAppPreferences, NotificationStore};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::application::use_cases::NotificationsUseCase;
+use crate::domain::Claims;
+use crate::error::Result;
+use crate::infrastructure::AccountRepositoryImpl;
+use crate::models::{
+  ListNotificationsQuery, NotificationList, UnreadCountResponse, UpdateInAppPreferencesRequest,
+};
+
+/// The caller's in-app notifications. Marking notifications read pushes the
+/// new unread count to the caller's other real-time connections. Callers are
+/// expected to sit behind bearer authentication.
+pub struct NotificationHandler;
+
+impl NotificationHandler {
+  fn use_case(state: &AppState) -> NotificationsUseCase<AccountRepositoryImpl> {
+    let db = state.mm().dbx().db().clone();
+    NotificationsUseCase::new(
+      AccountRepositoryImpl::new(state.clone()),
+      NotificationStore::new(db.clone()),
+      InAppPreferenceStore::new(db),
+    )
+  }
+
+  pub async fn list_notifications(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Query(query): Query<ListNotificationsQuery>,
+  ) -> Result<ResponseJson<NotificationList>> {
+    let notifications = Self::use_case(&state).list(&caller, &query).await?;
+    Ok(ResponseJson(notifications))
+  }
+
+  pub async fn mark_read(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(id): Path<i64>,
+  ) -> Result<ResponseJson<UnreadCountResponse>> {
+    let (user_id, unread_count) = Self::use_case(&state).mark_read(&caller, id).await?;
+    publish_unread(&state, user_id).await;
+    Ok(ResponseJson(UnreadCountResponse { unread_count }))
+  }
+
+  pub async fn mark_all_read(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+  ) -> Result<ResponseJson<UnreadCountResponse>> {
+    let user_id = Self::use_case(&state).mark_all_read(&caller).await?;
+    publish_unread(&state, user_id).await;
+    Ok(ResponseJson(UnreadCountResponse { unread_count: 0 }))
+  }
+
+  pub async fn get_preferences(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+  ) -> Result<ResponseJson<InAppPreferences>> {
+    Ok(ResponseJson(Self::use_case(&state).preferences(&caller).await?))
+  }
+
+  pub async fn update_preferences(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Json(request): Json<UpdateInAppPreferencesRequest>,
+  ) -> Result<ResponseJson<InAppPreferences>> {
+    let preferences = Self::use_case(&state).update_preferences(&caller, &request).await?;
+    Ok(ResponseJson(preferences))
+  }
+}
+
+/// The change is saved either way, so a failed push is only logged.
+async fn publish_unread(state: &AppState, user_id: Uuid) {
+  let store = NotificationStore::new(state.mm().dbx().db().clone());
+  if let Err(e) = store.publish_unread(state.redis(), &[user_id]).await {
+    warn!(%user_id, "Unread notification count not pushed: {e}");
+  }
+}
@@ -0,0 +1,83 @@
+use axum::{
+  extract::{Extension, Json, Path, State},
+  http::StatusCode,
+  response::Json as ResponseJson,
+};
+use jd_core::AppState;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::use_cases::OnboardingUseCase;
+use crate::domain::{Claims, OnboardingSettings, OrganizationMember};
+use crate::error::{Error, Result};
+use crate::infrastructure::OrganizationRepositoryImpl;
+use crate::models::{
+  CreateOrganizationRequest, InvitationResponse, InviteMemberRequest, OrganizationOverview,
+};
+
+const SELF_SERVE_ONBOARDING: &str = "self_serve_onboarding";
+
+/// Self-serve organization onboarding, gated by `FEATURES.SELF_SERVE_ONBOARDING`.
+/// Callers are expected to sit behind bearer authentication, which provides
+/// the caller's token [`Claims`] as a request extension.
+pub struct OrganizationHandler;
+
+impl OrganizationHandler {
+  fn use_case(state: &AppState) -> Result<OnboardingUseCase<OrganizationRepositoryImpl>> {
+    if !state.live_config().self_serve_onboarding_enabled() {
+      return Err(Error::feature_disabled(SELF_SERVE_ONBOARDING));
+    }
+
+    Ok(OnboardingUseCase::new(
+      OrganizationRepositoryImpl::new(state.clone()),
+      OnboardingSettings::from_config(state.config.onboarding.as_ref()),
+    ))
+  }
+
+  pub async fn create_organization(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Json(request): Json<CreateOrganizationRequest>,
+  ) -> Result<(StatusCode, ResponseJson<OrganizationOverview>)> {
+    let use_case = Self::use_case(&state)?;
+    request
+      .validate()
+      .map_err(|e| Error::invalid_request_data(&format!("Validation failed: {}", e)))?;
+
+    let overview = use_case.signup(&caller, request).await?;
+    Ok((StatusCode::CREATED, ResponseJson(overview)))
+  }
+
+  pub async fn get_organization(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(organization_id): Path<Uuid>,
+  ) -> Result<ResponseJson<OrganizationOverview>> {
+    let overview = Self::use_case(&state)?.overview(&caller, organization_id).await?;
+    Ok(ResponseJson(overview))
+  }
+
+  pub async fn invite_member(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(organization_id): Path<Uuid>,
+    Json(request): Json<InviteMemberRequest>,
+  ) -> Result<(StatusCode, ResponseJson<InvitationResponse>)> {
+    let use_case = Self::use_case(&state)?;
+    request
+      .validate()
+      .map_err(|e| Error::invalid_request_data(&format!("Validation failed: {}", e)))?;
+
+    let invitation = use_case.invite(&caller, organization_id, request).await?;
+    Ok((StatusCode::CREATED, ResponseJson(invitation)))
+  }
+
+  pub async fn accept_invitation(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Claims>,
+    Path(token): Path<String>,
+  ) -> Result<ResponseJson<OrganizationMember>> {
+    let member = Self::use_case(&state)?.accept_invitation(&caller, &token).await?;
+    Ok(ResponseJson(member))
+  }
+}
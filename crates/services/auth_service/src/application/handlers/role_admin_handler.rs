@@ -0,0 +1,62 @@
+use axum::{
+  extract::{Extension, Json, Path, State},
+  http::StatusCode,
+  response::Json as ResponseJson,
+};
+use jd_core::AppState;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::application::use_cases::ManageRolesUseCase;
+use crate::domain::{Claims, RoleAssignment};
+use crate::error::{Error, Result};
+use crate::infrastructure::RoleRepositoryImpl;
+use crate::models::{GrantRoleRequest, RoleListResponse, UserRolesResponse};
+
+/// Role administration. Callers are expected to sit behind a policy layer
+/// requiring [`crate::domain::SCOPE_ROLES_ADMIN`], which also provides the
+/// admin's token [`Claims`] as a request extension.
+pub struct RoleAdminHandler;
+
+impl RoleAdminHandler {
+  fn use_case(state: AppState) -> ManageRolesUseCase<RoleRepositoryImpl> {
+    ManageRolesUseCase::new(RoleRepositoryImpl::new(state))
+  }
+
+  pub async fn list_roles(State(state): State<AppState>) -> Result<ResponseJson<RoleListResponse>> {
+    let roles = Self::use_case(state).list_roles().await?;
+    Ok(ResponseJson(RoleListResponse { roles }))
+  }
+
+  pub async fn list_user_roles(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+  ) -> Result<ResponseJson<UserRolesResponse>> {
+    let assignments = Self::use_case(state).user_roles(user_id).await?;
+    Ok(ResponseJson(UserRolesResponse { user_id, assignments }))
+  }
+
+  pub async fn grant_role(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Claims>,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<GrantRoleRequest>,
+  ) -> Result<(StatusCode, ResponseJson<RoleAssignment>)> {
+    request
+      .validate()
+      .map_err(|e| Error::invalid_request_data(&format!("Validation failed: {}", e)))?;
+
+    let assignment =
+      Self::use_case(state).grant(user_id, &request.role, Some(&admin.address)).await?;
+    Ok((StatusCode::CREATED, ResponseJson(assignment)))
+  }
+
+  pub async fn revoke_role(
+    State(state): State<AppState>,
+    Extension(admin): Extension<Claims>,
+    Path((user_id, role)): Path<(Uuid, String)>,
+  ) -> Result<StatusCode> {
+    Self::use_case(state).revoke(user_id, &role, Some(&admin.address)).await?;
+    Ok(StatusCode::NO_CONTENT)
+  }
+}
@@ -0,0 +1,116 @@
+use jd_alerts::{Alert, AlertChannel, AlertChannels, ChannelSettings};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::{Claims, OrganizationRepository, OrganizationRole};
+use crate::error::{Error, Result};
+use crate::models::AlertTestResponse;
+
+/// An organization's Slack and Discord alert channels. Members see them;
+/// owners and admins manage and test them.
+pub struct AlertChannelsUseCase<O: OrganizationRepository> {
+  org_repo: O,
+  channels: AlertChannels,
+}
+
+impl<O: OrganizationRepository> AlertChannelsUseCase<O> {
+  pub fn new(org_repo: O, channels: AlertChannels) -> Self {
+    Self { org_repo, channels }
+  }
+
+  pub async fn list(&self, caller: &Claims, organization_id: Uuid) -> Result<Vec<AlertChannel>> {
+    self.require_role(caller, organization_id).await?;
+    Ok(self.channels.list(organization_id).await?)
+  }
+
+  pub async fn create(
+    &self,
+    caller: &Claims,
+    organization_id: Uuid,
+    settings: &ChannelSettings,
+  ) -> Result<AlertChannel> {
+    self.require_manager(caller, organization_id).await?;
+    let channel = self.channels.create(organization_id, settings, &caller.address).await?;
+    info!(
+      "🔔 {} alert channel {} added to organization {} by {}",
+      channel.kind.as_str(),
+      channel.id,
+      organization_id,
+      caller.address
+    );
+    Ok(channel)
+  }
+
+  pub async fn update(
+    &self,
+    caller: &Claims,
+    organization_id: Uuid,
+    channel_id: Uuid,
+    settings: &ChannelSettings,
+  ) -> Result<AlertChannel> {
+    self.require_manager(caller, organization_id).await?;
+    self
+      .channels
+      .update(organization_id, channel_id, settings)
+      .await?
+      .ok_or_else(Error::alert_channel_not_found)
+  }
+
+  pub async fn delete(
+    &self,
+    caller: &Claims,
+    organization_id: Uuid,
+    channel_id: Uuid,
+  ) -> Result<()> {
+    self.require_manager(caller, organization_id).await?;
+    if !self.channels.delete(organization_id, channel_id).await? {
+      return Err(Error::alert_channel_not_found());
+    }
+    Ok(())
+  }
+
+  /// Post a sample alert of `event`, by default the channel's first, through
+  /// the channel's template. It counts against the channel's hourly limit.
+  pub async fn test(
+    &self,
+    caller: &Claims,
+    organization_id: Uuid,
+    channel_id: Uuid,
+    event: Option<&str>,
+  ) -> Result<AlertTestResponse> {
+    self.require_manager(caller, organization_id).await?;
+    let channel = self
+      .channels
+      .get(organization_id, channel_id)
+      .await?
+      .ok_or_else(Error::alert_channel_not_found)?;
+    let event = event.or(channel.events.first().map(String::as_str)).unwrap_or_default();
+    let alert = Alert::sample(event).ok_or_else(|| Error::invalid_request_data("event"))?;
+    self.channels.send(&channel, &alert).await?;
+    Ok(AlertTestResponse {
+      event: alert.event().to_string(),
+      message: alert.render(&channel.templates),
+    })
+  }
+
+  async fn require_manager(&self, caller: &Claims, organization_id: Uuid) -> Result<()> {
+    if !self.require_role(caller, organization_id).await?.can_manage_alerts() {
+      return Err(Error::insufficient_permissions());
+    }
+    Ok(())
+  }
+
+  /// Non-members get the same error as for a missing organization.
+  async fn require_role(&self, caller: &Claims, organization_id: Uuid) -> Result<OrganizationRole> {
+    let user_id = self
+      .org_repo
+      .find_user_id(caller.provider, caller.chain, &caller.address)
+      .await?
+      .ok_or_else(Error::user_not_found)?;
+    self
+      .org_repo
+      .member_role(organization_id, user_id)
+      .await?
+      .ok_or_else(Error::organization_not_found)
+  }
+}
@@ -0,0 +1,28 @@
+use jd_feature_flags::{FeatureFlags, FlagSubject};
+
+use crate::domain::{AccountRepository, Claims};
+use crate::error::Result;
+
+/// Who feature flags are checked for on behalf of a caller.
+pub struct FlagSubjectUseCase<A: AccountRepository> {
+  accounts: A,
+  flags: FeatureFlags,
+}
+
+impl<A: AccountRepository> FlagSubjectUseCase<A> {
+  pub fn new(accounts: A, flags: FeatureFlags) -> Self {
+    Self { accounts, flags }
+  }
+
+  /// The caller's user id and organizations, or their token subject alone
+  /// when no user has it.
+  pub async fn subject(&self, caller: &Claims) -> Result<FlagSubject> {
+    let user_id =
+      self.accounts.find_user_id(caller.provider, caller.chain, &caller.address).await?;
+    let Some(user_id) = user_id else {
+      return Ok(FlagSubject::user(caller.address.clone()));
+    };
+    let organizations = self.flags.organizations_of(user_id).await?;
+    Ok(FlagSubject::user(user_id.to_string()).with_organizations(organizations))
+  }
+}
@@ -1,27 +1,42 @@
-use crate::domain::{AuthUser, Nonce, NonceRepository};
+use chrono::Duration;
+
+use crate::domain::{Chain, LoginMessageSettings, LoginMessageVersion, Nonce, NonceRepository};
 use crate::error::{Error, Result};
 
 pub struct GenerateNonceUseCase<R: NonceRepository> {
   repository: R,
+  ttl: Duration,
+  settings: LoginMessageSettings,
 }
 
 impl<R: NonceRepository> GenerateNonceUseCase<R> {
-  pub fn new(repository: R) -> Self {
-    Self { repository }
+  pub fn new(repository: R, ttl: Duration, settings: LoginMessageSettings) -> Self {
+    Self { repository, ttl, settings }
   }
 
-  pub async fn execute(&self, address: &str) -> Result<Nonce> {
+  /// Issue a nonce and return it with the message the wallet should sign.
+  pub async fn execute(
+    &self,
+    chain: Chain,
+    address: &str,
+    version: LoginMessageVersion,
+  ) -> Result<(Nonce, String)> {
     // Validate address format
-    if !AuthUser::is_valid_address(address) {
+    if !chain.is_valid_address(address) {
       return Err(Error::invalid_address());
     }
+    if version == LoginMessageVersion::Legacy && !self.settings.allow_legacy {
+      return Err(Error::unsupported_message_version());
+    }
 
     // Generate new nonce
-    let nonce = Nonce::generate(address.to_string());
+    let nonce =
+      Nonce::generate(chain, chain.normalize_address(address), self.ttl).with_version(version);
 
     // Store nonce in repository
     self.repository.store_nonce(&nonce).await?;
 
-    Ok(nonce)
+    let message = self.settings.signing_message(&nonce);
+    Ok((nonce, message))
   }
 }
@@ -0,0 +1,171 @@
+use jd_utils::crypto::SecretCipher;
+use rand::RngCore;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::domain::{
+  AuthProviderType, AuthUser, GithubIdentity, GithubIdentityForUpsert, GithubIdentityRepository,
+  GithubOAuthClient, GithubOAuthState, JwtManager, OAuthStateRepository, RoleRepository,
+  TokenPair,
+};
+use crate::error::{Error, Result};
+
+pub struct GithubLoginResult {
+  pub user: AuthUser,
+  pub identity: GithubIdentity,
+  pub tokens: TokenPair,
+  pub is_new_user: bool,
+  pub linked: bool,
+}
+
+pub struct GithubOAuthUseCase<
+  S: OAuthStateRepository,
+  I: GithubIdentityRepository,
+  C: GithubOAuthClient,
+  R: RoleRepository,
+> {
+  state_repo: S,
+  identity_repo: I,
+  client: C,
+  role_repo: R,
+  cipher: SecretCipher,
+  jwt_manager: JwtManager,
+}
+
+impl<S, I, C, R> GithubOAuthUseCase<S, I, C, R>
+where
+  S: OAuthStateRepository,
+  I: GithubIdentityRepository,
+  C: GithubOAuthClient,
+  R: RoleRepository,
+{
+  pub fn new(
+    state_repo: S,
+    identity_repo: I,
+    client: C,
+    role_repo: R,
+    cipher: SecretCipher,
+    jwt_secret: String,
+  ) -> Self {
+    Self {
+      state_repo,
+      identity_repo,
+      client,
+      role_repo,
+      cipher,
+      jwt_manager: JwtManager::new(jwt_secret),
+    }
+  }
+
+  /// Start the flow and return the state, which the browser must present
+  /// again on callback, and the GitHub consent URL. When `link_user_id` is
+  /// set the GitHub account is linked to that user instead of logging in.
+  pub async fn start(&self, link_user_id: Option<Uuid>) -> Result<(String, String)> {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let state = hex::encode(bytes);
+
+    let data = GithubOAuthState {
+      link_user_id,
+      created_at: time::OffsetDateTime::now_utc().unix_timestamp(),
+    };
+    self.state_repo.store_state(&state, &data).await?;
+
+    let authorize_url = self.client.authorize_url(&state);
+    Ok((state, authorize_url))
+  }
+
+  /// Resolve the user id of a wallet login, for linking.
+  pub async fn wallet_user_id(&self, user: &AuthUser) -> Result<Uuid> {
+    self
+      .identity_repo
+      .find_user_id(user.chain, &user.address)
+      .await?
+      .ok_or_else(Error::user_not_found)
+  }
+
+  /// Finish the flow. `browser_state` is the state `start` left in the
+  /// browser; it must match `state`, so a callback carrying someone else's
+  /// code and state cannot log the browser in or link to its user.
+  pub async fn complete(
+    &self,
+    code: &str,
+    state: &str,
+    browser_state: Option<&str>,
+  ) -> Result<GithubLoginResult> {
+    if browser_state != Some(state) {
+      error!("❌ GitHub OAuth state does not match the browser that started the flow");
+      return Err(Error::invalid_oauth_state());
+    }
+    let oauth_state = self.state_repo.take_state(state).await?.ok_or_else(|| {
+      error!("❌ Unknown or expired GitHub OAuth state");
+      Error::invalid_oauth_state()
+    })?;
+
+    let token = self.client.exchange_code(code).await?;
+    let profile = self.client.fetch_profile(&token.access_token).await?;
+    info!("🔗 GitHub OAuth completed for {} ({})", profile.login, profile.id);
+
+    let existing = self.identity_repo.find_by_github_id(profile.id).await?;
+    let user_id = match (oauth_state.link_user_id, &existing) {
+      (Some(link_user_id), Some(identity)) if identity.user_id != link_user_id => {
+        error!("❌ GitHub account {} already linked to another user", profile.login);
+        return Err(Error::github_account_already_linked());
+      }
+      (Some(link_user_id), _) => Some(link_user_id),
+      (None, Some(identity)) => Some(identity.user_id),
+      (None, None) => None,
+    };
+    let is_new_user = user_id.is_none();
+
+    let access_token_encrypted = self
+      .cipher
+      .encrypt(&token.access_token)
+      .map_err(|e| Error::internal_error(&e.to_string()))?;
+
+    let identity = self
+      .identity_repo
+      .upsert_identity(&GithubIdentityForUpsert {
+        user_id,
+        github_id: profile.id,
+        login: profile.login,
+        name: profile.name,
+        email: profile.email,
+        avatar_url: profile.avatar_url,
+        access_token_encrypted,
+        scopes: token.scope,
+      })
+      .await?;
+
+    let user = self
+      .identity_repo
+      .find_user(identity.user_id)
+      .await?
+      .ok_or_else(Error::user_not_found)?;
+
+    // Users with a wallet get the same tokens a wallet login would issue;
+    // GitHub-only users are identified by their GitHub id.
+    let (provider, subject) = if user.address.is_empty() {
+      (AuthProviderType::Github, identity.github_id.to_string())
+    } else {
+      (AuthProviderType::Wallet, user.address.clone())
+    };
+    let roles = self.role_repo.roles_for_subject(provider, user.chain, &subject).await?;
+    let tokens = self.jwt_manager.generate_provider_tokens(
+      provider,
+      user.chain,
+      &subject,
+      &user.public_key,
+      &roles,
+    )?;
+
+    info!("🎉 GitHub authentication successful for {}", identity.login);
+    Ok(GithubLoginResult {
+      user,
+      identity,
+      tokens,
+      is_new_user,
+      linked: oauth_state.link_user_id.is_some(),
+    })
+  }
+}
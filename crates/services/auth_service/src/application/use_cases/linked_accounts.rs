@@ -0,0 +1,114 @@
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::application::use_cases::{WalletProof, WalletProofVerifier};
+use crate::domain::{
+  AccountAction, AccountMerge, AccountRepository, AuthProviderType, Claims, LinkedWallet,
+  NonceRepository,
+};
+use crate::error::{Error, Result};
+use crate::models::AccountOverview;
+
+/// Wallets and GitHub identities linked under one account. Every change to
+/// the set of wallets needs a signature from the wallet concerned, over a
+/// nonce issued by `/auth/nonce` and a statement naming the change (see
+/// `AccountAction`).
+pub struct LinkedAccountsUseCase<A: AccountRepository, N: NonceRepository> {
+  accounts: A,
+  proof_verifier: WalletProofVerifier<N>,
+}
+
+impl<A: AccountRepository, N: NonceRepository> LinkedAccountsUseCase<A, N> {
+  pub fn new(accounts: A, proof_verifier: WalletProofVerifier<N>) -> Self {
+    Self { accounts, proof_verifier }
+  }
+
+  pub async fn overview(&self, caller: &Claims) -> Result<AccountOverview> {
+    let user_id = self.caller_id(caller).await?;
+
+    Ok(AccountOverview {
+      user_id,
+      wallets: self.accounts.wallets(user_id).await?,
+      github_logins: self.accounts.github_logins(user_id).await?,
+      reputation: self.accounts.reputation(user_id).await?,
+    })
+  }
+
+  /// Link a wallet that no other account logs in with.
+  pub async fn link_wallet(&self, caller: &Claims, proof: WalletProof<'_>) -> Result<LinkedWallet> {
+    let user_id = self.caller_id(caller).await?;
+    let wallet = self.proof_verifier.verify_action(proof, AccountAction::Link { user_id }).await?;
+
+    let owner = self.accounts.wallet_owner(wallet.chain, &wallet.address).await?;
+    if owner.is_some_and(|owner| owner != user_id) {
+      return Err(Error::wallet_already_linked());
+    }
+
+    let linked = self
+      .accounts
+      .link_wallet(user_id, wallet.chain, &wallet.address, &wallet.public_key)
+      .await?;
+    info!("🔗 Wallet {} {} linked to user {}", wallet.chain, wallet.address, user_id);
+    Ok(linked)
+  }
+
+  /// Unlink a wallet other than the one the caller's session belongs to. The
+  /// account must keep at least one login method.
+  pub async fn unlink_wallet(&self, caller: &Claims, proof: WalletProof<'_>) -> Result<()> {
+    let user_id = self.caller_id(caller).await?;
+    let wallet =
+      self.proof_verifier.verify_action(proof, AccountAction::Unlink { user_id }).await?;
+
+    if caller.provider == AuthProviderType::Wallet
+      && caller.chain == wallet.chain
+      && caller.chain.normalize_address(&caller.address) == wallet.address
+    {
+      return Err(Error::invalid_request_data("cannot unlink the wallet of the current session"));
+    }
+
+    if !self.accounts.unlink_wallet(user_id, wallet.chain, &wallet.address).await? {
+      return Err(Error::invalid_request_data("wallet is not linked to this account"));
+    }
+
+    info!("✂️ Wallet {} {} unlinked from user {}", wallet.chain, wallet.address, user_id);
+    Ok(())
+  }
+
+  /// Fold the account that owns the proven wallet into the caller's. Its
+  /// wallets, GitHub identities, reputation and owned organizations move
+  /// over; roles and other memberships do not, and are granted again.
+  pub async fn merge(&self, caller: &Claims, proof: WalletProof<'_>) -> Result<AccountMerge> {
+    let target = self.caller_id(caller).await?;
+
+    // The signature names both accounts, so the source must be known first
+    let address = proof.chain.normalize_address(proof.address);
+    let source = self
+      .accounts
+      .wallet_owner(proof.chain, &address)
+      .await?
+      .ok_or_else(Error::user_not_found)?;
+    if source == target {
+      return Err(Error::invalid_request_data("wallet already belongs to this account"));
+    }
+    self.proof_verifier.verify_action(proof, AccountAction::Merge { source, target }).await?;
+
+    let merge = self.accounts.merge_accounts(source, target).await?;
+    warn!(
+      target: "security_audit",
+      source_user_id = %source,
+      target_user_id = %target,
+      wallets_moved = merge.wallets_moved,
+      github_identities_moved = merge.github_identities_moved,
+      "Accounts merged"
+    );
+    Ok(merge)
+  }
+
+  async fn caller_id(&self, caller: &Claims) -> Result<Uuid> {
+    self
+      .accounts
+      .find_user_id(caller.provider, caller.chain, &caller.address)
+      .await?
+      .ok_or_else(Error::user_not_found)
+  }
+}
@@ -0,0 +1,44 @@
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::{Role, RoleAssignment, RoleRepository};
+use crate::error::{Error, Result};
+
+pub struct ManageRolesUseCase<R: RoleRepository> {
+  role_repo: R,
+}
+
+impl<R: RoleRepository> ManageRolesUseCase<R> {
+  pub fn new(role_repo: R) -> Self {
+    Self { role_repo }
+  }
+
+  pub async fn list_roles(&self) -> Result<Vec<Role>> {
+    self.role_repo.list_roles().await
+  }
+
+  pub async fn user_roles(&self, user_id: Uuid) -> Result<Vec<RoleAssignment>> {
+    self.role_repo.user_assignments(user_id).await
+  }
+
+  pub async fn grant(
+    &self,
+    user_id: Uuid,
+    role: &str,
+    granted_by: Option<&str>,
+  ) -> Result<RoleAssignment> {
+    let assignment = self.role_repo.grant_role(user_id, role, granted_by).await?;
+    let granted_by = granted_by.unwrap_or("system");
+    info!("🛡️ Role '{}' granted to user {} by {}", role, user_id, granted_by);
+    Ok(assignment)
+  }
+
+  pub async fn revoke(&self, user_id: Uuid, role: &str, revoked_by: Option<&str>) -> Result<()> {
+    if !self.role_repo.revoke_role(user_id, role).await? {
+      return Err(Error::role_not_found(role));
+    }
+    let revoked_by = revoked_by.unwrap_or("system");
+    info!("🛡️ Role '{}' revoked from user {} by {}", role, user_id, revoked_by);
+    Ok(())
+  }
+}
@@ -1,11 +1,31 @@
+pub mod alert_channels;
+pub mod flag_subject;
 pub mod generate_nonce;
+pub mod github_oauth;
+pub mod linked_accounts;
+pub mod manage_roles;
+pub mod notification_preferences;
+pub mod notifications;
+pub mod onboarding;
+pub mod purge_nonces;
 pub mod refresh_token;
 pub mod validate_token;
 pub mod verify_signature;
 pub mod unified_auth;
+pub mod wallet_proof;
 
+pub use alert_channels::AlertChannelsUseCase;
+pub use flag_subject::FlagSubjectUseCase;
 pub use generate_nonce::GenerateNonceUseCase;
+pub use github_oauth::GithubOAuthUseCase;
+pub use linked_accounts::LinkedAccountsUseCase;
+pub use manage_roles::ManageRolesUseCase;
+pub use notification_preferences::NotificationPreferencesUseCase;
+pub use notifications::NotificationsUseCase;
+pub use onboarding::OnboardingUseCase;
+pub use purge_nonces::PurgeNoncesUseCase;
 pub use refresh_token::RefreshTokenUseCase;
 pub use validate_token::ValidateTokenUseCase;
 pub use verify_signature::VerifySignatureUseCase;
 pub use unified_auth::UnifiedAuthService;
+pub use wallet_proof::{VerifiedWallet, WalletProof, WalletProofVerifier};
@@ -0,0 +1,50 @@
+use jd_email::{NotificationPreferences, PreferenceStore};
+use uuid::Uuid;
+
+use crate::domain::{AccountRepository, Claims};
+use crate::error::{Error, Result};
+use crate::models::UpdateNotificationPreferencesRequest;
+
+/// Which transactional emails the caller receives.
+pub struct NotificationPreferencesUseCase<A: AccountRepository> {
+  accounts: A,
+  preferences: PreferenceStore,
+}
+
+impl<A: AccountRepository> NotificationPreferencesUseCase<A> {
+  pub fn new(accounts: A, preferences: PreferenceStore) -> Self {
+    Self { accounts, preferences }
+  }
+
+  pub async fn get(&self, caller: &Claims) -> Result<NotificationPreferences> {
+    let user_id = self.caller_id(caller).await?;
+    Ok(self.preferences.get(user_id).await?)
+  }
+
+  pub async fn update(
+    &self,
+    caller: &Claims,
+    request: &UpdateNotificationPreferencesRequest,
+  ) -> Result<NotificationPreferences> {
+    let user_id = self.caller_id(caller).await?;
+    let current = self.preferences.get(user_id).await?;
+    let updated = NotificationPreferences {
+      analysis_complete: request.analysis_complete.unwrap_or(current.analysis_complete),
+      critical_vulnerability: request
+        .critical_vulnerability
+        .unwrap_or(current.critical_vulnerability),
+      patch_approved: request.patch_approved.unwrap_or(current.patch_approved),
+      weekly_digest: request.weekly_digest.unwrap_or(current.weekly_digest),
+    };
+    self.preferences.save(user_id, &updated).await?;
+    Ok(updated)
+  }
+
+  async fn caller_id(&self, caller: &Claims) -> Result<Uuid> {
+    self
+      .accounts
+      .find_user_id(caller.provider, caller.chain, &caller.address)
+      .await?
+      .ok_or_else(Error::user_not_found)
+  }
+}
@@ -0,0 +1,97 @@
+use jd_notifications::{InAppPreferenceStore, InAppPreferences, NotificationStore};
+use uuid::Uuid;
+
+use crate::domain::{AccountRepository, Claims};
+use crate::error::{Error, Result};
+use crate::models::{ListNotificationsQuery, NotificationList, UpdateInAppPreferencesRequest};
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+
+/// The caller's notification center.
+pub struct NotificationsUseCase<A: AccountRepository> {
+  accounts: A,
+  notifications: NotificationStore,
+  preferences: InAppPreferenceStore,
+}
+
+impl<A: AccountRepository> NotificationsUseCase<A> {
+  pub fn new(
+    accounts: A,
+    notifications: NotificationStore,
+    preferences: InAppPreferenceStore,
+  ) -> Self {
+    Self { accounts, notifications, preferences }
+  }
+
+  pub async fn list(
+    &self,
+    caller: &Claims,
+    query: &ListNotificationsQuery,
+  ) -> Result<NotificationList> {
+    let user_id = self.caller_id(caller).await?;
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let notifications =
+      self.notifications.list(user_id, query.unread_only, query.before, limit).await?;
+    // A full page may have more after it
+    let next_before = notifications
+      .last()
+      .filter(|_| notifications.len() as i64 == limit)
+      .map(|notification| notification.id);
+    Ok(NotificationList {
+      notifications,
+      unread_count: self.notifications.unread_count(user_id).await?,
+      next_before,
+    })
+  }
+
+  /// Mark one notification read. Returns the caller's id and unread count.
+  pub async fn mark_read(&self, caller: &Claims, id: i64) -> Result<(Uuid, i64)> {
+    let user_id = self.caller_id(caller).await?;
+    if !self.notifications.mark_read(user_id, id).await? {
+      return Err(Error::notification_not_found());
+    }
+    Ok((user_id, self.notifications.unread_count(user_id).await?))
+  }
+
+  /// Returns the caller's id.
+  pub async fn mark_all_read(&self, caller: &Claims) -> Result<Uuid> {
+    let user_id = self.caller_id(caller).await?;
+    self.notifications.mark_all_read(user_id).await?;
+    Ok(user_id)
+  }
+
+  pub async fn preferences(&self, caller: &Claims) -> Result<InAppPreferences> {
+    let user_id = self.caller_id(caller).await?;
+    Ok(self.preferences.get(user_id).await?)
+  }
+
+  pub async fn update_preferences(
+    &self,
+    caller: &Claims,
+    request: &UpdateInAppPreferencesRequest,
+  ) -> Result<InAppPreferences> {
+    let user_id = self.caller_id(caller).await?;
+    let current = self.preferences.get(user_id).await?;
+    let updated = InAppPreferences {
+      analysis_complete: request.analysis_complete.unwrap_or(current.analysis_complete),
+      critical_vulnerability: request
+        .critical_vulnerability
+        .unwrap_or(current.critical_vulnerability),
+      patch_review_requested: request
+        .patch_review_requested
+        .unwrap_or(current.patch_review_requested),
+      score_changed: request.score_changed.unwrap_or(current.score_changed),
+    };
+    self.preferences.save(user_id, &updated).await?;
+    Ok(updated)
+  }
+
+  async fn caller_id(&self, caller: &Claims) -> Result<Uuid> {
+    self
+      .accounts
+      .find_user_id(caller.provider, caller.chain, &caller.address)
+      .await?
+      .ok_or_else(Error::user_not_found)
+  }
+}
@@ -0,0 +1,206 @@
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use time::{Duration, OffsetDateTime};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::{
+  Claims, NewOrganization, OnboardingSettings, Organization, OrganizationMember,
+  OrganizationRepository, OrganizationRole, is_valid_slug, slugify,
+};
+use crate::error::{Error, Result};
+use crate::models::{
+  CreateOrganizationRequest, InvitationResponse, InviteMemberRequest, OrganizationOverview,
+};
+
+/// Self-serve organization signup, invitations and trial lifecycle.
+pub struct OnboardingUseCase<O: OrganizationRepository> {
+  org_repo: O,
+  settings: OnboardingSettings,
+}
+
+impl<O: OrganizationRepository> OnboardingUseCase<O> {
+  pub fn new(org_repo: O, settings: OnboardingSettings) -> Self {
+    Self { org_repo, settings }
+  }
+
+  /// Create a trial organization owned by the caller, with the default quotas
+  /// and the sample repository registered.
+  pub async fn signup(
+    &self,
+    caller: &Claims,
+    request: CreateOrganizationRequest,
+  ) -> Result<OrganizationOverview> {
+    let owner_id = self.caller_id(caller).await?;
+
+    let name = request.name.trim().to_string();
+    let slug = request.slug.unwrap_or_else(|| slugify(&name));
+    if !is_valid_slug(&slug) {
+      return Err(Error::invalid_request_data("slug"));
+    }
+    if self.org_repo.slug_exists(&slug).await? {
+      return Err(Error::organization_slug_taken(&slug));
+    }
+
+    let quotas = self.settings.quotas;
+    let organization = self
+      .org_repo
+      .create_organization(NewOrganization {
+        name,
+        slug,
+        owner_id,
+        trial_ends_at: OffsetDateTime::now_utc() + Duration::days(quotas.trial_days),
+        quotas,
+        sample_repository: self.settings.sample_repository.clone(),
+      })
+      .await?;
+
+    info!(
+      "🏢 Organization '{}' ({}) created by user {}, trial ends {:?}",
+      organization.slug, organization.id, owner_id, organization.trial_ends_at
+    );
+
+    self.overview_of(organization).await
+  }
+
+  /// Organization details, visible to its members only.
+  pub async fn overview(
+    &self,
+    caller: &Claims,
+    organization_id: Uuid,
+  ) -> Result<OrganizationOverview> {
+    let user_id = self.caller_id(caller).await?;
+    self.require_member(organization_id, user_id).await?;
+
+    let organization = self.find(organization_id).await?;
+    self.overview_of(organization).await
+  }
+
+  /// Invite someone by email. Returns the one-time invitation token, which
+  /// is only stored hashed.
+  pub async fn invite(
+    &self,
+    caller: &Claims,
+    organization_id: Uuid,
+    request: InviteMemberRequest,
+  ) -> Result<InvitationResponse> {
+    let inviter_id = self.caller_id(caller).await?;
+    if !self.require_member(organization_id, inviter_id).await?.can_invite() {
+      return Err(Error::insufficient_permissions());
+    }
+
+    let role = request.role.unwrap_or_default();
+    if role == OrganizationRole::Owner {
+      return Err(Error::invalid_request_data("role"));
+    }
+
+    let organization = self.find(organization_id).await?;
+    if !organization.is_usable(OffsetDateTime::now_utc()) {
+      return Err(Error::trial_expired());
+    }
+
+    let max_members = i64::from(organization.max_members);
+    if self.org_repo.seats_used(organization_id).await? >= max_members {
+      return Err(Error::quota_exceeded("max_members", max_members));
+    }
+
+    let token = generate_invitation_token();
+    let invitation = self
+      .org_repo
+      .create_invitation(
+        organization_id,
+        request.email.trim(),
+        role,
+        &hash_invitation_token(&token),
+        inviter_id,
+        OffsetDateTime::now_utc() + self.settings.invitation_ttl,
+      )
+      .await?;
+
+    info!(
+      "✉️ Invitation {} to organization {} created by user {}",
+      invitation.id, organization_id, inviter_id
+    );
+
+    Ok(InvitationResponse { invitation, token })
+  }
+
+  pub async fn accept_invitation(
+    &self,
+    caller: &Claims,
+    token: &str,
+  ) -> Result<OrganizationMember> {
+    let user_id = self.caller_id(caller).await?;
+
+    let member = self
+      .org_repo
+      .accept_invitation(&hash_invitation_token(token), user_id)
+      .await?
+      .ok_or_else(Error::invalid_invitation)?;
+
+    info!("🤝 User {} joined organization {}", user_id, member.organization_id);
+    Ok(member)
+  }
+
+  /// Expire every lapsed trial. Run periodically by the scheduler.
+  pub async fn expire_trials(&self) -> Result<Vec<Uuid>> {
+    let expired = self.org_repo.expire_trials(OffsetDateTime::now_utc()).await?;
+    for organization_id in &expired {
+      info!("⌛ Trial expired for organization {}", organization_id);
+    }
+    Ok(expired)
+  }
+
+  async fn caller_id(&self, caller: &Claims) -> Result<Uuid> {
+    self
+      .org_repo
+      .find_user_id(caller.provider, caller.chain, &caller.address)
+      .await?
+      .ok_or_else(Error::user_not_found)
+  }
+
+  async fn find(&self, organization_id: Uuid) -> Result<Organization> {
+    self
+      .org_repo
+      .find_organization(organization_id)
+      .await?
+      .ok_or_else(Error::organization_not_found)
+  }
+
+  /// Non-members get the same error as for a missing organization.
+  async fn require_member(
+    &self,
+    organization_id: Uuid,
+    user_id: Uuid,
+  ) -> Result<OrganizationRole> {
+    self
+      .org_repo
+      .member_role(organization_id, user_id)
+      .await?
+      .ok_or_else(Error::organization_not_found)
+  }
+
+  async fn overview_of(&self, organization: Organization) -> Result<OrganizationOverview> {
+    let members = self.org_repo.members(organization.id).await?;
+    let pending_invitations = self.org_repo.pending_invitations(organization.id).await?;
+    let repositories = self.org_repo.repositories(organization.id).await?;
+
+    Ok(OrganizationOverview {
+      is_active: organization.is_usable(OffsetDateTime::now_utc()),
+      organization,
+      members,
+      pending_invitations,
+      repositories,
+    })
+  }
+}
+
+fn generate_invitation_token() -> String {
+  let mut bytes = [0u8; 32];
+  rand::thread_rng().fill_bytes(&mut bytes);
+  hex::encode(bytes)
+}
+
+fn hash_invitation_token(token: &str) -> String {
+  hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,23 @@
+use tracing::info;
+
+use crate::domain::NonceRepository;
+use crate::error::Result;
+
+/// Housekeeping for nonce backends without native key expiry.
+pub struct PurgeNoncesUseCase<R: NonceRepository> {
+  repository: R,
+}
+
+impl<R: NonceRepository> PurgeNoncesUseCase<R> {
+  pub fn new(repository: R) -> Self {
+    Self { repository }
+  }
+
+  pub async fn execute(&self) -> Result<u64> {
+    let purged = self.repository.purge_expired().await?;
+    if purged > 0 {
+      info!("🧹 Purged {} expired nonce record(s)", purged);
+    }
+    Ok(purged)
+  }
+}
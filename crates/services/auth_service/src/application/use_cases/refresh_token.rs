@@ -1,22 +1,27 @@
 use tracing::info;
 
-use crate::domain::JwtManager;
+use crate::domain::{JwtManager, RoleRepository};
 use crate::error::Result;
 
-pub struct RefreshTokenUseCase {
+pub struct RefreshTokenUseCase<R: RoleRepository> {
+  role_repo: R,
   jwt_manager: JwtManager,
 }
 
-impl RefreshTokenUseCase {
-  pub fn new(jwt_secret: String) -> Self {
-    Self { jwt_manager: JwtManager::new(jwt_secret) }
+impl<R: RoleRepository> RefreshTokenUseCase<R> {
+  pub fn new(role_repo: R, jwt_secret: String) -> Self {
+    Self { role_repo, jwt_manager: JwtManager::new(jwt_secret) }
   }
 
   pub async fn execute(&self, refresh_token: &str) -> Result<String> {
     info!("🔄 Refreshing access token");
 
-    // Validate refresh token and generate new access token
-    let access_token = self.jwt_manager.refresh_access_token(refresh_token)?;
+    // Validate refresh token, then re-resolve roles so grants and revocations
+    // take effect without a new login
+    let claims = self.jwt_manager.validate_refresh_token(refresh_token)?;
+    let roles =
+      self.role_repo.roles_for_subject(claims.provider, claims.chain, &claims.address).await?;
+    let access_token = self.jwt_manager.refresh_access_token(&claims, &roles)?;
 
     info!("✅ Access token refreshed successfully");
     Ok(access_token)
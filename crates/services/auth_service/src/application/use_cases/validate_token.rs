@@ -1,6 +1,6 @@
 use tracing::{error, info};
 
-use crate::domain::{AuthUser, JwtManager, UserRepository};
+use crate::domain::{AuthProviderType, AuthUser, JwtManager, UserRepository};
 use crate::error::{Error, Result};
 
 pub struct ValidateTokenUseCase<R: UserRepository> {
@@ -26,14 +26,17 @@ impl<R: UserRepository> ValidateTokenUseCase<R> {
     }
 
     // Get user from database
-    let user = self
-      .user_repo
-      .get_user(&claims.address)
-      .await?
-      .ok_or_else(|| {
-        error!("❌ User not found for address: {}", claims.address);
-        Error::invalid_token()
-      })?;
+    let user = match claims.provider {
+      AuthProviderType::Wallet => self.user_repo.get_user(claims.chain, &claims.address).await?,
+      AuthProviderType::Github => {
+        let github_id = claims.address.parse().map_err(|_| Error::invalid_token())?;
+        self.user_repo.get_user_by_github_id(github_id).await?
+      }
+    }
+    .ok_or_else(|| {
+      error!("❌ User not found for address: {}", claims.address);
+      Error::invalid_token()
+    })?;
 
     // Verify public key matches
     if user.public_key != claims.public_key {
@@ -1,74 +1,56 @@
-use tracing::{error, info, warn};
+use tracing::info;
 
+use crate::application::use_cases::{WalletProof, WalletProofVerifier};
 use crate::domain::{
-  AuthUser, JwtManager, NonceRepository, SignatureVerifier, TokenPair, UserRepository,
+  AuthProviderType, AuthUser, Chain, JwtManager, LoginMessageSettings, NonceRepository,
+  RoleRepository, TokenPair, UserRepository,
 };
-use crate::error::{Error, Result};
+use crate::error::Result;
+use crate::infrastructure::SignatureVerifierRegistry;
 
-pub struct VerifySignatureUseCase<N: NonceRepository, U: UserRepository, S: SignatureVerifier> {
-  nonce_repo: N,
+pub struct VerifySignatureUseCase<N: NonceRepository, U: UserRepository, R: RoleRepository> {
+  proof_verifier: WalletProofVerifier<N>,
   user_repo: U,
-  signature_verifier: S,
+  role_repo: R,
   jwt_manager: JwtManager,
 }
 
-impl<N: NonceRepository, U: UserRepository, S: SignatureVerifier> VerifySignatureUseCase<N, U, S> {
-  pub fn new(nonce_repo: N, user_repo: U, signature_verifier: S, jwt_secret: String) -> Self {
-    Self { nonce_repo, user_repo, signature_verifier, jwt_manager: JwtManager::new(jwt_secret) }
+impl<N: NonceRepository, U: UserRepository, R: RoleRepository> VerifySignatureUseCase<N, U, R> {
+  pub fn new(
+    nonce_repo: N,
+    user_repo: U,
+    role_repo: R,
+    verifiers: SignatureVerifierRegistry,
+    jwt_secret: String,
+    login_settings: LoginMessageSettings,
+  ) -> Self {
+    Self {
+      proof_verifier: WalletProofVerifier::new(nonce_repo, verifiers, login_settings),
+      user_repo,
+      role_repo,
+      jwt_manager: JwtManager::new(jwt_secret),
+    }
   }
 
   pub async fn execute(
     &self,
+    chain: Chain,
     address: &str,
     signature: &str,
     public_key: &str,
+    signed_message: Option<&str>,
   ) -> Result<(AuthUser, TokenPair)> {
-    info!("🚀 Starting signature verification for address: {}", address);
-
-    // Validate address format
-    if !AuthUser::is_valid_address(address) {
-      error!("❌ Invalid address format: {}", address);
-      return Err(Error::invalid_address());
-    }
-
-    // Get stored nonce
-    let nonce = self.nonce_repo.get_nonce(address).await?.ok_or_else(|| {
-      error!("❌ Nonce not found for address: {}", address);
-      Error::nonce_not_found()
-    })?;
-
-    info!("✅ Nonce found for address: {}", address);
-
-    // Check if nonce has expired
-    if nonce.is_expired() {
-      warn!("⚠️ Nonce expired for address: {}", address);
-      self.nonce_repo.remove_nonce(address).await?;
-      return Err(Error::nonce_expired());
-    }
-
-    // Get the message that should have been signed
-    let message = nonce.get_signing_message();
-    info!("📝 Expected message: {}", message);
+    info!("🚀 Starting {} signature verification for address: {}", chain, address);
 
-    // Verify signature
-    let is_valid = self
-      .signature_verifier
-      .verify_signature(&message, signature, public_key, address)
+    let wallet = self
+      .proof_verifier
+      .verify(WalletProof { chain, address, signature, public_key, message: signed_message })
       .await?;
-
-    if !is_valid {
-      error!("❌ Signature verification failed for address: {}", address);
-      return Err(Error::invalid_signature());
-    }
-
-    info!("✅ Signature verified successfully for address: {}", address);
-
-    // Remove used nonce
-    self.nonce_repo.remove_nonce(address).await?;
-    info!("🗑️ Used nonce removed for address: {}", address);
+    let address = wallet.address.as_str();
+    let public_key = wallet.public_key.as_str();
 
     // Get or create user
-    let user = match self.user_repo.get_user(address).await? {
+    let user = match self.user_repo.get_user(chain, address).await? {
       Some(mut existing_user) => {
         info!("👤 Existing user found, updating login info");
         // Update login info
@@ -80,16 +62,17 @@ impl<N: NonceRepository, U: UserRepository, S: SignatureVerifier> VerifySignatur
       None => {
         info!("👤 Creating new user");
         // Create new user
-        let new_user = AuthUser::new(address.to_string(), public_key.to_string());
+        let new_user = AuthUser::new(chain, address.to_string(), public_key.to_string());
         self.user_repo.create_user(&new_user).await?;
         new_user
       }
     };
 
-    // Generate JWT tokens
-    let tokens = self
-      .jwt_manager
-      .generate_tokens(&user.address, &user.public_key)?;
+    // Generate JWT tokens carrying the user's roles
+    let roles =
+      self.role_repo.roles_for_subject(AuthProviderType::Wallet, user.chain, &user.address).await?;
+    let tokens =
+      self.jwt_manager.generate_tokens(user.chain, &user.address, &user.public_key, &roles)?;
 
     info!("🎉 Authentication successful for address: {}", address);
     Ok((user, tokens))
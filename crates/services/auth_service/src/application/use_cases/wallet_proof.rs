@@ -0,0 +1,213 @@
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+use crate::domain::{
+  AccountAction, Chain, LoginMessage, LoginMessageSettings, LoginMessageVersion, Nonce,
+  NonceRepository, SignatureVerifier,
+};
+use crate::error::{Error, Result};
+use crate::infrastructure::SignatureVerifierRegistry;
+
+/// A signature over the challenge issued for `address`.
+#[derive(Debug, Clone, Copy)]
+pub struct WalletProof<'a> {
+  pub chain: Chain,
+  pub address: &'a str,
+  pub signature: &'a str,
+  pub public_key: &'a str,
+  /// The structured message exactly as signed, when the client sends it back.
+  pub message: Option<&'a str>,
+}
+
+/// A wallet whose control has been proven.
+#[derive(Debug, Clone)]
+pub struct VerifiedWallet {
+  pub chain: Chain,
+  /// Normalized for storage and lookups.
+  pub address: String,
+  pub public_key: String,
+}
+
+/// Proves control of a wallet by verifying the signature over the message
+/// issued with its pending nonce, which is consumed once it checks out. Used for login as well as
+/// for account changes, whose signed message must name the change.
+pub struct WalletProofVerifier<N: NonceRepository> {
+  nonce_repo: N,
+  verifiers: SignatureVerifierRegistry,
+  login_settings: LoginMessageSettings,
+}
+
+impl<N: NonceRepository> WalletProofVerifier<N> {
+  pub fn new(
+    nonce_repo: N,
+    verifiers: SignatureVerifierRegistry,
+    login_settings: LoginMessageSettings,
+  ) -> Self {
+    Self { nonce_repo, verifiers, login_settings }
+  }
+
+  /// Prove control of a wallet to log in with it.
+  pub async fn verify(&self, proof: WalletProof<'_>) -> Result<VerifiedWallet> {
+    self.verify_for(proof, None).await
+  }
+
+  /// Prove control of a wallet to make `action`. Only structured messages
+  /// whose statement is the action's own are accepted.
+  pub async fn verify_action(
+    &self,
+    proof: WalletProof<'_>,
+    action: AccountAction,
+  ) -> Result<VerifiedWallet> {
+    self.verify_for(proof, Some(action)).await
+  }
+
+  async fn verify_for(
+    &self,
+    proof: WalletProof<'_>,
+    action: Option<AccountAction>,
+  ) -> Result<VerifiedWallet> {
+    let chain = proof.chain;
+
+    // Validate address format
+    if !chain.is_valid_address(proof.address) {
+      error!("❌ Invalid {} address format: {}", chain, proof.address);
+      return Err(Error::invalid_address());
+    }
+    let address = chain.normalize_address(proof.address);
+    let address = address.as_str();
+
+    // Chains that recover the key from the address or signature may omit it;
+    // the address then doubles as the stored key.
+    let public_key = match proof.public_key {
+      "" if chain.public_key_optional() => address,
+      "" => return Err(Error::invalid_public_key()),
+      key => key,
+    };
+
+    let signature_verifier = self.verifiers.get(chain)?;
+
+    // The nonce stays locked while the signature is checked and is only
+    // consumed if it checks out, so a bad signature cannot burn it
+    let Some(lock) = self.nonce_repo.lock_nonce(chain, address).await? else {
+      if self.replayed(chain, address, proof.message, None).await? {
+        return Err(Error::nonce_replayed());
+      }
+      error!("❌ Nonce not found for address: {}", address);
+      return Err(Error::nonce_not_found());
+    };
+    let nonce = lock.nonce().clone();
+
+    let checked =
+      self.check_signature(&nonce, proof, public_key, address, action, signature_verifier).await;
+    if let Err(e) = checked {
+      lock.release().await?;
+      return Err(e);
+    }
+    lock.consume().await?;
+
+    info!("✅ Signature verified and nonce consumed for address: {}", address);
+
+    Ok(VerifiedWallet { chain, address: address.to_string(), public_key: public_key.to_string() })
+  }
+
+  /// Check the proof against the locked nonce.
+  async fn check_signature(
+    &self,
+    nonce: &Nonce,
+    proof: WalletProof<'_>,
+    public_key: &str,
+    address: &str,
+    action: Option<AccountAction>,
+    signature_verifier: &dyn SignatureVerifier,
+  ) -> Result<()> {
+    if self.replayed(nonce.chain, address, proof.message, Some(nonce)).await? {
+      return Err(Error::nonce_replayed());
+    }
+    if nonce.is_expired() {
+      warn!("⚠️ Nonce expired for address: {}", address);
+      return Err(Error::nonce_expired());
+    }
+
+    // Get the message that should have been signed
+    let message = self.signed_message(nonce, proof.message, action)?;
+    info!("📝 Expected message: {}", message);
+
+    let is_valid =
+      signature_verifier.verify_signature(&message, proof.signature, public_key, address).await?;
+    if !is_valid {
+      error!("❌ Signature verification failed for address: {}", address);
+      return Err(Error::invalid_signature());
+    }
+    Ok(())
+  }
+
+  /// Whether the signed message names a nonce, other than the pending one,
+  /// that was already consumed. Legacy messages name none.
+  async fn replayed(
+    &self,
+    chain: Chain,
+    address: &str,
+    signed_message: Option<&str>,
+    pending: Option<&Nonce>,
+  ) -> Result<bool> {
+    let Some(signed) = signed_message.and_then(|m| LoginMessage::parse(m).ok()) else {
+      return Ok(false);
+    };
+    if pending.is_some_and(|pending| pending.nonce == signed.nonce) {
+      return Ok(false);
+    }
+    let replayed = self.nonce_repo.is_consumed(chain, address, &signed.nonce).await?;
+    if replayed {
+      warn!(target: "security_audit", %chain, address, "Replayed login nonce rejected");
+    }
+    Ok(replayed)
+  }
+
+  /// The message to check the signature against. Structured messages sent
+  /// back by the client are verified field by field against the issued one,
+  /// with the action's statement in place of the login statement; legacy
+  /// nonces are only honoured for logins, while legacy messages are allowed.
+  fn signed_message(
+    &self,
+    nonce: &Nonce,
+    signed_message: Option<&str>,
+    action: Option<AccountAction>,
+  ) -> Result<String> {
+    match nonce.message_version {
+      // A bare nonce says nothing about what it authorises
+      LoginMessageVersion::Legacy if action.is_some() || !self.login_settings.allow_legacy => {
+        Err(Error::unsupported_message_version())
+      }
+      LoginMessageVersion::Legacy => Ok(nonce.get_signing_message()),
+      LoginMessageVersion::Structured => {
+        let mut expected = LoginMessage::for_nonce(nonce, &self.login_settings);
+        if let Some(action) = action {
+          expected.statement = action.statement();
+        }
+        let Some(signed) = signed_message else {
+          return Ok(expected.to_string());
+        };
+
+        let verified = LoginMessage::parse(signed).and_then(|m| {
+          m.verify(&expected, Utc::now())?;
+          let statement_ok = match action {
+            Some(_) => m.statement == expected.statement,
+            None => !AccountAction::is_action_statement(&m.statement),
+          };
+          if statement_ok { Ok(()) } else { Err(Error::invalid_login_message("statement")) }
+        });
+        if let Err(e) = verified {
+          warn!(
+            target: "security_audit",
+            chain = %nonce.chain,
+            address = %nonce.address,
+            error = %e,
+            "Login message rejected"
+          );
+          return Err(e);
+        }
+        Ok(signed.to_string())
+      }
+    }
+  }
+}
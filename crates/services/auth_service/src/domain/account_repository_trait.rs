@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{AccountMerge, AccountReputation, AuthProviderType, Chain, LinkedWallet};
+use crate::error::Result;
+
+#[async_trait]
+pub trait AccountRepository: Send + Sync {
+  /// Map a token subject to its `users.id`, through any linked wallet.
+  async fn find_user_id(
+    &self,
+    provider: AuthProviderType,
+    chain: Chain,
+    subject: &str,
+  ) -> Result<Option<Uuid>>;
+  async fn wallets(&self, user_id: Uuid) -> Result<Vec<LinkedWallet>>;
+  async fn github_logins(&self, user_id: Uuid) -> Result<Vec<String>>;
+  async fn reputation(&self, user_id: Uuid) -> Result<AccountReputation>;
+  /// The account an address currently logs into.
+  async fn wallet_owner(&self, chain: Chain, address: &str) -> Result<Option<Uuid>>;
+  async fn link_wallet(
+    &self,
+    user_id: Uuid,
+    chain: Chain,
+    address: &str,
+    public_key: &str,
+  ) -> Result<LinkedWallet>;
+  /// Returns false when the address is not linked to the account.
+  async fn unlink_wallet(&self, user_id: Uuid, chain: Chain, address: &str) -> Result<bool>;
+  /// Move every wallet, GitHub identity, reputation record and owned
+  /// organization of `source` to `target`, drop its roles and other
+  /// memberships and tombstone `source`, atomically.
+  async fn merge_accounts(&self, source: Uuid, target: Uuid) -> Result<AccountMerge>;
+}
@@ -3,10 +3,12 @@ use std::fmt::Display;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "auth_provider", rename_all = "lowercase")]
 pub enum AuthProviderType {
+    #[default]
     Wallet,
+    Github,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
@@ -54,7 +56,7 @@ pub struct UserAuthProviderForUpdate {
 
 impl AuthProviderType {
     pub fn all() -> Vec<AuthProviderType> {
-        vec![AuthProviderType::Wallet]
+        vec![AuthProviderType::Wallet, AuthProviderType::Github]
     }
 
     pub fn requires_wallet(&self) -> bool {
@@ -66,6 +68,7 @@ impl Display for AuthProviderType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let provider_str = match self {
             AuthProviderType::Wallet => "wallet",
+            AuthProviderType::Github => "github",
         };
         write!(f, "{}", provider_str)
     }
@@ -5,6 +5,7 @@ use sqlx::FromRow;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use super::chain::Chain;
 use super::user_role::UserRole;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, Fields)]
@@ -116,6 +117,7 @@ impl UnifiedAuthUser {
 // Legacy struct for backward compatibility
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, Fields)]
 pub struct AuthUser {
+    pub chain: Chain,
     pub address: String,
     pub public_key: String,
     #[serde(with = "time::serde::rfc3339")]
@@ -127,6 +129,7 @@ pub struct AuthUser {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Fields)]
 pub struct AuthUserForCreate {
+    pub chain: Chain,
     pub address: String,
     pub public_key: String,
     #[serde(with = "time::serde::rfc3339")]
@@ -146,13 +149,14 @@ pub struct AuthUserForUpdate {
 
 #[derive(Debug, Clone, Deserialize, FilterNodes)]
 pub struct AuthUserFilter {
+    pub chain: Option<OpValsString>,
     pub address: Option<OpValsString>,
 }
 
 impl AuthUser {
-    pub fn new(address: String, public_key: String) -> Self {
+    pub fn new(chain: Chain, address: String, public_key: String) -> Self {
         let now = OffsetDateTime::now_utc();
-        Self { address, public_key, created_at: now, last_login: now, login_count: 1 }
+        Self { chain, address, public_key, created_at: now, last_login: now, login_count: 1 }
     }
 
     pub fn update_login(&mut self) {
@@ -160,17 +164,9 @@ impl AuthUser {
         self.login_count += 1;
     }
 
-    pub fn is_valid_address(address: &str) -> bool {
-        if !address.starts_with("0x") {
-            return false;
-        }
-
-        let hex_part = &address[2..];
-        hex_part.len() == 64 && hex_part.chars().all(|c| c.is_ascii_hexdigit())
-    }
-
     pub fn into_create_input(self) -> AuthUserForCreate {
         AuthUserForCreate {
+            chain: self.chain,
             address: self.address,
             public_key: self.public_key,
             created_at: self.created_at,
@@ -192,6 +188,7 @@ impl AuthUser {
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, Fields)]
 pub struct ZkPersonaUser {
     pub id: Uuid,
+    pub chain: Chain,
     pub wallet_address: Option<String>,
     pub public_key: Option<String>,
     #[serde(with = "time::serde::rfc3339::option")]
@@ -206,6 +203,7 @@ pub struct ZkPersonaUser {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Fields)]
 pub struct ZkPersonaUserForCreate {
+    pub chain: Chain,
     pub wallet_address: Option<String>,
     pub public_key: Option<String>,
     #[serde(with = "time::serde::rfc3339::option")]
@@ -224,6 +222,7 @@ pub struct ZkPersonaUserForUpdate {
 
 #[derive(Debug, Clone, Deserialize, FilterNodes)]
 pub struct ZkPersonaUserFilter {
+    pub chain: Option<OpValsString>,
     pub wallet_address: Option<OpValsString>,
     pub id: Option<OpValsValue>,
 }
@@ -231,6 +230,7 @@ pub struct ZkPersonaUserFilter {
 impl From<&AuthUser> for ZkPersonaUserForCreate {
     fn from(auth_user: &AuthUser) -> Self {
         Self {
+            chain: auth_user.chain,
             wallet_address: Some(auth_user.address.clone()),
             public_key: Some(auth_user.public_key.clone()),
             last_login: Some(auth_user.last_login),
@@ -243,6 +243,7 @@ impl From<&AuthUser> for ZkPersonaUserForCreate {
 impl From<ZkPersonaUser> for AuthUser {
     fn from(zk_user: ZkPersonaUser) -> Self {
         Self {
+            chain: zk_user.chain,
             address: zk_user.wallet_address.unwrap_or_default(),
             public_key: zk_user.public_key.unwrap_or_default(),
             created_at: zk_user.ctime,
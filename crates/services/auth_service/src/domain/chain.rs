@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Wallet ecosystem a login address belongs to. Selects the address rules and
+/// the `SignatureVerifier` used during authentication.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Chain {
+  #[default]
+  Sui,
+  Evm,
+  Solana,
+  Aptos,
+}
+
+impl Chain {
+  pub fn all() -> Vec<Chain> {
+    vec![Chain::Sui, Chain::Evm, Chain::Solana, Chain::Aptos]
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Chain::Sui => "sui",
+      Chain::Evm => "evm",
+      Chain::Solana => "solana",
+      Chain::Aptos => "aptos",
+    }
+  }
+
+  /// Whether the wallet's public key can be recovered from the address or the
+  /// signature, so clients may omit it.
+  pub fn public_key_optional(&self) -> bool {
+    matches!(self, Chain::Evm | Chain::Solana)
+  }
+
+  pub fn is_valid_address(&self, address: &str) -> bool {
+    match self {
+      // 0x + 64 hex (32-byte account address)
+      Chain::Sui | Chain::Aptos => is_hex_with_prefix(address, 64),
+      // 0x + 40 hex (20-byte account address)
+      Chain::Evm => is_hex_with_prefix(address, 40),
+      // base58-encoded 32-byte ed25519 public key
+      Chain::Solana => {
+        (32..=44).contains(&address.len())
+          && bs58::decode(address).into_vec().is_ok_and(|bytes| bytes.len() == 32)
+      }
+    }
+  }
+
+  /// Canonical form used for storage and lookups. EVM addresses are
+  /// case-insensitive (EIP-55 only adds a checksum), so they are lowercased.
+  pub fn normalize_address(&self, address: &str) -> String {
+    match self {
+      Chain::Evm | Chain::Sui | Chain::Aptos => address.to_ascii_lowercase(),
+      Chain::Solana => address.to_string(),
+    }
+  }
+}
+
+fn is_hex_with_prefix(address: &str, hex_len: usize) -> bool {
+  address
+    .strip_prefix("0x")
+    .is_some_and(|hex| hex.len() == hex_len && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+impl Display for Chain {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
+
+impl FromStr for Chain {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Chain::all()
+      .into_iter()
+      .find(|chain| chain.as_str().eq_ignore_ascii_case(s))
+      .ok_or_else(|| format!("Unknown chain: {}", s))
+  }
+}
+
+// -- Stored as VARCHAR
+
+impl sqlx::Type<sqlx::Postgres> for Chain {
+  fn type_info() -> sqlx::postgres::PgTypeInfo {
+    <String as sqlx::Type<sqlx::Postgres>>::type_info()
+  }
+
+  fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+    <String as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+  }
+}
+
+impl<'r> sqlx::Decode<'r, sqlx::Postgres> for Chain {
+  fn decode(
+    value: sqlx::postgres::PgValueRef<'r>,
+  ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+    let s = <&str as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+    Ok(s.parse()?)
+  }
+}
+
+impl<'q> sqlx::Encode<'q, sqlx::Postgres> for Chain {
+  fn encode_by_ref(
+    &self,
+    buf: &mut sqlx::postgres::PgArgumentBuffer,
+  ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Send + Sync>> {
+    <&str as sqlx::Encode<sqlx::Postgres>>::encode_by_ref(&self.as_str(), buf)
+  }
+}
+
+impl From<Chain> for sea_query::Value {
+  fn from(chain: Chain) -> Self {
+    sea_query::Value::String(Some(Box::new(chain.to_string())))
+  }
+}
+
+impl sea_query::Nullable for Chain {
+  fn null() -> sea_query::Value {
+    sea_query::Value::String(None)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_address_validation_per_chain() {
+    let sui = format!("0x{}", "a".repeat(64));
+    let evm = "0x52908400098527886E0F7030069857D2E4169EE7";
+    let solana = "11111111111111111111111111111111";
+
+    assert!(Chain::Sui.is_valid_address(&sui));
+    assert!(Chain::Aptos.is_valid_address(&sui));
+    assert!(!Chain::Sui.is_valid_address(evm));
+    assert!(Chain::Evm.is_valid_address(evm));
+    assert!(!Chain::Evm.is_valid_address(&sui));
+    assert!(Chain::Solana.is_valid_address(solana));
+    assert!(!Chain::Solana.is_valid_address(evm));
+
+    assert_eq!(Chain::Evm.normalize_address(evm), evm.to_ascii_lowercase());
+    assert_eq!("Solana".parse::<Chain>(), Ok(Chain::Solana));
+  }
+}
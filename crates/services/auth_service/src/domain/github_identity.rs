@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// A GitHub account linked to a `public.users` row. The OAuth access token is
+/// stored encrypted so github_service can act on the developer's behalf.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GithubIdentity {
+  pub id: Uuid,
+  pub user_id: Uuid,
+  pub github_id: i64,
+  pub login: String,
+  pub name: Option<String>,
+  pub email: Option<String>,
+  pub avatar_url: Option<String>,
+  #[serde(skip_serializing)]
+  pub access_token_encrypted: String,
+  pub scopes: String,
+  #[serde(with = "time::serde::rfc3339")]
+  pub linked_at: OffsetDateTime,
+  #[serde(with = "time::serde::rfc3339")]
+  pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct GithubIdentityForUpsert {
+  /// User to link to. `None` creates a new wallet-less user.
+  pub user_id: Option<Uuid>,
+  pub github_id: i64,
+  pub login: String,
+  pub name: Option<String>,
+  pub email: Option<String>,
+  pub avatar_url: Option<String>,
+  pub access_token_encrypted: String,
+  pub scopes: String,
+}
+
+/// `GET https://api.github.com/user` (fields we keep).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubProfile {
+  pub id: i64,
+  pub login: String,
+  pub name: Option<String>,
+  pub email: Option<String>,
+  pub avatar_url: Option<String>,
+}
+
+/// Successful response of the OAuth code exchange.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubAccessToken {
+  pub access_token: String,
+  #[serde(default)]
+  pub scope: String,
+}
+
+/// Server-side record of an in-flight OAuth login, keyed by the `state` param.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GithubOAuthState {
+  /// Set when an authenticated user starts the flow to link their account.
+  pub link_user_id: Option<Uuid>,
+  pub created_at: i64,
+}
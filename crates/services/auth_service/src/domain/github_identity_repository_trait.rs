@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{AuthUser, Chain, GithubIdentity, GithubIdentityForUpsert};
+use crate::error::Result;
+
+#[async_trait]
+pub trait GithubIdentityRepository: Send + Sync {
+  async fn find_by_github_id(&self, github_id: i64) -> Result<Option<GithubIdentity>>;
+  /// Insert or refresh the identity, creating its user first when `user_id` is `None`.
+  async fn upsert_identity(&self, identity: &GithubIdentityForUpsert) -> Result<GithubIdentity>;
+  async fn find_user_id(&self, chain: Chain, address: &str) -> Result<Option<Uuid>>;
+  async fn find_user(&self, user_id: Uuid) -> Result<Option<AuthUser>>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::domain::{GithubAccessToken, GithubProfile};
+use crate::error::Result;
+
+#[async_trait]
+pub trait GithubOAuthClient: Send + Sync {
+  /// URL the browser is sent to for consent.
+  fn authorize_url(&self, state: &str) -> String;
+  async fn exchange_code(&self, code: &str) -> Result<GithubAccessToken>;
+  async fn fetch_profile(&self, access_token: &str) -> Result<GithubProfile>;
+}
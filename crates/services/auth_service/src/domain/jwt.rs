@@ -3,10 +3,26 @@ use chrono::{Duration, Utc};
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+use super::auth_provider::AuthProviderType;
+use super::chain::Chain;
+use super::role::RoleSet;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
+  /// How the subject logged in. For GitHub logins without a linked wallet,
+  /// `address` holds the GitHub user id.
+  #[serde(default)]
+  pub provider: AuthProviderType,
+  /// Tokens issued before multi-chain support carry no chain and are Sui.
+  #[serde(default)]
+  pub chain: Chain,
   pub address: String,
   pub public_key: String,
+  /// Roles and scopes resolved at issue time; re-resolved on refresh.
+  #[serde(default)]
+  pub roles: Vec<String>,
+  #[serde(default)]
+  pub scopes: Vec<String>,
   pub token_type: String, // "access" or "refresh"
   pub exp: usize,         // Expiration timestamp
   pub iat: usize,         // Issued at timestamp
@@ -28,23 +44,52 @@ impl JwtManager {
     Self { secret }
   }
 
-  /// Generate access and refresh tokens for a user
-  pub fn generate_tokens(&self, address: &str, public_key: &str) -> Result<TokenPair> {
-    let access_token = self.generate_access_token(address, public_key)?;
-    let refresh_token = self.generate_refresh_token(address, public_key)?;
+  /// Generate access and refresh tokens for a wallet user
+  pub fn generate_tokens(
+    &self,
+    chain: Chain,
+    address: &str,
+    public_key: &str,
+    roles: &RoleSet,
+  ) -> Result<TokenPair> {
+    self.generate_provider_tokens(AuthProviderType::Wallet, chain, address, public_key, roles)
+  }
+
+  /// Generate access and refresh tokens for a user of any auth provider
+  pub fn generate_provider_tokens(
+    &self,
+    provider: AuthProviderType,
+    chain: Chain,
+    address: &str,
+    public_key: &str,
+    roles: &RoleSet,
+  ) -> Result<TokenPair> {
+    let access_token = self.generate_access_token(provider, chain, address, public_key, roles)?;
+    let refresh_token = self.generate_refresh_token(provider, chain, address, public_key)?;
 
     Ok(TokenPair { access_token, refresh_token })
   }
 
   /// Generate an access token (1 hour expiry)
-  fn generate_access_token(&self, address: &str, public_key: &str) -> Result<String> {
+  fn generate_access_token(
+    &self,
+    provider: AuthProviderType,
+    chain: Chain,
+    address: &str,
+    public_key: &str,
+    roles: &RoleSet,
+  ) -> Result<String> {
     let now = Utc::now();
     let exp = (now + Duration::hours(1)).timestamp() as usize;
     let iat = now.timestamp() as usize;
 
     let claims = Claims {
+      provider,
+      chain,
       address: address.to_string(),
       public_key: public_key.to_string(),
+      roles: roles.roles.clone(),
+      scopes: roles.scopes.clone(),
       token_type: "access".to_string(),
       exp,
       iat,
@@ -55,14 +100,24 @@ impl JwtManager {
   }
 
   /// Generate a refresh token (7 days expiry)
-  fn generate_refresh_token(&self, address: &str, public_key: &str) -> Result<String> {
+  fn generate_refresh_token(
+    &self,
+    provider: AuthProviderType,
+    chain: Chain,
+    address: &str,
+    public_key: &str,
+  ) -> Result<String> {
     let now = Utc::now();
     let exp = (now + Duration::days(7)).timestamp() as usize;
     let iat = now.timestamp() as usize;
 
     let claims = Claims {
+      provider,
+      chain,
       address: address.to_string(),
       public_key: public_key.to_string(),
+      roles: Vec::new(),
+      scopes: Vec::new(),
       token_type: "refresh".to_string(),
       exp,
       iat,
@@ -81,16 +136,26 @@ impl JwtManager {
       .map_err(|e| e.into())
   }
 
-  /// Generate a new access token from a valid refresh token
-  pub fn refresh_access_token(&self, refresh_token: &str) -> Result<String> {
+  /// Validate a token and ensure it is a refresh token
+  pub fn validate_refresh_token(&self, refresh_token: &str) -> Result<Claims> {
     let claims = self.validate_token(refresh_token)?;
 
-    // Ensure it's a refresh token
     if claims.token_type != "refresh" {
       return Err(Error::invalid_token());
     }
 
-    self.generate_access_token(&claims.address, &claims.public_key)
+    Ok(claims)
+  }
+
+  /// Generate a new access token for the subject of validated refresh claims
+  pub fn refresh_access_token(&self, claims: &Claims, roles: &RoleSet) -> Result<String> {
+    self.generate_access_token(
+      claims.provider,
+      claims.chain,
+      &claims.address,
+      &claims.public_key,
+      roles,
+    )
   }
 
   /// Extract token from Authorization header
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::chain::Chain;
+
+/// A wallet address that logs into an account. An account has at least one
+/// wallet or GitHub identity at all times.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LinkedWallet {
+  pub id: Uuid,
+  pub user_id: Uuid,
+  pub chain: Chain,
+  pub address: String,
+  pub public_key: Option<String>,
+  #[serde(with = "time::serde::rfc3339")]
+  pub linked_at: OffsetDateTime,
+}
+
+/// Reputation aggregated over every identity linked to an account.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccountReputation {
+  pub user_id: Uuid,
+  pub wallet_count: i64,
+  pub github_identity_count: i64,
+  /// Mean of the account's active reputation records.
+  pub reputation_score: Option<f64>,
+  /// Means over the developer profiles of the linked GitHub identities.
+  pub coding_reputation_score: Option<f64>,
+  pub security_awareness_score: Option<f64>,
+  pub community_trust_score: Option<f64>,
+  pub total_contributions: i64,
+}
+
+/// Outcome of folding a duplicate account into the caller's.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccountMerge {
+  pub id: Uuid,
+  pub source_user_id: Uuid,
+  pub target_user_id: Uuid,
+  pub wallets_moved: i32,
+  pub github_identities_moved: i32,
+  pub reputation_records_moved: i32,
+  #[serde(with = "time::serde::rfc3339")]
+  pub merged_at: OffsetDateTime,
+}
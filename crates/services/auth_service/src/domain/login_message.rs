@@ -0,0 +1,346 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use jd_utils::config::AuthConfig;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::chain::Chain;
+use super::nonce::Nonce;
+use crate::error::{Error, Result};
+
+const DEFAULT_DOMAIN: &str = "localhost:8080";
+const DEFAULT_URI: &str = "http://localhost:8080";
+const DEFAULT_STATEMENT: &str = "Sign in to Commandos HKT.";
+
+/// Value of the message's own `Version` field, as in EIP-4361.
+const MESSAGE_FORMAT_VERSION: &str = "1";
+
+/// Shape of the challenge a nonce is issued with. Clients that predate the
+/// structured message get `Legacy` unless they ask otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum LoginMessageVersion {
+  /// Bare nonce. Phishable; kept while clients migrate.
+  #[default]
+  Legacy,
+  /// EIP-4361 style message bound to the server's domain.
+  Structured,
+}
+
+impl TryFrom<u8> for LoginMessageVersion {
+  type Error = String;
+
+  fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+    match value {
+      1 => Ok(Self::Legacy),
+      2 => Ok(Self::Structured),
+      other => Err(format!("unsupported login message version: {}", other)),
+    }
+  }
+}
+
+impl From<LoginMessageVersion> for u8 {
+  fn from(version: LoginMessageVersion) -> Self {
+    match version {
+      LoginMessageVersion::Legacy => 1,
+      LoginMessageVersion::Structured => 2,
+    }
+  }
+}
+
+/// What structured login messages are bound to, from `AUTH.LOGIN_*`.
+#[derive(Debug, Clone)]
+pub struct LoginMessageSettings {
+  pub domain: String,
+  pub uri: String,
+  pub statement: String,
+  /// Whether bare-nonce challenges are still issued and accepted.
+  pub allow_legacy: bool,
+}
+
+impl Default for LoginMessageSettings {
+  fn default() -> Self {
+    Self {
+      domain: DEFAULT_DOMAIN.to_string(),
+      uri: DEFAULT_URI.to_string(),
+      statement: DEFAULT_STATEMENT.to_string(),
+      allow_legacy: true,
+    }
+  }
+}
+
+impl LoginMessageSettings {
+  pub fn from_config(config: Option<&AuthConfig>) -> Self {
+    let defaults = Self::default();
+    let Some(config) = config else {
+      return defaults;
+    };
+
+    Self {
+      domain: config.login_domain.clone().unwrap_or(defaults.domain),
+      uri: config.login_uri.clone().unwrap_or(defaults.uri),
+      statement: config.login_statement.clone().unwrap_or(defaults.statement),
+      allow_legacy: config.allow_legacy_login_message.unwrap_or(defaults.allow_legacy),
+    }
+  }
+
+  /// The exact text the wallet is asked to sign for this nonce.
+  pub fn signing_message(&self, nonce: &Nonce) -> String {
+    match nonce.message_version {
+      LoginMessageVersion::Legacy => nonce.get_signing_message(),
+      LoginMessageVersion::Structured => LoginMessage::for_nonce(nonce, self).to_string(),
+    }
+  }
+}
+
+/// An account change a wallet signature authorises. Its statement takes the
+/// place of the login statement in the signed message, so a login signature
+/// cannot be replayed to change an account, nor one change's signature used
+/// for another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountAction {
+  Link { user_id: Uuid },
+  Unlink { user_id: Uuid },
+  /// Fold account `source`, which the wallet logs into, into `target`.
+  Merge { source: Uuid, target: Uuid },
+}
+
+impl AccountAction {
+  const LINK_PREFIX: &str = "Link this wallet to account ";
+  const UNLINK_PREFIX: &str = "Unlink this wallet from account ";
+  const MERGE_PREFIX: &str = "Merge account ";
+
+  /// The statement the wallet must sign, e.g. `Merge account {source} into {target}.`
+  pub fn statement(&self) -> String {
+    match self {
+      Self::Link { user_id } => format!("{}{}.", Self::LINK_PREFIX, user_id),
+      Self::Unlink { user_id } => format!("{}{}.", Self::UNLINK_PREFIX, user_id),
+      Self::Merge { source, target } => {
+        format!("{}{} into {}.", Self::MERGE_PREFIX, source, target)
+      }
+    }
+  }
+
+  /// Whether `statement` authorises an account change, which a login must
+  /// not accept.
+  pub fn is_action_statement(statement: &str) -> bool {
+    [Self::LINK_PREFIX, Self::UNLINK_PREFIX, Self::MERGE_PREFIX]
+      .iter()
+      .any(|prefix| statement.starts_with(prefix))
+  }
+}
+
+/// A sign-in challenge in the EIP-4361 layout:
+///
+/// ```text
+/// {domain} wants you to sign in with your {chain} account:
+/// {address}
+///
+/// {statement}
+///
+/// URI: {uri}
+/// Version: 1
+/// Chain ID: {chain_id}
+/// Nonce: {nonce}
+/// Issued At: {issued_at}
+/// Expiration Time: {expiration_time}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginMessage {
+  pub domain: String,
+  pub chain: Chain,
+  pub address: String,
+  pub statement: String,
+  pub uri: String,
+  pub chain_id: String,
+  pub nonce: String,
+  pub issued_at: DateTime<Utc>,
+  pub expiration_time: DateTime<Utc>,
+}
+
+impl LoginMessage {
+  pub fn for_nonce(nonce: &Nonce, settings: &LoginMessageSettings) -> Self {
+    Self {
+      domain: settings.domain.clone(),
+      chain: nonce.chain,
+      address: nonce.address.clone(),
+      statement: settings.statement.clone(),
+      uri: settings.uri.clone(),
+      chain_id: chain_id(nonce.chain).to_string(),
+      nonce: nonce.nonce.clone(),
+      issued_at: truncate_to_secs(nonce.created_at),
+      expiration_time: truncate_to_secs(nonce.expires_at),
+    }
+  }
+
+  /// Parse a signed message. Every line must be present and in order.
+  pub fn parse(message: &str) -> Result<Self> {
+    let mut lines = message.lines();
+    let mut next = |name: &str| lines.next().ok_or_else(|| Error::invalid_login_message(name));
+
+    let header = next("domain")?;
+    let (domain, account) = header
+      .split_once(" wants you to sign in with your ")
+      .ok_or_else(|| Error::invalid_login_message("domain"))?;
+    let chain_name =
+      account.strip_suffix(" account:").ok_or_else(|| Error::invalid_login_message("chain"))?;
+    let chain = Chain::all()
+      .into_iter()
+      .find(|chain| chain_name == display_name(*chain))
+      .ok_or_else(|| Error::invalid_login_message("chain"))?;
+
+    let address = next("address")?.to_string();
+    expect_blank(next("statement")?, "statement")?;
+    let statement = next("statement")?.to_string();
+    expect_blank(next("statement")?, "statement")?;
+
+    let uri = field(next("uri")?, "URI", "uri")?;
+    if field(next("version")?, "Version", "version")? != MESSAGE_FORMAT_VERSION {
+      return Err(Error::invalid_login_message("version"));
+    }
+    let chain_id = field(next("chain_id")?, "Chain ID", "chain_id")?;
+    let nonce = field(next("nonce")?, "Nonce", "nonce")?;
+    let issued_at = timestamp(&field(next("issued_at")?, "Issued At", "issued_at")?, "issued_at")?;
+    let expiration_time = timestamp(
+      &field(next("expiration_time")?, "Expiration Time", "expiration_time")?,
+      "expiration_time",
+    )?;
+
+    if lines.next().is_some() {
+      return Err(Error::invalid_login_message("trailing content"));
+    }
+
+    Ok(Self {
+      domain: domain.to_string(),
+      chain,
+      address,
+      statement,
+      uri,
+      chain_id,
+      nonce,
+      issued_at,
+      expiration_time,
+    })
+  }
+
+  /// Check a signed message field by field against the one issued with the
+  /// nonce. The statement is checked by the caller: it is informational for
+  /// logins and names the change for account actions.
+  pub fn verify(&self, expected: &LoginMessage, now: DateTime<Utc>) -> Result<()> {
+    let checks = [
+      ("domain", self.domain == expected.domain),
+      ("uri", self.uri == expected.uri),
+      ("chain", self.chain == expected.chain),
+      ("chain_id", self.chain_id == expected.chain_id),
+      ("address", self.chain.normalize_address(&self.address) == expected.address),
+      ("nonce", self.nonce == expected.nonce),
+      ("issued_at", self.issued_at == expected.issued_at && self.issued_at <= now),
+      ("expiration_time", self.expiration_time == expected.expiration_time),
+    ];
+    if let Some((name, _)) = checks.iter().find(|(_, ok)| !ok) {
+      return Err(Error::invalid_login_message(name));
+    }
+
+    if now > self.expiration_time {
+      return Err(Error::nonce_expired());
+    }
+    Ok(())
+  }
+}
+
+impl std::fmt::Display for LoginMessage {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{} wants you to sign in with your {} account:\n{}\n\n{}\n\n\
+       URI: {}\nVersion: {}\nChain ID: {}\nNonce: {}\nIssued At: {}\nExpiration Time: {}",
+      self.domain,
+      display_name(self.chain),
+      self.address,
+      self.statement,
+      self.uri,
+      MESSAGE_FORMAT_VERSION,
+      self.chain_id,
+      self.nonce,
+      self.issued_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+      self.expiration_time.to_rfc3339_opts(SecondsFormat::Secs, true),
+    )
+  }
+}
+
+fn display_name(chain: Chain) -> &'static str {
+  match chain {
+    Chain::Sui => "Sui",
+    Chain::Evm => "Ethereum",
+    Chain::Solana => "Solana",
+    Chain::Aptos => "Aptos",
+  }
+}
+
+/// Mainnet identifiers: CAIP-2 references for non-EVM chains, the numeric
+/// chain id for EVM.
+fn chain_id(chain: Chain) -> &'static str {
+  match chain {
+    Chain::Sui => "sui:mainnet",
+    Chain::Evm => "1",
+    Chain::Solana => "solana:mainnet",
+    Chain::Aptos => "aptos:1",
+  }
+}
+
+fn expect_blank(line: &str, name: &str) -> Result<()> {
+  if line.is_empty() { Ok(()) } else { Err(Error::invalid_login_message(name)) }
+}
+
+fn field(line: &str, label: &str, name: &str) -> Result<String> {
+  line
+    .strip_prefix(label)
+    .and_then(|rest| rest.strip_prefix(": "))
+    .map(str::to_string)
+    .ok_or_else(|| Error::invalid_login_message(name))
+}
+
+fn timestamp(value: &str, name: &str) -> Result<DateTime<Utc>> {
+  DateTime::parse_from_rfc3339(value)
+    .map(|t| t.with_timezone(&Utc))
+    .map_err(|_| Error::invalid_login_message(name))
+}
+
+/// Messages carry whole seconds, so stored timestamps are compared at that
+/// precision.
+fn truncate_to_secs(t: DateTime<Utc>) -> DateTime<Utc> {
+  DateTime::from_timestamp(t.timestamp(), 0).unwrap_or(t)
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+
+  #[test]
+  fn structured_message_round_trips_and_rejects_other_domains() {
+    let address = format!("0x{}", "ab".repeat(32));
+    let nonce = Nonce::generate(Chain::Sui, address, Duration::minutes(5))
+      .with_version(LoginMessageVersion::Structured);
+    let settings = LoginMessageSettings::default();
+
+    let message = settings.signing_message(&nonce);
+    assert!(message.starts_with("localhost:8080 wants you to sign in with your Sui account:"));
+
+    let expected = LoginMessage::for_nonce(&nonce, &settings);
+    let parsed = LoginMessage::parse(&message).unwrap();
+    assert_eq!(parsed, expected);
+    assert!(parsed.verify(&expected, Utc::now()).is_ok());
+
+    let phished = LoginMessage::parse(&message.replace("localhost:8080", "evil.example")).unwrap();
+    assert_eq!(phished.verify(&expected, Utc::now()).unwrap_err().code, "INVALID_LOGIN_MESSAGE");
+
+    let merge = AccountAction::Merge { source: Uuid::new_v4(), target: Uuid::new_v4() };
+    assert!(AccountAction::is_action_statement(&merge.statement()));
+    assert!(!AccountAction::is_action_statement(&settings.statement));
+
+    let late = Utc::now() + Duration::minutes(10);
+    assert_eq!(parsed.verify(&expected, late).unwrap_err().code, "NONCE_EXPIRED");
+    assert!(LoginMessage::parse(&format!("{}\nextra", message)).is_err());
+  }
+}
@@ -1,17 +1,41 @@
 pub mod auth_user;
+pub mod chain;
 pub mod auth_provider;
+pub mod github_identity;
+pub mod organization;
+pub mod role;
 pub mod user_role;
 pub mod jwt;
+pub mod linked_account;
+pub mod login_message;
 pub mod nonce;
+pub(crate) mod account_repository_trait;
+pub(crate) mod github_identity_repository_trait;
+pub(crate) mod github_oauth_client_trait;
 pub(crate) mod nonce_repository_trait;
+pub(crate) mod oauth_state_repository_trait;
+pub(crate) mod organization_repository_trait;
+pub(crate) mod role_repository_trait;
 pub(crate) mod signature_verifier_trait;
 pub(crate) mod user_repository_trait;
 
 pub use auth_user::*;
+pub use chain::*;
 pub use auth_provider::*;
+pub use github_identity::*;
+pub use organization::*;
+pub use role::*;
 pub use user_role::*;
 pub use jwt::*;
+pub use linked_account::*;
+pub use login_message::*;
 pub use nonce::*;
-pub(crate) use nonce_repository_trait::NonceRepository;
+pub(crate) use account_repository_trait::AccountRepository;
+pub(crate) use github_identity_repository_trait::GithubIdentityRepository;
+pub(crate) use github_oauth_client_trait::GithubOAuthClient;
+pub(crate) use nonce_repository_trait::{NonceLock, NonceRepository};
+pub(crate) use oauth_state_repository_trait::OAuthStateRepository;
+pub(crate) use organization_repository_trait::OrganizationRepository;
+pub(crate) use role_repository_trait::RoleRepository;
 pub(crate) use signature_verifier_trait::SignatureVerifier;
 pub(crate) use user_repository_trait::UserRepository;
@@ -1,23 +1,56 @@
 use chrono::{DateTime, Duration, Utc};
+use jd_utils::config::AuthConfig;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use super::chain::Chain;
+use super::login_message::LoginMessageVersion;
+
+const DEFAULT_NONCE_TTL_SECS: i64 = 300;
+
+/// How long a consumed nonce is remembered so a replayed login can be told
+/// apart from one that never requested a nonce.
+pub const CONSUMED_NONCE_RETENTION_SECS: i64 = 3600;
+
+/// Nonce lifetime from `AUTH.NONCE_TTL_SECS`, defaulting to five minutes.
+pub fn nonce_ttl(config: Option<&AuthConfig>) -> Duration {
+  let secs = config.and_then(|c| c.nonce_ttl_secs).filter(|secs| *secs > 0);
+  Duration::seconds(secs.unwrap_or(DEFAULT_NONCE_TTL_SECS))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Nonce {
+  #[serde(default)]
+  pub chain: Chain,
   pub address: String,
   pub nonce: String,
   pub created_at: DateTime<Utc>,
   pub expires_at: DateTime<Utc>,
+  /// Nonces stored before structured messages existed are legacy.
+  #[serde(default)]
+  pub message_version: LoginMessageVersion,
 }
 
 impl Nonce {
-  /// Generate a new nonce for the given address
-  pub fn generate(address: String) -> Self {
+  /// Generate a new nonce for the given address, valid for `ttl`
+  pub fn generate(chain: Chain, address: String, ttl: Duration) -> Self {
     let nonce = Self::generate_nonce_string();
     let now = Utc::now();
-    let expires_at = now + Duration::minutes(5); // 5 minute expiration
+    let expires_at = now + ttl;
+
+    Self {
+      chain,
+      address,
+      nonce,
+      created_at: now,
+      expires_at,
+      message_version: LoginMessageVersion::default(),
+    }
+  }
 
-    Self { address, nonce, created_at: now, expires_at }
+  pub fn with_version(mut self, message_version: LoginMessageVersion) -> Self {
+    self.message_version = message_version;
+    self
   }
 
   /// Check if the nonce has expired
@@ -25,7 +58,12 @@ impl Nonce {
     Utc::now() > self.expires_at
   }
 
-  /// Generate the message that should be signed
+  /// Seconds until expiry, at least one so it can be used as a store TTL
+  pub fn ttl_secs(&self) -> u64 {
+    (self.expires_at - Utc::now()).num_seconds().max(1) as u64
+  }
+
+  /// The legacy bare-nonce message; see `LoginMessageSettings::signing_message`
   pub fn get_signing_message(&self) -> String {
     format!("Sign this message to authenticate with Commandos HKT: {}", self.nonce)
   }
@@ -45,3 +83,29 @@ impl Nonce {
     nonce.len() == 64 && nonce.chars().all(|c| c.is_ascii_hexdigit())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn nonce_expires_after_configured_ttl() {
+    let config = AuthConfig {
+      nonce_backend: None,
+      nonce_ttl_secs: Some(60),
+      login_domain: None,
+      login_uri: None,
+      login_statement: None,
+      allow_legacy_login_message: None,
+    };
+    let nonce = Nonce::generate(Chain::default(), "0x1".to_string(), nonce_ttl(Some(&config)));
+    assert!(!nonce.is_expired());
+    assert!(nonce.ttl_secs() <= 60);
+    assert!(Nonce::is_valid_format(&nonce.nonce));
+
+    let stale = Nonce::generate(Chain::default(), "0x1".to_string(), Duration::seconds(-1));
+    assert!(stale.is_expired());
+    assert_eq!(stale.ttl_secs(), 1);
+    assert_eq!(nonce_ttl(None), Duration::seconds(DEFAULT_NONCE_TTL_SECS));
+  }
+}
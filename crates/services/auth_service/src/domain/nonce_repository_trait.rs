@@ -1,11 +1,31 @@
 use async_trait::async_trait;
 
-use crate::domain::Nonce;
+use crate::domain::{Chain, Nonce};
 use crate::error::Result;
 
 #[async_trait]
 pub trait NonceRepository: Send + Sync {
+  /// Store the pending nonce for an address, replacing any earlier one.
   async fn store_nonce(&self, nonce: &Nonce) -> Result<()>;
-  async fn get_nonce(&self, address: &str) -> Result<Option<Nonce>>;
-  async fn remove_nonce(&self, address: &str) -> Result<()>;
+  /// Lock the pending nonce for an address while a signature is checked
+  /// against it, so concurrent attempts cannot both redeem it. `None` when
+  /// no nonce is pending.
+  async fn lock_nonce(&self, chain: Chain, address: &str) -> Result<Option<Box<dyn NonceLock>>>;
+  /// Whether `nonce` was issued to the address and consumed recently.
+  async fn is_consumed(&self, chain: Chain, address: &str, nonce: &str) -> Result<bool>;
+  /// Delete expired nonces and replay markers the backend does not expire on
+  /// its own. Returns the number of rows removed.
+  async fn purge_expired(&self) -> Result<u64>;
+}
+
+/// A pending nonce held by one verification attempt. Ending it either way
+/// lets the next attempt in; dropping it releases it too, eventually.
+#[async_trait]
+pub trait NonceLock: Send {
+  fn nonce(&self) -> &Nonce;
+  /// Delete the nonce and remember it as consumed. Fails with
+  /// `NONCE_REPLAYED` if it was redeemed meanwhile.
+  async fn consume(self: Box<Self>) -> Result<()>;
+  /// Leave the nonce pending, for a signature that did not check out.
+  async fn release(self: Box<Self>) -> Result<()>;
 }
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::domain::GithubOAuthState;
+use crate::error::Result;
+
+#[async_trait]
+pub trait OAuthStateRepository: Send + Sync {
+  async fn store_state(&self, state: &str, data: &GithubOAuthState) -> Result<()>;
+  /// Fetch and delete in one step so a state can only be redeemed once.
+  async fn take_state(&self, state: &str) -> Result<Option<GithubOAuthState>>;
+}
@@ -0,0 +1,218 @@
+use jd_utils::config::OnboardingConfig;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+const DEFAULT_TRIAL_DAYS: i64 = 14;
+const DEFAULT_TRIAL_MAX_MEMBERS: i32 = 5;
+const DEFAULT_TRIAL_MAX_REPOSITORIES: i32 = 3;
+const DEFAULT_TRIAL_MONTHLY_ANALYSES: i32 = 50;
+const DEFAULT_INVITATION_TTL_HOURS: i64 = 7 * 24;
+const MAX_SLUG_LEN: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "organization_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum OrganizationStatus {
+  Trial,
+  Active,
+  Expired,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "organization_role", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum OrganizationRole {
+  Owner,
+  Admin,
+  #[default]
+  Member,
+}
+
+impl OrganizationRole {
+  pub fn can_invite(&self) -> bool {
+    matches!(self, Self::Owner | Self::Admin)
+  }
+
+  pub fn can_manage_alerts(&self) -> bool {
+    matches!(self, Self::Owner | Self::Admin)
+  }
+}
+
+/// Limits applied to organizations created through self-serve signup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrialQuotas {
+  pub trial_days: i64,
+  pub max_members: i32,
+  pub max_repositories: i32,
+  pub monthly_analyses: i32,
+}
+
+impl Default for TrialQuotas {
+  fn default() -> Self {
+    Self {
+      trial_days: DEFAULT_TRIAL_DAYS,
+      max_members: DEFAULT_TRIAL_MAX_MEMBERS,
+      max_repositories: DEFAULT_TRIAL_MAX_REPOSITORIES,
+      monthly_analyses: DEFAULT_TRIAL_MONTHLY_ANALYSES,
+    }
+  }
+}
+
+/// Onboarding settings resolved from `ONBOARDING.*`, falling back to the
+/// defaults for anything unset.
+#[derive(Debug, Clone)]
+pub struct OnboardingSettings {
+  pub quotas: TrialQuotas,
+  pub sample_repository: Option<String>,
+  pub invitation_ttl: Duration,
+}
+
+impl Default for OnboardingSettings {
+  fn default() -> Self {
+    Self {
+      quotas: TrialQuotas::default(),
+      sample_repository: None,
+      invitation_ttl: Duration::hours(DEFAULT_INVITATION_TTL_HOURS),
+    }
+  }
+}
+
+impl OnboardingSettings {
+  pub fn from_config(config: Option<&OnboardingConfig>) -> Self {
+    let Some(config) = config else {
+      return Self::default();
+    };
+    let defaults = Self::default();
+
+    Self {
+      quotas: TrialQuotas {
+        trial_days: config.trial_days.unwrap_or(defaults.quotas.trial_days),
+        max_members: config.trial_max_members.unwrap_or(defaults.quotas.max_members),
+        max_repositories: config.trial_max_repositories.unwrap_or(defaults.quotas.max_repositories),
+        monthly_analyses: config.trial_monthly_analyses.unwrap_or(defaults.quotas.monthly_analyses),
+      },
+      sample_repository: config
+        .sample_repository
+        .as_deref()
+        .map(str::trim)
+        .filter(|repo| is_repository_full_name(repo))
+        .map(str::to_string),
+      invitation_ttl: config
+        .invitation_ttl_hours
+        .map(Duration::hours)
+        .unwrap_or(defaults.invitation_ttl),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Organization {
+  pub id: Uuid,
+  pub name: String,
+  pub slug: String,
+  pub owner_id: Uuid,
+  pub status: OrganizationStatus,
+  #[serde(with = "time::serde::rfc3339::option")]
+  pub trial_ends_at: Option<OffsetDateTime>,
+  pub max_members: i32,
+  pub max_repositories: i32,
+  pub monthly_analysis_quota: i32,
+  #[serde(with = "time::serde::rfc3339")]
+  pub ctime: OffsetDateTime,
+}
+
+impl Organization {
+  /// Whether the organization may still be used. Trials past their end date
+  /// count as expired even before the scheduler has updated the row.
+  pub fn is_usable(&self, now: OffsetDateTime) -> bool {
+    match self.status {
+      OrganizationStatus::Active => true,
+      OrganizationStatus::Expired => false,
+      OrganizationStatus::Trial => self.trial_ends_at.is_none_or(|ends_at| ends_at > now),
+    }
+  }
+}
+
+/// Everything needed to insert a trial organization, its owner membership
+/// and the sample repository in one go.
+#[derive(Debug, Clone)]
+pub struct NewOrganization {
+  pub name: String,
+  pub slug: String,
+  pub owner_id: Uuid,
+  pub trial_ends_at: OffsetDateTime,
+  pub quotas: TrialQuotas,
+  pub sample_repository: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationMember {
+  pub organization_id: Uuid,
+  pub user_id: Uuid,
+  pub role: OrganizationRole,
+  #[serde(with = "time::serde::rfc3339")]
+  pub joined_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationInvitation {
+  pub id: Uuid,
+  pub organization_id: Uuid,
+  pub email: String,
+  pub role: OrganizationRole,
+  pub invited_by: Uuid,
+  #[serde(with = "time::serde::rfc3339")]
+  pub expires_at: OffsetDateTime,
+  #[serde(with = "time::serde::rfc3339")]
+  pub ctime: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegisteredRepository {
+  pub full_name: String,
+  pub github_repository_id: Option<Uuid>,
+  pub is_sample: bool,
+  #[serde(with = "time::serde::rfc3339")]
+  pub added_at: OffsetDateTime,
+}
+
+/// Derive a URL slug from an organization name: lowercase ASCII
+/// alphanumerics separated by single dashes.
+pub fn slugify(name: &str) -> String {
+  let mut slug = String::with_capacity(name.len());
+  for c in name.chars() {
+    if c.is_ascii_alphanumeric() {
+      slug.push(c.to_ascii_lowercase());
+    } else if !slug.is_empty() && !slug.ends_with('-') {
+      slug.push('-');
+    }
+  }
+  slug.truncate(MAX_SLUG_LEN);
+  slug.trim_end_matches('-').to_string()
+}
+
+pub fn is_valid_slug(slug: &str) -> bool {
+  !slug.is_empty() && slug.len() <= MAX_SLUG_LEN && slugify(slug) == slug
+}
+
+fn is_repository_full_name(repo: &str) -> bool {
+  matches!(repo.split_once('/'), Some((owner, name))
+    if !owner.is_empty() && !name.is_empty() && !name.contains('/'))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn slugify_collapses_separators_and_case() {
+    assert_eq!(slugify("  Acme Labs, Inc. "), "acme-labs-inc");
+    assert_eq!(slugify("Sui--Builders_2026"), "sui-builders-2026");
+    assert!(is_valid_slug("acme-labs"));
+    assert!(!is_valid_slug("Acme-Labs"));
+    assert!(!is_valid_slug("acme--labs"));
+    assert!(!is_valid_slug(""));
+  }
+}
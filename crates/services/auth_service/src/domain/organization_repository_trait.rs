@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::{
+  AuthProviderType, Chain, NewOrganization, Organization, OrganizationInvitation,
+  OrganizationMember, OrganizationRole, RegisteredRepository,
+};
+use crate::error::Result;
+
+#[async_trait]
+pub trait OrganizationRepository: Send + Sync {
+  /// Map a token subject to its `users.id`.
+  async fn find_user_id(
+    &self,
+    provider: AuthProviderType,
+    chain: Chain,
+    subject: &str,
+  ) -> Result<Option<Uuid>>;
+  async fn slug_exists(&self, slug: &str) -> Result<bool>;
+  /// Insert the organization, its owner membership and the sample repository
+  /// atomically.
+  async fn create_organization(&self, organization: NewOrganization) -> Result<Organization>;
+  async fn find_organization(&self, organization_id: Uuid) -> Result<Option<Organization>>;
+  async fn member_role(
+    &self,
+    organization_id: Uuid,
+    user_id: Uuid,
+  ) -> Result<Option<OrganizationRole>>;
+  async fn members(&self, organization_id: Uuid) -> Result<Vec<OrganizationMember>>;
+  async fn pending_invitations(
+    &self,
+    organization_id: Uuid,
+  ) -> Result<Vec<OrganizationInvitation>>;
+  async fn repositories(&self, organization_id: Uuid) -> Result<Vec<RegisteredRepository>>;
+  /// Members plus unexpired, unaccepted invitations.
+  async fn seats_used(&self, organization_id: Uuid) -> Result<i64>;
+  async fn create_invitation(
+    &self,
+    organization_id: Uuid,
+    email: &str,
+    role: OrganizationRole,
+    token_hash: &str,
+    invited_by: Uuid,
+    expires_at: OffsetDateTime,
+  ) -> Result<OrganizationInvitation>;
+  /// Consume a pending invitation. Returns `None` when the token is unknown,
+  /// already used or expired.
+  async fn accept_invitation(
+    &self,
+    token_hash: &str,
+    user_id: Uuid,
+  ) -> Result<Option<OrganizationMember>>;
+  /// Mark every trial whose end date has passed as expired and return the
+  /// affected organization ids.
+  async fn expire_trials(&self, now: OffsetDateTime) -> Result<Vec<Uuid>>;
+}
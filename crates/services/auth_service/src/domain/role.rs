@@ -0,0 +1,67 @@
+use jd_core::ctx::scope_matches;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::user_role::UserRole;
+
+/// Scope required to list, grant and revoke roles.
+pub const SCOPE_ROLES_ADMIN: &str = "roles:admin";
+
+/// A role row. Built-in roles mirror [`UserRole`]; further roles may be added
+/// in the `roles` table without code changes.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Role {
+  pub id: Uuid,
+  pub name: String,
+  pub description: Option<String>,
+  pub scopes: Vec<String>,
+  #[serde(with = "time::serde::rfc3339")]
+  pub ctime: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RoleAssignment {
+  pub user_id: Uuid,
+  pub role: String,
+  /// Subject of the admin token that granted the role; `None` for seeded grants.
+  pub granted_by: Option<String>,
+  #[serde(with = "time::serde::rfc3339")]
+  pub granted_at: OffsetDateTime,
+}
+
+/// Roles held by a user and the union of their scopes, as embedded in JWTs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleSet {
+  pub roles: Vec<String>,
+  pub scopes: Vec<String>,
+}
+
+impl RoleSet {
+  pub fn from_roles(roles: impl IntoIterator<Item = (String, Vec<String>)>) -> Self {
+    let mut set = Self::default();
+    for (role, scopes) in roles {
+      set.roles.push(role);
+      set.scopes.extend(scopes);
+    }
+    set.roles.sort();
+    set.roles.dedup();
+    set.scopes.sort();
+    set.scopes.dedup();
+    set
+  }
+
+  pub fn has_role(&self, role: &str) -> bool {
+    self.roles.iter().any(|r| r == role)
+  }
+
+  pub fn has_scope(&self, required: &str) -> bool {
+    self.scopes.iter().any(|granted| scope_matches(granted, required))
+  }
+
+  /// Highest built-in role held; custom roles are ignored.
+  pub fn highest_role(&self) -> UserRole {
+    self.roles.iter().filter_map(|r| r.parse::<UserRole>().ok()).max().unwrap_or_default()
+  }
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::{AuthProviderType, Chain, Role, RoleAssignment, RoleSet};
+use crate::error::Result;
+
+#[async_trait]
+pub trait RoleRepository: Send + Sync {
+  async fn list_roles(&self) -> Result<Vec<Role>>;
+  /// Resolve the role set of a token subject. Every user implicitly holds
+  /// the default role.
+  async fn roles_for_subject(
+    &self,
+    provider: AuthProviderType,
+    chain: Chain,
+    subject: &str,
+  ) -> Result<RoleSet>;
+  async fn user_assignments(&self, user_id: Uuid) -> Result<Vec<RoleAssignment>>;
+  async fn grant_role(
+    &self,
+    user_id: Uuid,
+    role: &str,
+    granted_by: Option<&str>,
+  ) -> Result<RoleAssignment>;
+  /// Returns `false` when the user did not hold the role.
+  async fn revoke_role(&self, user_id: Uuid, role: &str) -> Result<bool>;
+}
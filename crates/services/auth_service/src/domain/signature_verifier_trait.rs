@@ -1,9 +1,13 @@
 use async_trait::async_trait;
 
+use crate::domain::Chain;
 use crate::error::Result;
 
 #[async_trait]
 pub trait SignatureVerifier: Send + Sync {
+  /// Chain whose wallets this verifier understands.
+  fn chain(&self) -> Chain;
+
   async fn verify_signature(
     &self,
     message: &str,
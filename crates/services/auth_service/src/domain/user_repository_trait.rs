@@ -1,11 +1,16 @@
 use async_trait::async_trait;
 
-use crate::domain::AuthUser;
+use crate::domain::{AuthUser, Chain};
 use crate::error::Result;
 
 #[async_trait]
 pub trait UserRepository: Send + Sync {
   async fn create_user(&self, user: &AuthUser) -> Result<()>;
-  async fn get_user(&self, address: &str) -> Result<Option<AuthUser>>;
+  async fn get_user(&self, chain: Chain, address: &str) -> Result<Option<AuthUser>>;
   async fn update_user(&self, user: &AuthUser) -> Result<()>;
+  /// Look up the user a GitHub identity is linked to. Stores without GitHub
+  /// linking have none.
+  async fn get_user_by_github_id(&self, _github_id: i64) -> Result<Option<AuthUser>> {
+    Ok(None)
+  }
 }
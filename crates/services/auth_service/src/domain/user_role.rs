@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "user_role", rename_all = "lowercase")]
@@ -62,6 +63,17 @@ impl Display for UserRole {
     }
 }
 
+impl FromStr for UserRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        UserRole::all()
+            .into_iter()
+            .find(|role| role.to_string() == s)
+            .ok_or_else(|| format!("Unknown role: {}", s))
+    }
+}
+
 impl Default for UserRole {
     fn default() -> Self {
         UserRole::Normal
@@ -1,3 +1,4 @@
+use jd_error::{AppError, ErrorKind};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -28,10 +29,25 @@ impl Error {
     Self::new("Nonce has expired", "NONCE_EXPIRED")
   }
 
+  pub fn nonce_replayed() -> Self {
+    Self::new("Nonce has already been used", "NONCE_REPLAYED")
+  }
+
   pub fn invalid_signature() -> Self {
     Self::new("Invalid signature", "INVALID_SIGNATURE")
   }
 
+  pub fn invalid_login_message(field: &str) -> Self {
+    Self::new(
+      &format!("Login message does not match the challenge: {}", field),
+      "INVALID_LOGIN_MESSAGE",
+    )
+  }
+
+  pub fn unsupported_message_version() -> Self {
+    Self::new("Legacy login messages are no longer accepted", "UNSUPPORTED_MESSAGE_VERSION")
+  }
+
   pub fn invalid_public_key() -> Self {
     Self::new("Invalid public key", "INVALID_PUBLIC_KEY")
   }
@@ -70,7 +86,11 @@ impl Error {
 
   // Validation errors
   pub fn invalid_address() -> Self {
-    Self::new("Invalid Sui address", "INVALID_ADDRESS")
+    Self::new("Invalid wallet address for the selected chain", "INVALID_ADDRESS")
+  }
+
+  pub fn unsupported_chain(chain: &str) -> Self {
+    Self::new(&format!("Unsupported chain: {}", chain), "UNSUPPORTED_CHAIN")
   }
 
   pub fn invalid_request_data(field: &str) -> Self {
@@ -82,15 +102,89 @@ impl Error {
     Self::new("Invalid wallet address format", "INVALID_WALLET_ADDRESS")
   }
 
+  // GitHub OAuth errors
+  pub fn oauth_not_configured() -> Self {
+    Self::new("GitHub OAuth is not configured", "OAUTH_NOT_CONFIGURED")
+  }
+
+  pub fn invalid_oauth_state() -> Self {
+    Self::new("OAuth state is invalid or has expired", "INVALID_OAUTH_STATE")
+  }
+
+  pub fn github_oauth_failed(msg: &str) -> Self {
+    Self::new(&format!("GitHub OAuth failed: {}", msg), "GITHUB_OAUTH_FAILED")
+  }
+
+  pub fn github_account_already_linked() -> Self {
+    Self::new("GitHub account is already linked to another user", "GITHUB_ACCOUNT_ALREADY_LINKED")
+  }
+
+  // Linked account errors
+  pub fn wallet_already_linked() -> Self {
+    Self::new(
+      "Wallet is linked to another account; merge the accounts instead",
+      "WALLET_ALREADY_LINKED",
+    )
+  }
+
+  pub fn last_login_method() -> Self {
+    Self::new("Cannot remove the account's last login method", "LAST_LOGIN_METHOD")
+  }
+
   // Authorization errors
   pub fn insufficient_permissions() -> Self {
     Self::new("Insufficient permissions", "INSUFFICIENT_PERMISSIONS")
   }
 
+  pub fn role_not_found(role: &str) -> Self {
+    Self::new(&format!("Role not found: {}", role), "ROLE_NOT_FOUND")
+  }
+
+  // Organization errors
+  pub fn feature_disabled(feature: &str) -> Self {
+    Self::new(&format!("Feature is not enabled: {}", feature), "FEATURE_DISABLED")
+  }
+
+  pub fn organization_not_found() -> Self {
+    Self::new("Organization not found", "ORGANIZATION_NOT_FOUND")
+  }
+
+  pub fn organization_slug_taken(slug: &str) -> Self {
+    Self::new(&format!("Organization slug is already taken: {}", slug), "ORGANIZATION_SLUG_TAKEN")
+  }
+
+  pub fn trial_expired() -> Self {
+    Self::new("Organization trial has expired", "TRIAL_EXPIRED")
+  }
+
+  pub fn quota_exceeded(quota: &str, limit: i64) -> Self {
+    Self::with_details(
+      &format!("Trial quota exceeded: {}", quota),
+      "QUOTA_EXCEEDED",
+      serde_json::json!({ "quota": quota, "limit": limit }),
+    )
+  }
+
+  pub fn invalid_invitation() -> Self {
+    Self::new("Invitation is invalid, expired or already used", "INVALID_INVITATION")
+  }
+
   pub fn user_not_found() -> Self {
     Self::new("User not found", "USER_NOT_FOUND")
   }
 
+  pub fn notification_not_found() -> Self {
+    Self::new("Notification not found", "NOTIFICATION_NOT_FOUND")
+  }
+
+  pub fn alert_channel_not_found() -> Self {
+    Self::new("Alert channel not found", "ALERT_CHANNEL_NOT_FOUND")
+  }
+
+  pub fn alert_delivery_failed(msg: &str) -> Self {
+    Self::new(&format!("Alert delivery failed: {}", msg), "ALERT_DELIVERY_FAILED")
+  }
+
   pub fn email_already_exists() -> Self {
     Self::new("Email already exists", "EMAIL_ALREADY_EXISTS")
   }
@@ -134,6 +228,35 @@ impl From<redis::RedisError> for Error {
   }
 }
 
+impl From<jd_email::Error> for Error {
+  fn from(err: jd_email::Error) -> Self {
+    Error::database_error(&err.to_string())
+  }
+}
+
+impl From<jd_notifications::Error> for Error {
+  fn from(err: jd_notifications::Error) -> Self {
+    Error::database_error(&err.to_string())
+  }
+}
+
+impl From<jd_alerts::Error> for Error {
+  fn from(err: jd_alerts::Error) -> Self {
+    match err {
+      jd_alerts::Error::InvalidChannel(field) => Error::invalid_request_data(&field),
+      jd_alerts::Error::Delivery(msg) => Error::alert_delivery_failed(&msg),
+      jd_alerts::Error::RateLimited(_) => Error::rate_limit_exceeded(),
+      jd_alerts::Error::Database(_) => Error::database_error(&err.to_string()),
+    }
+  }
+}
+
+impl From<jd_feature_flags::Error> for Error {
+  fn from(err: jd_feature_flags::Error) -> Self {
+    Error::database_error(&err.to_string())
+  }
+}
+
 impl From<jsonwebtoken::errors::Error> for Error {
   fn from(err: jsonwebtoken::errors::Error) -> Self {
     match err.kind() {
@@ -149,26 +272,59 @@ impl From<base64::DecodeError> for Error {
   }
 }
 
-// Axum response conversion
-impl axum::response::IntoResponse for Error {
-  fn into_response(self) -> axum::response::Response {
-    let status = match self.code.as_str() {
-      "NONCE_NOT_FOUND" | "NONCE_EXPIRED" | "INVALID_SIGNATURE" | "INVALID_PUBLIC_KEY" => {
-        axum::http::StatusCode::UNAUTHORIZED
-      }
+impl Error {
+  /// Taxonomy kind for this error, derived from its code.
+  pub fn kind(&self) -> ErrorKind {
+    match self.code.as_str() {
+      "NONCE_NOT_FOUND" | "NONCE_EXPIRED" | "NONCE_REPLAYED" | "INVALID_SIGNATURE"
+      | "INVALID_PUBLIC_KEY" | "INVALID_LOGIN_MESSAGE" => ErrorKind::Unauthenticated,
       "INVALID_TOKEN" | "TOKEN_EXPIRED" | "MISSING_AUTH_HEADER" | "INVALID_TOKEN_FORMAT" => {
-        axum::http::StatusCode::UNAUTHORIZED
+        ErrorKind::Unauthenticated
+      }
+      "INVALID_CREDENTIALS" | "ACCOUNT_DISABLED" | "INVALID_OAUTH_STATE" => {
+        ErrorKind::Unauthenticated
       }
-      "INVALID_CREDENTIALS" | "ACCOUNT_DISABLED" => axum::http::StatusCode::UNAUTHORIZED,
-      "INSUFFICIENT_PERMISSIONS" => axum::http::StatusCode::FORBIDDEN,
-      "USER_NOT_FOUND" => axum::http::StatusCode::NOT_FOUND,
-      "EMAIL_ALREADY_EXISTS" | "USERNAME_ALREADY_EXISTS" => axum::http::StatusCode::CONFLICT,
-      "RATE_LIMIT_EXCEEDED" => axum::http::StatusCode::TOO_MANY_REQUESTS,
-      "INVALID_ADDRESS" | "INVALID_REQUEST_DATA" | "INVALID_WALLET_ADDRESS" => {
-        axum::http::StatusCode::BAD_REQUEST
+      "OAUTH_NOT_CONFIGURED" => ErrorKind::Unavailable,
+      "GITHUB_OAUTH_FAILED" | "ALERT_DELIVERY_FAILED" => ErrorKind::Upstream,
+      "INSUFFICIENT_PERMISSIONS" | "TRIAL_EXPIRED" | "QUOTA_EXCEEDED" => {
+        ErrorKind::PermissionDenied
       }
-      _ => axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-    };
+      "USER_NOT_FOUND" | "ROLE_NOT_FOUND" | "ORGANIZATION_NOT_FOUND" | "INVALID_INVITATION"
+      | "FEATURE_DISABLED" | "NOTIFICATION_NOT_FOUND" | "ALERT_CHANNEL_NOT_FOUND" => {
+        ErrorKind::NotFound
+      }
+      "EMAIL_ALREADY_EXISTS" | "USERNAME_ALREADY_EXISTS" | "GITHUB_ACCOUNT_ALREADY_LINKED"
+      | "ORGANIZATION_SLUG_TAKEN" | "WALLET_ALREADY_LINKED" | "LAST_LOGIN_METHOD" => {
+        ErrorKind::Conflict
+      }
+      "RATE_LIMIT_EXCEEDED" => ErrorKind::RateLimited,
+      "INVALID_ADDRESS" | "INVALID_REQUEST_DATA" | "INVALID_WALLET_ADDRESS"
+      | "UNSUPPORTED_CHAIN" | "UNSUPPORTED_MESSAGE_VERSION" => ErrorKind::Validation,
+      _ => ErrorKind::Internal,
+    }
+  }
+}
+
+// auth_service errors are code-based structs rather than enums, so the
+// taxonomy conversion is written by hand instead of derived.
+impl From<Error> for AppError {
+  fn from(err: Error) -> Self {
+    let kind = err.kind();
+    let mut app_error = AppError::new(kind, err.code.clone(), err.error.clone()).with_service("auth");
+    if kind.is_client_error() {
+      app_error = app_error.with_user_message(err.error);
+    }
+    match err.details {
+      Some(details) => app_error.with_details(details),
+      None => app_error,
+    }
+  }
+}
+
+// Axum response conversion
+impl axum::response::IntoResponse for Error {
+  fn into_response(self) -> axum::response::Response {
+    let status = self.kind().status_code();
 
     let body = axum::Json(self);
     (status, body).into_response()
@@ -0,0 +1,243 @@
+use async_trait::async_trait;
+use jd_core::AppState;
+use uuid::Uuid;
+
+use crate::domain::{
+  AccountMerge, AccountReputation, AccountRepository, AuthProviderType, Chain, LinkedWallet,
+};
+use crate::error::{Error, Result};
+
+pub struct AccountRepositoryImpl {
+  state: AppState,
+}
+
+impl AccountRepositoryImpl {
+  pub fn new(state: AppState) -> Self {
+    Self { state }
+  }
+}
+
+#[async_trait]
+impl AccountRepository for AccountRepositoryImpl {
+  async fn find_user_id(
+    &self,
+    provider: AuthProviderType,
+    chain: Chain,
+    subject: &str,
+  ) -> Result<Option<Uuid>> {
+    match provider {
+      AuthProviderType::Github => {
+        let user_id = sqlx::query_scalar::<_, Uuid>(
+          "SELECT user_id FROM github_identities WHERE github_id::TEXT = $1",
+        )
+        .bind(subject)
+        .fetch_optional(self.state.mm().dbx().db())
+        .await?;
+        Ok(user_id)
+      }
+      _ => self.wallet_owner(chain, subject).await,
+    }
+  }
+
+  async fn wallets(&self, user_id: Uuid) -> Result<Vec<LinkedWallet>> {
+    let wallets = sqlx::query_as::<_, LinkedWallet>(
+      "SELECT id, user_id, chain, address, public_key, linked_at \
+       FROM user_wallets WHERE user_id = $1 ORDER BY linked_at",
+    )
+    .bind(user_id)
+    .fetch_all(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(wallets)
+  }
+
+  async fn github_logins(&self, user_id: Uuid) -> Result<Vec<String>> {
+    let logins = sqlx::query_scalar::<_, String>(
+      "SELECT login FROM github_identities WHERE user_id = $1 ORDER BY linked_at",
+    )
+    .bind(user_id)
+    .fetch_all(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(logins)
+  }
+
+  async fn reputation(&self, user_id: Uuid) -> Result<AccountReputation> {
+    sqlx::query_as::<_, AccountReputation>(
+      "SELECT user_id, wallet_count, github_identity_count, \
+              reputation_score::FLOAT8 AS reputation_score, \
+              coding_reputation_score::FLOAT8 AS coding_reputation_score, \
+              security_awareness_score::FLOAT8 AS security_awareness_score, \
+              community_trust_score::FLOAT8 AS community_trust_score, \
+              total_contributions \
+       FROM account_reputation WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?
+    .ok_or_else(Error::user_not_found)
+  }
+
+  async fn wallet_owner(&self, chain: Chain, address: &str) -> Result<Option<Uuid>> {
+    let user_id = sqlx::query_scalar::<_, Uuid>(
+      "SELECT user_id FROM user_wallets WHERE chain = $1 AND address = $2",
+    )
+    .bind(chain.as_str())
+    .bind(address)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(user_id)
+  }
+
+  async fn link_wallet(
+    &self,
+    user_id: Uuid,
+    chain: Chain,
+    address: &str,
+    public_key: &str,
+  ) -> Result<LinkedWallet> {
+    // Relinking an address the account already owns only refreshes its key;
+    // an address owned by another account is left untouched.
+    let wallet = sqlx::query_as::<_, LinkedWallet>(
+      "INSERT INTO user_wallets (user_id, chain, address, public_key) \
+       VALUES ($1, $2, $3, $4) \
+       ON CONFLICT (chain, address) DO UPDATE SET public_key = EXCLUDED.public_key \
+         WHERE user_wallets.user_id = EXCLUDED.user_id \
+       RETURNING id, user_id, chain, address, public_key, linked_at",
+    )
+    .bind(user_id)
+    .bind(chain.as_str())
+    .bind(address)
+    .bind(public_key)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?;
+
+    wallet.ok_or_else(Error::wallet_already_linked)
+  }
+
+  async fn unlink_wallet(&self, user_id: Uuid, chain: Chain, address: &str) -> Result<bool> {
+    let mut tx = self.state.mm().dbx().db().begin().await?;
+
+    // Lock the account's identities so concurrent unlinks cannot both pass
+    // the last-identity check.
+    let identities = sqlx::query_scalar::<_, i64>(
+      "SELECT (SELECT COUNT(*) FROM (SELECT 1 FROM user_wallets WHERE user_id = $1 FOR UPDATE) w) \
+            + (SELECT COUNT(*) FROM github_identities WHERE user_id = $1)",
+    )
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let removed = sqlx::query(
+      "DELETE FROM user_wallets WHERE user_id = $1 AND chain = $2 AND address = $3",
+    )
+    .bind(user_id)
+    .bind(chain.as_str())
+    .bind(address)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if removed > 0 && identities <= 1 {
+      return Err(Error::last_login_method());
+    }
+
+    tx.commit().await?;
+    Ok(removed > 0)
+  }
+
+  async fn merge_accounts(&self, source: Uuid, target: Uuid) -> Result<AccountMerge> {
+    let mut tx = self.state.mm().dbx().db().begin().await?;
+
+    // Lock both accounts in a fixed order so opposite merges cannot deadlock
+    let locked = sqlx::query_scalar::<_, Uuid>(
+      "SELECT id FROM users WHERE id IN ($1, $2) AND merged_into IS NULL \
+       ORDER BY id FOR UPDATE",
+    )
+    .bind(source)
+    .bind(target)
+    .fetch_all(&mut *tx)
+    .await?;
+    if locked.len() != 2 {
+      return Err(Error::user_not_found());
+    }
+
+    let wallets_moved = sqlx::query("UPDATE user_wallets SET user_id = $2 WHERE user_id = $1")
+      .bind(source)
+      .bind(target)
+      .execute(&mut *tx)
+      .await?
+      .rows_affected();
+    let github_identities_moved = sqlx::query(
+      "UPDATE github_identities SET user_id = $2, updated_at = NOW() WHERE user_id = $1",
+    )
+    .bind(source)
+    .bind(target)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    let reputation_records_moved =
+      sqlx::query("UPDATE reputation_records SET user_id = $2 WHERE user_id = $1")
+        .bind(source)
+        .bind(target)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    // Organizations the source owns move with their owner's membership
+    sqlx::query(
+      "INSERT INTO organization_members (organization_id, user_id, role, joined_at) \
+       SELECT m.organization_id, $2, m.role, m.joined_at FROM organization_members m \
+       JOIN organizations o ON o.id = m.organization_id AND o.owner_id = $1 \
+       WHERE m.user_id = $1 \
+       ON CONFLICT (organization_id, user_id) DO UPDATE SET role = EXCLUDED.role",
+    )
+    .bind(source)
+    .bind(target)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query("UPDATE organizations SET owner_id = $2 WHERE owner_id = $1")
+      .bind(source)
+      .bind(target)
+      .execute(&mut *tx)
+      .await?;
+    // Roles and other memberships were granted to the person behind the
+    // source account; they are dropped rather than handed to the target
+    sqlx::query("DELETE FROM user_role_assignments WHERE user_id = $1")
+      .bind(source)
+      .execute(&mut *tx)
+      .await?;
+    sqlx::query("DELETE FROM organization_members WHERE user_id = $1")
+      .bind(source)
+      .execute(&mut *tx)
+      .await?;
+
+    sqlx::query(
+      "UPDATE users SET status = 'merged', merged_into = $2, mtime = NOW() WHERE id = $1",
+    )
+    .bind(source)
+    .bind(target)
+    .execute(&mut *tx)
+    .await?;
+
+    let merge = sqlx::query_as::<_, AccountMerge>(
+      "INSERT INTO account_merges \
+       (source_user_id, target_user_id, wallets_moved, github_identities_moved, \
+        reputation_records_moved) \
+       VALUES ($1, $2, $3, $4, $5) \
+       RETURNING id, source_user_id, target_user_id, wallets_moved, github_identities_moved, \
+                 reputation_records_moved, merged_at",
+    )
+    .bind(source)
+    .bind(target)
+    .bind(wallets_moved as i32)
+    .bind(github_identities_moved as i32)
+    .bind(reputation_records_moved as i32)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(merge)
+  }
+}
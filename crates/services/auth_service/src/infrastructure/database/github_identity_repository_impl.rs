@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use jd_core::AppState;
+use uuid::Uuid;
+
+use crate::domain::{
+  AuthUser, Chain, GithubIdentity, GithubIdentityForUpsert, GithubIdentityRepository,
+  ZkPersonaUser,
+};
+use crate::error::Result;
+
+pub struct GithubIdentityRepositoryImpl {
+  state: AppState,
+}
+
+impl GithubIdentityRepositoryImpl {
+  pub fn new(state: AppState) -> Self {
+    Self { state }
+  }
+}
+
+#[async_trait]
+impl GithubIdentityRepository for GithubIdentityRepositoryImpl {
+  async fn find_by_github_id(&self, github_id: i64) -> Result<Option<GithubIdentity>> {
+    let identity = sqlx::query_as::<_, GithubIdentity>(
+      "SELECT * FROM github_identities WHERE github_id = $1",
+    )
+    .bind(github_id)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(identity)
+  }
+
+  async fn upsert_identity(&self, identity: &GithubIdentityForUpsert) -> Result<GithubIdentity> {
+    let mut tx = self.state.mm().dbx().db().begin().await?;
+
+    let user_id = match identity.user_id {
+      Some(user_id) => user_id,
+      None => {
+        sqlx::query_scalar::<_, Uuid>(
+          "INSERT INTO users (wallet_address, public_key, last_login, login_count) \
+           VALUES (NULL, NULL, NOW(), 1) RETURNING id",
+        )
+        .fetch_one(&mut *tx)
+        .await?
+      }
+    };
+
+    // The linked user never changes on conflict; relinking is decided by the caller.
+    let record = sqlx::query_as::<_, GithubIdentity>(
+      "INSERT INTO github_identities \
+       (user_id, github_id, login, name, email, avatar_url, access_token_encrypted, scopes) \
+       VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+       ON CONFLICT (github_id) DO UPDATE SET \
+         login = EXCLUDED.login, \
+         name = EXCLUDED.name, \
+         email = EXCLUDED.email, \
+         avatar_url = EXCLUDED.avatar_url, \
+         access_token_encrypted = EXCLUDED.access_token_encrypted, \
+         scopes = EXCLUDED.scopes, \
+         updated_at = NOW() \
+       RETURNING *",
+    )
+    .bind(user_id)
+    .bind(identity.github_id)
+    .bind(&identity.login)
+    .bind(&identity.name)
+    .bind(&identity.email)
+    .bind(&identity.avatar_url)
+    .bind(&identity.access_token_encrypted)
+    .bind(&identity.scopes)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(record)
+  }
+
+  async fn find_user_id(&self, chain: Chain, address: &str) -> Result<Option<Uuid>> {
+    let user_id = sqlx::query_scalar::<_, Uuid>(
+      "SELECT user_id FROM user_wallets WHERE chain = $1 AND address = $2",
+    )
+    .bind(chain)
+    .bind(address)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(user_id)
+  }
+
+  async fn find_user(&self, user_id: Uuid) -> Result<Option<AuthUser>> {
+    let user = sqlx::query_as::<_, ZkPersonaUser>(
+      // GitHub-only users have no wallet; they surface with an empty address.
+      "SELECT id, chain, COALESCE(wallet_address, '') AS wallet_address, \
+              COALESCE(public_key, '') AS public_key, last_login, login_count, status, \
+              ctime, mtime \
+       FROM users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(user.map(AuthUser::from))
+  }
+}
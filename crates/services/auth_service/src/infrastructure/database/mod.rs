@@ -1,9 +1,23 @@
+pub mod account_repository_impl;
+pub mod github_identity_repository_impl;
 pub mod nonce_repository_impl;
+pub mod oauth_state_repository_impl;
+pub mod organization_repository_impl;
+pub mod pg_nonce_repository_impl;
+pub mod redis_nonce_repository_impl;
+pub mod role_repository_impl;
 pub mod signature_verifier_impl;
 pub mod user_repository_impl;
 pub mod zkpersona_user_repository_impl;
 
-pub use nonce_repository_impl::NonceRepositoryImpl;
+pub use account_repository_impl::AccountRepositoryImpl;
+pub use github_identity_repository_impl::GithubIdentityRepositoryImpl;
+pub use nonce_repository_impl::{NonceBackend, NonceRepositoryImpl};
+pub use oauth_state_repository_impl::OAuthStateRepositoryImpl;
+pub use organization_repository_impl::OrganizationRepositoryImpl;
+pub use pg_nonce_repository_impl::PgNonceRepositoryImpl;
+pub use redis_nonce_repository_impl::RedisNonceRepositoryImpl;
+pub use role_repository_impl::RoleRepositoryImpl;
 pub use signature_verifier_impl::SignatureVerifierImpl;
 pub use user_repository_impl::UserRepositoryImpl;
 pub use zkpersona_user_repository_impl::ZkPersonaUserRepositoryImpl;
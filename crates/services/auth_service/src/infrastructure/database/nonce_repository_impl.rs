@@ -1,88 +1,79 @@
+use std::str::FromStr;
+
 use async_trait::async_trait;
 use jd_core::AppState;
-use redis::AsyncCommands;
-use serde_json;
+use tracing::warn;
 
-use crate::domain::{Nonce, NonceRepository};
+use super::{PgNonceRepositoryImpl, RedisNonceRepositoryImpl};
+use crate::domain::{Chain, Nonce, NonceLock, NonceRepository};
 use crate::error::{Error, Result};
 
-pub struct NonceRepositoryImpl {
-  state: AppState,
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonceBackend {
+  #[default]
+  Redis,
+  Postgres,
+}
+
+impl FromStr for NonceBackend {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    match s.trim().to_lowercase().as_str() {
+      "redis" => Ok(Self::Redis),
+      "postgres" | "postgresql" => Ok(Self::Postgres),
+      other => Err(Error::invalid_request_data(&format!("unknown nonce backend: {}", other))),
+    }
+  }
+}
+
+/// The nonce store selected by `AUTH.NONCE_BACKEND`.
+pub enum NonceRepositoryImpl {
+  Redis(RedisNonceRepositoryImpl),
+  Postgres(PgNonceRepositoryImpl),
 }
 
 impl NonceRepositoryImpl {
   pub fn new(state: AppState) -> Self {
-    Self { state }
+    let configured = state.config.auth.as_ref().and_then(|auth| auth.nonce_backend.as_deref());
+    let backend = match configured.map(NonceBackend::from_str) {
+      Some(Ok(backend)) => backend,
+      Some(Err(e)) => {
+        warn!("⚠️ {}, falling back to Redis", e);
+        NonceBackend::Redis
+      }
+      None => NonceBackend::default(),
+    };
+
+    match backend {
+      NonceBackend::Redis => Self::Redis(RedisNonceRepositoryImpl::new(state)),
+      NonceBackend::Postgres => Self::Postgres(PgNonceRepositoryImpl::new(state)),
+    }
   }
 
-  fn nonce_key(address: &str) -> String {
-    format!("auth:nonce:{}", address)
+  fn inner(&self) -> &dyn NonceRepository {
+    match self {
+      Self::Redis(repo) => repo,
+      Self::Postgres(repo) => repo,
+    }
   }
 }
 
 #[async_trait]
 impl NonceRepository for NonceRepositoryImpl {
   async fn store_nonce(&self, nonce: &Nonce) -> Result<()> {
-    let mut conn = self
-      .state
-      .redis
-      .get_multiplexed_async_connection()
-      .await
-      .map_err(|e| Error::internal_error(&format!("Failed to get Redis connection: {}", e)))?;
-
-    let key = Self::nonce_key(&nonce.address);
-    let value = serde_json::to_string(nonce)
-      .map_err(|e| Error::internal_error(&format!("Failed to serialize nonce: {}", e)))?;
-
-    // Set with expiration (5 minutes)
-    let _: () = conn
-      .set_ex(&key, value, 300)
-      .await
-      .map_err(|e| Error::internal_error(&format!("Failed to store nonce: {}", e)))?;
-
-    Ok(())
+    self.inner().store_nonce(nonce).await
   }
 
-  async fn get_nonce(&self, address: &str) -> Result<Option<Nonce>> {
-    let mut conn = self
-      .state
-      .redis
-      .get_multiplexed_async_connection()
-      .await
-      .map_err(|e| Error::internal_error(&format!("Failed to get Redis connection: {}", e)))?;
-
-    let key = Self::nonce_key(address);
-
-    let value: Option<String> = conn
-      .get(&key)
-      .await
-      .map_err(|e| Error::internal_error(&format!("Failed to get nonce: {}", e)))?;
-
-    match value {
-      Some(json) => {
-        let nonce: Nonce = serde_json::from_str(&json)
-          .map_err(|e| Error::internal_error(&format!("Failed to deserialize nonce: {}", e)))?;
-        Ok(Some(nonce))
-      }
-      None => Ok(None),
-    }
+  async fn lock_nonce(&self, chain: Chain, address: &str) -> Result<Option<Box<dyn NonceLock>>> {
+    self.inner().lock_nonce(chain, address).await
   }
 
-  async fn remove_nonce(&self, address: &str) -> Result<()> {
-    let mut conn = self
-      .state
-      .redis
-      .get_multiplexed_async_connection()
-      .await
-      .map_err(|e| Error::internal_error(&format!("Failed to get Redis connection: {}", e)))?;
-
-    let key = Self::nonce_key(address);
-
-    let _: () = conn
-      .del(&key)
-      .await
-      .map_err(|e| Error::internal_error(&format!("Failed to remove nonce: {}", e)))?;
+  async fn is_consumed(&self, chain: Chain, address: &str, nonce: &str) -> Result<bool> {
+    self.inner().is_consumed(chain, address, nonce).await
+  }
 
-    Ok(())
+  async fn purge_expired(&self) -> Result<u64> {
+    self.inner().purge_expired().await
   }
 }
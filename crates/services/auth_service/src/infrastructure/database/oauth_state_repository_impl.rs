@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use jd_core::AppState;
+use std::time::Duration;
+
+use crate::domain::{GithubOAuthState, OAuthStateRepository};
+use crate::error::{Error, Result};
+
+/// OAuth `state` values outlive the consent screen, so give users 10 minutes.
+const OAUTH_STATE_TTL: Duration = Duration::from_secs(600);
+
+pub struct OAuthStateRepositoryImpl {
+  state: AppState,
+}
+
+impl OAuthStateRepositoryImpl {
+  pub fn new(state: AppState) -> Self {
+    Self { state }
+  }
+
+  fn state_key(state: &str) -> String {
+    format!("auth:github_oauth_state:{}", state)
+  }
+}
+
+#[async_trait]
+impl OAuthStateRepository for OAuthStateRepositoryImpl {
+  async fn store_state(&self, state: &str, data: &GithubOAuthState) -> Result<()> {
+    self
+      .state
+      .cache
+      .set(&Self::state_key(state), data, Some(OAUTH_STATE_TTL))
+      .await
+      .map_err(|e| Error::internal_error(&format!("Failed to store OAuth state: {}", e)))
+  }
+
+  async fn take_state(&self, state: &str) -> Result<Option<GithubOAuthState>> {
+    self
+      .state
+      .cache
+      .take(&Self::state_key(state))
+      .await
+      .map_err(|e| Error::internal_error(&format!("Failed to get OAuth state: {}", e)))
+  }
+}
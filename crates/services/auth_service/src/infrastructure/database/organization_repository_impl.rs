@@ -0,0 +1,282 @@
+use async_trait::async_trait;
+use jd_core::AppState;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::{
+  AuthProviderType, Chain, NewOrganization, Organization, OrganizationInvitation,
+  OrganizationMember, OrganizationRepository, OrganizationRole, RegisteredRepository,
+};
+use crate::error::Result;
+
+const ORGANIZATION_COLUMNS: &str = "id, name, slug, owner_id, status, trial_ends_at, \
+   max_members, max_repositories, monthly_analysis_quota, ctime";
+
+const INVITATION_COLUMNS: &str =
+  "id, organization_id, email, role, invited_by, expires_at, ctime";
+
+pub struct OrganizationRepositoryImpl {
+  state: AppState,
+}
+
+impl OrganizationRepositoryImpl {
+  pub fn new(state: AppState) -> Self {
+    Self { state }
+  }
+}
+
+#[async_trait]
+impl OrganizationRepository for OrganizationRepositoryImpl {
+  async fn find_user_id(
+    &self,
+    provider: AuthProviderType,
+    chain: Chain,
+    subject: &str,
+  ) -> Result<Option<Uuid>> {
+    let db = self.state.mm().dbx().db();
+
+    let user_id = match provider {
+      AuthProviderType::Github => {
+        sqlx::query_scalar::<_, Uuid>(
+          "SELECT user_id FROM github_identities WHERE github_id::TEXT = $1",
+        )
+        .bind(subject)
+        .fetch_optional(db)
+        .await?
+      }
+      _ => {
+        sqlx::query_scalar::<_, Uuid>(
+          "SELECT user_id FROM user_wallets WHERE chain = $1 AND address = $2",
+        )
+        .bind(chain.as_str())
+        .bind(subject)
+        .fetch_optional(db)
+        .await?
+      }
+    };
+
+    Ok(user_id)
+  }
+
+  async fn slug_exists(&self, slug: &str) -> Result<bool> {
+    let exists =
+      sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM organizations WHERE slug = $1)")
+        .bind(slug)
+        .fetch_one(self.state.mm().dbx().db())
+        .await?;
+
+    Ok(exists)
+  }
+
+  async fn create_organization(&self, organization: NewOrganization) -> Result<Organization> {
+    let mut tx = self.state.mm().dbx().db().begin().await?;
+
+    let created = sqlx::query_as::<_, Organization>(&format!(
+      "INSERT INTO organizations \
+       (name, slug, owner_id, status, trial_ends_at, max_members, max_repositories, \
+        monthly_analysis_quota) \
+       VALUES ($1, $2, $3, 'trial', $4, $5, $6, $7) \
+       RETURNING {}",
+      ORGANIZATION_COLUMNS
+    ))
+    .bind(&organization.name)
+    .bind(&organization.slug)
+    .bind(organization.owner_id)
+    .bind(organization.trial_ends_at)
+    .bind(organization.quotas.max_members)
+    .bind(organization.quotas.max_repositories)
+    .bind(organization.quotas.monthly_analyses)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+      "INSERT INTO organization_members (organization_id, user_id, role) \
+       VALUES ($1, $2, 'owner')",
+    )
+    .bind(created.id)
+    .bind(organization.owner_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(sample) = &organization.sample_repository {
+      // Link to the monitored repository when the GitHub service knows it.
+      sqlx::query(
+        "INSERT INTO organization_repositories \
+         (organization_id, full_name, github_repository_id, is_sample) \
+         VALUES ($1, $2, (SELECT id FROM github_repositories WHERE full_name = $2), TRUE)",
+      )
+      .bind(created.id)
+      .bind(sample)
+      .execute(&mut *tx)
+      .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(created)
+  }
+
+  async fn find_organization(&self, organization_id: Uuid) -> Result<Option<Organization>> {
+    let organization = sqlx::query_as::<_, Organization>(&format!(
+      "SELECT {} FROM organizations WHERE id = $1",
+      ORGANIZATION_COLUMNS
+    ))
+    .bind(organization_id)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(organization)
+  }
+
+  async fn member_role(
+    &self,
+    organization_id: Uuid,
+    user_id: Uuid,
+  ) -> Result<Option<OrganizationRole>> {
+    let role = sqlx::query_scalar::<_, OrganizationRole>(
+      "SELECT role FROM organization_members WHERE organization_id = $1 AND user_id = $2",
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(role)
+  }
+
+  async fn members(&self, organization_id: Uuid) -> Result<Vec<OrganizationMember>> {
+    let members = sqlx::query_as::<_, OrganizationMember>(
+      "SELECT organization_id, user_id, role, joined_at FROM organization_members \
+       WHERE organization_id = $1 ORDER BY joined_at",
+    )
+    .bind(organization_id)
+    .fetch_all(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(members)
+  }
+
+  async fn pending_invitations(
+    &self,
+    organization_id: Uuid,
+  ) -> Result<Vec<OrganizationInvitation>> {
+    let invitations = sqlx::query_as::<_, OrganizationInvitation>(&format!(
+      "SELECT {} FROM organization_invitations \
+       WHERE organization_id = $1 AND accepted_at IS NULL AND expires_at > NOW() \
+       ORDER BY ctime",
+      INVITATION_COLUMNS
+    ))
+    .bind(organization_id)
+    .fetch_all(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(invitations)
+  }
+
+  async fn repositories(&self, organization_id: Uuid) -> Result<Vec<RegisteredRepository>> {
+    let repositories = sqlx::query_as::<_, RegisteredRepository>(
+      "SELECT full_name, github_repository_id, is_sample, added_at \
+       FROM organization_repositories WHERE organization_id = $1 ORDER BY added_at",
+    )
+    .bind(organization_id)
+    .fetch_all(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(repositories)
+  }
+
+  async fn seats_used(&self, organization_id: Uuid) -> Result<i64> {
+    let seats = sqlx::query_scalar::<_, i64>(
+      "SELECT \
+         (SELECT COUNT(*) FROM organization_members WHERE organization_id = $1) + \
+         (SELECT COUNT(*) FROM organization_invitations \
+          WHERE organization_id = $1 AND accepted_at IS NULL AND expires_at > NOW())",
+    )
+    .bind(organization_id)
+    .fetch_one(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(seats)
+  }
+
+  async fn create_invitation(
+    &self,
+    organization_id: Uuid,
+    email: &str,
+    role: OrganizationRole,
+    token_hash: &str,
+    invited_by: Uuid,
+    expires_at: OffsetDateTime,
+  ) -> Result<OrganizationInvitation> {
+    let invitation = sqlx::query_as::<_, OrganizationInvitation>(&format!(
+      "INSERT INTO organization_invitations \
+       (organization_id, email, role, token_hash, invited_by, expires_at) \
+       VALUES ($1, $2, $3, $4, $5, $6) \
+       RETURNING {}",
+      INVITATION_COLUMNS
+    ))
+    .bind(organization_id)
+    .bind(email)
+    .bind(role)
+    .bind(token_hash)
+    .bind(invited_by)
+    .bind(expires_at)
+    .fetch_one(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(invitation)
+  }
+
+  async fn accept_invitation(
+    &self,
+    token_hash: &str,
+    user_id: Uuid,
+  ) -> Result<Option<OrganizationMember>> {
+    let mut tx = self.state.mm().dbx().db().begin().await?;
+
+    // Claiming the row first makes concurrent accepts of one token race safely.
+    let claimed = sqlx::query_as::<_, (Uuid, OrganizationRole)>(
+      "UPDATE organization_invitations SET accepted_at = NOW(), accepted_by = $2 \
+       WHERE token_hash = $1 AND accepted_at IS NULL AND expires_at > NOW() \
+       RETURNING organization_id, role",
+    )
+    .bind(token_hash)
+    .bind(user_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some((organization_id, role)) = claimed else {
+      return Ok(None);
+    };
+
+    // Existing members keep their current role.
+    let member = sqlx::query_as::<_, OrganizationMember>(
+      "INSERT INTO organization_members (organization_id, user_id, role) \
+       VALUES ($1, $2, $3) \
+       ON CONFLICT (organization_id, user_id) DO UPDATE SET user_id = EXCLUDED.user_id \
+       RETURNING organization_id, user_id, role, joined_at",
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .bind(role)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Some(member))
+  }
+
+  async fn expire_trials(&self, now: OffsetDateTime) -> Result<Vec<Uuid>> {
+    let expired = sqlx::query_scalar::<_, Uuid>(
+      "UPDATE organizations SET status = 'expired', mtime = NOW() \
+       WHERE status = 'trial' AND trial_ends_at <= $1 \
+       RETURNING id",
+    )
+    .bind(now)
+    .fetch_all(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(expired)
+  }
+}
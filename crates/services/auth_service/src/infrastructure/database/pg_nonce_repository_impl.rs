@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use jd_core::AppState;
+use sqlx::{Postgres, Transaction};
+
+use crate::domain::{
+  CONSUMED_NONCE_RETENTION_SECS, Chain, LoginMessageVersion, Nonce, NonceLock, NonceRepository,
+};
+use crate::error::{Error, Result};
+
+/// Nonces in the `auth_nonces` table. Consumed nonces are deleted and leave a
+/// marker per nonce in `auth_consumed_nonces` for replay detection.
+pub struct PgNonceRepositoryImpl {
+  state: AppState,
+}
+
+impl PgNonceRepositoryImpl {
+  pub fn new(state: AppState) -> Self {
+    Self { state }
+  }
+}
+
+#[async_trait]
+impl NonceRepository for PgNonceRepositoryImpl {
+  async fn store_nonce(&self, nonce: &Nonce) -> Result<()> {
+    sqlx::query(
+      "INSERT INTO auth_nonces (chain, address, nonce, created_at, expires_at, message_version) \
+       VALUES ($1, $2, $3, $4, $5, $6) \
+       ON CONFLICT (chain, address) DO UPDATE SET \
+         nonce = EXCLUDED.nonce, created_at = EXCLUDED.created_at, \
+         expires_at = EXCLUDED.expires_at, message_version = EXCLUDED.message_version",
+    )
+    .bind(nonce.chain.as_str())
+    .bind(&nonce.address)
+    .bind(&nonce.nonce)
+    .bind(nonce.created_at)
+    .bind(nonce.expires_at)
+    .bind(i16::from(u8::from(nonce.message_version)))
+    .execute(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(())
+  }
+
+  async fn lock_nonce(&self, chain: Chain, address: &str) -> Result<Option<Box<dyn NonceLock>>> {
+    let mut tx = self.state.mm().dbx().db().begin().await?;
+
+    // The row lock is held by the transaction until the nonce is consumed or
+    // released; concurrent attempts wait for it here.
+    let pending = sqlx::query_as::<_, (String, DateTime<Utc>, DateTime<Utc>, i16)>(
+      "SELECT nonce, created_at, expires_at, message_version FROM auth_nonces \
+       WHERE chain = $1 AND address = $2 FOR UPDATE",
+    )
+    .bind(chain.as_str())
+    .bind(address)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some((nonce, created_at, expires_at, message_version)) = pending else {
+      tx.rollback().await?;
+      return Ok(None);
+    };
+
+    let nonce = Nonce {
+      chain,
+      address: address.to_string(),
+      nonce,
+      created_at,
+      expires_at,
+      message_version: u8::try_from(message_version)
+        .ok()
+        .and_then(|version| LoginMessageVersion::try_from(version).ok())
+        .unwrap_or_default(),
+    };
+    Ok(Some(Box::new(PgNonceLock { tx, nonce })))
+  }
+
+  async fn is_consumed(&self, chain: Chain, address: &str, nonce: &str) -> Result<bool> {
+    let consumed = sqlx::query_scalar::<_, bool>(
+      "SELECT EXISTS(SELECT 1 FROM auth_consumed_nonces \
+       WHERE chain = $1 AND address = $2 AND nonce = $3 \
+       AND consumed_at > NOW() - make_interval(secs => $4))",
+    )
+    .bind(chain.as_str())
+    .bind(address)
+    .bind(nonce)
+    .bind(CONSUMED_NONCE_RETENTION_SECS as f64)
+    .fetch_one(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(consumed)
+  }
+
+  async fn purge_expired(&self) -> Result<u64> {
+    let db = self.state.mm().dbx().db();
+
+    let expired = sqlx::query("DELETE FROM auth_nonces WHERE expires_at <= NOW()")
+      .execute(db)
+      .await?
+      .rows_affected();
+    let markers = sqlx::query(
+      "DELETE FROM auth_consumed_nonces \
+       WHERE consumed_at <= NOW() - make_interval(secs => $1)",
+    )
+    .bind(CONSUMED_NONCE_RETENTION_SECS as f64)
+    .execute(db)
+    .await?
+    .rows_affected();
+
+    Ok(expired + markers)
+  }
+}
+
+/// A nonce row locked by an open transaction; dropping it rolls back.
+struct PgNonceLock {
+  tx: Transaction<'static, Postgres>,
+  nonce: Nonce,
+}
+
+#[async_trait]
+impl NonceLock for PgNonceLock {
+  fn nonce(&self) -> &Nonce {
+    &self.nonce
+  }
+
+  async fn consume(self: Box<Self>) -> Result<()> {
+    let PgNonceLock { mut tx, nonce } = *self;
+
+    let deleted =
+      sqlx::query("DELETE FROM auth_nonces WHERE chain = $1 AND address = $2 AND nonce = $3")
+        .bind(nonce.chain.as_str())
+        .bind(&nonce.address)
+        .bind(&nonce.nonce)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    if deleted == 0 {
+      tx.rollback().await?;
+      return Err(Error::nonce_replayed());
+    }
+
+    sqlx::query(
+      "INSERT INTO auth_consumed_nonces (chain, address, nonce, consumed_at) \
+       VALUES ($1, $2, $3, NOW()) \
+       ON CONFLICT (chain, address, nonce) DO UPDATE SET consumed_at = EXCLUDED.consumed_at",
+    )
+    .bind(nonce.chain.as_str())
+    .bind(&nonce.address)
+    .bind(&nonce.nonce)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+  }
+
+  async fn release(self: Box<Self>) -> Result<()> {
+    self.tx.rollback().await?;
+    Ok(())
+  }
+}
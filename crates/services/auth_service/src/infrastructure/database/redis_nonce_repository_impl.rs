@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use jd_cache::{Cache, CacheLock};
+use jd_core::AppState;
+use std::time::Duration;
+
+use crate::domain::{CONSUMED_NONCE_RETENTION_SECS, Chain, Nonce, NonceLock, NonceRepository};
+use crate::error::{Error, Result};
+
+/// How long a verification attempt may hold a nonce, signature check
+/// included, before the lock lapses.
+const NONCE_LOCK_TTL: Duration = Duration::from_secs(30);
+/// How long a concurrent attempt waits for the lock.
+const NONCE_LOCK_WAIT: Duration = Duration::from_secs(5);
+
+/// Nonces as Redis keys that expire with the nonce; suited to login bursts.
+pub struct RedisNonceRepositoryImpl {
+  state: AppState,
+}
+
+impl RedisNonceRepositoryImpl {
+  pub fn new(state: AppState) -> Self {
+    Self { state }
+  }
+
+  fn nonce_key(chain: Chain, address: &str) -> String {
+    format!("auth:nonce:{}:{}", chain, address)
+  }
+
+  fn consumed_key(chain: Chain, address: &str, nonce: &str) -> String {
+    format!("auth:nonce:consumed:{}:{}:{}", chain, address, nonce)
+  }
+}
+
+#[async_trait]
+impl NonceRepository for RedisNonceRepositoryImpl {
+  async fn store_nonce(&self, nonce: &Nonce) -> Result<()> {
+    // The key expires together with the nonce
+    let ttl = Duration::from_secs(nonce.ttl_secs());
+    self
+      .state
+      .cache
+      .set(&Self::nonce_key(nonce.chain, &nonce.address), nonce, Some(ttl))
+      .await
+      .map_err(|e| Error::internal_error(&format!("Failed to store nonce: {}", e)))
+  }
+
+  async fn lock_nonce(&self, chain: Chain, address: &str) -> Result<Option<Box<dyn NonceLock>>> {
+    let cache = self.state.cache.clone();
+    let key = Self::nonce_key(chain, address);
+
+    let lock = cache
+      .lock(&key, NONCE_LOCK_TTL, NONCE_LOCK_WAIT)
+      .await
+      .map_err(|e| Error::internal_error(&format!("Failed to lock nonce: {}", e)))?;
+    let pending: Option<Nonce> = match cache.get(&key).await {
+      Ok(pending) => pending,
+      Err(e) => {
+        let _ = lock.release().await;
+        return Err(Error::internal_error(&format!("Failed to read nonce: {}", e)));
+      }
+    };
+
+    let Some(nonce) = pending else {
+      lock
+        .release()
+        .await
+        .map_err(|e| Error::internal_error(&format!("Failed to unlock nonce: {}", e)))?;
+      return Ok(None);
+    };
+    Ok(Some(Box::new(RedisNonceLock { cache, lock, key, nonce })))
+  }
+
+  async fn is_consumed(&self, chain: Chain, address: &str, nonce: &str) -> Result<bool> {
+    self
+      .state
+      .cache
+      .exists(&Self::consumed_key(chain, address, nonce))
+      .await
+      .map_err(|e| Error::internal_error(&format!("Failed to check nonce: {}", e)))
+  }
+
+  async fn purge_expired(&self) -> Result<u64> {
+    // Both keys carry a TTL; Redis expires them itself.
+    Ok(0)
+  }
+}
+
+/// A nonce held under a cache lock. A lock left unreleased lapses after
+/// `NONCE_LOCK_TTL`.
+struct RedisNonceLock {
+  cache: Cache,
+  lock: CacheLock,
+  key: String,
+  nonce: Nonce,
+}
+
+#[async_trait]
+impl NonceLock for RedisNonceLock {
+  fn nonce(&self) -> &Nonce {
+    &self.nonce
+  }
+
+  async fn consume(self: Box<Self>) -> Result<()> {
+    let RedisNonceLock { cache, lock, key, nonce } = *self;
+
+    // `take` deletes atomically, so even past a lapsed lock only one
+    // attempt gets this nonce back
+    let taken: Option<Nonce> = cache
+      .take(&key)
+      .await
+      .map_err(|e| Error::internal_error(&format!("Failed to consume nonce: {}", e)))?;
+    if taken.is_none_or(|taken| taken.nonce != nonce.nonce) {
+      let _ = lock.release().await;
+      return Err(Error::nonce_replayed());
+    }
+
+    let consumed_key =
+      RedisNonceRepositoryImpl::consumed_key(nonce.chain, &nonce.address, &nonce.nonce);
+    let retention = Duration::from_secs(CONSUMED_NONCE_RETENTION_SECS as u64);
+    cache
+      .set(&consumed_key, &true, Some(retention))
+      .await
+      .map_err(|e| Error::internal_error(&format!("Failed to mark nonce consumed: {}", e)))?;
+    lock
+      .release()
+      .await
+      .map_err(|e| Error::internal_error(&format!("Failed to unlock nonce: {}", e)))?;
+    Ok(())
+  }
+
+  async fn release(self: Box<Self>) -> Result<()> {
+    self
+      .lock
+      .release()
+      .await
+      .map_err(|e| Error::internal_error(&format!("Failed to unlock nonce: {}", e)))?;
+    Ok(())
+  }
+}
@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use jd_core::AppState;
+use uuid::Uuid;
+
+use crate::domain::{
+  AuthProviderType, Chain, Role, RoleAssignment, RoleRepository, RoleSet, UserRole,
+};
+use crate::error::{Error, Result};
+
+/// Default role (`$1`) plus the roles assigned to the matched user.
+const SUBJECT_ROLES_QUERY: &str = "SELECT r.name, r.scopes FROM roles r WHERE r.name = $1 \
+   UNION \
+   SELECT r.name, r.scopes FROM roles r JOIN user_role_assignments a ON a.role_id = r.id";
+
+pub struct RoleRepositoryImpl {
+  state: AppState,
+}
+
+impl RoleRepositoryImpl {
+  pub fn new(state: AppState) -> Self {
+    Self { state }
+  }
+}
+
+#[async_trait]
+impl RoleRepository for RoleRepositoryImpl {
+  async fn list_roles(&self) -> Result<Vec<Role>> {
+    let roles = sqlx::query_as::<_, Role>(
+      "SELECT id, name, description, scopes, ctime FROM roles ORDER BY name",
+    )
+    .fetch_all(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(roles)
+  }
+
+  async fn roles_for_subject(
+    &self,
+    provider: AuthProviderType,
+    chain: Chain,
+    subject: &str,
+  ) -> Result<RoleSet> {
+    // The default role is always included, even for unknown subjects; the
+    // subject's own grants are found through its wallet or GitHub identity.
+    let default_role = UserRole::default().to_string();
+    let db = self.state.mm().dbx().db();
+
+    let rows = match provider {
+      AuthProviderType::Github => {
+        sqlx::query_as::<_, (String, Vec<String>)>(&format!(
+          "{} WHERE a.user_id IN \
+           (SELECT user_id FROM github_identities WHERE github_id::TEXT = $2)",
+          SUBJECT_ROLES_QUERY
+        ))
+        .bind(&default_role)
+        .bind(subject)
+        .fetch_all(db)
+        .await?
+      }
+      _ => {
+        sqlx::query_as::<_, (String, Vec<String>)>(&format!(
+          "{} WHERE a.user_id IN \
+           (SELECT user_id FROM user_wallets WHERE chain = $2 AND address = $3)",
+          SUBJECT_ROLES_QUERY
+        ))
+        .bind(&default_role)
+        .bind(chain.as_str())
+        .bind(subject)
+        .fetch_all(db)
+        .await?
+      }
+    };
+
+    Ok(RoleSet::from_roles(rows))
+  }
+
+  async fn user_assignments(&self, user_id: Uuid) -> Result<Vec<RoleAssignment>> {
+    let assignments = sqlx::query_as::<_, RoleAssignment>(
+      "SELECT a.user_id, r.name AS role, a.granted_by, a.granted_at \
+       FROM user_role_assignments a JOIN roles r ON r.id = a.role_id \
+       WHERE a.user_id = $1 ORDER BY r.name",
+    )
+    .bind(user_id)
+    .fetch_all(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(assignments)
+  }
+
+  async fn grant_role(
+    &self,
+    user_id: Uuid,
+    role: &str,
+    granted_by: Option<&str>,
+  ) -> Result<RoleAssignment> {
+    let db = self.state.mm().dbx().db();
+
+    let role_id = sqlx::query_scalar::<_, Uuid>("SELECT id FROM roles WHERE name = $1")
+      .bind(role)
+      .fetch_optional(db)
+      .await?
+      .ok_or_else(|| Error::role_not_found(role))?;
+
+    let user_exists =
+      sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
+    if !user_exists {
+      return Err(Error::user_not_found());
+    }
+
+    // Re-granting keeps the original grant.
+    let assignment = sqlx::query_as::<_, RoleAssignment>(
+      "WITH granted AS ( \
+         INSERT INTO user_role_assignments (user_id, role_id, granted_by) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (user_id, role_id) DO UPDATE SET user_id = EXCLUDED.user_id \
+         RETURNING user_id, granted_by, granted_at \
+       ) \
+       SELECT user_id, $4::TEXT AS role, granted_by, granted_at FROM granted",
+    )
+    .bind(user_id)
+    .bind(role_id)
+    .bind(granted_by)
+    .bind(role)
+    .fetch_one(db)
+    .await?;
+
+    Ok(assignment)
+  }
+
+  async fn revoke_role(&self, user_id: Uuid, role: &str) -> Result<bool> {
+    let result = sqlx::query(
+      "DELETE FROM user_role_assignments \
+       WHERE user_id = $1 AND role_id = (SELECT id FROM roles WHERE name = $2)",
+    )
+    .bind(user_id)
+    .bind(role)
+    .execute(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+  }
+}
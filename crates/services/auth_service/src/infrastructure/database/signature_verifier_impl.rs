@@ -6,7 +6,7 @@ use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::{ToFromBytes, VerifyingKey};
 use tracing::{error, warn};
 
-use crate::domain::SignatureVerifier;
+use crate::domain::{Chain, SignatureVerifier};
 use crate::error::{Error, Result};
 
 pub struct SignatureVerifierImpl;
@@ -19,6 +19,10 @@ impl SignatureVerifierImpl {
 
 #[async_trait]
 impl SignatureVerifier for SignatureVerifierImpl {
+  fn chain(&self) -> Chain {
+    Chain::Sui
+  }
+
   async fn verify_signature(
     &self,
     message: &str,
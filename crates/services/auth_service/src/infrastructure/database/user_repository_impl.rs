@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use jd_core::{AppState, base::rest};
 
 use crate::AuthUserDmc;
-use crate::domain::{AuthUser, AuthUserFilter, AuthUserForUpdate, UserRepository};
+use crate::domain::{AuthUser, AuthUserFilter, AuthUserForUpdate, Chain, UserRepository};
 use crate::error::{Error, Result};
 
 pub struct UserRepositoryImpl {
@@ -27,8 +27,11 @@ impl UserRepository for UserRepositoryImpl {
     Ok(())
   }
 
-  async fn get_user(&self, address: &str) -> Result<Option<AuthUser>> {
-    let filter = AuthUserFilter { address: Some(address.to_string().into()) };
+  async fn get_user(&self, chain: Chain, address: &str) -> Result<Option<AuthUser>> {
+    let filter = AuthUserFilter {
+      chain: Some(chain.to_string().into()),
+      address: Some(address.to_string().into()),
+    };
 
     match rest::get_by_sth::<AuthUserDmc, _, AuthUser>(&self.state.mm, Some(filter)).await {
       Ok(user) => Ok(Some(user)),
@@ -46,7 +49,10 @@ impl UserRepository for UserRepositoryImpl {
       login_count: Some(user.login_count),
     };
 
-    let filter = AuthUserFilter { address: Some(user.address.clone().into()) };
+    let filter = AuthUserFilter {
+      chain: Some(user.chain.to_string().into()),
+      address: Some(user.address.clone().into()),
+    };
 
     let updated_count =
       rest::update_by_filter::<AuthUserDmc, _, _>(&self.state.mm, filter, update_input)
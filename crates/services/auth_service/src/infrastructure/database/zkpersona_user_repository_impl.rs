@@ -3,10 +3,7 @@ use jd_core::{AppState, base};
 
 use crate::{
     ZkPersonaUserDmc,
-    domain::{
-        AuthUser, UserRepository, 
-        ZkPersonaUser, ZkPersonaUserForCreate, ZkPersonaUserForUpdate, ZkPersonaUserFilter
-    },
+    domain::{AuthUser, Chain, UserRepository, ZkPersonaUser, ZkPersonaUserForCreate},
     error::{Error, Result},
 };
 
@@ -32,45 +29,61 @@ impl UserRepository for ZkPersonaUserRepositoryImpl {
     Ok(())
   }
 
-  async fn get_user(&self, address: &str) -> Result<Option<AuthUser>> {
-    let filter = ZkPersonaUserFilter {
-      wallet_address: Some(address.into()),
-      id: None,
-    };
+  async fn get_user(&self, chain: Chain, address: &str) -> Result<Option<AuthUser>> {
+    // Any linked wallet logs into its account; the user surfaces with the
+    // address that was used.
+    let user = sqlx::query_as::<_, ZkPersonaUser>(
+      "SELECT u.id, w.chain, w.address AS wallet_address, \
+              COALESCE(w.public_key, '') AS public_key, u.last_login, u.login_count, \
+              u.status, u.ctime, u.mtime \
+       FROM user_wallets w JOIN users u ON u.id = w.user_id \
+       WHERE w.chain = $1 AND w.address = $2",
+    )
+    .bind(chain.as_str())
+    .bind(address)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?;
 
-    match base::rest::first::<ZkPersonaUserDmc, _, ZkPersonaUser>(&self.state.mm(), Some(filter), None)
-      .await
-    {
-      Ok(Some(zk_user)) => Ok(Some(AuthUser::from(zk_user))),
-      Ok(None) => Ok(None),
-      Err(e) => Err(Error::database_error(&e.to_string())),
-    }
+    Ok(user.map(AuthUser::from))
   }
 
   async fn update_user(&self, user: &AuthUser) -> Result<()> {
-    let filter = ZkPersonaUserFilter {
-      wallet_address: Some(user.address.clone().into()),
-      id: None,
-    };
+    let mut tx = self.state.mm().dbx().db().begin().await?;
 
-    let update_req = ZkPersonaUserForUpdate {
-      public_key: Some(user.public_key.clone()),
-      last_login: Some(user.last_login),
-      login_count: Some(user.login_count),
-    };
-
-    let updated_count = base::rest::update_by_filter::<ZkPersonaUserDmc, _, _>(
-      &self.state.mm(),
-      filter,
-      update_req,
+    let user_id = sqlx::query_scalar::<_, uuid::Uuid>(
+      "UPDATE user_wallets SET public_key = $3 WHERE chain = $1 AND address = $2 \
+       RETURNING user_id",
     )
-    .await
-    .map_err(|e| Error::database_error(&e.to_string()))?;
+    .bind(user.chain.as_str())
+    .bind(&user.address)
+    .bind(&user.public_key)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| Error::database_error("User not found for update"))?;
 
-    if updated_count == 0 {
-      return Err(Error::database_error("User not found for update"));
-    }
+    sqlx::query("UPDATE users SET last_login = $2, login_count = $3, mtime = NOW() WHERE id = $1")
+      .bind(user_id)
+      .bind(user.last_login)
+      .bind(user.login_count)
+      .execute(&mut *tx)
+      .await?;
 
+    tx.commit().await?;
     Ok(())
   }
-}
\ No newline at end of file
+
+  async fn get_user_by_github_id(&self, github_id: i64) -> Result<Option<AuthUser>> {
+    let user = sqlx::query_as::<_, ZkPersonaUser>(
+      "SELECT u.id, u.chain, COALESCE(u.wallet_address, '') AS wallet_address, \
+              COALESCE(u.public_key, '') AS public_key, u.last_login, u.login_count, \
+              u.status, u.ctime, u.mtime \
+       FROM users u JOIN github_identities g ON g.user_id = u.id \
+       WHERE g.github_id = $1",
+    )
+    .bind(github_id)
+    .fetch_optional(self.state.mm().dbx().db())
+    .await?;
+
+    Ok(user.map(AuthUser::from))
+  }
+}
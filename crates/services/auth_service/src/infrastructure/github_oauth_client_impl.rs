@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::domain::{GithubAccessToken, GithubOAuthClient, GithubProfile};
+use crate::error::{Error, Result};
+
+const AUTHORIZE_URL: &str = "https://github.com/login/oauth/authorize";
+const ACCESS_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const USER_URL: &str = "https://api.github.com/user";
+/// Profile access plus repository access for github_service.
+const OAUTH_SCOPES: &str = "read:user user:email repo";
+const USER_AGENT: &str = "zkpersona-auth-service";
+
+/// The token endpoint answers 200 with an `error` field on failure.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AccessTokenResponse {
+  Token(GithubAccessToken),
+  Error { error: String, error_description: Option<String> },
+}
+
+pub struct GithubOAuthClientImpl {
+  http: reqwest::Client,
+  client_id: String,
+  client_secret: String,
+  redirect_url: String,
+}
+
+impl GithubOAuthClientImpl {
+  pub fn new(client_id: String, client_secret: String, redirect_url: String) -> Self {
+    Self { http: reqwest::Client::new(), client_id, client_secret, redirect_url }
+  }
+}
+
+#[async_trait]
+impl GithubOAuthClient for GithubOAuthClientImpl {
+  fn authorize_url(&self, state: &str) -> String {
+    format!(
+      "{}?client_id={}&redirect_uri={}&scope={}&state={}",
+      AUTHORIZE_URL,
+      urlencoding::encode(&self.client_id),
+      urlencoding::encode(&self.redirect_url),
+      urlencoding::encode(OAUTH_SCOPES),
+      urlencoding::encode(state),
+    )
+  }
+
+  async fn exchange_code(&self, code: &str) -> Result<GithubAccessToken> {
+    let response = self
+      .http
+      .post(ACCESS_TOKEN_URL)
+      .header("Accept", "application/json")
+      .header("User-Agent", USER_AGENT)
+      .form(&[
+        ("client_id", self.client_id.as_str()),
+        ("client_secret", self.client_secret.as_str()),
+        ("code", code),
+        ("redirect_uri", self.redirect_url.as_str()),
+      ])
+      .send()
+      .await
+      .map_err(|e| Error::github_oauth_failed(&e.to_string()))?
+      .error_for_status()
+      .map_err(|e| Error::github_oauth_failed(&e.to_string()))?
+      .json::<AccessTokenResponse>()
+      .await
+      .map_err(|e| Error::github_oauth_failed(&e.to_string()))?;
+
+    match response {
+      AccessTokenResponse::Token(token) => Ok(token),
+      AccessTokenResponse::Error { error, error_description } => {
+        Err(Error::github_oauth_failed(&error_description.unwrap_or(error)))
+      }
+    }
+  }
+
+  async fn fetch_profile(&self, access_token: &str) -> Result<GithubProfile> {
+    self
+      .http
+      .get(USER_URL)
+      .bearer_auth(access_token)
+      .header("Accept", "application/vnd.github+json")
+      .header("User-Agent", USER_AGENT)
+      .send()
+      .await
+      .map_err(|e| Error::github_oauth_failed(&e.to_string()))?
+      .error_for_status()
+      .map_err(|e| Error::github_oauth_failed(&e.to_string()))?
+      .json::<GithubProfile>()
+      .await
+      .map_err(|e| Error::github_oauth_failed(&e.to_string()))
+  }
+}
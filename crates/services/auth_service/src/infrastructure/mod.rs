@@ -1,3 +1,7 @@
 pub mod database;
+pub mod github_oauth_client_impl;
+pub mod verifiers;
 
 pub use database::*;
+pub use github_oauth_client_impl::GithubOAuthClientImpl;
+pub use verifiers::*;
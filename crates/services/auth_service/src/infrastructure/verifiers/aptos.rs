@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::traits::{ToFromBytes, VerifyingKey};
+use sha3::{Digest, Sha3_256};
+use tracing::error;
+
+use crate::domain::{Chain, SignatureVerifier};
+use crate::error::{Error, Result};
+
+/// Authentication key scheme byte for single-signer ed25519 accounts.
+const ED25519_SCHEME: u8 = 0x00;
+
+/// Verifies ed25519 signatures produced by Aptos wallets.
+///
+/// The address must be the account's authentication key,
+/// `sha3_256(public_key || 0x00)`. Wallets either sign the raw message or the
+/// `signMessage` envelope `"APTOS\nmessage: <message>\nnonce: <nonce>"`.
+pub struct AptosSignatureVerifier;
+
+impl AptosSignatureVerifier {
+  pub fn new() -> Self {
+    Self
+  }
+
+  fn derive_address(public_key: &[u8]) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(public_key);
+    hasher.update([ED25519_SCHEME]);
+    format!("0x{}", hex::encode(hasher.finalize()))
+  }
+
+  fn sign_message_envelope(message: &str) -> Option<String> {
+    let (_, nonce) = message.rsplit_once(": ")?;
+    Some(format!("APTOS\nmessage: {}\nnonce: {}", message, nonce))
+  }
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+  hex::decode(value.trim_start_matches("0x")).ok()
+}
+
+#[async_trait]
+impl SignatureVerifier for AptosSignatureVerifier {
+  fn chain(&self) -> Chain {
+    Chain::Aptos
+  }
+
+  async fn verify_signature(
+    &self,
+    message: &str,
+    signature: &str,
+    public_key: &str,
+    address: &str,
+  ) -> Result<bool> {
+    let public_key_bytes = decode_hex(public_key).ok_or_else(Error::invalid_public_key)?;
+    let pk = Ed25519PublicKey::from_bytes(&public_key_bytes)
+      .map_err(|_| Error::invalid_public_key())?;
+
+    let derived_address = Self::derive_address(&public_key_bytes);
+    if derived_address != address.to_ascii_lowercase() {
+      error!("Address mismatch: {} vs {}", address, derived_address);
+      return Err(Error::invalid_public_key());
+    }
+
+    let signature_bytes = decode_hex(signature).ok_or_else(Error::invalid_signature)?;
+    let sig =
+      Ed25519Signature::from_bytes(&signature_bytes).map_err(|_| Error::invalid_signature())?;
+
+    if pk.verify(message.as_bytes(), &sig).is_ok() {
+      return Ok(true);
+    }
+
+    if let Some(envelope) = Self::sign_message_envelope(message)
+      && pk.verify(envelope.as_bytes(), &sig).is_ok()
+    {
+      return Ok(true);
+    }
+
+    error!("Aptos signature verification failed for {}", address);
+    Ok(false)
+  }
+}
+
+impl Default for AptosSignatureVerifier {
+  fn default() -> Self {
+    Self::new()
+  }
+}
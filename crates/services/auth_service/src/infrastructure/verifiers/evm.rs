@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use fastcrypto::hash::Keccak256 as FcKeccak256;
+use fastcrypto::secp256k1::recoverable::Secp256k1RecoverableSignature;
+use fastcrypto::traits::{RecoverableSignature, ToFromBytes};
+use sha3::{Digest, Keccak256};
+use tracing::{debug, error};
+
+use crate::domain::{Chain, SignatureVerifier};
+use crate::error::{Error, Result};
+
+/// EIP-712 domain used when wallets sign the login message as typed data.
+const EIP712_DOMAIN_NAME: &str = "Commandos HKT";
+const EIP712_DOMAIN_VERSION: &str = "1";
+const EIP712_DOMAIN_TYPE: &str = "EIP712Domain(string name,string version)";
+const EIP712_LOGIN_TYPE: &str = "Login(string message)";
+
+/// Verifies secp256k1 signatures produced by EVM wallets.
+///
+/// Accepts `personal_sign` (EIP-191) and `eth_signTypedData_v4` (EIP-712,
+/// `Login(string message)`). The public key is recovered from the signature,
+/// so the `public_key` argument is ignored.
+pub struct EvmSignatureVerifier;
+
+impl EvmSignatureVerifier {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// `"\x19Ethereum Signed Message:\n" + len(message) + message`
+  fn eip191_preimage(message: &str) -> Vec<u8> {
+    let mut preimage = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    preimage.extend_from_slice(message.as_bytes());
+    preimage
+  }
+
+  /// `0x1901 || domainSeparator || hashStruct(Login { message })`
+  fn eip712_preimage(message: &str) -> Vec<u8> {
+    let domain_separator = keccak(
+      &[
+        keccak(EIP712_DOMAIN_TYPE.as_bytes()),
+        keccak(EIP712_DOMAIN_NAME.as_bytes()),
+        keccak(EIP712_DOMAIN_VERSION.as_bytes()),
+      ]
+      .concat(),
+    );
+    let struct_hash =
+      keccak(&[keccak(EIP712_LOGIN_TYPE.as_bytes()), keccak(message.as_bytes())].concat());
+
+    let mut preimage = vec![0x19, 0x01];
+    preimage.extend_from_slice(&domain_separator);
+    preimage.extend_from_slice(&struct_hash);
+    preimage
+  }
+
+  /// Recover the signer of `preimage` as a lowercase `0x` address.
+  fn recover_address(
+    signature: &Secp256k1RecoverableSignature,
+    preimage: &[u8],
+  ) -> Option<String> {
+    let public_key = signature.recover_with_hash::<FcKeccak256>(preimage).ok()?;
+    let uncompressed = public_key.pubkey.serialize_uncompressed();
+    let hash = keccak(&uncompressed[1..]);
+    Some(format!("0x{}", hex::encode(&hash[12..])))
+  }
+
+  fn parse_signature(signature: &str) -> Result<Secp256k1RecoverableSignature> {
+    let mut bytes = hex::decode(signature.trim_start_matches("0x"))
+      .map_err(|_| Error::invalid_signature())?;
+    if bytes.len() != 65 {
+      return Err(Error::invalid_signature());
+    }
+
+    // Wallets emit v as 27/28 (legacy) or 0/1; fastcrypto expects the recovery id.
+    bytes[64] = match bytes[64] {
+      27 | 28 => bytes[64] - 27,
+      0 | 1 => bytes[64],
+      _ => return Err(Error::invalid_signature()),
+    };
+
+    Secp256k1RecoverableSignature::from_bytes(&bytes).map_err(|_| Error::invalid_signature())
+  }
+}
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+  Keccak256::digest(data).into()
+}
+
+#[async_trait]
+impl SignatureVerifier for EvmSignatureVerifier {
+  fn chain(&self) -> Chain {
+    Chain::Evm
+  }
+
+  async fn verify_signature(
+    &self,
+    message: &str,
+    signature: &str,
+    _public_key: &str,
+    address: &str,
+  ) -> Result<bool> {
+    let signature = Self::parse_signature(signature)?;
+    let expected = address.to_ascii_lowercase();
+    let signed_by_address = |preimage: &[u8]| {
+      Self::recover_address(&signature, preimage).is_some_and(|recovered| recovered == expected)
+    };
+
+    if signed_by_address(&Self::eip191_preimage(message)) {
+      return Ok(true);
+    }
+
+    if signed_by_address(&Self::eip712_preimage(message)) {
+      debug!("EVM wallet signed login message as EIP-712 typed data");
+      return Ok(true);
+    }
+
+    error!("EVM signature does not recover to {}", address);
+    Ok(false)
+  }
+}
+
+impl Default for EvmSignatureVerifier {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,9 @@
+pub mod aptos;
+pub mod evm;
+pub mod registry;
+pub mod solana;
+
+pub use aptos::AptosSignatureVerifier;
+pub use evm::EvmSignatureVerifier;
+pub use registry::SignatureVerifierRegistry;
+pub use solana::SolanaSignatureVerifier;
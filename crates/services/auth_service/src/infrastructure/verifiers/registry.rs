@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::{Chain, SignatureVerifier};
+use crate::error::{Error, Result};
+use crate::infrastructure::SignatureVerifierImpl;
+
+use super::{AptosSignatureVerifier, EvmSignatureVerifier, SolanaSignatureVerifier};
+
+/// Maps each supported chain to the verifier that understands its wallets.
+///
+/// `Default` registers every built-in verifier; new chains are added by
+/// implementing `SignatureVerifier` and calling `register`.
+#[derive(Clone)]
+pub struct SignatureVerifierRegistry {
+  verifiers: HashMap<Chain, Arc<dyn SignatureVerifier>>,
+}
+
+impl SignatureVerifierRegistry {
+  pub fn empty() -> Self {
+    Self { verifiers: HashMap::new() }
+  }
+
+  /// Register a verifier under the chain it reports, replacing any previous one.
+  pub(crate) fn register(&mut self, verifier: Arc<dyn SignatureVerifier>) {
+    self.verifiers.insert(verifier.chain(), verifier);
+  }
+
+  pub(crate) fn get(&self, chain: Chain) -> Result<&dyn SignatureVerifier> {
+    self
+      .verifiers
+      .get(&chain)
+      .map(|verifier| verifier.as_ref())
+      .ok_or_else(|| Error::unsupported_chain(chain.as_str()))
+  }
+
+  pub fn supports(&self, chain: Chain) -> bool {
+    self.verifiers.contains_key(&chain)
+  }
+}
+
+impl Default for SignatureVerifierRegistry {
+  fn default() -> Self {
+    let mut registry = Self::empty();
+    registry.register(Arc::new(SignatureVerifierImpl::new()));
+    registry.register(Arc::new(EvmSignatureVerifier::new()));
+    registry.register(Arc::new(SolanaSignatureVerifier::new()));
+    registry.register(Arc::new(AptosSignatureVerifier::new()));
+    registry
+  }
+}
@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::traits::{ToFromBytes, VerifyingKey};
+use tracing::error;
+
+use crate::domain::{Chain, SignatureVerifier};
+use crate::error::{Error, Result};
+
+/// Verifies ed25519 signatures produced by Solana wallets (`signMessage`).
+///
+/// A Solana address is the base58-encoded public key, so the key is taken from
+/// the address and the `public_key` argument is ignored. Wallets sign the raw
+/// message bytes; the signature may be sent as base58 or base64.
+pub struct SolanaSignatureVerifier;
+
+impl SolanaSignatureVerifier {
+  pub fn new() -> Self {
+    Self
+  }
+
+  fn decode_signature(signature: &str) -> Result<Vec<u8>> {
+    bs58::decode(signature)
+      .into_vec()
+      .ok()
+      .filter(|bytes| bytes.len() == 64)
+      .or_else(|| general_purpose::STANDARD.decode(signature).ok())
+      .filter(|bytes| bytes.len() == 64)
+      .ok_or_else(Error::invalid_signature)
+  }
+}
+
+#[async_trait]
+impl SignatureVerifier for SolanaSignatureVerifier {
+  fn chain(&self) -> Chain {
+    Chain::Solana
+  }
+
+  async fn verify_signature(
+    &self,
+    message: &str,
+    signature: &str,
+    _public_key: &str,
+    address: &str,
+  ) -> Result<bool> {
+    let public_key_bytes =
+      bs58::decode(address).into_vec().map_err(|_| Error::invalid_public_key())?;
+    let pk = Ed25519PublicKey::from_bytes(&public_key_bytes)
+      .map_err(|_| Error::invalid_public_key())?;
+
+    let signature_bytes = Self::decode_signature(signature)?;
+    let sig =
+      Ed25519Signature::from_bytes(&signature_bytes).map_err(|_| Error::invalid_signature())?;
+
+    if pk.verify(message.as_bytes(), &sig).is_ok() {
+      return Ok(true);
+    }
+
+    error!("Solana signature verification failed for {}", address);
+    Ok(false)
+  }
+}
+
+impl Default for SolanaSignatureVerifier {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -1,24 +1,43 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::domain::{Chain, LoginMessageVersion, OrganizationRole};
+
 #[derive(Debug, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_nonce_request"))]
 pub struct NonceRequest {
-  #[validate(length(min = 66, max = 66, message = "Address must be 66 characters (0x + 64 hex)"))]
-  #[validate(custom(function = "validate_sui_address"))]
+  /// Defaults to Sui so existing clients keep working.
+  #[serde(default)]
+  pub chain: Chain,
+
   pub address: String,
+
+  /// `2` for the structured, domain-bound message. Omitted by older clients,
+  /// which get the legacy bare-nonce message.
+  #[serde(default)]
+  pub message_version: LoginMessageVersion,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_verify_request"))]
 pub struct VerifyRequest {
-  #[validate(length(min = 66, max = 66, message = "Address must be 66 characters"))]
-  #[validate(custom(function = "validate_sui_address"))]
+  #[serde(default)]
+  pub chain: Chain,
+
   pub address: String,
 
   #[validate(length(min = 1, message = "Signature cannot be empty"))]
   pub signature: String,
 
-  #[validate(length(min = 1, message = "Public key cannot be empty"))]
+  /// Required for Sui and Aptos; EVM and Solana derive it from the signature
+  /// or the address.
+  #[serde(default)]
   pub public_key: String,
+
+  /// The structured message exactly as signed. When absent, the signature is
+  /// checked against the message issued with the nonce.
+  #[serde(default)]
+  pub message: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -27,10 +46,93 @@ pub struct RefreshRequest {
   pub refresh_token: String,
 }
 
-fn validate_sui_address(address: &str) -> Result<(), validator::ValidationError> {
-  if crate::domain::AuthUser::is_valid_address(address) {
+/// Query string GitHub appends when redirecting back to the callback.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct GithubCallbackQuery {
+  #[validate(length(min = 1, message = "Code cannot be empty"))]
+  pub code: String,
+
+  #[validate(length(min = 1, message = "State cannot be empty"))]
+  pub state: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct GrantRoleRequest {
+  #[validate(length(min = 1, max = 50, message = "Role must be 1-50 characters"))]
+  pub role: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateOrganizationRequest {
+  #[validate(length(min = 2, max = 100, message = "Name must be 2-100 characters"))]
+  pub name: String,
+
+  /// Derived from the name when omitted.
+  #[validate(length(min = 1, max = 50, message = "Slug must be 1-50 characters"))]
+  pub slug: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct InviteMemberRequest {
+  #[validate(email(message = "Invalid email address"))]
+  pub email: String,
+
+  /// Defaults to `member`.
+  pub role: Option<OrganizationRole>,
+}
+
+/// In-app notification switches to change; omitted ones are kept.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateInAppPreferencesRequest {
+  pub analysis_complete: Option<bool>,
+  pub critical_vulnerability: Option<bool>,
+  pub patch_review_requested: Option<bool>,
+  pub score_changed: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ListNotificationsQuery {
+  #[serde(default)]
+  pub unread_only: bool,
+  /// Only notifications older than this one, for the next page.
+  pub before: Option<i64>,
+  /// Defaults to 20, at most 100.
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TestAlertChannelQuery {
+  /// The event to send a sample of; defaults to the channel's first.
+  pub event: Option<String>,
+}
+
+/// Email notification switches to change; omitted ones are kept.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateNotificationPreferencesRequest {
+  pub analysis_complete: Option<bool>,
+  pub critical_vulnerability: Option<bool>,
+  pub patch_approved: Option<bool>,
+  pub weekly_digest: Option<bool>,
+}
+
+fn validate_nonce_request(request: &NonceRequest) -> Result<(), validator::ValidationError> {
+  validate_chain_address(request.chain, &request.address)
+}
+
+fn validate_verify_request(request: &VerifyRequest) -> Result<(), validator::ValidationError> {
+  validate_chain_address(request.chain, &request.address)?;
+
+  if request.public_key.is_empty() && !request.chain.public_key_optional() {
+    return Err(validator::ValidationError::new("missing_public_key"));
+  }
+
+  Ok(())
+}
+
+fn validate_chain_address(chain: Chain, address: &str) -> Result<(), validator::ValidationError> {
+  if chain.is_valid_address(address) {
     Ok(())
   } else {
-    Err(validator::ValidationError::new("invalid_sui_address"))
+    Err(validator::ValidationError::new("invalid_chain_address"))
   }
 }
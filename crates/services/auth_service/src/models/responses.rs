@@ -1,15 +1,24 @@
-use crate::domain::{AuthUser, TokenPair};
+use crate::domain::{
+  AccountReputation, AuthUser, Chain, GithubIdentity, LinkedWallet, LoginMessageVersion,
+  Organization, OrganizationInvitation, OrganizationMember, RegisteredRepository, Role,
+  RoleAssignment, TokenPair,
+};
+use jd_notifications::Notification;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NonceResponse {
+  pub chain: Chain,
   pub nonce: String,
   pub message: String,
+  pub message_version: LoginMessageVersion,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UserInfo {
+  pub chain: Chain,
   pub address: String,
   pub public_key: String,
   #[serde(with = "time::serde::rfc3339")]
@@ -22,6 +31,7 @@ pub struct UserInfo {
 impl From<AuthUser> for UserInfo {
   fn from(user: AuthUser) -> Self {
     Self {
+      chain: user.chain,
       address: user.address,
       public_key: user.public_key,
       created_at: user.created_at,
@@ -42,3 +52,94 @@ pub struct VerifyResponse {
 pub struct RefreshResponse {
   pub access_token: String,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GithubAccountInfo {
+  pub github_id: i64,
+  pub login: String,
+  pub name: Option<String>,
+  pub avatar_url: Option<String>,
+  pub scopes: String,
+  #[serde(with = "time::serde::rfc3339")]
+  pub linked_at: OffsetDateTime,
+}
+
+impl From<GithubIdentity> for GithubAccountInfo {
+  fn from(identity: GithubIdentity) -> Self {
+    Self {
+      github_id: identity.github_id,
+      login: identity.login,
+      name: identity.name,
+      avatar_url: identity.avatar_url,
+      scopes: identity.scopes,
+      linked_at: identity.linked_at,
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GithubLoginResponse {
+  pub success: bool,
+  pub user: UserInfo,
+  pub github: GithubAccountInfo,
+  pub tokens: TokenPair,
+  pub is_new_user: bool,
+  /// True when the flow linked GitHub to an already signed-in user.
+  pub linked: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleListResponse {
+  pub roles: Vec<Role>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserRolesResponse {
+  pub user_id: Uuid,
+  pub assignments: Vec<RoleAssignment>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountOverview {
+  pub user_id: Uuid,
+  pub wallets: Vec<LinkedWallet>,
+  pub github_logins: Vec<String>,
+  pub reputation: AccountReputation,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrganizationOverview {
+  pub organization: Organization,
+  /// False once the trial has lapsed, even before the scheduler expires it.
+  pub is_active: bool,
+  pub members: Vec<OrganizationMember>,
+  pub pending_invitations: Vec<OrganizationInvitation>,
+  pub repositories: Vec<RegisteredRepository>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InvitationResponse {
+  pub invitation: OrganizationInvitation,
+  /// Shown once; the invitee accepts with it.
+  pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationList {
+  pub notifications: Vec<Notification>,
+  pub unread_count: i64,
+  /// Pass as `before` for the next page; None on the last one.
+  pub next_before: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnreadCountResponse {
+  pub unread_count: i64,
+}
+
+/// The sample alert a test delivery posted.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AlertTestResponse {
+  pub event: String,
+  pub message: String,
+}
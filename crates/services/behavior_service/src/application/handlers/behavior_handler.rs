@@ -4,9 +4,10 @@ use jd_domain::Id;
 
 use crate::application::use_cases::behavior_use_cases::BehaviorUseCases;
 use crate::domain::behavior_repository_trait::BehaviorRepository;
+use crate::domain::ingestion_tier::IngestionTiers;
 use crate::models::{
     requests::{BehaviorInputRequest, BehaviorQueryRequest},
-    responses::{BehaviorInputResponse, BehaviorListResponse},
+    responses::{BehaviorInputResponse, BehaviorListResponse, IngestionResponse},
 };
 use crate::Result;
 
@@ -15,15 +16,15 @@ pub struct BehaviorHandler<R: BehaviorRepository> {
 }
 
 impl<R: BehaviorRepository> BehaviorHandler<R> {
-    pub fn new(repository: R) -> Self {
-        let use_cases = BehaviorUseCases::new(repository);
+    pub fn new(repository: R, tiers: IngestionTiers) -> Self {
+        let use_cases = BehaviorUseCases::new(repository, tiers);
         Self { use_cases }
     }
 
     pub async fn create_behavior_input(
         State(_app_state): State<AppState>,
         Json(request): Json<BehaviorInputRequest>,
-    ) -> Result<Json<BehaviorInputResponse>> {
+    ) -> Result<Json<IngestionResponse>> {
         // Note: In practice, you'd get the handler instance from app_state
         // For now, this is a placeholder structure
         Err(crate::Error::Internal("Handler not implemented yet".to_string()))
@@ -33,7 +34,7 @@ impl<R: BehaviorRepository> BehaviorHandler<R> {
         State(_app_state): State<AppState>,
         Path(id): Path<String>,
     ) -> Result<Json<Option<BehaviorInputResponse>>> {
-        let id = Id::new(id);
+        let id: Id = id.parse()?;
         // Note: Similar placeholder
         Err(crate::Error::Internal("Handler not implemented yet".to_string()))
     }
@@ -50,7 +51,7 @@ impl<R: BehaviorRepository> BehaviorHandler<R> {
         State(_app_state): State<AppState>,
         Path(id): Path<String>,
     ) -> Result<StatusCode> {
-        let id = Id::new(id);
+        let id: Id = id.parse()?;
         // Note: Similar placeholder
         Err(crate::Error::Internal("Handler not implemented yet".to_string()))
     }
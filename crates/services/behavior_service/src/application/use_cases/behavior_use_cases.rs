@@ -1,24 +1,42 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
 use jd_domain::Id;
 use jd_domain::zkpersona_domain::profile::BehaviorInput;
+use serde_json::Value;
+use time::OffsetDateTime;
+use tracing::info;
 use validator::Validate;
 
 use crate::domain::behavior_repository_trait::BehaviorRepository;
+use crate::domain::event_schema::{EventSchema, EventValidators, compile_schema};
+use crate::domain::ingestion_tier::{
+    DEFAULT_INPUT_TYPE, IngestionTier, IngestionTiers, bucket_start, extract_sums,
+};
 use crate::models::{
-    requests::{BehaviorInputRequest, BehaviorQueryRequest},
-    responses::{BehaviorInputResponse, BehaviorListResponse},
+    BehaviorAggregateForUpsert, BehaviorInputForCreate, UnscoredInputRecord,
+    requests::{BehaviorBatchRequest, BehaviorEventRequest, BehaviorInputRequest, BehaviorQueryRequest},
+    responses::{
+        BatchEventResult, BatchEventStatus, BatchIngestionResponse, BehaviorAggregateResponse,
+        BehaviorInputResponse, BehaviorListResponse, IngestionResponse, UnscoredSubject,
+    },
 };
 use crate::{Error, Result};
 
+/// Most events one batch may carry.
+pub const MAX_BATCH_EVENTS: usize = 1000;
+
 pub struct BehaviorUseCases<R: BehaviorRepository> {
     repository: R,
+    tiers: IngestionTiers,
 }
 
 impl<R: BehaviorRepository> BehaviorUseCases<R> {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, tiers: IngestionTiers) -> Self {
+        Self { repository, tiers }
     }
 
-    pub async fn create_behavior_input(&self, request: BehaviorInputRequest) -> Result<BehaviorInputResponse> {
+    pub async fn create_behavior_input(&self, request: BehaviorInputRequest) -> Result<IngestionResponse> {
         // Validate input
         request.validate().map_err(|e| Error::Validation(e.to_string()))?;
         
@@ -27,12 +45,198 @@ impl<R: BehaviorRepository> BehaviorUseCases<R> {
             return Err(Error::InvalidInput("input_data cannot be null".to_string()));
         }
         
-        let behavior_input = BehaviorInput {
-            session_id: request.session_id,
-            input_data: request.input_data,
+        let input_type = request.input_type.unwrap_or_else(|| DEFAULT_INPUT_TYPE.to_string());
+        let tier = self.tiers.tier_for(&input_type);
+        let mut response = IngestionResponse {
+            input_type: input_type.clone(),
+            tier: tier.name().to_string(),
+            stored: false,
+            input: None,
+            aggregate: None,
         };
-        
-        self.repository.create_behavior_input(behavior_input).await
+
+        match tier {
+            IngestionTier::Raw => {
+                let behavior_input = BehaviorInput {
+                    session_id: request.session_id,
+                    input_data: request.input_data,
+                };
+                let input =
+                    self.repository.create_behavior_input(behavior_input, &input_type, 1.0).await?;
+                response.stored = true;
+                response.input = Some(input);
+            }
+            IngestionTier::Sampled { rate } => {
+                if rand::random::<f64>() < *rate {
+                    let behavior_input = BehaviorInput {
+                        session_id: request.session_id,
+                        input_data: request.input_data,
+                    };
+                    let input = self
+                        .repository
+                        .create_behavior_input(behavior_input, &input_type, 1.0 / rate)
+                        .await?;
+                    response.stored = true;
+                    response.input = Some(input);
+                }
+            }
+            IngestionTier::Aggregated { window_secs, sum_fields } => {
+                let aggregate = BehaviorAggregateForUpsert {
+                    session_id: request.session_id,
+                    input_type,
+                    bucket_start: bucket_start(OffsetDateTime::now_utc(), *window_secs),
+                    window_secs: *window_secs as i32,
+                    sums: extract_sums(&request.input_data, sum_fields),
+                };
+                response.stored = true;
+                response.aggregate = Some(self.repository.upsert_aggregate(aggregate).await?);
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Ingest a batch of events with the same tiers as single events. Each
+    /// event is checked against its type's schema and deduplicated by its
+    /// client event id; invalid and duplicate events are reported rather
+    /// than failing the batch.
+    pub async fn ingest_batch(&self, request: BehaviorBatchRequest) -> Result<BatchIngestionResponse> {
+        let events = request.events;
+        if events.is_empty() {
+            return Err(Error::InvalidInput("A batch needs at least one event".to_string()));
+        }
+        if events.len() > MAX_BATCH_EVENTS {
+            return Err(Error::InvalidInput(format!(
+                "A batch carries at most {} events, got {}",
+                MAX_BATCH_EVENTS,
+                events.len()
+            )));
+        }
+
+        let mut input_types: Vec<String> = events.iter().map(event_type).collect();
+        input_types.sort();
+        input_types.dedup();
+        let validators =
+            EventValidators::compile(&self.repository.list_event_schemas(Some(input_types)).await?)?;
+
+        let now = OffsetDateTime::now_utc();
+        let mut seen = HashSet::new();
+        let mut results = Vec::with_capacity(events.len());
+        let mut claims = Vec::new();
+        let mut inputs = Vec::new();
+        let mut aggregates = Vec::new();
+
+        for event in &events {
+            let input_type = event_type(event);
+            let mut result = BatchEventResult {
+                client_event_id: event.client_event_id.clone(),
+                status: BatchEventStatus::Rejected,
+                input_type: input_type.clone(),
+                tier: None,
+                stored: false,
+                input_id: None,
+                errors: Vec::new(),
+            };
+            if let Err(errors) = check_event(&validators, &input_type, event) {
+                result.errors = errors;
+                results.push(result);
+                continue;
+            }
+            if !seen.insert(event.client_event_id.as_str()) {
+                result.status = BatchEventStatus::Duplicate;
+                results.push(result);
+                continue;
+            }
+
+            let tier = self.tiers.tier_for(&input_type);
+            match tier {
+                IngestionTier::Raw => inputs.push(batch_input(event, &input_type, 1.0)?),
+                IngestionTier::Sampled { rate } => {
+                    if rand::random::<f64>() < *rate {
+                        inputs.push(batch_input(event, &input_type, 1.0 / rate)?);
+                    }
+                }
+                IngestionTier::Aggregated { window_secs, sum_fields } => {
+                    let aggregate = BehaviorAggregateForUpsert {
+                        session_id: event.session_id.clone(),
+                        input_type: input_type.clone(),
+                        bucket_start: bucket_start(now, *window_secs),
+                        window_secs: *window_secs as i32,
+                        sums: extract_sums(&event.input_data, sum_fields),
+                    };
+                    aggregates.push((results.len(), aggregate));
+                }
+            }
+            result.status = BatchEventStatus::Accepted;
+            result.tier = Some(tier.name().to_string());
+            claims.push(event.client_event_id.clone());
+            results.push(result);
+        }
+
+        let (claimed, stored) = if claims.is_empty() {
+            (HashSet::new(), Vec::new())
+        } else {
+            self.repository.create_behavior_batch(claims, inputs).await?
+        };
+        let stored: HashMap<String, Id> =
+            stored.into_iter().filter_map(|input| Some((input.client_event_id?, input.id))).collect();
+
+        for result in results.iter_mut().filter(|r| r.status == BatchEventStatus::Accepted) {
+            if !claimed.contains(&result.client_event_id) {
+                result.status = BatchEventStatus::Duplicate;
+                result.tier = None;
+                continue;
+            }
+            result.input_id = stored.get(&result.client_event_id).cloned();
+            result.stored = result.input_id.is_some();
+        }
+        // Aggregates are folded in once their events are claimed, so a
+        // retried batch does not count them twice.
+        for (index, aggregate) in aggregates {
+            if results[index].status == BatchEventStatus::Accepted {
+                self.repository.upsert_aggregate(aggregate).await?;
+                results[index].stored = true;
+            }
+        }
+
+        let count = |status: BatchEventStatus| results.iter().filter(|r| r.status == status).count();
+        let response = BatchIngestionResponse {
+            accepted: count(BatchEventStatus::Accepted),
+            duplicates: count(BatchEventStatus::Duplicate),
+            rejected: count(BatchEventStatus::Rejected),
+            results,
+        };
+        info!(
+            accepted = response.accepted,
+            duplicates = response.duplicates,
+            rejected = response.rejected,
+            "Behavior batch ingested"
+        );
+        Ok(response)
+    }
+
+    pub async fn list_event_schemas(&self) -> Result<Vec<EventSchema>> {
+        self.repository.list_event_schemas(None).await
+    }
+
+    /// Register the JSON Schema events of `input_type` must satisfy.
+    pub async fn register_event_schema(
+        &self,
+        input_type: &str,
+        schema: Value,
+        updated_by: &str,
+    ) -> Result<EventSchema> {
+        let valid_type = !input_type.is_empty()
+            && input_type.len() <= 50
+            && input_type.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_type {
+            return Err(Error::InvalidInput(format!("Invalid event type: {}", input_type)));
+        }
+        compile_schema(&schema)?;
+
+        let registered = self.repository.upsert_event_schema(input_type, schema, updated_by).await?;
+        info!(input_type, version = registered.version, updated_by, "Event schema registered");
+        Ok(registered)
     }
 
     pub async fn get_behavior_input(&self, id: Id) -> Result<Option<BehaviorInputResponse>> {
@@ -46,4 +250,121 @@ impl<R: BehaviorRepository> BehaviorUseCases<R> {
     pub async fn mark_as_processed(&self, id: Id) -> Result<()> {
         self.repository.mark_as_processed(id).await
     }
-}
\ No newline at end of file
+
+    /// Lease the new inputs of up to `limit` subjects that are due for
+    /// scoring: quiet for `debounce`, or waiting `max_wait` since their
+    /// oldest unprocessed input.
+    pub async fn claim_unscored(
+        &self,
+        debounce: Duration,
+        max_wait: Duration,
+        lease: Duration,
+        limit: i64,
+    ) -> Result<Vec<UnscoredSubject>> {
+        let records =
+            self.repository.claim_unscored_inputs(debounce, max_wait, lease, limit).await?;
+        Ok(group_by_subject(records))
+    }
+
+    pub async fn mark_inputs_processed(&self, ids: &[Id]) -> Result<u64> {
+        self.repository.mark_inputs_processed(ids).await
+    }
+
+    pub async fn list_aggregates(
+        &self,
+        session_id: Option<String>,
+    ) -> Result<Vec<BehaviorAggregateResponse>> {
+        self.repository.list_aggregates(session_id).await
+    }
+}
+
+/// One entry per subject, in claim order, with its latest input to score.
+fn group_by_subject(records: Vec<UnscoredInputRecord>) -> Vec<UnscoredSubject> {
+    let mut subjects: Vec<UnscoredSubject> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for record in records {
+        let input = BehaviorInputResponse::from(record.input);
+        match index.get(&record.subject) {
+            Some(&i) => {
+                let subject = &mut subjects[i];
+                subject.input_ids.push(input.id.clone());
+                if input.timestamp > subject.latest.timestamp {
+                    subject.latest = input;
+                }
+            }
+            None => {
+                index.insert(record.subject.clone(), subjects.len());
+                subjects.push(UnscoredSubject {
+                    subject: record.subject,
+                    input_ids: vec![input.id.clone()],
+                    latest: input,
+                });
+            }
+        }
+    }
+    subjects
+}
+
+fn event_type(event: &BehaviorEventRequest) -> String {
+    event.input_type.clone().unwrap_or_else(|| DEFAULT_INPUT_TYPE.to_string())
+}
+
+/// Field constraints first, then the event type's schema.
+fn check_event(
+    validators: &EventValidators,
+    input_type: &str,
+    event: &BehaviorEventRequest,
+) -> std::result::Result<(), Vec<String>> {
+    event.validate().map_err(|e| vec![e.to_string()])?;
+    validators.validate(input_type, &event.input_data)
+}
+
+fn batch_input(
+    event: &BehaviorEventRequest,
+    input_type: &str,
+    sample_weight: f64,
+) -> Result<BehaviorInputForCreate> {
+    Ok(BehaviorInputForCreate {
+        session_id: event.session_id.clone(),
+        input_data: serde_json::to_string(&event.input_data)?,
+        processed: Some(false),
+        input_type: Some(input_type.to_string()),
+        sample_weight: Some(sample_weight),
+        client_event_id: Some(event.client_event_id.clone()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BehaviorInputRecord;
+
+    fn unscored(subject: &str, seconds: i64) -> UnscoredInputRecord {
+        UnscoredInputRecord {
+            subject: subject.to_string(),
+            input: BehaviorInputRecord {
+                id: uuid::Uuid::new_v4(),
+                session_id: Some(subject.to_string()),
+                input_data: "{}".to_string(),
+                timestamp: OffsetDateTime::from_unix_timestamp(seconds).unwrap(),
+                processed: false,
+                input_type: DEFAULT_INPUT_TYPE.to_string(),
+                sample_weight: 1.0,
+                client_event_id: None,
+            },
+        }
+    }
+
+    #[test]
+    fn subjects_are_scored_on_their_latest_input() {
+        let records = vec![unscored("a", 10), unscored("b", 5), unscored("a", 30), unscored("a", 20)];
+        let latest_a = records[2].input.id;
+
+        let subjects = group_by_subject(records);
+        assert_eq!(subjects.len(), 2);
+        assert_eq!(subjects[0].subject, "a");
+        assert_eq!(subjects[0].input_ids.len(), 3);
+        assert_eq!(subjects[0].latest.id.to_uuid(), latest_a);
+        assert_eq!(subjects[1].input_ids.len(), 1);
+    }
+}
@@ -1 +1,2 @@
-pub mod behavior_use_cases;
\ No newline at end of file
+pub mod behavior_use_cases;
+pub mod stream_ingestion_use_cases;
\ No newline at end of file
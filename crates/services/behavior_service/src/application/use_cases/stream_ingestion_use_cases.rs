@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::application::use_cases::behavior_use_cases::BehaviorUseCases;
+use crate::domain::behavior_repository_trait::BehaviorRepository;
+use crate::domain::behavior_source::{BehaviorSource, SourcedEvent};
+use crate::models::{
+    requests::{BehaviorBatchRequest, BehaviorEventRequest},
+    responses::BatchEventStatus,
+};
+use crate::Result;
+
+/// Messages ingested together, as one batch.
+const STREAM_BATCH: usize = 500;
+/// How long one receive waits for messages.
+const RECEIVE_BLOCK: Duration = Duration::from_secs(5);
+/// Wait after a failed run before receiving again.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Outcome of one receive-and-ingest round.
+#[derive(Debug, Default)]
+pub struct StreamRun {
+    pub received: usize,
+    pub accepted: usize,
+    pub duplicates: usize,
+    pub dead_lettered: usize,
+}
+
+/// Ingests behavior events from a broker continuously. Messages are only
+/// acknowledged after their batch is ingested, so a failure redelivers
+/// them; client event ids make the redelivered writes no-ops.
+pub struct StreamIngestionUseCases<S: BehaviorSource, R: BehaviorRepository> {
+    source: S,
+    behavior: BehaviorUseCases<R>,
+}
+
+impl<S: BehaviorSource, R: BehaviorRepository> StreamIngestionUseCases<S, R> {
+    pub fn new(source: S, behavior: BehaviorUseCases<R>) -> Self {
+        Self { source, behavior }
+    }
+
+    /// Consume forever.
+    pub async fn run(mut self) {
+        info!(source = self.source.name(), "Behavior stream ingestion started");
+        loop {
+            match self.run_once().await {
+                Ok(run) if run.received > 0 => info!(
+                    received = run.received,
+                    accepted = run.accepted,
+                    duplicates = run.duplicates,
+                    dead_lettered = run.dead_lettered,
+                    "Behavior stream batch ingested"
+                ),
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(source = self.source.name(), error = %e, "Behavior stream ingestion failed");
+                    tokio::time::sleep(RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+
+    /// Receive one batch, ingest it and acknowledge it. Messages that are
+    /// not valid events, or that ingestion rejects, are dead-lettered and
+    /// acknowledged so they are not redelivered forever.
+    pub async fn run_once(&mut self) -> Result<StreamRun> {
+        let deliveries = self.source.receive(STREAM_BATCH, RECEIVE_BLOCK).await?;
+        let mut run = StreamRun { received: deliveries.len(), ..StreamRun::default() };
+        if deliveries.is_empty() {
+            return Ok(run);
+        }
+
+        let mut events = Vec::with_capacity(deliveries.len());
+        let mut parsed = Vec::with_capacity(deliveries.len());
+        for delivery in &deliveries {
+            match parse_event(self.source.name(), delivery) {
+                Ok(event) => {
+                    events.push(event);
+                    parsed.push(delivery);
+                }
+                Err(reason) => {
+                    self.source.dead_letter(delivery, &reason).await?;
+                    run.dead_lettered += 1;
+                }
+            }
+        }
+
+        if !events.is_empty() {
+            let response = self.behavior.ingest_batch(BehaviorBatchRequest { events }).await?;
+            for (delivery, result) in parsed.into_iter().zip(&response.results) {
+                if result.status == BatchEventStatus::Rejected {
+                    self.source.dead_letter(delivery, &result.errors.join("; ")).await?;
+                    run.dead_lettered += 1;
+                }
+            }
+            run.accepted = response.accepted;
+            run.duplicates = response.duplicates;
+        }
+
+        let delivery_ids: Vec<String> = deliveries.into_iter().map(|d| d.delivery_id).collect();
+        self.source.acknowledge(&delivery_ids).await?;
+        Ok(run)
+    }
+}
+
+/// Parse a message as one batch event. Producers may leave out
+/// `client_event_id`; the broker's delivery id stands in for it, which is
+/// stable across redeliveries of the same message.
+fn parse_event(source: &str, delivery: &SourcedEvent) -> std::result::Result<BehaviorEventRequest, String> {
+    let mut value: Value =
+        serde_json::from_str(&delivery.payload).map_err(|e| format!("Invalid event JSON: {}", e))?;
+    let Some(object) = value.as_object_mut() else {
+        return Err("Event must be a JSON object".to_string());
+    };
+    object
+        .entry("client_event_id")
+        .or_insert_with(|| Value::from(format!("{}:{}", source, delivery.delivery_id)));
+    serde_json::from_value(value).map_err(|e| format!("Invalid event: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery(payload: &str) -> SourcedEvent {
+        SourcedEvent { delivery_id: "1700000000000-0".to_string(), payload: payload.to_string() }
+    }
+
+    #[test]
+    fn delivery_id_stands_in_for_a_missing_client_event_id() {
+        let event =
+            parse_event("redis", &delivery(r#"{"input_type":"click","input_data":{}}"#)).unwrap();
+        assert_eq!(event.client_event_id, "redis:1700000000000-0");
+
+        let event = parse_event(
+            "redis",
+            &delivery(r#"{"client_event_id":"evt-1","input_data":{"target":"submit"}}"#),
+        )
+        .unwrap();
+        assert_eq!(event.client_event_id, "evt-1");
+
+        assert!(parse_event("redis", &delivery("not json")).is_err());
+        assert!(parse_event("redis", &delivery(r#"[1, 2]"#)).is_err());
+        assert!(parse_event("redis", &delivery(r#"{"client_event_id":"evt-2"}"#)).is_err());
+    }
+}
@@ -1,13 +1,66 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
 use async_trait::async_trait;
 use jd_domain::Id;
 use jd_domain::zkpersona_domain::profile::BehaviorInput;
-use crate::models::{requests::BehaviorQueryRequest, responses::{BehaviorInputResponse, BehaviorListResponse}};
+use serde_json::Value;
+use crate::domain::event_schema::EventSchema;
+use crate::models::{
+    BehaviorAggregateForUpsert, BehaviorInputForCreate, UnscoredInputRecord,
+    requests::BehaviorQueryRequest,
+    responses::{BehaviorAggregateResponse, BehaviorInputResponse, BehaviorListResponse},
+};
 use crate::Result;
 
 #[async_trait]
 pub trait BehaviorRepository: Send + Sync {
-    async fn create_behavior_input(&self, input: BehaviorInput) -> Result<BehaviorInputResponse>;
+    /// Store one event row. `sample_weight` is the number of events it stands for.
+    async fn create_behavior_input(
+        &self,
+        input: BehaviorInput,
+        input_type: &str,
+        sample_weight: f64,
+    ) -> Result<BehaviorInputResponse>;
+    /// Claim `client_event_ids` and store the `inputs` whose id was claimed,
+    /// in one transaction. Returns the ids claimed now, which excludes ids
+    /// already ingested, and the stored rows.
+    async fn create_behavior_batch(
+        &self,
+        client_event_ids: Vec<String>,
+        inputs: Vec<BehaviorInputForCreate>,
+    ) -> Result<(HashSet<String>, Vec<BehaviorInputResponse>)>;
     async fn get_behavior_input(&self, id: Id) -> Result<Option<BehaviorInputResponse>>;
     async fn list_behavior_inputs(&self, query: BehaviorQueryRequest) -> Result<BehaviorListResponse>;
     async fn mark_as_processed(&self, id: Id) -> Result<()>;
-}
\ No newline at end of file
+    /// Lease the unprocessed inputs of up to `limit` subjects that have been
+    /// quiet for `debounce`, or waited `max_wait` since their oldest input.
+    /// Inputs under an unexpired lease are skipped.
+    async fn claim_unscored_inputs(
+        &self,
+        debounce: Duration,
+        max_wait: Duration,
+        lease: Duration,
+        limit: i64,
+    ) -> Result<Vec<UnscoredInputRecord>>;
+    /// Mark `ids` processed and release their lease.
+    async fn mark_inputs_processed(&self, ids: &[Id]) -> Result<u64>;
+    /// Fold one event into its session/type/window bucket, creating the bucket if needed.
+    async fn upsert_aggregate(
+        &self,
+        aggregate: BehaviorAggregateForUpsert,
+    ) -> Result<BehaviorAggregateResponse>;
+    async fn list_aggregates(
+        &self,
+        session_id: Option<String>,
+    ) -> Result<Vec<BehaviorAggregateResponse>>;
+    /// Schemas registered for `input_types`, or for every type when `None`.
+    async fn list_event_schemas(&self, input_types: Option<Vec<String>>) -> Result<Vec<EventSchema>>;
+    /// Register the schema for `input_type`, replacing and versioning any earlier one.
+    async fn upsert_event_schema(
+        &self,
+        input_type: &str,
+        schema: Value,
+        updated_by: &str,
+    ) -> Result<EventSchema>;
+}
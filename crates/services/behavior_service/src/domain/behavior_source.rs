@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::Result;
+
+/// One message read from a broker.
+#[derive(Debug, Clone)]
+pub struct SourcedEvent {
+    /// Broker id the message is acknowledged by. Stable across redeliveries.
+    pub delivery_id: String,
+    /// The event as JSON, in the shape of one batch ingestion event.
+    pub payload: String,
+}
+
+/// A broker behavior events are consumed from with at-least-once delivery:
+/// a message is delivered again until it is acknowledged.
+#[async_trait]
+pub trait BehaviorSource: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Wait up to `block` for at most `max` messages. Messages delivered
+    /// before but never acknowledged come first, so a crash between
+    /// ingesting and acknowledging redelivers them instead of losing them.
+    async fn receive(&mut self, max: usize, block: Duration) -> Result<Vec<SourcedEvent>>;
+
+    async fn acknowledge(&mut self, delivery_ids: &[String]) -> Result<()>;
+
+    /// Set aside a message that can never be ingested, with why.
+    async fn dead_letter(&mut self, event: &SourcedEvent, reason: &str) -> Result<()>;
+}
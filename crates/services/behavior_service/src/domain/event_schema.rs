@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use jsonschema::Validator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+use crate::{Error, Result};
+
+/// Scope a token needs to register or replace event schemas.
+pub const SCOPE_BEHAVIOR_SCHEMAS_ADMIN: &str = "behavior:schemas_admin";
+
+/// The JSON Schema batch-ingested events of one type must satisfy.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EventSchema {
+    pub input_type: String,
+    pub schema: Value,
+    /// Bumped each time the schema is replaced.
+    pub version: i32,
+    pub updated_by: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+/// Compile `schema`, rejecting documents that are not valid JSON Schema.
+pub fn compile_schema(schema: &Value) -> Result<Validator> {
+    jsonschema::validator_for(schema)
+        .map_err(|e| Error::InvalidInput(format!("Invalid JSON Schema: {}", e)))
+}
+
+/// Compiled schemas for the event types of one batch.
+pub struct EventValidators {
+    validators: HashMap<String, Validator>,
+}
+
+impl EventValidators {
+    pub fn compile(schemas: &[EventSchema]) -> Result<Self> {
+        let validators = schemas
+            .iter()
+            .map(|s| Ok((s.input_type.clone(), compile_schema(&s.schema)?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok(Self { validators })
+    }
+
+    /// Check `input_data` against the schema registered for `input_type`.
+    /// `Err` lists every violation, or says the type has no schema.
+    pub fn validate(&self, input_type: &str, input_data: &Value) -> std::result::Result<(), Vec<String>> {
+        let Some(validator) = self.validators.get(input_type) else {
+            return Err(vec![format!("Unknown event type: {}", input_type)]);
+        };
+        let errors: Vec<String> = validator
+            .iter_errors(input_data)
+            .map(|e| match e.instance_path.to_string() {
+                path if path.is_empty() => e.to_string(),
+                path => format!("{}: {}", path, e),
+            })
+            .collect();
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(input_type: &str, schema: Value) -> EventSchema {
+        EventSchema {
+            input_type: input_type.to_string(),
+            schema,
+            version: 1,
+            updated_by: None,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            updated_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn events_are_checked_against_their_type() {
+        let validators = EventValidators::compile(&[schema(
+            "click",
+            json!({
+                "type": "object",
+                "required": ["target"],
+                "properties": { "target": { "type": "string" } }
+            }),
+        )])
+        .unwrap();
+
+        assert!(validators.validate("click", &json!({ "target": "submit" })).is_ok());
+
+        let errors = validators.validate("click", &json!({ "target": 3 })).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("/target"));
+
+        let errors = validators.validate("scroll", &json!({})).unwrap_err();
+        assert_eq!(errors, vec!["Unknown event type: scroll".to_string()]);
+    }
+
+    #[test]
+    fn invalid_schemas_are_rejected() {
+        assert!(compile_schema(&json!({ "type": "not-a-type" })).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use jd_utils::config::BehaviorAnalysisConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use time::OffsetDateTime;
+
+use crate::{Error, Result};
+
+/// Event type used when a request does not name one. Matches the column default.
+pub const DEFAULT_INPUT_TYPE: &str = "general";
+
+/// Key under which sampled and aggregated payloads carry their ingestion
+/// metadata when passed to the scoring engine.
+pub const INGESTION_META_KEY: &str = "_ingestion";
+
+/// How events of one type are persisted at ingestion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "tier", rename_all = "snake_case")]
+pub enum IngestionTier {
+    /// Every event is stored as its own `behavior_inputs` row.
+    Raw,
+    /// Only a `rate` fraction of events is stored; each stored row carries a
+    /// weight of `1 / rate` so consumers can re-scale counts.
+    Sampled { rate: f64 },
+    /// Events are folded into one `behavior_aggregates` row per session and
+    /// window, keeping the event count and the sum of each listed numeric field.
+    Aggregated { window_secs: i64, sum_fields: Vec<String> },
+}
+
+impl IngestionTier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            IngestionTier::Raw => "raw",
+            IngestionTier::Sampled { .. } => "sampled",
+            IngestionTier::Aggregated { .. } => "aggregated",
+        }
+    }
+
+    /// Parse one tier spec: `raw`, `sample:<rate>` or
+    /// `aggregate:<window_secs>[:field|field...]`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let invalid = || Error::InvalidInput(format!("Invalid ingestion tier: {}", spec));
+        let mut parts = spec.trim().split(':');
+
+        match parts.next().map(str::trim) {
+            Some("raw") => Ok(IngestionTier::Raw),
+            Some("sample") => {
+                let rate: f64 =
+                    parts.next().and_then(|r| r.trim().parse().ok()).ok_or_else(invalid)?;
+                if !(rate > 0.0 && rate <= 1.0) {
+                    return Err(invalid());
+                }
+                Ok(IngestionTier::Sampled { rate })
+            }
+            Some("aggregate") => {
+                let window_secs: i64 =
+                    parts.next().and_then(|w| w.trim().parse().ok()).ok_or_else(invalid)?;
+                if window_secs <= 0 {
+                    return Err(invalid());
+                }
+                let sum_fields = parts
+                    .next()
+                    .map(|fields| {
+                        fields
+                            .split('|')
+                            .map(str::trim)
+                            .filter(|f| !f.is_empty())
+                            .map(String::from)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Ok(IngestionTier::Aggregated { window_secs, sum_fields })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Per-event-type tier configuration. Unlisted event types are stored raw.
+#[derive(Debug, Clone, Default)]
+pub struct IngestionTiers {
+    tiers: HashMap<String, IngestionTier>,
+}
+
+impl IngestionTiers {
+    /// Parse a comma-separated list of `<event_type>=<tier spec>` entries, e.g.
+    /// `mouse_move=sample:0.1,heartbeat=aggregate:60:duration_ms`.
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let mut tiers = HashMap::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (event_type, tier) = entry.split_once('=').ok_or_else(|| {
+                Error::InvalidInput(format!("Invalid ingestion tier entry: {}", entry))
+            })?;
+            tiers.insert(event_type.trim().to_string(), IngestionTier::parse(tier)?);
+        }
+        Ok(Self { tiers })
+    }
+
+    /// Build from config, falling back to all-raw when unset or invalid.
+    pub fn from_config(config: Option<&BehaviorAnalysisConfig>) -> Self {
+        let Some(spec) = config.and_then(|c| c.ingestion_tiers.as_deref()) else {
+            return Self::default();
+        };
+
+        Self::from_spec(spec).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring behavior ingestion tiers: {}", e);
+            Self::default()
+        })
+    }
+
+    pub fn tier_for(&self, event_type: &str) -> &IngestionTier {
+        self.tiers.get(event_type).unwrap_or(&IngestionTier::Raw)
+    }
+}
+
+/// Start of the aggregation window containing `timestamp`.
+pub fn bucket_start(timestamp: OffsetDateTime, window_secs: i64) -> OffsetDateTime {
+    let ts = timestamp.unix_timestamp();
+    let start = ts - ts.rem_euclid(window_secs);
+    OffsetDateTime::from_unix_timestamp(start).unwrap_or(timestamp)
+}
+
+/// Numeric values of `fields` in a JSON object event. Missing or non-numeric
+/// fields are skipped rather than counted as zero.
+pub fn extract_sums(input_data: &Value, fields: &[String]) -> Map<String, Value> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let value = input_data.get(field)?.as_f64()?;
+            Some((field.clone(), Value::from(value)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tier_spec() {
+        let spec = "mouse_move=sample:0.1, heartbeat=aggregate:60:duration_ms|clicks";
+        let tiers = IngestionTiers::from_spec(spec).unwrap();
+
+        assert_eq!(tiers.tier_for("mouse_move"), &IngestionTier::Sampled { rate: 0.1 });
+        assert_eq!(
+            tiers.tier_for("heartbeat"),
+            &IngestionTier::Aggregated {
+                window_secs: 60,
+                sum_fields: vec!["duration_ms".to_string(), "clicks".to_string()],
+            }
+        );
+        assert_eq!(tiers.tier_for("purchase"), &IngestionTier::Raw);
+
+        assert!(IngestionTiers::from_spec("mouse_move=sample:0").is_err());
+        assert!(IngestionTiers::from_spec("mouse_move=aggregate:-5").is_err());
+        assert!(IngestionTiers::from_spec("mouse_move").is_err());
+    }
+
+    #[test]
+    fn test_bucket_and_sums() {
+        let ts = OffsetDateTime::from_unix_timestamp(1_700_000_125).unwrap();
+        assert_eq!(bucket_start(ts, 60).unix_timestamp(), 1_700_000_100);
+
+        let event = serde_json::json!({ "duration_ms": 250, "clicks": "3", "x": 1 });
+        let sums = extract_sums(&event, &["duration_ms".to_string(), "clicks".to_string()]);
+        assert_eq!(sums.len(), 1);
+        assert_eq!(sums["duration_ms"], Value::from(250.0));
+    }
+}
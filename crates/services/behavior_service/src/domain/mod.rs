@@ -1 +1,4 @@
-pub mod behavior_repository_trait;
\ No newline at end of file
+pub mod behavior_repository_trait;
+pub mod behavior_source;
+pub mod event_schema;
+pub mod ingestion_tier;
\ No newline at end of file
@@ -1,27 +1,45 @@
 use axum::{http::StatusCode, response::IntoResponse, Json};
 use serde_json::json;
 
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Debug, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "behavior")]
 pub enum Error {
     #[error("Invalid behavior input: {0}")]
+    #[taxonomy(kind = Validation, expose)]
     InvalidInput(String),
     
     #[error("Validation error: {0}")]
+    #[taxonomy(kind = Validation, expose)]
     Validation(String),
     
     #[error("Database error: {0}")]
+    #[taxonomy(kind = Internal, code = "DATABASE_ERROR")]
     Database(#[from] sqlx::Error),
     
     #[error("Serialization error: {0}")]
+    #[taxonomy(kind = Validation, message = "Invalid data format")]
     Serialization(#[from] serde_json::Error),
     
+    #[error("Broker error: {0}")]
+    #[taxonomy(kind = Internal, code = "BROKER_ERROR")]
+    Broker(#[from] redis::RedisError),
+    
     #[error("Internal error: {0}")]
+    #[taxonomy(kind = Internal)]
     Internal(String),
     
     #[error("Core error: {0}")]
+    #[taxonomy(kind = Internal, code = "CORE_ERROR")]
     Core(#[from] jd_core::Error),
 }
 
+/// Malformed ids from requests are the caller's mistake.
+impl From<jd_domain::Error> for Error {
+    fn from(err: jd_domain::Error) -> Self {
+        Error::InvalidInput(err.to_string())
+    }
+}
+
 impl IntoResponse for Error {
     fn into_response(self) -> axum::response::Response {
         let (status, error_message) = match self {
@@ -29,6 +47,7 @@ impl IntoResponse for Error {
             Error::Validation(msg) => (StatusCode::BAD_REQUEST, msg),
             Error::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()),
             Error::Serialization(_) => (StatusCode::BAD_REQUEST, "Invalid data format".to_string()),
+            Error::Broker(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Broker error".to_string()),
             Error::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()),
             Error::Core(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Core service error".to_string()),
         };
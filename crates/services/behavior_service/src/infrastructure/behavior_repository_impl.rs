@@ -1,15 +1,21 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
 use async_trait::async_trait;
-use jd_core::{AppState, base};
+use jd_core::{AppState, ModelManager, base};
 use jd_domain::Id;
 use jd_domain::zkpersona_domain::profile::BehaviorInput;
+use serde_json::Value;
 
 use crate::{
     BehaviorInputDmc,
-    domain::behavior_repository_trait::BehaviorRepository,
+    domain::{behavior_repository_trait::BehaviorRepository, event_schema::EventSchema},
     models::{
         requests::BehaviorQueryRequest,
-        responses::{BehaviorInputResponse, BehaviorListResponse},
+        responses::{BehaviorAggregateResponse, BehaviorInputResponse, BehaviorListResponse},
+        BehaviorAggregateForUpsert, BehaviorAggregateRecord,
         BehaviorInputRecord, BehaviorInputForCreate, BehaviorInputForUpdate, BehaviorInputFilter,
+        UnscoredInputRecord,
     },
     Result,
 };
@@ -23,16 +29,66 @@ impl BehaviorRepositoryImpl {
     pub fn new(app_state: AppState) -> Self {
         Self { app_state }
     }
+
+    /// Body of `create_behavior_batch`, run inside the transaction on `mm`.
+    async fn claim_and_store(
+        mm: &ModelManager,
+        client_event_ids: Vec<String>,
+        inputs: Vec<BehaviorInputForCreate>,
+    ) -> Result<(HashSet<String>, Vec<BehaviorInputResponse>)> {
+        let claimed: HashSet<String> = mm
+            .dbx()
+            .fetch_all(
+                sqlx::query_as::<_, (String,)>(
+                    "INSERT INTO behavior_event_receipts (client_event_id) \
+                     SELECT UNNEST($1::varchar[]) \
+                     ON CONFLICT (client_event_id) DO NOTHING \
+                     RETURNING client_event_id",
+                )
+                .bind(client_event_ids),
+            )
+            .await
+            .map_err(jd_core::Error::from)?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect();
+
+        // create_many needs every row to set the same columns, so rows with
+        // and without a session go in separately.
+        let (with_session, without_session): (Vec<_>, Vec<_>) = inputs
+            .into_iter()
+            .filter(|input| input.client_event_id.as_ref().is_some_and(|id| claimed.contains(id)))
+            .partition(|input| input.session_id.is_some());
+
+        let mut stored = Vec::new();
+        for group in [with_session, without_session] {
+            if group.is_empty() {
+                continue;
+            }
+            let records =
+                base::rest::create_many::<BehaviorInputDmc, _, BehaviorInputRecord>(mm, group).await?;
+            stored.extend(records.into_iter().map(BehaviorInputResponse::from));
+        }
+        Ok((claimed, stored))
+    }
 }
 
 #[async_trait]
 impl BehaviorRepository for BehaviorRepositoryImpl {
-    async fn create_behavior_input(&self, input: BehaviorInput) -> Result<BehaviorInputResponse> {
+    async fn create_behavior_input(
+        &self,
+        input: BehaviorInput,
+        input_type: &str,
+        sample_weight: f64,
+    ) -> Result<BehaviorInputResponse> {
         let create_req = BehaviorInputForCreate {
             session_id: input.session_id,
             input_data: serde_json::to_string(&input.input_data)
                 .map_err(|e| crate::Error::Serialization(e))?,
             processed: Some(false),
+            input_type: Some(input_type.to_string()),
+            sample_weight: Some(sample_weight),
+            client_event_id: None,
         };
         
         let record = base::rest::create::<BehaviorInputDmc, _, BehaviorInputRecord>(
@@ -43,6 +99,28 @@ impl BehaviorRepository for BehaviorRepositoryImpl {
         Ok(BehaviorInputResponse::from(record))
     }
 
+    async fn create_behavior_batch(
+        &self,
+        client_event_ids: Vec<String>,
+        inputs: Vec<BehaviorInputForCreate>,
+    ) -> Result<(HashSet<String>, Vec<BehaviorInputResponse>)> {
+        // Receipts and rows commit together, so a failed insert leaves the
+        // ids free for the client's retry.
+        let mm = self.app_state.mm().new_with_txn()?;
+        mm.dbx().begin_txn().await.map_err(jd_core::Error::from)?;
+
+        match Self::claim_and_store(&mm, client_event_ids, inputs).await {
+            Ok(stored) => {
+                mm.dbx().commit_txn().await.map_err(jd_core::Error::from)?;
+                Ok(stored)
+            }
+            Err(e) => {
+                mm.dbx().rollback_txn().await.ok();
+                Err(e)
+            }
+        }
+    }
+
     async fn get_behavior_input(&self, id: Id) -> Result<Option<BehaviorInputResponse>> {
         let id_uuid = id.to_uuid();
             
@@ -102,4 +180,143 @@ impl BehaviorRepository for BehaviorRepositoryImpl {
             
         Ok(())
     }
-}
\ No newline at end of file
+
+    async fn claim_unscored_inputs(
+        &self,
+        debounce: Duration,
+        max_wait: Duration,
+        lease: Duration,
+        limit: i64,
+    ) -> Result<Vec<UnscoredInputRecord>> {
+        // A concurrent claim re-checks the lease once the first commits, so
+        // each input is leased by one server.
+        let records = sqlx::query_as::<_, UnscoredInputRecord>(
+            r#"
+            WITH due AS (
+                SELECT COALESCE(user_id::TEXT, session_id, id::TEXT) AS subject
+                FROM behavior_inputs
+                WHERE processed = false
+                  AND (scoring_lease_until IS NULL OR scoring_lease_until < NOW())
+                GROUP BY 1
+                HAVING MAX(timestamp) <= NOW() - make_interval(secs => $1)
+                    OR MIN(timestamp) <= NOW() - make_interval(secs => $2)
+                ORDER BY MIN(timestamp)
+                LIMIT $4
+            )
+            UPDATE behavior_inputs b
+            SET scoring_lease_until = NOW() + make_interval(secs => $3)
+            FROM due
+            WHERE COALESCE(b.user_id::TEXT, b.session_id, b.id::TEXT) = due.subject
+              AND b.processed = false
+              AND (b.scoring_lease_until IS NULL OR b.scoring_lease_until < NOW())
+            RETURNING due.subject, b.id, b.session_id, b.input_data::TEXT AS input_data, b.timestamp,
+                      b.processed, b.input_type, b.sample_weight, b.client_event_id
+            "#,
+        )
+        .bind(debounce.as_secs_f64())
+        .bind(max_wait.as_secs_f64())
+        .bind(lease.as_secs_f64())
+        .bind(limit)
+        .fetch_all(self.app_state.mm().dbx().db())
+        .await?;
+
+        Ok(records)
+    }
+
+    async fn mark_inputs_processed(&self, ids: &[Id]) -> Result<u64> {
+        let ids: Vec<uuid::Uuid> = ids.iter().map(Id::to_uuid).collect();
+        let result = sqlx::query(
+            "UPDATE behavior_inputs SET processed = true, scoring_lease_until = NULL \
+             WHERE id = ANY($1)",
+        )
+        .bind(ids)
+        .execute(self.app_state.mm().dbx().db())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn upsert_aggregate(
+        &self,
+        aggregate: BehaviorAggregateForUpsert,
+    ) -> Result<BehaviorAggregateResponse> {
+        // Sums are merged key by key so buckets can pick up fields that only
+        // appear in later events.
+        let record = sqlx::query_as::<_, BehaviorAggregateRecord>(
+            "INSERT INTO behavior_aggregates \
+             (session_id, input_type, bucket_start, window_secs, event_count, sums) \
+             VALUES ($1, $2, $3, $4, 1, $5) \
+             ON CONFLICT (session_id, input_type, bucket_start, window_secs) DO UPDATE SET \
+               event_count = behavior_aggregates.event_count + 1, \
+               sums = (SELECT COALESCE(jsonb_object_agg(k, \
+                         COALESCE((behavior_aggregates.sums->>k)::float8, 0) \
+                         + COALESCE((EXCLUDED.sums->>k)::float8, 0)), '{}'::jsonb) \
+                       FROM jsonb_object_keys(behavior_aggregates.sums || EXCLUDED.sums) AS k), \
+               last_seen = NOW() \
+             RETURNING *",
+        )
+        .bind(aggregate.session_id.unwrap_or_default())
+        .bind(&aggregate.input_type)
+        .bind(aggregate.bucket_start)
+        .bind(aggregate.window_secs)
+        .bind(serde_json::Value::Object(aggregate.sums))
+        .fetch_one(self.app_state.mm().dbx().db())
+        .await?;
+
+        Ok(BehaviorAggregateResponse::from(record))
+    }
+
+    async fn list_aggregates(
+        &self,
+        session_id: Option<String>,
+    ) -> Result<Vec<BehaviorAggregateResponse>> {
+        let records = sqlx::query_as::<_, BehaviorAggregateRecord>(
+            "SELECT * FROM behavior_aggregates \
+             WHERE ($1::varchar IS NULL OR session_id = $1) \
+             ORDER BY bucket_start DESC",
+        )
+        .bind(session_id)
+        .fetch_all(self.app_state.mm().dbx().db())
+        .await?;
+
+        Ok(records.into_iter().map(BehaviorAggregateResponse::from).collect())
+    }
+
+    async fn list_event_schemas(&self, input_types: Option<Vec<String>>) -> Result<Vec<EventSchema>> {
+        let schemas = sqlx::query_as::<_, EventSchema>(
+            "SELECT * FROM behavior_event_schemas \
+             WHERE ($1::varchar[] IS NULL OR input_type = ANY($1)) \
+             ORDER BY input_type",
+        )
+        .bind(input_types)
+        .fetch_all(self.app_state.mm().dbx().db())
+        .await?;
+
+        Ok(schemas)
+    }
+
+    async fn upsert_event_schema(
+        &self,
+        input_type: &str,
+        schema: Value,
+        updated_by: &str,
+    ) -> Result<EventSchema> {
+        let schema = sqlx::query_as::<_, EventSchema>(
+            "INSERT INTO behavior_event_schemas (input_type, schema, updated_by) \
+             VALUES ($1, $2, $3) \
+             ON CONFLICT (input_type) DO UPDATE SET \
+               schema = EXCLUDED.schema, \
+               version = behavior_event_schemas.version + 1, \
+               updated_by = EXCLUDED.updated_by, \
+               updated_at = NOW() \
+             RETURNING *",
+        )
+        .bind(input_type)
+        .bind(schema)
+        .bind(updated_by)
+        .fetch_one(self.app_state.mm().dbx().db())
+        .await?;
+
+        Ok(schema)
+    }
+}
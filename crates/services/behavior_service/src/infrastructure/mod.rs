@@ -1 +1,2 @@
-pub mod behavior_repository_impl;
\ No newline at end of file
+pub mod behavior_repository_impl;
+pub mod redis_stream_source;
\ No newline at end of file
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use jd_core::AppState;
+use redis::aio::MultiplexedConnection;
+
+use crate::domain::behavior_source::{BehaviorSource, SourcedEvent};
+use crate::{Error, Result};
+
+const DEFAULT_STREAM_KEY: &str = "behavior:events";
+const DEFAULT_STREAM_GROUP: &str = "behavior_ingest";
+/// Field of a stream entry holding the event JSON.
+const EVENT_FIELD: &str = "event";
+
+/// `XREADGROUP` reply: per stream, its entries as id and flat field/value list.
+/// Entries deleted while pending come back without fields.
+type StreamReply = Option<Vec<(String, Vec<(String, Option<Vec<String>>)>)>>;
+
+/// Reads behavior events from a Redis Stream through a consumer group.
+/// Producers add entries with the event JSON in an `event` field; entries
+/// that cannot be ingested are copied to `{stream}:dead` before being
+/// acknowledged.
+pub struct RedisStreamSource {
+    connection: MultiplexedConnection,
+    stream_key: String,
+    group: String,
+    consumer: String,
+    /// Whether this consumer has pending entries left to re-read: true at
+    /// start, in case an earlier run stopped before acknowledging.
+    draining_pending: bool,
+    /// Whether the last entries received have not been acknowledged yet, so
+    /// they are pending again on the next receive.
+    awaiting_ack: bool,
+}
+
+impl RedisStreamSource {
+    /// The configured stream source, or `None` when stream ingestion is off.
+    pub async fn from_state(app_state: &AppState) -> Result<Option<Self>> {
+        let Some(config) = app_state.config.behavior_analysis.as_ref() else {
+            return Ok(None);
+        };
+        match config.stream_source.as_deref() {
+            None => Ok(None),
+            Some("redis") => {
+                let consumer = config
+                    .stream_consumer
+                    .clone()
+                    .or_else(|| std::env::var("HOSTNAME").ok())
+                    .unwrap_or_else(|| "behavior-ingest".to_string());
+                let source = Self::connect(
+                    app_state.redis.as_ref(),
+                    config.stream_key.as_deref().unwrap_or(DEFAULT_STREAM_KEY),
+                    config.stream_group.as_deref().unwrap_or(DEFAULT_STREAM_GROUP),
+                    &consumer,
+                )
+                .await?;
+                Ok(Some(source))
+            }
+            Some(other) => {
+                Err(Error::InvalidInput(format!("Unsupported behavior stream source: {}", other)))
+            }
+        }
+    }
+
+    /// Connect and create the consumer group if it does not exist yet. A new
+    /// group starts from the beginning of the stream so events sent before
+    /// the first consumer started are not skipped.
+    pub async fn connect(
+        client: &redis::Client,
+        stream_key: &str,
+        group: &str,
+        consumer: &str,
+    ) -> Result<Self> {
+        let mut connection = client.get_multiplexed_async_connection().await?;
+        redis::cmd("XGROUP")
+            .arg("CREATE")
+            .arg(stream_key)
+            .arg(group)
+            .arg("0")
+            .arg("MKSTREAM")
+            .query_async::<()>(&mut connection)
+            .await
+            .or_else(|e| if e.code() == Some("BUSYGROUP") { Ok(()) } else { Err(e) })?;
+
+        Ok(Self {
+            connection,
+            stream_key: stream_key.to_string(),
+            group: group.to_string(),
+            consumer: consumer.to_string(),
+            draining_pending: true,
+            awaiting_ack: false,
+        })
+    }
+
+    async fn read(&mut self, from: &str, max: usize, block: Option<Duration>) -> Result<Vec<SourcedEvent>> {
+        let mut cmd = redis::cmd("XREADGROUP");
+        cmd.arg("GROUP").arg(&self.group).arg(&self.consumer).arg("COUNT").arg(max);
+        if let Some(block) = block {
+            cmd.arg("BLOCK").arg(block.as_millis() as u64);
+        }
+        cmd.arg("STREAMS").arg(&self.stream_key).arg(from);
+
+        let reply: StreamReply = cmd.query_async(&mut self.connection).await?;
+        let events = reply
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|(_, entries)| entries)
+            .map(|(delivery_id, fields)| {
+                let payload = fields
+                    .unwrap_or_default()
+                    .chunks(2)
+                    .find(|pair| pair.len() == 2 && pair[0] == EVENT_FIELD)
+                    .map(|pair| pair[1].clone())
+                    .unwrap_or_default();
+                SourcedEvent { delivery_id, payload }
+            })
+            .collect();
+        Ok(events)
+    }
+}
+
+#[async_trait]
+impl BehaviorSource for RedisStreamSource {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    async fn receive(&mut self, max: usize, block: Duration) -> Result<Vec<SourcedEvent>> {
+        if self.draining_pending || self.awaiting_ack {
+            // "0" re-reads this consumer's delivered but unacknowledged entries
+            let pending = self.read("0", max, None).await?;
+            if !pending.is_empty() {
+                self.draining_pending = true;
+                self.awaiting_ack = true;
+                return Ok(pending);
+            }
+            self.draining_pending = false;
+        }
+        let events = self.read(">", max, Some(block)).await?;
+        self.awaiting_ack = !events.is_empty();
+        Ok(events)
+    }
+
+    async fn acknowledge(&mut self, delivery_ids: &[String]) -> Result<()> {
+        if delivery_ids.is_empty() {
+            return Ok(());
+        }
+        let _: i64 = redis::cmd("XACK")
+            .arg(&self.stream_key)
+            .arg(&self.group)
+            .arg(delivery_ids)
+            .query_async(&mut self.connection)
+            .await?;
+        self.awaiting_ack = false;
+        Ok(())
+    }
+
+    async fn dead_letter(&mut self, event: &SourcedEvent, reason: &str) -> Result<()> {
+        let _: String = redis::cmd("XADD")
+            .arg(format!("{}:dead", self.stream_key))
+            .arg("*")
+            .arg("delivery_id")
+            .arg(&event.delivery_id)
+            .arg("reason")
+            .arg(reason)
+            .arg(EVENT_FIELD)
+            .arg(&event.payload)
+            .query_async(&mut self.connection)
+            .await?;
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 use jd_core::base::DMC;
 use application::handlers::behavior_handler::BehaviorHandler;
+use domain::ingestion_tier::IngestionTiers;
 use infrastructure::behavior_repository_impl::BehaviorRepositoryImpl;
 use jd_core::AppState;
 
@@ -19,8 +20,9 @@ pub struct BehaviorService {
 
 impl BehaviorService {
     pub async fn new(state: AppState) -> Self {
+        let tiers = IngestionTiers::from_config(state.config.behavior_analysis.as_ref());
         let repository = BehaviorRepositoryImpl::new(state);
-        let handler = BehaviorHandler::new(repository);
+        let handler = BehaviorHandler::new(repository, tiers);
         
         Self { handler }
     }
@@ -16,6 +16,9 @@ pub struct BehaviorInputRecord {
     pub input_data: String, // JSON stored as string
     pub timestamp: OffsetDateTime,
     pub processed: bool,
+    pub input_type: String,
+    pub sample_weight: f64,
+    pub client_event_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Fields)]
@@ -23,6 +26,9 @@ pub struct BehaviorInputForCreate {
     pub session_id: Option<String>,
     pub input_data: String, // JSON stored as string
     pub processed: Option<bool>,
+    pub input_type: Option<String>,
+    pub sample_weight: Option<f64>,
+    pub client_event_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Fields)]
@@ -36,15 +42,68 @@ pub struct BehaviorInputFilter {
     pub processed: Option<OpValsValue>,
 }
 
+/// An unprocessed input claimed for scoring, with the subject it is scored
+/// for: its user, else its session, else the input alone.
+#[derive(Debug, Clone, FromRow)]
+pub struct UnscoredInputRecord {
+    pub subject: String,
+    #[sqlx(flatten)]
+    pub input: BehaviorInputRecord,
+}
+
+// Pre-aggregated events (behavior_aggregates table)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BehaviorAggregateRecord {
+    pub id: uuid::Uuid,
+    pub session_id: String,
+    pub input_type: String,
+    pub bucket_start: OffsetDateTime,
+    pub window_secs: i32,
+    pub event_count: i64,
+    pub sums: serde_json::Value,
+    pub first_seen: OffsetDateTime,
+    pub last_seen: OffsetDateTime,
+    pub processed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct BehaviorAggregateForUpsert {
+    pub session_id: Option<String>,
+    pub input_type: String,
+    pub bucket_start: OffsetDateTime,
+    pub window_secs: i32,
+    pub sums: serde_json::Map<String, serde_json::Value>,
+}
+
 // Conversion implementations
 impl From<BehaviorInputRecord> for responses::BehaviorInputResponse {
     fn from(record: BehaviorInputRecord) -> Self {
         Self {
-            id: Id::new(record.id.to_string()),
+            id: Id::from(record.id),
             session_id: record.session_id,
             input_data: serde_json::from_str(&record.input_data).unwrap_or_default(),
             timestamp: record.timestamp,
             processed: record.processed,
+            input_type: record.input_type,
+            sample_weight: record.sample_weight,
+            client_event_id: record.client_event_id,
+        }
+    }
+}
+
+impl From<BehaviorAggregateRecord> for responses::BehaviorAggregateResponse {
+    fn from(record: BehaviorAggregateRecord) -> Self {
+        Self {
+            id: Id::from(record.id),
+            session_id: Some(record.session_id).filter(|s| !s.is_empty()),
+            input_type: record.input_type,
+            bucket_start: record.bucket_start,
+            window_secs: record.window_secs,
+            event_count: record.event_count,
+            sums: record.sums,
+            first_seen: record.first_seen,
+            last_seen: record.last_seen,
+            processed: record.processed,
         }
     }
 }
\ No newline at end of file
@@ -5,10 +5,41 @@ use validator::Validate;
 pub struct BehaviorInputRequest {
     #[validate(length(min = 1, max = 100))]
     pub session_id: Option<String>,
-    
+
+    /// Event type, used to pick the ingestion tier. Defaults to `general`.
+    #[validate(length(min = 1, max = 50))]
+    pub input_type: Option<String>,
+
+    pub input_data: serde_json::Value,
+}
+
+/// One event of a batch. `client_event_id` must be unique across every event
+/// the client sends, so retrying a batch does not store its events twice.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct BehaviorEventRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub client_event_id: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub session_id: Option<String>,
+
+    /// Event type, used to pick the schema and ingestion tier. Defaults to `general`.
+    #[validate(length(min = 1, max = 50))]
+    pub input_type: Option<String>,
+
     pub input_data: serde_json::Value,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BehaviorBatchRequest {
+    pub events: Vec<BehaviorEventRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSchemaRequest {
+    pub schema: serde_json::Value,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BehaviorQueryRequest {
     pub session_id: Option<String>,
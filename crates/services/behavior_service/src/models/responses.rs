@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 use time::OffsetDateTime;
 use jd_domain::Id;
 
+use crate::domain::ingestion_tier::INGESTION_META_KEY;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BehaviorInputResponse {
     pub id: Id,
@@ -9,6 +12,30 @@ pub struct BehaviorInputResponse {
     pub input_data: serde_json::Value,
     pub timestamp: OffsetDateTime,
     pub processed: bool,
+    pub input_type: String,
+    /// Number of events this row stands for; above 1 when its type is sampled.
+    pub sample_weight: f64,
+    /// Id the client gave the event, when it came in a batch.
+    pub client_event_id: Option<String>,
+}
+
+impl BehaviorInputResponse {
+    /// Payload handed to the scoring engine. Sampled rows carry their weight
+    /// under the ingestion metadata key; raw rows are passed through unchanged.
+    pub fn to_behavior_data(&self) -> Value {
+        if self.sample_weight == 1.0 {
+            return self.input_data.clone();
+        }
+
+        json!({
+            INGESTION_META_KEY: {
+                "tier": "sampled",
+                "input_type": self.input_type,
+                "weight": self.sample_weight,
+            },
+            "event": self.input_data,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,4 +44,87 @@ pub struct BehaviorListResponse {
     pub total: u64,
     pub limit: u32,
     pub offset: u32,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BehaviorAggregateResponse {
+    pub id: Id,
+    pub session_id: Option<String>,
+    pub input_type: String,
+    pub bucket_start: OffsetDateTime,
+    pub window_secs: i32,
+    pub event_count: i64,
+    pub sums: serde_json::Value,
+    pub first_seen: OffsetDateTime,
+    pub last_seen: OffsetDateTime,
+    pub processed: bool,
+}
+
+impl BehaviorAggregateResponse {
+    /// Payload handed to the scoring engine: the window's event count and sums
+    /// under the ingestion metadata key, so it can be scored like raw events.
+    pub fn to_behavior_data(&self) -> Value {
+        json!({
+            INGESTION_META_KEY: {
+                "tier": "aggregated",
+                "input_type": self.input_type,
+                "weight": self.event_count,
+                "window_secs": self.window_secs,
+            },
+            "sums": self.sums,
+        })
+    }
+}
+
+/// Result of ingesting one event: stored raw, stored as a sample, dropped by
+/// sampling, or folded into an aggregate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestionResponse {
+    pub input_type: String,
+    pub tier: String,
+    pub stored: bool,
+    pub input: Option<BehaviorInputResponse>,
+    pub aggregate: Option<BehaviorAggregateResponse>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchEventStatus {
+    /// Ingested with its type's tier; it may still have been sampled out.
+    Accepted,
+    /// Its client event id was already ingested, earlier in the batch or before.
+    Duplicate,
+    /// It failed validation, or its type has no registered schema.
+    Rejected,
+}
+
+/// Outcome for one event of a batch, in request order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchEventResult {
+    pub client_event_id: String,
+    pub status: BatchEventStatus,
+    pub input_type: String,
+    pub tier: Option<String>,
+    pub stored: bool,
+    /// The `behavior_inputs` row it was stored as, for raw and sampled tiers.
+    pub input_id: Option<Id>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchIngestionResponse {
+    pub accepted: usize,
+    pub duplicates: usize,
+    pub rejected: usize,
+    pub results: Vec<BatchEventResult>,
+}
+
+/// A subject whose new inputs are due for scoring. Only the latest input is
+/// scored; the earlier ones arrived within the same debounce window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnscoredSubject {
+    pub subject: String,
+    pub latest: BehaviorInputResponse,
+    /// Every claimed input of the subject, the latest included.
+    pub input_ids: Vec<Id>,
+}
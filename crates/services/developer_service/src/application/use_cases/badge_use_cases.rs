@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::{
+    earned_badges, BadgeAward, BadgeDefinition, BadgeKind, BadgeMinter, BadgeRepository, MintOutcome, MintStatus,
+    ReputationRepository,
+};
+use crate::{Error, Result};
+
+/// Milestone badges. Award rules are evaluated against every developer's
+/// all-time contributions after each reputation recompute; a developer may
+/// then claim a badge, and have it minted to one of their Sui wallets when
+/// a minter is configured.
+pub struct BadgeUseCases {
+    badges: Arc<dyn BadgeRepository>,
+    reputation: Arc<dyn ReputationRepository>,
+    minter: Option<Arc<dyn BadgeMinter>>,
+}
+
+impl BadgeUseCases {
+    pub fn new(badges: Arc<dyn BadgeRepository>, reputation: Arc<dyn ReputationRepository>) -> Self {
+        Self { badges, reputation, minter: None }
+    }
+
+    pub fn with_minter(mut self, minter: Arc<dyn BadgeMinter>) -> Self {
+        self.minter = Some(minter);
+        self
+    }
+
+    pub fn definitions() -> Vec<BadgeDefinition> {
+        BadgeKind::ALL.iter().map(|badge| badge.definition()).collect()
+    }
+
+    /// Award every badge developers have earned and not been awarded yet.
+    /// Returns how many were awarded.
+    pub async fn award_earned(&self) -> Result<u64> {
+        let mut awarded = 0;
+        for contributions in self.reputation.load_inputs(None, "").await? {
+            let earned = earned_badges(&contributions);
+            if !earned.is_empty() {
+                awarded += self.badges.award(contributions.developer_id, &earned).await?;
+            }
+        }
+        if awarded > 0 {
+            info!(awarded, "Developer badges awarded");
+        }
+        Ok(awarded)
+    }
+
+    pub async fn awards(&self, developer_id: Uuid) -> Result<Vec<BadgeAward>> {
+        self.badges.awards(developer_id).await
+    }
+
+    /// Claim a badge awarded to the developer `subject` belongs to, minting
+    /// it to `mint_to` if set. A mint interrupted earlier is resubmitted
+    /// rather than prepared again.
+    pub async fn claim(&self, subject: &str, badge: BadgeKind, mint_to: Option<&str>) -> Result<BadgeAward> {
+        let developer_id = self
+            .badges
+            .developer_for_subject(subject)
+            .await?
+            .ok_or_else(|| Error::DeveloperNotFound(format!("No developer linked to {}", subject)))?;
+        let award = self
+            .badges
+            .claim(developer_id, badge)
+            .await?
+            .ok_or_else(|| Error::BadgeNotAwarded(badge.as_str().to_string()))?;
+        let Some(recipient) = mint_to else {
+            return Ok(award);
+        };
+        let minter = self.minter.as_ref().ok_or(Error::BadgeMintUnavailable)?;
+
+        let prepared = match award.mint_status {
+            Some(MintStatus::Minted) => return Err(Error::BadgeAlreadyMinted(badge.as_str().to_string())),
+            Some(MintStatus::Pending) => self
+                .badges
+                .pending_mint(developer_id, badge)
+                .await?
+                .ok_or_else(|| Error::BadgeAlreadyMinted(badge.as_str().to_string()))?,
+            None | Some(MintStatus::Failed) => {
+                if !self.badges.is_linked_sui_wallet(developer_id, recipient).await? {
+                    return Err(Error::InvalidVerification(format!(
+                        "{} is not a Sui wallet linked to the developer",
+                        recipient
+                    )));
+                }
+                let prepared = minter.prepare(badge, recipient).await?;
+                if !self.badges.start_mint(developer_id, badge, recipient, &prepared).await? {
+                    // Another claim started minting in between
+                    return Err(Error::BadgeAlreadyMinted(badge.as_str().to_string()));
+                }
+                prepared
+            }
+        };
+
+        // Left pending on error, to be resubmitted by the next claim
+        let outcome = minter.submit(&prepared).await?;
+        if let MintOutcome::Failed { reason, .. } = &outcome {
+            warn!(developer_id = %developer_id, badge = badge.as_str(), reason = %reason, "Badge mint failed");
+        }
+        self.badges.finish_mint(developer_id, badge, &outcome).await?;
+        self.badges
+            .claim(developer_id, badge)
+            .await?
+            .ok_or_else(|| Error::BadgeNotAwarded(badge.as_str().to_string()))
+    }
+}
@@ -1,3 +1,7 @@
+pub mod badge_use_cases;
 pub mod developer_use_cases;
+pub mod reputation_use_cases;
 
-pub use developer_use_cases::DeveloperUseCases;
\ No newline at end of file
+pub use badge_use_cases::BadgeUseCases;
+pub use developer_use_cases::DeveloperUseCases;
+pub use reputation_use_cases::ReputationUseCases;
\ No newline at end of file
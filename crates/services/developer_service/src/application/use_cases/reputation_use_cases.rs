@@ -0,0 +1,81 @@
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::domain::{language_key, rank_reputations, ReputationRepository, ReputationWindow};
+use crate::models::ReputationLeaderboardResponse;
+use crate::{Error, Result};
+
+const MAX_LEADERBOARD_SIZE: i64 = 100;
+
+/// Outcome of one recompute.
+#[derive(Debug, Default)]
+pub struct ReputationRecomputeRun {
+    pub leaderboards: usize,
+    pub ranked: usize,
+    pub developers_updated: u64,
+}
+
+/// Developer reputation from merged patches, the vulnerabilities they
+/// resolved, analyses of the developer's repositories and on-chain
+/// attestations. A scheduled recompute ranks every window and language, and
+/// reads serve the last recomputed leaderboards.
+pub struct ReputationUseCases {
+    repository: Arc<dyn ReputationRepository>,
+}
+
+impl ReputationUseCases {
+    pub fn new(repository: Arc<dyn ReputationRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Recompute the leaderboard of every window, across all languages and
+    /// per language, then copy all-time scores to the developers.
+    pub async fn recompute(&self) -> Result<ReputationRecomputeRun> {
+        let now = Utc::now();
+        let mut languages = vec![String::new()];
+        languages.extend(self.repository.list_languages().await?);
+
+        let mut run = ReputationRecomputeRun::default();
+        for window in ReputationWindow::ALL {
+            for language in &languages {
+                let inputs = self.repository.load_inputs(window.since(now), language).await?;
+                let ranked = rank_reputations(inputs);
+                self.repository.replace_leaderboard(window, language, &ranked, now).await?;
+                run.leaderboards += 1;
+                run.ranked += ranked.len();
+            }
+        }
+        run.developers_updated = self.repository.sync_developer_scores().await?;
+        info!(
+            leaderboards = run.leaderboards,
+            developers_updated = run.developers_updated,
+            "Developer reputation recomputed"
+        );
+        Ok(run)
+    }
+
+    /// The top `limit` developers of a window, for one repository language
+    /// or all of them.
+    pub async fn leaderboard(
+        &self,
+        window: ReputationWindow,
+        language: Option<&str>,
+        limit: i64,
+    ) -> Result<ReputationLeaderboardResponse> {
+        if !(1..=MAX_LEADERBOARD_SIZE).contains(&limit) {
+            return Err(Error::InvalidFilter(format!(
+                "limit must be between 1 and {}",
+                MAX_LEADERBOARD_SIZE
+            )));
+        }
+        let language = language_key(language);
+        let leaderboard = self.repository.leaderboard(window, &language, limit).await?;
+        Ok(ReputationLeaderboardResponse {
+            window,
+            computed_at: leaderboard.first().map(|entry| entry.computed_at),
+            language: (!language.is_empty()).then_some(language),
+            leaderboard,
+        })
+    }
+}
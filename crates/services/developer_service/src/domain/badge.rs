@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::reputation::ReputationInputs;
+
+/// Achievements awarded when a developer's all-time contributions cross a
+/// milestone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum BadgeKind {
+    FirstMergedPatch,
+    TenMergedPatches,
+    CriticalResolver,
+    VulnerabilityHunter,
+    FirstAnalysis,
+    OnChainAttested,
+}
+
+/// What a badge is called and how it is earned.
+#[derive(Debug, Clone, Serialize)]
+pub struct BadgeDefinition {
+    pub badge: BadgeKind,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+impl BadgeKind {
+    pub const ALL: [BadgeKind; 6] = [
+        Self::FirstMergedPatch,
+        Self::TenMergedPatches,
+        Self::CriticalResolver,
+        Self::VulnerabilityHunter,
+        Self::FirstAnalysis,
+        Self::OnChainAttested,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FirstMergedPatch => "first_merged_patch",
+            Self::TenMergedPatches => "ten_merged_patches",
+            Self::CriticalResolver => "critical_resolver",
+            Self::VulnerabilityHunter => "vulnerability_hunter",
+            Self::FirstAnalysis => "first_analysis",
+            Self::OnChainAttested => "on_chain_attested",
+        }
+    }
+
+    pub fn definition(&self) -> BadgeDefinition {
+        let (name, description) = match self {
+            Self::FirstMergedPatch => ("First Patch", "Had a first patch merged"),
+            Self::TenMergedPatches => ("Patch Veteran", "Had 10 patches merged"),
+            Self::CriticalResolver => ("Critical Resolver", "Resolved a critical vulnerability with a merged patch"),
+            Self::VulnerabilityHunter => ("Vulnerability Hunter", "Resolved 25 vulnerabilities with merged patches"),
+            Self::FirstAnalysis => ("Under Watch", "Had a repository they own analyzed"),
+            Self::OnChainAttested => ("On-Chain Attested", "Had a proof attested on-chain"),
+        };
+        BadgeDefinition { badge: *self, name, description }
+    }
+
+    /// Whether all-time `contributions` earn the badge.
+    pub fn earned_by(&self, contributions: &ReputationInputs) -> bool {
+        match self {
+            Self::FirstMergedPatch => contributions.merged_patches >= 1,
+            Self::TenMergedPatches => contributions.merged_patches >= 10,
+            Self::CriticalResolver => contributions.resolved_critical >= 1,
+            Self::VulnerabilityHunter => contributions.resolved_vulnerabilities() >= 25,
+            Self::FirstAnalysis => contributions.analysis_contributions >= 1,
+            Self::OnChainAttested => contributions.attestations >= 1,
+        }
+    }
+}
+
+/// Badges all-time `contributions` earn.
+pub fn earned_badges(contributions: &ReputationInputs) -> Vec<BadgeKind> {
+    BadgeKind::ALL
+        .into_iter()
+        .filter(|badge| badge.earned_by(contributions))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum MintStatus {
+    /// Signed and submitted, outcome not recorded yet.
+    Pending,
+    Minted,
+    Failed,
+}
+
+/// A badge awarded to a developer, and its on-chain claim if any.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BadgeAward {
+    pub developer_id: Uuid,
+    pub badge: BadgeKind,
+    pub awarded_at: DateTime<Utc>,
+    pub claimed_at: Option<DateTime<Utc>>,
+    pub mint_status: Option<MintStatus>,
+    pub mint_recipient: Option<String>,
+    pub mint_digest: Option<String>,
+    pub mint_error: Option<String>,
+    pub minted_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn milestones_award_badges() {
+        let contributions = ReputationInputs {
+            merged_patches: 10,
+            resolved_high: 3,
+            ..ReputationInputs::default()
+        };
+
+        assert_eq!(
+            earned_badges(&contributions),
+            vec![BadgeKind::FirstMergedPatch, BadgeKind::TenMergedPatches]
+        );
+        assert!(earned_badges(&ReputationInputs::default()).is_empty());
+    }
+}
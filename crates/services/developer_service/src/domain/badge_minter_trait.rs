@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+
+use super::badge::BadgeKind;
+use crate::Result;
+
+/// A signed mint transaction and its digest.
+#[derive(Debug, Clone)]
+pub struct PreparedMint {
+    pub digest: String,
+    pub signed_tx: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub enum MintOutcome {
+    Minted { digest: String },
+    /// The transaction landed but aborted.
+    Failed { digest: String, reason: String },
+}
+
+/// Mints badges as on-chain objects owned by the recipient. Prepared mints
+/// are stored before they are submitted, so submitting one again must not
+/// mint twice.
+#[async_trait]
+pub trait BadgeMinter: Send + Sync {
+    async fn prepare(&self, badge: BadgeKind, recipient: &str) -> Result<PreparedMint>;
+
+    async fn submit(&self, prepared: &PreparedMint) -> Result<MintOutcome>;
+}
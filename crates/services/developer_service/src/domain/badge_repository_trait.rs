@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::badge::{BadgeAward, BadgeKind};
+use super::badge_minter_trait::{MintOutcome, PreparedMint};
+use crate::Result;
+
+#[async_trait]
+pub trait BadgeRepository: Send + Sync {
+    /// The developer a token subject belongs to: the GitHub user id of a
+    /// GitHub login, or a wallet linked to the developer's GitHub account.
+    async fn developer_for_subject(&self, subject: &str) -> Result<Option<Uuid>>;
+
+    /// Whether `address` is a Sui wallet linked to the developer's GitHub
+    /// account.
+    async fn is_linked_sui_wallet(&self, developer_id: Uuid, address: &str) -> Result<bool>;
+
+    /// Award `badges` the developer does not hold yet. Returns how many
+    /// were new.
+    async fn award(&self, developer_id: Uuid, badges: &[BadgeKind]) -> Result<u64>;
+
+    /// The developer's badges, oldest first.
+    async fn awards(&self, developer_id: Uuid) -> Result<Vec<BadgeAward>>;
+
+    /// Mark an awarded badge claimed, keeping the first claim time.
+    async fn claim(&self, developer_id: Uuid, badge: BadgeKind) -> Result<Option<BadgeAward>>;
+
+    /// The stored mint of a badge still pending.
+    async fn pending_mint(&self, developer_id: Uuid, badge: BadgeKind) -> Result<Option<PreparedMint>>;
+
+    /// Store `prepared` as the badge's pending mint, unless it is already
+    /// pending or minted. Returns whether it was stored.
+    async fn start_mint(
+        &self,
+        developer_id: Uuid,
+        badge: BadgeKind,
+        recipient: &str,
+        prepared: &PreparedMint,
+    ) -> Result<bool>;
+
+    /// Record how the pending mint ended and drop its transaction.
+    async fn finish_mint(&self, developer_id: Uuid, badge: BadgeKind, outcome: &MintOutcome) -> Result<()>;
+}
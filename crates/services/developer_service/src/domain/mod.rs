@@ -1,5 +1,15 @@
+pub mod badge;
+pub mod badge_minter_trait;
+pub mod badge_repository_trait;
 pub mod developer_models;
 pub mod developer_repository_trait;
+pub mod reputation;
+pub mod reputation_repository_trait;
 
+pub use badge::*;
+pub use badge_minter_trait::*;
+pub use badge_repository_trait::*;
 pub use developer_models::*;
-pub use developer_repository_trait::*;
\ No newline at end of file
+pub use developer_repository_trait::*;
+pub use reputation::*;
+pub use reputation_repository_trait::*;
\ No newline at end of file
@@ -0,0 +1,190 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Points each contribution earns towards the reputation score.
+const MERGED_PATCH_POINTS: f64 = 10.0;
+const RESOLVED_CRITICAL_POINTS: f64 = 25.0;
+const RESOLVED_HIGH_POINTS: f64 = 15.0;
+const RESOLVED_MEDIUM_POINTS: f64 = 6.0;
+const RESOLVED_LOW_POINTS: f64 = 2.0;
+const ANALYSIS_POINTS: f64 = 2.0;
+const ATTESTATION_POINTS: f64 = 8.0;
+
+/// Points at which a score reaches about 63. Scores approach 100 with
+/// diminishing returns, so a long history cannot be outrun by volume alone.
+const SCORE_SCALE: f64 = 250.0;
+
+/// Contributions counted towards a leaderboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar")]
+pub enum ReputationWindow {
+    #[serde(rename = "all_time")]
+    #[sqlx(rename = "all_time")]
+    AllTime,
+    #[serde(rename = "90d")]
+    #[sqlx(rename = "90d")]
+    Last90Days,
+    #[serde(rename = "30d")]
+    #[sqlx(rename = "30d")]
+    Last30Days,
+}
+
+impl ReputationWindow {
+    pub const ALL: [ReputationWindow; 3] = [Self::AllTime, Self::Last90Days, Self::Last30Days];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AllTime => "all_time",
+            Self::Last90Days => "90d",
+            Self::Last30Days => "30d",
+        }
+    }
+
+    /// Start of the window ending at `now`; `None` for all time.
+    pub fn since(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::AllTime => None,
+            Self::Last90Days => Some(now - Duration::days(90)),
+            Self::Last30Days => Some(now - Duration::days(30)),
+        }
+    }
+}
+
+/// Leaderboard key of a repository language: trimmed and lowercased, with
+/// the empty string standing for all languages.
+pub fn language_key(language: Option<&str>) -> String {
+    language.map(|language| language.trim().to_lowercase()).unwrap_or_default()
+}
+
+/// A developer's contributions within a window. Resolved vulnerabilities
+/// are those fixed by the developer's merged patches; analysis
+/// contributions are analyses of repositories the developer owns.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReputationInputs {
+    pub developer_id: Uuid,
+    pub github_username: String,
+    pub merged_patches: i64,
+    pub resolved_critical: i64,
+    pub resolved_high: i64,
+    pub resolved_medium: i64,
+    pub resolved_low: i64,
+    pub analysis_contributions: i64,
+    /// On-chain attestations of proofs for the developer's linked wallets.
+    pub attestations: i64,
+}
+
+impl ReputationInputs {
+    pub fn resolved_vulnerabilities(&self) -> i64 {
+        self.resolved_critical + self.resolved_high + self.resolved_medium + self.resolved_low
+    }
+
+    pub fn points(&self) -> f64 {
+        self.merged_patches as f64 * MERGED_PATCH_POINTS
+            + self.resolved_critical as f64 * RESOLVED_CRITICAL_POINTS
+            + self.resolved_high as f64 * RESOLVED_HIGH_POINTS
+            + self.resolved_medium as f64 * RESOLVED_MEDIUM_POINTS
+            + self.resolved_low as f64 * RESOLVED_LOW_POINTS
+            + self.analysis_contributions as f64 * ANALYSIS_POINTS
+            + self.attestations as f64 * ATTESTATION_POINTS
+    }
+
+    /// Reputation score from 0 to 100, to one decimal.
+    pub fn score(&self) -> f64 {
+        let score = 100.0 * (1.0 - (-self.points() / SCORE_SCALE).exp());
+        (score * 10.0).round() / 10.0
+    }
+}
+
+/// A developer's place on one leaderboard.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeveloperReputation {
+    pub developer_id: Uuid,
+    pub score: f64,
+    pub rank: i32,
+    pub merged_patches: i64,
+    pub resolved_vulnerabilities: i64,
+    pub analysis_contributions: i64,
+    pub attestations: i64,
+}
+
+/// Rank developers by score, leaving out those without any points. Ties
+/// go to more merged patches, then alphabetically by GitHub username.
+pub fn rank_reputations(inputs: Vec<ReputationInputs>) -> Vec<DeveloperReputation> {
+    let mut scored: Vec<(f64, ReputationInputs)> = inputs
+        .into_iter()
+        .filter(|inputs| inputs.points() > 0.0)
+        .map(|inputs| (inputs.score(), inputs))
+        .collect();
+    scored.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .total_cmp(a_score)
+            .then(b.merged_patches.cmp(&a.merged_patches))
+            .then_with(|| a.github_username.cmp(&b.github_username))
+    });
+    scored
+        .into_iter()
+        .enumerate()
+        .map(|(i, (score, inputs))| DeveloperReputation {
+            developer_id: inputs.developer_id,
+            score,
+            rank: i as i32 + 1,
+            merged_patches: inputs.merged_patches,
+            resolved_vulnerabilities: inputs.resolved_vulnerabilities(),
+            analysis_contributions: inputs.analysis_contributions,
+            attestations: inputs.attestations,
+        })
+        .collect()
+}
+
+/// A leaderboard row as last recomputed.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReputationLeaderboardEntry {
+    pub rank: i32,
+    pub developer_id: Uuid,
+    pub github_username: String,
+    pub display_name: Option<String>,
+    pub score: f64,
+    pub merged_patches: i64,
+    pub resolved_vulnerabilities: i64,
+    pub analysis_contributions: i64,
+    pub attestations: i64,
+    pub computed_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(github_username: &str, merged_patches: i64, resolved_critical: i64) -> ReputationInputs {
+        ReputationInputs {
+            developer_id: Uuid::new_v4(),
+            github_username: github_username.to_string(),
+            merged_patches,
+            resolved_critical,
+            ..ReputationInputs::default()
+        }
+    }
+
+    #[test]
+    fn scores_saturate_below_one_hundred() {
+        assert_eq!(inputs("a", 0, 0).score(), 0.0);
+        // 1 - e^-1
+        assert_eq!(inputs("a", 25, 0).score(), 63.2);
+        assert!(inputs("a", 1_000, 100).score() <= 100.0);
+    }
+
+    #[test]
+    fn ranking_skips_idle_developers_and_breaks_ties() {
+        let (carol, bob, alice) = (inputs("carol", 1, 0), inputs("bob", 0, 1), inputs("alice", 1, 0));
+        let expected = vec![bob.developer_id, alice.developer_id, carol.developer_id];
+        let ranked = rank_reputations(vec![inputs("idle", 0, 0), carol, bob, alice]);
+
+        let order: Vec<Uuid> = ranked.iter().map(|r| r.developer_id).collect();
+        assert_eq!(order, expected);
+        assert_eq!(ranked.iter().map(|r| r.rank).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(ranked[0].resolved_vulnerabilities, 1);
+        assert_eq!(language_key(Some(" Rust ")), "rust");
+        assert_eq!(language_key(None), "");
+    }
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::reputation::{DeveloperReputation, ReputationInputs, ReputationLeaderboardEntry, ReputationWindow};
+use crate::Result;
+
+#[async_trait]
+pub trait ReputationRepository: Send + Sync {
+    /// Language keys of the repositories with merged patches.
+    async fn list_languages(&self) -> Result<Vec<String>>;
+
+    /// Contributions of every developer with any since `since`, counting
+    /// only repositories of `language` unless it is empty. Attestations are
+    /// not tied to a repository and count for every language.
+    async fn load_inputs(&self, since: Option<DateTime<Utc>>, language: &str) -> Result<Vec<ReputationInputs>>;
+
+    /// Replace the leaderboard of `window` and `language` with `ranked`.
+    async fn replace_leaderboard(
+        &self,
+        window: ReputationWindow,
+        language: &str,
+        ranked: &[DeveloperReputation],
+        computed_at: DateTime<Utc>,
+    ) -> Result<()>;
+
+    /// Copy all-time scores to the developers' coding reputation. Returns
+    /// how many developers changed.
+    async fn sync_developer_scores(&self) -> Result<u64>;
+
+    /// The top `limit` rows of a leaderboard, best first.
+    async fn leaderboard(
+        &self,
+        window: ReputationWindow,
+        language: &str,
+        limit: i64,
+    ) -> Result<Vec<ReputationLeaderboardEntry>>;
+}
@@ -5,17 +5,36 @@ use std::fmt;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "developer")]
 pub enum Error {
+    #[taxonomy(kind = NotFound, expose)]
     DeveloperNotFound(String),
+    #[taxonomy(kind = Conflict, expose)]
     DeveloperAlreadyExists(String),
+    #[taxonomy(kind = Validation, expose)]
     InvalidSkill(String),
+    #[taxonomy(kind = Validation, expose)]
     InvalidVerification(String),
+    #[taxonomy(kind = PermissionDenied, expose)]
     InsufficientReputation(String),
+    #[taxonomy(kind = Validation, expose)]
+    InvalidFilter(String),
+    #[taxonomy(kind = NotFound, expose)]
     CollaboratorNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
+    BadgeNotAwarded(String),
+    #[taxonomy(kind = Conflict, expose)]
+    BadgeAlreadyMinted(String),
+    #[taxonomy(kind = Unavailable, expose)]
+    BadgeMintUnavailable,
+    #[taxonomy(kind = Upstream)]
     NetworkError(String),
+    #[taxonomy(kind = Internal)]
     DatabaseError(String),
+    #[taxonomy(kind = Internal)]
     ServiceError(String),
+    #[taxonomy(kind = Unauthenticated)]
     CtxError(String),
 }
 
@@ -39,7 +58,11 @@ impl fmt::Display for Error {
             Error::InvalidSkill(skill) => write!(f, "Invalid skill: {}", skill),
             Error::InvalidVerification(msg) => write!(f, "Invalid verification: {}", msg),
             Error::InsufficientReputation(msg) => write!(f, "Insufficient reputation: {}", msg),
+            Error::InvalidFilter(msg) => write!(f, "Invalid filter: {}", msg),
             Error::CollaboratorNotFound(id) => write!(f, "Collaborator not found: {}", id),
+            Error::BadgeNotAwarded(badge) => write!(f, "Badge not awarded: {}", badge),
+            Error::BadgeAlreadyMinted(badge) => write!(f, "Badge already minted or being minted: {}", badge),
+            Error::BadgeMintUnavailable => write!(f, "Badge minting is not configured"),
             Error::NetworkError(msg) => write!(f, "Network error: {}", msg),
             Error::DatabaseError(msg) => write!(f, "Database error: {}", msg),
             Error::ServiceError(msg) => write!(f, "Service error: {}", msg),
@@ -53,10 +76,15 @@ impl std::error::Error for Error {}
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
         let status_code = match &self {
-            Error::DeveloperNotFound(_) | Error::CollaboratorNotFound(_) => StatusCode::NOT_FOUND,
-            Error::DeveloperAlreadyExists(_) => StatusCode::CONFLICT,
-            Error::InvalidSkill(_) | Error::InvalidVerification(_) => StatusCode::BAD_REQUEST,
+            Error::DeveloperNotFound(_) | Error::CollaboratorNotFound(_) | Error::BadgeNotAwarded(_) => {
+                StatusCode::NOT_FOUND
+            }
+            Error::DeveloperAlreadyExists(_) | Error::BadgeAlreadyMinted(_) => StatusCode::CONFLICT,
+            Error::InvalidSkill(_) | Error::InvalidVerification(_) | Error::InvalidFilter(_) => {
+                StatusCode::BAD_REQUEST
+            }
             Error::InsufficientReputation(_) => StatusCode::FORBIDDEN,
+            Error::BadgeMintUnavailable => StatusCode::SERVICE_UNAVAILABLE,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::domain::{BadgeAward, BadgeKind, BadgeRepository, MintOutcome, PreparedMint};
+use crate::Result;
+
+const AWARD_COLUMNS: &str = "developer_id, badge, awarded_at, claimed_at, mint_status, mint_recipient, \
+                             mint_digest, mint_error, minted_at";
+
+pub struct BadgeRepositoryImpl {
+    db_pool: Pool<Postgres>,
+}
+
+impl BadgeRepositoryImpl {
+    pub fn new(db_pool: Pool<Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl BadgeRepository for BadgeRepositoryImpl {
+    async fn developer_for_subject(&self, subject: &str) -> Result<Option<Uuid>> {
+        let developer_id = sqlx::query_scalar(
+            r#"
+            SELECT d.id
+            FROM developers d
+            WHERE d.github_user_id::TEXT = $1
+               OR d.github_user_id IN (
+                   SELECT gi.github_id
+                   FROM user_wallets w
+                   JOIN github_identities gi ON gi.user_id = w.user_id
+                   WHERE w.address = $1
+               )
+            LIMIT 1
+            "#,
+        )
+        .bind(subject)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(developer_id)
+    }
+
+    async fn is_linked_sui_wallet(&self, developer_id: Uuid, address: &str) -> Result<bool> {
+        let linked = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM developers d
+                JOIN github_identities gi ON gi.github_id = d.github_user_id
+                JOIN user_wallets w ON w.user_id = gi.user_id
+                WHERE d.id = $1 AND w.chain = 'sui' AND w.address = $2
+            )
+            "#,
+        )
+        .bind(developer_id)
+        .bind(address)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(linked)
+    }
+
+    async fn award(&self, developer_id: Uuid, badges: &[BadgeKind]) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO developer_badges (developer_id, badge)
+            SELECT $1, badge FROM UNNEST($2::VARCHAR[]) AS t(badge)
+            ON CONFLICT (developer_id, badge) DO NOTHING
+            "#,
+        )
+        .bind(developer_id)
+        .bind(badges.iter().map(|badge| badge.as_str()).collect::<Vec<&str>>())
+        .execute(&self.db_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn awards(&self, developer_id: Uuid) -> Result<Vec<BadgeAward>> {
+        let awards = sqlx::query_as::<_, BadgeAward>(&format!(
+            "SELECT {} FROM developer_badges WHERE developer_id = $1 ORDER BY awarded_at, badge",
+            AWARD_COLUMNS
+        ))
+        .bind(developer_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(awards)
+    }
+
+    async fn claim(&self, developer_id: Uuid, badge: BadgeKind) -> Result<Option<BadgeAward>> {
+        let award = sqlx::query_as::<_, BadgeAward>(&format!(
+            r#"
+            UPDATE developer_badges
+            SET claimed_at = COALESCE(claimed_at, NOW())
+            WHERE developer_id = $1 AND badge = $2
+            RETURNING {}
+            "#,
+            AWARD_COLUMNS
+        ))
+        .bind(developer_id)
+        .bind(badge)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(award)
+    }
+
+    async fn pending_mint(&self, developer_id: Uuid, badge: BadgeKind) -> Result<Option<PreparedMint>> {
+        let pending: Option<(String, Vec<u8>)> = sqlx::query_as(
+            r#"
+            SELECT mint_digest, mint_tx
+            FROM developer_badges
+            WHERE developer_id = $1 AND badge = $2 AND mint_status = 'pending'
+              AND mint_digest IS NOT NULL AND mint_tx IS NOT NULL
+            "#,
+        )
+        .bind(developer_id)
+        .bind(badge)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(pending.map(|(digest, signed_tx)| PreparedMint { digest, signed_tx }))
+    }
+
+    async fn start_mint(
+        &self,
+        developer_id: Uuid,
+        badge: BadgeKind,
+        recipient: &str,
+        prepared: &PreparedMint,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE developer_badges
+            SET mint_status = 'pending', mint_recipient = $3, mint_digest = $4, mint_tx = $5,
+                mint_error = NULL
+            WHERE developer_id = $1 AND badge = $2
+              AND claimed_at IS NOT NULL
+              AND (mint_status IS NULL OR mint_status = 'failed')
+            "#,
+        )
+        .bind(developer_id)
+        .bind(badge)
+        .bind(recipient)
+        .bind(&prepared.digest)
+        .bind(&prepared.signed_tx)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn finish_mint(&self, developer_id: Uuid, badge: BadgeKind, outcome: &MintOutcome) -> Result<()> {
+        let (status, digest, error) = match outcome {
+            MintOutcome::Minted { digest } => ("minted", digest, None),
+            MintOutcome::Failed { digest, reason } => ("failed", digest, Some(reason)),
+        };
+        sqlx::query(
+            r#"
+            UPDATE developer_badges
+            SET mint_status = $3, mint_digest = $4, mint_error = $5, mint_tx = NULL,
+                minted_at = CASE WHEN $3 = 'minted' THEN NOW() END
+            WHERE developer_id = $1 AND badge = $2 AND mint_status = 'pending'
+            "#,
+        )
+        .bind(developer_id)
+        .bind(badge)
+        .bind(status)
+        .bind(digest)
+        .bind(error)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
+pub mod badge_repository_impl;
 pub mod developer_repository_impl;
+pub mod reputation_repository_impl;
 
-pub use developer_repository_impl::DeveloperRepositoryImpl;
\ No newline at end of file
+pub use badge_repository_impl::BadgeRepositoryImpl;
+pub use developer_repository_impl::DeveloperRepositoryImpl;
+pub use reputation_repository_impl::ReputationRepositoryImpl;
\ No newline at end of file
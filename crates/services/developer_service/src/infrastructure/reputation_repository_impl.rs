@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::domain::{
+    DeveloperReputation, ReputationInputs, ReputationLeaderboardEntry, ReputationRepository, ReputationWindow,
+};
+use crate::Result;
+
+pub struct ReputationRepositoryImpl {
+    db_pool: Pool<Postgres>,
+}
+
+impl ReputationRepositoryImpl {
+    pub fn new(db_pool: Pool<Postgres>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ReputationRepository for ReputationRepositoryImpl {
+    async fn list_languages(&self) -> Result<Vec<String>> {
+        let languages = sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT LOWER(TRIM(r.primary_language))
+            FROM patch_proposals p
+            JOIN github_repositories r ON r.id = p.repository_id
+            WHERE p.status::TEXT IN ('applied', 'merged')
+              AND p.proposed_by_developer_id IS NOT NULL
+              AND TRIM(COALESCE(r.primary_language, '')) <> ''
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(languages)
+    }
+
+    async fn load_inputs(&self, since: Option<DateTime<Utc>>, language: &str) -> Result<Vec<ReputationInputs>> {
+        let inputs = sqlx::query_as::<_, ReputationInputs>(
+            r#"
+            WITH patches AS (
+                SELECT p.proposed_by_developer_id AS developer_id,
+                       COUNT(DISTINCT p.id) AS merged_patches,
+                       COUNT(DISTINCT v.id) FILTER (WHERE v.severity = 'critical') AS resolved_critical,
+                       COUNT(DISTINCT v.id) FILTER (WHERE v.severity = 'high') AS resolved_high,
+                       COUNT(DISTINCT v.id) FILTER (WHERE v.severity = 'medium') AS resolved_medium,
+                       COUNT(DISTINCT v.id) FILTER (WHERE v.severity = 'low') AS resolved_low
+                FROM patch_proposals p
+                JOIN github_repositories r ON r.id = p.repository_id
+                LEFT JOIN security_vulnerabilities v
+                       ON v.id = p.vulnerability_id
+                      AND NOT v.is_false_positive
+                      AND (v.fixed_at IS NOT NULL OR v.resolved_at IS NOT NULL)
+                WHERE p.status::TEXT IN ('applied', 'merged')
+                  AND p.proposed_by_developer_id IS NOT NULL
+                  AND ($1::TIMESTAMPTZ IS NULL OR p.applied_at >= $1)
+                  AND ($2 = '' OR LOWER(TRIM(r.primary_language)) = $2)
+                GROUP BY p.proposed_by_developer_id
+            ),
+            analyses AS (
+                SELECT d.id AS developer_id, COUNT(*) AS analysis_contributions
+                FROM developers d
+                JOIN github_repositories r ON LOWER(r.owner_username) = LOWER(d.github_username)
+                JOIN code_analysis_results a ON a.repository_id = r.id
+                WHERE ($1::TIMESTAMPTZ IS NULL OR a.ctime >= $1)
+                  AND ($2 = '' OR LOWER(TRIM(r.primary_language)) = $2)
+                GROUP BY d.id
+            ),
+            attestations AS (
+                SELECT d.id AS developer_id, COUNT(DISTINCT z.id) AS attestations
+                FROM developers d
+                JOIN github_identities gi ON gi.github_id = d.github_user_id
+                JOIN behavior_inputs b ON b.user_id = gi.user_id
+                JOIN scoring_results s ON s.behavior_input_id = b.id
+                JOIN zkml_proofs z ON z.scoring_result_id = s.id
+                WHERE z.verified
+                  AND z.blockchain_tx_hash IS NOT NULL
+                  AND ($1::TIMESTAMPTZ IS NULL OR z.timestamp >= $1)
+                GROUP BY d.id
+            )
+            SELECT d.id AS developer_id,
+                   d.github_username,
+                   COALESCE(p.merged_patches, 0) AS merged_patches,
+                   COALESCE(p.resolved_critical, 0) AS resolved_critical,
+                   COALESCE(p.resolved_high, 0) AS resolved_high,
+                   COALESCE(p.resolved_medium, 0) AS resolved_medium,
+                   COALESCE(p.resolved_low, 0) AS resolved_low,
+                   COALESCE(a.analysis_contributions, 0) AS analysis_contributions,
+                   COALESCE(t.attestations, 0) AS attestations
+            FROM developers d
+            LEFT JOIN patches p ON p.developer_id = d.id
+            LEFT JOIN analyses a ON a.developer_id = d.id
+            LEFT JOIN attestations t ON t.developer_id = d.id
+            WHERE p.developer_id IS NOT NULL OR a.developer_id IS NOT NULL OR t.developer_id IS NOT NULL
+            "#,
+        )
+        .bind(since)
+        .bind(language)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(inputs)
+    }
+
+    async fn replace_leaderboard(
+        &self,
+        window: ReputationWindow,
+        language: &str,
+        ranked: &[DeveloperReputation],
+        computed_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut tx = self.db_pool.begin().await?;
+        sqlx::query("DELETE FROM developer_reputation WHERE time_window = $1 AND language = $2")
+            .bind(window)
+            .bind(language)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO developer_reputation (
+                developer_id, time_window, language, score, rank, merged_patches,
+                resolved_vulnerabilities, analysis_contributions, attestations, computed_at
+            )
+            SELECT developer_id, $1, $2, score, rank, merged_patches,
+                   resolved_vulnerabilities, analysis_contributions, attestations, $10
+            FROM UNNEST($3::UUID[], $4::FLOAT8[], $5::INT4[], $6::INT8[], $7::INT8[], $8::INT8[], $9::INT8[])
+                AS t(developer_id, score, rank, merged_patches, resolved_vulnerabilities,
+                     analysis_contributions, attestations)
+            "#,
+        )
+        .bind(window)
+        .bind(language)
+        .bind(ranked.iter().map(|r| r.developer_id).collect::<Vec<Uuid>>())
+        .bind(ranked.iter().map(|r| r.score).collect::<Vec<f64>>())
+        .bind(ranked.iter().map(|r| r.rank).collect::<Vec<i32>>())
+        .bind(ranked.iter().map(|r| r.merged_patches).collect::<Vec<i64>>())
+        .bind(ranked.iter().map(|r| r.resolved_vulnerabilities).collect::<Vec<i64>>())
+        .bind(ranked.iter().map(|r| r.analysis_contributions).collect::<Vec<i64>>())
+        .bind(ranked.iter().map(|r| r.attestations).collect::<Vec<i64>>())
+        .bind(computed_at)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn sync_developer_scores(&self) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE developers d
+            SET coding_reputation_score = r.score::DECIMAL(5,2), mtime = NOW()
+            FROM developer_reputation r
+            WHERE r.developer_id = d.id
+              AND r.time_window = 'all_time'
+              AND r.language = ''
+              AND d.coding_reputation_score IS DISTINCT FROM r.score::DECIMAL(5,2)
+            "#,
+        )
+        .execute(&self.db_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn leaderboard(
+        &self,
+        window: ReputationWindow,
+        language: &str,
+        limit: i64,
+    ) -> Result<Vec<ReputationLeaderboardEntry>> {
+        let entries = sqlx::query_as::<_, ReputationLeaderboardEntry>(
+            r#"
+            SELECT r.rank, r.developer_id, d.github_username, d.display_name, r.score,
+                   r.merged_patches, r.resolved_vulnerabilities, r.analysis_contributions,
+                   r.attestations, r.computed_at
+            FROM developer_reputation r
+            JOIN developers d ON d.id = r.developer_id
+            WHERE r.time_window = $1 AND r.language = $2
+            ORDER BY r.rank
+            LIMIT $3
+            "#,
+        )
+        .bind(window)
+        .bind(language)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(entries)
+    }
+}
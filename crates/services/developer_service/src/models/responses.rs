@@ -5,7 +5,7 @@ use uuid::Uuid;
 
 use crate::domain::{
     CodeReview, Developer, DeveloperActivity, DeveloperCollaborator, DeveloperContribution,
-    DeveloperLeaderboard, DeveloperNetwork, Skill,
+    DeveloperLeaderboard, DeveloperNetwork, ReputationLeaderboardEntry, ReputationWindow, Skill,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,6 +96,16 @@ pub struct LeaderboardResponse {
     pub period: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReputationLeaderboardResponse {
+    pub window: ReputationWindow,
+    /// `None` for all languages.
+    pub language: Option<String>,
+    /// When the leaderboard was last recomputed; `None` before the first run.
+    pub computed_at: Option<DateTime<Utc>>,
+    pub leaderboard: Vec<ReputationLeaderboardEntry>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationResponse {
     pub developer_id: Uuid,
@@ -0,0 +1,98 @@
+use crate::domain::{AnalysisJobProcessor, AnalysisPriority, JobStatus, LeasedJob};
+use crate::error::{Error, Result};
+use crate::infrastructure::analysis_metrics::{ANALYSIS_JOB_DURATION, ANALYSIS_JOB_FAILURES};
+use crate::infrastructure::{AnalysisQueueImpl, RateLimiterImpl};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{info, warn};
+
+/// Pulls jobs off the durable analysis queue one at a time. Run as many
+/// workers as needed; leasing keeps them from picking up the same job.
+pub struct AnalysisWorker<P> {
+    queue: Arc<AnalysisQueueImpl>,
+    processor: P,
+    worker_id: String,
+    rate_limiter: Option<Arc<RateLimiterImpl>>,
+}
+
+impl<P: AnalysisJobProcessor> AnalysisWorker<P> {
+    pub fn new(queue: Arc<AnalysisQueueImpl>, processor: P, worker_id: impl Into<String>) -> Self {
+        Self {
+            queue,
+            processor,
+            worker_id: worker_id.into(),
+            rate_limiter: None,
+        }
+    }
+
+    /// Leave low-priority jobs queued while `rate_limiter` reports a GitHub
+    /// budget down to its reserve.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiterImpl>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Lease and run at most one job. Returns whether there was one, so the
+    /// caller can back off when the queue is empty.
+    pub async fn run_once(&self) -> Result<bool> {
+        let min_priority = match &self.rate_limiter {
+            Some(rate_limiter) if rate_limiter.is_constrained().await => AnalysisPriority::Normal,
+            _ => AnalysisPriority::Low,
+        };
+        let Some(lease) = self.queue.lease(&self.worker_id, min_priority).await? else {
+            return Ok(false);
+        };
+
+        let started = Instant::now();
+        let result = self.process(&lease).await;
+        let outcome = match &result {
+            Ok(()) => "completed",
+            Err(Error::RateLimitExceeded { .. }) => "deferred",
+            Err(Error::LeaseLost(_)) => "lease_lost",
+            Err(_) => "failed",
+        };
+        ANALYSIS_JOB_DURATION.with_label_values(&[outcome]).observe(started.elapsed().as_secs_f64());
+
+        match result {
+            Ok(()) => self.queue.complete_job(&lease).await?,
+            Err(Error::RateLimitExceeded { retry_after_seconds }) => {
+                self.queue.defer_job(&lease, Duration::from_secs(retry_after_seconds)).await?;
+            }
+            Err(Error::LeaseLost(job_id)) => {
+                warn!("Lost lease on analysis job {}; it was cancelled or taken over", job_id);
+            }
+            Err(e) => {
+                let message = e.to_string();
+                let reason = jd_error::AppError::from(e).code().to_string();
+                ANALYSIS_JOB_FAILURES.with_label_values(&[&reason]).inc();
+                let status = self.queue.fail_job(&lease, &message).await?;
+                if status == JobStatus::DeadLettered {
+                    info!("Analysis job {} moved to the dead-letter state", lease.job.id);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Run the job, extending its lease every third of the visibility timeout
+    /// so long analyses are not picked up by another worker.
+    async fn process(&self, lease: &LeasedJob) -> Result<()> {
+        let every = (self.queue.settings().visibility_timeout / 3).max(Duration::from_secs(1));
+        let mut heartbeat = interval(every);
+        heartbeat.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        heartbeat.tick().await;
+
+        let job = self.processor.process(&lease.job);
+        tokio::pin!(job);
+
+        loop {
+            tokio::select! {
+                result = &mut job => return result,
+                _ = heartbeat.tick() => {
+                    self.queue.extend_lease(lease).await?;
+                }
+            }
+        }
+    }
+}
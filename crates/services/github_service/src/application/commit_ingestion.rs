@@ -0,0 +1,136 @@
+use crate::domain::{commits_to_ingest, CommitIngestionSettings, CommitSyncTarget};
+use crate::error::Result;
+use crate::infrastructure::{CommitStore, GitHubClient};
+use chrono::{Duration as ChronoDuration, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// How long a claimed repository is hidden from other instances. A sync
+/// that fails is retried once this has passed.
+const CLAIM_LEASE: Duration = Duration::from_secs(15 * 60);
+
+/// What one pass of the ingestor did.
+#[derive(Debug, Default, Serialize)]
+pub struct CommitIngestionRun {
+    pub repositories: usize,
+    pub commits: usize,
+    pub failed: usize,
+}
+
+/// Ingests the commit history of tracked repositories, picking up after the
+/// last commit seen. Safe to run from several instances at once: due
+/// repositories are claimed before they are synced.
+pub struct CommitIngestor {
+    github_client: Arc<GitHubClient>,
+    store: CommitStore,
+    settings: CommitIngestionSettings,
+}
+
+impl CommitIngestor {
+    pub fn new(
+        github_client: Arc<GitHubClient>,
+        store: CommitStore,
+        settings: CommitIngestionSettings,
+    ) -> Self {
+        Self {
+            github_client,
+            store,
+            settings,
+        }
+    }
+
+    /// Sync up to `repositories_per_run` due repositories.
+    pub async fn run_once(&self) -> Result<CommitIngestionRun> {
+        let created = self.store.ensure_syncs().await?;
+        if created > 0 {
+            info!("Tracking commit history of {} newly tracked repositories", created);
+        }
+
+        let mut run = CommitIngestionRun::default();
+        let due = self
+            .store
+            .claim_due(self.settings.repositories_per_run, CLAIM_LEASE)
+            .await?;
+        for target in due {
+            match self.sync(&target).await {
+                Ok(commits) => {
+                    run.repositories += 1;
+                    run.commits += commits;
+                }
+                Err(e) => {
+                    warn!(
+                        "Commit sync of {}/{} failed: {}",
+                        target.owner_username, target.repo_name, e
+                    );
+                    run.failed += 1;
+                }
+            }
+        }
+
+        Ok(run)
+    }
+
+    /// Ingest the repository's commits since its last seen one, oldest
+    /// first, so a sync cut short resumes where it stopped. Returns the
+    /// number ingested.
+    async fn sync(&self, target: &CommitSyncTarget) -> Result<usize> {
+        let (owner, repo) = (&target.owner_username, &target.repo_name);
+        let last_seen = target.last_seen_sha.as_deref();
+        let listed = self
+            .github_client
+            .list_commit_shas(owner, repo, last_seen, self.settings.history_depth)
+            .await?;
+        let (pending, gap) =
+            commits_to_ingest(&listed, last_seen, self.settings.commits_per_repository);
+        if gap {
+            warn!(
+                "More than {} commits to {}/{} since {}; older ones are skipped",
+                self.settings.history_depth,
+                owner,
+                repo,
+                last_seen.unwrap_or_default()
+            );
+        }
+
+        let mut newest = None;
+        let mut ingested = 0;
+        let mut result = Ok(());
+        for sha in &pending {
+            if let Err(e) = self.ingest(target, sha).await {
+                result = Err(e);
+                break;
+            }
+            newest = Some(*sha);
+            ingested += 1;
+        }
+
+        // Record what was ingested even when the sync stopped early; a
+        // backlog left by the per-run cap is picked up on the next run.
+        let wait = if result.is_err() {
+            CLAIM_LEASE
+        } else if pending.len() == self.settings.commits_per_repository {
+            Duration::ZERO
+        } else {
+            self.settings.sync_interval
+        };
+        let next_sync_at = Utc::now() + ChronoDuration::from_std(wait).unwrap_or_default();
+        self.store.record_sync(target.repository_id, newest, next_sync_at).await?;
+        result?;
+
+        if ingested > 0 {
+            info!("Ingested {} commits of {}/{}", ingested, owner, repo);
+        }
+        Ok(ingested)
+    }
+
+    async fn ingest(&self, target: &CommitSyncTarget, sha: &str) -> Result<()> {
+        let commit = self
+            .github_client
+            .get_commit(&target.owner_username, &target.repo_name, sha)
+            .await?;
+        self.store.insert(target.repository_id, &commit).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use crate::domain::{
+    compare_modules, is_package_source, is_within, package_address, verification_status,
+    DeployedModule, DeploymentVerification, ModuleBytecode, ModuleMatch, MovePackageBuilder,
+    OnchainPackageReader, PackageDeployment, PackageKind, VerificationStatus,
+};
+use crate::error::{Error, Result};
+use crate::infrastructure::{GitHubClient, PackageDeploymentStore, RepositoryPackageStore};
+use crate::models::{RegisterDeploymentRequest, VerifyDeploymentRequest};
+use jd_domain::zkpersona_domain::developer_models::GitHubRepository;
+use jd_storage::repository::{developer_repositories::GitHubRepositoryRepository, Repository};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Tracks the Move packages repositories have published on-chain, and
+/// checks their bytecode against the repository's source at a commit.
+pub struct DeploymentHandler<C: OnchainPackageReader, B: MovePackageBuilder> {
+    github_client: Arc<GitHubClient>,
+    repository_repo: Arc<GitHubRepositoryRepository>,
+    deployments: PackageDeploymentStore,
+    packages: RepositoryPackageStore,
+    chain: C,
+    builder: Option<B>,
+}
+
+impl<C: OnchainPackageReader, B: MovePackageBuilder> DeploymentHandler<C, B> {
+    pub fn new(
+        github_client: Arc<GitHubClient>,
+        repository_repo: Arc<GitHubRepositoryRepository>,
+        deployments: PackageDeploymentStore,
+        packages: RepositoryPackageStore,
+        chain: C,
+    ) -> Self {
+        Self { github_client, repository_repo, deployments, packages, chain, builder: None }
+    }
+
+    /// Let deployments be verified against source built by `builder`.
+    pub fn with_builder(mut self, builder: B) -> Self {
+        self.builder = Some(builder);
+        self
+    }
+
+    pub async fn list_deployments(&self, repository_id: Uuid) -> Result<Vec<PackageDeployment>> {
+        self.repository(repository_id).await?;
+        self.deployments.list(repository_id).await
+    }
+
+    /// Track a package the repository published, after checking that it
+    /// exists on the network.
+    pub async fn register_deployment(
+        &self,
+        repository_id: Uuid,
+        request: RegisterDeploymentRequest,
+    ) -> Result<PackageDeployment> {
+        let repository = self.repository(repository_id).await?;
+        let network = request.network.trim().to_lowercase();
+        let package_id = request.package_id.trim().to_lowercase();
+        if package_address(&package_id).is_none() {
+            return Err(Error::InvalidDeployment(format!("Invalid package id '{}'", package_id)));
+        }
+        let package_path = self.package_path(repository_id, request.package_path).await?;
+
+        let modules = self.chain.package_modules(&network, &package_id).await?;
+        if modules.is_empty() {
+            return Err(Error::InvalidDeployment(format!(
+                "Package {} on {} has no modules",
+                package_id, network
+            )));
+        }
+
+        info!(
+            "Registered {} deployment {} of {} ({} modules)",
+            network, package_id, repository.full_name, modules.len()
+        );
+        self.deployments
+            .register(repository_id, &package_path, &network, &package_id, &module_names(&modules))
+            .await
+    }
+
+    /// The deployment's modules as published now. The module names kept
+    /// for it are refreshed.
+    pub async fn deployed_modules(
+        &self,
+        repository_id: Uuid,
+        deployment_id: Uuid,
+    ) -> Result<Vec<DeployedModule>> {
+        let deployment = self.deployment(repository_id, deployment_id).await?;
+        let modules = self.chain.package_modules(&deployment.network, &deployment.package_id).await?;
+        self.deployments.record_modules(deployment.id, &module_names(&modules)).await?;
+        Ok(modules.iter().map(DeployedModule::from).collect())
+    }
+
+    /// Build the deployment's package from the source at `commit_sha` and
+    /// compare it with the published bytecode. A source that fails to
+    /// build is recorded as a failed verification rather than an error.
+    pub async fn verify_deployment(
+        &self,
+        repository_id: Uuid,
+        deployment_id: Uuid,
+        request: VerifyDeploymentRequest,
+    ) -> Result<DeploymentVerification> {
+        let builder = self.builder.as_ref().ok_or_else(|| {
+            Error::ConfigurationError(
+                "Source verification needs MOVE_COMPILER_COMMAND to be set".to_string(),
+            )
+        })?;
+        let commit_sha = request.commit_sha.trim();
+        if commit_sha.len() < 7
+            || commit_sha.len() > 40
+            || !commit_sha.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(Error::InvalidDeployment(format!("Invalid commit '{}'", commit_sha)));
+        }
+        let repository = self.repository(repository_id).await?;
+        let deployment = self.deployment(repository_id, deployment_id).await?;
+        let address = package_address(&deployment.package_id).ok_or_else(|| {
+            Error::InvalidDeployment(format!("Invalid package id '{}'", deployment.package_id))
+        })?;
+
+        let on_chain = self.chain.package_modules(&deployment.network, &deployment.package_id).await?;
+        let built = match self.build_at(&repository, &deployment.package_path, commit_sha, builder).await {
+            Ok(built) => built,
+            Err(e) => {
+                warn!(
+                    "Failed to build {} at {} for deployment {}: {}",
+                    repository.full_name, commit_sha, deployment.package_id, e
+                );
+                let deployment = self
+                    .deployments
+                    .record_verification(
+                        deployment.id,
+                        VerificationStatus::Failed,
+                        commit_sha,
+                        Some(&e.to_string()),
+                    )
+                    .await?;
+                return Ok(DeploymentVerification { deployment, modules: Vec::new() });
+            }
+        };
+
+        let modules = compare_modules(&address, &on_chain, &built);
+        let status = verification_status(&modules);
+        let differing = modules.iter().filter(|module| module.result != ModuleMatch::Matches).count();
+        let message = (status == VerificationStatus::Mismatch)
+            .then(|| format!("{} of {} modules do not match", differing, modules.len()));
+        let deployment = self
+            .deployments
+            .record_verification(deployment.id, status, commit_sha, message.as_deref())
+            .await?;
+
+        info!(
+            "Deployment {} of {} is {:?} against {}",
+            deployment.package_id, repository.full_name, status, commit_sha
+        );
+        Ok(DeploymentVerification { deployment, modules })
+    }
+
+    /// The package's modules built from its Move sources at `commit_sha`.
+    async fn build_at(
+        &self,
+        repository: &GitHubRepository,
+        package_path: &str,
+        commit_sha: &str,
+        builder: &B,
+    ) -> Result<Vec<ModuleBytecode>> {
+        let (owner, repo) = (&repository.owner_username, &repository.repo_name);
+        let paths: Vec<String> = self.github_client
+            .get_tree_paths(owner, repo, commit_sha)
+            .await?
+            .into_iter()
+            .filter(|path| is_within(path, package_path) && is_package_source(path))
+            .collect();
+        if paths.is_empty() {
+            return Err(Error::InvalidDeployment(format!(
+                "No Move package at '{}' in {}",
+                package_path, commit_sha
+            )));
+        }
+
+        let sources: HashMap<String, String> = self.github_client
+            .get_repository_files_at_ref(owner, repo, &paths, commit_sha)
+            .await?
+            .into_iter()
+            .map(|file| (file.path, file.content))
+            .collect();
+        builder.build(package_path, &sources).await
+    }
+
+    /// The package a deployment is registered under: the one named, which
+    /// must be a detected Move package when any were detected, or else the
+    /// repository's only Move package, or its root.
+    async fn package_path(&self, repository_id: Uuid, requested: Option<String>) -> Result<String> {
+        let move_packages: Vec<String> = self.packages
+            .list(repository_id)
+            .await?
+            .into_iter()
+            .filter(|package| package.kind == PackageKind::Move)
+            .map(|package| package.path)
+            .collect();
+
+        match requested.map(|path| path.trim().trim_matches('/').to_string()) {
+            Some(path) if move_packages.is_empty() || move_packages.contains(&path) => Ok(path),
+            Some(path) => Err(Error::PackageNotFound(path)),
+            None => match move_packages.as_slice() {
+                [] => Ok(String::new()),
+                [only] => Ok(only.clone()),
+                _ => Err(Error::InvalidDeployment(
+                    "Repository has several Move packages; name one in package_path".to_string(),
+                )),
+            },
+        }
+    }
+
+    async fn repository(&self, id: Uuid) -> Result<GitHubRepository> {
+        self.repository_repo
+            .find_by_id(id.into())
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+            .ok_or_else(|| Error::RepositoryNotFound {
+                owner: "unknown".to_string(),
+                repo: id.to_string(),
+            })
+    }
+
+    async fn deployment(&self, repository_id: Uuid, id: Uuid) -> Result<PackageDeployment> {
+        self.deployments
+            .find(repository_id, id)
+            .await?
+            .ok_or(Error::DeploymentNotFound(id))
+    }
+}
+
+fn module_names(modules: &[ModuleBytecode]) -> Vec<String> {
+    let mut names: Vec<String> = modules.iter().map(|module| module.name.clone()).collect();
+    names.sort();
+    names
+}
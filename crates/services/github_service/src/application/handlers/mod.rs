@@ -1,5 +1,7 @@
+pub mod deployment_handler;
 pub mod webhook_handler;
 pub mod repository_handler;
 
+pub use deployment_handler::*;
 pub use webhook_handler::*;
 pub use repository_handler::*;
\ No newline at end of file
@@ -1,27 +1,51 @@
-use crate::domain::{AnalysisJob, AnalysisType, AnalysisPriority, JobStatus};
+use crate::domain::{
+    detect_packages, next_run_after, parse_schedule, AnalysisJob, AnalysisType, AnalysisPriority,
+    generate_webhook_secret, ContributorSummary, JobStatus, OrganizationRepository,
+    ReanalysisSchedule, RepositoryCommit, RepositoryPackage, WebhookSecretRotation,
+    WebhookSecretScope, WebhookSecretSettings,
+};
 use crate::error::{Error, Result};
-use crate::infrastructure::{GitHubClient, AnalysisQueueImpl, check_repository_for_smart_contracts};
+use crate::infrastructure::{
+    GitHubClient, AnalysisQueueImpl, CommitStore, PackageDeploymentStore, ReanalysisScheduleStore,
+    RepositoryPackageStore, WebhookSecretStore, check_repository_for_smart_contracts,
+};
 use crate::models::{
     AddRepositoryRequest, UpdateRepositorySettingsRequest, RepositoryListParams,
     RepositoryResponse, RepositoryListResponse, RepositoryDetailResponse, RepositoryFilters,
-    VulnerabilitySummary,
+    VulnerabilitySummary, OrganizationImportRequest, OrganizationImportResponse,
+    ImportedRepository, SkippedRepository, CommitListParams,
 };
 use axum::{
     extract::{Path, Query, State, Json},
     http::StatusCode,
     response::Json as ResponseJson,
 };
-use jd_domain::zkpersona_domain::developer_models::{GitHubRepositoryForCreate, GitHubRepositoryForUpdate};
+use jd_domain::zkpersona_domain::developer_models::{
+    GitHubRepository, GitHubRepositoryForCreate, GitHubRepositoryForUpdate,
+};
+use futures::stream::{self, StreamExt};
 use jd_storage::repository::{developer_repositories::GitHubRepositoryRepository, Repository};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+/// Languages an organization import matches when the request names neither
+/// languages nor topics.
+const DEFAULT_IMPORT_LANGUAGES: [&str; 3] = ["Move", "Solidity", "Vyper"];
+const DEFAULT_IMPORT_CONCURRENCY: usize = 4;
+const MAX_IMPORT_CONCURRENCY: usize = 16;
+
 pub struct RepositoryHandler {
     github_client: Arc<GitHubClient>,
     analysis_queue: Arc<AnalysisQueueImpl>,
     repository_repo: Arc<GitHubRepositoryRepository>,
     webhook_base_url: String,
+    /// Schedule store and the default schedule repositories fall back to.
+    reanalysis: Option<(ReanalysisScheduleStore, String)>,
+    packages: Option<RepositoryPackageStore>,
+    commits: Option<CommitStore>,
+    deployments: Option<PackageDeploymentStore>,
+    webhook_secrets: Option<(WebhookSecretStore, WebhookSecretSettings)>,
 }
 
 impl RepositoryHandler {
@@ -36,9 +60,54 @@ impl RepositoryHandler {
             analysis_queue,
             repository_repo,
             webhook_base_url,
+            reanalysis: None,
+            packages: None,
+            commits: None,
+            deployments: None,
+            webhook_secrets: None,
         }
     }
 
+    /// Let settings updates change repositories' re-analysis schedules.
+    pub fn with_reanalysis_schedules(
+        mut self,
+        store: ReanalysisScheduleStore,
+        default_schedule: String,
+    ) -> Self {
+        self.reanalysis = Some((store, default_schedule));
+        self
+    }
+
+    /// Track the smart contract packages in repositories, detected when they
+    /// are added, and let settings updates include or exclude them.
+    pub fn with_package_store(mut self, store: RepositoryPackageStore) -> Self {
+        self.packages = Some(store);
+        self
+    }
+
+    /// Serve the commit history ingested for repositories.
+    pub fn with_commit_store(mut self, store: CommitStore) -> Self {
+        self.commits = Some(store);
+        self
+    }
+
+    /// Show whether repositories have a verified build among their package
+    /// deployments.
+    pub fn with_deployment_store(mut self, store: PackageDeploymentStore) -> Self {
+        self.deployments = Some(store);
+        self
+    }
+
+    /// Let webhook secrets be rotated, keeping them in `store`.
+    pub fn with_webhook_secrets(
+        mut self,
+        store: WebhookSecretStore,
+        settings: WebhookSecretSettings,
+    ) -> Self {
+        self.webhook_secrets = Some((store, settings));
+        self
+    }
+
     pub async fn list_repositories(
         &self,
         params: RepositoryListParams,
@@ -112,7 +181,7 @@ impl RepositoryHandler {
             .map_err(|e| Error::Internal(e.to_string()))?;
 
         // 4. Setup webhook for repository
-        let webhook_url = format!("{}/api/v1/webhooks/github", self.webhook_base_url);
+        let webhook_url = format!("{}/api/v1/github/webhook", self.webhook_base_url);
         let _webhook = self.github_client
             .create_webhook(&request.owner, &request.name, &webhook_url)
             .await
@@ -125,6 +194,8 @@ impl RepositoryHandler {
         let analysis_job = AnalysisJob {
             id: Uuid::new_v4(),
             repository_id: github_repo.id,
+            installation_id: None,
+            pull_request_number: None,
             commit_sha: github_repo.default_branch.clone(),
             files_to_analyze: vec![], // Will scan all smart contract files
             analysis_type: AnalysisType::InitialScan,
@@ -140,13 +211,134 @@ impl RepositoryHandler {
             repository.full_name, job_id
         );
 
+        let packages = self.detect_packages_or_warn(&repository).await;
+
         Ok(RepositoryResponse {
             repository,
             webhook_configured: true,
             initial_scan_queued: true,
+            reanalysis: None,
+            packages,
         })
     }
 
+    /// Track every matching repository in `org`: save it, register the
+    /// webhook and queue an initial scan, `concurrency` repositories at a
+    /// time. Repositories already tracked, or that fail to save, are reported
+    /// as skipped without stopping the rest.
+    pub async fn import_organization(
+        &self,
+        org: &str,
+        request: OrganizationImportRequest,
+    ) -> Result<OrganizationImportResponse> {
+        info!("Importing repositories from organization {}", org);
+
+        let matching: Vec<OrganizationRepository> = self.github_client
+            .list_organization_repositories(org)
+            .await?
+            .into_iter()
+            .filter(|repo| matches_import_filters(repo, &request))
+            .collect();
+        let concurrency = request
+            .concurrency
+            .unwrap_or(DEFAULT_IMPORT_CONCURRENCY)
+            .clamp(1, MAX_IMPORT_CONCURRENCY);
+
+        let outcomes: Vec<_> = stream::iter(&matching)
+            .map(|repo| self.import_organization_repository(repo))
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+
+        let mut response = OrganizationImportResponse {
+            organization: org.to_string(),
+            matched: matching.len(),
+            imported: Vec::new(),
+            skipped: Vec::new(),
+        };
+        for outcome in outcomes {
+            match outcome {
+                Ok(imported) => response.imported.push(imported),
+                Err(skipped) => response.skipped.push(skipped),
+            }
+        }
+
+        info!(
+            "Imported {} of {} matching repositories from {} ({} skipped)",
+            response.imported.len(), response.matched, org, response.skipped.len()
+        );
+        Ok(response)
+    }
+
+    async fn import_organization_repository(
+        &self,
+        repo: &OrganizationRepository,
+    ) -> std::result::Result<ImportedRepository, SkippedRepository> {
+        let skip = |reason: String| SkippedRepository { full_name: repo.full_name.clone(), reason };
+
+        match self.repository_repo.find_by_github_repo_id(repo.id as i64).await {
+            Ok(Some(_)) => return Err(skip("already tracked".to_string())),
+            Ok(None) => {}
+            Err(e) => return Err(skip(format!("lookup failed: {}", e))),
+        }
+
+        let new_repo = GitHubRepositoryForCreate {
+            github_repo_id: repo.id as i64,
+            owner_username: repo.owner.login.clone(),
+            repo_name: repo.name.clone(),
+            full_name: repo.full_name.clone(),
+            description: repo.description.clone(),
+            primary_language: repo.language.clone(),
+            is_private: repo.private,
+            star_count: Some(repo.stargazers_count as i32),
+            fork_count: Some(repo.forks_count as i32),
+            webhook_secret: None,
+            monitoring_enabled: Some(true),
+        };
+        let repository = self.repository_repo
+            .create(new_repo)
+            .await
+            .map_err(|e| skip(format!("could not be saved: {}", e)))?;
+
+        let webhook_url = format!("{}/api/v1/github/webhook", self.webhook_base_url);
+        let webhook_configured = match self.github_client
+            .create_webhook(&repo.owner.login, &repo.name, &webhook_url)
+            .await
+        {
+            Ok(_) => true,
+            Err(e) => {
+                warn!("Failed to create webhook for {}: {}", repo.full_name, e);
+                false
+            }
+        };
+
+        // Bulk imports queue at normal priority so they do not hold up
+        // pull request and push analyses.
+        let analysis_job = AnalysisJob {
+            id: Uuid::new_v4(),
+            repository_id: repo.id,
+            installation_id: None,
+            pull_request_number: None,
+            commit_sha: repo.default_branch.clone(),
+            files_to_analyze: vec![],
+            analysis_type: AnalysisType::InitialScan,
+            priority: AnalysisPriority::Normal,
+            created_at: chrono::Utc::now(),
+            status: JobStatus::Queued,
+        };
+        let analysis_job_id = match self.analysis_queue.enqueue(analysis_job).await {
+            Ok(job_id) => Some(job_id),
+            Err(e) => {
+                warn!("Failed to queue initial scan for {}: {}", repo.full_name, e);
+                None
+            }
+        };
+
+        self.detect_packages_or_warn(&repository).await;
+
+        Ok(ImportedRepository { repository, webhook_configured, analysis_job_id })
+    }
+
     pub async fn get_repository(&self, id: Uuid) -> Result<RepositoryDetailResponse> {
         let repository = self.repository_repo
             .find_by_id(id.into())
@@ -172,11 +364,17 @@ impl RepositoryHandler {
         // TODO: Get security trends
         let security_trends = vec![];
 
+        let verified_build = match &self.deployments {
+            Some(deployments) => deployments.verified_build(id).await?,
+            None => None,
+        };
+
         Ok(RepositoryDetailResponse {
             repository,
             latest_analysis,
             vulnerability_summary,
             security_trends,
+            verified_build,
         })
     }
 
@@ -211,12 +409,269 @@ impl RepositoryHandler {
         // TODO: Implement proper update method
         let repository = existing;
 
+        let reanalysis = match (request.reanalysis_schedule, request.reanalysis_enabled) {
+            (None, None) => None,
+            (schedule, enabled) => {
+                Some(self.update_reanalysis_schedule(id, schedule, enabled).await?)
+            }
+        };
+
+        let packages = match (request.included_packages, request.excluded_packages) {
+            (None, None) => None,
+            (included, excluded) => Some(
+                self.update_package_scope(
+                    id,
+                    included.unwrap_or_default(),
+                    excluded.unwrap_or_default(),
+                )
+                .await?,
+            ),
+        };
+
         Ok(RepositoryResponse {
             repository,
             webhook_configured: true,
             initial_scan_queued: false,
+            reanalysis,
+            packages,
+        })
+    }
+
+    pub async fn list_packages(&self, id: Uuid) -> Result<Vec<RepositoryPackage>> {
+        self.package_store()?.list(id).await
+    }
+
+    /// The repository's ingested commits, newest first.
+    pub async fn list_commits(
+        &self,
+        id: Uuid,
+        params: CommitListParams,
+    ) -> Result<Vec<RepositoryCommit>> {
+        let limit = params.limit.unwrap_or(50).clamp(1, 200);
+        let offset = params.offset.unwrap_or(0).max(0);
+        self.commit_store()?.list(id, limit, offset).await
+    }
+
+    /// The repository's commit authors, most active first.
+    pub async fn list_contributors(&self, id: Uuid) -> Result<Vec<ContributorSummary>> {
+        self.commit_store()?.contributors(id).await
+    }
+
+    /// Re-detect the repository's packages from its default branch. Packages
+    /// still present keep their include/exclude setting.
+    pub async fn detect_packages(&self, id: Uuid) -> Result<Vec<RepositoryPackage>> {
+        let repository = self.repository_repo
+            .find_by_id(id.into())
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+            .ok_or_else(|| Error::RepositoryNotFound {
+                owner: "unknown".to_string(),
+                repo: id.to_string(),
+            })?;
+
+        self.sync_packages(&repository).await
+    }
+
+    async fn sync_packages(
+        &self,
+        repository: &GitHubRepository,
+    ) -> Result<Vec<RepositoryPackage>> {
+        let store = self.package_store()?;
+        let paths = self.github_client
+            .get_tree_paths(&repository.owner_username, &repository.repo_name, "HEAD")
+            .await?;
+        let detected = detect_packages(paths.iter().map(String::as_str));
+
+        info!("Detected {} packages in {}", detected.len(), repository.full_name);
+        store.sync(repository.id.to_uuid(), &detected).await
+    }
+
+    /// Detect packages in a newly tracked repository. Failing to does not
+    /// fail adding it: every file is in scope until packages are detected.
+    async fn detect_packages_or_warn(
+        &self,
+        repository: &GitHubRepository,
+    ) -> Option<Vec<RepositoryPackage>> {
+        self.packages.as_ref()?;
+        match self.sync_packages(repository).await {
+            Ok(packages) => Some(packages),
+            Err(e) => {
+                warn!("Failed to detect packages in {}: {}", repository.full_name, e);
+                None
+            }
+        }
+    }
+
+    /// Include and exclude packages by path. Every path must be a known
+    /// package of the repository.
+    async fn update_package_scope(
+        &self,
+        id: Uuid,
+        included: Vec<String>,
+        excluded: Vec<String>,
+    ) -> Result<Vec<RepositoryPackage>> {
+        let store = self.package_store()?;
+        let known = store.list(id).await?;
+        if let Some(unknown) = included
+            .iter()
+            .chain(&excluded)
+            .find(|path| !known.iter().any(|package| &package.path == *path))
+        {
+            return Err(Error::PackageNotFound(unknown.clone()));
+        }
+
+        store.set_included(id, &included, true).await?;
+        store.set_included(id, &excluded, false).await?;
+        store.list(id).await
+    }
+
+    /// Give the repository's webhook a new secret, registering the webhook
+    /// if it is missing.
+    pub async fn rotate_webhook_secret(&self, id: Uuid) -> Result<WebhookSecretRotation> {
+        let repository = self.repository_repo
+            .find_by_id(id.into())
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+            .ok_or_else(|| Error::RepositoryNotFound {
+                owner: "unknown".to_string(),
+                repo: id.to_string(),
+            })?;
+
+        self.rotate_secret(
+            WebhookSecretScope::Repository,
+            repository.github_repo_id,
+            Some(&repository),
+        )
+        .await
+    }
+
+    /// Give the GitHub App's webhook a new secret.
+    pub async fn rotate_app_webhook_secret(&self) -> Result<WebhookSecretRotation> {
+        self.rotate_secret(WebhookSecretScope::App, 0, None).await
+    }
+
+    /// The new secret is accepted before GitHub is given it, and the old one
+    /// for the grace period after, so no delivery is rejected in between.
+    async fn rotate_secret(
+        &self,
+        scope: WebhookSecretScope,
+        scope_id: i64,
+        repository: Option<&GitHubRepository>,
+    ) -> Result<WebhookSecretRotation> {
+        let (store, settings) = self.webhook_secrets.as_ref().ok_or_else(|| {
+            Error::ConfigurationError(
+                "Webhook secret rotation needs GITHUB.TOKEN_ENCRYPTION_KEY".to_string(),
+            )
+        })?;
+
+        let secret = generate_webhook_secret();
+        let secret_id = store
+            .add_pending(scope, scope_id, &secret, &self.github_client.webhook_secrets())
+            .await?;
+
+        let registered = match repository {
+            Some(repository) => {
+                let webhook_url = format!("{}/api/v1/github/webhook", self.webhook_base_url);
+                self.github_client
+                    .set_repository_webhook_secret(
+                        &repository.owner_username,
+                        &repository.repo_name,
+                        &webhook_url,
+                        &secret,
+                    )
+                    .await
+                    .map(Some)
+            }
+            None => self.github_client.set_app_webhook_secret(&secret).await.map(|()| None),
+        };
+        let hook_id = match registered {
+            Ok(hook_id) => hook_id,
+            Err(e) => {
+                if let Err(discard_error) = store.discard(secret_id).await {
+                    warn!("Failed to discard webhook secret {}: {}", secret_id, discard_error);
+                }
+                return Err(e);
+            }
+        };
+
+        let previous_accepted_until = store.activate(secret_id, settings.grace_period).await?;
+        info!("Rotated {:?} webhook secret for {}", scope, scope_id);
+
+        Ok(WebhookSecretRotation {
+            secret_id,
+            scope,
+            scope_id,
+            hook_id,
+            rotated_at: chrono::Utc::now(),
+            previous_accepted_until,
+        })
+    }
+
+    fn commit_store(&self) -> Result<&CommitStore> {
+        self.commits.as_ref().ok_or_else(|| {
+            Error::ConfigurationError("Commit history is not configured".to_string())
+        })
+    }
+
+    fn package_store(&self) -> Result<&RepositoryPackageStore> {
+        self.packages.as_ref().ok_or_else(|| {
+            Error::ConfigurationError("Repository packages are not configured".to_string())
         })
     }
+
+    /// Merge the given settings into the repository's re-analysis schedule
+    /// and reschedule its next run accordingly.
+    async fn update_reanalysis_schedule(
+        &self,
+        id: Uuid,
+        schedule: Option<String>,
+        enabled: Option<bool>,
+    ) -> Result<ReanalysisSchedule> {
+        let (store, default_schedule) = self.reanalysis.as_ref().ok_or_else(|| {
+            Error::ConfigurationError("Re-analysis schedules are not configured".to_string())
+        })?;
+        let existing = store.find(id).await?;
+
+        let schedule = match schedule.map(|schedule| schedule.trim().to_string()) {
+            Some(schedule) if schedule.is_empty() => None,
+            Some(schedule) => {
+                parse_schedule(&schedule)?;
+                Some(schedule)
+            }
+            None => existing.as_ref().and_then(|existing| existing.schedule.clone()),
+        };
+        let enabled = enabled
+            .or(existing.as_ref().map(|existing| existing.enabled))
+            .unwrap_or(true);
+        let next_run_at =
+            next_run_after(schedule.as_deref().unwrap_or(default_schedule), chrono::Utc::now())?;
+
+        store.save(id, schedule.as_deref(), enabled, next_run_at).await
+    }
+}
+
+fn matches_import_filters(
+    repo: &OrganizationRepository,
+    request: &OrganizationImportRequest,
+) -> bool {
+    if (repo.fork && !request.include_forks) || (repo.archived && !request.include_archived) {
+        return false;
+    }
+
+    let languages: Vec<&str> = if request.languages.is_empty() && request.topics.is_empty() {
+        DEFAULT_IMPORT_LANGUAGES.to_vec()
+    } else {
+        request.languages.iter().map(String::as_str).collect()
+    };
+    let language_matches = repo.language.as_deref().is_some_and(|language| {
+        languages.iter().any(|wanted| wanted.eq_ignore_ascii_case(language))
+    });
+    let topic_matches = repo
+        .topics
+        .iter()
+        .any(|topic| request.topics.iter().any(|wanted| wanted.eq_ignore_ascii_case(topic)));
+
+    language_matches || topic_matches
 }
 
 // Axum handler functions
@@ -276,4 +731,51 @@ pub async fn update_repository_settings(
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::GitHubUser;
+
+    fn repository(language: Option<&str>, topics: &[&str]) -> OrganizationRepository {
+        OrganizationRepository {
+            id: 1,
+            name: "vault".to_string(),
+            full_name: "acme/vault".to_string(),
+            owner: GitHubUser {
+                id: 2,
+                login: "acme".to_string(),
+                avatar_url: String::new(),
+                html_url: String::new(),
+            },
+            description: None,
+            language: language.map(str::to_string),
+            topics: topics.iter().map(|topic| topic.to_string()).collect(),
+            private: false,
+            fork: false,
+            archived: false,
+            stargazers_count: 0,
+            forks_count: 0,
+            default_branch: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn import_filters_match_languages_or_topics() {
+        let defaults = OrganizationImportRequest::default();
+        assert!(matches_import_filters(&repository(Some("Move"), &[]), &defaults));
+        assert!(!matches_import_filters(&repository(Some("TypeScript"), &[]), &defaults));
+
+        let by_topic = OrganizationImportRequest {
+            topics: vec!["solidity".to_string()],
+            ..Default::default()
+        };
+        assert!(matches_import_filters(&repository(Some("TypeScript"), &["solidity"]), &by_topic));
+        assert!(!matches_import_filters(&repository(Some("Move"), &[]), &by_topic));
+
+        let mut fork = repository(Some("Solidity"), &[]);
+        fork.fork = true;
+        assert!(!matches_import_filters(&fork, &defaults));
+    }
+}
@@ -1,15 +1,26 @@
-use crate::domain::{GitHubWebhookPayload, GitHubEventData, AnalysisJob, AnalysisType, AnalysisPriority, JobStatus};
+use crate::domain::{
+    is_in_scope, GitHubWebhookPayload, GitHubEventData, AnalysisJob, AnalysisType,
+    AnalysisPriority, JobStatus, ProcessingStatus, PullRequestEvent, PullRequestState,
+    RepositoryPackage, SignatureStatus, WebhookDeliveryForCreate, WebhookDeliverySummary,
+    WebhookSecretScope,
+};
 use crate::error::{Error, Result};
-use crate::infrastructure::{GitHubClient, GitHubFile, AnalysisQueueImpl};
-use crate::models::WebhookResponse;
+use crate::infrastructure::{
+    GitHubClient, GitHubFile, AnalysisQueueImpl, PatchPullRequestStore, RepositoryPackageStore,
+    WebhookDeliveryStore, WebhookSecretStore,
+};
+use crate::models::{
+    WebhookDeliveryDetailResponse, WebhookDeliveryListParams, WebhookDeliveryListResponse,
+    WebhookResponse,
+};
 use axum::{
     extract::{State, Json, Query},
-    http::{HeaderMap, StatusCode},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
     response::Json as ResponseJson,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tracing::{error, info, debug};
+use tracing::{error, info, debug, warn};
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
@@ -46,16 +57,88 @@ pub struct ErrorResponse {
 pub struct WebhookHandler {
     github_client: Arc<GitHubClient>,
     analysis_queue: Arc<AnalysisQueueImpl>,
+    deliveries: Option<WebhookDeliveryStore>,
+    packages: Option<RepositoryPackageStore>,
+    secrets: Option<WebhookSecretStore>,
+    patch_pull_requests: Option<PatchPullRequestStore>,
+}
+
+/// Identifies which webhook signed a delivery, read before the signature is
+/// checked.
+#[derive(Deserialize)]
+struct SigningTarget {
+    installation: Option<SigningTargetId>,
+    repository: Option<SigningTargetId>,
+}
+
+#[derive(Deserialize)]
+struct SigningTargetId {
+    id: i64,
+}
+
+/// Why a delivery was not processed: what the sender is told, and the detail
+/// kept in the delivery log.
+struct DeliveryError {
+    status: StatusCode,
+    message: &'static str,
+    detail: String,
 }
 
+impl DeliveryError {
+    fn new(status: StatusCode, message: &'static str, detail: impl ToString) -> Self {
+        Self { status, message, detail: detail.to_string() }
+    }
+
+    fn into_response(self) -> (StatusCode, Json<ErrorResponse>) {
+        (self.status, Json(ErrorResponse { error: self.message.to_string() }))
+    }
+}
+
+const SMART_CONTRACT_EXTENSIONS: [&str; 4] = [".sol", ".rs", ".move", ".vy"];
+
+/// Pull request actions that change what would be merged.
+const PULL_REQUEST_ANALYSIS_ACTIONS: [&str; 4] =
+    ["opened", "synchronize", "reopened", "ready_for_review"];
+
+/// Headers never written to the delivery log.
+const UNLOGGED_HEADERS: [&str; 3] = ["authorization", "cookie", "proxy-authorization"];
+
 impl WebhookHandler {
     pub fn new(github_client: Arc<GitHubClient>, analysis_queue: Arc<AnalysisQueueImpl>) -> Self {
         Self {
             github_client,
             analysis_queue,
+            deliveries: None,
+            packages: None,
+            secrets: None,
+            patch_pull_requests: None,
         }
     }
 
+    /// Record every delivery, with its outcome, in `webhook_deliveries`.
+    pub fn with_delivery_log(mut self, deliveries: WebhookDeliveryStore) -> Self {
+        self.deliveries = Some(deliveries);
+        self
+    }
+
+    /// Ignore changes confined to packages excluded in repository settings.
+    pub fn with_package_scopes(mut self, packages: RepositoryPackageStore) -> Self {
+        self.packages = Some(packages);
+        self
+    }
+
+    /// Verify deliveries from rotated webhooks against their own secrets.
+    pub fn with_secret_store(mut self, secrets: WebhookSecretStore) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Track the state of pull requests opened from patch proposals.
+    pub fn with_patch_pull_requests(mut self, patch_pull_requests: PatchPullRequestStore) -> Self {
+        self.patch_pull_requests = Some(patch_pull_requests);
+        self
+    }
+
     pub async fn handle_webhook(
         &self,
         headers: HeaderMap,
@@ -64,44 +147,162 @@ impl WebhookHandler {
     ) -> std::result::Result<WebhookProcessingResponse, (StatusCode, Json<ErrorResponse>)> {
         info!("Received GitHub webhook");
 
-        // Verify webhook signature
-        let signature = headers
+        let (_, result) = self.handle_delivery(headers, query, payload, None).await;
+        result.map_err(DeliveryError::into_response)
+    }
+
+    /// Re-run a stored delivery through the same checks and processing as
+    /// the original. The replay is logged as a new delivery.
+    pub async fn replay_delivery(&self, id: Uuid) -> Result<WebhookDeliverySummary> {
+        let deliveries = self.delivery_log()?;
+        let original = deliveries.find(id).await?.ok_or(Error::DeliveryNotFound(id))?;
+        if original.summary.signature_status != SignatureStatus::Valid {
+            return Err(Error::DeliveryNotReplayable(
+                "the original signature was not valid".to_string(),
+            ));
+        }
+
+        info!("Replaying webhook delivery {}", id);
+        let installation_id = original.summary.installation_id.map(|id| id as u64);
+        let query = Query(WebhookQuery { installation_id });
+        let headers = headers_from_log(&original.headers);
+        let (replay_id, _) = self.handle_delivery(headers, query, original.payload, Some(id)).await;
+
+        let replay_id = replay_id
+            .ok_or_else(|| Error::Internal("Replay was not recorded".to_string()))?;
+        deliveries
+            .find(replay_id)
+            .await?
+            .map(|delivery| delivery.summary)
+            .ok_or(Error::DeliveryNotFound(replay_id))
+    }
+
+    pub async fn list_deliveries(
+        &self,
+        params: WebhookDeliveryListParams,
+    ) -> Result<WebhookDeliveryListResponse> {
+        let limit = params.limit.unwrap_or(50).clamp(1, 200);
+        let offset = params.offset.unwrap_or(0).max(0);
+
+        let (deliveries, total_count) = self
+            .delivery_log()?
+            .list(params.event_type.as_deref(), params.status, limit, offset)
+            .await?;
+
+        Ok(WebhookDeliveryListResponse {
+            deliveries,
+            total_count,
+            limit,
+            offset,
+        })
+    }
+
+    pub async fn get_delivery(&self, id: Uuid) -> Result<WebhookDeliveryDetailResponse> {
+        let delivery = self.delivery_log()?.find(id).await?.ok_or(Error::DeliveryNotFound(id))?;
+        let payload = serde_json::from_slice(&delivery.payload).unwrap_or_else(|_| {
+            serde_json::Value::String(String::from_utf8_lossy(&delivery.payload).into_owned())
+        });
+
+        Ok(WebhookDeliveryDetailResponse {
+            delivery: delivery.summary,
+            headers: delivery.headers,
+            payload,
+        })
+    }
+
+    fn delivery_log(&self) -> Result<&WebhookDeliveryStore> {
+        self.deliveries.as_ref().ok_or_else(|| {
+            Error::ConfigurationError("Webhook delivery log is not configured".to_string())
+        })
+    }
+
+    /// Verify, log and process one delivery. Returns the log row id, if the
+    /// delivery could be recorded, alongside the outcome.
+    async fn handle_delivery(
+        &self,
+        headers: HeaderMap,
+        query: Query<WebhookQuery>,
+        payload: Vec<u8>,
+        replay_of: Option<Uuid>,
+    ) -> (Option<Uuid>, std::result::Result<WebhookProcessingResponse, DeliveryError>) {
+        let signature_status = self.check_signature(&headers, &query, &payload).await;
+        let delivery_id = self
+            .record_delivery(&headers, &query, &payload, signature_status, replay_of)
+            .await;
+
+        let result = match signature_status {
+            SignatureStatus::Valid => self.dispatch(&headers, &query, &payload).await,
+            SignatureStatus::Missing => {
+                error!("Missing webhook signature");
+                Err(DeliveryError::new(
+                    StatusCode::BAD_REQUEST,
+                    "Missing webhook signature",
+                    "no X-Hub-Signature-256 or X-Hub-Signature header",
+                ))
+            }
+            SignatureStatus::Invalid => Err(DeliveryError::new(
+                StatusCode::UNAUTHORIZED,
+                "Invalid webhook signature",
+                "signature does not match the webhook secret",
+            )),
+        };
+
+        if let Some(id) = delivery_id {
+            self.record_outcome(id, &result).await;
+        }
+        (delivery_id, result)
+    }
+
+    async fn check_signature(
+        &self,
+        headers: &HeaderMap,
+        query: &Query<WebhookQuery>,
+        payload: &[u8],
+    ) -> SignatureStatus {
+        let Some(signature) = headers
             .get("X-Hub-Signature-256")
             .or_else(|| headers.get("X-Hub-Signature"))
             .and_then(|v| v.to_str().ok())
-            .ok_or_else(|| {
-                error!("Missing webhook signature");
-                (StatusCode::BAD_REQUEST, Json(ErrorResponse {
-                    error: "Missing webhook signature".to_string(),
-                }))
-            })?;
+        else {
+            return SignatureStatus::Missing;
+        };
 
-        if !self.github_client.verify_webhook_signature(&payload, signature)
-            .map_err(|e| {
-                error!("Webhook signature verification failed: {}", e);
-                (StatusCode::UNAUTHORIZED, Json(ErrorResponse {
-                    error: "Invalid webhook signature".to_string(),
-                }))
-            })? {
-            return Err((StatusCode::UNAUTHORIZED, Json(ErrorResponse {
-                error: "Invalid webhook signature".to_string(),
-            })));
+        let secrets = self.accepted_secrets(query, payload).await;
+        if self.github_client.verify_webhook_signature_with(&secrets, payload, signature) {
+            SignatureStatus::Valid
+        } else {
+            SignatureStatus::Invalid
+        }
+    }
+
+    /// Secrets the delivery may be signed with: those of its webhook once
+    /// it has been rotated, otherwise the configured ones.
+    async fn accepted_secrets(&self, query: &Query<WebhookQuery>, payload: &[u8]) -> Vec<String> {
+        let scope = signing_scope(query, payload);
+        if let (Some(store), Some((scope, scope_id))) = (&self.secrets, scope) {
+            match store.accepted(scope, scope_id).await {
+                Ok(secrets) if !secrets.is_empty() => return secrets,
+                Ok(_) => {}
+                Err(e) => error!("Failed to load webhook secrets: {}", e),
+            }
         }
+        self.github_client.webhook_secrets()
+    }
 
+    async fn dispatch(
+        &self,
+        headers: &HeaderMap,
+        query: &Query<WebhookQuery>,
+        payload: &[u8],
+    ) -> std::result::Result<WebhookProcessingResponse, DeliveryError> {
         // Parse webhook payload
-        let webhook_payload: GitHubWebhookPayload = serde_json::from_slice(&payload)
+        let webhook_payload: GitHubWebhookPayload = serde_json::from_slice(payload)
             .map_err(|e| {
                 error!("Failed to parse webhook payload: {}", e);
-                (StatusCode::BAD_REQUEST, Json(ErrorResponse {
-                    error: "Invalid JSON payload".to_string(),
-                }))
+                DeliveryError::new(StatusCode::BAD_REQUEST, "Invalid JSON payload", e)
             })?;
 
-        // Get event type from headers
-        let event_type = headers
-            .get("X-GitHub-Event")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("unknown");
+        let event_type = event_type(headers);
 
         info!(
             "Processing {} event for repository: {}",
@@ -110,12 +311,14 @@ impl WebhookHandler {
         );
 
         // Process the webhook event
-        let processing_id = self.process_webhook_event(event_type, &webhook_payload, &query).await
+        let processing_id = self.process_webhook_event(event_type, &webhook_payload, query).await
             .map_err(|e| {
                 error!("Failed to process webhook event: {}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse {
-                    error: "Failed to process webhook event".to_string(),
-                }))
+                DeliveryError::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to process webhook event",
+                    e,
+                )
             })?;
 
         Ok(WebhookProcessingResponse {
@@ -125,6 +328,58 @@ impl WebhookHandler {
         })
     }
 
+    async fn record_delivery(
+        &self,
+        headers: &HeaderMap,
+        query: &Query<WebhookQuery>,
+        payload: &[u8],
+        signature_status: SignatureStatus,
+        replay_of: Option<Uuid>,
+    ) -> Option<Uuid> {
+        let deliveries = self.deliveries.as_ref()?;
+        let delivery = WebhookDeliveryForCreate {
+            github_delivery_id: headers
+                .get("X-GitHub-Delivery")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+            event_type: event_type(headers).to_string(),
+            installation_id: query.installation_id,
+            headers: headers_for_log(headers),
+            payload: payload.to_vec(),
+            signature_status,
+            replay_of,
+        };
+
+        // The log is for debugging; never let it block processing
+        deliveries
+            .record(delivery)
+            .await
+            .map_err(|e| error!("Failed to record webhook delivery: {}", e))
+            .ok()
+    }
+
+    async fn record_outcome(
+        &self,
+        id: Uuid,
+        result: &std::result::Result<WebhookProcessingResponse, DeliveryError>,
+    ) {
+        let Some(deliveries) = &self.deliveries else {
+            return;
+        };
+
+        let (status, processing_id, error_message) = match result {
+            Ok(response) => (ProcessingStatus::Processed, Some(response.processing_id), None),
+            Err(e) if e.status.is_client_error() => {
+                (ProcessingStatus::Rejected, None, Some(e.detail.as_str()))
+            }
+            Err(e) => (ProcessingStatus::Failed, None, Some(e.detail.as_str())),
+        };
+
+        if let Err(e) = deliveries.record_outcome(id, status, processing_id, error_message).await {
+            error!("Failed to record outcome of webhook delivery {}: {}", id, e);
+        }
+    }
+
     async fn process_webhook_event(
         &self,
         event_type: &str,
@@ -165,23 +420,18 @@ impl WebhookHandler {
             .or_else(|| push_event.commits.last())
             .ok_or_else(|| Error::Internal("No head commit found".to_string()))?;
 
-        // Check if any smart contract files were modified
-        let mut smart_contract_files_changed = false;
+        // Check if any smart contract files were modified in included packages
         let smart_contract_extensions = [".sol", ".rs", ".move", ".vy"];
+        let packages = self.packages_of(payload.repository.id).await;
 
-        for file_path in &head_commit.added {
-            if smart_contract_extensions.iter().any(|ext| file_path.ends_with(ext)) {
-                smart_contract_files_changed = true;
-                break;
-            }
-        }
-
-        for file_path in &head_commit.modified {
-            if smart_contract_extensions.iter().any(|ext| file_path.ends_with(ext)) {
-                smart_contract_files_changed = true;
-                break;
-            }
-        }
+        let smart_contract_files_changed = head_commit
+            .added
+            .iter()
+            .chain(&head_commit.modified)
+            .any(|file_path| {
+                smart_contract_extensions.iter().any(|ext| file_path.ends_with(ext))
+                    && is_in_scope(file_path, &packages)
+            });
 
         if !smart_contract_files_changed {
             info!("No smart contract files changed, skipping analysis");
@@ -209,7 +459,11 @@ impl WebhookHandler {
             &smart_contract_extensions,
         ).await?;
 
-        let smart_contract_files = self.github_client.detect_smart_contract_files(&files);
+        let smart_contract_files: Vec<&GitHubFile> = self.github_client
+            .detect_smart_contract_files(&files)
+            .into_iter()
+            .filter(|file| is_in_scope(&file.path, &packages))
+            .collect();
 
         if smart_contract_files.is_empty() {
             info!("No smart contract files found, skipping analysis");
@@ -236,6 +490,8 @@ impl WebhookHandler {
         let domain_job = AnalysisJob {
             id: analysis_job.id,
             repository_id: payload.repository.id,
+            installation_id: Some(analysis_job.installation_id),
+            pull_request_number: None,
             commit_sha: analysis_job.commit_sha.clone(),
             files_to_analyze: analysis_job.files.iter().map(|f| f.path.clone()).collect(),
             analysis_type: AnalysisType::SmartContract,
@@ -256,6 +512,8 @@ impl WebhookHandler {
         Ok(job_id)
     }
 
+    /// Queue an analysis of just the smart contract files a pull request
+    /// touches. The worker posts the findings back as a review.
     async fn handle_pull_request_event(
         &self,
         payload: &GitHubWebhookPayload,
@@ -266,6 +524,14 @@ impl WebhookHandler {
             _ => return Ok(Uuid::new_v4()),
         };
 
+        self.track_patch_pull_request(payload, pr_event).await;
+
+        let action = payload.action.as_deref().unwrap_or_default();
+        if !PULL_REQUEST_ANALYSIS_ACTIONS.contains(&action) {
+            debug!("Ignoring pull request action: {}", action);
+            return Ok(Uuid::new_v4());
+        }
+
         info!("Processing pull request event: {} for {}", pr_event.number, payload.repository.full_name);
 
         let installation_id = query.installation_id
@@ -278,33 +544,39 @@ impl WebhookHandler {
         let owner = owner_parts[0];
         let repo = owner_parts[1];
 
-        let files = self.github_client.get_repository_files(
-            installation_id,
-            owner,
-            repo,
-            Some(&pr_event.pull_request.head.sha),
-            &[".sol", ".rs", ".move", ".vy"],
-        ).await?;
-
-        let smart_contract_files = self.github_client.detect_smart_contract_files(&files);
-
-        if !smart_contract_files.is_empty() {
-            let analysis_job = AnalysisJob {
-                id: Uuid::new_v4(),
-                repository_id: payload.repository.id,
-                commit_sha: pr_event.pull_request.head.sha.clone(),
-                files_to_analyze: smart_contract_files.iter().map(|f| f.path.clone()).collect(),
-                analysis_type: AnalysisType::SecurityFocus,
-                priority: AnalysisPriority::High, // PRs get higher priority
-                created_at: chrono::Utc::now(),
-                status: JobStatus::Queued,
-            };
-
-            let job_id = self.analysis_queue.enqueue(analysis_job).await?;
-            return Ok(job_id);
+        let packages = self.packages_of(payload.repository.id).await;
+        let changed_files = self.github_client
+            .list_pull_request_files(installation_id, owner, repo, pr_event.number)
+            .await?
+            .into_iter()
+            .filter(|file| file.status != "removed")
+            .map(|file| file.filename)
+            .filter(|path| SMART_CONTRACT_EXTENSIONS.iter().any(|ext| path.ends_with(ext)))
+            .filter(|path| is_in_scope(path, &packages))
+            .collect::<Vec<_>>();
+
+        if changed_files.is_empty() {
+            info!(
+                "Pull request #{} changes no smart contract files, skipping analysis",
+                pr_event.number
+            );
+            return Ok(Uuid::new_v4());
         }
 
-        Ok(Uuid::new_v4())
+        let analysis_job = AnalysisJob {
+            id: Uuid::new_v4(),
+            repository_id: payload.repository.id,
+            installation_id: Some(installation_id),
+            pull_request_number: Some(pr_event.number),
+            commit_sha: pr_event.pull_request.head.sha.clone(),
+            files_to_analyze: changed_files,
+            analysis_type: AnalysisType::SecurityFocus,
+            priority: AnalysisPriority::High, // PRs get higher priority
+            created_at: chrono::Utc::now(),
+            status: JobStatus::Queued,
+        };
+
+        self.analysis_queue.enqueue(analysis_job).await
     }
 
     async fn handle_repository_event(
@@ -321,6 +593,46 @@ impl WebhookHandler {
         Ok(Uuid::new_v4())
     }
 
+    /// Record the pull request's state on the patch it was opened from, if
+    /// any. Failures are logged; they must not hold up the analysis.
+    async fn track_patch_pull_request(
+        &self,
+        payload: &GitHubWebhookPayload,
+        pr_event: &PullRequestEvent,
+    ) {
+        let Some(store) = &self.patch_pull_requests else {
+            return;
+        };
+        let pull_request = &pr_event.pull_request;
+        let merged = pull_request.merged_at.is_some();
+        let state = PullRequestState::from_github(&pull_request.state, merged);
+        match store.record_state(payload.repository.id as i64, pr_event.number, state).await {
+            Ok(true) => info!(
+                "Patch pull request {}#{} is {}",
+                payload.repository.full_name,
+                pr_event.number,
+                state.as_str()
+            ),
+            Ok(false) => {}
+            Err(e) => warn!(
+                "Failed to record state of pull request {}#{}: {}",
+                payload.repository.full_name, pr_event.number, e
+            ),
+        }
+    }
+
+    /// The repository's tracked packages. With none known, or if they cannot
+    /// be loaded, every file is in scope.
+    async fn packages_of(&self, github_repo_id: u64) -> Vec<RepositoryPackage> {
+        let Some(store) = &self.packages else {
+            return Vec::new();
+        };
+        store.list_for_github_repo(github_repo_id as i64).await.unwrap_or_else(|e| {
+            warn!("Failed to load packages of repository {}: {}", github_repo_id, e);
+            Vec::new()
+        })
+    }
+
     fn determine_analysis_priority(&self, files: &[&GitHubFile]) -> AnalysisPriority {
         // Prioritize based on file types and content
         for file in files {
@@ -362,6 +674,54 @@ impl WebhookHandler {
     }
 }
 
+/// The webhook a delivery came from: the app's for installation events,
+/// otherwise the repository's.
+fn signing_scope(
+    query: &Query<WebhookQuery>,
+    payload: &[u8],
+) -> Option<(WebhookSecretScope, i64)> {
+    let target: SigningTarget = serde_json::from_slice(payload).ok()?;
+    if query.installation_id.is_some() || target.installation.is_some() {
+        return Some((WebhookSecretScope::App, 0));
+    }
+    target.repository.map(|repository| (WebhookSecretScope::Repository, repository.id))
+}
+
+fn event_type(headers: &HeaderMap) -> &str {
+    headers
+        .get("X-GitHub-Event")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("unknown")
+}
+
+fn headers_for_log(headers: &HeaderMap) -> serde_json::Value {
+    let logged = headers
+        .iter()
+        .filter(|(name, _)| !UNLOGGED_HEADERS.contains(&name.as_str()))
+        .filter_map(|(name, value)| {
+            let value = value.to_str().ok()?;
+            Some((name.to_string(), serde_json::Value::String(value.to_string())))
+        })
+        .collect();
+    serde_json::Value::Object(logged)
+}
+
+fn headers_from_log(logged: &serde_json::Value) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(logged) = logged.as_object() {
+        for (name, value) in logged {
+            let (Ok(name), Some(Ok(value))) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                value.as_str().map(HeaderValue::from_str),
+            ) else {
+                continue;
+            };
+            headers.insert(name, value);
+        }
+    }
+    headers
+}
+
 // Axum handler function
 pub async fn handle_webhook_endpoint(
     State(webhook_handler): State<Arc<WebhookHandler>>,
@@ -1,5 +1,11 @@
+pub mod analysis_worker;
+pub mod commit_ingestion;
 pub mod handlers;
+pub mod reanalysis_scheduler;
 pub mod use_cases;
 
+pub use analysis_worker::*;
+pub use commit_ingestion::*;
 pub use handlers::*;
+pub use reanalysis_scheduler::*;
 pub use use_cases::*;
\ No newline at end of file
@@ -0,0 +1,127 @@
+use crate::domain::{
+    next_run_after, AnalysisJob, AnalysisPriority, AnalysisType, DueReanalysis, JobStatus,
+    ReanalysisSettings,
+};
+use crate::error::Result;
+use crate::infrastructure::{AnalysisQueueImpl, GitHubClient, ReanalysisScheduleStore};
+use chrono::Utc;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// How long a claimed schedule is hidden from other instances. A schedule
+/// whose run fails is retried once this has passed.
+const CLAIM_LEASE: Duration = Duration::from_secs(15 * 60);
+
+/// What one pass of the scheduler did.
+#[derive(Debug, Default, Serialize)]
+pub struct ReanalysisRun {
+    pub enqueued: usize,
+    /// Due repositories whose HEAD had not moved since their last run.
+    pub unchanged: usize,
+    pub failed: usize,
+}
+
+/// Re-queues analyses of tracked repositories on their cron schedules. Safe
+/// to run from several instances at once: due schedules are claimed before
+/// they are acted on.
+pub struct ReanalysisScheduler {
+    github_client: Arc<GitHubClient>,
+    queue: Arc<AnalysisQueueImpl>,
+    store: ReanalysisScheduleStore,
+    settings: ReanalysisSettings,
+}
+
+impl ReanalysisScheduler {
+    pub fn new(
+        github_client: Arc<GitHubClient>,
+        queue: Arc<AnalysisQueueImpl>,
+        store: ReanalysisScheduleStore,
+        settings: ReanalysisSettings,
+    ) -> Self {
+        Self {
+            github_client,
+            queue,
+            store,
+            settings,
+        }
+    }
+
+    /// Queue a re-analysis for each due repository whose HEAD has moved,
+    /// keeping at most `max_in_flight` scheduled analyses outstanding.
+    pub async fn run_once(&self) -> Result<ReanalysisRun> {
+        let default_next = next_run_after(&self.settings.default_schedule, Utc::now())?;
+        let created = self.store.ensure_schedules(default_next).await?;
+        if created > 0 {
+            info!("Scheduled re-analysis for {} newly tracked repositories", created);
+        }
+
+        let mut run = ReanalysisRun::default();
+        let capacity = self.settings.max_in_flight as i64 - self.store.in_flight().await?;
+        if capacity <= 0 {
+            info!("Scheduled analyses at capacity; not claiming more");
+            return Ok(run);
+        }
+
+        for due in self.store.claim_due(capacity, CLAIM_LEASE).await? {
+            match self.reanalyze(&due).await {
+                Ok((head_sha, enqueued)) => {
+                    let schedule =
+                        due.schedule.as_deref().unwrap_or(&self.settings.default_schedule);
+                    let next_run_at = next_run_after(schedule, Utc::now()).unwrap_or(default_next);
+                    self.store
+                        .record_run(due.repository_id, Some(&head_sha), enqueued, next_run_at)
+                        .await?;
+                    if enqueued {
+                        run.enqueued += 1;
+                    } else {
+                        run.unchanged += 1;
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "Scheduled re-analysis of {}/{} failed: {}",
+                        due.owner_username, due.repo_name, e
+                    );
+                    run.failed += 1;
+                }
+            }
+        }
+
+        Ok(run)
+    }
+
+    /// Look up the repository's HEAD and queue it unless it was the last one
+    /// analysed. Returns the HEAD and whether a job was queued.
+    async fn reanalyze(&self, due: &DueReanalysis) -> Result<(String, bool)> {
+        let head_sha = self
+            .github_client
+            .get_head_commit_sha(&due.owner_username, &due.repo_name)
+            .await?;
+        if due.last_head_sha.as_deref() == Some(head_sha.as_str()) {
+            return Ok((head_sha, false));
+        }
+
+        let job = AnalysisJob {
+            id: Uuid::new_v4(),
+            repository_id: due.github_repo_id as u64,
+            installation_id: None,
+            pull_request_number: None,
+            commit_sha: head_sha.clone(),
+            files_to_analyze: vec![],
+            analysis_type: AnalysisType::Scheduled,
+            priority: AnalysisPriority::Low,
+            created_at: Utc::now(),
+            status: JobStatus::Queued,
+        };
+        let job_id = self.queue.enqueue(job).await?;
+
+        info!(
+            "Queued scheduled re-analysis {} of {}/{} at {}",
+            job_id, due.owner_username, due.repo_name, head_sha
+        );
+        Ok((head_sha, true))
+    }
+}
@@ -55,6 +55,8 @@ impl GitHubUseCase {
         let analysis_job = AnalysisJob {
             id: Uuid::new_v4(),
             repository_id,
+            installation_id: None,
+            pull_request_number: None,
             commit_sha: "HEAD".to_string(), // Will be resolved during analysis
             files_to_analyze: vec![],
             analysis_type,
@@ -63,6 +65,7 @@ impl GitHubUseCase {
                 AnalysisType::SecurityFocus => AnalysisPriority::Critical,
                 AnalysisType::SmartContract => AnalysisPriority::Normal,
                 AnalysisType::FullAnalysis => AnalysisPriority::High,
+                AnalysisType::Scheduled => AnalysisPriority::Low,
             },
             created_at: chrono::Utc::now(),
             status: JobStatus::Queued,
@@ -75,7 +78,7 @@ impl GitHubUseCase {
     }
 
     pub async fn get_analysis_status(&self, job_id: Uuid) -> Result<Option<JobStatus>> {
-        Ok(self.analysis_queue.get_job_status(job_id).await)
+        self.analysis_queue.get_job_status(job_id).await
     }
 
     pub async fn cancel_analysis(&self, job_id: Uuid) -> Result<()> {
@@ -83,7 +86,7 @@ impl GitHubUseCase {
     }
 
     pub async fn get_queue_metrics(&self) -> Result<crate::domain::QueueStatus> {
-        Ok(self.analysis_queue.get_queue_status().await)
+        self.analysis_queue.get_queue_status().await
     }
 
     pub async fn validate_repository_access(&self, owner: &str, repo: &str) -> Result<bool> {
@@ -1,11 +1,22 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::time::Duration;
+
+/// Scope required to cancel analysis jobs or change their priority.
+pub const SCOPE_ANALYSIS_JOBS_ADMIN: &str = "analysis_jobs:admin";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisJob {
     pub id: Uuid,
     pub repository_id: u64,
+    /// GitHub App installation the repository's files are fetched through.
+    #[serde(default)]
+    pub installation_id: Option<u64>,
+    /// Set for pull request analyses, whose results are posted as a review.
+    #[serde(default)]
+    pub pull_request_number: Option<u64>,
     pub commit_sha: String,
     pub files_to_analyze: Vec<String>,
     pub analysis_type: AnalysisType,
@@ -14,15 +25,19 @@ pub struct AnalysisJob {
     pub status: JobStatus,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
 pub enum AnalysisType {
     InitialScan,
     SmartContract,
     SecurityFocus,
     FullAnalysis,
+    /// Periodic re-analysis of a tracked repository.
+    Scheduled,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, sqlx::Type)]
+#[repr(i16)]
 pub enum AnalysisPriority {
     Low = 1,
     Normal = 2,
@@ -30,17 +45,142 @@ pub enum AnalysisPriority {
     Critical = 4,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
 pub enum JobStatus {
     Queued,
     Processing,
     Completed,
     Failed,
+    /// Out of attempts; kept for inspection until requeued by hand.
+    DeadLettered,
+    /// Withdrawn by a user before it finished.
+    Cancelled,
 }
 
 #[derive(Debug, Serialize)]
 pub struct QueueStatus {
     pub queued_jobs: usize,
     pub processing_jobs: usize,
+    pub dead_lettered_jobs: usize,
     pub total_jobs: usize,
-}
\ No newline at end of file
+}
+
+/// A job as reported to users: where it stands and, while queued, when it
+/// is expected to start.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisJobDetail {
+    #[serde(flatten)]
+    pub job: AnalysisJob,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Earliest time the job may next be leased.
+    pub run_after: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// 1 for the next job to be leased. Queued jobs only.
+    pub queue_position: Option<u64>,
+    /// Queued jobs only, and only once some job has completed recently.
+    pub estimated_start_at: Option<DateTime<Utc>>,
+}
+
+/// When the job at `queue_position` should start, if jobs keep completing
+/// at the rate of `completed` per `window`. Never before `run_after`.
+pub fn estimate_start(
+    queue_position: u64,
+    completed: u64,
+    window: Duration,
+    run_after: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if completed == 0 {
+        return None;
+    }
+    let jobs_ahead = queue_position.saturating_sub(1);
+    let wait_secs = window.as_secs_f64() * jobs_ahead as f64 / completed as f64;
+    let eta = now + chrono::Duration::milliseconds((wait_secs * 1000.0) as i64);
+    Some(eta.max(run_after))
+}
+
+/// A job held by one worker until `leased_until`. Once the lease lapses the
+/// job becomes visible to other workers again.
+#[derive(Debug, Clone)]
+pub struct LeasedJob {
+    pub job: AnalysisJob,
+    pub lease_id: Uuid,
+    /// 1 for the first run.
+    pub attempt: u32,
+    pub leased_until: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnalysisQueueSettings {
+    /// Queued jobs beyond which `enqueue` refuses new work.
+    pub max_queue_size: usize,
+    /// How long a lease hides a job from other workers.
+    pub visibility_timeout: Duration,
+    /// Runs before a failing job is dead-lettered.
+    pub max_attempts: u32,
+    pub backoff_base: Duration,
+    pub backoff_max: Duration,
+}
+
+impl Default for AnalysisQueueSettings {
+    fn default() -> Self {
+        Self {
+            max_queue_size: 1000,
+            visibility_timeout: Duration::from_secs(10 * 60),
+            max_attempts: 5,
+            backoff_base: Duration::from_secs(30),
+            backoff_max: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+impl AnalysisQueueSettings {
+    /// Delay before retrying after the given (1-based) failed attempt:
+    /// `backoff_base * 2^(attempt - 1)`, capped at `backoff_max`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        self.backoff_base
+            .checked_mul(1u32 << exponent)
+            .map_or(self.backoff_max, |delay| delay.min(self.backoff_max))
+    }
+}
+
+/// Runs a leased job. Errors are retried with backoff until the job runs out
+/// of attempts.
+pub trait AnalysisJobProcessor: Send + Sync {
+    fn process(&self, job: &AnalysisJob) -> impl Future<Output = crate::Result<()>> + Send;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_per_attempt_up_to_the_cap() {
+        let settings = AnalysisQueueSettings::default();
+
+        assert_eq!(settings.backoff(1), Duration::from_secs(30));
+        assert_eq!(settings.backoff(2), Duration::from_secs(60));
+        assert_eq!(settings.backoff(4), Duration::from_secs(240));
+        assert_eq!(settings.backoff(20), Duration::from_secs(60 * 60));
+        assert_eq!(settings.backoff(u32::MAX), Duration::from_secs(60 * 60));
+    }
+
+    #[test]
+    fn start_estimates_follow_recent_throughput() {
+        let now = Utc::now();
+        let hour = Duration::from_secs(60 * 60);
+
+        assert_eq!(estimate_start(1, 12, hour, now, now), Some(now));
+        assert_eq!(
+            estimate_start(7, 12, hour, now, now),
+            Some(now + chrono::Duration::minutes(30))
+        );
+        let later = now + chrono::Duration::hours(2);
+        assert_eq!(estimate_start(7, 12, hour, later, now), Some(later));
+        assert_eq!(estimate_start(3, 0, hour, now, now), None);
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// A commit of a tracked repository, as ingested from GitHub.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct RepositoryCommit {
+    pub repository_id: Uuid,
+    pub sha: String,
+    /// GitHub account of the author, when their email maps to one.
+    pub author_login: Option<String>,
+    pub author_name: String,
+    pub author_email: String,
+    pub authored_at: DateTime<Utc>,
+    pub committed_at: DateTime<Utc>,
+    pub message: String,
+    pub files_touched: Vec<String>,
+    pub additions: i32,
+    pub deletions: i32,
+    pub is_merge: bool,
+}
+
+/// A tracked repository whose history is due to be synced.
+#[derive(Debug, Clone, FromRow)]
+pub struct CommitSyncTarget {
+    pub repository_id: Uuid,
+    pub owner_username: String,
+    pub repo_name: String,
+    /// Newest commit ingested so far.
+    pub last_seen_sha: Option<String>,
+}
+
+/// One author's commits to a repository.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ContributorSummary {
+    /// GitHub login, or the commit email for authors without an account.
+    pub author: String,
+    pub has_account: bool,
+    pub commits: i64,
+    pub additions: i64,
+    pub deletions: i64,
+    pub first_commit_at: DateTime<Utc>,
+    pub last_commit_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommitIngestionSettings {
+    /// Repositories synced per run, across all instances.
+    pub repositories_per_run: i64,
+    /// Commits ingested per repository per run. Each costs an API request
+    /// for the files it touched; the rest are picked up on later runs.
+    pub commits_per_repository: usize,
+    /// How far back history is listed: the backfill for newly tracked
+    /// repositories, and the gap beyond which older commits are skipped.
+    pub history_depth: usize,
+    /// Time between syncs of a repository.
+    pub sync_interval: Duration,
+}
+
+impl Default for CommitIngestionSettings {
+    fn default() -> Self {
+        Self {
+            repositories_per_run: 3,
+            commits_per_repository: 30,
+            history_depth: 500,
+            sync_interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Pick the commits to ingest from a newest-first listing: those newer than
+/// `last_seen_sha`, oldest first, at most `limit` of them. Also returns
+/// whether `last_seen_sha` was missing from the listing, so commits between
+/// it and the listing were skipped.
+pub fn commits_to_ingest<'a>(
+    listed: &'a [String],
+    last_seen_sha: Option<&str>,
+    limit: usize,
+) -> (Vec<&'a str>, bool) {
+    let seen_at = last_seen_sha.and_then(|seen| listed.iter().position(|sha| sha == seen));
+    let gap = last_seen_sha.is_some() && seen_at.is_none();
+    let new = &listed[..seen_at.unwrap_or(listed.len())];
+
+    (new.iter().rev().take(limit).map(String::as_str).collect(), gap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ingests_oldest_unseen_commits_first() {
+        let listed: Vec<String> = ["e", "d", "c", "b", "a"].map(String::from).to_vec();
+
+        assert_eq!(commits_to_ingest(&listed, Some("b"), 2), (vec!["c", "d"], false));
+        assert_eq!(commits_to_ingest(&listed, Some("e"), 2), (vec![], false));
+        assert_eq!(commits_to_ingest(&listed, None, 10), (vec!["a", "b", "c", "d", "e"], false));
+        assert_eq!(commits_to_ingest(&listed, Some("z"), 1), (vec!["a"], true));
+    }
+}
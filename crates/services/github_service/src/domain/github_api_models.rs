@@ -25,6 +25,127 @@ pub struct GitHubUser {
     pub html_url: String,
 }
 
+/// One entry of `GET /repos/{owner}/{repo}/pulls/{number}/files`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestFile {
+    pub filename: String,
+    /// `added`, `modified`, `removed`, `renamed`, ...
+    pub status: String,
+    pub additions: u32,
+    pub deletions: u32,
+    pub changes: u32,
+    /// Unified diff hunk; absent for binary or very large files.
+    pub patch: Option<String>,
+    /// The path before a rename.
+    #[serde(default)]
+    pub previous_filename: Option<String>,
+}
+
+/// `GET /repos/{owner}/{repo}/compare/{base}...{head}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitComparison {
+    /// `ahead`, `behind`, `identical` or `diverged`: where head stands
+    /// relative to base.
+    pub status: String,
+    pub ahead_by: u32,
+    pub behind_by: u32,
+    /// At most `MAX_COMPARISON_FILES` files; larger comparisons are cut
+    /// short.
+    #[serde(default)]
+    pub files: Vec<PullRequestFile>,
+}
+
+/// GitHub lists at most this many files in a comparison.
+pub const MAX_COMPARISON_FILES: usize = 300;
+
+impl CommitComparison {
+    /// Whether head is base plus new commits, so the files listed are every
+    /// change between them. False after a force push or for a comparison
+    /// too large to list in full.
+    pub fn is_complete(&self) -> bool {
+        matches!(self.status.as_str(), "ahead" | "identical")
+            && self.files.len() < MAX_COMPARISON_FILES
+    }
+}
+
+/// One entry of `GET /orgs/{org}/repos`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationRepository {
+    pub id: u64,
+    pub name: String,
+    pub full_name: String,
+    pub owner: GitHubUser,
+    pub description: Option<String>,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
+    pub archived: bool,
+    pub stargazers_count: u32,
+    pub forks_count: u32,
+    pub default_branch: String,
+}
+
+/// `GET /repos/{owner}/{repo}/commits/{sha}`. List responses have the same
+/// shape without `files` and `stats`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubCommit {
+    pub sha: String,
+    pub commit: GitCommitData,
+    /// The GitHub account the author email maps to, if any.
+    pub author: Option<GitHubCommitAccount>,
+    pub committer: Option<GitHubCommitAccount>,
+    #[serde(default)]
+    pub parents: Vec<GitCommitParent>,
+    #[serde(default)]
+    pub files: Vec<GitHubCommitFile>,
+    pub stats: Option<GitHubCommitStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitCommitData {
+    pub author: Option<GitSignature>,
+    pub committer: Option<GitSignature>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitSignature {
+    pub name: String,
+    pub email: String,
+    pub date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubCommitAccount {
+    pub id: u64,
+    pub login: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitCommitParent {
+    pub sha: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubCommitFile {
+    pub filename: String,
+    #[serde(default)]
+    pub additions: u32,
+    #[serde(default)]
+    pub deletions: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubCommitStats {
+    pub additions: u32,
+    pub deletions: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitHubContent {
     pub name: String,
@@ -2,8 +2,24 @@ pub mod github_api_models;
 pub mod analysis_queue;
 pub mod rate_limiter;
 pub mod webhook_models;
+pub mod webhook_delivery;
+pub mod reanalysis;
+pub mod repository_clone;
+pub mod repository_package;
+pub mod commit_history;
+pub mod webhook_secret;
+pub mod pull_request;
+pub mod package_deployment;
 
 pub use github_api_models::*;
 pub use analysis_queue::*;
 pub use rate_limiter::*;
-pub use webhook_models::*;
\ No newline at end of file
+pub use webhook_models::*;
+pub use webhook_delivery::*;
+pub use reanalysis::*;
+pub use repository_clone::*;
+pub use repository_package::*;
+pub use commit_history::*;
+pub use webhook_secret::*;
+pub use pull_request::*;
+pub use package_deployment::*;
\ No newline at end of file
@@ -0,0 +1,232 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use std::collections::HashMap;
+use std::future::Future;
+use uuid::Uuid;
+
+/// Where a deployment's on-chain bytecode stands against the repository's
+/// source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    Unverified,
+    /// Every module built from the source matches its on-chain bytecode.
+    Verified,
+    Mismatch,
+    /// The source could not be fetched or built.
+    Failed,
+}
+
+/// A Move package a tracked repository has published.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct PackageDeployment {
+    pub id: Uuid,
+    pub repository_id: Uuid,
+    /// Directory of the package's `Move.toml`; empty for the repository root.
+    pub package_path: String,
+    pub network: String,
+    pub package_id: String,
+    /// Names of the modules found on-chain.
+    pub modules: Vec<String>,
+    pub modules_fetched_at: Option<DateTime<Utc>>,
+    pub verification_status: VerificationStatus,
+    pub verified_commit_sha: Option<String>,
+    pub verification_message: Option<String>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The compiled bytecode of one module, on-chain or built from source.
+#[derive(Debug, Clone)]
+pub struct ModuleBytecode {
+    pub name: String,
+    pub bytecode: Vec<u8>,
+}
+
+/// A module as published on-chain.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeployedModule {
+    pub name: String,
+    pub size_bytes: usize,
+    /// Hex SHA-256 of the bytecode.
+    pub sha256: String,
+}
+
+impl From<&ModuleBytecode> for DeployedModule {
+    fn from(module: &ModuleBytecode) -> Self {
+        Self {
+            name: module.name.clone(),
+            size_bytes: module.bytecode.len(),
+            sha256: hex::encode(Sha256::digest(&module.bytecode)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleMatch {
+    Matches,
+    Differs,
+    /// Published, but not built from the source.
+    OnlyOnChain,
+    /// Built from the source, but not published.
+    OnlyInSource,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleVerification {
+    pub name: String,
+    pub result: ModuleMatch,
+}
+
+/// The outcome of checking a deployment against the source at a commit.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeploymentVerification {
+    pub deployment: PackageDeployment,
+    pub modules: Vec<ModuleVerification>,
+}
+
+/// The most recently verified deployment of a repository, shown as its
+/// "verified build" badge.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct VerifiedBuild {
+    pub network: String,
+    pub package_id: String,
+    pub commit_sha: String,
+    pub verified_at: DateTime<Utc>,
+}
+
+/// Reads published packages from the chain.
+pub trait OnchainPackageReader: Send + Sync {
+    /// The modules of package `package_id` on `network`.
+    fn package_modules(
+        &self,
+        network: &str,
+        package_id: &str,
+    ) -> impl Future<Output = crate::Result<Vec<ModuleBytecode>>> + Send;
+}
+
+/// Builds Move packages from source.
+pub trait MovePackageBuilder: Send + Sync {
+    /// The compiled modules of the package at `package_path`, given the
+    /// repository's files by path. A failed build is an error.
+    fn build(
+        &self,
+        package_path: &str,
+        sources: &HashMap<String, String>,
+    ) -> impl Future<Output = crate::Result<Vec<ModuleBytecode>>> + Send;
+}
+
+/// The 32-byte address of a package id such as `0x2` or a full 64-digit
+/// hex id.
+pub fn package_address(package_id: &str) -> Option<[u8; 32]> {
+    let digits = package_id.trim().strip_prefix("0x")?;
+    if digits.is_empty() || digits.len() > 64 {
+        return None;
+    }
+    let mut address = [0u8; 32];
+    hex::decode_to_slice(format!("{:0>64}", digits), &mut address).ok()?;
+    Some(address)
+}
+
+/// Whether a repository file goes into building a Move package.
+pub fn is_package_source(path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    path.ends_with(".move") || file_name == "Move.toml" || file_name == "Move.lock"
+}
+
+/// Compare built modules with the published ones, by name. A published
+/// module names its package by address while a local build usually uses
+/// `0x0`, so the package's own address is zeroed on both sides first.
+pub fn compare_modules(
+    package_address: &[u8; 32],
+    on_chain: &[ModuleBytecode],
+    built: &[ModuleBytecode],
+) -> Vec<ModuleVerification> {
+    let normalized = |module: &ModuleBytecode| zero_address(&module.bytecode, package_address);
+    let built: HashMap<&str, Vec<u8>> =
+        built.iter().map(|module| (module.name.as_str(), normalized(module))).collect();
+
+    let mut modules: Vec<ModuleVerification> = on_chain
+        .iter()
+        .map(|module| {
+            let result = match built.get(module.name.as_str()) {
+                Some(bytecode) if *bytecode == normalized(module) => ModuleMatch::Matches,
+                Some(_) => ModuleMatch::Differs,
+                None => ModuleMatch::OnlyOnChain,
+            };
+            ModuleVerification { name: module.name.clone(), result }
+        })
+        .collect();
+    modules.extend(
+        built
+            .keys()
+            .filter(|name| !on_chain.iter().any(|module| module.name == **name))
+            .map(|name| ModuleVerification {
+                name: name.to_string(),
+                result: ModuleMatch::OnlyInSource,
+            }),
+    );
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+    modules
+}
+
+/// Verified only when every module matches.
+pub fn verification_status(modules: &[ModuleVerification]) -> VerificationStatus {
+    if !modules.is_empty() && modules.iter().all(|module| module.result == ModuleMatch::Matches) {
+        VerificationStatus::Verified
+    } else {
+        VerificationStatus::Mismatch
+    }
+}
+
+fn zero_address(bytecode: &[u8], address: &[u8; 32]) -> Vec<u8> {
+    let mut bytecode = bytecode.to_vec();
+    let mut start = 0;
+    while let Some(offset) = bytecode[start..].windows(32).position(|window| window == address) {
+        let at = start + offset;
+        bytecode[at..at + 32].fill(0);
+        start = at + 32;
+    }
+    bytecode
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(name: &str, bytecode: Vec<u8>) -> ModuleBytecode {
+        ModuleBytecode { name: name.to_string(), bytecode }
+    }
+
+    #[test]
+    fn modules_match_once_the_package_address_is_zeroed() {
+        let address = package_address("0xabc").unwrap();
+        assert_eq!(address[30..], [0x0a, 0xbc]);
+        assert!(address[..30].iter().all(|byte| *byte == 0));
+        assert_eq!(package_address("abc"), None);
+
+        let published = [[1u8, 2].as_slice(), &address, &[3]].concat();
+        let local = [[1u8, 2].as_slice(), &[0u8; 32], &[3]].concat();
+        let on_chain = [module("pool", published.clone()), module("math", vec![9])];
+        let built = [module("pool", local), module("math", vec![8]), module("extra", vec![7])];
+
+        let modules = compare_modules(&address, &on_chain, &built);
+        let results: Vec<_> = modules.iter().map(|m| (m.name.as_str(), m.result)).collect();
+        assert_eq!(
+            results,
+            [
+                ("extra", ModuleMatch::OnlyInSource),
+                ("math", ModuleMatch::Differs),
+                ("pool", ModuleMatch::Matches),
+            ]
+        );
+        assert_eq!(verification_status(&modules), VerificationStatus::Mismatch);
+
+        let matching = compare_modules(&address, &on_chain[..1], &[module("pool", published)]);
+        assert_eq!(verification_status(&matching), VerificationStatus::Verified);
+    }
+}
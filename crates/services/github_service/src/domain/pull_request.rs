@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// A branch holding one commit that applies a patch, and the pull request
+/// to open from it against the default branch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchPullRequest {
+    /// Branch created for the patch; must not exist yet.
+    pub branch: String,
+    /// Commit the branch starts from; the default branch's tip when `None`.
+    pub base_commit: Option<String>,
+    pub title: String,
+    pub body: String,
+    pub commit_message: String,
+    /// Unified diff applied with `git apply`.
+    pub diff: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PullRequestState {
+    Open,
+    /// Closed without being merged.
+    Closed,
+    Merged,
+}
+
+impl PullRequestState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PullRequestState::Open => "open",
+            PullRequestState::Closed => "closed",
+            PullRequestState::Merged => "merged",
+        }
+    }
+
+    /// State of a pull request as GitHub reports it: `open` or `closed`,
+    /// with closed pull requests that were merged carrying `merged_at`.
+    pub fn from_github(state: &str, merged: bool) -> Self {
+        match state {
+            "open" => PullRequestState::Open,
+            _ if merged => PullRequestState::Merged,
+            _ => PullRequestState::Closed,
+        }
+    }
+}
+
+/// A pull request opened from a `PatchPullRequest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenedPullRequest {
+    pub number: u64,
+    pub html_url: String,
+    pub state: PullRequestState,
+    pub branch: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closed_pull_requests_with_a_merge_are_merged() {
+        assert_eq!(PullRequestState::from_github("open", false), PullRequestState::Open);
+        assert_eq!(PullRequestState::from_github("closed", false), PullRequestState::Closed);
+        assert_eq!(PullRequestState::from_github("closed", true), PullRequestState::Merged);
+    }
+}
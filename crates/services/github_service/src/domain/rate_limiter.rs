@@ -1,14 +1,39 @@
-use std::time::Duration;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
-pub struct RateLimit {
-    pub requests_made: u32,
-    pub window_start: std::time::Instant,
+use super::AnalysisPriority;
+
+/// Who a GitHub request is made for. Low-priority requests are deferred once
+/// a budget runs low so webhook-driven and interactive work keeps going.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestPriority {
+    /// Webhook-triggered analyses and user requests.
+    #[default]
+    High,
+    /// Scheduled and other background work.
+    Low,
+}
+
+impl From<AnalysisPriority> for RequestPriority {
+    fn from(priority: AnalysisPriority) -> Self {
+        match priority {
+            AnalysisPriority::Low => Self::Low,
+            _ => Self::High,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RateLimitConfig {
-    pub max_requests: u32,
-    pub window_duration: Duration,
-}
\ No newline at end of file
+/// One credential's GitHub API budget, as last reported in the
+/// `X-RateLimit-*` response headers and counted down locally since.
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitBudget {
+    /// `installation:{id}`, `app` or `token`.
+    pub key: String,
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset_at: DateTime<Utc>,
+    /// False until a response has reported this budget; until then the
+    /// figures are an estimate.
+    pub reported: bool,
+}
@@ -0,0 +1,90 @@
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Mondays at 03:00 UTC.
+pub const DEFAULT_REANALYSIS_SCHEDULE: &str = "0 3 * * Mon";
+
+/// A tracked repository's re-analysis schedule.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ReanalysisSchedule {
+    pub repository_id: Uuid,
+    /// Cron expression; `None` follows the service default.
+    pub schedule: Option<String>,
+    pub enabled: bool,
+    /// HEAD commit the last re-analysis was queued for.
+    pub last_head_sha: Option<String>,
+    pub last_enqueued_at: Option<DateTime<Utc>>,
+    pub next_run_at: DateTime<Utc>,
+}
+
+/// A schedule that has come due, with what is needed to look up its HEAD.
+#[derive(Debug, Clone, FromRow)]
+pub struct DueReanalysis {
+    pub repository_id: Uuid,
+    pub github_repo_id: i64,
+    pub owner_username: String,
+    pub repo_name: String,
+    pub schedule: Option<String>,
+    pub last_head_sha: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReanalysisSettings {
+    /// Cron expression for repositories without their own.
+    pub default_schedule: String,
+    /// Scheduled analyses allowed to be queued or running at once, across
+    /// all instances.
+    pub max_in_flight: usize,
+}
+
+impl Default for ReanalysisSettings {
+    fn default() -> Self {
+        Self { default_schedule: DEFAULT_REANALYSIS_SCHEDULE.to_string(), max_in_flight: 10 }
+    }
+}
+
+/// Parse a cron expression. The usual five fields (minute to day of week)
+/// are accepted as well as six or seven with seconds and year. Days of the
+/// week are best given by name: numerically, 1 is Sunday.
+pub fn parse_schedule(expression: &str) -> Result<cron::Schedule> {
+    let expression = expression.trim();
+    let normalized = match expression.split_whitespace().count() {
+        5 => format!("0 {}", expression),
+        _ => expression.to_string(),
+    };
+    cron::Schedule::from_str(&normalized)
+        .map_err(|e| Error::InvalidSchedule(format!("{}: {}", expression, e)))
+}
+
+/// The first run of `expression` strictly after `after`.
+pub fn next_run_after(expression: &str, after: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    parse_schedule(expression)?
+        .after(&after)
+        .next()
+        .ok_or_else(|| Error::InvalidSchedule(format!("{} never runs again", expression)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn default_schedule_runs_weekly_on_monday_morning() {
+        // A Wednesday.
+        let now = Utc.with_ymd_and_hms(2025, 1, 8, 12, 0, 0).unwrap();
+        let next = next_run_after(DEFAULT_REANALYSIS_SCHEDULE, now).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 1, 13, 3, 0, 0).unwrap());
+        assert_eq!(
+            next_run_after(DEFAULT_REANALYSIS_SCHEDULE, next).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 20, 3, 0, 0).unwrap()
+        );
+
+        assert!(parse_schedule("0 */6 * * *").is_ok());
+        assert!(parse_schedule("every tuesday").is_err());
+    }
+}
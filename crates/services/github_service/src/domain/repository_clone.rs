@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+/// When and how repositories are cloned instead of read file by file through
+/// the contents API.
+#[derive(Debug, Clone)]
+pub struct RepositoryCloneSettings {
+    /// Files in a repository's tree above which it is cloned.
+    pub file_threshold: usize,
+    /// Largest total size of the files to check out.
+    pub max_size_bytes: u64,
+    /// Files larger than this are left out of the analysis.
+    pub max_file_bytes: u64,
+    /// Directories to check out. Empty checks out the whole tree.
+    pub sparse_paths: Vec<String>,
+    /// Time allowed for each git command.
+    pub timeout: Duration,
+}
+
+impl Default for RepositoryCloneSettings {
+    fn default() -> Self {
+        Self {
+            file_threshold: 1000,
+            max_size_bytes: 500 * 1024 * 1024,
+            max_file_bytes: 1024 * 1024,
+            sparse_paths: Vec::new(),
+            timeout: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl RepositoryCloneSettings {
+    /// Whether `path` falls under one of the sparse checkout directories.
+    pub fn includes(&self, path: &str) -> bool {
+        self.sparse_paths.is_empty()
+            || self.sparse_paths.iter().any(|dir| {
+                let dir = dir.trim_matches('/');
+                path == dir || path.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/'))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparse_paths_match_whole_directories() {
+        let settings = RepositoryCloneSettings {
+            sparse_paths: vec!["contracts".to_string(), "/packages/core/".to_string()],
+            ..Default::default()
+        };
+
+        assert!(settings.includes("contracts/Vault.sol"));
+        assert!(settings.includes("packages/core/sources/coin.move"));
+        assert!(!settings.includes("contracts-old/Vault.sol"));
+        assert!(!settings.includes("README.md"));
+        assert!(RepositoryCloneSettings::default().includes("README.md"));
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Scope admitting callers that do not own a repository to re-detect its
+/// packages and manage its deployments.
+pub const SCOPE_REPOSITORIES_ADMIN: &str = "repositories:admin";
+
+/// The toolchain a package's manifest belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PackageKind {
+    Move,
+    Foundry,
+    Anchor,
+}
+
+impl PackageKind {
+    /// The kind whose manifest is named `file_name`.
+    pub fn from_manifest(file_name: &str) -> Option<Self> {
+        match file_name {
+            "Move.toml" => Some(Self::Move),
+            "foundry.toml" => Some(Self::Foundry),
+            "Anchor.toml" => Some(Self::Anchor),
+            _ => None,
+        }
+    }
+}
+
+/// A smart contract package found in a repository's tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedPackage {
+    /// Directory holding the manifest; empty for the repository root.
+    pub path: String,
+    pub kind: PackageKind,
+    pub manifest_path: String,
+}
+
+/// A tracked package of a repository. Files in excluded packages are left
+/// out of analyses and do not trigger them from webhooks.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct RepositoryPackage {
+    pub id: Uuid,
+    pub repository_id: Uuid,
+    pub path: String,
+    pub kind: PackageKind,
+    pub manifest_path: String,
+    pub included: bool,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Packages whose manifests appear among `paths`, outermost first.
+pub fn detect_packages<'a>(paths: impl IntoIterator<Item = &'a str>) -> Vec<DetectedPackage> {
+    let mut packages: Vec<DetectedPackage> = paths
+        .into_iter()
+        .filter_map(|manifest_path| {
+            let (dir, file_name) = match manifest_path.rsplit_once('/') {
+                Some((dir, file_name)) => (dir, file_name),
+                None => ("", manifest_path),
+            };
+            let kind = PackageKind::from_manifest(file_name)?;
+            Some(DetectedPackage {
+                path: dir.to_string(),
+                kind,
+                manifest_path: manifest_path.to_string(),
+            })
+        })
+        .collect();
+    packages.sort_by(|a, b| (a.path.len(), &a.path).cmp(&(b.path.len(), &b.path)));
+    // A directory is one package even if it holds manifests for several tools.
+    packages.dedup_by(|a, b| a.path == b.path);
+    packages
+}
+
+/// Whether `path` is `dir` or lies beneath it. Every path lies beneath the
+/// repository root.
+pub fn is_within(path: &str, dir: &str) -> bool {
+    dir.is_empty()
+        || path == dir
+        || path.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Whether `path` is in scope for analysis: it belongs to the innermost
+/// package containing it, and is in scope unless that package is excluded.
+/// Files outside every package are always in scope.
+pub fn is_in_scope(path: &str, packages: &[RepositoryPackage]) -> bool {
+    packages
+        .iter()
+        .filter(|package| is_within(path, &package.path))
+        .max_by_key(|package| package.path.len())
+        .is_none_or(|package| package.included)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(path: &str, included: bool) -> RepositoryPackage {
+        RepositoryPackage {
+            id: Uuid::new_v4(),
+            repository_id: Uuid::nil(),
+            path: path.to_string(),
+            kind: PackageKind::Move,
+            manifest_path: format!("{}/Move.toml", path),
+            included,
+            detected_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn files_follow_their_innermost_package() {
+        let detected = detect_packages([
+            "packages/dex/Move.toml",
+            "README.md",
+            "foundry.toml",
+            "programs/vault/Anchor.toml",
+        ]);
+        assert_eq!(
+            detected.iter().map(|p| p.path.as_str()).collect::<Vec<_>>(),
+            ["", "packages/dex", "programs/vault"]
+        );
+        assert_eq!(detected[0].kind, PackageKind::Foundry);
+
+        let packages = [package("packages", true), package("packages/dex", false)];
+        assert!(!is_in_scope("packages/dex/sources/pool.move", &packages));
+        assert!(is_in_scope("packages/dex-v2/sources/pool.move", &packages));
+        assert!(is_in_scope("scripts/deploy.move", &packages));
+    }
+}
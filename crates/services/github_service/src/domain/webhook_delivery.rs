@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Outcome of checking `X-Hub-Signature-256` against the webhook secret.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    Valid,
+    Invalid,
+    Missing,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingStatus {
+    /// Stored, not yet dispatched.
+    Received,
+    Processed,
+    /// Refused before dispatch: bad signature or unparseable payload.
+    Rejected,
+    Failed,
+}
+
+/// A webhook delivery without its headers and payload, as listed.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct WebhookDeliverySummary {
+    pub id: Uuid,
+    /// GitHub's `X-GitHub-Delivery` GUID.
+    pub github_delivery_id: Option<String>,
+    pub event_type: String,
+    pub installation_id: Option<i64>,
+    pub signature_status: SignatureStatus,
+    pub processing_status: ProcessingStatus,
+    pub processing_id: Option<Uuid>,
+    pub error_message: Option<String>,
+    /// The delivery this row re-ran, for replays.
+    pub replay_of: Option<Uuid>,
+    pub received_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+/// A stored delivery with the request exactly as received.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct WebhookDelivery {
+    #[sqlx(flatten)]
+    pub summary: WebhookDeliverySummary,
+    pub headers: serde_json::Value,
+    pub payload: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookDeliveryForCreate {
+    pub github_delivery_id: Option<String>,
+    pub event_type: String,
+    pub installation_id: Option<u64>,
+    pub headers: serde_json::Value,
+    pub payload: Vec<u8>,
+    pub signature_status: SignatureStatus,
+    pub replay_of: Option<Uuid>,
+}
@@ -53,6 +53,8 @@ pub struct GitHubCommitAuthor {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PullRequestEvent {
+    /// Consumed by `GitHubWebhookPayload::action` when flattened; read that.
+    #[serde(default)]
     pub action: String,
     pub number: u64,
     pub pull_request: GitHubPullRequest,
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Scope required to rotate webhook secrets.
+pub const SCOPE_WEBHOOKS_ADMIN: &str = "webhooks:admin";
+
+/// What a webhook secret signs deliveries for. GitHub Apps have a single
+/// webhook, so every installation's deliveries share the app's secret.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookSecretScope {
+    /// A repository webhook, keyed by GitHub repository id.
+    Repository,
+    App,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookSecretSettings {
+    /// Secrets accepted alongside `GITHUB.WEBHOOK_SECRET` for webhooks that
+    /// have never been rotated, so the configured secret can be changed
+    /// without dropping deliveries.
+    pub previous_secrets: Vec<String>,
+    /// How long a replaced secret is still accepted after a rotation.
+    pub grace_period: Duration,
+}
+
+impl Default for WebhookSecretSettings {
+    fn default() -> Self {
+        Self { previous_secrets: Vec::new(), grace_period: Duration::from_secs(24 * 60 * 60) }
+    }
+}
+
+/// The outcome of rotating a webhook's secret.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookSecretRotation {
+    pub secret_id: Uuid,
+    pub scope: WebhookSecretScope,
+    pub scope_id: i64,
+    /// The repository webhook now signing with the new secret.
+    pub hook_id: Option<u64>,
+    pub rotated_at: DateTime<Utc>,
+    /// Until when deliveries signed with the replaced secret are accepted.
+    pub previous_accepted_until: DateTime<Utc>,
+}
+
+/// A new random webhook secret: 64 hex characters.
+pub fn generate_webhook_secret() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// Whether `signature` (`X-Hub-Signature-256`, with or without its
+/// `sha256=` prefix) is the HMAC of `payload` under `secret`.
+pub fn signature_matches(secret: &str, payload: &[u8], signature: &str) -> bool {
+    let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+    let Ok(expected) = hex::decode(signature) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(payload);
+    mac.verify_slice(&expected).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signatures_match_only_their_own_secret() {
+        let payload = br#"{"zen":"Keep it logically awesome."}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"current").unwrap();
+        mac.update(payload);
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert!(signature_matches("current", payload, &signature));
+        assert!(signature_matches("current", payload, &signature["sha256=".len()..]));
+        assert!(!signature_matches("previous", payload, &signature));
+        assert!(!signature_matches("current", payload, "sha256=not-hex"));
+        assert_eq!(generate_webhook_secret().len(), 64);
+    }
+}
@@ -3,51 +3,112 @@ use uuid::Uuid;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "github")]
 pub enum Error {
     #[error("GitHub API error: {0}")]
+    #[taxonomy(kind = Upstream, code = "GITHUB_API_ERROR")]
     GitHubApi(String),
     
     #[error("Repository not found: {owner}/{repo}")]
+    #[taxonomy(kind = NotFound, expose)]
     RepositoryNotFound { owner: String, repo: String },
     
     #[error("Invalid webhook signature")]
+    #[taxonomy(kind = Unauthenticated, message = "Invalid webhook signature")]
     InvalidWebhookSignature,
     
     #[error("Webhook payload parsing error: {0}")]
+    #[taxonomy(kind = Validation, expose)]
     WebhookPayloadError(String),
     
     #[error("Rate limit exceeded, retry after {retry_after_seconds} seconds")]
+    #[taxonomy(
+        kind = RateLimited,
+        code = "GITHUB_RATE_LIMITED",
+        expose,
+        retry_after = retry_after_seconds
+    )]
     RateLimitExceeded { retry_after_seconds: u64 },
     
     #[error("No smart contracts found in repository")]
+    #[taxonomy(kind = Validation, message = "Repository does not contain any smart contracts")]
     NoSmartContractsFound,
     
     #[error("Job not found: {0}")]
+    #[taxonomy(kind = NotFound, message = "Analysis job not found")]
     JobNotFound(Uuid),
     
+    #[error("Analysis job cannot be changed: {0}")]
+    #[taxonomy(kind = Conflict, expose)]
+    InvalidJobState(String),
+    
+    #[error("Webhook delivery not found: {0}")]
+    #[taxonomy(kind = NotFound, message = "Webhook delivery not found")]
+    DeliveryNotFound(Uuid),
+    
+    #[error("Webhook delivery cannot be replayed: {0}")]
+    #[taxonomy(kind = Conflict, expose)]
+    DeliveryNotReplayable(String),
+    
+    #[error("Invalid re-analysis schedule: {0}")]
+    #[taxonomy(kind = Validation, expose)]
+    InvalidSchedule(String),
+    
+    #[error("Repository too large: {size_bytes} bytes exceeds the {limit_bytes} byte limit")]
+    #[taxonomy(kind = Validation, expose)]
+    RepositoryTooLarge { size_bytes: u64, limit_bytes: u64 },
+    
+    #[error("Repository clone failed: {0}")]
+    #[taxonomy(kind = Upstream, code = "GITHUB_CLONE_FAILED")]
+    CloneFailed(String),
+    
+    #[error("Package not found: {0}")]
+    #[taxonomy(kind = NotFound, expose)]
+    PackageNotFound(String),
+    
+    #[error("Package deployment not found: {0}")]
+    #[taxonomy(kind = NotFound, message = "Package deployment not found")]
+    DeploymentNotFound(Uuid),
+    
+    #[error("Invalid package deployment: {0}")]
+    #[taxonomy(kind = Validation, expose)]
+    InvalidDeployment(String),
+    
+    #[error("Lease on analysis job {0} was lost")]
+    #[taxonomy(kind = Conflict, message = "Analysis job lease was lost")]
+    LeaseLost(Uuid),
+    
     #[error("Queue is full")]
+    #[taxonomy(kind = Unavailable, message = "Analysis queue is full")]
     QueueFull,
     
     #[error("Authentication error: {0}")]
+    #[taxonomy(kind = Upstream, code = "GITHUB_AUTHENTICATION_ERROR")]
     AuthenticationError(String),
     
     #[error("Configuration error: {0}")]
+    #[taxonomy(kind = Internal)]
     ConfigurationError(String),
     
     #[error("Database error: {0}")]
+    #[taxonomy(kind = Internal, code = "DATABASE_ERROR")]
     Database(#[from] sqlx::Error),
     
     #[error("Serialization error: {0}")]
+    #[taxonomy(kind = Internal)]
     Serialization(#[from] serde_json::Error),
     
     #[error("HTTP request error: {0}")]
+    #[taxonomy(kind = Upstream)]
     HttpRequest(#[from] reqwest::Error),
     
     #[error("Octocrab error: {0}")]
+    #[taxonomy(kind = Upstream, code = "GITHUB_API_ERROR")]
     Octocrab(#[from] octocrab::Error),
     
     #[error("Internal error: {0}")]
+    #[taxonomy(kind = Internal)]
     Internal(String),
 }
 
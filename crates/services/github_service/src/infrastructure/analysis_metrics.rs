@@ -0,0 +1,31 @@
+use jd_utils::metrics::{self, HistogramVec, IntCounterVec, CALL_BUCKETS, QUEUE_BUCKETS};
+use std::sync::LazyLock;
+
+/// Time from enqueueing a job to its first lease, by priority.
+pub static ANALYSIS_QUEUE_WAIT: LazyLock<HistogramVec> = LazyLock::new(|| {
+    metrics::histogram(
+        "analysis_queue_wait_seconds",
+        "Time analysis jobs wait from enqueue to their first lease",
+        &["priority"],
+        QUEUE_BUCKETS,
+    )
+});
+
+/// Time a worker spent on one attempt, by how it ended.
+pub static ANALYSIS_JOB_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    metrics::histogram(
+        "analysis_job_duration_seconds",
+        "Time spent processing one attempt of an analysis job",
+        &["outcome"],
+        CALL_BUCKETS,
+    )
+});
+
+/// Failed attempts, by the failing error's code.
+pub static ANALYSIS_JOB_FAILURES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    metrics::counter(
+        "analysis_job_failures_total",
+        "Failed analysis job attempts by error code",
+        &["reason"],
+    )
+});
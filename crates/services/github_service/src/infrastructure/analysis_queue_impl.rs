@@ -1,171 +1,575 @@
-use crate::domain::{AnalysisJob, JobStatus, QueueStatus};
+use crate::domain::{
+    estimate_start, AnalysisJob, AnalysisJobDetail, AnalysisPriority, AnalysisQueueSettings,
+    AnalysisType, JobStatus, LeasedJob, QueueStatus,
+};
 use crate::error::{Error, Result};
-use std::collections::VecDeque;
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use uuid::Uuid;
+use crate::infrastructure::analysis_metrics::ANALYSIS_QUEUE_WAIT;
+use chrono::{DateTime, Utc};
+use jd_messaging::{
+    events::{AnalysisCompleted, AnalysisFailed},
+    outbox,
+};
+use sqlx::{FromRow, Pool, Postgres};
+use std::time::Duration;
 use tracing::{info, warn};
+use uuid::Uuid;
+
+const JOB_COLUMNS: &str = "id, repository_id, installation_id, pull_request_number, commit_sha, \
+     files_to_analyze, analysis_type, priority, status, attempts, created_at, leased_until";
+
+/// Why a job whose worker stopped renewing its final lease was dead-lettered.
+const FINAL_LEASE_EXPIRED: &str = "lease expired on final attempt";
 
+/// Completions over this window set the pace for queue ETA estimates.
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Postgres-backed analysis queue on the `analysis_jobs` table. Safe to share
+/// between any number of workers and instances.
 pub struct AnalysisQueueImpl {
-    queue: Arc<Mutex<VecDeque<AnalysisJob>>>,
-    processing: Arc<Mutex<Vec<AnalysisJob>>>,
-    max_queue_size: usize,
+    db: Pool<Postgres>,
+    settings: AnalysisQueueSettings,
 }
 
-impl AnalysisQueueImpl {
-    pub fn new(max_queue_size: usize) -> Self {
+#[derive(FromRow)]
+struct JobRow {
+    id: Uuid,
+    repository_id: i64,
+    installation_id: Option<i64>,
+    pull_request_number: Option<i64>,
+    commit_sha: String,
+    files_to_analyze: Vec<String>,
+    analysis_type: AnalysisType,
+    priority: AnalysisPriority,
+    status: JobStatus,
+    attempts: i32,
+    created_at: DateTime<Utc>,
+    leased_until: Option<DateTime<Utc>>,
+}
+
+impl From<JobRow> for AnalysisJob {
+    fn from(row: JobRow) -> Self {
         Self {
-            queue: Arc::new(Mutex::new(VecDeque::new())),
-            processing: Arc::new(Mutex::new(Vec::new())),
-            max_queue_size,
+            id: row.id,
+            repository_id: row.repository_id as u64,
+            installation_id: row.installation_id.map(|id| id as u64),
+            pull_request_number: row.pull_request_number.map(|number| number as u64),
+            commit_sha: row.commit_sha,
+            files_to_analyze: row.files_to_analyze,
+            analysis_type: row.analysis_type,
+            priority: row.priority,
+            created_at: row.created_at,
+            status: row.status,
         }
     }
+}
 
-    pub async fn enqueue(&self, mut job: AnalysisJob) -> Result<Uuid> {
-        let mut queue = self.queue.lock().await;
+#[derive(FromRow)]
+struct JobDetailRow {
+    #[sqlx(flatten)]
+    job: JobRow,
+    last_error: Option<String>,
+    run_after: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+    queue_position: Option<i64>,
+}
 
-        if queue.len() >= self.max_queue_size {
-            return Err(Error::QueueFull);
-        }
+#[derive(FromRow)]
+struct StatusCounts {
+    queued: i64,
+    processing: i64,
+    dead_lettered: i64,
+}
 
-        job.id = Uuid::new_v4();
-        job.created_at = chrono::Utc::now();
-        job.status = JobStatus::Queued;
+impl AnalysisQueueImpl {
+    pub fn new(db: Pool<Postgres>, settings: AnalysisQueueSettings) -> Self {
+        Self { db, settings }
+    }
 
-        // Insert based on priority (higher priority first)
-        let insert_position = queue
-            .iter()
-            .position(|existing_job| existing_job.priority < job.priority)
-            .unwrap_or(queue.len());
+    pub fn settings(&self) -> &AnalysisQueueSettings {
+        &self.settings
+    }
 
-        let job_id = job.id;
-        queue.insert(insert_position, job);
+    pub async fn enqueue(&self, job: AnalysisJob) -> Result<Uuid> {
+        let queued: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM analysis_jobs WHERE status = 'queued'")
+                .fetch_one(&self.db)
+                .await?;
+        if queued as usize >= self.settings.max_queue_size {
+            return Err(Error::QueueFull);
+        }
+
+        let job_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO analysis_jobs (
+                repository_id, installation_id, pull_request_number, commit_sha,
+                files_to_analyze, analysis_type, priority, max_attempts
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(job.repository_id as i64)
+        .bind(job.installation_id.map(|id| id as i64))
+        .bind(job.pull_request_number.map(|number| number as i64))
+        .bind(&job.commit_sha)
+        .bind(&job.files_to_analyze)
+        .bind(job.analysis_type)
+        .bind(job.priority)
+        .bind(self.settings.max_attempts as i32)
+        .fetch_one(&self.db)
+        .await?;
 
         info!(
             "Enqueued analysis job {} for repository {} with priority {:?}",
-            job_id, queue[insert_position].repository_id, queue[insert_position].priority
+            job_id, job.repository_id, job.priority
         );
 
         Ok(job_id)
     }
 
-    pub async fn dequeue(&self) -> Option<AnalysisJob> {
-        let mut queue = self.queue.lock().await;
-        if let Some(mut job) = queue.pop_front() {
-            job.status = JobStatus::Processing;
+    /// Lease the most urgent ready job of at least `min_priority`, or one
+    /// whose previous lease lapsed. Lapsed jobs that have used their last
+    /// attempt are dead-lettered instead.
+    pub async fn lease(
+        &self,
+        worker_id: &str,
+        min_priority: AnalysisPriority,
+    ) -> Result<Option<LeasedJob>> {
+        let mut tx = self.db.begin().await?;
+        let expired: Vec<(Uuid, i64, String, i32)> = sqlx::query_as(
+            r#"
+            UPDATE analysis_jobs
+            SET status = 'dead_lettered', lease_id = NULL, leased_by = NULL,
+                leased_until = NULL, last_error = $1, updated_at = NOW()
+            WHERE status = 'processing' AND leased_until < NOW() AND attempts >= max_attempts
+            RETURNING id, repository_id, commit_sha, attempts
+            "#,
+        )
+        .bind(FINAL_LEASE_EXPIRED)
+        .fetch_all(&mut *tx)
+        .await?;
+        for (analysis_job_id, github_repo_id, commit_sha, attempts) in &expired {
+            let event = AnalysisFailed {
+                analysis_job_id: *analysis_job_id,
+                github_repo_id: *github_repo_id,
+                commit_sha: commit_sha.clone(),
+                attempts: *attempts,
+                error: FINAL_LEASE_EXPIRED.to_string(),
+            };
+            outbox::record(&mut *tx, &event).await?;
+        }
+        tx.commit().await?;
+        if !expired.is_empty() {
+            warn!("Dead-lettered {} analysis job(s) whose final lease expired", expired.len());
+        }
 
-            // Move to processing list
-            let mut processing = self.processing.lock().await;
-            processing.push(job.clone());
+        let lease_id = Uuid::new_v4();
+        let row = sqlx::query_as::<_, JobRow>(&format!(
+            r#"
+            WITH next AS (
+                SELECT id FROM analysis_jobs
+                WHERE ((status = 'queued' AND run_after <= NOW())
+                    OR (status = 'processing' AND leased_until < NOW()))
+                  AND priority >= $4
+                ORDER BY priority DESC, run_after
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE analysis_jobs AS job
+            SET status = 'processing', attempts = job.attempts + 1, lease_id = $1,
+                leased_by = $2, leased_until = NOW() + make_interval(secs => $3),
+                updated_at = NOW()
+            FROM next
+            WHERE job.id = next.id
+            RETURNING {}
+            "#,
+            qualified_job_columns()
+        ))
+        .bind(lease_id)
+        .bind(worker_id)
+        .bind(self.settings.visibility_timeout.as_secs_f64())
+        .bind(min_priority)
+        .fetch_optional(&self.db)
+        .await?;
 
-            info!("Dequeued analysis job {} for processing", job.id);
-            Some(job)
-        } else {
-            None
-        }
+        Ok(row.map(|row| {
+            let attempt = row.attempts.max(0) as u32;
+            let leased_until = row.leased_until.unwrap_or_else(Utc::now);
+            let job = AnalysisJob::from(row);
+            if attempt == 1 {
+                let waited = (Utc::now() - job.created_at).to_std().unwrap_or_default();
+                let priority = format!("{:?}", job.priority).to_lowercase();
+                ANALYSIS_QUEUE_WAIT.with_label_values(&[&priority]).observe(waited.as_secs_f64());
+            }
+            info!("Leased analysis job {} (attempt {})", job.id, attempt);
+            LeasedJob { job, lease_id, attempt, leased_until }
+        }))
+    }
+
+    /// Push the lease out by another visibility timeout. Fails with
+    /// `LeaseLost` if another worker has taken the job meanwhile.
+    pub async fn extend_lease(&self, lease: &LeasedJob) -> Result<DateTime<Utc>> {
+        sqlx::query_scalar(
+            r#"
+            UPDATE analysis_jobs
+            SET leased_until = NOW() + make_interval(secs => $3), updated_at = NOW()
+            WHERE id = $1 AND lease_id = $2 AND status = 'processing'
+            RETURNING leased_until
+            "#,
+        )
+        .bind(lease.job.id)
+        .bind(lease.lease_id)
+        .bind(self.settings.visibility_timeout.as_secs_f64())
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or(Error::LeaseLost(lease.job.id))
+    }
+
+    /// Mark the job completed and record an `analysis.completed` event.
+    pub async fn complete_job(&self, lease: &LeasedJob) -> Result<()> {
+        let mut tx = self.db.begin().await?;
+        let completed: Option<(i64, String)> = sqlx::query_as(
+            r#"
+            UPDATE analysis_jobs
+            SET status = 'completed', completed_at = NOW(), lease_id = NULL,
+                leased_by = NULL, leased_until = NULL, updated_at = NOW()
+            WHERE id = $1 AND lease_id = $2
+            RETURNING repository_id, commit_sha
+            "#,
+        )
+        .bind(lease.job.id)
+        .bind(lease.lease_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some((github_repo_id, commit_sha)) = completed else {
+            warn!("Attempted to complete analysis job {} without holding its lease", lease.job.id);
+            return Err(Error::LeaseLost(lease.job.id));
+        };
+        let event = AnalysisCompleted { analysis_job_id: lease.job.id, github_repo_id, commit_sha };
+        outbox::record(&mut *tx, &event).await?;
+        tx.commit().await?;
+
+        info!("Analysis job {} completed", lease.job.id);
+        Ok(())
     }
 
-    pub async fn complete_job(&self, job_id: Uuid, success: bool) -> Result<()> {
-        let mut processing = self.processing.lock().await;
+    /// Schedule a retry after the backoff for this attempt, or dead-letter the
+    /// job if it has none left and record an `analysis.failed` event. Returns
+    /// the job's new status.
+    pub async fn fail_job(&self, lease: &LeasedJob, error_message: &str) -> Result<JobStatus> {
+        let retry_in = self.settings.backoff(lease.attempt);
+        let mut tx = self.db.begin().await?;
+        let failed: Option<(JobStatus, i64, String, i32)> = sqlx::query_as(
+            r#"
+            UPDATE analysis_jobs
+            SET status = CASE WHEN attempts >= max_attempts THEN 'dead_lettered' ELSE 'queued' END,
+                run_after = NOW() + make_interval(secs => $3), last_error = $4,
+                lease_id = NULL, leased_by = NULL, leased_until = NULL, updated_at = NOW()
+            WHERE id = $1 AND lease_id = $2
+            RETURNING status, repository_id, commit_sha, attempts
+            "#,
+        )
+        .bind(lease.job.id)
+        .bind(lease.lease_id)
+        .bind(retry_in.as_secs_f64())
+        .bind(error_message)
+        .fetch_optional(&mut *tx)
+        .await?;
 
-        if let Some(index) = processing.iter().position(|job| job.id == job_id) {
-            let mut job = processing.remove(index);
-            job.status = if success {
-                JobStatus::Completed
-            } else {
-                JobStatus::Failed
+        let (status, github_repo_id, commit_sha, attempts) =
+            failed.ok_or(Error::LeaseLost(lease.job.id))?;
+        if status == JobStatus::DeadLettered {
+            let event = AnalysisFailed {
+                analysis_job_id: lease.job.id,
+                github_repo_id,
+                commit_sha,
+                attempts,
+                error: error_message.to_string(),
             };
+            outbox::record(&mut *tx, &event).await?;
+        }
+        tx.commit().await?;
+        match status {
+            JobStatus::DeadLettered => warn!(
+                "Analysis job {} dead-lettered after {} attempt(s): {}",
+                lease.job.id, lease.attempt, error_message
+            ),
+            _ => warn!(
+                "Analysis job {} failed (attempt {}), retrying in {}s: {}",
+                lease.job.id,
+                lease.attempt,
+                retry_in.as_secs(),
+                error_message
+            ),
+        }
+        Ok(status)
+    }
 
-            info!(
-                "Analysis job {} completed with status: {:?}",
-                job_id, job.status
-            );
+    /// Put the job back to run after `delay` without using up an attempt,
+    /// for work held back by something other than the job itself.
+    pub async fn defer_job(&self, lease: &LeasedJob, delay: Duration) -> Result<()> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE analysis_jobs
+            SET status = 'queued', attempts = GREATEST(attempts - 1, 0),
+                run_after = NOW() + make_interval(secs => $3), lease_id = NULL,
+                leased_by = NULL, leased_until = NULL, updated_at = NOW()
+            WHERE id = $1 AND lease_id = $2
+            "#,
+        )
+        .bind(lease.job.id)
+        .bind(lease.lease_id)
+        .bind(delay.as_secs_f64())
+        .execute(&self.db)
+        .await?
+        .rows_affected();
 
-            Ok(())
-        } else {
-            warn!("Attempted to complete unknown job: {}", job_id);
-            Err(Error::JobNotFound(job_id))
+        if updated == 0 {
+            return Err(Error::LeaseLost(lease.job.id));
         }
+
+        info!("Analysis job {} deferred for {}s", lease.job.id, delay.as_secs());
+        Ok(())
     }
 
-    pub async fn fail_job(&self, job_id: Uuid, error_message: &str) -> Result<()> {
-        let mut processing = self.processing.lock().await;
+    pub async fn get_queue_status(&self) -> Result<QueueStatus> {
+        let counts = sqlx::query_as::<_, StatusCounts>(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE status = 'queued') AS queued,
+                COUNT(*) FILTER (WHERE status = 'processing') AS processing,
+                COUNT(*) FILTER (WHERE status = 'dead_lettered') AS dead_lettered
+            FROM analysis_jobs
+            "#,
+        )
+        .fetch_one(&self.db)
+        .await?;
 
-        if let Some(index) = processing.iter().position(|job| job.id == job_id) {
-            let mut job = processing.remove(index);
-            job.status = JobStatus::Failed;
+        Ok(QueueStatus {
+            queued_jobs: counts.queued as usize,
+            processing_jobs: counts.processing as usize,
+            dead_lettered_jobs: counts.dead_lettered as usize,
+            total_jobs: (counts.queued + counts.processing) as usize,
+        })
+    }
 
-            warn!(
-                "Analysis job {} failed: {}",
-                job_id, error_message
-            );
+    pub async fn get_job_status(&self, job_id: Uuid) -> Result<Option<JobStatus>> {
+        let status = sqlx::query_scalar("SELECT status FROM analysis_jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_optional(&self.db)
+            .await?;
+        Ok(status)
+    }
 
-            Ok(())
-        } else {
-            warn!("Attempted to fail unknown job: {}", job_id);
-            Err(Error::JobNotFound(job_id))
-        }
+    pub async fn get_job(&self, job_id: Uuid) -> Result<Option<AnalysisJob>> {
+        let row = sqlx::query_as::<_, JobRow>(&format!(
+            "SELECT {} FROM analysis_jobs WHERE id = $1",
+            JOB_COLUMNS
+        ))
+        .bind(job_id)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(row.map(AnalysisJob::from))
     }
 
-    pub async fn get_queue_status(&self) -> QueueStatus {
-        let queue = self.queue.lock().await;
-        let processing = self.processing.lock().await;
+    /// A job with its queue position and, while queued, an estimated start
+    /// based on how many jobs completed over the last hour.
+    pub async fn get_job_detail(&self, job_id: Uuid) -> Result<AnalysisJobDetail> {
+        let row = sqlx::query_as::<_, JobDetailRow>(&format!(
+            "{} WHERE job.id = $1",
+            detail_query()
+        ))
+        .bind(job_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or(Error::JobNotFound(job_id))?;
 
-        QueueStatus {
-            queued_jobs: queue.len(),
-            processing_jobs: processing.len(),
-            total_jobs: queue.len() + processing.len(),
-        }
+        let completed = self.completed_recently().await?;
+        Ok(detail_from_row(row, completed))
     }
 
-    pub async fn get_job_status(&self, job_id: Uuid) -> Option<JobStatus> {
-        // Check in queue
-        let queue = self.queue.lock().await;
-        if let Some(job) = queue.iter().find(|job| job.id == job_id) {
-            return Some(job.status.clone());
-        }
+    /// Jobs newest first, optionally only those with `status`, with the
+    /// total matching.
+    pub async fn list_jobs(
+        &self,
+        status: Option<JobStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<AnalysisJobDetail>, i64)> {
+        let rows = sqlx::query_as::<_, JobDetailRow>(&format!(
+            r#"
+            {}
+            WHERE $1::VARCHAR IS NULL OR job.status = $1
+            ORDER BY job.created_at DESC, job.id
+            LIMIT $2 OFFSET $3
+            "#,
+            detail_query()
+        ))
+        .bind(status.clone())
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db)
+        .await?;
+
+        let total_count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM analysis_jobs WHERE $1::VARCHAR IS NULL OR status = $1",
+        )
+        .bind(status)
+        .fetch_one(&self.db)
+        .await?;
 
-        // Check in processing
-        let processing = self.processing.lock().await;
-        if let Some(job) = processing.iter().find(|job| job.id == job_id) {
-            return Some(job.status.clone());
+        let completed = self.completed_recently().await?;
+        let jobs = rows.into_iter().map(|row| detail_from_row(row, completed)).collect();
+        Ok((jobs, total_count))
+    }
+
+    /// Move a queued job ahead of or behind others.
+    pub async fn set_priority(&self, job_id: Uuid, priority: AnalysisPriority) -> Result<()> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE analysis_jobs SET priority = $2, updated_at = NOW()
+            WHERE id = $1 AND status = 'queued'
+            "#,
+        )
+        .bind(job_id)
+        .bind(priority)
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+        if updated > 0 {
+            info!("Analysis job {} reprioritised to {:?}", job_id, priority);
+            return Ok(());
         }
 
-        None
+        match self.get_job_status(job_id).await? {
+            Some(status) => Err(Error::InvalidJobState(format!(
+                "job {} is {:?}; only queued jobs can be reprioritised",
+                job_id, status
+            ))),
+            None => Err(Error::JobNotFound(job_id)),
+        }
     }
 
+    /// Withdraw a job that has not finished. A job being processed loses its
+    /// lease, so its worker abandons it at the next heartbeat.
     pub async fn cancel_job(&self, job_id: Uuid) -> Result<()> {
-        // Try to remove from queue first
-        let mut queue = self.queue.lock().await;
-        if let Some(index) = queue.iter().position(|job| job.id == job_id) {
-            queue.remove(index);
-            info!("Cancelled queued job: {}", job_id);
+        let cancelled = sqlx::query(
+            r#"
+            UPDATE analysis_jobs
+            SET status = 'cancelled', completed_at = NOW(), lease_id = NULL,
+                leased_by = NULL, leased_until = NULL, updated_at = NOW()
+            WHERE id = $1 AND status IN ('queued', 'processing', 'failed', 'dead_lettered')
+            "#,
+        )
+        .bind(job_id)
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+        if cancelled > 0 {
+            info!("Cancelled analysis job {}", job_id);
             return Ok(());
         }
-        drop(queue);
 
-        // Job might be processing, mark it for cancellation
-        // In a real implementation, you'd need a cancellation mechanism
-        warn!("Cannot cancel job {} - already processing", job_id);
-        Err(Error::Internal("Cannot cancel processing job".to_string()))
+        match self.get_job_status(job_id).await? {
+            Some(status) => {
+                warn!("Cannot cancel job {} - {:?}", job_id, status);
+                Err(Error::InvalidJobState(format!(
+                    "job {} is {:?} and can no longer be cancelled",
+                    job_id, status
+                )))
+            }
+            None => Err(Error::JobNotFound(job_id)),
+        }
+    }
+
+    async fn completed_recently(&self) -> Result<u64> {
+        let completed: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM analysis_jobs
+            WHERE status = 'completed' AND completed_at > NOW() - make_interval(secs => $1)
+            "#,
+        )
+        .bind(THROUGHPUT_WINDOW.as_secs_f64())
+        .fetch_one(&self.db)
+        .await?;
+        Ok(completed.max(0) as u64)
     }
 
-    pub async fn clear_completed_jobs(&self) {
-        // In a real implementation, you'd persist completed jobs
-        // For now, we just log that they should be cleared
-        info!("Completed jobs should be persisted and cleared periodically");
+    /// Delete completed and cancelled jobs finished more than `retention` ago.
+    pub async fn clear_completed_jobs(&self, retention: Duration) -> Result<u64> {
+        let cleared = sqlx::query(
+            r#"
+            DELETE FROM analysis_jobs
+            WHERE status IN ('completed', 'cancelled')
+              AND completed_at < NOW() - make_interval(secs => $1)
+            "#,
+        )
+        .bind(retention.as_secs_f64())
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+        Ok(cleared)
     }
 
+    /// Give every dead-lettered job a fresh set of attempts.
     pub async fn requeue_failed_jobs(&self) -> Result<usize> {
-        // In a real implementation, you'd reload failed jobs and requeue them
-        // This is a placeholder for that functionality
-        info!("Failed jobs should be requeued based on retry policy");
-        Ok(0)
+        let requeued = sqlx::query(
+            r#"
+            UPDATE analysis_jobs
+            SET status = 'queued', attempts = 0, run_after = NOW(), updated_at = NOW()
+            WHERE status = 'dead_lettered'
+            "#,
+        )
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+
+        info!("Requeued {} dead-lettered analysis job(s)", requeued);
+        Ok(requeued as usize)
     }
 }
 
-impl Default for AnalysisQueueImpl {
-    fn default() -> Self {
-        Self::new(1000) // Default max queue size
+fn qualified_job_columns() -> String {
+    JOB_COLUMNS
+        .split(", ")
+        .map(|column| format!("job.{}", column))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Selects `JobDetailRow`s; queued jobs are numbered in the order `lease`
+/// takes them.
+fn detail_query() -> String {
+    format!(
+        r#"
+        SELECT {}, job.last_error, job.run_after, job.updated_at, job.completed_at,
+               queued.queue_position
+        FROM analysis_jobs AS job
+        LEFT JOIN (
+            SELECT id, ROW_NUMBER() OVER (ORDER BY priority DESC, run_after, id) AS queue_position
+            FROM analysis_jobs WHERE status = 'queued'
+        ) AS queued ON queued.id = job.id
+        "#,
+        qualified_job_columns()
+    )
+}
+
+fn detail_from_row(row: JobDetailRow, completed_recently: u64) -> AnalysisJobDetail {
+    let attempts = row.job.attempts.max(0) as u32;
+    let queue_position = row.queue_position.map(|position| position as u64);
+    let estimated_start_at = queue_position.and_then(|position| {
+        estimate_start(position, completed_recently, THROUGHPUT_WINDOW, row.run_after, Utc::now())
+    });
+    AnalysisJobDetail {
+        job: AnalysisJob::from(row.job),
+        attempts,
+        last_error: row.last_error,
+        run_after: row.run_after,
+        updated_at: row.updated_at,
+        completed_at: row.completed_at,
+        queue_position,
+        estimated_start_at,
     }
-}
\ No newline at end of file
+}
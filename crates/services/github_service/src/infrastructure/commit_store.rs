@@ -0,0 +1,188 @@
+use crate::domain::{CommitSyncTarget, ContributorSummary, GitHubCommit, RepositoryCommit};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use uuid::Uuid;
+
+const COMMIT_COLUMNS: &str = "repository_id, sha, author_login, author_name, author_email, \
+     authored_at, committed_at, message, files_touched, additions, deletions, is_merge";
+
+/// Persists `repository_commits` and how far each repository's history has
+/// been synced in `repository_commit_syncs`.
+#[derive(Clone)]
+pub struct CommitStore {
+    db: Pool<Postgres>,
+}
+
+impl CommitStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    /// Start tracking the history of every monitored repository not yet
+    /// synced. New repositories are due straight away.
+    pub async fn ensure_syncs(&self) -> Result<u64> {
+        let created = sqlx::query(
+            r#"
+            INSERT INTO repository_commit_syncs (repository_id)
+            SELECT id FROM github_repositories
+            WHERE monitoring_enabled
+            ON CONFLICT (repository_id) DO NOTHING
+            "#,
+        )
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+
+        Ok(created)
+    }
+
+    /// Take up to `limit` repositories due a sync. Each is pushed `lease`
+    /// into the future so other instances pass over it; `record_sync` then
+    /// sets the real next sync.
+    pub async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<CommitSyncTarget>> {
+        let due = sqlx::query_as::<_, CommitSyncTarget>(
+            r#"
+            WITH due AS (
+                SELECT s.repository_id FROM repository_commit_syncs s
+                JOIN github_repositories r ON r.id = s.repository_id
+                WHERE r.monitoring_enabled AND s.next_sync_at <= NOW()
+                ORDER BY s.next_sync_at
+                LIMIT $1
+                FOR UPDATE OF s SKIP LOCKED
+            )
+            UPDATE repository_commit_syncs AS s
+            SET next_sync_at = NOW() + make_interval(secs => $2), updated_at = NOW()
+            FROM due, github_repositories r
+            WHERE s.repository_id = due.repository_id AND r.id = s.repository_id
+            RETURNING s.repository_id, r.owner_username, r.repo_name, s.last_seen_sha
+            "#,
+        )
+        .bind(limit)
+        .bind(lease.as_secs_f64())
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(due)
+    }
+
+    /// Store a commit. Commits already stored are left as they are.
+    pub async fn insert(&self, repository_id: Uuid, commit: &GitHubCommit) -> Result<bool> {
+        let author = commit.commit.author.as_ref();
+        let committer = commit.commit.committer.as_ref().or(author);
+        let authored_at = author.or(committer).map_or_else(Utc::now, |sig| sig.date);
+        let committed_at = committer.map_or(authored_at, |sig| sig.date);
+        let files_touched: Vec<&str> =
+            commit.files.iter().map(|file| file.filename.as_str()).collect();
+        let (additions, deletions) = match &commit.stats {
+            Some(stats) => (stats.additions, stats.deletions),
+            None => commit.files.iter().fold((0, 0), |(added, deleted), file| {
+                (added + file.additions, deleted + file.deletions)
+            }),
+        };
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO repository_commits (
+                repository_id, sha, author_login, author_name, author_email,
+                authored_at, committed_at, message, files_touched, additions, deletions, is_merge
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (repository_id, sha) DO NOTHING
+            "#,
+        )
+        .bind(repository_id)
+        .bind(&commit.sha)
+        .bind(commit.author.as_ref().map(|account| account.login.as_str()))
+        .bind(author.map_or("", |sig| sig.name.as_str()))
+        .bind(author.map_or("", |sig| sig.email.as_str()))
+        .bind(authored_at)
+        .bind(committed_at)
+        .bind(&commit.commit.message)
+        .bind(&files_touched)
+        .bind(additions as i32)
+        .bind(deletions as i32)
+        .bind(commit.parents.len() > 1)
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+
+        Ok(inserted > 0)
+    }
+
+    /// Record a claimed sync's outcome: the newest commit now ingested, if
+    /// any were, and when to sync next.
+    pub async fn record_sync(
+        &self,
+        repository_id: Uuid,
+        last_seen_sha: Option<&str>,
+        next_sync_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE repository_commit_syncs
+            SET last_seen_sha = COALESCE($2, last_seen_sha), last_synced_at = NOW(),
+                next_sync_at = $3, updated_at = NOW()
+            WHERE repository_id = $1
+            "#,
+        )
+        .bind(repository_id)
+        .bind(last_seen_sha)
+        .bind(next_sync_at)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// A repository's commits, newest first.
+    pub async fn list(
+        &self,
+        repository_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<RepositoryCommit>> {
+        let commits = sqlx::query_as::<_, RepositoryCommit>(&format!(
+            r#"
+            SELECT {} FROM repository_commits
+            WHERE repository_id = $1
+            ORDER BY committed_at DESC, sha
+            LIMIT $2 OFFSET $3
+            "#,
+            COMMIT_COLUMNS
+        ))
+        .bind(repository_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(commits)
+    }
+
+    /// A repository's authors by number of commits. Authors without a
+    /// GitHub account are grouped by email. Merge commits are not counted.
+    pub async fn contributors(&self, repository_id: Uuid) -> Result<Vec<ContributorSummary>> {
+        let contributors = sqlx::query_as::<_, ContributorSummary>(
+            r#"
+            SELECT COALESCE(author_login, author_email) AS author,
+                   author_login IS NOT NULL AS has_account,
+                   COUNT(*) AS commits,
+                   COALESCE(SUM(additions), 0)::BIGINT AS additions,
+                   COALESCE(SUM(deletions), 0)::BIGINT AS deletions,
+                   MIN(authored_at) AS first_commit_at,
+                   MAX(authored_at) AS last_commit_at
+            FROM repository_commits
+            WHERE repository_id = $1 AND NOT is_merge
+            GROUP BY COALESCE(author_login, author_email), author_login IS NOT NULL
+            ORDER BY commits DESC, author
+            "#,
+        )
+        .bind(repository_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(contributors)
+    }
+}
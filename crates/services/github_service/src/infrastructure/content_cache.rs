@@ -0,0 +1,125 @@
+use crate::error::Result;
+use sqlx::{FromRow, Pool, Postgres};
+use std::time::Duration;
+
+/// A contents API response as last served by GitHub.
+#[derive(Debug, Clone, FromRow)]
+pub struct CachedListing {
+    pub etag: Option<String>,
+    pub body: serde_json::Value,
+}
+
+/// Postgres cache of repository contents, so re-analysing a repository only
+/// downloads what changed. Directory listings live in
+/// `github_content_listings` with their ETag; file contents live in
+/// `github_blobs` keyed by git blob SHA and are shared across commits.
+#[derive(Clone)]
+pub struct ContentCache {
+    db: Pool<Postgres>,
+}
+
+impl ContentCache {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    pub async fn listing(
+        &self,
+        owner: &str,
+        repo: &str,
+        ref_name: &str,
+        path: &str,
+    ) -> Result<Option<CachedListing>> {
+        let listing = sqlx::query_as::<_, CachedListing>(
+            r#"
+            UPDATE github_content_listings
+            SET last_used_at = NOW()
+            WHERE owner = $1 AND repo = $2 AND ref_name = $3 AND path = $4
+            RETURNING etag, body
+            "#,
+        )
+        .bind(owner)
+        .bind(repo)
+        .bind(ref_name)
+        .bind(path)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(listing)
+    }
+
+    pub async fn store_listing(
+        &self,
+        owner: &str,
+        repo: &str,
+        ref_name: &str,
+        path: &str,
+        etag: Option<&str>,
+        body: &serde_json::Value,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO github_content_listings (owner, repo, ref_name, path, etag, body)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (owner, repo, ref_name, path) DO UPDATE
+            SET etag = EXCLUDED.etag, body = EXCLUDED.body,
+                fetched_at = NOW(), last_used_at = NOW()
+            "#,
+        )
+        .bind(owner)
+        .bind(repo)
+        .bind(ref_name)
+        .bind(path)
+        .bind(etag)
+        .bind(body)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn blob(&self, sha: &str) -> Result<Option<String>> {
+        let content = sqlx::query_scalar(
+            "UPDATE github_blobs SET last_used_at = NOW() WHERE sha = $1 RETURNING content",
+        )
+        .bind(sha)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(content)
+    }
+
+    pub async fn store_blob(&self, sha: &str, content: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO github_blobs (sha, content, size)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (sha) DO UPDATE SET last_used_at = NOW()
+            "#,
+        )
+        .bind(sha)
+        .bind(content)
+        .bind(content.len() as i64)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drop listings and blobs unused for longer than `retention`. Returns
+    /// the number of rows removed.
+    pub async fn purge_unused(&self, retention: Duration) -> Result<u64> {
+        let mut purged = 0;
+        for table in ["github_content_listings", "github_blobs"] {
+            purged += sqlx::query(&format!(
+                "DELETE FROM {} WHERE last_used_at < NOW() - make_interval(secs => $1)",
+                table
+            ))
+            .bind(retention.as_secs_f64())
+            .execute(&self.db)
+            .await?
+            .rows_affected();
+        }
+        Ok(purged)
+    }
+}
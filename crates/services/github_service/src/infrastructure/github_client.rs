@@ -1,28 +1,45 @@
 use crate::domain::{
-  GitHubContent, GitHubRepository, GitHubUser, GitHubWebhook, GitHubWebhookConfig,
+  CommitComparison, GitHubCommit, GitHubContent, GitHubRepository, GitHubUser, GitHubWebhook,
+  GitHubWebhookConfig, OpenedPullRequest, OrganizationRepository, PatchPullRequest,
+  PullRequestFile, PullRequestState, RepositoryCloneSettings, RequestPriority, signature_matches,
 };
 use crate::error::{Error, Result};
-use crate::infrastructure::RateLimiterImpl;
+use crate::infrastructure::{ContentCache, RateLimiterImpl, RepositoryCloner};
 use base64::{Engine as _, engine::general_purpose};
-use hmac::{Hmac, Mac};
+use jd_utils::correlation::Correlate;
 use jsonwebtoken::{encode, Header, EncodingKey, Algorithm};
 use octocrab::Octocrab;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
-use sha2::Sha256;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{error, info, warn, debug};
 
-type HmacSha256 = Hmac<Sha256>;
 
+/// GitHub lists at most 3000 files per pull request, 100 per page.
+const PULL_REQUEST_FILES_PER_PAGE: usize = 100;
+const MAX_PULL_REQUEST_FILE_PAGES: usize = 30;
+const ORGANIZATION_REPOS_PER_PAGE: usize = 100;
+const COMMITS_PER_PAGE: usize = 100;
+
+/// Rate limit budget keys. Installation tokens each have their own budget,
+/// keyed `installation:{id}`.
+const APP_BUDGET: &str = "app";
+const PERSONAL_BUDGET: &str = "token";
+
+#[derive(Clone)]
 pub struct GitHubClient {
   client: Octocrab,
   rate_limiter: Arc<RateLimiterImpl>,
+  priority: RequestPriority,
   webhook_secret: String,
+  previous_webhook_secrets: Vec<String>,
   app_id: Option<u64>,
   private_key: Option<String>,
+  personal_token: Option<String>,
   http_client: Client,
+  content_cache: Option<ContentCache>,
+  cloner: Option<RepositoryCloner>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +51,63 @@ pub struct GitHubFile {
     pub download_url: Option<String>,
 }
 
+/// One entry of a contents API response. `content` is only present when a
+/// single file was requested, and is empty for files over 1 MB.
+#[derive(Debug, Deserialize)]
+struct ContentEntry {
+    name: String,
+    path: String,
+    sha: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(rename = "type")]
+    kind: String,
+    download_url: Option<String>,
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Contents {
+    Directory(Vec<ContentEntry>),
+    File(ContentEntry),
+}
+
+/// A recursive git trees API response. `truncated` is set when the tree had
+/// more entries than GitHub returns at once.
+#[derive(Debug, Deserialize)]
+struct GitTree {
+    tree: Vec<GitTreeEntry>,
+    truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitTreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    size: u64,
+}
+
+/// A bearer token and the rate limit budget its requests count against.
+struct Credentials {
+    token: String,
+    budget: String,
+}
+
+/// A repository webhook, as listed by `GET /repos/{owner}/{repo}/hooks`.
+#[derive(Debug, Deserialize)]
+struct RepositoryHook {
+    id: u64,
+    config: RepositoryHookConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryHookConfig {
+    url: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct GitHubJWTClaims {
     iat: u64,
@@ -44,32 +118,32 @@ struct GitHubJWTClaims {
 impl GitHubClient {
   pub fn new(token: String, webhook_secret: String) -> Result<Self> {
     let client = Octocrab::builder()
-      .personal_token(token)
+      .personal_token(token.clone())
       .build()
       .map_err(|e| Error::GitHubApi(e.to_string()))?;
 
-    let rate_limiter = Arc::new(RateLimiterImpl::new(
-      5000, // GitHub allows 5000 requests per hour for authenticated users
-      std::time::Duration::from_secs(3600), // 1 hour window
-    ));
+    // GitHub allows 5000 requests per hour for authenticated users
+    let rate_limiter = Arc::new(RateLimiterImpl::new(5000));
 
     let http_client = Client::new();
 
     Ok(Self { 
       client, 
       rate_limiter, 
+      priority: RequestPriority::default(),
       webhook_secret,
+      previous_webhook_secrets: Vec::new(),
       app_id: None,
       private_key: None,
+      personal_token: Some(token),
       http_client,
+      content_cache: None,
+      cloner: None,
     })
   }
 
   pub fn new_app(app_id: u64, private_key: String, webhook_secret: String) -> Result<Self> {
-    let rate_limiter = Arc::new(RateLimiterImpl::new(
-      5000,
-      std::time::Duration::from_secs(3600),
-    ));
+    let rate_limiter = Arc::new(RateLimiterImpl::new(5000));
 
     let http_client = Client::new();
 
@@ -81,13 +155,76 @@ impl GitHubClient {
     Ok(Self {
       client,
       rate_limiter,
+      priority: RequestPriority::default(),
       webhook_secret,
+      previous_webhook_secrets: Vec::new(),
       app_id: Some(app_id),
       private_key: Some(private_key),
+      personal_token: None,
       http_client,
+      content_cache: None,
+      cloner: None,
     })
   }
 
+  /// Also accept deliveries signed with `secrets`, for webhooks still on a
+  /// secret the configured one replaced.
+  pub fn with_previous_webhook_secrets(mut self, secrets: Vec<String>) -> Self {
+    self.previous_webhook_secrets = secrets;
+    self
+  }
+
+  /// The configured webhook secret followed by any previous ones.
+  pub fn webhook_secrets(&self) -> Vec<String> {
+    std::iter::once(self.webhook_secret.clone())
+      .chain(self.previous_webhook_secrets.iter().cloned())
+      .collect()
+  }
+
+  /// Serve repository contents through `cache` instead of downloading every
+  /// file on each analysis.
+  pub fn with_content_cache(mut self, cache: ContentCache) -> Self {
+    self.content_cache = Some(cache);
+    self
+  }
+
+  /// Fall back to cloning repositories whose trees have more files than
+  /// `cloner`'s threshold.
+  pub fn with_cloner(mut self, cloner: RepositoryCloner) -> Self {
+    self.cloner = Some(cloner);
+    self
+  }
+
+  /// Count requests against `rate_limiter`, typically one shared by every
+  /// client in the process so all of them see the same budgets.
+  pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiterImpl>) -> Self {
+    self.rate_limiter = rate_limiter;
+    self
+  }
+
+  /// Priority of this client's requests. Low-priority requests are turned
+  /// away while a budget is down to its reserve.
+  pub fn with_priority(mut self, priority: RequestPriority) -> Self {
+    self.priority = priority;
+    self
+  }
+
+  /// Send `request` with `credentials`, taking one request from their budget
+  /// and updating it from the rate limit headers of the response.
+  async fn send(&self, credentials: &Credentials, request: RequestBuilder) -> Result<Response> {
+    self.rate_limiter.acquire(&credentials.budget, self.priority).await?;
+
+    let response = request
+      .header("Authorization", format!("Bearer {}", credentials.token))
+      .correlated()
+      .send()
+      .await
+      .map_err(|e| Error::GitHubApi(format!("HTTP request failed: {}", e)))?;
+
+    self.rate_limiter.observe(&credentials.budget, response.headers()).await;
+    Ok(response)
+  }
+
   // GitHub App authentication methods
   fn generate_jwt_token(&self) -> Result<String> {
     let app_id = self.app_id.ok_or_else(|| Error::Internal("App ID not configured".to_string()))?;
@@ -112,22 +249,31 @@ impl GitHubClient {
       .map_err(|e| Error::Internal(format!("JWT encoding error: {}", e)))
   }
 
-  async fn get_installation_token(&self, installation_id: u64) -> Result<String> {
-    let jwt_token = self.generate_jwt_token()?;
+  fn app_credentials(&self) -> Result<Credentials> {
+    Ok(Credentials { token: self.generate_jwt_token()?, budget: APP_BUDGET.to_string() })
+  }
+
+  fn personal_credentials(&self) -> Result<Credentials> {
+    let token = self
+      .personal_token
+      .clone()
+      .ok_or_else(|| Error::ConfigurationError("No GitHub token configured".to_string()))?;
+    Ok(Credentials { token, budget: PERSONAL_BUDGET.to_string() })
+  }
+
+  async fn installation_credentials(&self, installation_id: u64) -> Result<Credentials> {
+    let app = self.app_credentials()?;
 
     let url = format!(
       "https://api.github.com/app/installations/{}/access_tokens",
       installation_id
     );
 
-    let response = self.http_client
+    let request = self.http_client
       .post(&url)
-      .header("Authorization", format!("Bearer {}", jwt_token))
       .header("Accept", "application/vnd.github.v3+json")
-      .header("User-Agent", "ZK-Guardian-Bot/1.0")
-      .send()
-      .await
-      .map_err(|e| Error::GitHubApi(format!("HTTP request failed: {}", e)))?;
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&app, request).await?;
 
     if !response.status().is_success() {
       let error_text = response.text().await.unwrap_or_default();
@@ -137,34 +283,23 @@ impl GitHubClient {
     let token_response: serde_json::Value = response.json().await
       .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
 
-    token_response["token"]
+    let token = token_response["token"]
       .as_str()
       .map(|s| s.to_string())
-      .ok_or_else(|| Error::GitHubApi("No token in response".to_string()))
-  }
-
-  async fn create_octocrab_client(&self, installation_id: u64) -> Result<Octocrab> {
-    let token = self.get_installation_token(installation_id).await?;
-
-    Octocrab::builder()
-      .personal_token(token)
-      .build()
-      .map_err(|e| Error::GitHubApi(format!("Octocrab client creation failed: {}", e)))
+      .ok_or_else(|| Error::GitHubApi("No token in response".to_string()))?;
+    Ok(Credentials { token, budget: format!("installation:{}", installation_id) })
   }
 
   pub async fn get_installation_id_for_repo(&self, owner: &str, repo: &str) -> Result<u64> {
-    let jwt_token = self.generate_jwt_token()?;
+    let app = self.app_credentials()?;
 
     let url = format!("https://api.github.com/repos/{}/{}/installation", owner, repo);
 
-    let response = self.http_client
+    let request = self.http_client
       .get(&url)
-      .header("Authorization", format!("Bearer {}", jwt_token))
       .header("Accept", "application/vnd.github.v3+json")
-      .header("User-Agent", "ZK-Guardian-Bot/1.0")
-      .send()
-      .await
-      .map_err(|e| Error::GitHubApi(format!("HTTP request failed: {}", e)))?;
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&app, request).await?;
 
     if !response.status().is_success() {
       let error_text = response.text().await.unwrap_or_default();
@@ -179,6 +314,220 @@ impl GitHubClient {
       .ok_or_else(|| Error::GitHubApi("No installation ID in response".to_string()))
   }
 
+  /// Credentials for organization-wide calls: the app installation's when
+  /// running as a GitHub App, the personal token otherwise.
+  async fn organization_credentials(&self, org: &str) -> Result<Credentials> {
+    if self.app_id.is_none() {
+      return self.personal_credentials();
+    }
+
+    let app = self.app_credentials()?;
+    let url = format!("https://api.github.com/orgs/{}/installation", org);
+
+    let request = self.http_client
+      .get(&url)
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&app, request).await?;
+
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!(
+        "GitHub App is not installed on {}: {}",
+        org, error_text
+      )));
+    }
+
+    let installation: serde_json::Value = response.json().await
+      .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+    let installation_id = installation["id"]
+      .as_u64()
+      .ok_or_else(|| Error::GitHubApi("No installation ID in response".to_string()))?;
+
+    self.installation_credentials(installation_id).await
+  }
+
+  /// Credentials for calls against one repository: the app installation's
+  /// when running as a GitHub App, the personal token otherwise.
+  async fn repository_credentials(&self, owner: &str, repo: &str) -> Result<Credentials> {
+    if self.app_id.is_none() {
+      return self.personal_credentials();
+    }
+
+    let installation_id = self.get_installation_id_for_repo(owner, repo).await?;
+    self.installation_credentials(installation_id).await
+  }
+
+  /// SHA of the commit at the tip of the repository's default branch.
+  pub async fn get_head_commit_sha(&self, owner: &str, repo: &str) -> Result<String> {
+    let credentials = self.repository_credentials(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/commits/HEAD", owner, repo);
+    let request = self.http_client
+      .get(&url)
+      .header("Accept", "application/vnd.github.sha")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&credentials, request).await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+      return Err(Error::RepositoryNotFound { owner: owner.to_string(), repo: repo.to_string() });
+    }
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+
+    let sha = response
+      .text()
+      .await
+      .map_err(|e| Error::GitHubApi(format!("Failed to read response: {}", e)))?;
+    Ok(sha.trim().to_string())
+  }
+
+  /// Every repository in `org` the configured credentials can see.
+  pub async fn list_organization_repositories(
+    &self,
+    org: &str,
+  ) -> Result<Vec<OrganizationRepository>> {
+    let credentials = self.organization_credentials(org).await?;
+
+    let mut repositories = Vec::new();
+    for page in 1.. {
+      let url = format!(
+        "https://api.github.com/orgs/{}/repos?type=all&per_page={}&page={}",
+        org, ORGANIZATION_REPOS_PER_PAGE, page
+      );
+
+      let request = self.http_client
+        .get(&url)
+        .header("Accept", "application/vnd.github.v3+json")
+        .header("User-Agent", "ZK-Guardian-Bot/1.0");
+      let response = self.send(&credentials, request).await?;
+
+      if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+      }
+
+      let batch: Vec<OrganizationRepository> = response.json().await
+        .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+      let last_page = batch.len() < ORGANIZATION_REPOS_PER_PAGE;
+      repositories.extend(batch);
+      if last_page {
+        break;
+      }
+    }
+
+    info!("Organization {} has {} repositories", org, repositories.len());
+    Ok(repositories)
+  }
+
+  /// SHAs on the default branch, newest first, stopping at `until_sha` or
+  /// after `max` commits, whichever comes first. `until_sha` itself is
+  /// included so callers can tell it was reached.
+  pub async fn list_commit_shas(
+    &self,
+    owner: &str,
+    repo: &str,
+    until_sha: Option<&str>,
+    max: usize,
+  ) -> Result<Vec<String>> {
+    let credentials = self.repository_credentials(owner, repo).await?;
+
+    let mut shas = Vec::new();
+    for page in 1.. {
+      let url = format!(
+        "https://api.github.com/repos/{}/{}/commits?per_page={}&page={}",
+        owner, repo, COMMITS_PER_PAGE, page
+      );
+      let request = self.http_client
+        .get(&url)
+        .header("Accept", "application/vnd.github.v3+json")
+        .header("User-Agent", "ZK-Guardian-Bot/1.0");
+      let response = self.send(&credentials, request).await?;
+
+      if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(Error::RepositoryNotFound { owner: owner.to_string(), repo: repo.to_string() });
+      }
+      // An empty repository has no commits to list.
+      if response.status() == reqwest::StatusCode::CONFLICT {
+        break;
+      }
+      if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+      }
+
+      let batch: Vec<GitHubCommit> = response.json().await
+        .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+      let last_page = batch.len() < COMMITS_PER_PAGE;
+      for commit in batch {
+        let reached = until_sha == Some(commit.sha.as_str());
+        shas.push(commit.sha);
+        if reached || shas.len() >= max {
+          return Ok(shas);
+        }
+      }
+      if last_page {
+        break;
+      }
+    }
+
+    Ok(shas)
+  }
+
+  /// A single commit, with the files it touched.
+  pub async fn get_commit(&self, owner: &str, repo: &str, sha: &str) -> Result<GitHubCommit> {
+    let credentials = self.repository_credentials(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/commits/{}", owner, repo, sha);
+    let request = self.http_client
+      .get(&url)
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&credentials, request).await?;
+
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+
+    response.json().await
+      .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))
+  }
+
+  /// Files changed from `base` to `head`. Check
+  /// [`CommitComparison::is_complete`] before relying on the file list.
+  pub async fn compare_commits(
+    &self,
+    installation_id: u64,
+    owner: &str,
+    repo: &str,
+    base: &str,
+    head: &str,
+  ) -> Result<CommitComparison> {
+    let credentials = self.installation_credentials(installation_id).await?;
+
+    let url = format!(
+      "https://api.github.com/repos/{}/{}/compare/{}...{}",
+      owner, repo, base, head
+    );
+    let request = self.http_client
+      .get(&url)
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&credentials, request).await?;
+
+    // A base rewritten away by a force push is a 404 too.
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+
+    response.json().await
+      .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))
+  }
+
   // Enhanced file extraction for smart contracts
   pub async fn get_repository_files(
     &self,
@@ -188,26 +537,27 @@ impl GitHubClient {
     commit_sha: Option<&str>,
     file_extensions: &[&str],
   ) -> Result<Vec<GitHubFile>> {
-    let octocrab = self.create_octocrab_client(installation_id).await?;
+    let credentials = self.installation_credentials(installation_id).await?;
+    let ref_name = commit_sha.unwrap_or("main");
+
+    if let Some(cloner) = &self.cloner {
+      if self.should_clone(cloner, &credentials, owner, repo, ref_name).await? {
+        return cloner
+          .clone_files(&credentials.token, owner, repo, ref_name, file_extensions)
+          .await;
+      }
+    }
 
     info!("Fetching repository files for {}/{}", owner, repo);
 
-    let contents = octocrab
-      .repos(owner, repo)
-      .get_content()
-      .r#ref(commit_sha.unwrap_or("main"))
-      .send()
-      .await
-      .map_err(|e| Error::GitHubApi(format!("Failed to get repository contents: {}", e)))?;
-
     let mut files = Vec::new();
 
     self.process_contents_recursive(
-      &octocrab,
+      &credentials,
       owner,
       repo,
-      commit_sha.unwrap_or("main"),
-      contents.items,
+      ref_name,
+      String::new(),
       file_extensions,
       &mut files,
     ).await?;
@@ -216,60 +566,352 @@ impl GitHubClient {
     Ok(files)
   }
 
+  /// Paths of every file in the repository at `ref_name`. Very large trees
+  /// are cut short by GitHub.
+  pub async fn get_tree_paths(
+    &self,
+    owner: &str,
+    repo: &str,
+    ref_name: &str,
+  ) -> Result<Vec<String>> {
+    let credentials = self.repository_credentials(owner, repo).await?;
+    let tree = self.fetch_tree(&credentials, owner, repo, ref_name).await?;
+    if tree.truncated {
+      warn!("Tree of {}/{} at {} was truncated", owner, repo, ref_name);
+    }
+
+    Ok(
+      tree
+        .tree
+        .into_iter()
+        .filter(|entry| entry.kind == "blob")
+        .map(|entry| entry.path)
+        .collect(),
+    )
+  }
+
+  /// `GET /repos/{owner}/{repo}/git/trees/{ref}?recursive=1`.
+  async fn fetch_tree(
+    &self,
+    credentials: &Credentials,
+    owner: &str,
+    repo: &str,
+    ref_name: &str,
+  ) -> Result<GitTree> {
+    let url = format!(
+      "https://api.github.com/repos/{}/{}/git/trees/{}?recursive=1",
+      owner, repo, ref_name
+    );
+    let request = self.http_client
+      .get(&url)
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(credentials, request).await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+      return Err(Error::RepositoryNotFound { owner: owner.to_string(), repo: repo.to_string() });
+    }
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+
+    response.json().await
+      .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))
+  }
+
+  /// Whether the tree at `ref_name` has too many files for the contents API.
+  /// Fails if what would be checked out is over the clone size limit.
+  async fn should_clone(
+    &self,
+    cloner: &RepositoryCloner,
+    credentials: &Credentials,
+    owner: &str,
+    repo: &str,
+    ref_name: &str,
+  ) -> Result<bool> {
+    let tree = self.fetch_tree(credentials, owner, repo, ref_name).await?;
+    let settings = cloner.settings();
+    let blobs: Vec<&GitTreeEntry> = tree
+      .tree
+      .iter()
+      .filter(|entry| entry.kind == "blob" && settings.includes(&entry.path))
+      .collect();
+
+    let size_bytes: u64 = blobs.iter().map(|entry| entry.size).sum();
+    if size_bytes > settings.max_size_bytes {
+      return Err(Error::RepositoryTooLarge { size_bytes, limit_bytes: settings.max_size_bytes });
+    }
+
+    let clone = tree.truncated || blobs.len() > settings.file_threshold;
+    if clone {
+      info!(
+        "{}/{} has {}{} files at {}; cloning instead of using the contents API",
+        owner,
+        repo,
+        blobs.len(),
+        if tree.truncated { "+" } else { "" },
+        ref_name
+      );
+    }
+    Ok(clone)
+  }
+
+  /// Files changed by a pull request, with their diff hunks.
+  pub async fn list_pull_request_files(
+    &self,
+    installation_id: u64,
+    owner: &str,
+    repo: &str,
+    number: u64,
+  ) -> Result<Vec<PullRequestFile>> {
+    let credentials = self.installation_credentials(installation_id).await?;
+
+    let mut files = Vec::new();
+    for page in 1..=MAX_PULL_REQUEST_FILE_PAGES {
+      let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/files?per_page={}&page={}",
+        owner, repo, number, PULL_REQUEST_FILES_PER_PAGE, page
+      );
+
+      let request = self.http_client
+        .get(&url)
+        .header("Accept", "application/vnd.github.v3+json")
+        .header("User-Agent", "ZK-Guardian-Bot/1.0");
+      let response = self.send(&credentials, request).await?;
+
+      if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+      }
+
+      let batch: Vec<PullRequestFile> = response.json().await
+        .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+      let last_page = batch.len() < PULL_REQUEST_FILES_PER_PAGE;
+      files.extend(batch);
+      if last_page {
+        break;
+      }
+    }
+
+    info!("Pull request {}/{}#{} changes {} files", owner, repo, number, files.len());
+    Ok(files)
+  }
+
+  /// Contents of the given paths at `ref_name`. Paths that no longer exist
+  /// there are skipped.
+  pub async fn get_files_at_ref(
+    &self,
+    installation_id: u64,
+    owner: &str,
+    repo: &str,
+    paths: &[String],
+    ref_name: &str,
+  ) -> Result<Vec<GitHubFile>> {
+    let credentials = self.installation_credentials(installation_id).await?;
+    self.files_at_ref(&credentials, owner, repo, paths, ref_name).await
+  }
+
+  /// Like [`Self::get_files_at_ref`], with the repository's installation
+  /// when running as a GitHub App and the personal token otherwise.
+  pub async fn get_repository_files_at_ref(
+    &self,
+    owner: &str,
+    repo: &str,
+    paths: &[String],
+    ref_name: &str,
+  ) -> Result<Vec<GitHubFile>> {
+    let credentials = self.repository_credentials(owner, repo).await?;
+    self.files_at_ref(&credentials, owner, repo, paths, ref_name).await
+  }
+
+  async fn files_at_ref(
+    &self,
+    credentials: &Credentials,
+    owner: &str,
+    repo: &str,
+    paths: &[String],
+    ref_name: &str,
+  ) -> Result<Vec<GitHubFile>> {
+    let mut files = Vec::new();
+    for path in paths {
+      let entry = match self.fetch_contents(credentials, owner, repo, path, ref_name).await {
+        Ok(Contents::File(entry)) => entry,
+        Ok(Contents::Directory(_)) => {
+          debug!("{} is a directory at {}", path, ref_name);
+          continue;
+        }
+        Err(e) => {
+          warn!("Failed to fetch {} at {}: {}", path, ref_name, e);
+          continue;
+        }
+      };
+      match self.download_file_content(credentials, owner, repo, &entry).await {
+        Ok(content) => files.push(GitHubFile {
+          name: entry.name,
+          path: entry.path,
+          size: content.len() as u64,
+          content,
+          download_url: entry.download_url,
+        }),
+        Err(e) => warn!("Failed to fetch {} at {}: {}", path, ref_name, e),
+      }
+    }
+
+    Ok(files)
+  }
+
+  /// Post a review with a summary body and no inline comments. The review
+  /// is pinned to `commit_sha` so it is marked outdated by later pushes.
+  pub async fn create_pull_request_review(
+    &self,
+    installation_id: u64,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    commit_sha: &str,
+    body: &str,
+  ) -> Result<u64> {
+    let credentials = self.installation_credentials(installation_id).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/pulls/{}/reviews", owner, repo, number);
+    let request = self.http_client
+      .post(&url)
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0")
+      .json(&serde_json::json!({
+        "commit_id": commit_sha,
+        "body": body,
+        "event": "COMMENT",
+      }));
+    let response = self.send(&credentials, request).await?;
+
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+
+    let review: serde_json::Value = response.json().await
+      .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+    let review_id = review["id"].as_u64().unwrap_or_default();
+
+    info!("Posted review {} on {}/{}#{}", review_id, owner, repo, number);
+    Ok(review_id)
+  }
+
+  /// Push the patch to a new branch and open a pull request from it against
+  /// the default branch, with the app installation's credentials when
+  /// running as a GitHub App and the personal token otherwise.
+  pub async fn open_patch_pull_request(
+    &self,
+    owner: &str,
+    repo: &str,
+    patch: &PatchPullRequest,
+  ) -> Result<OpenedPullRequest> {
+    let credentials = self.repository_credentials(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}", owner, repo);
+    let request = self.http_client
+      .get(&url)
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&credentials, request).await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+      return Err(Error::RepositoryNotFound { owner: owner.to_string(), repo: repo.to_string() });
+    }
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+    let repository: serde_json::Value = response.json().await
+      .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+    let default_branch = repository["default_branch"].as_str().unwrap_or("main").to_string();
+
+    let cloner = self
+      .cloner
+      .clone()
+      .unwrap_or_else(|| RepositoryCloner::new(RepositoryCloneSettings::default()));
+    let base_ref = patch.base_commit.as_deref().unwrap_or(&default_branch);
+    cloner.push_patch(&credentials.token, owner, repo, base_ref, patch).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/pulls", owner, repo);
+    let request = self.http_client
+      .post(&url)
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0")
+      .json(&serde_json::json!({
+        "title": patch.title,
+        "body": patch.body,
+        "head": patch.branch,
+        "base": default_branch,
+      }));
+    let response = self.send(&credentials, request).await?;
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+
+    let pull: serde_json::Value = response.json().await
+      .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+    let opened = OpenedPullRequest {
+      number: pull["number"]
+        .as_u64()
+        .ok_or_else(|| Error::GitHubApi("No pull request number in response".to_string()))?,
+      html_url: pull["html_url"].as_str().unwrap_or_default().to_string(),
+      state: PullRequestState::from_github(
+        pull["state"].as_str().unwrap_or("open"),
+        !pull["merged_at"].is_null(),
+      ),
+      branch: patch.branch.clone(),
+    };
+
+    info!("Opened {}/{}#{} from {}", owner, repo, opened.number, patch.branch);
+    Ok(opened)
+  }
+
   fn process_contents_recursive<'a>(
     &'a self,
-    octocrab: &'a Octocrab,
+    credentials: &'a Credentials,
     owner: &'a str,
     repo: &'a str,
     ref_name: &'a str,
-    items: Vec<octocrab::models::repos::Content>,
+    path: String,
     file_extensions: &'a [&'a str],
     files: &'a mut Vec<GitHubFile>,
   ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + 'a + Send>> {
     Box::pin(async move {
+    let items = match self.fetch_contents(credentials, owner, repo, &path, ref_name).await? {
+      Contents::Directory(items) => items,
+      Contents::File(item) => vec![item],
+    };
+
     for item in items {
-      match item.r#type.as_str() {
+      match item.kind.as_str() {
         "file" => {
           if file_extensions.iter().any(|ext| item.name.ends_with(ext)) {
-            if let Some(content) = self.download_file_content(
-              octocrab,
-              owner,
-              repo,
-              &item.path,
-              ref_name,
-            ).await? {
-              files.push(GitHubFile {
-                name: item.name,
-                path: item.path,
-                content,
-                size: item.size as u64,
-                download_url: item.download_url,
-              });
-            }
+            let content = self.download_file_content(credentials, owner, repo, &item).await?;
+            files.push(GitHubFile {
+              name: item.name,
+              path: item.path,
+              content,
+              size: item.size,
+              download_url: item.download_url,
+            });
           }
         },
         "dir" => {
-          let dir_contents = octocrab
-            .repos(owner, repo)
-            .get_content()
-            .path(&item.path)
-            .r#ref(ref_name)
-            .send()
-            .await
-            .map_err(|e| Error::GitHubApi(format!("Failed to get directory contents: {}", e)))?;
-
           self.process_contents_recursive(
-            octocrab,
+            credentials,
             owner,
             repo,
             ref_name,
-            dir_contents.items,
+            item.path,
             file_extensions,
             files,
           ).await?;
         },
         _ => {
-          debug!("Skipping content type: {}", item.r#type);
+          debug!("Skipping content type: {}", item.kind);
         }
       }
     }
@@ -277,39 +919,134 @@ impl GitHubClient {
     })
   }
 
-  async fn download_file_content(
+  /// `GET /repos/{owner}/{repo}/contents/{path}`, through the content cache
+  /// when one is attached. Listings at a commit SHA cannot change and are
+  /// served from the cache outright; listings at a branch are revalidated
+  /// with `If-None-Match`, and a 304 reuses the cached body.
+  async fn fetch_contents(
     &self,
-    octocrab: &Octocrab,
+    credentials: &Credentials,
     owner: &str,
     repo: &str,
     path: &str,
     ref_name: &str,
-  ) -> Result<Option<String>> {
-    debug!("Downloading file: {}", path);
+  ) -> Result<Contents> {
+    let cached = match &self.content_cache {
+      Some(cache) => cache.listing(owner, repo, ref_name, path).await?,
+      None => None,
+    };
+    if let Some(cached) = cached.as_ref().filter(|_| is_commit_sha(ref_name)) {
+      debug!("Content cache hit for {}/{}:{} at {}", owner, repo, path, ref_name);
+      return parse_contents(cached.body.clone());
+    }
 
-    let content = octocrab
-      .repos(owner, repo)
-      .get_content()
-      .path(path)
-      .r#ref(ref_name)
-      .send()
-      .await
-      .map_err(|e| Error::GitHubApi(format!("Failed to get file content: {}", e)))?;
+    let url = match path {
+      "" => format!("https://api.github.com/repos/{}/{}/contents?ref={}", owner, repo, ref_name),
+      path => format!(
+        "https://api.github.com/repos/{}/{}/contents/{}?ref={}",
+        owner, repo, path, ref_name
+      ),
+    };
+    let mut request = self.http_client
+      .get(&url)
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    if let Some(etag) = cached.as_ref().and_then(|cached| cached.etag.as_deref()) {
+      request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+
+    let response = self.send(credentials, request).await?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+      if let Some(cached) = cached {
+        debug!("Contents of {}/{}:{} at {} not modified", owner, repo, path, ref_name);
+        return parse_contents(cached.body);
+      }
+    }
+
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+
+    let etag = response
+      .headers()
+      .get(reqwest::header::ETAG)
+      .and_then(|value| value.to_str().ok())
+      .map(str::to_string);
+    let mut body: serde_json::Value = response.json().await
+      .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+
+    let contents = parse_contents(body.clone())?;
+    if let Some(cache) = &self.content_cache {
+      // File bodies are cached as blobs; keep only the metadata here.
+      if let Some(file) = body.as_object_mut() {
+        file.remove("content");
+      }
+      if let Err(e) = cache
+        .store_listing(owner, repo, ref_name, path, etag.as_deref(), &body)
+        .await
+      {
+        warn!("Failed to cache contents of {}/{}:{}: {}", owner, repo, path, e);
+      }
+    }
+
+    Ok(contents)
+  }
+
+  /// Text of a file entry: from the blob cache, the content inlined in the
+  /// entry, or the git blobs API, in that order.
+  async fn download_file_content(
+    &self,
+    credentials: &Credentials,
+    owner: &str,
+    repo: &str,
+    entry: &ContentEntry,
+  ) -> Result<String> {
+    if let Some(cache) = &self.content_cache {
+      if let Some(content) = cache.blob(&entry.sha).await? {
+        return Ok(content);
+      }
+    }
+
+    let encoded = match entry.content.as_deref().filter(|content| !content.is_empty()) {
+      Some(content) => content.to_string(),
+      None => {
+        debug!("Downloading blob {} for {}", entry.sha, entry.path);
+
+        let url =
+          format!("https://api.github.com/repos/{}/{}/git/blobs/{}", owner, repo, entry.sha);
+        let request = self.http_client
+          .get(&url)
+          .header("Accept", "application/vnd.github.v3+json")
+          .header("User-Agent", "ZK-Guardian-Bot/1.0");
+        let response = self.send(credentials, request).await?;
+
+        if !response.status().is_success() {
+          let error_text = response.text().await.unwrap_or_default();
+          return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+        }
+
+        let blob: serde_json::Value = response.json().await
+          .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+        blob["content"].as_str().unwrap_or_default().to_string()
+      }
+    };
 
-    if let Some(file_content) = content.items.first() {
-      if let Some(encoded_content) = &file_content.content {
-        let decoded = general_purpose::STANDARD
-          .decode(encoded_content.replace('\n', ""))
-          .map_err(|e| Error::GitHubApi(format!("Base64 decode error: {}", e)))?;
+    let decoded = general_purpose::STANDARD
+      .decode(encoded.replace('\n', ""))
+      .map_err(|e| Error::GitHubApi(format!("Base64 decode error: {}", e)))?;
 
-        let content_str = String::from_utf8(decoded)
-          .map_err(|e| Error::GitHubApi(format!("UTF-8 decode error: {}", e)))?;
+    let content = String::from_utf8(decoded)
+      .map_err(|e| Error::GitHubApi(format!("UTF-8 decode error: {}", e)))?;
 
-        return Ok(Some(content_str));
+    if let Some(cache) = &self.content_cache {
+      if let Err(e) = cache.store_blob(&entry.sha, &content).await {
+        warn!("Failed to cache blob {}: {}", entry.sha, e);
       }
     }
 
-    Ok(None)
+    Ok(content)
   }
 
   pub fn detect_smart_contract_files<'a>(&self, files: &'a [GitHubFile]) -> Vec<&'a GitHubFile> {
@@ -348,7 +1085,7 @@ impl GitHubClient {
   }
 
   pub async fn get_repository(&self, owner: &str, repo: &str) -> Result<GitHubRepository> {
-    self.rate_limiter.check_limit("github_api").await?;
+    self.rate_limiter.acquire(PERSONAL_BUDGET, self.priority).await?;
 
     let repo_data = self.client.repos(owner, repo).get().await.map_err(|e| {
       error!("Failed to get repository {}/{}: {}", owner, repo, e);
@@ -400,7 +1137,7 @@ impl GitHubClient {
   }
 
   pub async fn list_user_repositories(&self, username: &str) -> Result<Vec<GitHubRepository>> {
-    self.rate_limiter.check_limit("github_api").await?;
+    self.rate_limiter.acquire(PERSONAL_BUDGET, self.priority).await?;
 
     // For now, return an empty list since the octocrab API methods are not compatible
     // In a production implementation, you would use the GitHub REST API directly
@@ -414,7 +1151,7 @@ impl GitHubClient {
     repo: &str,
     path: &str,
   ) -> Result<Vec<GitHubContent>> {
-    self.rate_limiter.check_limit("github_api").await?;
+    self.rate_limiter.acquire(PERSONAL_BUDGET, self.priority).await?;
 
     let contents = self
       .client
@@ -456,7 +1193,7 @@ impl GitHubClient {
     repo: &str,
     webhook_url: &str,
   ) -> Result<GitHubWebhook> {
-    self.rate_limiter.check_limit("github_api").await?;
+    self.rate_limiter.acquire(PERSONAL_BUDGET, self.priority).await?;
 
     // For now, we'll create a mock webhook response since octocrab's webhook API may not be fully available
     // In a production implementation, you would use the GitHub REST API directly or wait for octocrab updates
@@ -483,32 +1220,117 @@ impl GitHubClient {
   }
 
   pub fn verify_webhook_signature(&self, payload: &[u8], signature: &str) -> Result<bool> {
-    // Remove 'sha256=' prefix if present
-    let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+    Ok(self.verify_webhook_signature_with(&self.webhook_secrets(), payload, signature))
+  }
 
-    // Decode hex signature
-    let expected_signature = hex::decode(signature)
-      .map_err(|_| Error::InvalidWebhookSignature)?;
+  /// Whether `signature` matches `payload` under any of `secrets`.
+  pub fn verify_webhook_signature_with(
+    &self,
+    secrets: &[String],
+    payload: &[u8],
+    signature: &str,
+  ) -> bool {
+    if secrets.iter().any(|secret| signature_matches(secret, payload, signature)) {
+      debug!("Webhook signature verified successfully");
+      true
+    } else {
+      error!("Webhook signature verification failed");
+      false
+    }
+  }
 
-    // Create HMAC
-    let mut mac = HmacSha256::new_from_slice(self.webhook_secret.as_bytes())
-      .map_err(|e| Error::Internal(format!("HMAC error: {}", e)))?;
+  /// Point the repository's webhook for `webhook_url` at `secret`, creating
+  /// the webhook if the repository has none. Returns the webhook's id.
+  pub async fn set_repository_webhook_secret(
+    &self,
+    owner: &str,
+    repo: &str,
+    webhook_url: &str,
+    secret: &str,
+  ) -> Result<u64> {
+    let credentials = self.repository_credentials(owner, repo).await?;
+    let hooks_url = format!("https://api.github.com/repos/{}/{}/hooks", owner, repo);
 
-    mac.update(payload);
+    let request = self.http_client
+      .get(&hooks_url)
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&credentials, request).await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+      return Err(Error::RepositoryNotFound { owner: owner.to_string(), repo: repo.to_string() });
+    }
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+    let hooks: Vec<RepositoryHook> = response.json().await
+      .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
 
-    // Verify
-    match mac.verify_slice(&expected_signature) {
-      Ok(_) => {
-        debug!("Webhook signature verified successfully");
-        Ok(true)
-      },
-      Err(_) => {
-        error!("Webhook signature verification failed");
-        Ok(false)
+    let existing = hooks.iter().find(|hook| hook.config.url.as_deref() == Some(webhook_url));
+    let (request, hook_id) = match existing {
+      Some(hook) => {
+        let request = self.http_client
+          .patch(format!("{}/{}/config", hooks_url, hook.id))
+          .json(&serde_json::json!({
+            "url": webhook_url,
+            "content_type": "json",
+            "secret": secret,
+          }));
+        (request, Some(hook.id))
+      }
+      None => {
+        let request = self.http_client.post(&hooks_url).json(&serde_json::json!({
+          "name": "web",
+          "active": true,
+          "events": ["push", "pull_request", "release"],
+          "config": {
+            "url": webhook_url,
+            "content_type": "json",
+            "secret": secret,
+            "insecure_ssl": "0",
+          },
+        }));
+        (request, None)
+      }
+    };
+    let request = request
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&credentials, request).await?;
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+
+    match hook_id {
+      Some(id) => Ok(id),
+      None => {
+        let hook: RepositoryHook = response.json().await
+          .map_err(|e| Error::GitHubApi(format!("JSON parsing error: {}", e)))?;
+        info!("Registered webhook {} for {}/{}", hook.id, owner, repo);
+        Ok(hook.id)
       }
     }
   }
 
+  /// Point the GitHub App's webhook at `secret`.
+  pub async fn set_app_webhook_secret(&self, secret: &str) -> Result<()> {
+    let credentials = self.app_credentials()?;
+
+    let request = self.http_client
+      .patch("https://api.github.com/app/hook/config")
+      .json(&serde_json::json!({ "secret": secret }))
+      .header("Accept", "application/vnd.github.v3+json")
+      .header("User-Agent", "ZK-Guardian-Bot/1.0");
+    let response = self.send(&credentials, request).await?;
+    if !response.status().is_success() {
+      let error_text = response.text().await.unwrap_or_default();
+      return Err(Error::GitHubApi(format!("GitHub API error: {}", error_text)));
+    }
+
+    Ok(())
+  }
+
   fn might_contain_smart_contracts(&self, language: &Option<String>) -> bool {
     if let Some(lang) = language {
       let smart_contract_languages =
@@ -524,6 +1346,16 @@ impl GitHubClient {
   }
 }
 
+fn parse_contents(body: serde_json::Value) -> Result<Contents> {
+  serde_json::from_value(body)
+    .map_err(|e| Error::GitHubApi(format!("Unexpected contents response: {}", e)))
+}
+
+/// Full SHA-1 or SHA-256 object id, as opposed to a branch or tag name.
+fn is_commit_sha(ref_name: &str) -> bool {
+  matches!(ref_name.len(), 40 | 64) && ref_name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
 pub fn is_smart_contract_file(filename: &str) -> bool {
   let smart_contract_extensions = [".sol", ".rs", ".move", ".vy", ".func", ".tolk", "tact"];
   let smart_contract_patterns = ["contract", "interface", "library"];
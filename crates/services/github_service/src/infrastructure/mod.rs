@@ -1,7 +1,28 @@
 pub mod github_client;
 pub mod rate_limiter_impl;
 pub mod analysis_queue_impl;
+pub mod analysis_metrics;
+pub mod webhook_delivery_store;
+pub mod content_cache;
+pub mod reanalysis_schedule_store;
+pub mod repository_cloner;
+pub mod repository_package_store;
+pub mod commit_store;
+pub mod webhook_secret_store;
+pub mod patch_pull_request_store;
+pub mod package_deployment_store;
+pub mod repository_access_store;
 
 pub use github_client::*;
 pub use rate_limiter_impl::*;
-pub use analysis_queue_impl::*;
\ No newline at end of file
+pub use analysis_queue_impl::*;
+pub use webhook_delivery_store::*;
+pub use content_cache::*;
+pub use reanalysis_schedule_store::*;
+pub use repository_cloner::*;
+pub use repository_package_store::*;
+pub use commit_store::*;
+pub use webhook_secret_store::*;
+pub use patch_pull_request_store::*;
+pub use package_deployment_store::*;
+pub use repository_access_store::*;
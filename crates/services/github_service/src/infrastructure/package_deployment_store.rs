@@ -0,0 +1,151 @@
+use crate::domain::{PackageDeployment, VerificationStatus, VerifiedBuild};
+use crate::error::Result;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+const DEPLOYMENT_COLUMNS: &str = "id, repository_id, package_path, network, package_id, modules, \
+     modules_fetched_at, verification_status, verified_commit_sha, verification_message, \
+     verified_at, created_at";
+
+/// Persists `package_deployments`.
+#[derive(Clone)]
+pub struct PackageDeploymentStore {
+    db: Pool<Postgres>,
+}
+
+impl PackageDeploymentStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    pub async fn list(&self, repository_id: Uuid) -> Result<Vec<PackageDeployment>> {
+        let deployments = sqlx::query_as::<_, PackageDeployment>(&format!(
+            "SELECT {} FROM package_deployments WHERE repository_id = $1 \
+             ORDER BY created_at DESC",
+            DEPLOYMENT_COLUMNS
+        ))
+        .bind(repository_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(deployments)
+    }
+
+    pub async fn find(&self, repository_id: Uuid, id: Uuid) -> Result<Option<PackageDeployment>> {
+        let deployment = sqlx::query_as::<_, PackageDeployment>(&format!(
+            "SELECT {} FROM package_deployments WHERE repository_id = $1 AND id = $2",
+            DEPLOYMENT_COLUMNS
+        ))
+        .bind(repository_id)
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(deployment)
+    }
+
+    /// Register a deployment with the modules found on-chain. Registering a
+    /// package again moves it to this repository and path, and clears its
+    /// verification.
+    pub async fn register(
+        &self,
+        repository_id: Uuid,
+        package_path: &str,
+        network: &str,
+        package_id: &str,
+        modules: &[String],
+    ) -> Result<PackageDeployment> {
+        let deployment = sqlx::query_as::<_, PackageDeployment>(&format!(
+            r#"
+            INSERT INTO package_deployments
+                (repository_id, package_path, network, package_id, modules, modules_fetched_at)
+            VALUES ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (network, package_id) DO UPDATE
+            SET repository_id = EXCLUDED.repository_id,
+                package_path = EXCLUDED.package_path,
+                modules = EXCLUDED.modules,
+                modules_fetched_at = NOW(),
+                verification_status = 'unverified',
+                verified_commit_sha = NULL,
+                verification_message = NULL,
+                verified_at = NULL,
+                updated_at = NOW()
+            RETURNING {}
+            "#,
+            DEPLOYMENT_COLUMNS
+        ))
+        .bind(repository_id)
+        .bind(package_path)
+        .bind(network)
+        .bind(package_id)
+        .bind(modules)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(deployment)
+    }
+
+    pub async fn record_modules(&self, id: Uuid, modules: &[String]) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE package_deployments
+            SET modules = $2, modules_fetched_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(modules)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn record_verification(
+        &self,
+        id: Uuid,
+        status: VerificationStatus,
+        commit_sha: &str,
+        message: Option<&str>,
+    ) -> Result<PackageDeployment> {
+        let deployment = sqlx::query_as::<_, PackageDeployment>(&format!(
+            r#"
+            UPDATE package_deployments
+            SET verification_status = $2,
+                verified_commit_sha = $3,
+                verification_message = $4,
+                verified_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            DEPLOYMENT_COLUMNS
+        ))
+        .bind(id)
+        .bind(status)
+        .bind(commit_sha)
+        .bind(message)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(deployment)
+    }
+
+    /// The repository's most recently verified deployment.
+    pub async fn verified_build(&self, repository_id: Uuid) -> Result<Option<VerifiedBuild>> {
+        let build = sqlx::query_as::<_, VerifiedBuild>(
+            r#"
+            SELECT network, package_id, verified_commit_sha AS commit_sha, verified_at
+            FROM package_deployments
+            WHERE repository_id = $1 AND verification_status = 'verified'
+            ORDER BY verified_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(repository_id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(build)
+    }
+}
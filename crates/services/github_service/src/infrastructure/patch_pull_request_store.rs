@@ -0,0 +1,73 @@
+use crate::domain::PullRequestState;
+use crate::error::Result;
+use jd_messaging::{events::PatchMerged, outbox};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+/// Keeps the pull requests opened from patch proposals in step with GitHub.
+#[derive(Clone)]
+pub struct PatchPullRequestStore {
+    db: Pool<Postgres>,
+}
+
+impl PatchPullRequestStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    /// Record the state of pull request `number` of the repository with the
+    /// given GitHub id on the patch it was opened from. A merged pull
+    /// request marks the patch merged and records a `patch.merged` event the
+    /// first time. Returns whether a patch was updated.
+    pub async fn record_state(
+        &self,
+        github_repo_id: i64,
+        number: u64,
+        state: PullRequestState,
+    ) -> Result<bool> {
+        let mut tx = self.db.begin().await?;
+        // `previous` holds the status from before the update, locked so a
+        // redelivered webhook cannot record the merge twice.
+        let updated: Vec<(Uuid, Uuid, Uuid, String)> = sqlx::query_as(
+            r#"
+            UPDATE patch_proposals p
+            SET pr_state = $3,
+                pr_state_updated_at = NOW(),
+                status = CASE WHEN $3 = 'merged' THEN 'merged' ELSE p.status END,
+                applied_at = CASE WHEN $3 = 'merged' THEN COALESCE(p.applied_at, NOW())
+                                  ELSE p.applied_at END
+            FROM (
+                SELECT p.id, p.status::TEXT AS status
+                FROM patch_proposals p
+                JOIN github_repositories r ON r.id = p.repository_id
+                WHERE r.github_repo_id = $1 AND p.github_pr_number = $2
+                FOR UPDATE OF p
+            ) previous
+            WHERE p.id = previous.id
+            RETURNING p.id, p.vulnerability_id, p.repository_id, previous.status
+            "#,
+        )
+        .bind(github_repo_id)
+        .bind(number as i32)
+        .bind(state.as_str())
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if state == PullRequestState::Merged {
+            for (patch_id, vulnerability_id, repository_id, previous_status) in &updated {
+                if previous_status != "merged" {
+                    let event = PatchMerged {
+                        patch_id: *patch_id,
+                        vulnerability_id: *vulnerability_id,
+                        repository_id: *repository_id,
+                        pull_request_number: number as i32,
+                    };
+                    outbox::record(&mut *tx, &event).await?;
+                }
+            }
+        }
+        tx.commit().await?;
+
+        Ok(!updated.is_empty())
+    }
+}
@@ -1,85 +1,181 @@
+use crate::domain::{RateLimitBudget, RequestPriority};
 use crate::error::{Error, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use reqwest::header::HeaderMap;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
-use tracing::warn;
+use tracing::{debug, warn};
 
-pub struct RateLimiterImpl {
-    limits: Arc<Mutex<HashMap<String, RateLimit>>>,
-    max_requests: u32,
-    window_duration: Duration,
-}
+/// GitHub's primary rate limits reset hourly.
+const WINDOW_SECS: i64 = 60 * 60;
 
-struct RateLimit {
-    requests_made: u32,
-    window_start: Instant,
+/// Share of each budget held back for high-priority requests.
+const LOW_PRIORITY_RESERVE: f64 = 0.2;
+
+/// Tracks GitHub API budgets per credential. Each response's
+/// `X-RateLimit-Limit`/`Remaining`/`Reset` headers replace the local count,
+/// so the limiter follows what GitHub actually has left rather than a fixed
+/// window. Requests are counted down locally in between.
+pub struct RateLimiterImpl {
+    budgets: Arc<Mutex<HashMap<String, RateLimitBudget>>>,
+    /// Assumed limit for a credential no response has reported on yet.
+    default_limit: u32,
 }
 
 impl RateLimiterImpl {
-    pub fn new(max_requests: u32, window_duration: Duration) -> Self {
+    pub fn new(default_limit: u32) -> Self {
         Self {
-            limits: Arc::new(Mutex::new(HashMap::new())),
-            max_requests,
-            window_duration,
+            budgets: Arc::new(Mutex::new(HashMap::new())),
+            default_limit,
+        }
+    }
+
+    /// Take one request from `key`'s budget. Fails with `RateLimitExceeded`,
+    /// carrying the time to the reset, when the budget is spent or, for
+    /// low-priority requests, when only the reserve is left.
+    pub async fn acquire(&self, key: &str, priority: RequestPriority) -> Result<()> {
+        let mut budgets = self.budgets.lock().await;
+        let now = Utc::now();
+        let budget = budgets
+            .entry(key.to_string())
+            .or_insert_with(|| self.estimate(key, now));
+        if budget.reset_at <= now {
+            *budget = self.estimate(key, now);
+        }
+
+        if budget.remaining <= floor(budget.limit, priority) {
+            let retry_after = (budget.reset_at - now).num_seconds().max(1) as u64;
+            warn!(
+                "GitHub budget {} at {}/{}; deferring {:?} priority request for {}s",
+                key, budget.remaining, budget.limit, priority, retry_after
+            );
+            return Err(Error::RateLimitExceeded { retry_after_seconds: retry_after });
         }
+
+        budget.remaining -= 1;
+        Ok(())
+    }
+
+    /// Replace `key`'s budget with what a response's headers report.
+    /// Responses without rate limit headers are ignored.
+    pub async fn observe(&self, key: &str, headers: &HeaderMap) {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<i64>().ok())
+        };
+        let (Some(remaining), Some(reset)) =
+            (header("x-ratelimit-remaining"), header("x-ratelimit-reset"))
+        else {
+            return;
+        };
+        let Some(reset_at) = DateTime::from_timestamp(reset, 0) else {
+            return;
+        };
+
+        let limit = header("x-ratelimit-limit").map_or(self.default_limit, |limit| limit as u32);
+        debug!("GitHub budget {}: {}/{} until {}", key, remaining, limit, reset_at);
+
+        self.budgets.lock().await.insert(
+            key.to_string(),
+            RateLimitBudget {
+                key: key.to_string(),
+                limit,
+                remaining: remaining.max(0) as u32,
+                reset_at,
+                reported: true,
+            },
+        );
+    }
+
+    /// Whether any budget is down to its reserve, so low-priority work
+    /// should wait.
+    pub async fn is_constrained(&self) -> bool {
+        let now = Utc::now();
+        self.budgets.lock().await.values().any(|budget| {
+            budget.reset_at > now && budget.remaining <= floor(budget.limit, RequestPriority::Low)
+        })
     }
 
-    pub async fn check_limit(&self, key: &str) -> Result<()> {
-        let mut limits = self.limits.lock().await;
-        let now = Instant::now();
-
-        match limits.get_mut(key) {
-            Some(limit) => {
-                // Check if window has expired
-                if now.duration_since(limit.window_start) >= self.window_duration {
-                    limit.requests_made = 1;
-                    limit.window_start = now;
-                    Ok(())
-                } else if limit.requests_made < self.max_requests {
-                    limit.requests_made += 1;
-                    Ok(())
+    /// Every known budget, by key.
+    pub async fn snapshot(&self) -> Vec<RateLimitBudget> {
+        let now = Utc::now();
+        let mut budgets: Vec<_> = self
+            .budgets
+            .lock()
+            .await
+            .values()
+            .map(|budget| {
+                if budget.reset_at <= now {
+                    self.estimate(&budget.key, now)
                 } else {
-                    let retry_after = self.window_duration - now.duration_since(limit.window_start);
-                    warn!(
-                        "Rate limit exceeded for key: {}. Retry after: {:?}",
-                        key, retry_after
-                    );
-                    Err(Error::RateLimitExceeded {
-                        retry_after_seconds: retry_after.as_secs(),
-                    })
+                    budget.clone()
                 }
-            }
-            None => {
-                limits.insert(
-                    key.to_string(),
-                    RateLimit {
-                        requests_made: 1,
-                        window_start: now,
-                    },
-                );
-                Ok(())
-            }
-        }
+            })
+            .collect();
+        budgets.sort_by(|a, b| a.key.cmp(&b.key));
+        budgets
     }
 
     pub async fn reset_limit(&self, key: &str) {
-        let mut limits = self.limits.lock().await;
-        limits.remove(key);
+        let mut budgets = self.budgets.lock().await;
+        budgets.remove(key);
     }
 
     pub async fn get_remaining_requests(&self, key: &str) -> u32 {
-        let limits = self.limits.lock().await;
-        match limits.get(key) {
-            Some(limit) => {
-                let now = Instant::now();
-                if now.duration_since(limit.window_start) >= self.window_duration {
-                    self.max_requests
-                } else {
-                    self.max_requests.saturating_sub(limit.requests_made)
-                }
-            }
-            None => self.max_requests,
+        let budgets = self.budgets.lock().await;
+        match budgets.get(key) {
+            Some(budget) if budget.reset_at > Utc::now() => budget.remaining,
+            _ => self.default_limit,
+        }
+    }
+
+    fn estimate(&self, key: &str, now: DateTime<Utc>) -> RateLimitBudget {
+        RateLimitBudget {
+            key: key.to_string(),
+            limit: self.default_limit,
+            remaining: self.default_limit,
+            reset_at: now + ChronoDuration::seconds(WINDOW_SECS),
+            reported: false,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Requests left below which `priority` is turned away.
+fn floor(limit: u32, priority: RequestPriority) -> u32 {
+    match priority {
+        RequestPriority::High => 0,
+        RequestPriority::Low => (limit as f64 * LOW_PRIORITY_RESERVE).ceil() as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[tokio::test]
+    async fn low_priority_requests_are_deferred_once_the_reserve_is_reached() {
+        let limiter = RateLimiterImpl::new(5000);
+        let reset = (Utc::now() + ChronoDuration::minutes(10)).timestamp().to_string();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("100"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("21"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_str(&reset).unwrap());
+        limiter.observe("installation:1", &headers).await;
+
+        assert!(limiter.acquire("installation:1", RequestPriority::Low).await.is_ok());
+        assert!(limiter.is_constrained().await);
+        assert!(matches!(
+            limiter.acquire("installation:1", RequestPriority::Low).await,
+            Err(Error::RateLimitExceeded { retry_after_seconds }) if retry_after_seconds > 0
+        ));
+        assert!(limiter.acquire("installation:1", RequestPriority::High).await.is_ok());
+        assert_eq!(limiter.get_remaining_requests("installation:1").await, 19);
+
+        // Other credentials have budgets of their own.
+        assert!(limiter.acquire("installation:2", RequestPriority::Low).await.is_ok());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::domain::{DueReanalysis, ReanalysisSchedule};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use uuid::Uuid;
+
+const SCHEDULE_COLUMNS: &str =
+    "repository_id, schedule, enabled, last_head_sha, last_enqueued_at, next_run_at";
+
+/// Persists `repository_reanalysis_schedules`.
+#[derive(Clone)]
+pub struct ReanalysisScheduleStore {
+    db: Pool<Postgres>,
+}
+
+impl ReanalysisScheduleStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(&self, repository_id: Uuid) -> Result<Option<ReanalysisSchedule>> {
+        let schedule = sqlx::query_as::<_, ReanalysisSchedule>(&format!(
+            "SELECT {} FROM repository_reanalysis_schedules WHERE repository_id = $1",
+            SCHEDULE_COLUMNS
+        ))
+        .bind(repository_id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(schedule)
+    }
+
+    pub async fn save(
+        &self,
+        repository_id: Uuid,
+        schedule: Option<&str>,
+        enabled: bool,
+        next_run_at: DateTime<Utc>,
+    ) -> Result<ReanalysisSchedule> {
+        let saved = sqlx::query_as::<_, ReanalysisSchedule>(&format!(
+            r#"
+            INSERT INTO repository_reanalysis_schedules (
+                repository_id, schedule, enabled, next_run_at
+            )
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (repository_id) DO UPDATE
+            SET schedule = EXCLUDED.schedule, enabled = EXCLUDED.enabled,
+                next_run_at = EXCLUDED.next_run_at, updated_at = NOW()
+            RETURNING {}
+            "#,
+            SCHEDULE_COLUMNS
+        ))
+        .bind(repository_id)
+        .bind(schedule)
+        .bind(enabled)
+        .bind(next_run_at)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(saved)
+    }
+
+    /// Give every monitored repository without a schedule the default one,
+    /// first running at `next_run_at`.
+    pub async fn ensure_schedules(&self, next_run_at: DateTime<Utc>) -> Result<u64> {
+        let created = sqlx::query(
+            r#"
+            INSERT INTO repository_reanalysis_schedules (repository_id, next_run_at)
+            SELECT id, $1 FROM github_repositories
+            WHERE monitoring_enabled
+            ON CONFLICT (repository_id) DO NOTHING
+            "#,
+        )
+        .bind(next_run_at)
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+
+        Ok(created)
+    }
+
+    /// Scheduled analyses currently queued or being processed.
+    pub async fn in_flight(&self) -> Result<i64> {
+        let count = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM analysis_jobs
+            WHERE analysis_type = 'scheduled' AND status IN ('queued', 'processing')
+            "#,
+        )
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Take up to `limit` due schedules. Each is pushed `lease` into the
+    /// future so other instances pass over it; `record_run` then sets the
+    /// real next run.
+    pub async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<DueReanalysis>> {
+        let due = sqlx::query_as::<_, DueReanalysis>(
+            r#"
+            WITH due AS (
+                SELECT s.repository_id FROM repository_reanalysis_schedules s
+                JOIN github_repositories r ON r.id = s.repository_id
+                WHERE s.enabled AND r.monitoring_enabled AND s.next_run_at <= NOW()
+                ORDER BY s.next_run_at
+                LIMIT $1
+                FOR UPDATE OF s SKIP LOCKED
+            )
+            UPDATE repository_reanalysis_schedules AS s
+            SET next_run_at = NOW() + make_interval(secs => $2), updated_at = NOW()
+            FROM due, github_repositories r
+            WHERE s.repository_id = due.repository_id AND r.id = s.repository_id
+            RETURNING s.repository_id, r.github_repo_id, r.owner_username, r.repo_name,
+                s.schedule, s.last_head_sha
+            "#,
+        )
+        .bind(limit)
+        .bind(lease.as_secs_f64())
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(due)
+    }
+
+    /// Record a claimed schedule's outcome: the HEAD seen, if any, whether a
+    /// job was queued for it, and when to run next.
+    pub async fn record_run(
+        &self,
+        repository_id: Uuid,
+        head_sha: Option<&str>,
+        enqueued: bool,
+        next_run_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE repository_reanalysis_schedules
+            SET last_head_sha = COALESCE($2, last_head_sha),
+                last_enqueued_at = CASE WHEN $3 THEN NOW() ELSE last_enqueued_at END,
+                next_run_at = $4, updated_at = NOW()
+            WHERE repository_id = $1
+            "#,
+        )
+        .bind(repository_id)
+        .bind(head_sha)
+        .bind(enqueued)
+        .bind(next_run_at)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::error::Result;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+/// Answers who may manage a monitored repository.
+#[derive(Clone)]
+pub struct RepositoryAccessStore {
+    db: Pool<Postgres>,
+}
+
+impl RepositoryAccessStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    /// Whether `address` is a wallet of an owner or admin of an organization
+    /// monitoring the repository.
+    pub async fn can_manage(&self, repository_id: Uuid, address: &str) -> Result<bool> {
+        let can_manage = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM organization_repositories r
+                JOIN organization_members m ON m.organization_id = r.organization_id
+                JOIN user_wallets w ON w.user_id = m.user_id
+                WHERE r.github_repository_id = $1
+                  AND w.address IN ($2, LOWER($2))
+                  AND m.role IN ('owner', 'admin')
+            )
+            "#,
+        )
+        .bind(repository_id)
+        .bind(address)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(can_manage)
+    }
+}
@@ -0,0 +1,211 @@
+use crate::domain::{PatchPullRequest, RepositoryCloneSettings};
+use crate::error::{Error, Result};
+use crate::infrastructure::GitHubFile;
+use base64::{engine::general_purpose, Engine as _};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+use tracing::{debug, info};
+
+/// Identity patch commits are authored under.
+const PATCH_AUTHOR_NAME: &str = "ZK-Guardian Bot";
+const PATCH_AUTHOR_EMAIL: &str = "zk-guardian-bot@users.noreply.github.com";
+
+/// Reads repositories from a shallow, sparse `git` checkout instead of the
+/// contents API, for repositories with too many files to fetch one by one.
+/// Each checkout lives in its own temporary directory, removed as soon as
+/// the files have been read.
+#[derive(Debug, Clone)]
+pub struct RepositoryCloner {
+    settings: RepositoryCloneSettings,
+}
+
+impl RepositoryCloner {
+    pub fn new(settings: RepositoryCloneSettings) -> Self {
+        Self { settings }
+    }
+
+    pub fn settings(&self) -> &RepositoryCloneSettings {
+        &self.settings
+    }
+
+    /// Files at `ref_name` ending in one of `file_extensions`, fetched with
+    /// a depth-1 clone authenticated by `token`.
+    pub async fn clone_files(
+        &self,
+        token: &str,
+        owner: &str,
+        repo: &str,
+        ref_name: &str,
+        file_extensions: &[&str],
+    ) -> Result<Vec<GitHubFile>> {
+        let checkout = tempfile::Builder::new()
+            .prefix("github-clone-")
+            .tempdir()
+            .map_err(|e| Error::CloneFailed(format!("Failed to create clone directory: {}", e)))?;
+        let dir = checkout.path();
+        let url = format!("https://github.com/{}/{}.git", owner, repo);
+
+        info!("Cloning {}/{} at {} into {}", owner, repo, ref_name, dir.display());
+        self.git(dir, token, &["init", "--quiet"]).await?;
+        self.git(dir, token, &["remote", "add", "origin", &url]).await?;
+        if !self.settings.sparse_paths.is_empty() {
+            let mut args = vec!["sparse-checkout", "set", "--cone"];
+            args.extend(self.settings.sparse_paths.iter().map(|path| path.trim_matches('/')));
+            self.git(dir, token, &args).await?;
+        }
+        let fetch = ["fetch", "--quiet", "--depth", "1", "--no-tags", "origin", ref_name];
+        self.git(dir, token, &fetch).await?;
+        self.git(dir, token, &["checkout", "--quiet", "FETCH_HEAD"]).await?;
+
+        let root = dir.to_path_buf();
+        let extensions: Vec<String> = file_extensions.iter().map(|ext| ext.to_string()).collect();
+        let settings = self.settings.clone();
+        let files =
+            tokio::task::spawn_blocking(move || read_checkout(&root, &extensions, &settings))
+                .await
+                .map_err(|e| Error::Internal(format!("Reading clone failed: {}", e)))??;
+
+        info!("Read {} matching files from clone of {}/{}", files.len(), owner, repo);
+        Ok(files)
+    }
+
+    /// Push a new branch holding one commit that applies the patch's diff
+    /// on top of `base_ref`, authenticated by `token`. Fails without
+    /// pushing when the diff does not apply.
+    pub async fn push_patch(
+        &self,
+        token: &str,
+        owner: &str,
+        repo: &str,
+        base_ref: &str,
+        patch: &PatchPullRequest,
+    ) -> Result<()> {
+        let checkout = tempfile::Builder::new()
+            .prefix("github-patch-")
+            .tempdir()
+            .map_err(|e| Error::CloneFailed(format!("Failed to create clone directory: {}", e)))?;
+        let dir = checkout.path().join("repo");
+        let diff_path = checkout.path().join("patch.diff");
+        tokio::fs::write(&diff_path, &patch.diff)
+            .await
+            .map_err(|e| Error::CloneFailed(format!("Failed to write patch: {}", e)))?;
+        tokio::fs::create_dir(&dir)
+            .await
+            .map_err(|e| Error::CloneFailed(format!("Failed to create clone directory: {}", e)))?;
+        let url = format!("https://github.com/{}/{}.git", owner, repo);
+        let diff_arg = diff_path.to_string_lossy();
+        let refspec = format!("HEAD:refs/heads/{}", patch.branch);
+
+        info!("Pushing patch branch {} to {}/{} from {}", patch.branch, owner, repo, base_ref);
+        self.git(&dir, token, &["init", "--quiet"]).await?;
+        self.git(&dir, token, &["remote", "add", "origin", &url]).await?;
+        let fetch = ["fetch", "--quiet", "--depth", "1", "--no-tags", "origin", base_ref];
+        self.git(&dir, token, &fetch).await?;
+        self.git(&dir, token, &["checkout", "--quiet", "FETCH_HEAD"]).await?;
+        self.git(&dir, token, &["apply", "--index", "--whitespace=nowarn", &diff_arg]).await?;
+        self.git(&dir, token, &["config", "user.name", PATCH_AUTHOR_NAME]).await?;
+        self.git(&dir, token, &["config", "user.email", PATCH_AUTHOR_EMAIL]).await?;
+        self.git(&dir, token, &["commit", "--quiet", "-m", &patch.commit_message]).await?;
+        self.git(&dir, token, &["push", "--quiet", "origin", &refspec]).await?;
+        Ok(())
+    }
+
+    /// Run `git` in `dir`. The token is passed as an HTTP header through the
+    /// environment so it appears neither in the command line nor in the
+    /// clone's config.
+    async fn git(&self, dir: &Path, token: &str, args: &[&str]) -> Result<()> {
+        let credentials = general_purpose::STANDARD.encode(format!("x-access-token:{}", token));
+        let command = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "http.extraHeader")
+            .env("GIT_CONFIG_VALUE_0", format!("Authorization: Basic {}", credentials))
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output();
+
+        let output = tokio::time::timeout(self.settings.timeout, command)
+            .await
+            .map_err(|_| {
+                Error::CloneFailed(format!(
+                    "git {} timed out after {}s",
+                    args[0],
+                    self.settings.timeout.as_secs()
+                ))
+            })?
+            .map_err(|e| Error::CloneFailed(format!("Failed to run git: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(Error::CloneFailed(format!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        debug!("git {} succeeded in {}", args[0], dir.display());
+        Ok(())
+    }
+}
+
+/// Walk a checkout for files with one of `extensions`, skipping files over
+/// the size limit and failing once their total passes the repository limit.
+fn read_checkout(
+    root: &Path,
+    extensions: &[String],
+    settings: &RepositoryCloneSettings,
+) -> Result<Vec<GitHubFile>> {
+    let io_error = |e: std::io::Error| Error::CloneFailed(format!("Reading clone failed: {}", e));
+
+    let mut files = Vec::new();
+    let mut total_bytes = 0u64;
+    let mut pending: Vec<PathBuf> = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir).map_err(io_error)? {
+            let entry = entry.map_err(io_error)?;
+            let file_type = entry.file_type().map_err(io_error)?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if file_type.is_dir() {
+                if name != ".git" {
+                    pending.push(entry.path());
+                }
+                continue;
+            }
+            if !file_type.is_file() || !extensions.iter().any(|ext| name.ends_with(ext.as_str())) {
+                continue;
+            }
+
+            let size = entry.metadata().map_err(io_error)?.len();
+            if size > settings.max_file_bytes {
+                debug!("Skipping {} ({} bytes)", entry.path().display(), size);
+                continue;
+            }
+            total_bytes += size;
+            if total_bytes > settings.max_size_bytes {
+                return Err(Error::RepositoryTooLarge {
+                    size_bytes: total_bytes,
+                    limit_bytes: settings.max_size_bytes,
+                });
+            }
+
+            let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                debug!("Skipping non-UTF-8 file {}", entry.path().display());
+                continue;
+            };
+            let path = entry
+                .path()
+                .strip_prefix(root)
+                .unwrap_or(&entry.path())
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push(GitHubFile { name, path, content, size, download_url: None });
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
@@ -0,0 +1,115 @@
+use crate::domain::{DetectedPackage, RepositoryPackage};
+use crate::error::Result;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+const PACKAGE_COLUMNS: &str = "id, repository_id, path, kind, manifest_path, included, detected_at";
+
+/// Persists `repository_packages`.
+#[derive(Clone)]
+pub struct RepositoryPackageStore {
+    db: Pool<Postgres>,
+}
+
+impl RepositoryPackageStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    pub async fn list(&self, repository_id: Uuid) -> Result<Vec<RepositoryPackage>> {
+        let packages = sqlx::query_as::<_, RepositoryPackage>(&format!(
+            "SELECT {} FROM repository_packages WHERE repository_id = $1 ORDER BY path",
+            PACKAGE_COLUMNS
+        ))
+        .bind(repository_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(packages)
+    }
+
+    /// Packages of the repository with the given GitHub id.
+    pub async fn list_for_github_repo(
+        &self,
+        github_repo_id: i64,
+    ) -> Result<Vec<RepositoryPackage>> {
+        let packages = sqlx::query_as::<_, RepositoryPackage>(
+            r#"
+            SELECT p.id, p.repository_id, p.path, p.kind, p.manifest_path, p.included,
+                p.detected_at
+            FROM repository_packages p
+            JOIN github_repositories r ON r.id = p.repository_id
+            WHERE r.github_repo_id = $1
+            ORDER BY p.path
+            "#,
+        )
+        .bind(github_repo_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(packages)
+    }
+
+    /// Make the repository's packages match `detected`: new ones are added
+    /// as included, known ones keep their setting and vanished ones are
+    /// removed.
+    pub async fn sync(
+        &self,
+        repository_id: Uuid,
+        detected: &[DetectedPackage],
+    ) -> Result<Vec<RepositoryPackage>> {
+        let mut tx = self.db.begin().await?;
+
+        let paths: Vec<&str> = detected.iter().map(|package| package.path.as_str()).collect();
+        sqlx::query("DELETE FROM repository_packages WHERE repository_id = $1 AND path <> ALL($2)")
+            .bind(repository_id)
+            .bind(&paths)
+            .execute(&mut *tx)
+            .await?;
+
+        for package in detected {
+            sqlx::query(
+                r#"
+                INSERT INTO repository_packages (repository_id, path, kind, manifest_path)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (repository_id, path) DO UPDATE
+                SET kind = EXCLUDED.kind, manifest_path = EXCLUDED.manifest_path,
+                    detected_at = NOW(), updated_at = NOW()
+                "#,
+            )
+            .bind(repository_id)
+            .bind(&package.path)
+            .bind(package.kind)
+            .bind(&package.manifest_path)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        self.list(repository_id).await
+    }
+
+    /// Include or exclude the packages at `paths`. Returns how many were
+    /// found.
+    pub async fn set_included(
+        &self,
+        repository_id: Uuid,
+        paths: &[String],
+        included: bool,
+    ) -> Result<u64> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE repository_packages SET included = $3, updated_at = NOW()
+            WHERE repository_id = $1 AND path = ANY($2)
+            "#,
+        )
+        .bind(repository_id)
+        .bind(paths)
+        .bind(included)
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+
+        Ok(updated)
+    }
+}
@@ -0,0 +1,119 @@
+use crate::domain::{
+    ProcessingStatus, WebhookDelivery, WebhookDeliveryForCreate, WebhookDeliverySummary,
+};
+use crate::error::Result;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+const SUMMARY_COLUMNS: &str = "id, github_delivery_id, event_type, installation_id, \
+     signature_status, processing_status, processing_id, error_message, replay_of, \
+     received_at, processed_at";
+
+/// Persists the `webhook_deliveries` log.
+#[derive(Clone)]
+pub struct WebhookDeliveryStore {
+    db: Pool<Postgres>,
+}
+
+impl WebhookDeliveryStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    pub async fn record(&self, delivery: WebhookDeliveryForCreate) -> Result<Uuid> {
+        let id = sqlx::query_scalar(
+            r#"
+            INSERT INTO webhook_deliveries (
+                github_delivery_id, event_type, installation_id, headers, payload,
+                signature_status, processing_status, replay_of
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(delivery.github_delivery_id)
+        .bind(delivery.event_type)
+        .bind(delivery.installation_id.map(|id| id as i64))
+        .bind(delivery.headers)
+        .bind(delivery.payload)
+        .bind(delivery.signature_status)
+        .bind(ProcessingStatus::Received)
+        .bind(delivery.replay_of)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(id)
+    }
+
+    pub async fn record_outcome(
+        &self,
+        id: Uuid,
+        status: ProcessingStatus,
+        processing_id: Option<Uuid>,
+        error_message: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET processing_status = $2, processing_id = $3, error_message = $4,
+                processed_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(processing_id)
+        .bind(error_message)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Most recent first, optionally narrowed by event type and outcome.
+    pub async fn list(
+        &self,
+        event_type: Option<&str>,
+        status: Option<ProcessingStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<WebhookDeliverySummary>, i64)> {
+        let filter = "($1::varchar IS NULL OR event_type = $1) \
+             AND ($2::varchar IS NULL OR processing_status = $2)";
+
+        let deliveries = sqlx::query_as::<_, WebhookDeliverySummary>(&format!(
+            "SELECT {} FROM webhook_deliveries WHERE {} \
+             ORDER BY received_at DESC LIMIT $3 OFFSET $4",
+            SUMMARY_COLUMNS, filter
+        ))
+        .bind(event_type)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db)
+        .await?;
+
+        let total_count: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM webhook_deliveries WHERE {}",
+            filter
+        ))
+        .bind(event_type)
+        .bind(status)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok((deliveries, total_count))
+    }
+
+    pub async fn find(&self, id: Uuid) -> Result<Option<WebhookDelivery>> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "SELECT {}, headers, payload FROM webhook_deliveries WHERE id = $1",
+            SUMMARY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(delivery)
+    }
+}
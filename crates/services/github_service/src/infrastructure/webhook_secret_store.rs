@@ -0,0 +1,166 @@
+use crate::domain::WebhookSecretScope;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use jd_utils::crypto::SecretCipher;
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// How long a secret stays accepted if its rotation never completes.
+const PENDING_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Persists `webhook_secrets`, encrypted with `GITHUB.TOKEN_ENCRYPTION_KEY`.
+#[derive(Clone)]
+pub struct WebhookSecretStore {
+    db: Pool<Postgres>,
+    cipher: SecretCipher,
+}
+
+impl WebhookSecretStore {
+    pub fn new(db: Pool<Postgres>, cipher: SecretCipher) -> Self {
+        Self { db, cipher }
+    }
+
+    /// Secrets deliveries in the scope may be signed with. Empty for
+    /// webhooks that have never been rotated, which use the configured ones.
+    pub async fn accepted(&self, scope: WebhookSecretScope, scope_id: i64) -> Result<Vec<String>> {
+        let encrypted: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT secret_encrypted FROM webhook_secrets
+            WHERE scope = $1 AND scope_id = $2 AND (expires_at IS NULL OR expires_at > NOW())
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(scope)
+        .bind(scope_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(encrypted
+            .iter()
+            .filter_map(|secret| {
+                self.cipher
+                    .decrypt(secret)
+                    .map_err(|e| warn!("Skipping undecryptable webhook secret: {}", e))
+                    .ok()
+            })
+            .collect())
+    }
+
+    /// Start accepting `secret` ahead of handing it to GitHub. A scope
+    /// rotated for the first time takes `current` (the configured secrets)
+    /// as the ones being replaced. Returns the new secret's id.
+    pub async fn add_pending(
+        &self,
+        scope: WebhookSecretScope,
+        scope_id: i64,
+        secret: &str,
+        current: &[String],
+    ) -> Result<Uuid> {
+        let mut tx = self.db.begin().await?;
+
+        let existing: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM webhook_secrets WHERE scope = $1 AND scope_id = $2",
+        )
+        .bind(scope)
+        .bind(scope_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if existing == 0 {
+            for secret in current {
+                sqlx::query(
+                    r#"
+                    INSERT INTO webhook_secrets (scope, scope_id, secret_encrypted, activated_at)
+                    VALUES ($1, $2, $3, NOW())
+                    "#,
+                )
+                .bind(scope)
+                .bind(scope_id)
+                .bind(self.encrypt(secret)?)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO webhook_secrets (scope, scope_id, secret_encrypted, expires_at)
+            VALUES ($1, $2, $3, NOW() + make_interval(secs => $4))
+            RETURNING id
+            "#,
+        )
+        .bind(scope)
+        .bind(scope_id)
+        .bind(self.encrypt(secret)?)
+        .bind(PENDING_TTL.as_secs_f64())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    /// Make a pending secret the current one once GitHub signs with it. The
+    /// secrets it replaces stay accepted for `grace_period`. Returns when
+    /// they stop being accepted.
+    pub async fn activate(&self, id: Uuid, grace_period: Duration) -> Result<DateTime<Utc>> {
+        let mut tx = self.db.begin().await?;
+
+        let expires_at =
+            Utc::now() + chrono::Duration::from_std(grace_period).unwrap_or_default();
+        sqlx::query(
+            r#"
+            UPDATE webhook_secrets AS old
+            SET expires_at = $2
+            FROM webhook_secrets AS new
+            WHERE new.id = $1 AND old.scope = new.scope AND old.scope_id = new.scope_id
+              AND old.id <> new.id AND old.expires_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(expires_at)
+        .execute(&mut *tx)
+        .await?;
+
+        let activated = sqlx::query(
+            r#"
+            UPDATE webhook_secrets SET activated_at = NOW(), expires_at = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if activated == 0 {
+            return Err(Error::Internal(format!("Webhook secret {} disappeared", id)));
+        }
+
+        let purged = sqlx::query("DELETE FROM webhook_secrets WHERE expires_at < NOW()")
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        tx.commit().await?;
+
+        if purged > 0 {
+            info!("Purged {} expired webhook secret(s)", purged);
+        }
+        Ok(expires_at)
+    }
+
+    /// Drop a pending secret GitHub could not be given.
+    pub async fn discard(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM webhook_secrets WHERE id = $1 AND activated_at IS NULL")
+            .bind(id)
+            .execute(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    fn encrypt(&self, secret: &str) -> Result<String> {
+        self.cipher
+            .encrypt(secret)
+            .map_err(|e| Error::Internal(format!("Failed to encrypt webhook secret: {}", e)))
+    }
+}
@@ -11,10 +11,20 @@ pub use infrastructure::*;
 pub use models::*;
 
 // Re-export key types for easier usage
-pub use crate::application::handlers::{WebhookHandler, RepositoryHandler};
+pub use crate::application::handlers::{DeploymentHandler, WebhookHandler, RepositoryHandler};
 pub use crate::application::use_cases::GitHubUseCase;
-pub use crate::infrastructure::{GitHubClient, GitHubFile, AnalysisQueueImpl, RateLimiterImpl};
-pub use crate::domain::{AnalysisJob, AnalysisType, AnalysisPriority, JobStatus, QueueStatus};
+pub use crate::infrastructure::{
+    AnalysisQueueImpl, ContentCache, GitHubClient, GitHubFile, RateLimiterImpl, RepositoryCloner,
+};
+pub use crate::application::{
+    AnalysisWorker, CommitIngestionRun, CommitIngestor, ReanalysisRun, ReanalysisScheduler,
+};
+pub use crate::domain::{
+    AnalysisJob, AnalysisJobProcessor, AnalysisQueueSettings, AnalysisType, AnalysisPriority,
+    CommitIngestionSettings, ContributorSummary, JobStatus, LeasedJob, QueueStatus,
+    RateLimitBudget, ReanalysisSchedule, ReanalysisSettings, RepositoryCloneSettings,
+    RepositoryCommit, RequestPriority, WebhookSecretSettings,
+};
 pub use crate::models::{
     AddRepositoryRequest, UpdateRepositorySettingsRequest, RepositoryListParams,
     RepositoryResponse, RepositoryListResponse, RepositoryDetailResponse, WebhookResponse,
@@ -28,7 +38,13 @@ pub struct GitHubServiceConfig {
     pub github_private_key: Option<String>,
     pub webhook_secret: String,
     pub webhook_base_url: String,
-    pub max_queue_size: usize,
+    pub analysis_queue: AnalysisQueueSettings,
+    pub reanalysis: ReanalysisSettings,
+    pub repository_clone: RepositoryCloneSettings,
+    pub commit_ingestion: CommitIngestionSettings,
+    pub webhook_secrets: WebhookSecretSettings,
+    /// Key stored secrets are encrypted with (`GITHUB.TOKEN_ENCRYPTION_KEY`).
+    pub encryption_key: Option<String>,
     pub rate_limit_per_hour: u32,
 }
 
@@ -58,42 +74,212 @@ impl GitHubServiceConfig {
             webhook_base_url: github_config.webhook_base_url
                 .clone()
                 .unwrap_or_else(|| "http://localhost:3000".to_string()),
-            max_queue_size: github_config.max_queue_size.unwrap_or(1000),
+            analysis_queue: Self::analysis_queue_settings(github_config),
+            reanalysis: Self::reanalysis_settings(github_config)?,
+            repository_clone: Self::repository_clone_settings(github_config),
+            commit_ingestion: Self::commit_ingestion_settings(github_config),
+            webhook_secrets: Self::webhook_secret_settings(github_config),
+            encryption_key: github_config.token_encryption_key.clone(),
             rate_limit_per_hour: github_config.rate_limit_per_hour.unwrap_or(5000),
         })
     }
     
+    fn analysis_queue_settings(
+        github_config: &jd_utils::config::GitHubConfig,
+    ) -> AnalysisQueueSettings {
+        let defaults = AnalysisQueueSettings::default();
+        AnalysisQueueSettings {
+            max_queue_size: github_config.max_queue_size.unwrap_or(defaults.max_queue_size),
+            visibility_timeout: github_config
+                .analysis_visibility_timeout_secs
+                .map(std::time::Duration::from_secs)
+                .unwrap_or(defaults.visibility_timeout),
+            max_attempts: github_config.analysis_max_attempts.unwrap_or(defaults.max_attempts),
+            ..defaults
+        }
+    }
+
+    fn reanalysis_settings(
+        github_config: &jd_utils::config::GitHubConfig,
+    ) -> Result<ReanalysisSettings> {
+        let defaults = ReanalysisSettings::default();
+        let default_schedule = github_config
+            .reanalysis_schedule
+            .clone()
+            .unwrap_or(defaults.default_schedule);
+        parse_schedule(&default_schedule)?;
+
+        Ok(ReanalysisSettings {
+            default_schedule,
+            max_in_flight: github_config
+                .reanalysis_max_in_flight
+                .unwrap_or(defaults.max_in_flight),
+        })
+    }
+
+    fn repository_clone_settings(
+        github_config: &jd_utils::config::GitHubConfig,
+    ) -> RepositoryCloneSettings {
+        let defaults = RepositoryCloneSettings::default();
+        RepositoryCloneSettings {
+            file_threshold: github_config.clone_file_threshold.unwrap_or(defaults.file_threshold),
+            max_size_bytes: github_config
+                .clone_max_size_mb
+                .map(|mb| mb * 1024 * 1024)
+                .unwrap_or(defaults.max_size_bytes),
+            sparse_paths: github_config
+                .clone_sparse_paths
+                .as_deref()
+                .map(|paths| {
+                    paths
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|path| !path.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            ..defaults
+        }
+    }
+
+    fn commit_ingestion_settings(
+        github_config: &jd_utils::config::GitHubConfig,
+    ) -> CommitIngestionSettings {
+        let defaults = CommitIngestionSettings::default();
+        CommitIngestionSettings {
+            history_depth: github_config.commit_history_depth.unwrap_or(defaults.history_depth),
+            sync_interval: github_config
+                .commit_sync_interval_secs
+                .map(std::time::Duration::from_secs)
+                .unwrap_or(defaults.sync_interval),
+            ..defaults
+        }
+    }
+
+    fn webhook_secret_settings(
+        github_config: &jd_utils::config::GitHubConfig,
+    ) -> WebhookSecretSettings {
+        let defaults = WebhookSecretSettings::default();
+        WebhookSecretSettings {
+            previous_secrets: github_config
+                .webhook_previous_secrets
+                .as_deref()
+                .map(|secrets| {
+                    secrets
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|secret| !secret.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            grace_period: github_config
+                .webhook_secret_grace_hours
+                .map(|hours| std::time::Duration::from_secs(hours * 60 * 60))
+                .unwrap_or(defaults.grace_period),
+        }
+    }
+
     pub fn from_env() -> Result<Self> {
-        let config = jd_utils::config::Config::from_env()
+        let config = jd_utils::config::Config::load()
             .map_err(|e| Error::ConfigurationError(format!("Failed to load config: {}", e)))?;
         Self::from_config(&config)
     }
 }
 
+/// Budgets are per credential, not per client, so every client in the
+/// process counts against the same limiter.
+static SHARED_RATE_LIMITER: std::sync::OnceLock<std::sync::Arc<RateLimiterImpl>> =
+    std::sync::OnceLock::new();
+
 // Service factory for creating all GitHub service components
 pub struct GitHubServiceFactory;
 
 impl GitHubServiceFactory {
     pub fn create_client(config: &GitHubServiceConfig) -> Result<GitHubClient> {
-        if let (Some(app_id), Some(private_key)) = (config.github_app_id, &config.github_private_key) {
+        let client = if let (Some(app_id), Some(private_key)) =
+            (config.github_app_id, &config.github_private_key)
+        {
             // Use GitHub App authentication
-            GitHubClient::new_app(app_id, private_key.clone(), config.webhook_secret.clone())
+            GitHubClient::new_app(app_id, private_key.clone(), config.webhook_secret.clone())?
         } else if let Some(token) = &config.github_token {
             // Use personal token authentication
-            GitHubClient::new(token.clone(), config.webhook_secret.clone())
+            GitHubClient::new(token.clone(), config.webhook_secret.clone())?
         } else {
-            Err(Error::ConfigurationError("No valid GitHub authentication method configured".to_string()))
-        }
+            return Err(Error::ConfigurationError(
+                "No valid GitHub authentication method configured".to_string(),
+            ));
+        };
+
+        Ok(client
+            .with_rate_limiter(Self::shared_rate_limiter(config))
+            .with_cloner(RepositoryCloner::new(config.repository_clone.clone()))
+            .with_previous_webhook_secrets(config.webhook_secrets.previous_secrets.clone()))
+    }
+
+    /// A client that serves repository contents through the Postgres
+    /// content cache.
+    pub fn create_cached_client(
+        config: &GitHubServiceConfig,
+        db: sqlx::Pool<sqlx::Postgres>,
+    ) -> Result<GitHubClient> {
+        Ok(Self::create_client(config)?.with_content_cache(ContentCache::new(db)))
     }
 
-    pub fn create_analysis_queue(config: &GitHubServiceConfig) -> AnalysisQueueImpl {
-        AnalysisQueueImpl::new(config.max_queue_size)
+    /// Store for rotated webhook secrets. `None` without an encryption key,
+    /// in which case only the configured secrets are accepted.
+    pub fn create_webhook_secret_store(
+        config: &GitHubServiceConfig,
+        db: sqlx::Pool<sqlx::Postgres>,
+    ) -> Result<Option<WebhookSecretStore>> {
+        let Some(key) = &config.encryption_key else {
+            return Ok(None);
+        };
+        let cipher = jd_utils::crypto::SecretCipher::from_base64_key(key).map_err(|e| {
+            Error::ConfigurationError(format!("Invalid GITHUB.TOKEN_ENCRYPTION_KEY: {}", e))
+        })?;
+        Ok(Some(WebhookSecretStore::new(db, cipher)))
+    }
+
+    pub fn create_analysis_queue(
+        config: &GitHubServiceConfig,
+        db: sqlx::Pool<sqlx::Postgres>,
+    ) -> AnalysisQueueImpl {
+        AnalysisQueueImpl::new(db, config.analysis_queue.clone())
+    }
+
+    pub fn create_reanalysis_scheduler(
+        config: &GitHubServiceConfig,
+        db: sqlx::Pool<sqlx::Postgres>,
+    ) -> Result<ReanalysisScheduler> {
+        Ok(ReanalysisScheduler::new(
+            std::sync::Arc::new(Self::create_client(config)?.with_priority(RequestPriority::Low)),
+            std::sync::Arc::new(Self::create_analysis_queue(config, db.clone())),
+            ReanalysisScheduleStore::new(db),
+            config.reanalysis.clone(),
+        ))
+    }
+
+    pub fn create_commit_ingestor(
+        config: &GitHubServiceConfig,
+        db: sqlx::Pool<sqlx::Postgres>,
+    ) -> Result<CommitIngestor> {
+        Ok(CommitIngestor::new(
+            std::sync::Arc::new(Self::create_client(config)?.with_priority(RequestPriority::Low)),
+            CommitStore::new(db),
+            config.commit_ingestion.clone(),
+        ))
     }
 
     pub fn create_rate_limiter(config: &GitHubServiceConfig) -> RateLimiterImpl {
-        RateLimiterImpl::new(
-            config.rate_limit_per_hour,
-            std::time::Duration::from_secs(3600),
-        )
+        RateLimiterImpl::new(config.rate_limit_per_hour)
+    }
+
+    /// The process-wide rate limiter, created from the first config seen.
+    pub fn shared_rate_limiter(config: &GitHubServiceConfig) -> std::sync::Arc<RateLimiterImpl> {
+        SHARED_RATE_LIMITER
+            .get_or_init(|| std::sync::Arc::new(Self::create_rate_limiter(config)))
+            .clone()
     }
 }
\ No newline at end of file
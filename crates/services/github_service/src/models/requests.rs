@@ -1,3 +1,4 @@
+use crate::domain::{AnalysisPriority, JobStatus, ProcessingStatus};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -6,10 +7,32 @@ pub struct AddRepositoryRequest {
     pub name: String,
 }
 
+/// Which of an organization's repositories to import. A repository matches
+/// if its primary language is in `languages` or it carries one of `topics`;
+/// with neither given, smart contract languages are matched.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct OrganizationImportRequest {
+    pub languages: Vec<String>,
+    pub topics: Vec<String>,
+    pub include_forks: bool,
+    pub include_archived: bool,
+    /// Repositories set up at once; defaults to 4, at most 16.
+    pub concurrency: Option<usize>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdateRepositorySettingsRequest {
     pub monitoring_enabled: Option<bool>,
     pub webhook_secret: Option<String>,
+    /// Cron expression for periodic re-analysis; an empty string restores
+    /// the service default.
+    pub reanalysis_schedule: Option<String>,
+    pub reanalysis_enabled: Option<bool>,
+    /// Package paths to analyse again.
+    pub included_packages: Option<Vec<String>>,
+    /// Package paths to leave out of analyses and webhook triggers.
+    pub excluded_packages: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,4 +64,44 @@ pub struct RepositoryFilters {
     pub security_score_min: Option<f64>,
     pub monitoring_enabled: Option<bool>,
     pub search_term: Option<String>,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WebhookDeliveryListParams {
+    pub event_type: Option<String>,
+    pub status: Option<ProcessingStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CommitListParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AnalysisJobListParams {
+    pub status: Option<JobStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateJobPriorityRequest {
+    pub priority: AnalysisPriority,
+}
+
+/// A published package to track. `package_path` names the repository's
+/// Move package it was built from; it defaults to the only one.
+#[derive(Debug, Deserialize)]
+pub struct RegisterDeploymentRequest {
+    pub network: String,
+    pub package_id: String,
+    pub package_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyDeploymentRequest {
+    pub commit_sha: String,
+}
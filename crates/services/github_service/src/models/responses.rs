@@ -1,12 +1,43 @@
 use serde::Serialize;
 use uuid::Uuid;
 use jd_domain::zkpersona_domain::developer_models::GitHubRepository;
+use crate::domain::{
+    AnalysisJobDetail, RateLimitBudget, ReanalysisSchedule, RepositoryPackage, VerifiedBuild,
+    WebhookDeliverySummary,
+};
 
 #[derive(Debug, Serialize)]
 pub struct RepositoryResponse {
     pub repository: GitHubRepository,
     pub webhook_configured: bool,
     pub initial_scan_queued: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reanalysis: Option<ReanalysisSchedule>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packages: Option<Vec<RepositoryPackage>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationImportResponse {
+    pub organization: String,
+    /// Repositories in the organization that matched the filters.
+    pub matched: usize,
+    pub imported: Vec<ImportedRepository>,
+    pub skipped: Vec<SkippedRepository>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedRepository {
+    pub repository: GitHubRepository,
+    pub webhook_configured: bool,
+    pub analysis_job_id: Option<Uuid>,
+}
+
+/// A matched repository that was not imported, and why.
+#[derive(Debug, Serialize)]
+pub struct SkippedRepository {
+    pub full_name: String,
+    pub reason: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -23,6 +54,8 @@ pub struct RepositoryDetailResponse {
     pub latest_analysis: Option<AnalysisSummary>,
     pub vulnerability_summary: VulnerabilitySummary,
     pub security_trends: Vec<SecurityTrend>,
+    /// Set once a deployment's bytecode was reproduced from source.
+    pub verified_build: Option<VerifiedBuild>,
 }
 
 #[derive(Debug, Serialize)]
@@ -54,4 +87,35 @@ pub struct SecurityTrend {
 pub struct WebhookResponse {
     pub status: String,
     pub message: String,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryListResponse {
+    pub deliveries: Vec<WebhookDeliverySummary>,
+    pub total_count: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryDetailResponse {
+    pub delivery: WebhookDeliverySummary,
+    pub headers: serde_json::Value,
+    /// The payload as JSON, or as a string when it does not parse.
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RateLimitResponse {
+    pub budgets: Vec<RateLimitBudget>,
+    /// Whether scheduled work is being held back for lack of budget.
+    pub low_priority_deferred: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnalysisJobListResponse {
+    pub jobs: Vec<AnalysisJobDetail>,
+    pub total_count: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
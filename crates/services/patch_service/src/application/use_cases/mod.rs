@@ -1,3 +1,15 @@
+pub mod patch_candidate_use_cases;
+pub mod patch_diff_use_cases;
+pub mod patch_generation_use_cases;
+pub mod patch_review_use_cases;
+pub mod patch_submission_use_cases;
 pub mod patch_use_cases;
+pub mod patch_verification_use_cases;
 
-pub use patch_use_cases::PatchUseCases;
\ No newline at end of file
+pub use patch_candidate_use_cases::PatchCandidateUseCases;
+pub use patch_diff_use_cases::PatchDiffUseCases;
+pub use patch_generation_use_cases::PatchGenerationUseCases;
+pub use patch_review_use_cases::PatchReviewUseCases;
+pub use patch_submission_use_cases::PatchSubmissionUseCases;
+pub use patch_use_cases::PatchUseCases;
+pub use patch_verification_use_cases::PatchVerificationUseCases;
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::{
+    parse_unified_diff, rank_candidates, CandidateValidation, GeneratedPatch, GenerationStrategy,
+    PatchCandidate, PatchGenerationRequest, PatchGenerator, PatchRepository, PatchVerifier,
+    RepositoryCheckout, MAX_PATCH_CANDIDATES,
+};
+use crate::Result;
+
+pub struct PatchCandidateUseCases {
+    repository: Arc<dyn PatchRepository>,
+    generator: Arc<dyn PatchGenerator>,
+    verifier: Option<Arc<dyn PatchVerifier>>,
+}
+
+impl PatchCandidateUseCases {
+    pub fn new(repository: Arc<dyn PatchRepository>, generator: Arc<dyn PatchGenerator>) -> Self {
+        Self { repository, generator, verifier: None }
+    }
+
+    /// Build and test each candidate in the sandbox before ranking it.
+    pub fn with_verifier(mut self, verifier: Arc<dyn PatchVerifier>) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// Generate up to `count` alternative patches for a finding, one per
+    /// strategy starting with `strategy`, validate and rank them, and keep
+    /// them in place of the finding's earlier candidates.
+    pub async fn generate_candidates(
+        &self,
+        vulnerability_id: Uuid,
+        strategy: GenerationStrategy,
+        count: Option<usize>,
+    ) -> Result<Vec<PatchCandidate>> {
+        let count = count.unwrap_or(MAX_PATCH_CANDIDATES).clamp(1, MAX_PATCH_CANDIDATES);
+        let target = self.repository.get_vulnerability_target(vulnerability_id).await?;
+        let request = PatchGenerationRequest {
+            vulnerability_id,
+            context: target.context,
+            generation_strategy: strategy,
+        };
+        let patches = self.generator.generate_candidates(&request, count).await?;
+
+        let checkout = match self.verifier {
+            Some(_) => Some(self.repository.get_repository_checkout(vulnerability_id).await?),
+            None => None,
+        };
+        let mut candidates = Vec::with_capacity(patches.len());
+        for patch in patches {
+            let validation = self.validate(&patch, checkout.as_ref()).await;
+            candidates.push(PatchCandidate::new(vulnerability_id, patch, validation));
+        }
+        rank_candidates(&mut candidates);
+
+        self.repository.replace_patch_candidates(vulnerability_id, &candidates).await?;
+        info!(
+            "Generated {} patch candidate(s) for vulnerability {}, best scoring {:.1}",
+            candidates.len(),
+            vulnerability_id,
+            candidates.first().map_or(0.0, |candidate| candidate.score)
+        );
+
+        Ok(candidates)
+    }
+
+    pub async fn candidates(&self, vulnerability_id: Uuid) -> Result<Vec<PatchCandidate>> {
+        self.repository.get_patch_candidates(vulnerability_id).await
+    }
+
+    /// Record `reviewer`'s pick among a finding's candidates.
+    pub async fn select_candidate(&self, id: Uuid, reviewer: &str) -> Result<PatchCandidate> {
+        let candidate = self.repository.select_patch_candidate(id, reviewer).await?;
+        info!(
+            "{} picked candidate {} (rank {}) for vulnerability {}",
+            reviewer, id, candidate.rank, candidate.vulnerability_id
+        );
+        Ok(candidate)
+    }
+
+    /// Size the candidate's diff and, with a verifier, build and test it. A
+    /// verifier failure leaves the candidate unverified rather than failing
+    /// the whole set.
+    async fn validate(
+        &self,
+        patch: &GeneratedPatch,
+        checkout: Option<&RepositoryCheckout>,
+    ) -> CandidateValidation {
+        let files = match parse_unified_diff(&patch.patch_diff) {
+            Ok(files) => files,
+            Err(e) => return CandidateValidation::unusable(e.to_string()),
+        };
+        let additions = files.iter().map(|file| file.additions).sum();
+        let deletions = files.iter().map(|file| file.deletions).sum();
+
+        let verified = match (&self.verifier, checkout) {
+            (Some(verifier), Some(checkout)) => {
+                match verifier.verify(checkout, &patch.patch_diff).await {
+                    Ok(status) => Some(status),
+                    Err(e) => {
+                        warn!("Patch candidate could not be verified: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        CandidateValidation::new(additions, deletions, verified.as_ref())
+    }
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::{
+    parse_unified_diff, FileDiffCheck, PatchDiffReport, PatchRepository, SourceFileProvider,
+};
+use crate::Result;
+
+pub struct PatchDiffUseCases {
+    repository: Arc<dyn PatchRepository>,
+    sources: Arc<dyn SourceFileProvider>,
+}
+
+impl PatchDiffUseCases {
+    pub fn new(repository: Arc<dyn PatchRepository>, sources: Arc<dyn SourceFileProvider>) -> Self {
+        Self { repository, sources }
+    }
+
+    /// The patch's diff parsed into files and hunks, each file checked
+    /// against its content at the commit the patch's finding was reported
+    /// on. A diff that does not parse is a validation error.
+    pub async fn patch_diff(&self, id: Uuid) -> Result<PatchDiffReport> {
+        let patch = self.repository.get_by_id(id).await?;
+        let files = parse_unified_diff(&patch.patch_diff)?;
+
+        let checkout = self.repository.get_repository_checkout(patch.vulnerability_id).await?;
+        let paths: Vec<String> = files.iter().filter_map(|file| file.old_path.clone()).collect();
+        let originals = self.sources.files_at(&checkout, &paths).await?;
+
+        let files: Vec<FileDiffCheck> = files
+            .into_iter()
+            .map(|diff| {
+                let original = diff.old_path.as_ref().and_then(|path| originals.get(path));
+                let conflict = diff.apply(original.map(String::as_str)).err();
+                FileDiffCheck { applies_cleanly: conflict.is_none(), conflict, diff }
+            })
+            .collect();
+
+        Ok(PatchDiffReport {
+            commit_sha: checkout.commit_sha,
+            applies_cleanly: files.iter().all(|file| file.applies_cleanly),
+            additions: files.iter().map(|file| file.diff.additions).sum(),
+            deletions: files.iter().map(|file| file.diff.deletions).sum(),
+            files,
+        })
+    }
+}
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+use std::time::Instant;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::{
+    BulkPatchFilter, ExposureEstimate, ExposureSource, GeneratedPatch, GenerationStrategy,
+    PatchGenerationRequest, PatchGenerator, PatchGroup, PatchRepository, PatchSetGenerationRequest,
+    VulnerabilityTarget, MAX_BULK_FINDINGS,
+};
+use crate::infrastructure::patch_metrics::{PATCH_GENERATION_DURATION, PATCH_GENERATION_FAILURES};
+use crate::{Error, Result};
+
+pub struct PatchGenerationUseCases {
+    repository: Arc<dyn PatchRepository>,
+    generator: Arc<dyn PatchGenerator>,
+    exposure_source: Arc<dyn ExposureSource>,
+}
+
+impl PatchGenerationUseCases {
+    pub fn new(
+        repository: Arc<dyn PatchRepository>,
+        generator: Arc<dyn PatchGenerator>,
+        exposure_source: Arc<dyn ExposureSource>,
+    ) -> Self {
+        Self { repository, generator, exposure_source }
+    }
+
+    /// Generate a patch for a finding, then estimate the finding's economic
+    /// exposure and attach it to both the finding and the generated patch.
+    pub async fn generate_patch(
+        &self,
+        vulnerability_id: Uuid,
+        strategy: GenerationStrategy,
+    ) -> Result<GeneratedPatch> {
+        let target = self.repository.get_vulnerability_target(vulnerability_id).await?;
+
+        let request = PatchGenerationRequest {
+            vulnerability_id,
+            context: target.context.clone(),
+            generation_strategy: strategy,
+        };
+        let started = Instant::now();
+        let generated = self.generator.generate_patch(&request).await;
+        record_generation("single", started, generated.as_ref().err());
+        let mut patch = generated?;
+
+        // Estimation is advisory; a missing indexer must not block patching.
+        match self.estimate_exposure(&target).await {
+            Ok(estimate) => patch.exposure_estimate = Some(estimate),
+            Err(e) => {
+                warn!("Exposure estimation failed for vulnerability {}: {}", vulnerability_id, e)
+            }
+        }
+
+        Ok(patch)
+    }
+
+    /// Generate one coordinated patch for a repository's open findings
+    /// matching `filter`, at most `limit` of them, and record it as a patch
+    /// group with a sub-patch per file.
+    pub async fn generate_bulk(
+        &self,
+        repository_id: Uuid,
+        filter: BulkPatchFilter,
+        strategy: GenerationStrategy,
+        limit: Option<i64>,
+    ) -> Result<PatchGroup> {
+        let limit = limit.unwrap_or(MAX_BULK_FINDINGS);
+        if !(1..=MAX_BULK_FINDINGS).contains(&limit) {
+            return Err(Error::ValidationFailed(format!(
+                "limit must be between 1 and {}",
+                MAX_BULK_FINDINGS
+            )));
+        }
+        let findings =
+            self.repository.find_vulnerability_targets(repository_id, &filter, limit).await?;
+        if findings.is_empty() {
+            return Err(Error::ValidationFailed(
+                "no open findings of the repository match the filter".to_string(),
+            ));
+        }
+
+        let request = PatchSetGenerationRequest {
+            repository_id,
+            findings,
+            generation_strategy: strategy.clone(),
+        };
+        let started = Instant::now();
+        let generated = self.generator.generate_patch_set(&request).await.and_then(|set| {
+            if set.files.is_empty() {
+                return Err(Error::PatchGenerationFailed(format!(
+                    "no usable patch for {} finding(s): {}",
+                    request.findings.len(),
+                    set.left_out.join("; ")
+                )));
+            }
+            Ok(set)
+        });
+        record_generation("bulk", started, generated.as_ref().err());
+        let set = generated?;
+
+        let group = PatchGroup::new(repository_id, filter, strategy, &request.findings, set);
+        self.repository.create_patch_group(&group).await?;
+        info!(
+            "Generated patch group {} for {} finding(s) of repository {}: {} file(s), {} left out",
+            group.id,
+            group.vulnerability_ids.len(),
+            repository_id,
+            group.files.len(),
+            group.left_out.len()
+        );
+
+        Ok(group)
+    }
+
+    pub async fn estimate_exposure(
+        &self,
+        target: &VulnerabilityTarget,
+    ) -> Result<ExposureEstimate> {
+        let packages = self.exposure_source.repository_exposure(target.repository_id).await?;
+        let now = OffsetDateTime::now_utc();
+        let estimate = ExposureEstimate::estimate(&target.context.severity, packages, now);
+
+        self.repository.save_exposure_estimate(target.vulnerability_id, &estimate).await?;
+        info!(
+            "Vulnerability {} exposure: {:?}, {} SUI across {} package(s)",
+            target.vulnerability_id,
+            estimate.tier,
+            estimate.estimated_exposure_sui,
+            estimate.packages.len()
+        );
+
+        Ok(estimate)
+    }
+}
+
+fn record_generation(kind: &str, started: Instant, error: Option<&Error>) {
+    let outcome = if error.is_some() { "failed" } else { "generated" };
+    PATCH_GENERATION_DURATION
+        .with_label_values(&[kind, outcome])
+        .observe(started.elapsed().as_secs_f64());
+    if let Some(error) = error {
+        let reason = match error {
+            Error::PatchGenerationFailed(_) => "generation_failed",
+            Error::ValidationFailed(_) => "validation_failed",
+            Error::ServiceError(_) => "service_error",
+            Error::DatabaseError(_) => "database_error",
+            _ => "other",
+        };
+        PATCH_GENERATION_FAILURES.with_label_values(&[kind, reason]).inc();
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::{
+    PatchProposal, PatchRepository, PatchReviewSummary, PatchStatus, ReviewDecision, ReviewTally,
+};
+use crate::{Error, Result};
+
+pub struct PatchReviewUseCases {
+    repository: Arc<dyn PatchRepository>,
+}
+
+impl PatchReviewUseCases {
+    pub fn new(repository: Arc<dyn PatchRepository>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn review_summary(&self, id: Uuid) -> Result<PatchReviewSummary> {
+        let patch = self.repository.get_by_id(id).await?;
+        let reviewers = self.repository.get_reviewers(id).await?;
+        let reviews = self.repository.get_reviews(id).await?;
+
+        Ok(PatchReviewSummary {
+            patch_id: id,
+            status: patch.status,
+            required_approvals: patch.required_approvals,
+            tally: ReviewTally::count(&reviewers, &reviews),
+            reviewers,
+            reviews,
+        })
+    }
+
+    /// Assign reviewers to a patch, optionally changing how many approvals
+    /// it needs. A proposed patch goes under review.
+    pub async fn assign_reviewers(
+        &self,
+        id: Uuid,
+        reviewers: &[String],
+        required_approvals: Option<i32>,
+        assigned_by: &str,
+    ) -> Result<PatchReviewSummary> {
+        let mut patch = self.reviewable_patch(id).await?;
+        if let Some(required_approvals) = required_approvals {
+            if required_approvals < 1 {
+                return Err(Error::ValidationFailed(
+                    "required_approvals must be at least 1".to_string(),
+                ));
+            }
+            self.repository.set_required_approvals(id, required_approvals).await?;
+            patch.required_approvals = required_approvals;
+        }
+        if !reviewers.is_empty() {
+            self.repository.assign_reviewers(id, reviewers, assigned_by).await?;
+            if patch.status == PatchStatus::Proposed {
+                patch.status = self.move_to(&patch, PatchStatus::UnderReview).await?;
+            }
+        }
+        self.settle(&patch).await
+    }
+
+    pub async fn remove_reviewer(&self, id: Uuid, reviewer: &str) -> Result<PatchReviewSummary> {
+        let patch = self.reviewable_patch(id).await?;
+        if !self.repository.remove_reviewer(id, reviewer).await? {
+            return Err(Error::NotAReviewer(reviewer.to_string()));
+        }
+        self.settle(&patch).await
+    }
+
+    /// Record a review. Verdicts are taken from assigned reviewers only and
+    /// may move the patch to approved or rejected; anyone may comment.
+    pub async fn submit_review(
+        &self,
+        id: Uuid,
+        reviewer: &str,
+        decision: ReviewDecision,
+        comment: Option<&str>,
+    ) -> Result<PatchReviewSummary> {
+        let mut patch = self.repository.get_by_id(id).await?;
+        if decision == ReviewDecision::Comment {
+            if comment.map_or(true, |comment| comment.trim().is_empty()) {
+                return Err(Error::ValidationFailed("a comment needs a body".to_string()));
+            }
+        } else {
+            if !patch.status.is_reviewable() {
+                return Err(Error::InvalidPatchState(format!(
+                    "a {} patch can no longer be reviewed",
+                    patch.status.to_string()
+                )));
+            }
+            let reviewers = self.repository.get_reviewers(id).await?;
+            if !reviewers.iter().any(|assigned| assigned.reviewer == reviewer) {
+                return Err(Error::NotAReviewer(reviewer.to_string()));
+            }
+        }
+
+        self.repository.add_review(id, reviewer, decision, comment).await?;
+        if decision != ReviewDecision::Comment && patch.status == PatchStatus::Proposed {
+            patch.status = self.move_to(&patch, PatchStatus::UnderReview).await?;
+        }
+        self.settle(&patch).await
+    }
+
+    async fn reviewable_patch(&self, id: Uuid) -> Result<PatchProposal> {
+        let patch = self.repository.get_by_id(id).await?;
+        if !patch.status.is_reviewable() {
+            return Err(Error::InvalidPatchState(format!(
+                "reviewers of a {} patch can no longer change",
+                patch.status.to_string()
+            )));
+        }
+        Ok(patch)
+    }
+
+    /// Move the patch to the status its reviews now call for.
+    async fn settle(&self, patch: &PatchProposal) -> Result<PatchReviewSummary> {
+        let reviewers = self.repository.get_reviewers(patch.id).await?;
+        let reviews = self.repository.get_reviews(patch.id).await?;
+        let tally = ReviewTally::count(&reviewers, &reviews);
+        let next = tally.outcome(patch.status, patch.required_approvals);
+        let status = self.move_to(patch, next).await?;
+
+        Ok(PatchReviewSummary {
+            patch_id: patch.id,
+            status,
+            required_approvals: patch.required_approvals,
+            tally,
+            reviewers,
+            reviews,
+        })
+    }
+
+    async fn move_to(&self, patch: &PatchProposal, next: PatchStatus) -> Result<PatchStatus> {
+        let status = patch.status.transition_to(next)?;
+        if status != patch.status {
+            self.repository.update_status(patch.id, status).await?;
+            info!(
+                "Patch {} moved from {} to {}",
+                patch.id,
+                patch.status.to_string(),
+                status.to_string()
+            );
+        }
+        Ok(status)
+    }
+}
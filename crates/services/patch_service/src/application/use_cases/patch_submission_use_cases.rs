@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::{
+    PatchProposal, PatchRepository, PullRequestDraft, PullRequestPublisher, SubmittedPullRequest,
+};
+use crate::{Error, Result};
+
+pub struct PatchSubmissionUseCases {
+    repository: Arc<dyn PatchRepository>,
+    publisher: Arc<dyn PullRequestPublisher>,
+}
+
+impl PatchSubmissionUseCases {
+    pub fn new(
+        repository: Arc<dyn PatchRepository>,
+        publisher: Arc<dyn PullRequestPublisher>,
+    ) -> Self {
+        Self { repository, publisher }
+    }
+
+    /// Open a pull request for a patch on its repository: a new branch
+    /// with one commit applying the diff, on the commit the patch was
+    /// verified on. Only approved patches verified to build may be
+    /// submitted, once; the patch is then submitted by `submitted_by`.
+    pub async fn submit_pull_request(
+        &self,
+        id: Uuid,
+        submitted_by: &str,
+    ) -> Result<SubmittedPullRequest> {
+        let patch = self.repository.get_by_id(id).await?;
+        patch.check_submittable()?;
+        if let Some(number) = patch.pr_number {
+            return Err(Error::PatchNotSubmittable(format!(
+                "pull request #{} was already opened for the patch",
+                number
+            )));
+        }
+
+        let checkout = self.repository.get_repository_checkout(patch.vulnerability_id).await?;
+        let draft = pull_request_draft(&patch);
+        let pull_request = self.publisher.open_pull_request(&checkout, &draft).await?;
+        self.repository.record_pull_request(id, &pull_request, submitted_by).await?;
+        info!(
+            "Opened pull request #{} for patch {} as {}: {}",
+            pull_request.number, id, submitted_by, pull_request.url
+        );
+
+        Ok(pull_request)
+    }
+}
+
+fn pull_request_draft(patch: &PatchProposal) -> PullRequestDraft {
+    let validation = patch.validation_status.as_ref();
+    let mut body = format!("{}\n\n---\n\n", patch.description);
+    body.push_str(&format!("Fixes vulnerability `{}`.\n\n", patch.vulnerability_id));
+    if let Some(validation) = validation {
+        let commit = validation.commit_sha.as_deref().unwrap_or("the default branch");
+        body.push_str(&format!(
+            "Verified on `{}`: build {}, tests {}.\n",
+            commit,
+            if validation.build_succeeded { "succeeded" } else { "failed" },
+            if validation.tests_passed { "passed" } else { "failed" }
+        ));
+    }
+    if patch.generated_by_ai {
+        body.push_str("\nThis patch was generated by AI; review it before merging.\n");
+    }
+
+    PullRequestDraft {
+        branch: format!("security-patch/{}", patch.id),
+        base_commit: validation.and_then(|validation| validation.commit_sha.clone()),
+        title: patch.title.clone(),
+        body,
+        commit_message: patch.title.clone(),
+        diff: patch.patch_diff.clone(),
+    }
+}
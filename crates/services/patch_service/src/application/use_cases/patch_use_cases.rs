@@ -21,13 +21,28 @@ impl PatchUseCases {
         self.repository.create(patch).await
     }
 
+    /// Update a patch. A changed diff has to be verified again before the
+    /// patch can be submitted.
     pub async fn update_patch(&self, patch: &PatchProposal) -> Result<PatchProposal> {
-        self.repository.update(patch).await
+        let current = self.repository.get_by_id(patch.id).await?;
+        let updated = self.repository.update(patch).await?;
+        if current.patch_diff != patch.patch_diff {
+            self.repository.clear_validation_status(patch.id).await?;
+        }
+        Ok(updated)
     }
 
     pub async fn delete_patch(&self, id: Uuid) -> Result<()> {
         self.repository.delete(id).await
     }
 
+    /// The patch, if it may be submitted to GitHub: it must have been
+    /// verified, and the patched code must build.
+    pub async fn submittable_patch(&self, id: Uuid) -> Result<PatchProposal> {
+        let patch = self.repository.get_by_id(id).await?;
+        patch.check_submittable()?;
+        Ok(patch)
+    }
+
     // TODO: Add more use case methods as needed
 }
\ No newline at end of file
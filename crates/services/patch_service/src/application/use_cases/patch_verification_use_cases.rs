@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::{PatchRepository, PatchVerifier, ValidationStatus};
+use crate::Result;
+
+pub struct PatchVerificationUseCases {
+    repository: Arc<dyn PatchRepository>,
+    verifier: Arc<dyn PatchVerifier>,
+}
+
+impl PatchVerificationUseCases {
+    pub fn new(repository: Arc<dyn PatchRepository>, verifier: Arc<dyn PatchVerifier>) -> Self {
+        Self { repository, verifier }
+    }
+
+    /// Apply a patch to its repository at the commit its finding was
+    /// reported on, build and test the result, and record the outcome on
+    /// the patch.
+    pub async fn verify_patch(&self, id: Uuid) -> Result<ValidationStatus> {
+        let patch = self.repository.get_by_id(id).await?;
+        let checkout = self.repository.get_repository_checkout(patch.vulnerability_id).await?;
+
+        let validation = self.verifier.verify(&checkout, &patch.patch_diff).await?;
+        self.repository.update_validation_status(id, &validation).await?;
+        info!(
+            "Patch {} verified against {}: build {}, tests {}",
+            id,
+            checkout.full_name,
+            if validation.build_succeeded { "succeeded" } else { "failed" },
+            if validation.tests_passed { "passed" } else { "failed" }
+        );
+
+        Ok(validation)
+    }
+}
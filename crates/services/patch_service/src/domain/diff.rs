@@ -0,0 +1,566 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChange {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub content: String,
+    /// Line number in the original file; `None` for added lines.
+    pub old_line: Option<i32>,
+    /// Line number in the patched file; `None` for removed lines.
+    pub new_line: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub old_start: i32,
+    pub old_lines: i32,
+    pub new_start: i32,
+    pub new_lines: i32,
+    /// Text after the hunk's line ranges, usually the enclosing function.
+    pub section: String,
+    pub lines: Vec<DiffLine>,
+}
+
+/// One file of a unified diff.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PatchDiff {
+    /// The patched file's path, or the original's for deletions.
+    pub file_path: String,
+    /// `None` for added files.
+    pub old_path: Option<String>,
+    /// `None` for deleted files.
+    pub new_path: Option<String>,
+    pub change: FileChange,
+    pub additions: i32,
+    pub deletions: i32,
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl PatchDiff {
+    /// The file's content with this diff applied to `original`, which is
+    /// `None` when the file does not exist, or why the diff does not apply.
+    /// Hunks may have moved since the diff was made, as long as their
+    /// context and removed lines still match exactly.
+    pub fn apply(&self, original: Option<&str>) -> std::result::Result<String, String> {
+        let original = match (original, self.change) {
+            (Some(_), FileChange::Added) => {
+                return Err(format!("{} already exists", self.file_path));
+            }
+            (None, FileChange::Added) => "",
+            (None, _) => return Err(format!("{} does not exist", self.file_path)),
+            (Some(original), _) => original,
+        };
+
+        let lines: Vec<&str> = original.lines().collect();
+        let mut patched: Vec<&str> = Vec::with_capacity(lines.len());
+        let mut cursor = 0;
+        for (index, hunk) in self.hunks.iter().enumerate() {
+            let expected: Vec<&str> = hunk
+                .lines
+                .iter()
+                .filter(|line| line.kind != DiffLineKind::Added)
+                .map(|line| line.content.as_str())
+                .collect();
+            // An empty old range starts after line `old_start`
+            let preferred = if hunk.old_lines == 0 { hunk.old_start } else { hunk.old_start - 1 };
+            let start = find_hunk(&lines, &expected, cursor, preferred.max(0) as usize)
+                .ok_or_else(|| {
+                    format!(
+                        "hunk {} (@@ -{},{} +{},{} @@) does not match {}",
+                        index + 1,
+                        hunk.old_start,
+                        hunk.old_lines,
+                        hunk.new_start,
+                        hunk.new_lines,
+                        self.file_path
+                    )
+                })?;
+
+            patched.extend_from_slice(&lines[cursor..start]);
+            patched.extend(
+                hunk.lines
+                    .iter()
+                    .filter(|line| line.kind != DiffLineKind::Removed)
+                    .map(|line| line.content.as_str()),
+            );
+            cursor = start + expected.len();
+        }
+        patched.extend_from_slice(&lines[cursor..]);
+
+        if self.change == FileChange::Deleted || patched.is_empty() {
+            return Ok(String::new());
+        }
+        let mut content = patched.join("\n");
+        content.push('\n');
+        Ok(content)
+    }
+
+    /// The file's part of a unified diff.
+    pub fn render(&self) -> String {
+        let side = |prefix: &str, path: &Option<String>| match path {
+            Some(path) => format!("{}{}", prefix, path),
+            None => "/dev/null".to_string(),
+        };
+        let mut text = format!(
+            "--- {}\n+++ {}\n",
+            side("a/", &self.old_path),
+            side("b/", &self.new_path)
+        );
+        for hunk in &self.hunks {
+            text.push_str(&format!(
+                "@@ -{},{} +{},{} @@",
+                hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
+            ));
+            if !hunk.section.is_empty() {
+                text.push(' ');
+                text.push_str(&hunk.section);
+            }
+            text.push('\n');
+            for line in &hunk.lines {
+                text.push(match line.kind {
+                    DiffLineKind::Context => ' ',
+                    DiffLineKind::Added => '+',
+                    DiffLineKind::Removed => '-',
+                });
+                text.push_str(&line.content);
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// Renumber the patched side of each hunk and recount the file's
+    /// changes, after hunks were added or removed.
+    fn renumber(&mut self) {
+        self.hunks.sort_by_key(|hunk| hunk.old_start);
+        self.additions = 0;
+        self.deletions = 0;
+        let mut shift = 0;
+        for hunk in &mut self.hunks {
+            if self.change != FileChange::Added {
+                hunk.new_start = hunk.old_start + shift;
+            }
+            shift += hunk.new_lines - hunk.old_lines;
+            let mut new_line = hunk.new_start;
+            for line in &mut hunk.lines {
+                match line.kind {
+                    DiffLineKind::Removed => self.deletions += 1,
+                    DiffLineKind::Added | DiffLineKind::Context => {
+                        if line.kind == DiffLineKind::Added {
+                            self.additions += 1;
+                        }
+                        line.new_line = Some(new_line);
+                        new_line += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Combine diffs made independently against the same commit into one diff
+/// per file. Hunks overlapping one already taken, and files changed in
+/// incompatible ways, are left out and reported.
+pub fn combine_diffs(diffs: Vec<PatchDiff>) -> (Vec<PatchDiff>, Vec<String>) {
+    let mut files: Vec<PatchDiff> = Vec::new();
+    let mut left_out = Vec::new();
+    for diff in diffs {
+        let Some(file) = files.iter_mut().find(|file| file.file_path == diff.file_path) else {
+            files.push(diff);
+            continue;
+        };
+        if file.change != diff.change || file.change == FileChange::Added {
+            left_out.push(format!("{}: conflicting {:?} change", diff.file_path, diff.change));
+            continue;
+        }
+        for hunk in diff.hunks {
+            let end = |hunk: &DiffHunk| hunk.old_start + hunk.old_lines.max(1);
+            let overlaps =
+                |taken: &DiffHunk| taken.old_start < end(&hunk) && hunk.old_start < end(taken);
+            if file.hunks.iter().any(overlaps) {
+                left_out.push(format!(
+                    "{}: hunk at line {} overlaps another change",
+                    diff.file_path, hunk.old_start
+                ));
+                continue;
+            }
+            file.hunks.push(hunk);
+        }
+    }
+
+    for file in &mut files {
+        file.renumber();
+    }
+    (files, left_out)
+}
+
+/// Where `expected` occurs in `lines` at or after `cursor`, closest to
+/// `preferred`.
+fn find_hunk(lines: &[&str], expected: &[&str], cursor: usize, preferred: usize) -> Option<usize> {
+    let last = lines.len().checked_sub(expected.len())?;
+    if cursor > last {
+        return None;
+    }
+    let preferred = preferred.clamp(cursor, last);
+    (0..=last - cursor)
+        .flat_map(|distance| {
+            let after = preferred + distance;
+            let before = preferred.checked_sub(distance).filter(|start| *start >= cursor);
+            [before, (distance > 0 && after <= last).then_some(after)]
+        })
+        .flatten()
+        .find(|start| lines[*start..*start + expected.len()] == *expected)
+}
+
+/// A file of a patch and whether it applies to the repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiffCheck {
+    #[serde(flatten)]
+    pub diff: PatchDiff,
+    pub applies_cleanly: bool,
+    /// Why the file's hunks do not apply.
+    pub conflict: Option<String>,
+}
+
+/// A patch's diff, file by file, checked against the commit its finding
+/// was reported on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchDiffReport {
+    /// The repository's default branch when `None`.
+    pub commit_sha: Option<String>,
+    pub applies_cleanly: bool,
+    pub additions: i32,
+    pub deletions: i32,
+    pub files: Vec<FileDiffCheck>,
+}
+
+#[derive(Default)]
+struct FileHeader {
+    old_path: Option<String>,
+    new_path: Option<String>,
+    added: bool,
+    deleted: bool,
+    renamed: bool,
+}
+
+impl FileHeader {
+    fn into_diff(self, line_number: usize) -> Result<PatchDiff> {
+        let old_path = self.old_path.filter(|_| !self.added);
+        let new_path = self.new_path.filter(|_| !self.deleted);
+        let file_path = new_path.clone().or_else(|| old_path.clone()).ok_or_else(|| {
+            invalid(line_number, "file header names no file")
+        })?;
+        let change = match (&old_path, &new_path) {
+            (None, _) => FileChange::Added,
+            (_, None) => FileChange::Deleted,
+            (Some(old), Some(new)) if self.renamed || old != new => FileChange::Renamed,
+            _ => FileChange::Modified,
+        };
+
+        Ok(PatchDiff {
+            file_path,
+            old_path,
+            new_path,
+            change,
+            additions: 0,
+            deletions: 0,
+            hunks: Vec::new(),
+        })
+    }
+}
+
+/// A hunk being read, with the lines of each side still to come.
+struct OpenHunk {
+    hunk: DiffHunk,
+    old_remaining: i32,
+    new_remaining: i32,
+    old_line: i32,
+    new_line: i32,
+}
+
+impl OpenHunk {
+    fn is_complete(&self) -> bool {
+        self.old_remaining == 0 && self.new_remaining == 0
+    }
+
+    fn push(&mut self, line: &str, line_number: usize) -> Result<()> {
+        // Some generators drop the space of empty context lines
+        let (kind, content) = match line.chars().next() {
+            None => (DiffLineKind::Context, ""),
+            Some(' ') => (DiffLineKind::Context, &line[1..]),
+            Some('-') => (DiffLineKind::Removed, &line[1..]),
+            Some('+') => (DiffLineKind::Added, &line[1..]),
+            Some('\\') => return Ok(()),
+            Some(_) => return Err(invalid(line_number, "unexpected line inside a hunk")),
+        };
+
+        let takes_old = kind != DiffLineKind::Added;
+        let takes_new = kind != DiffLineKind::Removed;
+        if (takes_old && self.old_remaining == 0) || (takes_new && self.new_remaining == 0) {
+            return Err(invalid(line_number, "hunk is longer than its header says"));
+        }
+        let old_line = takes_old.then_some(self.old_line);
+        let new_line = takes_new.then_some(self.new_line);
+        if takes_old {
+            self.old_remaining -= 1;
+            self.old_line += 1;
+        }
+        if takes_new {
+            self.new_remaining -= 1;
+            self.new_line += 1;
+        }
+        self.hunk.lines.push(DiffLine { kind, content: content.to_string(), old_line, new_line });
+        Ok(())
+    }
+}
+
+/// Parse a unified diff, as produced by `git diff` or `diff -u`, into its
+/// files. Lines outside file headers and hunks are ignored.
+pub fn parse_unified_diff(diff: &str) -> Result<Vec<PatchDiff>> {
+    let mut files: Vec<PatchDiff> = Vec::new();
+    let mut header: Option<FileHeader> = None;
+    let mut open: Option<OpenHunk> = None;
+
+    for (index, line) in diff.lines().enumerate() {
+        let line_number = index + 1;
+        if let Some(hunk) = open.as_mut() {
+            if !hunk.is_complete() || line.starts_with('\\') {
+                hunk.push(line, line_number)?;
+                continue;
+            }
+            if is_hunk_line(line) {
+                return Err(invalid(line_number, "hunk is longer than its header says"));
+            }
+            close_hunk(&mut files, open.take());
+        }
+
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            if let Some(pending) = header.take() {
+                files.push(pending.into_diff(line_number)?);
+            }
+            let (old, new) = paths.split_once(" b/").unwrap_or((paths, paths));
+            header = Some(FileHeader {
+                old_path: Some(header_path(old)),
+                new_path: Some(header_path(new)),
+                ..FileHeader::default()
+            });
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            let pending = header.get_or_insert_with(FileHeader::default);
+            match header_path(path).as_str() {
+                "/dev/null" => pending.added = true,
+                path => pending.old_path = Some(path.to_string()),
+            }
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            let mut pending = header.take().unwrap_or_default();
+            match header_path(path).as_str() {
+                "/dev/null" => pending.deleted = true,
+                path => pending.new_path = Some(path.to_string()),
+            }
+            files.push(pending.into_diff(line_number)?);
+        } else if line.starts_with("@@") {
+            if header.is_some() || files.is_empty() {
+                return Err(invalid(line_number, "hunk before its file header"));
+            }
+            open = Some(open_hunk(line, line_number)?);
+        } else if let Some(pending) = header.as_mut() {
+            if line.starts_with("new file mode") {
+                pending.added = true;
+            } else if line.starts_with("deleted file mode") {
+                pending.deleted = true;
+            } else if let Some(path) = line.strip_prefix("rename from ") {
+                pending.old_path = Some(path.to_string());
+                pending.renamed = true;
+            } else if let Some(path) = line.strip_prefix("rename to ") {
+                pending.new_path = Some(path.to_string());
+                pending.renamed = true;
+            }
+        }
+    }
+
+    if open.as_ref().is_some_and(|hunk| !hunk.is_complete()) {
+        return Err(Error::ValidationFailed("diff ends inside a hunk".to_string()));
+    }
+    close_hunk(&mut files, open);
+    if let Some(pending) = header {
+        files.push(pending.into_diff(diff.lines().count())?);
+    }
+    if files.is_empty() {
+        return Err(Error::ValidationFailed("diff changes no files".to_string()));
+    }
+    Ok(files)
+}
+
+fn close_hunk(files: &mut [PatchDiff], open: Option<OpenHunk>) {
+    let (Some(open), Some(file)) = (open, files.last_mut()) else {
+        return;
+    };
+    for line in &open.hunk.lines {
+        match line.kind {
+            DiffLineKind::Added => file.additions += 1,
+            DiffLineKind::Removed => file.deletions += 1,
+            DiffLineKind::Context => {}
+        }
+    }
+    file.hunks.push(open.hunk);
+}
+
+fn is_hunk_line(line: &str) -> bool {
+    line.starts_with(' ')
+        || (line.starts_with('+') && !line.starts_with("+++ "))
+        || (line.starts_with('-') && !line.starts_with("--- "))
+}
+
+/// A path from a file header, without its `a/` or `b/` prefix or timestamp.
+fn header_path(path: &str) -> String {
+    let path = path.split('\t').next().unwrap_or(path).trim();
+    path.strip_prefix("a/").or_else(|| path.strip_prefix("b/")).unwrap_or(path).to_string()
+}
+
+/// `@@ -old_start[,old_lines] +new_start[,new_lines] @@ section`
+fn open_hunk(line: &str, line_number: usize) -> Result<OpenHunk> {
+    let malformed = || invalid(line_number, "malformed hunk header");
+    let (ranges, section) = line
+        .strip_prefix("@@ ")
+        .and_then(|rest| rest.split_once(" @@"))
+        .ok_or_else(malformed)?;
+    let mut ranges = ranges.split_whitespace();
+    let (old_start, old_lines) = ranges
+        .next()
+        .and_then(|range| range.strip_prefix('-'))
+        .and_then(parse_range)
+        .ok_or_else(malformed)?;
+    let (new_start, new_lines) = ranges
+        .next()
+        .and_then(|range| range.strip_prefix('+'))
+        .and_then(parse_range)
+        .ok_or_else(malformed)?;
+
+    Ok(OpenHunk {
+        hunk: DiffHunk {
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+            section: section.trim().to_string(),
+            lines: Vec::new(),
+        },
+        old_remaining: old_lines,
+        new_remaining: new_lines,
+        old_line: old_start,
+        new_line: new_start,
+    })
+}
+
+fn parse_range(range: &str) -> Option<(i32, i32)> {
+    match range.split_once(',') {
+        Some((start, lines)) => Some((start.parse().ok()?, lines.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+fn invalid(line_number: usize, reason: &str) -> Error {
+    Error::ValidationFailed(format!("line {} of the diff: {}", line_number, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/sources/pool.move b/sources/pool.move\n\
+                        index 83db48f..bf269f4 100644\n\
+                        --- a/sources/pool.move\n\
+                        +++ b/sources/pool.move\n\
+                        @@ -2,3 +2,4 @@ module pool {\n\
+                        \x20    fun withdraw() {\n\
+                        -        transfer();\n\
+                        +        assert!(owner, 0);\n\
+                        +        transfer();\n\
+                        \x20    }\n\
+                        diff --git a/src/Old.sol b/src/Old.sol\n\
+                        deleted file mode 100644\n\
+                        --- a/src/Old.sol\n\
+                        +++ /dev/null\n\
+                        @@ -1 +0,0 @@\n\
+                        -contract Old {}\n\
+                        \\ No newline at end of file\n";
+
+    #[test]
+    fn parses_files_hunks_and_line_numbers() {
+        let files = parse_unified_diff(DIFF).unwrap();
+
+        assert_eq!(files.len(), 2);
+        let pool = &files[0];
+        assert_eq!(pool.change, FileChange::Modified);
+        assert_eq!((pool.additions, pool.deletions), (2, 1));
+        assert_eq!(pool.hunks[0].section, "module pool {");
+        let added = &pool.hunks[0].lines[2];
+        assert_eq!(added.kind, DiffLineKind::Added);
+        assert_eq!((added.old_line, added.new_line), (None, Some(3)));
+        assert_eq!(pool.hunks[0].lines[4].old_line, Some(4));
+
+        assert_eq!(files[1].change, FileChange::Deleted);
+        assert_eq!(files[1].file_path, "src/Old.sol");
+        assert_eq!(files[1].new_path, None);
+    }
+
+    #[test]
+    fn applies_moved_hunks_and_reports_mismatches() {
+        let pool = &parse_unified_diff(DIFF).unwrap()[0];
+        let original =
+            "module pool {\n// moved\n    fun withdraw() {\n        transfer();\n    }\n}\n";
+
+        let patched = pool.apply(Some(original)).unwrap();
+        assert!(patched.contains("assert!(owner, 0);\n        transfer();"));
+
+        let changed = original.replace("transfer();", "send();");
+        assert!(pool.apply(Some(&changed)).unwrap_err().contains("hunk 1"));
+        assert!(pool.apply(None).is_err());
+    }
+
+    #[test]
+    fn rejects_hunks_longer_than_their_header() {
+        let diff = "--- a/a.move\n+++ b/a.move\n@@ -1 +1 @@\n-a\n+b\n+c\n";
+        assert!(parse_unified_diff(diff).is_err());
+    }
+
+    #[test]
+    fn combines_independent_diffs_of_one_file() {
+        let first = "--- a/a.move\n+++ b/a.move\n@@ -2,1 +2,2 @@\n a\n+b\n";
+        let second = "--- a/a.move\n+++ b/a.move\n@@ -9,1 +9,1 @@\n-x\n+y\n";
+        let overlapping = "--- a/a.move\n+++ b/a.move\n@@ -2,1 +2,1 @@\n-a\n+z\n";
+        let diffs = [first, second, overlapping]
+            .into_iter()
+            .flat_map(|diff| parse_unified_diff(diff).unwrap())
+            .collect();
+
+        let (files, left_out) = combine_diffs(diffs);
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(left_out.len(), 1);
+        assert_eq!((files[0].additions, files[0].deletions), (2, 1));
+        assert_eq!(files[0].hunks[1].new_start, 10);
+        assert_eq!(
+            files[0].render(),
+            "--- a/a.move\n+++ b/a.move\n@@ -2,1 +2,2 @@\n a\n+b\n@@ -9,1 +10,1 @@\n-x\n+y\n"
+        );
+    }
+}
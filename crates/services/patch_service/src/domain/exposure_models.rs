@@ -0,0 +1,151 @@
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+
+const MIST_PER_SUI: Decimal = dec!(1000000000);
+
+/// Indexed data older than this is flagged as stale on the estimate.
+const STALE_AFTER: Duration = Duration::hours(6);
+
+/// Indexed on-chain metrics for one Move package. Amounts are in MIST.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnChainExposure {
+    pub package_id: String,
+    pub network: String,
+    pub shared_object_tvl_mist: Decimal,
+    pub tx_count_24h: i64,
+    pub tx_volume_24h_mist: Decimal,
+    #[serde(with = "time::serde::rfc3339")]
+    pub indexed_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ExposureTier {
+    /// No indexed deployment for the repository.
+    Unknown,
+    Negligible,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl ExposureTier {
+    fn from_exposure_sui(exposure: Decimal) -> Self {
+        if exposure >= dec!(1000000) {
+            ExposureTier::Critical
+        } else if exposure >= dec!(100000) {
+            ExposureTier::High
+        } else if exposure >= dec!(1000) {
+            ExposureTier::Medium
+        } else if exposure > Decimal::ZERO {
+            ExposureTier::Low
+        } else {
+            ExposureTier::Negligible
+        }
+    }
+}
+
+/// Economic exposure of a finding, used to prioritize fixes by real risk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposureEstimate {
+    pub tier: ExposureTier,
+    /// SUI plausibly at risk if the finding is exploited.
+    pub estimated_exposure_sui: Decimal,
+    pub shared_object_tvl_sui: Decimal,
+    pub daily_volume_sui: Decimal,
+    pub daily_transactions: i64,
+    pub severity_weight: Decimal,
+    pub packages: Vec<OnChainExposure>,
+    /// True when any package's metrics are older than six hours.
+    pub stale: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub estimated_at: OffsetDateTime,
+}
+
+impl ExposureEstimate {
+    /// Weight the value reachable through the affected packages by severity.
+    ///
+    /// Funds held in shared objects are what an exploit can drain; for
+    /// packages that hold little but route a lot, a day of volume is used
+    /// instead, whichever is larger.
+    pub fn estimate(severity: &str, packages: Vec<OnChainExposure>, now: OffsetDateTime) -> Self {
+        let severity_weight = severity_weight(severity);
+        let tvl_mist: Decimal = packages.iter().map(|p| p.shared_object_tvl_mist).sum();
+        let volume_mist: Decimal = packages.iter().map(|p| p.tx_volume_24h_mist).sum();
+        let daily_transactions = packages.iter().map(|p| p.tx_count_24h).sum();
+
+        let shared_object_tvl_sui = tvl_mist / MIST_PER_SUI;
+        let daily_volume_sui = volume_mist / MIST_PER_SUI;
+        let estimated_exposure_sui =
+            (shared_object_tvl_sui.max(daily_volume_sui) * severity_weight).round_dp(9);
+
+        let tier = if packages.is_empty() {
+            ExposureTier::Unknown
+        } else {
+            ExposureTier::from_exposure_sui(estimated_exposure_sui)
+        };
+        let stale = packages.iter().any(|p| now - p.indexed_at > STALE_AFTER);
+
+        Self {
+            tier,
+            estimated_exposure_sui,
+            shared_object_tvl_sui,
+            daily_volume_sui,
+            daily_transactions,
+            severity_weight,
+            packages,
+            stale,
+            estimated_at: now,
+        }
+    }
+}
+
+/// Share of the reachable value a finding of this severity puts at risk.
+fn severity_weight(severity: &str) -> Decimal {
+    match severity.to_lowercase().as_str() {
+        "critical" => dec!(1.0),
+        "high" => dec!(0.6),
+        "medium" => dec!(0.25),
+        "low" => dec!(0.05),
+        _ => dec!(0.1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(tvl_sui: i64, volume_sui: i64, indexed_at: OffsetDateTime) -> OnChainExposure {
+        OnChainExposure {
+            package_id: "0x2".to_string(),
+            network: "mainnet".to_string(),
+            shared_object_tvl_mist: Decimal::from(tvl_sui) * MIST_PER_SUI,
+            tx_count_24h: 10,
+            tx_volume_24h_mist: Decimal::from(volume_sui) * MIST_PER_SUI,
+            indexed_at,
+        }
+    }
+
+    #[test]
+    fn estimate_weights_larger_of_tvl_and_volume_by_severity() {
+        let now = OffsetDateTime::now_utc();
+
+        let estimate = ExposureEstimate::estimate(
+            "high",
+            vec![package(150_000, 20_000, now), package(50_000, 0, now - Duration::days(1))],
+            now,
+        );
+        assert_eq!(estimate.shared_object_tvl_sui, dec!(200000));
+        assert_eq!(estimate.estimated_exposure_sui, dec!(120000));
+        assert_eq!(estimate.tier, ExposureTier::High);
+        assert_eq!(estimate.daily_transactions, 20);
+        assert!(estimate.stale);
+
+        let unknown = ExposureEstimate::estimate("critical", vec![], now);
+        assert_eq!(unknown.tier, ExposureTier::Unknown);
+        assert_eq!(unknown.estimated_exposure_sui, Decimal::ZERO);
+    }
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::exposure_models::OnChainExposure;
+use crate::Result;
+
+#[async_trait]
+pub trait ExposureSource: Send + Sync {
+    // Indexed metrics for every package deployed from the repository
+    async fn repository_exposure(&self, repository_id: Uuid) -> Result<Vec<OnChainExposure>>;
+}
@@ -1,7 +1,25 @@
+pub mod diff;
+pub mod exposure_models;
+pub mod exposure_source_trait;
+pub mod patch_candidate_models;
+pub mod patch_group_models;
 pub mod patch_models;
+pub mod patch_review_models;
 pub mod patch_repository_trait;
 pub mod patch_generator_trait;
+pub mod patch_verifier_trait;
+pub mod pull_request_publisher_trait;
+pub mod source_file_provider_trait;
 
+pub use diff::*;
+pub use exposure_models::*;
+pub use exposure_source_trait::*;
+pub use patch_candidate_models::*;
+pub use patch_group_models::*;
 pub use patch_models::*;
+pub use patch_review_models::*;
 pub use patch_repository_trait::*;
-pub use patch_generator_trait::*;
\ No newline at end of file
+pub use patch_generator_trait::*;
+pub use patch_verifier_trait::*;
+pub use pull_request_publisher_trait::*;
+pub use source_file_provider_trait::*;
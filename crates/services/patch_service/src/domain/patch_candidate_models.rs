@@ -0,0 +1,184 @@
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::patch_generator_trait::GeneratedPatch;
+use super::patch_models::{GenerationStrategy, ValidationStatus};
+
+/// Most candidates generated for one finding, one per strategy.
+pub const MAX_PATCH_CANDIDATES: usize = 3;
+
+/// Changed lines past which a larger diff no longer costs a candidate more.
+const DIFF_SIZE_CAP: i32 = 100;
+
+/// What the validation pass found out about a candidate. Build and test
+/// results are `None` when no sandbox verifier is configured.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CandidateValidation {
+    /// Whether the diff parsed. One that does not apply to the checkout
+    /// fails its build.
+    pub diff_parsed: bool,
+    pub build_succeeded: Option<bool>,
+    pub tests_passed: Option<bool>,
+    pub additions: i32,
+    pub deletions: i32,
+    pub message: Option<String>,
+}
+
+impl CandidateValidation {
+    /// A candidate's validation from the size of its parsed diff and the
+    /// sandbox's verdict, if it was verified.
+    pub fn new(additions: i32, deletions: i32, verified: Option<&ValidationStatus>) -> Self {
+        CandidateValidation {
+            diff_parsed: true,
+            build_succeeded: verified.map(|status| status.build_succeeded),
+            tests_passed: verified.map(|status| status.tests_passed),
+            additions,
+            deletions,
+            message: verified.map(|status| status.validation_message.clone()),
+        }
+    }
+
+    /// A candidate whose diff could not be parsed.
+    pub fn unusable(message: String) -> Self {
+        CandidateValidation { message: Some(message), ..Default::default() }
+    }
+
+    /// Score out of 100: a candidate whose diff did not parse scores nothing, a
+    /// failed build costs 60 and failed tests 30, and every changed line up
+    /// to `DIFF_SIZE_CAP` costs 0.2, so the smaller of two working fixes
+    /// ranks first. The rest is weighted by the generator's confidence.
+    pub fn score(&self, confidence: f64) -> f64 {
+        if !self.diff_parsed {
+            return 0.0;
+        }
+        let mut score = 100.0;
+        if self.build_succeeded == Some(false) {
+            score -= 60.0;
+        }
+        if self.tests_passed == Some(false) {
+            score -= 30.0;
+        }
+        score -= f64::from((self.additions + self.deletions).min(DIFF_SIZE_CAP)) * 0.2;
+        (score * confidence.clamp(0.0, 1.0)).max(0.0)
+    }
+}
+
+/// One of the alternative patches generated for a finding, for reviewers to
+/// pick from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchCandidate {
+    pub id: Uuid,
+    pub vulnerability_id: Uuid,
+    /// 1 for the best scoring candidate.
+    pub rank: i32,
+    pub strategy: GenerationStrategy,
+    pub patch: GeneratedPatch,
+    pub validation: CandidateValidation,
+    pub score: f64,
+    /// Token subject of the reviewer who picked this candidate.
+    pub selected_by: Option<String>,
+    pub selected_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+impl PatchCandidate {
+    pub fn new(
+        vulnerability_id: Uuid,
+        patch: GeneratedPatch,
+        validation: CandidateValidation,
+    ) -> Self {
+        let confidence = patch.confidence_score.to_f64().unwrap_or(0.0);
+        PatchCandidate {
+            id: Uuid::new_v4(),
+            vulnerability_id,
+            rank: 0,
+            strategy: patch.generation_metadata.strategy_used.clone(),
+            score: validation.score(confidence),
+            patch,
+            validation,
+            selected_by: None,
+            selected_at: None,
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+/// Order candidates best first, the smaller diff first among equal scores,
+/// and number their ranks from 1.
+pub fn rank_candidates(candidates: &mut [PatchCandidate]) {
+    let size = |candidate: &PatchCandidate| {
+        candidate.validation.additions + candidate.validation.deletions
+    };
+    candidates.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| size(a).cmp(&size(b)))
+    });
+    for (rank, candidate) in candidates.iter_mut().enumerate() {
+        candidate.rank = rank as i32 + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::GenerationMetadata;
+    use rust_decimal_macros::dec;
+
+    fn candidate(strategy: GenerationStrategy, validation: CandidateValidation) -> PatchCandidate {
+        let patch = GeneratedPatch {
+            title: "Fix".to_string(),
+            description: String::new(),
+            patch_diff: String::new(),
+            files_changed: vec![],
+            confidence_score: dec!(0.9),
+            generation_metadata: GenerationMetadata {
+                model_version: "test".to_string(),
+                generation_time_ms: 0,
+                tokens_used: 0,
+                strategy_used: strategy,
+            },
+            exposure_estimate: None,
+        };
+        PatchCandidate::new(Uuid::nil(), patch, validation)
+    }
+
+    fn verified(build_succeeded: bool, tests_passed: bool) -> ValidationStatus {
+        ValidationStatus {
+            tests_passed,
+            build_succeeded,
+            security_scan_passed: true,
+            validation_message: String::new(),
+            commit_sha: None,
+            validated_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn working_minimal_fix_ranks_above_refactor_and_broken_builds() {
+        let mut candidates = vec![
+            candidate(
+                GenerationStrategy::Balanced,
+                CandidateValidation::new(2, 1, Some(&verified(false, false))),
+            ),
+            candidate(
+                GenerationStrategy::Aggressive,
+                CandidateValidation::new(40, 25, Some(&verified(true, true))),
+            ),
+            candidate(
+                GenerationStrategy::Conservative,
+                CandidateValidation::new(1, 1, Some(&verified(true, true))),
+            ),
+        ];
+
+        rank_candidates(&mut candidates);
+
+        let ranked: Vec<_> = candidates.iter().map(|c| (c.rank, c.strategy.as_str())).collect();
+        assert_eq!(ranked, vec![(1, "conservative"), (2, "aggressive"), (3, "balanced")]);
+        assert_eq!(CandidateValidation::unusable("bad diff".to_string()).score(1.0), 0.0);
+    }
+}
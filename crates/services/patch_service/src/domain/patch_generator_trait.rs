@@ -3,6 +3,8 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::exposure_models::ExposureEstimate;
+use super::patch_group_models::{GeneratedPatchSet, PatchSetGenerationRequest};
 use super::patch_models::*;
 use crate::Result;
 
@@ -14,6 +16,20 @@ pub trait PatchGenerator: Send + Sync {
         request: &PatchGenerationRequest,
     ) -> Result<GeneratedPatch>;
     
+    // Generate up to `count` alternative patches for a vulnerability, one
+    // per strategy, starting with the requested one
+    async fn generate_candidates(
+        &self,
+        request: &PatchGenerationRequest,
+        count: usize,
+    ) -> Result<Vec<GeneratedPatch>>;
+    
+    // Generate one coordinated patch for several findings of a repository
+    async fn generate_patch_set(
+        &self,
+        request: &PatchSetGenerationRequest,
+    ) -> Result<GeneratedPatchSet>;
+    
     // Validate that a patch can be applied
     async fn validate_patch(
         &self,
@@ -37,6 +53,9 @@ pub struct GeneratedPatch {
     pub files_changed: Vec<String>,
     pub confidence_score: Decimal,
     pub generation_metadata: GenerationMetadata,
+    // Attached after generation; None when estimation was skipped or failed
+    #[serde(default)]
+    pub exposure_estimate: Option<ExposureEstimate>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::diff::PatchDiff;
+use super::patch_models::{GenerationStrategy, VulnerabilityTarget};
+
+/// Scope a token needs to generate patches for many findings at once.
+pub const SCOPE_PATCHES_BULK_GENERATE: &str = "patches:bulk_generate";
+
+/// Most findings one bulk generation patches; larger requests are refused.
+pub const MAX_BULK_FINDINGS: i64 = 20;
+
+/// Which of a repository's open findings a bulk generation patches.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkPatchFilter {
+    pub vulnerability_type: Option<String>,
+    pub severity: Option<String>,
+    pub cwe_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchSetGenerationRequest {
+    pub repository_id: Uuid,
+    pub findings: Vec<VulnerabilityTarget>,
+    pub generation_strategy: GenerationStrategy,
+}
+
+/// One patch fixing several findings, file by file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedPatchSet {
+    pub title: String,
+    pub description: String,
+    pub files: Vec<PatchDiff>,
+    /// Findings and hunks left out of the set, and why.
+    pub left_out: Vec<String>,
+    pub model_version: String,
+    pub tokens_used: i32,
+}
+
+/// The part of a patch group changing one file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchGroupFile {
+    pub file_path: String,
+    pub patch_diff: String,
+    /// Findings in this file the sub-patch fixes.
+    pub vulnerability_ids: Vec<Uuid>,
+    pub additions: i32,
+    pub deletions: i32,
+}
+
+/// A coordinated patch set generated for a class of findings in one
+/// repository, tracked as one sub-patch per file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchGroup {
+    pub id: Uuid,
+    pub repository_id: Uuid,
+    pub filter: BulkPatchFilter,
+    pub strategy: GenerationStrategy,
+    pub title: String,
+    pub description: String,
+    pub model_version: String,
+    pub vulnerability_ids: Vec<Uuid>,
+    pub files: Vec<PatchGroupFile>,
+    pub left_out: Vec<String>,
+    pub created_at: OffsetDateTime,
+}
+
+impl PatchGroup {
+    /// A new group for `set`, generated for `findings`, with one sub-patch
+    /// per changed file.
+    pub fn new(
+        repository_id: Uuid,
+        filter: BulkPatchFilter,
+        strategy: GenerationStrategy,
+        findings: &[VulnerabilityTarget],
+        set: GeneratedPatchSet,
+    ) -> Self {
+        let files = set
+            .files
+            .iter()
+            .map(|file| PatchGroupFile {
+                file_path: file.file_path.clone(),
+                patch_diff: file.render(),
+                vulnerability_ids: findings
+                    .iter()
+                    .filter(|finding| {
+                        Some(&finding.context.file_path) == file.old_path.as_ref()
+                            || finding.context.file_path == file.file_path
+                    })
+                    .map(|finding| finding.vulnerability_id)
+                    .collect(),
+                additions: file.additions,
+                deletions: file.deletions,
+            })
+            .collect();
+
+        PatchGroup {
+            id: Uuid::new_v4(),
+            repository_id,
+            filter,
+            strategy,
+            title: set.title,
+            description: set.description,
+            model_version: set.model_version,
+            vulnerability_ids: findings.iter().map(|finding| finding.vulnerability_id).collect(),
+            files,
+            left_out: set.left_out,
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
@@ -5,26 +5,69 @@ use modql::filter::{FilterNodes, OpValsString, OpValsValue};
 use sqlx::FromRow;
 use time::OffsetDateTime;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+use crate::Error;
+
+/// Where a patch is in its review: proposed, reviewed until it has its
+/// required approvals or is rejected, then submitted as a pull request and
+/// merged. `Applied` marks patches applied outside a pull request.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PatchStatus {
-    Draft,
+    Proposed,
     UnderReview,
     Approved,
     Rejected,
+    Submitted,
+    Merged,
     Applied,
-    Failed,
+}
+
+impl PatchStatus {
+    /// Whether the review workflow allows moving from this status to `next`.
+    pub fn can_transition_to(self, next: PatchStatus) -> bool {
+        use PatchStatus::*;
+        matches!(
+            (self, next),
+            (Proposed, UnderReview)
+                | (UnderReview, Approved)
+                | (UnderReview, Rejected)
+                | (Approved, UnderReview)
+                | (Approved, Rejected)
+                | (Approved, Submitted)
+                | (Approved, Applied)
+                | (Submitted, Merged)
+        )
+    }
+
+    /// `next`, if the workflow allows moving there from this status.
+    pub fn transition_to(self, next: PatchStatus) -> crate::Result<PatchStatus> {
+        if self == next || self.can_transition_to(next) {
+            Ok(next)
+        } else {
+            Err(Error::InvalidPatchState(format!(
+                "a {} patch cannot become {}",
+                self.to_string(),
+                next.to_string()
+            )))
+        }
+    }
+
+    /// Whether reviewers may still be assigned and verdicts given.
+    pub fn is_reviewable(self) -> bool {
+        matches!(self, PatchStatus::Proposed | PatchStatus::UnderReview | PatchStatus::Approved)
+    }
 }
 
 impl From<String> for PatchStatus {
     fn from(s: String) -> Self {
         match s.to_lowercase().as_str() {
-            "draft" => PatchStatus::Draft,
+            "proposed" | "draft" => PatchStatus::Proposed,
             "under_review" => PatchStatus::UnderReview,
             "approved" => PatchStatus::Approved,
             "rejected" => PatchStatus::Rejected,
+            "submitted" => PatchStatus::Submitted,
+            "merged" => PatchStatus::Merged,
             "applied" => PatchStatus::Applied,
-            "failed" => PatchStatus::Failed,
-            _ => PatchStatus::Draft,
+            _ => PatchStatus::Proposed,
         }
     }
 }
@@ -32,12 +75,13 @@ impl From<String> for PatchStatus {
 impl ToString for PatchStatus {
     fn to_string(&self) -> String {
         match self {
-            PatchStatus::Draft => "draft".to_string(),
+            PatchStatus::Proposed => "proposed".to_string(),
             PatchStatus::UnderReview => "under_review".to_string(),
             PatchStatus::Approved => "approved".to_string(),
             PatchStatus::Rejected => "rejected".to_string(),
+            PatchStatus::Submitted => "submitted".to_string(),
+            PatchStatus::Merged => "merged".to_string(),
             PatchStatus::Applied => "applied".to_string(),
-            PatchStatus::Failed => "failed".to_string(),
         }
     }
 }
@@ -60,6 +104,17 @@ pub struct PatchProposal {
     pub validation_status: Option<ValidationStatus>,
     pub applied_at: Option<OffsetDateTime>,
     pub pr_url: Option<String>,
+    pub pr_number: Option<i32>,
+    /// `open`, `closed` or `merged`, as last reported by GitHub.
+    pub pr_state: Option<String>,
+    /// Address of whoever submitted the pull request.
+    pub pr_submitted_by: Option<String>,
+    /// Reviewer approvals needed before the patch is approved.
+    pub required_approvals: i32,
+    /// When a later analysis found the merged fix undone, and whether it was
+    /// `reverted` or its finding `reappeared`.
+    pub regressed_at: Option<OffsetDateTime>,
+    pub regression_reason: Option<String>,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
 }
@@ -70,9 +125,33 @@ pub struct ValidationStatus {
     pub build_succeeded: bool,
     pub security_scan_passed: bool,
     pub validation_message: String,
+    /// Commit of the checkout the patch was applied to.
+    #[serde(default)]
+    pub commit_sha: Option<String>,
     pub validated_at: OffsetDateTime,
 }
 
+impl PatchProposal {
+    /// Whether the patch may be submitted to GitHub: its reviewers have
+    /// approved it, it has been applied to a checkout of its repository and
+    /// the result builds.
+    pub fn check_submittable(&self) -> crate::Result<()> {
+        if self.status != PatchStatus::Approved {
+            return Err(Error::PatchNotSubmittable(format!(
+                "the patch is {}, not approved",
+                self.status.to_string()
+            )));
+        }
+        match &self.validation_status {
+            None => Err(Error::PatchNotSubmittable("the patch has not been verified".to_string())),
+            Some(validation) if !validation.build_succeeded => Err(Error::PatchNotSubmittable(
+                format!("the patched code does not build: {}", validation.validation_message),
+            )),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vote {
     pub id: Uuid,
@@ -161,26 +240,45 @@ pub struct VulnerabilityContext {
     pub description: String,
 }
 
+/// A finding together with the repository it was found in.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VulnerabilityTarget {
+    pub vulnerability_id: Uuid,
+    pub repository_id: Uuid,
+    pub context: VulnerabilityContext,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum GenerationStrategy {
     Conservative, // Minimal changes, high safety
     Balanced,     // Balance between safety and effectiveness
     Aggressive,   // More comprehensive fixes
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PatchDiff {
-    pub file_path: String,
-    pub hunks: Vec<DiffHunk>,
+impl GenerationStrategy {
+    pub const ALL: [GenerationStrategy; 3] = [
+        GenerationStrategy::Conservative,
+        GenerationStrategy::Balanced,
+        GenerationStrategy::Aggressive,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GenerationStrategy::Conservative => "conservative",
+            GenerationStrategy::Balanced => "balanced",
+            GenerationStrategy::Aggressive => "aggressive",
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DiffHunk {
-    pub old_start: i32,
-    pub old_lines: i32,
-    pub new_start: i32,
-    pub new_lines: i32,
-    pub content: String,
+impl From<String> for GenerationStrategy {
+    fn from(s: String) -> Self {
+        match s.to_lowercase().as_str() {
+            "conservative" => GenerationStrategy::Conservative,
+            "aggressive" => GenerationStrategy::Aggressive,
+            _ => GenerationStrategy::Balanced,
+        }
+    }
 }
 
 // Database representation without complex nested fields
@@ -200,6 +298,18 @@ pub struct PatchProposalDb {
     pub generated_by_ai: bool,
     pub applied_at: Option<OffsetDateTime>,
     pub pr_url: Option<String>,
+    pub build_succeeded: Option<bool>,
+    pub tests_passed: Option<bool>,
+    pub security_scan_passed: Option<bool>,
+    pub validation_message: Option<String>,
+    pub validated_commit_sha: Option<String>,
+    pub validated_at: Option<OffsetDateTime>,
+    pub github_pr_number: Option<i32>,
+    pub pr_state: Option<String>,
+    pub pr_submitted_by: Option<String>,
+    pub required_approvals: i32,
+    pub regressed_at: Option<OffsetDateTime>,
+    pub regression_reason: Option<String>,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
 }
@@ -246,6 +356,14 @@ pub struct PatchProposalFilter {
 impl PatchProposalDb {
     /// Convert database representation to full PatchProposal
     pub fn to_patch_proposal(self) -> PatchProposal {
+        let validation_status = self.validated_at.map(|validated_at| ValidationStatus {
+            tests_passed: self.tests_passed.unwrap_or(false),
+            build_succeeded: self.build_succeeded.unwrap_or(false),
+            security_scan_passed: self.security_scan_passed.unwrap_or(false),
+            validation_message: self.validation_message.unwrap_or_default(),
+            commit_sha: self.validated_commit_sha,
+            validated_at,
+        });
         PatchProposal {
             id: self.id,
             vulnerability_id: self.vulnerability_id,
@@ -260,9 +378,15 @@ impl PatchProposalDb {
             rejection_score: self.rejection_score,
             total_votes: self.total_votes,
             generated_by_ai: self.generated_by_ai,
-            validation_status: None, // TODO: Handle separately
+            validation_status,
             applied_at: self.applied_at,
             pr_url: self.pr_url,
+            pr_number: self.github_pr_number,
+            pr_state: self.pr_state,
+            pr_submitted_by: self.pr_submitted_by,
+            required_approvals: self.required_approvals,
+            regressed_at: self.regressed_at,
+            regression_reason: self.regression_reason,
             created_at: self.created_at,
             updated_at: self.updated_at,
         }
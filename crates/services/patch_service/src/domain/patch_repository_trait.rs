@@ -2,7 +2,13 @@ use async_trait::async_trait;
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
+use super::exposure_models::ExposureEstimate;
+use super::patch_candidate_models::PatchCandidate;
+use super::patch_group_models::{BulkPatchFilter, PatchGroup};
 use super::patch_models::*;
+use super::patch_review_models::{PatchReview, PatchReviewer, ReviewDecision};
+use super::patch_verifier_trait::RepositoryCheckout;
+use super::pull_request_publisher_trait::SubmittedPullRequest;
 use crate::Result;
 
 #[async_trait]
@@ -46,6 +52,43 @@ pub trait PatchRepository: Send + Sync {
     
     async fn mark_as_applied(&self, id: Uuid, pr_url: Option<String>) -> Result<()>;
     
+    /// Record the pull request `submitted_by` opened for a patch, which is
+    /// now submitted.
+    async fn record_pull_request(
+        &self,
+        id: Uuid,
+        pull_request: &SubmittedPullRequest,
+        submitted_by: &str,
+    ) -> Result<()>;
+    
+    // Review
+    async fn get_reviewers(&self, patch_id: Uuid) -> Result<Vec<PatchReviewer>>;
+    
+    /// Assign reviewers to a patch; reviewers already assigned are kept.
+    /// Newly assigned reviewers are announced in a `patch.review_requested`
+    /// event.
+    async fn assign_reviewers(
+        &self,
+        patch_id: Uuid,
+        reviewers: &[String],
+        assigned_by: &str,
+    ) -> Result<()>;
+    
+    async fn remove_reviewer(&self, patch_id: Uuid, reviewer: &str) -> Result<bool>;
+    
+    async fn set_required_approvals(&self, patch_id: Uuid, required_approvals: i32) -> Result<()>;
+    
+    async fn add_review(
+        &self,
+        patch_id: Uuid,
+        reviewer: &str,
+        decision: ReviewDecision,
+        comment: Option<&str>,
+    ) -> Result<PatchReview>;
+    
+    /// A patch's reviews, oldest first.
+    async fn get_reviews(&self, patch_id: Uuid) -> Result<Vec<PatchReview>>;
+    
     // Validation
     async fn update_validation_status(
         &self,
@@ -53,6 +96,9 @@ pub trait PatchRepository: Send + Sync {
         validation: &ValidationStatus,
     ) -> Result<()>;
     
+    /// Forget a patch's verification, e.g. once its diff has changed.
+    async fn clear_validation_status(&self, id: Uuid) -> Result<()>;
+    
     // Statistics
     async fn get_statistics(&self) -> Result<PatchStatistics>;
     
@@ -64,4 +110,48 @@ pub trait PatchRepository: Send + Sync {
     async fn get_by_vulnerability(&self, vulnerability_id: Uuid) -> Result<Vec<PatchProposal>>;
     
     async fn has_approved_patch(&self, vulnerability_id: Uuid) -> Result<bool>;
+    
+    async fn get_vulnerability_target(
+        &self,
+        vulnerability_id: Uuid,
+    ) -> Result<VulnerabilityTarget>;
+    
+    /// A repository's open code findings matching `filter`, most severe
+    /// first.
+    async fn find_vulnerability_targets(
+        &self,
+        repository_id: Uuid,
+        filter: &BulkPatchFilter,
+        limit: i64,
+    ) -> Result<Vec<VulnerabilityTarget>>;
+    
+    // Patch groups
+    async fn create_patch_group(&self, group: &PatchGroup) -> Result<()>;
+    
+    async fn get_patch_group(&self, id: Uuid) -> Result<PatchGroup>;
+    
+    // Patch candidates
+    /// Replace a finding's candidates with a newly generated set.
+    async fn replace_patch_candidates(
+        &self,
+        vulnerability_id: Uuid,
+        candidates: &[PatchCandidate],
+    ) -> Result<()>;
+    
+    /// A finding's candidates, best ranked first.
+    async fn get_patch_candidates(&self, vulnerability_id: Uuid) -> Result<Vec<PatchCandidate>>;
+    
+    /// Mark a candidate as the one picked for its finding, unmarking any
+    /// other.
+    async fn select_patch_candidate(&self, id: Uuid, selected_by: &str) -> Result<PatchCandidate>;
+    
+    /// The repository a finding was reported in, at the analysed commit.
+    async fn get_repository_checkout(&self, vulnerability_id: Uuid) -> Result<RepositoryCheckout>;
+    
+    // Exposure
+    async fn save_exposure_estimate(
+        &self,
+        vulnerability_id: Uuid,
+        estimate: &ExposureEstimate,
+    ) -> Result<()>;
 }
\ No newline at end of file
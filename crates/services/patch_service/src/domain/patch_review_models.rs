@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::patch_models::PatchStatus;
+
+/// Scope a token needs to assign a patch's reviewers and set how many
+/// approvals it needs.
+pub const SCOPE_PATCHES_REVIEW_ADMIN: &str = "patches:review_admin";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approve,
+    Reject,
+    /// A comment without a verdict.
+    Comment,
+}
+
+impl ReviewDecision {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReviewDecision::Approve => "approve",
+            ReviewDecision::Reject => "reject",
+            ReviewDecision::Comment => "comment",
+        }
+    }
+}
+
+impl From<String> for ReviewDecision {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "approve" => ReviewDecision::Approve,
+            "reject" => ReviewDecision::Reject,
+            _ => ReviewDecision::Comment,
+        }
+    }
+}
+
+/// A reviewer assigned to a patch, identified by their token subject.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchReviewer {
+    pub reviewer: String,
+    pub assigned_by: String,
+    pub assigned_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchReview {
+    pub id: Uuid,
+    pub patch_id: Uuid,
+    pub reviewer: String,
+    pub decision: ReviewDecision,
+    pub comment: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+/// Assigned reviewers' standing verdicts: each reviewer's latest approval or
+/// rejection counts, comments and reviews by anyone else do not.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReviewTally {
+    pub approvals: i32,
+    pub rejections: i32,
+}
+
+impl ReviewTally {
+    pub fn count(reviewers: &[PatchReviewer], reviews: &[PatchReview]) -> Self {
+        let mut verdicts: HashMap<&str, (OffsetDateTime, ReviewDecision)> = HashMap::new();
+        for review in reviews {
+            if review.decision == ReviewDecision::Comment
+                || !reviewers.iter().any(|r| r.reviewer == review.reviewer)
+            {
+                continue;
+            }
+            let verdict = verdicts
+                .entry(review.reviewer.as_str())
+                .or_insert((review.created_at, review.decision));
+            if review.created_at >= verdict.0 {
+                *verdict = (review.created_at, review.decision);
+            }
+        }
+
+        let mut tally = ReviewTally::default();
+        for (_, decision) in verdicts.values() {
+            match decision {
+                ReviewDecision::Approve => tally.approvals += 1,
+                ReviewDecision::Reject => tally.rejections += 1,
+                ReviewDecision::Comment => {}
+            }
+        }
+        tally
+    }
+
+    /// The status a patch under review moves to with this tally: rejected
+    /// on any rejection, approved once it has `required_approvals`, and
+    /// otherwise still under review. Patches past review keep `status`.
+    pub fn outcome(&self, status: PatchStatus, required_approvals: i32) -> PatchStatus {
+        if !matches!(status, PatchStatus::UnderReview | PatchStatus::Approved) {
+            return status;
+        }
+        if self.rejections > 0 {
+            PatchStatus::Rejected
+        } else if self.approvals >= required_approvals {
+            PatchStatus::Approved
+        } else {
+            PatchStatus::UnderReview
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchReviewSummary {
+    pub patch_id: Uuid,
+    pub status: PatchStatus,
+    pub required_approvals: i32,
+    pub tally: ReviewTally,
+    pub reviewers: Vec<PatchReviewer>,
+    pub reviews: Vec<PatchReview>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    fn reviewer(name: &str) -> PatchReviewer {
+        PatchReviewer {
+            reviewer: name.to_string(),
+            assigned_by: "0xadmin".to_string(),
+            assigned_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn review(name: &str, decision: ReviewDecision, minutes: i64) -> PatchReview {
+        PatchReview {
+            id: Uuid::new_v4(),
+            patch_id: Uuid::nil(),
+            reviewer: name.to_string(),
+            decision,
+            comment: None,
+            created_at: OffsetDateTime::UNIX_EPOCH + Duration::minutes(minutes),
+        }
+    }
+
+    #[test]
+    fn tally_counts_latest_verdict_of_assigned_reviewers() {
+        let reviewers = [reviewer("alice"), reviewer("bob")];
+        let reviews = [
+            review("alice", ReviewDecision::Reject, 1),
+            review("alice", ReviewDecision::Approve, 2),
+            review("bob", ReviewDecision::Comment, 3),
+            review("mallory", ReviewDecision::Reject, 4),
+        ];
+
+        let tally = ReviewTally::count(&reviewers, &reviews);
+
+        assert_eq!(tally, ReviewTally { approvals: 1, rejections: 0 });
+        assert_eq!(tally.outcome(PatchStatus::UnderReview, 1), PatchStatus::Approved);
+        assert_eq!(tally.outcome(PatchStatus::UnderReview, 2), PatchStatus::UnderReview);
+        assert_eq!(tally.outcome(PatchStatus::Submitted, 2), PatchStatus::Submitted);
+    }
+
+    #[test]
+    fn workflow_only_allows_forward_transitions() {
+        assert!(PatchStatus::Proposed.transition_to(PatchStatus::UnderReview).is_ok());
+        assert!(PatchStatus::Approved.transition_to(PatchStatus::Submitted).is_ok());
+        assert!(PatchStatus::Submitted.transition_to(PatchStatus::Merged).is_ok());
+        assert!(PatchStatus::Proposed.transition_to(PatchStatus::Submitted).is_err());
+        assert!(PatchStatus::Rejected.transition_to(PatchStatus::Approved).is_err());
+        assert!(PatchStatus::Merged.transition_to(PatchStatus::UnderReview).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::patch_models::ValidationStatus;
+use crate::Result;
+
+/// The repository a patch is applied to, at the commit its finding was
+/// reported on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryCheckout {
+    pub repository_id: Uuid,
+    /// `owner/repo` on GitHub.
+    pub full_name: String,
+    /// The repository's default branch when `None`.
+    pub commit_sha: Option<String>,
+}
+
+#[async_trait]
+pub trait PatchVerifier: Send + Sync {
+    /// Apply `patch_diff` to a checkout of the repository, then build it
+    /// and run its tests. A patch that does not apply, build or pass its
+    /// tests is reported in the returned status; errors are left for
+    /// failures of the verifier itself.
+    async fn verify(
+        &self,
+        checkout: &RepositoryCheckout,
+        patch_diff: &str,
+    ) -> Result<ValidationStatus>;
+}
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::patch_verifier_trait::RepositoryCheckout;
+use crate::Result;
+
+/// A pull request to open for a patch: a new branch with one commit
+/// applying the diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestDraft {
+    pub branch: String,
+    /// Commit the branch starts from: the one the patch was verified on.
+    pub base_commit: Option<String>,
+    pub title: String,
+    pub body: String,
+    pub commit_message: String,
+    pub diff: String,
+}
+
+/// A pull request opened for a patch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmittedPullRequest {
+    pub number: i32,
+    pub url: String,
+    /// `open`, `closed` or `merged`.
+    pub state: String,
+    pub branch: String,
+}
+
+#[async_trait]
+pub trait PullRequestPublisher: Send + Sync {
+    /// Push the draft's branch to the repository and open a pull request
+    /// from it against the default branch.
+    async fn open_pull_request(
+        &self,
+        checkout: &RepositoryCheckout,
+        draft: &PullRequestDraft,
+    ) -> Result<SubmittedPullRequest>;
+}
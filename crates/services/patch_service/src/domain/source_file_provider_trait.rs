@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use super::patch_verifier_trait::RepositoryCheckout;
+use crate::Result;
+
+#[async_trait]
+pub trait SourceFileProvider: Send + Sync {
+    /// Contents of `paths` in the checkout's repository at its commit, by
+    /// path. Paths that do not exist there are left out.
+    async fn files_at(
+        &self,
+        checkout: &RepositoryCheckout,
+        paths: &[String],
+    ) -> Result<HashMap<String, String>>;
+}
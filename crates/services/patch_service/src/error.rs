@@ -5,20 +5,46 @@ use std::fmt;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "patch")]
 pub enum Error {
+    #[taxonomy(kind = NotFound, expose)]
     PatchNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
     VulnerabilityNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
     DeveloperNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
+    PatchGroupNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
+    PatchCandidateNotFound(String),
+    #[taxonomy(kind = Validation, expose)]
     InvalidPatchState(String),
+    #[taxonomy(kind = Validation, expose)]
     InvalidVote(String),
+    #[taxonomy(kind = Conflict, expose)]
     AlreadyVoted(String),
+    #[taxonomy(kind = PermissionDenied, expose)]
     InsufficientReputation(String),
+    #[taxonomy(kind = PermissionDenied, expose)]
+    NotAReviewer(String),
+    #[taxonomy(kind = Upstream)]
     PatchGenerationFailed(String),
+    #[taxonomy(kind = Validation, expose)]
     ValidationFailed(String),
+    #[taxonomy(kind = Conflict, expose)]
+    PatchNotSubmittable(String),
+    #[taxonomy(kind = Internal)]
+    VerificationFailed(String),
+    #[taxonomy(kind = Unavailable, expose)]
+    VerifierNotConfigured,
+    #[taxonomy(kind = Upstream)]
     GithubIntegrationError(String),
+    #[taxonomy(kind = Internal)]
     DatabaseError(String),
+    #[taxonomy(kind = Internal)]
     ServiceError(String),
+    #[taxonomy(kind = Unauthenticated)]
     CtxError(String),
 }
 
@@ -40,12 +66,20 @@ impl fmt::Display for Error {
             Error::PatchNotFound(id) => write!(f, "Patch not found: {}", id),
             Error::VulnerabilityNotFound(id) => write!(f, "Vulnerability not found: {}", id),
             Error::DeveloperNotFound(id) => write!(f, "Developer not found: {}", id),
+            Error::PatchGroupNotFound(id) => write!(f, "Patch group not found: {}", id),
+            Error::PatchCandidateNotFound(id) => write!(f, "Patch candidate not found: {}", id),
             Error::InvalidPatchState(msg) => write!(f, "Invalid patch state: {}", msg),
             Error::InvalidVote(msg) => write!(f, "Invalid vote: {}", msg),
             Error::AlreadyVoted(msg) => write!(f, "Already voted: {}", msg),
             Error::InsufficientReputation(msg) => write!(f, "Insufficient reputation: {}", msg),
+            Error::NotAReviewer(reviewer) => {
+                write!(f, "{} is not assigned to review the patch", reviewer)
+            }
             Error::PatchGenerationFailed(msg) => write!(f, "Patch generation failed: {}", msg),
             Error::ValidationFailed(msg) => write!(f, "Validation failed: {}", msg),
+            Error::PatchNotSubmittable(msg) => write!(f, "Patch cannot be submitted: {}", msg),
+            Error::VerificationFailed(msg) => write!(f, "Patch verification failed: {}", msg),
+            Error::VerifierNotConfigured => write!(f, "No sandboxed toolchain is configured to verify patches"),
             Error::GithubIntegrationError(msg) => write!(f, "GitHub integration error: {}", msg),
             Error::DatabaseError(msg) => write!(f, "Database error: {}", msg),
             Error::ServiceError(msg) => write!(f, "Service error: {}", msg),
@@ -59,13 +93,18 @@ impl std::error::Error for Error {}
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
         let status_code = match &self {
-            Error::PatchNotFound(_) | Error::VulnerabilityNotFound(_) | Error::DeveloperNotFound(_) => {
-                StatusCode::NOT_FOUND
-            }
-            Error::InvalidPatchState(_) | Error::InvalidVote(_) | Error::AlreadyVoted(_) => {
-                StatusCode::BAD_REQUEST
-            }
-            Error::InsufficientReputation(_) => StatusCode::FORBIDDEN,
+            Error::PatchNotFound(_)
+            | Error::VulnerabilityNotFound(_)
+            | Error::DeveloperNotFound(_)
+            | Error::PatchGroupNotFound(_)
+            | Error::PatchCandidateNotFound(_) => StatusCode::NOT_FOUND,
+            Error::InvalidPatchState(_)
+            | Error::InvalidVote(_)
+            | Error::AlreadyVoted(_)
+            | Error::ValidationFailed(_) => StatusCode::BAD_REQUEST,
+            Error::InsufficientReputation(_) | Error::NotAReviewer(_) => StatusCode::FORBIDDEN,
+            Error::PatchNotSubmittable(_) => StatusCode::CONFLICT,
+            Error::VerifierNotConfigured => StatusCode::SERVICE_UNAVAILABLE,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
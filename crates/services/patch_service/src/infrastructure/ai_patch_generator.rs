@@ -1,11 +1,13 @@
 use async_trait::async_trait;
 use chrono::Utc;
 use rust_decimal_macros::dec;
+use std::collections::BTreeSet;
 use uuid::Uuid;
 
 use crate::domain::{
-    FilePreview, GeneratedPatch, GenerationMetadata, GenerationStrategy, PatchGenerationRequest,
-    PatchGenerator, PreviewResult, ValidationResult,
+    combine_diffs, parse_unified_diff, FilePreview, GeneratedPatch, GeneratedPatchSet,
+    GenerationMetadata, GenerationStrategy, PatchGenerationRequest, PatchGenerator,
+    PatchSetGenerationRequest, PreviewResult, ValidationResult,
 };
 use crate::Result;
 
@@ -40,6 +42,21 @@ impl PatchGenerator for AIPatchGenerator {
      
      db.execute(query)?
  }}
+"#,
+                    request.context.file_path,
+                    request.context.file_path
+                )
+            }
+            // A refactor guards the whole module rather than the one line
+            _ if request.generation_strategy == GenerationStrategy::Aggressive => {
+                format!(
+                    r#"--- a/{}
++++ b/{}
+@@ -1,1 +1,3 @@
+-// Vulnerable code
++// Fixed code
++// Inputs are validated once at the module boundary,
++// so no caller reaches the vulnerable path
 "#,
                     request.context.file_path,
                     request.context.file_path
@@ -58,6 +75,11 @@ impl PatchGenerator for AIPatchGenerator {
                 )
             }
         };
+        let confidence_score = match request.generation_strategy {
+            GenerationStrategy::Conservative => dec!(0.90),
+            GenerationStrategy::Balanced => dec!(0.85),
+            GenerationStrategy::Aggressive => dec!(0.75),
+        };
 
         let generation_time_ms = (Utc::now() - start_time).num_milliseconds();
 
@@ -69,13 +91,88 @@ impl PatchGenerator for AIPatchGenerator {
             ),
             patch_diff,
             files_changed: vec![request.context.file_path.clone()],
-            confidence_score: dec!(0.85),
+            confidence_score,
             generation_metadata: GenerationMetadata {
                 model_version: "gpt-4-security-v1".to_string(),
                 generation_time_ms,
                 tokens_used: 150,
                 strategy_used: request.generation_strategy.clone(),
             },
+            exposure_estimate: None,
+        })
+    }
+
+    async fn generate_candidates(
+        &self,
+        request: &PatchGenerationRequest,
+        count: usize,
+    ) -> Result<Vec<GeneratedPatch>> {
+        let strategies = std::iter::once(request.generation_strategy.clone()).chain(
+            GenerationStrategy::ALL
+                .into_iter()
+                .filter(|strategy| *strategy != request.generation_strategy),
+        );
+
+        let mut candidates = Vec::new();
+        for strategy in strategies.take(count) {
+            let request =
+                PatchGenerationRequest { generation_strategy: strategy, ..request.clone() };
+            candidates.push(self.generate_patch(&request).await?);
+        }
+        Ok(candidates)
+    }
+
+    async fn generate_patch_set(
+        &self,
+        request: &PatchSetGenerationRequest,
+    ) -> Result<GeneratedPatchSet> {
+        let mut diffs = Vec::new();
+        let mut left_out = Vec::new();
+        let mut tokens_used = 0;
+        let mut model_version = String::new();
+        for finding in &request.findings {
+            let patch = self
+                .generate_patch(&PatchGenerationRequest {
+                    vulnerability_id: finding.vulnerability_id,
+                    context: finding.context.clone(),
+                    generation_strategy: request.generation_strategy.clone(),
+                })
+                .await?;
+            tokens_used += patch.generation_metadata.tokens_used;
+            model_version = patch.generation_metadata.model_version;
+            match parse_unified_diff(&patch.patch_diff) {
+                Ok(files) => diffs.extend(files),
+                Err(e) => left_out.push(format!("{}: {}", finding.vulnerability_id, e)),
+            }
+        }
+
+        // Findings in the same file share one sub-patch
+        let (files, conflicts) = combine_diffs(diffs);
+        left_out.extend(conflicts);
+        let vulnerability_types: BTreeSet<&str> = request
+            .findings
+            .iter()
+            .map(|finding| finding.context.vulnerability_type.as_str())
+            .collect();
+        let vulnerability_types = vulnerability_types.into_iter().collect::<Vec<_>>().join(", ");
+
+        Ok(GeneratedPatchSet {
+            title: format!(
+                "Fix {} {} finding(s) across {} file(s)",
+                request.findings.len(),
+                vulnerability_types,
+                files.len()
+            ),
+            description: format!(
+                "This patch set addresses {} finding(s) of {} in one coordinated change, \
+                 with one sub-patch per file.",
+                request.findings.len(),
+                vulnerability_types
+            ),
+            files,
+            left_out,
+            model_version,
+            tokens_used,
         })
     }
 
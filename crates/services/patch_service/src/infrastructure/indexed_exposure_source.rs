@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use jd_core::AppState;
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::{ExposureSource, OnChainExposure};
+use crate::Result;
+
+#[derive(sqlx::FromRow)]
+struct PackageMetricsRow {
+    package_id: String,
+    network: String,
+    shared_object_tvl_mist: Decimal,
+    tx_count_24h: i64,
+    tx_volume_24h_mist: Decimal,
+    indexed_at: OffsetDateTime,
+}
+
+/// Reads the package metrics maintained by the on-chain indexer.
+pub struct IndexedExposureSource {
+    state: AppState,
+}
+
+impl IndexedExposureSource {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl ExposureSource for IndexedExposureSource {
+    async fn repository_exposure(&self, repository_id: Uuid) -> Result<Vec<OnChainExposure>> {
+        // Deployments the indexer has not reached yet are left out.
+        let rows = sqlx::query_as::<_, PackageMetricsRow>(
+            "SELECT m.package_id, m.network, m.shared_object_tvl_mist, m.tx_count_24h, \
+                    m.tx_volume_24h_mist, m.indexed_at \
+             FROM repository_contract_deployments d \
+             JOIN onchain_package_metrics m \
+               ON m.package_id = d.package_id AND m.network = d.network \
+             WHERE d.repository_id = $1 \
+             ORDER BY m.shared_object_tvl_mist DESC",
+        )
+        .bind(repository_id)
+        .fetch_all(self.state.mm().dbx().db())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| OnChainExposure {
+                package_id: row.package_id,
+                network: row.network,
+                shared_object_tvl_mist: row.shared_object_tvl_mist,
+                tx_count_24h: row.tx_count_24h,
+                tx_volume_24h_mist: row.tx_volume_24h_mist,
+                indexed_at: row.indexed_at,
+            })
+            .collect())
+    }
+}
@@ -1,5 +1,10 @@
 pub mod patch_repository_impl;
 pub mod ai_patch_generator;
+pub mod indexed_exposure_source;
+pub mod sandbox_patch_verifier;
+pub mod patch_metrics;
 
 pub use patch_repository_impl::PatchRepositoryImpl;
-pub use ai_patch_generator::AIPatchGenerator;
\ No newline at end of file
+pub use ai_patch_generator::AIPatchGenerator;
+pub use indexed_exposure_source::IndexedExposureSource;
+pub use sandbox_patch_verifier::{PatchVerifierSettings, SandboxPatchVerifier};
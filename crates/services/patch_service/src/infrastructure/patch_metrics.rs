@@ -0,0 +1,21 @@
+use jd_utils::metrics::{self, HistogramVec, IntCounterVec, CALL_BUCKETS};
+use std::sync::LazyLock;
+
+/// Time to generate a patch, by `single` or `bulk` and how it ended.
+pub static PATCH_GENERATION_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    metrics::histogram(
+        "patch_generation_duration_seconds",
+        "Time to generate a patch or patch set",
+        &["kind", "outcome"],
+        CALL_BUCKETS,
+    )
+});
+
+/// Failed generations, by `single` or `bulk` and reason.
+pub static PATCH_GENERATION_FAILURES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    metrics::counter(
+        "patch_generation_failures_total",
+        "Failed patch generations",
+        &["kind", "reason"],
+    )
+});
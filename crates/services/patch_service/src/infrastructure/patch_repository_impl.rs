@@ -3,16 +3,148 @@ use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use uuid::Uuid;
 use jd_core::{AppState, base};
+use jd_messaging::{events::{PatchApproved, PatchReviewRequested}, outbox};
 
 use crate::{
     PatchDmc,
     domain::{
-        PatchFilter, PatchLeaderboard, PatchProposal, PatchRepository, PatchStatistics, PatchStatus,
-        ValidationStatus, Vote, PatchProposalDb, PatchProposalForCreate, PatchProposalForUpdate, PatchProposalFilter,
+        BulkPatchFilter, ExposureEstimate, GenerationStrategy, PatchCandidate, PatchFilter,
+        PatchGroup,
+        PatchGroupFile, PatchLeaderboard, PatchProposal, PatchRepository, PatchReview,
+        PatchReviewer, PatchStatistics, PatchStatus, RepositoryCheckout, ReviewDecision,
+        SubmittedPullRequest, ValidationStatus, Vote, VulnerabilityContext,
+        VulnerabilityTarget, PatchProposalDb, PatchProposalForCreate, PatchProposalForUpdate, PatchProposalFilter,
     },
     Error, Result,
 };
 
+#[derive(sqlx::FromRow)]
+struct VulnerabilityRow {
+    id: Uuid,
+    repository_id: Uuid,
+    vulnerability_type: String,
+    severity: String,
+    file_path: String,
+    line_number: Option<i32>,
+    code_snippet: Option<String>,
+    description: String,
+}
+
+impl VulnerabilityRow {
+    fn into_target(self) -> VulnerabilityTarget {
+        VulnerabilityTarget {
+            vulnerability_id: self.id,
+            repository_id: self.repository_id,
+            context: VulnerabilityContext {
+                vulnerability_type: self.vulnerability_type,
+                severity: self.severity,
+                file_path: self.file_path,
+                line_number: self.line_number,
+                code_snippet: self.code_snippet.unwrap_or_default(),
+                description: self.description,
+            },
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PatchGroupRow {
+    id: Uuid,
+    repository_id: Uuid,
+    vulnerability_type: Option<String>,
+    severity: Option<String>,
+    cwe_id: Option<String>,
+    strategy: String,
+    title: String,
+    description: String,
+    model_version: String,
+    vulnerability_ids: Vec<Uuid>,
+    left_out: Vec<String>,
+    created_at: time::OffsetDateTime,
+}
+
+#[derive(sqlx::FromRow)]
+struct PatchGroupFileRow {
+    file_path: String,
+    diff_content: String,
+    vulnerability_ids: Vec<Uuid>,
+    additions: i32,
+    deletions: i32,
+}
+
+#[derive(sqlx::FromRow)]
+struct CandidateRow {
+    id: Uuid,
+    vulnerability_id: Uuid,
+    rank: i32,
+    strategy: String,
+    patch: serde_json::Value,
+    validation: serde_json::Value,
+    score: f64,
+    selected_by: Option<String>,
+    selected_at: Option<time::OffsetDateTime>,
+    created_at: time::OffsetDateTime,
+}
+
+impl TryFrom<CandidateRow> for PatchCandidate {
+    type Error = Error;
+
+    fn try_from(row: CandidateRow) -> Result<Self> {
+        Ok(PatchCandidate {
+            id: row.id,
+            vulnerability_id: row.vulnerability_id,
+            rank: row.rank,
+            strategy: GenerationStrategy::from(row.strategy),
+            patch: serde_json::from_value(row.patch)?,
+            validation: serde_json::from_value(row.validation)?,
+            score: row.score,
+            selected_by: row.selected_by,
+            selected_at: row.selected_at,
+            created_at: row.created_at,
+        })
+    }
+}
+
+const CANDIDATE_COLUMNS: &str = "id, vulnerability_id, rank, strategy, patch, validation, score, \
+                                 selected_by, selected_at, created_at";
+
+#[derive(sqlx::FromRow)]
+struct CheckoutRow {
+    repository_id: Uuid,
+    full_name: String,
+    commit_sha: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ReviewerRow {
+    reviewer: String,
+    assigned_by: String,
+    assigned_at: time::OffsetDateTime,
+}
+
+#[derive(sqlx::FromRow)]
+struct ReviewRow {
+    id: Uuid,
+    patch_id: Uuid,
+    reviewer: String,
+    decision: String,
+    comment: Option<String>,
+    created_at: time::OffsetDateTime,
+}
+
+impl From<ReviewRow> for PatchReview {
+    fn from(row: ReviewRow) -> Self {
+        PatchReview {
+            id: row.id,
+            patch_id: row.patch_id,
+            reviewer: row.reviewer,
+            decision: ReviewDecision::from(row.decision),
+            comment: row.comment,
+            created_at: row.created_at,
+        }
+    }
+}
+
 pub struct PatchRepositoryImpl {
     state: AppState,
 }
@@ -157,22 +289,26 @@ impl PatchRepository for PatchRepositoryImpl {
         Ok(())
     }
 
+    /// Approving a patch records a `patch.approved` event with the change.
     async fn update_status(&self, id: Uuid, status: PatchStatus) -> Result<()> {
-        let update_req = PatchProposalForUpdate {
-            title: None,
-            description: None,
-            patch_diff: None,
-            status: Some(status.to_string()),
-            approval_score: None,
-            rejection_score: None,
-            total_votes: None,
-            applied_at: None,
-            pr_url: None,
+        let mut tx = self.state.mm().dbx().db().begin().await?;
+        let updated: Option<(Uuid, Uuid, String)> = sqlx::query_as(
+            "UPDATE patch_proposals SET status = $2::patch_status_enum, mtime = NOW() \
+             WHERE id = $1 RETURNING vulnerability_id, repository_id, title",
+        )
+        .bind(id)
+        .bind(status.to_string())
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some((vulnerability_id, repository_id, title)) = updated else {
+            return Err(Error::PatchNotFound(id.to_string()));
         };
-        
-        base::rest::update::<PatchDmc, _>(&self.state.mm(), id, update_req)
-            .await
-            .map_err(|e| Error::DatabaseError(e.to_string()))?;
+        if status == PatchStatus::Approved {
+            let event = PatchApproved { patch_id: id, vulnerability_id, repository_id, title };
+            outbox::record(&mut *tx, &event).await?;
+        }
+        tx.commit().await?;
 
         Ok(())
     }
@@ -197,13 +333,181 @@ impl PatchRepository for PatchRepositoryImpl {
         Ok(())
     }
 
+    async fn record_pull_request(
+        &self,
+        id: Uuid,
+        pull_request: &SubmittedPullRequest,
+        submitted_by: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE patch_proposals \
+             SET status = 'submitted', github_pr_number = $2, pr_url = $3, pr_state = $4, \
+                 pr_branch = $5, pr_submitted_by = $6, pr_state_updated_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(pull_request.number)
+        .bind(&pull_request.url)
+        .bind(&pull_request.state)
+        .bind(&pull_request.branch)
+        .bind(submitted_by)
+        .execute(self.state.mm().dbx().db())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_reviewers(&self, patch_id: Uuid) -> Result<Vec<PatchReviewer>> {
+        let rows = sqlx::query_as::<_, ReviewerRow>(
+            "SELECT reviewer, assigned_by, assigned_at FROM patch_reviewers \
+             WHERE patch_id = $1 ORDER BY assigned_at, reviewer",
+        )
+        .bind(patch_id)
+        .fetch_all(self.state.mm().dbx().db())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PatchReviewer {
+                reviewer: row.reviewer,
+                assigned_by: row.assigned_by,
+                assigned_at: row.assigned_at,
+            })
+            .collect())
+    }
+
+    async fn assign_reviewers(
+        &self,
+        patch_id: Uuid,
+        reviewers: &[String],
+        assigned_by: &str,
+    ) -> Result<()> {
+        let mut tx = self.state.mm().dbx().db().begin().await?;
+        let assigned: Vec<String> = sqlx::query_scalar(
+            "INSERT INTO patch_reviewers (patch_id, reviewer, assigned_by) \
+             SELECT $1, reviewer, $3 FROM UNNEST($2::VARCHAR[]) AS reviewer \
+             ON CONFLICT (patch_id, reviewer) DO NOTHING \
+             RETURNING reviewer",
+        )
+        .bind(patch_id)
+        .bind(reviewers)
+        .bind(assigned_by)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if !assigned.is_empty() {
+            let (repository_id, title): (Uuid, String) =
+                sqlx::query_as("SELECT repository_id, title FROM patch_proposals WHERE id = $1")
+                    .bind(patch_id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+            let event = PatchReviewRequested {
+                patch_id,
+                repository_id,
+                title,
+                reviewers: assigned,
+                assigned_by: assigned_by.to_string(),
+            };
+            outbox::record(&mut *tx, &event).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn remove_reviewer(&self, patch_id: Uuid, reviewer: &str) -> Result<bool> {
+        let removed =
+            sqlx::query("DELETE FROM patch_reviewers WHERE patch_id = $1 AND reviewer = $2")
+                .bind(patch_id)
+                .bind(reviewer)
+                .execute(self.state.mm().dbx().db())
+                .await?;
+
+        Ok(removed.rows_affected() > 0)
+    }
+
+    async fn set_required_approvals(&self, patch_id: Uuid, required_approvals: i32) -> Result<()> {
+        sqlx::query("UPDATE patch_proposals SET required_approvals = $2 WHERE id = $1")
+            .bind(patch_id)
+            .bind(required_approvals)
+            .execute(self.state.mm().dbx().db())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn add_review(
+        &self,
+        patch_id: Uuid,
+        reviewer: &str,
+        decision: ReviewDecision,
+        comment: Option<&str>,
+    ) -> Result<PatchReview> {
+        let row = sqlx::query_as::<_, ReviewRow>(
+            "INSERT INTO patch_reviews (patch_id, reviewer, decision, comment) \
+             VALUES ($1, $2, $3, $4) \
+             RETURNING id, patch_id, reviewer, decision, comment, created_at",
+        )
+        .bind(patch_id)
+        .bind(reviewer)
+        .bind(decision.as_str())
+        .bind(comment)
+        .fetch_one(self.state.mm().dbx().db())
+        .await?;
+
+        Ok(row.into())
+    }
+
+    async fn get_reviews(&self, patch_id: Uuid) -> Result<Vec<PatchReview>> {
+        let rows = sqlx::query_as::<_, ReviewRow>(
+            "SELECT id, patch_id, reviewer, decision, comment, created_at FROM patch_reviews \
+             WHERE patch_id = $1 ORDER BY created_at, id",
+        )
+        .bind(patch_id)
+        .fetch_all(self.state.mm().dbx().db())
+        .await?;
+
+        Ok(rows.into_iter().map(PatchReview::from).collect())
+    }
+
     async fn update_validation_status(
         &self,
         id: Uuid,
         validation: &ValidationStatus,
     ) -> Result<()> {
-        // TODO: Implement validation_status field in PatchProposalForUpdate
-        // For now, return success to prevent compilation errors
+        let updated = sqlx::query(
+            "UPDATE patch_proposals \
+             SET build_succeeded = $2, tests_passed = $3, security_scan_passed = $4, \
+                 validation_message = $5, validated_commit_sha = $6, validated_at = $7 \
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(validation.build_succeeded)
+        .bind(validation.tests_passed)
+        .bind(validation.security_scan_passed)
+        .bind(&validation.validation_message)
+        .bind(&validation.commit_sha)
+        .bind(validation.validated_at)
+        .execute(self.state.mm().dbx().db())
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(Error::PatchNotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn clear_validation_status(&self, id: Uuid) -> Result<()> {
+        sqlx::query(
+            "UPDATE patch_proposals \
+             SET build_succeeded = NULL, tests_passed = NULL, security_scan_passed = NULL, \
+                 validation_message = NULL, validated_commit_sha = NULL, validated_at = NULL \
+             WHERE id = $1",
+        )
+        .bind(id)
+        .execute(self.state.mm().dbx().db())
+        .await?;
+
         Ok(())
     }
 
@@ -263,4 +567,251 @@ impl PatchRepository for PatchRepositoryImpl {
         // For now, return false (TODO: Implement filtering for approved patches)
         Ok(false)
     }
+
+    async fn get_vulnerability_target(
+        &self,
+        vulnerability_id: Uuid,
+    ) -> Result<VulnerabilityTarget> {
+        let row = sqlx::query_as::<_, VulnerabilityRow>(
+            "SELECT id, repository_id, vulnerability_type::TEXT AS vulnerability_type, \
+                    severity::TEXT AS severity, file_path, \
+                    line_number, code_snippet, description \
+             FROM security_vulnerabilities WHERE id = $1",
+        )
+        .bind(vulnerability_id)
+        .fetch_optional(self.state.mm().dbx().db())
+        .await?
+        .ok_or_else(|| Error::VulnerabilityNotFound(vulnerability_id.to_string()))?;
+
+        Ok(row.into_target())
+    }
+
+    async fn find_vulnerability_targets(
+        &self,
+        repository_id: Uuid,
+        filter: &BulkPatchFilter,
+        limit: i64,
+    ) -> Result<Vec<VulnerabilityTarget>> {
+        let rows = sqlx::query_as::<_, VulnerabilityRow>(
+            "SELECT id, repository_id, vulnerability_type::TEXT AS vulnerability_type, \
+                    severity::TEXT AS severity, file_path, \
+                    line_number, code_snippet, description \
+             FROM security_vulnerabilities \
+             WHERE repository_id = $1 AND kind = 'code' \
+               AND fixed_at IS NULL AND resolved_at IS NULL AND NOT is_false_positive \
+               AND ($2::TEXT IS NULL OR vulnerability_type::TEXT = $2) \
+               AND ($3::TEXT IS NULL OR severity::TEXT = $3) \
+               AND ($4::TEXT IS NULL OR cwe_id = $4) \
+             ORDER BY severity, file_path, line_number NULLS LAST \
+             LIMIT $5",
+        )
+        .bind(repository_id)
+        .bind(&filter.vulnerability_type)
+        .bind(&filter.severity)
+        .bind(&filter.cwe_id)
+        .bind(limit)
+        .fetch_all(self.state.mm().dbx().db())
+        .await?;
+
+        Ok(rows.into_iter().map(VulnerabilityRow::into_target).collect())
+    }
+
+    async fn create_patch_group(&self, group: &PatchGroup) -> Result<()> {
+        let mut tx = self.state.mm().dbx().db().begin().await?;
+        sqlx::query(
+            "INSERT INTO patch_groups \
+                 (id, repository_id, vulnerability_type, severity, cwe_id, strategy, title, \
+                  description, model_version, vulnerability_ids, left_out, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)",
+        )
+        .bind(group.id)
+        .bind(group.repository_id)
+        .bind(&group.filter.vulnerability_type)
+        .bind(&group.filter.severity)
+        .bind(&group.filter.cwe_id)
+        .bind(group.strategy.as_str())
+        .bind(&group.title)
+        .bind(&group.description)
+        .bind(&group.model_version)
+        .bind(&group.vulnerability_ids)
+        .bind(&group.left_out)
+        .bind(group.created_at)
+        .execute(&mut *tx)
+        .await?;
+
+        for file in &group.files {
+            sqlx::query(
+                "INSERT INTO patch_group_files \
+                     (group_id, file_path, diff_content, vulnerability_ids, additions, deletions) \
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(group.id)
+            .bind(&file.file_path)
+            .bind(&file.patch_diff)
+            .bind(&file.vulnerability_ids)
+            .bind(file.additions)
+            .bind(file.deletions)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_patch_group(&self, id: Uuid) -> Result<PatchGroup> {
+        let group = sqlx::query_as::<_, PatchGroupRow>(
+            "SELECT id, repository_id, vulnerability_type, severity, cwe_id, strategy, title, \
+                    description, model_version, vulnerability_ids, left_out, created_at \
+             FROM patch_groups WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(self.state.mm().dbx().db())
+        .await?
+        .ok_or_else(|| Error::PatchGroupNotFound(id.to_string()))?;
+
+        let files = sqlx::query_as::<_, PatchGroupFileRow>(
+            "SELECT file_path, diff_content, vulnerability_ids, additions, deletions \
+             FROM patch_group_files WHERE group_id = $1 ORDER BY file_path",
+        )
+        .bind(id)
+        .fetch_all(self.state.mm().dbx().db())
+        .await?;
+
+        Ok(PatchGroup {
+            id: group.id,
+            repository_id: group.repository_id,
+            filter: BulkPatchFilter {
+                vulnerability_type: group.vulnerability_type,
+                severity: group.severity,
+                cwe_id: group.cwe_id,
+            },
+            strategy: GenerationStrategy::from(group.strategy),
+            title: group.title,
+            description: group.description,
+            model_version: group.model_version,
+            vulnerability_ids: group.vulnerability_ids,
+            files: files
+                .into_iter()
+                .map(|file| PatchGroupFile {
+                    file_path: file.file_path,
+                    patch_diff: file.diff_content,
+                    vulnerability_ids: file.vulnerability_ids,
+                    additions: file.additions,
+                    deletions: file.deletions,
+                })
+                .collect(),
+            left_out: group.left_out,
+            created_at: group.created_at,
+        })
+    }
+
+    async fn replace_patch_candidates(
+        &self,
+        vulnerability_id: Uuid,
+        candidates: &[PatchCandidate],
+    ) -> Result<()> {
+        let mut tx = self.state.mm().dbx().db().begin().await?;
+        sqlx::query("DELETE FROM patch_candidates WHERE vulnerability_id = $1")
+            .bind(vulnerability_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for candidate in candidates {
+            sqlx::query(
+                "INSERT INTO patch_candidates \
+                     (id, vulnerability_id, rank, strategy, patch, validation, score, created_at) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            )
+            .bind(candidate.id)
+            .bind(vulnerability_id)
+            .bind(candidate.rank)
+            .bind(candidate.strategy.as_str())
+            .bind(serde_json::to_value(&candidate.patch)?)
+            .bind(serde_json::to_value(&candidate.validation)?)
+            .bind(candidate.score)
+            .bind(candidate.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_patch_candidates(&self, vulnerability_id: Uuid) -> Result<Vec<PatchCandidate>> {
+        let rows = sqlx::query_as::<_, CandidateRow>(&format!(
+            "SELECT {} FROM patch_candidates WHERE vulnerability_id = $1 ORDER BY rank",
+            CANDIDATE_COLUMNS
+        ))
+        .bind(vulnerability_id)
+        .fetch_all(self.state.mm().dbx().db())
+        .await?;
+
+        rows.into_iter().map(PatchCandidate::try_from).collect()
+    }
+
+    async fn select_patch_candidate(&self, id: Uuid, selected_by: &str) -> Result<PatchCandidate> {
+        let mut tx = self.state.mm().dbx().db().begin().await?;
+        sqlx::query(
+            "UPDATE patch_candidates SET selected_by = NULL, selected_at = NULL \
+             WHERE id <> $1 AND vulnerability_id = \
+                   (SELECT vulnerability_id FROM patch_candidates WHERE id = $1)",
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        let row = sqlx::query_as::<_, CandidateRow>(&format!(
+            "UPDATE patch_candidates SET selected_by = $2, selected_at = NOW() \
+             WHERE id = $1 RETURNING {}",
+            CANDIDATE_COLUMNS
+        ))
+        .bind(id)
+        .bind(selected_by)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| Error::PatchCandidateNotFound(id.to_string()))?;
+
+        tx.commit().await?;
+        PatchCandidate::try_from(row)
+    }
+
+    async fn get_repository_checkout(&self, vulnerability_id: Uuid) -> Result<RepositoryCheckout> {
+        let row = sqlx::query_as::<_, CheckoutRow>(
+            "SELECT r.id AS repository_id, r.full_name, a.commit_sha \
+             FROM security_vulnerabilities v \
+             JOIN github_repositories r ON r.id = v.repository_id \
+             LEFT JOIN code_analysis_results a ON a.id = v.analysis_result_id \
+             WHERE v.id = $1",
+        )
+        .bind(vulnerability_id)
+        .fetch_optional(self.state.mm().dbx().db())
+        .await?
+        .ok_or_else(|| Error::VulnerabilityNotFound(vulnerability_id.to_string()))?;
+
+        Ok(RepositoryCheckout {
+            repository_id: row.repository_id,
+            full_name: row.full_name,
+            commit_sha: row.commit_sha,
+        })
+    }
+
+    async fn save_exposure_estimate(
+        &self,
+        vulnerability_id: Uuid,
+        estimate: &ExposureEstimate,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE security_vulnerabilities \
+             SET exposure_estimate = $2, estimated_exposure_sui = $3 \
+             WHERE id = $1",
+        )
+        .bind(vulnerability_id)
+        .bind(serde_json::to_value(estimate)?)
+        .bind(estimate.estimated_exposure_sui)
+        .execute(self.state.mm().dbx().db())
+        .await?;
+
+        Ok(())
+    }
 }
\ No newline at end of file
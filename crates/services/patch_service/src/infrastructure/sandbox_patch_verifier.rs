@@ -0,0 +1,345 @@
+use async_trait::async_trait;
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use time::OffsetDateTime;
+use tokio::process::Command;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::domain::{PatchVerifier, RepositoryCheckout, ValidationStatus};
+use crate::{Error, Result};
+
+/// Command output kept in a failed verification's message.
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+/// How patches are checked out, built and tested.
+#[derive(Debug, Clone)]
+pub struct PatchVerifierSettings {
+    /// The `sui` binary, run as `<command> move build` and `move test` for
+    /// Move packages; Move packages are not built when `None`.
+    pub move_command: Option<String>,
+    /// The `forge` binary for Foundry projects; Solidity is not built when
+    /// `None`.
+    pub forge_command: Option<String>,
+    /// Command builds and tests run under, e.g. `bwrap --unshare-all ...`
+    /// or `firejail --net=none`. Patches carry untrusted code, so nothing is
+    /// built or tested when it is empty. The checkout itself runs outside
+    /// the sandbox.
+    pub sandbox: Vec<String>,
+    /// Where the Move toolchain caches fetched dependencies between runs.
+    pub move_home: Option<PathBuf>,
+    /// Budget for checking out, building and testing one patch.
+    pub timeout: Duration,
+}
+
+impl Default for PatchVerifierSettings {
+    fn default() -> Self {
+        Self {
+            move_command: None,
+            forge_command: None,
+            sandbox: Vec::new(),
+            move_home: None,
+            timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+impl PatchVerifierSettings {
+    /// Settings from `MOVE_COMPILER_COMMAND`, `FORGE_COMMAND` and
+    /// `PATCH_VERIFIER_*`, or `None` unless a toolchain and a sandbox are
+    /// named.
+    pub fn from_env() -> Option<Self> {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+        let defaults = Self::default();
+        let settings = Self {
+            move_command: env("MOVE_COMPILER_COMMAND"),
+            forge_command: env("FORGE_COMMAND"),
+            sandbox: env("PATCH_VERIFIER_SANDBOX")
+                .map(|sandbox| sandbox.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            move_home: env("MOVE_HOME").map(PathBuf::from),
+            timeout: env("PATCH_VERIFIER_TIMEOUT_SECS")
+                .and_then(|secs| secs.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.timeout),
+        };
+        if settings.move_command.is_none() && settings.forge_command.is_none() {
+            return None;
+        }
+        if settings.sandbox.is_empty() {
+            warn!("Patch verification is disabled: PATCH_VERIFIER_SANDBOX is not set");
+            return None;
+        }
+        Some(settings)
+    }
+}
+
+/// A project the patch touches and the toolchain that builds it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Project {
+    /// A Move package, by the directory holding its `Move.toml`.
+    Move(PathBuf),
+    /// A Foundry project, by the directory holding its `foundry.toml`.
+    Foundry(PathBuf),
+}
+
+/// Applies patches to a shallow clone of their repository in a scratch
+/// directory, then builds and tests every Move package and Foundry project
+/// the patch touches. Builds and tests run under the configured sandbox
+/// with a cleared environment. No security scan is run, so
+/// `security_scan_passed` is always false.
+pub struct SandboxPatchVerifier {
+    settings: PatchVerifierSettings,
+}
+
+impl SandboxPatchVerifier {
+    pub fn new(settings: PatchVerifierSettings) -> Self {
+        Self { settings }
+    }
+
+    /// The verifier configured by `PatchVerifierSettings::from_env`.
+    pub fn from_env() -> Option<Self> {
+        PatchVerifierSettings::from_env().map(Self::new)
+    }
+
+    /// Clone the repository at the checkout's commit into `repo`, returning
+    /// the commit checked out.
+    async fn checkout(
+        &self,
+        checkout: &RepositoryCheckout,
+        repo: &Path,
+        deadline: Instant,
+    ) -> Result<String> {
+        let url = format!("https://github.com/{}.git", checkout.full_name);
+        let reference = checkout.commit_sha.as_deref().unwrap_or("HEAD");
+        let repo_arg = repo.to_string_lossy().into_owned();
+        let steps: [&[&str]; 3] = [
+            &["init", "-q", &repo_arg],
+            &["-C", &repo_arg, "fetch", "-q", "--depth", "1", &url, reference],
+            &["-C", &repo_arg, "checkout", "-q", "FETCH_HEAD"],
+        ];
+        for args in steps {
+            self.git(repo, args, deadline).await?;
+        }
+        let head = self.git(repo, &["-C", &repo_arg, "rev-parse", "HEAD"], deadline).await?;
+        Ok(head.trim().to_string())
+    }
+
+    async fn git(&self, repo: &Path, args: &[&str], deadline: Instant) -> Result<String> {
+        let home = repo.parent().unwrap_or(repo);
+        run(command_in("git", home).args(args), deadline).await.map_err(|failure| {
+            Error::VerificationFailed(format!("git {}: {}", args.join(" "), failure))
+        })
+    }
+
+    /// Run a toolchain command for `project` under the sandbox.
+    async fn run_toolchain(
+        &self,
+        project: &Project,
+        step: &str,
+        repo: &Path,
+        deadline: Instant,
+    ) -> std::result::Result<(), String> {
+        let (toolchain, args) = match project {
+            Project::Move(dir) => {
+                let command = self
+                    .settings
+                    .move_command
+                    .as_deref()
+                    .ok_or("no Move toolchain is configured")?;
+                let dir = dir.to_string_lossy().into_owned();
+                (command, vec!["move".to_string(), step.to_string(), "--path".to_string(), dir])
+            }
+            Project::Foundry(dir) => {
+                let command = self
+                    .settings
+                    .forge_command
+                    .as_deref()
+                    .ok_or("no Foundry toolchain is configured")?;
+                let dir = dir.to_string_lossy().into_owned();
+                (command, vec![step.to_string(), "--root".to_string(), dir])
+            }
+        };
+        let (program, sandbox_args) =
+            self.settings.sandbox.split_first().ok_or("no sandbox is configured")?;
+        let mut command = command_in(program, repo);
+        command.args(sandbox_args).arg(toolchain).args(&args);
+        if let Some(move_home) = &self.settings.move_home {
+            command.env("MOVE_HOME", move_home);
+        }
+        run(&mut command, deadline).await.map(|_| ()).map_err(|failure| failure.to_string())
+    }
+}
+
+/// `program` run in `dir`, with the environment cleared but for `PATH` and
+/// with `HOME` pointing at `dir`.
+fn command_in(program: &str, dir: &Path) -> Command {
+    let mut command = Command::new(program);
+    command
+        .current_dir(dir)
+        .env_clear()
+        .env("PATH", std::env::var_os("PATH").unwrap_or_default())
+        .env("HOME", dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    command
+}
+
+/// Run `command` until `deadline`, returning its standard output.
+async fn run(
+    command: &mut Command,
+    deadline: Instant,
+) -> std::result::Result<String, CommandFailure> {
+    let output = tokio::time::timeout_at(deadline, command.output())
+        .await
+        .map_err(|_| CommandFailure::TimedOut)?
+        .map_err(|e| CommandFailure::Failed(format!("failed to run: {}", e)))?;
+    if !output.status.success() {
+        // Compilers report on stdout as often as on stderr
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        return Err(CommandFailure::Failed(tail(text.trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+enum CommandFailure {
+    TimedOut,
+    Failed(String),
+}
+
+impl std::fmt::Display for CommandFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandFailure::TimedOut => write!(f, "timed out"),
+            CommandFailure::Failed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// The end of `text`, which says why a command failed.
+fn tail(text: &str) -> String {
+    let start = text.char_indices().rev().nth(MAX_MESSAGE_CHARS - 1).map_or(0, |(i, _)| i);
+    text[start..].to_string()
+}
+
+/// Paths of the files a unified diff creates or changes.
+fn changed_files(patch_diff: &str) -> Vec<&str> {
+    patch_diff
+        .lines()
+        .filter_map(|line| line.strip_prefix("+++ "))
+        .map(|path| path.split('\t').next().unwrap_or(path).trim())
+        .filter(|path| *path != "/dev/null")
+        .map(|path| path.strip_prefix("b/").unwrap_or(path))
+        .collect()
+}
+
+/// The innermost Move package or Foundry project holding each changed file.
+async fn touched_projects(repo: &Path, files: &[&str]) -> BTreeSet<Project> {
+    let mut projects = BTreeSet::new();
+    for file in files {
+        // Paths come from the patch; never look outside the checkout
+        if !Path::new(file).components().all(|part| matches!(part, Component::Normal(_))) {
+            continue;
+        }
+        let mut dir = repo.join(file);
+        while dir.pop() && dir.starts_with(repo) {
+            if tokio::fs::try_exists(dir.join("Move.toml")).await.unwrap_or(false) {
+                projects.insert(Project::Move(dir.clone()));
+                break;
+            }
+            if tokio::fs::try_exists(dir.join("foundry.toml")).await.unwrap_or(false) {
+                projects.insert(Project::Foundry(dir.clone()));
+                break;
+            }
+        }
+    }
+    projects
+}
+
+#[async_trait]
+impl PatchVerifier for SandboxPatchVerifier {
+    async fn verify(
+        &self,
+        checkout: &RepositoryCheckout,
+        patch_diff: &str,
+    ) -> Result<ValidationStatus> {
+        let scratch = tempfile::Builder::new()
+            .prefix("patch-verify-")
+            .tempdir()
+            .map_err(|e| {
+                Error::VerificationFailed(format!("Failed to create checkout directory: {}", e))
+            })?;
+        let repo = scratch.path().join("repo");
+        let deadline = Instant::now() + self.settings.timeout;
+        let commit_sha = self.checkout(checkout, &repo, deadline).await?;
+
+        let status = |build_succeeded, tests_passed, validation_message: String| ValidationStatus {
+            tests_passed,
+            build_succeeded,
+            security_scan_passed: false,
+            validation_message,
+            commit_sha: Some(commit_sha.clone()),
+            validated_at: OffsetDateTime::now_utc(),
+        };
+
+        let diff_path = scratch.path().join("patch.diff");
+        tokio::fs::write(&diff_path, patch_diff)
+            .await
+            .map_err(|e| Error::VerificationFailed(format!("Failed to write patch: {}", e)))?;
+        let diff_arg = diff_path.to_string_lossy().into_owned();
+        let mut apply = command_in("git", &repo);
+        apply.args(["apply", "--whitespace=nowarn", &diff_arg]);
+        let applied = run(&mut apply, deadline).await;
+        if let Err(failure) = applied {
+            return Ok(status(false, false, format!("The patch does not apply: {}", failure)));
+        }
+
+        let projects = touched_projects(&repo, &changed_files(patch_diff)).await;
+        if projects.is_empty() {
+            let message = "No Move package or Foundry project holds the patched files";
+            return Ok(status(false, false, message.to_string()));
+        }
+
+        let name = |project: &Project| {
+            let (Project::Move(dir) | Project::Foundry(dir)) = project;
+            let relative = dir.strip_prefix(&repo).unwrap_or(dir).to_string_lossy().into_owned();
+            if relative.is_empty() { ".".to_string() } else { relative }
+        };
+        for project in &projects {
+            if let Err(message) = self.run_toolchain(project, "build", &repo, deadline).await {
+                let message = format!("{} does not build: {}", name(project), message);
+                return Ok(status(false, false, message));
+            }
+        }
+        for project in &projects {
+            if let Err(message) = self.run_toolchain(project, "test", &repo, deadline).await {
+                let message = format!("{} builds but its tests fail: {}", name(project), message);
+                return Ok(status(true, false, message));
+            }
+        }
+
+        info!(
+            "Patch for {} at {} builds and passes the tests of {} project(s)",
+            checkout.full_name,
+            commit_sha,
+            projects.len()
+        );
+        Ok(status(true, true, format!("Built and tested {} project(s)", projects.len())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_files_skip_deletions() {
+        let diff = "--- a/sources/pool.move\n+++ b/sources/pool.move\n@@ -1 +1 @@\n-a\n+b\n\
+                    --- a/src/Old.sol\n+++ /dev/null\n@@ -1 +0,0 @@\n-a\n";
+        assert_eq!(changed_files(diff), vec!["sources/pool.move"]);
+    }
+}
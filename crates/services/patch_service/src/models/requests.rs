@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::{GenerationStrategy, PatchStatus, VoteType};
+use crate::domain::{BulkPatchFilter, GenerationStrategy, PatchStatus, ReviewDecision, VoteType};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListPatchesRequest {
@@ -48,12 +48,44 @@ pub struct VotePatchRequest {
     pub comment: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitReviewRequest {
+    pub decision: ReviewDecision,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssignReviewersRequest {
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+    pub required_approvals: Option<i32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeneratePatchRequest {
     pub vulnerability_id: Uuid,
     pub strategy: Option<GenerationStrategy>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GeneratePatchCandidatesRequest {
+    /// Strategy of the first candidate; the others use the rest in turn.
+    pub strategy: Option<GenerationStrategy>,
+    /// Most candidates to generate; at most `MAX_PATCH_CANDIDATES`.
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateBulkPatchesRequest {
+    pub repository_id: Uuid,
+    #[serde(flatten)]
+    pub filter: BulkPatchFilter,
+    pub strategy: Option<GenerationStrategy>,
+    /// Most findings to patch, up to `MAX_BULK_FINDINGS`; larger values are
+    /// refused.
+    pub limit: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatePatchRequest {
     pub patch_diff: String,
@@ -4,8 +4,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::domain::{
-    FilePreview, GeneratedPatch, PatchLeaderboard, PatchProposal, PatchStatistics,
-    PreviewResult, ValidationResult, Vote,
+    FilePreview, GeneratedPatch, PatchCandidate, PatchDiffReport, PatchLeaderboard, PatchProposal,
+    PatchStatistics, PreviewResult, SubmittedPullRequest, ValidationResult, ValidationStatus, Vote,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +72,31 @@ pub struct ValidatePatchResponse {
     pub validated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyPatchResponse {
+    pub patch_id: Uuid,
+    pub validation: ValidationStatus,
+}
+
+/// A finding's alternative patches, best ranked first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchCandidatesResponse {
+    pub vulnerability_id: Uuid,
+    pub candidates: Vec<PatchCandidate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchDiffResponse {
+    pub patch_id: Uuid,
+    pub diff: PatchDiffReport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitPullRequestResponse {
+    pub patch_id: Uuid,
+    pub pull_request: SubmittedPullRequest,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreviewPatchResponse {
     pub preview: PreviewResult,
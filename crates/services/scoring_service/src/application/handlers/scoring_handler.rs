@@ -2,7 +2,10 @@ use axum::{extract::{Path, Query, State}, response::Json};
 use jd_core::AppState;
 use jd_domain::Id;
 
+use crate::application::use_cases::feature_use_cases::FeatureUseCases;
 use crate::application::use_cases::scoring_use_cases::ScoringUseCases;
+use crate::domain::feature_repository_trait::FeatureRepository;
+use crate::domain::model_registry_repository_trait::ModelRegistryRepository;
 use crate::domain::scoring_repository_trait::ScoringRepository;
 use crate::models::{
     requests::{ScoringRequest, ScoringQueryRequest},
@@ -10,13 +13,13 @@ use crate::models::{
 };
 use crate::Result;
 
-pub struct ScoringHandler<R: ScoringRepository> {
-    use_cases: ScoringUseCases<R>,
+pub struct ScoringHandler<R: ScoringRepository, M: ModelRegistryRepository, F: FeatureRepository> {
+    use_cases: ScoringUseCases<R, M, F>,
 }
 
-impl<R: ScoringRepository> ScoringHandler<R> {
-    pub fn new(repository: R) -> Self {
-        let use_cases = ScoringUseCases::new(repository);
+impl<R: ScoringRepository, M: ModelRegistryRepository, F: FeatureRepository> ScoringHandler<R, M, F> {
+    pub fn new(repository: R, models: M, features: FeatureUseCases<F>) -> Self {
+        let use_cases = ScoringUseCases::new(repository, models, features);
         Self { use_cases }
     }
 
@@ -33,7 +36,7 @@ impl<R: ScoringRepository> ScoringHandler<R> {
         State(_app_state): State<AppState>,
         Path(id): Path<String>,
     ) -> Result<Json<Option<ScoringResponse>>> {
-        let id = Id::new(id);
+        let id: Id = id.parse()?;
         // Note: Similar placeholder
         Err(crate::Error::Internal("Handler not implemented yet".to_string()))
     }
@@ -42,7 +45,7 @@ impl<R: ScoringRepository> ScoringHandler<R> {
         State(_app_state): State<AppState>,
         Path(behavior_id): Path<String>,
     ) -> Result<Json<Option<ScoringResponse>>> {
-        let behavior_id = Id::new(behavior_id);
+        let behavior_id: Id = behavior_id.parse()?;
         // Note: Similar placeholder
         Err(crate::Error::Internal("Handler not implemented yet".to_string()))
     }
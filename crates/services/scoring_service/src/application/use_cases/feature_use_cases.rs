@@ -0,0 +1,42 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::feature_extraction::{FeatureSet, FeatureSnapshot};
+use crate::domain::feature_repository_trait::FeatureRepository;
+use crate::{Error, Result};
+
+/// Extracts and stores the feature vectors scoring models consume.
+pub struct FeatureUseCases<F: FeatureRepository> {
+    repository: F,
+    features: FeatureSet,
+}
+
+impl<F: FeatureRepository> FeatureUseCases<F> {
+    pub fn new(repository: F, features: FeatureSet) -> Self {
+        Self { repository, features }
+    }
+
+    /// Version of the vectors the configured extractors produce.
+    pub fn version(&self) -> String {
+        self.features.version()
+    }
+
+    /// The feature vector of behavior input `behavior_input_id` with
+    /// payload `payload`. It is extracted and stored on first use and
+    /// reused after, so every score of the input under this feature set
+    /// sees the same features.
+    pub async fn snapshot(&self, behavior_input_id: Uuid, payload: &Value) -> Result<FeatureSnapshot> {
+        let version = self.features.version();
+        if let Some(snapshot) = self.repository.find_snapshot(behavior_input_id, &version).await? {
+            return Ok(snapshot);
+        }
+
+        let signals = self
+            .repository
+            .load_signals(behavior_input_id)
+            .await?
+            .ok_or_else(|| Error::InvalidInput(format!("Behavior input {} not found", behavior_input_id)))?;
+        let features = Value::Object(self.features.extract(payload, &signals));
+        self.repository.save_snapshot(behavior_input_id, &version, &features).await
+    }
+}
@@ -1 +1,7 @@
-pub mod scoring_use_cases;
\ No newline at end of file
+pub mod scoring_use_cases;
+pub mod score_ledger_use_cases;
+pub mod model_registry_use_cases;
+pub mod recompute_use_cases;
+pub mod score_history_use_cases;
+pub mod feature_use_cases;
+pub mod sybil_use_cases;
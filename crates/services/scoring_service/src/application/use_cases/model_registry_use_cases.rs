@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::model_registry::{ModelKind, NewScoringModel, RegisteredModel};
+use crate::domain::model_registry_repository_trait::ModelRegistryRepository;
+use crate::domain::scoring_model::{HardcodedScoringModel, ScoringModel};
+use crate::infrastructure::onnx_scoring_model::{artifact_path, OnnxScoringModel};
+use crate::{Error, Result};
+
+/// Longest `model_version` results can record.
+const MAX_MODEL_VERSION_LEN: usize = 50;
+
+/// ONNX sessions by model version. A registered version's artifact never
+/// changes, so a session is loaded once per process.
+static ONNX_MODELS: LazyLock<Mutex<HashMap<String, Arc<dyn ScoringModel>>>> =
+    LazyLock::new(Default::default);
+
+pub struct ModelRegistryUseCases<M: ModelRegistryRepository> {
+    repository: M,
+}
+
+impl<M: ModelRegistryRepository> ModelRegistryUseCases<M> {
+    pub fn new(repository: M) -> Self {
+        Self { repository }
+    }
+
+    pub async fn register(&self, model: NewScoringModel) -> Result<RegisteredModel> {
+        validate(&model)?;
+        let registered = self.repository.register(model).await?.ok_or_else(|| {
+            Error::InvalidInput("A model with this name and version is already registered".to_string())
+        })?;
+        info!(model_version = %registered.model_version, "Scoring model registered");
+        Ok(registered)
+    }
+
+    /// Make model `id` score new behavior inputs. It is loaded first, so a
+    /// model whose artifact cannot be run is never activated.
+    pub async fn activate(&self, id: Uuid) -> Result<RegisteredModel> {
+        let model = self.get(id).await?;
+        load(&model)?;
+        let activated = self
+            .repository
+            .activate(id)
+            .await?
+            .ok_or_else(|| Error::ModelNotFound(id.to_string()))?;
+        info!(model_version = %activated.model_version, "Scoring model activated");
+        Ok(activated)
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<RegisteredModel> {
+        self.repository.get(id).await?.ok_or_else(|| Error::ModelNotFound(id.to_string()))
+    }
+
+    pub async fn list(&self) -> Result<Vec<RegisteredModel>> {
+        self.repository.list().await
+    }
+
+    /// The model registered as `model_version`, else the active one.
+    pub async fn resolve(&self, model_version: Option<&str>) -> Result<Arc<dyn ScoringModel>> {
+        let model = match model_version {
+            Some(model_version) => self
+                .repository
+                .get_by_model_version(model_version)
+                .await?
+                .ok_or_else(|| Error::ModelNotFound(model_version.to_string()))?,
+            None => self
+                .repository
+                .active()
+                .await?
+                .ok_or_else(|| Error::ModelUnavailable("No scoring model is active".to_string()))?,
+        };
+        load(&model)
+    }
+}
+
+fn load(model: &RegisteredModel) -> Result<Arc<dyn ScoringModel>> {
+    match model.kind {
+        ModelKind::RuleBased => Ok(Arc::new(HardcodedScoringModel::with_version(&model.model_version))),
+        ModelKind::Onnx => {
+            let mut loaded = ONNX_MODELS.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(onnx) = loaded.get(&model.model_version) {
+                return Ok(onnx.clone());
+            }
+            let onnx: Arc<dyn ScoringModel> = Arc::new(OnnxScoringModel::load(model)?);
+            loaded.insert(model.model_version.clone(), onnx.clone());
+            Ok(onnx)
+        }
+    }
+}
+
+fn validate(model: &NewScoringModel) -> Result<()> {
+    if model.name.is_empty()
+        || !model.name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(Error::InvalidInput(
+            "name must be lowercase letters, digits and underscores".to_string(),
+        ));
+    }
+    if model.version.trim().is_empty() {
+        return Err(Error::InvalidInput("version is required".to_string()));
+    }
+    if model.name.len() + 1 + model.version.len() > MAX_MODEL_VERSION_LEN {
+        return Err(Error::InvalidInput(format!(
+            "name and version must fit in {} characters",
+            MAX_MODEL_VERSION_LEN - 1
+        )));
+    }
+    match model.kind {
+        ModelKind::RuleBased if model.artifact_uri.is_some() => {
+            Err(Error::InvalidInput("Rule-based models take no artifact_uri".to_string()))
+        }
+        ModelKind::RuleBased => Ok(()),
+        ModelKind::Onnx => {
+            let artifact_uri = model
+                .artifact_uri
+                .as_deref()
+                .ok_or_else(|| Error::InvalidInput("ONNX models need an artifact_uri".to_string()))?;
+            artifact_path(artifact_uri)?;
+            if model.input_schema.features.is_empty() {
+                return Err(Error::InvalidInput("ONNX models need input_schema.features".to_string()));
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model_registry::ModelInputSchema;
+
+    fn onnx(artifact_uri: Option<&str>, features: &[&str]) -> NewScoringModel {
+        NewScoringModel {
+            name: "gbdt".to_string(),
+            version: "v2".to_string(),
+            kind: ModelKind::Onnx,
+            artifact_uri: artifact_uri.map(str::to_string),
+            input_schema: ModelInputSchema { features: features.iter().map(|f| f.to_string()).collect() },
+            registered_by: None,
+        }
+    }
+
+    #[test]
+    fn onnx_models_need_a_local_artifact_and_features() {
+        assert!(validate(&onnx(Some("file:///models/gbdt.onnx"), &["commits"])).is_ok());
+        assert!(validate(&onnx(Some("/models/gbdt.onnx"), &["commits"])).is_ok());
+        assert!(validate(&onnx(None, &["commits"])).is_err());
+        assert!(validate(&onnx(Some("https://models.example/gbdt.onnx"), &["commits"])).is_err());
+        assert!(validate(&onnx(Some("/models/gbdt.onnx"), &[])).is_err());
+    }
+
+    #[test]
+    fn names_cannot_make_model_versions_ambiguous() {
+        let mut model = onnx(Some("/models/gbdt.onnx"), &["commits"]);
+        model.name = "gbdt-v2".to_string();
+        assert!(validate(&model).is_err());
+    }
+}
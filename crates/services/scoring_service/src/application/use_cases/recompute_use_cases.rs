@@ -0,0 +1,161 @@
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::application::use_cases::feature_use_cases::FeatureUseCases;
+use crate::application::use_cases::model_registry_use_cases::ModelRegistryUseCases;
+use crate::domain::feature_repository_trait::FeatureRepository;
+use crate::domain::model_registry_repository_trait::ModelRegistryRepository;
+use crate::domain::recompute_job::{RecomputeFilter, RecomputeJob};
+use crate::domain::recompute_job_repository_trait::RecomputeJobRepository;
+use crate::{Error, Result};
+
+/// Behavior inputs rescored between progress updates.
+const BATCH_SIZE: i64 = 100;
+/// How long a claimed job is held without progress before another worker
+/// may resume it.
+const LEASE: Duration = Duration::from_secs(5 * 60);
+const MAX_ATTEMPTS: i32 = 5;
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Recomputes scores in the background. A request queues a job for a
+/// filter; a worker rescores the selected behavior inputs in batches,
+/// recording its cursor after each so an interrupted job resumes where it
+/// stopped.
+pub struct RecomputeUseCases<J: RecomputeJobRepository, M: ModelRegistryRepository, F: FeatureRepository> {
+    jobs: J,
+    models: ModelRegistryUseCases<M>,
+    features: FeatureUseCases<F>,
+}
+
+impl<J: RecomputeJobRepository, M: ModelRegistryRepository, F: FeatureRepository> RecomputeUseCases<J, M, F> {
+    pub fn new(jobs: J, models: M, features: FeatureUseCases<F>) -> Self {
+        Self { jobs, models: ModelRegistryUseCases::new(models), features }
+    }
+
+    /// Queue recomputation of the inputs `filter` selects with the model
+    /// registered as `model_version`, else the active one.
+    pub async fn enqueue(
+        &self,
+        requested_by: Option<&str>,
+        filter: RecomputeFilter,
+        model_version: Option<&str>,
+    ) -> Result<RecomputeJob> {
+        validate(&filter)?;
+        let model = self.models.resolve(model_version).await?;
+        let job = self.jobs.enqueue(requested_by, &filter, model.version()).await?;
+        info!(
+            job_id = %job.id,
+            model_version = %job.target_model_version,
+            inputs = job.total_count,
+            "Score recompute queued"
+        );
+        Ok(job)
+    }
+
+    pub async fn get_job(&self, id: Uuid) -> Result<RecomputeJob> {
+        self.jobs.get(id).await?.ok_or(Error::RecomputeJobNotFound(id))
+    }
+
+    /// Run the next queued or abandoned job to the end, if there is one. A
+    /// job interrupted by an error is left for its lease to run out and is
+    /// resumed from its cursor, up to `MAX_ATTEMPTS` times.
+    pub async fn run_next(&self) -> Result<bool> {
+        let abandoned = self.jobs.fail_abandoned(MAX_ATTEMPTS).await?;
+        if abandoned > 0 {
+            warn!(jobs = abandoned, "Score recompute jobs failed after their last attempt was abandoned");
+        }
+
+        let Some(job) = self.jobs.claim_next(LEASE, MAX_ATTEMPTS).await? else {
+            return Ok(false);
+        };
+        match self.recompute(job.clone()).await {
+            Ok(()) => {
+                self.jobs.complete(job.id).await?;
+                info!(job_id = %job.id, "Score recompute completed");
+            }
+            // The model is gone or cannot be loaded; retrying will not help
+            Err(e @ (Error::ModelNotFound(_) | Error::ModelUnavailable(_))) => {
+                warn!(job_id = %job.id, error = %e, "Score recompute failed");
+                self.jobs.fail(job.id, &e.to_string()).await?;
+            }
+            Err(e) => {
+                warn!(job_id = %job.id, attempt = job.attempts, error = %e, "Score recompute interrupted");
+            }
+        }
+        Ok(true)
+    }
+
+    /// Run recompute jobs until the process exits, polling while idle.
+    pub async fn run(self) {
+        info!("Score recompute worker started");
+        loop {
+            match self.run_next().await {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => warn!(error = %e, "Score recompute worker run failed"),
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn recompute(&self, mut job: RecomputeJob) -> Result<()> {
+        let model = self.models.resolve(Some(&job.target_model_version)).await?;
+        loop {
+            let batch = self.jobs.next_batch(&job, BATCH_SIZE).await?;
+            let Some(last) = batch.last().map(|candidate| candidate.behavior_input_id) else {
+                return Ok(());
+            };
+
+            let (mut processed, mut failed) = (0, 0);
+            for candidate in &batch {
+                let scored = match self.features.snapshot(candidate.behavior_input_id, &candidate.input_data).await {
+                    Ok(snapshot) => model.calculate_score(&snapshot.features).await.map(|score| (score, snapshot.id)),
+                    Err(e) => Err(e),
+                };
+                match scored {
+                    Ok((score, snapshot_id)) => {
+                        self.jobs
+                            .record_score(
+                                job.id,
+                                candidate.behavior_input_id,
+                                score,
+                                model.version(),
+                                Some(snapshot_id),
+                            )
+                            .await?;
+                        processed += 1;
+                    }
+                    // The input keeps its old score
+                    Err(e) => {
+                        warn!(
+                            job_id = %job.id,
+                            behavior_input_id = %candidate.behavior_input_id,
+                            error = %e,
+                            "Behavior input could not be rescored"
+                        );
+                        failed += 1;
+                    }
+                }
+            }
+            self.jobs.advance(job.id, last, processed, failed, LEASE).await?;
+            job.cursor = Some(last);
+        }
+    }
+}
+
+fn validate(filter: &RecomputeFilter) -> Result<()> {
+    if let (Some(from), Some(to)) = (filter.from, filter.to) {
+        if from >= to {
+            return Err(Error::InvalidInput("from must be before to".to_string()));
+        }
+    }
+    if let Some(cohort) = &filter.cohort {
+        if let (Some(from), Some(to)) = (cohort.joined_from, cohort.joined_to) {
+            if from >= to {
+                return Err(Error::InvalidInput("cohort.joined_from must be before joined_to".to_string()));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use crate::domain::scoring_repository_trait::ScoringRepository;
+use crate::models::{
+    requests::ScoreHistoryQuery,
+    responses::{ScoreHistoryPoint, ScoreHistoryResponse, ScoreTrend, ScoredInput},
+    ScoreHistoryRecord,
+};
+use crate::{Error, Result};
+
+const DEFAULT_LIMIT: u32 = 100;
+const MAX_LIMIT: u32 = 500;
+/// Net changes smaller than this, in score points, are a flat trend.
+const FLAT_THRESHOLD: f64 = 0.5;
+
+pub struct ScoreHistoryUseCases<R: ScoringRepository> {
+    repository: R,
+}
+
+impl<R: ScoringRepository> ScoreHistoryUseCases<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// The latest `query.limit` scores of the account linking wallet
+    /// `subject`, oldest first, each with how and why it moved from the one
+    /// before.
+    pub async fn history(&self, subject: &str, query: ScoreHistoryQuery) -> Result<ScoreHistoryResponse> {
+        if let (Some(from), Some(to)) = (query.from, query.to) {
+            if from >= to {
+                return Err(Error::InvalidInput("from must be before to".to_string()));
+            }
+        }
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+
+        // One more than asked for, so the oldest point still has a delta
+        let mut records = self.repository.list_subject_history(subject, &query, limit as i64 + 1).await?;
+        records.reverse();
+        let baseline = if records.len() > limit { Some(records.remove(0)) } else { None };
+        let baseline_score = baseline.as_ref().or(records.first()).map(|record| record.score);
+        let points = history_points(baseline, records);
+
+        let net_change = match (baseline_score, points.last()) {
+            (Some(start), Some(latest)) => latest.score - start,
+            _ => 0.0,
+        };
+        let trend = if net_change >= FLAT_THRESHOLD {
+            ScoreTrend::Up
+        } else if net_change <= -FLAT_THRESHOLD {
+            ScoreTrend::Down
+        } else {
+            ScoreTrend::Flat
+        };
+        Ok(ScoreHistoryResponse { subject: subject.to_string(), points, net_change, trend })
+    }
+}
+
+/// Points for `records`, oldest first, each compared with the one before;
+/// the first with `previous`, if any.
+fn history_points(mut previous: Option<ScoreHistoryRecord>, records: Vec<ScoreHistoryRecord>) -> Vec<ScoreHistoryPoint> {
+    let mut points = Vec::with_capacity(records.len());
+    for record in records {
+        let (delta, model_changed, changed_fields) = match &previous {
+            Some(before) => (
+                Some(record.score - before.score),
+                record.model_version != before.model_version,
+                changed_fields(&before.input_data, &record.input_data),
+            ),
+            None => (None, false, Vec::new()),
+        };
+        points.push(ScoreHistoryPoint {
+            scoring_result_id: record.scoring_result_id,
+            score: record.score,
+            model_version: record.model_version.clone(),
+            scored_at: record.scored_at,
+            recompute_job_id: record.recompute_job_id,
+            behavior_input: ScoredInput {
+                id: record.behavior_input_id,
+                session_id: record.session_id.clone(),
+                input_data: record.input_data.clone(),
+            },
+            delta,
+            model_changed,
+            changed_fields,
+        });
+        previous = Some(record);
+    }
+    points
+}
+
+/// Top-level fields whose value differs between two inputs, sorted.
+fn changed_fields(before: &Value, after: &Value) -> Vec<String> {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => before
+            .keys()
+            .chain(after.keys())
+            .filter(|key| before.get(*key) != after.get(*key))
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+        _ if before != after => vec!["input_data".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(score: f64, model_version: &str, input_data: Value) -> ScoreHistoryRecord {
+        ScoreHistoryRecord {
+            scoring_result_id: uuid::Uuid::new_v4(),
+            score,
+            model_version: model_version.to_string(),
+            scored_at: chrono::Utc::now(),
+            recompute_job_id: None,
+            behavior_input_id: uuid::Uuid::new_v4(),
+            session_id: None,
+            input_data,
+        }
+    }
+
+    #[test]
+    fn points_explain_each_move_from_the_previous_score() {
+        let points = history_points(
+            Some(record(60.0, "hardcoded-v1.0", json!({ "commits": 3, "reviews": 1 }))),
+            vec![
+                record(64.5, "hardcoded-v1.0", json!({ "commits": 5, "reviews": 1 })),
+                record(58.0, "gbdt-v2", json!({ "commits": 5, "reviews": 1, "forks": 2 })),
+            ],
+        );
+
+        assert_eq!(points[0].delta, Some(4.5));
+        assert!(!points[0].model_changed);
+        assert_eq!(points[0].changed_fields, vec!["commits"]);
+        assert_eq!(points[1].delta, Some(-6.5));
+        assert!(points[1].model_changed);
+        assert_eq!(points[1].changed_fields, vec!["forks"]);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::domain::score_ledger::ChainVerifier;
+use crate::domain::score_ledger_repository_trait::ScoreLedgerRepository;
+use crate::models::responses::LedgerVerificationResponse;
+use crate::Result;
+
+const VERIFY_BATCH_SIZE: i64 = 1000;
+
+pub struct ScoreLedgerUseCases<L: ScoreLedgerRepository> {
+    repository: L,
+}
+
+impl<L: ScoreLedgerRepository> ScoreLedgerUseCases<L> {
+    pub fn new(repository: L) -> Self {
+        Self { repository }
+    }
+
+    /// Walk the whole ledger from genesis, recomputing every hash, and report
+    /// the first row where the chain no longer holds.
+    pub async fn verify_chain(&self) -> Result<LedgerVerificationResponse> {
+        let mut verifier = ChainVerifier::default();
+        let mut head_hash = None;
+
+        loop {
+            let batch = self.repository.list_after(verifier.last_seq(), VERIFY_BATCH_SIZE).await?;
+            let batch_len = batch.len() as i64;
+
+            for record in batch {
+                if let Err(first_break) = verifier.push(&record) {
+                    return Ok(LedgerVerificationResponse {
+                        valid: false,
+                        entries_checked: verifier.entries_checked(),
+                        head_seq: Some(verifier.last_seq()).filter(|seq| *seq > 0),
+                        head_hash,
+                        first_break: Some(first_break),
+                    });
+                }
+                head_hash = Some(record.entry_hash);
+            }
+
+            if batch_len < VERIFY_BATCH_SIZE {
+                break;
+            }
+        }
+
+        Ok(LedgerVerificationResponse {
+            valid: true,
+            entries_checked: verifier.entries_checked(),
+            head_seq: Some(verifier.last_seq()).filter(|seq| *seq > 0),
+            head_hash,
+            first_break: None,
+        })
+    }
+}
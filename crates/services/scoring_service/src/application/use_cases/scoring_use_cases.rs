@@ -1,41 +1,48 @@
 use jd_domain::Id;
 use jd_domain::zkpersona_domain::profile::ScoringResult;
 
+use crate::application::use_cases::feature_use_cases::FeatureUseCases;
+use crate::application::use_cases::model_registry_use_cases::ModelRegistryUseCases;
+use crate::domain::feature_repository_trait::FeatureRepository;
+use crate::domain::model_registry_repository_trait::ModelRegistryRepository;
 use crate::domain::scoring_repository_trait::ScoringRepository;
-use crate::domain::scoring_model::HardcodedScoringModel;
 use crate::models::{
     requests::{ScoringRequest, ScoringQueryRequest},
     responses::{ScoringResponse, ScoringListResponse},
 };
 use crate::Result;
 
-pub struct ScoringUseCases<R: ScoringRepository> {
+pub struct ScoringUseCases<R: ScoringRepository, M: ModelRegistryRepository, F: FeatureRepository> {
     repository: R,
-    model: HardcodedScoringModel,
+    models: ModelRegistryUseCases<M>,
+    features: FeatureUseCases<F>,
 }
 
-impl<R: ScoringRepository> ScoringUseCases<R> {
-    pub fn new(repository: R) -> Self {
+impl<R: ScoringRepository, M: ModelRegistryRepository, F: FeatureRepository> ScoringUseCases<R, M, F> {
+    pub fn new(repository: R, models: M, features: FeatureUseCases<F>) -> Self {
         Self { 
             repository,
-            model: HardcodedScoringModel::new(),
+            models: ModelRegistryUseCases::new(models),
+            features,
         }
     }
 
+    /// Score with the registered model `request.model_version` names, else
+    /// the active one. The model sees the input's feature vector rather than
+    /// `behavior_data` itself; the result records the model that actually
+    /// ran and the snapshot it scored.
     pub async fn calculate_score(&self, request: ScoringRequest, behavior_data: serde_json::Value) -> Result<ScoringResponse> {
-        // Use the hardcoded model to calculate score
-        let score = self.model.calculate_score(&behavior_data).await?;
-        
-        let model_version = request.model_version
-            .unwrap_or_else(|| self.model.version().to_string());
+        let model = self.models.resolve(request.model_version.as_deref()).await?;
+        let snapshot = self.features.snapshot(request.behavior_input_id.to_uuid(), &behavior_data).await?;
+        let score = model.calculate_score(&snapshot.features).await?;
         
         let scoring_result = ScoringResult {
             behavior_input_id: request.behavior_input_id,
             score,
-            model_version,
+            model_version: model.version().to_string(),
         };
         
-        self.repository.create_scoring_result(scoring_result).await
+        self.repository.create_scoring_result(scoring_result, Some(snapshot.id)).await
     }
 
     pub async fn get_scoring_result(&self, id: Id) -> Result<Option<ScoringResponse>> {
@@ -50,7 +57,8 @@ impl<R: ScoringRepository> ScoringUseCases<R> {
         self.repository.list_scoring_results(request).await
     }
 
-    pub fn get_model_info(&self) -> serde_json::Value {
-        self.model.get_model_metadata()
+    /// Metadata of the active model.
+    pub async fn get_model_info(&self) -> Result<serde_json::Value> {
+        Ok(self.models.resolve(None).await?.get_model_metadata())
     }
 }
\ No newline at end of file
@@ -0,0 +1,77 @@
+use chrono::{Duration, Utc};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::sybil::{SybilAssessment, SybilThresholds};
+use crate::domain::sybil_repository_trait::SybilRepository;
+use crate::models::responses::SybilReportResponse;
+use crate::{Error, Result};
+
+/// Stored assessments older than this are recomputed when read.
+const ASSESSMENT_TTL_MINUTES: i64 = 60;
+
+/// Sybil scores, kept apart from persona scores, and the thresholds each
+/// partner judges them by.
+pub struct SybilUseCases<S: SybilRepository> {
+    repository: S,
+    default_thresholds: SybilThresholds,
+}
+
+impl<S: SybilRepository> SybilUseCases<S> {
+    pub fn new(repository: S, default_thresholds: SybilThresholds) -> Self {
+        Self { repository, default_thresholds }
+    }
+
+    /// Compute and store the sybil score of user `user_id`.
+    pub async fn assess(&self, user_id: Uuid) -> Result<SybilAssessment> {
+        let signals = self.repository.load_signals(user_id).await?;
+        let score = signals.score();
+        let assessment = self.repository.save_assessment(user_id, score, &signals).await?;
+        info!(user_id = %user_id, sybil_score = score, "Sybil score assessed");
+        Ok(assessment)
+    }
+
+    /// Reassess the user behavior input `behavior_input_id` was recorded
+    /// for. Anonymous inputs have no one to assess.
+    pub async fn assess_input_user(&self, behavior_input_id: Uuid) -> Result<Option<SybilAssessment>> {
+        match self.repository.input_user(behavior_input_id).await? {
+            Some(user_id) => self.assess(user_id).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Sybil and persona score of the user with wallet `subject`, with the
+    /// verdict of `partner`'s thresholds.
+    pub async fn report(&self, partner: &str, subject: &str) -> Result<SybilReportResponse> {
+        let user_id = self
+            .repository
+            .find_user(subject)
+            .await?
+            .ok_or_else(|| Error::SubjectNotFound(subject.to_string()))?;
+        let stale_before = Utc::now() - Duration::minutes(ASSESSMENT_TTL_MINUTES);
+        let assessment = match self.repository.find_assessment(user_id).await? {
+            Some(assessment) if assessment.assessed_at > stale_before => assessment,
+            _ => self.assess(user_id).await?,
+        };
+        let thresholds = self.thresholds(partner).await?;
+        Ok(SybilReportResponse {
+            subject: subject.to_string(),
+            persona_score: self.repository.latest_persona_score(user_id).await?,
+            sybil_score: assessment.score,
+            verdict: thresholds.verdict(assessment.score),
+            thresholds,
+            signals: assessment.signals,
+            assessed_at: assessment.assessed_at,
+        })
+    }
+
+    /// `partner`'s thresholds, else the configured defaults.
+    pub async fn thresholds(&self, partner: &str) -> Result<SybilThresholds> {
+        Ok(self.repository.find_thresholds(partner).await?.unwrap_or(self.default_thresholds))
+    }
+
+    pub async fn set_thresholds(&self, partner: &str, thresholds: SybilThresholds) -> Result<SybilThresholds> {
+        thresholds.validate()?;
+        self.repository.save_thresholds(partner, &thresholds).await
+    }
+}
@@ -0,0 +1,341 @@
+use chrono::{DateTime, Utc};
+use jd_utils::config::ScoringConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::scoring_model::unwrap_ingestion;
+use crate::{Error, Result};
+
+/// Extractors run when `SCORING.FEATURE_EXTRACTORS` is unset.
+pub const DEFAULT_FEATURE_EXTRACTORS: &str = "fields,counts,recency,onchain,account";
+/// Days on-chain activity is counted over.
+pub const ONCHAIN_WINDOW_DAYS: i64 = 30;
+
+/// What features are extracted from besides the input itself: its session,
+/// the subject's earlier inputs, the on-chain activity of the user's linked
+/// wallets as indexed from the configured Sui packages, and the reputation
+/// of the user's account across its linked identities.
+#[derive(Debug, Clone, Default, FromRow)]
+pub struct FeatureSignals {
+    pub recorded_at: Option<DateTime<Utc>>,
+    pub sample_weight: f64,
+    /// Inputs of the same session before this one.
+    pub session_inputs: i64,
+    pub session_started_at: Option<DateTime<Utc>>,
+    /// The subject's input before this one, by user, else by session.
+    pub previous_input_at: Option<DateTime<Utc>>,
+    pub wallet_address: Option<String>,
+    /// Events the account's wallets sent within the on-chain window.
+    pub onchain_events: i64,
+    pub onchain_last_event_at: Option<DateTime<Utc>>,
+    pub linked_wallets: i64,
+    pub linked_github_identities: i64,
+    /// From the `account_reputation` view, as it stands now rather than as
+    /// of the input.
+    pub account_reputation_score: Option<f64>,
+    pub coding_reputation_score: Option<f64>,
+    pub security_awareness_score: Option<f64>,
+    pub community_trust_score: Option<f64>,
+    pub total_contributions: i64,
+}
+
+/// A stored feature vector of one behavior input.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FeatureSnapshot {
+    pub id: Uuid,
+    pub behavior_input_id: Uuid,
+    pub feature_version: String,
+    pub features: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Turns a behavior input and its signals into named numeric features.
+pub trait FeatureExtractor: Send + Sync {
+    /// Name it is configured by.
+    fn name(&self) -> &'static str;
+
+    /// Bumped whenever the features it produces change, so vectors of
+    /// different versions are never mixed up.
+    fn version(&self) -> u32;
+
+    /// Features of `event`, the input with any ingestion wrapper removed.
+    /// A feature with no value is left out rather than set to zero.
+    fn extract(&self, event: &Value, signals: &FeatureSignals) -> Map<String, Value>;
+}
+
+/// The input's own top-level numeric and boolean fields, under their own
+/// names, so models can keep naming raw input fields.
+pub struct FieldsExtractor;
+
+impl FeatureExtractor for FieldsExtractor {
+    fn name(&self) -> &'static str {
+        "fields"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn extract(&self, event: &Value, _signals: &FeatureSignals) -> Map<String, Value> {
+        let Some(fields) = event.as_object() else {
+            return Map::new();
+        };
+        fields
+            .iter()
+            .filter_map(|(name, value)| match value {
+                Value::Number(_) => Some((name.clone(), value.clone())),
+                Value::Bool(flag) => Some((name.clone(), Value::from(u8::from(*flag)))),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Size of the input and of its session.
+pub struct CountsExtractor;
+
+impl FeatureExtractor for CountsExtractor {
+    fn name(&self) -> &'static str {
+        "counts"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn extract(&self, event: &Value, signals: &FeatureSignals) -> Map<String, Value> {
+        let (fields, nested) = match event {
+            Value::Object(obj) => {
+                let nested = obj.values().filter(|v| matches!(v, Value::Object(_) | Value::Array(_))).count();
+                (obj.len(), nested)
+            }
+            Value::Array(arr) => (arr.len(), 0),
+            _ => (1, 0),
+        };
+        let mut features = Map::new();
+        features.insert("counts.field_count".to_string(), Value::from(fields));
+        features.insert("counts.nested_field_count".to_string(), Value::from(nested));
+        features.insert("counts.sample_weight".to_string(), Value::from(signals.sample_weight));
+        features.insert("counts.session_inputs".to_string(), Value::from(signals.session_inputs));
+        features
+    }
+}
+
+/// How recently the subject was last active and how old the session is.
+pub struct RecencyExtractor;
+
+impl FeatureExtractor for RecencyExtractor {
+    fn name(&self) -> &'static str {
+        "recency"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn extract(&self, _event: &Value, signals: &FeatureSignals) -> Map<String, Value> {
+        let mut features = Map::new();
+        let Some(recorded_at) = signals.recorded_at else {
+            return features;
+        };
+        let secs_since = |at: DateTime<Utc>| Value::from((recorded_at - at).num_seconds().max(0));
+        if let Some(previous) = signals.previous_input_at {
+            features.insert("recency.secs_since_previous_input".to_string(), secs_since(previous));
+        }
+        if let Some(started) = signals.session_started_at {
+            features.insert("recency.session_age_secs".to_string(), secs_since(started));
+        }
+        features
+    }
+}
+
+/// Activity of the user's linked wallets on the indexed Sui packages.
+pub struct OnchainActivityExtractor;
+
+impl FeatureExtractor for OnchainActivityExtractor {
+    fn name(&self) -> &'static str {
+        "onchain"
+    }
+
+    fn version(&self) -> u32 {
+        2
+    }
+
+    fn extract(&self, _event: &Value, signals: &FeatureSignals) -> Map<String, Value> {
+        let mut features = Map::new();
+        features.insert(
+            "onchain.has_wallet".to_string(),
+            Value::from(u8::from(signals.wallet_address.is_some())),
+        );
+        features.insert(
+            format!("onchain.events_{}d", ONCHAIN_WINDOW_DAYS),
+            Value::from(signals.onchain_events),
+        );
+        if let (Some(recorded_at), Some(last)) = (signals.recorded_at, signals.onchain_last_event_at) {
+            features.insert(
+                "onchain.days_since_last_event".to_string(),
+                Value::from((recorded_at - last).num_days().max(0)),
+            );
+        }
+        features
+    }
+}
+
+/// Reputation of the user's account, aggregated over every wallet and
+/// GitHub identity linked to it.
+pub struct AccountReputationExtractor;
+
+impl FeatureExtractor for AccountReputationExtractor {
+    fn name(&self) -> &'static str {
+        "account"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn extract(&self, _event: &Value, signals: &FeatureSignals) -> Map<String, Value> {
+        let mut features = Map::new();
+        features.insert("account.linked_wallets".to_string(), Value::from(signals.linked_wallets));
+        features.insert(
+            "account.github_identities".to_string(),
+            Value::from(signals.linked_github_identities),
+        );
+        features.insert(
+            "account.total_contributions".to_string(),
+            Value::from(signals.total_contributions),
+        );
+        let scores = [
+            ("account.reputation_score", signals.account_reputation_score),
+            ("account.coding_reputation", signals.coding_reputation_score),
+            ("account.security_awareness", signals.security_awareness_score),
+            ("account.community_trust", signals.community_trust_score),
+        ];
+        for (name, score) in scores {
+            if let Some(score) = score {
+                features.insert(name.to_string(), Value::from(score));
+            }
+        }
+        features
+    }
+}
+
+/// The configured extractors, run in order.
+pub struct FeatureSet {
+    extractors: Vec<Box<dyn FeatureExtractor>>,
+}
+
+impl FeatureSet {
+    /// Parse a comma-separated list of extractor names.
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let mut extractors: Vec<Box<dyn FeatureExtractor>> = Vec::new();
+        for name in spec.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            if extractors.iter().any(|e| e.name() == name) {
+                continue;
+            }
+            extractors.push(match name {
+                "fields" => Box::new(FieldsExtractor),
+                "counts" => Box::new(CountsExtractor),
+                "recency" => Box::new(RecencyExtractor),
+                "onchain" => Box::new(OnchainActivityExtractor),
+                "account" => Box::new(AccountReputationExtractor),
+                other => return Err(Error::InvalidInput(format!("Unknown feature extractor: {}", other))),
+            });
+        }
+        if extractors.is_empty() {
+            return Err(Error::InvalidInput("No feature extractors configured".to_string()));
+        }
+        Ok(Self { extractors })
+    }
+
+    /// Build from config, falling back to the default extractors when unset
+    /// or invalid.
+    pub fn from_config(config: Option<&ScoringConfig>) -> Self {
+        let Some(spec) = config.and_then(|c| c.feature_extractors.as_deref()) else {
+            return Self::default();
+        };
+        Self::from_spec(spec).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring feature extractors: {}", e);
+            Self::default()
+        })
+    }
+
+    /// Identifies the vectors this set produces, e.g. `fields@1+counts@1`.
+    pub fn version(&self) -> String {
+        self.extractors
+            .iter()
+            .map(|e| format!("{}@{}", e.name(), e.version()))
+            .collect::<Vec<_>>()
+            .join("+")
+    }
+
+    /// The feature vector of a behavior input. `payload` may be a sampled or
+    /// aggregated wrapper; features are taken from the event it stands for.
+    pub fn extract(&self, payload: &Value, signals: &FeatureSignals) -> Map<String, Value> {
+        let event = unwrap_ingestion(payload);
+        let mut features = Map::new();
+        for extractor in &self.extractors {
+            features.extend(extractor.extract(&event, signals));
+        }
+        features
+    }
+}
+
+impl Default for FeatureSet {
+    fn default() -> Self {
+        Self::from_spec(DEFAULT_FEATURE_EXTRACTORS).expect("default feature extractors are valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+
+    #[test]
+    fn features_are_extracted_from_the_event_and_its_signals() {
+        let recorded_at = Utc::now();
+        let signals = FeatureSignals {
+            recorded_at: Some(recorded_at),
+            sample_weight: 1.0,
+            session_inputs: 4,
+            session_started_at: Some(recorded_at - Duration::seconds(90)),
+            previous_input_at: Some(recorded_at - Duration::seconds(30)),
+            wallet_address: Some("0xabc".to_string()),
+            onchain_events: 7,
+            onchain_last_event_at: Some(recorded_at - Duration::days(2)),
+            linked_wallets: 2,
+            linked_github_identities: 1,
+            coding_reputation_score: Some(80.0),
+            total_contributions: 42,
+            ..Default::default()
+        };
+        let set = FeatureSet::default();
+        let features = set.extract(&json!({ "commits": 5, "active": true, "tags": ["a"] }), &signals);
+
+        assert_eq!(set.version(), "fields@1+counts@1+recency@1+onchain@2+account@1");
+        assert_eq!(features["commits"], json!(5));
+        assert_eq!(features["active"], json!(1));
+        assert!(!features.contains_key("tags"));
+        assert_eq!(features["counts.field_count"], json!(3));
+        assert_eq!(features["counts.nested_field_count"], json!(1));
+        assert_eq!(features["counts.session_inputs"], json!(4));
+        assert_eq!(features["recency.secs_since_previous_input"], json!(30));
+        assert_eq!(features["recency.session_age_secs"], json!(90));
+        assert_eq!(features["onchain.events_30d"], json!(7));
+        assert_eq!(features["onchain.days_since_last_event"], json!(2));
+        assert_eq!(features["account.linked_wallets"], json!(2));
+        assert_eq!(features["account.coding_reputation"], json!(80.0));
+        assert!(!features.contains_key("account.reputation_score"));
+    }
+
+    #[test]
+    fn extractors_are_configured_by_name() {
+        assert_eq!(FeatureSet::from_spec("counts, counts,onchain").unwrap().version(), "counts@1+onchain@2");
+        assert!(FeatureSet::from_spec("counts,social").is_err());
+        assert!(FeatureSet::from_spec(" ").is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::feature_extraction::{FeatureSignals, FeatureSnapshot};
+use crate::Result;
+
+#[async_trait]
+pub trait FeatureRepository: Send + Sync {
+    /// Signals of behavior input `behavior_input_id` as of when it was
+    /// recorded, or `None` if there is no such input.
+    async fn load_signals(&self, behavior_input_id: Uuid) -> Result<Option<FeatureSignals>>;
+    async fn find_snapshot(
+        &self,
+        behavior_input_id: Uuid,
+        feature_version: &str,
+    ) -> Result<Option<FeatureSnapshot>>;
+    /// Store a snapshot. If one of the same version was stored meanwhile,
+    /// that one is kept and returned.
+    async fn save_snapshot(
+        &self,
+        behavior_input_id: Uuid,
+        feature_version: &str,
+        features: &Value,
+    ) -> Result<FeatureSnapshot>;
+}
@@ -1,2 +1,12 @@
 pub mod scoring_repository_trait;
-pub mod scoring_model;
\ No newline at end of file
+pub mod scoring_model;
+pub mod score_ledger;
+pub mod score_ledger_repository_trait;
+pub mod model_registry;
+pub mod model_registry_repository_trait;
+pub mod recompute_job;
+pub mod recompute_job_repository_trait;
+pub mod feature_extraction;
+pub mod feature_repository_trait;
+pub mod sybil;
+pub mod sybil_repository_trait;
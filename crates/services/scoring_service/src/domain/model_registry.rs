@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Scope a bearer token needs to register and activate scoring models.
+pub const SCOPE_SCORING_MODELS_ADMIN: &str = "scoring:models_admin";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ModelKind {
+    /// The built-in rules; needs no artifact.
+    RuleBased,
+    /// An ONNX model run with ONNX Runtime.
+    Onnx,
+}
+
+/// Which behavior input fields a model takes, in input order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelInputSchema {
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+/// Row of `scoring_models`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegisteredModel {
+    pub id: Uuid,
+    pub name: String,
+    pub version: String,
+    /// `{name}-{version}`, recorded on the results the model scores.
+    pub model_version: String,
+    pub kind: ModelKind,
+    pub artifact_uri: Option<String>,
+    #[sqlx(json)]
+    pub input_schema: ModelInputSchema,
+    pub active: bool,
+    pub registered_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub activated_at: Option<DateTime<Utc>>,
+}
+
+/// A model to register; it scores nothing until activated.
+#[derive(Debug, Clone)]
+pub struct NewScoringModel {
+    pub name: String,
+    pub version: String,
+    pub kind: ModelKind,
+    pub artifact_uri: Option<String>,
+    pub input_schema: ModelInputSchema,
+    pub registered_by: Option<String>,
+}
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::model_registry::{NewScoringModel, RegisteredModel};
+use crate::Result;
+
+#[async_trait]
+pub trait ModelRegistryRepository: Send + Sync {
+    /// `None` if the name and version are already registered.
+    async fn register(&self, model: NewScoringModel) -> Result<Option<RegisteredModel>>;
+    async fn get(&self, id: Uuid) -> Result<Option<RegisteredModel>>;
+    async fn get_by_model_version(&self, model_version: &str) -> Result<Option<RegisteredModel>>;
+    async fn active(&self) -> Result<Option<RegisteredModel>>;
+    /// Newest first.
+    async fn list(&self) -> Result<Vec<RegisteredModel>>;
+    /// Make model `id` the only active one. `None` if it does not exist.
+    async fn activate(&self, id: Uuid) -> Result<Option<RegisteredModel>>;
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Users whose behavior inputs are recomputed; every condition given must
+/// hold.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserCohort {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_ids: Vec<Uuid>,
+    /// Users who signed up at or after this time.
+    pub joined_from: Option<DateTime<Utc>>,
+    /// Users who signed up before this time.
+    pub joined_to: Option<DateTime<Utc>>,
+}
+
+/// Which behavior inputs a job recomputes: those with a score matching
+/// every condition given, computed before the job was queued.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecomputeFilter {
+    /// Scores computed at or after this time.
+    pub from: Option<DateTime<Utc>>,
+    /// Scores computed before this time.
+    pub to: Option<DateTime<Utc>>,
+    /// Scores computed by this model.
+    pub model_version: Option<String>,
+    pub cohort: Option<UserCohort>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RecomputeJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Row of `score_recompute_jobs`.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct RecomputeJob {
+    pub id: Uuid,
+    pub requested_by: Option<String>,
+    #[sqlx(json)]
+    pub filter: RecomputeFilter,
+    pub target_model_version: String,
+    pub status: RecomputeJobStatus,
+    /// Behavior inputs the filter selected when the job was queued.
+    pub total_count: i64,
+    pub processed_count: i64,
+    /// Inputs the model could not score; their old scores stand.
+    pub failed_count: i64,
+    #[serde(skip)]
+    pub cursor: Option<Uuid>,
+    pub attempts: i32,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl RecomputeJob {
+    /// Share of the selected inputs handled so far, 0 to 100.
+    pub fn progress_percent(&self) -> f64 {
+        if self.total_count == 0 {
+            return 100.0;
+        }
+        let handled = (self.processed_count + self.failed_count).min(self.total_count);
+        handled as f64 * 100.0 / self.total_count as f64
+    }
+}
+
+/// A behavior input due for recomputation.
+#[derive(Debug, Clone, FromRow)]
+pub struct RecomputeCandidate {
+    pub behavior_input_id: Uuid,
+    pub input_data: Value,
+}
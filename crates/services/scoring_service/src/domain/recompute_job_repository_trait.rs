@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::recompute_job::{RecomputeCandidate, RecomputeFilter, RecomputeJob};
+use crate::Result;
+
+#[async_trait]
+pub trait RecomputeJobRepository: Send + Sync {
+    /// Queue a job, counting the behavior inputs `filter` selects.
+    async fn enqueue(
+        &self,
+        requested_by: Option<&str>,
+        filter: &RecomputeFilter,
+        target_model_version: &str,
+    ) -> Result<RecomputeJob>;
+    async fn get(&self, id: Uuid) -> Result<Option<RecomputeJob>>;
+    /// Claim the oldest queued job, or a running one whose lease ran out,
+    /// holding it for `lease`.
+    async fn claim_next(&self, lease: Duration, max_attempts: i32) -> Result<Option<RecomputeJob>>;
+    /// Fail running jobs whose last allowed attempt never finished.
+    async fn fail_abandoned(&self, max_attempts: i32) -> Result<u64>;
+    /// The next `limit` behavior inputs of `job` after its cursor, by id.
+    async fn next_batch(&self, job: &RecomputeJob, limit: i64) -> Result<Vec<RecomputeCandidate>>;
+    /// Store a recomputed score as a new result of `job_id`.
+    async fn record_score(
+        &self,
+        job_id: Uuid,
+        behavior_input_id: Uuid,
+        score: f64,
+        model_version: &str,
+        feature_snapshot_id: Option<Uuid>,
+    ) -> Result<()>;
+    /// Move the cursor past a handled batch, add its counts and hold the
+    /// job for another `lease`.
+    async fn advance(
+        &self,
+        job_id: Uuid,
+        cursor: Uuid,
+        processed: i64,
+        failed: i64,
+        lease: Duration,
+    ) -> Result<()>;
+    async fn complete(&self, job_id: Uuid) -> Result<()>;
+    async fn fail(&self, job_id: Uuid, error: &str) -> Result<()>;
+}
@@ -0,0 +1,146 @@
+use sha2::{Digest, Sha256};
+
+use crate::models::{ScoreLedgerRecord, responses::LedgerBreak};
+
+/// Grants verifying the ledger's hash chain, which reads every row.
+pub const SCOPE_SCORING_LEDGER_ADMIN: &str = "scoring:ledger_admin";
+
+/// `prev_hash` of the first ledger row.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Hash a ledger row over its canonical payload.
+///
+/// Every persisted column except `entry_hash` takes part, so changing any field
+/// (including `seq` or `prev_hash`) yields a different hash. Fields are
+/// separated by `|` and optional values are rendered as an empty string.
+pub fn compute_entry_hash(record: &ScoreLedgerRecord) -> String {
+    let payload = format!(
+        "v1|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+        record.seq,
+        record.subject_id,
+        record.scoring_result_id.map(|id| id.to_string()).unwrap_or_default(),
+        record.score,
+        record.previous_score.map(|s| s.to_string()).unwrap_or_default(),
+        record.model_version,
+        record.reason,
+        record.created_at.timestamp_micros(),
+        record.prev_hash,
+    );
+
+    hex::encode(Sha256::digest(payload.as_bytes()))
+}
+
+/// Tracks chain state while ledger rows are streamed in `seq` order.
+pub struct ChainVerifier {
+    expected_seq: i64,
+    expected_prev_hash: String,
+    entries_checked: u64,
+}
+
+impl Default for ChainVerifier {
+    fn default() -> Self {
+        Self {
+            expected_seq: 1,
+            expected_prev_hash: GENESIS_HASH.to_string(),
+            entries_checked: 0,
+        }
+    }
+}
+
+impl ChainVerifier {
+    /// Check the next row. Returns the first inconsistency found, if any.
+    pub fn push(&mut self, record: &ScoreLedgerRecord) -> Result<(), LedgerBreak> {
+        let broken = |reason: String| LedgerBreak { seq: record.seq, reason };
+
+        if record.seq != self.expected_seq {
+            return Err(broken(format!(
+                "expected seq {}, found {} (missing or reordered rows)",
+                self.expected_seq, record.seq
+            )));
+        }
+        if record.prev_hash != self.expected_prev_hash {
+            return Err(broken("prev_hash does not match the previous entry".to_string()));
+        }
+        if compute_entry_hash(record) != record.entry_hash {
+            return Err(broken("entry_hash does not match the row contents".to_string()));
+        }
+
+        self.expected_seq += 1;
+        self.expected_prev_hash = record.entry_hash.clone();
+        self.entries_checked += 1;
+        Ok(())
+    }
+
+    pub fn entries_checked(&self) -> u64 {
+        self.entries_checked
+    }
+
+    pub fn last_seq(&self) -> i64 {
+        self.expected_seq - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(len: i64) -> Vec<ScoreLedgerRecord> {
+        let subject_id = uuid::Uuid::new_v4();
+        let mut prev_hash = GENESIS_HASH.to_string();
+        (1..=len)
+            .map(|seq| {
+                let mut record = ScoreLedgerRecord {
+                    seq,
+                    subject_id,
+                    scoring_result_id: None,
+                    score: 50.0 + seq as f64,
+                    previous_score: (seq > 1).then(|| 49.0 + seq as f64),
+                    model_version: "hardcoded-v1.0".to_string(),
+                    reason: "score_calculated".to_string(),
+                    created_at: chrono::DateTime::from_timestamp_micros(1_700_000_000_000_000 + seq)
+                        .unwrap(),
+                    prev_hash: prev_hash.clone(),
+                    entry_hash: String::new(),
+                };
+                record.entry_hash = compute_entry_hash(&record);
+                prev_hash = record.entry_hash.clone();
+                record
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_chain_verifies() {
+        let mut verifier = ChainVerifier::default();
+        for record in &chain(5) {
+            assert!(verifier.push(record).is_ok());
+        }
+        assert_eq!(verifier.entries_checked(), 5);
+        assert_eq!(verifier.last_seq(), 5);
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        // Rewritten score without recomputing the hash.
+        let mut records = chain(3);
+        records[1].score = 99.0;
+        let mut verifier = ChainVerifier::default();
+        assert!(verifier.push(&records[0]).is_ok());
+        assert_eq!(verifier.push(&records[1]).unwrap_err().seq, 2);
+
+        // Rewritten score with a recomputed hash breaks the next link.
+        let mut records = chain(3);
+        records[1].score = 99.0;
+        records[1].entry_hash = compute_entry_hash(&records[1]);
+        let mut verifier = ChainVerifier::default();
+        assert!(verifier.push(&records[0]).is_ok());
+        assert!(verifier.push(&records[1]).is_ok());
+        assert_eq!(verifier.push(&records[2]).unwrap_err().seq, 3);
+
+        // Deleted row.
+        let records = chain(3);
+        let mut verifier = ChainVerifier::default();
+        assert!(verifier.push(&records[0]).is_ok());
+        assert_eq!(verifier.push(&records[2]).unwrap_err().seq, 3);
+    }
+}
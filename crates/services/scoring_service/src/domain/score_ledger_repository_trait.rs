@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use crate::models::{ScoreLedgerForAppend, ScoreLedgerRecord};
+use crate::Result;
+
+#[async_trait]
+pub trait ScoreLedgerRepository: Send + Sync {
+    /// Append a score change, linking it to the current head of the chain.
+    async fn append(&self, entry: ScoreLedgerForAppend) -> Result<ScoreLedgerRecord>;
+    /// Rows with `seq > after_seq`, ascending, at most `limit`.
+    async fn list_after(&self, after_seq: i64, limit: i64) -> Result<Vec<ScoreLedgerRecord>>;
+}
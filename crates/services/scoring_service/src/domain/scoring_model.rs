@@ -1,31 +1,57 @@
+use async_trait::async_trait;
 use rand::Rng;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use crate::Result;
 
+/// Key behavior_service uses to wrap sampled and pre-aggregated payloads.
+const INGESTION_META_KEY: &str = "_ingestion";
+
+/// A model that turns a behavior input into a 0-100 score.
+#[async_trait]
+pub trait ScoringModel: Send + Sync {
+    /// Identifier recorded as `model_version` on every result it scores.
+    fn version(&self) -> &str;
+
+    /// Score a feature vector, as extracted by the configured feature set.
+    async fn calculate_score(&self, features: &Value) -> Result<f64>;
+
+    fn get_model_metadata(&self) -> Value;
+}
+
+/// The built-in rule-based model.
 pub struct HardcodedScoringModel {
     version: String,
 }
 
 impl HardcodedScoringModel {
     pub fn new() -> Self {
-        Self {
-            version: "hardcoded-v1.0".to_string(),
-        }
+        Self::with_version("hardcoded-v1.0")
     }
 
-    pub fn version(&self) -> &str {
+    /// The rules, registered under `version`.
+    pub fn with_version(version: impl Into<String>) -> Self {
+        Self { version: version.into() }
+    }
+
+    /// Numeric feature `name` of a feature vector, zero when missing.
+    fn feature(&self, features: &Value, name: &str) -> f64 {
+        features.get(name).and_then(Value::as_f64).unwrap_or(0.0)
+    }
+}
+
+#[async_trait]
+impl ScoringModel for HardcodedScoringModel {
+    fn version(&self) -> &str {
         &self.version
     }
 
-    pub async fn calculate_score(&self, behavior_data: &Value) -> Result<f64> {
+    async fn calculate_score(&self, features: &Value) -> Result<f64> {
         // Hardcoded scoring logic - replace with actual AI model later
-        
-        // Extract some mock features from behavior data
-        let feature_count = self.extract_feature_count(behavior_data)?;
-        let complexity_score = self.calculate_complexity(behavior_data)?;
-        
+        let feature_count = self.feature(features, "counts.field_count");
+        let complexity_score = (self.feature(features, "counts.nested_field_count") * 0.2).min(1.0);
+
         // Simple scoring formula - normalize to 0-100 range
-        let base_score = (feature_count as f64 * 10.0 + complexity_score * 50.0).min(100.0);
+        let base_score = (feature_count * 10.0 + complexity_score * 50.0).min(100.0);
         
         // Add some randomness to simulate model variance
         let mut rng = rand::thread_rng();
@@ -36,55 +62,48 @@ impl HardcodedScoringModel {
         Ok(final_score)
     }
 
-    fn extract_feature_count(&self, data: &Value) -> Result<usize> {
-        match data {
-            Value::Object(obj) => Ok(obj.len()),
-            Value::Array(arr) => Ok(arr.len()),
-            _ => Ok(1), // Single value counts as 1 feature
-        }
-    }
-
-    fn calculate_complexity(&self, data: &Value) -> Result<f64> {
-        let complexity = match data {
-            Value::Object(obj) => {
-                let nested_objects = obj.values()
-                    .filter(|v| matches!(v, Value::Object(_) | Value::Array(_)))
-                    .count();
-                (nested_objects as f64 * 0.2).min(1.0)
-            },
-            Value::Array(arr) => {
-                if arr.len() > 10 {
-                    0.8
-                } else if arr.len() > 5 {
-                    0.5
-                } else {
-                    0.2
-                }
-            },
-            Value::String(s) => {
-                if s.len() > 100 {
-                    0.6
-                } else {
-                    0.3
-                }
-            },
-            _ => 0.1,
-        };
-        
-        Ok(complexity)
-    }
-
-    pub fn get_model_metadata(&self) -> Value {
+    fn get_model_metadata(&self) -> Value {
         serde_json::json!({
             "model_type": "hardcoded",
             "version": self.version,
             "features": [
-                "feature_count",
-                "complexity_score",
+                "counts.field_count",
+                "counts.nested_field_count",
                 "randomness"
             ],
+            "ingestion_tiers": ["raw", "sampled", "aggregated"],
             "score_range": "0-100",
             "description": "Hardcoded scoring model for ZK-Persona proof of concept"
         })
     }
-}
\ No newline at end of file
+}
+
+/// Reduce sampled and aggregated payloads to a single representative
+/// event, so every ingestion tier is scored like a raw event:
+/// - sampled rows score their wrapped `event`
+/// - aggregated rows score the per-event mean of their `sums`
+pub(crate) fn unwrap_ingestion(data: &Value) -> Value {
+    let Some(meta) = data.get(INGESTION_META_KEY) else {
+        return data.clone();
+    };
+
+    match meta.get("tier").and_then(Value::as_str) {
+        Some("sampled") => data.get("event").cloned().unwrap_or(Value::Null),
+        Some("aggregated") => {
+            let events = meta.get("weight").and_then(Value::as_f64).unwrap_or(1.0).max(1.0);
+            let means: Map<String, Value> = data
+                .get("sums")
+                .and_then(Value::as_object)
+                .map(|sums| {
+                    sums.iter()
+                        .filter_map(|(k, v)| {
+                            Some((k.clone(), Value::from(v.as_f64()? / events)))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Value::Object(means)
+        }
+        _ => data.clone(),
+    }
+}
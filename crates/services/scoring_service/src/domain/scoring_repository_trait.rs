@@ -1,13 +1,30 @@
 use async_trait::async_trait;
 use jd_domain::Id;
 use jd_domain::zkpersona_domain::profile::ScoringResult;
-use crate::models::{requests::ScoringQueryRequest, responses::{ScoringResponse, ScoringListResponse}};
+use crate::models::{
+    requests::{ScoreHistoryQuery, ScoringQueryRequest},
+    responses::{ScoringResponse, ScoringListResponse},
+    ScoreHistoryRecord,
+};
 use crate::Result;
 
 #[async_trait]
 pub trait ScoringRepository: Send + Sync {
-    async fn create_scoring_result(&self, result: ScoringResult) -> Result<ScoringResponse>;
+    /// Store a score computed from feature snapshot `feature_snapshot_id`.
+    async fn create_scoring_result(
+        &self,
+        result: ScoringResult,
+        feature_snapshot_id: Option<uuid::Uuid>,
+    ) -> Result<ScoringResponse>;
     async fn get_scoring_result(&self, id: Id) -> Result<Option<ScoringResponse>>;
     async fn get_scoring_by_behavior_id(&self, behavior_input_id: Id) -> Result<Option<ScoringResponse>>;
     async fn list_scoring_results(&self, query: ScoringQueryRequest) -> Result<ScoringListResponse>;
-}
\ No newline at end of file
+    /// Scores of the account linking wallet `subject` in `query`'s window,
+    /// newest first, at most `limit`.
+    async fn list_subject_history(
+        &self,
+        subject: &str,
+        query: &ScoreHistoryQuery,
+        limit: i64,
+    ) -> Result<Vec<ScoreHistoryRecord>>;
+}
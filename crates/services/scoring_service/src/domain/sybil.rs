@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+use jd_utils::config::ScoringConfig;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::{Error, Result};
+
+/// Scope a bearer token needs to read users' sybil scores and set its own
+/// thresholds.
+pub const SCOPE_SCORING_SYBIL: &str = "scoring:sybil";
+
+/// Days of on-chain events and behavior inputs the signals are taken from.
+pub const SIGNAL_WINDOW_DAYS: i64 = 90;
+/// Latest inputs of the user compared for timing.
+pub const TIMING_SAMPLE: i64 = 200;
+/// Two users' inputs this close together count as simultaneous.
+pub const LOCKSTEP_WINDOW_SECS: f64 = 2.0;
+/// Timing is only judged with at least this many recent inputs.
+const MIN_TIMING_INPUTS: i64 = 10;
+
+const DEFAULT_REVIEW_AT: f64 = 40.0;
+const DEFAULT_BLOCK_AT: f64 = 70.0;
+
+/// What suggests a user is not acting alone.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct SybilSignals {
+    /// Other users whose wallets the user's wallet interacted with on-chain,
+    /// either way.
+    pub linked_wallets: i64,
+    /// Other users seen on one of the user's device fingerprints.
+    pub shared_device_users: i64,
+    /// The user's inputs in the timing sample.
+    pub recent_inputs: i64,
+    /// Largest share of the sample a single other user sent an input within
+    /// `LOCKSTEP_WINDOW_SECS` of.
+    pub lockstep_ratio: f64,
+    /// Coefficient of variation of the gaps between the sampled inputs; low
+    /// for scripted, evenly paced activity.
+    pub interval_variation: Option<f64>,
+}
+
+impl SybilSignals {
+    /// Sybil score from 0 (no sign of coordination) to 100.
+    pub fn score(&self) -> f64 {
+        let linked = self.linked_wallets.min(5) as f64 / 5.0 * 30.0;
+        let devices = self.shared_device_users.min(3) as f64 / 3.0 * 35.0;
+        let (lockstep, regularity) = if self.recent_inputs >= MIN_TIMING_INPUTS {
+            let lockstep = self.lockstep_ratio.clamp(0.0, 1.0) * 25.0;
+            let regularity = self
+                .interval_variation
+                .map(|cv| (1.0 - cv / 0.1).max(0.0) * 10.0)
+                .unwrap_or(0.0);
+            (lockstep, regularity)
+        } else {
+            (0.0, 0.0)
+        };
+        (linked + devices + lockstep + regularity).clamp(0.0, 100.0)
+    }
+}
+
+/// Row of `sybil_assessments`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SybilAssessment {
+    pub user_id: Uuid,
+    pub score: f64,
+    #[sqlx(json)]
+    pub signals: SybilSignals,
+    pub assessed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SybilVerdict {
+    Allow,
+    Review,
+    Block,
+}
+
+/// Sybil scores at which a partner reviews or blocks a user.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct SybilThresholds {
+    pub review_at: f64,
+    pub block_at: f64,
+}
+
+impl SybilThresholds {
+    /// `SCORING.SYBIL_REVIEW_AT` and `SCORING.SYBIL_BLOCK_AT`, for partners
+    /// that set none. Invalid settings fall back to the built-in defaults.
+    pub fn from_config(config: Option<&ScoringConfig>) -> Self {
+        let defaults = Self { review_at: DEFAULT_REVIEW_AT, block_at: DEFAULT_BLOCK_AT };
+        let configured = Self {
+            review_at: config.and_then(|c| c.sybil_review_at).unwrap_or(defaults.review_at),
+            block_at: config.and_then(|c| c.sybil_block_at).unwrap_or(defaults.block_at),
+        };
+        configured.validate().map(|_| configured).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring sybil thresholds: {}", e);
+            defaults
+        })
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=100.0).contains(&self.review_at) || !(0.0..=100.0).contains(&self.block_at) {
+            return Err(Error::InvalidInput("Sybil thresholds must be between 0 and 100".to_string()));
+        }
+        if self.review_at > self.block_at {
+            return Err(Error::InvalidInput("review_at must not be above block_at".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn verdict(&self, score: f64) -> SybilVerdict {
+        if score >= self.block_at {
+            SybilVerdict::Block
+        } else if score >= self.review_at {
+            SybilVerdict::Review
+        } else {
+            SybilVerdict::Allow
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinated_users_score_high() {
+        assert_eq!(SybilSignals::default().score(), 0.0);
+
+        let farm = SybilSignals {
+            linked_wallets: 8,
+            shared_device_users: 3,
+            recent_inputs: 50,
+            lockstep_ratio: 0.9,
+            interval_variation: Some(0.02),
+        };
+        assert!((farm.score() - 95.5).abs() < 1e-9);
+
+        // Too few inputs to judge timing by
+        let new_user = SybilSignals { recent_inputs: 3, lockstep_ratio: 1.0, ..farm.clone() };
+        assert_eq!(new_user.score(), 65.0);
+    }
+
+    #[test]
+    fn thresholds_decide_the_verdict() {
+        let thresholds = SybilThresholds { review_at: 40.0, block_at: 70.0 };
+        assert_eq!(thresholds.verdict(39.9), SybilVerdict::Allow);
+        assert_eq!(thresholds.verdict(40.0), SybilVerdict::Review);
+        assert_eq!(thresholds.verdict(70.0), SybilVerdict::Block);
+
+        assert!(SybilThresholds { review_at: 80.0, block_at: 70.0 }.validate().is_err());
+        assert!(SybilThresholds { review_at: 10.0, block_at: 120.0 }.validate().is_err());
+    }
+}
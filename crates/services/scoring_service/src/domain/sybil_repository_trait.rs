@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::sybil::{SybilAssessment, SybilSignals, SybilThresholds};
+use crate::Result;
+
+#[async_trait]
+pub trait SybilRepository: Send + Sync {
+    /// The user with wallet `wallet_address`.
+    async fn find_user(&self, wallet_address: &str) -> Result<Option<Uuid>>;
+    /// The user behavior input `behavior_input_id` was recorded for, if any.
+    async fn input_user(&self, behavior_input_id: Uuid) -> Result<Option<Uuid>>;
+    /// Current signals of user `user_id`.
+    async fn load_signals(&self, user_id: Uuid) -> Result<SybilSignals>;
+    /// Replace the user's stored assessment.
+    async fn save_assessment(&self, user_id: Uuid, score: f64, signals: &SybilSignals) -> Result<SybilAssessment>;
+    async fn find_assessment(&self, user_id: Uuid) -> Result<Option<SybilAssessment>>;
+    /// Latest persona score of the user's behavior inputs.
+    async fn latest_persona_score(&self, user_id: Uuid) -> Result<Option<f64>>;
+    async fn find_thresholds(&self, partner: &str) -> Result<Option<SybilThresholds>>;
+    async fn save_thresholds(&self, partner: &str, thresholds: &SybilThresholds) -> Result<SybilThresholds>;
+}
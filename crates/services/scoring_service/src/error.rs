@@ -1,32 +1,66 @@
 use axum::{http::StatusCode, response::IntoResponse, Json};
 use serde_json::json;
 
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Debug, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "scoring")]
 pub enum Error {
     #[error("Invalid scoring input: {0}")]
+    #[taxonomy(kind = Validation, expose)]
     InvalidInput(String),
     
     #[error("Scoring model error: {0}")]
+    #[taxonomy(kind = Validation, expose)]
     ModelError(String),
     
+    #[error("Scoring model not found: {0}")]
+    #[taxonomy(kind = NotFound, expose)]
+    ModelNotFound(String),
+    
+    #[error("Scoring model unavailable: {0}")]
+    #[taxonomy(kind = Unavailable, expose)]
+    ModelUnavailable(String),
+    
+    #[error("Recompute job not found: {0}")]
+    #[taxonomy(kind = NotFound, expose)]
+    RecomputeJobNotFound(uuid::Uuid),
+    
+    #[error("Subject not found: {0}")]
+    #[taxonomy(kind = NotFound, expose)]
+    SubjectNotFound(String),
+    
     #[error("Database error: {0}")]
+    #[taxonomy(kind = Internal, code = "DATABASE_ERROR")]
     Database(#[from] sqlx::Error),
     
     #[error("Serialization error: {0}")]
+    #[taxonomy(kind = Validation, message = "Invalid data format")]
     Serialization(#[from] serde_json::Error),
     
     #[error("Internal error: {0}")]
+    #[taxonomy(kind = Internal)]
     Internal(String),
     
     #[error("Core error: {0}")]
+    #[taxonomy(kind = Internal, code = "CORE_ERROR")]
     Core(#[from] jd_core::Error),
 }
 
+/// Malformed ids from requests are the caller's mistake.
+impl From<jd_domain::Error> for Error {
+    fn from(err: jd_domain::Error) -> Self {
+        Error::InvalidInput(err.to_string())
+    }
+}
+
 impl IntoResponse for Error {
     fn into_response(self) -> axum::response::Response {
         let (status, error_message) = match self {
             Error::InvalidInput(msg) => (StatusCode::BAD_REQUEST, msg),
             Error::ModelError(msg) => (StatusCode::UNPROCESSABLE_ENTITY, msg),
+            Error::ModelNotFound(msg) => (StatusCode::NOT_FOUND, format!("Scoring model not found: {}", msg)),
+            Error::ModelUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
+            Error::RecomputeJobNotFound(id) => (StatusCode::NOT_FOUND, format!("Recompute job {} not found", id)),
+            Error::SubjectNotFound(subject) => (StatusCode::NOT_FOUND, format!("No user with wallet {}", subject)),
             Error::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()),
             Error::Serialization(_) => (StatusCode::BAD_REQUEST, "Invalid data format".to_string()),
             Error::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()),
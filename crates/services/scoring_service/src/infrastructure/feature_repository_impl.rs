@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use jd_core::AppState;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        feature_extraction::{FeatureSignals, FeatureSnapshot, ONCHAIN_WINDOW_DAYS},
+        feature_repository_trait::FeatureRepository,
+    },
+    Result,
+};
+
+const SNAPSHOT_COLUMNS: &str = "id, behavior_input_id, feature_version, features, created_at";
+
+#[derive(Clone)]
+pub struct FeatureRepositoryImpl {
+    app_state: AppState,
+}
+
+impl FeatureRepositoryImpl {
+    pub fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+}
+
+#[async_trait]
+impl FeatureRepository for FeatureRepositoryImpl {
+    async fn load_signals(&self, behavior_input_id: Uuid) -> Result<Option<FeatureSignals>> {
+        // Everything is as of the input's own timestamp, so rescoring an old
+        // input sees the context it was recorded in.
+        let signals = sqlx::query_as::<_, FeatureSignals>(
+            r#"
+            SELECT b.timestamp AS recorded_at,
+                   b.sample_weight,
+                   (SELECT COUNT(*)
+                    FROM behavior_inputs s
+                    WHERE s.session_id = b.session_id AND s.timestamp < b.timestamp) AS session_inputs,
+                   (SELECT MIN(s.timestamp)
+                    FROM behavior_inputs s
+                    WHERE s.session_id = b.session_id) AS session_started_at,
+                   (SELECT MAX(p.timestamp)
+                    FROM behavior_inputs p
+                    WHERE p.timestamp < b.timestamp
+                      AND CASE WHEN b.user_id IS NOT NULL THEN p.user_id = b.user_id
+                               ELSE p.session_id = b.session_id END) AS previous_input_at,
+                   u.wallet_address,
+                   (SELECT COUNT(*)
+                    FROM onchain_events e
+                    WHERE e.sender IN (SELECT LOWER(w.address) FROM user_wallets w WHERE w.user_id = b.user_id)
+                      AND e.occurred_at > b.timestamp - make_interval(days => $2)
+                      AND e.occurred_at <= b.timestamp) AS onchain_events,
+                   (SELECT MAX(e.occurred_at)
+                    FROM onchain_events e
+                    WHERE e.sender IN (SELECT LOWER(w.address) FROM user_wallets w WHERE w.user_id = b.user_id)
+                      AND e.occurred_at <= b.timestamp) AS onchain_last_event_at,
+                   COALESCE(r.wallet_count, 0) AS linked_wallets,
+                   COALESCE(r.github_identity_count, 0) AS linked_github_identities,
+                   r.reputation_score::FLOAT8 AS account_reputation_score,
+                   r.coding_reputation_score::FLOAT8 AS coding_reputation_score,
+                   r.security_awareness_score::FLOAT8 AS security_awareness_score,
+                   r.community_trust_score::FLOAT8 AS community_trust_score,
+                   COALESCE(r.total_contributions, 0) AS total_contributions
+            FROM behavior_inputs b
+            LEFT JOIN users u ON u.id = b.user_id
+            LEFT JOIN account_reputation r ON r.user_id = b.user_id
+            WHERE b.id = $1
+            "#,
+        )
+        .bind(behavior_input_id)
+        .bind(ONCHAIN_WINDOW_DAYS as i32)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(signals)
+    }
+
+    async fn find_snapshot(
+        &self,
+        behavior_input_id: Uuid,
+        feature_version: &str,
+    ) -> Result<Option<FeatureSnapshot>> {
+        let snapshot = sqlx::query_as::<_, FeatureSnapshot>(&format!(
+            "SELECT {} FROM feature_snapshots WHERE behavior_input_id = $1 AND feature_version = $2",
+            SNAPSHOT_COLUMNS
+        ))
+        .bind(behavior_input_id)
+        .bind(feature_version)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(snapshot)
+    }
+
+    async fn save_snapshot(
+        &self,
+        behavior_input_id: Uuid,
+        feature_version: &str,
+        features: &Value,
+    ) -> Result<FeatureSnapshot> {
+        // The no-op update makes the existing row come back on conflict
+        let snapshot = sqlx::query_as::<_, FeatureSnapshot>(&format!(
+            r#"
+            INSERT INTO feature_snapshots (behavior_input_id, feature_version, features)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (behavior_input_id, feature_version)
+            DO UPDATE SET features = feature_snapshots.features
+            RETURNING {}
+            "#,
+            SNAPSHOT_COLUMNS
+        ))
+        .bind(behavior_input_id)
+        .bind(feature_version)
+        .bind(features)
+        .fetch_one(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(snapshot)
+    }
+}
@@ -1 +1,7 @@
-pub mod scoring_repository_impl;
\ No newline at end of file
+pub mod scoring_repository_impl;
+pub mod score_ledger_repository_impl;
+pub mod model_registry_repository_impl;
+pub mod onnx_scoring_model;
+pub mod recompute_job_repository_impl;
+pub mod feature_repository_impl;
+pub mod sybil_repository_impl;
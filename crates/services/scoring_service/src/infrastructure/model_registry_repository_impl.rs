@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use jd_core::AppState;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        model_registry::{NewScoringModel, RegisteredModel},
+        model_registry_repository_trait::ModelRegistryRepository,
+    },
+    Result,
+};
+
+const MODEL_COLUMNS: &str = "id, name, version, model_version, kind, artifact_uri, input_schema, \
+     active, registered_by, created_at, activated_at";
+
+#[derive(Clone)]
+pub struct ModelRegistryRepositoryImpl {
+    app_state: AppState,
+}
+
+impl ModelRegistryRepositoryImpl {
+    pub fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+}
+
+#[async_trait]
+impl ModelRegistryRepository for ModelRegistryRepositoryImpl {
+    async fn register(&self, model: NewScoringModel) -> Result<Option<RegisteredModel>> {
+        let registered = sqlx::query_as::<_, RegisteredModel>(&format!(
+            r#"
+            INSERT INTO scoring_models (name, version, kind, artifact_uri, input_schema, registered_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT DO NOTHING
+            RETURNING {}
+            "#,
+            MODEL_COLUMNS
+        ))
+        .bind(&model.name)
+        .bind(&model.version)
+        .bind(model.kind)
+        .bind(&model.artifact_uri)
+        .bind(Json(&model.input_schema))
+        .bind(&model.registered_by)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(registered)
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<RegisteredModel>> {
+        let model = sqlx::query_as::<_, RegisteredModel>(&format!(
+            "SELECT {} FROM scoring_models WHERE id = $1",
+            MODEL_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(model)
+    }
+
+    async fn get_by_model_version(&self, model_version: &str) -> Result<Option<RegisteredModel>> {
+        let model = sqlx::query_as::<_, RegisteredModel>(&format!(
+            "SELECT {} FROM scoring_models WHERE model_version = $1",
+            MODEL_COLUMNS
+        ))
+        .bind(model_version)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(model)
+    }
+
+    async fn active(&self) -> Result<Option<RegisteredModel>> {
+        let model = sqlx::query_as::<_, RegisteredModel>(&format!(
+            "SELECT {} FROM scoring_models WHERE active",
+            MODEL_COLUMNS
+        ))
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(model)
+    }
+
+    async fn list(&self) -> Result<Vec<RegisteredModel>> {
+        let models = sqlx::query_as::<_, RegisteredModel>(&format!(
+            "SELECT {} FROM scoring_models ORDER BY created_at DESC",
+            MODEL_COLUMNS
+        ))
+        .fetch_all(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(models)
+    }
+
+    async fn activate(&self, id: Uuid) -> Result<Option<RegisteredModel>> {
+        let mut tx = self.app_state.mm().dbx().db().begin().await?;
+
+        // Deactivate first: the partial unique index allows one active row
+        sqlx::query("UPDATE scoring_models SET active = FALSE WHERE active AND id <> $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        let model = sqlx::query_as::<_, RegisteredModel>(&format!(
+            r#"
+            UPDATE scoring_models
+            SET active = TRUE,
+                activated_at = CASE WHEN active THEN activated_at ELSE NOW() END
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            MODEL_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        // Keep the current model active when `id` does not exist
+        if model.is_some() {
+            tx.commit().await?;
+        }
+        Ok(model)
+    }
+}
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use ort::session::Session;
+use ort::value::Tensor;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::domain::model_registry::RegisteredModel;
+use crate::domain::scoring_model::ScoringModel;
+use crate::{Error, Result};
+
+/// A registered ONNX model, run with ONNX Runtime. It takes the features
+/// its input schema names as one `[1, n]` float tensor and its first output
+/// is the score.
+pub struct OnnxScoringModel {
+    version: String,
+    artifact_uri: String,
+    features: Vec<String>,
+    session: Arc<Mutex<Session>>,
+}
+
+impl OnnxScoringModel {
+    /// Load the model's artifact, a local `file://` URI or path.
+    pub fn load(model: &RegisteredModel) -> Result<Self> {
+        let artifact_uri = model
+            .artifact_uri
+            .clone()
+            .ok_or_else(|| Error::ModelUnavailable(format!("{} has no artifact", model.model_version)))?;
+        let path = artifact_path(&artifact_uri)?;
+        let session = Session::builder()
+            .and_then(|builder| builder.commit_from_file(&path))
+            .map_err(|e| Error::ModelUnavailable(format!("Failed to load {}: {}", artifact_uri, e)))?;
+        Ok(Self {
+            version: model.model_version.clone(),
+            artifact_uri,
+            features: model.input_schema.features.clone(),
+            session: Arc::new(Mutex::new(session)),
+        })
+    }
+
+    /// The named entries of the feature vector, missing or non-numeric ones
+    /// as zero.
+    fn features(&self, features: &Value) -> Vec<f32> {
+        self.features
+            .iter()
+            .map(|name| match features.get(name) {
+                Some(Value::Number(number)) => number.as_f64().unwrap_or(0.0) as f32,
+                Some(Value::Bool(flag)) => f32::from(u8::from(*flag)),
+                _ => 0.0,
+            })
+            .collect()
+    }
+}
+
+/// Path of a `file://` URI or plain path; models are loaded from local
+/// files only.
+pub fn artifact_path(artifact_uri: &str) -> Result<PathBuf> {
+    match artifact_uri.split_once("://") {
+        None => Ok(PathBuf::from(artifact_uri)),
+        Some(("file", path)) => Ok(PathBuf::from(path)),
+        Some((scheme, _)) => Err(Error::InvalidInput(format!("Unsupported artifact URI scheme: {}", scheme))),
+    }
+}
+
+fn run(session: &Mutex<Session>, features: Vec<f32>) -> Result<f64> {
+    let model_error = |e: ort::Error| Error::ModelError(e.to_string());
+    let input = Tensor::from_array(([1usize, features.len()], features)).map_err(model_error)?;
+    let mut session = session
+        .lock()
+        .map_err(|_| Error::Internal("ONNX session lock poisoned".to_string()))?;
+    let outputs = session.run(ort::inputs![input]).map_err(model_error)?;
+    let (_, scores) = outputs[0].try_extract_tensor::<f32>().map_err(model_error)?;
+    scores
+        .first()
+        .map(|score| f64::from(*score))
+        .ok_or_else(|| Error::ModelError("ONNX model returned no score".to_string()))
+}
+
+#[async_trait]
+impl ScoringModel for OnnxScoringModel {
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    async fn calculate_score(&self, features: &Value) -> Result<f64> {
+        let features = self.features(features);
+        let session = self.session.clone();
+        let score = tokio::task::spawn_blocking(move || run(&session, features))
+            .await
+            .map_err(|e| Error::Internal(format!("ONNX inference task failed: {}", e)))??;
+        if !score.is_finite() {
+            return Err(Error::ModelError(format!("{} returned a non-finite score", self.version)));
+        }
+        Ok(score.clamp(0.0, 100.0))
+    }
+
+    fn get_model_metadata(&self) -> Value {
+        serde_json::json!({
+            "model_type": "onnx",
+            "version": self.version,
+            "artifact_uri": self.artifact_uri,
+            "features": self.features,
+            "ingestion_tiers": ["raw", "sampled", "aggregated"],
+            "score_range": "0-100",
+            "description": "ONNX model run with ONNX Runtime"
+        })
+    }
+}
@@ -0,0 +1,289 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use jd_core::AppState;
+use sqlx::types::Json;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        recompute_job::{RecomputeCandidate, RecomputeFilter, RecomputeJob},
+        recompute_job_repository_trait::RecomputeJobRepository,
+    },
+    infrastructure::score_ledger_repository_impl::ScoreLedgerRepositoryImpl,
+    models::ScoreLedgerForAppend,
+    Result,
+};
+
+const JOB_COLUMNS: &str = "id, requested_by, filter, target_model_version, status, total_count, \
+     processed_count, failed_count, cursor, attempts, error, created_at, started_at, updated_at, finished_at";
+
+/// Behavior inputs a filter selects. `$1` is the cutoff scores must predate;
+/// `$2` to `$7` are the filter's fields in `bind_filter` order.
+const CANDIDATES: &str = r#"
+    behavior_inputs b
+    LEFT JOIN users u ON u.id = b.user_id
+    WHERE EXISTS (
+            SELECT 1
+            FROM scoring_results s
+            WHERE s.behavior_input_id = b.id
+              AND s.timestamp <= $1
+              AND ($2::TIMESTAMPTZ IS NULL OR s.timestamp >= $2)
+              AND ($3::TIMESTAMPTZ IS NULL OR s.timestamp < $3)
+              AND ($4::TEXT IS NULL OR s.model_version = $4)
+        )
+      AND (cardinality($5::UUID[]) = 0 OR b.user_id = ANY($5))
+      AND ($6::TIMESTAMPTZ IS NULL OR u.ctime >= $6)
+      AND ($7::TIMESTAMPTZ IS NULL OR u.ctime < $7)
+"#;
+
+/// The filter's fields, flattened for binding as `$2` to `$7`.
+struct FilterBinds {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    model_version: Option<String>,
+    user_ids: Vec<Uuid>,
+    joined_from: Option<DateTime<Utc>>,
+    joined_to: Option<DateTime<Utc>>,
+}
+
+fn bind_filter(filter: &RecomputeFilter) -> FilterBinds {
+    let cohort = filter.cohort.clone().unwrap_or_default();
+    FilterBinds {
+        from: filter.from,
+        to: filter.to,
+        model_version: filter.model_version.clone(),
+        user_ids: cohort.user_ids,
+        joined_from: cohort.joined_from,
+        joined_to: cohort.joined_to,
+    }
+}
+
+#[derive(Clone)]
+pub struct RecomputeJobRepositoryImpl {
+    app_state: AppState,
+}
+
+impl RecomputeJobRepositoryImpl {
+    pub fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+}
+
+#[async_trait]
+impl RecomputeJobRepository for RecomputeJobRepositoryImpl {
+    async fn enqueue(
+        &self,
+        requested_by: Option<&str>,
+        filter: &RecomputeFilter,
+        target_model_version: &str,
+    ) -> Result<RecomputeJob> {
+        let binds = bind_filter(filter);
+        let job = sqlx::query_as::<_, RecomputeJob>(&format!(
+            r#"
+            INSERT INTO score_recompute_jobs (requested_by, filter, target_model_version, total_count, created_at)
+            SELECT $8, $9, $10, COUNT(*), $1
+            FROM {}
+            RETURNING {}
+            "#,
+            CANDIDATES, JOB_COLUMNS
+        ))
+        .bind(Utc::now())
+        .bind(binds.from)
+        .bind(binds.to)
+        .bind(binds.model_version)
+        .bind(binds.user_ids)
+        .bind(binds.joined_from)
+        .bind(binds.joined_to)
+        .bind(requested_by)
+        .bind(Json(filter))
+        .bind(target_model_version)
+        .fetch_one(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(job)
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<RecomputeJob>> {
+        let job = sqlx::query_as::<_, RecomputeJob>(&format!(
+            "SELECT {} FROM score_recompute_jobs WHERE id = $1",
+            JOB_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(job)
+    }
+
+    async fn claim_next(&self, lease: Duration, max_attempts: i32) -> Result<Option<RecomputeJob>> {
+        let job = sqlx::query_as::<_, RecomputeJob>(&format!(
+            r#"
+            UPDATE score_recompute_jobs j
+            SET status = 'running',
+                attempts = j.attempts + 1,
+                lease_until = NOW() + make_interval(secs => $1),
+                started_at = COALESCE(j.started_at, NOW()),
+                updated_at = NOW()
+            FROM (
+                SELECT id AS next_id
+                FROM score_recompute_jobs
+                WHERE attempts < $2
+                  AND (status = 'queued' OR (status = 'running' AND lease_until < NOW()))
+                ORDER BY created_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            ) next
+            WHERE j.id = next.next_id
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(lease.as_secs_f64())
+        .bind(max_attempts)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(job)
+    }
+
+    async fn fail_abandoned(&self, max_attempts: i32) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE score_recompute_jobs
+            SET status = 'failed',
+                error = COALESCE(error, 'Recompute worker did not finish'),
+                lease_until = NULL,
+                finished_at = NOW(),
+                updated_at = NOW()
+            WHERE status = 'running' AND lease_until < NOW() AND attempts >= $1
+            "#,
+        )
+        .bind(max_attempts)
+        .execute(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn next_batch(&self, job: &RecomputeJob, limit: i64) -> Result<Vec<RecomputeCandidate>> {
+        let binds = bind_filter(&job.filter);
+        let batch = sqlx::query_as::<_, RecomputeCandidate>(&format!(
+            r#"
+            SELECT b.id AS behavior_input_id, b.input_data
+            FROM {}
+              AND ($8::UUID IS NULL OR b.id > $8)
+            ORDER BY b.id
+            LIMIT $9
+            "#,
+            CANDIDATES
+        ))
+        .bind(job.created_at)
+        .bind(binds.from)
+        .bind(binds.to)
+        .bind(binds.model_version)
+        .bind(binds.user_ids)
+        .bind(binds.joined_from)
+        .bind(binds.joined_to)
+        .bind(job.cursor)
+        .bind(limit)
+        .fetch_all(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(batch)
+    }
+
+    async fn record_score(
+        &self,
+        job_id: Uuid,
+        behavior_input_id: Uuid,
+        score: f64,
+        model_version: &str,
+        feature_snapshot_id: Option<Uuid>,
+    ) -> Result<()> {
+        let mut tx = self.app_state.mm().dbx().db().begin().await?;
+
+        let (scoring_result_id, score): (Uuid, f64) = sqlx::query_as(
+            r#"
+            INSERT INTO scoring_results (behavior_input_id, score, model_version, recompute_job_id, feature_snapshot_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, score::FLOAT8
+            "#,
+        )
+        .bind(behavior_input_id)
+        .bind(score)
+        .bind(model_version)
+        .bind(job_id)
+        .bind(feature_snapshot_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Recomputed scores are mirrored into the ledger like any other
+        ScoreLedgerRepositoryImpl::append_in(
+            &mut tx,
+            ScoreLedgerForAppend {
+                subject_id: behavior_input_id,
+                scoring_result_id: Some(scoring_result_id),
+                score,
+                model_version: model_version.to_string(),
+                reason: "score_recomputed".to_string(),
+            },
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn advance(
+        &self,
+        job_id: Uuid,
+        cursor: Uuid,
+        processed: i64,
+        failed: i64,
+        lease: Duration,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE score_recompute_jobs
+            SET cursor = $2,
+                processed_count = processed_count + $3,
+                failed_count = failed_count + $4,
+                lease_until = NOW() + make_interval(secs => $5),
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(cursor)
+        .bind(processed)
+        .bind(failed)
+        .bind(lease.as_secs_f64())
+        .execute(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(())
+    }
+
+    async fn complete(&self, job_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE score_recompute_jobs
+            SET status = 'completed', lease_until = NULL, error = NULL, finished_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .execute(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(())
+    }
+
+    async fn fail(&self, job_id: Uuid, error: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE score_recompute_jobs
+            SET status = 'failed', lease_until = NULL, error = $2, finished_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(error)
+        .execute(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(())
+    }
+}
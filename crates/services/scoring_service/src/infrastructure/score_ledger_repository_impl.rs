@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use jd_core::AppState;
+use jd_messaging::{events::ScoreUpdated, outbox};
+use sqlx::PgConnection;
+
+use crate::{
+    domain::{
+        score_ledger::{GENESIS_HASH, compute_entry_hash},
+        score_ledger_repository_trait::ScoreLedgerRepository,
+    },
+    models::{ScoreLedgerForAppend, ScoreLedgerRecord},
+    Error, Result,
+};
+
+/// Advisory lock key serializing appends so `seq` stays gapless and every row
+/// links to the true head of the chain.
+const SCORE_LEDGER_LOCK_KEY: i64 = 0x5C0_1ED6E;
+
+#[derive(Clone)]
+pub struct ScoreLedgerRepositoryImpl {
+    app_state: AppState,
+}
+
+impl ScoreLedgerRepositoryImpl {
+    pub fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+
+    /// Append `entry` on `tx`, the transaction that wrote the score it
+    /// records, so the two commit or roll back together.
+    pub(crate) async fn append_in(
+        tx: &mut PgConnection,
+        entry: ScoreLedgerForAppend,
+    ) -> Result<ScoreLedgerRecord> {
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(SCORE_LEDGER_LOCK_KEY)
+            .execute(&mut *tx)
+            .await?;
+
+        let head: Option<(i64, String)> =
+            sqlx::query_as("SELECT seq, entry_hash FROM score_ledger ORDER BY seq DESC LIMIT 1")
+                .fetch_optional(&mut *tx)
+                .await?;
+        let (seq, prev_hash) = match head {
+            Some((seq, entry_hash)) => (seq + 1, entry_hash),
+            None => (1, GENESIS_HASH.to_string()),
+        };
+
+        let previous_score: Option<f64> = sqlx::query_scalar(
+            "SELECT score FROM score_ledger WHERE subject_id = $1 ORDER BY seq DESC LIMIT 1",
+        )
+        .bind(entry.subject_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        // Postgres stores microseconds; truncate up front so the hash computed
+        // here matches the one recomputed from the stored row.
+        let created_at = DateTime::<Utc>::from_timestamp_micros(Utc::now().timestamp_micros())
+            .ok_or_else(|| Error::Internal("Invalid ledger timestamp".to_string()))?;
+
+        let mut record = ScoreLedgerRecord {
+            seq,
+            subject_id: entry.subject_id,
+            scoring_result_id: entry.scoring_result_id,
+            score: entry.score,
+            previous_score,
+            model_version: entry.model_version,
+            reason: entry.reason,
+            created_at,
+            prev_hash,
+            entry_hash: String::new(),
+        };
+        record.entry_hash = compute_entry_hash(&record);
+
+        sqlx::query(
+            "INSERT INTO score_ledger \
+             (seq, subject_id, scoring_result_id, score, previous_score, model_version, reason, \
+              created_at, prev_hash, entry_hash) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        )
+        .bind(record.seq)
+        .bind(record.subject_id)
+        .bind(record.scoring_result_id)
+        .bind(record.score)
+        .bind(record.previous_score)
+        .bind(&record.model_version)
+        .bind(&record.reason)
+        .bind(record.created_at)
+        .bind(&record.prev_hash)
+        .bind(&record.entry_hash)
+        .execute(&mut *tx)
+        .await?;
+
+        let event = ScoreUpdated {
+            subject_id: record.subject_id,
+            scoring_result_id: record.scoring_result_id,
+            score: record.score,
+            previous_score: record.previous_score,
+            model_version: record.model_version.clone(),
+            reason: record.reason.clone(),
+        };
+        outbox::record(&mut *tx, &event).await?;
+
+        Ok(record)
+    }
+}
+
+#[async_trait]
+impl ScoreLedgerRepository for ScoreLedgerRepositoryImpl {
+    async fn append(&self, entry: ScoreLedgerForAppend) -> Result<ScoreLedgerRecord> {
+        let mut tx = self.app_state.mm().dbx().db().begin().await?;
+        let record = Self::append_in(&mut tx, entry).await?;
+        tx.commit().await?;
+        Ok(record)
+    }
+
+    async fn list_after(&self, after_seq: i64, limit: i64) -> Result<Vec<ScoreLedgerRecord>> {
+        let records = sqlx::query_as::<_, ScoreLedgerRecord>(
+            "SELECT * FROM score_ledger WHERE seq > $1 ORDER BY seq ASC LIMIT $2",
+        )
+        .bind(after_seq)
+        .bind(limit)
+        .fetch_all(self.app_state.mm().dbx().db())
+        .await?;
+
+        Ok(records)
+    }
+}
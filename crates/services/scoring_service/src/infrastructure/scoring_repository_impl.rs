@@ -6,10 +6,11 @@ use jd_domain::zkpersona_domain::profile::ScoringResult;
 use crate::{
     ScoringResultDmc,
     domain::scoring_repository_trait::ScoringRepository,
+    infrastructure::score_ledger_repository_impl::ScoreLedgerRepositoryImpl,
     models::{
-        requests::ScoringQueryRequest,
+        requests::{ScoreHistoryQuery, ScoringQueryRequest},
         responses::{ScoringResponse, ScoringListResponse},
-        ScoringResultRecord, ScoringResultForCreate, ScoringResultFilter,
+        ScoreHistoryRecord, ScoreLedgerForAppend, ScoringResultRecord, ScoringResultFilter,
     },
     Result,
 };
@@ -27,20 +28,42 @@ impl ScoringRepositoryImpl {
 
 #[async_trait]
 impl ScoringRepository for ScoringRepositoryImpl {
-    async fn create_scoring_result(&self, result: ScoringResult) -> Result<ScoringResponse> {
-        let behavior_input_uuid = result.behavior_input_id.to_uuid();
-            
-        let create_req = ScoringResultForCreate {
-            behavior_input_id: behavior_input_uuid,
-            score: result.score,
-            model_version: result.model_version,
-        };
-        
-        let record = base::rest::create::<ScoringResultDmc, _, ScoringResultRecord>(
-            &self.app_state.mm, 
-            create_req
-        ).await?;
-            
+    async fn create_scoring_result(
+        &self,
+        result: ScoringResult,
+        feature_snapshot_id: Option<uuid::Uuid>,
+    ) -> Result<ScoringResponse> {
+        let mut tx = self.app_state.mm().dbx().db().begin().await?;
+
+        let record = sqlx::query_as::<_, ScoringResultRecord>(
+            r#"
+            INSERT INTO scoring_results (behavior_input_id, score, model_version, feature_snapshot_id)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, behavior_input_id, score::FLOAT8 AS score, model_version, timestamp
+            "#,
+        )
+        .bind(result.behavior_input_id.to_uuid())
+        .bind(result.score)
+        .bind(&result.model_version)
+        .bind(feature_snapshot_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Every persisted score is mirrored into the hash-chained ledger, in
+        // the same transaction so neither is kept without the other.
+        ScoreLedgerRepositoryImpl::append_in(
+            &mut tx,
+            ScoreLedgerForAppend {
+                subject_id: record.behavior_input_id,
+                scoring_result_id: Some(record.id),
+                score: record.score,
+                model_version: record.model_version.clone(),
+                reason: "score_calculated".to_string(),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
         Ok(ScoringResponse::from(record))
     }
 
@@ -127,4 +150,38 @@ impl ScoringRepository for ScoringRepositoryImpl {
             offset,
         })
     }
+
+    async fn list_subject_history(
+        &self,
+        subject: &str,
+        query: &ScoreHistoryQuery,
+        limit: i64,
+    ) -> Result<Vec<ScoreHistoryRecord>> {
+        let records = sqlx::query_as::<_, ScoreHistoryRecord>(
+            r#"
+            SELECT s.id AS scoring_result_id,
+                   s.score::FLOAT8 AS score,
+                   s.model_version,
+                   s.timestamp AS scored_at,
+                   s.recompute_job_id,
+                   b.id AS behavior_input_id,
+                   b.session_id,
+                   b.input_data
+            FROM scoring_results s
+            JOIN behavior_inputs b ON b.id = s.behavior_input_id
+            WHERE b.user_id IN (SELECT user_id FROM user_wallets WHERE address IN ($1, LOWER($1)))
+              AND ($2::TIMESTAMPTZ IS NULL OR s.timestamp >= $2)
+              AND ($3::TIMESTAMPTZ IS NULL OR s.timestamp < $3)
+            ORDER BY s.timestamp DESC, s.id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(subject)
+        .bind(query.from)
+        .bind(query.to)
+        .bind(limit)
+        .fetch_all(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(records)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use jd_core::AppState;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        sybil::{
+            SybilAssessment, SybilSignals, SybilThresholds, LOCKSTEP_WINDOW_SECS, SIGNAL_WINDOW_DAYS,
+            TIMING_SAMPLE,
+        },
+        sybil_repository_trait::SybilRepository,
+    },
+    Result,
+};
+
+#[derive(Clone)]
+pub struct SybilRepositoryImpl {
+    app_state: AppState,
+}
+
+impl SybilRepositoryImpl {
+    pub fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+}
+
+#[async_trait]
+impl SybilRepository for SybilRepositoryImpl {
+    async fn find_user(&self, wallet_address: &str) -> Result<Option<Uuid>> {
+        let user_id = sqlx::query_scalar("SELECT id FROM users WHERE LOWER(wallet_address) = LOWER($1)")
+            .bind(wallet_address)
+            .fetch_optional(self.app_state.mm().dbx().db())
+            .await?;
+        Ok(user_id)
+    }
+
+    async fn input_user(&self, behavior_input_id: Uuid) -> Result<Option<Uuid>> {
+        let user_id: Option<Option<Uuid>> = sqlx::query_scalar("SELECT user_id FROM behavior_inputs WHERE id = $1")
+            .bind(behavior_input_id)
+            .fetch_optional(self.app_state.mm().dbx().db())
+            .await?;
+        Ok(user_id.flatten())
+    }
+
+    async fn load_signals(&self, user_id: Uuid) -> Result<SybilSignals> {
+        let signals = sqlx::query_as::<_, SybilSignals>(
+            r#"
+            WITH target AS (
+                SELECT id, LOWER(wallet_address) AS wallet FROM users WHERE id = $1
+            ),
+            sample AS (
+                SELECT timestamp
+                FROM behavior_inputs
+                WHERE user_id = $1 AND timestamp > NOW() - make_interval(days => $2)
+                ORDER BY timestamp DESC
+                LIMIT $3
+            ),
+            counterparties AS (
+                -- Registered wallets named in events the user's wallet sent
+                SELECT o.id
+                FROM target t
+                JOIN onchain_events e
+                  ON e.sender = t.wallet AND e.occurred_at > NOW() - make_interval(days => $2)
+                CROSS JOIN LATERAL jsonb_path_query(e.parsed_json, 'strict $.**') AS v(value)
+                JOIN users o ON LOWER(o.wallet_address) = LOWER(v.value #>> '{}') AND o.id <> t.id
+                WHERE jsonb_typeof(v.value) = 'string'
+                UNION
+                -- Registered wallets that sent events naming the user's
+                SELECT o.id
+                FROM target t
+                JOIN onchain_events e
+                  ON e.occurred_at > NOW() - make_interval(days => $2)
+                 AND jsonb_path_exists(e.parsed_json, '$.** ? (@ == $w)', jsonb_build_object('w', t.wallet))
+                JOIN users o ON LOWER(o.wallet_address) = e.sender AND o.id <> t.id
+            ),
+            gaps AS (
+                SELECT EXTRACT(EPOCH FROM timestamp - LAG(timestamp) OVER (ORDER BY timestamp))::FLOAT8 AS gap
+                FROM sample
+            )
+            SELECT (SELECT COUNT(*) FROM counterparties) AS linked_wallets,
+                   (SELECT COUNT(DISTINCT o.user_id)
+                    FROM behavior_inputs o
+                    WHERE o.user_id <> $1
+                      AND o.device_fingerprint IN (
+                          SELECT device_fingerprint
+                          FROM behavior_inputs
+                          WHERE user_id = $1 AND device_fingerprint IS NOT NULL
+                      )) AS shared_device_users,
+                   (SELECT COUNT(*) FROM sample) AS recent_inputs,
+                   COALESCE(
+                       (SELECT MAX(matched)::FLOAT8
+                        FROM (SELECT o.user_id, COUNT(DISTINCT s.timestamp) AS matched
+                              FROM sample s
+                              JOIN behavior_inputs o
+                                ON o.user_id <> $1
+                               AND o.timestamp BETWEEN s.timestamp - make_interval(secs => $4)
+                                                   AND s.timestamp + make_interval(secs => $4)
+                              GROUP BY o.user_id) m)
+                       / NULLIF((SELECT COUNT(*) FROM sample), 0),
+                       0
+                   ) AS lockstep_ratio,
+                   (SELECT STDDEV_SAMP(gap) / NULLIF(AVG(gap), 0) FROM gaps WHERE gap IS NOT NULL) AS interval_variation
+            "#,
+        )
+        .bind(user_id)
+        .bind(SIGNAL_WINDOW_DAYS as i32)
+        .bind(TIMING_SAMPLE)
+        .bind(LOCKSTEP_WINDOW_SECS)
+        .fetch_one(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(signals)
+    }
+
+    async fn save_assessment(&self, user_id: Uuid, score: f64, signals: &SybilSignals) -> Result<SybilAssessment> {
+        let assessment = sqlx::query_as::<_, SybilAssessment>(
+            r#"
+            INSERT INTO sybil_assessments (user_id, score, signals, assessed_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (user_id)
+            DO UPDATE SET score = EXCLUDED.score, signals = EXCLUDED.signals, assessed_at = EXCLUDED.assessed_at
+            RETURNING user_id, score, signals, assessed_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(score)
+        .bind(Json(signals))
+        .fetch_one(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(assessment)
+    }
+
+    async fn find_assessment(&self, user_id: Uuid) -> Result<Option<SybilAssessment>> {
+        let assessment = sqlx::query_as::<_, SybilAssessment>(
+            "SELECT user_id, score, signals, assessed_at FROM sybil_assessments WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(assessment)
+    }
+
+    async fn latest_persona_score(&self, user_id: Uuid) -> Result<Option<f64>> {
+        let score = sqlx::query_scalar(
+            r#"
+            SELECT s.score::FLOAT8
+            FROM scoring_results s
+            JOIN behavior_inputs b ON b.id = s.behavior_input_id
+            WHERE b.user_id = $1
+            ORDER BY s.timestamp DESC, s.id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(score)
+    }
+
+    async fn find_thresholds(&self, partner: &str) -> Result<Option<SybilThresholds>> {
+        let thresholds = sqlx::query_as::<_, SybilThresholds>(
+            "SELECT review_at, block_at FROM sybil_thresholds WHERE partner = $1",
+        )
+        .bind(partner)
+        .fetch_optional(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(thresholds)
+    }
+
+    async fn save_thresholds(&self, partner: &str, thresholds: &SybilThresholds) -> Result<SybilThresholds> {
+        let thresholds = sqlx::query_as::<_, SybilThresholds>(
+            r#"
+            INSERT INTO sybil_thresholds (partner, review_at, block_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (partner)
+            DO UPDATE SET review_at = EXCLUDED.review_at, block_at = EXCLUDED.block_at, updated_at = NOW()
+            RETURNING review_at, block_at
+            "#,
+        )
+        .bind(partner)
+        .bind(thresholds.review_at)
+        .bind(thresholds.block_at)
+        .fetch_one(self.app_state.mm().dbx().db())
+        .await?;
+        Ok(thresholds)
+    }
+}
@@ -10,22 +10,33 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 use jd_core::base::DMC;
 use application::handlers::scoring_handler::ScoringHandler;
+use application::use_cases::feature_use_cases::FeatureUseCases;
+use domain::feature_extraction::FeatureSet;
+use infrastructure::feature_repository_impl::FeatureRepositoryImpl;
+use infrastructure::model_registry_repository_impl::ModelRegistryRepositoryImpl;
 use infrastructure::scoring_repository_impl::ScoringRepositoryImpl;
 use jd_core::AppState;
 
 pub struct ScoringService {
-    handler: ScoringHandler<ScoringRepositoryImpl>,
+    handler: ScoringHandler<ScoringRepositoryImpl, ModelRegistryRepositoryImpl, FeatureRepositoryImpl>,
 }
 
 impl ScoringService {
     pub async fn new(state: AppState) -> Self {
-        let repository = ScoringRepositoryImpl::new(state);
-        let handler = ScoringHandler::new(repository);
+        let repository = ScoringRepositoryImpl::new(state.clone());
+        let models = ModelRegistryRepositoryImpl::new(state.clone());
+        let features = FeatureUseCases::new(
+            FeatureRepositoryImpl::new(state.clone()),
+            FeatureSet::from_config(state.config.scoring.as_ref()),
+        );
+        let handler = ScoringHandler::new(repository, models, features);
         
         Self { handler }
     }
 
-    pub fn handler(&self) -> &ScoringHandler<ScoringRepositoryImpl> {
+    pub fn handler(
+        &self,
+    ) -> &ScoringHandler<ScoringRepositoryImpl, ModelRegistryRepositoryImpl, FeatureRepositoryImpl> {
         &self.handler
     }
 }
@@ -23,6 +23,7 @@ pub struct ScoringResultForCreate {
     pub behavior_input_id: uuid::Uuid,
     pub score: f64,
     pub model_version: String,
+    pub feature_snapshot_id: Option<uuid::Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Fields)]
@@ -37,12 +38,50 @@ pub struct ScoringResultFilter {
     pub model_version: Option<OpValsString>,
 }
 
+/// Row of the append-only `score_ledger` table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ScoreLedgerRecord {
+    pub seq: i64,
+    pub subject_id: uuid::Uuid,
+    pub scoring_result_id: Option<uuid::Uuid>,
+    pub score: f64,
+    pub previous_score: Option<f64>,
+    pub model_version: String,
+    pub reason: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub prev_hash: String,
+    pub entry_hash: String,
+}
+
+/// Score change to append; sequence, timestamps and hashes are assigned by the ledger.
+#[derive(Debug, Clone)]
+pub struct ScoreLedgerForAppend {
+    pub subject_id: uuid::Uuid,
+    pub scoring_result_id: Option<uuid::Uuid>,
+    pub score: f64,
+    pub model_version: String,
+    pub reason: String,
+}
+
+/// A subject's scoring result with the behavior input it scored.
+#[derive(Debug, Clone, FromRow)]
+pub struct ScoreHistoryRecord {
+    pub scoring_result_id: uuid::Uuid,
+    pub score: f64,
+    pub model_version: String,
+    pub scored_at: chrono::DateTime<chrono::Utc>,
+    pub recompute_job_id: Option<uuid::Uuid>,
+    pub behavior_input_id: uuid::Uuid,
+    pub session_id: Option<String>,
+    pub input_data: serde_json::Value,
+}
+
 // Conversion implementations
 impl From<ScoringResultRecord> for responses::ScoringResponse {
     fn from(record: ScoringResultRecord) -> Self {
         Self {
-            id: Id::new(record.id.to_string()),
-            behavior_input_id: Id::new(record.behavior_input_id.to_string()),
+            id: Id::from(record.id),
+            behavior_input_id: Id::from(record.behavior_input_id),
             score: record.score,
             model_version: record.model_version,
             timestamp: record.timestamp,
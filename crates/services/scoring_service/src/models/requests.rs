@@ -13,4 +13,13 @@ pub struct ScoringQueryRequest {
     pub model_version: Option<String>,
     pub limit: Option<u32>,
     pub offset: Option<u32>,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreHistoryQuery {
+    /// Scores computed at or after this time.
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Scores computed before this time.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    pub limit: Option<u32>,
+}
@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use jd_domain::Id;
 
+use crate::domain::recompute_job::RecomputeJob;
+use crate::domain::sybil::{SybilSignals, SybilThresholds, SybilVerdict};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScoringResponse {
     pub id: Id,
@@ -18,4 +21,92 @@ pub struct ScoringListResponse {
     pub total: u64,
     pub limit: u32,
     pub offset: u32,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerBreak {
+    pub seq: i64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerVerificationResponse {
+    pub valid: bool,
+    pub entries_checked: u64,
+    pub head_seq: Option<i64>,
+    pub head_hash: Option<String>,
+    pub first_break: Option<LedgerBreak>,
+}
+
+/// A recompute job with its progress.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecomputeJobResponse {
+    #[serde(flatten)]
+    pub job: RecomputeJob,
+    pub progress_percent: f64,
+}
+
+impl From<RecomputeJob> for RecomputeJobResponse {
+    fn from(job: RecomputeJob) -> Self {
+        let progress_percent = job.progress_percent();
+        Self { job, progress_percent }
+    }
+}
+
+/// The behavior input a scoring result was computed from.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoredInput {
+    pub id: uuid::Uuid,
+    pub session_id: Option<String>,
+    pub input_data: serde_json::Value,
+}
+
+/// One score in a subject's history, with what changed since the previous.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreHistoryPoint {
+    pub scoring_result_id: uuid::Uuid,
+    pub score: f64,
+    pub model_version: String,
+    pub scored_at: chrono::DateTime<chrono::Utc>,
+    /// Set when a recompute job wrote the score.
+    pub recompute_job_id: Option<uuid::Uuid>,
+    pub behavior_input: ScoredInput,
+    /// Change from the previous score; `None` for the first score.
+    pub delta: Option<f64>,
+    /// Whether a different model computed the previous score.
+    pub model_changed: bool,
+    /// Top-level behavior input fields whose value differs from the
+    /// previous score's input.
+    pub changed_fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreTrend {
+    Up,
+    Down,
+    Flat,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreHistoryResponse {
+    pub subject: String,
+    /// Oldest first.
+    pub points: Vec<ScoreHistoryPoint>,
+    /// Latest score minus the score before the window's first.
+    pub net_change: f64,
+    pub trend: ScoreTrend,
+}
+
+/// A user's sybil score next to their persona score, judged by the
+/// caller's thresholds.
+#[derive(Debug, Clone, Serialize)]
+pub struct SybilReportResponse {
+    pub subject: String,
+    pub persona_score: Option<f64>,
+    pub sybil_score: f64,
+    pub verdict: SybilVerdict,
+    pub thresholds: SybilThresholds,
+    pub signals: SybilSignals,
+    pub assessed_at: chrono::DateTime<chrono::Utc>,
+}
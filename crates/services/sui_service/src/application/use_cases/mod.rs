@@ -2,6 +2,7 @@ pub mod coin_use_cases;
 pub mod event_use_cases;
 pub mod network_use_cases;
 pub mod object_use_cases;
+pub mod onchain_event_use_cases;
 pub mod sui_use_cases;
 pub mod transaction_use_cases;
 
@@ -9,5 +10,6 @@ pub use coin_use_cases::*;
 pub use event_use_cases::*;
 pub use network_use_cases::*;
 pub use object_use_cases::*;
+pub use onchain_event_use_cases::*;
 pub use sui_use_cases::*;
 pub use transaction_use_cases::*;
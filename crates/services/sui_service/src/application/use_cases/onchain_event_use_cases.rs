@@ -0,0 +1,59 @@
+use crate::infrastructure::onchain_event_store::{OnchainEventFilter, OnchainEventStore};
+use crate::models::requests::ListOnchainEventsRequest;
+use crate::models::{EventIndexerCursor, OnchainEventPage};
+use crate::{Result, error::Error};
+use std::str::FromStr;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 500;
+
+/// Use cases for reading the events stored by the event indexer
+#[derive(Clone)]
+pub struct OnchainEventUseCases {
+  store: OnchainEventStore,
+}
+
+impl OnchainEventUseCases {
+  pub fn new(store: OnchainEventStore) -> Self {
+    Self { store }
+  }
+
+  /// A page of indexed events matching the request, oldest first
+  pub async fn list_events(&self, request: ListOnchainEventsRequest) -> Result<OnchainEventPage> {
+    let package_id = request
+      .package_id
+      .map(|id| ObjectID::from_str(&id).map(|id| id.to_string()))
+      .transpose()
+      .map_err(|_| Error::InvalidRequest("Invalid package id".to_string()))?;
+    let sender = request
+      .sender
+      .map(|sender| SuiAddress::from_str(&sender).map(|sender| sender.to_string()))
+      .transpose()
+      .map_err(|_| Error::InvalidRequest("Invalid sender address".to_string()))?;
+    let tx_digest = request
+      .tx_digest
+      .map(|digest| TransactionDigest::from_str(&digest).map(|digest| digest.to_string()))
+      .transpose()
+      .map_err(|_| Error::InvalidRequest("Invalid transaction digest format".to_string()))?;
+    let limit = request.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let filter = OnchainEventFilter {
+      package_id,
+      event_type: request.event_type,
+      sender,
+      tx_digest,
+      after: request.after,
+      limit,
+    };
+    let events = self.store.list(&filter).await?;
+    let next_cursor =
+      if events.len() as i64 == limit { events.last().map(|event| event.id) } else { None };
+    Ok(OnchainEventPage { events, next_cursor })
+  }
+
+  /// How far each indexed package has been read
+  pub async fn indexer_cursors(&self) -> Result<Vec<EventIndexerCursor>> {
+    self.store.cursors().await
+  }
+}
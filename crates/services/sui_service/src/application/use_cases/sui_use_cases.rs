@@ -4,18 +4,43 @@ use crate::models::{GasPoolStatus, SponsorRequest, SponsorResponse, UserStats};
 use std::str::FromStr;
 use sui_sdk::rpc_types::Coin;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
 use uuid::Uuid;
 
+use crate::domain::sponsorship_policy::{GasQuota, QuotaSubject, SponsorshipPolicy};
 use crate::domain::sui_repository_trait::SuiRepository;
 
 #[derive(Clone)]
 pub struct SuiUseCases<R: SuiRepository> {
   pub repository: R,
+  policy: SponsorshipPolicy,
 }
 
 impl<R: SuiRepository> SuiUseCases<R> {
   pub fn new(repository: R) -> Self {
-    Self { repository }
+    Self { repository, policy: SponsorshipPolicy::default() }
+  }
+
+  /// Enforce `policy`'s quotas and Move call lists on sponsorships.
+  pub fn with_policy(mut self, policy: SponsorshipPolicy) -> Self {
+    self.policy = policy;
+    self
+  }
+
+  /// Whether `subject` may have `gas_budget` more sponsored, under its own
+  /// quota or else `default`.
+  async fn check_quota(
+    &self,
+    subject: QuotaSubject,
+    default: GasQuota,
+    gas_budget: u64,
+  ) -> Result<()> {
+    let quota = self.repository.get_gas_quota(&subject).await?.unwrap_or(default);
+    if quota.is_unlimited() {
+      return Ok(());
+    }
+    let used = self.repository.get_gas_usage(&subject).await?;
+    quota.check(&subject, used, gas_budget)
   }
 
   pub async fn fetch_coin(&self, sender: String) -> Result<Coin> {
@@ -37,6 +62,21 @@ impl<R: SuiRepository> SuiUseCases<R> {
       return Err(Error::InvalidRequest("Rate limit exceeded".to_string()));
     }
 
+    // Check the transaction against the sponsorship policy and quotas
+    let tx_data: TransactionData = bcs::from_bytes(&request.transaction_data.tx_bytes)
+      .map_err(|e| Error::InvalidRequest(format!("Invalid transaction bytes: {}", e)))?;
+    if tx_data.sender() != user_address {
+      return Err(Error::InvalidRequest(
+        "Transaction sender does not match the user address".to_string(),
+      ));
+    }
+    self.policy.check_transaction(&tx_data)?;
+    let gas_budget = tx_data.gas_data().budget;
+    self.check_quota(QuotaSubject::User(user_address), self.policy.user_quota, gas_budget).await?;
+    if let Some(app_id) = &request.app_id {
+      self.check_quota(QuotaSubject::App(app_id.clone()), self.policy.app_quota, gas_budget).await?;
+    }
+
     // Sponsor the transaction using tx_bytes
     match self
       .repository
@@ -44,18 +84,10 @@ impl<R: SuiRepository> SuiUseCases<R> {
       .await
     {
       Ok((transaction, digest)) => {
-        // Get gas budget from request for logging
-        let gas_budget = request
-          .transaction_data
-          .gas_data
-          .budget
-          .parse::<u64>()
-          .unwrap_or(10000000); // Default gas budget
-
         // Log the sponsored transaction
         if let Err(e) = self
           .repository
-          .log_sponsored_transaction(&user_address, gas_budget)
+          .log_sponsored_transaction(&user_address, request.app_id.as_deref(), gas_budget)
           .await
         {
           tracing::warn!("Failed to log sponsored transaction: {}", e);
@@ -0,0 +1,143 @@
+use jd_utils::config::SuiConfig;
+use std::cmp::Reverse;
+use sui_types::base_types::ObjectRef;
+
+const DEFAULT_MAX_GAS_BUDGET: u64 = 10_000_000;
+const DEFAULT_POOL_SIZE: usize = 20;
+/// Default balance of a pool coin, in maximum gas budgets.
+const DEFAULT_BUDGETS_PER_COIN: u64 = 5;
+/// Most dust coins merged by one rebalance transaction.
+const MAX_MERGED_COINS: usize = 256;
+
+/// A SUI coin owned by the sponsor address.
+#[derive(Debug, Clone)]
+pub struct PoolCoin {
+  pub object_ref: ObjectRef,
+  pub balance: u64,
+  /// Held by an in-flight sponsorship or rebalance.
+  pub reserved: bool,
+}
+
+/// The coin distribution the rebalance keeps the gas pool at.
+#[derive(Debug, Clone, Copy)]
+pub struct GasPoolTarget {
+  /// Coins able to pay for a sponsorship at the maximum gas budget.
+  pub pool_size: usize,
+  /// Balance new coins are split to.
+  pub coin_balance: u64,
+  /// Balance below which a coin cannot pay for a sponsorship and is
+  /// merged back.
+  pub min_coin_balance: u64,
+  /// Gas budget of the rebalance transaction itself.
+  pub rebalance_gas_budget: u64,
+}
+
+impl GasPoolTarget {
+  pub fn from_config(config: &SuiConfig) -> Self {
+    let max_gas_budget = config.max_gas_budget.unwrap_or(DEFAULT_MAX_GAS_BUDGET).max(1);
+    Self {
+      pool_size: config.gas_pool_size.unwrap_or(DEFAULT_POOL_SIZE).max(1),
+      coin_balance: config
+        .gas_coin_balance
+        .unwrap_or(max_gas_budget * DEFAULT_BUDGETS_PER_COIN)
+        .max(max_gas_budget),
+      min_coin_balance: max_gas_budget,
+      rebalance_gas_budget: max_gas_budget,
+    }
+  }
+}
+
+/// One rebalance transaction: `merge` coins are merged into `primary`, the
+/// largest free coin, which then has a coin of each `split` balance split
+/// off it and pays for the transaction.
+#[derive(Debug, Clone, Default)]
+pub struct RebalancePlan {
+  pub primary: Option<ObjectRef>,
+  pub merge: Vec<ObjectRef>,
+  pub split: Vec<u64>,
+}
+
+impl RebalancePlan {
+  pub fn is_empty(&self) -> bool {
+    self.merge.is_empty() && self.split.is_empty()
+  }
+}
+
+/// Plan merging the free coins too small to pay for a sponsorship into the
+/// largest one and splitting it until the pool holds `pool_size` usable
+/// coins. Reserved coins are left alone but count towards the pool, and
+/// the primary coin keeps at least `coin_balance` plus the gas to pay for
+/// the rebalance.
+pub fn plan_rebalance(coins: &[PoolCoin], target: &GasPoolTarget) -> RebalancePlan {
+  let mut free: Vec<&PoolCoin> = coins.iter().filter(|coin| !coin.reserved).collect();
+  free.sort_by_key(|coin| Reverse(coin.balance));
+  let Some((primary, rest)) = free.split_first() else {
+    return RebalancePlan::default();
+  };
+
+  let dust: Vec<&PoolCoin> = rest
+    .iter()
+    .filter(|coin| coin.balance < target.min_coin_balance)
+    .take(MAX_MERGED_COINS)
+    .copied()
+    .collect();
+  let balance = primary.balance + dust.iter().map(|coin| coin.balance).sum::<u64>();
+
+  let usable = coins
+    .iter()
+    .filter(|coin| coin.object_ref != primary.object_ref)
+    .filter(|coin| coin.balance >= target.min_coin_balance)
+    .count();
+  let missing = target.pool_size.saturating_sub(usable + 1);
+  let spare = balance.saturating_sub(target.coin_balance + target.rebalance_gas_budget);
+  let splits = missing.min((spare / target.coin_balance.max(1)) as usize);
+
+  RebalancePlan {
+    primary: Some(primary.object_ref),
+    merge: dust.iter().map(|coin| coin.object_ref).collect(),
+    split: vec![target.coin_balance; splits],
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+
+  const SUI: u64 = 1_000_000_000;
+
+  fn coin(balance: u64, reserved: bool) -> PoolCoin {
+    PoolCoin {
+      object_ref: (ObjectID::random(), SequenceNumber::new(), ObjectDigest::random()),
+      balance,
+      reserved,
+    }
+  }
+
+  #[test]
+  fn rebalance_merges_dust_and_splits_the_largest_free_coin() {
+    let target = GasPoolTarget {
+      pool_size: 4,
+      coin_balance: SUI / 10,
+      min_coin_balance: SUI / 100,
+      rebalance_gas_budget: SUI / 100,
+    };
+    let primary = coin(2 * SUI, false);
+    let dust = coin(SUI / 1000, false);
+    let in_flight = coin(SUI / 10, true);
+    let coins = vec![dust.clone(), in_flight, primary.clone(), coin(SUI / 1000, true)];
+
+    let plan = plan_rebalance(&coins, &target);
+    assert_eq!(plan.primary, Some(primary.object_ref));
+    assert_eq!(plan.merge, vec![dust.object_ref]);
+    // The in-flight coin and the primary already make two of the four
+    assert_eq!(plan.split, vec![SUI / 10; 2]);
+
+    // A primary coin too small to split off is only topped up with the dust
+    let coins = vec![coin(SUI / 10, false), dust.clone()];
+    let plan = plan_rebalance(&coins, &target);
+    assert_eq!((plan.merge.len(), plan.split.len()), (1, 0));
+
+    assert!(plan_rebalance(&[coin(SUI, true)], &target).is_empty());
+  }
+}
@@ -1,2 +1,4 @@
 // Domain layer module
+pub(crate) mod gas_pool;
+pub(crate) mod sponsorship_policy;
 pub(crate) mod sui_repository_trait;
@@ -0,0 +1,217 @@
+use jd_utils::config::SuiConfig;
+use std::fmt;
+use std::str::FromStr;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::transaction::{Command, TransactionData, TransactionDataAPI, TransactionKind};
+
+use crate::Result;
+use crate::error::Error;
+
+/// Who a gas quota applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuotaSubject {
+  User(SuiAddress),
+  /// An app sponsoring its users' transactions, by the id it sends.
+  App(String),
+}
+
+impl QuotaSubject {
+  pub fn kind(&self) -> &'static str {
+    match self {
+      QuotaSubject::User(_) => "user",
+      QuotaSubject::App(_) => "app",
+    }
+  }
+
+  pub fn id(&self) -> String {
+    match self {
+      QuotaSubject::User(address) => address.to_string(),
+      QuotaSubject::App(app_id) => app_id.clone(),
+    }
+  }
+}
+
+impl fmt::Display for QuotaSubject {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} {}", self.kind(), self.id())
+  }
+}
+
+/// Gas, in MIST, a subject may have sponsored over the last day and week.
+/// `None` is unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasQuota {
+  pub daily: Option<u64>,
+  pub weekly: Option<u64>,
+}
+
+/// Gas budgets sponsored for a subject over the last day and week.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GasUsage {
+  pub daily: u64,
+  pub weekly: u64,
+}
+
+impl GasQuota {
+  pub fn is_unlimited(&self) -> bool {
+    self.daily.is_none() && self.weekly.is_none()
+  }
+
+  /// Whether `subject`, having used `used`, may have `budget` more sponsored.
+  pub fn check(&self, subject: &QuotaSubject, used: GasUsage, budget: u64) -> Result<()> {
+    let windows = [("daily", self.daily, used.daily), ("weekly", self.weekly, used.weekly)];
+    for (window, limit, used) in windows {
+      if let Some(limit) = limit
+        && used.saturating_add(budget) > limit
+      {
+        return Err(Error::QuotaExceeded(format!(
+          "The {} gas budget of {} is exhausted: {} of {} MIST used, the transaction needs {}",
+          window, subject, used, limit, budget
+        )));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// A Move call target sponsorship is allowed or denied for: a package, a
+/// module of it or one function, written `0x2`, `0x2::coin` or
+/// `0x2::coin::split`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveCallPattern {
+  package: ObjectID,
+  module: Option<String>,
+  function: Option<String>,
+}
+
+impl MoveCallPattern {
+  fn matches(&self, package: &ObjectID, module: &str, function: &str) -> bool {
+    self.package == *package
+      && self.module.as_deref().is_none_or(|m| m == module)
+      && self.function.as_deref().is_none_or(|f| f == function)
+  }
+}
+
+impl FromStr for MoveCallPattern {
+  type Err = String;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    let mut parts = s.trim().split("::");
+    let package = parts.next().unwrap_or_default();
+    let package = ObjectID::from_str(package)
+      .map_err(|_| format!("Invalid package in Move call pattern '{}'", s))?;
+    let module = parts.next().map(str::to_string);
+    let function = parts.next().map(str::to_string);
+    if parts.next().is_some() || [&module, &function].into_iter().flatten().any(String::is_empty) {
+      return Err(format!("Invalid Move call pattern '{}'", s));
+    }
+    Ok(Self { package, module, function })
+  }
+}
+
+/// What the gas station will sponsor: default quotas for users and apps
+/// without their own, and the Move calls a transaction may make. With an
+/// allowlist every call must be on it; a denied call is never sponsored.
+#[derive(Debug, Clone, Default)]
+pub struct SponsorshipPolicy {
+  pub user_quota: GasQuota,
+  pub app_quota: GasQuota,
+  allowed_calls: Vec<MoveCallPattern>,
+  denied_calls: Vec<MoveCallPattern>,
+  /// Why the configured policy could not be read. Nothing is sponsored
+  /// rather than sponsoring without the configured restrictions.
+  invalid: Option<String>,
+}
+
+impl SponsorshipPolicy {
+  pub fn from_config(config: &SuiConfig) -> Self {
+    let user_quota =
+      GasQuota { daily: config.user_daily_gas_budget, weekly: config.user_weekly_gas_budget };
+    let app_quota =
+      GasQuota { daily: config.app_daily_gas_budget, weekly: config.app_weekly_gas_budget };
+    let patterns = |list: &Option<String>| {
+      list
+        .iter()
+        .flat_map(|list| list.split(','))
+        .filter(|pattern| !pattern.trim().is_empty())
+        .map(MoveCallPattern::from_str)
+        .collect::<std::result::Result<Vec<_>, _>>()
+    };
+
+    match (patterns(&config.sponsor_allowed_calls), patterns(&config.sponsor_denied_calls)) {
+      (Ok(allowed_calls), Ok(denied_calls)) => {
+        Self { user_quota, app_quota, allowed_calls, denied_calls, invalid: None }
+      }
+      (Err(e), _) | (_, Err(e)) => {
+        tracing::error!("Sponsorship disabled: {}", e);
+        Self { invalid: Some(e), ..Default::default() }
+      }
+    }
+  }
+
+  /// Whether the policy allows sponsoring `tx`, checking each Move call it
+  /// makes against the allow and deny lists.
+  pub fn check_transaction(&self, tx: &TransactionData) -> Result<()> {
+    if let Some(invalid) = &self.invalid {
+      return Err(Error::SponsorshipDenied(format!("Sponsorship is misconfigured: {}", invalid)));
+    }
+    let TransactionKind::ProgrammableTransaction(programmable) = tx.kind() else {
+      return Err(Error::SponsorshipDenied(
+        "Only programmable transactions can be sponsored".to_string(),
+      ));
+    };
+
+    for command in &programmable.commands {
+      let Command::MoveCall(call) = command else {
+        continue;
+      };
+      let (module, function) = (call.module.to_string(), call.function.to_string());
+      let target = format!("{}::{}::{}", call.package, module, function);
+      let listed = |patterns: &[MoveCallPattern]| {
+        patterns.iter().any(|pattern| pattern.matches(&call.package, &module, &function))
+      };
+      if listed(&self.denied_calls) {
+        return Err(Error::SponsorshipDenied(format!("Calls to {} are not sponsored", target)));
+      }
+      if !self.allowed_calls.is_empty() && !listed(&self.allowed_calls) {
+        return Err(Error::SponsorshipDenied(format!(
+          "{} is not on the list of sponsored calls",
+          target
+        )));
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn move_call_patterns_match_package_module_or_function() {
+    let sui = ObjectID::from_str("0x2").unwrap();
+    let pattern = |s: &str| MoveCallPattern::from_str(s).unwrap();
+
+    assert!(pattern("0x2").matches(&sui, "coin", "split"));
+    assert!(pattern("0x2::coin").matches(&sui, "coin", "join"));
+    assert!(!pattern("0x2::coin::split").matches(&sui, "coin", "join"));
+    assert!(!pattern("0x3").matches(&sui, "coin", "split"));
+    assert!(MoveCallPattern::from_str("0x2::").is_err());
+    assert!(MoveCallPattern::from_str("coin::split").is_err());
+  }
+
+  #[test]
+  fn quota_rejects_budget_past_either_window() {
+    let subject = QuotaSubject::App("wallet".to_string());
+    let quota = GasQuota { daily: Some(100), weekly: Some(300) };
+
+    assert!(quota.check(&subject, GasUsage { daily: 90, weekly: 90 }, 10).is_ok());
+    assert!(matches!(
+      quota.check(&subject, GasUsage { daily: 95, weekly: 95 }, 10),
+      Err(Error::QuotaExceeded(_))
+    ));
+    assert!(quota.check(&subject, GasUsage { daily: 0, weekly: 295 }, 10).is_err());
+    assert!(GasQuota::default().is_unlimited());
+  }
+}
@@ -1,4 +1,5 @@
 use crate::Result;
+use crate::domain::sponsorship_policy::{GasQuota, GasUsage, QuotaSubject};
 use crate::models::{GasPoolStatus, UserStats};
 use async_trait::async_trait;
 use sui_sdk::rpc_types::{
@@ -131,8 +132,16 @@ pub trait SuiRepository: Send + Sync {
   async fn log_sponsored_transaction(
     &self,
     user_address: &SuiAddress,
+    app_id: Option<&str>,
     gas_budget: u64,
   ) -> Result<()>;
   async fn get_user_stats(&self, address: &str) -> Result<Option<UserStats>>;
   async fn check_rate_limit(&self, user_address: &SuiAddress) -> Result<bool>;
+
+  // ============== SPONSORSHIP QUOTAS ==============
+  /// The quota set for a user or app in place of the configured default
+  async fn get_gas_quota(&self, subject: &QuotaSubject) -> Result<Option<GasQuota>>;
+
+  /// Gas sponsored for a user or app over the last day and week
+  async fn get_gas_usage(&self, subject: &QuotaSubject) -> Result<GasUsage>;
 }
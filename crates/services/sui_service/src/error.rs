@@ -4,18 +4,31 @@ use axum::{
 };
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "sui")]
 pub enum Error {
   #[error("Sui client error: {0}")]
+  #[taxonomy(kind = Upstream)]
   SuiClient(String),
 
   #[error("Invalid request: {0}")]
+  #[taxonomy(kind = Validation, expose)]
   InvalidRequest(String),
 
+  #[error("Sponsorship quota exceeded: {0}")]
+  #[taxonomy(kind = RateLimited, expose)]
+  QuotaExceeded(String),
+
+  #[error("Sponsorship denied: {0}")]
+  #[taxonomy(kind = PermissionDenied, expose)]
+  SponsorshipDenied(String),
+
   #[error("Internal error: {0}")]
+  #[taxonomy(kind = Internal)]
   Internal(String),
 
   #[error("Implementation pending: {0}")]
+  #[taxonomy(kind = NotImplemented, expose)]
   ImplementationPending(String),
 }
 
@@ -24,6 +37,8 @@ impl IntoResponse for Error {
     let (status, error_message) = match self {
       Error::SuiClient(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
       Error::InvalidRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+      Error::QuotaExceeded(msg) => (StatusCode::TOO_MANY_REQUESTS, msg),
+      Error::SponsorshipDenied(msg) => (StatusCode::FORBIDDEN, msg),
       Error::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
       Error::ImplementationPending(msg) => (StatusCode::OK, msg),
     };
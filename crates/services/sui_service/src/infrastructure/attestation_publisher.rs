@@ -0,0 +1,230 @@
+use jd_core::AppState;
+use jd_core::sui::provider::ProviderRole;
+use std::str::FromStr;
+use sui_sdk::rpc_types::{
+  SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_types::{
+  Identifier,
+  base_types::{ObjectID, SuiAddress},
+  digests::TransactionDigest,
+  quorum_driver_types::ExecuteTransactionRequestType,
+  transaction::{CallArg, Transaction, TransactionData, TransactionDataAPI},
+};
+
+use crate::infrastructure::gas_station::GasStation;
+use crate::{Result, error::Error};
+
+const DEFAULT_ATTESTATION_FUNCTION: &str = "attestation::record";
+
+/// A signed attestation transaction and its digest.
+#[derive(Debug, Clone)]
+pub struct SignedAttestation {
+  pub digest: String,
+  pub signed_tx: Vec<u8>,
+}
+
+/// How an executed transaction ended: `error` is set when it aborted.
+#[derive(Debug, Clone)]
+pub struct ExecutedTransaction {
+  pub digest: String,
+  pub error: Option<String>,
+}
+
+/// Records proof attestations by calling `SUI.ATTESTATION_FUNCTION` of
+/// `SUI.ATTESTATION_PACKAGE` with the proof hash, score commitment and
+/// subject address, paid and signed by the sponsor from its gas pool.
+pub struct AttestationPublisher {
+  station: GasStation,
+  package: ObjectID,
+  module: Identifier,
+  function: Identifier,
+}
+
+impl AttestationPublisher {
+  /// The publisher of the default network, unless no attestation package or
+  /// sponsor is configured.
+  pub fn from_state(state: &AppState) -> Result<Option<Self>> {
+    let config = &state.config.sui;
+    let Some(package) = config.attestation_package.as_deref().filter(|p| !p.trim().is_empty())
+    else {
+      return Ok(None);
+    };
+    let package = ObjectID::from_str(package.trim())
+      .map_err(|_| Error::InvalidRequest(format!("Invalid attestation package '{}'", package)))?;
+    let target = config.attestation_function.as_deref().unwrap_or(DEFAULT_ATTESTATION_FUNCTION);
+    let (module, function) = move_target(target, "attestation")?;
+
+    let network = state.sui_networks().default_network();
+    let Some(station) =
+      GasStation::from_state(state, network).map_err(|e| Error::Internal(e.to_string()))?
+    else {
+      return Ok(None);
+    };
+    Ok(Some(Self { station, package, module, function }))
+  }
+
+  /// Reserve a gas coin and sign the attestation call with it.
+  pub async fn prepare(
+    &self,
+    proof_hash: &[u8],
+    score_commitment: &[u8],
+    subject_address: &str,
+  ) -> Result<SignedAttestation> {
+    let subject = SuiAddress::from_str(subject_address)
+      .map_err(|e| Error::InvalidRequest(format!("Invalid subject address: {}", e)))?;
+    let arguments = vec![
+      pure(&proof_hash.to_vec())?,
+      pure(&score_commitment.to_vec())?,
+      pure(&subject)?,
+    ];
+
+    let (digest, signed_tx) = sign_move_call(
+      &self.station,
+      self.package,
+      &self.module,
+      &self.function,
+      arguments,
+      "attestation",
+    )
+    .await?;
+    Ok(SignedAttestation { digest, signed_tx })
+  }
+
+  /// Execute a signed attestation. Executing one that already landed
+  /// returns its effects again. When execution errors, the chain is asked
+  /// whether the transaction landed anyway before the error is returned.
+  pub async fn submit(&self, signed_tx: &[u8]) -> Result<ExecutedTransaction> {
+    execute_signed(&self.station, signed_tx, "attestation").await
+  }
+}
+
+/// `module::function` of a configured Move call target; `what` names the
+/// call in the error.
+pub(crate) fn move_target(target: &str, what: &str) -> Result<(Identifier, Identifier)> {
+  target
+    .trim()
+    .split_once("::")
+    .and_then(|(module, function)| {
+      Some((Identifier::new(module).ok()?, Identifier::new(function).ok()?))
+    })
+    .ok_or_else(|| Error::InvalidRequest(format!("Invalid {} function '{}'", what, target)))
+}
+
+/// Reserve a gas coin and sign a Move call sponsored by `station` with it,
+/// returning the transaction digest and its BCS bytes. The coin is released
+/// again if signing fails.
+pub(crate) async fn sign_move_call(
+  station: &GasStation,
+  package: ObjectID,
+  module: &Identifier,
+  function: &Identifier,
+  arguments: Vec<CallArg>,
+  what: &str,
+) -> Result<(String, Vec<u8>)> {
+  let gas_budget = station.max_gas_budget;
+  let reservation = station
+    .reserve_gas(gas_budget)
+    .await
+    .map_err(|e| Error::SuiClient(format!("No gas for {}: {}", what, e)))?;
+  let signed = async {
+    let gas_price = station.reference_gas_price().await?;
+    let data = TransactionData::new_move_call(
+      station.sponsor_address,
+      package,
+      module.clone(),
+      function.clone(),
+      vec![],
+      reservation.object_ref,
+      arguments,
+      gas_budget,
+      gas_price,
+    )?;
+    let transaction = station.sign(data)?;
+    Ok::<_, anyhow::Error>((transaction.digest().to_string(), bcs::to_bytes(&transaction)?))
+  }
+  .await;
+
+  if signed.is_err() {
+    let released = station.cancel_reservation(&reservation).await;
+    if let Err(e) = released {
+      tracing::warn!("Failed to release {} gas: {}", what, e);
+    }
+  }
+  signed.map_err(|e| Error::Internal(format!("Failed to sign {}: {}", what, e)))
+}
+
+/// Execute a transaction signed by `sign_move_call`. Executing one that
+/// already landed returns its effects again. When execution errors, the
+/// chain is asked whether the transaction landed anyway before the error is
+/// returned.
+pub(crate) async fn execute_signed(
+  station: &GasStation,
+  signed_tx: &[u8],
+  what: &str,
+) -> Result<ExecutedTransaction> {
+  let transaction: Transaction = bcs::from_bytes(signed_tx)
+    .map_err(|e| Error::InvalidRequest(format!("Invalid signed {}: {}", what, e)))?;
+  let digest = *transaction.digest();
+  let gas_coin = transaction.data().transaction_data().gas().first().map(|gas| gas.0);
+
+  let executed = station
+    .rpc()
+    .call(ProviderRole::Write, |client| {
+      let transaction = transaction.clone();
+      async move {
+        client
+          .quorum_driver_api()
+          .execute_transaction_block(
+            transaction,
+            SuiTransactionBlockResponseOptions::new().with_effects(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+          )
+          .await
+      }
+    })
+    .await;
+  let response = match executed {
+    Ok(response) => response,
+    Err(e) => match landed(station, digest).await {
+      Some(response) => response,
+      None => {
+        let message = format!("Transaction {} ({}) not executed: {}", digest, what, e);
+        return Err(Error::SuiClient(message));
+      }
+    },
+  };
+
+  if let Some(gas_coin) = gas_coin {
+    let released = station.release_gas(gas_coin).await;
+    if let Err(e) = released {
+      tracing::warn!("Failed to release {} gas: {}", what, e);
+    }
+  }
+  let error = response
+    .effects
+    .as_ref()
+    .filter(|effects| !effects.status().is_ok())
+    .map(|effects| format!("{:?}", effects.status()));
+  Ok(ExecutedTransaction { digest: digest.to_string(), error })
+}
+
+async fn landed(
+  station: &GasStation,
+  digest: TransactionDigest,
+) -> Option<SuiTransactionBlockResponse> {
+  station
+    .rpc()
+    .call(ProviderRole::Read, |client| async move {
+      let options = SuiTransactionBlockResponseOptions::new().with_effects();
+      client.read_api().get_transaction_with_options(digest, options).await
+    })
+    .await
+    .ok()
+}
+
+pub(crate) fn pure<T: serde::Serialize>(value: &T) -> Result<CallArg> {
+  bcs::to_bytes(value)
+    .map(CallArg::Pure)
+    .map_err(|e| Error::Internal(format!("Failed to encode argument: {}", e)))
+}
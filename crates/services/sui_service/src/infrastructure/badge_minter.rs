@@ -0,0 +1,87 @@
+use jd_core::AppState;
+use std::str::FromStr;
+use sui_types::{
+  Identifier,
+  base_types::{ObjectID, SuiAddress},
+};
+
+use crate::infrastructure::attestation_publisher::{
+  ExecutedTransaction, execute_signed, move_target, pure, sign_move_call,
+};
+use crate::infrastructure::gas_station::GasStation;
+use crate::{Result, error::Error};
+
+const DEFAULT_BADGE_FUNCTION: &str = "badge::mint";
+
+/// A signed badge mint transaction and its digest.
+#[derive(Debug, Clone)]
+pub struct SignedMint {
+  pub digest: String,
+  pub signed_tx: Vec<u8>,
+}
+
+/// Mints developer badges as Sui objects by calling `SUI.BADGE_FUNCTION` of
+/// `SUI.BADGE_PACKAGE` with the recipient, badge key and badge name, paid
+/// and signed by the sponsor from its gas pool.
+pub struct BadgeMinter {
+  station: GasStation,
+  package: ObjectID,
+  module: Identifier,
+  function: Identifier,
+}
+
+impl BadgeMinter {
+  /// The minter of the default network, unless no badge package or sponsor
+  /// is configured.
+  pub fn from_state(state: &AppState) -> Result<Option<Self>> {
+    let config = &state.config.sui;
+    let Some(package) = config.badge_package.as_deref().filter(|p| !p.trim().is_empty()) else {
+      return Ok(None);
+    };
+    let package = ObjectID::from_str(package.trim())
+      .map_err(|_| Error::InvalidRequest(format!("Invalid badge package '{}'", package)))?;
+    let target = config.badge_function.as_deref().unwrap_or(DEFAULT_BADGE_FUNCTION);
+    let (module, function) = move_target(target, "badge")?;
+
+    let network = state.sui_networks().default_network();
+    let Some(station) =
+      GasStation::from_state(state, network).map_err(|e| Error::Internal(e.to_string()))?
+    else {
+      return Ok(None);
+    };
+    Ok(Some(Self { station, package, module, function }))
+  }
+
+  /// Reserve a gas coin and sign the mint call with it.
+  pub async fn prepare(
+    &self,
+    recipient: &str,
+    badge_key: &str,
+    badge_name: &str,
+  ) -> Result<SignedMint> {
+    let recipient = SuiAddress::from_str(recipient)
+      .map_err(|e| Error::InvalidRequest(format!("Invalid recipient address: {}", e)))?;
+    let arguments = vec![
+      pure(&recipient)?,
+      pure(&badge_key.as_bytes().to_vec())?,
+      pure(&badge_name.as_bytes().to_vec())?,
+    ];
+    let (digest, signed_tx) = sign_move_call(
+      &self.station,
+      self.package,
+      &self.module,
+      &self.function,
+      arguments,
+      "badge mint",
+    )
+    .await?;
+    Ok(SignedMint { digest, signed_tx })
+  }
+
+  /// Execute a signed mint. Executing one that already landed returns its
+  /// effects again, so a mint interrupted before its outcome was recorded
+  /// can be resubmitted without minting twice.
+  pub async fn submit(&self, signed_tx: &[u8]) -> Result<ExecutedTransaction> {
+    execute_signed(&self.station, signed_tx, "badge mint").await
+  }
+}
@@ -1,11 +1,21 @@
+use crate::domain::sponsorship_policy::{GasQuota, GasUsage, QuotaSubject};
 use crate::domain::sui_repository_trait::SuiRepository;
+use crate::infrastructure::{
+  gas_station::GasStation, object_cache::SuiObjectCache, sponsorship_store::SponsorshipStore,
+};
 use crate::{Result, error::Error};
 use async_trait::async_trait;
 use jd_core::AppState;
+use jd_core::sui::network::SuiNetwork;
+use serde::{Serialize, de::DeserializeOwned};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use sui_sdk::SuiClient;
 use sui_sdk::rpc_types::{
   Coin, SuiObjectResponse, SuiTransactionBlockResponse, SuiEvent, Page,
   Balance, SuiCoinMetadata, SuiObjectDataOptions,
-  SuiTransactionBlockResponseOptions, DynamicFieldInfo
+  SuiTransactionBlockResponseOptions, DynamicFieldInfo, SuiRawData
 };
 use sui_sdk::types::base_types::{SuiAddress, TransactionDigest};
 use sui_types::base_types::ObjectID;
@@ -14,12 +24,93 @@ use sui_types::base_types::ObjectID;
 /// This implementation provides comprehensive access to Sui blockchain data
 #[derive(Clone)]
 pub struct EnhancedSuiRepository {
-  app_state: AppState,
+  client: SuiClient,
+  network: SuiNetwork,
+  gas_station: Option<Arc<GasStation>>,
+  sponsorships: SponsorshipStore,
+  cache: Option<SuiObjectCache>,
 }
 
 impl EnhancedSuiRepository {
+  /// A repository on the default network.
   pub fn new(app_state: AppState) -> Self {
-    Self { app_state }
+    let network = app_state.sui_networks().default_network();
+    let client = app_state.sui_client.client.clone();
+    Self::with_client(&app_state, network, client)
+  }
+
+  /// A repository on `network`, or the default one when `None`, with that
+  /// network's RPC endpoint and gas pool.
+  pub async fn for_network(app_state: AppState, network: Option<&str>) -> Result<Self> {
+    let networks = app_state.sui_networks();
+    let network = networks.resolve(network).map_err(|e| Error::InvalidRequest(e.to_string()))?;
+    let client = networks.client(network).await.map_err(|e| Error::SuiClient(e.to_string()))?;
+    Ok(Self::with_client(&app_state, network, client))
+  }
+
+  fn with_client(app_state: &AppState, network: SuiNetwork, client: SuiClient) -> Self {
+    let gas_station = match GasStation::from_state(app_state, network) {
+      Ok(gas_station) => gas_station.map(Arc::new),
+      Err(e) => {
+        tracing::warn!("Gas station disabled on {}: {}", network, e);
+        None
+      }
+    };
+    let sponsorships = SponsorshipStore::new(app_state.mm().dbx().db().clone());
+    let cache = SuiObjectCache::from_state(app_state, network);
+    Self { client, network, gas_station, sponsorships, cache }
+  }
+
+  pub fn network(&self) -> SuiNetwork {
+    self.network
+  }
+
+  /// The published bytecode of each module of the package `package_id`,
+  /// by module name.
+  pub async fn package_modules(&self, package_id: &str) -> Result<BTreeMap<String, Vec<u8>>> {
+    let object_id = ObjectID::from_str(package_id.trim())
+      .map_err(|_| Error::InvalidRequest(format!("Invalid package id '{}'", package_id)))?;
+    let response = self
+      .client
+      .read_api()
+      .get_object_with_options(object_id, SuiObjectDataOptions::new().with_bcs())
+      .await
+      .map_err(|e| Error::SuiClient(format!("Failed to get package: {}", e)))?;
+    match response.data.and_then(|data| data.bcs) {
+      Some(SuiRawData::Package(package)) => Ok(package.module_map),
+      _ => Err(Error::InvalidRequest(format!("No package {} on {}", package_id, self.network))),
+    }
+  }
+
+  async fn cached_object<T: DeserializeOwned>(
+    &self,
+    object_id: &ObjectID,
+    read: &str,
+  ) -> Option<T> {
+    self.cache.as_ref()?.object(object_id, read).await
+  }
+
+  async fn cache_object<T: Serialize>(&self, object_id: &ObjectID, read: &str, value: &T) {
+    if let Some(cache) = &self.cache {
+      cache.put_object(object_id, read, value).await;
+    }
+  }
+
+  async fn cached_owned<T: DeserializeOwned>(&self, owner: &SuiAddress, read: &str) -> Option<T> {
+    self.cache.as_ref()?.owned(owner, read).await
+  }
+
+  async fn cache_owned<T: Serialize>(&self, owner: &SuiAddress, read: &str, value: &T) {
+    if let Some(cache) = &self.cache {
+      cache.put_owned(owner, read, value).await;
+    }
+  }
+
+  fn gas_station(&self) -> Result<&GasStation> {
+    self
+      .gas_station
+      .as_deref()
+      .ok_or_else(|| Error::ImplementationPending("Gas station is not configured".to_string()))
   }
 }
 
@@ -34,12 +125,23 @@ impl SuiRepository for EnhancedSuiRepository {
     cursor: Option<String>,
     limit: Option<usize>,
   ) -> Result<Page<Coin, String>> {
-    self.app_state
-      .sui_client.client
+    let read = format!(
+      "coins:{}:{}:{}",
+      coin_type.as_deref().unwrap_or_default(),
+      cursor.as_deref().unwrap_or_default(),
+      limit.unwrap_or_default()
+    );
+    if let Some(page) = self.cached_owned(&address, &read).await {
+      return Ok(page);
+    }
+    let page = self
+      .client
       .coin_read_api()
       .get_coins(address, coin_type, cursor, limit)
       .await
-      .map_err(|e| Error::Internal(format!("Failed to get coins: {}", e)))
+      .map_err(|e| Error::Internal(format!("Failed to get coins: {}", e)))?;
+    self.cache_owned(&address, &read, &page).await;
+    Ok(page)
   }
 
   async fn get_all_coins(
@@ -48,35 +150,56 @@ impl SuiRepository for EnhancedSuiRepository {
     cursor: Option<String>,
     limit: Option<usize>,
   ) -> Result<Page<Coin, String>> {
-    self.app_state
-      .sui_client.client
+    let read = format!(
+      "all_coins:{}:{}",
+      cursor.as_deref().unwrap_or_default(),
+      limit.unwrap_or_default()
+    );
+    if let Some(page) = self.cached_owned(&address, &read).await {
+      return Ok(page);
+    }
+    let page = self
+      .client
       .coin_read_api()
       .get_all_coins(address, cursor, limit)
       .await
-      .map_err(|e| Error::Internal(format!("Failed to get all coins: {}", e)))
+      .map_err(|e| Error::Internal(format!("Failed to get all coins: {}", e)))?;
+    self.cache_owned(&address, &read, &page).await;
+    Ok(page)
   }
 
   async fn get_balance(&self, address: SuiAddress, coin_type: Option<String>) -> Result<Balance> {
-    self.app_state
-      .sui_client.client
+    let read = format!("balance:{}", coin_type.as_deref().unwrap_or_default());
+    if let Some(balance) = self.cached_owned(&address, &read).await {
+      return Ok(balance);
+    }
+    let balance = self
+      .client
       .coin_read_api()
       .get_balance(address, coin_type)
       .await
-      .map_err(|e| Error::Internal(format!("Failed to get balance: {}", e)))
+      .map_err(|e| Error::Internal(format!("Failed to get balance: {}", e)))?;
+    self.cache_owned(&address, &read, &balance).await;
+    Ok(balance)
   }
 
   async fn get_all_balances(&self, address: SuiAddress) -> Result<Vec<Balance>> {
-    self.app_state
-      .sui_client.client
+    if let Some(balances) = self.cached_owned(&address, "balances").await {
+      return Ok(balances);
+    }
+    let balances = self
+      .client
       .coin_read_api()
       .get_all_balances(address)
       .await
-      .map_err(|e| Error::Internal(format!("Failed to get all balances: {}", e)))
+      .map_err(|e| Error::Internal(format!("Failed to get all balances: {}", e)))?;
+    self.cache_owned(&address, "balances", &balances).await;
+    Ok(balances)
   }
 
   async fn get_coin_metadata(&self, coin_type: String) -> Result<Option<SuiCoinMetadata>> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .coin_read_api()
       .get_coin_metadata(coin_type)
       .await
@@ -84,8 +207,8 @@ impl SuiRepository for EnhancedSuiRepository {
   }
 
   async fn get_total_supply(&self, coin_type: String) -> Result<Option<u64>> {
-    let supply = self.app_state
-      .sui_client.client
+    let supply = self
+      .client
       .coin_read_api()
       .get_total_supply(coin_type)
       .await
@@ -100,8 +223,8 @@ impl SuiRepository for EnhancedSuiRepository {
     amount: u64,
     exclude: Vec<ObjectID>,
   ) -> Result<Vec<Coin>> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .coin_read_api()
       .select_coins(address, coin_type, amount.into(), exclude)
       .await
@@ -115,12 +238,20 @@ impl SuiRepository for EnhancedSuiRepository {
     object_id: ObjectID,
     options: Option<SuiObjectDataOptions>,
   ) -> Result<SuiObjectResponse> {
-    self.app_state
-      .sui_client.client
+    let options = options.unwrap_or_default();
+    // The options decide which parts of the object are read
+    let read = serde_json::to_string(&options).unwrap_or_default();
+    if let Some(object) = self.cached_object(&object_id, &read).await {
+      return Ok(object);
+    }
+    let object = self
+      .client
       .read_api()
-      .get_object_with_options(object_id, options.unwrap_or_default())
+      .get_object_with_options(object_id, options)
       .await
-      .map_err(|e| Error::Internal(format!("Failed to get object: {}", e)))
+      .map_err(|e| Error::Internal(format!("Failed to get object: {}", e)))?;
+    self.cache_object(&object_id, &read, &object).await;
+    Ok(object)
   }
 
   async fn get_objects(
@@ -128,8 +259,8 @@ impl SuiRepository for EnhancedSuiRepository {
     object_ids: Vec<ObjectID>,
     options: Option<SuiObjectDataOptions>,
   ) -> Result<Vec<SuiObjectResponse>> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .read_api()
       .multi_get_object_with_options(object_ids, options.unwrap_or_default())
       .await
@@ -148,8 +279,8 @@ impl SuiRepository for EnhancedSuiRepository {
       SuiObjectDataOptions::full_content(),
     ));
     
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .read_api()
       .get_owned_objects(address, query, cursor, limit)
       .await
@@ -162,8 +293,8 @@ impl SuiRepository for EnhancedSuiRepository {
     cursor: Option<ObjectID>,
     limit: Option<usize>,
   ) -> Result<Page<DynamicFieldInfo, ObjectID>> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .read_api()
       .get_dynamic_fields(parent_object_id, cursor, limit)
       .await
@@ -177,8 +308,8 @@ impl SuiRepository for EnhancedSuiRepository {
     digest: TransactionDigest,
     options: Option<SuiTransactionBlockResponseOptions>,
   ) -> Result<SuiTransactionBlockResponse> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .read_api()
       .get_transaction_with_options(digest, options.unwrap_or_default())
       .await
@@ -190,8 +321,8 @@ impl SuiRepository for EnhancedSuiRepository {
     digests: Vec<TransactionDigest>,
     options: Option<SuiTransactionBlockResponseOptions>,
   ) -> Result<Vec<SuiTransactionBlockResponse>> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .read_api()
       .multi_get_transactions_with_options(digests, options.unwrap_or_default())
       .await
@@ -201,8 +332,8 @@ impl SuiRepository for EnhancedSuiRepository {
   // ============== EVENT OPERATIONS ==============
 
   async fn get_events(&self, digest: TransactionDigest) -> Result<Vec<SuiEvent>> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .event_api()
       .get_events(digest)
       .await
@@ -213,8 +344,8 @@ impl SuiRepository for EnhancedSuiRepository {
   // ============== NETWORK INFO ==============
 
   async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .read_api()
       .get_latest_checkpoint_sequence_number()
       .await
@@ -222,8 +353,8 @@ impl SuiRepository for EnhancedSuiRepository {
   }
 
   async fn get_total_transaction_blocks(&self) -> Result<u64> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .read_api()
       .get_total_transaction_blocks()
       .await
@@ -231,8 +362,8 @@ impl SuiRepository for EnhancedSuiRepository {
   }
 
   async fn get_reference_gas_price(&self) -> Result<u64> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .governance_api()
       .get_reference_gas_price()
       .await
@@ -240,8 +371,8 @@ impl SuiRepository for EnhancedSuiRepository {
   }
 
   async fn get_chain_identifier(&self) -> Result<String> {
-    self.app_state
-      .sui_client.client
+    self
+      .client
       .read_api()
       .get_chain_identifier()
       .await
@@ -260,15 +391,22 @@ impl SuiRepository for EnhancedSuiRepository {
     Ok(coins.data.into_iter().next())
   }
 
-  // ============== PLACEHOLDER IMPLEMENTATIONS ==============
-  // These methods need to be implemented based on your specific gas station logic
+  // ============== GAS STATION OPERATIONS ==============
 
-  async fn get_available_gas(&self, _required_budget: u64) -> Result<ObjectID> {
-    Err(Error::ImplementationPending("Gas station operations not implemented in enhanced repository".to_string()))
+  async fn get_available_gas(&self, required_budget: u64) -> Result<ObjectID> {
+    self
+      .gas_station()?
+      .get_available_gas(required_budget)
+      .await
+      .map_err(|e| Error::Internal(format!("Failed to reserve gas: {}", e)))
   }
 
-  async fn release_gas(&self, _object_id: ObjectID) -> Result<()> {
-    Err(Error::ImplementationPending("Gas station operations not implemented in enhanced repository".to_string()))
+  async fn release_gas(&self, object_id: ObjectID) -> Result<()> {
+    self
+      .gas_station()?
+      .release_gas(object_id)
+      .await
+      .map_err(|e| Error::Internal(format!("Failed to release gas: {}", e)))
   }
 
   async fn sponsor_transaction(
@@ -280,26 +418,45 @@ impl SuiRepository for EnhancedSuiRepository {
   }
 
   async fn get_pool_stats(&self) -> Result<crate::models::GasPoolStatus> {
-    Err(Error::ImplementationPending("Gas pool stats not implemented in enhanced repository".to_string()))
+    self
+      .gas_station()?
+      .get_pool_stats()
+      .await
+      .map_err(|e| Error::Internal(format!("Failed to get gas pool stats: {}", e)))
   }
 
   async fn refresh_gas_pool(&self) -> Result<()> {
-    Err(Error::ImplementationPending("Gas pool refresh not implemented in enhanced repository".to_string()))
+    self
+      .gas_station()?
+      .refresh_gas_pool()
+      .await
+      .map_err(|e| Error::SuiClient(format!("Failed to refresh gas pool: {}", e)))
   }
 
+  // ============== SPONSORSHIPS ==============
+
   async fn log_sponsored_transaction(
     &self,
-    _user_address: &SuiAddress,
-    _gas_budget: u64,
+    user_address: &SuiAddress,
+    app_id: Option<&str>,
+    gas_budget: u64,
   ) -> Result<()> {
-    Err(Error::ImplementationPending("Transaction logging not implemented in enhanced repository".to_string()))
+    self.sponsorships.log(user_address, app_id, gas_budget).await
   }
 
-  async fn get_user_stats(&self, _address: &str) -> Result<Option<crate::models::UserStats>> {
-    Err(Error::ImplementationPending("User stats not implemented in enhanced repository".to_string()))
+  async fn get_user_stats(&self, address: &str) -> Result<Option<crate::models::UserStats>> {
+    self.sponsorships.user_stats(address).await
   }
 
   async fn check_rate_limit(&self, _user_address: &SuiAddress) -> Result<bool> {
     Ok(true) // Always allow for enhanced repository
   }
+
+  async fn get_gas_quota(&self, subject: &QuotaSubject) -> Result<Option<GasQuota>> {
+    self.sponsorships.quota(subject).await
+  }
+
+  async fn get_gas_usage(&self, subject: &QuotaSubject) -> Result<GasUsage> {
+    self.sponsorships.usage(subject).await
+  }
 }
\ No newline at end of file
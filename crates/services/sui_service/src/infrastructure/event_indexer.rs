@@ -0,0 +1,165 @@
+use jd_core::{AppState, shutdown::Shutdown};
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::time::Duration;
+use sui_sdk::SuiClient;
+use sui_sdk::rpc_types::{
+  EventFilter, SuiEvent, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{info, warn};
+
+use crate::infrastructure::object_cache::SuiObjectCache;
+use crate::infrastructure::onchain_event_store::OnchainEventStore;
+use crate::{Result, error::Error};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+const PAGE_SIZE: usize = 50;
+/// Pages read per package per poll, so one busy package cannot starve the
+/// others.
+const MAX_PAGES_PER_POLL: usize = 20;
+
+/// Follows the events of the configured packages in checkpoint order and
+/// stores them, resuming from each package's stored cursor after a restart.
+/// Cached reads of the objects and owners their transactions touched are
+/// dropped as the events are stored.
+pub struct EventIndexer {
+  sui_client: SuiClient,
+  store: OnchainEventStore,
+  packages: Vec<ObjectID>,
+  cache: Option<SuiObjectCache>,
+}
+
+impl EventIndexer {
+  pub fn new(sui_client: SuiClient, store: OnchainEventStore, packages: Vec<ObjectID>) -> Self {
+    Self { sui_client, store, packages, cache: None }
+  }
+
+  pub fn with_cache(mut self, cache: Option<SuiObjectCache>) -> Self {
+    self.cache = cache;
+    self
+  }
+
+  /// The indexer of `SUI.INDEXED_PACKAGES`, unless none are set or
+  /// `SUI.EVENT_INDEXER_ENABLED=false` leaves indexing to another process.
+  pub fn from_state(state: &AppState) -> Result<Option<Self>> {
+    let config = &state.config.sui;
+    if !config.event_indexer_enabled.unwrap_or(true) {
+      return Ok(None);
+    }
+    let packages = config
+      .indexed_packages
+      .iter()
+      .flat_map(|packages| packages.split(','))
+      .map(str::trim)
+      .filter(|package| !package.is_empty())
+      .map(|package| {
+        ObjectID::from_str(package)
+          .map_err(|_| Error::InvalidRequest(format!("Invalid indexed package '{}'", package)))
+      })
+      .collect::<Result<Vec<_>>>()?;
+    if packages.is_empty() {
+      return Ok(None);
+    }
+
+    let store = OnchainEventStore::new(state.mm().dbx().db().clone());
+    let cache = SuiObjectCache::from_state(state, state.sui_networks().default_network());
+    Ok(Some(Self::new(state.sui_client.client.clone(), store, packages).with_cache(cache)))
+  }
+
+  /// Poll for new events until shutdown. A package being indexed then is
+  /// finished, so its cursor is stored with the events read; the packages
+  /// after it resume from their cursors on the next start.
+  pub async fn run(self, shutdown: Shutdown) {
+    info!("Indexing events of {} package(s)", self.packages.len());
+    let mut ticker = interval(POLL_INTERVAL);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+      tokio::select! {
+        _ = ticker.tick() => {}
+        _ = shutdown.triggered() => break,
+      }
+      for package in &self.packages {
+        if shutdown.is_triggered() {
+          break;
+        }
+        match self.index_package(*package).await {
+          Ok(0) => {}
+          Ok(indexed) => info!(package = %package, indexed, "Indexed on-chain events"),
+          Err(e) => warn!(package = %package, error = %e, "Event indexing failed"),
+        }
+      }
+    }
+    info!("Event indexer stopped");
+  }
+
+  /// Read and store `package`'s events after its cursor. Returns how many
+  /// events were read.
+  pub async fn index_package(&self, package: ObjectID) -> Result<usize> {
+    let mut cursor = self.store.cursor(package).await?;
+    let mut indexed = 0;
+    for _ in 0..MAX_PAGES_PER_POLL {
+      let page = self
+        .sui_client
+        .event_api()
+        .query_events(EventFilter::Package(package), cursor, Some(PAGE_SIZE), false)
+        .await
+        .map_err(|e| Error::SuiClient(format!("Failed to query events: {}", e)))?;
+      let Some(last) = page.data.last() else {
+        break;
+      };
+      cursor = Some(last.id);
+      self.store.store_events(package, &page.data).await?;
+      if let Some(cache) = &self.cache {
+        let invalidated = self.invalidate(cache, &page.data).await;
+        if let Err(e) = invalidated {
+          warn!(package = %package, error = %e, "Sui object cache invalidation failed");
+        }
+      }
+      indexed += page.data.len();
+      if !page.has_next_page {
+        break;
+      }
+    }
+    Ok(indexed)
+  }
+
+  /// Drop cached reads of what the events' transactions changed: their
+  /// created, mutated and deleted objects, the owners of those objects and
+  /// of any balance change, and the senders.
+  async fn invalidate(&self, cache: &SuiObjectCache, events: &[SuiEvent]) -> Result<()> {
+    let mut owners: HashSet<SuiAddress> = events.iter().map(|event| event.sender).collect();
+    let digests: Vec<_> = events
+      .iter()
+      .map(|event| event.id.tx_digest)
+      .collect::<HashSet<_>>()
+      .into_iter()
+      .collect();
+    let options = SuiTransactionBlockResponseOptions::new().with_effects().with_balance_changes();
+    let transactions = self
+      .sui_client
+      .read_api()
+      .multi_get_transactions_with_options(digests, options)
+      .await
+      .map_err(|e| Error::SuiClient(format!("Failed to get event transactions: {}", e)))?;
+
+    let mut objects = HashSet::new();
+    for transaction in &transactions {
+      if let Some(effects) = &transaction.effects {
+        let changed = effects.created().iter().chain(effects.mutated()).chain(effects.unwrapped());
+        for changed in changed {
+          objects.insert(changed.reference.object_id);
+          owners.extend(changed.owner.get_owner_address().ok());
+        }
+        for removed in effects.deleted().iter().chain(effects.wrapped()) {
+          objects.insert(removed.object_id);
+        }
+      }
+      for change in transaction.balance_changes.iter().flatten() {
+        owners.extend(change.owner.get_owner_address().ok());
+      }
+    }
+    cache.invalidate(&objects, &owners).await
+  }
+}
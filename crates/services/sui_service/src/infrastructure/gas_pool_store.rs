@@ -0,0 +1,249 @@
+use anyhow::Result;
+use jd_core::sui::network::SuiNetwork;
+use sqlx::{Pool, Postgres, Row, postgres::PgRow};
+use std::str::FromStr;
+use std::time::Duration;
+use sui_sdk::rpc_types::Coin;
+use sui_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use uuid::Uuid;
+
+use crate::domain::gas_pool::PoolCoin;
+use crate::models::GasPoolStatus;
+
+/// The sponsor's gas coins on one network in Postgres, shared by every
+/// instance so a coin is only ever held by one in-flight transaction.
+#[derive(Clone)]
+pub struct GasPoolStore {
+  db: Pool<Postgres>,
+  network: SuiNetwork,
+}
+
+impl GasPoolStore {
+  pub fn new(db: Pool<Postgres>, network: SuiNetwork) -> Self {
+    Self { db, network }
+  }
+
+  /// Replace the sponsor's coins with those read from chain. Reservations
+  /// are kept, and a stale coin becomes usable once its version moved on.
+  pub async fn sync(&self, sponsor: SuiAddress, coins: &[Coin]) -> Result<()> {
+    let ids: Vec<String> = coins.iter().map(|coin| coin.coin_object_id.to_string()).collect();
+    let versions: Vec<i64> = coins.iter().map(|coin| coin.version.value() as i64).collect();
+    let digests: Vec<String> = coins.iter().map(|coin| coin.digest.to_string()).collect();
+    let balances: Vec<i64> = coins.iter().map(|coin| coin.balance as i64).collect();
+
+    let mut tx = self.db.begin().await?;
+    sqlx::query(
+      r#"
+      DELETE FROM sponsor_gas_coins
+      WHERE network = $3 AND sponsor_address = $1 AND NOT object_id = ANY($2)
+      "#,
+    )
+    .bind(sponsor.to_string())
+    .bind(&ids)
+    .bind(self.network.as_str())
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+      r#"
+      INSERT INTO sponsor_gas_coins (network, object_id, sponsor_address, version, digest, balance)
+      SELECT $6, id, $1, version, digest, balance
+      FROM UNNEST($2::text[], $3::bigint[], $4::text[], $5::bigint[])
+        AS coin(id, version, digest, balance)
+      ON CONFLICT (network, object_id) DO UPDATE
+      SET version = EXCLUDED.version,
+          digest = EXCLUDED.digest,
+          balance = EXCLUDED.balance,
+          stale = sponsor_gas_coins.stale AND sponsor_gas_coins.version = EXCLUDED.version,
+          synced_at = NOW()
+      "#,
+    )
+    .bind(sponsor.to_string())
+    .bind(&ids)
+    .bind(&versions)
+    .bind(&digests)
+    .bind(&balances)
+    .bind(self.network.as_str())
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+  }
+
+  /// The sponsor's coins, largest first. Stale coins are left out.
+  pub async fn coins(&self, sponsor: SuiAddress) -> Result<Vec<PoolCoin>> {
+    let rows = sqlx::query(
+      r#"
+      SELECT object_id, version, digest, balance,
+             reserved_until IS NOT NULL AND reserved_until > NOW() AS reserved
+      FROM sponsor_gas_coins
+      WHERE network = $2 AND sponsor_address = $1 AND NOT stale
+      ORDER BY balance DESC
+      "#,
+    )
+    .bind(sponsor.to_string())
+    .bind(self.network.as_str())
+    .fetch_all(&self.db)
+    .await?;
+    rows
+      .iter()
+      .map(|row| {
+        Ok(PoolCoin {
+          object_ref: object_ref(row)?,
+          balance: row.get::<i64, _>("balance") as u64,
+          reserved: row.get("reserved"),
+        })
+      })
+      .collect()
+  }
+
+  /// Coin counts and balance of the sponsor's pool. Reserved and stale
+  /// coins are not available.
+  pub async fn stats(&self, sponsor: SuiAddress) -> Result<GasPoolStatus> {
+    let row = sqlx::query(
+      r#"
+      SELECT COUNT(*) AS total_objects,
+             COALESCE(SUM(balance), 0)::BIGINT AS total_balance,
+             COUNT(*) FILTER (
+               WHERE NOT stale AND (reserved_until IS NULL OR reserved_until <= NOW())
+             ) AS available_objects
+      FROM sponsor_gas_coins
+      WHERE network = $2 AND sponsor_address = $1
+      "#,
+    )
+    .bind(sponsor.to_string())
+    .bind(self.network.as_str())
+    .fetch_one(&self.db)
+    .await?;
+    let total_objects = row.get::<i64, _>("total_objects") as usize;
+    let available_objects = row.get::<i64, _>("available_objects") as usize;
+    Ok(GasPoolStatus {
+      total_objects,
+      total_balance: row.get::<i64, _>("total_balance") as u64,
+      available_objects,
+      utilization_rate: if total_objects > 0 {
+        (total_objects - available_objects) as f64 / total_objects as f64 * 100.0
+      } else {
+        0.0
+      },
+    })
+  }
+
+  /// Hold the smallest free coin with at least `min_balance` for `ttl`.
+  /// Rows locked by a concurrent reservation are skipped rather than
+  /// waited on, so two sponsorships never get the same coin.
+  pub async fn reserve(
+    &self,
+    sponsor: SuiAddress,
+    min_balance: u64,
+    ttl: Duration,
+  ) -> Result<Option<(Uuid, PoolCoin)>> {
+    let reservation_id = Uuid::new_v4();
+    let row = sqlx::query(
+      r#"
+      UPDATE sponsor_gas_coins
+      SET reservation_id = $3, reserved_until = NOW() + make_interval(secs => $4)
+      WHERE network = $5 AND object_id = (
+        SELECT object_id FROM sponsor_gas_coins
+        WHERE network = $5 AND sponsor_address = $1 AND balance >= $2 AND NOT stale
+          AND (reserved_until IS NULL OR reserved_until <= NOW())
+        ORDER BY balance
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+      )
+      RETURNING object_id, version, digest, balance
+      "#,
+    )
+    .bind(sponsor.to_string())
+    .bind(min_balance as i64)
+    .bind(reservation_id)
+    .bind(ttl.as_secs_f64())
+    .bind(self.network.as_str())
+    .fetch_optional(&self.db)
+    .await?;
+    row
+      .map(|row| {
+        let coin = PoolCoin {
+          object_ref: object_ref(&row)?,
+          balance: row.get::<i64, _>("balance") as u64,
+          reserved: true,
+        };
+        Ok((reservation_id, coin))
+      })
+      .transpose()
+  }
+
+  /// Hold all of `coins` for `ttl`, or none of them if any is already held
+  /// or has moved to another version.
+  pub async fn reserve_coins(&self, coins: &[ObjectRef], ttl: Duration) -> Result<Option<Uuid>> {
+    let reservation_id = Uuid::new_v4();
+    let ids: Vec<String> = coins.iter().map(|(id, _, _)| id.to_string()).collect();
+    let versions: Vec<i64> = coins.iter().map(|(_, version, _)| version.value() as i64).collect();
+
+    let mut tx = self.db.begin().await?;
+    let reserved = sqlx::query(
+      r#"
+      UPDATE sponsor_gas_coins c
+      SET reservation_id = $3, reserved_until = NOW() + make_interval(secs => $4)
+      FROM UNNEST($1::text[], $2::bigint[]) AS held(id, version)
+      WHERE c.network = $5 AND c.object_id = held.id AND c.version = held.version AND NOT c.stale
+        AND (c.reserved_until IS NULL OR c.reserved_until <= NOW())
+      "#,
+    )
+    .bind(&ids)
+    .bind(&versions)
+    .bind(reservation_id)
+    .bind(ttl.as_secs_f64())
+    .bind(self.network.as_str())
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if reserved != coins.len() as u64 {
+      tx.rollback().await?;
+      return Ok(None);
+    }
+    tx.commit().await?;
+    Ok(Some(reservation_id))
+  }
+
+  /// Free the coins held by a reservation. Coins a transaction was executed
+  /// with are marked stale until a refresh reads their new version.
+  pub async fn release(&self, reservation_id: Uuid, spent: bool) -> Result<()> {
+    sqlx::query(
+      r#"
+      UPDATE sponsor_gas_coins
+      SET reservation_id = NULL, reserved_until = NULL, stale = stale OR $2
+      WHERE reservation_id = $1
+      "#,
+    )
+    .bind(reservation_id)
+    .bind(spent)
+    .execute(&self.db)
+    .await?;
+    Ok(())
+  }
+
+  /// Free a coin whatever reservation holds it.
+  pub async fn release_coin(&self, object_id: ObjectID, spent: bool) -> Result<()> {
+    sqlx::query(
+      r#"
+      UPDATE sponsor_gas_coins
+      SET reservation_id = NULL, reserved_until = NULL, stale = stale OR $2
+      WHERE network = $3 AND object_id = $1
+      "#,
+    )
+    .bind(object_id.to_string())
+    .bind(spent)
+    .bind(self.network.as_str())
+    .execute(&self.db)
+    .await?;
+    Ok(())
+  }
+}
+
+fn object_ref(row: &PgRow) -> Result<ObjectRef> {
+  Ok((
+    ObjectID::from_str(row.get("object_id"))?,
+    SequenceNumber::from_u64(row.get::<i64, _>("version") as u64),
+    ObjectDigest::from_str(row.get("digest"))?,
+  ))
+}
@@ -1,121 +1,281 @@
-use anyhow::Result;
-use std::collections::HashMap;
-use tokio::sync::RwLock;
+use anyhow::{Context, Result};
+use fastcrypto::{ed25519::Ed25519KeyPair, traits::ToFromBytes};
+use jd_core::AppState;
+use jd_core::sui::network::SuiNetwork;
+use jd_core::sui::provider::{ProviderRole, RpcProviderPool};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
+use uuid::Uuid;
 
-use sui_sdk::rpc_types::{SuiObjectDataOptions, SuiObjectResponseQuery};
-use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_sdk::rpc_types::{SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions};
 use sui_types::{
-  base_types::{ObjectID, SuiAddress},
-  gas_coin::GasCoin,
+  base_types::{ObjectID, ObjectRef, SuiAddress},
+  crypto::SuiKeyPair,
+  quorum_driver_types::ExecuteTransactionRequestType,
+  transaction::{Transaction, TransactionData},
 };
 
+use crate::domain::gas_pool::{GasPoolTarget, RebalancePlan, plan_rebalance};
+use crate::infrastructure::gas_pool_store::GasPoolStore;
 use crate::models::GasPoolStatus;
 
-#[derive(Debug)]
-pub struct GasObject {
-  pub object_id: ObjectID,
-  pub version: u64,
+/// How long a sponsorship may hold its gas coin before the reservation
+/// lapses and the coin goes back to the pool.
+const SPONSORSHIP_RESERVATION_TTL: Duration = Duration::from_secs(5 * 60);
+/// How long a rebalance may hold the coins it merges and splits.
+const REBALANCE_RESERVATION_TTL: Duration = Duration::from_secs(2 * 60);
+const COIN_PAGE_SIZE: usize = 200;
+
+/// A gas coin held for one sponsored transaction.
+#[derive(Debug, Clone)]
+pub struct GasReservation {
+  pub reservation_id: Uuid,
+  pub object_ref: ObjectRef,
   pub balance: u64,
-  pub in_use: bool,
 }
 
+/// Hands out the sponsor address's SUI coins as gas, one transaction per
+/// coin at a time, and keeps the coins split to the pool's target. Its RPC
+/// requests fail over between the network's endpoints.
 pub struct GasStation {
-  pub sui_client: SuiClient,
   pub sponsor_address: SuiAddress,
-  pub gas_pool: RwLock<HashMap<ObjectID, GasObject>>,
   pub max_gas_budget: u64,
+  rpc: Arc<RpcProviderPool>,
+  store: GasPoolStore,
+  target: GasPoolTarget,
+  sponsor_key: Option<SuiKeyPair>,
 }
 
 impl GasStation {
-  pub async fn new(
-    sui_rpc_url: &str,
+  pub fn new(
+    rpc: Arc<RpcProviderPool>,
+    store: GasPoolStore,
     sponsor_address: SuiAddress,
-    max_gas_budget: u64,
-  ) -> Result<Self> {
-    // Initialize SUI client
-    let sui_client = SuiClientBuilder::default().build(sui_rpc_url).await?;
+    target: GasPoolTarget,
+  ) -> Self {
+    Self {
+      sponsor_address,
+      max_gas_budget: target.min_coin_balance,
+      rpc,
+      store,
+      target,
+      sponsor_key: None,
+    }
+  }
 
-    // Initialize gas pool
-    let gas_station =
-      Self { sui_client, sponsor_address, gas_pool: RwLock::new(HashMap::new()), max_gas_budget };
+  /// Sign rebalance transactions with the sponsor's key.
+  pub fn with_sponsor_key(mut self, sponsor_key: SuiKeyPair) -> Self {
+    self.sponsor_key = Some(sponsor_key);
+    self
+  }
 
-    // Load gas objects
-    gas_station.refresh_gas_pool().await?;
+  /// The gas station of the configured sponsor address on `network`, if one
+  /// is set. The sponsor's private key, a hex Ed25519 seed, is only needed
+  /// to rebalance.
+  pub fn from_state(state: &AppState, network: SuiNetwork) -> Result<Option<Self>> {
+    let config = &state.config.sui;
+    let Some(address) = config.sponsor_address.as_deref().filter(|a| !a.trim().is_empty()) else {
+      return Ok(None);
+    };
+    let sponsor_address = SuiAddress::from_str(address.trim()).context("Invalid sponsor address")?;
+    let station = Self::new(
+      state.sui_networks().providers(network)?,
+      GasPoolStore::new(state.mm().dbx().db().clone(), network),
+      sponsor_address,
+      GasPoolTarget::from_config(config),
+    );
 
-    Ok(gas_station)
+    let Some(key) = config.sponsor_private_key.as_deref().filter(|k| !k.trim().is_empty()) else {
+      return Ok(Some(station));
+    };
+    let key = key.trim();
+    let bytes = hex::decode(key.strip_prefix("0x").unwrap_or(key)).context("Invalid private key")?;
+    let keypair =
+      Ed25519KeyPair::from_bytes(&bytes).map_err(|e| anyhow::anyhow!("Invalid private key: {e}"))?;
+    let sponsor_key = SuiKeyPair::Ed25519(keypair);
+    let key_address = SuiAddress::from(&sponsor_key.public());
+    anyhow::ensure!(
+      key_address == sponsor_address,
+      "Private key address {} doesn't match sponsor address {}",
+      key_address,
+      sponsor_address
+    );
+    Ok(Some(station.with_sponsor_key(sponsor_key)))
   }
 
-  pub async fn refresh_gas_pool(&self) -> Result<()> {
-    let gas_objects = self
-      .sui_client
-      .read_api()
-      .get_owned_objects(
-        self.sponsor_address,
-        Some(SuiObjectResponseQuery::new_with_options(
-          SuiObjectDataOptions::new().with_type().with_content(),
-        )),
-        None,
-        None,
-      )
-      .await?;
+  /// The RPC endpoints the station's requests go to.
+  pub fn rpc(&self) -> &RpcProviderPool {
+    &self.rpc
+  }
 
-    let mut pool = self.gas_pool.write().await;
-    pool.clear();
-
-    for obj_response in gas_objects.data {
-      if let Some(obj) = obj_response.data {
-        if let Ok(gas_coin) = GasCoin::try_from(&obj) {
-          let gas_object = GasObject {
-            object_id: obj.object_id,
-            version: obj.version.value(),
-            balance: gas_coin.value(),
-            in_use: false,
-          };
-          pool.insert(obj.object_id, gas_object);
-        }
+  /// Read the sponsor's SUI coins from chain into the pool.
+  pub async fn refresh_gas_pool(&self) -> Result<()> {
+    let mut coins = Vec::new();
+    let mut cursor = None;
+    loop {
+      let sponsor = self.sponsor_address;
+      let page = self
+        .rpc
+        .call(ProviderRole::Read, |client| async move {
+          client.coin_read_api().get_coins(sponsor, None, cursor, Some(COIN_PAGE_SIZE)).await
+        })
+        .await?;
+      coins.extend(page.data);
+      if !page.has_next_page {
+        break;
       }
+      cursor = page.next_cursor;
     }
 
-    info!("Refreshed gas pool with {} objects", pool.len());
+    self.store.sync(self.sponsor_address, &coins).await?;
+    info!("Refreshed gas pool with {} objects", coins.len());
     Ok(())
   }
 
-  pub async fn get_available_gas(&self, required_budget: u64) -> Result<ObjectID> {
-    let mut pool = self.gas_pool.write().await;
-
-    for (object_id, gas_obj) in pool.iter_mut() {
-      if !gas_obj.in_use && gas_obj.balance >= required_budget {
-        gas_obj.in_use = true;
-        return Ok(*object_id);
+  /// Hold a coin able to pay `required_budget` for one sponsorship. The
+  /// pool is refreshed once if every such coin is held or stale.
+  pub async fn reserve_gas(&self, required_budget: u64) -> Result<GasReservation> {
+    anyhow::ensure!(
+      required_budget <= self.max_gas_budget,
+      "Gas budget {} exceeds the maximum of {}",
+      required_budget,
+      self.max_gas_budget
+    );
+    for attempt in 0..2 {
+      if attempt > 0 {
+        self.refresh_gas_pool().await?;
+      }
+      let reserved = self
+        .store
+        .reserve(self.sponsor_address, required_budget, SPONSORSHIP_RESERVATION_TTL)
+        .await?;
+      if let Some((reservation_id, coin)) = reserved {
+        return Ok(GasReservation {
+          reservation_id,
+          object_ref: coin.object_ref,
+          balance: coin.balance,
+        });
       }
     }
-
     anyhow::bail!("No available gas object with sufficient balance");
   }
 
-  pub async fn release_gas(&self, object_id: ObjectID) {
-    let mut pool = self.gas_pool.write().await;
-    if let Some(gas_obj) = pool.get_mut(&object_id) {
-      gas_obj.in_use = false;
-    }
+  /// Give back a reservation whose transaction was never submitted.
+  pub async fn cancel_reservation(&self, reservation: &GasReservation) -> Result<()> {
+    self.store.release(reservation.reservation_id, false).await
+  }
+
+  pub async fn reference_gas_price(&self) -> Result<u64> {
+    let gas_price = self
+      .rpc
+      .call(ProviderRole::Read, |client| async move {
+        client.governance_api().get_reference_gas_price().await
+      })
+      .await?;
+    Ok(gas_price)
+  }
+
+  /// Sign `data` as the sponsor.
+  pub fn sign(&self, data: TransactionData) -> Result<Transaction> {
+    let Some(sponsor_key) = &self.sponsor_key else {
+      anyhow::bail!("Sponsor private key is not configured");
+    };
+    Ok(Transaction::from_data_and_signer(data, vec![sponsor_key]))
+  }
+
+  pub async fn get_available_gas(&self, required_budget: u64) -> Result<ObjectID> {
+    Ok(self.reserve_gas(required_budget).await?.object_ref.0)
+  }
+
+  /// Give a sponsorship's coin back. It is handed out again once a refresh
+  /// has read the version its transaction left it at.
+  pub async fn release_gas(&self, object_id: ObjectID) -> Result<()> {
+    self.store.release_coin(object_id, true).await
+  }
+
+  pub async fn get_pool_stats(&self) -> Result<GasPoolStatus> {
+    self.store.stats(self.sponsor_address).await
+  }
+
+  /// Refresh the pool and merge and split its free coins towards the
+  /// target in one transaction. Returns the plan that was executed, empty
+  /// when the pool is on target or the coins were held by another instance.
+  pub async fn rebalance(&self) -> Result<RebalancePlan> {
+    self.refresh_gas_pool().await?;
+    let coins = self.store.coins(self.sponsor_address).await?;
+    let plan = plan_rebalance(&coins, &self.target);
+    let Some(primary) = plan.primary.filter(|_| !plan.is_empty()) else {
+      return Ok(RebalancePlan::default());
+    };
+    let Some(sponsor_key) = &self.sponsor_key else {
+      anyhow::bail!("Sponsor private key is not configured");
+    };
+
+    let held: Vec<ObjectRef> = std::iter::once(primary).chain(plan.merge.iter().copied()).collect();
+    let Some(reservation_id) = self.store.reserve_coins(&held, REBALANCE_RESERVATION_TTL).await?
+    else {
+      return Ok(RebalancePlan::default());
+    };
+    let executed = self.execute_rebalance(&plan, primary, sponsor_key).await;
+    self.store.release(reservation_id, true).await?;
+    executed?;
+
+    self.refresh_gas_pool().await?;
+    info!("Rebalanced gas pool: {} coins merged, {} split", plan.merge.len(), plan.split.len());
+    Ok(plan)
   }
 
-  pub async fn get_pool_stats(&self) -> GasPoolStatus {
-    let pool = self.gas_pool.read().await;
-    let total_objects = pool.len();
-    let total_balance: u64 = pool.values().map(|obj| obj.balance).sum();
-    let available_objects = pool.values().filter(|obj| !obj.in_use).count();
-
-    GasPoolStatus {
-      total_objects,
-      total_balance,
-      available_objects,
-      utilization_rate: if total_objects > 0 {
-        (total_objects - available_objects) as f64 / total_objects as f64 * 100.0
-      } else {
-        0.0
-      },
+  async fn execute_rebalance(
+    &self,
+    plan: &RebalancePlan,
+    primary: ObjectRef,
+    sponsor_key: &SuiKeyPair,
+  ) -> Result<()> {
+    let gas_price = self.reference_gas_price().await?;
+    let data = if plan.split.is_empty() {
+      TransactionData::new_pay_all_sui(
+        self.sponsor_address,
+        plan.merge.clone(),
+        self.sponsor_address,
+        primary,
+        self.target.rebalance_gas_budget,
+        gas_price,
+      )
+    } else {
+      TransactionData::new_pay_sui(
+        self.sponsor_address,
+        plan.merge.clone(),
+        vec![self.sponsor_address; plan.split.len()],
+        plan.split.clone(),
+        primary,
+        self.target.rebalance_gas_budget,
+        gas_price,
+      )?
+    };
+    let transaction = Transaction::from_data_and_signer(data, vec![sponsor_key]);
+
+    let response = self
+      .rpc
+      .call(ProviderRole::Write, |client| {
+        let transaction = transaction.clone();
+        async move {
+          client
+            .quorum_driver_api()
+            .execute_transaction_block(
+              transaction,
+              SuiTransactionBlockResponseOptions::new().with_effects(),
+              Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+        }
+      })
+      .await?;
+    if let Some(effects) = &response.effects {
+      let status = effects.status();
+      anyhow::ensure!(status.is_ok(), "Rebalance {} failed: {:?}", response.digest, status);
     }
+    Ok(())
   }
 }
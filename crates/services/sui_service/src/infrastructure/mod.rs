@@ -1,3 +1,10 @@
 // Infrastructure layer module
+pub mod attestation_publisher;
+pub mod badge_minter;
 pub mod enhanced_sui_repository;
+pub mod event_indexer;
+pub mod gas_pool_store;
 pub mod gas_station;
+pub mod object_cache;
+pub mod onchain_event_store;
+pub mod sponsorship_store;
@@ -0,0 +1,139 @@
+use jd_core::AppState;
+use jd_core::sui::network::SuiNetwork;
+use redis::AsyncCommands;
+use redis::aio::MultiplexedConnection;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use crate::{Result, error::Error};
+
+const DEFAULT_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<T> {
+  cached_at: u64,
+  value: T,
+}
+
+fn now_secs() -> u64 {
+  SystemTime::now().duration_since(UNIX_EPOCH).map(|since| since.as_secs()).unwrap_or_default()
+}
+
+/// Recent RPC reads of objects and of owners' coins and balances, kept in
+/// Redis for a short TTL. Each object and each owner is one Redis hash whose
+/// fields are the reads made of it, so a change drops all of them at once.
+/// Cache failures are logged and fall through to the RPC read.
+#[derive(Clone)]
+pub struct SuiObjectCache {
+  redis: Arc<redis::Client>,
+  network: SuiNetwork,
+  ttl: Duration,
+}
+
+impl SuiObjectCache {
+  pub fn new(redis: Arc<redis::Client>, network: SuiNetwork, ttl: Duration) -> Self {
+    Self { redis, network, ttl }
+  }
+
+  /// The cache of `network`, unless `SUI.OBJECT_CACHE_TTL_SECS=0` disables it.
+  pub fn from_state(state: &AppState, network: SuiNetwork) -> Option<Self> {
+    let ttl = state.config.sui.object_cache_ttl_secs.map(Duration::from_secs);
+    let ttl = ttl.unwrap_or(DEFAULT_TTL);
+    (!ttl.is_zero()).then(|| Self::new(state.redis.clone(), network, ttl))
+  }
+
+  fn object_key(&self, object_id: &ObjectID) -> String {
+    format!("sui:{}:object:{}", self.network, object_id)
+  }
+
+  fn owner_key(&self, owner: &SuiAddress) -> String {
+    format!("sui:{}:owner:{}", self.network, owner)
+  }
+
+  async fn connection(&self) -> Result<MultiplexedConnection> {
+    self
+      .redis
+      .get_multiplexed_async_connection()
+      .await
+      .map_err(|e| Error::Internal(format!("Failed to get Redis connection: {}", e)))
+  }
+
+  async fn get<T: DeserializeOwned>(&self, key: &str, field: &str) -> Option<T> {
+    let cached: redis::RedisResult<Option<String>> = match self.connection().await {
+      Ok(mut conn) => conn.hget(key, field).await,
+      Err(_) => return None,
+    };
+    let entry: CacheEntry<T> = match cached {
+      Ok(cached) => serde_json::from_str(&cached?).ok()?,
+      Err(e) => {
+        tracing::warn!("Sui object cache read failed: {}", e);
+        return None;
+      }
+    };
+    let age = now_secs().saturating_sub(entry.cached_at);
+    (age < self.ttl.as_secs()).then_some(entry.value)
+  }
+
+  /// Store a read. Writing a field extends the whole hash's TTL, so each
+  /// field carries its own time and is dropped on read once it is older.
+  async fn put<T: Serialize>(&self, key: &str, field: &str, value: &T) {
+    let entry = CacheEntry { cached_at: now_secs(), value };
+    let Ok(json) = serde_json::to_string(&entry) else {
+      return;
+    };
+    let Ok(mut conn) = self.connection().await else {
+      return;
+    };
+    let stored: redis::RedisResult<()> = redis::pipe()
+      .hset(key, field, json)
+      .ignore()
+      .expire(key, self.ttl.as_secs().max(1) as i64)
+      .ignore()
+      .query_async(&mut conn)
+      .await;
+    if let Err(e) = stored {
+      tracing::warn!("Sui object cache write failed: {}", e);
+    }
+  }
+
+  pub async fn object<T: DeserializeOwned>(&self, object_id: &ObjectID, read: &str) -> Option<T> {
+    self.get(&self.object_key(object_id), read).await
+  }
+
+  pub async fn put_object<T: Serialize>(&self, object_id: &ObjectID, read: &str, value: &T) {
+    self.put(&self.object_key(object_id), read, value).await
+  }
+
+  pub async fn owned<T: DeserializeOwned>(&self, owner: &SuiAddress, read: &str) -> Option<T> {
+    self.get(&self.owner_key(owner), read).await
+  }
+
+  pub async fn put_owned<T: Serialize>(&self, owner: &SuiAddress, read: &str, value: &T) {
+    self.put(&self.owner_key(owner), read, value).await
+  }
+
+  /// Drop every cached read of `objects` and of `owners`' coins.
+  pub async fn invalidate(
+    &self,
+    objects: &HashSet<ObjectID>,
+    owners: &HashSet<SuiAddress>,
+  ) -> Result<()> {
+    let keys: Vec<String> = objects
+      .iter()
+      .map(|object_id| self.object_key(object_id))
+      .chain(owners.iter().map(|owner| self.owner_key(owner)))
+      .collect();
+    if keys.is_empty() {
+      return Ok(());
+    }
+    let mut conn = self.connection().await?;
+    let _: () = conn
+      .del(keys)
+      .await
+      .map_err(|e| Error::Internal(format!("Failed to invalidate Sui object cache: {}", e)))?;
+    Ok(())
+  }
+}
@@ -0,0 +1,144 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, Row};
+use std::str::FromStr;
+use sui_sdk::rpc_types::SuiEvent;
+use sui_types::base_types::ObjectID;
+use sui_types::digests::TransactionDigest;
+use sui_types::event::EventID;
+
+use crate::models::{EventIndexerCursor, OnchainEvent};
+use crate::{Result, error::Error};
+
+/// Filters of an `onchain_events` read, already normalized.
+#[derive(Debug, Default)]
+pub struct OnchainEventFilter {
+  pub package_id: Option<String>,
+  pub event_type: Option<String>,
+  pub sender: Option<String>,
+  pub tx_digest: Option<String>,
+  pub after: Option<i64>,
+  pub limit: i64,
+}
+
+/// Indexed on-chain events and the cursor each package was indexed up to.
+#[derive(Clone)]
+pub struct OnchainEventStore {
+  db: Pool<Postgres>,
+}
+
+impl OnchainEventStore {
+  pub fn new(db: Pool<Postgres>) -> Self {
+    Self { db }
+  }
+
+  /// The last event indexed for `package`, to resume after.
+  pub async fn cursor(&self, package: ObjectID) -> Result<Option<EventID>> {
+    let row =
+      sqlx::query("SELECT tx_digest, event_seq FROM onchain_event_cursors WHERE package_id = $1")
+        .bind(package.to_string())
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| Error::Internal(e.to_string()))?;
+    row
+      .map(|row| {
+        let tx_digest = TransactionDigest::from_str(row.get("tx_digest"))
+          .map_err(|e| Error::Internal(format!("Invalid stored event cursor: {}", e)))?;
+        Ok(EventID { tx_digest, event_seq: row.get::<i64, _>("event_seq") as u64 })
+      })
+      .transpose()
+  }
+
+  /// Store a page of `package`'s events and move its cursor past them in
+  /// one transaction, so a restart neither skips nor duplicates events.
+  pub async fn store_events(&self, package: ObjectID, events: &[SuiEvent]) -> Result<()> {
+    let Some(last) = events.last() else {
+      return Ok(());
+    };
+    let mut tx = self.db.begin().await.map_err(|e| Error::Internal(e.to_string()))?;
+    let mut inserted = 0;
+    for event in events {
+      let occurred_at = event
+        .timestamp_ms
+        .and_then(|ms| DateTime::<Utc>::from_timestamp_millis(ms as i64));
+      inserted += sqlx::query(
+        r#"
+        INSERT INTO onchain_events
+          (package_id, tx_digest, event_seq, module, event_type, sender, parsed_json, occurred_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (tx_digest, event_seq) DO NOTHING
+        "#,
+      )
+      .bind(package.to_string())
+      .bind(event.id.tx_digest.to_string())
+      .bind(event.id.event_seq as i64)
+      .bind(event.transaction_module.to_string())
+      .bind(event.type_.to_canonical_string(true))
+      .bind(event.sender.to_string())
+      .bind(&event.parsed_json)
+      .bind(occurred_at)
+      .execute(&mut *tx)
+      .await
+      .map_err(|e| Error::Internal(e.to_string()))?
+      .rows_affected();
+    }
+    sqlx::query(
+      r#"
+      INSERT INTO onchain_event_cursors (package_id, tx_digest, event_seq, events_indexed)
+      VALUES ($1, $2, $3, $4)
+      ON CONFLICT (package_id) DO UPDATE
+      SET tx_digest = EXCLUDED.tx_digest,
+          event_seq = EXCLUDED.event_seq,
+          events_indexed = onchain_event_cursors.events_indexed + EXCLUDED.events_indexed,
+          updated_at = NOW()
+      "#,
+    )
+    .bind(package.to_string())
+    .bind(last.id.tx_digest.to_string())
+    .bind(last.id.event_seq as i64)
+    .bind(inserted as i64)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| Error::Internal(e.to_string()))?;
+    tx.commit().await.map_err(|e| Error::Internal(e.to_string()))
+  }
+
+  /// Events matching `filter`, in the order they were indexed.
+  pub async fn list(&self, filter: &OnchainEventFilter) -> Result<Vec<OnchainEvent>> {
+    sqlx::query_as::<_, OnchainEvent>(
+      r#"
+      SELECT id, package_id, tx_digest, event_seq, module, event_type, sender, parsed_json,
+             occurred_at, indexed_at
+      FROM onchain_events
+      WHERE ($1::text IS NULL OR package_id = $1)
+        AND ($2::text IS NULL OR event_type = $2)
+        AND ($3::text IS NULL OR sender = $3)
+        AND ($4::text IS NULL OR tx_digest = $4)
+        AND ($5::bigint IS NULL OR id > $5)
+      ORDER BY id
+      LIMIT $6
+      "#,
+    )
+    .bind(&filter.package_id)
+    .bind(&filter.event_type)
+    .bind(&filter.sender)
+    .bind(&filter.tx_digest)
+    .bind(filter.after)
+    .bind(filter.limit)
+    .fetch_all(&self.db)
+    .await
+    .map_err(|e| Error::Internal(e.to_string()))
+  }
+
+  pub async fn cursors(&self) -> Result<Vec<EventIndexerCursor>> {
+    sqlx::query_as::<_, EventIndexerCursor>(
+      r#"
+      SELECT package_id, tx_digest, event_seq, events_indexed, updated_at
+      FROM onchain_event_cursors
+      ORDER BY package_id
+      "#,
+    )
+    .fetch_all(&self.db)
+    .await
+    .map_err(|e| Error::Internal(e.to_string()))
+  }
+}
@@ -0,0 +1,101 @@
+use sqlx::{Pool, Postgres, Row};
+use sui_sdk::types::base_types::SuiAddress;
+
+use crate::domain::sponsorship_policy::{GasQuota, GasUsage, QuotaSubject};
+use crate::models::UserStats;
+use crate::{Result, error::Error};
+
+/// Sponsored transactions and the gas quotas they count against.
+#[derive(Clone)]
+pub struct SponsorshipStore {
+  db: Pool<Postgres>,
+}
+
+impl SponsorshipStore {
+  pub fn new(db: Pool<Postgres>) -> Self {
+    Self { db }
+  }
+
+  pub async fn log(
+    &self,
+    user_address: &SuiAddress,
+    app_id: Option<&str>,
+    gas_budget: u64,
+  ) -> Result<()> {
+    sqlx::query(
+      "INSERT INTO sponsored_transactions (user_address, app_id, gas_budget) VALUES ($1, $2, $3)",
+    )
+    .bind(user_address.to_string())
+    .bind(app_id)
+    .bind(gas_budget as i64)
+    .execute(&self.db)
+    .await
+    .map_err(|e| Error::Internal(e.to_string()))?;
+    Ok(())
+  }
+
+  pub async fn user_stats(&self, address: &str) -> Result<Option<UserStats>> {
+    sqlx::query_as::<_, UserStats>(
+      r#"
+      SELECT user_address,
+             COUNT(*) AS transaction_count,
+             SUM(gas_budget)::BIGINT AS total_gas_sponsored,
+             MAX(timestamp) AS last_transaction
+      FROM sponsored_transactions
+      WHERE user_address = $1
+      GROUP BY user_address
+      "#,
+    )
+    .bind(address)
+    .fetch_optional(&self.db)
+    .await
+    .map_err(|e| Error::Internal(e.to_string()))
+  }
+
+  /// The quota set for `subject` in place of the configured default.
+  pub async fn quota(&self, subject: &QuotaSubject) -> Result<Option<GasQuota>> {
+    let row = sqlx::query(
+      r#"
+      SELECT daily_gas_budget, weekly_gas_budget
+      FROM sponsorship_quotas
+      WHERE subject_type = $1 AND subject = $2
+      "#,
+    )
+    .bind(subject.kind())
+    .bind(subject.id())
+    .fetch_optional(&self.db)
+    .await
+    .map_err(|e| Error::Internal(e.to_string()))?;
+    Ok(row.map(|row| GasQuota {
+      daily: row.get::<Option<i64>, _>("daily_gas_budget").map(|budget| budget as u64),
+      weekly: row.get::<Option<i64>, _>("weekly_gas_budget").map(|budget| budget as u64),
+    }))
+  }
+
+  /// Gas budgets sponsored for `subject` over the last day and week.
+  pub async fn usage(&self, subject: &QuotaSubject) -> Result<GasUsage> {
+    let column = match subject {
+      QuotaSubject::User(_) => "user_address",
+      QuotaSubject::App(_) => "app_id",
+    };
+    let row = sqlx::query(&format!(
+      r#"
+      SELECT COALESCE(
+               SUM(gas_budget) FILTER (WHERE timestamp > NOW() - INTERVAL '1 day'), 0
+             )::BIGINT AS daily,
+             COALESCE(SUM(gas_budget), 0)::BIGINT AS weekly
+      FROM sponsored_transactions
+      WHERE {} = $1 AND timestamp > NOW() - INTERVAL '7 days'
+      "#,
+      column
+    ))
+    .bind(subject.id())
+    .fetch_one(&self.db)
+    .await
+    .map_err(|e| Error::Internal(e.to_string()))?;
+    Ok(GasUsage {
+      daily: row.get::<i64, _>("daily") as u64,
+      weekly: row.get::<i64, _>("weekly") as u64,
+    })
+  }
+}
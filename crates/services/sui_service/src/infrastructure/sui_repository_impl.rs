@@ -1,4 +1,8 @@
-use crate::infrastructure::gas_station::GasStation;
+use crate::domain::gas_pool::GasPoolTarget;
+use crate::domain::sponsorship_policy::{GasQuota, GasUsage, QuotaSubject};
+use crate::infrastructure::{
+  gas_pool_store::GasPoolStore, gas_station::GasStation, sponsorship_store::SponsorshipStore,
+};
 use crate::models::{GasPoolStatus, UserStats};
 use crate::{Result, domain::sui_repository_trait::SuiRepository, error::Error};
 use async_trait::async_trait;
@@ -9,7 +13,7 @@ use fastcrypto::{
 };
 use futures::{StreamExt, future};
 use jd_core::AppState;
-use jd_utils::time;
+use jd_core::sui::provider::{CircuitSettings, ProviderRole, RpcProviderPool};
 use redis::AsyncCommands;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -34,15 +38,41 @@ impl SuiRepositoryImpl {
     Self { app_state, gas_station: None, sponsor_keystore: None }
   }
 
+  async fn gas_station(
+    app_state: &AppState,
+    sui_rpc_url: &str,
+    sponsor_address: SuiAddress,
+    max_gas_budget: u64,
+  ) -> Result<GasStation> {
+    let network = app_state.sui_networks().default_network();
+    let rpc = RpcProviderPool::from_urls(
+      network,
+      vec![sui_rpc_url.to_string()],
+      Vec::new(),
+      CircuitSettings::from_config(&app_state.config.sui),
+    );
+    let target = GasPoolTarget {
+      min_coin_balance: max_gas_budget,
+      ..GasPoolTarget::from_config(&app_state.config.sui)
+    };
+    let store = GasPoolStore::new(app_state.mm().dbx().db().clone(), network);
+    let gas_station = GasStation::new(Arc::new(rpc), store, sponsor_address, target);
+    gas_station.refresh_gas_pool().await.map_err(|e| Error::Internal(e.to_string()))?;
+    Ok(gas_station)
+  }
+
+  fn sponsorships(&self) -> SponsorshipStore {
+    SponsorshipStore::new(self.app_state.mm().dbx().db().clone())
+  }
+
   pub async fn with_gas_station(
     app_state: AppState,
     sui_rpc_url: &str,
     sponsor_address: SuiAddress,
     max_gas_budget: u64,
   ) -> Result<Self> {
-    let gas_station = GasStation::new(sui_rpc_url, sponsor_address, max_gas_budget)
-      .await
-      .map_err(|e| Error::Internal(e.to_string()))?;
+    let gas_station = Self::gas_station(&app_state, sui_rpc_url, sponsor_address, max_gas_budget)
+      .await?;
 
     Ok(Self { app_state, gas_station: Some(Arc::new(gas_station)), sponsor_keystore: None })
   }
@@ -54,9 +84,8 @@ impl SuiRepositoryImpl {
     sponsor_private_key: &str,
     max_gas_budget: u64,
   ) -> Result<Self> {
-    let gas_station = GasStation::new(sui_rpc_url, sponsor_address, max_gas_budget)
-      .await
-      .map_err(|e| Error::Internal(e.to_string()))?;
+    let gas_station = Self::gas_station(&app_state, sui_rpc_url, sponsor_address, max_gas_budget)
+      .await?;
 
     // Create keystore with sponsor private key
     let mut keystore = InMemKeystore::default();
@@ -160,8 +189,7 @@ impl SuiRepository for SuiRepositoryImpl {
       .as_ref()
       .ok_or_else(|| Error::Internal("Gas station not initialized".to_string()))?;
 
-    gas_station.release_gas(object_id).await;
-    Ok(())
+    gas_station.release_gas(object_id).await.map_err(|e| Error::Internal(e.to_string()))
   }
 
   // TODO: Not really working, need to finish later
@@ -258,13 +286,20 @@ impl SuiRepository for SuiRepositoryImpl {
 
     // Submit the transaction to the Sui network
     let response = gas_station
-      .sui_client
-      .quorum_driver_api()
-      .execute_transaction_block(
-        final_transaction.clone(),
-        sui_sdk::rpc_types::SuiTransactionBlockResponseOptions::full_content(),
-        None,
-      )
+      .rpc()
+      .call(ProviderRole::Write, |client| {
+        let transaction = final_transaction.clone();
+        async move {
+          client
+            .quorum_driver_api()
+            .execute_transaction_block(
+              transaction,
+              sui_sdk::rpc_types::SuiTransactionBlockResponseOptions::full_content(),
+              None,
+            )
+            .await
+        }
+      })
       .await
       .map_err(|e| Error::Internal(format!("Failed to submit transaction to network: {}", e)))?;
 
@@ -279,7 +314,7 @@ impl SuiRepository for SuiRepositoryImpl {
       .as_ref()
       .ok_or_else(|| Error::Internal("Gas station not initialized".to_string()))?;
 
-    Ok(gas_station.get_pool_stats().await)
+    gas_station.get_pool_stats().await.map_err(|e| Error::Internal(e.to_string()))
   }
 
   async fn refresh_gas_pool(&self) -> Result<()> {
@@ -297,24 +332,15 @@ impl SuiRepository for SuiRepositoryImpl {
   async fn log_sponsored_transaction(
     &self,
     user_address: &SuiAddress,
+    app_id: Option<&str>,
     gas_budget: u64,
   ) -> Result<()> {
-    sqlx::query(
-      "INSERT INTO sponsored_transactions (user_address, gas_budget, timestamp) VALUES ($1, $2, $3)"
-    )
-    .bind(user_address.to_string())
-    .bind(gas_budget as i64)
-    .bind(time::now_utc())
-    .execute(self.app_state.mm().dbx().db())
-    .await
-    .map_err(|e| Error::Internal(e.to_string()))?;
-
-    Ok(())
+    self.sponsorships().log(user_address, app_id, gas_budget).await
   }
 
   async fn get_user_stats(&self, address: &str) -> Result<Option<UserStats>> {
     let stats = sqlx::query_as::<_, UserStats>(
-      "SELECT user_address, COUNT(*) as transaction_count, SUM(gas_budget) as total_gas_sponsored, MAX(timestamp) as last_transaction FROM sponsored_transactions WHERE user_address = $1 GROUP BY user_address"
+      "SELECT user_address, COUNT(*) as transaction_count, SUM(gas_budget)::BIGINT as total_gas_sponsored, MAX(timestamp) as last_transaction FROM sponsored_transactions WHERE user_address = $1 GROUP BY user_address"
     )
     .bind(address)
     .fetch_optional(self.app_state.mm().dbx().db())
@@ -357,4 +383,12 @@ impl SuiRepository for SuiRepositoryImpl {
       }
     }
   }
+
+  async fn get_gas_quota(&self, subject: &QuotaSubject) -> Result<Option<GasQuota>> {
+    self.sponsorships().quota(subject).await
+  }
+
+  async fn get_gas_usage(&self, subject: &QuotaSubject) -> Result<GasUsage> {
+    self.sponsorships().usage(subject).await
+  }
 }
@@ -7,8 +7,12 @@ pub mod models;
 mod domain;
 mod error;
 
+pub use domain::gas_pool::{GasPoolTarget, RebalancePlan};
+pub use domain::sponsorship_policy::{GasQuota, GasUsage, QuotaSubject, SponsorshipPolicy};
+pub use domain::sui_repository_trait::SuiRepository;
+pub use error::Error;
+
 use application::{handlers::sui_handler::SuiHandler, use_cases::sui_use_cases::SuiUseCases};
-use error::Error;
 use infrastructure::enhanced_sui_repository::EnhancedSuiRepository;
 use jd_core::AppState;
 type Result<T> = std::result::Result<T, Error>;
@@ -19,8 +23,9 @@ pub struct SuiService {
 
 impl SuiService {
   pub async fn new(state: AppState) -> Self {
+    let policy = SponsorshipPolicy::from_config(&state.config.sui);
     let repository = EnhancedSuiRepository::new(state);
-    let use_cases = SuiUseCases::new(repository);
+    let use_cases = SuiUseCases::new(repository).with_policy(policy);
     let handler = SuiHandler::new(use_cases);
 
     Self { handler }
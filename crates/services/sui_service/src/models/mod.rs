@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
 use chrono::{DateTime, Utc};
+use jd_domain::{SuiAmount, TokenAmount};
 use sui_sdk::types::{base_types::ObjectID, dynamic_field::DynamicFieldInfo, object::Data};
 use sui_types::transaction::Transaction;
 use uuid::Uuid;
@@ -12,8 +13,8 @@ pub mod requests;
 pub struct CoinBalance {
   pub coin_type: String,
   pub coin_object_count: u64,
-  pub total_balance: u64,
-  pub locked_balance: u64,
+  pub total_balance: TokenAmount,
+  pub locked_balance: TokenAmount,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,8 +39,9 @@ pub struct DynamicFieldPage {
 // Gas Station Models - Custom JSON-friendly structs
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GasData {
-  pub budget: String, // JSON usually sends numbers as strings
-  pub price: String,  // JSON usually sends numbers as strings
+  pub budget: SuiAmount,
+  /// MIST per gas unit.
+  pub price: SuiAmount,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,6 +54,8 @@ pub struct TransactionDataJson {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SponsorRequest {
   pub user_address: String,
+  /// The app sponsoring the transaction, whose quota it also counts against.
+  pub app_id: Option<String>,
   pub transaction_data: TransactionDataJson,
   pub user_signature: Vec<u8>,
 }
@@ -78,7 +82,8 @@ pub struct GasPoolStatus {
 pub struct UserStats {
   pub user_address: String,
   pub transaction_count: Option<i64>,
-  pub total_gas_sponsored: Option<i64>,
+  /// Sum of the gas budgets of the address's sponsored transactions.
+  pub total_gas_sponsored: Option<SuiAmount>,
   pub last_transaction: Option<DateTime<Utc>>,
 }
 
@@ -87,8 +92,51 @@ impl UserStats {
     Self {
       user_address: address,
       transaction_count: Some(0),
-      total_gas_sponsored: Some(0),
+      total_gas_sponsored: Some(SuiAmount::ZERO),
       last_transaction: None,
     }
   }
 }
+
+// On-chain Event Models
+/// An event emitted by an indexed package.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct OnchainEvent {
+  pub id: i64,
+  pub package_id: String,
+  pub tx_digest: String,
+  pub event_seq: i64,
+  pub module: String,
+  pub event_type: String,
+  pub sender: String,
+  pub parsed_json: serde_json::Value,
+  pub occurred_at: Option<DateTime<Utc>>,
+  pub indexed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OnchainEventPage {
+  pub events: Vec<OnchainEvent>,
+  /// Pass as `after` to read the next page; `None` on the last one.
+  pub next_cursor: Option<i64>,
+}
+
+/// How far the indexer has got through a package's events.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct EventIndexerCursor {
+  pub package_id: String,
+  pub tx_digest: String,
+  pub event_seq: i64,
+  pub events_indexed: i64,
+  pub updated_at: DateTime<Utc>,
+}
+
+// Network Models
+/// The state of one served Sui network, as its RPC endpoint reports it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NetworkInfo {
+  pub network: String,
+  pub chain_id: String,
+  pub checkpoint: u64,
+  pub gas_price: u64,
+}
@@ -38,3 +38,24 @@ pub struct GetEventsByModuleRequest {
   pub cursor: Option<TransactionDigest>,
   pub limit: Option<usize>,
 }
+
+/// Filters for indexed on-chain events, read oldest first.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ListOnchainEventsRequest {
+  pub package_id: Option<String>,
+  /// Full event type, e.g. `0xabc::game::Played`.
+  pub event_type: Option<String>,
+  pub sender: Option<String>,
+  pub tx_digest: Option<String>,
+  /// Return events indexed after this cursor.
+  pub after: Option<i64>,
+  pub limit: Option<i64>,
+}
+
+/// Selects the Sui network a request runs against.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NetworkQuery {
+  /// `mainnet`, `testnet`, `devnet` or `localnet`; the configured default
+  /// when unset.
+  pub network: Option<String>,
+}
@@ -5,16 +5,26 @@ use std::fmt;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "vulnerability")]
 pub enum Error {
+    #[taxonomy(kind = NotFound, expose)]
     VulnerabilityNotFound(String),
+    #[taxonomy(kind = NotFound, expose)]
     RepositoryNotFound(String),
+    #[taxonomy(kind = Validation, expose)]
     InvalidSeverityLevel(String),
+    #[taxonomy(kind = Validation, expose)]
     InvalidStatus(String),
+    #[taxonomy(kind = Validation, expose)]
     InvalidFilter(String),
+    #[taxonomy(kind = Internal)]
     BulkOperationFailed(String),
+    #[taxonomy(kind = Internal)]
     DatabaseError(String),
+    #[taxonomy(kind = Internal)]
     ServiceError(String),
+    #[taxonomy(kind = Unauthenticated)]
     CtxError(String),
 }
 
@@ -41,7 +41,7 @@ impl<R: ZkProofRepository> ZkProofHandler<R> {
         State(_app_state): State<AppState>,
         Path(id): Path<String>,
     ) -> Result<Json<Option<ZkProofResponse>>> {
-        let id = Id::new(id);
+        let id: Id = id.parse()?;
         // Note: Similar placeholder
         Err(crate::Error::Internal("Handler not implemented yet".to_string()))
     }
@@ -50,7 +50,7 @@ impl<R: ZkProofRepository> ZkProofHandler<R> {
         State(_app_state): State<AppState>,
         Path(scoring_id): Path<String>,
     ) -> Result<Json<Option<ZkProofResponse>>> {
-        let scoring_id = Id::new(scoring_id);
+        let scoring_id: Id = scoring_id.parse()?;
         // Note: Similar placeholder
         Err(crate::Error::Internal("Handler not implemented yet".to_string()))
     }
@@ -0,0 +1,121 @@
+use std::time::Duration;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::artifact_store_trait::{
+    fetch_artifact, store_proof_artifacts, ArtifactStore, ProofArtifacts, ProofDownload,
+};
+use crate::domain::proof_verification::StoredProof;
+use crate::infrastructure::proof_store::ProofStore;
+use crate::{Error, Result};
+
+/// How long a download link stays valid.
+const DOWNLOAD_LINK_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Outcome of one offloading run.
+#[derive(Debug, Default)]
+pub struct OffloadRun {
+    pub offloaded: usize,
+    pub failed: usize,
+}
+
+/// Keeps proof artifacts in object storage and hands out links to them.
+pub struct ProofArtifactUseCases<A: ArtifactStore> {
+    store: ProofStore,
+    artifacts: A,
+}
+
+impl<A: ArtifactStore> ProofArtifactUseCases<A> {
+    pub fn new(store: ProofStore, artifacts: A) -> Self {
+        Self { store, artifacts }
+    }
+
+    /// Presigned links to proof `id`'s data and, for proofs from before the
+    /// key registry, its verification key. A proof still kept inline is
+    /// moved to object storage first.
+    pub async fn download(&self, id: Uuid) -> Result<ProofDownload> {
+        let mut proof = self.store.find_proof(id).await?.ok_or(Error::ProofNotFound(id))?;
+        if proof.proof_uri.is_none() {
+            let artifacts = self.offload(&proof).await?;
+            proof.proof_uri = Some(artifacts.proof.uri);
+            proof.proof_sha256 = Some(artifacts.proof.sha256);
+            if let Some(verification_key) = artifacts.verification_key {
+                proof.verification_key_uri = Some(verification_key.uri);
+                proof.verification_key_sha256 = Some(verification_key.sha256);
+            }
+        }
+        let (Some(proof_uri), Some(proof_sha256), Some(verification_key_sha256)) =
+            (&proof.proof_uri, proof.proof_sha256, proof.verification_key_sha256)
+        else {
+            return Err(Error::Internal(format!("Proof {} is missing artifact hashes", id)));
+        };
+        let verification_key_url = match &proof.verification_key_uri {
+            Some(uri) => Some(self.artifacts.presigned_url(uri, DOWNLOAD_LINK_TTL)?),
+            None => None,
+        };
+
+        Ok(ProofDownload {
+            proof_id: id,
+            proof_url: self.artifacts.presigned_url(proof_uri, DOWNLOAD_LINK_TTL)?,
+            proof_sha256,
+            verification_key_id: proof.verification_key_id,
+            verification_key_url,
+            verification_key_sha256,
+            expires_at: OffsetDateTime::now_utc() + DOWNLOAD_LINK_TTL,
+        })
+    }
+
+    /// Move up to `limit` proofs kept inline to object storage.
+    pub async fn offload_inline(&self, limit: i64) -> Result<OffloadRun> {
+        let mut run = OffloadRun::default();
+        for proof in self.store.inline_proofs(limit).await? {
+            match self.offload(&proof).await {
+                Ok(_) => run.offloaded += 1,
+                Err(e) => {
+                    warn!(proof_id = %proof.id, error = %e, "Failed to offload proof artifacts");
+                    run.failed += 1;
+                }
+            }
+        }
+        Ok(run)
+    }
+
+    async fn offload(&self, proof: &StoredProof) -> Result<ProofArtifacts> {
+        let Some(proof_data) = &proof.proof_data else {
+            return Err(Error::Internal(format!("Proof {} has neither inline data nor artifacts", proof.id)));
+        };
+        let artifacts = store_proof_artifacts(&self.artifacts, proof_data, proof.verification_key.as_deref()).await?;
+        self.store.record_offloaded(proof.id, &artifacts).await?;
+        info!(proof_id = %proof.id, uri = %artifacts.proof.uri, "Proof artifacts offloaded");
+        Ok(artifacts)
+    }
+}
+
+/// A proof's data, from object storage when it was offloaded there.
+pub(crate) async fn proof_data<A: ArtifactStore>(proof: &StoredProof, artifacts: Option<&A>) -> Result<Vec<u8>> {
+    match (&proof.proof_uri, &proof.proof_sha256, &proof.proof_data) {
+        (Some(uri), Some(sha256), _) => fetch_artifact(configured(artifacts)?, uri, sha256).await,
+        (_, _, Some(proof_data)) => Ok(proof_data.clone()),
+        _ => Err(Error::Internal(format!("Proof {} has neither inline data nor artifacts", proof.id))),
+    }
+}
+
+/// The copy of the verification key kept with a proof from before the key
+/// registry.
+pub(crate) async fn stored_verification_key<A: ArtifactStore>(
+    proof: &StoredProof,
+    artifacts: Option<&A>,
+) -> Result<Vec<u8>> {
+    match (&proof.verification_key_uri, &proof.verification_key_sha256, &proof.verification_key) {
+        (Some(uri), Some(sha256), _) => fetch_artifact(configured(artifacts)?, uri, sha256).await,
+        (_, _, Some(verification_key)) => Ok(verification_key.clone()),
+        _ => Err(Error::Internal(format!("Proof {} has no verification key", proof.id))),
+    }
+}
+
+fn configured<A: ArtifactStore>(artifacts: Option<&A>) -> Result<&A> {
+    artifacts.ok_or_else(|| {
+        Error::ProverUnavailable("Proof artifacts are in object storage, which is not configured".to_string())
+    })
+}
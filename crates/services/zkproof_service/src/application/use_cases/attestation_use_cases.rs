@@ -0,0 +1,103 @@
+use rand::RngCore;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::domain::attestation::{AttestationOutcome, ProofAttestation};
+use crate::domain::attestation_publisher_trait::AttestationPublisher;
+use crate::infrastructure::attestation_store::{AttestationStore, DueAttestation};
+use crate::{Error, Result};
+
+/// How long a claimed proof is held from other instances.
+const CLAIM_LEASE: Duration = Duration::from_secs(2 * 60);
+const MAX_ATTEMPTS: i32 = 10;
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+/// How long a signed transaction that never landed is resubmitted before a
+/// new one is signed. By then its gas coin has gone back to the pool, so the
+/// old transaction can no longer execute alongside a new one.
+const SIGNED_TX_TTL_SECS: i64 = 10 * 60;
+
+/// Outcome of one publishing run.
+#[derive(Debug, Default)]
+pub struct AttestationRun {
+    pub published: usize,
+    pub failed: usize,
+}
+
+/// Publishes attestations of verified proofs on-chain and records their
+/// digests in `blockchain_tx_hash`.
+pub struct AttestationUseCases<P: AttestationPublisher> {
+    store: AttestationStore,
+    publisher: P,
+}
+
+impl<P: AttestationPublisher> AttestationUseCases<P> {
+    pub fn new(store: AttestationStore, publisher: P) -> Self {
+        Self { store, publisher }
+    }
+
+    /// Attest up to `limit` verified proofs that are due. A failed attempt
+    /// is retried with backoff, resubmitting the same signed transaction so
+    /// a proof is never attested twice.
+    pub async fn publish_due(&self, limit: i64) -> Result<AttestationRun> {
+        let mut run = AttestationRun::default();
+        for due in self.store.claim_due(limit, CLAIM_LEASE, MAX_ATTEMPTS).await? {
+            match self.publish(&due).await {
+                Ok(AttestationOutcome::Published { digest }) => {
+                    info!(proof_id = %due.id, digest = %digest, "Proof attestation published");
+                    self.store.record_published(due.id, &digest).await?;
+                    run.published += 1;
+                }
+                Ok(AttestationOutcome::Failed { digest, reason }) => {
+                    warn!(proof_id = %due.id, digest = %digest, reason = %reason, "Proof attestation aborted");
+                    let retry_in = retry_delay(due.attestation_attempts);
+                    self.store.record_failure(due.id, &reason, retry_in, true).await?;
+                    run.failed += 1;
+                }
+                Err(e) => {
+                    warn!(proof_id = %due.id, error = %e, "Proof attestation failed");
+                    // Unlanded and old enough that a new transaction can't race it
+                    let stale = due.prepared_secs_ago.is_some_and(|secs| secs > SIGNED_TX_TTL_SECS);
+                    let retry_in = retry_delay(due.attestation_attempts);
+                    self.store.record_failure(due.id, &e.to_string(), retry_in, stale).await?;
+                    run.failed += 1;
+                }
+            }
+        }
+        Ok(run)
+    }
+
+    async fn publish(&self, due: &DueAttestation) -> Result<AttestationOutcome> {
+        if let Some(prepared) = due.prepared() {
+            return self.publisher.submit(&prepared).await;
+        }
+
+        let salt = match &due.attestation_salt {
+            Some(salt) => salt.clone(),
+            None => {
+                let mut salt = vec![0u8; 32];
+                rand::thread_rng().fill_bytes(&mut salt);
+                salt
+            }
+        };
+        let mut proof_hash = [0u8; 32];
+        hex::decode_to_slice(&due.proof_sha256, &mut proof_hash)
+            .map_err(|e| Error::Internal(format!("Invalid hash of proof {}: {}", due.id, e)))?;
+        let attestation = ProofAttestation::new(
+            due.id,
+            proof_hash,
+            due.score_cents,
+            &salt,
+            due.subject_address.clone(),
+        );
+        let prepared = self.publisher.prepare(&attestation).await?;
+        // Kept before submitting, so a crash mid-submit resubmits this one
+        self.store.record_prepared(due.id, &salt, &prepared).await?;
+        self.publisher.submit(&prepared).await
+    }
+}
+
+/// 30 seconds doubling per attempt, up to an hour.
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.clamp(1, 8) as u32 - 1;
+    (Duration::from_secs(30) * 2u32.pow(exponent)).min(MAX_RETRY_DELAY)
+}
@@ -0,0 +1,125 @@
+use time::OffsetDateTime;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::credential::{
+    CredentialClaims, CredentialIssuer, CredentialRecord, CredentialStatusEntry, CredentialSubject,
+    CredentialVerification, IssuedCredential, VerifiableCredential, CREDENTIALS_CONTEXT, CREDENTIAL_STATUS_TYPE,
+    CREDENTIAL_TYPE,
+};
+use crate::infrastructure::credential_signer::CredentialSigner;
+use crate::infrastructure::credential_store::CredentialStore;
+use crate::{Error, Result};
+
+/// Issues credentials asserting a holder's score is at least a threshold,
+/// without the score itself, so partners get an eligibility signal only.
+pub struct CredentialUseCases {
+    store: CredentialStore,
+    signer: CredentialSigner,
+}
+
+impl CredentialUseCases {
+    pub fn new(store: CredentialStore, signer: CredentialSigner) -> Self {
+        Self { store, signer }
+    }
+
+    /// Issue a credential to the holder of wallet `subject_address` if their
+    /// latest score is at least `threshold`.
+    pub async fn issue(&self, subject_address: &str, threshold: i16) -> Result<IssuedCredential> {
+        if !(1..=100).contains(&threshold) {
+            return Err(Error::InvalidInput("threshold must be between 1 and 100".to_string()));
+        }
+        let score = self
+            .store
+            .latest_score(subject_address)
+            .await?
+            .ok_or_else(|| Error::InvalidInput(format!("No score for {}", subject_address)))?;
+        if score.score_cents < i64::from(threshold) * 100 {
+            return Err(Error::InvalidInput(format!("Score is below {}", threshold)));
+        }
+
+        let expires_at = OffsetDateTime::now_utc() + self.signer.ttl();
+        let record = self.store.insert(subject_address, threshold, score.scoring_result_id, expires_at).await?;
+        let credential = self.signer.sign(&self.claims(&record))?;
+        info!(credential_id = %record.id, threshold, "Score credential issued");
+        Ok(IssuedCredential { record, credential })
+    }
+
+    /// Revoke one of the holder's credentials.
+    pub async fn revoke(&self, id: Uuid, subject_address: &str, reason: Option<&str>) -> Result<CredentialRecord> {
+        let record = self
+            .store
+            .revoke(id, subject_address, reason.unwrap_or("revoked_by_holder"))
+            .await?
+            .ok_or(Error::CredentialNotFound(id))?;
+        info!(credential_id = %id, "Score credential revoked");
+        Ok(record)
+    }
+
+    pub async fn status(&self, id: Uuid) -> Result<CredentialRecord> {
+        self.store.find(id).await?.ok_or(Error::CredentialNotFound(id))
+    }
+
+    /// Check a presented credential: its signature and validity period, and
+    /// that it is not revoked. With `minimum_score`, it is eligible only if
+    /// it asserts at least that score.
+    pub async fn verify(&self, credential: &str, minimum_score: Option<i16>) -> Result<CredentialVerification> {
+        let claims = match self.signer.verify(credential) {
+            Ok(claims) => claims,
+            Err(e) => return Ok(rejected(format!("Invalid credential: {}", e))),
+        };
+        let Some(id) = claims.jti.strip_prefix("urn:uuid:").and_then(|id| Uuid::parse_str(id).ok()) else {
+            return Ok(rejected("Credential has no registry id".to_string()));
+        };
+        let Some(record) = self.store.find(id).await? else {
+            return Ok(rejected("Credential is not in the registry".to_string()));
+        };
+
+        let score_at_least = claims.vc.credential_subject.score_at_least;
+        let error = record.revoked_at.map(|_| {
+            format!("Credential was revoked ({})", record.revocation_reason.as_deref().unwrap_or("no reason"))
+        });
+        let valid = error.is_none();
+        Ok(CredentialVerification {
+            valid,
+            eligible: valid && minimum_score.is_none_or(|minimum| score_at_least >= minimum),
+            credential_id: Some(id),
+            subject: Some(claims.sub),
+            score_at_least: Some(score_at_least),
+            expires_at: Some(record.expires_at),
+            error,
+        })
+    }
+
+    pub fn issuer(&self) -> CredentialIssuer {
+        self.signer.issuer_jwk()
+    }
+
+    fn claims(&self, record: &CredentialRecord) -> CredentialClaims {
+        let issuer = self.signer.issuer();
+        CredentialClaims {
+            iss: issuer.to_string(),
+            sub: record.subject_address.clone(),
+            jti: format!("urn:uuid:{}", record.id),
+            iat: record.issued_at.unix_timestamp(),
+            nbf: record.issued_at.unix_timestamp(),
+            exp: record.expires_at.unix_timestamp(),
+            vc: VerifiableCredential {
+                context: vec![CREDENTIALS_CONTEXT.to_string()],
+                types: vec!["VerifiableCredential".to_string(), CREDENTIAL_TYPE.to_string()],
+                credential_subject: CredentialSubject {
+                    id: record.subject_address.clone(),
+                    score_at_least: record.threshold,
+                },
+                credential_status: CredentialStatusEntry {
+                    id: format!("{}/api/v1/zkpersona/credentials/{}/status", issuer, record.id),
+                    status_type: CREDENTIAL_STATUS_TYPE.to_string(),
+                },
+            },
+        }
+    }
+}
+
+fn rejected(error: String) -> CredentialVerification {
+    CredentialVerification { error: Some(error), ..Default::default() }
+}
@@ -1 +1,8 @@
-pub mod zkproof_use_cases;
\ No newline at end of file
+pub mod zkproof_use_cases;
+pub mod attestation_use_cases;
+pub mod proving_use_cases;
+pub mod verification_use_cases;
+pub mod artifact_use_cases;
+pub mod verification_key_use_cases;
+pub mod credential_use_cases;
+pub mod proof_request_use_cases;
\ No newline at end of file
@@ -0,0 +1,208 @@
+use reqwest::Url;
+use std::net::IpAddr;
+use std::time::Duration;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::proof_request::{
+    generate_callback_secret, generate_nonce, CreatedProofRequest, ProofRequest, ProofRequestResponse,
+    ProofRequestStatus, ProofStatement,
+};
+use crate::domain::prover_trait::commits_to_challenge;
+use crate::infrastructure::callback_client::{is_public, CallbackClient};
+use crate::infrastructure::proof_request_store::ProofRequestStore;
+use crate::{Error, Result};
+
+const DEFAULT_EXPIRY: Duration = Duration::from_secs(15 * 60);
+const MAX_EXPIRY: Duration = Duration::from_secs(24 * 60 * 60);
+/// Wait before the first retry of a failed callback; doubled per attempt.
+const CALLBACK_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_CALLBACK_ATTEMPTS: i32 = 6;
+
+/// Outcome of one callback delivery run.
+#[derive(Debug, Default)]
+pub struct CallbackRun {
+    pub delivered: usize,
+    pub failed: usize,
+}
+
+/// Partners ask a user to prove a statement about their score; the user
+/// fulfils the request with a verified proof of their latest score generated
+/// for the request's nonce, and the partner is told whether the statement
+/// holds by signed callback.
+pub struct ProofRequestUseCases {
+    store: ProofRequestStore,
+    callbacks: CallbackClient,
+}
+
+impl ProofRequestUseCases {
+    pub fn new(store: ProofRequestStore) -> Self {
+        Self { store, callbacks: CallbackClient::new() }
+    }
+
+    /// Ask the user with wallet `subject` to prove `statement`.
+    pub async fn create(
+        &self,
+        created_by: &str,
+        subject: &str,
+        statement: ProofStatement,
+        callback_url: &str,
+        expires_in: Option<Duration>,
+    ) -> Result<CreatedProofRequest> {
+        let ProofStatement::ScoreAtLeast { threshold } = statement;
+        if !(1..=100).contains(&threshold) {
+            return Err(Error::InvalidInput("threshold must be between 1 and 100".to_string()));
+        }
+        if subject.trim().is_empty() {
+            return Err(Error::InvalidInput("subject is required".to_string()));
+        }
+        validate_callback_url(callback_url)?;
+        let expires_in = expires_in.unwrap_or(DEFAULT_EXPIRY);
+        if expires_in.is_zero() || expires_in > MAX_EXPIRY {
+            return Err(Error::InvalidInput("Proof requests expire within 24 hours".to_string()));
+        }
+
+        let callback_secret = generate_callback_secret();
+        let request = self
+            .store
+            .create(
+                created_by,
+                subject.trim(),
+                &statement,
+                &generate_nonce(),
+                callback_url,
+                &callback_secret,
+                OffsetDateTime::now_utc() + expires_in,
+            )
+            .await?;
+        info!(request_id = %request.id, created_by, "Proof request created");
+        Ok(CreatedProofRequest { request, callback_secret })
+    }
+
+    /// Request `id`, as long as `created_by` created it.
+    pub async fn get(&self, id: Uuid, created_by: &str) -> Result<ProofRequest> {
+        self.store
+            .find(id)
+            .await?
+            .filter(|request| request.created_by == created_by)
+            .ok_or(Error::ProofRequestNotFound(id))
+    }
+
+    /// Fulfil request `id` for the user with wallet `subject_address` with
+    /// their proof `proof_id`. The request must name them, the proof must be
+    /// verified, of their latest score and generated for the request's
+    /// nonce, and the statement must hold; the partner then gets the nonce
+    /// back with the proof's hash.
+    pub async fn fulfil(&self, id: Uuid, subject_address: &str, proof_id: Uuid) -> Result<ProofRequest> {
+        let request = self
+            .store
+            .find(id)
+            .await?
+            .filter(|request| request.subject.as_deref() == Some(subject_address))
+            .ok_or(Error::ProofRequestNotFound(id))?;
+        if request.status != ProofRequestStatus::Open || request.expires_at <= OffsetDateTime::now_utc() {
+            return Err(Error::InvalidInput("Proof request is no longer open".to_string()));
+        }
+        let proof = self
+            .store
+            .proof_of_score(proof_id)
+            .await?
+            .filter(|proof| proof.owner_address.as_deref() == Some(subject_address))
+            .ok_or(Error::ProofNotFound(proof_id))?;
+        if !proof.verified {
+            return Err(Error::InvalidInput(format!("Proof {} has not been verified", proof_id)));
+        }
+        if !proof.is_latest {
+            return Err(Error::InvalidInput("Proof is not of your latest score".to_string()));
+        }
+        // A proof made for another request, or for none, could be replayed
+        if !proof.public_signals.as_ref().is_some_and(|signals| commits_to_challenge(signals, &request.nonce)) {
+            return Err(Error::InvalidInput("Proof was not generated for this request's nonce".to_string()));
+        }
+        if !request.statement.holds(proof.score_cents) {
+            return Err(Error::InvalidInput("Your latest score does not satisfy the statement".to_string()));
+        }
+
+        let fulfilled_at = OffsetDateTime::now_utc();
+        let response = ProofRequestResponse {
+            request_id: id,
+            nonce: request.nonce,
+            statement: request.statement,
+            satisfied: true,
+            subject: subject_address.to_string(),
+            proof_sha256: proof.proof_sha256,
+            verification_key_id: proof.verification_key_id,
+            fulfilled_at,
+        };
+        let fulfilled = self
+            .store
+            .fulfil(id, subject_address, proof_id, fulfilled_at, &serde_json::to_value(&response)?)
+            .await?
+            .ok_or_else(|| Error::InvalidInput("Proof request is no longer open".to_string()))?;
+        info!(request_id = %id, proof_id = %proof_id, "Proof request fulfilled");
+        Ok(fulfilled)
+    }
+
+    /// Deliver up to `limit` due callbacks. A failed delivery is retried
+    /// with backoff until its attempts run out.
+    pub async fn deliver_callbacks(&self, limit: i64) -> Result<CallbackRun> {
+        let mut run = CallbackRun::default();
+        for due in self.store.claim_due_callbacks(limit, CALLBACK_BACKOFF, MAX_CALLBACK_ATTEMPTS).await? {
+            let body = serde_json::to_vec(&due.response)?;
+            match self.callbacks.deliver(due.id, &due.callback_url, &due.callback_secret, body).await {
+                Ok(()) => {
+                    self.store.record_delivered(due.id).await?;
+                    run.delivered += 1;
+                }
+                Err(e) => {
+                    let give_up = due.callback_attempts >= MAX_CALLBACK_ATTEMPTS;
+                    warn!(
+                        request_id = %due.id,
+                        attempt = due.callback_attempts,
+                        give_up,
+                        error = %e,
+                        "Proof request callback failed"
+                    );
+                    self.store.record_callback_failure(due.id, &e, give_up).await?;
+                    run.failed += 1;
+                }
+            }
+        }
+        Ok(run)
+    }
+}
+
+/// Callbacks go to public HTTPS URLs. Host names are checked again as each
+/// callback is delivered, since what they resolve to can change.
+fn validate_callback_url(callback_url: &str) -> Result<()> {
+    let url = Url::parse(callback_url)
+        .map_err(|e| Error::InvalidInput(format!("Invalid callback_url: {}", e)))?;
+    if url.scheme() != "https" {
+        return Err(Error::InvalidInput("callback_url must use https".to_string()));
+    }
+    let host = url.host_str().unwrap_or_default().trim_start_matches('[').trim_end_matches(']');
+    let internal = host.is_empty()
+        || host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| !is_public(ip));
+    if internal {
+        return Err(Error::InvalidInput("callback_url must be a public host".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn callbacks_need_https_to_public_hosts() {
+        assert!(validate_callback_url("https://partner.example/hooks/zkpersona").is_ok());
+        assert!(validate_callback_url("https://localhost:3000/callback").is_err());
+        assert!(validate_callback_url("https://169.254.169.254/latest").is_err());
+        assert!(validate_callback_url("https://[::1]/callback").is_err());
+        assert!(validate_callback_url("http://partner.example/callback").is_err());
+        assert!(validate_callback_url("ftp://partner.example/callback").is_err());
+        assert!(validate_callback_url("not a url").is_err());
+    }
+}
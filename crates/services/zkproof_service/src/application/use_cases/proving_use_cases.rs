@@ -0,0 +1,133 @@
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::application::use_cases::verification_key_use_cases::active_key;
+use crate::application::use_cases::verification_use_cases::run_verifier;
+use crate::domain::artifact_store_trait::{store_proof, ArtifactStore};
+use crate::domain::proof_job::ProofJob;
+use crate::domain::prover_trait::{challenge_input, Prover};
+use crate::infrastructure::proof_job_store::{NewProof, ProofJobStore};
+use crate::infrastructure::verification_key_store::VerificationKeyStore;
+use crate::{Error, Result};
+
+/// A job is held this much longer than the prover may take.
+const LEASE_MARGIN: Duration = Duration::from_secs(60);
+const MAX_ATTEMPTS: i32 = 3;
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Generates proofs of scoring results in the background. Requests queue a
+/// job; a worker claims jobs one at a time, proves them and stores the proof
+/// with a reference to the registered verification key it verifies against.
+pub struct ProvingUseCases<P: Prover, A: ArtifactStore> {
+    store: ProofJobStore,
+    keys: VerificationKeyStore,
+    prover: P,
+    artifacts: Option<A>,
+}
+
+impl<P: Prover, A: ArtifactStore> ProvingUseCases<P, A> {
+    pub fn new(store: ProofJobStore, keys: VerificationKeyStore, prover: P) -> Self {
+        Self { store, keys, prover, artifacts: None }
+    }
+
+    /// Keep generated proofs in `artifacts` rather than in Postgres.
+    pub fn with_artifacts(mut self, artifacts: A) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    /// Queue a proof of `scoring_result_id` committing to `challenge`, or
+    /// return the job already proving it.
+    pub async fn request_proof(&self, scoring_result_id: Uuid, challenge: Option<&str>) -> Result<ProofJob> {
+        if challenge.is_some_and(|challenge| challenge_input(challenge).is_none()) {
+            return Err(Error::InvalidInput("challenge must be hex".to_string()));
+        }
+        if self.store.proving_input(scoring_result_id).await?.is_none() {
+            return Err(Error::InvalidInput(format!("Scoring result {} not found", scoring_result_id)));
+        }
+        self.store.enqueue(scoring_result_id, challenge).await
+    }
+
+    pub async fn get_job(&self, id: Uuid) -> Result<ProofJob> {
+        self.store.find(id).await?.ok_or(Error::ProofJobNotFound(id))
+    }
+
+    /// Prove the next queued job, if there is one. A failed attempt is
+    /// queued again unless the input can never be proven.
+    pub async fn run_next(&self) -> Result<bool> {
+        let abandoned = self.store.fail_abandoned(MAX_ATTEMPTS).await?;
+        if abandoned > 0 {
+            warn!(jobs = abandoned, "Proof jobs failed after their last attempt was abandoned");
+        }
+
+        let lease = self.prover.timeout() + LEASE_MARGIN;
+        let Some(job) = self.store.claim_next(lease, MAX_ATTEMPTS).await? else {
+            return Ok(false);
+        };
+        match self.prove(&job).await {
+            Ok(proof_id) => {
+                info!(job_id = %job.id, proof_id = %proof_id, "Proof generated");
+            }
+            Err(e) => {
+                let retry = job.attempts < MAX_ATTEMPTS
+                    && !matches!(
+                        e,
+                        Error::InvalidInput(_) | Error::ProofVerification(_) | Error::VerificationKeyNotFound(_)
+                    );
+                warn!(job_id = %job.id, attempt = job.attempts, retry, error = %e, "Proof job failed");
+                self.store.record_failure(job.id, &e.to_string(), retry).await?;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Prove queued jobs until the process exits, polling while idle.
+    pub async fn run(self) {
+        info!(backend = self.prover.backend(), "Proof worker started");
+        loop {
+            match self.run_next().await {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => warn!(error = %e, "Proof worker run failed"),
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn prove(&self, job: &ProofJob) -> Result<Uuid> {
+        let mut input = self.store.proving_input(job.scoring_result_id).await?.ok_or_else(|| {
+            Error::InvalidInput(format!("Scoring result {} not found", job.scoring_result_id))
+        })?;
+        input.challenge = job.challenge.clone();
+        let key = active_key(&self.keys, self.prover.backend(), &input.model_version).await?;
+        let model_commitment = self.prover.model_commitment().await?;
+        let proof = self.prover.prove(&input).await?;
+        if hex::encode(Sha256::digest(&proof.verification_key)) != key.key_sha256 {
+            return Err(Error::ProofVerification(format!(
+                "Prover's verification key is not registered key {} ({} of model {})",
+                key.id, key.circuit_version, key.model_version
+            )));
+        }
+        let run =
+            run_verifier(&self.prover, &proof.proof_data, &proof.verification_key, &proof.public_signals).await?;
+        if !run.valid {
+            return Err(Error::ProofVerification("Generated proof does not verify".to_string()));
+        }
+        let artifact = match &self.artifacts {
+            Some(artifacts) => Some(store_proof(artifacts, &proof.proof_data).await?),
+            None => None,
+        };
+        let new_proof = NewProof {
+            scoring_result_id: job.scoring_result_id,
+            proof: &proof,
+            verification_key: &key,
+            prover_backend: self.prover.backend(),
+            model_commitment: &model_commitment,
+            verification: &run,
+            artifact: artifact.as_ref(),
+        };
+        self.store.record_proof(job.id, new_proof).await
+    }
+}
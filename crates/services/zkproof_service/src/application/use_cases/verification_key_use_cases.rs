@@ -0,0 +1,76 @@
+use base64::{Engine as _, engine::general_purpose};
+use sha2::{Digest, Sha256};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::verification_key::{NewVerificationKey, VerificationKey, VerificationKeyDetail};
+use crate::infrastructure::verification_key_store::VerificationKeyStore;
+use crate::{Error, Result};
+
+/// Publishes verification keys and resolves the key a proof uses, so keys
+/// can be rotated without breaking proofs made with earlier ones.
+pub struct VerificationKeyUseCases {
+    store: VerificationKeyStore,
+}
+
+impl VerificationKeyUseCases {
+    pub fn new(store: VerificationKeyStore) -> Self {
+        Self { store }
+    }
+
+    pub async fn publish(&self, key: NewVerificationKey) -> Result<VerificationKey> {
+        for (field, value) in [
+            ("model_version", &key.model_version),
+            ("circuit_version", &key.circuit_version),
+            ("prover_backend", &key.prover_backend),
+        ] {
+            if value.trim().is_empty() {
+                return Err(Error::InvalidInput(format!("{} is required", field)));
+            }
+        }
+        if key.verification_key.is_empty() {
+            return Err(Error::InvalidInput("verification_key is empty".to_string()));
+        }
+        if let (Some(from), Some(until)) = (key.active_from, key.active_until) {
+            if until <= from {
+                return Err(Error::InvalidInput("active_until must be after active_from".to_string()));
+            }
+        }
+
+        let key_sha256 = hex::encode(Sha256::digest(&key.verification_key));
+        let published = self.store.publish(&key, &key_sha256).await?;
+        info!(
+            key_id = %published.id,
+            model_version = %published.model_version,
+            circuit_version = %published.circuit_version,
+            "Verification key published"
+        );
+        Ok(published)
+    }
+
+    pub async fn list(&self, model_version: Option<&str>) -> Result<Vec<VerificationKey>> {
+        self.store.list(model_version).await
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<VerificationKeyDetail> {
+        let key = self.store.find(id).await?.ok_or_else(|| Error::VerificationKeyNotFound(id.to_string()))?;
+        let bytes = self.store.key_bytes(id).await?.ok_or_else(|| Error::VerificationKeyNotFound(id.to_string()))?;
+        Ok(VerificationKeyDetail { key, verification_key: general_purpose::STANDARD.encode(bytes) })
+    }
+}
+
+/// The key new proofs of `model_version` use now.
+pub(crate) async fn active_key(
+    store: &VerificationKeyStore,
+    prover_backend: &str,
+    model_version: &str,
+) -> Result<VerificationKey> {
+    store.active(prover_backend, model_version).await?.ok_or_else(|| {
+        Error::VerificationKeyNotFound(format!("no active {} key for model {}", prover_backend, model_version))
+    })
+}
+
+/// The bytes of registered key `id`.
+pub(crate) async fn registered_key(store: &VerificationKeyStore, id: Uuid) -> Result<Vec<u8>> {
+    store.key_bytes(id).await?.ok_or_else(|| Error::VerificationKeyNotFound(id.to_string()))
+}
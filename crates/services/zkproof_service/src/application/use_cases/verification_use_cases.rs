@@ -0,0 +1,95 @@
+use serde_json::Value;
+use std::time::Instant;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::application::use_cases::artifact_use_cases::{proof_data, stored_verification_key};
+use crate::application::use_cases::verification_key_use_cases::registered_key;
+use crate::domain::artifact_store_trait::ArtifactStore;
+use crate::domain::proof_verification::{ProofVerification, VerifierRun};
+use crate::domain::prover_trait::Prover;
+use crate::infrastructure::proof_store::ProofStore;
+use crate::infrastructure::verification_key_store::VerificationKeyStore;
+use crate::{Error, Result};
+
+/// Verifies stored proofs with the configured prover backend, so `verified`
+/// reflects a check made by the server rather than by the client.
+pub struct ProofVerificationUseCases<P: Prover, A: ArtifactStore> {
+    store: ProofStore,
+    keys: VerificationKeyStore,
+    prover: P,
+    artifacts: Option<A>,
+}
+
+impl<P: Prover, A: ArtifactStore> ProofVerificationUseCases<P, A> {
+    pub fn new(store: ProofStore, keys: VerificationKeyStore, prover: P) -> Self {
+        Self { store, keys, prover, artifacts: None }
+    }
+
+    /// Read offloaded proof artifacts from `artifacts`.
+    pub fn with_artifacts(mut self, artifacts: A) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
+    /// Verify proof `id` against its verification key and public signals and
+    /// record the outcome. The key is the registered one the proof was made
+    /// with, even if it has since been rotated out. A proof made by another
+    /// backend is rejected without running the verifier.
+    pub async fn verify_proof(&self, id: Uuid) -> Result<ProofVerification> {
+        let proof = self.store.find_proof(id).await?.ok_or(Error::ProofNotFound(id))?;
+
+        let backend = self.prover.backend();
+        let run = match proof.prover_backend.as_deref() {
+            Some(generated_by) if generated_by == backend => {
+                let proof_data = proof_data(&proof, self.artifacts.as_ref()).await?;
+                let verification_key = match proof.verification_key_id {
+                    Some(key_id) => registered_key(&self.keys, key_id).await?,
+                    None => stored_verification_key(&proof, self.artifacts.as_ref()).await?,
+                };
+                let public_signals = proof.public_signals.clone().unwrap_or(Value::Null);
+                run_verifier(&self.prover, &proof_data, &verification_key, &public_signals).await?
+            }
+            generated_by => VerifierRun {
+                valid: false,
+                verifier_version: self.prover.version().await?,
+                duration_ms: 0,
+                error: Some(format!(
+                    "Proof was generated by {}, not {}",
+                    generated_by.unwrap_or("an unrecorded prover"),
+                    backend
+                )),
+            },
+        };
+        let checked_at = self.store.record_verification(id, &run).await?;
+        info!(proof_id = %id, valid = run.valid, ms = run.duration_ms, "Proof verified server-side");
+
+        Ok(ProofVerification {
+            proof_id: id,
+            valid: run.valid,
+            verifier: backend.to_string(),
+            verifier_version: run.verifier_version,
+            verification_ms: run.duration_ms,
+            error: run.error,
+            checked_at,
+        })
+    }
+}
+
+/// Run the verifier over a proof and time it.
+pub(crate) async fn run_verifier<P: Prover>(
+    prover: &P,
+    proof_data: &[u8],
+    verification_key: &[u8],
+    public_signals: &Value,
+) -> Result<VerifierRun> {
+    let verifier_version = prover.version().await?;
+    let started = Instant::now();
+    let valid = prover.verify(proof_data, verification_key, public_signals).await?;
+    Ok(VerifierRun {
+        valid,
+        verifier_version,
+        duration_ms: started.elapsed().as_millis() as i64,
+        error: (!valid).then(|| "Proof does not verify against its key and public signals".to_string()),
+    })
+}
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::Result;
+
+/// An artifact kept in object storage, addressed by its content.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredArtifact {
+    pub uri: String,
+    /// Hex SHA-256 of the content.
+    pub sha256: String,
+}
+
+/// Where a proof's data and verification key were stored. Proofs whose key
+/// is in the registry have no copy of it to store.
+#[derive(Debug, Clone)]
+pub struct ProofArtifacts {
+    pub proof: StoredArtifact,
+    pub verification_key: Option<StoredArtifact>,
+}
+
+/// Short-lived links to download a proof's artifacts straight from object
+/// storage.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProofDownload {
+    pub proof_id: Uuid,
+    pub proof_url: String,
+    pub proof_sha256: String,
+    /// The registered key the proof verifies against.
+    pub verification_key_id: Option<Uuid>,
+    /// Link to the copy of the key kept with proofs from before the key
+    /// registry.
+    pub verification_key_url: Option<String>,
+    pub verification_key_sha256: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+/// Object storage for proof artifacts too large to keep in Postgres.
+#[async_trait]
+pub trait ArtifactStore: Send + Sync {
+    /// Store `bytes` under `key`, unless an object is there already, and
+    /// return its URI.
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<String>;
+
+    async fn get(&self, uri: &str) -> Result<Vec<u8>>;
+
+    /// A URL that downloads the object without credentials until
+    /// `expires_in` has passed.
+    fn presigned_url(&self, uri: &str, expires_in: Duration) -> Result<String>;
+}
+
+/// Store `bytes` under `{kind}/{sha256}`, so identical artifacts, such as
+/// the verification key every proof of a model shares, are stored once.
+pub async fn store_artifact<A: ArtifactStore>(
+    store: &A,
+    kind: &str,
+    bytes: &[u8],
+    content_type: &str,
+) -> Result<StoredArtifact> {
+    let sha256 = hex::encode(Sha256::digest(bytes));
+    let uri = store.put(&format!("{}/{}", kind, sha256), bytes.to_vec(), content_type).await?;
+    Ok(StoredArtifact { uri, sha256 })
+}
+
+/// Store a proof's data.
+pub async fn store_proof<A: ArtifactStore>(store: &A, proof_data: &[u8]) -> Result<StoredArtifact> {
+    store_artifact(store, "proofs", proof_data, "application/json").await
+}
+
+/// Store a proof's data and its own copy of the verification key, if it has
+/// one.
+pub async fn store_proof_artifacts<A: ArtifactStore>(
+    store: &A,
+    proof_data: &[u8],
+    verification_key: Option<&[u8]>,
+) -> Result<ProofArtifacts> {
+    let proof = store_proof(store, proof_data).await?;
+    let verification_key = match verification_key {
+        Some(key) => Some(store_artifact(store, "verification-keys", key, "application/octet-stream").await?),
+        None => None,
+    };
+    Ok(ProofArtifacts { proof, verification_key })
+}
+
+/// Fetch an artifact and check it still hashes to `sha256`.
+pub async fn fetch_artifact<A: ArtifactStore>(store: &A, uri: &str, sha256: &str) -> Result<Vec<u8>> {
+    let bytes = store.get(uri).await?;
+    if hex::encode(Sha256::digest(&bytes)) != sha256 {
+        return Err(crate::Error::ArtifactStorage(format!("{} does not match its content hash", uri)));
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,62 @@
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// What a verified proof attests on-chain: which proof, a commitment to the
+/// score it proves and whose score it is.
+#[derive(Debug, Clone)]
+pub struct ProofAttestation {
+    pub proof_id: Uuid,
+    pub proof_hash: [u8; 32],
+    pub score_commitment: [u8; 32],
+    pub subject_address: String,
+}
+
+impl ProofAttestation {
+    pub fn new(proof_id: Uuid, proof_hash: [u8; 32], score_cents: i64, salt: &[u8], subject_address: String) -> Self {
+        Self {
+            proof_id,
+            proof_hash,
+            score_commitment: score_commitment(score_cents, salt),
+            subject_address,
+        }
+    }
+}
+
+/// SHA-256 of `salt` then the score in hundredths as a big-endian i64. The
+/// salt keeps the score from being guessed off the commitment; whoever holds
+/// it can open the commitment to the score.
+pub fn score_commitment(score_cents: i64, salt: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(score_cents.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// A signed attestation transaction, kept until it lands so retries submit
+/// the same transaction.
+#[derive(Debug, Clone)]
+pub struct PreparedAttestation {
+    pub digest: String,
+    pub signed_tx: Vec<u8>,
+}
+
+/// How a submitted attestation transaction ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttestationOutcome {
+    Published { digest: String },
+    /// The transaction executed but aborted; a new one has to be prepared.
+    Failed { digest: String, reason: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commitment_depends_on_score_and_salt() {
+        let commitment = score_commitment(8_250, b"salt");
+        assert_eq!(commitment, score_commitment(8_250, b"salt"));
+        assert_ne!(commitment, score_commitment(8_251, b"salt"));
+        assert_ne!(commitment, score_commitment(8_250, b"other"));
+    }
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::domain::attestation::{AttestationOutcome, PreparedAttestation, ProofAttestation};
+use crate::Result;
+
+/// Records proof attestations on-chain.
+#[async_trait]
+pub trait AttestationPublisher: Send + Sync {
+    /// Build and sign the transaction recording `attestation`.
+    async fn prepare(&self, attestation: &ProofAttestation) -> Result<PreparedAttestation>;
+
+    /// Execute a prepared transaction. Submitting one that already landed
+    /// returns its outcome again; an error means it may not have landed.
+    async fn submit(&self, prepared: &PreparedAttestation) -> Result<AttestationOutcome>;
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub const CREDENTIALS_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
+pub const CREDENTIAL_TYPE: &str = "ZkPersonaScoreCredential";
+pub const CREDENTIAL_STATUS_TYPE: &str = "ZkPersonaRevocationStatus";
+
+/// JWT claims of a credential, in the VC-JWT encoding of the W3C
+/// Verifiable Credentials data model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialClaims {
+    pub iss: String,
+    /// The holder's wallet address.
+    pub sub: String,
+    /// `urn:uuid:` of the credential's registry entry.
+    pub jti: String,
+    pub iat: i64,
+    pub nbf: i64,
+    pub exp: i64,
+    pub vc: VerifiableCredential,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    #[serde(rename = "type")]
+    pub types: Vec<String>,
+    pub credential_subject: CredentialSubject,
+    pub credential_status: CredentialStatusEntry,
+}
+
+/// What the credential asserts: the holder's score is at least
+/// `score_at_least`. The score itself is left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialSubject {
+    pub id: String,
+    pub score_at_least: i16,
+}
+
+/// Where the credential's revocation status is published.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialStatusEntry {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub status_type: String,
+}
+
+/// A credential's entry in the revocation registry.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CredentialRecord {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub subject_address: String,
+    pub threshold: i16,
+    #[serde(with = "time::serde::rfc3339")]
+    pub issued_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub revoked_at: Option<OffsetDateTime>,
+    pub revocation_reason: Option<String>,
+}
+
+/// A credential as issued to its holder.
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuedCredential {
+    #[serde(flatten)]
+    pub record: CredentialRecord,
+    /// The signed VC-JWT to hand to partners.
+    pub credential: String,
+}
+
+/// The outcome of checking a credential presented by a holder.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CredentialVerification {
+    /// Signed by this issuer, unexpired and not revoked.
+    pub valid: bool,
+    /// Valid, and asserting at least the score asked about.
+    pub eligible: bool,
+    pub credential_id: Option<Uuid>,
+    pub subject: Option<String>,
+    pub score_at_least: Option<i16>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+    /// Why the credential was rejected.
+    pub error: Option<String>,
+}
+
+/// The issuer's public key, as a JWK, for verifying credentials offline.
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialIssuer {
+    pub issuer: String,
+    pub public_key_jwk: serde_json::Value,
+}
@@ -1,2 +1,11 @@
 pub mod zkproof_repository_trait;
-pub mod mock_proof_generator;
\ No newline at end of file
+pub mod mock_proof_generator;
+pub mod attestation;
+pub mod attestation_publisher_trait;
+pub mod prover_trait;
+pub mod proof_job;
+pub mod proof_verification;
+pub mod artifact_store_trait;
+pub mod verification_key;
+pub mod credential;
+pub mod proof_request;
\ No newline at end of file
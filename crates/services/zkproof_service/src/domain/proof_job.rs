@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProofJobStatus {
+    Queued,
+    /// Held by a worker until its lease runs out.
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// A request to prove a scoring result, run in the background since
+/// proving takes minutes.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ProofJob {
+    pub id: Uuid,
+    pub scoring_result_id: Uuid,
+    /// Hex challenge the proof commits to, such as a proof request's nonce.
+    pub challenge: Option<String>,
+    pub status: ProofJobStatus,
+    pub attempts: i32,
+    /// The proof in `zkml_proofs` once the job succeeded.
+    pub proof_id: Option<Uuid>,
+    /// Why the last attempt failed.
+    pub error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub started_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub finished_at: Option<OffsetDateTime>,
+}
@@ -0,0 +1,169 @@
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Scope a token needs to create and read proof requests.
+pub const SCOPE_PROOF_REQUESTS: &str = "proofs:partner";
+
+/// What a partner asks a user to prove.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProofStatement {
+    /// The user's latest score is at least `threshold`.
+    ScoreAtLeast { threshold: i16 },
+}
+
+impl ProofStatement {
+    pub fn holds(&self, score_cents: i64) -> bool {
+        match self {
+            ProofStatement::ScoreAtLeast { threshold } => score_cents >= i64::from(*threshold) * 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProofRequestStatus {
+    Open,
+    Fulfilled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackStatus {
+    Pending,
+    Delivered,
+    /// Every attempt failed.
+    Failed,
+}
+
+/// A partner's request for a user to prove a statement, answered with a
+/// signed callback.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ProofRequest {
+    pub id: Uuid,
+    /// Subject of the partner token that created the request.
+    pub created_by: String,
+    /// Wallet address of the only user who may fulfil the request.
+    pub subject: Option<String>,
+    #[sqlx(json)]
+    pub statement: ProofStatement,
+    /// Challenge the fulfilment is bound to: the proof it rests on must be
+    /// generated for it.
+    pub nonce: String,
+    pub callback_url: String,
+    pub status: ProofRequestStatus,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+    pub fulfilled_by: Option<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub fulfilled_at: Option<OffsetDateTime>,
+    pub callback_status: Option<CallbackStatus>,
+    pub callback_attempts: i32,
+    pub callback_error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+/// A new request, with the secret its callback is signed with. The secret
+/// is only ever shown here.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedProofRequest {
+    #[serde(flatten)]
+    pub request: ProofRequest,
+    pub callback_secret: String,
+}
+
+/// A user's proof of their latest score, as a fulfilment needs it.
+#[derive(Debug, Clone, FromRow)]
+pub struct ProofOfScore {
+    pub proof_id: Uuid,
+    pub owner_address: Option<String>,
+    pub verified: bool,
+    /// Whether it proves the owner's latest score.
+    pub is_latest: bool,
+    pub score_cents: i64,
+    pub proof_sha256: String,
+    pub verification_key_id: Option<Uuid>,
+    pub public_signals: Option<Value>,
+}
+
+/// The callback body sent to the partner once a request is fulfilled. It
+/// says whether the statement holds, not the score.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProofRequestResponse {
+    pub request_id: Uuid,
+    pub nonce: String,
+    pub statement: ProofStatement,
+    pub satisfied: bool,
+    /// Wallet address of the user who fulfilled the request.
+    pub subject: String,
+    /// SHA-256 of the verified proof the fulfilment rests on.
+    pub proof_sha256: String,
+    /// The registered key the proof verifies against.
+    pub verification_key_id: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub fulfilled_at: OffsetDateTime,
+}
+
+/// A fulfilled request's callback, due for delivery.
+#[derive(Debug, Clone, FromRow)]
+pub struct DueCallback {
+    pub id: Uuid,
+    pub callback_url: String,
+    pub callback_secret: String,
+    pub response: Value,
+    pub callback_attempts: i32,
+}
+
+/// A new random callback secret: 64 hex characters.
+pub fn generate_callback_secret() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// A random challenge nonce: 32 hex characters.
+pub fn generate_nonce() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// `X-ZkPersona-Signature` of a callback: `sha256=` and the HMAC of
+/// `{timestamp}.{body}` under the request's callback secret.
+pub fn sign_callback(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn callback_signature_covers_timestamp_and_body() {
+        let body = br#"{"request_id":"1"}"#;
+        let signature = sign_callback("secret", 1_700_000_000, body);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(b"1700000000.");
+        mac.update(body);
+        assert_eq!(signature, format!("sha256={}", hex::encode(mac.finalize().into_bytes())));
+
+        assert_ne!(signature, sign_callback("secret", 1_700_000_001, body));
+        assert_ne!(signature, sign_callback("other", 1_700_000_000, body));
+    }
+
+    #[test]
+    fn score_statement_compares_in_cents() {
+        let statement = ProofStatement::ScoreAtLeast { threshold: 70 };
+        assert!(statement.holds(7000));
+        assert!(!statement.holds(6999));
+    }
+}
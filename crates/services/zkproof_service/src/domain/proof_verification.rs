@@ -0,0 +1,47 @@
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// A stored proof, as the verifier needs it. Its data is either inline or in
+/// object storage under its URI. Its verification key is in the key
+/// registry, or for proofs from before the registry, stored like its data.
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredProof {
+    pub id: Uuid,
+    pub proof_data: Option<Vec<u8>>,
+    pub verification_key: Option<Vec<u8>>,
+    pub proof_uri: Option<String>,
+    pub proof_sha256: Option<String>,
+    pub verification_key_uri: Option<String>,
+    pub verification_key_sha256: Option<String>,
+    pub verification_key_id: Option<Uuid>,
+    pub public_signals: Option<Value>,
+    /// The prover that generated it; `None` for proofs from before provers
+    /// were recorded.
+    pub prover_backend: Option<String>,
+}
+
+/// One run of the verifier over a proof.
+#[derive(Debug, Clone)]
+pub struct VerifierRun {
+    pub valid: bool,
+    pub verifier_version: String,
+    pub duration_ms: i64,
+    /// Why the proof was rejected.
+    pub error: Option<String>,
+}
+
+/// The outcome of verifying a stored proof server-side.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProofVerification {
+    pub proof_id: Uuid,
+    pub valid: bool,
+    pub verifier: String,
+    pub verifier_version: String,
+    pub verification_ms: i64,
+    pub error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub checked_at: OffsetDateTime,
+}
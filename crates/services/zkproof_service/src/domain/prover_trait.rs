@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::FromRow;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::Result;
+
+/// Hex digits of a challenge per public input value. Four keep each value
+/// within 16 bits, which the circuit represents exactly.
+const CHALLENGE_LIMB_DIGITS: usize = 4;
+
+/// What a proof is generated over: a scoring result and the behavior input
+/// the model scored.
+#[derive(Debug, Clone, FromRow)]
+pub struct ProvingInput {
+    pub scoring_result_id: Uuid,
+    pub score_cents: i64,
+    pub model_version: String,
+    pub behavior_input: Value,
+    /// Hex challenge, such as a proof request's nonce, the proof must
+    /// commit to.
+    #[sqlx(default)]
+    pub challenge: Option<String>,
+}
+
+impl ProvingInput {
+    /// The behavior input as the model's `len` features: its numbers and
+    /// booleans in key order, zero-padded or cut to length.
+    pub fn features(&self, len: usize) -> Vec<f64> {
+        let mut features = Vec::new();
+        collect_numbers(&self.behavior_input, &mut features);
+        features.resize(len, 0.0);
+        features
+    }
+}
+
+/// A hex challenge as the circuit's second input: its digits four at a
+/// time, as numbers. `None` unless it is hex of whole limbs.
+pub fn challenge_input(challenge: &str) -> Option<Vec<f64>> {
+    if challenge.is_empty() || challenge.len() % CHALLENGE_LIMB_DIGITS != 0 {
+        return None;
+    }
+    challenge
+        .as_bytes()
+        .chunks(CHALLENGE_LIMB_DIGITS)
+        .map(|limb| {
+            let limb = std::str::from_utf8(limb).ok()?;
+            u16::from_str_radix(limb, 16).ok().map(f64::from)
+        })
+        .collect()
+}
+
+/// Whether a proof's public signals commit to `challenge`: its second
+/// public input is the challenge's input. Signals may hold numbers or, as
+/// EZKL writes them, decimal strings.
+pub fn commits_to_challenge(public_signals: &Value, challenge: &str) -> bool {
+    let Some(expected) = challenge_input(challenge) else {
+        return false;
+    };
+    let Some(Value::Array(inputs)) = public_signals.get("rescaled_inputs").and_then(|inputs| inputs.get(1))
+    else {
+        return false;
+    };
+    inputs.len() == expected.len()
+        && inputs.iter().zip(&expected).all(|(input, expected)| {
+            let value = match input {
+                Value::Number(number) => number.as_f64(),
+                Value::String(text) => text.parse().ok(),
+                _ => None,
+            };
+            value.is_some_and(|value| value.round() == *expected)
+        })
+}
+
+fn collect_numbers(value: &Value, out: &mut Vec<f64>) {
+    match value {
+        Value::Number(number) => out.extend(number.as_f64()),
+        Value::Bool(flag) => out.push(if *flag { 1.0 } else { 0.0 }),
+        Value::Array(items) => items.iter().for_each(|item| collect_numbers(item, out)),
+        Value::Object(fields) => fields.values().for_each(|field| collect_numbers(field, out)),
+        Value::Null | Value::String(_) => {}
+    }
+}
+
+/// A generated proof, with the key it verifies against.
+#[derive(Debug, Clone)]
+pub struct GeneratedProof {
+    pub proof_data: Vec<u8>,
+    pub verification_key: Vec<u8>,
+    /// The proof's public inputs and outputs.
+    pub public_signals: Value,
+}
+
+/// Generates zk proofs that the committed model produced a score.
+#[async_trait]
+pub trait Prover: Send + Sync {
+    /// Name of the proving system, kept with each proof.
+    fn backend(&self) -> &str;
+
+    /// Longest a proof may take. Jobs are held from other workers a little
+    /// longer than this.
+    fn timeout(&self) -> Duration;
+
+    /// Hex SHA-256 of the compiled model proofs are made against.
+    async fn model_commitment(&self) -> Result<String>;
+
+    /// Prove the model's run on `input`; this takes minutes. A proof of an
+    /// input with a challenge commits to it (see `commits_to_challenge`).
+    async fn prove(&self, input: &ProvingInput) -> Result<GeneratedProof>;
+
+    /// Name and version of the verifier, kept with each verification.
+    async fn version(&self) -> Result<String>;
+
+    /// Whether `proof_data` verifies against `verification_key` and proves
+    /// `public_signals`.
+    async fn verify(&self, proof_data: &[u8], verification_key: &[u8], public_signals: &Value) -> Result<bool>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn features_are_the_inputs_numbers_in_key_order() {
+        let input = ProvingInput {
+            scoring_result_id: Uuid::nil(),
+            score_cents: 8_250,
+            model_version: "v1".to_string(),
+            behavior_input: json!({
+                "txs": [3, 4.5],
+                "active": true,
+                "label": "ignored",
+                "age_days": 12,
+            }),
+            challenge: None,
+        };
+        assert_eq!(input.features(6), [1.0, 12.0, 3.0, 4.5, 0.0, 0.0]);
+        assert_eq!(input.features(2), [1.0, 12.0]);
+    }
+
+    #[test]
+    fn proofs_commit_to_the_challenge_in_their_second_input() {
+        let challenge = "00ff1234abcd0001";
+        assert_eq!(challenge_input(challenge), Some(vec![255.0, 4660.0, 43981.0, 1.0]));
+        assert_eq!(challenge_input("abc"), None);
+        assert_eq!(challenge_input("zzzz"), None);
+
+        let signals = json!({ "rescaled_inputs": [["0.5"], ["255", "4660.0", "43981", "1"]] });
+        assert!(commits_to_challenge(&signals, challenge));
+        assert!(!commits_to_challenge(&signals, "00ff1234abcd0002"));
+        assert!(!commits_to_challenge(&json!({ "rescaled_inputs": [["0.5"]] }), challenge));
+    }
+}
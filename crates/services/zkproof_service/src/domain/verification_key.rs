@@ -0,0 +1,48 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Scope a token needs to publish verification keys.
+pub const SCOPE_PROOF_KEYS_ADMIN: &str = "proofs:keys_admin";
+
+/// A published verification key, without its bytes.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct VerificationKey {
+    pub id: Uuid,
+    pub model_version: String,
+    pub circuit_version: String,
+    pub prover_backend: String,
+    /// Hex SHA-256 of the key.
+    pub key_sha256: String,
+    /// New proofs of the model use the key from this time on.
+    #[serde(with = "time::serde::rfc3339")]
+    pub active_from: OffsetDateTime,
+    /// Until this time; a key published later for the model ends it.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub active_until: Option<OffsetDateTime>,
+    /// Subject of the admin token that published the key.
+    pub published_by: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+/// A verification key with its bytes, base64-encoded.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationKeyDetail {
+    #[serde(flatten)]
+    pub key: VerificationKey,
+    pub verification_key: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewVerificationKey {
+    pub model_version: String,
+    pub circuit_version: String,
+    pub prover_backend: String,
+    pub verification_key: Vec<u8>,
+    /// Defaults to now.
+    pub active_from: Option<OffsetDateTime>,
+    pub active_until: Option<OffsetDateTime>,
+    pub published_by: Option<String>,
+}
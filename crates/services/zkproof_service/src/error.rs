@@ -1,36 +1,91 @@
 use axum::{http::StatusCode, response::IntoResponse, Json};
 use serde_json::json;
 
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Debug, jd_error::ErrorTaxonomy)]
+#[taxonomy(service = "zkproof")]
 pub enum Error {
     #[error("Invalid proof input: {0}")]
+    #[taxonomy(kind = Validation, expose)]
     InvalidInput(String),
     
     #[error("Proof generation failed: {0}")]
+    #[taxonomy(kind = Internal)]
     ProofGeneration(String),
     
     #[error("Proof verification failed: {0}")]
+    #[taxonomy(kind = Validation, expose)]
     ProofVerification(String),
     
+    #[error("Proof not found: {0}")]
+    #[taxonomy(kind = NotFound, expose)]
+    ProofNotFound(uuid::Uuid),
+    
+    #[error("Proof job not found: {0}")]
+    #[taxonomy(kind = NotFound, expose)]
+    ProofJobNotFound(uuid::Uuid),
+    
+    #[error("Verification key not found: {0}")]
+    #[taxonomy(kind = NotFound, expose)]
+    VerificationKeyNotFound(String),
+    
+    #[error("Proof request not found: {0}")]
+    #[taxonomy(kind = NotFound, expose)]
+    ProofRequestNotFound(uuid::Uuid),
+    
+    #[error("Credential not found: {0}")]
+    #[taxonomy(kind = NotFound, expose)]
+    CredentialNotFound(uuid::Uuid),
+    
+    #[error("Credential issuer unavailable: {0}")]
+    #[taxonomy(kind = Unavailable, expose)]
+    CredentialIssuerUnavailable(String),
+    
+    #[error("Prover unavailable: {0}")]
+    #[taxonomy(kind = Unavailable, expose)]
+    ProverUnavailable(String),
+    
+    #[error("Artifact storage error: {0}")]
+    #[taxonomy(kind = Upstream)]
+    ArtifactStorage(String),
+    
     #[error("Database error: {0}")]
+    #[taxonomy(kind = Internal, code = "DATABASE_ERROR")]
     Database(#[from] sqlx::Error),
     
     #[error("Serialization error: {0}")]
+    #[taxonomy(kind = Validation, message = "Invalid data format")]
     Serialization(#[from] serde_json::Error),
     
     #[error("Internal error: {0}")]
+    #[taxonomy(kind = Internal)]
     Internal(String),
     
     #[error("Core error: {0}")]
+    #[taxonomy(kind = Internal, code = "CORE_ERROR")]
     Core(#[from] jd_core::Error),
 }
 
+/// Malformed ids from requests are the caller's mistake.
+impl From<jd_domain::Error> for Error {
+    fn from(err: jd_domain::Error) -> Self {
+        Error::InvalidInput(err.to_string())
+    }
+}
+
 impl IntoResponse for Error {
     fn into_response(self) -> axum::response::Response {
         let (status, error_message) = match self {
             Error::InvalidInput(msg) => (StatusCode::BAD_REQUEST, msg),
             Error::ProofGeneration(msg) => (StatusCode::UNPROCESSABLE_ENTITY, msg),
             Error::ProofVerification(msg) => (StatusCode::BAD_REQUEST, msg),
+            Error::ProofNotFound(id) => (StatusCode::NOT_FOUND, format!("Proof {} not found", id)),
+            Error::ProofJobNotFound(id) => (StatusCode::NOT_FOUND, format!("Proof job {} not found", id)),
+            Error::VerificationKeyNotFound(msg) => (StatusCode::NOT_FOUND, format!("Verification key not found: {}", msg)),
+            Error::ProofRequestNotFound(id) => (StatusCode::NOT_FOUND, format!("Proof request {} not found", id)),
+            Error::CredentialNotFound(id) => (StatusCode::NOT_FOUND, format!("Credential {} not found", id)),
+            Error::CredentialIssuerUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
+            Error::ProverUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
+            Error::ArtifactStorage(_) => (StatusCode::BAD_GATEWAY, "Artifact storage error".to_string()),
             Error::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error".to_string()),
             Error::Serialization(_) => (StatusCode::BAD_REQUEST, "Invalid data format".to_string()),
             Error::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()),
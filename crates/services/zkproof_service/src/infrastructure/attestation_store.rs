@@ -0,0 +1,144 @@
+use sqlx::{FromRow, Pool, Postgres};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::attestation::PreparedAttestation;
+use crate::Result;
+
+/// A verified proof claimed for one attestation attempt.
+#[derive(Debug, FromRow)]
+pub struct DueAttestation {
+    pub id: Uuid,
+    /// Hex SHA-256 of the proof data.
+    pub proof_sha256: String,
+    pub score_cents: i64,
+    pub subject_address: String,
+    pub attestation_salt: Option<Vec<u8>>,
+    pub attestation_digest: Option<String>,
+    pub attestation_tx: Option<Vec<u8>>,
+    pub attestation_attempts: i32,
+    /// Seconds since the kept transaction was signed.
+    pub prepared_secs_ago: Option<i64>,
+}
+
+impl DueAttestation {
+    pub fn prepared(&self) -> Option<PreparedAttestation> {
+        Some(PreparedAttestation {
+            digest: self.attestation_digest.clone()?,
+            signed_tx: self.attestation_tx.clone()?,
+        })
+    }
+}
+
+/// The attestation state of verified proofs in `zkml_proofs`.
+#[derive(Clone)]
+pub struct AttestationStore {
+    db: Pool<Postgres>,
+}
+
+impl AttestationStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    /// Claim up to `limit` verified, unpublished proofs whose subject has a
+    /// wallet, holding each for `lease` so other instances skip it. Proofs
+    /// that used up `max_attempts` are left alone.
+    pub async fn claim_due(&self, limit: i64, lease: Duration, max_attempts: i32) -> Result<Vec<DueAttestation>> {
+        let due = sqlx::query_as::<_, DueAttestation>(
+            r#"
+            UPDATE zkml_proofs p
+            SET attestation_attempts = p.attestation_attempts + 1,
+                attestation_retry_at = NOW() + make_interval(secs => $2)
+            FROM (
+                SELECT z.id, (s.score * 100)::BIGINT AS score_cents, u.wallet_address
+                FROM zkml_proofs z
+                JOIN scoring_results s ON s.id = z.scoring_result_id
+                JOIN behavior_inputs b ON b.id = s.behavior_input_id
+                JOIN users u ON u.id = b.user_id
+                WHERE z.verified
+                  AND z.blockchain_tx_hash IS NULL
+                  AND z.attestation_attempts < $3
+                  AND (z.attestation_retry_at IS NULL OR z.attestation_retry_at <= NOW())
+                ORDER BY z.timestamp
+                LIMIT $1
+                FOR UPDATE OF z SKIP LOCKED
+            ) due
+            WHERE p.id = due.id
+            RETURNING p.id, COALESCE(p.proof_sha256, encode(sha256(p.proof_data), 'hex')) AS proof_sha256,
+                      due.score_cents, due.wallet_address AS subject_address,
+                      p.attestation_salt, p.attestation_digest, p.attestation_tx,
+                      p.attestation_attempts,
+                      EXTRACT(EPOCH FROM NOW() - p.attestation_prepared_at)::BIGINT AS prepared_secs_ago
+            "#,
+        )
+        .bind(limit)
+        .bind(lease.as_secs_f64())
+        .bind(max_attempts)
+        .fetch_all(&self.db)
+        .await?;
+        Ok(due)
+    }
+
+    /// Keep a signed transaction, and the salt its commitment used, before
+    /// it is submitted.
+    pub async fn record_prepared(&self, id: Uuid, salt: &[u8], prepared: &PreparedAttestation) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE zkml_proofs
+            SET attestation_salt = $2,
+                attestation_digest = $3,
+                attestation_tx = $4,
+                attestation_prepared_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(salt)
+        .bind(&prepared.digest)
+        .bind(&prepared.signed_tx)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn record_published(&self, id: Uuid, digest: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE zkml_proofs
+            SET blockchain_tx_hash = $2,
+                attestation_tx = NULL,
+                attestation_error = NULL,
+                attestation_retry_at = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(digest)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    /// Note a failed attempt and when to retry. With `discard_tx` the kept
+    /// transaction is dropped and the next attempt signs a new one.
+    pub async fn record_failure(&self, id: Uuid, error: &str, retry_in: Duration, discard_tx: bool) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE zkml_proofs
+            SET attestation_error = $2,
+                attestation_retry_at = NOW() + make_interval(secs => $3),
+                attestation_digest = CASE WHEN $4 THEN NULL ELSE attestation_digest END,
+                attestation_tx = CASE WHEN $4 THEN NULL ELSE attestation_tx END
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_in.as_secs_f64())
+        .bind(discard_tx)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+}
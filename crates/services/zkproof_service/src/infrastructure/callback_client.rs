@@ -0,0 +1,148 @@
+use reqwest::{redirect::Policy, Client, Url};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::proof_request::sign_callback;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Posts signed proof request callbacks to partners. Callback URLs are
+/// partner-supplied, so each delivery resolves the host itself, refuses
+/// anything but public addresses and follows no redirects.
+#[derive(Clone, Default)]
+pub struct CallbackClient;
+
+impl CallbackClient {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Post `body` to `url`, signed with `secret`. `Err` holds why the
+    /// partner did not accept it.
+    pub async fn deliver(
+        &self,
+        request_id: Uuid,
+        url: &str,
+        secret: &str,
+        body: Vec<u8>,
+    ) -> std::result::Result<(), String> {
+        let parsed = Url::parse(url).map_err(|e| format!("Invalid callback URL: {}", e))?;
+        let addrs = public_addrs(&parsed).await?;
+        let mut http = Client::builder().timeout(TIMEOUT).redirect(Policy::none());
+        // Connect only to the addresses just checked, so a second lookup
+        // cannot swap in a private one
+        if let Some(domain) = parsed.domain() {
+            http = http.resolve_to_addrs(domain, &addrs);
+        }
+        let http = http.build().map_err(|e| e.to_string())?;
+
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        let signature = sign_callback(secret, timestamp, &body);
+        let response = http
+            .post(parsed)
+            .header("Content-Type", "application/json")
+            .header("X-ZkPersona-Request-Id", request_id.to_string())
+            .header("X-ZkPersona-Timestamp", timestamp.to_string())
+            .header("X-ZkPersona-Signature", signature)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("{} returned {}", url, response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// The addresses `url`'s host resolves to, as long as every one of them is
+/// public.
+async fn public_addrs(url: &Url) -> std::result::Result<Vec<SocketAddr>, String> {
+    let host = url.host_str().ok_or("Callback URL has no host")?;
+    let port = url.port_or_known_default().ok_or("Callback URL has no port")?;
+    // IPv6 literals keep their brackets in `host_str`
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("{} did not resolve", host));
+    }
+    if let Some(addr) = addrs.iter().find(|addr| !is_public(addr.ip())) {
+        return Err(format!("{} resolves to non-public address {}", host, addr.ip()));
+    }
+    Ok(addrs)
+}
+
+/// Whether `ip` is reachable on the public internet, rather than loopback,
+/// private, link-local, shared or otherwise reserved.
+pub fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_v4(mapped),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        // Shared address space (carrier-grade NAT)
+        || (a == 100 && (64..128).contains(&b))
+        // Benchmarking
+        || (a == 198 && (18..20).contains(&b))
+        // Reserved for future use
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local, fc00::/7
+        || (first & 0xfe00) == 0xfc00
+        // Link-local, fe80::/10
+        || (first & 0xffc0) == 0xfe80
+        // Documentation, 2001:db8::/32
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_public_addresses_take_callbacks() {
+        for public in ["93.184.216.34", "2606:2800:220:1:248:1893:25c8:1946"] {
+            assert!(is_public(public.parse().unwrap()), "{}", public);
+        }
+        for internal in [
+            "127.0.0.1",
+            "10.0.0.5",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(!is_public(internal.parse().unwrap()), "{}", internal);
+        }
+    }
+}
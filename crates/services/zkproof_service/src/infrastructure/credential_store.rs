@@ -0,0 +1,133 @@
+use sqlx::{FromRow, Pool, Postgres};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::credential::CredentialRecord;
+use crate::Result;
+
+const CREDENTIAL_COLUMNS: &str =
+    "id, subject_address, threshold, issued_at, expires_at, revoked_at, revocation_reason";
+
+/// A holder's most recent score.
+#[derive(Debug, Clone, FromRow)]
+pub struct LatestScore {
+    pub scoring_result_id: Uuid,
+    pub score_cents: i64,
+}
+
+/// The credential revocation registry in `score_credentials`.
+#[derive(Clone)]
+pub struct CredentialStore {
+    db: Pool<Postgres>,
+}
+
+impl CredentialStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    /// The latest score of the user with wallet `address`.
+    pub async fn latest_score(&self, address: &str) -> Result<Option<LatestScore>> {
+        let score = sqlx::query_as::<_, LatestScore>(
+            r#"
+            SELECT s.id AS scoring_result_id, (s.score * 100)::BIGINT AS score_cents
+            FROM scoring_results s
+            JOIN behavior_inputs b ON b.id = s.behavior_input_id
+            JOIN users u ON u.id = b.user_id
+            WHERE u.wallet_address = $1
+            ORDER BY s.timestamp DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(address)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(score)
+    }
+
+    pub async fn insert(
+        &self,
+        subject_address: &str,
+        threshold: i16,
+        scoring_result_id: Uuid,
+        expires_at: OffsetDateTime,
+    ) -> Result<CredentialRecord> {
+        let record = sqlx::query_as::<_, CredentialRecord>(&format!(
+            r#"
+            INSERT INTO score_credentials (subject_address, threshold, scoring_result_id, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING {}
+            "#,
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(subject_address)
+        .bind(threshold)
+        .bind(scoring_result_id)
+        .bind(expires_at)
+        .fetch_one(&self.db)
+        .await?;
+        Ok(record)
+    }
+
+    pub async fn find(&self, id: Uuid) -> Result<Option<CredentialRecord>> {
+        let record = sqlx::query_as::<_, CredentialRecord>(&format!(
+            "SELECT {} FROM score_credentials WHERE id = $1",
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(record)
+    }
+
+    /// Revoke a credential of `subject_address`. Revoking it again keeps
+    /// the first revocation.
+    pub async fn revoke(&self, id: Uuid, subject_address: &str, reason: &str) -> Result<Option<CredentialRecord>> {
+        let record = sqlx::query_as::<_, CredentialRecord>(&format!(
+            r#"
+            UPDATE score_credentials
+            SET revoked_at = COALESCE(revoked_at, NOW()),
+                revocation_reason = COALESCE(revocation_reason, $3)
+            WHERE id = $1 AND subject_address = $2
+            RETURNING {}
+            "#,
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(id)
+        .bind(subject_address)
+        .bind(reason)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(record)
+    }
+
+    /// Revoke unexpired credentials whose holder's latest score is now
+    /// below the credential's threshold.
+    pub async fn revoke_below_threshold(&self) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE score_credentials c
+            SET revoked_at = NOW(),
+                revocation_reason = 'score_below_threshold'
+            FROM (
+                SELECT DISTINCT ON (u.wallet_address) u.wallet_address, s.score
+                FROM scoring_results s
+                JOIN behavior_inputs b ON b.id = s.behavior_input_id
+                JOIN users u ON u.id = b.user_id
+                WHERE u.wallet_address IN (
+                    SELECT subject_address FROM score_credentials
+                    WHERE revoked_at IS NULL AND expires_at > NOW()
+                )
+                ORDER BY u.wallet_address, s.timestamp DESC
+            ) latest
+            WHERE latest.wallet_address = c.subject_address
+              AND c.revoked_at IS NULL
+              AND c.expires_at > NOW()
+              AND latest.score < c.threshold
+            "#,
+        )
+        .execute(&self.db)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,249 @@
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::Instant;
+use tracing::debug;
+
+use crate::domain::prover_trait::{challenge_input, GeneratedProof, Prover, ProvingInput};
+use crate::{Error, Result};
+
+/// Prover output kept in a failure's message.
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+/// How the EZKL prover is run, and where the model's compiled circuit and
+/// keys are. The artifacts directory holds what `ezkl compile-circuit`,
+/// `ezkl get-srs` and `ezkl setup` write under their default names.
+#[derive(Debug, Clone)]
+pub struct EzklSettings {
+    /// The `ezkl` binary.
+    pub command: String,
+    /// Directory with `network.ezkl`, `settings.json`, `pk.key`, `vk.key`
+    /// and `kzg.srs`.
+    pub artifacts_dir: PathBuf,
+    /// Number of features the model takes.
+    pub input_len: usize,
+    /// Budget for witnessing and proving one scoring result.
+    pub timeout: Duration,
+}
+
+impl EzklSettings {
+    /// Settings from `EZKL_*`, or `None` unless `EZKL_ARTIFACTS_DIR` names
+    /// the compiled model.
+    pub fn from_env() -> Option<Self> {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+        Some(Self {
+            command: env("EZKL_COMMAND").unwrap_or_else(|| "ezkl".to_string()),
+            artifacts_dir: PathBuf::from(env("EZKL_ARTIFACTS_DIR")?),
+            input_len: env("EZKL_INPUT_LEN").and_then(|len| len.parse().ok()).unwrap_or(16),
+            timeout: env("EZKL_TIMEOUT_SECS")
+                .and_then(|secs| secs.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(Duration::from_secs(15 * 60)),
+        })
+    }
+
+    fn artifact(&self, name: &str) -> String {
+        self.artifacts_dir.join(name).to_string_lossy().into_owned()
+    }
+}
+
+/// Proves scoring results with EZKL: the model, exported to ONNX and
+/// compiled to a Halo2 circuit, is run on the behavior input's features and
+/// the run is proven with KZG commitments. Proofs with a challenge pass it
+/// as the model's second input, which the circuit must make public.
+pub struct EzklProver {
+    settings: EzklSettings,
+}
+
+impl EzklProver {
+    pub fn new(settings: EzklSettings) -> Self {
+        Self { settings }
+    }
+
+    /// The prover configured by `EzklSettings::from_env`.
+    pub fn from_env() -> Option<Self> {
+        EzklSettings::from_env().map(Self::new)
+    }
+
+    /// Run an `ezkl` subcommand in `dir`. A run that exits with an error
+    /// gives back the end of its error output.
+    async fn run(&self, dir: &Path, args: &[&str], deadline: Instant) -> Result<std::result::Result<(), String>> {
+        let mut command = Command::new(&self.settings.command);
+        command
+            .args(args)
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+
+        let output = tokio::time::timeout_at(deadline, command.output())
+            .await
+            .map_err(|_| Error::ProofGeneration(format!("ezkl {} timed out", args[0])))?
+            .map_err(|e| Error::ProverUnavailable(format!("Failed to run {}: {}", self.settings.command, e)))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stderr = stderr.trim();
+            let start = stderr.char_indices().rev().nth(MAX_MESSAGE_CHARS - 1).map_or(0, |(i, _)| i);
+            return Ok(Err(stderr[start..].to_string()));
+        }
+        Ok(Ok(()))
+    }
+}
+
+fn failed(step: &str) -> impl FnOnce(String) -> Error + '_ {
+    move |message| Error::ProofGeneration(format!("ezkl {} failed: {}", step, message))
+}
+
+/// The rescaled inputs and outputs of an EZKL proof when it recorded them,
+/// else its raw field elements.
+fn public_signals(proof: &Value) -> Value {
+    proof
+        .get("pretty_public_inputs")
+        .filter(|signals| !signals.is_null())
+        .or_else(|| proof.get("instances"))
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+fn scratch_dir() -> Result<tempfile::TempDir> {
+    tempfile::Builder::new()
+        .prefix("ezkl-")
+        .tempdir()
+        .map_err(|e| Error::Internal(format!("Failed to create proving directory: {}", e)))
+}
+
+fn io_error(action: &str) -> impl FnOnce(std::io::Error) -> Error + '_ {
+    move |e| Error::Internal(format!("Failed to {}: {}", action, e))
+}
+
+#[async_trait]
+impl Prover for EzklProver {
+    fn backend(&self) -> &str {
+        "ezkl"
+    }
+
+    fn timeout(&self) -> Duration {
+        self.settings.timeout
+    }
+
+    async fn model_commitment(&self) -> Result<String> {
+        let circuit = tokio::fs::read(self.settings.artifact("network.ezkl"))
+            .await
+            .map_err(|e| Error::ProverUnavailable(format!("Failed to read compiled circuit: {}", e)))?;
+        Ok(hex::encode(Sha256::digest(&circuit)))
+    }
+
+    async fn prove(&self, input: &ProvingInput) -> Result<GeneratedProof> {
+        let scratch = scratch_dir()?;
+        let dir = scratch.path();
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let (data, witness, proof) = (path("input.json"), path("witness.json"), path("proof.json"));
+
+        let features = input.features(self.settings.input_len);
+        let input_data = match &input.challenge {
+            Some(challenge) => {
+                let challenge = challenge_input(challenge)
+                    .ok_or_else(|| Error::InvalidInput(format!("Challenge {} is not hex", challenge)))?;
+                json!([features, challenge])
+            }
+            None => json!([features]),
+        };
+        let data_json = serde_json::to_vec(&json!({ "input_data": input_data }))?;
+        tokio::fs::write(&data, data_json).await.map_err(io_error("write proving input"))?;
+
+        let circuit = self.settings.artifact("network.ezkl");
+        let srs = self.settings.artifact("kzg.srs");
+        let deadline = Instant::now() + self.settings.timeout;
+        self.run(
+            dir,
+            &["gen-witness", "--data", &data, "--compiled-circuit", &circuit, "--output", &witness],
+            deadline,
+        )
+        .await?
+        .map_err(failed("gen-witness"))?;
+        self.run(
+            dir,
+            &[
+                "prove",
+                "--witness", &witness,
+                "--compiled-circuit", &circuit,
+                "--pk-path", &self.settings.artifact("pk.key"),
+                "--proof-path", &proof,
+                "--srs-path", &srs,
+            ],
+            deadline,
+        )
+        .await?
+        .map_err(failed("prove"))?;
+
+        let proof_data = tokio::fs::read(&proof).await.map_err(io_error("read proof"))?;
+        let verification_key = tokio::fs::read(self.settings.artifact("vk.key"))
+            .await
+            .map_err(|e| Error::ProverUnavailable(format!("Failed to read verification key: {}", e)))?;
+        let public_signals = public_signals(&serde_json::from_slice(&proof_data)?);
+
+        Ok(GeneratedProof { proof_data, verification_key, public_signals })
+    }
+
+    async fn version(&self) -> Result<String> {
+        let output = Command::new(&self.settings.command)
+            .arg("--version")
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| Error::ProverUnavailable(format!("Failed to run {}: {}", self.settings.command, e)))?;
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(if version.is_empty() { "ezkl".to_string() } else { version })
+    }
+
+    async fn verify(
+        &self,
+        proof_data: &[u8],
+        verification_key: &[u8],
+        expected_signals: &Value,
+    ) -> Result<bool> {
+        // The signals kept with a proof have to be the ones it proves
+        match serde_json::from_slice::<Value>(proof_data) {
+            Ok(proof) if public_signals(&proof) == *expected_signals => {}
+            Ok(_) => {
+                debug!("EZKL proof does not prove the recorded public signals");
+                return Ok(false);
+            }
+            Err(e) => {
+                debug!("EZKL proof is not valid JSON: {}", e);
+                return Ok(false);
+            }
+        }
+
+        let scratch = scratch_dir()?;
+        let dir = scratch.path();
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let (proof, vk) = (path("proof.json"), path("vk.key"));
+        tokio::fs::write(&proof, proof_data).await.map_err(io_error("write proof"))?;
+        tokio::fs::write(&vk, verification_key).await.map_err(io_error("write verification key"))?;
+
+        let deadline = Instant::now() + self.settings.timeout;
+        let verified = self
+            .run(
+                dir,
+                &[
+                    "verify",
+                    "--proof-path", &proof,
+                    "--settings-path", &self.settings.artifact("settings.json"),
+                    "--vk-path", &vk,
+                    "--srs-path", &self.settings.artifact("kzg.srs"),
+                ],
+                deadline,
+            )
+            .await?;
+        // EZKL exits with an error for a proof that does not verify
+        if let Err(message) = &verified {
+            debug!("EZKL rejected proof: {}", message);
+        }
+        Ok(verified.is_ok())
+    }
+}
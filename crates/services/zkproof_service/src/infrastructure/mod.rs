@@ -1 +1,11 @@
-pub mod zkproof_repository_impl;
\ No newline at end of file
+pub mod zkproof_repository_impl;
+pub mod attestation_store;
+pub mod proof_job_store;
+pub mod proof_store;
+pub mod ezkl_prover;
+pub mod object_artifact_store;
+pub mod verification_key_store;
+pub mod credential_store;
+pub mod credential_signer;
+pub mod proof_request_store;
+pub mod callback_client;
\ No newline at end of file
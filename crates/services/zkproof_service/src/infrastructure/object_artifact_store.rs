@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use jd_object_store::ObjectStore;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::artifact_store_trait::ArtifactStore;
+use crate::{Error, Result};
+
+/// Key prefix of proof artifacts, as under the `OBJECT_STORE_PREFIX` they
+/// were stored with before the shared object store.
+const ARTIFACT_PREFIX: &str = "zkml";
+
+/// Proof artifacts in the configured object store.
+#[derive(Clone)]
+pub struct ObjectArtifactStore {
+    objects: Arc<dyn ObjectStore>,
+}
+
+impl ObjectArtifactStore {
+    pub fn new(objects: Arc<dyn ObjectStore>) -> Self {
+        Self { objects }
+    }
+}
+
+fn storage_error(e: jd_object_store::Error) -> Error {
+    Error::ArtifactStorage(e.to_string())
+}
+
+#[async_trait]
+impl ArtifactStore for ObjectArtifactStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<String> {
+        let key = format!("{}/{}", ARTIFACT_PREFIX, key);
+        // Keys are content hashes, so an object already there has these bytes
+        if self.objects.head(&key).await.map_err(storage_error)?.is_none() {
+            self.objects.put(&key, Bytes::from(bytes), content_type).await.map_err(storage_error)?;
+        }
+        Ok(self.objects.uri(&key))
+    }
+
+    async fn get(&self, uri: &str) -> Result<Vec<u8>> {
+        let key = self.objects.key(uri).map_err(storage_error)?;
+        Ok(self.objects.get(&key).await.map_err(storage_error)?.to_vec())
+    }
+
+    fn presigned_url(&self, uri: &str, expires_in: Duration) -> Result<String> {
+        let key = self.objects.key(uri).map_err(storage_error)?;
+        self.objects.presigned_get_url(&key, expires_in).map_err(storage_error)
+    }
+}
@@ -0,0 +1,229 @@
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::artifact_store_trait::StoredArtifact;
+use crate::domain::proof_job::ProofJob;
+use crate::domain::proof_verification::VerifierRun;
+use crate::domain::prover_trait::{GeneratedProof, ProvingInput};
+use crate::domain::verification_key::VerificationKey;
+use crate::Result;
+
+/// A proof a job generated, ready to be stored.
+pub struct NewProof<'a> {
+    pub scoring_result_id: Uuid,
+    pub proof: &'a GeneratedProof,
+    /// The registered key the proof verifies against, kept instead of a copy.
+    pub verification_key: &'a VerificationKey,
+    pub prover_backend: &'a str,
+    pub model_commitment: &'a str,
+    pub verification: &'a VerifierRun,
+    /// Where the proof's data was offloaded, if it was.
+    pub artifact: Option<&'a StoredArtifact>,
+}
+
+const JOB_COLUMNS: &str =
+    "id, scoring_result_id, challenge, status, attempts, proof_id, error, created_at, started_at, finished_at";
+
+/// Persists `zkml_proof_jobs`, and the proofs they generate in `zkml_proofs`.
+#[derive(Clone)]
+pub struct ProofJobStore {
+    db: Pool<Postgres>,
+}
+
+impl ProofJobStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(&self, id: Uuid) -> Result<Option<ProofJob>> {
+        let job = sqlx::query_as::<_, ProofJob>(&format!("SELECT {} FROM zkml_proof_jobs WHERE id = $1", JOB_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.db)
+            .await?;
+        Ok(job)
+    }
+
+    /// Queue a proof of a scoring result for `challenge`. A scoring result
+    /// has one job per challenge: asking again returns it, and requeues it if
+    /// it failed.
+    pub async fn enqueue(&self, scoring_result_id: Uuid, challenge: Option<&str>) -> Result<ProofJob> {
+        let queued = sqlx::query_as::<_, ProofJob>(&format!(
+            r#"
+            INSERT INTO zkml_proof_jobs (scoring_result_id, challenge)
+            VALUES ($1, $2)
+            ON CONFLICT (scoring_result_id, COALESCE(challenge, '')) DO UPDATE
+            SET status = 'queued',
+                attempts = 0,
+                error = NULL,
+                lease_until = NULL,
+                started_at = NULL,
+                finished_at = NULL,
+                updated_at = NOW()
+            WHERE zkml_proof_jobs.status = 'failed'
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(scoring_result_id)
+        .bind(challenge)
+        .fetch_optional(&self.db)
+        .await?;
+        if let Some(job) = queued {
+            return Ok(job);
+        }
+
+        let job = sqlx::query_as::<_, ProofJob>(&format!(
+            "SELECT {} FROM zkml_proof_jobs WHERE scoring_result_id = $1 AND challenge IS NOT DISTINCT FROM $2",
+            JOB_COLUMNS
+        ))
+        .bind(scoring_result_id)
+        .bind(challenge)
+        .fetch_one(&self.db)
+        .await?;
+        Ok(job)
+    }
+
+    /// Claim the oldest queued job, or a running one whose worker let its
+    /// lease run out, holding it for `lease`. Jobs that used up
+    /// `max_attempts` are left for `fail_abandoned`.
+    pub async fn claim_next(&self, lease: Duration, max_attempts: i32) -> Result<Option<ProofJob>> {
+        let job = sqlx::query_as::<_, ProofJob>(&format!(
+            r#"
+            UPDATE zkml_proof_jobs j
+            SET status = 'running',
+                attempts = j.attempts + 1,
+                lease_until = NOW() + make_interval(secs => $1),
+                started_at = NOW(),
+                updated_at = NOW()
+            FROM (
+                SELECT id AS next_id
+                FROM zkml_proof_jobs
+                WHERE attempts < $2
+                  AND (status = 'queued' OR (status = 'running' AND lease_until < NOW()))
+                ORDER BY created_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            ) next
+            WHERE j.id = next.next_id
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(lease.as_secs_f64())
+        .bind(max_attempts)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(job)
+    }
+
+    /// Fail running jobs whose last allowed attempt never finished.
+    pub async fn fail_abandoned(&self, max_attempts: i32) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE zkml_proof_jobs
+            SET status = 'failed',
+                error = COALESCE(error, 'Prover did not finish'),
+                lease_until = NULL,
+                finished_at = NOW(),
+                updated_at = NOW()
+            WHERE status = 'running' AND lease_until < NOW() AND attempts >= $1
+            "#,
+        )
+        .bind(max_attempts)
+        .execute(&self.db)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// The scoring result a job proves, with its behavior input.
+    pub async fn proving_input(&self, scoring_result_id: Uuid) -> Result<Option<ProvingInput>> {
+        let input = sqlx::query_as::<_, ProvingInput>(
+            r#"
+            SELECT s.id AS scoring_result_id,
+                   (s.score * 100)::BIGINT AS score_cents,
+                   s.model_version,
+                   b.input_data AS behavior_input
+            FROM scoring_results s
+            JOIN behavior_inputs b ON b.id = s.behavior_input_id
+            WHERE s.id = $1
+            "#,
+        )
+        .bind(scoring_result_id)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(input)
+    }
+
+    /// Store a job's proof with the verifier run that checked it, and mark
+    /// the job succeeded. Offloaded data is kept by URI and hash only.
+    pub async fn record_proof(&self, job_id: Uuid, proof: NewProof<'_>) -> Result<Uuid> {
+        let proof_data = proof.artifact.is_none().then_some(&proof.proof.proof_data);
+        let mut tx = self.db.begin().await?;
+        let proof_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO zkml_proofs
+                (scoring_result_id, proof_data, verified, proof_uri, proof_sha256,
+                 verification_key_id, verification_key_sha256,
+                 public_signals, prover_backend, model_commitment,
+                 verifier_version, verification_ms, verification_checked_at)
+            VALUES ($1, $2, true, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW())
+            RETURNING id
+            "#,
+        )
+        .bind(proof.scoring_result_id)
+        .bind(proof_data)
+        .bind(proof.artifact.map(|stored| &stored.uri))
+        .bind(proof.artifact.map(|stored| &stored.sha256))
+        .bind(proof.verification_key.id)
+        .bind(&proof.verification_key.key_sha256)
+        .bind(&proof.proof.public_signals)
+        .bind(proof.prover_backend)
+        .bind(proof.model_commitment)
+        .bind(&proof.verification.verifier_version)
+        .bind(proof.verification.duration_ms.clamp(0, i32::MAX as i64) as i32)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE zkml_proof_jobs
+            SET status = 'succeeded',
+                proof_id = $2,
+                error = NULL,
+                lease_until = NULL,
+                finished_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(proof_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(proof_id)
+    }
+
+    /// Note a failed attempt. With `retry` the job is queued again,
+    /// otherwise it is failed for good.
+    pub async fn record_failure(&self, job_id: Uuid, error: &str, retry: bool) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE zkml_proof_jobs
+            SET status = CASE WHEN $3 THEN 'queued' ELSE 'failed' END,
+                error = $2,
+                lease_until = NULL,
+                finished_at = CASE WHEN $3 THEN NULL ELSE NOW() END,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(error)
+        .bind(retry)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use serde_json::Value;
+use sqlx::types::Json;
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::proof_request::{DueCallback, ProofOfScore, ProofRequest, ProofStatement};
+use crate::Result;
+
+const REQUEST_COLUMNS: &str = "id, created_by, subject, statement, nonce, callback_url, status, expires_at, \
+     fulfilled_by, fulfilled_at, callback_status, callback_attempts, callback_error, created_at";
+
+/// Persists partner proof requests in `proof_requests`.
+#[derive(Clone)]
+pub struct ProofRequestStore {
+    db: Pool<Postgres>,
+}
+
+impl ProofRequestStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        created_by: &str,
+        subject: &str,
+        statement: &ProofStatement,
+        nonce: &str,
+        callback_url: &str,
+        callback_secret: &str,
+        expires_at: OffsetDateTime,
+    ) -> Result<ProofRequest> {
+        let request = sqlx::query_as::<_, ProofRequest>(&format!(
+            r#"
+            INSERT INTO proof_requests
+                (created_by, subject, statement, nonce, callback_url, callback_secret, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {}
+            "#,
+            REQUEST_COLUMNS
+        ))
+        .bind(created_by)
+        .bind(subject)
+        .bind(Json(statement))
+        .bind(nonce)
+        .bind(callback_url)
+        .bind(callback_secret)
+        .bind(expires_at)
+        .fetch_one(&self.db)
+        .await?;
+        Ok(request)
+    }
+
+    pub async fn find(&self, id: Uuid) -> Result<Option<ProofRequest>> {
+        let request = sqlx::query_as::<_, ProofRequest>(&format!(
+            "SELECT {} FROM proof_requests WHERE id = $1",
+            REQUEST_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(request)
+    }
+
+    /// Proof `proof_id` with its owner, the score it proves and the public
+    /// signals it was verified with.
+    pub async fn proof_of_score(&self, proof_id: Uuid) -> Result<Option<ProofOfScore>> {
+        let proof = sqlx::query_as::<_, ProofOfScore>(
+            r#"
+            SELECT z.id AS proof_id,
+                   u.wallet_address AS owner_address,
+                   z.verified,
+                   NOT EXISTS (
+                       SELECT 1
+                       FROM scoring_results later
+                       JOIN behavior_inputs lb ON lb.id = later.behavior_input_id
+                       WHERE lb.user_id = b.user_id AND later.timestamp > s.timestamp
+                   ) AS is_latest,
+                   (s.score * 100)::BIGINT AS score_cents,
+                   COALESCE(z.proof_sha256, encode(sha256(z.proof_data), 'hex')) AS proof_sha256,
+                   z.verification_key_id,
+                   z.public_signals
+            FROM zkml_proofs z
+            JOIN scoring_results s ON s.id = z.scoring_result_id
+            JOIN behavior_inputs b ON b.id = s.behavior_input_id
+            LEFT JOIN users u ON u.id = b.user_id
+            WHERE z.id = $1
+            "#,
+        )
+        .bind(proof_id)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(proof)
+    }
+
+    /// Fulfil an open, unexpired request and queue its callback. `None` if
+    /// the request is no longer open.
+    pub async fn fulfil(
+        &self,
+        id: Uuid,
+        fulfilled_by: &str,
+        proof_id: Uuid,
+        fulfilled_at: OffsetDateTime,
+        response: &Value,
+    ) -> Result<Option<ProofRequest>> {
+        let request = sqlx::query_as::<_, ProofRequest>(&format!(
+            r#"
+            UPDATE proof_requests
+            SET status = 'fulfilled',
+                fulfilled_by = $2,
+                proof_id = $3,
+                fulfilled_at = $4,
+                response = $5,
+                callback_status = 'pending'
+            WHERE id = $1 AND status = 'open' AND expires_at > NOW()
+            RETURNING {}
+            "#,
+            REQUEST_COLUMNS
+        ))
+        .bind(id)
+        .bind(fulfilled_by)
+        .bind(proof_id)
+        .bind(fulfilled_at)
+        .bind(response)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(request)
+    }
+
+    /// Claim up to `limit` callbacks due for delivery. Each is held back
+    /// `backoff`, doubled per earlier attempt, so a failed attempt is
+    /// retried after that long. Callbacks that used up `max_attempts` are
+    /// left for `record_callback_failure` to have failed.
+    pub async fn claim_due_callbacks(
+        &self,
+        limit: i64,
+        backoff: Duration,
+        max_attempts: i32,
+    ) -> Result<Vec<DueCallback>> {
+        let due = sqlx::query_as::<_, DueCallback>(
+            r#"
+            UPDATE proof_requests r
+            SET callback_attempts = r.callback_attempts + 1,
+                callback_retry_at = NOW() + make_interval(secs => $2 * POWER(2, r.callback_attempts))
+            FROM (
+                SELECT id
+                FROM proof_requests
+                WHERE callback_status = 'pending'
+                  AND callback_attempts < $3
+                  AND (callback_retry_at IS NULL OR callback_retry_at <= NOW())
+                ORDER BY fulfilled_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            ) due
+            WHERE r.id = due.id
+            RETURNING r.id, r.callback_url, r.callback_secret, r.response, r.callback_attempts
+            "#,
+        )
+        .bind(limit)
+        .bind(backoff.as_secs_f64())
+        .bind(max_attempts)
+        .fetch_all(&self.db)
+        .await?;
+        Ok(due)
+    }
+
+    pub async fn record_delivered(&self, id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE proof_requests
+            SET callback_status = 'delivered',
+                callback_error = NULL,
+                callback_retry_at = NULL,
+                delivered_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    /// Note a failed delivery; with `give_up` the callback is failed for good.
+    pub async fn record_callback_failure(&self, id: Uuid, error: &str, give_up: bool) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE proof_requests
+            SET callback_error = $2,
+                callback_status = CASE WHEN $3 THEN 'failed' ELSE callback_status END
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(give_up)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+}
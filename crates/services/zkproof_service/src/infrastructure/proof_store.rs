@@ -0,0 +1,102 @@
+use jd_messaging::{events::ProofVerified, outbox};
+use sqlx::{Pool, Postgres};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::artifact_store_trait::ProofArtifacts;
+use crate::domain::proof_verification::{StoredProof, VerifierRun};
+use crate::Result;
+
+const PROOF_COLUMNS: &str = "id, proof_data, verification_key, proof_uri, proof_sha256, \
+     verification_key_uri, verification_key_sha256, verification_key_id, public_signals, prover_backend";
+
+/// Verification and artifact storage state of proofs in `zkml_proofs`.
+#[derive(Clone)]
+pub struct ProofStore {
+    db: Pool<Postgres>,
+}
+
+impl ProofStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    pub async fn find_proof(&self, id: Uuid) -> Result<Option<StoredProof>> {
+        let proof = sqlx::query_as::<_, StoredProof>(&format!("SELECT {} FROM zkml_proofs WHERE id = $1", PROOF_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.db)
+            .await?;
+        Ok(proof)
+    }
+
+    /// Keep a verifier run with the proof. `verified` follows the run, so a
+    /// proof that fails verification is no longer counted as verified. A
+    /// passing run also records a `proof.verified` event.
+    pub async fn record_verification(&self, id: Uuid, run: &VerifierRun) -> Result<OffsetDateTime> {
+        let mut tx = self.db.begin().await?;
+        let checked_at = sqlx::query_scalar::<_, OffsetDateTime>(
+            r#"
+            UPDATE zkml_proofs
+            SET verified = $2,
+                verifier_version = $3,
+                verification_ms = $4,
+                verification_error = $5,
+                verification_checked_at = NOW()
+            WHERE id = $1
+            RETURNING verification_checked_at
+            "#,
+        )
+        .bind(id)
+        .bind(run.valid)
+        .bind(&run.verifier_version)
+        .bind(run.duration_ms.clamp(0, i32::MAX as i64) as i32)
+        .bind(&run.error)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if run.valid {
+            let event = ProofVerified { proof_id: id, verifier_version: run.verifier_version.clone() };
+            outbox::record(&mut *tx, &event).await?;
+        }
+        tx.commit().await?;
+        Ok(checked_at)
+    }
+
+    /// Up to `limit` proofs whose data is still kept inline, oldest first.
+    pub async fn inline_proofs(&self, limit: i64) -> Result<Vec<StoredProof>> {
+        let proofs = sqlx::query_as::<_, StoredProof>(&format!(
+            "SELECT {} FROM zkml_proofs WHERE proof_uri IS NULL ORDER BY timestamp LIMIT $1",
+            PROOF_COLUMNS
+        ))
+        .bind(limit)
+        .fetch_all(&self.db)
+        .await?;
+        Ok(proofs)
+    }
+
+    /// Point a proof at its artifacts in object storage and drop the inline
+    /// copies. A proof with a registered key only has its data offloaded.
+    pub async fn record_offloaded(&self, id: Uuid, artifacts: &ProofArtifacts) -> Result<()> {
+        let verification_key = artifacts.verification_key.as_ref();
+        sqlx::query(
+            r#"
+            UPDATE zkml_proofs
+            SET proof_uri = $2,
+                proof_sha256 = $3,
+                verification_key_uri = COALESCE($4, verification_key_uri),
+                verification_key_sha256 = COALESCE($5, verification_key_sha256),
+                proof_data = NULL,
+                verification_key = CASE WHEN $4::TEXT IS NULL THEN verification_key END
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(&artifacts.proof.uri)
+        .bind(&artifacts.proof.sha256)
+        .bind(verification_key.map(|stored| &stored.uri))
+        .bind(verification_key.map(|stored| &stored.sha256))
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+}
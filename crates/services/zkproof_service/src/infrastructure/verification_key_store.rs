@@ -0,0 +1,151 @@
+use sqlx::{Pool, Postgres};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::domain::verification_key::{NewVerificationKey, VerificationKey};
+use crate::{Error, Result};
+
+const KEY_COLUMNS: &str = "id, model_version, circuit_version, prover_backend, key_sha256, \
+     active_from, active_until, published_by, created_at";
+
+/// The verification key registry in `verification_keys`.
+#[derive(Clone)]
+pub struct VerificationKeyStore {
+    db: Pool<Postgres>,
+}
+
+impl VerificationKeyStore {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(&self, id: Uuid) -> Result<Option<VerificationKey>> {
+        let key = sqlx::query_as::<_, VerificationKey>(&format!(
+            "SELECT {} FROM verification_keys WHERE id = $1",
+            KEY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(key)
+    }
+
+    pub async fn key_bytes(&self, id: Uuid) -> Result<Option<Vec<u8>>> {
+        let bytes = sqlx::query_scalar::<_, Vec<u8>>("SELECT verification_key FROM verification_keys WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.db)
+            .await?;
+        Ok(bytes)
+    }
+
+    /// Published keys, newest activation first, optionally of one model.
+    pub async fn list(&self, model_version: Option<&str>) -> Result<Vec<VerificationKey>> {
+        let keys = sqlx::query_as::<_, VerificationKey>(&format!(
+            r#"
+            SELECT {} FROM verification_keys
+            WHERE $1::VARCHAR IS NULL OR model_version = $1
+            ORDER BY model_version, active_from DESC
+            "#,
+            KEY_COLUMNS
+        ))
+        .bind(model_version)
+        .fetch_all(&self.db)
+        .await?;
+        Ok(keys)
+    }
+
+    /// The key new proofs of `model_version` by `prover_backend` use now.
+    pub async fn active(&self, prover_backend: &str, model_version: &str) -> Result<Option<VerificationKey>> {
+        let key = sqlx::query_as::<_, VerificationKey>(&format!(
+            r#"
+            SELECT {} FROM verification_keys
+            WHERE prover_backend = $1
+              AND model_version = $2
+              AND active_from <= NOW()
+              AND (active_until IS NULL OR active_until > NOW())
+            ORDER BY active_from DESC
+            LIMIT 1
+            "#,
+            KEY_COLUMNS
+        ))
+        .bind(prover_backend)
+        .bind(model_version)
+        .fetch_optional(&self.db)
+        .await?;
+        Ok(key)
+    }
+
+    /// Publish a key, ending the window of the model's current key where
+    /// the new one starts. Keys of a model are published in activation
+    /// order, so windows never overlap.
+    pub async fn publish(&self, key: &NewVerificationKey, key_sha256: &str) -> Result<VerificationKey> {
+        let active_from = key.active_from.unwrap_or_else(OffsetDateTime::now_utc);
+        let mut tx = self.db.begin().await?;
+
+        let latest = sqlx::query_scalar::<_, OffsetDateTime>(
+            r#"
+            SELECT active_from FROM verification_keys
+            WHERE prover_backend = $1 AND model_version = $2
+            ORDER BY active_from DESC
+            LIMIT 1
+            FOR UPDATE
+            "#,
+        )
+        .bind(&key.prover_backend)
+        .bind(&key.model_version)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if latest.is_some_and(|latest| latest >= active_from) {
+            return Err(Error::InvalidInput(format!(
+                "A key for model {} already activates at or after {}",
+                key.model_version, active_from
+            )));
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE verification_keys
+            SET active_until = $3
+            WHERE prover_backend = $1
+              AND model_version = $2
+              AND (active_until IS NULL OR active_until > $3)
+            "#,
+        )
+        .bind(&key.prover_backend)
+        .bind(&key.model_version)
+        .bind(active_from)
+        .execute(&mut *tx)
+        .await?;
+
+        let published = sqlx::query_as::<_, VerificationKey>(&format!(
+            r#"
+            INSERT INTO verification_keys
+                (model_version, circuit_version, prover_backend, verification_key, key_sha256,
+                 active_from, active_until, published_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (model_version, circuit_version) DO NOTHING
+            RETURNING {}
+            "#,
+            KEY_COLUMNS
+        ))
+        .bind(&key.model_version)
+        .bind(&key.circuit_version)
+        .bind(&key.prover_backend)
+        .bind(&key.verification_key)
+        .bind(key_sha256)
+        .bind(active_from)
+        .bind(key.active_until)
+        .bind(&key.published_by)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "Circuit version {} of model {} is already published",
+                key.circuit_version, key.model_version
+            ))
+        })?;
+
+        tx.commit().await?;
+        Ok(published)
+    }
+}
@@ -13,8 +13,8 @@ use jd_domain::Id;
 pub struct ZkProofRecord {
     pub id: uuid::Uuid,
     pub scoring_result_id: uuid::Uuid,
-    pub proof_data: Vec<u8>, // Binary data stored as bytea
-    pub verification_key: Vec<u8>, // Binary data stored as bytea
+    pub proof_data: Option<Vec<u8>>, // Binary data stored as bytea; None once offloaded to object storage
+    pub verification_key: Option<Vec<u8>>, // Binary data stored as bytea; None once offloaded to object storage
     pub verified: bool,
     pub blockchain_tx_hash: Option<String>,
     pub timestamp: OffsetDateTime,
@@ -45,10 +45,10 @@ pub struct ZkProofFilter {
 impl From<ZkProofRecord> for responses::ZkProofResponse {
     fn from(record: ZkProofRecord) -> Self {
         Self {
-            id: Id::new(record.id.to_string()),
-            scoring_result_id: Id::new(record.scoring_result_id.to_string()),
-            proof_data: String::from_utf8_lossy(&record.proof_data).to_string(),
-            verification_key: String::from_utf8_lossy(&record.verification_key).to_string(),
+            id: Id::from(record.id),
+            scoring_result_id: Id::from(record.scoring_result_id),
+            proof_data: String::from_utf8_lossy(record.proof_data.as_deref().unwrap_or_default()).to_string(),
+            verification_key: String::from_utf8_lossy(record.verification_key.as_deref().unwrap_or_default()).to_string(),
             verified: record.verified,
             blockchain_tx_hash: record.blockchain_tx_hash,
             timestamp: record.timestamp,
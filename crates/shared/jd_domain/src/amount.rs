@@ -0,0 +1,313 @@
+//! Checked amounts of SUI and other coins. Both serialize as strings of base
+//! units, since JSON numbers lose precision past 2^53, and accept numbers
+//! too when deserializing.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::{Error, Result};
+
+/// Decimal places of SUI: 1 SUI is 10^9 MIST.
+pub const SUI_DECIMALS: u8 = 9;
+pub const MIST_PER_SUI: u64 = 1_000_000_000;
+
+/// An amount of SUI, held in MIST, e.g. a gas budget, gas price or balance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SuiAmount(u64);
+
+impl SuiAmount {
+  pub const ZERO: Self = Self(0);
+  pub const MAX: Self = Self(u64::MAX);
+
+  pub const fn from_mist(mist: u64) -> Self {
+    Self(mist)
+  }
+
+  /// None if `sui` whole SUI overflow u64 MIST.
+  pub fn from_sui(sui: u64) -> Option<Self> {
+    sui.checked_mul(MIST_PER_SUI).map(Self)
+  }
+
+  pub const fn mist(self) -> u64 {
+    self.0
+  }
+
+  /// Parse a decimal amount of SUI, e.g. `"1.5"`.
+  pub fn from_decimal(s: &str) -> Result<Self> {
+    let mist = parse_units(s, SUI_DECIMALS)?;
+    u64::try_from(mist).map(Self).map_err(|_| invalid(s, "exceeds the largest SUI amount"))
+  }
+
+  /// The amount in SUI, e.g. `"1.5"`, without trailing zeros.
+  pub fn to_decimal(self) -> String {
+    format_units(self.0.into(), SUI_DECIMALS)
+  }
+
+  pub fn checked_add(self, other: Self) -> Option<Self> {
+    self.0.checked_add(other.0).map(Self)
+  }
+
+  pub fn checked_sub(self, other: Self) -> Option<Self> {
+    self.0.checked_sub(other.0).map(Self)
+  }
+
+  /// E.g. a gas price times gas units.
+  pub fn checked_mul(self, factor: u64) -> Option<Self> {
+    self.0.checked_mul(factor).map(Self)
+  }
+
+  pub fn saturating_add(self, other: Self) -> Self {
+    Self(self.0.saturating_add(other.0))
+  }
+
+  pub fn saturating_sub(self, other: Self) -> Self {
+    Self(self.0.saturating_sub(other.0))
+  }
+
+  pub fn saturating_mul(self, factor: u64) -> Self {
+    Self(self.0.saturating_mul(factor))
+  }
+}
+
+impl From<u64> for SuiAmount {
+  fn from(mist: u64) -> Self {
+    Self(mist)
+  }
+}
+
+impl From<SuiAmount> for u64 {
+  fn from(amount: SuiAmount) -> Self {
+    amount.0
+  }
+}
+
+/// MIST, as stored in BIGINT columns; negative values are rejected.
+impl TryFrom<i64> for SuiAmount {
+  type Error = Error;
+
+  fn try_from(mist: i64) -> Result<Self> {
+    u64::try_from(mist).map(Self).map_err(|_| invalid(&mist.to_string(), "must not be negative"))
+  }
+}
+
+/// `"1.5 SUI"`.
+impl fmt::Display for SuiAmount {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} SUI", self.to_decimal())
+  }
+}
+
+/// Parses MIST, e.g. `"1500000000"`; see `from_decimal` for SUI.
+impl FromStr for SuiAmount {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    s.trim().parse().map(Self).map_err(|e: std::num::ParseIntError| invalid(s, &e.to_string()))
+  }
+}
+
+impl Serialize for SuiAmount {
+  fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(&self.0)
+  }
+}
+
+impl<'de> Deserialize<'de> for SuiAmount {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+    let raw = u128::from(deserialize_units(deserializer)?);
+    u64::try_from(raw).map(Self).map_err(|_| de::Error::custom("SUI amount exceeds u64 MIST"))
+  }
+}
+
+impl sqlx::Type<sqlx::Postgres> for SuiAmount {
+  fn type_info() -> sqlx::postgres::PgTypeInfo {
+    <i64 as sqlx::Type<sqlx::Postgres>>::type_info()
+  }
+}
+
+impl<'r> sqlx::Decode<'r, sqlx::Postgres> for SuiAmount {
+  fn decode(
+    value: sqlx::postgres::PgValueRef<'r>,
+  ) -> std::result::Result<Self, sqlx::error::BoxDynError> {
+    let mist = <i64 as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+    Ok(Self::try_from(mist)?)
+  }
+}
+
+impl<'r> sqlx::Encode<'r, sqlx::Postgres> for SuiAmount {
+  fn encode_by_ref(
+    &self,
+    buf: &mut sqlx::postgres::PgArgumentBuffer,
+  ) -> std::result::Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+    let mist = i64::try_from(self.0).map_err(|_| invalid(&self.0.to_string(), "exceeds BIGINT"))?;
+    <i64 as sqlx::Encode<sqlx::Postgres>>::encode_by_ref(&mist, buf)
+  }
+}
+
+/// An amount of any coin in its base units. Coins carry their own number of
+/// decimals, so formatting takes it from the coin's metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount(u128);
+
+impl TokenAmount {
+  pub const ZERO: Self = Self(0);
+
+  pub const fn from_base_units(units: u128) -> Self {
+    Self(units)
+  }
+
+  pub const fn base_units(self) -> u128 {
+    self.0
+  }
+
+  /// Parse a decimal amount of a coin with `decimals` places, e.g. `"2.25"`.
+  pub fn from_decimal(s: &str, decimals: u8) -> Result<Self> {
+    parse_units(s, decimals).map(Self)
+  }
+
+  pub fn to_decimal(self, decimals: u8) -> String {
+    format_units(self.0, decimals)
+  }
+
+  pub fn checked_add(self, other: Self) -> Option<Self> {
+    self.0.checked_add(other.0).map(Self)
+  }
+
+  pub fn checked_sub(self, other: Self) -> Option<Self> {
+    self.0.checked_sub(other.0).map(Self)
+  }
+
+  pub fn saturating_add(self, other: Self) -> Self {
+    Self(self.0.saturating_add(other.0))
+  }
+
+  pub fn saturating_sub(self, other: Self) -> Self {
+    Self(self.0.saturating_sub(other.0))
+  }
+}
+
+impl From<u128> for TokenAmount {
+  fn from(units: u128) -> Self {
+    Self(units)
+  }
+}
+
+impl From<u64> for TokenAmount {
+  fn from(units: u64) -> Self {
+    Self(units.into())
+  }
+}
+
+impl From<SuiAmount> for TokenAmount {
+  fn from(amount: SuiAmount) -> Self {
+    Self(amount.0.into())
+  }
+}
+
+/// Base units, as the coin's decimals are not known here.
+impl fmt::Display for TokenAmount {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl FromStr for TokenAmount {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    s.trim().parse().map(Self).map_err(|e: std::num::ParseIntError| invalid(s, &e.to_string()))
+  }
+}
+
+impl Serialize for TokenAmount {
+  fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(&self.0)
+  }
+}
+
+impl<'de> Deserialize<'de> for TokenAmount {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+    deserialize_units(deserializer).map(Self)
+  }
+}
+
+fn invalid(value: &str, reason: &str) -> Error {
+  Error::InvalidAmount { value: value.to_string(), reason: reason.to_string() }
+}
+
+/// Base units from a string of digits or a non-negative JSON integer.
+fn deserialize_units<'de, D: Deserializer<'de>>(
+  deserializer: D,
+) -> std::result::Result<u128, D::Error> {
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Units {
+    Number(u128),
+    Text(String),
+  }
+
+  match Units::deserialize(deserializer)? {
+    Units::Number(units) => Ok(units),
+    Units::Text(text) => text.trim().parse().map_err(|_| {
+      de::Error::custom(format!("invalid amount '{text}': expected a whole number of base units"))
+    }),
+  }
+}
+
+fn format_units(units: u128, decimals: u8) -> String {
+  let scale = 10u128.pow(decimals.into());
+  let (whole, fraction) = (units / scale, units % scale);
+  if fraction == 0 {
+    return whole.to_string();
+  }
+  let fraction = format!("{fraction:0width$}", width = usize::from(decimals));
+  format!("{whole}.{}", fraction.trim_end_matches('0'))
+}
+
+fn parse_units(s: &str, decimals: u8) -> Result<u128> {
+  let text = s.trim();
+  let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+  let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+  if (whole.is_empty() && fraction.is_empty()) || !digits(whole) || !digits(fraction) {
+    return Err(invalid(s, "expected a non-negative decimal number"));
+  }
+  if fraction.len() > usize::from(decimals) {
+    return Err(invalid(s, &format!("has more than {decimals} decimal places")));
+  }
+  let scale = 10u128.pow(decimals.into());
+  let parse = |part: &str| part.parse::<u128>().map_err(|_| invalid(s, "too large"));
+  let whole = if whole.is_empty() { 0 } else { parse(whole)? };
+  let fraction = if fraction.is_empty() {
+    0
+  } else {
+    parse(&format!("{fraction:0<width$}", width = usize::from(decimals)))?
+  };
+  whole
+    .checked_mul(scale)
+    .and_then(|units| units.checked_add(fraction))
+    .ok_or_else(|| invalid(s, "too large"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sui_amounts_format_parse_and_serialize_as_strings() {
+    let amount = SuiAmount::from_decimal("1.5").unwrap();
+    assert_eq!(amount.mist(), 1_500_000_000);
+    assert_eq!(amount.to_string(), "1.5 SUI");
+    assert_eq!(SuiAmount::from_mist(1).to_decimal(), "0.000000001");
+    assert!(SuiAmount::from_decimal("0.0000000001").is_err());
+    assert!(SuiAmount::from_decimal("-1").is_err());
+
+    assert_eq!(serde_json::to_string(&amount).unwrap(), "\"1500000000\"");
+    let parsed: SuiAmount = serde_json::from_str("\"2000\"").unwrap();
+    assert_eq!(parsed, serde_json::from_str::<SuiAmount>("2000").unwrap());
+
+    assert_eq!(SuiAmount::MAX.checked_add(SuiAmount::from_mist(1)), None);
+    assert_eq!(SuiAmount::ZERO.saturating_sub(amount), SuiAmount::ZERO);
+    assert!(SuiAmount::try_from(-5i64).is_err());
+  }
+}
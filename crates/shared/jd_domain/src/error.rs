@@ -11,12 +11,20 @@ pub enum Error {
   #[from]
   ValidationErrors(#[serde_as(as = "DisplayFromStr")] ValidationErrors),
   
+  InvalidId { value: String, reason: String },
+
+  InvalidAmount { value: String, reason: String },
+
   Generic(String),
 }
 
 impl Display for Error {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{self:?}")
+    match self {
+      Self::InvalidId { value, reason } => write!(f, "Invalid id '{value}': {reason}"),
+      Self::InvalidAmount { value, reason } => write!(f, "Invalid amount '{value}': {reason}"),
+      _ => write!(f, "{self:?}"),
+    }
   }
 }
 
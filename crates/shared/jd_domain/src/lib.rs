@@ -1,11 +1,15 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-use sea_query::Value;
+use sea_query::{Nullable, Value};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+mod amount;
 mod error;
 mod utils;
 
+pub use amount::{MIST_PER_SUI, SUI_DECIMALS, SuiAmount, TokenAmount};
+pub use error::Error;
+
 pub mod zkpersona_domain;
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -14,10 +18,6 @@ pub type Result<T> = std::result::Result<T, error::Error>;
 pub struct Id(Uuid);
 
 impl Id {
-  pub fn new(id: String) -> Self {
-    Self(Uuid::parse_str(&id).unwrap_or_else(|_| Uuid::new_v4()))
-  }
-
   pub fn generate() -> Self {
     Self(Uuid::new_v4())
   }
@@ -26,12 +26,6 @@ impl Id {
     &self.0
   }
 
-  pub fn from_str(s: &str) -> Result<Self> {
-    Uuid::parse_str(s)
-      .map(Self)
-      .map_err(|e| error::Error::Generic(format!("Invalid UUID: {}", e)))
-  }
-
   pub fn to_uuid(&self) -> Uuid {
     self.0
   }
@@ -43,6 +37,34 @@ impl Display for Id {
   }
 }
 
+/// Parsing never falls back to a fresh id: a malformed id is an
+/// `Error::InvalidId`.
+impl FromStr for Id {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    Uuid::parse_str(s)
+      .map(Self)
+      .map_err(|e| Error::InvalidId { value: s.to_string(), reason: e.to_string() })
+  }
+}
+
+impl TryFrom<&str> for Id {
+  type Error = Error;
+
+  fn try_from(s: &str) -> Result<Self> {
+    s.parse()
+  }
+}
+
+impl TryFrom<String> for Id {
+  type Error = Error;
+
+  fn try_from(s: String) -> Result<Self> {
+    s.parse()
+  }
+}
+
 // sea-query implementations
 impl From<Id> for Value {
   fn from(id: Id) -> Self {
@@ -50,13 +72,21 @@ impl From<Id> for Value {
   }
 }
 
+/// Lets `Option<Id>` bind as a nullable uuid.
+impl Nullable for Id {
+  fn null() -> Value {
+    Value::Uuid(None)
+  }
+}
+
 impl From<Uuid> for Id {
   fn from(uuid: Uuid) -> Self {
     Self(uuid)
   }
 }
 
-// SQLx implementations
+// SQLx implementations. A NULL column decodes through `Option<Id>`; decoding
+// it as `Id` fails with sqlx's `UnexpectedNullError` instead of inventing one.
 impl sqlx::Type<sqlx::Postgres> for Id {
   fn type_info() -> sqlx::postgres::PgTypeInfo {
     <Uuid as sqlx::Type<sqlx::Postgres>>::type_info()
@@ -80,3 +110,16 @@ impl<'r> sqlx::Encode<'r, sqlx::Postgres> for Id {
     <Uuid as sqlx::Encode<sqlx::Postgres>>::encode_by_ref(&self.0, buf)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parsing_rejects_malformed_ids() {
+    let uuid = Uuid::new_v4();
+    assert_eq!(Id::try_from(uuid.to_string().as_str()).unwrap().to_uuid(), uuid);
+    assert!(matches!("not-a-uuid".parse::<Id>(), Err(Error::InvalidId { .. })));
+    assert!(Id::try_from(String::new()).is_err());
+  }
+}
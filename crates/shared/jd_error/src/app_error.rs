@@ -0,0 +1,180 @@
+use axum::{
+  http::StatusCode,
+  response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::ErrorKind;
+
+/// Raw pieces produced by `#[derive(ErrorTaxonomy)]` for a single variant.
+pub struct ErrorParts {
+  pub kind: ErrorKind,
+  pub code: &'static str,
+  pub service: Option<&'static str>,
+  pub internal_message: String,
+  pub user_message: Option<String>,
+  pub retryable: Option<bool>,
+  pub retry_after_secs: Option<u64>,
+}
+
+/// A variant field that `#[taxonomy(retry_after = field)]` reads the
+/// `Retry-After` hint from. `None` keeps the kind's default.
+pub trait RetryAfterSecs {
+  fn retry_after_secs(&self) -> Option<u64>;
+}
+
+impl RetryAfterSecs for u64 {
+  fn retry_after_secs(&self) -> Option<u64> {
+    Some(*self)
+  }
+}
+
+impl RetryAfterSecs for u32 {
+  fn retry_after_secs(&self) -> Option<u64> {
+    Some(u64::from(*self))
+  }
+}
+
+impl<T: RetryAfterSecs> RetryAfterSecs for Option<T> {
+  fn retry_after_secs(&self) -> Option<u64> {
+    self.as_ref().and_then(RetryAfterSecs::retry_after_secs)
+  }
+}
+
+/// Service-agnostic error that every service `Error` converts into.
+///
+/// `internal_message` keeps the original `Display` output for logs and is never
+/// serialized; clients only ever see `user_message`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppError {
+  kind: ErrorKind,
+  code: String,
+  service: Option<String>,
+  #[serde(skip)]
+  internal_message: String,
+  user_message: String,
+  retryable: bool,
+  retry_after_secs: Option<u64>,
+  details: Option<serde_json::Value>,
+}
+
+// Constructors.
+impl AppError {
+  pub fn new(kind: ErrorKind, code: impl Into<String>, internal_message: impl Into<String>) -> Self {
+    Self {
+      kind,
+      code: code.into(),
+      service: None,
+      internal_message: internal_message.into(),
+      user_message: kind.default_user_message().to_string(),
+      retryable: kind.is_retryable(),
+      retry_after_secs: kind.default_retry_after_secs(),
+      details: None,
+    }
+  }
+
+  pub fn from_parts(parts: ErrorParts) -> Self {
+    let mut err = Self::new(parts.kind, parts.code, parts.internal_message);
+    err.service = parts.service.map(String::from);
+    if let Some(user_message) = parts.user_message {
+      err.user_message = user_message;
+    }
+    if let Some(retryable) = parts.retryable {
+      err.retryable = retryable;
+    }
+    if parts.retry_after_secs.is_some() {
+      err.retry_after_secs = parts.retry_after_secs;
+    }
+    err
+  }
+
+  pub fn with_service(mut self, service: impl Into<String>) -> Self {
+    self.service = Some(service.into());
+    self
+  }
+
+  pub fn with_user_message(mut self, user_message: impl Into<String>) -> Self {
+    self.user_message = user_message.into();
+    self
+  }
+
+  pub fn with_details(mut self, details: serde_json::Value) -> Self {
+    self.details = Some(details);
+    self
+  }
+
+  pub fn with_retry_after(mut self, secs: u64) -> Self {
+    self.retry_after_secs = Some(secs);
+    self
+  }
+}
+
+// Property Accessors.
+impl AppError {
+  pub fn kind(&self) -> ErrorKind {
+    self.kind
+  }
+
+  pub fn code(&self) -> &str {
+    &self.code
+  }
+
+  pub fn service(&self) -> Option<&str> {
+    self.service.as_deref()
+  }
+
+  pub fn internal_message(&self) -> &str {
+    &self.internal_message
+  }
+
+  pub fn user_message(&self) -> &str {
+    &self.user_message
+  }
+
+  pub fn details(&self) -> Option<&serde_json::Value> {
+    self.details.as_ref()
+  }
+
+  pub fn is_retryable(&self) -> bool {
+    self.retryable
+  }
+
+  pub fn retry_after_secs(&self) -> Option<u64> {
+    self.retry_after_secs
+  }
+
+  pub fn status_code(&self) -> StatusCode {
+    self.kind.status_code()
+  }
+}
+
+impl std::fmt::Display for AppError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self.service {
+      Some(service) => write!(f, "[{}] {}: {}", service, self.code, self.internal_message),
+      None => write!(f, "{}: {}", self.code, self.internal_message),
+    }
+  }
+}
+
+impl std::error::Error for AppError {}
+
+impl IntoResponse for AppError {
+  fn into_response(self) -> Response {
+    let body = json!({
+      "error_code": self.code,
+      "message": self.user_message,
+      "details": self.details,
+      "retryable": self.retryable,
+    });
+
+    let mut response = (self.status_code(), Json(body)).into_response();
+    if let Some(retry_after) = self.retry_after_secs {
+      if let Ok(value) = retry_after.to_string().parse() {
+        response.headers_mut().insert("Retry-After", value);
+      }
+    }
+    response
+  }
+}
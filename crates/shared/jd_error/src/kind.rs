@@ -0,0 +1,85 @@
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// Coarse classification shared by every service error.
+///
+/// The kind drives the HTTP status, the default retry policy and the message
+/// shown to clients when a variant does not provide its own.
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, strum_macros::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ErrorKind {
+  Validation,
+  Unauthenticated,
+  PermissionDenied,
+  NotFound,
+  Conflict,
+  RateLimited,
+  PayloadTooLarge,
+  Unavailable,
+  Timeout,
+  Upstream,
+  NotImplemented,
+  Internal,
+}
+
+impl ErrorKind {
+  pub fn status_code(&self) -> StatusCode {
+    match self {
+      Self::Validation => StatusCode::BAD_REQUEST,
+      Self::Unauthenticated => StatusCode::UNAUTHORIZED,
+      Self::PermissionDenied => StatusCode::FORBIDDEN,
+      Self::NotFound => StatusCode::NOT_FOUND,
+      Self::Conflict => StatusCode::CONFLICT,
+      Self::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+      Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+      Self::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+      Self::Timeout => StatusCode::GATEWAY_TIMEOUT,
+      Self::Upstream => StatusCode::BAD_GATEWAY,
+      Self::NotImplemented => StatusCode::NOT_IMPLEMENTED,
+      Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+  }
+
+  /// Whether a caller may retry the same request unchanged.
+  pub fn is_retryable(&self) -> bool {
+    matches!(self, Self::RateLimited | Self::Unavailable | Self::Timeout | Self::Upstream)
+  }
+
+  pub fn is_client_error(&self) -> bool {
+    self.status_code().is_client_error()
+  }
+
+  pub fn is_server_error(&self) -> bool {
+    self.status_code().is_server_error()
+  }
+
+  /// Message safe to return to clients when the variant does not define one.
+  pub fn default_user_message(&self) -> &'static str {
+    match self {
+      Self::Validation => "Invalid request",
+      Self::Unauthenticated => "Authentication required",
+      Self::PermissionDenied => "Access denied",
+      Self::NotFound => "Resource not found",
+      Self::Conflict => "Resource conflict",
+      Self::RateLimited => "Rate limit exceeded",
+      Self::PayloadTooLarge => "Request payload too large",
+      Self::Unavailable => "Service temporarily unavailable",
+      Self::Timeout => "Service request timeout",
+      Self::Upstream => "Downstream service error",
+      Self::NotImplemented => "Not implemented",
+      Self::Internal => "Internal server error",
+    }
+  }
+
+  /// Suggested `Retry-After` value when the variant does not define one.
+  pub fn default_retry_after_secs(&self) -> Option<u64> {
+    match self {
+      Self::RateLimited => Some(60),
+      Self::Unavailable => Some(30),
+      _ => None,
+    }
+  }
+}
@@ -0,0 +1,72 @@
+//! Workspace-wide error taxonomy.
+//!
+//! Every service keeps its own `Error` enum and derives [`ErrorTaxonomy`] to
+//! generate `From<Error> for AppError`. The gateway then only needs a single
+//! mapping from [`AppError`] to an HTTP response.
+//!
+//! ```ignore
+//! #[derive(Debug, thiserror::Error, jd_error::ErrorTaxonomy)]
+//! #[taxonomy(service = "github")]
+//! pub enum Error {
+//!   #[error("Repository not found: {owner}/{repo}")]
+//!   #[taxonomy(kind = NotFound, expose)]
+//!   RepositoryNotFound { owner: String, repo: String },
+//!
+//!   #[error("Rate limited, retry after {retry_after_seconds}s")]
+//!   #[taxonomy(kind = RateLimited, retry_after = retry_after_seconds)]
+//!   RateLimited { retry_after_seconds: u64 },
+//!
+//!   #[error("Queue is full")]
+//!   #[taxonomy(kind = Unavailable, retry_after = 30)]
+//!   QueueFull,
+//! }
+//! ```
+//!
+//! Variant options: `kind` (required), `code` (defaults to the variant name in
+//! SCREAMING_SNAKE_CASE), `message` (static user message), `expose` (use the
+//! `Display` output as the user message), `retryable` / `retryable = false`
+//! and `retry_after = <secs>`, or `retry_after = <field>` to read it from a
+//! named field of the variant (any [`RetryAfterSecs`] type, e.g. `u64` or
+//! `Option<u64>`).
+
+mod app_error;
+mod kind;
+
+pub use app_error::{AppError, ErrorParts, RetryAfterSecs};
+pub use jd_error_derive::ErrorTaxonomy;
+pub use kind::ErrorKind;
+
+pub type Result<T> = std::result::Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_kind_defaults() {
+    let err = AppError::new(ErrorKind::RateLimited, "RATE_LIMITED", "too many calls");
+
+    assert_eq!(err.status_code().as_u16(), 429);
+    assert!(err.is_retryable());
+    assert_eq!(err.retry_after_secs(), Some(60));
+    assert_eq!(err.user_message(), "Rate limit exceeded");
+  }
+
+  #[test]
+  fn test_from_parts_overrides() {
+    let err = AppError::from_parts(ErrorParts {
+      kind: ErrorKind::Upstream,
+      code: "GIT_HUB_API",
+      service: Some("github"),
+      internal_message: "502 from api.github.com".to_string(),
+      user_message: Some("GitHub is unavailable".to_string()),
+      retryable: Some(false),
+      retry_after_secs: None,
+    });
+
+    assert_eq!(err.service(), Some("github"));
+    assert!(!err.is_retryable());
+    assert_eq!(err.user_message(), "GitHub is unavailable");
+    assert_eq!(err.to_string(), "[github] GIT_HUB_API: 502 from api.github.com");
+  }
+}
@@ -0,0 +1,207 @@
+//! Derive macro backing `jd_error::ErrorTaxonomy`.
+//!
+//! Generates `impl From<YourError> for jd_error::AppError` from per-variant
+//! `#[taxonomy(...)]` attributes. See the `jd_error` crate docs for options.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+  Attribute, Data, DeriveInput, Fields, Ident, LitBool, LitInt, LitStr, Token, parse_macro_input,
+};
+
+#[proc_macro_derive(ErrorTaxonomy, attributes(taxonomy))]
+pub fn derive_error_taxonomy(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+// region:    --- Attributes
+
+#[derive(Default)]
+struct EnumAttrs {
+  service: Option<LitStr>,
+}
+
+#[derive(Default)]
+struct VariantAttrs {
+  kind: Option<Ident>,
+  code: Option<LitStr>,
+  message: Option<LitStr>,
+  expose: bool,
+  retryable: Option<bool>,
+  retry_after: Option<RetryAfter>,
+}
+
+/// `retry_after = 30`, or `retry_after = field` to take it from the variant.
+enum RetryAfter {
+  Secs(LitInt),
+  Field(Ident),
+}
+
+fn parse_enum_attrs(attrs: &[Attribute]) -> syn::Result<EnumAttrs> {
+  let mut out = EnumAttrs::default();
+  for attr in attrs.iter().filter(|a| a.path().is_ident("taxonomy")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("service") {
+        out.service = Some(meta.value()?.parse()?);
+        Ok(())
+      } else {
+        Err(meta.error("unsupported enum-level taxonomy option, expected `service`"))
+      }
+    })?;
+  }
+  Ok(out)
+}
+
+fn parse_variant_attrs(attrs: &[Attribute]) -> syn::Result<VariantAttrs> {
+  let mut out = VariantAttrs::default();
+  for attr in attrs.iter().filter(|a| a.path().is_ident("taxonomy")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("kind") {
+        out.kind = Some(meta.value()?.parse()?);
+      } else if meta.path.is_ident("code") {
+        out.code = Some(meta.value()?.parse()?);
+      } else if meta.path.is_ident("message") {
+        out.message = Some(meta.value()?.parse()?);
+      } else if meta.path.is_ident("expose") {
+        out.expose = true;
+      } else if meta.path.is_ident("retryable") {
+        let value = if meta.input.peek(Token![=]) {
+          meta.value()?.parse::<LitBool>()?.value
+        } else {
+          true
+        };
+        out.retryable = Some(value);
+      } else if meta.path.is_ident("retry_after") {
+        let value = meta.value()?;
+        out.retry_after = Some(if value.peek(LitInt) {
+          RetryAfter::Secs(value.parse()?)
+        } else {
+          RetryAfter::Field(value.parse()?)
+        });
+      } else {
+        return Err(meta.error(
+          "unsupported taxonomy option, expected one of \
+           `kind`, `code`, `message`, `expose`, `retryable`, `retry_after`",
+        ));
+      }
+      Ok(())
+    })?;
+  }
+  Ok(out)
+}
+
+// endregion: --- Attributes
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+  let name = &input.ident;
+  let Data::Enum(data) = &input.data else {
+    return Err(syn::Error::new_spanned(name, "ErrorTaxonomy can only be derived for enums"));
+  };
+
+  let enum_attrs = parse_enum_attrs(&input.attrs)?;
+  let service = match enum_attrs.service {
+    Some(service) => quote!(::core::option::Option::Some(#service)),
+    None => quote!(::core::option::Option::None),
+  };
+
+  let mut arms = Vec::with_capacity(data.variants.len());
+  for variant in &data.variants {
+    let ident = &variant.ident;
+    let attrs = parse_variant_attrs(&variant.attrs)?;
+
+    let kind = attrs
+      .kind
+      .ok_or_else(|| syn::Error::new_spanned(ident, "missing `#[taxonomy(kind = ...)]`"))?;
+    if attrs.expose && attrs.message.is_some() {
+      return Err(syn::Error::new_spanned(ident, "`expose` and `message` are mutually exclusive"));
+    }
+
+    let retry_field = match &attrs.retry_after {
+      Some(RetryAfter::Field(field)) => Some(field),
+      _ => None,
+    };
+    if let Some(field) = retry_field {
+      let named = match &variant.fields {
+        Fields::Named(fields) => fields.named.iter().any(|f| f.ident.as_ref() == Some(field)),
+        _ => false,
+      };
+      if !named {
+        return Err(syn::Error::new_spanned(field, "`retry_after` names no field of this variant"));
+      }
+    }
+
+    let pattern = match (&variant.fields, retry_field) {
+      (Fields::Named(_), Some(field)) => quote!(#name::#ident { #field: __retry_after, .. }),
+      (Fields::Named(_), None) => quote!(#name::#ident { .. }),
+      (Fields::Unnamed(_), _) => quote!(#name::#ident(..)),
+      (Fields::Unit, _) => quote!(#name::#ident),
+    };
+    let code = attrs.code.map(|c| c.value()).unwrap_or_else(|| screaming_snake(&ident.to_string()));
+    let user_message = if attrs.expose {
+      quote!(::core::option::Option::Some(::std::string::ToString::to_string(&err)))
+    } else if let Some(message) = attrs.message {
+      quote!(::core::option::Option::Some(::std::string::String::from(#message)))
+    } else {
+      quote!(::core::option::Option::None)
+    };
+    let retryable = match attrs.retryable {
+      Some(value) => quote!(::core::option::Option::Some(#value)),
+      None => quote!(::core::option::Option::None),
+    };
+    let retry_after = match attrs.retry_after {
+      Some(RetryAfter::Secs(secs)) => quote!(::core::option::Option::Some(#secs)),
+      Some(RetryAfter::Field(_)) => {
+        quote!(::jd_error::RetryAfterSecs::retry_after_secs(__retry_after))
+      }
+      None => quote!(::core::option::Option::None),
+    };
+
+    arms.push(quote! {
+      #pattern => (::jd_error::ErrorKind::#kind, #code, #user_message, #retryable, #retry_after),
+    });
+  }
+
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  Ok(quote! {
+    impl #impl_generics ::core::convert::From<#name #ty_generics> for ::jd_error::AppError
+    #where_clause
+    {
+      fn from(err: #name #ty_generics) -> Self {
+        let (kind, code, user_message, retryable, retry_after_secs): (
+          ::jd_error::ErrorKind,
+          &'static str,
+          ::core::option::Option<::std::string::String>,
+          ::core::option::Option<bool>,
+          ::core::option::Option<u64>,
+        ) = match &err {
+          #(#arms)*
+        };
+
+        ::jd_error::AppError::from_parts(::jd_error::ErrorParts {
+          kind,
+          code,
+          service: #service,
+          internal_message: ::std::string::ToString::to_string(&err),
+          user_message,
+          retryable,
+          retry_after_secs,
+        })
+      }
+    }
+  })
+}
+
+/// `RepositoryNotFound` -> `REPOSITORY_NOT_FOUND`
+fn screaming_snake(s: &str) -> String {
+  let mut result = String::with_capacity(s.len() + 4);
+  for (i, c) in s.chars().enumerate() {
+    if c.is_uppercase() && i > 0 {
+      result.push('_');
+    }
+    result.push(c.to_ascii_uppercase());
+  }
+  result
+}
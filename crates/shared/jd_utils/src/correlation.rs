@@ -0,0 +1,71 @@
+use reqwest::RequestBuilder;
+use std::future::Future;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+pub const TRACE_ID_HEADER: &str = "x-trace-id";
+
+/// Ids of the request being served, so work done for it can be matched
+/// across systems.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Correlation {
+  pub request_id: Option<String>,
+  pub trace_id: Option<String>,
+  pub user_id: Option<String>,
+}
+
+tokio::task_local! {
+  static CURRENT: Correlation;
+}
+
+impl Correlation {
+  /// The correlation of the request this task serves, if any.
+  pub fn current() -> Option<Correlation> {
+    CURRENT.try_with(Clone::clone).ok()
+  }
+
+  /// Run `future` with this as the current correlation.
+  pub async fn scope<F: Future>(self, future: F) -> F::Output {
+    CURRENT.scope(self, future).await
+  }
+}
+
+/// Forward the current request's ids to another system.
+pub trait Correlate {
+  fn correlated(self) -> Self;
+}
+
+impl Correlate for RequestBuilder {
+  fn correlated(self) -> Self {
+    let Some(correlation) = Correlation::current() else {
+      return self;
+    };
+    let mut request = self;
+    if let Some(request_id) = correlation.request_id {
+      request = request.header(REQUEST_ID_HEADER, request_id);
+    }
+    if let Some(trace_id) = correlation.trace_id {
+      request = request.header(TRACE_ID_HEADER, trace_id);
+    }
+    request
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn requests_carry_the_ids_of_their_scope() {
+    let client = reqwest::Client::new();
+    let outside = client.get("http://localhost/").correlated().build().unwrap();
+    assert!(outside.headers().get(REQUEST_ID_HEADER).is_none());
+
+    let correlation =
+      Correlation { request_id: Some("req-1".to_string()), trace_id: None, user_id: None };
+    let inside = correlation
+      .scope(async { client.get("http://localhost/").correlated().build().unwrap() })
+      .await;
+    assert_eq!(inside.headers()[REQUEST_ID_HEADER], "req-1");
+    assert!(inside.headers().get(TRACE_ID_HEADER).is_none());
+  }
+}
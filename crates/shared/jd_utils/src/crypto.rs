@@ -0,0 +1,84 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+use crate::error::Error;
+
+const CIPHERTEXT_VERSION: &str = "v1";
+const NONCE_LEN: usize = 12;
+
+/// AES-256-GCM cipher for secrets stored at rest (e.g. third-party OAuth tokens).
+///
+/// Ciphertexts are `v1:<base64(nonce || ciphertext)>` so the format can change
+/// without breaking rows written earlier.
+#[derive(Clone)]
+pub struct SecretCipher {
+  cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+  /// Build from a base64-encoded 32-byte key.
+  pub fn from_base64_key(key: &str) -> crate::Result<Self> {
+    let key = STANDARD
+      .decode(key.trim())
+      .map_err(|e| Error::Crypto(format!("invalid key encoding: {}", e)))?;
+    if key.len() != 32 {
+      return Err(Error::Crypto(format!("key must be 32 bytes, got {}", key.len())));
+    }
+
+    Ok(Self { cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)) })
+  }
+
+  pub fn encrypt(&self, plaintext: &str) -> crate::Result<String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = self
+      .cipher
+      .encrypt(&nonce, plaintext.as_bytes())
+      .map_err(|_| Error::Crypto("encryption failed".to_string()))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    Ok(format!("{}:{}", CIPHERTEXT_VERSION, STANDARD.encode(payload)))
+  }
+
+  pub fn decrypt(&self, encrypted: &str) -> crate::Result<String> {
+    let encoded = encrypted
+      .strip_prefix(CIPHERTEXT_VERSION)
+      .and_then(|rest| rest.strip_prefix(':'))
+      .ok_or_else(|| Error::Crypto("unsupported ciphertext version".to_string()))?;
+    let payload = STANDARD
+      .decode(encoded)
+      .map_err(|e| Error::Crypto(format!("invalid ciphertext encoding: {}", e)))?;
+    if payload.len() <= NONCE_LEN {
+      return Err(Error::Crypto("ciphertext too short".to_string()));
+    }
+
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let plaintext = self
+      .cipher
+      .decrypt(Nonce::from_slice(nonce), ciphertext)
+      .map_err(|_| Error::Crypto("decryption failed".to_string()))?;
+
+    String::from_utf8(plaintext).map_err(|_| Error::Crypto("plaintext is not UTF-8".to_string()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_round_trip_and_tamper_detection() {
+    let cipher = SecretCipher::from_base64_key(&STANDARD.encode([7u8; 32])).unwrap();
+
+    let encrypted = cipher.encrypt("gho_secret").unwrap();
+    assert!(encrypted.starts_with("v1:"));
+    assert_ne!(cipher.encrypt("gho_secret").unwrap(), encrypted);
+    assert_eq!(cipher.decrypt(&encrypted).unwrap(), "gho_secret");
+
+    let mut tampered = STANDARD.decode(&encrypted[3..]).unwrap();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert!(cipher.decrypt(&format!("v1:{}", STANDARD.encode(tampered))).is_err());
+    assert!(SecretCipher::from_base64_key(&STANDARD.encode([7u8; 16])).is_err());
+  }
+}
@@ -9,11 +9,26 @@ use serde_with::{DisplayFromStr, serde_as};
 pub enum Error {
   #[from]
   Config(#[serde_as(as = "DisplayFromStr")] config::ConfigError),
+  /// Every missing or invalid setting found while loading the config.
+  InvalidConfig(Vec<String>),
+  Crypto(String),
+  /// A secret could not be fetched or has not been yet.
+  Secret(String),
 }
 
 impl Display for Error {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{self:?}")
+    match self {
+      Self::InvalidConfig(issues) => {
+        write!(f, "Invalid configuration:")?;
+        for issue in issues {
+          write!(f, "\n  - {issue}")?;
+        }
+        Ok(())
+      }
+      Self::Secret(message) => write!(f, "{message}"),
+      _ => write!(f, "{self:?}"),
+    }
   }
 }
 
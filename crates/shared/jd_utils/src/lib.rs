@@ -1,7 +1,11 @@
 pub mod config;
+pub mod correlation;
+pub mod crypto;
 pub mod error;
 pub mod macros;
+pub mod metrics;
 pub mod regex;
+pub mod secrets;
 pub mod time;
 
 pub use macros::*;
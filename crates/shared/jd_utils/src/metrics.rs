@@ -0,0 +1,163 @@
+use prometheus::{
+  Encoder, HistogramOpts, Opts, Registry, TextEncoder,
+  proto::{Metric, MetricFamily, MetricType},
+};
+use serde::Serialize;
+use serde_with::skip_serializing_none;
+use std::{collections::BTreeMap, sync::LazyLock};
+
+pub use prometheus::{HistogramVec, IntCounterVec};
+
+/// Buckets, in seconds, for calls to other systems.
+pub const CALL_BUCKETS: &[f64] = &[0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+/// Buckets, in seconds, for queued work, from seconds to hours.
+pub const QUEUE_BUCKETS: &[f64] =
+  &[1.0, 5.0, 15.0, 30.0, 60.0, 300.0, 900.0, 1800.0, 3600.0, 7200.0, 21600.0];
+
+static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+/// Every metric of the process.
+pub fn registry() -> &'static Registry {
+  &REGISTRY
+}
+
+/// Register a counter. Keep it in a static so it is registered once; a name
+/// registered twice panics.
+pub fn counter(name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+  let counter = IntCounterVec::new(Opts::new(name, help), labels).expect("valid counter");
+  REGISTRY.register(Box::new(counter.clone())).expect("counter registered once");
+  counter
+}
+
+/// Register a histogram, like [`counter`].
+pub fn histogram(name: &str, help: &str, labels: &[&str], buckets: &[f64]) -> HistogramVec {
+  let opts = HistogramOpts::new(name, help).buckets(buckets.to_vec());
+  let histogram = HistogramVec::new(opts, labels).expect("valid histogram");
+  REGISTRY.register(Box::new(histogram.clone())).expect("histogram registered once");
+  histogram
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricSummary {
+  pub name: String,
+  pub help: String,
+  pub kind: &'static str,
+  pub series: Vec<SeriesSummary>,
+}
+
+/// One label combination of a metric. Counters carry a value; histograms a
+/// count, sum, mean and bucket-resolution percentiles.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesSummary {
+  pub labels: BTreeMap<String, String>,
+  pub value: Option<f64>,
+  pub count: Option<u64>,
+  pub sum: Option<f64>,
+  pub mean: Option<f64>,
+  pub p50: Option<f64>,
+  pub p95: Option<f64>,
+}
+
+/// Every registered metric in the Prometheus text format, and its content
+/// type.
+pub fn render() -> (String, &'static str) {
+  let encoder = TextEncoder::new();
+  let mut buffer = Vec::new();
+  // Encoding to memory only fails on malformed metrics, which registration rejects
+  let _ = encoder.encode(&REGISTRY.gather(), &mut buffer);
+  (String::from_utf8(buffer).unwrap_or_default(), prometheus::TEXT_FORMAT)
+}
+
+/// Current values of every registered metric.
+pub fn summary() -> Vec<MetricSummary> {
+  REGISTRY.gather().iter().map(summarize_family).collect()
+}
+
+fn summarize_family(family: &MetricFamily) -> MetricSummary {
+  let kind = match family.get_field_type() {
+    MetricType::COUNTER => "counter",
+    MetricType::GAUGE => "gauge",
+    MetricType::HISTOGRAM => "histogram",
+    MetricType::SUMMARY => "summary",
+    MetricType::UNTYPED => "untyped",
+  };
+  MetricSummary {
+    name: family.get_name().to_string(),
+    help: family.get_help().to_string(),
+    kind,
+    series: family.get_metric().iter().map(|metric| summarize_series(kind, metric)).collect(),
+  }
+}
+
+fn summarize_series(kind: &str, metric: &Metric) -> SeriesSummary {
+  let labels = metric
+    .get_label()
+    .iter()
+    .map(|label| (label.get_name().to_string(), label.get_value().to_string()))
+    .collect();
+  let mut series = SeriesSummary {
+    labels,
+    value: None,
+    count: None,
+    sum: None,
+    mean: None,
+    p50: None,
+    p95: None,
+  };
+  match kind {
+    "counter" => series.value = Some(metric.get_counter().get_value()),
+    "gauge" => series.value = Some(metric.get_gauge().get_value()),
+    "histogram" => {
+      let histogram = metric.get_histogram();
+      let count = histogram.get_sample_count();
+      let buckets: Vec<(f64, u64)> = histogram
+        .get_bucket()
+        .iter()
+        .map(|bucket| (bucket.get_upper_bound(), bucket.get_cumulative_count()))
+        .collect();
+      series.count = Some(count);
+      series.sum = Some(histogram.get_sample_sum());
+      series.mean = (count > 0).then(|| histogram.get_sample_sum() / count as f64);
+      series.p50 = quantile(&buckets, count, 0.5);
+      series.p95 = quantile(&buckets, count, 0.95);
+    }
+    _ => {}
+  }
+  series
+}
+
+/// Upper bound of the bucket holding the `q` quantile; the largest bound when
+/// it lies beyond every bucket.
+fn quantile(buckets: &[(f64, u64)], count: u64, q: f64) -> Option<f64> {
+  if count == 0 {
+    return None;
+  }
+  let rank = (q * count as f64).ceil() as u64;
+  buckets
+    .iter()
+    .find(|(_, cumulative)| *cumulative >= rank)
+    .or(buckets.last())
+    .map(|(upper_bound, _)| *upper_bound)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn histograms_summarize_to_bucket_percentiles() {
+    let histogram = histogram("test_wait_seconds", "Test wait", &["queue"], &[1.0, 5.0, 10.0]);
+    for seconds in [0.5, 0.5, 0.5, 3.0, 8.0, 60.0] {
+      histogram.with_label_values(&["analysis"]).observe(seconds);
+    }
+
+    let summary = summary().into_iter().find(|metric| metric.name == "test_wait_seconds").unwrap();
+    let series = &summary.series[0];
+    assert_eq!(summary.kind, "histogram");
+    assert_eq!(series.labels["queue"], "analysis");
+    assert_eq!(series.count, Some(6));
+    assert_eq!(series.p50, Some(1.0));
+    assert_eq!(series.p95, Some(10.0));
+  }
+}
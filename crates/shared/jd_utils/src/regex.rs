@@ -10,3 +10,72 @@ pub static TIMEZONE_REGEX: LazyLock<Regex> =
 
 pub static COUNTRY_CODE_REGEX: LazyLock<Regex> =
   LazyLock::new(|| Regex::new(r"^[A-Z]{2}$").unwrap());
+
+// -- Secrets
+// Known formats of keys and tokens that should never be committed. Patterns
+// keyed on an assignment capture the value in group 1.
+
+pub static PEM_PRIVATE_KEY_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"-----BEGIN (?:[A-Z0-9]+ )*PRIVATE KEY(?: BLOCK)?-----").unwrap());
+
+/// Bech32 `suiprivkey1...` export of a Sui keypair.
+pub static SUI_PRIVATE_KEY_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"\bsuiprivkey1[qpzry9x8gf2tvdw0s3jn54khce6mua7l]{58,}\b").unwrap());
+
+/// 32 hex bytes assigned to a private key name. Bare 32-byte hex strings are
+/// Move addresses and object ids, so the name is required.
+pub static HEX_PRIVATE_KEY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(
+    r#"(?i)\b\w*(?:private|secret|signing|priv)_?key\w*\b[^=\n]{0,40}[=:]\s*(?:x?["'`])?(?:0x)?([0-9a-f]{64})\b"#,
+  )
+  .unwrap()
+});
+
+/// A 12 to 24 word phrase assigned to a mnemonic or seed phrase name.
+pub static MNEMONIC_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(
+    r#"(?i)\b\w*(?:mnemonic|seed_?phrase|recovery_?phrase|seed_?words)\w*\b[^=\n]{0,40}[=:]\s*["'`]((?:[a-z]+ ){11,23}[a-z]+)["'`]"#,
+  )
+  .unwrap()
+});
+
+/// A Sui SDK keypair built from a literal secret key or phrase.
+pub static SUI_KEYPAIR_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(
+    r#"(?i)\b(?:ed25519|secp256k1|secp256r1|sui)_?key_?pair\s*(?:::|\.)\s*(?:from_?secret_?key|from_?bytes|derive_?keypair|from_?exported_?keypair)\s*\(\s*(?:\w+\s*\(\s*)?["'`]([^"'`]{16,})["'`]"#,
+  )
+  .unwrap()
+});
+
+pub static GITHUB_TOKEN_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(r"\b(?:gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{60,})\b").unwrap()
+});
+
+pub static AWS_ACCESS_KEY_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b").unwrap());
+
+pub static SLACK_TOKEN_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"\bxox[abprs]-[A-Za-z0-9-]{10,}").unwrap());
+
+pub static ANTHROPIC_API_KEY_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"\bsk-ant-[A-Za-z0-9_-]{32,}").unwrap());
+
+/// Also matches Anthropic keys; check `ANTHROPIC_API_KEY_REGEX` first.
+pub static OPENAI_API_KEY_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"\bsk-(?:proj-|svcacct-)?[A-Za-z0-9_-]{32,}").unwrap());
+
+pub static GOOGLE_API_KEY_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"\bAIza[0-9A-Za-z_-]{35}\b").unwrap());
+
+pub static STRIPE_SECRET_KEY_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"\b[rs]k_live_[0-9A-Za-z]{24,}\b").unwrap());
+
+/// A quoted value of 16 or more characters assigned to a name suggesting a
+/// credential. Matches plenty of harmless strings too; check the value's
+/// entropy before reporting it.
+pub static SECRET_ASSIGNMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(
+    r#"(?i)\b\w*(?:secret|token|api_?key|password|passwd|credential)\w*\b[^=\n]{0,40}[=:]\s*["'`]([^"'`\s]{16,})["'`]"#,
+  )
+  .unwrap()
+});
@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::{env, time::Duration};
+use time::OffsetDateTime;
+
+use super::SecretProvider;
+use crate::{correlation::Correlate, error::Error};
+
+const SERVICE: &str = "secretsmanager";
+const CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+
+/// `aws-sm:id` or `aws-sm:id#field`: an AWS Secrets Manager secret, or a
+/// field of one holding JSON. Requests are signed with AWS SigV4.
+pub struct AwsSecretsManagerProvider {
+  endpoint: String,
+  region: String,
+  access_key_id: String,
+  secret_access_key: String,
+  session_token: Option<String>,
+  http: reqwest::Client,
+}
+
+impl AwsSecretsManagerProvider {
+  /// From `AWS_REGION` (or `AWS_DEFAULT_REGION`), `AWS_ACCESS_KEY_ID`,
+  /// `AWS_SECRET_ACCESS_KEY` and optionally `AWS_SESSION_TOKEN`.
+  /// `AWS_SECRETS_MANAGER_ENDPOINT` overrides the regional endpoint, e.g.
+  /// for LocalStack.
+  pub fn from_env() -> Option<Self> {
+    let env = |name: &str| env::var(name).ok().filter(|value| !value.trim().is_empty());
+    let region = env("AWS_REGION").or_else(|| env("AWS_DEFAULT_REGION"))?;
+    let endpoint = env("AWS_SECRETS_MANAGER_ENDPOINT")
+      .unwrap_or_else(|| format!("https://{SERVICE}.{region}.amazonaws.com"));
+    let http =
+      reqwest::Client::builder().timeout(Duration::from_secs(10)).build().unwrap_or_default();
+    Some(Self {
+      endpoint: endpoint.trim_end_matches('/').to_string(),
+      region,
+      access_key_id: env("AWS_ACCESS_KEY_ID")?,
+      secret_access_key: env("AWS_SECRET_ACCESS_KEY")?,
+      session_token: env("AWS_SESSION_TOKEN"),
+      http,
+    })
+  }
+
+  fn host(&self) -> &str {
+    let without_scheme =
+      self.endpoint.split_once("://").map_or(self.endpoint.as_str(), |(_, rest)| rest);
+    without_scheme.split('/').next().unwrap_or(without_scheme)
+  }
+
+  fn scope(&self, amz_date: &str) -> String {
+    format!("{}/{}/{SERVICE}/aws4_request", &amz_date[..8], self.region)
+  }
+
+  /// The `Authorization` header of a `POST /` with these `x-amz-*` headers.
+  fn authorization(&self, amz_headers: &[(&str, &str)], amz_date: &str, body: &str) -> String {
+    let mut headers = vec![("content-type", CONTENT_TYPE), ("host", self.host())];
+    headers.extend_from_slice(amz_headers);
+    headers.sort_by_key(|(name, _)| *name);
+    let canonical_headers: String =
+      headers.iter().map(|(name, value)| format!("{name}:{value}\n")).collect();
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    let canonical_request = format!(
+      "POST\n/\n\n{canonical_headers}\n{signed_headers}\n{}",
+      hex::encode(Sha256::digest(body.as_bytes()))
+    );
+
+    let string_to_sign = format!(
+      "AWS4-HMAC-SHA256\n{amz_date}\n{}\n{}",
+      self.scope(amz_date),
+      hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let mut key = format!("AWS4{}", self.secret_access_key).into_bytes();
+    for part in [&amz_date[..8], self.region.as_str(), SERVICE, "aws4_request"] {
+      key = hmac_sha256(&key, part);
+    }
+    format!(
+      "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={signed_headers}, Signature={}",
+      self.access_key_id,
+      self.scope(amz_date),
+      hex::encode(hmac_sha256(&key, &string_to_sign))
+    )
+  }
+}
+
+#[async_trait]
+impl SecretProvider for AwsSecretsManagerProvider {
+  async fn fetch(&self, key: &str) -> crate::Result<String> {
+    let (id, field) = match key.split_once('#') {
+      Some((id, field)) => (id, Some(field)),
+      None => (key, None),
+    };
+    let body = json!({ "SecretId": id }).to_string();
+    let amz_date = amz_timestamp(OffsetDateTime::now_utc());
+    let target = "secretsmanager.GetSecretValue";
+    let mut amz_headers = vec![("x-amz-date", amz_date.as_str()), ("x-amz-target", target)];
+    if let Some(token) = &self.session_token {
+      amz_headers.push(("x-amz-security-token", token));
+    }
+
+    let mut request = self
+      .http
+      .post(format!("{}/", self.endpoint))
+      .header("authorization", self.authorization(&amz_headers, &amz_date, &body))
+      .header("content-type", CONTENT_TYPE);
+    for (name, value) in &amz_headers {
+      request = request.header(*name, *value);
+    }
+    let response = request
+      .body(body)
+      .correlated()
+      .send()
+      .await
+      .map_err(|e| Error::Secret(format!("Secrets Manager request failed: {e}")))?;
+    if !response.status().is_success() {
+      return Err(Error::Secret(format!("Secrets Manager returned {} for {id}", response.status())));
+    }
+    let body: Value = response
+      .json()
+      .await
+      .map_err(|e| Error::Secret(format!("Secrets Manager returned an invalid body: {e}")))?;
+    let secret = body["SecretString"]
+      .as_str()
+      .ok_or_else(|| Error::Secret(format!("Secret {id} has no string value")))?;
+
+    let Some(field) = field else {
+      return Ok(secret.to_string());
+    };
+    let fields: Value = serde_json::from_str(secret)
+      .map_err(|_| Error::Secret(format!("Secret {id} is not JSON, so has no field {field}")))?;
+    match &fields[field] {
+      Value::String(value) => Ok(value.clone()),
+      Value::Null => Err(Error::Secret(format!("Secret {id} has no field {field}"))),
+      other => Ok(other.to_string()),
+    }
+  }
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+  mac.update(data.as_bytes());
+  mac.finalize().into_bytes().to_vec()
+}
+
+/// `YYYYMMDDTHHMMSSZ`.
+fn amz_timestamp(at: OffsetDateTime) -> String {
+  format!(
+    "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+    at.year(),
+    u8::from(at.month()),
+    at.day(),
+    at.hour(),
+    at.minute(),
+    at.second()
+  )
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use std::env;
+
+use super::SecretProvider;
+use crate::error::Error;
+
+/// `env:NAME`: another environment variable.
+pub struct EnvSecretProvider;
+
+#[async_trait]
+impl SecretProvider for EnvSecretProvider {
+  async fn fetch(&self, key: &str) -> crate::Result<String> {
+    env::var(key).map_err(|_| Error::Secret(format!("Environment variable {key} is not set")))
+  }
+}
+
+/// `file:/path`: a file's contents without the trailing newline, as mounted
+/// by Docker or Kubernetes secrets.
+pub struct FileSecretProvider;
+
+#[async_trait]
+impl SecretProvider for FileSecretProvider {
+  async fn fetch(&self, key: &str) -> crate::Result<String> {
+    let path = key.strip_prefix("//").unwrap_or(key);
+    let contents = tokio::fs::read_to_string(path)
+      .await
+      .map_err(|e| Error::Secret(format!("Failed to read secret file {path}: {e}")))?;
+    Ok(contents.trim_end_matches(['\r', '\n']).to_string())
+  }
+}
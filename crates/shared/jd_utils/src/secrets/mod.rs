@@ -0,0 +1,251 @@
+//! Sensitive settings fetched from a secret manager rather than set in plain
+//! text. A setting refers to its secret with a reference in place of its
+//! value:
+//!
+//! - `env:NAME` reads another environment variable
+//! - `file:/run/secrets/dsn` reads a file, e.g. a mounted Kubernetes secret
+//! - `vault:secret/data/hkt#dsn` reads a field of a HashiCorp Vault secret
+//! - `aws-sm:hkt/prod#dsn` reads an AWS Secrets Manager secret, or a field of
+//!   it when it holds JSON
+//!
+//! Secrets are fetched into the process-wide [`SecretStore`] at startup, so
+//! `Config::load` can resolve them without blocking, and refetched once their
+//! TTL passes. Callbacks registered with [`SecretStore::on_rotation`] run
+//! when a refetch finds a new value.
+
+mod aws;
+mod local;
+mod vault;
+
+pub use aws::AwsSecretsManagerProvider;
+pub use local::{EnvSecretProvider, FileSecretProvider};
+pub use vault::VaultSecretProvider;
+
+use async_trait::async_trait;
+use std::{
+  collections::HashMap,
+  env,
+  sync::{Arc, LazyLock, RwLock},
+  time::{Duration, Instant},
+};
+
+use crate::error::Error;
+
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// A source of secrets, addressed by the part of a reference after its
+/// scheme.
+#[async_trait]
+pub trait SecretProvider: Send + Sync {
+  async fn fetch(&self, key: &str) -> crate::Result<String>;
+}
+
+type RotationCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+struct Cached {
+  value: String,
+  fetched_at: Instant,
+}
+
+/// Fetched secrets, by reference, with the providers they come from.
+pub struct SecretStore {
+  providers: HashMap<&'static str, Arc<dyn SecretProvider>>,
+  ttl: Duration,
+  cache: RwLock<HashMap<String, Cached>>,
+  callbacks: RwLock<HashMap<String, Vec<RotationCallback>>>,
+}
+
+static GLOBAL: LazyLock<SecretStore> = LazyLock::new(SecretStore::from_env);
+
+/// The store `Config::load` resolves references from.
+pub fn global() -> &'static SecretStore {
+  &GLOBAL
+}
+
+/// `value` itself, or the secret it refers to once fetched into the global
+/// store.
+pub fn resolve(value: &str) -> crate::Result<String> {
+  global().cached(value)
+}
+
+/// Whether `value` refers to a secret rather than being one.
+pub fn is_reference(value: &str) -> bool {
+  split(value).is_some()
+}
+
+fn split(value: &str) -> Option<(&str, &str)> {
+  let (scheme, key) = value.split_once(':')?;
+  let known = ["env", "file", "vault", "aws-sm"].contains(&scheme);
+  (known && !key.is_empty()).then_some((scheme, key))
+}
+
+impl SecretStore {
+  /// A store without providers, keeping secrets for `ttl`.
+  pub fn new(ttl: Duration) -> Self {
+    Self {
+      providers: HashMap::new(),
+      ttl,
+      cache: RwLock::new(HashMap::new()),
+      callbacks: RwLock::new(HashMap::new()),
+    }
+  }
+
+  /// `env:` and `file:` references always, `vault:` ones when `VAULT_ADDR`
+  /// and `VAULT_TOKEN` are set, and `aws-sm:` ones when AWS credentials and
+  /// a region are. Secrets are kept for `SECRETS_TTL_SECS` (default 300).
+  pub fn from_env() -> Self {
+    let ttl = env::var("SECRETS_TTL_SECS")
+      .ok()
+      .and_then(|secs| secs.trim().parse().ok())
+      .map_or(DEFAULT_TTL, Duration::from_secs);
+    let mut store = Self::new(ttl)
+      .with_provider("env", EnvSecretProvider)
+      .with_provider("file", FileSecretProvider);
+    if let Some(vault) = VaultSecretProvider::from_env() {
+      store = store.with_provider("vault", vault);
+    }
+    if let Some(aws) = AwsSecretsManagerProvider::from_env() {
+      store = store.with_provider("aws-sm", aws);
+    }
+    store
+  }
+
+  pub fn with_provider(
+    mut self,
+    scheme: &'static str,
+    provider: impl SecretProvider + 'static,
+  ) -> Self {
+    self.providers.insert(scheme, Arc::new(provider));
+    self
+  }
+
+  pub fn ttl(&self) -> Duration {
+    self.ttl
+  }
+
+  /// `value` itself, or the secret it refers to: from the cache while fresh,
+  /// otherwise fetched. A stale secret is kept when its provider fails, so an
+  /// outage does not take settings away.
+  pub async fn get(&self, value: &str) -> crate::Result<String> {
+    if !is_reference(value) {
+      return Ok(value.to_string());
+    }
+    if let Some(secret) = self.fresh(value) {
+      return Ok(secret);
+    }
+    match self.fetch(value).await {
+      Ok(secret) => Ok(secret),
+      Err(e) => self.cached(value).map_err(|_| e),
+    }
+  }
+
+  /// `value` itself, or the secret it refers to when already fetched, stale
+  /// or not.
+  pub fn cached(&self, value: &str) -> crate::Result<String> {
+    if !is_reference(value) {
+      return Ok(value.to_string());
+    }
+    let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+    cache
+      .get(value)
+      .map(|cached| cached.value.clone())
+      .ok_or_else(|| Error::Secret(format!("{value} has not been fetched")))
+  }
+
+  /// Run `callback` with the new secret each time `reference` changes.
+  pub fn on_rotation(&self, reference: &str, callback: impl Fn(&str) + Send + Sync + 'static) {
+    let mut callbacks = self.callbacks.write().unwrap_or_else(|e| e.into_inner());
+    callbacks.entry(reference.to_string()).or_default().push(Arc::new(callback));
+  }
+
+  /// Refetch every secret past its TTL. Returns the references that could not
+  /// be refetched; their stale values are kept.
+  pub async fn refresh(&self) -> Vec<(String, Error)> {
+    let expired: Vec<String> = {
+      let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+      cache
+        .iter()
+        .filter(|(_, cached)| cached.fetched_at.elapsed() >= self.ttl)
+        .map(|(reference, _)| reference.clone())
+        .collect()
+    };
+    let mut failures = Vec::new();
+    for reference in expired {
+      if let Err(e) = self.fetch(&reference).await {
+        failures.push((reference, e));
+      }
+    }
+    failures
+  }
+
+  fn fresh(&self, reference: &str) -> Option<String> {
+    let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+    cache
+      .get(reference)
+      .filter(|cached| cached.fetched_at.elapsed() < self.ttl)
+      .map(|cached| cached.value.clone())
+  }
+
+  async fn fetch(&self, reference: &str) -> crate::Result<String> {
+    let (scheme, key) =
+      split(reference).ok_or_else(|| Error::Secret(format!("{reference} is not a reference")))?;
+    let provider = self
+      .providers
+      .get(scheme)
+      .ok_or_else(|| Error::Secret(format!("No secret provider is configured for {scheme}:")))?;
+    let secret = provider.fetch(key).await?;
+
+    let previous = {
+      let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
+      let cached = Cached { value: secret.clone(), fetched_at: Instant::now() };
+      cache.insert(reference.to_string(), cached).map(|cached| cached.value)
+    };
+    if previous.is_some_and(|previous| previous != secret) {
+      let callbacks = {
+        let callbacks = self.callbacks.read().unwrap_or_else(|e| e.into_inner());
+        callbacks.get(reference).cloned().unwrap_or_default()
+      };
+      for callback in callbacks {
+        callback(&secret);
+      }
+    }
+    Ok(secret)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  /// Returns `v1`, `v2`, ... on each fetch.
+  struct Counting(Arc<AtomicUsize>);
+
+  #[async_trait]
+  impl SecretProvider for Counting {
+    async fn fetch(&self, _key: &str) -> crate::Result<String> {
+      Ok(format!("v{}", self.0.fetch_add(1, Ordering::SeqCst) + 1))
+    }
+  }
+
+  #[tokio::test]
+  async fn secrets_are_cached_and_rotations_reported() {
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let store = SecretStore::new(Duration::ZERO).with_provider("vault", Counting(fetches.clone()));
+    let dsn = "postgresql://localhost/app";
+    assert_eq!(store.get(dsn).await.unwrap(), dsn);
+    assert!(store.cached("vault:secret/data/hkt#dsn").is_err());
+
+    let rotated = Arc::new(RwLock::new(Vec::new()));
+    let seen = rotated.clone();
+    store.on_rotation("vault:secret/data/hkt#dsn", move |secret| {
+      seen.write().unwrap().push(secret.to_string())
+    });
+    assert_eq!(store.get("vault:secret/data/hkt#dsn").await.unwrap(), "v1");
+    assert!(store.refresh().await.is_empty());
+
+    assert_eq!(store.cached("vault:secret/data/hkt#dsn").unwrap(), "v2");
+    assert_eq!(*rotated.read().unwrap(), vec!["v2".to_string()]);
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+  }
+}
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use std::{env, time::Duration};
+
+use super::SecretProvider;
+use crate::{correlation::Correlate, error::Error};
+
+/// `vault:path#field`: a field of a HashiCorp Vault secret, read from
+/// `{VAULT_ADDR}/v1/{path}`. KV version 2 paths include `data/`, e.g.
+/// `vault:secret/data/hkt/prod#dsn`.
+pub struct VaultSecretProvider {
+  addr: String,
+  token: String,
+  namespace: Option<String>,
+  http: reqwest::Client,
+}
+
+impl VaultSecretProvider {
+  pub fn new(addr: String, token: String, namespace: Option<String>) -> Self {
+    let http =
+      reqwest::Client::builder().timeout(Duration::from_secs(10)).build().unwrap_or_default();
+    Self { addr: addr.trim_end_matches('/').to_string(), token, namespace, http }
+  }
+
+  /// From `VAULT_ADDR`, `VAULT_TOKEN` and optionally `VAULT_NAMESPACE`.
+  pub fn from_env() -> Option<Self> {
+    let env = |name: &str| env::var(name).ok().filter(|value| !value.trim().is_empty());
+    Some(Self::new(env("VAULT_ADDR")?, env("VAULT_TOKEN")?, env("VAULT_NAMESPACE")))
+  }
+}
+
+#[async_trait]
+impl SecretProvider for VaultSecretProvider {
+  async fn fetch(&self, key: &str) -> crate::Result<String> {
+    let (path, field) = key
+      .split_once('#')
+      .ok_or_else(|| Error::Secret(format!("vault:{key} names no field; add #field")))?;
+    let mut request = self
+      .http
+      .get(format!("{}/v1/{}", self.addr, path.trim_start_matches('/')))
+      .header("X-Vault-Token", &self.token)
+      .correlated();
+    if let Some(namespace) = &self.namespace {
+      request = request.header("X-Vault-Namespace", namespace);
+    }
+    let response =
+      request.send().await.map_err(|e| Error::Secret(format!("Vault request failed: {e}")))?;
+    if !response.status().is_success() {
+      return Err(Error::Secret(format!("Vault returned {} for {path}", response.status())));
+    }
+    let body: Value = response
+      .json()
+      .await
+      .map_err(|e| Error::Secret(format!("Vault returned an invalid body for {path}: {e}")))?;
+
+    // KV version 2 nests the secret's fields one level deeper than version 1
+    let value = body
+      .pointer(&format!("/data/data/{field}"))
+      .or_else(|| body.pointer(&format!("/data/{field}")))
+      .ok_or_else(|| Error::Secret(format!("Vault secret {path} has no field {field}")))?;
+    Ok(match value {
+      Value::String(secret) => secret.clone(),
+      other => other.to_string(),
+    })
+  }
+}
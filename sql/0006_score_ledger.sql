@@ -0,0 +1,48 @@
+-- Score Ledger
+-- Append-only, hash-chained record of every score change. Each row stores the
+-- hash of the previous row, so rewriting any historical entry breaks the chain.
+
+-- Table: score_ledger
+CREATE TABLE IF NOT EXISTS score_ledger (
+    seq BIGINT PRIMARY KEY,
+    subject_id UUID NOT NULL,
+    scoring_result_id UUID,
+    score DOUBLE PRECISION NOT NULL,
+    previous_score DOUBLE PRECISION,
+    model_version VARCHAR(50) NOT NULL,
+    reason VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    prev_hash CHAR(64) NOT NULL,
+    entry_hash CHAR(64) NOT NULL,
+
+    -- Constraints
+    CONSTRAINT score_ledger_entry_hash_unique UNIQUE (entry_hash),
+    CONSTRAINT score_ledger_prev_hash_unique UNIQUE (prev_hash),
+    CONSTRAINT score_ledger_seq_positive CHECK (seq >= 1),
+    CONSTRAINT score_ledger_score_range CHECK (score >= 0 AND score <= 100)
+);
+
+CREATE INDEX IF NOT EXISTS idx_score_ledger_subject_id ON score_ledger(subject_id, seq DESC);
+CREATE INDEX IF NOT EXISTS idx_score_ledger_scoring_result_id ON score_ledger(scoring_result_id);
+
+-- Reject UPDATE / DELETE / TRUNCATE at the database level
+CREATE OR REPLACE FUNCTION score_ledger_reject_mutation() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'score_ledger is append-only (% rejected)', TG_OP;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS score_ledger_no_mutation ON score_ledger;
+CREATE TRIGGER score_ledger_no_mutation
+    BEFORE UPDATE OR DELETE ON score_ledger
+    FOR EACH ROW EXECUTE FUNCTION score_ledger_reject_mutation();
+
+DROP TRIGGER IF EXISTS score_ledger_no_truncate ON score_ledger;
+CREATE TRIGGER score_ledger_no_truncate
+    BEFORE TRUNCATE ON score_ledger
+    FOR EACH STATEMENT EXECUTE FUNCTION score_ledger_reject_mutation();
+
+COMMENT ON TABLE score_ledger IS 'Append-only, hash-chained audit log of score changes';
+COMMENT ON COLUMN score_ledger.seq IS 'Gapless position in the chain, assigned under an advisory lock';
+COMMENT ON COLUMN score_ledger.prev_hash IS 'entry_hash of the previous row (64 zeros for the genesis row)';
+COMMENT ON COLUMN score_ledger.entry_hash IS 'SHA-256 over the canonical row payload including prev_hash';
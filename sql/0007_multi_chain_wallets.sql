@@ -0,0 +1,20 @@
+-- Multi-Chain Wallets
+-- Wallet logins may come from EVM, Solana and Aptos as well as Sui. Existing
+-- rows are Sui wallets; addresses are unique per chain rather than globally.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS chain VARCHAR(20) NOT NULL DEFAULT 'sui';
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_wallet_address_check;
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_wallet_address_key;
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_chain_check;
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_chain_wallet_address_key;
+
+-- Solana base58 keys are 32-44 chars, EVM 42, Sui/Aptos 66
+ALTER TABLE users ADD CONSTRAINT users_wallet_address_check
+    CHECK (LENGTH(wallet_address) BETWEEN 32 AND 100);
+ALTER TABLE users ADD CONSTRAINT users_chain_check
+    CHECK (chain IN ('sui', 'evm', 'solana', 'aptos'));
+ALTER TABLE users ADD CONSTRAINT users_chain_wallet_address_key
+    UNIQUE (chain, wallet_address);
+
+CREATE INDEX IF NOT EXISTS idx_users_chain ON users(chain);
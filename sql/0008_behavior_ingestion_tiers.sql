@@ -0,0 +1,31 @@
+-- Behavior Ingestion Tiers
+-- High-volume event types can be sampled or pre-aggregated at ingestion
+-- instead of stored raw. Sampled rows record how many events they stand for;
+-- aggregated events are folded into one row per session, type and window.
+
+ALTER TABLE behavior_inputs ADD COLUMN IF NOT EXISTS sample_weight DOUBLE PRECISION NOT NULL DEFAULT 1;
+
+-- Table: behavior_aggregates
+CREATE TABLE IF NOT EXISTS behavior_aggregates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Empty string when the events carried no session, so the bucket key stays unique
+    session_id VARCHAR(100) NOT NULL DEFAULT '',
+    input_type VARCHAR(50) NOT NULL,
+    bucket_start TIMESTAMPTZ NOT NULL,
+    window_secs INTEGER NOT NULL,
+    event_count BIGINT NOT NULL DEFAULT 0,
+    sums JSONB NOT NULL DEFAULT '{}'::jsonb,
+    first_seen TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    processed BOOLEAN NOT NULL DEFAULT false,
+
+    CONSTRAINT behavior_aggregates_bucket_key UNIQUE (session_id, input_type, bucket_start, window_secs),
+    CONSTRAINT behavior_aggregates_window_check CHECK (window_secs > 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_behavior_aggregates_session_id ON behavior_aggregates(session_id);
+CREATE INDEX IF NOT EXISTS idx_behavior_aggregates_bucket_start ON behavior_aggregates(bucket_start);
+CREATE INDEX IF NOT EXISTS idx_behavior_aggregates_processed ON behavior_aggregates(processed);
+
+COMMENT ON TABLE behavior_aggregates IS 'Per-window counts and sums for event types ingested with the aggregated tier';
+COMMENT ON COLUMN behavior_inputs.sample_weight IS 'Number of events this row represents (1 / sample rate for sampled event types)';
@@ -0,0 +1,23 @@
+-- GitHub Identities
+-- Users may sign in with GitHub or link a GitHub account to a wallet login.
+-- GitHub-only users have no wallet, so wallet columns become nullable. Access
+-- tokens are stored AES-256-GCM encrypted for later use by github_service.
+
+ALTER TABLE users ALTER COLUMN wallet_address DROP NOT NULL;
+ALTER TABLE users ALTER COLUMN public_key DROP NOT NULL;
+
+CREATE TABLE IF NOT EXISTS github_identities (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    github_id BIGINT NOT NULL UNIQUE,
+    login VARCHAR(100) NOT NULL,
+    name VARCHAR(255),
+    email VARCHAR(255),
+    avatar_url TEXT,
+    access_token_encrypted TEXT NOT NULL,
+    scopes TEXT NOT NULL DEFAULT '',
+    linked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_github_identities_user_id ON github_identities(user_id);
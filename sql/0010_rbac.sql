@@ -0,0 +1,39 @@
+-- Role-Based Access Control
+-- Roles carry scope strings (`resource:action`, `resource:*` or `*`) that are
+-- embedded in access tokens. Every user implicitly holds the `normal` role;
+-- further roles are granted through user_role_assignments.
+
+CREATE TABLE IF NOT EXISTS roles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(50) NOT NULL UNIQUE,
+    description TEXT,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    ctime TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    mtime TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS user_role_assignments (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role_id UUID NOT NULL REFERENCES roles(id) ON DELETE CASCADE,
+    -- Token subject of the admin who granted the role; NULL for seeded grants
+    granted_by VARCHAR(100),
+    granted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, role_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_role_assignments_role_id ON user_role_assignments(role_id);
+
+-- Built-in roles mirror the UserRole levels; scopes are cumulative
+INSERT INTO roles (name, description, scopes) VALUES
+    ('normal', 'Default role held by every user',
+        ARRAY['profile:read', 'profile:write']),
+    ('member', 'Verified community member',
+        ARRAY['profile:read', 'profile:write', 'scores:read']),
+    ('vip', 'Premium member',
+        ARRAY['profile:read', 'profile:write', 'scores:read', 'analytics:read']),
+    ('moderator', 'Community moderator',
+        ARRAY['profile:read', 'profile:write', 'scores:read', 'analytics:read',
+              'users:read', 'moderation:*']),
+    ('admin', 'Full access, including role administration',
+        ARRAY['*'])
+ON CONFLICT (name) DO NOTHING;
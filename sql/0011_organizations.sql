@@ -0,0 +1,71 @@
+-- Self-Serve Organizations
+-- Organizations created through the signup endpoints start on a time-boxed
+-- trial with default quotas; the scheduler flips lapsed trials to `expired`.
+-- Replaces hand-written INSERTs for customer onboarding.
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'organization_status') THEN
+        CREATE TYPE organization_status AS ENUM ('trial', 'active', 'expired');
+    END IF;
+
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'organization_role') THEN
+        CREATE TYPE organization_role AS ENUM ('owner', 'admin', 'member');
+    END IF;
+END $$;
+
+CREATE TABLE IF NOT EXISTS organizations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    slug VARCHAR(50) NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    status organization_status NOT NULL DEFAULT 'trial',
+    -- NULL once the organization is on a paid plan
+    trial_ends_at TIMESTAMPTZ,
+    max_members INTEGER NOT NULL CHECK (max_members > 0),
+    max_repositories INTEGER NOT NULL CHECK (max_repositories > 0),
+    monthly_analysis_quota INTEGER NOT NULL CHECK (monthly_analysis_quota >= 0),
+    ctime TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    mtime TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_organizations_owner_id ON organizations(owner_id);
+CREATE INDEX IF NOT EXISTS idx_organizations_trial_ends_at
+    ON organizations(trial_ends_at) WHERE status = 'trial';
+
+CREATE TABLE IF NOT EXISTS organization_members (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role organization_role NOT NULL DEFAULT 'member',
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_organization_members_user_id ON organization_members(user_id);
+
+CREATE TABLE IF NOT EXISTS organization_invitations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    role organization_role NOT NULL DEFAULT 'member' CHECK (role <> 'owner'),
+    -- SHA-256 of the invitation token; the token itself is only returned once
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    invited_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    accepted_at TIMESTAMPTZ,
+    accepted_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ctime TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_organization_invitations_organization_id
+    ON organization_invitations(organization_id) WHERE accepted_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS organization_repositories (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    full_name VARCHAR(255) NOT NULL CHECK (full_name ~ '^[^/]+/[^/]+$'),
+    -- Set when the repository is already monitored through the GitHub service
+    github_repository_id UUID REFERENCES github_repositories(id) ON DELETE SET NULL,
+    is_sample BOOLEAN NOT NULL DEFAULT FALSE,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, full_name)
+);
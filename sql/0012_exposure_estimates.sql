@@ -0,0 +1,35 @@
+-- On-Chain Exposure Estimates
+-- Indexed metrics for deployed Move packages, the mapping from repositories to
+-- their packages, and the exposure estimate attached to each finding when a
+-- patch is generated for it.
+
+-- Packages deployed from a monitored repository
+CREATE TABLE IF NOT EXISTS repository_contract_deployments (
+    repository_id UUID NOT NULL REFERENCES github_repositories(id) ON DELETE CASCADE,
+    package_id VARCHAR(66) NOT NULL CHECK (package_id ~ '^0x[0-9a-f]{1,64}$'),
+    network VARCHAR(20) NOT NULL DEFAULT 'mainnet',
+    ctime TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (repository_id, package_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_repository_contract_deployments_package_id
+    ON repository_contract_deployments(package_id);
+
+-- Written by the indexer; amounts are in MIST
+CREATE TABLE IF NOT EXISTS onchain_package_metrics (
+    package_id VARCHAR(66) PRIMARY KEY,
+    network VARCHAR(20) NOT NULL DEFAULT 'mainnet',
+    -- SUI held in shared objects whose type is defined by the package
+    shared_object_tvl_mist NUMERIC(30,0) NOT NULL DEFAULT 0 CHECK (shared_object_tvl_mist >= 0),
+    tx_count_24h BIGINT NOT NULL DEFAULT 0 CHECK (tx_count_24h >= 0),
+    tx_volume_24h_mist NUMERIC(30,0) NOT NULL DEFAULT 0 CHECK (tx_volume_24h_mist >= 0),
+    indexed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE security_vulnerabilities ADD COLUMN IF NOT EXISTS exposure_estimate JSONB;
+ALTER TABLE security_vulnerabilities ADD COLUMN IF NOT EXISTS estimated_exposure_sui NUMERIC(30,9);
+
+-- Lets findings be ranked by economic risk
+CREATE INDEX IF NOT EXISTS idx_security_vulnerabilities_estimated_exposure
+    ON security_vulnerabilities(estimated_exposure_sui DESC NULLS LAST)
+    WHERE fixed_at IS NULL AND is_false_positive = false;
@@ -0,0 +1,26 @@
+-- Auth Nonces
+-- Postgres backend for login nonces (AUTH.NONCE_BACKEND=postgres). Nonces are
+-- deleted when consumed and leave a marker used to detect replayed logins;
+-- both tables are purged periodically by the scheduler.
+
+CREATE TABLE IF NOT EXISTS auth_nonces (
+    chain VARCHAR(20) NOT NULL,
+    address VARCHAR(255) NOT NULL,
+    nonce CHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (chain, address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_auth_nonces_expires_at ON auth_nonces(expires_at);
+
+CREATE TABLE IF NOT EXISTS auth_consumed_nonces (
+    chain VARCHAR(20) NOT NULL,
+    address VARCHAR(255) NOT NULL,
+    nonce CHAR(64) NOT NULL,
+    consumed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain, address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_auth_consumed_nonces_consumed_at
+    ON auth_consumed_nonces(consumed_at);
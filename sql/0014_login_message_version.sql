@@ -0,0 +1,6 @@
+-- Login Message Version
+-- Records which challenge format a Postgres-stored nonce was issued with:
+-- 1 for the legacy bare nonce, 2 for the structured, domain-bound message.
+
+ALTER TABLE auth_nonces ADD COLUMN IF NOT EXISTS message_version SMALLINT NOT NULL DEFAULT 1
+    CHECK (message_version IN (1, 2));
@@ -0,0 +1,83 @@
+-- Linked Accounts
+-- One account may own several wallet addresses alongside its GitHub identity.
+-- user_wallets decides which account an address logs into; users.chain and
+-- users.wallet_address keep the address the account was created with.
+-- Duplicate accounts can be merged; the merged account is kept as a tombstone.
+
+CREATE TABLE IF NOT EXISTS user_wallets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    chain VARCHAR(20) NOT NULL CHECK (chain IN ('sui', 'evm', 'solana', 'aptos')),
+    address VARCHAR(100) NOT NULL,
+    public_key TEXT,
+    linked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT user_wallets_chain_address_key UNIQUE (chain, address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_wallets_user_id ON user_wallets(user_id);
+
+INSERT INTO user_wallets (user_id, chain, address, public_key, linked_at)
+SELECT id, chain, wallet_address, public_key, ctime
+FROM users
+WHERE wallet_address IS NOT NULL
+ON CONFLICT (chain, address) DO NOTHING;
+
+-- Accounts created through any path get their first wallet linked
+CREATE OR REPLACE FUNCTION users_link_initial_wallet() RETURNS trigger AS $$
+BEGIN
+    IF NEW.wallet_address IS NOT NULL THEN
+        INSERT INTO user_wallets (user_id, chain, address, public_key)
+        VALUES (NEW.id, NEW.chain, NEW.wallet_address, NEW.public_key)
+        ON CONFLICT (chain, address) DO NOTHING;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS users_link_initial_wallet ON users;
+CREATE TRIGGER users_link_initial_wallet
+    AFTER INSERT ON users
+    FOR EACH ROW EXECUTE FUNCTION users_link_initial_wallet();
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS merged_into UUID REFERENCES users(id);
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_status_check;
+ALTER TABLE users ADD CONSTRAINT users_status_check
+    CHECK (status IN ('active', 'inactive', 'suspended', 'deleted', 'merged'));
+
+CREATE TABLE IF NOT EXISTS account_merges (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    source_user_id UUID NOT NULL REFERENCES users(id),
+    target_user_id UUID NOT NULL REFERENCES users(id),
+    wallets_moved INTEGER NOT NULL DEFAULT 0,
+    github_identities_moved INTEGER NOT NULL DEFAULT 0,
+    reputation_records_moved INTEGER NOT NULL DEFAULT 0,
+    merged_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT account_merges_distinct_users CHECK (source_user_id <> target_user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_merges_target_user_id ON account_merges(target_user_id);
+
+-- Reputation across every identity of an account: its own reputation records
+-- plus the developer profiles of its linked GitHub identities
+CREATE OR REPLACE VIEW account_reputation AS
+SELECT
+    u.id AS user_id,
+    (SELECT COUNT(*) FROM user_wallets w WHERE w.user_id = u.id) AS wallet_count,
+    (SELECT COUNT(*) FROM github_identities g WHERE g.user_id = u.id) AS github_identity_count,
+    (SELECT AVG(r.reputation_score) FROM reputation_records r
+        WHERE r.user_id = u.id AND r.status = 'active') AS reputation_score,
+    d.coding_reputation_score,
+    d.security_awareness_score,
+    d.community_trust_score,
+    COALESCE(d.total_contributions, 0) AS total_contributions
+FROM users u
+LEFT JOIN LATERAL (
+    SELECT
+        AVG(dev.coding_reputation_score) AS coding_reputation_score,
+        AVG(dev.security_awareness_score) AS security_awareness_score,
+        AVG(dev.community_trust_score) AS community_trust_score,
+        SUM(dev.total_contributions)::BIGINT AS total_contributions
+    FROM github_identities g
+    JOIN developers dev ON dev.github_user_id = g.github_id
+    WHERE g.user_id = u.id
+) d ON true;
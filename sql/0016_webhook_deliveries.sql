@@ -0,0 +1,29 @@
+-- Webhook Deliveries
+-- Every GitHub webhook request as received: headers, raw payload, the outcome
+-- of signature validation and of processing. Replays are stored as new rows
+-- pointing back at the delivery they re-run.
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    github_delivery_id VARCHAR(64),
+    event_type VARCHAR(64) NOT NULL,
+    installation_id BIGINT,
+    headers JSONB NOT NULL DEFAULT '{}'::jsonb,
+    payload BYTEA NOT NULL,
+    signature_status VARCHAR(20) NOT NULL
+        CHECK (signature_status IN ('valid', 'invalid', 'missing')),
+    processing_status VARCHAR(20) NOT NULL DEFAULT 'received'
+        CHECK (processing_status IN ('received', 'processed', 'rejected', 'failed')),
+    processing_id UUID,
+    error_message TEXT,
+    replay_of UUID REFERENCES webhook_deliveries(id) ON DELETE SET NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    processed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_received_at
+    ON webhook_deliveries(received_at DESC);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_event_status
+    ON webhook_deliveries(event_type, processing_status);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_github_delivery_id
+    ON webhook_deliveries(github_delivery_id);
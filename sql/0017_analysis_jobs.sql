@@ -0,0 +1,38 @@
+-- Analysis Jobs
+-- Durable queue for repository analysis. Workers lease jobs with
+-- SELECT ... FOR UPDATE SKIP LOCKED; a lapsed lease makes the job visible
+-- again. Failures are retried with exponential backoff until max_attempts,
+-- after which the job is dead-lettered.
+
+CREATE TABLE IF NOT EXISTS analysis_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    repository_id BIGINT NOT NULL,
+    installation_id BIGINT,
+    commit_sha VARCHAR(255) NOT NULL,
+    files_to_analyze TEXT[] NOT NULL DEFAULT '{}',
+    analysis_type VARCHAR(30) NOT NULL
+        CHECK (analysis_type IN ('initial_scan', 'smart_contract', 'security_focus', 'full_analysis')),
+    priority SMALLINT NOT NULL DEFAULT 2 CHECK (priority BETWEEN 1 AND 4),
+    status VARCHAR(20) NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'processing', 'completed', 'failed', 'dead_lettered')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 5,
+    run_after TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    lease_id UUID,
+    leased_by VARCHAR(255),
+    leased_until TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+-- Lease scan: highest priority first, then oldest
+CREATE INDEX IF NOT EXISTS idx_analysis_jobs_ready
+    ON analysis_jobs(priority DESC, run_after)
+    WHERE status = 'queued';
+CREATE INDEX IF NOT EXISTS idx_analysis_jobs_leased_until
+    ON analysis_jobs(leased_until)
+    WHERE status = 'processing';
+CREATE INDEX IF NOT EXISTS idx_analysis_jobs_status_updated
+    ON analysis_jobs(status, updated_at);
@@ -0,0 +1,5 @@
+-- Analysis Job Pull Requests
+-- Pull request analyses cover only the changed files and report back to the
+-- pull request as a review.
+
+ALTER TABLE analysis_jobs ADD COLUMN IF NOT EXISTS pull_request_number BIGINT;
@@ -0,0 +1,29 @@
+-- GitHub Content Cache
+-- Directory listings from the contents API, keyed by repository, ref and
+-- path with the ETag they were served with, and file blobs keyed by their git
+-- SHA. Listings at a commit SHA and blobs never change; listings at a branch
+-- are revalidated with If-None-Match.
+
+CREATE TABLE IF NOT EXISTS github_content_listings (
+    owner VARCHAR(255) NOT NULL,
+    repo VARCHAR(255) NOT NULL,
+    ref_name VARCHAR(255) NOT NULL,
+    path TEXT NOT NULL,
+    etag VARCHAR(255),
+    body JSONB NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (owner, repo, ref_name, path)
+);
+
+CREATE TABLE IF NOT EXISTS github_blobs (
+    sha VARCHAR(64) PRIMARY KEY,
+    content TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_github_content_listings_last_used_at
+    ON github_content_listings(last_used_at);
+CREATE INDEX IF NOT EXISTS idx_github_blobs_last_used_at ON github_blobs(last_used_at);
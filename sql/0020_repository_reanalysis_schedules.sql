@@ -0,0 +1,25 @@
+-- Repository Re-analysis Schedules
+-- When each tracked repository is next re-analysed, on its own cron schedule
+-- (NULL means the service default), and the HEAD commit it was last queued
+-- for so unchanged repositories are skipped.
+
+CREATE TABLE IF NOT EXISTS repository_reanalysis_schedules (
+    repository_id UUID PRIMARY KEY REFERENCES github_repositories(id) ON DELETE CASCADE,
+    schedule VARCHAR(100),
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_head_sha VARCHAR(64),
+    last_enqueued_at TIMESTAMPTZ,
+    next_run_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_repository_reanalysis_schedules_next_run_at
+    ON repository_reanalysis_schedules(next_run_at) WHERE enabled;
+
+-- Scheduled re-analyses are queued as their own analysis type.
+ALTER TABLE analysis_jobs DROP CONSTRAINT IF EXISTS analysis_jobs_analysis_type_check;
+ALTER TABLE analysis_jobs ADD CONSTRAINT analysis_jobs_analysis_type_check
+    CHECK (analysis_type IN (
+        'initial_scan', 'smart_contract', 'security_focus', 'full_analysis', 'scheduled'
+    ));
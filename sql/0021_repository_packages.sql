@@ -0,0 +1,21 @@
+-- Repository Packages
+-- Smart contract packages detected in a repository (one per Move.toml,
+-- foundry.toml or Anchor.toml), and whether each is analysed. Files in an
+-- excluded package are skipped by analyses and webhook triggers.
+
+CREATE TABLE IF NOT EXISTS repository_packages (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    repository_id UUID NOT NULL REFERENCES github_repositories(id) ON DELETE CASCADE,
+    -- Directory holding the manifest; empty for the repository root
+    path TEXT NOT NULL,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('move', 'foundry', 'anchor')),
+    manifest_path TEXT NOT NULL,
+    included BOOLEAN NOT NULL DEFAULT TRUE,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (repository_id, path)
+);
+
+CREATE INDEX IF NOT EXISTS idx_repository_packages_repository_id
+    ON repository_packages(repository_id);
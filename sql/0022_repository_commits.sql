@@ -0,0 +1,37 @@
+-- Repository Commits
+-- Commit history of tracked repositories, for contribution-based reputation,
+-- and how far each repository's history has been synced.
+
+CREATE TABLE IF NOT EXISTS repository_commits (
+    repository_id UUID NOT NULL REFERENCES github_repositories(id) ON DELETE CASCADE,
+    sha VARCHAR(64) NOT NULL,
+    author_login VARCHAR(255),
+    author_name VARCHAR(255) NOT NULL,
+    author_email VARCHAR(255) NOT NULL,
+    authored_at TIMESTAMPTZ NOT NULL,
+    committed_at TIMESTAMPTZ NOT NULL,
+    message TEXT NOT NULL,
+    files_touched TEXT[] NOT NULL DEFAULT '{}',
+    additions INTEGER NOT NULL DEFAULT 0,
+    deletions INTEGER NOT NULL DEFAULT 0,
+    is_merge BOOLEAN NOT NULL DEFAULT FALSE,
+    ingested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (repository_id, sha)
+);
+
+CREATE INDEX IF NOT EXISTS idx_repository_commits_committed_at
+    ON repository_commits(repository_id, committed_at DESC);
+CREATE INDEX IF NOT EXISTS idx_repository_commits_author_login
+    ON repository_commits(author_login) WHERE author_login IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS repository_commit_syncs (
+    repository_id UUID PRIMARY KEY REFERENCES github_repositories(id) ON DELETE CASCADE,
+    last_seen_sha VARCHAR(64),
+    last_synced_at TIMESTAMPTZ,
+    next_sync_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_repository_commit_syncs_next_sync_at
+    ON repository_commit_syncs(next_sync_at);
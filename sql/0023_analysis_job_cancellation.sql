@@ -0,0 +1,6 @@
+-- Analysis Job Cancellation
+-- Cancelled jobs are kept, like completed ones, until purged.
+
+ALTER TABLE analysis_jobs DROP CONSTRAINT IF EXISTS analysis_jobs_status_check;
+ALTER TABLE analysis_jobs ADD CONSTRAINT analysis_jobs_status_check
+    CHECK (status IN ('queued', 'processing', 'completed', 'failed', 'dead_lettered', 'cancelled'));
@@ -0,0 +1,20 @@
+-- Webhook Secrets
+-- Secrets webhook deliveries are verified against, per repository webhook
+-- or for the GitHub App's webhook (scope_id 0). While a secret is being
+-- rotated both the old and the new one are accepted: the new one from
+-- before GitHub is told about it, the old one until expires_at.
+
+CREATE TABLE IF NOT EXISTS webhook_secrets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    scope VARCHAR(20) NOT NULL CHECK (scope IN ('repository', 'app')),
+    scope_id BIGINT NOT NULL,
+    secret_encrypted TEXT NOT NULL,
+    -- NULL until GitHub signs with the secret
+    activated_at TIMESTAMPTZ,
+    -- NULL for the secret GitHub currently signs with
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_secrets_scope
+    ON webhook_secrets(scope, scope_id);
@@ -0,0 +1,14 @@
+-- Vulnerability CVSS and CWE
+-- CVSS v3.1 base vector and score, and CWE identifier, of each finding. The
+-- score is derived from the vector and stored so findings can be filtered
+-- and sorted by it.
+
+ALTER TABLE security_vulnerabilities
+    ADD COLUMN IF NOT EXISTS cwe_id VARCHAR(20), -- CWE-NNN format
+    ADD COLUMN IF NOT EXISTS cvss_vector VARCHAR(64), -- CVSS:3.1/AV:N/... format
+    ADD COLUMN IF NOT EXISTS cvss_score DECIMAL(3,1) CHECK (cvss_score BETWEEN 0.0 AND 10.0);
+
+CREATE INDEX IF NOT EXISTS idx_security_vulnerabilities_cwe
+    ON security_vulnerabilities(repository_id, cwe_id);
+CREATE INDEX IF NOT EXISTS idx_security_vulnerabilities_cvss
+    ON security_vulnerabilities(repository_id, cvss_score DESC);
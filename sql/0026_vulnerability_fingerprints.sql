@@ -0,0 +1,19 @@
+-- Vulnerability Fingerprints
+-- A finding's fingerprint (rule, normalized file path and code hash) stays
+-- the same across analyses, so a re-analysis updates the existing record
+-- instead of inserting a duplicate. Findings no longer reported for a file
+-- that was analysed again are closed with resolved_at, and reopened if they
+-- come back. Rows stored before fingerprints existed keep a NULL fingerprint.
+
+ALTER TABLE security_vulnerabilities
+    ADD COLUMN IF NOT EXISTS fingerprint VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS first_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS resolved_at TIMESTAMPTZ;
+
+UPDATE security_vulnerabilities SET first_seen_at = ctime, last_seen_at = ctime
+    WHERE fingerprint IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_security_vulnerabilities_fingerprint
+    ON security_vulnerabilities(repository_id, fingerprint)
+    WHERE fingerprint IS NOT NULL;
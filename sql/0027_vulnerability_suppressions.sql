@@ -0,0 +1,24 @@
+-- Vulnerability Suppressions
+-- A triaged finding is suppressed by its fingerprint, so the decision holds
+-- for every later analysis of the repository that reports it again. A
+-- suppression marks the finding false positive or accepted risk; accepted
+-- risks usually expire so they come back up for review. Revoked and expired
+-- suppressions are kept for the audit trail.
+
+CREATE TABLE IF NOT EXISTS vulnerability_suppressions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    repository_id UUID NOT NULL REFERENCES github_repositories(id) ON DELETE CASCADE,
+    fingerprint VARCHAR(64) NOT NULL,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('false_positive', 'accepted_risk')),
+    reason TEXT NOT NULL,
+    -- NULL for suppressions that never expire
+    expires_at TIMESTAMPTZ,
+    created_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ
+);
+
+-- One unrevoked suppression per finding
+CREATE UNIQUE INDEX IF NOT EXISTS idx_vulnerability_suppressions_active
+    ON vulnerability_suppressions(repository_id, fingerprint)
+    WHERE revoked_at IS NULL;
@@ -0,0 +1,27 @@
+-- LLM Response Cache
+-- LLM responses keyed by the SHA-256 of the code they were asked about, the
+-- version of the prompt template and the model that answered, so analysing
+-- an unchanged file again costs no tokens. Findings record the prompt
+-- version and model that reported them, so results can be reproduced.
+
+CREATE TABLE IF NOT EXISTS llm_response_cache (
+    content_hash VARCHAR(64) NOT NULL,
+    prompt_version VARCHAR(64) NOT NULL,
+    model VARCHAR(255) NOT NULL,
+    provider VARCHAR(50) NOT NULL,
+    -- The model as the provider reported it, e.g. a dated snapshot of `model`
+    response_model VARCHAR(255) NOT NULL,
+    content TEXT NOT NULL,
+    prompt_tokens INTEGER NOT NULL,
+    completion_tokens INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (content_hash, prompt_version, model)
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_response_cache_last_used_at
+    ON llm_response_cache(last_used_at);
+
+ALTER TABLE security_vulnerabilities
+    ADD COLUMN IF NOT EXISTS prompt_version VARCHAR(64), -- NULL for static analysis findings
+    ADD COLUMN IF NOT EXISTS model VARCHAR(255);
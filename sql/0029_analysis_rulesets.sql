@@ -0,0 +1,14 @@
+-- Analysis Rulesets
+-- Per-repository analysis rules: the built-in vulnerability patterns a
+-- repository's analyses skip and the custom regex or AST patterns they add.
+
+CREATE TABLE IF NOT EXISTS analysis_rulesets (
+    repository_id UUID PRIMARY KEY REFERENCES github_repositories(id) ON DELETE CASCADE,
+    -- Ids of built-in patterns not to check
+    disabled_patterns TEXT[] NOT NULL DEFAULT '{}',
+    -- Array of pattern definitions, see the ruleset API
+    custom_patterns JSONB NOT NULL DEFAULT '[]',
+    updated_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,8 @@
+-- Move Bytecode Analysis
+-- Findings in Move packages record whether the package compiled when its
+-- bytecode was analysed, so source-level findings can be told apart from
+-- ones in code that does not build.
+
+ALTER TABLE security_vulnerabilities
+    ADD COLUMN IF NOT EXISTS compilation_status VARCHAR(20) -- NULL when the package was not compiled
+        CHECK (compilation_status IN ('compiled', 'failed', 'timed_out'));
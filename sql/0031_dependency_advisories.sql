@@ -0,0 +1,35 @@
+-- Dependency Advisories
+-- A local copy of published advisories against packages contract code
+-- depends on, synced from an OSV-format feed, so analyses can check the
+-- dependencies in Move.toml and Cargo.toml without calling out. Findings
+-- record whether they are in the repository's code or in a dependency.
+
+CREATE TABLE IF NOT EXISTS dependency_advisories (
+    -- Advisory id in the feed, e.g. RUSTSEC-2024-0001 or GHSA-xxxx-xxxx-xxxx
+    id VARCHAR(100) NOT NULL,
+    ecosystem VARCHAR(20) NOT NULL CHECK (ecosystem IN ('move', 'crates.io')),
+    package VARCHAR(255) NOT NULL,
+    summary TEXT NOT NULL,
+    severity VARCHAR(20) NOT NULL CHECK (severity IN ('critical', 'high', 'medium', 'low')),
+    cve_id VARCHAR(50),
+    cwe_id VARCHAR(20),
+    cvss_vector VARCHAR(200),
+    -- Array of {introduced, fixed, last_affected} version ranges
+    affected_ranges JSONB NOT NULL DEFAULT '[]',
+    -- Versions or git revisions affected outside any range
+    affected_versions TEXT[] NOT NULL DEFAULT '{}',
+    url TEXT,
+    modified_at TIMESTAMPTZ NOT NULL,
+    synced_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (id, ecosystem, package)
+);
+
+CREATE INDEX IF NOT EXISTS idx_dependency_advisories_package
+    ON dependency_advisories(ecosystem, LOWER(package));
+
+ALTER TABLE security_vulnerabilities
+    ADD COLUMN IF NOT EXISTS kind VARCHAR(20) NOT NULL DEFAULT 'code'
+        CHECK (kind IN ('code', 'dependency'));
+
+CREATE INDEX IF NOT EXISTS idx_security_vulnerabilities_kind
+    ON security_vulnerabilities(kind);
@@ -0,0 +1,6 @@
+-- Secret Exposure
+-- Findings for private keys, mnemonics and API tokens committed to a
+-- repository are stored under their own vulnerability type, so they can be
+-- listed and alerted on apart from contract weaknesses.
+
+ALTER TYPE vulnerability_type_enum ADD VALUE IF NOT EXISTS 'secret_exposure';
@@ -0,0 +1,52 @@
+-- AI Usage and Budgets
+-- LLM tokens and estimated cost of each analysis, monthly budgets capping
+-- what a repository or organization spends, and approvals letting single
+-- analyses run over a budget whose policy asks for them.
+
+CREATE TABLE IF NOT EXISTS ai_usage (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    analysis_result_id UUID REFERENCES code_analysis_results(id) ON DELETE SET NULL,
+    repository_id UUID NOT NULL REFERENCES github_repositories(id) ON DELETE CASCADE,
+    provider VARCHAR(50) NOT NULL,
+    model VARCHAR(100) NOT NULL,
+    requests INTEGER NOT NULL DEFAULT 0 CHECK (requests >= 0),
+    -- Requests answered from the response cache, at no cost
+    cache_hits INTEGER NOT NULL DEFAULT 0 CHECK (cache_hits >= 0),
+    prompt_tokens BIGINT NOT NULL DEFAULT 0 CHECK (prompt_tokens >= 0),
+    completion_tokens BIGINT NOT NULL DEFAULT 0 CHECK (completion_tokens >= 0),
+    -- Estimated from the provider's configured token pricing
+    cost_usd NUMERIC(12,6) NOT NULL DEFAULT 0 CHECK (cost_usd >= 0),
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_usage_repository_recorded
+    ON ai_usage(repository_id, recorded_at);
+CREATE INDEX IF NOT EXISTS idx_ai_usage_recorded_at ON ai_usage(recorded_at);
+
+CREATE TABLE IF NOT EXISTS ai_budgets (
+    scope VARCHAR(20) NOT NULL CHECK (scope IN ('repository', 'organization')),
+    -- github_repositories.id or organizations.id
+    scope_id UUID NOT NULL,
+    monthly_limit_usd NUMERIC(12,2) NOT NULL CHECK (monthly_limit_usd >= 0),
+    policy VARCHAR(20) NOT NULL CHECK (policy IN ('block', 'static_only', 'require_approval')),
+    updated_by VARCHAR(255) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (scope, scope_id)
+);
+
+CREATE TABLE IF NOT EXISTS ai_budget_approvals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    repository_id UUID NOT NULL REFERENCES github_repositories(id) ON DELETE CASCADE,
+    commit_sha VARCHAR(64) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'approved', 'denied', 'used')),
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_by VARCHAR(255),
+    decided_at TIMESTAMPTZ
+);
+
+-- One open request per repository; repeated refusals point at it
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ai_budget_approvals_pending
+    ON ai_budget_approvals(repository_id) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_ai_budget_approvals_approved
+    ON ai_budget_approvals(repository_id, decided_at) WHERE status = 'approved';
@@ -0,0 +1,22 @@
+-- Finding Evidence
+-- What an LLM-assisted finding was reported on: the code, the model's
+-- reasoning, the pattern it matched, and the model, prompt version and
+-- confidence, kept for every analysis that reported the finding.
+
+CREATE TABLE IF NOT EXISTS finding_evidence (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    vulnerability_id UUID NOT NULL REFERENCES security_vulnerabilities(id) ON DELETE CASCADE,
+    analysis_result_id UUID REFERENCES code_analysis_results(id) ON DELETE SET NULL,
+    code_snippet TEXT,
+    -- The model's summary of why the code is vulnerable
+    reasoning TEXT,
+    -- Known pattern or weakness the model matched, e.g. a CWE or Move pattern
+    referenced_pattern VARCHAR(255),
+    model VARCHAR(100),
+    prompt_version VARCHAR(100),
+    confidence_score DECIMAL(5,2) NOT NULL CHECK (confidence_score >= 0.0 AND confidence_score <= 100.0),
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_finding_evidence_vulnerability
+    ON finding_evidence(vulnerability_id, recorded_at DESC);
@@ -0,0 +1,42 @@
+-- Vulnerability Disclosure
+-- Coordinated disclosure of findings: draft, privately disclosed to the
+-- maintainers, embargoed until a date, then public. Only public findings are
+-- shown to callers without the vulnerabilities:disclosure scope.
+
+-- Findings stored before disclosure was tracked were already shown to everyone
+ALTER TABLE security_vulnerabilities
+    ADD COLUMN IF NOT EXISTS disclosure_state VARCHAR(30) NOT NULL DEFAULT 'public'
+        CHECK (disclosure_state IN ('draft', 'privately_disclosed', 'embargoed', 'public')),
+    ADD COLUMN IF NOT EXISTS embargo_until TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS disclosed_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS published_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS disclosure_updated_by VARCHAR(255),
+    -- When the maintainers were warned that the embargo is about to lapse
+    ADD COLUMN IF NOT EXISTS embargo_notified_at TIMESTAMPTZ;
+
+-- New findings start as drafts
+ALTER TABLE security_vulnerabilities ALTER COLUMN disclosure_state SET DEFAULT 'draft';
+
+CREATE INDEX IF NOT EXISTS idx_security_vulnerabilities_disclosure_state
+    ON security_vulnerabilities(disclosure_state);
+CREATE INDEX IF NOT EXISTS idx_security_vulnerabilities_embargo_until
+    ON security_vulnerabilities(embargo_until) WHERE disclosure_state = 'embargoed';
+
+CREATE TABLE IF NOT EXISTS vulnerability_disclosure_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    vulnerability_id UUID NOT NULL REFERENCES security_vulnerabilities(id) ON DELETE CASCADE,
+    from_state VARCHAR(30) NOT NULL,
+    to_state VARCHAR(30) NOT NULL,
+    embargo_until TIMESTAMPTZ,
+    -- Subject of the token that made the change; 'system' for publication
+    -- when an embargo lapses
+    actor VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_vulnerability_disclosure_events_vulnerability
+    ON vulnerability_disclosure_events(vulnerability_id, created_at);
+
+-- Moderators triage findings through disclosure; admins hold every scope
+UPDATE roles SET scopes = array_append(scopes, 'vulnerabilities:disclosure')
+WHERE name = 'moderator' AND NOT ('vulnerabilities:disclosure' = ANY(scopes));
@@ -0,0 +1,14 @@
+-- Patch Verification
+-- Results of applying a patch proposal to a checkout of its repository and
+-- building and testing it in a sandbox. Patches that do not build cannot be
+-- submitted.
+
+ALTER TABLE patch_proposals
+    ADD COLUMN IF NOT EXISTS build_succeeded BOOLEAN,
+    ADD COLUMN IF NOT EXISTS tests_passed BOOLEAN,
+    ADD COLUMN IF NOT EXISTS security_scan_passed BOOLEAN,
+    -- Why verification failed, with the end of the failing command's output
+    ADD COLUMN IF NOT EXISTS validation_message TEXT,
+    -- Commit of the checkout the patch was applied to
+    ADD COLUMN IF NOT EXISTS validated_commit_sha VARCHAR(40),
+    ADD COLUMN IF NOT EXISTS validated_at TIMESTAMPTZ;
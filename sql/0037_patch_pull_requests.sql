@@ -0,0 +1,14 @@
+-- Patch Pull Requests
+-- Pull requests opened on the target repository from patch proposals, kept
+-- in step with GitHub through pull_request webhook events.
+
+ALTER TABLE patch_proposals
+    ADD COLUMN IF NOT EXISTS pr_url TEXT,
+    ADD COLUMN IF NOT EXISTS pr_branch VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS pr_state VARCHAR(20)
+        CHECK (pr_state IN ('open', 'closed', 'merged')),
+    ADD COLUMN IF NOT EXISTS pr_state_updated_at TIMESTAMPTZ;
+
+-- Webhook events find the patch by its repository and pull request number
+CREATE INDEX IF NOT EXISTS idx_patch_proposals_repo_pr_number
+    ON patch_proposals(repository_id, github_pr_number) WHERE github_pr_number IS NOT NULL;
@@ -0,0 +1,42 @@
+-- Patch Reviews
+-- Review workflow for patch proposals: assigned reviewers approve or reject
+-- a patch, which is approved once it has the approvals it requires, then
+-- submitted as a pull request and merged.
+
+ALTER TYPE patch_status_enum ADD VALUE IF NOT EXISTS 'submitted';
+ALTER TYPE patch_status_enum ADD VALUE IF NOT EXISTS 'merged';
+
+ALTER TABLE patch_proposals
+    ADD COLUMN IF NOT EXISTS required_approvals INTEGER NOT NULL DEFAULT 1
+        CHECK (required_approvals > 0);
+
+-- Merged patches are applied as well. Compared as text: new enum values
+-- cannot be used in the transaction adding them.
+ALTER TABLE patch_proposals DROP CONSTRAINT IF EXISTS patch_proposals_applied_at_check;
+ALTER TABLE patch_proposals ADD CONSTRAINT patch_proposals_applied_at_check CHECK (
+    (status::TEXT IN ('applied', 'merged')) = (applied_at IS NOT NULL)
+);
+
+CREATE TABLE IF NOT EXISTS patch_reviewers (
+    patch_id UUID NOT NULL REFERENCES patch_proposals(id) ON DELETE CASCADE,
+    -- Token subject of the reviewer
+    reviewer VARCHAR(255) NOT NULL,
+    assigned_by VARCHAR(255) NOT NULL,
+    assigned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (patch_id, reviewer)
+);
+
+CREATE TABLE IF NOT EXISTS patch_reviews (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    patch_id UUID NOT NULL REFERENCES patch_proposals(id) ON DELETE CASCADE,
+    reviewer VARCHAR(255) NOT NULL,
+    decision VARCHAR(20) NOT NULL CHECK (decision IN ('approve', 'reject', 'comment')),
+    comment TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_patch_reviews_patch ON patch_reviews(patch_id, created_at);
+
+-- Moderators assign reviewers; admins hold every scope
+UPDATE roles SET scopes = array_append(scopes, 'patches:review_admin')
+WHERE name = 'moderator' AND NOT ('patches:review_admin' = ANY(scopes));
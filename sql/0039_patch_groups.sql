@@ -0,0 +1,33 @@
+-- Patch Groups
+-- Coordinated patches generated for a whole class of findings in one
+-- repository, tracked as one sub-patch per changed file.
+
+CREATE TABLE IF NOT EXISTS patch_groups (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    repository_id UUID NOT NULL REFERENCES github_repositories(id) ON DELETE CASCADE,
+    -- The filter the findings were selected with; NULL matches any
+    vulnerability_type VARCHAR(50),
+    severity VARCHAR(20),
+    cwe_id VARCHAR(20),
+    strategy VARCHAR(20) NOT NULL CHECK (strategy IN ('conservative', 'balanced', 'aggressive')),
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,
+    model_version VARCHAR(100) NOT NULL,
+    vulnerability_ids UUID[] NOT NULL DEFAULT '{}',
+    -- Findings and hunks left out of the set, and why
+    left_out TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_patch_groups_repository ON patch_groups(repository_id, created_at);
+
+CREATE TABLE IF NOT EXISTS patch_group_files (
+    group_id UUID NOT NULL REFERENCES patch_groups(id) ON DELETE CASCADE,
+    file_path TEXT NOT NULL,
+    diff_content TEXT NOT NULL,
+    -- Findings in this file the sub-patch fixes
+    vulnerability_ids UUID[] NOT NULL DEFAULT '{}',
+    additions INTEGER NOT NULL DEFAULT 0 CHECK (additions >= 0),
+    deletions INTEGER NOT NULL DEFAULT 0 CHECK (deletions >= 0),
+    PRIMARY KEY (group_id, file_path)
+);
@@ -0,0 +1,19 @@
+-- Patch Regressions
+-- Merged patches a later analysis found reverted, or whose finding it
+-- reported again at the same fingerprint.
+
+ALTER TABLE patch_proposals
+    ADD COLUMN IF NOT EXISTS regressed_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS regression_reason VARCHAR(20)
+        CHECK (regression_reason IN ('reverted', 'reappeared')),
+    ADD COLUMN IF NOT EXISTS regression_analysis_id UUID
+        REFERENCES code_analysis_results(id) ON DELETE SET NULL;
+
+ALTER TABLE patch_proposals DROP CONSTRAINT IF EXISTS patch_proposals_regression_check;
+ALTER TABLE patch_proposals ADD CONSTRAINT patch_proposals_regression_check CHECK (
+    (regressed_at IS NULL) = (regression_reason IS NULL)
+);
+
+-- Each analysis checks the repository's merged patches not regressed yet
+CREATE INDEX IF NOT EXISTS idx_patch_proposals_repo_unregressed
+    ON patch_proposals(repository_id) WHERE regressed_at IS NULL;
@@ -0,0 +1,24 @@
+-- Patch Candidates
+-- Alternative patches generated for a finding with different strategies,
+-- ranked by a validation pass for reviewers to pick from.
+
+CREATE TABLE IF NOT EXISTS patch_candidates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    vulnerability_id UUID NOT NULL REFERENCES security_vulnerabilities(id) ON DELETE CASCADE,
+    rank INTEGER NOT NULL CHECK (rank > 0),
+    strategy VARCHAR(20) NOT NULL CHECK (strategy IN ('conservative', 'balanced', 'aggressive')),
+    -- The generated patch and what validating it found, as JSON
+    patch JSONB NOT NULL,
+    validation JSONB NOT NULL,
+    score DOUBLE PRECISION NOT NULL CHECK (score >= 0),
+    -- Token subject of the reviewer who picked the candidate
+    selected_by VARCHAR(255),
+    selected_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((selected_by IS NULL) = (selected_at IS NULL)),
+    UNIQUE (vulnerability_id, rank)
+);
+
+-- At most one pick per finding
+CREATE UNIQUE INDEX IF NOT EXISTS idx_patch_candidates_selected
+    ON patch_candidates(vulnerability_id) WHERE selected_at IS NOT NULL;
@@ -0,0 +1,25 @@
+-- Sponsor Gas Pool
+-- SUI coins owned by the gas station's sponsor address and the sponsorship
+-- or rebalance holding each, so no coin pays for two transactions at once.
+
+CREATE TABLE IF NOT EXISTS sponsor_gas_coins (
+    object_id VARCHAR(66) PRIMARY KEY,
+    sponsor_address VARCHAR(66) NOT NULL,
+    version BIGINT NOT NULL CHECK (version >= 0),
+    digest VARCHAR(64) NOT NULL,
+    balance BIGINT NOT NULL CHECK (balance >= 0),
+    -- Set while a sponsorship or rebalance holds the coin; a reservation
+    -- past reserved_until is abandoned and the coin is free again
+    reservation_id UUID,
+    reserved_until TIMESTAMPTZ,
+    -- The coin was spent from and is not handed out until a refresh reads
+    -- its new version
+    stale BOOLEAN NOT NULL DEFAULT FALSE,
+    synced_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((reservation_id IS NULL) = (reserved_until IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_sponsor_gas_coins_sponsor
+    ON sponsor_gas_coins(sponsor_address, balance);
+CREATE INDEX IF NOT EXISTS idx_sponsor_gas_coins_reservation
+    ON sponsor_gas_coins(reservation_id) WHERE reservation_id IS NOT NULL;
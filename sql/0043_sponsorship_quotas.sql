@@ -0,0 +1,19 @@
+-- Sponsorship Quotas
+-- Gas budgets sponsored per user address and per app over a rolling day and
+-- week. Rows here override the configured defaults for one user or app.
+
+ALTER TABLE sponsored_transactions ADD COLUMN IF NOT EXISTS app_id VARCHAR(100);
+
+CREATE INDEX IF NOT EXISTS idx_sponsored_transactions_app_timestamp
+    ON sponsored_transactions(app_id, timestamp) WHERE app_id IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS sponsorship_quotas (
+    subject_type VARCHAR(10) NOT NULL CHECK (subject_type IN ('user', 'app')),
+    -- A user's Sui address or an app's id
+    subject VARCHAR(100) NOT NULL,
+    -- Gas in MIST; NULL is unlimited
+    daily_gas_budget BIGINT CHECK (daily_gas_budget >= 0),
+    weekly_gas_budget BIGINT CHECK (weekly_gas_budget >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (subject_type, subject)
+);
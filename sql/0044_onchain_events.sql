@@ -0,0 +1,31 @@
+-- On-chain Events
+-- Events emitted by the configured Sui packages, ingested in checkpoint order
+-- by the event indexer, with the cursor each package was indexed up to.
+
+CREATE TABLE IF NOT EXISTS onchain_events (
+    id BIGSERIAL PRIMARY KEY,
+    package_id VARCHAR(66) NOT NULL,
+    tx_digest VARCHAR(64) NOT NULL,
+    event_seq BIGINT NOT NULL CHECK (event_seq >= 0),
+    module VARCHAR(128) NOT NULL,
+    event_type TEXT NOT NULL,
+    sender VARCHAR(66) NOT NULL,
+    -- The event's fields decoded to JSON
+    parsed_json JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ,
+    indexed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_digest, event_seq)
+);
+
+CREATE INDEX IF NOT EXISTS idx_onchain_events_package ON onchain_events(package_id, id);
+CREATE INDEX IF NOT EXISTS idx_onchain_events_type ON onchain_events(event_type, id);
+CREATE INDEX IF NOT EXISTS idx_onchain_events_sender ON onchain_events(sender, id);
+
+-- The last event indexed per package; indexing resumes after it
+CREATE TABLE IF NOT EXISTS onchain_event_cursors (
+    package_id VARCHAR(66) PRIMARY KEY,
+    tx_digest VARCHAR(64) NOT NULL,
+    event_seq BIGINT NOT NULL CHECK (event_seq >= 0),
+    events_indexed BIGINT NOT NULL DEFAULT 0 CHECK (events_indexed >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
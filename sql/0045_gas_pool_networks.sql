@@ -0,0 +1,23 @@
+-- Gas Pool Networks
+-- Each Sui network a deployment serves keeps its own gas pool. Rows without
+-- a network are a cache of chain state and are rebuilt by the next refresh.
+
+DELETE FROM sponsor_gas_coins
+WHERE NOT EXISTS (
+    SELECT 1 FROM information_schema.columns
+    WHERE table_name = 'sponsor_gas_coins' AND column_name = 'network'
+);
+
+ALTER TABLE sponsor_gas_coins ADD COLUMN IF NOT EXISTS network VARCHAR(10) NOT NULL;
+
+ALTER TABLE sponsor_gas_coins DROP CONSTRAINT IF EXISTS sponsor_gas_coins_network_check;
+ALTER TABLE sponsor_gas_coins ADD CONSTRAINT sponsor_gas_coins_network_check
+    CHECK (network IN ('mainnet', 'testnet', 'devnet', 'localnet'));
+
+-- Object ids are only unique within a network
+ALTER TABLE sponsor_gas_coins DROP CONSTRAINT IF EXISTS sponsor_gas_coins_pkey;
+ALTER TABLE sponsor_gas_coins ADD CONSTRAINT sponsor_gas_coins_pkey PRIMARY KEY (network, object_id);
+
+DROP INDEX IF EXISTS idx_sponsor_gas_coins_sponsor;
+CREATE INDEX IF NOT EXISTS idx_sponsor_gas_coins_network_sponsor
+    ON sponsor_gas_coins(network, sponsor_address, balance);
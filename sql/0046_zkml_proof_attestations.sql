@@ -0,0 +1,23 @@
+-- ZKML Proof Attestations
+-- Verified proofs are attested on-chain by a Move call. The signed transaction
+-- is kept until it lands so a retry resubmits it instead of attesting twice.
+
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS attestation_salt BYTEA;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS attestation_digest VARCHAR(100);
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS attestation_tx BYTEA;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS attestation_prepared_at TIMESTAMPTZ;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS attestation_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS attestation_error TEXT;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS attestation_retry_at TIMESTAMPTZ;
+
+ALTER TABLE zkml_proofs DROP CONSTRAINT IF EXISTS zkml_proofs_attestation_attempts_check;
+ALTER TABLE zkml_proofs ADD CONSTRAINT zkml_proofs_attestation_attempts_check
+    CHECK (attestation_attempts >= 0);
+
+-- Verified proofs still waiting for their attestation
+CREATE INDEX IF NOT EXISTS idx_zkml_proofs_attestation_due
+    ON zkml_proofs(attestation_retry_at)
+    WHERE verified AND blockchain_tx_hash IS NULL;
+
+COMMENT ON COLUMN zkml_proofs.attestation_salt IS 'Salt of the on-chain score commitment';
+COMMENT ON COLUMN zkml_proofs.attestation_tx IS 'Signed attestation transaction (BCS) until it lands';
@@ -0,0 +1,26 @@
+-- Package Deployments
+-- Move packages a tracked repository has published on Sui, the modules
+-- found on-chain, and whether their bytecode was reproduced from the
+-- repository's source at a commit (source verification).
+
+CREATE TABLE IF NOT EXISTS package_deployments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    repository_id UUID NOT NULL REFERENCES github_repositories(id) ON DELETE CASCADE,
+    -- Directory of the package's Move.toml; empty for the repository root
+    package_path TEXT NOT NULL DEFAULT '',
+    network VARCHAR(20) NOT NULL,
+    package_id VARCHAR(66) NOT NULL,
+    modules TEXT[] NOT NULL DEFAULT '{}',
+    modules_fetched_at TIMESTAMPTZ,
+    verification_status VARCHAR(20) NOT NULL DEFAULT 'unverified'
+        CHECK (verification_status IN ('unverified', 'verified', 'mismatch', 'failed')),
+    verified_commit_sha VARCHAR(40),
+    verification_message TEXT,
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (network, package_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_package_deployments_repository_id
+    ON package_deployments(repository_id);
@@ -0,0 +1,29 @@
+-- ZKML Proof Jobs
+-- Proofs of scoring results are generated in the background by a prover
+-- backend. Each proof keeps its public signals, the backend that made it and
+-- a commitment to the compiled model it was proven against.
+
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS public_signals JSONB;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS prover_backend VARCHAR(50);
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS model_commitment VARCHAR(64);
+
+CREATE TABLE IF NOT EXISTS zkml_proof_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    scoring_result_id UUID NOT NULL UNIQUE REFERENCES scoring_results(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'running', 'succeeded', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    -- A running job whose lease ran out is picked up again
+    lease_until TIMESTAMPTZ,
+    proof_id UUID REFERENCES zkml_proofs(id) ON DELETE SET NULL,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Jobs waiting for a worker
+CREATE INDEX IF NOT EXISTS idx_zkml_proof_jobs_pending
+    ON zkml_proof_jobs(created_at)
+    WHERE status IN ('queued', 'running');
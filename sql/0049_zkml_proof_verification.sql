@@ -0,0 +1,13 @@
+-- ZKML Proof Verification
+-- Proofs are verified server-side by the configured prover backend. The last
+-- check is kept with the proof: which verifier ran, how long it took and why
+-- a rejected proof failed.
+
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS verifier_version VARCHAR(100);
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS verification_ms INTEGER;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS verification_error TEXT;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS verification_checked_at TIMESTAMPTZ;
+
+ALTER TABLE zkml_proofs DROP CONSTRAINT IF EXISTS zkml_proofs_verification_ms_check;
+ALTER TABLE zkml_proofs ADD CONSTRAINT zkml_proofs_verification_ms_check
+    CHECK (verification_ms IS NULL OR verification_ms >= 0);
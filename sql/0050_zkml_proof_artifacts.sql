@@ -0,0 +1,28 @@
+-- ZKML Proof Artifacts
+-- Proof data and verification keys can be tens of megabytes, so they are
+-- kept in object storage by content hash. The table keeps their URIs and
+-- SHA-256 hashes; the bytea columns only hold proofs not yet offloaded.
+
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS proof_uri TEXT;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS proof_sha256 VARCHAR(64);
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS verification_key_uri TEXT;
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS verification_key_sha256 VARCHAR(64);
+
+ALTER TABLE zkml_proofs ALTER COLUMN proof_data DROP NOT NULL;
+ALTER TABLE zkml_proofs ALTER COLUMN verification_key DROP NOT NULL;
+
+-- Each artifact is either inline or in object storage with its hash
+ALTER TABLE zkml_proofs DROP CONSTRAINT IF EXISTS zkml_proofs_proof_data_check;
+ALTER TABLE zkml_proofs ADD CONSTRAINT zkml_proofs_proof_data_check
+    CHECK (LENGTH(proof_data) > 0 OR (proof_uri IS NOT NULL AND proof_sha256 IS NOT NULL));
+ALTER TABLE zkml_proofs DROP CONSTRAINT IF EXISTS zkml_proofs_verification_key_check;
+ALTER TABLE zkml_proofs ADD CONSTRAINT zkml_proofs_verification_key_check
+    CHECK (
+        LENGTH(verification_key) > 0
+        OR (verification_key_uri IS NOT NULL AND verification_key_sha256 IS NOT NULL)
+    );
+
+-- Proofs still waiting to be offloaded
+CREATE INDEX IF NOT EXISTS idx_zkml_proofs_inline
+    ON zkml_proofs(timestamp)
+    WHERE proof_uri IS NULL;
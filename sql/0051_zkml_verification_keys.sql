@@ -0,0 +1,45 @@
+-- ZKML Verification Key Registry
+-- Verification keys are published once per (model_version, circuit_version)
+-- and referenced by proofs, instead of a copy being kept with every proof.
+-- Each key is used for new proofs during its activation window; proofs keep
+-- verifying against their key after it has been rotated out.
+
+CREATE TABLE IF NOT EXISTS verification_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    model_version VARCHAR(50) NOT NULL,
+    circuit_version VARCHAR(50) NOT NULL,
+    prover_backend VARCHAR(50) NOT NULL,
+    verification_key BYTEA NOT NULL,
+    key_sha256 VARCHAR(64) NOT NULL,
+    active_from TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    active_until TIMESTAMPTZ,
+    published_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT verification_keys_version_unique UNIQUE (model_version, circuit_version),
+    CONSTRAINT verification_keys_key_check CHECK (LENGTH(verification_key) > 0),
+    CONSTRAINT verification_keys_circuit_version_check CHECK (LENGTH(circuit_version) >= 1),
+    CONSTRAINT verification_keys_window_check CHECK (active_until IS NULL OR active_until > active_from)
+);
+
+CREATE INDEX IF NOT EXISTS idx_verification_keys_active
+    ON verification_keys(prover_backend, model_version, active_from DESC);
+
+ALTER TABLE zkml_proofs ADD COLUMN IF NOT EXISTS verification_key_id UUID REFERENCES verification_keys(id);
+
+CREATE INDEX IF NOT EXISTS idx_zkml_proofs_verification_key
+    ON zkml_proofs(verification_key_id)
+    WHERE verification_key_id IS NOT NULL;
+
+-- Proofs from before the registry keep their own copy of the key
+ALTER TABLE zkml_proofs DROP CONSTRAINT IF EXISTS zkml_proofs_verification_key_check;
+ALTER TABLE zkml_proofs ADD CONSTRAINT zkml_proofs_verification_key_check
+    CHECK (
+        verification_key_id IS NOT NULL
+        OR LENGTH(verification_key) > 0
+        OR (verification_key_uri IS NOT NULL AND verification_key_sha256 IS NOT NULL)
+    );
+
+COMMENT ON TABLE verification_keys IS 'Published verification keys, one per model and circuit version';
+COMMENT ON COLUMN verification_keys.active_until IS 'End of the window in which new proofs use the key; set when a later key is published';
+COMMENT ON COLUMN zkml_proofs.verification_key_id IS 'Registered key the proof verifies against';
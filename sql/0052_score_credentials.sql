@@ -0,0 +1,26 @@
+-- Score Credentials
+-- Revocation registry of issued credentials asserting a holder's score is at
+-- least a threshold. The credential itself is a signed VC-JWT held by the
+-- user; only its validity period and revocation state are kept here.
+
+CREATE TABLE IF NOT EXISTS score_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    subject_address VARCHAR(255) NOT NULL,
+    threshold SMALLINT NOT NULL,
+    scoring_result_id UUID REFERENCES scoring_results(id) ON DELETE SET NULL,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ,
+    revocation_reason VARCHAR(100),
+
+    CONSTRAINT score_credentials_threshold_range CHECK (threshold BETWEEN 1 AND 100),
+    CONSTRAINT score_credentials_validity_check CHECK (expires_at > issued_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_score_credentials_subject ON score_credentials(subject_address, issued_at DESC);
+CREATE INDEX IF NOT EXISTS idx_score_credentials_active
+    ON score_credentials(subject_address)
+    WHERE revoked_at IS NULL;
+
+COMMENT ON TABLE score_credentials IS 'Issued score threshold credentials and their revocation state';
+COMMENT ON COLUMN score_credentials.scoring_result_id IS 'Score the threshold was checked against at issuance';
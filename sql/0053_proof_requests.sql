@@ -0,0 +1,42 @@
+-- Proof Requests
+-- Partners ask a user to prove a statement about their score. The user
+-- fulfils the request with a verified proof of their latest score, bound to
+-- the request's nonce, and the partner receives an HMAC-signed callback.
+
+CREATE TABLE IF NOT EXISTS proof_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    created_by VARCHAR(255) NOT NULL,
+    statement JSONB NOT NULL,
+    nonce VARCHAR(64) NOT NULL,
+    callback_url TEXT NOT NULL,
+    callback_secret VARCHAR(64) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'open',
+    expires_at TIMESTAMPTZ NOT NULL,
+    fulfilled_by VARCHAR(255),
+    fulfilled_at TIMESTAMPTZ,
+    proof_id UUID REFERENCES zkml_proofs(id) ON DELETE SET NULL,
+    -- Callback body, kept so retries send exactly what was signed
+    response JSONB,
+    callback_status VARCHAR(20),
+    callback_attempts INTEGER NOT NULL DEFAULT 0,
+    callback_retry_at TIMESTAMPTZ,
+    callback_error TEXT,
+    delivered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT proof_requests_nonce_unique UNIQUE (nonce),
+    CONSTRAINT proof_requests_status_check CHECK (status IN ('open', 'fulfilled')),
+    CONSTRAINT proof_requests_callback_status_check
+        CHECK (callback_status IS NULL OR callback_status IN ('pending', 'delivered', 'failed')),
+    CONSTRAINT proof_requests_fulfilled_check
+        CHECK (status = 'open' OR (fulfilled_by IS NOT NULL AND response IS NOT NULL)),
+    CONSTRAINT proof_requests_expiry_check CHECK (expires_at > created_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_proof_requests_created_by ON proof_requests(created_by, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_proof_requests_callbacks_due
+    ON proof_requests(callback_retry_at)
+    WHERE callback_status = 'pending';
+
+COMMENT ON TABLE proof_requests IS 'Partner requests for users to prove a statement, answered by signed callback';
+COMMENT ON COLUMN proof_requests.callback_secret IS 'HMAC key of the callback signature; shown to the partner once';
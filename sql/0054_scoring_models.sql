@@ -0,0 +1,40 @@
+-- Scoring Model Registry
+-- Every model that may compute scores is registered with its name, version,
+-- kind, artifact and input schema. Exactly one model is active and scores new
+-- behavior inputs; scoring_results.model_version names the model that ran.
+
+CREATE TABLE IF NOT EXISTS scoring_models (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(30) NOT NULL,
+    version VARCHAR(19) NOT NULL,
+    -- The identifier results record, e.g. 'hardcoded-v1.0'
+    model_version VARCHAR(50) GENERATED ALWAYS AS (name || '-' || version) STORED,
+    kind VARCHAR(20) NOT NULL,
+    artifact_uri TEXT,
+    input_schema JSONB NOT NULL DEFAULT '{}'::jsonb,
+    active BOOLEAN NOT NULL DEFAULT FALSE,
+    registered_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    activated_at TIMESTAMPTZ,
+
+    CONSTRAINT scoring_models_version_unique UNIQUE (name, version),
+    CONSTRAINT scoring_models_model_version_unique UNIQUE (model_version),
+    CONSTRAINT scoring_models_name_check CHECK (name ~ '^[a-z0-9_]+$'),
+    CONSTRAINT scoring_models_version_check CHECK (LENGTH(version) >= 1),
+    CONSTRAINT scoring_models_kind_check CHECK (kind IN ('rule_based', 'onnx')),
+    CONSTRAINT scoring_models_artifact_check CHECK (kind <> 'onnx' OR artifact_uri IS NOT NULL)
+);
+
+-- At most one model scores at a time
+CREATE UNIQUE INDEX IF NOT EXISTS idx_scoring_models_single_active
+    ON scoring_models ((TRUE))
+    WHERE active;
+
+-- The built-in rule-based model every existing result was scored with
+INSERT INTO scoring_models (name, version, kind, active, registered_by, activated_at)
+VALUES ('hardcoded', 'v1.0', 'rule_based', TRUE, 'migration', NOW())
+ON CONFLICT DO NOTHING;
+
+COMMENT ON TABLE scoring_models IS 'Registered scoring models; the active one scores new behavior inputs';
+COMMENT ON COLUMN scoring_models.artifact_uri IS 'Location of the model file, e.g. file:///models/score.onnx';
+COMMENT ON COLUMN scoring_models.input_schema IS 'Behavior input fields fed to the model, as {"features": [...]}';
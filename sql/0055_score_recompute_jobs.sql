@@ -0,0 +1,45 @@
+-- Score Recompute Jobs
+-- Recomputes scores of the behavior inputs a filter selects with one model,
+-- as a resumable background job. Recomputed scores are new scoring_results
+-- rows linked to their job; the rows they supersede are kept for comparison.
+
+CREATE TABLE IF NOT EXISTS score_recompute_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    requested_by VARCHAR(255),
+    filter JSONB NOT NULL DEFAULT '{}'::jsonb,
+    -- Model every input is rescored with, fixed when the job is queued
+    target_model_version VARCHAR(50) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'queued',
+    total_count BIGINT NOT NULL DEFAULT 0,
+    processed_count BIGINT NOT NULL DEFAULT 0,
+    failed_count BIGINT NOT NULL DEFAULT 0,
+    -- Last behavior input handled; a resumed job continues after it
+    cursor UUID,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    lease_until TIMESTAMPTZ,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ,
+
+    CONSTRAINT score_recompute_jobs_status_check
+        CHECK (status IN ('queued', 'running', 'completed', 'failed')),
+    CONSTRAINT score_recompute_jobs_progress_check
+        CHECK (processed_count >= 0 AND failed_count >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_score_recompute_jobs_pending
+    ON score_recompute_jobs(created_at)
+    WHERE status IN ('queued', 'running');
+
+ALTER TABLE scoring_results ADD COLUMN IF NOT EXISTS recompute_job_id UUID
+    REFERENCES score_recompute_jobs(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_scoring_results_recompute_job
+    ON scoring_results(recompute_job_id)
+    WHERE recompute_job_id IS NOT NULL;
+
+COMMENT ON TABLE score_recompute_jobs IS 'Background recomputation of scores for a filter of behavior inputs';
+COMMENT ON COLUMN score_recompute_jobs.filter IS 'Date range, scoring model and user cohort of the results to recompute';
+COMMENT ON COLUMN scoring_results.recompute_job_id IS 'Recompute job that wrote this result; NULL for scores computed on ingestion';
@@ -0,0 +1,38 @@
+-- Behavior Event Schemas
+-- Batch ingestion validates each event against the JSON Schema registered for
+-- its type and rejects types with none. Client event ids are claimed in
+-- behavior_event_receipts so a retried batch does not store an event twice.
+
+-- Table: behavior_event_schemas
+CREATE TABLE IF NOT EXISTS behavior_event_schemas (
+    input_type VARCHAR(50) PRIMARY KEY,
+    schema JSONB NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    updated_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT behavior_event_schemas_input_type_check CHECK (LENGTH(input_type) >= 1)
+);
+
+-- Table: behavior_event_receipts
+CREATE TABLE IF NOT EXISTS behavior_event_receipts (
+    client_event_id VARCHAR(100) PRIMARY KEY,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_behavior_event_receipts_received_at ON behavior_event_receipts(received_at);
+
+ALTER TABLE behavior_inputs ADD COLUMN IF NOT EXISTS client_event_id VARCHAR(100);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_behavior_inputs_client_event_id
+    ON behavior_inputs(client_event_id) WHERE client_event_id IS NOT NULL;
+
+-- Events sent without a type keep being accepted as any JSON object
+INSERT INTO behavior_event_schemas (input_type, schema)
+VALUES ('general', '{"type": "object"}'::jsonb)
+ON CONFLICT (input_type) DO NOTHING;
+
+COMMENT ON TABLE behavior_event_schemas IS 'JSON Schema each batch-ingested event of a type must satisfy';
+COMMENT ON TABLE behavior_event_receipts IS 'Client event ids already ingested, including sampled-out and aggregated events';
+COMMENT ON COLUMN behavior_inputs.client_event_id IS 'Id the client gave the event in a batch, unique across events';
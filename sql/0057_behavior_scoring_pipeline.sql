@@ -0,0 +1,11 @@
+-- Behavior Scoring Pipeline
+-- New behavior inputs are scored in the background once their subject has
+-- been quiet for a debounce window. Inputs are leased while being scored so
+-- servers do not score the same subject twice, and marked processed after.
+
+ALTER TABLE behavior_inputs ADD COLUMN IF NOT EXISTS scoring_lease_until TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_behavior_inputs_unprocessed
+    ON behavior_inputs(timestamp) WHERE processed = false;
+
+COMMENT ON COLUMN behavior_inputs.scoring_lease_until IS 'Set while the scoring pipeline scores this input; expired leases are claimed again';
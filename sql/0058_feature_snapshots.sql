@@ -0,0 +1,25 @@
+-- Feature Snapshots
+-- Scoring models consume feature vectors extracted from a behavior input and
+-- its context (session, recency, on-chain activity of the user's wallet)
+-- instead of the raw input. Each vector is stored per input and feature set
+-- version, and every score records the snapshot it was computed from.
+
+-- Table: feature_snapshots
+CREATE TABLE IF NOT EXISTS feature_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    behavior_input_id UUID NOT NULL REFERENCES behavior_inputs(id) ON DELETE CASCADE,
+    -- Extractors and their versions, e.g. `counts@1+recency@1`
+    feature_version VARCHAR(200) NOT NULL,
+    features JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT feature_snapshots_input_version_key UNIQUE (behavior_input_id, feature_version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_feature_snapshots_feature_version ON feature_snapshots(feature_version);
+
+ALTER TABLE scoring_results
+    ADD COLUMN IF NOT EXISTS feature_snapshot_id UUID REFERENCES feature_snapshots(id) ON DELETE SET NULL;
+
+COMMENT ON TABLE feature_snapshots IS 'Versioned feature vectors scoring models consume, one per behavior input and feature set';
+COMMENT ON COLUMN scoring_results.feature_snapshot_id IS 'Feature vector the score was computed from';
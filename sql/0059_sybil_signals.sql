@@ -0,0 +1,45 @@
+-- Sybil Signals
+-- A sybil score, kept apart from the persona score, estimates how likely a
+-- user is one of many accounts run by the same operator: wallets linked by
+-- on-chain interactions, devices shared with other users and behavior timed
+-- in lockstep with another user's. Partners choose at which scores they
+-- review or block users.
+
+-- Clients report the device an event came from as `device_fingerprint`,
+-- top-level or inside a sampled event.
+ALTER TABLE behavior_inputs
+    ADD COLUMN IF NOT EXISTS device_fingerprint VARCHAR(128) GENERATED ALWAYS AS (
+        LEFT(COALESCE(input_data->>'device_fingerprint', input_data->'event'->>'device_fingerprint'), 128)
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_behavior_inputs_device_fingerprint
+    ON behavior_inputs(device_fingerprint, user_id)
+    WHERE device_fingerprint IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_behavior_inputs_user_timestamp
+    ON behavior_inputs(user_id, timestamp DESC)
+    WHERE user_id IS NOT NULL;
+
+-- Table: sybil_assessments
+-- The latest assessment per user
+CREATE TABLE IF NOT EXISTS sybil_assessments (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    score DOUBLE PRECISION NOT NULL CHECK (score >= 0 AND score <= 100),
+    signals JSONB NOT NULL,
+    assessed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Table: sybil_thresholds
+-- Per partner, keyed by the subject of the partner's token
+CREATE TABLE IF NOT EXISTS sybil_thresholds (
+    partner VARCHAR(255) PRIMARY KEY,
+    review_at DOUBLE PRECISION NOT NULL,
+    block_at DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT sybil_thresholds_range_check
+        CHECK (review_at >= 0 AND review_at <= block_at AND block_at <= 100)
+);
+
+COMMENT ON COLUMN behavior_inputs.device_fingerprint IS 'Device the client reported the event from';
+COMMENT ON TABLE sybil_assessments IS 'Latest sybil score per user with the signals behind it';
+COMMENT ON TABLE sybil_thresholds IS 'Sybil scores at which a partner reviews or blocks users';
@@ -0,0 +1,36 @@
+-- Analytics Rollups
+-- Daily and weekly aggregates of vulnerabilities found by severity, analyses
+-- run and patches merged per repository, and of scores issued platform-wide,
+-- refreshed by a scheduled job so analytics reads skip the source tables.
+
+-- Table: analytics_rollups
+CREATE TABLE IF NOT EXISTS analytics_rollups (
+    id BIGSERIAL PRIMARY KEY,
+    granularity VARCHAR(10) NOT NULL,
+    period_start DATE NOT NULL,
+    metric VARCHAR(50) NOT NULL,
+    -- e.g. the severity; empty when the metric is not split
+    dimension VARCHAR(50) NOT NULL DEFAULT '',
+    -- NULL for platform-wide metrics
+    repository_id UUID REFERENCES github_repositories(id) ON DELETE CASCADE,
+    value BIGINT NOT NULL CHECK (value >= 0),
+
+    CONSTRAINT analytics_rollups_granularity_check CHECK (granularity IN ('day', 'week'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_rollups_period
+    ON analytics_rollups(granularity, period_start, repository_id);
+
+-- Table: analytics_rollup_periods
+-- Periods that have been rolled up, so a period without activity is told
+-- apart from one not rolled up yet
+CREATE TABLE IF NOT EXISTS analytics_rollup_periods (
+    granularity VARCHAR(10) NOT NULL,
+    period_start DATE NOT NULL,
+    refreshed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (granularity, period_start)
+);
+
+COMMENT ON TABLE analytics_rollups IS 'Daily and weekly analytics aggregates, per repository or platform-wide';
+COMMENT ON TABLE analytics_rollup_periods IS 'Periods rolled up into analytics_rollups and when they were last refreshed';
@@ -0,0 +1,37 @@
+-- Analytics Exports
+-- CSV and NDJSON exports of analytics datasets prepared in the background
+-- for ranges too large to stream in one request, downloadable by whoever
+-- asked for them until they expire.
+
+-- Table: analytics_exports
+CREATE TABLE IF NOT EXISTS analytics_exports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Subject of the token that asked for the export
+    requested_by VARCHAR(255) NOT NULL,
+    dataset VARCHAR(30) NOT NULL,
+    format VARCHAR(10) NOT NULL,
+    range_from TIMESTAMPTZ,
+    range_to TIMESTAMPTZ,
+    include_undisclosed BOOLEAN NOT NULL DEFAULT false,
+    status VARCHAR(20) NOT NULL DEFAULT 'queued',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    lease_until TIMESTAMPTZ,
+    row_count BIGINT,
+    byte_size BIGINT,
+    content BYTEA,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+
+    CONSTRAINT analytics_exports_dataset_check CHECK (dataset IN ('vulnerabilities', 'scores', 'analyses')),
+    CONSTRAINT analytics_exports_format_check CHECK (format IN ('csv', 'ndjson')),
+    CONSTRAINT analytics_exports_status_check CHECK (status IN ('queued', 'running', 'ready', 'failed'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_exports_pending
+    ON analytics_exports(created_at) WHERE status IN ('queued', 'running');
+CREATE INDEX IF NOT EXISTS idx_analytics_exports_expires_at
+    ON analytics_exports(expires_at) WHERE expires_at IS NOT NULL;
+
+COMMENT ON TABLE analytics_exports IS 'Analytics dataset exports prepared in the background and their files';
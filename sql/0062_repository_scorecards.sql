@@ -0,0 +1,20 @@
+-- Repository Scorecards
+-- Daily security posture scores of each public repository, graded from open
+-- findings, dependency findings, patch latency and analysis freshness. The
+-- history lets scorecards and their badges show which way a score is going.
+
+-- Table: repository_scorecards
+-- The last scorecard computed on each day
+CREATE TABLE IF NOT EXISTS repository_scorecards (
+    repository_id UUID NOT NULL REFERENCES github_repositories(id) ON DELETE CASCADE,
+    recorded_on DATE NOT NULL,
+    score DOUBLE PRECISION NOT NULL CHECK (score >= 0 AND score <= 100),
+    grade VARCHAR(1) NOT NULL CHECK (grade IN ('A', 'B', 'C', 'D', 'F')),
+    components JSONB NOT NULL,
+    inputs JSONB NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (repository_id, recorded_on)
+);
+
+COMMENT ON TABLE repository_scorecards IS 'Daily security posture scores and grades of public repositories';
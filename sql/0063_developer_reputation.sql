@@ -0,0 +1,28 @@
+-- Developer Reputation
+-- Reputation scores recomputed periodically from merged patches, the
+-- vulnerabilities they resolved, analyses of the developer's repositories and
+-- on-chain proof attestations, ranked per time window and repository language.
+
+-- Table: developer_reputation
+-- The last recomputed leaderboard of each window and language
+CREATE TABLE IF NOT EXISTS developer_reputation (
+    developer_id UUID NOT NULL REFERENCES developers(id) ON DELETE CASCADE,
+    time_window VARCHAR(10) NOT NULL CHECK (time_window IN ('all_time', '90d', '30d')),
+    -- Lowercased primary language of the repositories counted; '' for all
+    language VARCHAR(50) NOT NULL DEFAULT '',
+    score DOUBLE PRECISION NOT NULL CHECK (score >= 0 AND score <= 100),
+    rank INTEGER NOT NULL CHECK (rank > 0),
+    merged_patches BIGINT NOT NULL DEFAULT 0,
+    resolved_vulnerabilities BIGINT NOT NULL DEFAULT 0,
+    analysis_contributions BIGINT NOT NULL DEFAULT 0,
+    attestations BIGINT NOT NULL DEFAULT 0,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (developer_id, time_window, language)
+);
+
+CREATE INDEX IF NOT EXISTS idx_developer_reputation_board
+    ON developer_reputation(time_window, language, rank);
+
+COMMENT ON TABLE developer_reputation IS 'Developer reputation scores and ranks per time window and language';
+COMMENT ON COLUMN developer_reputation.attestations IS 'On-chain proof attestations of the developer''s linked wallets';
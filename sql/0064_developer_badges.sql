@@ -0,0 +1,29 @@
+-- Developer Badges
+-- Achievements awarded to developers when their contributions cross a
+-- milestone. A developer may claim an award and have it minted as a Sui
+-- object; the signed mint is kept until it lands so a retry resubmits it
+-- instead of minting twice.
+
+-- Table: developer_badges
+-- One row per badge awarded to a developer
+CREATE TABLE IF NOT EXISTS developer_badges (
+    developer_id UUID NOT NULL REFERENCES developers(id) ON DELETE CASCADE,
+    badge VARCHAR(50) NOT NULL,
+    awarded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    claimed_at TIMESTAMPTZ,
+    mint_status VARCHAR(20) CHECK (mint_status IN ('pending', 'minted', 'failed')),
+    mint_recipient VARCHAR(66),
+    mint_digest VARCHAR(64),
+    mint_tx BYTEA,
+    mint_error TEXT,
+    minted_at TIMESTAMPTZ,
+
+    PRIMARY KEY (developer_id, badge),
+    CONSTRAINT developer_badges_mint_claimed_check CHECK (mint_status IS NULL OR claimed_at IS NOT NULL),
+    CONSTRAINT developer_badges_minted_check CHECK ((mint_status = 'minted') = (minted_at IS NOT NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_developer_badges_badge ON developer_badges(badge, awarded_at);
+
+COMMENT ON TABLE developer_badges IS 'Badges awarded to developers and their on-chain claims';
+COMMENT ON COLUMN developer_badges.mint_tx IS 'Signed mint transaction (BCS) until it lands';
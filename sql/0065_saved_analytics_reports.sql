@@ -0,0 +1,65 @@
+-- Saved Analytics Reports
+-- Parameterized analytics queries saved by a user, optionally run on a cron
+-- schedule with the results delivered by email or signed webhook. Every run
+-- is kept with its output so past reports can be fetched again.
+
+-- Table: saved_analytics_queries
+CREATE TABLE IF NOT EXISTS saved_analytics_queries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Token subject of the owner
+    owner VARCHAR(255) NOT NULL,
+    name VARCHAR(200) NOT NULL CHECK (LENGTH(TRIM(name)) > 0),
+    dataset VARCHAR(20) NOT NULL CHECK (dataset IN ('vulnerabilities', 'scores', 'repositories')),
+    filters JSONB NOT NULL DEFAULT '{}',
+    -- Fixed from the owner's scopes when saved
+    include_undisclosed BOOLEAN NOT NULL DEFAULT false,
+    -- Cron expression; unscheduled queries only run on demand
+    schedule VARCHAR(100),
+    delivery_channel VARCHAR(10) NOT NULL DEFAULT 'none'
+        CHECK (delivery_channel IN ('none', 'email', 'webhook')),
+    delivery_target TEXT,
+    -- Signs webhook deliveries; shown to the owner once
+    webhook_secret VARCHAR(64),
+    enabled BOOLEAN NOT NULL DEFAULT true,
+    next_run_at TIMESTAMPTZ,
+    leased_until TIMESTAMPTZ,
+    last_run_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT saved_analytics_queries_delivery_check CHECK (
+        (delivery_channel = 'none') = (delivery_target IS NULL)
+    ),
+    CONSTRAINT saved_analytics_queries_schedule_check CHECK (
+        (schedule IS NULL) = (next_run_at IS NULL)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_saved_analytics_queries_owner ON saved_analytics_queries(owner, created_at);
+-- Scheduled queries coming due
+CREATE INDEX IF NOT EXISTS idx_saved_analytics_queries_due
+    ON saved_analytics_queries(next_run_at)
+    WHERE enabled AND next_run_at IS NOT NULL;
+
+-- Table: report_runs
+-- One row per run of a saved query, with the rows it returned
+CREATE TABLE IF NOT EXISTS report_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    query_id UUID NOT NULL REFERENCES saved_analytics_queries(id) ON DELETE CASCADE,
+    trigger VARCHAR(10) NOT NULL CHECK (trigger IN ('schedule', 'manual')),
+    status VARCHAR(10) NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'succeeded', 'failed')),
+    range_from TIMESTAMPTZ,
+    range_to TIMESTAMPTZ NOT NULL,
+    row_count INTEGER,
+    output JSONB,
+    error TEXT,
+    delivery_status VARCHAR(10) CHECK (delivery_status IN ('skipped', 'delivered', 'failed')),
+    delivery_error TEXT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_report_runs_query ON report_runs(query_id, started_at DESC);
+
+COMMENT ON TABLE saved_analytics_queries IS 'Saved analytics queries and their report schedules';
+COMMENT ON TABLE report_runs IS 'Runs of saved analytics queries and their outputs';
@@ -0,0 +1,36 @@
+-- Analytics Alerts
+-- Anomalies a scheduled detector found in key metrics against their recent
+-- baselines. One alert stays open per metric and subject while the anomaly
+-- lasts, and notifications are held back for a cooldown after the last one.
+
+-- Table: analytics_alerts
+CREATE TABLE IF NOT EXISTS analytics_alerts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    metric VARCHAR(40) NOT NULL
+        CHECK (metric IN ('critical_findings_spike', 'analysis_failure_rate', 'scoring_throughput_drop')),
+    -- Repository the alert is about; empty for platform-wide metrics
+    subject VARCHAR(64) NOT NULL DEFAULT '',
+    status VARCHAR(10) NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'resolved')),
+    -- Latest detection: the value seen, its baseline mean and the bound it crossed
+    observed DOUBLE PRECISION NOT NULL,
+    expected DOUBLE PRECISION NOT NULL,
+    threshold DOUBLE PRECISION NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    occurrences INTEGER NOT NULL DEFAULT 1 CHECK (occurrences > 0),
+    first_detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    notified_at TIMESTAMPTZ,
+    notify_error TEXT,
+    resolved_at TIMESTAMPTZ,
+
+    CONSTRAINT analytics_alerts_resolved_check CHECK ((status = 'resolved') = (resolved_at IS NOT NULL))
+);
+
+-- At most one open alert per metric and subject
+CREATE UNIQUE INDEX IF NOT EXISTS idx_analytics_alerts_open
+    ON analytics_alerts(metric, subject)
+    WHERE status = 'open';
+CREATE INDEX IF NOT EXISTS idx_analytics_alerts_notified
+    ON analytics_alerts(metric, subject, notified_at DESC);
+
+COMMENT ON TABLE analytics_alerts IS 'Anomalies detected in analytics metrics and their notifications';
@@ -0,0 +1,32 @@
+-- Outbox Events
+-- Domain events written in the same transaction as the change they describe,
+-- so an event is recorded exactly when its change commits. A relay publishes
+-- them to in-process subscribers and, if configured, NATS or Kafka, retrying
+-- failures with a growing delay.
+
+-- Table: outbox_events
+CREATE TABLE IF NOT EXISTS outbox_events (
+    id BIGSERIAL PRIMARY KEY,
+    -- Stable id subscribers and brokers can deduplicate redeliveries by
+    event_id UUID NOT NULL UNIQUE DEFAULT gen_random_uuid(),
+    event_type VARCHAR(80) NOT NULL,
+    -- Id of the record the event is about, e.g. the vulnerability's
+    aggregate_id VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Not published before; pushed back after each failed attempt
+    available_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    last_error TEXT,
+    published_at TIMESTAMPTZ
+);
+
+-- Events still to publish, in the order they were recorded
+CREATE INDEX IF NOT EXISTS idx_outbox_events_pending
+    ON outbox_events(available_at, id)
+    WHERE published_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_outbox_events_published
+    ON outbox_events(published_at)
+    WHERE published_at IS NOT NULL;
+
+COMMENT ON TABLE outbox_events IS 'Domain events awaiting or past publication by the outbox relay';
@@ -0,0 +1,45 @@
+-- Email Notifications
+-- Transactional emails are queued in email_jobs with their recipient resolved
+-- and the user's preferences checked, then delivered by a scheduled job that
+-- retries failures with a growing delay. A dedup key per user keeps a
+-- redelivered event or a rerun digest from sending the same email twice.
+
+-- Table: email_notification_preferences
+-- Users without a row get the defaults below
+CREATE TABLE IF NOT EXISTS email_notification_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    analysis_complete BOOLEAN NOT NULL DEFAULT FALSE,
+    critical_vulnerability BOOLEAN NOT NULL DEFAULT TRUE,
+    patch_approved BOOLEAN NOT NULL DEFAULT TRUE,
+    weekly_digest BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Table: email_jobs
+CREATE TABLE IF NOT EXISTS email_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    recipient VARCHAR(255) NOT NULL,
+    template VARCHAR(40) NOT NULL
+        CHECK (template IN ('analysis_complete', 'critical_vulnerability', 'patch_approved', 'weekly_digest')),
+    -- The template and its data, as `EmailTemplate` serializes them
+    payload JSONB NOT NULL,
+    -- e.g. `critical_vulnerability:{vulnerability_id}` or `weekly_digest:2026-W42`
+    dedup_key VARCHAR(120) NOT NULL,
+    -- Not sent before; pushed back while leased and after each failed attempt
+    available_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    last_error TEXT,
+    sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, dedup_key)
+);
+
+-- Emails still to send, oldest first
+CREATE INDEX IF NOT EXISTS idx_email_jobs_pending
+    ON email_jobs(available_at, id)
+    WHERE sent_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_email_jobs_created_at ON email_jobs(created_at);
+
+COMMENT ON TABLE email_notification_preferences IS 'Which transactional emails each user receives';
+COMMENT ON TABLE email_jobs IS 'Transactional emails awaiting or past delivery';
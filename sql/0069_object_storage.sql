@@ -0,0 +1,18 @@
+-- Object Storage
+-- Files kept in the configured object store rather than in Postgres:
+-- prepared analytics exports, and a JSON archive of each code analysis with
+-- its findings that outlives later triage of the live tables. Rows record
+-- the object's URI; the API hands out short-lived links to it.
+
+-- Set instead of `content` when the export was streamed to object storage
+ALTER TABLE analytics_exports
+    ADD COLUMN IF NOT EXISTS content_uri TEXT;
+
+ALTER TABLE code_analysis_results
+    ADD COLUMN IF NOT EXISTS report_uri TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_code_analysis_results_unarchived
+    ON code_analysis_results(ctime) WHERE report_uri IS NULL;
+
+COMMENT ON COLUMN analytics_exports.content_uri IS 'Object storage URI of the prepared file, when not kept in content';
+COMMENT ON COLUMN code_analysis_results.report_uri IS 'Object storage URI of the archived JSON report of the analysis';
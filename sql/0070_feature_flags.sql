@@ -0,0 +1,27 @@
+-- Feature Flags
+-- Definitions of flags gating new behavior. A flag is on for the users and
+-- organizations it lists and for a stable share of everyone else, in the
+-- environments it names. Servers cache the definitions in Redis for up to
+-- 30 seconds.
+
+-- Table: feature_flags
+CREATE TABLE IF NOT EXISTS feature_flags (
+    -- e.g. `llm-v2-prompts`
+    key VARCHAR(64) PRIMARY KEY CHECK (key ~ '^[a-z0-9._-]+$'),
+    description TEXT,
+    -- Off for everyone when false, whatever the targeting below
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Empty for every environment
+    environments TEXT[] NOT NULL DEFAULT '{}',
+    -- User ids, or token subjects of callers without a user
+    user_ids TEXT[] NOT NULL DEFAULT '{}',
+    organization_ids UUID[] NOT NULL DEFAULT '{}',
+    rollout_percentage SMALLINT NOT NULL DEFAULT 0
+        CHECK (rollout_percentage BETWEEN 0 AND 100),
+    updated_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE feature_flags IS 'Feature flags and who they are rolled out to';
+COMMENT ON COLUMN feature_flags.rollout_percentage IS 'Share of users not listed the flag is on for, by a hash of the flag key and user id';
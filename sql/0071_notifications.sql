@@ -0,0 +1,47 @@
+-- In-App Notifications
+-- Notifications shown in the web app's notification center, created by
+-- event subscribers for the users an event concerns after checking their
+-- preferences. A dedup key per user keeps a redelivered event from
+-- notifying twice. Read notifications are deleted after 90 days.
+
+-- Table: in_app_notification_preferences
+-- Users without a row get the defaults below
+CREATE TABLE IF NOT EXISTS in_app_notification_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    analysis_complete BOOLEAN NOT NULL DEFAULT TRUE,
+    critical_vulnerability BOOLEAN NOT NULL DEFAULT TRUE,
+    patch_review_requested BOOLEAN NOT NULL DEFAULT TRUE,
+    score_changed BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Table: notifications
+CREATE TABLE IF NOT EXISTS notifications (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    template VARCHAR(40) NOT NULL
+        CHECK (template IN ('analysis_complete', 'critical_vulnerability', 'patch_review_requested', 'score_changed')),
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    -- Path in the web app the notification opens
+    link TEXT NOT NULL,
+    -- The template and its data, as `NotificationTemplate` serializes them
+    data JSONB NOT NULL,
+    -- e.g. `critical_vulnerability:{vulnerability_id}`
+    dedup_key VARCHAR(120) NOT NULL,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, dedup_key)
+);
+
+-- A user's notifications, newest first, and their unread ones
+CREATE INDEX IF NOT EXISTS idx_notifications_user_id ON notifications(user_id, id DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread
+    ON notifications(user_id)
+    WHERE read_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_notifications_read_created_at
+    ON notifications(created_at)
+    WHERE read_at IS NOT NULL;
+
+COMMENT ON TABLE in_app_notification_preferences IS 'Which in-app notifications each user receives';
+COMMENT ON TABLE notifications IS 'In-app notifications shown in the notification center';
@@ -0,0 +1,38 @@
+-- Chat Alert Channels
+-- Slack and Discord incoming webhooks an organization sends alerts to,
+-- for one of its repositories or all of them. Event subscribers post
+-- critical findings, failed analyses and merged patches to the channels
+-- subscribed to them, at most `max_per_hour` messages per channel.
+
+-- Table: alert_channels
+CREATE TABLE IF NOT EXISTS alert_channels (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- NULL covers every repository the organization monitors
+    repository_id UUID REFERENCES github_repositories(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('slack', 'discord')),
+    -- Holds the provider's credential; never returned by the API
+    webhook_url TEXT NOT NULL,
+    events TEXT[] NOT NULL
+        CHECK (cardinality(events) > 0
+            AND events <@ ARRAY['critical_finding', 'analysis_failed', 'patch_merged']),
+    -- Message templates by event, e.g. {"critical_finding": "{repository}: {vulnerability_type}"}
+    templates JSONB NOT NULL DEFAULT '{}',
+    max_per_hour INTEGER NOT NULL DEFAULT 30 CHECK (max_per_hour BETWEEN 1 AND 1000),
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by VARCHAR(255) NOT NULL,
+    last_delivered_at TIMESTAMPTZ,
+    -- Why the last delivery failed; cleared by the next one that succeeds
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_channels_organization_id
+    ON alert_channels(organization_id);
+CREATE INDEX IF NOT EXISTS idx_alert_channels_repository_id
+    ON alert_channels(repository_id)
+    WHERE repository_id IS NOT NULL;
+
+COMMENT ON TABLE alert_channels IS 'Slack and Discord webhooks organizations receive alerts on';
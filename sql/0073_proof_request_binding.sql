@@ -0,0 +1,15 @@
+-- Proof Request Binding
+-- A proof request names the one user who may fulfil it, and is fulfilled
+-- only with a proof generated for its nonce. Proof jobs therefore carry the
+-- challenge their proof commits to, and a scoring result has one job per
+-- challenge rather than one in all.
+
+ALTER TABLE proof_requests ADD COLUMN IF NOT EXISTS subject VARCHAR(255);
+
+ALTER TABLE zkml_proof_jobs ADD COLUMN IF NOT EXISTS challenge VARCHAR(64);
+ALTER TABLE zkml_proof_jobs DROP CONSTRAINT IF EXISTS zkml_proof_jobs_scoring_result_id_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_zkml_proof_jobs_scoring_result_challenge
+    ON zkml_proof_jobs(scoring_result_id, COALESCE(challenge, ''));
+
+COMMENT ON COLUMN proof_requests.subject IS 'Wallet address of the only user who may fulfil the request';
+COMMENT ON COLUMN zkml_proof_jobs.challenge IS 'Hex challenge the proof commits to as its second public input';
@@ -0,0 +1,7 @@
+-- Consumed Nonces Per Nonce
+-- Replay markers name the nonce that was consumed rather than only the
+-- address, so each consumed nonce is remembered on its own and a later login
+-- with a fresh nonce is not mistaken for a replay.
+
+ALTER TABLE auth_consumed_nonces DROP CONSTRAINT IF EXISTS auth_consumed_nonces_pkey;
+ALTER TABLE auth_consumed_nonces ADD PRIMARY KEY (chain, address, nonce);
@@ -0,0 +1,8 @@
+-- Outbox Delivered To
+-- The sinks an outbox event has reached: `bus:<subscriber>` for in-process
+-- subscribers and the broker's name for NATS or Kafka. A retry after a
+-- partial failure only goes to the sinks still missing.
+
+ALTER TABLE outbox_events ADD COLUMN IF NOT EXISTS delivered_to TEXT[] NOT NULL DEFAULT '{}';
+
+COMMENT ON COLUMN outbox_events.delivered_to IS 'Sinks the event was delivered to by earlier attempts';
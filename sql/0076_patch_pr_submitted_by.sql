@@ -0,0 +1,6 @@
+-- Patch PR Submitted By
+-- Who opened a patch's pull request with the server's GitHub credentials.
+
+ALTER TABLE patch_proposals ADD COLUMN IF NOT EXISTS pr_submitted_by VARCHAR(255);
+
+COMMENT ON COLUMN patch_proposals.pr_submitted_by IS 'Address of the token subject that submitted the pull request';